@@ -0,0 +1,264 @@
+//! Command-line arguments.
+
+use clap::{Parser, Subcommand};
+use uuid::Uuid;
+
+/// Audited support operations for Zeltra.
+///
+/// Commands that change data print their plan and stop unless `--execute`
+/// is passed. Every command is recorded in the operator audit log under the
+/// name in `ZELTRA_OPERATOR`.
+#[derive(Debug, Parser)]
+#[command(name = "admin")]
+pub struct Cli {
+    /// Carry out a command that changes data instead of only printing its plan
+    #[arg(long, global = true)]
+    pub execute: bool,
+
+    /// Command to run
+    #[command(subcommand)]
+    pub command: Command,
+}
+
+/// Top-level command groups.
+#[derive(Debug, Clone, PartialEq, Eq, Subcommand)]
+pub enum Command {
+    /// Organization lookups
+    #[command(subcommand)]
+    Org(OrgCommand),
+    /// User account fixes
+    #[command(subcommand)]
+    User(UserCommand),
+    /// Transactional email
+    #[command(subcommand)]
+    Email(EmailCommand),
+    /// Transaction lookups and fixes
+    #[command(subcommand)]
+    Transaction(TransactionCommand),
+}
+
+/// `org` subcommands.
+#[derive(Debug, Clone, PartialEq, Eq, Subcommand)]
+pub enum OrgCommand {
+    /// Show an organization's tier, usage, members and fiscal periods
+    Info {
+        /// Organization slug
+        slug: String,
+    },
+}
+
+/// `user` subcommands.
+#[derive(Debug, Clone, PartialEq, Eq, Subcommand)]
+pub enum UserCommand {
+    /// Re-enable a disabled account so the user can log in again
+    Unlock {
+        /// User's email address
+        email: String,
+        /// Why the account is being unlocked, e.g. the support ticket
+        #[arg(long)]
+        reason: Option<String>,
+    },
+    /// Erase a user's personal data, keeping the user ID on their records
+    Erase {
+        /// User ID; the email address is among the data erased
+        id: Uuid,
+        /// Why the data is erased, e.g. the erasure request's reference
+        #[arg(long)]
+        reason: String,
+    },
+}
+
+/// `email` subcommands.
+#[derive(Debug, Clone, PartialEq, Eq, Subcommand)]
+pub enum EmailCommand {
+    /// Send a new verification link to an unverified user
+    ResendVerification {
+        /// User's email address
+        email: String,
+    },
+}
+
+/// `transaction` subcommands.
+#[derive(Debug, Clone, PartialEq, Eq, Subcommand)]
+pub enum TransactionCommand {
+    /// Show a transaction with its ledger entries
+    Show {
+        /// Transaction ID
+        id: Uuid,
+    },
+    /// Void a posted transaction through the regular void workflow
+    ForceVoid {
+        /// Transaction ID
+        id: Uuid,
+        /// Why the transaction is voided; stored as the void reason
+        #[arg(long)]
+        reason: String,
+        /// Member of the organization the void is recorded under
+        #[arg(long, value_name = "EMAIL")]
+        on_behalf_of: String,
+    },
+}
+
+impl Command {
+    /// Command name as recorded in the audit log.
+    #[must_use]
+    pub const fn name(&self) -> &'static str {
+        match self {
+            Self::Org(OrgCommand::Info { .. }) => "org info",
+            Self::User(UserCommand::Unlock { .. }) => "user unlock",
+            Self::User(UserCommand::Erase { .. }) => "user erase",
+            Self::Email(EmailCommand::ResendVerification { .. }) => "email resend-verification",
+            Self::Transaction(TransactionCommand::Show { .. }) => "transaction show",
+            Self::Transaction(TransactionCommand::ForceVoid { .. }) => "transaction force-void",
+        }
+    }
+
+    /// What the command is run against.
+    #[must_use]
+    pub fn target(&self) -> String {
+        match self {
+            Self::Org(OrgCommand::Info { slug }) => slug.clone(),
+            Self::User(UserCommand::Unlock { email, .. })
+            | Self::Email(EmailCommand::ResendVerification { email }) => email.clone(),
+            Self::User(UserCommand::Erase { id, .. })
+            | Self::Transaction(
+                TransactionCommand::Show { id } | TransactionCommand::ForceVoid { id, .. },
+            ) => id.to_string(),
+        }
+    }
+
+    /// Reason the operator gave, if the command takes one.
+    #[must_use]
+    pub fn reason(&self) -> Option<String> {
+        match self {
+            Self::User(UserCommand::Unlock { reason, .. }) => reason.clone(),
+            Self::User(UserCommand::Erase { reason, .. })
+            | Self::Transaction(TransactionCommand::ForceVoid { reason, .. }) => {
+                Some(reason.clone())
+            }
+            _ => None,
+        }
+    }
+
+    /// Whether the command changes data and so needs `--execute`.
+    #[must_use]
+    pub const fn writes(&self) -> bool {
+        matches!(
+            self,
+            Self::User(UserCommand::Unlock { .. } | UserCommand::Erase { .. })
+                | Self::Email(EmailCommand::ResendVerification { .. })
+                | Self::Transaction(TransactionCommand::ForceVoid { .. })
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::plan::Mode;
+
+    fn parse(args: &[&str]) -> Cli {
+        Cli::try_parse_from(std::iter::once("admin").chain(args.iter().copied()))
+            .expect("arguments parse")
+    }
+
+    #[test]
+    fn test_force_void_is_a_dry_run_unless_executed() {
+        let id = Uuid::new_v4().to_string();
+        let args = [
+            "transaction",
+            "force-void",
+            &id,
+            "--reason",
+            "Duplicate import, ticket 812",
+            "--on-behalf-of",
+            "cfo@example.com",
+        ];
+
+        let cli = parse(&args);
+        assert!(cli.command.writes());
+        assert_eq!(cli.command.name(), "transaction force-void");
+        assert_eq!(cli.command.target(), id);
+        assert_eq!(
+            cli.command.reason().as_deref(),
+            Some("Duplicate import, ticket 812")
+        );
+        assert_eq!(
+            Mode::resolve(cli.command.writes(), cli.execute),
+            Mode::DryRun
+        );
+
+        // The flag is accepted before or after the subcommand
+        let after = parse(&[&args[..], &["--execute"]].concat());
+        let before = parse(&[&["--execute"], &args[..]].concat());
+        for cli in [after, before] {
+            assert_eq!(
+                Mode::resolve(cli.command.writes(), cli.execute),
+                Mode::Execute
+            );
+        }
+    }
+
+    #[test]
+    fn test_lookups_do_not_need_execute() {
+        let cli = parse(&["org", "info", "acme"]);
+        assert_eq!(
+            cli.command,
+            Command::Org(OrgCommand::Info {
+                slug: "acme".to_string()
+            })
+        );
+        assert_eq!(
+            Mode::resolve(cli.command.writes(), cli.execute),
+            Mode::ReadOnly
+        );
+
+        let cli = parse(&["transaction", "show", &Uuid::nil().to_string()]);
+        assert!(!cli.command.writes());
+        assert_eq!(cli.command.reason(), None);
+    }
+
+    #[test]
+    fn test_fixes_are_gated() {
+        for args in [
+            &["user", "unlock", "ana@example.com"][..],
+            &["email", "resend-verification", "ana@example.com"][..],
+        ] {
+            assert!(parse(args).command.writes());
+        }
+    }
+
+    #[test]
+    fn test_erase_targets_the_user_id() {
+        let id = Uuid::new_v4().to_string();
+        let cli = parse(&["user", "erase", &id, "--reason", "Erasure request 31"]);
+        assert!(cli.command.writes());
+        assert_eq!(cli.command.name(), "user erase");
+        assert_eq!(cli.command.target(), id);
+        assert_eq!(cli.command.reason().as_deref(), Some("Erasure request 31"));
+
+        assert!(Cli::try_parse_from(["admin", "user", "erase", &id]).is_err());
+        assert!(
+            Cli::try_parse_from(["admin", "user", "erase", "ana@example.com", "--reason", "x"])
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_force_void_requires_reason_and_member() {
+        let id = Uuid::new_v4().to_string();
+        assert!(Cli::try_parse_from(["admin", "transaction", "force-void", &id]).is_err());
+        assert!(
+            Cli::try_parse_from([
+                "admin",
+                "transaction",
+                "force-void",
+                &id,
+                "--reason",
+                "Duplicate"
+            ])
+            .is_err()
+        );
+        assert!(Cli::try_parse_from(["admin", "transaction", "show", "not-a-uuid"]).is_err());
+    }
+}
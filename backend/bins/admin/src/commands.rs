@@ -0,0 +1,454 @@
+//! Command implementations.
+//!
+//! Lookups go through the repositories and entities; fixes go through the
+//! same repository methods the API uses, so they are subject to the same
+//! checks. Each command returns the organization it touched and details for
+//! the audit log.
+
+use std::collections::BTreeMap;
+
+use anyhow::{Context as _, bail};
+use chrono::Utc;
+use sea_orm::{ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, QueryOrder};
+use serde_json::{Value, json};
+use uuid::Uuid;
+
+use zeltra_core::workflow::VoidReasonCode;
+use zeltra_db::entities::{
+    chart_of_accounts, fiscal_periods, organization_usage, sea_orm_active_enums::TransactionStatus,
+    transactions, users,
+};
+use zeltra_db::repositories::{
+    EmailVerificationRepository, FiscalRepository, OperatorAuditRepository, OrganizationRepository,
+    SubscriptionRepository, TransactionRepository, UserRepository, WorkflowRepository,
+};
+use zeltra_shared::{AppConfig, EmailService};
+
+use crate::args::{Command, EmailCommand, OrgCommand, TransactionCommand, UserCommand};
+use crate::plan::{Mode, Plan};
+
+/// Support operations listed by `org info`.
+const RECENT_OPERATIONS: u64 = 5;
+
+/// What a command touched, for the audit log.
+#[derive(Debug, Clone)]
+pub struct Outcome {
+    /// Organization the command touched, if any.
+    pub organization_id: Option<Uuid>,
+    /// Command-specific details.
+    pub details: Value,
+}
+
+/// Runs `command` in `mode`.
+pub async fn run(
+    db: &DatabaseConnection,
+    command: &Command,
+    mode: Mode,
+) -> anyhow::Result<Outcome> {
+    match command {
+        Command::Org(OrgCommand::Info { slug }) => org_info(db, slug).await,
+        Command::User(UserCommand::Unlock { email, .. }) => user_unlock(db, email, mode).await,
+        Command::User(UserCommand::Erase { id, .. }) => user_erase(db, *id, mode).await,
+        Command::Email(EmailCommand::ResendVerification { email }) => {
+            resend_verification(db, email, mode).await
+        }
+        Command::Transaction(TransactionCommand::Show { id }) => transaction_show(db, *id).await,
+        Command::Transaction(TransactionCommand::ForceVoid {
+            id,
+            reason,
+            on_behalf_of,
+        }) => force_void(db, *id, reason, on_behalf_of, mode).await,
+    }
+}
+
+/// `org info <slug>`
+async fn org_info(db: &DatabaseConnection, slug: &str) -> anyhow::Result<Outcome> {
+    let org_repo = OrganizationRepository::new(db.clone());
+    let org = org_repo
+        .find_by_slug(slug)
+        .await?
+        .with_context(|| format!("no organization with slug '{slug}'"))?;
+
+    let members = org_repo.get_users(org.id).await?;
+    let mut roles: BTreeMap<String, usize> = BTreeMap::new();
+    for (_, membership) in &members {
+        *roles
+            .entry(format!("{:?}", membership.role).to_lowercase())
+            .or_default() += 1;
+    }
+
+    let limits = SubscriptionRepository::get_tier_limits(db, org.subscription_tier.clone()).await?;
+    let usage = organization_usage::Entity::find()
+        .filter(organization_usage::Column::OrganizationId.eq(org.id))
+        .order_by_desc(organization_usage::Column::YearMonth)
+        .one(db)
+        .await?;
+
+    let periods = fiscal_periods::Entity::find()
+        .filter(fiscal_periods::Column::OrganizationId.eq(org.id))
+        .all(db)
+        .await?;
+    let mut period_counts: BTreeMap<String, usize> = BTreeMap::new();
+    for period in &periods {
+        *period_counts
+            .entry(format!("{:?}", period.status))
+            .or_default() += 1;
+    }
+    let current = FiscalRepository::new(db.clone())
+        .find_period_for_date(org.id, Utc::now().date_naive())
+        .await?;
+
+    let recent = OperatorAuditRepository::new(db.clone())
+        .list_for_organization(org.id, RECENT_OPERATIONS)
+        .await?;
+
+    println!("Organization  {} ({})", org.name, org.slug);
+    println!("ID            {}", org.id);
+    println!(
+        "Status        {}, created {}",
+        if org.is_active { "active" } else { "inactive" },
+        org.created_at.date_naive()
+    );
+    let trial = org
+        .trial_ends_at
+        .map(|t| format!(", trial ends {}", t.date_naive()))
+        .unwrap_or_default();
+    println!(
+        "Tier          {:?}, subscription {:?}{trial}",
+        org.subscription_tier, org.subscription_status
+    );
+    println!(
+        "Members       {} ({})",
+        members.len(),
+        counts(&roles).unwrap_or_else(|| "none".to_string())
+    );
+    match &usage {
+        Some(usage) => {
+            let max = limits
+                .as_ref()
+                .and_then(|l| l.max_transactions_per_month)
+                .map_or_else(|| "unlimited".to_string(), |m| m.to_string());
+            println!(
+                "Usage         {}: {}/{max} transactions, {} API calls, {} bytes stored",
+                usage.year_month,
+                usage.transaction_count,
+                usage.api_call_count,
+                usage.storage_used_bytes
+            );
+        }
+        None => println!("Usage         none recorded"),
+    }
+    match &current {
+        Some(period) => println!("Period        {} is {:?}", period.name, period.status),
+        None => println!("Period        no fiscal period covers today"),
+    }
+    println!(
+        "All periods   {}",
+        counts(&period_counts).unwrap_or_else(|| "none".to_string())
+    );
+    if !recent.is_empty() {
+        println!("Recent support operations:");
+        for op in &recent {
+            println!(
+                "  {}  {:<26} {} by {}{}",
+                op.created_at.format("%Y-%m-%d %H:%M"),
+                op.command,
+                op.target,
+                op.operator,
+                if op.executed { "" } else { " (not executed)" }
+            );
+        }
+    }
+
+    Ok(Outcome {
+        organization_id: Some(org.id),
+        details: json!({}),
+    })
+}
+
+/// `user unlock <email>`
+///
+/// Zeltra counts no failed logins; a user is locked out when their account
+/// is disabled, which is what this reverses.
+async fn user_unlock(db: &DatabaseConnection, email: &str, mode: Mode) -> anyhow::Result<Outcome> {
+    let user_repo = UserRepository::new(db.clone());
+    let user = find_user(&user_repo, email).await?;
+
+    if user.is_active {
+        println!("{email} is not locked out; nothing to change.");
+        return Ok(Outcome {
+            organization_id: None,
+            details: json!({ "user_id": user.id, "reactivated": false }),
+        });
+    }
+
+    let plan = Plan::new(format!("Re-enable login for {} <{email}>", user.full_name))
+        .step("Mark the account active")
+        .step("Leave the password, sessions and memberships as they are");
+    println!("{}", plan.render(mode));
+
+    let mut reactivated = false;
+    if mode == Mode::Execute {
+        reactivated = user_repo.reactivate(user.id).await?;
+        println!("Account re-enabled.");
+    }
+
+    Ok(Outcome {
+        organization_id: None,
+        details: json!({ "user_id": user.id, "reactivated": reactivated }),
+    })
+}
+
+/// `user erase <id> --reason <text>`
+///
+/// Nothing identifying goes into the audit log: the target is the user ID
+/// and the details are counts.
+async fn user_erase(db: &DatabaseConnection, id: Uuid, mode: Mode) -> anyhow::Result<Outcome> {
+    let user_repo = UserRepository::new(db.clone());
+    let user = user_repo
+        .find_by_id(id)
+        .await?
+        .with_context(|| format!("no user {id}"))?;
+    let sole_owner = user_repo.sole_owner_of(id).await?;
+    if !sole_owner.is_empty() {
+        bail!(
+            "{} is the only owner of {}; transfer ownership or delete those organizations first",
+            user.email,
+            sole_owner.join(", ")
+        );
+    }
+    let memberships = user_repo.get_user_organizations(id).await?;
+
+    let plan = Plan::new(format!(
+        "Erase personal data of {} <{}>",
+        user.full_name, user.email
+    ))
+    .step("Replace the email and name with placeholders and clear the password")
+    .step("Disable the account and delete its sessions, verification links and notifications")
+    .step(format!(
+        "Remove {} organization membership(s)",
+        memberships.len()
+    ))
+    .step("Keep the user ID on transactions, approvals and comments");
+    println!("{}", plan.render(mode));
+
+    let mut details = json!({ "memberships": memberships.len() });
+    if mode == Mode::Execute {
+        let erased = user_repo.anonymize(id).await?;
+        println!("Erased; the user now shows as {}.", erased.user.full_name);
+        details = json!({
+            "memberships_removed": erased.memberships_removed,
+            "sessions_deleted": erased.sessions_deleted,
+        });
+    }
+
+    Ok(Outcome {
+        organization_id: None,
+        details,
+    })
+}
+
+/// `email resend-verification <email>`
+async fn resend_verification(
+    db: &DatabaseConnection,
+    email: &str,
+    mode: Mode,
+) -> anyhow::Result<Outcome> {
+    let user = find_user(&UserRepository::new(db.clone()), email).await?;
+    if user.email_verified_at.is_some() {
+        bail!("{email} is already verified");
+    }
+
+    let config = AppConfig::load().context("Failed to load configuration for email")?;
+    let plan = Plan::new(format!("Send a new verification link to {email}"))
+        .step("Create a verification token, invalidating earlier links")
+        .step(format!(
+            "Email it through {}:{}",
+            config.email.smtp_host, config.email.smtp_port
+        ));
+    println!("{}", plan.render(mode));
+
+    if mode == Mode::Execute {
+        let token = EmailVerificationRepository::new(db.clone())
+            .create_token(user.id)
+            .await?;
+        EmailService::new(config.email)
+            .send_verification_email(&user.email, &user.full_name, &token)
+            .await?;
+        println!("Verification email sent.");
+    }
+
+    Ok(Outcome {
+        organization_id: None,
+        details: json!({ "user_id": user.id }),
+    })
+}
+
+/// `transaction show <id>`
+async fn transaction_show(db: &DatabaseConnection, id: Uuid) -> anyhow::Result<Outcome> {
+    let organization_id = find_transaction(db, id).await?.organization_id;
+    let detail = TransactionRepository::new(db.clone())
+        .get_transaction(organization_id.into(), id.into())
+        .await?;
+    let tx = &detail.transaction;
+
+    let account_ids: Vec<Uuid> = detail.entries.iter().map(|e| e.entry.account_id).collect();
+    let codes: BTreeMap<Uuid, String> = chart_of_accounts::Entity::find()
+        .filter(chart_of_accounts::Column::Id.is_in(account_ids))
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|a| (a.id, a.code))
+        .collect();
+
+    println!("Transaction   {}", tx.id);
+    println!("Organization  {}", tx.organization_id);
+    println!(
+        "Reference     {}",
+        tx.reference_number.as_deref().unwrap_or("-")
+    );
+    println!("Type          {:?}", tx.transaction_type);
+    println!("Date          {}", tx.transaction_date);
+    println!("Status        {:?}", tx.status);
+    println!("Description   {}", tx.description);
+    println!("Created       {} by {}", tx.created_at, tx.created_by);
+    if let (Some(at), Some(by)) = (tx.posted_at, tx.posted_by) {
+        println!("Posted        {at} by {by}");
+    }
+    if let (Some(at), Some(by)) = (tx.voided_at, tx.voided_by) {
+        println!(
+            "Voided        {at} by {by}: {}",
+            tx.void_reason.as_deref().unwrap_or("-")
+        );
+    }
+    if let Some(reversal) = tx.reversed_by_transaction_id {
+        println!("Reversed by   {reversal}");
+    }
+    if let Some(original) = tx.reverses_transaction_id {
+        println!("Reverses      {original}");
+    }
+    println!("Entries:");
+    for e in &detail.entries {
+        let entry = &e.entry;
+        println!(
+            "  {:<10} {:>18} {:>18}  {} {} @ {}{}",
+            codes.get(&entry.account_id).map_or("?", String::as_str),
+            entry.debit,
+            entry.credit,
+            entry.source_amount,
+            entry.source_currency,
+            entry.exchange_rate,
+            entry
+                .memo
+                .as_deref()
+                .map(|m| format!("  {m}"))
+                .unwrap_or_default()
+        );
+        if !e.dimensions.is_empty() {
+            let dimensions: Vec<String> = e.dimensions.iter().map(Uuid::to_string).collect();
+            println!("  {:<10} dimensions {}", "", dimensions.join(", "));
+        }
+    }
+
+    Ok(Outcome {
+        organization_id: Some(organization_id),
+        details: json!({}),
+    })
+}
+
+/// `transaction force-void <id> --reason <text> --on-behalf-of <email>`
+async fn force_void(
+    db: &DatabaseConnection,
+    id: Uuid,
+    reason: &str,
+    on_behalf_of: &str,
+    mode: Mode,
+) -> anyhow::Result<Outcome> {
+    let tx = find_transaction(db, id).await?;
+    let org_repo = OrganizationRepository::new(db.clone());
+    let org = org_repo
+        .find_by_id(tx.organization_id)
+        .await?
+        .context("transaction's organization not found")?;
+    if tx.status != TransactionStatus::Posted {
+        bail!(
+            "transaction is {:?}; only posted transactions can be voided",
+            tx.status
+        );
+    }
+    if reason.trim().is_empty() {
+        bail!("--reason must not be empty");
+    }
+    let actor = find_user(&UserRepository::new(db.clone()), on_behalf_of).await?;
+    if !org_repo.is_member(org.id, actor.id).await? {
+        bail!("{on_behalf_of} is not a member of {}", org.slug);
+    }
+
+    let plan = Plan::new(format!(
+        "Void transaction {} ({}) in {}",
+        tx.reference_number
+            .as_deref()
+            .unwrap_or("without reference"),
+        tx.id,
+        org.slug
+    ))
+    .step(format!(
+        "Post a reversing transaction dated {} into the original's period",
+        tx.transaction_date
+    ))
+    .step(format!("Mark the original voided with reason: {reason}"))
+    .step(format!("Record both under {on_behalf_of}"));
+    println!("{}", plan.render(mode));
+
+    let mut details = json!({
+        "on_behalf_of": actor.id,
+        "status": format!("{:?}", tx.status),
+    });
+    if mode == Mode::Execute {
+        let result = WorkflowRepository::new(db.clone())
+            .void_transaction(
+                org.id.into(),
+                tx.id.into(),
+                actor.id,
+                VoidReasonCode::Other,
+                reason.to_string(),
+            )
+            .await?;
+        let reversal = result.reversing_transaction.id;
+        println!("Voided; reversal {reversal} posted.");
+        details["reversing_transaction_id"] = json!(reversal);
+    }
+
+    Ok(Outcome {
+        organization_id: Some(org.id),
+        details,
+    })
+}
+
+async fn find_user(user_repo: &UserRepository, email: &str) -> anyhow::Result<users::Model> {
+    user_repo
+        .find_by_email(email)
+        .await?
+        .with_context(|| format!("no user with email {email}"))
+}
+
+async fn find_transaction(
+    db: &DatabaseConnection,
+    id: Uuid,
+) -> anyhow::Result<transactions::Model> {
+    transactions::Entity::find_by_id(id)
+        .one(db)
+        .await?
+        .with_context(|| format!("no transaction {id}"))
+}
+
+/// Formats counts as `name n, name n`, `None` when there are none.
+fn counts(counts: &BTreeMap<String, usize>) -> Option<String> {
+    (!counts.is_empty()).then(|| {
+        counts
+            .iter()
+            .map(|(name, n)| format!("{name} {n}"))
+            .collect::<Vec<_>>()
+            .join(", ")
+    })
+}
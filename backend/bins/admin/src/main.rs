@@ -0,0 +1,74 @@
+//! Audited support operations for Zeltra.
+//!
+//! Usage:
+//!   admin org info <slug>                        - Tier, usage, members and periods
+//!   admin user unlock <email> [--reason R]       - Re-enable a disabled account
+//!   admin user erase <id> --reason R             - Erase a user's personal data
+//!   admin email resend-verification <email>      - Send a new verification link
+//!   admin transaction show <id>                  - A transaction with its entries
+//!   admin transaction force-void <id> --reason R --on-behalf-of <email>
+//!                                                - Void through the workflow
+//!
+//! Commands that change data print a dry-run plan unless `--execute` is
+//! passed; see [`plan`]. Fixes go through the repositories, never raw SQL,
+//! so a forced void posts a reversal like one made in the app. Every
+//! command, dry run or not, writes a row to the operator audit log under the
+//! name in `ZELTRA_OPERATOR`, which must be set.
+
+mod args;
+mod commands;
+mod plan;
+
+use anyhow::{Context, bail};
+use clap::Parser;
+use serde_json::json;
+use zeltra_db::repositories::{OperatorActionInput, OperatorAuditRepository};
+
+use crate::args::Cli;
+use crate::plan::Mode;
+
+/// Environment variable naming the operator running the tool.
+const OPERATOR_VAR: &str = "ZELTRA_OPERATOR";
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    dotenvy::dotenv().ok();
+
+    let cli = Cli::parse();
+
+    let operator = std::env::var(OPERATOR_VAR).unwrap_or_default();
+    let operator = operator.trim();
+    if operator.is_empty() {
+        bail!("{OPERATOR_VAR} must name the operator running this command");
+    }
+
+    let database_url =
+        std::env::var("DATABASE_URL").context("DATABASE_URL must be set in environment")?;
+    let db = zeltra_db::connect(&database_url)
+        .await
+        .context("Failed to connect to database")?;
+
+    let command = &cli.command;
+    let mode = Mode::resolve(command.writes(), cli.execute);
+    let result = commands::run(&db, command, mode).await;
+
+    // Record the command whether it succeeded or not
+    let (organization_id, details) = match &result {
+        Ok(outcome) => (outcome.organization_id, outcome.details.clone()),
+        Err(e) => (None, json!({ "error": format!("{e:#}") })),
+    };
+    OperatorAuditRepository::new(db)
+        .record(OperatorActionInput {
+            operator: operator.to_string(),
+            command: command.name().to_string(),
+            target: command.target(),
+            organization_id,
+            executed: mode.carries_out() && result.is_ok(),
+            reason: command.reason(),
+            details,
+        })
+        .await
+        .context("Failed to write the audit log")?;
+
+    result.map(|_| ())
+}
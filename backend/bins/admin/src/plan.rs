@@ -0,0 +1,125 @@
+//! Dry-run gating for commands that change data.
+//!
+//! A command that writes first describes what it would do as a [`Plan`].
+//! Without `--execute` the plan is printed and nothing else happens; with it
+//! the plan is printed and then carried out. Read-only commands run either
+//! way.
+
+use std::fmt;
+
+/// How a command runs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mode {
+    /// The command only reads.
+    ReadOnly,
+    /// The command writes but `--execute` was not passed: print the plan.
+    DryRun,
+    /// The command writes and `--execute` was passed.
+    Execute,
+}
+
+impl Mode {
+    /// Decides how a command runs from whether it writes and the
+    /// `--execute` flag.
+    #[must_use]
+    pub const fn resolve(writes: bool, execute: bool) -> Self {
+        match (writes, execute) {
+            (false, _) => Self::ReadOnly,
+            (true, false) => Self::DryRun,
+            (true, true) => Self::Execute,
+        }
+    }
+
+    /// Whether the command is carried out rather than only planned.
+    #[must_use]
+    pub const fn carries_out(self) -> bool {
+        !matches!(self, Self::DryRun)
+    }
+}
+
+/// What a writing command is about to do.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Plan {
+    /// One-line description of the change.
+    pub summary: String,
+    /// Individual steps, in order.
+    pub steps: Vec<String>,
+}
+
+impl Plan {
+    /// Starts a plan with the given summary.
+    #[must_use]
+    pub fn new(summary: impl Into<String>) -> Self {
+        Self {
+            summary: summary.into(),
+            steps: Vec::new(),
+        }
+    }
+
+    /// Adds a step.
+    #[must_use]
+    pub fn step(mut self, step: impl Into<String>) -> Self {
+        self.steps.push(step.into());
+        self
+    }
+
+    /// Renders the plan as printed before running in `mode`.
+    #[must_use]
+    pub fn render(&self, mode: Mode) -> String {
+        let heading = match mode {
+            Mode::DryRun => "Dry run, nothing will be changed",
+            Mode::ReadOnly | Mode::Execute => "Executing",
+        };
+        let mut out = format!("{heading}: {self}");
+        if mode == Mode::DryRun {
+            out.push_str("\nRe-run with --execute to apply.");
+        }
+        out
+    }
+}
+
+impl fmt::Display for Plan {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.summary)?;
+        for (i, step) in self.steps.iter().enumerate() {
+            write!(f, "\n  {}. {step}", i + 1)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_writes_are_dry_runs_without_execute() {
+        assert_eq!(Mode::resolve(true, false), Mode::DryRun);
+        assert_eq!(Mode::resolve(true, true), Mode::Execute);
+        assert!(!Mode::DryRun.carries_out());
+        assert!(Mode::Execute.carries_out());
+    }
+
+    #[test]
+    fn test_reads_run_regardless_of_execute() {
+        assert_eq!(Mode::resolve(false, false), Mode::ReadOnly);
+        assert_eq!(Mode::resolve(false, true), Mode::ReadOnly);
+        assert!(Mode::ReadOnly.carries_out());
+    }
+
+    #[test]
+    fn test_render_marks_dry_runs() {
+        let plan = Plan::new("Re-enable login for ana@example.com")
+            .step("Set the account active")
+            .step("Record the unlock");
+
+        let dry = plan.render(Mode::DryRun);
+        assert!(dry.starts_with("Dry run, nothing will be changed: Re-enable login"));
+        assert!(dry.contains("\n  1. Set the account active\n  2. Record the unlock"));
+        assert!(dry.ends_with("Re-run with --execute to apply."));
+
+        let executed = plan.render(Mode::Execute);
+        assert!(executed.starts_with("Executing: "));
+        assert!(!executed.contains("--execute"));
+    }
+}
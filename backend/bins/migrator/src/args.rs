@@ -0,0 +1,213 @@
+//! Command-line parsing.
+
+use anyhow::{Context, bail};
+
+pub const USAGE: &str = "\
+Usage: migrator <COMMAND> [OPTIONS]
+
+Commands:
+  up        Apply pending migrations
+  down      Roll back applied migrations
+  status    List applied and pending migrations
+  fresh     Drop all tables and re-apply every migration
+
+Options:
+  --steps <n>               Migrations to apply or roll back [up: all, down: 1]
+  --json                    Print status as JSON
+  --confirm <phrase>        Confirmation phrase for fresh, instead of prompting
+  --i-know-what-im-doing    Allow down or fresh against a protected database
+  -h, --help                Print this help";
+
+/// A parsed command.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Command {
+    /// Apply pending migrations, all of them when `steps` is `None`.
+    Up { steps: Option<u32> },
+    /// Roll back the last `steps` migrations.
+    Down { steps: u32, force: bool },
+    /// List migrations.
+    Status { json: bool },
+    /// Drop everything and re-apply all migrations.
+    Fresh {
+        force: bool,
+        confirm: Option<String>,
+    },
+    /// Print usage.
+    Help,
+}
+
+/// Options seen on the command line, before checking they fit the command.
+#[derive(Debug, Default)]
+struct Flags {
+    steps: Option<u32>,
+    json: bool,
+    confirm: Option<String>,
+    force: bool,
+}
+
+/// Parses the arguments after the program name.
+///
+/// # Errors
+///
+/// Returns an error for an unknown command or option, a missing or invalid
+/// value, or an option the command does not take.
+pub fn parse<I>(args: I) -> anyhow::Result<Command>
+where
+    I: IntoIterator<Item = String>,
+{
+    let mut args = args.into_iter();
+    let Some(command) = args.next() else {
+        bail!("missing command\n\n{USAGE}");
+    };
+    if matches!(command.as_str(), "-h" | "--help") {
+        return Ok(Command::Help);
+    }
+
+    let mut flags = Flags::default();
+    while let Some(arg) = args.next() {
+        let (flag, inline) = match arg.split_once('=') {
+            Some((flag, value)) => (flag.to_string(), Some(value.to_string())),
+            None => (arg, None),
+        };
+        match flag.as_str() {
+            "-h" | "--help" => return Ok(Command::Help),
+            "--json" => {
+                flags.json = true;
+                continue;
+            }
+            "--i-know-what-im-doing" => {
+                flags.force = true;
+                continue;
+            }
+            "--steps" | "--confirm" => {}
+            other => bail!("unknown option '{other}'\n\n{USAGE}"),
+        }
+        let value = match inline {
+            Some(value) => value,
+            None => args
+                .next()
+                .with_context(|| format!("missing value for {flag}"))?,
+        };
+        if flag == "--steps" {
+            let steps: u32 = value
+                .parse()
+                .ok()
+                .filter(|n| *n > 0)
+                .with_context(|| format!("--steps must be a positive number, got '{value}'"))?;
+            flags.steps = Some(steps);
+        } else {
+            flags.confirm = Some(value);
+        }
+    }
+
+    let command = match command.as_str() {
+        "up" => {
+            reject(&command, "--json", flags.json)?;
+            reject(&command, "--confirm", flags.confirm.is_some())?;
+            reject(&command, "--i-know-what-im-doing", flags.force)?;
+            Command::Up { steps: flags.steps }
+        }
+        "down" => {
+            reject(&command, "--json", flags.json)?;
+            reject(&command, "--confirm", flags.confirm.is_some())?;
+            Command::Down {
+                steps: flags.steps.unwrap_or(1),
+                force: flags.force,
+            }
+        }
+        "status" => {
+            reject(&command, "--steps", flags.steps.is_some())?;
+            reject(&command, "--confirm", flags.confirm.is_some())?;
+            reject(&command, "--i-know-what-im-doing", flags.force)?;
+            Command::Status { json: flags.json }
+        }
+        "fresh" => {
+            reject(&command, "--steps", flags.steps.is_some())?;
+            reject(&command, "--json", flags.json)?;
+            Command::Fresh {
+                force: flags.force,
+                confirm: flags.confirm,
+            }
+        }
+        other => bail!("unknown command '{other}'\n\n{USAGE}"),
+    };
+    Ok(command)
+}
+
+/// Fails when `present` is set, naming the option `command` does not take.
+fn reject(command: &str, option: &str, present: bool) -> anyhow::Result<()> {
+    if present {
+        bail!("{command} does not take {option}");
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_str(line: &str) -> anyhow::Result<Command> {
+        parse(line.split_whitespace().map(str::to_string))
+    }
+
+    #[test]
+    fn test_up_and_down_steps() {
+        assert_eq!(parse_str("up").unwrap(), Command::Up { steps: None });
+        assert_eq!(
+            parse_str("up --steps 2").unwrap(),
+            Command::Up { steps: Some(2) }
+        );
+        assert_eq!(
+            parse_str("down").unwrap(),
+            Command::Down {
+                steps: 1,
+                force: false
+            }
+        );
+        assert_eq!(
+            parse_str("down --steps=3 --i-know-what-im-doing").unwrap(),
+            Command::Down {
+                steps: 3,
+                force: true
+            }
+        );
+    }
+
+    #[test]
+    fn test_status_and_fresh() {
+        assert_eq!(
+            parse_str("status").unwrap(),
+            Command::Status { json: false }
+        );
+        assert_eq!(
+            parse_str("status --json").unwrap(),
+            Command::Status { json: true }
+        );
+        assert_eq!(
+            parse_str("fresh --i-know-what-im-doing --confirm=fresh-zeltra").unwrap(),
+            Command::Fresh {
+                force: true,
+                confirm: Some("fresh-zeltra".to_string())
+            }
+        );
+        assert_eq!(parse_str("fresh -h").unwrap(), Command::Help);
+    }
+
+    #[test]
+    fn test_invalid_arguments() {
+        for line in [
+            "",
+            "refresh",
+            "up --steps",
+            "up --steps 0",
+            "down --steps -1",
+            "up --json",
+            "up --i-know-what-im-doing",
+            "status --steps 1",
+            "fresh --steps 1",
+            "down --verbose",
+        ] {
+            assert!(parse_str(line).is_err(), "'{line}' should not parse");
+        }
+    }
+}
@@ -0,0 +1,260 @@
+//! Safety checks for destructive commands.
+//!
+//! A database is protected when `ZELTRA_ENV` is not in the allow-list, or
+//! when its name matches the production pattern. `down` and `fresh` refuse to
+//! touch a protected database without `--i-know-what-im-doing`, and `fresh`
+//! always needs the confirmation phrase as well.
+
+use thiserror::Error;
+
+/// Environments `down` and `fresh` may run in by default.
+const DEFAULT_ALLOWED_ENVS: &str = "development,test";
+
+/// Database names treated as production by default.
+const DEFAULT_PRODUCTION_PATTERN: &str = "*prod*";
+
+/// Why a destructive command was refused.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum Refusal {
+    /// The database is protected and the override flag was not given.
+    #[error(
+        "refusing to {command} {database}: {reason}. Pass --i-know-what-im-doing if this is intended"
+    )]
+    Protected {
+        command: &'static str,
+        database: String,
+        reason: String,
+    },
+
+    /// The confirmation phrase was missing or wrong.
+    #[error("confirmation did not match '{expected}'; nothing was changed")]
+    NotConfirmed { expected: String },
+}
+
+/// Where the migrator is running, as read from the environment.
+#[derive(Debug, Clone)]
+pub struct Environment {
+    /// `ZELTRA_ENV`, `development` when unset.
+    pub name: String,
+    /// `MIGRATOR_ALLOWED_ENVS`, comma-separated.
+    pub allowed: Vec<String>,
+    /// `MIGRATOR_PRODUCTION_DB_PATTERN`, comma-separated globs.
+    pub production_pattern: String,
+}
+
+impl Environment {
+    /// Reads the environment variables, applying defaults.
+    #[must_use]
+    pub fn from_env() -> Self {
+        let var = |name: &str, default: &str| {
+            std::env::var(name)
+                .ok()
+                .filter(|v| !v.trim().is_empty())
+                .unwrap_or_else(|| default.to_string())
+        };
+        Self {
+            name: var("ZELTRA_ENV", "development"),
+            allowed: split_list(&var("MIGRATOR_ALLOWED_ENVS", DEFAULT_ALLOWED_ENVS)),
+            production_pattern: var("MIGRATOR_PRODUCTION_DB_PATTERN", DEFAULT_PRODUCTION_PATTERN),
+        }
+    }
+
+    /// Why `database` is protected, or `None` when it is not.
+    #[must_use]
+    pub fn protection(&self, database: &str) -> Option<String> {
+        if !self
+            .allowed
+            .iter()
+            .any(|env| env.eq_ignore_ascii_case(&self.name))
+        {
+            return Some(format!(
+                "ZELTRA_ENV={} is not one of {}",
+                self.name,
+                self.allowed.join(", ")
+            ));
+        }
+        if matches_pattern(&self.production_pattern, database) {
+            return Some(format!(
+                "database name matches the production pattern '{}'",
+                self.production_pattern
+            ));
+        }
+        None
+    }
+
+    /// Checks that `command` may run against `database`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Refusal::Protected`] if the database is protected and
+    /// `force` is not set.
+    pub fn check(&self, command: &'static str, database: &str, force: bool) -> Result<(), Refusal> {
+        match self.protection(database) {
+            Some(reason) if !force => Err(Refusal::Protected {
+                command,
+                database: database.to_string(),
+                reason,
+            }),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Phrase that must be typed, or passed with `--confirm`, to run `fresh`.
+#[must_use]
+pub fn confirmation_phrase(database: &str) -> String {
+    format!("fresh-{database}")
+}
+
+/// Checks a confirmation against the phrase for `database`.
+///
+/// # Errors
+///
+/// Returns [`Refusal::NotConfirmed`] unless `given` matches exactly,
+/// ignoring surrounding whitespace.
+pub fn check_confirmation(database: &str, given: &str) -> Result<(), Refusal> {
+    let expected = confirmation_phrase(database);
+    if given.trim() == expected {
+        Ok(())
+    } else {
+        Err(Refusal::NotConfirmed { expected })
+    }
+}
+
+/// Database name from a connection URL: the last path segment, without the
+/// query string.
+#[must_use]
+pub fn database_name(url: &str) -> Option<&str> {
+    let without_query = url.split(['?', '#']).next()?;
+    let (_, rest) = without_query.split_once("://")?;
+    let (_, path) = rest.split_once('/')?;
+    let name = path.rsplit('/').next()?;
+    (!name.is_empty()).then_some(name)
+}
+
+/// Whether `name` matches any of the comma-separated globs in `pattern`.
+///
+/// `*` matches any run of characters; matching ignores ASCII case.
+#[must_use]
+pub fn matches_pattern(pattern: &str, name: &str) -> bool {
+    let name = name.to_ascii_lowercase();
+    split_list(pattern)
+        .iter()
+        .any(|glob| glob_match(&glob.to_ascii_lowercase(), &name))
+}
+
+fn glob_match(glob: &str, name: &str) -> bool {
+    let mut parts = glob.split('*');
+    let first = parts.next().unwrap_or_default();
+    let Some(mut rest) = name.strip_prefix(first) else {
+        return false;
+    };
+    let parts: Vec<&str> = parts.collect();
+    let Some((last, middle)) = parts.split_last() else {
+        // No `*`: the whole name must match
+        return rest.is_empty();
+    };
+    for part in middle {
+        match rest.find(part) {
+            Some(i) => rest = &rest[i + part.len()..],
+            None => return false,
+        }
+    }
+    rest.ends_with(last)
+}
+
+fn split_list(value: &str) -> Vec<String> {
+    value
+        .split(',')
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn env(name: &str) -> Environment {
+        Environment {
+            name: name.to_string(),
+            allowed: split_list(DEFAULT_ALLOWED_ENVS),
+            production_pattern: "*prod*,zeltra_live".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_database_name() {
+        assert_eq!(
+            database_name("postgres://u:p@localhost:5432/zeltra_dev?sslmode=disable"),
+            Some("zeltra_dev")
+        );
+        assert_eq!(database_name("postgres://localhost/db"), Some("db"));
+        assert_eq!(database_name("postgres://localhost/"), None);
+        assert_eq!(database_name("postgres://localhost"), None);
+        assert_eq!(database_name("not a url"), None);
+    }
+
+    #[test]
+    fn test_matches_pattern() {
+        assert!(matches_pattern("*prod*", "zeltra_PROD"));
+        assert!(matches_pattern("*prod*", "production"));
+        assert!(matches_pattern("zeltra_*_live", "zeltra_eu_live"));
+        assert!(matches_pattern("a, zeltra", "zeltra"));
+        assert!(!matches_pattern("zeltra", "zeltra_dev"));
+        assert!(!matches_pattern("*prod*", "zeltra_staging"));
+        assert!(!matches_pattern("*_live", "live"));
+        assert!(!matches_pattern("", "anything"));
+    }
+
+    #[test]
+    fn test_protection() {
+        assert_eq!(env("development").protection("zeltra_dev"), None);
+        assert_eq!(env("Test").protection("zeltra_test"), None);
+
+        let reason = env("production").protection("zeltra_dev").unwrap();
+        assert!(reason.contains("ZELTRA_ENV=production"));
+        let reason = env("staging").protection("zeltra_dev").unwrap();
+        assert!(reason.contains("ZELTRA_ENV=staging"));
+        let reason = env("development").protection("zeltra_live").unwrap();
+        assert!(reason.contains("production pattern"));
+    }
+
+    #[test]
+    fn test_check_needs_force_when_protected() {
+        assert_eq!(
+            env("development").check("fresh", "zeltra_dev", false),
+            Ok(())
+        );
+        assert!(matches!(
+            env("production").check("fresh", "zeltra", false),
+            Err(Refusal::Protected {
+                command: "fresh",
+                ..
+            })
+        ));
+        assert_eq!(env("production").check("down", "zeltra", true), Ok(()));
+        assert!(
+            env("development")
+                .check("down", "zeltra_prod", false)
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_confirmation() {
+        assert_eq!(
+            check_confirmation("zeltra_dev", "fresh-zeltra_dev\n"),
+            Ok(())
+        );
+        for given in ["", "yes", "fresh-zeltra", "FRESH-zeltra_dev"] {
+            assert_eq!(
+                check_confirmation("zeltra_dev", given),
+                Err(Refusal::NotConfirmed {
+                    expected: "fresh-zeltra_dev".to_string()
+                })
+            );
+        }
+    }
+}
@@ -1,19 +1,110 @@
 //! Database migration runner for Zeltra.
 //!
 //! Usage:
-//!   migrator up      - Run all pending migrations
-//!   migrator down    - Rollback last migration
-//!   migrator status  - Show migration status
-//!   migrator fresh   - Drop all tables and re-run migrations
+//!   migrator up [--steps N]      - Apply pending migrations, all by default
+//!   migrator down [--steps N]    - Roll back the last N migrations, 1 by default
+//!   migrator status [--json]     - List applied and pending migrations
+//!   migrator fresh               - Drop all tables and re-run migrations
+//!
+//! `down` and `fresh` are guarded: see [`guard`] for when a database counts
+//! as protected. `fresh` also asks for a confirmation phrase, which
+//! non-interactive callers pass with `--confirm`.
+
+mod args;
+mod guard;
+mod status;
+
+use std::io::{BufRead, Write};
+use std::process::ExitCode;
 
-use sea_orm_migration::prelude::*;
+use anyhow::Context;
+use sea_orm_migration::MigratorTrait;
 use zeltra_db::migration::Migrator;
 
+use crate::args::{Command, USAGE};
+use crate::guard::Environment;
+
 #[tokio::main]
-async fn main() {
+async fn main() -> anyhow::Result<ExitCode> {
     // Load .env file if present
     dotenvy::dotenv().ok();
 
-    // Run the migrator CLI (it sets up its own tracing)
-    cli::run_cli(Migrator).await;
+    let command = args::parse(std::env::args().skip(1))?;
+    if command == Command::Help {
+        println!("{USAGE}");
+        return Ok(ExitCode::SUCCESS);
+    }
+
+    let database_url =
+        std::env::var("DATABASE_URL").context("DATABASE_URL must be set in environment")?;
+    let database = guard::database_name(&database_url)
+        .context("DATABASE_URL does not name a database")?
+        .to_string();
+    let environment = Environment::from_env();
+
+    // Pre-flight checks run before connecting
+    match &command {
+        Command::Down { force, .. } => environment.check("roll back", &database, *force)?,
+        Command::Fresh { force, confirm } => {
+            environment.check("drop all tables in", &database, *force)?;
+            let given = match confirm {
+                Some(given) => given.clone(),
+                None => prompt_confirmation(&database)?,
+            };
+            guard::check_confirmation(&database, &given)?;
+        }
+        _ => {}
+    }
+
+    let db = zeltra_db::connect(&database_url)
+        .await
+        .context("Failed to connect to database")?;
+
+    match command {
+        Command::Up { steps } => {
+            Migrator::up(&db, steps)
+                .await
+                .context("Failed to apply migrations")?;
+            println!("Migrations applied to {database}.");
+        }
+        Command::Down { steps, .. } => {
+            Migrator::down(&db, Some(steps))
+                .await
+                .context("Failed to roll back migrations")?;
+            println!("Rolled back {steps} migration(s) on {database}.");
+        }
+        Command::Status { json } => {
+            let migrations = status::load(&db).await?;
+            if json {
+                println!("{}", serde_json::to_string_pretty(&migrations)?);
+            } else {
+                for migration in &migrations {
+                    println!("{migration}");
+                }
+            }
+        }
+        Command::Fresh { .. } => {
+            Migrator::fresh(&db)
+                .await
+                .context("Failed to refresh database")?;
+            println!("Dropped all tables in {database} and re-applied migrations.");
+        }
+        Command::Help => unreachable!("handled above"),
+    }
+    Ok(ExitCode::SUCCESS)
+}
+
+/// Asks for the confirmation phrase on stdin.
+fn prompt_confirmation(database: &str) -> anyhow::Result<String> {
+    eprint!(
+        "This drops every table in {database}. Type '{}' to continue: ",
+        guard::confirmation_phrase(database)
+    );
+    std::io::stderr().flush()?;
+    let mut line = String::new();
+    std::io::stdin()
+        .lock()
+        .read_line(&mut line)
+        .context("Failed to read confirmation")?;
+    Ok(line)
 }
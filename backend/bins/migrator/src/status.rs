@@ -0,0 +1,93 @@
+//! Migration status listing.
+
+use std::collections::HashMap;
+use std::fmt;
+
+use anyhow::Context;
+use chrono::{DateTime, Utc};
+use sea_orm_migration::{MigrationStatus, MigratorTrait, sea_orm::DatabaseConnection};
+use serde::Serialize;
+use zeltra_db::migration::Migrator;
+
+/// One migration and whether it has been applied.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct MigrationState {
+    /// Migration name.
+    pub name: String,
+    /// `applied` or `pending`.
+    pub status: &'static str,
+    /// When it was applied.
+    pub applied_at: Option<DateTime<Utc>>,
+}
+
+impl fmt::Display for MigrationState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.applied_at {
+            Some(at) => write!(f, "{:<8} {} ({})", self.status, self.name, at.to_rfc3339()),
+            None => write!(f, "{:<8} {}", self.status, self.name),
+        }
+    }
+}
+
+/// Lists every migration in order with its status.
+///
+/// # Errors
+///
+/// Returns an error if the migrations table cannot be read, or lists a
+/// migration this binary does not know.
+pub async fn load(db: &DatabaseConnection) -> anyhow::Result<Vec<MigrationState>> {
+    let migrations = Migrator::get_migration_with_status(db)
+        .await
+        .context("Failed to read migration status")?;
+    let applied_at: HashMap<String, i64> = Migrator::get_migration_models(db)
+        .await
+        .context("Failed to read applied migrations")?
+        .into_iter()
+        .map(|m| (m.version, m.applied_at))
+        .collect();
+
+    Ok(migrations
+        .iter()
+        .map(|m| {
+            let applied = matches!(m.status(), MigrationStatus::Applied);
+            state(m.name(), applied, applied_at.get(m.name()).copied())
+        })
+        .collect())
+}
+
+/// Builds the listing entry for one migration.
+fn state(name: &str, applied: bool, applied_at: Option<i64>) -> MigrationState {
+    MigrationState {
+        name: name.to_string(),
+        status: if applied { "applied" } else { "pending" },
+        applied_at: applied_at
+            .filter(|_| applied)
+            .and_then(|secs| DateTime::from_timestamp(secs, 0)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_state_json() {
+        let applied = state("m20250101_000001_init", true, Some(1_735_689_600));
+        assert_eq!(
+            serde_json::to_value(&applied).unwrap(),
+            serde_json::json!({
+                "name": "m20250101_000001_init",
+                "status": "applied",
+                "applied_at": "2025-01-01T00:00:00Z"
+            })
+        );
+        assert_eq!(
+            applied.to_string(),
+            "applied  m20250101_000001_init (2025-01-01T00:00:00+00:00)"
+        );
+
+        let pending = state("m20250102_000001_next", false, None);
+        assert_eq!(pending.applied_at, None);
+        assert_eq!(serde_json::to_value(&pending).unwrap()["status"], "pending");
+    }
+}
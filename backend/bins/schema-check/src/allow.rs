@@ -0,0 +1,113 @@
+//! Allow-list of intentional differences from generated entities.
+
+use std::path::Path;
+
+use anyhow::Context;
+use serde::Deserialize;
+
+/// Parsed `allow.toml`.
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct AllowList {
+    /// Entries by file.
+    #[serde(default, rename = "file")]
+    pub files: Vec<AllowedFile>,
+}
+
+/// Allowed differences for one entity file.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct AllowedFile {
+    /// File name within the entities directory, e.g. `users.rs`.
+    pub name: String,
+    /// Why the file differs from the generated one.
+    pub reason: String,
+    /// Skip the file entirely.
+    #[serde(default)]
+    pub skip: bool,
+    /// Lines, compared trimmed, that may appear on either side.
+    #[serde(default)]
+    pub ignore_lines: Vec<String>,
+}
+
+impl AllowList {
+    /// Reads an allow-list, or an empty one when the file does not exist.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read or parsed, or if an entry
+    /// has no reason.
+    pub fn load(path: &Path) -> anyhow::Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let text = std::fs::read_to_string(path)
+            .with_context(|| format!("failed to read {}", path.display()))?;
+        let list: Self =
+            toml::from_str(&text).with_context(|| format!("failed to parse {}", path.display()))?;
+
+        if let Some(entry) = list.files.iter().find(|f| f.reason.trim().is_empty()) {
+            anyhow::bail!("{}: entry for {} has no reason", path.display(), entry.name);
+        }
+        Ok(list)
+    }
+
+    /// Entry for a file, if any.
+    #[must_use]
+    pub fn get(&self, name: &str) -> Option<&AllowedFile> {
+        self.files.iter().find(|f| f.name == name)
+    }
+
+    /// Whether a file is exempt from the check.
+    #[must_use]
+    pub fn skips(&self, name: &str) -> bool {
+        self.get(name).is_some_and(|f| f.skip)
+    }
+
+    /// Lines that may differ in a file.
+    #[must_use]
+    pub fn ignored_lines(&self, name: &str) -> &[String] {
+        self.get(name).map_or(&[], |f| f.ignore_lines.as_slice())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parses_entries() {
+        let list: AllowList = toml::from_str(
+            r##"
+[[file]]
+name = "mod.rs"
+reason = "hand maintained"
+skip = true
+
+[[file]]
+name = "users.rs"
+reason = "custom derive"
+ignore_lines = ["#[derive(Hash)]"]
+"##,
+        )
+        .unwrap();
+
+        assert!(list.skips("mod.rs"));
+        assert!(!list.skips("users.rs"));
+        assert_eq!(list.ignored_lines("users.rs"), ["#[derive(Hash)]"]);
+        assert!(list.ignored_lines("sessions.rs").is_empty());
+    }
+
+    #[test]
+    fn test_rejects_unknown_keys() {
+        let result: Result<AllowList, _> = toml::from_str(
+            r#"
+[[file]]
+name = "mod.rs"
+reason = "x"
+skipped = true
+"#,
+        );
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,254 @@
+//! Comparison of generated and checked-in entity files.
+
+use std::{
+    collections::BTreeSet,
+    fmt, io,
+    path::{Path, PathBuf},
+};
+
+use similar::TextDiff;
+
+use crate::allow::AllowList;
+
+/// How a checked-in entity file differs from the generated one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Difference {
+    /// Generated but not checked in, usually a new table.
+    Missing,
+    /// Checked in but no longer generated, usually a dropped table.
+    Stale,
+    /// Present on both sides with different code; holds a unified diff.
+    Changed(String),
+}
+
+/// A difference in one file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileDifference {
+    /// File name within the entities directory.
+    pub name: String,
+    /// What differs.
+    pub difference: Difference,
+}
+
+impl fmt::Display for FileDifference {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.difference {
+            Difference::Missing => write!(f, "{}: generated but not checked in", self.name),
+            Difference::Stale => write!(f, "{}: checked in but not generated", self.name),
+            Difference::Changed(diff) => write!(f, "{}: differs\n{diff}", self.name),
+        }
+    }
+}
+
+/// The lines of `source` that are compared: trimmed of trailing whitespace,
+/// without blank lines, comment lines or `ignore`d lines.
+#[must_use]
+pub fn significant_lines(source: &str, ignore: &[String]) -> String {
+    let mut out = String::with_capacity(source.len());
+    for line in source.lines().map(str::trim_end) {
+        let trimmed = line.trim_start();
+        if trimmed.is_empty()
+            || trimmed.starts_with("//")
+            || ignore.iter().any(|i| i.trim() == trimmed)
+        {
+            continue;
+        }
+        out.push_str(line);
+        out.push('\n');
+    }
+    out
+}
+
+/// Compares one file, returning a unified diff from the checked-in version
+/// to the generated one when their significant lines differ.
+#[must_use]
+pub fn compare_source(
+    name: &str,
+    generated: &str,
+    checked_in: &str,
+    ignore: &[String],
+) -> Option<String> {
+    let generated = significant_lines(generated, ignore);
+    let checked_in = significant_lines(checked_in, ignore);
+    if generated == checked_in {
+        return None;
+    }
+
+    Some(
+        TextDiff::from_lines(&checked_in, &generated)
+            .unified_diff()
+            .context_radius(3)
+            .header(&format!("checked-in/{name}"), &format!("generated/{name}"))
+            .to_string(),
+    )
+}
+
+/// Compares every `.rs` file in the two directories, skipping allow-listed
+/// files. Results are ordered by file name.
+///
+/// # Errors
+///
+/// Returns an error if a directory or file cannot be read.
+pub fn compare_dirs(
+    generated: &Path,
+    checked_in: &Path,
+    allow: &AllowList,
+) -> io::Result<Vec<FileDifference>> {
+    let generated_files = rust_files(generated)?;
+    let checked_in_files = rust_files(checked_in)?;
+
+    let mut differences = Vec::new();
+    for name in generated_files.union(&checked_in_files) {
+        if allow.skips(name) {
+            continue;
+        }
+        let difference = match (
+            generated_files.contains(name),
+            checked_in_files.contains(name),
+        ) {
+            (true, false) => Difference::Missing,
+            (false, true) => Difference::Stale,
+            _ => {
+                let generated_source = std::fs::read_to_string(generated.join(name))?;
+                let checked_in_source = std::fs::read_to_string(checked_in.join(name))?;
+                match compare_source(
+                    name,
+                    &generated_source,
+                    &checked_in_source,
+                    allow.ignored_lines(name),
+                ) {
+                    Some(diff) => Difference::Changed(diff),
+                    None => continue,
+                }
+            }
+        };
+        differences.push(FileDifference {
+            name: name.clone(),
+            difference,
+        });
+    }
+    Ok(differences)
+}
+
+/// Copies generated files over the checked-in ones, leaving skipped files
+/// alone, and returns the paths written.
+///
+/// Stale files are reported but not deleted.
+///
+/// # Errors
+///
+/// Returns an error if a file cannot be copied.
+pub fn write_generated(
+    differences: &[FileDifference],
+    generated: &Path,
+    checked_in: &Path,
+) -> io::Result<Vec<PathBuf>> {
+    let mut written = Vec::new();
+    for file in differences {
+        if file.difference == Difference::Stale {
+            continue;
+        }
+        let target = checked_in.join(&file.name);
+        std::fs::copy(generated.join(&file.name), &target)?;
+        written.push(target);
+    }
+    Ok(written)
+}
+
+/// Names of the `.rs` files directly inside `dir`.
+fn rust_files(dir: &Path) -> io::Result<BTreeSet<String>> {
+    let mut names = BTreeSet::new();
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.extension().is_some_and(|ext| ext == "rs")
+            && let Some(name) = path.file_name().and_then(|n| n.to_str())
+        {
+            names.insert(name.to_string());
+        }
+    }
+    Ok(names)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GENERATED: &str = "\
+//! `SeaORM` Entity, @generated by sea-orm-codegen 1.1.19
+
+use sea_orm::entity::prelude::*;
+
+pub struct Model {
+    pub id: Uuid,
+    pub memo: Option<String>,
+}
+";
+
+    #[test]
+    fn test_comments_and_blank_lines_are_not_compared() {
+        let checked_in = "\
+//! `SeaORM` Entity for the example table.
+use sea_orm::entity::prelude::*;
+pub struct Model {
+    /// Primary key.
+    pub id: Uuid,
+    pub memo: Option<String>,
+}
+";
+        assert_eq!(compare_source("x.rs", GENERATED, checked_in, &[]), None);
+    }
+
+    #[test]
+    fn test_nullability_drift_is_reported() {
+        let checked_in = GENERATED.replace("Option<String>", "String");
+        let diff = compare_source("x.rs", GENERATED, &checked_in, &[]).unwrap();
+
+        assert!(diff.contains("--- checked-in/x.rs"));
+        assert!(diff.contains("-    pub memo: String,"));
+        assert!(diff.contains("+    pub memo: Option<String>,"));
+    }
+
+    #[test]
+    fn test_ignored_lines_may_differ() {
+        let checked_in = GENERATED.replace(
+            "pub struct Model {",
+            "#[allow(clippy::struct_excessive_bools)]\npub struct Model {",
+        );
+        let ignore = vec!["#[allow(clippy::struct_excessive_bools)]".to_string()];
+
+        assert!(compare_source("x.rs", GENERATED, &checked_in, &[]).is_some());
+        assert_eq!(
+            compare_source("x.rs", GENERATED, &checked_in, &ignore),
+            None
+        );
+    }
+
+    #[test]
+    fn test_compare_dirs_reports_missing_and_stale_files() {
+        let generated = tempfile::tempdir().unwrap();
+        let checked_in = tempfile::tempdir().unwrap();
+        std::fs::write(generated.path().join("users.rs"), GENERATED).unwrap();
+        std::fs::write(generated.path().join("new_table.rs"), GENERATED).unwrap();
+        std::fs::write(checked_in.path().join("users.rs"), GENERATED).unwrap();
+        std::fs::write(checked_in.path().join("dropped.rs"), GENERATED).unwrap();
+        std::fs::write(checked_in.path().join("mod.rs"), "pub mod users;\n").unwrap();
+
+        let allow: AllowList =
+            toml::from_str("[[file]]\nname = \"mod.rs\"\nreason = \"x\"\nskip = true\n").unwrap();
+        let differences = compare_dirs(generated.path(), checked_in.path(), &allow).unwrap();
+
+        assert_eq!(
+            differences,
+            [
+                FileDifference {
+                    name: "dropped.rs".to_string(),
+                    difference: Difference::Stale,
+                },
+                FileDifference {
+                    name: "new_table.rs".to_string(),
+                    difference: Difference::Missing,
+                },
+            ]
+        );
+    }
+}
@@ -0,0 +1,85 @@
+//! Entity generation from a live database.
+//!
+//! Mirrors `sea-orm-cli generate entity --with-serde both`, which produced
+//! the checked-in entities.
+
+use std::{path::Path, process::Command};
+
+use anyhow::{Context, bail};
+use sea_orm_codegen::{
+    DateTimeCrate, EntityTransformer, EntityWriterContext, WithPrelude, WithSerde,
+};
+use sea_schema::postgres::discovery::SchemaDiscovery;
+use sqlx::postgres::PgPoolOptions;
+
+/// Schema holding the application tables.
+const SCHEMA: &str = "public";
+
+/// Tables that are not entities.
+const IGNORED_TABLES: &[&str] = &["seaql_migrations"];
+
+/// Generates entities for every table in the database into `out_dir` and
+/// formats them with rustfmt.
+///
+/// # Errors
+///
+/// Returns an error if the schema cannot be read, code generation fails or
+/// the files cannot be written or formatted.
+pub async fn generate_entities(database_url: &str, out_dir: &Path) -> anyhow::Result<()> {
+    let pool = PgPoolOptions::new()
+        .max_connections(1)
+        .connect(database_url)
+        .await
+        .context("failed to connect for schema discovery")?;
+    let schema = SchemaDiscovery::new(pool, SCHEMA)
+        .discover()
+        .await
+        .context("failed to discover schema")?;
+
+    let tables = schema
+        .tables
+        .into_iter()
+        .filter(|table| !IGNORED_TABLES.contains(&table.info.name.as_str()))
+        .map(|table| table.write())
+        .collect();
+
+    let context = EntityWriterContext::new(
+        false,
+        false,
+        WithPrelude::All,
+        WithSerde::Both,
+        false,
+        DateTimeCrate::Chrono,
+        None,
+        false,
+        false,
+        false,
+        vec![],
+        vec![],
+        vec![],
+        vec![],
+        false,
+        true,
+    );
+    let output = EntityTransformer::transform(tables)
+        .context("failed to transform schema into entities")?
+        .generate(&context);
+
+    let mut paths = Vec::with_capacity(output.files.len());
+    for file in output.files {
+        let path = out_dir.join(&file.name);
+        std::fs::write(&path, file.content)
+            .with_context(|| format!("failed to write {}", path.display()))?;
+        paths.push(path);
+    }
+
+    let status = Command::new("rustfmt")
+        .args(["--edition", "2024"])
+        .args(&paths)
+        .status()
+        .context("failed to run rustfmt")?;
+    if !status.success() {
+        bail!("rustfmt failed on generated entities");
+    }
+    Ok(())
+}
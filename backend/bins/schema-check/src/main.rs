@@ -0,0 +1,146 @@
+//! Entity drift check for Zeltra.
+//!
+//! Applies pending migrations to the database at `DATABASE_URL`, generates
+//! `SeaORM` entities from it into a temporary directory and compares them
+//! with the checked-in entities in `crates/db/src/entities`. Differences not
+//! covered by `allow.toml` are printed as unified diffs and the process exits
+//! with status 1.
+//!
+//! Point it at a scratch database: migrations are applied to it.
+//!
+//! Usage: cargo run --bin schema-check -- [--write] [--entities <dir>]
+//!        [--allow <file>]
+
+mod allow;
+mod diff;
+mod generate;
+
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use anyhow::{Context, bail};
+use sea_orm_migration::MigratorTrait;
+use zeltra_db::migration::Migrator;
+
+use crate::allow::AllowList;
+use crate::diff::{compare_dirs, write_generated};
+use crate::generate::generate_entities;
+
+const USAGE: &str = "\
+Usage: schema-check [OPTIONS]
+
+Options:
+  --write            Overwrite checked-in entities with the generated ones
+  --entities <dir>   Checked-in entities [default: crates/db/src/entities]
+  --allow <file>     Allow-list of intentional differences [default: bins/schema-check/allow.toml]
+  -h, --help         Print this help";
+
+/// Command-line options.
+#[derive(Debug)]
+struct Options {
+    write: bool,
+    entities: PathBuf,
+    allow: PathBuf,
+}
+
+impl Options {
+    /// Parses options from the process arguments, exiting on `--help`.
+    fn from_args() -> anyhow::Result<Self> {
+        let manifest_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        let mut options = Self {
+            write: false,
+            entities: manifest_dir.join("../../crates/db/src/entities"),
+            allow: manifest_dir.join("allow.toml"),
+        };
+
+        let mut args = std::env::args().skip(1);
+        while let Some(arg) = args.next() {
+            let (flag, inline) = match arg.split_once('=') {
+                Some((flag, value)) => (flag.to_string(), Some(value.to_string())),
+                None => (arg, None),
+            };
+            match flag.as_str() {
+                "-h" | "--help" => {
+                    println!("{USAGE}");
+                    std::process::exit(0);
+                }
+                "--write" => {
+                    options.write = true;
+                    continue;
+                }
+                _ => {}
+            }
+            let value = match inline {
+                Some(value) => value,
+                None => args
+                    .next()
+                    .with_context(|| format!("missing value for {flag}"))?,
+            };
+
+            match flag.as_str() {
+                "--entities" => options.entities = PathBuf::from(value),
+                "--allow" => options.allow = PathBuf::from(value),
+                other => bail!("unknown option '{other}'\n\n{USAGE}"),
+            }
+        }
+
+        Ok(options)
+    }
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<ExitCode> {
+    dotenvy::dotenv().ok();
+
+    let options = Options::from_args()?;
+    let allow = AllowList::load(&options.allow)?;
+
+    let database_url =
+        std::env::var("DATABASE_URL").context("DATABASE_URL must be set in environment")?;
+
+    println!("Applying migrations...");
+    let db = zeltra_db::connect(&database_url)
+        .await
+        .context("Failed to connect to database")?;
+    Migrator::up(&db, None)
+        .await
+        .context("Failed to apply migrations")?;
+
+    println!("Generating entities...");
+    let generated = tempfile::tempdir().context("Failed to create temporary directory")?;
+    generate_entities(&database_url, generated.path()).await?;
+
+    let differences = compare_dirs(generated.path(), &options.entities, &allow)
+        .context("Failed to compare entities")?;
+    if differences.is_empty() {
+        println!("Entities match the schema.");
+        return Ok(ExitCode::SUCCESS);
+    }
+
+    if options.write {
+        for path in write_generated(&differences, generated.path(), &options.entities)
+            .context("Failed to write entities")?
+        {
+            println!("  wrote {}", path.display());
+        }
+        for file in differences
+            .iter()
+            .filter(|f| f.difference == diff::Difference::Stale)
+        {
+            println!("  {file}; remove it if the table was dropped");
+        }
+        println!("Entities regenerated. Update entities/mod.rs for added or removed tables.");
+        return Ok(ExitCode::SUCCESS);
+    }
+
+    for file in &differences {
+        eprintln!("{file}");
+    }
+    eprintln!(
+        "\n{} entity file(s) differ from the schema. Fix the entity or migration, \
+         run with --write to regenerate, or record an intentional difference in {}.",
+        differences.len(),
+        options.allow.display()
+    );
+    Ok(ExitCode::FAILURE)
+}
@@ -0,0 +1,565 @@
+//! Ledger data for the `demo` and `load-test` scenarios.
+//!
+//! Seeds a fiscal year, a chart of accounts, posted transactions with
+//! dimensions and (for `demo`) a budget and pending approvals. Everything is
+//! created through the repositories so triggers and validation apply as in
+//! production. Seeded transactions carry a reference-number prefix, which is
+//! how re-runs find what an earlier run already created.
+
+use std::collections::HashMap;
+
+use anyhow::{Context, Result};
+use chrono::{Datelike, NaiveDate, Utc};
+use rust_decimal::Decimal;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, PaginatorTrait, QueryFilter,
+    Set,
+};
+use uuid::Uuid;
+use zeltra_db::{
+    entities::{
+        chart_of_accounts, dimension_types, dimension_values, fiscal_periods, organization_users,
+        sea_orm_active_enums::{BudgetType, TransactionType, UserRole},
+        transactions,
+    },
+    repositories::{
+        AccountRepository, BudgetError, BudgetRepository, ChartTemplate, CreateAccountInput,
+        CreateBudgetInput, CreateBudgetLineInput, CreateFiscalYearInput, CreateLedgerEntryInput,
+        CreateTransactionInput, FiscalRepository, FiscalYearWithPeriods, TransactionRepository,
+        WorkflowRepository,
+    },
+};
+
+/// Reference prefix of transactions seeded by the `demo` scenario.
+const DEMO_REFERENCE_PREFIX: &str = "SEED-DEMO-";
+/// Reference prefix of transactions seeded by the `load-test` scenario.
+const LOAD_TEST_REFERENCE_PREFIX: &str = "SEED-LOAD-";
+/// Number of posted transactions in the `demo` scenario.
+const DEMO_TRANSACTIONS: usize = 200;
+/// Number of transactions left pending approval in the `demo` scenario.
+const DEMO_PENDING: usize = 2;
+/// Transactions created per repository call.
+const BATCH_SIZE: usize = 250;
+/// Functional currency of seeded organizations.
+const CURRENCY: &str = "USD";
+/// Chart of accounts of seeded organizations.
+const CHART_OF_ACCOUNTS: ChartTemplate = ChartTemplate::Service;
+
+/// Expense accounts that operating expenses rotate through.
+const EXPENSE_ACCOUNTS: &[&str] = &["6000", "6100", "6200", "6300"];
+
+/// Everything transactions are generated against.
+struct Ledger {
+    org_id: Uuid,
+    user_id: Uuid,
+    fiscal_year: FiscalYearWithPeriods,
+    accounts: HashMap<&'static str, Uuid>,
+    departments: Vec<Uuid>,
+    projects: Vec<Uuid>,
+}
+
+impl Ledger {
+    fn account(&self, code: &str) -> Uuid {
+        self.accounts[code]
+    }
+
+    /// Regular (non-adjustment) periods, in order.
+    fn periods(&self) -> Vec<&fiscal_periods::Model> {
+        self.fiscal_year
+            .periods
+            .iter()
+            .filter(|period| !period.is_adjustment_period)
+            .collect()
+    }
+}
+
+/// Seeds the `demo` scenario.
+pub async fn seed_demo(db: &DatabaseConnection, org_id: Uuid, user_id: Uuid) -> Result<()> {
+    let ledger = prepare_ledger(db, org_id, user_id).await?;
+
+    println!("Seeding demo transactions...");
+    let existing = count_seeded(db, org_id, DEMO_REFERENCE_PREFIX).await?;
+    if existing > 0 {
+        println!("  {existing} demo transactions already exist, skipping...");
+    } else {
+        let mut inputs = vec![opening_balance(&ledger, DEMO_REFERENCE_PREFIX)];
+        inputs.extend(
+            (1..DEMO_TRANSACTIONS)
+                .map(|i| sample_transaction(&ledger, DEMO_REFERENCE_PREFIX, i, DEMO_TRANSACTIONS)),
+        );
+        let posted = create_and_post(db, &ledger, inputs).await?;
+        println!("  Posted {posted} transactions");
+
+        let pending: Vec<_> = (0..DEMO_PENDING)
+            .map(|i| pending_transaction(&ledger, i))
+            .collect();
+        let created = TransactionRepository::new(db.clone())
+            .create_transactions(pending)
+            .await?;
+        let workflow = WorkflowRepository::new(db.clone());
+        for created in &created {
+            workflow
+                .submit_transaction(org_id.into(), created.transaction.id.into(), user_id)
+                .await?;
+        }
+        println!("  Submitted {} transactions for approval", created.len());
+    }
+
+    println!("Seeding demo budget...");
+    seed_budget(db, &ledger).await?;
+
+    Ok(())
+}
+
+/// Seeds the `load-test` scenario with `target` posted transactions.
+///
+/// Transactions from earlier runs count towards the target.
+pub async fn seed_load_test(
+    db: &DatabaseConnection,
+    org_id: Uuid,
+    user_id: Uuid,
+    target: usize,
+) -> Result<()> {
+    let ledger = prepare_ledger(db, org_id, user_id).await?;
+
+    println!("Seeding load-test transactions...");
+    let existing = usize::try_from(count_seeded(db, org_id, LOAD_TEST_REFERENCE_PREFIX).await?)?;
+    if existing >= target {
+        println!("  {existing} load-test transactions already exist, skipping...");
+        return Ok(());
+    }
+
+    let mut inputs = Vec::with_capacity(target - existing + 1);
+    if existing == 0 {
+        inputs.push(opening_balance(&ledger, LOAD_TEST_REFERENCE_PREFIX));
+    }
+    let first = existing.max(1);
+    inputs.extend(
+        (first..target).map(|i| sample_transaction(&ledger, LOAD_TEST_REFERENCE_PREFIX, i, target)),
+    );
+
+    let posted = create_and_post(db, &ledger, inputs).await?;
+    println!("  Posted {posted} transactions ({target} in total)");
+
+    Ok(())
+}
+
+/// Seeds membership, fiscal year and chart of accounts, and loads dimensions.
+async fn prepare_ledger(db: &DatabaseConnection, org_id: Uuid, user_id: Uuid) -> Result<Ledger> {
+    println!("Seeding owner membership...");
+    seed_membership(db, org_id, user_id).await?;
+
+    println!("Seeding fiscal year...");
+    let fiscal_year = seed_fiscal_year(db, org_id, Utc::now().year()).await?;
+
+    println!("Seeding chart of accounts...");
+    let accounts = seed_chart_of_accounts(db, org_id).await?;
+
+    Ok(Ledger {
+        org_id,
+        user_id,
+        fiscal_year,
+        accounts,
+        departments: dimension_value_ids(db, org_id, "DEPARTMENT").await?,
+        projects: dimension_value_ids(db, org_id, "PROJECT").await?,
+    })
+}
+
+/// Makes the seed user an owner of the organization.
+async fn seed_membership(db: &DatabaseConnection, org_id: Uuid, user_id: Uuid) -> Result<()> {
+    let existing = organization_users::Entity::find_by_id((user_id, org_id))
+        .one(db)
+        .await?;
+    if existing.is_some() {
+        println!("  Membership already exists, skipping...");
+        return Ok(());
+    }
+
+    organization_users::ActiveModel {
+        user_id: Set(user_id),
+        organization_id: Set(org_id),
+        role: Set(UserRole::Owner),
+        approval_limit: Set(None),
+        created_at: Set(Utc::now().into()),
+        updated_at: Set(Utc::now().into()),
+    }
+    .insert(db)
+    .await?;
+    println!("  Added test user as owner");
+
+    Ok(())
+}
+
+/// Returns the fiscal year covering January 1st of `year`, creating a
+/// calendar fiscal year with 12 monthly periods if none exists.
+async fn seed_fiscal_year(
+    db: &DatabaseConnection,
+    org_id: Uuid,
+    year: i32,
+) -> Result<FiscalYearWithPeriods> {
+    let repo = FiscalRepository::new(db.clone());
+    let start_date = NaiveDate::from_ymd_opt(year, 1, 1).context("invalid fiscal year start")?;
+    let end_date = NaiveDate::from_ymd_opt(year, 12, 31).context("invalid fiscal year end")?;
+
+    let existing = repo
+        .list_fiscal_years(org_id)
+        .await?
+        .into_iter()
+        .find(|fy| {
+            fy.fiscal_year.start_date <= start_date && fy.fiscal_year.end_date >= start_date
+        });
+    if let Some(fiscal_year) = existing {
+        println!(
+            "  Fiscal year {} already exists, skipping...",
+            fiscal_year.fiscal_year.name
+        );
+        return Ok(fiscal_year);
+    }
+
+    let fiscal_year = repo
+        .create_fiscal_year(CreateFiscalYearInput {
+            organization_id: org_id,
+            name: format!("FY {year}"),
+            start_date,
+            end_date,
+        })
+        .await?;
+    println!(
+        "  Created {} with {} periods",
+        fiscal_year.fiscal_year.name,
+        fiscal_year.periods.len()
+    );
+
+    Ok(fiscal_year)
+}
+
+/// Creates any missing accounts of the chart of accounts template.
+async fn seed_chart_of_accounts(
+    db: &DatabaseConnection,
+    org_id: Uuid,
+) -> Result<HashMap<&'static str, Uuid>> {
+    let repo = AccountRepository::new(db.clone());
+    let existing: HashMap<String, Uuid> = chart_of_accounts::Entity::find()
+        .filter(chart_of_accounts::Column::OrganizationId.eq(org_id))
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|account| (account.code, account.id))
+        .collect();
+
+    let mut accounts = HashMap::with_capacity(CHART_OF_ACCOUNTS.accounts().len());
+    let mut inserted = 0;
+    for template in CHART_OF_ACCOUNTS.accounts() {
+        let id = if let Some(id) = existing.get(template.code) {
+            *id
+        } else {
+            inserted += 1;
+            repo.create_account(CreateAccountInput {
+                organization_id: org_id,
+                code: template.code.to_string(),
+                name: template.name.to_string(),
+                description: None,
+                account_type: template.account_type.clone(),
+                account_subtype: template.subtype.clone(),
+                parent_id: None,
+                currency: CURRENCY.to_string(),
+                is_active: true,
+                allow_direct_posting: true,
+                is_bank_account: template.is_bank,
+                bank_account_number: None,
+            })
+            .await?
+            .id
+        };
+        accounts.insert(template.code, id);
+    }
+    println!("  Inserted {inserted} accounts");
+
+    Ok(accounts)
+}
+
+/// Returns the IDs of the active values of a dimension type.
+async fn dimension_value_ids(
+    db: &DatabaseConnection,
+    org_id: Uuid,
+    type_code: &str,
+) -> Result<Vec<Uuid>> {
+    let Some(dimension_type) = dimension_types::Entity::find()
+        .filter(dimension_types::Column::OrganizationId.eq(org_id))
+        .filter(dimension_types::Column::Code.eq(type_code))
+        .one(db)
+        .await?
+    else {
+        return Ok(Vec::new());
+    };
+
+    Ok(dimension_values::Entity::find()
+        .filter(dimension_values::Column::DimensionTypeId.eq(dimension_type.id))
+        .filter(dimension_values::Column::IsActive.eq(true))
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|value| value.id)
+        .collect())
+}
+
+/// Counts transactions whose reference starts with `prefix`.
+async fn count_seeded(db: &DatabaseConnection, org_id: Uuid, prefix: &str) -> Result<u64> {
+    Ok(transactions::Entity::find()
+        .filter(transactions::Column::OrganizationId.eq(org_id))
+        .filter(transactions::Column::ReferenceNumber.starts_with(prefix))
+        .count(db)
+        .await?)
+}
+
+/// Creates transactions in batches, then submits, approves and posts them.
+async fn create_and_post(
+    db: &DatabaseConnection,
+    ledger: &Ledger,
+    inputs: Vec<CreateTransactionInput>,
+) -> Result<usize> {
+    let transactions = TransactionRepository::new(db.clone());
+    let workflow = WorkflowRepository::new(db.clone());
+    let org_id = ledger.org_id.into();
+    let total = inputs.len();
+    let mut posted = 0;
+
+    let mut inputs = inputs.into_iter().peekable();
+    while inputs.peek().is_some() {
+        let batch: Vec<_> = inputs.by_ref().take(BATCH_SIZE).collect();
+        let created = transactions.create_transactions(batch).await?;
+
+        for created in created {
+            let id = created.transaction.id.into();
+            workflow
+                .submit_transaction(org_id, id, ledger.user_id)
+                .await?;
+            workflow
+                .approve_transaction(org_id, id, ledger.user_id, None)
+                .await?;
+            workflow
+                .post_transaction(org_id, id, ledger.user_id)
+                .await?;
+            posted += 1;
+        }
+        println!("  {posted}/{total}");
+    }
+
+    Ok(posted)
+}
+
+/// Initial capital so the bank account starts in credit.
+fn opening_balance(ledger: &Ledger, prefix: &str) -> CreateTransactionInput {
+    let amount = Decimal::new(250_000, 0);
+    let date = ledger.periods()[0].start_date;
+
+    CreateTransactionInput {
+        organization_id: ledger.org_id,
+        transaction_type: TransactionType::OpeningBalance,
+        transaction_date: date,
+        description: "Opening capital contribution".to_string(),
+        reference_number: Some(format!("{prefix}{:06}", 0)),
+        memo: None,
+        entries: vec![
+            entry(ledger.account("1000"), amount, Decimal::ZERO, Vec::new()),
+            entry(ledger.account("3000"), Decimal::ZERO, amount, Vec::new()),
+        ],
+        created_by: ledger.user_id,
+    }
+}
+
+/// Deterministic sample transaction `index` of `total`, spread evenly over
+/// the fiscal year's periods.
+///
+/// Rotates through invoices, customer payments, operating expenses tagged
+/// with a department and project, and supplier bills.
+fn sample_transaction(
+    ledger: &Ledger,
+    prefix: &str,
+    index: usize,
+    total: usize,
+) -> CreateTransactionInput {
+    let periods = ledger.periods();
+    let period = periods[index * periods.len() / total.max(1)];
+    let period_days = (period.end_date - period.start_date).num_days() + 1;
+    let day = i64::try_from(index).unwrap_or_default() % period_days;
+    let date = period.start_date + chrono::Duration::days(day);
+
+    // 50.00 to 4,999.99, varying with the index
+    let cents = i64::try_from(index.wrapping_mul(7919) % 495_000).unwrap_or_default() + 5_000;
+    let amount = Decimal::new(cents, 2);
+
+    let tags = |offset: usize| -> Vec<Uuid> {
+        let mut tags = Vec::new();
+        if !ledger.departments.is_empty() {
+            tags.push(ledger.departments[(index + offset) % ledger.departments.len()]);
+        }
+        if !ledger.projects.is_empty() {
+            tags.push(ledger.projects[(index + offset) % ledger.projects.len()]);
+        }
+        tags
+    };
+
+    let (transaction_type, description, debit_account, credit_account, debit_tags) = match index % 4
+    {
+        0 => (
+            TransactionType::Invoice,
+            format!("Consulting invoice #{index}"),
+            "1100",
+            if index.is_multiple_of(8) {
+                "4100"
+            } else {
+                "4000"
+            },
+            Vec::new(),
+        ),
+        1 => (
+            TransactionType::Payment,
+            format!("Customer payment #{index}"),
+            "1000",
+            "1100",
+            Vec::new(),
+        ),
+        2 => {
+            let account = EXPENSE_ACCOUNTS[(index / 4) % EXPENSE_ACCOUNTS.len()];
+            (
+                TransactionType::Expense,
+                format!("Operating expense #{index}"),
+                account,
+                "1000",
+                tags(0),
+            )
+        }
+        _ => (
+            TransactionType::Bill,
+            format!("Supplier bill #{index}"),
+            "5000",
+            "2000",
+            tags(1),
+        ),
+    };
+
+    CreateTransactionInput {
+        organization_id: ledger.org_id,
+        transaction_type,
+        transaction_date: date,
+        description,
+        reference_number: Some(format!("{prefix}{index:06}")),
+        memo: None,
+        entries: vec![
+            entry(
+                ledger.account(debit_account),
+                amount,
+                Decimal::ZERO,
+                debit_tags,
+            ),
+            entry(
+                ledger.account(credit_account),
+                Decimal::ZERO,
+                amount,
+                Vec::new(),
+            ),
+        ],
+        created_by: ledger.user_id,
+    }
+}
+
+/// Expense awaiting approval in the current month's period.
+fn pending_transaction(ledger: &Ledger, index: usize) -> CreateTransactionInput {
+    let today = Utc::now().date_naive();
+    let periods = ledger.periods();
+    let period = periods
+        .iter()
+        .find(|period| period.start_date <= today && today <= period.end_date)
+        .unwrap_or(&periods[periods.len() - 1]);
+    let amount = Decimal::new(7_500 + i64::try_from(index).unwrap_or_default() * 2_500, 0);
+
+    CreateTransactionInput {
+        organization_id: ledger.org_id,
+        transaction_type: TransactionType::Expense,
+        transaction_date: period.start_date,
+        description: format!("Equipment purchase awaiting approval #{}", index + 1),
+        reference_number: Some(format!("{DEMO_REFERENCE_PREFIX}PENDING-{:02}", index + 1)),
+        memo: None,
+        entries: vec![
+            entry(ledger.account("1500"), amount, Decimal::ZERO, Vec::new()),
+            entry(ledger.account("2000"), Decimal::ZERO, amount, Vec::new()),
+        ],
+        created_by: ledger.user_id,
+    }
+}
+
+fn entry(
+    account_id: Uuid,
+    debit: Decimal,
+    credit: Decimal,
+    dimensions: Vec<Uuid>,
+) -> CreateLedgerEntryInput {
+    let amount = debit + credit;
+    CreateLedgerEntryInput {
+        account_id,
+        source_currency: CURRENCY.to_string(),
+        source_amount: amount,
+        exchange_rate: Decimal::ONE,
+        functional_currency: CURRENCY.to_string(),
+        functional_amount: amount,
+        debit,
+        credit,
+        memo: None,
+        dimensions,
+        rate_override: None,
+        event_at: None,
+    }
+}
+
+/// Seeds an annual operating budget with a line per expense account and period.
+async fn seed_budget(db: &DatabaseConnection, ledger: &Ledger) -> Result<()> {
+    let repo = BudgetRepository::new(db.clone());
+    let name = format!("{} Operating Budget", ledger.fiscal_year.fiscal_year.name);
+
+    let budget = match repo
+        .create_budget(CreateBudgetInput {
+            organization_id: ledger.org_id,
+            fiscal_year_id: ledger.fiscal_year.fiscal_year.id,
+            name: name.clone(),
+            description: Some("Seeded operating expense budget".to_string()),
+            budget_type: BudgetType::Annual,
+            currency: CURRENCY.to_string(),
+            created_by: ledger.user_id,
+        })
+        .await
+    {
+        Ok(budget) => budget,
+        Err(BudgetError::DuplicateName) => {
+            println!("  Budget '{name}' already exists, skipping...");
+            return Ok(());
+        }
+        Err(e) => return Err(e.into()),
+    };
+
+    let lines: Vec<_> = EXPENSE_ACCOUNTS
+        .iter()
+        .chain(std::iter::once(&"5000"))
+        .enumerate()
+        .flat_map(|(i, code)| {
+            let monthly = Decimal::new(12_000 + i64::try_from(i).unwrap_or_default() * 4_000, 0);
+            ledger
+                .periods()
+                .into_iter()
+                .map(move |period| CreateBudgetLineInput {
+                    account_id: ledger.account(code),
+                    fiscal_period_id: period.id,
+                    amount: monthly,
+                    notes: None,
+                    dimensions: Vec::new(),
+                })
+        })
+        .collect();
+
+    let created = repo
+        .create_budget_lines(ledger.org_id, budget.id, budget.created_by, lines)
+        .await?;
+    println!("  Created budget '{name}' with {} lines", created.len());
+
+    Ok(())
+}
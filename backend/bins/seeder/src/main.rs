@@ -1,13 +1,21 @@
 //! Database seeder for Zeltra development and testing.
 //!
-//! Seeds test organization, exchange rates, dimension types, and dimension values
-//! for local development and testing purposes.
+//! Seeds a test user and organization with exchange rates, dimension types and
+//! dimension values. The `demo` and `load-test` scenarios add a fiscal year,
+//! chart of accounts and posted transactions on top.
 //!
-//! Usage: cargo run --bin seeder
+//! Re-running a scenario against the same organization only fills in what is
+//! missing.
+//!
+//! Usage: cargo run --bin seeder -- [--scenario minimal|demo|load-test]
+//!        [--org-slug <slug>] [--transactions <n>]
+
+mod ledger;
 
+use anyhow::{Context, bail};
 use chrono::{Duration, Utc};
 use rust_decimal::Decimal;
-use sea_orm::{ActiveModelTrait, DatabaseConnection, EntityTrait, Set};
+use sea_orm::{ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, Set};
 use std::str::FromStr;
 use uuid::Uuid;
 use zeltra_db::entities::{
@@ -20,35 +28,133 @@ use zeltra_db::entities::{
 const TEST_ORG_ID: &str = "00000000-0000-0000-0000-000000000001";
 /// Test user ID (consistent for all seeds)
 const TEST_USER_ID: &str = "00000000-0000-0000-0000-000000000002";
+/// Slug of the test organization
+const TEST_ORG_SLUG: &str = "test-org";
+/// Default number of transactions for the `load-test` scenario
+const DEFAULT_LOAD_TEST_TRANSACTIONS: usize = 10_000;
+
+const USAGE: &str = "\
+Usage: seeder [OPTIONS]
+
+Options:
+  --scenario <name>    minimal, demo or load-test [default: minimal]
+  --org-slug <slug>    Organization to seed [default: test-org]
+  --transactions <n>   Transactions for load-test [default: 10000]
+  -h, --help           Print this help";
+
+/// What to seed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Scenario {
+    /// User, organization, exchange rates and dimensions.
+    Minimal,
+    /// Minimal plus a year of ledger activity, a budget and pending approvals.
+    Demo,
+    /// Minimal plus a large number of posted transactions.
+    LoadTest,
+}
+
+impl FromStr for Scenario {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "minimal" => Ok(Self::Minimal),
+            "demo" => Ok(Self::Demo),
+            "load-test" => Ok(Self::LoadTest),
+            other => bail!("unknown scenario '{other}' (expected minimal, demo or load-test)"),
+        }
+    }
+}
+
+/// Command-line options.
+#[derive(Debug)]
+struct Options {
+    scenario: Scenario,
+    org_slug: String,
+    transactions: usize,
+}
+
+impl Options {
+    /// Parses options from the process arguments, exiting on `--help`.
+    fn from_args() -> anyhow::Result<Self> {
+        let mut options = Self {
+            scenario: Scenario::Minimal,
+            org_slug: TEST_ORG_SLUG.to_string(),
+            transactions: DEFAULT_LOAD_TEST_TRANSACTIONS,
+        };
+
+        let mut args = std::env::args().skip(1);
+        while let Some(arg) = args.next() {
+            let (flag, inline) = match arg.split_once('=') {
+                Some((flag, value)) => (flag.to_string(), Some(value.to_string())),
+                None => (arg, None),
+            };
+            if flag == "-h" || flag == "--help" {
+                println!("{USAGE}");
+                std::process::exit(0);
+            }
+            let value = match inline {
+                Some(value) => value,
+                None => args
+                    .next()
+                    .with_context(|| format!("missing value for {flag}"))?,
+            };
+
+            match flag.as_str() {
+                "--scenario" => options.scenario = value.parse()?,
+                "--org-slug" => options.org_slug = value,
+                "--transactions" => {
+                    options.transactions = value
+                        .parse()
+                        .with_context(|| format!("invalid transaction count '{value}'"))?;
+                }
+                other => bail!("unknown option '{other}'\n\n{USAGE}"),
+            }
+        }
+
+        Ok(options)
+    }
+}
 
 #[tokio::main]
-async fn main() {
+async fn main() -> anyhow::Result<()> {
     dotenvy::dotenv().ok();
 
+    let options = Options::from_args()?;
+
     let database_url =
-        std::env::var("DATABASE_URL").expect("DATABASE_URL must be set in environment");
+        std::env::var("DATABASE_URL").context("DATABASE_URL must be set in environment")?;
 
     println!("Connecting to database...");
     let db = zeltra_db::connect(&database_url)
         .await
-        .expect("Failed to connect to database");
+        .context("Failed to connect to database")?;
 
     println!("Seeding test user...");
     seed_test_user(&db).await;
 
-    println!("Seeding test organization...");
-    seed_test_organization(&db).await;
+    println!("Seeding organization '{}'...", options.org_slug);
+    let org_id = seed_organization(&db, &options.org_slug).await?;
 
     println!("Seeding exchange rates...");
-    seed_exchange_rates(&db).await;
+    seed_exchange_rates(&db, org_id).await;
 
     println!("Seeding dimension types...");
-    seed_dimension_types(&db).await;
+    seed_dimension_types(&db, org_id).await;
 
     println!("Seeding dimension values...");
-    seed_dimension_values(&db).await;
+    seed_dimension_values(&db, org_id).await;
+
+    match options.scenario {
+        Scenario::Minimal => {}
+        Scenario::Demo => ledger::seed_demo(&db, org_id, test_user_id()).await?,
+        Scenario::LoadTest => {
+            ledger::seed_load_test(&db, org_id, test_user_id(), options.transactions).await?;
+        }
+    }
 
     println!("Seeding complete!");
+    Ok(())
 }
 
 fn test_org_id() -> Uuid {
@@ -80,8 +186,11 @@ async fn seed_test_user(db: &DatabaseConnection) {
         full_name: Set("Test User".to_string()),
         is_active: Set(true),
         email_verified_at: Set(Some(Utc::now().into())),
+        token_version: Set(0),
         created_at: Set(Utc::now().into()),
         updated_at: Set(Utc::now().into()),
+        locale: Set(None),
+        default_organization_id: Set(None),
     };
 
     if let Err(e) = user.insert(db).await {
@@ -91,24 +200,29 @@ async fn seed_test_user(db: &DatabaseConnection) {
     }
 }
 
-/// Seeds a test organization for development.
-async fn seed_test_organization(db: &DatabaseConnection) {
-    // Check if org already exists
-    if organizations::Entity::find_by_id(test_org_id())
+/// Returns the organization with `slug`, creating it if it does not exist.
+///
+/// The default slug maps to the fixed test organization ID.
+async fn seed_organization(db: &DatabaseConnection, slug: &str) -> anyhow::Result<Uuid> {
+    let existing = organizations::Entity::find()
+        .filter(organizations::Column::Slug.eq(slug))
         .one(db)
-        .await
-        .ok()
-        .flatten()
-        .is_some()
-    {
-        println!("  Test organization already exists, skipping...");
-        return;
+        .await?;
+    if let Some(org) = existing {
+        println!("  Organization already exists, skipping...");
+        return Ok(org.id);
     }
 
+    let (id, name) = if slug == TEST_ORG_SLUG {
+        (test_org_id(), "Test Organization".to_string())
+    } else {
+        (Uuid::new_v4(), format!("Seeded Organization ({slug})"))
+    };
+
     let org = organizations::ActiveModel {
-        id: Set(test_org_id()),
-        name: Set("Test Organization".to_string()),
-        slug: Set("test-org".to_string()),
+        id: Set(id),
+        name: Set(name.clone()),
+        slug: Set(slug.to_string()),
         base_currency: Set("USD".to_string()),
         timezone: Set("UTC".to_string()),
         settings: Set(serde_json::json!({})),
@@ -124,16 +238,16 @@ async fn seed_test_organization(db: &DatabaseConnection) {
         updated_at: Set(Utc::now().into()),
     };
 
-    if let Err(e) = org.insert(db).await {
-        eprintln!("Failed to insert test organization: {e}");
-    } else {
-        println!("  Created test organization: Test Organization");
-    }
+    org.insert(db)
+        .await
+        .with_context(|| format!("Failed to insert organization '{slug}'"))?;
+    println!("  Created organization: {name}");
+
+    Ok(id)
 }
 
 /// Seeds 30 days of exchange rates with USD as base currency.
-async fn seed_exchange_rates(db: &DatabaseConnection) {
-    let org_id = test_org_id();
+async fn seed_exchange_rates(db: &DatabaseConnection, org_id: Uuid) {
     let user_id = test_user_id();
 
     // Exchange rates relative to USD (approximate values for testing)
@@ -205,9 +319,7 @@ async fn seed_exchange_rates(db: &DatabaseConnection) {
 }
 
 /// Seeds dimension types for organizational structure.
-async fn seed_dimension_types(db: &DatabaseConnection) {
-    let org_id = test_org_id();
-
+async fn seed_dimension_types(db: &DatabaseConnection, org_id: Uuid) {
     let dimension_types_data = [
         (
             "DEPARTMENT",
@@ -265,11 +377,7 @@ async fn seed_dimension_types(db: &DatabaseConnection) {
 
 /// Seeds sample dimension values for testing.
 #[allow(clippy::too_many_lines)]
-async fn seed_dimension_values(db: &DatabaseConnection) {
-    use sea_orm::{ColumnTrait, QueryFilter};
-
-    let org_id = test_org_id();
-
+async fn seed_dimension_values(db: &DatabaseConnection, org_id: Uuid) {
     // First, get the dimension type IDs
     let dept_type = dimension_types::Entity::find()
         .filter(dimension_types::Column::Code.eq("DEPARTMENT"))
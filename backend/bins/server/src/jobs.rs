@@ -0,0 +1,45 @@
+//! Background jobs that depend on server-only state.
+
+use std::{sync::Arc, time::Duration};
+
+use async_trait::async_trait;
+use zeltra_api::middleware::Metrics;
+use zeltra_jobs::{Job, JobContext, JobError, Schedule};
+
+/// Drains rendered histogram samples from the Prometheus recorder.
+#[derive(Debug)]
+pub struct MetricsUpkeepJob {
+    metrics: Arc<Metrics>,
+}
+
+impl MetricsUpkeepJob {
+    /// Creates the upkeep job for an installed recorder.
+    pub const fn new(metrics: Arc<Metrics>) -> Self {
+        Self { metrics }
+    }
+}
+
+#[async_trait]
+impl Job for MetricsUpkeepJob {
+    fn name(&self) -> &'static str {
+        "metrics_upkeep"
+    }
+
+    fn schedule(&self) -> Schedule {
+        Schedule::every(Duration::from_secs(5))
+    }
+
+    fn timeout(&self) -> Duration {
+        Duration::from_secs(5)
+    }
+
+    // Every replica keeps its own recorder
+    fn exclusive(&self) -> bool {
+        false
+    }
+
+    async fn run(&self, _ctx: &JobContext) -> Result<(), JobError> {
+        self.metrics.run_upkeep();
+        Ok(())
+    }
+}
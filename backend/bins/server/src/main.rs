@@ -2,17 +2,38 @@
 //!
 //! Main entry point for the Zeltra backend service.
 
+mod jobs;
+
 use std::sync::Arc;
+use std::time::Duration;
 
 use tokio::net::TcpListener;
 use tracing::info;
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
-use zeltra_api::{AppState, create_router};
+use zeltra_api::{
+    AppState,
+    cache::{DashboardCache, MembershipCache},
+    create_router,
+    middleware::{BodyLimits, Metrics},
+};
+use zeltra_core::attachment::StubOcrProvider;
 use zeltra_core::storage::{StorageConfig, StorageProvider, StorageService};
-use zeltra_db::connect;
-use zeltra_shared::{AppConfig, EmailService, JwtConfig, JwtService};
+use zeltra_db::{
+    connect,
+    repositories::{CurrencyRepository, Stores},
+};
+use zeltra_jobs::{
+    ApprovalEscalationJob, ExpiredSessionCleanupJob, ExpiredVerificationTokenCleanupJob,
+    FxRevaluationJob, JobContext, OcrExtractionJob, PeriodAutoCloseJob, Scheduler,
+};
+use zeltra_shared::{
+    AppConfig, EmailService, JwtConfig, JwtService, MetricsConfig, OcrConfig, StorageBackend,
+};
+
+use crate::jobs::MetricsUpkeepJob;
 
+#[allow(clippy::too_many_lines)]
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
     // Load environment variables from .env file
@@ -27,13 +48,35 @@ async fn main() -> anyhow::Result<()> {
         .with(tracing_subscriber::fmt::layer())
         .init();
 
-    // Load configuration
-    let config = AppConfig::load().expect("Failed to load configuration");
+    // Load and validate configuration before touching anything external
+    let config = match AppConfig::load() {
+        Ok(config) => config,
+        Err(e) => {
+            eprintln!("Failed to load configuration: {e}");
+            std::process::exit(1);
+        }
+    };
+    let storage_backend = match config.validate() {
+        Ok(()) => config.storage.backend().unwrap_or_default(),
+        Err(errors) => {
+            eprintln!("Invalid configuration:");
+            for problem in &errors.0 {
+                eprintln!("  {problem}");
+            }
+            std::process::exit(1);
+        }
+    };
+
+    // Install the metrics recorder before anything records
+    let metrics = install_metrics(&config.metrics);
 
     // Connect to database
     let db = connect(&config.database.url).await?;
     info!("Connected to database");
 
+    // Currencies are fixed by migrations, so they are loaded once
+    let currencies = CurrencyRepository::new(db.clone()).registry().await?;
+
     // Create JWT service
     let jwt_config = JwtConfig {
         secret: config.jwt.secret.clone(),
@@ -41,11 +84,17 @@ async fn main() -> anyhow::Result<()> {
         access_token_expires_minutes: (config.jwt.access_token_expiry_secs / 60) as i64,
         #[allow(clippy::cast_possible_wrap)]
         refresh_token_expires_days: (config.jwt.refresh_token_expiry_secs / 86400) as i64,
+        algorithm: config.jwt.algorithm,
+        key_id: config.jwt.key_id.clone(),
+        private_key_pem: config.jwt.private_key_pem.clone(),
+        public_key_pem: config.jwt.public_key_pem.clone(),
+        verification_keys: config.jwt.verification_keys.clone(),
     };
-    let jwt_service = JwtService::new(jwt_config);
+    let jwt_service = JwtService::try_new(jwt_config).expect("Invalid JWT key configuration");
+    info!(kid = %jwt_service.signing_key_id(), "JWT signing key loaded");
 
     // Create email service
-    let email_service = EmailService::new(config.email.clone());
+    let email_service = Arc::new(EmailService::new(config.email.clone()));
     info!(
         smtp_host = %config.email.smtp_host,
         smtp_port = %config.email.smtp_port,
@@ -53,18 +102,65 @@ async fn main() -> anyhow::Result<()> {
     );
 
     // Create storage service (optional, based on environment)
-    let storage = create_storage_service();
+    let storage = create_storage_service(storage_backend)?;
+
+    // Start background jobs
+    let mut scheduler = Scheduler::new(JobContext::new(db.clone()))
+        .register(ExpiredSessionCleanupJob)
+        .register(ExpiredVerificationTokenCleanupJob)
+        .register(ApprovalEscalationJob::new(Arc::clone(&email_service)))
+        .register(FxRevaluationJob)
+        .register(PeriodAutoCloseJob);
+    if let Some(metrics) = &metrics {
+        scheduler = scheduler.register(MetricsUpkeepJob::new(Arc::clone(metrics)));
+    }
+    let scheduler = register_ocr_job(scheduler, &config.ocr, storage.as_ref());
+    let jobs = scheduler.start();
 
     // Create application state
     let state = AppState {
+        stores: Stores::postgres(&db),
         db: Arc::new(db),
         jwt_service: Arc::new(jwt_service),
-        email_service: Arc::new(email_service),
+        email_service,
         storage,
+        metrics: metrics.clone(),
+        jobs: Some(jobs),
+        admin: config.admin.clone(),
+        dashboard_cache: DashboardCache::new(Duration::from_secs(config.dashboard.cache_ttl_secs)),
+        membership_cache: MembershipCache::default(),
+        currencies: Arc::new(currencies),
+    };
+
+    // Serve /metrics on its own listener when configured
+    if let (Some(addr), Some(_)) = (&config.metrics.bind_address, &metrics) {
+        let listener = TcpListener::bind(addr).await?;
+        info!("Metrics listening on {}", addr);
+        let metrics_app = zeltra_api::routes::metrics::routes().with_state(state.clone());
+        tokio::spawn(async move {
+            if let Err(e) = axum::serve(listener, metrics_app).await {
+                tracing::error!(error = %e, "Metrics listener failed");
+            }
+        });
+    }
+    // Scrapes then go to the dedicated listener only; requests are still recorded
+    let state = if config.metrics.bind_address.is_some() {
+        AppState {
+            metrics: None,
+            ..state
+        }
+    } else {
+        state
     };
 
     // Create router
-    let app = create_router(state);
+    let limits = BodyLimits::from(&config.server);
+    info!(
+        default_bytes = limits.default_bytes,
+        import_bytes = limits.import_bytes,
+        "Request body limits configured"
+    );
+    let app = create_router(state, limits);
 
     // Start server
     let addr = format!("{}:{}", config.server.host, config.server.port);
@@ -76,23 +172,94 @@ async fn main() -> anyhow::Result<()> {
     Ok(())
 }
 
-/// Create storage service from environment variables.
+/// Install the Prometheus recorder.
+///
+/// Its upkeep runs as a background job.
+fn install_metrics(config: &MetricsConfig) -> Option<Arc<Metrics>> {
+    if !config.enabled {
+        info!("Metrics disabled");
+        return None;
+    }
+
+    let metrics = Arc::new(
+        Metrics::install(config.token.clone()).expect("Failed to install metrics recorder"),
+    );
+
+    info!(
+        token_protected = config.token.is_some(),
+        "Metrics recorder installed"
+    );
+    Some(metrics)
+}
+
+/// Register the attachment OCR job when enabled and storage is available.
+///
+/// Uses the HTTP provider when an endpoint is configured and the binary is
+/// built with `ocr-http`, and the stub provider otherwise.
+fn register_ocr_job(
+    scheduler: Scheduler,
+    config: &OcrConfig,
+    storage: Option<&Arc<StorageService>>,
+) -> Scheduler {
+    if !config.enabled {
+        return scheduler;
+    }
+    let Some(storage) = storage else {
+        tracing::warn!("OCR enabled but no storage configured; extraction job not started");
+        return scheduler;
+    };
+    let interval = Duration::from_secs(config.poll_interval_secs);
+
+    #[cfg(feature = "ocr-http")]
+    if let Some(endpoint) = &config.endpoint {
+        match zeltra_core::attachment::HttpOcrProvider::new(
+            endpoint.clone(),
+            config.api_key.clone(),
+            Duration::from_secs(config.timeout_secs),
+        ) {
+            Ok(provider) => {
+                info!(endpoint = %endpoint, "OCR extraction using HTTP provider");
+                return scheduler.register(OcrExtractionJob::new(
+                    Arc::new(provider),
+                    Arc::clone(storage),
+                    interval,
+                    config.batch_size,
+                ));
+            }
+            Err(e) => tracing::error!(error = %e, "Failed to create OCR provider"),
+        }
+    }
+    #[cfg(not(feature = "ocr-http"))]
+    if config.endpoint.is_some() {
+        tracing::warn!("OCR endpoint configured but built without the ocr-http feature");
+    }
+
+    info!("OCR extraction using stub provider");
+    scheduler.register(OcrExtractionJob::new(
+        Arc::new(StubOcrProvider::default()),
+        Arc::clone(storage),
+        interval,
+        config.batch_size,
+    ))
+}
+
+/// Create the storage service for a validated backend.
 ///
 /// Supports:
 /// - `STORAGE_TYPE=local` with `STORAGE_LOCAL_PATH` (default: ./uploads)
 /// - `STORAGE_TYPE=s3` with S3-compatible config (R2, Supabase, AWS)
 /// - `STORAGE_TYPE=azure` with Azure Blob config
-fn create_storage_service() -> Option<Arc<StorageService>> {
-    let storage_type = std::env::var("STORAGE_TYPE").unwrap_or_default();
-
-    let config = match storage_type.as_str() {
-        "s3" => {
-            let endpoint = std::env::var("STORAGE_S3_ENDPOINT").ok()?;
-            let bucket = std::env::var("STORAGE_S3_BUCKET").ok()?;
-            let access_key = std::env::var("STORAGE_S3_ACCESS_KEY").ok()?;
-            let secret_key = std::env::var("STORAGE_S3_SECRET_KEY").ok()?;
-            let region = std::env::var("STORAGE_S3_REGION").unwrap_or_else(|_| "auto".to_string());
-
+fn create_storage_service(
+    backend: Option<StorageBackend>,
+) -> anyhow::Result<Option<Arc<StorageService>>> {
+    let config = match backend {
+        Some(StorageBackend::S3 {
+            endpoint,
+            bucket,
+            access_key,
+            secret_key,
+            region,
+        }) => {
             info!(
                 endpoint = %endpoint,
                 bucket = %bucket,
@@ -104,11 +271,11 @@ fn create_storage_service() -> Option<Arc<StorageService>> {
                 endpoint, bucket, access_key, secret_key, region,
             ))
         }
-        "azure" => {
-            let account = std::env::var("STORAGE_AZURE_ACCOUNT").ok()?;
-            let access_key = std::env::var("STORAGE_AZURE_ACCESS_KEY").ok()?;
-            let container = std::env::var("STORAGE_AZURE_CONTAINER").ok()?;
-
+        Some(StorageBackend::Azure {
+            account,
+            access_key,
+            container,
+        }) => {
             info!(
                 account = %account,
                 container = %container,
@@ -117,28 +284,19 @@ fn create_storage_service() -> Option<Arc<StorageService>> {
 
             StorageConfig::new(StorageProvider::azure_blob(account, access_key, container))
         }
-        "local" => {
-            let path =
-                std::env::var("STORAGE_LOCAL_PATH").unwrap_or_else(|_| "./uploads".to_string());
-
+        Some(StorageBackend::Local { path }) => {
             info!(path = %path, "Configuring local filesystem storage");
 
             StorageConfig::new(StorageProvider::local_fs(&path))
         }
-        _ => {
+        None => {
             info!("No storage configured (STORAGE_TYPE not set)");
-            return None;
+            return Ok(None);
         }
     };
 
-    match StorageService::from_config(config) {
-        Ok(service) => {
-            info!("Storage service initialized");
-            Some(Arc::new(service))
-        }
-        Err(e) => {
-            tracing::error!(error = %e, "Failed to initialize storage service");
-            None
-        }
-    }
+    let service = StorageService::from_config(config)
+        .map_err(|e| anyhow::anyhow!("Failed to initialize storage service: {e}"))?;
+    info!("Storage service initialized");
+    Ok(Some(Arc::new(service)))
 }
@@ -0,0 +1,468 @@
+//! In-process caching of dashboard sections and organization memberships.
+//!
+//! Each dashboard section is an aggregate over the ledger, so it is cached per
+//! organization for a short time-to-live. Workflow handlers call
+//! [`DashboardCache::invalidate`] after any change that moves balances or the
+//! approval queue, so the next read recomputes instead of waiting for expiry.
+//!
+//! Nearly every organization route checks the caller's membership first, so
+//! [`MembershipCache`] keeps those rows for a few seconds. The member routes
+//! invalidate an entry when they change a role or remove a member; changes
+//! made elsewhere take effect once the entry expires.
+
+use std::future::Future;
+use std::time::Duration;
+
+use chrono::NaiveDate;
+use moka::future::Cache;
+use rust_decimal::Decimal;
+use sea_orm::DbErr;
+use uuid::Uuid;
+use zeltra_db::entities::sea_orm_active_enums::UserRole;
+use zeltra_db::repositories::OrganizationStore;
+use zeltra_db::repositories::dashboard::{CashPosition, PendingApprovals};
+
+use crate::middleware::metrics::{DASHBOARD_CACHE_LOOKUPS_TOTAL, MEMBERSHIP_CACHE_LOOKUPS_TOTAL};
+
+/// Default time-to-live for cached sections.
+pub const DEFAULT_DASHBOARD_CACHE_TTL: Duration = Duration::from_secs(60);
+
+/// Upper bound on cached organizations per section.
+const MAX_ORGANIZATIONS: u64 = 10_000;
+
+/// One cached dashboard section, keyed by organization.
+#[derive(Clone)]
+struct Section<V> {
+    name: &'static str,
+    entries: Cache<Uuid, V>,
+}
+
+impl<V> Section<V>
+where
+    V: Clone + Send + Sync + 'static,
+{
+    fn new(name: &'static str, ttl: Duration) -> Self {
+        let entries = Cache::builder()
+            .max_capacity(MAX_ORGANIZATIONS)
+            .time_to_live(ttl)
+            .build();
+        Self { name, entries }
+    }
+
+    /// Returns the cached value if `is_fresh` accepts it, computing and
+    /// storing a new one otherwise. Errors are never cached.
+    async fn get_or_try_compute<E, F, Fut>(
+        &self,
+        org_id: Uuid,
+        is_fresh: impl FnOnce(&V) -> bool,
+        compute: F,
+    ) -> Result<V, E>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<V, E>>,
+    {
+        if let Some(value) = self.entries.get(&org_id).await
+            && is_fresh(&value)
+        {
+            self.record("hit");
+            return Ok(value);
+        }
+
+        self.record("miss");
+        let value = compute().await?;
+        self.entries.insert(org_id, value.clone()).await;
+        Ok(value)
+    }
+
+    fn record(&self, result: &'static str) {
+        metrics::counter!(
+            DASHBOARD_CACHE_LOOKUPS_TOTAL,
+            "section" => self.name,
+            "result" => result
+        )
+        .increment(1);
+    }
+}
+
+/// Per-organization cache of dashboard sections.
+///
+/// Cloning is cheap; clones share the same entries.
+#[derive(Clone)]
+pub struct DashboardCache {
+    cash_position: Section<(NaiveDate, CashPosition)>,
+    pending_approvals: Section<PendingApprovals>,
+}
+
+impl DashboardCache {
+    /// Creates a cache whose entries expire after `ttl`.
+    #[must_use]
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            cash_position: Section::new("cash_position", ttl),
+            pending_approvals: Section::new("pending_approvals", ttl),
+        }
+    }
+
+    /// Returns the organization's cash position as of `as_of`.
+    ///
+    /// A cached position computed for another date is treated as a miss.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error from `compute` on a miss.
+    pub async fn cash_position<E, F, Fut>(
+        &self,
+        org_id: Uuid,
+        as_of: NaiveDate,
+        compute: F,
+    ) -> Result<CashPosition, E>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<CashPosition, E>>,
+    {
+        self.cash_position
+            .get_or_try_compute(
+                org_id,
+                |(date, _)| *date == as_of,
+                || async move { compute().await.map(|position| (as_of, position)) },
+            )
+            .await
+            .map(|(_, position)| position)
+    }
+
+    /// Returns the organization's pending approval totals.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error from `compute` on a miss.
+    pub async fn pending_approvals<E, F, Fut>(
+        &self,
+        org_id: Uuid,
+        compute: F,
+    ) -> Result<PendingApprovals, E>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<PendingApprovals, E>>,
+    {
+        self.pending_approvals
+            .get_or_try_compute(org_id, |_| true, compute)
+            .await
+    }
+
+    /// Drops every cached section for the organization.
+    pub async fn invalidate(&self, org_id: Uuid) {
+        self.cash_position.entries.invalidate(&org_id).await;
+        self.pending_approvals.entries.invalidate(&org_id).await;
+    }
+}
+
+impl Default for DashboardCache {
+    fn default() -> Self {
+        Self::new(DEFAULT_DASHBOARD_CACHE_TTL)
+    }
+}
+
+/// Default time-to-live for cached memberships.
+///
+/// This bounds how long a member removed outside the member routes keeps
+/// access.
+pub const DEFAULT_MEMBERSHIP_CACHE_TTL: Duration = Duration::from_secs(30);
+
+/// Upper bound on cached memberships.
+const MAX_MEMBERSHIPS: u64 = 100_000;
+
+/// A user's role and approval limit in one organization.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Membership {
+    /// Role in the organization.
+    pub role: UserRole,
+    /// Largest amount the user may approve, if limited.
+    pub approval_limit: Option<Decimal>,
+}
+
+/// Cache of memberships keyed by organization and user.
+///
+/// Only memberships that exist are cached, so a user who has just been added
+/// is never turned away by a stale entry. Cloning is cheap; clones share the
+/// same entries.
+#[derive(Clone)]
+pub struct MembershipCache {
+    entries: Cache<(Uuid, Uuid), Membership>,
+}
+
+impl MembershipCache {
+    /// Creates a cache whose entries expire after `ttl`.
+    #[must_use]
+    pub fn new(ttl: Duration) -> Self {
+        let entries = Cache::builder()
+            .max_capacity(MAX_MEMBERSHIPS)
+            .time_to_live(ttl)
+            .build();
+        Self { entries }
+    }
+
+    /// Returns the user's membership in the organization, or `None` if they
+    /// are not a member, loading it from `store` on a miss.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the lookup fails.
+    pub async fn get(
+        &self,
+        store: &dyn OrganizationStore,
+        org_id: Uuid,
+        user_id: Uuid,
+    ) -> Result<Option<Membership>, DbErr> {
+        let key = (org_id, user_id);
+        if let Some(membership) = self.entries.get(&key).await {
+            record_membership_lookup("hit");
+            return Ok(Some(membership));
+        }
+
+        record_membership_lookup("miss");
+        let Some(row) = store.get_user_membership(org_id, user_id).await? else {
+            return Ok(None);
+        };
+        let membership = Membership {
+            role: row.role,
+            approval_limit: row.approval_limit,
+        };
+        self.entries.insert(key, membership.clone()).await;
+        Ok(Some(membership))
+    }
+
+    /// Drops the cached membership of the user in the organization.
+    pub async fn invalidate(&self, org_id: Uuid, user_id: Uuid) {
+        self.entries.invalidate(&(org_id, user_id)).await;
+    }
+}
+
+impl Default for MembershipCache {
+    fn default() -> Self {
+        Self::new(DEFAULT_MEMBERSHIP_CACHE_TTL)
+    }
+}
+
+fn record_membership_lookup(result: &'static str) {
+    metrics::counter!(MEMBERSHIP_CACHE_LOOKUPS_TOTAL, "result" => result).increment(1);
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::Infallible;
+    use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+
+    use async_trait::async_trait;
+    use zeltra_db::entities::{organization_users, organizations};
+
+    use super::*;
+    use crate::middleware::metrics::prometheus_builder;
+
+    fn position(balance: i64) -> CashPosition {
+        CashPosition {
+            balance: Decimal::from(balance),
+            currency: "USD".to_string(),
+            change_from_last_period: Decimal::ZERO,
+            change_percent: Decimal::ZERO,
+        }
+    }
+
+    fn today() -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 1, 15).unwrap()
+    }
+
+    /// Reads the cash position, counting how often it had to be computed.
+    async fn read(
+        cache: &DashboardCache,
+        org_id: Uuid,
+        as_of: NaiveDate,
+        calls: &AtomicU32,
+    ) -> i64 {
+        let position = cache
+            .cash_position(org_id, as_of, || async {
+                let n = calls.fetch_add(1, Ordering::SeqCst) + 1;
+                Ok::<_, Infallible>(position(i64::from(n) * 100))
+            })
+            .await
+            .unwrap();
+        position.balance.try_into().unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_second_read_is_served_from_cache() {
+        let cache = DashboardCache::default();
+        let org_id = Uuid::new_v4();
+        let calls = AtomicU32::new(0);
+
+        assert_eq!(read(&cache, org_id, today(), &calls).await, 100);
+        assert_eq!(read(&cache, org_id, today(), &calls).await, 100);
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_invalidate_forces_recompute_for_that_org_only() {
+        let cache = DashboardCache::default();
+        let (posted_org, other_org) = (Uuid::new_v4(), Uuid::new_v4());
+        let calls = AtomicU32::new(0);
+        read(&cache, posted_org, today(), &calls).await;
+        read(&cache, other_org, today(), &calls).await;
+
+        cache.invalidate(posted_org).await;
+
+        assert_eq!(read(&cache, posted_org, today(), &calls).await, 300);
+        assert_eq!(read(&cache, other_org, today(), &calls).await, 200);
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_new_date_is_a_miss() {
+        let cache = DashboardCache::default();
+        let org_id = Uuid::new_v4();
+        let calls = AtomicU32::new(0);
+        read(&cache, org_id, today(), &calls).await;
+
+        let tomorrow = today().succ_opt().unwrap();
+        assert_eq!(read(&cache, org_id, tomorrow, &calls).await, 200);
+    }
+
+    #[tokio::test]
+    async fn test_errors_are_not_cached() {
+        let cache = DashboardCache::default();
+        let org_id = Uuid::new_v4();
+
+        let failed = cache
+            .pending_approvals(org_id, || async { Err("database unavailable") })
+            .await;
+        assert!(failed.is_err());
+
+        let pending = cache
+            .pending_approvals(org_id, || async {
+                Ok::<_, &str>(PendingApprovals {
+                    count: 2,
+                    total_amount: Decimal::from(50),
+                })
+            })
+            .await
+            .unwrap();
+        assert_eq!(pending.count, 2);
+    }
+
+    #[tokio::test]
+    async fn test_lookups_are_counted_by_section_and_result() {
+        let recorder = prometheus_builder().unwrap().build_recorder();
+        let handle = recorder.handle();
+        let _guard = metrics::set_default_local_recorder(&recorder);
+        let cache = DashboardCache::default();
+        let org_id = Uuid::new_v4();
+        let calls = AtomicU32::new(0);
+
+        read(&cache, org_id, today(), &calls).await;
+        read(&cache, org_id, today(), &calls).await;
+        read(&cache, org_id, today(), &calls).await;
+
+        let body = handle.render();
+        assert!(
+            body.contains(
+                r#"dashboard_cache_lookups_total{section="cash_position",result="miss"} 1"#
+            )
+        );
+        assert!(
+            body.contains(
+                r#"dashboard_cache_lookups_total{section="cash_position",result="hit"} 2"#
+            )
+        );
+    }
+
+    /// Organization store with one approver whose membership can be toggled.
+    #[derive(Default)]
+    struct ToggledMembership {
+        removed: AtomicBool,
+        lookups: AtomicU32,
+    }
+
+    #[async_trait]
+    impl OrganizationStore for ToggledMembership {
+        async fn find_by_id(&self, _id: Uuid) -> Result<Option<organizations::Model>, DbErr> {
+            Ok(None)
+        }
+
+        async fn is_member(&self, _org_id: Uuid, _user_id: Uuid) -> Result<bool, DbErr> {
+            Ok(!self.removed.load(Ordering::SeqCst))
+        }
+
+        async fn get_user_membership(
+            &self,
+            org_id: Uuid,
+            user_id: Uuid,
+        ) -> Result<Option<organization_users::Model>, DbErr> {
+            self.lookups.fetch_add(1, Ordering::SeqCst);
+            if self.removed.load(Ordering::SeqCst) {
+                return Ok(None);
+            }
+            let now = chrono::Utc::now().fixed_offset();
+            Ok(Some(organization_users::Model {
+                user_id,
+                organization_id: org_id,
+                role: UserRole::Approver,
+                approval_limit: Some(Decimal::from(500)),
+                created_at: now,
+                updated_at: now,
+            }))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_removed_member_is_denied_once_invalidated() {
+        let cache = MembershipCache::default();
+        let store = ToggledMembership::default();
+        let (org_id, user_id) = (Uuid::new_v4(), Uuid::new_v4());
+
+        let membership = cache.get(&store, org_id, user_id).await.unwrap().unwrap();
+        assert_eq!(membership.role, UserRole::Approver);
+        assert_eq!(membership.approval_limit, Some(Decimal::from(500)));
+        cache.get(&store, org_id, user_id).await.unwrap();
+        assert_eq!(store.lookups.load(Ordering::SeqCst), 1);
+
+        // Until invalidated, the cached entry outlives the removal
+        store.removed.store(true, Ordering::SeqCst);
+        assert!(cache.get(&store, org_id, user_id).await.unwrap().is_some());
+
+        cache.invalidate(org_id, user_id).await;
+        assert_eq!(cache.get(&store, org_id, user_id).await.unwrap(), None);
+
+        // Non-members are looked up every time, so re-adding takes effect at once
+        store.removed.store(false, Ordering::SeqCst);
+        assert!(cache.get(&store, org_id, user_id).await.unwrap().is_some());
+        assert_eq!(store.lookups.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_removed_member_is_denied_after_ttl() {
+        let cache = MembershipCache::new(Duration::from_millis(50));
+        let store = ToggledMembership::default();
+        let (org_id, user_id) = (Uuid::new_v4(), Uuid::new_v4());
+        cache.get(&store, org_id, user_id).await.unwrap();
+
+        store.removed.store(true, Ordering::SeqCst);
+        tokio::time::sleep(Duration::from_millis(100)).await;
+
+        assert_eq!(cache.get(&store, org_id, user_id).await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn test_membership_lookups_are_counted() {
+        let recorder = prometheus_builder().unwrap().build_recorder();
+        let handle = recorder.handle();
+        let _guard = metrics::set_default_local_recorder(&recorder);
+        let cache = MembershipCache::default();
+        let store = ToggledMembership::default();
+        let (org_id, user_id) = (Uuid::new_v4(), Uuid::new_v4());
+
+        cache.get(&store, org_id, user_id).await.unwrap();
+        cache.get(&store, org_id, user_id).await.unwrap();
+
+        let body = handle.render();
+        assert!(body.contains(r#"membership_cache_lookups_total{result="miss"} 1"#));
+        assert!(body.contains(r#"membership_cache_lookups_total{result="hit"} 1"#));
+    }
+}
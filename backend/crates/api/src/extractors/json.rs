@@ -0,0 +1,404 @@
+//! JSON request bodies with field-level errors.
+//!
+//! [`StrictJson`] replaces axum's `Json` extractor for request bodies. When
+//! a body does not match the request type it answers 422 with the JSON
+//! pointer of each offending field, the type the field expects and, for
+//! unknown fields and enum-like strings, the values that are accepted.
+
+use std::fmt;
+
+use axum::{
+    Json,
+    body::Bytes,
+    extract::{FromRequest, OptionalFromRequest, Request},
+    http::{StatusCode, header},
+    response::{IntoResponse, Response},
+};
+use serde::{Deserialize, Deserializer, Serialize, de::DeserializeOwned};
+use serde_json::json;
+use serde_path_to_error::{Path, Segment};
+
+/// A JSON request body deserialized with field-level error reporting.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct StrictJson<T>(pub T);
+
+impl<T, S> FromRequest<S> for StrictJson<T>
+where
+    T: DeserializeOwned,
+    S: Send + Sync,
+{
+    type Rejection = Response;
+
+    async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+        if !has_json_content_type(&req) {
+            return Err(unsupported_media_type());
+        }
+
+        let bytes = Bytes::from_request(req, state)
+            .await
+            .map_err(IntoResponse::into_response)?;
+        parse_body(&bytes).map(Self)
+    }
+}
+
+/// An optional body: absent when the request has no `Content-Type`.
+impl<T, S> OptionalFromRequest<S> for StrictJson<T>
+where
+    T: DeserializeOwned,
+    S: Send + Sync,
+{
+    type Rejection = Response;
+
+    async fn from_request(req: Request, state: &S) -> Result<Option<Self>, Self::Rejection> {
+        if !req.headers().contains_key(header::CONTENT_TYPE) {
+            return Ok(None);
+        }
+        <Self as FromRequest<S>>::from_request(req, state)
+            .await
+            .map(Some)
+    }
+}
+
+/// A problem with one field of a request body.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct BodyFieldError {
+    /// JSON pointer to the field, e.g. `/entries/0/source_amount`.
+    pub pointer: String,
+    /// What is wrong with the field.
+    pub message: String,
+    /// What the field expects, for values of the wrong type or shape.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expected: Option<String>,
+    /// Accepted values: the known fields for an unknown field, or the
+    /// variants of an enum-like string.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub allowed: Option<Vec<String>>,
+}
+
+/// Deserializes `body`, describing where it went wrong if it does not fit.
+///
+/// # Errors
+///
+/// Returns a 400 response for malformed JSON and a 422 response naming the
+/// offending field for JSON that does not match `T`.
+#[allow(clippy::result_large_err)]
+pub fn parse_body<T: DeserializeOwned>(body: &[u8]) -> Result<T, Response> {
+    let mut deserializer = serde_json::Deserializer::from_slice(body);
+    let value =
+        serde_path_to_error::deserialize(&mut deserializer).map_err(|e| body_error_response(&e))?;
+    deserializer.end().map_err(|e| malformed_json(&e))?;
+    Ok(value)
+}
+
+/// Deserializes a string that `parse` accepts, listing `allowed` when it
+/// does not.
+///
+/// For enum-like request fields that stay strings in the request type, so
+/// a bad value is reported against its field with the accepted values.
+///
+/// # Errors
+///
+/// Returns a deserialization error if the value is not a string or not
+/// accepted by `parse`.
+pub fn enum_string<'de, D, T>(
+    deserializer: D,
+    parse: fn(&str) -> Option<T>,
+    allowed: impl FnOnce() -> Vec<String>,
+) -> Result<String, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let value = String::deserialize(deserializer)?;
+    if parse(&value).is_some() {
+        return Ok(value);
+    }
+    Err(serde::de::Error::invalid_value(
+        serde::de::Unexpected::Str(&value),
+        &OneOf(allowed()),
+    ))
+}
+
+/// Like [`enum_string`] for optional fields.
+///
+/// # Errors
+///
+/// Returns a deserialization error if the value is present but not a
+/// string accepted by `parse`.
+pub fn optional_enum_string<'de, D, T>(
+    deserializer: D,
+    parse: fn(&str) -> Option<T>,
+    allowed: impl FnOnce() -> Vec<String>,
+) -> Result<Option<String>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let Some(value) = Option::<String>::deserialize(deserializer)? else {
+        return Ok(None);
+    };
+    enum_string(
+        serde::de::value::StrDeserializer::<D::Error>::new(&value),
+        parse,
+        allowed,
+    )
+    .map(Some)
+}
+
+/// Expected values, formatted the way serde lists variants.
+struct OneOf(Vec<String>);
+
+impl serde::de::Expected for OneOf {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let quoted: Vec<String> = self.0.iter().map(|v| format!("`{v}`")).collect();
+        write!(f, "one of {}", quoted.join(", "))
+    }
+}
+
+fn unsupported_media_type() -> Response {
+    (
+        StatusCode::UNSUPPORTED_MEDIA_TYPE,
+        Json(json!({
+            "error": "unsupported_media_type",
+            "message": "Expected request with `Content-Type: application/json`"
+        })),
+    )
+        .into_response()
+}
+
+fn has_json_content_type(req: &Request) -> bool {
+    let Some(content_type) = req
+        .headers()
+        .get(header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+    else {
+        return false;
+    };
+    let essence = content_type
+        .split(';')
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_ascii_lowercase();
+    essence
+        .strip_prefix("application/")
+        .is_some_and(|subtype| subtype == "json" || subtype.ends_with("+json"))
+}
+
+fn body_error_response(error: &serde_path_to_error::Error<serde_json::Error>) -> Response {
+    let inner = error.inner();
+    if !inner.is_data() {
+        return malformed_json(inner);
+    }
+
+    (
+        StatusCode::UNPROCESSABLE_ENTITY,
+        Json(json!({
+            "error": "validation_failed",
+            "message": "One or more fields are invalid",
+            "fields": [field_error(error.path(), &inner.to_string())]
+        })),
+    )
+        .into_response()
+}
+
+fn malformed_json(error: &serde_json::Error) -> Response {
+    (
+        StatusCode::BAD_REQUEST,
+        Json(json!({
+            "error": "malformed_json",
+            "message": format!("Request body is not valid JSON: {error}")
+        })),
+    )
+        .into_response()
+}
+
+/// Describes a serde data error at `path`.
+fn field_error(path: &Path, error: &str) -> BodyFieldError {
+    // serde_json appends the position, which the pointer already conveys
+    let message = error
+        .rsplit_once(" at line ")
+        .map_or(error, |(message, _)| message);
+    let mut pointer = json_pointer(path);
+
+    if let Some(rest) = message.strip_prefix("missing field `") {
+        let field = rest.split('`').next().unwrap_or_default();
+        pointer.push('/');
+        pointer.push_str(&escape_pointer_token(field));
+        return BodyFieldError {
+            pointer,
+            message: "missing field".to_string(),
+            expected: None,
+            allowed: None,
+        };
+    }
+
+    if let Some(rest) = message.strip_prefix("unknown field `") {
+        let allowed = rest
+            .split_once(", expected ")
+            .map(|(_, expected)| backticked(expected));
+        return BodyFieldError {
+            pointer,
+            message: "unknown field".to_string(),
+            expected: None,
+            allowed,
+        };
+    }
+
+    match message.split_once(", expected ") {
+        Some((problem, expected)) if expected.starts_with("one of `") => BodyFieldError {
+            pointer,
+            message: problem.to_string(),
+            expected: None,
+            allowed: Some(backticked(expected)),
+        },
+        Some((problem, expected)) => BodyFieldError {
+            pointer,
+            message: problem.to_string(),
+            expected: Some(expected.to_string()),
+            allowed: None,
+        },
+        None => BodyFieldError {
+            pointer,
+            message: message.to_string(),
+            expected: None,
+            allowed: None,
+        },
+    }
+}
+
+/// Converts a serde path into a JSON pointer (RFC 6901).
+fn json_pointer(path: &Path) -> String {
+    path.iter()
+        .filter_map(|segment| match segment {
+            Segment::Seq { index } => Some(index.to_string()),
+            Segment::Map { key } => Some(escape_pointer_token(key)),
+            Segment::Enum { variant } => Some(escape_pointer_token(variant)),
+            Segment::Unknown => None,
+        })
+        .fold(String::new(), |mut pointer, token| {
+            pointer.push('/');
+            pointer.push_str(&token);
+            pointer
+        })
+}
+
+fn escape_pointer_token(token: &str) -> String {
+    token.replace('~', "~0").replace('/', "~1")
+}
+
+/// The backtick-quoted names in a serde "expected" list.
+fn backticked(list: &str) -> Vec<String> {
+    list.split('`')
+        .skip(1)
+        .step_by(2)
+        .map(ToString::to_string)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::body::to_bytes;
+    use serde_json::Value;
+
+    use super::*;
+    use crate::routes::{accounts::CreateAccountRequest, transactions::CreateTransactionRequest};
+
+    async fn rejection<T: DeserializeOwned + fmt::Debug>(body: &Value) -> (StatusCode, Value) {
+        let response = parse_body::<T>(body.to_string().as_bytes()).unwrap_err();
+        let status = response.status();
+        let bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        (status, serde_json::from_slice(&bytes).unwrap())
+    }
+
+    fn transaction(entries: &Value) -> Value {
+        json!({
+            "type": "journal",
+            "transaction_date": "2026-01-15",
+            "description": "Office rent",
+            "entries": entries
+        })
+    }
+
+    fn entry(entry_type: &str) -> Value {
+        json!({
+            "account_id": "6f1c5a0e-3c6a-4b7e-9a51-2a0f1e5d8c11",
+            "source_currency": "USD",
+            "source_amount": "100.00",
+            "entry_type": entry_type
+        })
+    }
+
+    #[tokio::test]
+    async fn test_unknown_field_points_at_field_and_lists_known_ones() {
+        let mut typo = entry("credit");
+        typo["amount"] = typo["source_amount"].take();
+        typo.as_object_mut().unwrap().remove("source_amount");
+        let body = transaction(&json!([entry("debit"), typo]));
+
+        let (status, body) = rejection::<CreateTransactionRequest>(&body).await;
+
+        assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(body["error"], "validation_failed");
+        let field = &body["fields"][0];
+        assert_eq!(field["pointer"], "/entries/1/amount");
+        assert_eq!(field["message"], "unknown field");
+        let allowed: Vec<&str> = field["allowed"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .filter_map(Value::as_str)
+            .collect();
+        assert!(allowed.contains(&"source_amount"));
+    }
+
+    #[tokio::test]
+    async fn test_wrong_type_reports_expected_type() {
+        let mut numeric = entry("debit");
+        numeric["source_amount"] = json!(100);
+        let body = transaction(&json!([numeric, entry("credit")]));
+
+        let (status, body) = rejection::<CreateTransactionRequest>(&body).await;
+
+        assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+        let field = &body["fields"][0];
+        assert_eq!(field["pointer"], "/entries/0/source_amount");
+        assert_eq!(field["message"], "invalid type: integer `100`");
+        assert_eq!(field["expected"], "a string");
+    }
+
+    #[tokio::test]
+    async fn test_invalid_enum_value_lists_allowed_values() {
+        let body = transaction(&json!([entry("debit"), entry("cedit")]));
+        let (status, body) = rejection::<CreateTransactionRequest>(&body).await;
+        assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(body["fields"][0]["pointer"], "/entries/1/entry_type");
+        assert_eq!(body["fields"][0]["allowed"], json!(["debit", "credit"]));
+
+        let body = json!({
+            "code": "1000",
+            "name": "Cash",
+            "type": "assets",
+            "currency": "USD"
+        });
+        let (status, body) = rejection::<CreateAccountRequest>(&body).await;
+        assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+        let field = &body["fields"][0];
+        assert_eq!(field["pointer"], "/type");
+        assert_eq!(field["message"], "invalid value: string \"assets\"");
+        assert_eq!(
+            field["allowed"],
+            json!(["asset", "liability", "equity", "revenue", "expense"])
+        );
+    }
+
+    #[tokio::test]
+    async fn test_missing_field_and_malformed_json() {
+        let body = json!({ "type": "journal", "description": "Rent", "entries": [] });
+        let (status, body) = rejection::<CreateTransactionRequest>(&body).await;
+        assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(body["fields"][0]["pointer"], "/transaction_date");
+        assert_eq!(body["fields"][0]["message"], "missing field");
+
+        let response = parse_body::<CreateTransactionRequest>(b"{\"type\": ").unwrap_err();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+}
@@ -1,4 +1,5 @@
 //! Custom Axum extractors.
 
-// Extractor modules will be added as needed
-// pub mod claims;
+pub mod json;
+
+pub use json::{BodyFieldError, StrictJson};
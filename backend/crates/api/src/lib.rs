@@ -5,7 +5,9 @@
 //! - Authentication middleware
 //! - Request extractors
 //! - Response types
+//! - Dashboard section and membership caching
 
+pub mod cache;
 pub mod extractors;
 pub mod middleware;
 pub mod routes;
@@ -15,8 +17,16 @@ use sea_orm::DatabaseConnection;
 use std::sync::Arc;
 use tower_http::cors::{Any, CorsLayer};
 use tower_http::trace::TraceLayer;
+use zeltra_core::currency::CurrencyRegistry;
 use zeltra_core::storage::StorageService;
-use zeltra_shared::{EmailService, JwtService};
+use zeltra_db::repositories::Stores;
+use zeltra_jobs::JobBoard;
+use zeltra_shared::{AdminConfig, EmailService, JwtService};
+
+use crate::cache::{DashboardCache, MembershipCache};
+use crate::middleware::{
+    BodyLimits, Metrics, REQUEST_ID_HEADER, assign_request_id, make_request_span, track_metrics,
+};
 
 /// Application state shared across handlers.
 #[derive(Clone)]
@@ -29,18 +39,73 @@ pub struct AppState {
     pub email_service: Arc<EmailService>,
     /// Storage service for file attachments (optional).
     pub storage: Option<Arc<StorageService>>,
+    /// Prometheus metrics handle; `None` hides `/metrics` on this router.
+    pub metrics: Option<Arc<Metrics>>,
+    /// Background job status board (optional).
+    pub jobs: Option<JobBoard>,
+    /// Operator access configuration for `/admin` routes.
+    pub admin: AdminConfig,
+    /// Per-organization cache of dashboard sections.
+    pub dashboard_cache: DashboardCache,
+    /// Cache of organization memberships used by the route guards.
+    pub membership_cache: MembershipCache,
+    /// Decimal places of every currency, loaded at startup.
+    pub currencies: Arc<CurrencyRegistry>,
+    /// Repositories used by route handlers, replaceable with fakes in tests.
+    pub stores: Stores,
 }
 
 /// Creates the main application router.
-pub fn create_router(state: AppState) -> Router {
+pub fn create_router(state: AppState, limits: BodyLimits) -> Router {
     Router::new()
-        .nest("/api/v1", routes::api_routes_with_state(state.clone()))
-        .layer(TraceLayer::new_for_http())
+        .merge(routes::jwks::routes())
+        .merge(routes::metrics::routes())
+        .nest(
+            "/api/v1",
+            routes::api_routes_with_state(state.clone(), limits),
+        )
+        .layer(axum::middleware::from_fn(track_metrics))
+        .layer(TraceLayer::new_for_http().make_span_with(make_request_span))
+        .layer(axum::middleware::from_fn(assign_request_id))
         .layer(
             CorsLayer::new()
                 .allow_origin(Any)
                 .allow_methods(Any)
-                .allow_headers(Any),
+                .allow_headers(Any)
+                .expose_headers([REQUEST_ID_HEADER]),
         )
         .with_state(state)
 }
+
+#[cfg(test)]
+impl zeltra_test_support::TestAppState for AppState {
+    fn from_test_db(test_db: &zeltra_test_support::TestDb) -> Self {
+        Self {
+            db: Arc::new(test_db.conn().clone()),
+            jwt_service: Arc::new(zeltra_test_support::jwt_service()),
+            email_service: Arc::new(EmailService::new(zeltra_shared::EmailConfig::default())),
+            storage: None,
+            metrics: None,
+            jobs: None,
+            admin: AdminConfig::default(),
+            dashboard_cache: DashboardCache::default(),
+            membership_cache: MembershipCache::default(),
+            currencies: Arc::default(),
+            stores: Stores::postgres(test_db.conn()),
+        }
+    }
+}
+
+#[cfg(test)]
+impl AppState {
+    /// Serves `routes` behind the authentication middleware, as the API
+    /// router does.
+    pub(crate) fn authenticated(&self, routes: Router<Self>) -> Router {
+        routes
+            .layer(axum::middleware::from_fn_with_state(
+                self.clone(),
+                middleware::auth_middleware,
+            ))
+            .with_state(self.clone())
+    }
+}
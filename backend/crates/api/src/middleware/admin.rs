@@ -0,0 +1,92 @@
+//! Operator access guard for `/admin` routes.
+//!
+//! A request is let through when it carries either the configured admin
+//! token, or an access token of an owner of the configured admin organization.
+//! Either way the request gets an [`AdminOperator`] naming who sent it, for
+//! the operator audit log.
+
+use axum::{
+    Json,
+    extract::{Request, State},
+    http::{StatusCode, header::AUTHORIZATION},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use serde_json::json;
+use subtle::ConstantTimeEq;
+
+use super::auth::{check_token_version, extract_bearer_token};
+use crate::AppState;
+
+/// Who passed [`require_admin`], as recorded in the operator audit log.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AdminOperator(pub String);
+
+/// Rejects requests that are neither operator-token nor admin-org owner requests.
+pub async fn require_admin(
+    State(state): State<AppState>,
+    mut request: Request,
+    next: Next,
+) -> Response {
+    let bearer = request
+        .headers()
+        .get(AUTHORIZATION)
+        .and_then(|h| h.to_str().ok())
+        .and_then(extract_bearer_token);
+
+    let Some(token) = bearer else {
+        return (
+            StatusCode::UNAUTHORIZED,
+            Json(json!({
+                "error": "missing_token",
+                "message": "Authorization header with Bearer token is required"
+            })),
+        )
+            .into_response();
+    };
+
+    if state
+        .admin
+        .token
+        .as_deref()
+        .is_some_and(|expected| bool::from(expected.as_bytes().ct_eq(token.as_bytes())))
+    {
+        request
+            .extensions_mut()
+            .insert(AdminOperator("admin token".to_string()));
+        return next.run(request).await;
+    }
+
+    let Ok(claims) = state.jwt_service.validate_token(token) else {
+        return (
+            StatusCode::UNAUTHORIZED,
+            Json(json!({
+                "error": "invalid_token",
+                "message": "Invalid or malformed token"
+            })),
+        )
+            .into_response();
+    };
+
+    if let Err(response) = check_token_version(&state, &claims).await {
+        return response;
+    }
+
+    let is_admin_owner =
+        state.admin.organization_id == Some(claims.organization_id()) && claims.role == "owner";
+    if !is_admin_owner {
+        return (
+            StatusCode::FORBIDDEN,
+            Json(json!({
+                "error": "forbidden",
+                "message": "Admin access required"
+            })),
+        )
+            .into_response();
+    }
+
+    request
+        .extensions_mut()
+        .insert(AdminOperator(format!("user {}", claims.user_id())));
+    next.run(request).await
+}
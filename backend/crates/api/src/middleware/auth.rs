@@ -7,13 +7,91 @@ use axum::{
     middleware::Next,
     response::{IntoResponse, Response},
 };
+use chrono::Utc;
+use rust_decimal::Decimal;
 use serde_json::json;
+use tracing::{error, warn};
+use uuid::Uuid;
 
 use crate::AppState;
+use crate::cache::Membership;
+use crate::middleware::record_user;
+use zeltra_core::auth::{API_KEY_PREFIX, ApiKeyRole, UserRole as CoreUserRole, route_access};
+use zeltra_db::{
+    UserRepository,
+    entities::sea_orm_active_enums::UserRole,
+    repositories::{
+        ApiKeyRepository, Feature, SubscriptionRepository, organization::get_role_level,
+    },
+};
 use zeltra_shared::Claims;
 
+/// Checks that the user is a member of the organization and returns their
+/// membership.
+///
+/// Goes through the [`MembershipCache`](crate::cache::MembershipCache), so
+/// repeated requests from the same member need no query. Non-members get
+/// 403 and lookup failures 500.
+pub(crate) async fn check_membership(
+    state: &AppState,
+    org_id: impl Into<Uuid>,
+    user_id: Uuid,
+) -> Result<Membership, Response> {
+    match state
+        .membership_cache
+        .get(state.stores.organizations.as_ref(), org_id.into(), user_id)
+        .await
+    {
+        Ok(Some(membership)) => Ok(membership),
+        Ok(None) => Err((
+            StatusCode::FORBIDDEN,
+            Json(json!({
+                "error": "forbidden",
+                "message": "You are not a member of this organization"
+            })),
+        )
+            .into_response()),
+        Err(e) => {
+            error!(error = %e, "Database error checking membership");
+            Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({
+                    "error": "internal_error",
+                    "message": "An error occurred"
+                })),
+            )
+                .into_response())
+        }
+    }
+}
+
+/// Checks that the user may read reports and the ledger in the organization.
+///
+/// Submitters see only their own transactions, so they get 403
+/// `report_access_restricted`; non-members get 403 as in
+/// [`check_membership`].
+pub(crate) async fn check_report_access(
+    state: &AppState,
+    org_id: impl Into<Uuid>,
+    auth: &AuthMember,
+) -> Result<(), Response> {
+    let role = auth.role_in(state, org_id).await?;
+    if role.can_view_reports() {
+        return Ok(());
+    }
+
+    Err((
+        StatusCode::FORBIDDEN,
+        Json(json!({
+            "error": "report_access_restricted",
+            "message": "Submitters cannot view organization reports"
+        })),
+    )
+        .into_response())
+}
+
 /// Extracts the bearer token from the Authorization header.
-fn extract_bearer_token(header: &str) -> Option<&str> {
+pub(crate) fn extract_bearer_token(header: &str) -> Option<&str> {
     header
         .strip_prefix("Bearer ")
         .or_else(|| header.strip_prefix("bearer "))
@@ -24,7 +102,12 @@ fn extract_bearer_token(header: &str) -> Option<&str> {
 /// This middleware:
 /// 1. Extracts the Bearer token from the Authorization header
 /// 2. Validates the token using the JWT service
-/// 3. Stores the claims in request extensions for handlers to access
+/// 3. Rejects tokens issued before the user's current token version
+/// 4. Stores the claims in request extensions for handlers to access
+///
+/// Tokens without a version (issued before versioning existed) skip step 3
+/// until they expire. Tokens starting with `zk_live_` are organization API
+/// keys and are handled by [`authenticate_api_key`] instead.
 pub async fn auth_middleware(
     State(state): State<AppState>,
     mut request: Request,
@@ -47,10 +130,20 @@ pub async fn auth_middleware(
             .into_response();
     };
 
+    if token.starts_with(API_KEY_PREFIX) {
+        let key = token.to_owned();
+        return authenticate_api_key(&state, request, next, &key).await;
+    }
+
     // Validate token
     match state.jwt_service.validate_token(token) {
         Ok(claims) => {
+            if let Err(response) = check_token_version(&state, &claims).await {
+                return response;
+            }
+
             // Store claims in request extensions
+            record_user(claims.user_id());
             request.extensions_mut().insert(claims);
             next.run(request).await
         }
@@ -73,6 +166,143 @@ pub async fn auth_middleware(
     }
 }
 
+/// Authenticates a request made with an organization API key.
+///
+/// The key must be active, its organization's plan must include API access,
+/// and the route must be one its scopes open within its own organization.
+/// Handlers then see claims for the key's creator with the key's acting role
+/// and scopes.
+async fn authenticate_api_key(
+    state: &AppState,
+    mut request: Request,
+    next: Next,
+    key: &str,
+) -> Response {
+    let reject = |status: StatusCode, error: &str, message: String| {
+        (status, Json(json!({ "error": error, "message": message }))).into_response()
+    };
+    let internal_error = || {
+        reject(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "internal_error",
+            "An error occurred".to_string(),
+        )
+    };
+
+    let api_key = match ApiKeyRepository::new((*state.db).clone())
+        .authenticate(key)
+        .await
+    {
+        Ok(Some(api_key)) => api_key,
+        Ok(None) => {
+            return reject(
+                StatusCode::UNAUTHORIZED,
+                "invalid_api_key",
+                "API key is invalid, expired or revoked".to_string(),
+            );
+        }
+        Err(e) => {
+            error!(error = %e, "Database error looking up API key");
+            return internal_error();
+        }
+    };
+    let Ok(role) = api_key.role.parse::<ApiKeyRole>() else {
+        error!(api_key_id = %api_key.id, role = %api_key.role, "API key has an unknown role");
+        return internal_error();
+    };
+
+    match SubscriptionRepository::has_feature(
+        &state.db,
+        api_key.organization_id,
+        Feature::ApiAccess,
+    )
+    .await
+    {
+        Ok(true) => {}
+        Ok(false) => {
+            return reject(
+                StatusCode::FORBIDDEN,
+                "feature_not_available",
+                "API access is not included in the organization's plan".to_string(),
+            );
+        }
+        Err(e) => {
+            error!(error = %e, "Database error checking API access");
+            return internal_error();
+        }
+    }
+
+    let Some(access) = route_access(request.method().as_str(), request.uri().path()) else {
+        return reject(
+            StatusCode::FORBIDDEN,
+            "api_key_not_allowed",
+            "This endpoint cannot be called with an API key".to_string(),
+        );
+    };
+    if access.organization_id != api_key.organization_id {
+        return reject(
+            StatusCode::FORBIDDEN,
+            "forbidden",
+            "API key belongs to another organization".to_string(),
+        );
+    }
+    if !api_key.scopes.iter().any(|s| s == access.scope.as_str()) {
+        return reject(
+            StatusCode::FORBIDDEN,
+            "insufficient_scope",
+            format!("API key lacks the {} scope", access.scope),
+        );
+    }
+
+    if let Err(e) =
+        SubscriptionRepository::increment_api_call_count(&state.db, api_key.organization_id).await
+    {
+        warn!(error = %e, "Failed to count API call");
+    }
+
+    let claims = Claims::for_api_key(
+        api_key.created_by,
+        api_key.organization_id,
+        &role.acting_role().to_string(),
+        api_key.scopes,
+        Utc::now(),
+    );
+    record_user(claims.user_id());
+    request.extensions_mut().insert(claims);
+    next.run(request).await
+}
+
+/// Rejects tokens whose version no longer matches the user's.
+pub(crate) async fn check_token_version(state: &AppState, claims: &Claims) -> Result<(), Response> {
+    let Some(version) = claims.token_version() else {
+        return Ok(());
+    };
+
+    let user_repo = UserRepository::new((*state.db).clone());
+    match user_repo.get_token_version(claims.user_id()).await {
+        Ok(Some(current)) if current == version => Ok(()),
+        Ok(_) => Err((
+            StatusCode::UNAUTHORIZED,
+            Json(json!({
+                "error": "token_revoked",
+                "message": "Token has been revoked, please refresh"
+            })),
+        )
+            .into_response()),
+        Err(e) => {
+            error!(error = %e, "Database error checking token version");
+            Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({
+                    "error": "internal_error",
+                    "message": "An error occurred"
+                })),
+            )
+                .into_response())
+        }
+    }
+}
+
 /// Extractor for authenticated user claims.
 ///
 /// Use this in handlers to get the authenticated user's claims:
@@ -136,15 +366,161 @@ where
     }
 }
 
+/// Extractor exposing the organization membership carried in the token.
+///
+/// Role and approval limit are snapshots from when the token was issued and
+/// only apply to the token's organization. Membership changes bump the user's
+/// token version, so stale tokens are rejected by [`auth_middleware`] and the
+/// new values take effect at the next refresh. Handlers that need fresh data
+/// should still query the database.
+#[derive(Debug, Clone)]
+pub struct AuthMember {
+    claims: Claims,
+    role: UserRole,
+}
+
+impl AuthMember {
+    /// Returns the user ID from the claims.
+    #[must_use]
+    pub fn user_id(&self) -> Uuid {
+        self.claims.user_id()
+    }
+
+    /// Returns the organization the token was issued for.
+    #[must_use]
+    pub fn organization_id(&self) -> Uuid {
+        self.claims.organization_id()
+    }
+
+    /// Returns the user's role in the token's organization.
+    #[must_use]
+    pub const fn role(&self) -> &UserRole {
+        &self.role
+    }
+
+    /// Returns the user's approval limit in the token's organization.
+    #[must_use]
+    pub const fn approval_limit(&self) -> Option<Decimal> {
+        self.claims.approval_limit
+    }
+
+    /// Checks whether the user has `required` or a higher role in `org_id`.
+    ///
+    /// Returns `None` if the token was issued for another organization, in
+    /// which case the caller has to look the membership up.
+    #[must_use]
+    pub fn has_role(&self, org_id: impl Into<Uuid>, required: &UserRole) -> Option<bool> {
+        (org_id.into() == self.organization_id())
+            .then(|| get_role_level(&self.role) >= get_role_level(required))
+    }
+
+    /// Resolves the user's role in `org_id`.
+    ///
+    /// Uses the token's role when it was issued for `org_id`, so the common
+    /// case needs no query; otherwise checks the membership through
+    /// [`check_membership`] and rejects non-members with 403.
+    pub(crate) async fn role_in(
+        &self,
+        state: &AppState,
+        org_id: impl Into<Uuid>,
+    ) -> Result<CoreUserRole, Response> {
+        let org_id = org_id.into();
+        if org_id == self.organization_id() {
+            return Ok(to_core_role(&self.role));
+        }
+
+        let membership = check_membership(state, org_id, self.user_id()).await?;
+        Ok(to_core_role(&membership.role))
+    }
+
+    /// Returns the inner claims.
+    #[must_use]
+    pub const fn claims(&self) -> &Claims {
+        &self.claims
+    }
+}
+
+impl<S> FromRequestParts<S> for AuthMember
+where
+    S: Send + Sync,
+{
+    type Rejection = (StatusCode, Json<serde_json::Value>);
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let AuthUser(claims) = AuthUser::from_request_parts(parts, state).await?;
+        let role = parse_role(&claims.role).ok_or_else(|| {
+            (
+                StatusCode::UNAUTHORIZED,
+                Json(json!({
+                    "error": "invalid_token",
+                    "message": "Token carries an unknown role"
+                })),
+            )
+        })?;
+
+        Ok(Self { claims, role })
+    }
+}
+
+/// Parses a role claim.
+fn parse_role(s: &str) -> Option<UserRole> {
+    match s {
+        "owner" => Some(UserRole::Owner),
+        "admin" => Some(UserRole::Admin),
+        "approver" => Some(UserRole::Approver),
+        "accountant" => Some(UserRole::Accountant),
+        "submitter" => Some(UserRole::Submitter),
+        "viewer" => Some(UserRole::Viewer),
+        _ => None,
+    }
+}
+
+/// Maps a stored role to the role used for permission checks.
+pub(crate) const fn to_core_role(role: &UserRole) -> CoreUserRole {
+    match role {
+        UserRole::Owner => CoreUserRole::Owner,
+        UserRole::Admin => CoreUserRole::Admin,
+        UserRole::Accountant => CoreUserRole::Accountant,
+        UserRole::Approver => CoreUserRole::Approver,
+        UserRole::Viewer => CoreUserRole::Viewer,
+        UserRole::Submitter => CoreUserRole::Submitter,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::cache::{DashboardCache, MembershipCache};
+    use async_trait::async_trait;
     use axum::{Router, body::Body, http::Request, middleware::from_fn_with_state};
-    use sea_orm::DatabaseConnection;
+    use sea_orm::{DatabaseConnection, DbErr};
     use std::sync::Arc;
     use tower::ServiceExt;
-    use uuid::Uuid;
-    use zeltra_shared::{EmailConfig, EmailService, JwtConfig, JwtService};
+    use zeltra_db::entities::{organization_users, organizations};
+    use zeltra_db::repositories::{OrganizationStore, Stores};
+    use zeltra_shared::{AdminConfig, EmailConfig, EmailService, JwtConfig, JwtService};
+
+    /// Organization store in which nobody is a member.
+    struct NoMemberships;
+
+    #[async_trait]
+    impl OrganizationStore for NoMemberships {
+        async fn find_by_id(&self, _id: Uuid) -> Result<Option<organizations::Model>, DbErr> {
+            Ok(None)
+        }
+
+        async fn is_member(&self, _org_id: Uuid, _user_id: Uuid) -> Result<bool, DbErr> {
+            Ok(false)
+        }
+
+        async fn get_user_membership(
+            &self,
+            _org_id: Uuid,
+            _user_id: Uuid,
+        ) -> Result<Option<organization_users::Model>, DbErr> {
+            Ok(None)
+        }
+    }
 
     // Helper to create a test AppState
     fn create_test_state() -> AppState {
@@ -154,10 +530,17 @@ mod tests {
         let email_service = EmailService::new(EmailConfig::default());
 
         AppState {
+            stores: Stores::postgres(&db),
             db: Arc::new(db),
             jwt_service: Arc::new(jwt_service),
             email_service: Arc::new(email_service),
             storage: None,
+            metrics: None,
+            jobs: None,
+            admin: AdminConfig::default(),
+            dashboard_cache: DashboardCache::default(),
+            membership_cache: MembershipCache::default(),
+            currencies: Arc::default(),
         }
     }
 
@@ -188,6 +571,85 @@ mod tests {
         assert_eq!(auth_user.claims().user_id(), user_id);
     }
 
+    #[test]
+    fn test_auth_member_role_checks() {
+        let org_id = Uuid::new_v4();
+        let claims = Claims::new(
+            Uuid::new_v4(),
+            org_id,
+            "accountant",
+            chrono::Utc::now() + chrono::Duration::hours(1),
+        );
+        let member = AuthMember {
+            role: parse_role(&claims.role).unwrap(),
+            claims,
+        };
+
+        assert_eq!(member.has_role(org_id, &UserRole::Viewer), Some(true));
+        assert_eq!(member.has_role(org_id, &UserRole::Accountant), Some(true));
+        assert_eq!(member.has_role(org_id, &UserRole::Admin), Some(false));
+        assert_eq!(member.has_role(Uuid::new_v4(), &UserRole::Viewer), None);
+        assert_eq!(member.approval_limit(), None);
+    }
+
+    #[tokio::test]
+    async fn test_role_in_token_org_needs_no_query() {
+        let org_id = Uuid::new_v4();
+        let claims = Claims::new(
+            Uuid::new_v4(),
+            org_id,
+            "submitter",
+            chrono::Utc::now() + chrono::Duration::hours(1),
+        );
+        let member = AuthMember {
+            role: parse_role(&claims.role).unwrap(),
+            claims,
+        };
+        // The test state's disconnected database would fail any lookup
+        let mut state = create_test_state();
+
+        let role = member.role_in(&state, org_id).await.unwrap();
+        assert_eq!(role, CoreUserRole::Submitter);
+        assert!(!role.can_view_all_transactions());
+
+        state.stores.organizations = Arc::new(NoMemberships);
+        let denied = member.role_in(&state, Uuid::new_v4()).await.unwrap_err();
+        assert_eq!(denied.status(), StatusCode::FORBIDDEN);
+    }
+
+    #[test]
+    fn test_parse_role() {
+        assert_eq!(parse_role("owner"), Some(UserRole::Owner));
+        assert_eq!(parse_role("submitter"), Some(UserRole::Submitter));
+        assert_eq!(parse_role("superuser"), None);
+    }
+
+    #[tokio::test]
+    async fn test_auth_member_rejects_unknown_role() {
+        let state = create_test_state();
+        let token = state
+            .jwt_service
+            .generate_access_token(Uuid::new_v4(), Uuid::new_v4(), "superuser")
+            .unwrap();
+
+        let app = Router::new()
+            .route("/", axum::routing::get(|_: AuthMember| async { "OK" }))
+            .layer(from_fn_with_state(state, auth_middleware));
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .uri("/")
+                    .header(AUTHORIZATION, format!("Bearer {token}"))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    }
+
     #[tokio::test]
     async fn test_auth_middleware_missing_token() {
         let state = create_test_state();
@@ -0,0 +1,253 @@
+//! Request body size limits.
+//!
+//! Bodies are capped per route group: regular endpoints get a small default
+//! and import endpoints a larger one. Oversized requests are rejected with
+//! `413 Payload Too Large` and the usual structured error body, whether the
+//! size is known from `Content-Length` or only discovered while buffering.
+//!
+//! Nesting depth needs no extra guard: `serde_json` stops at 128 levels, so
+//! the size cap bounds the remaining parsing cost.
+
+use axum::{
+    Json, Router,
+    extract::DefaultBodyLimit,
+    http::{StatusCode, header::CONTENT_TYPE},
+    middleware::map_response,
+    response::{IntoResponse, Response},
+};
+use serde_json::json;
+use tower_http::limit::RequestBodyLimitLayer;
+use zeltra_shared::config::ServerConfig;
+
+/// Request body size limits applied by the router.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BodyLimits {
+    /// Limit for regular endpoints, in bytes.
+    pub default_bytes: usize,
+    /// Limit for import endpoints, in bytes.
+    pub import_bytes: usize,
+    /// Limit for organization backup import, in bytes.
+    pub backup_bytes: usize,
+}
+
+impl Default for BodyLimits {
+    fn default() -> Self {
+        Self {
+            default_bytes: 2 * 1024 * 1024,
+            import_bytes: 10 * 1024 * 1024,
+            backup_bytes: 1024 * 1024 * 1024,
+        }
+    }
+}
+
+impl From<&ServerConfig> for BodyLimits {
+    fn from(config: &ServerConfig) -> Self {
+        Self {
+            default_bytes: config.body_limit_bytes,
+            import_bytes: config.import_body_limit_bytes,
+            backup_bytes: config.backup_body_limit_bytes,
+        }
+    }
+}
+
+/// Rejects request bodies larger than `limit` bytes on every route of `router`.
+pub fn limit_body<S>(router: Router<S>, limit: usize) -> Router<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    router
+        // Extractors enforce their own 2 MiB default unless told otherwise
+        .layer(DefaultBodyLimit::max(limit))
+        .layer(RequestBodyLimitLayer::new(limit))
+        .layer(map_response(move |response: Response| async move {
+            payload_too_large(response, limit)
+        }))
+}
+
+/// Replaces plain-text 413 responses with the structured error body.
+fn payload_too_large(response: Response, limit: usize) -> Response {
+    let is_json = response
+        .headers()
+        .get(CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|v| v.starts_with("application/json"));
+
+    if response.status() != StatusCode::PAYLOAD_TOO_LARGE || is_json {
+        return response;
+    }
+
+    (
+        StatusCode::PAYLOAD_TOO_LARGE,
+        Json(json!({
+            "error": "payload_too_large",
+            "message": format!("Request body must not exceed {limit} bytes")
+        })),
+    )
+        .into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{
+        body::{Body, to_bytes},
+        http::{Request, header::AUTHORIZATION},
+        routing::post,
+    };
+    use sea_orm::DatabaseConnection;
+    use std::sync::Arc;
+    use tower::ServiceExt;
+    use uuid::Uuid;
+    use zeltra_db::repositories::Stores;
+    use zeltra_shared::{AdminConfig, EmailConfig, EmailService, JwtConfig, JwtService};
+
+    use crate::{
+        AppState,
+        cache::{DashboardCache, MembershipCache},
+        create_router,
+    };
+
+    fn echo_router(limit: usize) -> Router {
+        limit_body(
+            Router::new().route(
+                "/",
+                post(|Json(body): Json<serde_json::Value>| async move { Json(body) }),
+            ),
+            limit,
+        )
+    }
+
+    fn json_request(uri: &str, body: String) -> Request<Body> {
+        Request::builder()
+            .method("POST")
+            .uri(uri)
+            .header(CONTENT_TYPE, "application/json")
+            .body(Body::from(body))
+            .unwrap()
+    }
+
+    /// A JSON object whose serialized form is roughly `size` bytes.
+    fn json_of_size(size: usize) -> String {
+        json!({ "padding": "x".repeat(size) }).to_string()
+    }
+
+    async fn error_code(response: Response) -> String {
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let value: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        value["error"].as_str().unwrap().to_string()
+    }
+
+    #[tokio::test]
+    async fn test_body_within_limit_is_accepted() {
+        let response = echo_router(1024)
+            .oneshot(json_request("/", json_of_size(512)))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_oversized_body_returns_structured_413() {
+        let response = echo_router(1024)
+            .oneshot(json_request("/", json_of_size(4096)))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+        assert_eq!(error_code(response).await, "payload_too_large");
+    }
+
+    #[tokio::test]
+    async fn test_oversized_body_without_content_length_returns_413() {
+        let chunks = vec![Ok::<_, std::io::Error>(json_of_size(4096))];
+        let request = Request::builder()
+            .method("POST")
+            .uri("/")
+            .header(CONTENT_TYPE, "application/json")
+            .body(Body::from_stream(futures::stream::iter(chunks)))
+            .unwrap();
+
+        let response = echo_router(1024).oneshot(request).await.unwrap();
+
+        assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+        assert_eq!(error_code(response).await, "payload_too_large");
+    }
+
+    #[tokio::test]
+    async fn test_limit_above_extractor_default_is_honoured() {
+        let response = echo_router(4 * 1024 * 1024)
+            .oneshot(json_request("/", json_of_size(3 * 1024 * 1024)))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    fn test_state() -> AppState {
+        AppState {
+            db: Arc::new(DatabaseConnection::Disconnected),
+            jwt_service: Arc::new(JwtService::new(JwtConfig::default())),
+            email_service: Arc::new(EmailService::new(EmailConfig::default())),
+            storage: None,
+            metrics: None,
+            jobs: None,
+            admin: AdminConfig::default(),
+            dashboard_cache: DashboardCache::default(),
+            membership_cache: MembershipCache::default(),
+            currencies: Arc::default(),
+            stores: Stores::postgres(&DatabaseConnection::Disconnected),
+        }
+    }
+
+    fn authed_request(state: &AppState, uri: &str, body: String) -> Request<Body> {
+        let token = state
+            .jwt_service
+            .generate_access_token(Uuid::new_v4(), Uuid::new_v4(), "owner")
+            .unwrap();
+        let mut request = json_request(uri, body);
+        request
+            .headers_mut()
+            .insert(AUTHORIZATION, format!("Bearer {token}").parse().unwrap());
+        request
+    }
+
+    #[tokio::test]
+    async fn test_router_rejects_oversized_transaction_payload() {
+        let state = test_state();
+        let limits = BodyLimits {
+            default_bytes: 1024,
+            import_bytes: 8192,
+            backup_bytes: 8192,
+        };
+        let uri = format!("/api/v1/organizations/{}/transactions", Uuid::new_v4());
+        let request = authed_request(&state, &uri, json_of_size(4096));
+
+        let response = create_router(state, limits).oneshot(request).await.unwrap();
+
+        assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+        assert_eq!(error_code(response).await, "payload_too_large");
+    }
+
+    #[tokio::test]
+    async fn test_router_applies_import_limit_to_bank_import() {
+        let state = test_state();
+        let limits = BodyLimits {
+            default_bytes: 1024,
+            import_bytes: 8192,
+            backup_bytes: 8192,
+        };
+        let uri = format!("/api/v1/organizations/{}/bank-import", Uuid::new_v4());
+
+        let request = authed_request(&state, &uri, json_of_size(4096));
+        let response = create_router(state.clone(), limits)
+            .oneshot(request)
+            .await
+            .unwrap();
+        assert_ne!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+
+        let request = authed_request(&state, &uri, json_of_size(16384));
+        let response = create_router(state, limits).oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+    }
+}
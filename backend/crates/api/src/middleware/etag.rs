@@ -0,0 +1,277 @@
+//! Conditional GET support for expensive read endpoints.
+//!
+//! Handlers derive an [`ETag`] from cheap inputs (the request parameters and
+//! the organization's [`DataVersion`]) and hand the expensive part of the
+//! response to [`respond_cached`]. When the client already holds the current
+//! representation the body is never built and `304 Not Modified` is returned.
+
+use std::future::Future;
+
+use axum::{
+    Json,
+    http::{
+        HeaderMap, HeaderValue, StatusCode,
+        header::{CACHE_CONTROL, ETAG, IF_NONE_MATCH, LAST_MODIFIED},
+    },
+    response::{IntoResponse, Response},
+};
+use chrono::{DateTime, TimeZone, Utc};
+use sea_orm::DatabaseConnection;
+use serde_json::json;
+use sha2::{Digest, Sha256};
+use tracing::error;
+use uuid::Uuid;
+use zeltra_db::repositories::{DataVersion, ReportRepository};
+
+/// Responses may be cached by the client only, and must be revalidated.
+const CACHE_POLICY: &str = "private, no-cache";
+
+/// An entity tag identifying one representation of a resource.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ETag(String);
+
+impl ETag {
+    /// Derives a strong tag from the given parts.
+    ///
+    /// Parts are length-prefixed before hashing, so `["ab", "c"]` and
+    /// `["a", "bc"]` give different tags.
+    pub fn from_parts<I, T>(parts: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+        T: AsRef<[u8]>,
+    {
+        let mut hasher = Sha256::new();
+        for part in parts {
+            let part = part.as_ref();
+            hasher.update((part.len() as u64).to_be_bytes());
+            hasher.update(part);
+        }
+        let digest = format!("{:x}", hasher.finalize());
+        Self(format!("\"{}\"", &digest[..32]))
+    }
+
+    /// Returns the tag in header form, including quotes.
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Checks the tag against an `If-None-Match` header value.
+    ///
+    /// Uses weak comparison, as RFC 9110 requires for `If-None-Match`.
+    #[must_use]
+    pub fn matches(&self, if_none_match: &str) -> bool {
+        if_none_match.split(',').map(str::trim).any(|candidate| {
+            candidate == "*" || candidate.strip_prefix("W/").unwrap_or(candidate) == self.0
+        })
+    }
+}
+
+/// Serves `body_fn` with an `ETag`, or `304 Not Modified` if the client's
+/// `If-None-Match` already names `etag`.
+///
+/// `body_fn` is only called when the body is needed. Headers are only added
+/// to successful responses, so errors are never cached.
+pub async fn respond_cached<F, Fut, R>(headers: &HeaderMap, etag: &ETag, body_fn: F) -> Response
+where
+    F: FnOnce() -> Fut,
+    Fut: Future<Output = R>,
+    R: IntoResponse,
+{
+    let not_modified = headers
+        .get_all(IF_NONE_MATCH)
+        .iter()
+        .filter_map(|v| v.to_str().ok())
+        .any(|v| etag.matches(v));
+
+    let mut response = if not_modified {
+        StatusCode::NOT_MODIFIED.into_response()
+    } else {
+        body_fn().await.into_response()
+    };
+
+    if response.status().is_success() || response.status() == StatusCode::NOT_MODIFIED {
+        if let Ok(value) = HeaderValue::from_str(etag.as_str()) {
+            response.headers_mut().insert(ETAG, value);
+        }
+        response
+            .headers_mut()
+            .insert(CACHE_CONTROL, HeaderValue::from_static(CACHE_POLICY));
+    }
+
+    response
+}
+
+/// Adds a `Last-Modified` header naming when a single resource last changed.
+///
+/// Only successful responses get the header. HTTP dates have whole-second
+/// precision, so clients syncing incrementally should compare the
+/// resource's `updated_at` instead.
+pub fn with_last_modified<Tz: TimeZone>(
+    mut response: Response,
+    updated_at: &DateTime<Tz>,
+) -> Response {
+    if response.status().is_success() {
+        let http_date = updated_at
+            .with_timezone(&Utc)
+            .format("%a, %d %b %Y %H:%M:%S GMT")
+            .to_string();
+        if let Ok(value) = HeaderValue::from_str(&http_date) {
+            response.headers_mut().insert(LAST_MODIFIED, value);
+        }
+    }
+    response
+}
+
+/// Builds the tag for an organization's report data.
+///
+/// `kind` names the endpoint and `params` are its resolved parameters
+/// (defaults filled in), so different reports and filters never share a tag.
+pub async fn org_data_etag(
+    db: &DatabaseConnection,
+    org_id: Uuid,
+    kind: &str,
+    params: &[&str],
+) -> Result<ETag, Response> {
+    let version: DataVersion = ReportRepository::new(db.clone())
+        .data_version(org_id)
+        .await
+        .map_err(|e| {
+            error!(error = %e, "Failed to read report data version");
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({
+                    "error": "internal_error",
+                    "message": "An error occurred"
+                })),
+            )
+                .into_response()
+        })?;
+
+    let org_id = org_id.to_string();
+    let version = version.to_string();
+    let parts = [kind, org_id.as_str(), version.as_str()];
+    Ok(ETag::from_parts(parts.iter().chain(params)))
+}
+
+/// Builds the tag for an endpoint described entirely by its query string.
+///
+/// Today's date is part of the tag because omitted date parameters default
+/// to it, so yesterday's tag never matches today's report.
+pub async fn query_etag(
+    db: &DatabaseConnection,
+    org_id: Uuid,
+    kind: &str,
+    raw_query: Option<&str>,
+) -> Result<ETag, Response> {
+    let today = chrono::Utc::now().date_naive().to_string();
+    org_data_etag(db, org_id, kind, &[&today, raw_query.unwrap_or_default()]).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    fn request_headers(if_none_match: Option<&str>) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        if let Some(value) = if_none_match {
+            headers.insert(IF_NONE_MATCH, HeaderValue::from_str(value).unwrap());
+        }
+        headers
+    }
+
+    #[test]
+    fn test_etag_depends_on_every_part() {
+        let tag = ETag::from_parts(["trial_balance", "2026-01-31"]);
+        assert_eq!(tag, ETag::from_parts(["trial_balance", "2026-01-31"]));
+        assert_ne!(tag, ETag::from_parts(["trial_balance", "2026-02-28"]));
+        assert_ne!(ETag::from_parts(["ab", "c"]), ETag::from_parts(["a", "bc"]));
+        assert!(tag.as_str().starts_with('"') && tag.as_str().ends_with('"'));
+    }
+
+    #[test]
+    fn test_etag_matches_lists_weak_tags_and_wildcard() {
+        let tag = ETag::from_parts(["report"]);
+        let weak = format!("W/{}", tag.as_str());
+
+        assert!(tag.matches(tag.as_str()));
+        assert!(tag.matches(&format!("\"other\", {weak}")));
+        assert!(tag.matches("*"));
+        assert!(!tag.matches("\"other\""));
+    }
+
+    #[tokio::test]
+    async fn test_respond_cached_serves_body_with_etag() {
+        let tag = ETag::from_parts(["report"]);
+        let calls = AtomicUsize::new(0);
+
+        let response = respond_cached(&request_headers(None), &tag, || async {
+            calls.fetch_add(1, Ordering::SeqCst);
+            Json(json!({ "ok": true }))
+        })
+        .await;
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[ETAG], tag.as_str());
+        assert_eq!(response.headers()[CACHE_CONTROL], CACHE_POLICY);
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_respond_cached_skips_body_when_not_modified() {
+        let tag = ETag::from_parts(["report"]);
+        let calls = AtomicUsize::new(0);
+
+        let response = respond_cached(&request_headers(Some(tag.as_str())), &tag, || async {
+            calls.fetch_add(1, Ordering::SeqCst);
+            Json(json!({ "ok": true }))
+        })
+        .await;
+
+        assert_eq!(response.status(), StatusCode::NOT_MODIFIED);
+        assert_eq!(response.headers()[ETAG], tag.as_str());
+        assert_eq!(calls.load(Ordering::SeqCst), 0);
+    }
+
+    #[tokio::test]
+    async fn test_respond_cached_serves_body_for_stale_tag() {
+        let tag = ETag::from_parts(["report", "v2"]);
+        let stale = ETag::from_parts(["report", "v1"]);
+
+        let response = respond_cached(&request_headers(Some(stale.as_str())), &tag, || async {
+            StatusCode::OK
+        })
+        .await;
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[ETAG], tag.as_str());
+    }
+
+    #[tokio::test]
+    async fn test_respond_cached_does_not_tag_errors() {
+        let tag = ETag::from_parts(["report"]);
+
+        let response = respond_cached(&request_headers(None), &tag, || async {
+            StatusCode::INTERNAL_SERVER_ERROR
+        })
+        .await;
+
+        assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+        assert!(response.headers().get(ETAG).is_none());
+    }
+
+    #[test]
+    fn test_last_modified_is_an_http_date_in_gmt() {
+        let updated_at = DateTime::parse_from_rfc3339("2026-03-05T09:07:03.456+07:00").unwrap();
+
+        let response = with_last_modified(StatusCode::OK.into_response(), &updated_at);
+        assert_eq!(
+            response.headers()[LAST_MODIFIED],
+            "Thu, 05 Mar 2026 02:07:03 GMT"
+        );
+
+        let response = with_last_modified(StatusCode::NOT_FOUND.into_response(), &updated_at);
+        assert!(response.headers().get(LAST_MODIFIED).is_none());
+    }
+}
@@ -0,0 +1,121 @@
+//! Prometheus metrics recording.
+//!
+//! Every request is counted and timed under its route template (for example
+//! `/api/v1/organizations/{org_id}/transactions`), so series cardinality stays
+//! bounded by the number of routes rather than by path parameters.
+
+use std::time::Instant;
+
+use axum::{
+    extract::{MatchedPath, Request},
+    middleware::Next,
+    response::Response,
+};
+use metrics_exporter_prometheus::{BuildError, Matcher, PrometheusBuilder, PrometheusHandle};
+
+/// Counter of handled HTTP requests.
+pub const HTTP_REQUESTS_TOTAL: &str = "http_requests_total";
+/// Histogram of HTTP request latency in seconds.
+pub const HTTP_REQUEST_DURATION_SECONDS: &str = "http_request_duration_seconds";
+/// Counter of dashboard cache lookups, labeled by section and hit or miss.
+pub const DASHBOARD_CACHE_LOOKUPS_TOTAL: &str = "dashboard_cache_lookups_total";
+/// Counter of membership cache lookups, labeled by hit or miss.
+pub const MEMBERSHIP_CACHE_LOOKUPS_TOTAL: &str = "membership_cache_lookups_total";
+
+/// Latency buckets in seconds, from 5 ms to 10 s.
+const LATENCY_BUCKETS: &[f64] = &[
+    0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0,
+];
+
+/// Route label for requests that matched no route.
+const UNMATCHED_ROUTE: &str = "unmatched";
+
+/// Handle to the installed Prometheus recorder.
+#[derive(Clone)]
+pub struct Metrics {
+    handle: PrometheusHandle,
+    token: Option<String>,
+}
+
+impl Metrics {
+    /// Wraps a recorder handle; scrapes must present `token` when one is set.
+    #[must_use]
+    pub fn new(handle: PrometheusHandle, token: Option<String>) -> Self {
+        Self { handle, token }
+    }
+
+    /// Installs the process-wide Prometheus recorder.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a recorder is already installed.
+    pub fn install(token: Option<String>) -> Result<Self, BuildError> {
+        let handle = prometheus_builder()?.install_recorder()?;
+        Ok(Self::new(handle, token))
+    }
+
+    /// Renders all series in the Prometheus text exposition format.
+    #[must_use]
+    pub fn render(&self) -> String {
+        self.handle.render()
+    }
+
+    /// Drains histogram samples that have already been rendered.
+    ///
+    /// Must be called periodically when no exporter listener does it.
+    pub fn run_upkeep(&self) {
+        self.handle.run_upkeep();
+    }
+
+    /// Returns `true` if a scrape presenting `bearer` may read the metrics.
+    #[must_use]
+    pub fn authorize(&self, bearer: Option<&str>) -> bool {
+        match &self.token {
+            Some(token) => bearer == Some(token.as_str()),
+            None => true,
+        }
+    }
+}
+
+impl std::fmt::Debug for Metrics {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Metrics")
+            .field("token", &self.token.as_ref().map(|_| "[REDACTED]"))
+            .finish_non_exhaustive()
+    }
+}
+
+/// Builder with the latency histogram buckets configured.
+///
+/// # Errors
+///
+/// Returns an error if the bucket list is empty.
+pub fn prometheus_builder() -> Result<PrometheusBuilder, BuildError> {
+    PrometheusBuilder::new().set_buckets_for_metric(
+        Matcher::Full(HTTP_REQUEST_DURATION_SECONDS.to_string()),
+        LATENCY_BUCKETS,
+    )
+}
+
+/// Records request count and latency labeled by method, route and status.
+pub async fn track_metrics(request: Request, next: Next) -> Response {
+    let start = Instant::now();
+    let method = request.method().to_string();
+    let route = request.extensions().get::<MatchedPath>().map_or_else(
+        || UNMATCHED_ROUTE.to_string(),
+        |path| path.as_str().to_string(),
+    );
+
+    let response = next.run(request).await;
+
+    let labels = [
+        ("method", method),
+        ("route", route),
+        ("status", response.status().as_u16().to_string()),
+    ];
+    metrics::counter!(HTTP_REQUESTS_TOTAL, &labels).increment(1);
+    metrics::histogram!(HTTP_REQUEST_DURATION_SECONDS, &labels)
+        .record(start.elapsed().as_secs_f64());
+
+    response
+}
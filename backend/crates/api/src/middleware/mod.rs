@@ -1,5 +1,17 @@
 //! Middleware for request processing.
 
+pub mod admin;
 pub mod auth;
+pub mod body_limit;
+pub mod etag;
+pub mod metrics;
+pub mod request_id;
 
-pub use auth::{AuthUser, auth_middleware};
+pub use admin::require_admin;
+pub use auth::{AuthMember, AuthUser, auth_middleware};
+pub use body_limit::{BodyLimits, limit_body};
+pub use etag::{ETag, org_data_etag, query_etag, respond_cached, with_last_modified};
+pub use metrics::{Metrics, track_metrics};
+pub use request_id::{
+    REQUEST_ID_HEADER, RequestId, assign_request_id, make_request_span, record_user,
+};
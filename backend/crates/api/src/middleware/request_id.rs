@@ -0,0 +1,332 @@
+//! Request IDs and the per-request tracing span.
+//!
+//! Every request gets an `X-Request-Id`: the caller's own when it sends a
+//! usable one, otherwise a new UUID. The id is echoed in the response headers
+//! and added to JSON error bodies, so a user report can be matched to the
+//! logs. The request span carries the id, the organization from the path and,
+//! once authenticated, the user; log lines written while handling the request
+//! inherit those fields.
+
+use axum::{
+    body::{Body, to_bytes},
+    extract::Request,
+    http::{
+        HeaderName, HeaderValue,
+        header::{CONTENT_LENGTH, CONTENT_TYPE},
+    },
+    middleware::Next,
+    response::Response,
+};
+use tracing::{Span, field, warn};
+use uuid::Uuid;
+
+/// Header carrying the request ID.
+pub const REQUEST_ID_HEADER: HeaderName = HeaderName::from_static("x-request-id");
+
+/// Longest inbound request ID that is kept.
+const MAX_REQUEST_ID_LENGTH: usize = 128;
+
+/// Largest error body rewritten to include the request ID.
+const MAX_ERROR_BODY_BYTES: usize = 64 * 1024;
+
+/// The request's ID, available to handlers as a request extension.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RequestId(pub String);
+
+/// Assigns the request ID, echoes it in the response and adds it to JSON
+/// error bodies.
+///
+/// Must wrap the `TraceLayer` so the span sees the ID.
+pub async fn assign_request_id(mut request: Request, next: Next) -> Response {
+    let id = request
+        .headers()
+        .get(&REQUEST_ID_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .filter(|value| is_usable_request_id(value))
+        .map_or_else(|| Uuid::new_v4().to_string(), ToString::to_string);
+    // Inbound IDs are checked and generated ones are UUIDs, so this holds
+    let Ok(header) = HeaderValue::from_str(&id) else {
+        return next.run(request).await;
+    };
+    request
+        .headers_mut()
+        .insert(REQUEST_ID_HEADER, header.clone());
+    request.extensions_mut().insert(RequestId(id.clone()));
+
+    let response = next.run(request).await;
+    let mut response = add_to_error_body(response, &id).await;
+    response.headers_mut().insert(REQUEST_ID_HEADER, header);
+    response
+}
+
+/// Creates the request span for the `TraceLayer`.
+///
+/// `user_id` starts empty and is filled in by [`record_user`] once the
+/// request is authenticated.
+pub fn make_request_span(request: &Request) -> Span {
+    let request_id = request
+        .headers()
+        .get(&REQUEST_ID_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .unwrap_or_default();
+    let span = tracing::info_span!(
+        "request",
+        method = %request.method(),
+        uri = %request.uri(),
+        request_id = %request_id,
+        org_id = field::Empty,
+        user_id = field::Empty,
+    );
+    if let Some(org_id) = organization_in_path(request.uri().path()) {
+        span.record("org_id", field::display(org_id));
+    }
+    span
+}
+
+/// Records the authenticated user on the current request span.
+pub fn record_user(user_id: Uuid) {
+    Span::current().record("user_id", field::display(user_id));
+}
+
+/// Whether an inbound request ID is safe to log and echo.
+fn is_usable_request_id(id: &str) -> bool {
+    !id.is_empty()
+        && id.len() <= MAX_REQUEST_ID_LENGTH
+        && id
+            .bytes()
+            .all(|b| b.is_ascii_alphanumeric() || matches!(b, b'-' | b'_' | b'.' | b':'))
+}
+
+/// The organization ID following `/organizations/` in a path.
+fn organization_in_path(path: &str) -> Option<Uuid> {
+    path.split('/')
+        .skip_while(|segment| *segment != "organizations")
+        .nth(1)?
+        .parse()
+        .ok()
+}
+
+/// Adds `request_id` to a JSON error body with an `error` code.
+async fn add_to_error_body(response: Response, id: &str) -> Response {
+    let is_json = response
+        .headers()
+        .get(CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| value.starts_with("application/json"));
+    let status = response.status();
+    if !is_json || !(status.is_client_error() || status.is_server_error()) {
+        return response;
+    }
+
+    let (mut parts, body) = response.into_parts();
+    let bytes = match to_bytes(body, MAX_ERROR_BODY_BYTES).await {
+        Ok(bytes) => bytes,
+        Err(e) => {
+            warn!(error = %e, "Failed to read error body");
+            return Response::from_parts(parts, Body::empty());
+        }
+    };
+    let mut value: serde_json::Value = match serde_json::from_slice(&bytes) {
+        Ok(value) => value,
+        Err(_) => return Response::from_parts(parts, Body::from(bytes)),
+    };
+    let Some(object) = value.as_object_mut().filter(|o| o.contains_key("error")) else {
+        return Response::from_parts(parts, Body::from(bytes));
+    };
+    object.insert("request_id".to_string(), id.into());
+
+    parts.headers.remove(CONTENT_LENGTH);
+    Response::from_parts(parts, Body::from(value.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+    use axum::http::{StatusCode, header::AUTHORIZATION};
+    use sea_orm::{DatabaseConnection, DbErr};
+    use std::io::Write;
+    use std::sync::{Arc, Mutex};
+    use tower::ServiceExt;
+    use tracing_subscriber::fmt::MakeWriter;
+    use zeltra_db::entities::{organization_users, organizations};
+    use zeltra_db::repositories::{OrganizationStore, Stores};
+    use zeltra_shared::{AdminConfig, EmailConfig, EmailService, JwtConfig, JwtService};
+
+    use crate::{
+        AppState,
+        cache::{DashboardCache, MembershipCache},
+        create_router,
+        middleware::BodyLimits,
+    };
+
+    /// Collects formatted log output.
+    #[derive(Clone, Default)]
+    struct CapturedLogs(Arc<Mutex<Vec<u8>>>);
+
+    impl CapturedLogs {
+        fn contents(&self) -> String {
+            String::from_utf8(self.0.lock().unwrap().clone()).unwrap()
+        }
+    }
+
+    impl Write for CapturedLogs {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    impl<'a> MakeWriter<'a> for CapturedLogs {
+        type Writer = Self;
+
+        fn make_writer(&'a self) -> Self::Writer {
+            self.clone()
+        }
+    }
+
+    /// Organization store whose database cannot be reached.
+    struct Unreachable;
+
+    #[async_trait]
+    impl OrganizationStore for Unreachable {
+        async fn find_by_id(&self, _id: Uuid) -> Result<Option<organizations::Model>, DbErr> {
+            Err(DbErr::Custom("connection refused".to_string()))
+        }
+
+        async fn is_member(&self, _org_id: Uuid, _user_id: Uuid) -> Result<bool, DbErr> {
+            Err(DbErr::Custom("connection refused".to_string()))
+        }
+
+        async fn get_user_membership(
+            &self,
+            _org_id: Uuid,
+            _user_id: Uuid,
+        ) -> Result<Option<organization_users::Model>, DbErr> {
+            Err(DbErr::Custom("connection refused".to_string()))
+        }
+    }
+
+    fn test_state() -> AppState {
+        AppState {
+            db: Arc::new(DatabaseConnection::Disconnected),
+            jwt_service: Arc::new(JwtService::new(JwtConfig::default())),
+            email_service: Arc::new(EmailService::new(EmailConfig::default())),
+            storage: None,
+            metrics: None,
+            jobs: None,
+            admin: AdminConfig::default(),
+            dashboard_cache: DashboardCache::default(),
+            membership_cache: MembershipCache::default(),
+            currencies: Arc::default(),
+            stores: Stores {
+                organizations: Arc::new(Unreachable),
+                ..Stores::postgres(&DatabaseConnection::Disconnected)
+            },
+        }
+    }
+
+    async fn json_body(response: Response) -> serde_json::Value {
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        serde_json::from_slice(&body).unwrap()
+    }
+
+    #[test]
+    fn test_inbound_request_id_rules() {
+        assert!(is_usable_request_id("req-42"));
+        assert!(is_usable_request_id("0192d1f4-5c3a-7b2e-9f10-0a1b2c3d4e5f"));
+        assert!(!is_usable_request_id(""));
+        assert!(!is_usable_request_id("bad id\nforged=1"));
+        assert!(!is_usable_request_id(
+            &"x".repeat(MAX_REQUEST_ID_LENGTH + 1)
+        ));
+    }
+
+    #[test]
+    fn test_organization_in_path() {
+        let org_id = Uuid::new_v4();
+        assert_eq!(
+            organization_in_path(&format!("/api/v1/organizations/{org_id}/accounts")),
+            Some(org_id)
+        );
+        assert_eq!(organization_in_path("/api/v1/organizations"), None);
+        assert_eq!(organization_in_path("/api/v1/organizations/acme"), None);
+    }
+
+    #[tokio::test]
+    async fn test_request_id_round_trips_and_reaches_handler_logs() {
+        let logs = CapturedLogs::default();
+        let subscriber = tracing_subscriber::fmt()
+            .with_writer(logs.clone())
+            .with_ansi(false)
+            .finish();
+        let _guard = tracing::subscriber::set_default(subscriber);
+
+        let state = test_state();
+        let user_id = Uuid::new_v4();
+        let org_id = Uuid::new_v4();
+        let token = state
+            .jwt_service
+            .generate_access_token(user_id, org_id, "owner")
+            .unwrap();
+        let request = Request::builder()
+            .uri(format!("/api/v1/organizations/{org_id}/accounts"))
+            .header(AUTHORIZATION, format!("Bearer {token}"))
+            .header(REQUEST_ID_HEADER, "support-ticket-1234")
+            .body(Body::empty())
+            .unwrap();
+
+        // The database cannot be reached, so the handler logs an error
+        let response = create_router(state, BodyLimits::default())
+            .oneshot(request)
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+        assert_eq!(
+            response.headers().get(&REQUEST_ID_HEADER),
+            Some(&HeaderValue::from_static("support-ticket-1234"))
+        );
+        assert_eq!(
+            json_body(response).await["request_id"],
+            "support-ticket-1234"
+        );
+
+        let logs = logs.contents();
+        let handler_line = logs
+            .lines()
+            .find(|line| line.contains("Database error checking membership"))
+            .unwrap_or_else(|| panic!("no handler error logged in:\n{logs}"));
+        assert!(handler_line.contains("request_id=support-ticket-1234"));
+        assert!(handler_line.contains(&format!("org_id={org_id}")));
+        assert!(handler_line.contains(&format!("user_id={user_id}")));
+    }
+
+    #[tokio::test]
+    async fn test_request_id_generated_when_missing() {
+        let request = Request::builder()
+            .uri("/api/v1/organizations")
+            .body(Body::empty())
+            .unwrap();
+
+        let response = create_router(test_state(), BodyLimits::default())
+            .oneshot(request)
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+        let id = response
+            .headers()
+            .get(&REQUEST_ID_HEADER)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.parse::<Uuid>().ok())
+            .expect("a UUID request id");
+        let body = json_body(response).await;
+        assert_eq!(body["error"], "missing_token");
+        assert_eq!(body["request_id"], id.to_string());
+    }
+}
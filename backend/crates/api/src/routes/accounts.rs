@@ -3,30 +3,62 @@
 use axum::{
     Json, Router,
     extract::{Path, Query, State},
-    http::StatusCode,
+    http::{
+        HeaderMap, StatusCode,
+        header::{CONTENT_DISPOSITION, CONTENT_TYPE},
+    },
     response::IntoResponse,
     routing::{delete, get, post, put},
 };
-use chrono::NaiveDate;
-use serde::{Deserialize, Serialize};
+use chrono::{DateTime, NaiveDate, Utc};
+use sea_orm::Iterable;
+use serde::{Deserialize, Deserializer, Serialize};
 use serde_json::json;
+use std::collections::HashSet;
 use tracing::{error, info};
 use uuid::Uuid;
 
-use crate::{AppState, middleware::AuthUser};
+use crate::{
+    AppState,
+    extractors::{
+        StrictJson,
+        json::{enum_string, optional_enum_string},
+    },
+    middleware::{
+        AuthMember, AuthUser,
+        auth::{check_membership, check_report_access},
+        org_data_etag, respond_cached, with_last_modified,
+    },
+    routes::{invalid_sort_response, reports::export_style},
+};
+use zeltra_core::account_import::{ImportIssue, ImportRow, parse_chart_csv};
+use zeltra_core::auth::UserRole as CoreUserRole;
+use zeltra_core::ledger::{ActivityGranularity, MAX_ACTIVITY_BUCKETS};
+use zeltra_core::reports::{BalanceMatrix, BalanceMatrixRow, MAX_BALANCE_MATRIX_DATES};
 use zeltra_db::{
     OrganizationRepository,
     entities::sea_orm_active_enums::{AccountSubtype, AccountType, UserRole},
     repositories::account::{
-        AccountFilter, AccountRepository, CreateAccountInput, UpdateAccountInput,
+        AccountError, AccountFilter, AccountRepository, AccountSortField, BalanceMatrixFilter,
+        CreateAccountInput, ImportAccountInput, UpdateAccountInput,
     },
+    repositories::dimension::{AccountDefaultDimension, DimensionError, DimensionRepository},
 };
+use zeltra_shared::types::{AccountId, OrganizationId, SortParams};
 
 /// Creates the account routes (requires auth middleware to be applied externally).
 pub fn routes() -> Router<AppState> {
     Router::new()
         .route("/organizations/{org_id}/accounts", get(list_accounts))
         .route("/organizations/{org_id}/accounts", post(create_account))
+        .route(
+            "/organizations/{org_id}/accounts/import",
+            post(import_accounts),
+        )
+        .route(
+            "/organizations/{org_id}/accounts/balance-matrix",
+            post(get_balance_matrix),
+        )
         .route(
             "/organizations/{org_id}/accounts/{account_id}",
             get(get_account),
@@ -47,6 +79,22 @@ pub fn routes() -> Router<AppState> {
             "/organizations/{org_id}/accounts/{account_id}/ledger",
             get(get_account_ledger),
         )
+        .route(
+            "/organizations/{org_id}/accounts/{account_id}/activity",
+            get(get_account_activity),
+        )
+        .route(
+            "/organizations/{org_id}/accounts/{account_id}/default-dimensions",
+            get(list_default_dimensions),
+        )
+        .route(
+            "/organizations/{org_id}/accounts/{account_id}/default-dimensions",
+            post(add_default_dimension),
+        )
+        .route(
+            "/organizations/{org_id}/accounts/{account_id}/default-dimensions/{dimension_value_id}",
+            delete(remove_default_dimension),
+        )
 }
 
 /// Query parameters for listing accounts.
@@ -59,10 +107,13 @@ pub struct ListAccountsQuery {
     pub active: Option<bool>,
     /// Filter by currency.
     pub currency: Option<String>,
+    /// Only accounts changed after this time (RFC 3339).
+    pub modified_since: Option<DateTime<Utc>>,
 }
 
 /// Request body for creating an account.
 #[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct CreateAccountRequest {
     /// Account code (must be unique within organization).
     pub code: String,
@@ -71,9 +122,10 @@ pub struct CreateAccountRequest {
     /// Account description.
     pub description: Option<String>,
     /// Account type: asset, liability, equity, revenue, expense.
-    #[serde(rename = "type")]
+    #[serde(rename = "type", deserialize_with = "deserialize_account_type")]
     pub account_type: String,
     /// Account subtype for more specific categorization.
+    #[serde(default, deserialize_with = "deserialize_account_subtype")]
     pub subtype: Option<String>,
     /// Parent account ID for hierarchical structure.
     pub parent_id: Option<Uuid>,
@@ -83,6 +135,38 @@ pub struct CreateAccountRequest {
     pub is_active: Option<bool>,
     /// Whether direct posting is allowed (default: true).
     pub allow_direct_posting: Option<bool>,
+    /// Whether this is a bank account (default: false).
+    pub is_bank_account: Option<bool>,
+    /// Bank account number, for bank accounts.
+    pub bank_account_number: Option<String>,
+}
+
+/// Query parameters for importing accounts.
+#[derive(Debug, Deserialize)]
+pub struct ImportAccountsQuery {
+    /// Validate every row without creating anything (default: false).
+    pub dry_run: Option<bool>,
+}
+
+/// Request body for importing accounts.
+#[derive(Debug, Deserialize)]
+pub struct ImportAccountsRequest {
+    /// CSV with a header row: code, name, type, currency, and optionally
+    /// subtype, parent_code, allow_direct_posting.
+    pub csv: String,
+}
+
+/// Validation result for one imported row.
+#[derive(Debug, Serialize)]
+pub struct ImportRowResult {
+    /// 1-based line number in the CSV (header is line 1).
+    pub row: usize,
+    /// Account code, when the row could be read.
+    pub code: Option<String>,
+    /// Whether the row has no problems.
+    pub valid: bool,
+    /// Problems with the row.
+    pub errors: Vec<ImportIssue>,
 }
 
 /// Request body for updating an account.
@@ -133,6 +217,50 @@ pub struct AccountResponse {
     pub is_active: bool,
     /// Whether direct posting is allowed.
     pub allow_direct_posting: bool,
+    /// When the account last changed.
+    pub updated_at: String,
+}
+
+/// Request body for adding a default dimension value to an account.
+#[derive(Debug, Deserialize)]
+pub struct AddDefaultDimensionRequest {
+    /// Dimension value applied to entries without a value of its type.
+    pub dimension_value_id: Uuid,
+}
+
+/// Response for an account's default dimension value.
+#[derive(Debug, Serialize)]
+pub struct DefaultDimensionResponse {
+    /// Dimension type the default fills in.
+    pub dimension_type_id: Uuid,
+    /// Dimension value ID.
+    pub dimension_value_id: Uuid,
+    /// Dimension value code.
+    pub code: String,
+    /// Dimension value name.
+    pub name: String,
+    /// When the default was added.
+    pub created_at: String,
+}
+
+impl From<AccountDefaultDimension> for DefaultDimensionResponse {
+    fn from(d: AccountDefaultDimension) -> Self {
+        Self {
+            dimension_type_id: d.value.dimension_type_id,
+            dimension_value_id: d.value.id,
+            code: d.value.code,
+            name: d.value.name,
+            created_at: d.default.created_at.to_rfc3339(),
+        }
+    }
+}
+
+/// Query parameters for getting account detail.
+#[derive(Debug, Default, Deserialize)]
+pub struct AccountDetailQuery {
+    /// Include the account's amounts per transaction status.
+    #[serde(default)]
+    pub include_status_breakdown: bool,
 }
 
 /// Query parameters for getting account balance at a specific date.
@@ -142,6 +270,44 @@ pub struct BalanceQuery {
     pub as_of: Option<NaiveDate>,
 }
 
+/// Request body for a balance matrix.
+#[derive(Debug, Deserialize)]
+pub struct BalanceMatrixRequest {
+    /// Column dates, each inside a fiscal year; at most 13.
+    pub dates: Vec<NaiveDate>,
+    /// Only these accounts; all when empty.
+    #[serde(default)]
+    pub account_ids: Vec<Uuid>,
+    /// Only accounts of these types; all when empty.
+    #[serde(default, deserialize_with = "deserialize_account_types")]
+    pub account_types: Vec<String>,
+    /// Include inactive accounts.
+    #[serde(default)]
+    pub include_inactive: bool,
+}
+
+/// Query parameters for a balance matrix.
+#[derive(Debug, Deserialize)]
+pub struct BalanceMatrixQuery {
+    /// Response format (default: json).
+    #[serde(default)]
+    pub format: ExportFormat,
+    /// Format CSV amounts and dates for the organization's locale.
+    #[serde(default)]
+    pub formatted: bool,
+}
+
+/// Response format of an exportable report.
+#[derive(Debug, Clone, Copy, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportFormat {
+    /// JSON body.
+    #[default]
+    Json,
+    /// CSV for pasting into a spreadsheet.
+    Csv,
+}
+
 /// Query parameters for listing ledger entries.
 #[derive(Debug, Deserialize)]
 pub struct LedgerQuery {
@@ -149,12 +315,67 @@ pub struct LedgerQuery {
     pub from: Option<NaiveDate>,
     /// End date filter (inclusive, YYYY-MM-DD format).
     pub to: Option<NaiveDate>,
+    /// Only entries booked at an overridden exchange rate.
+    #[serde(default)]
+    pub overridden_only: bool,
     /// Page number (1-indexed, default: 1).
     pub page: Option<u64>,
     /// Number of entries per page (default: 50, max: 100).
     pub limit: Option<u64>,
 }
 
+/// Query parameters for account activity buckets.
+#[derive(Debug, Deserialize)]
+pub struct ActivityQuery {
+    /// Start date (inclusive). Defaults to the first day of the month
+    /// eleven months before `to`.
+    pub from: Option<NaiveDate>,
+    /// End date (inclusive). Defaults to today.
+    pub to: Option<NaiveDate>,
+    /// Bucket width: `month` (default) or `week`.
+    pub granularity: Option<String>,
+}
+
+/// Response for account activity.
+#[derive(Debug, Serialize)]
+pub struct AccountActivityResponse {
+    /// Account ID.
+    pub account_id: Uuid,
+    /// Account code.
+    pub account_code: String,
+    /// Account name.
+    pub account_name: String,
+    /// Account currency.
+    pub currency: String,
+    /// Start date (inclusive).
+    pub from: NaiveDate,
+    /// End date (inclusive).
+    pub to: NaiveDate,
+    /// Bucket width.
+    pub granularity: ActivityGranularity,
+    /// Balance before `from`.
+    pub opening_balance: String,
+    /// Activity buckets in date order.
+    pub buckets: Vec<ActivityBucketResponse>,
+}
+
+/// One bucket of account activity.
+#[derive(Debug, Serialize)]
+pub struct ActivityBucketResponse {
+    /// First day of the bucket within the range.
+    pub start: NaiveDate,
+    /// Last day of the bucket within the range.
+    pub end: NaiveDate,
+    /// Total debits.
+    pub debit: String,
+    /// Total credits.
+    pub credit: String,
+    /// Change in balance.
+    pub net_movement: String,
+    /// Balance at the end of the bucket.
+    pub ending_balance: String,
+}
+
 /// Response for a ledger entry.
 #[derive(Debug, Serialize)]
 pub struct LedgerEntryResponse {
@@ -190,24 +411,32 @@ pub struct LedgerEntryResponse {
     pub previous_balance: String,
     /// Running balance after this entry.
     pub current_balance: String,
+    /// When the entry happened; orders entries within a day.
+    pub event_at: String,
     /// Entry timestamp.
     pub created_at: String,
+    /// Whether the exchange rate was entered rather than looked up.
+    pub is_override: bool,
 }
 
 /// GET `/organizations/{org_id}/accounts` - List accounts with balances.
 async fn list_accounts(
     State(state): State<AppState>,
     auth: AuthUser,
-    Path(org_id): Path<Uuid>,
+    Path(org_id): Path<OrganizationId>,
     Query(query): Query<ListAccountsQuery>,
+    Query(sort): Query<SortParams>,
 ) -> impl IntoResponse {
-    let org_repo = OrganizationRepository::new((*state.db).clone());
-
     // Check membership
-    if let Err(response) = check_membership(&org_repo, org_id, auth.user_id()).await {
+    if let Err(response) = check_membership(&state, org_id, auth.user_id()).await {
         return response;
     }
 
+    let sort = match sort.resolve::<AccountSortField>() {
+        Ok(sort) => sort,
+        Err(e) => return invalid_sort_response(&e),
+    };
+
     let account_repo = AccountRepository::new((*state.db).clone());
 
     // Build filter
@@ -218,6 +447,8 @@ async fn list_accounts(
             .and_then(|t| string_to_account_type(t)),
         is_active: query.active,
         parent_id: None,
+        sort,
+        modified_since: query.modified_since,
     };
 
     match account_repo.list_accounts(org_id, filter).await {
@@ -239,6 +470,7 @@ async fn list_accounts(
                     balance: a.balance.to_string(),
                     is_active: a.account.is_active,
                     allow_direct_posting: a.account.allow_direct_posting,
+                    updated_at: a.account.updated_at.to_rfc3339(),
                 })
                 .collect();
 
@@ -262,14 +494,11 @@ async fn list_accounts(
 #[allow(clippy::too_many_lines)]
 async fn create_account(
     State(state): State<AppState>,
-    auth: AuthUser,
-    Path(org_id): Path<Uuid>,
-    Json(payload): Json<CreateAccountRequest>,
+    auth: AuthMember,
+    Path(org_id): Path<OrganizationId>,
+    StrictJson(payload): StrictJson<CreateAccountRequest>,
 ) -> impl IntoResponse {
-    let org_repo = OrganizationRepository::new((*state.db).clone());
-
-    // Check admin/owner role
-    if let Err(response) = check_admin_role(&org_repo, org_id, auth.user_id()).await {
+    if let Err(response) = check_chart_of_accounts_role(&state, org_id, &auth).await {
         return response;
     }
 
@@ -294,7 +523,7 @@ async fn create_account(
     let account_repo = AccountRepository::new((*state.db).clone());
 
     let input = CreateAccountInput {
-        organization_id: org_id,
+        organization_id: org_id.into_inner(),
         code: payload.code,
         name: payload.name,
         description: payload.description,
@@ -304,16 +533,13 @@ async fn create_account(
         currency: payload.currency,
         is_active: payload.is_active.unwrap_or(true),
         allow_direct_posting: payload.allow_direct_posting.unwrap_or(true),
+        is_bank_account: payload.is_bank_account.unwrap_or(false),
+        bank_account_number: payload.bank_account_number,
     };
 
     match account_repo.create_account(input).await {
         Ok(account) => {
-            info!(
-                org_id = %org_id,
-                account_id = %account.id,
-                code = %account.code,
-                "Account created"
-            );
+            info!(account_id = %account.id, code = %account.code, "Account created");
 
             (
                 StatusCode::CREATED,
@@ -329,6 +555,8 @@ async fn create_account(
                     "balance": "0",
                     "is_active": account.is_active,
                     "allow_direct_posting": account.allow_direct_posting,
+                    "is_bank_account": account.is_bank_account,
+                    "bank_account_number": account.bank_account_number,
                     "created_at": account.created_at
                 })),
             )
@@ -382,31 +610,155 @@ async fn create_account(
     }
 }
 
+/// POST `/organizations/{org_id}/accounts/import` - Import a chart of accounts
+/// from CSV.
+///
+/// Every row is validated first; accounts are only created, in one database
+/// transaction, when the whole file is valid. With `dry_run=true` the
+/// per-row results are returned and nothing is written.
+#[allow(clippy::too_many_lines)]
+async fn import_accounts(
+    State(state): State<AppState>,
+    auth: AuthMember,
+    Path(org_id): Path<OrganizationId>,
+    Query(query): Query<ImportAccountsQuery>,
+    StrictJson(payload): StrictJson<ImportAccountsRequest>,
+) -> impl IntoResponse {
+    if let Err(response) = check_chart_of_accounts_role(&state, org_id, &auth).await {
+        return response;
+    }
+
+    let parsed = match parse_chart_csv(&payload.csv) {
+        Ok(parsed) => parsed,
+        Err(e) => {
+            return (
+                StatusCode::BAD_REQUEST,
+                Json(json!({
+                    "error": "invalid_csv",
+                    "message": e.to_string()
+                })),
+            )
+                .into_response();
+        }
+    };
+
+    let mut issues = parsed.issues;
+    let mut inputs = Vec::with_capacity(parsed.rows.len());
+    for row in &parsed.rows {
+        match import_row_to_input(org_id, row) {
+            Ok(input) => inputs.push(input),
+            Err(row_issues) => issues.extend(row_issues),
+        }
+    }
+
+    let account_repo = AccountRepository::new((*state.db).clone());
+    let dry_run = query.dry_run.unwrap_or(false);
+
+    if dry_run || !issues.is_empty() {
+        match account_repo
+            .validate_accounts_batch(org_id.into_inner(), &inputs)
+            .await
+        {
+            Ok(batch_issues) => issues.extend(batch_issues),
+            Err(e) => {
+                error!(error = %e, "Failed to validate account import");
+                return (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    Json(json!({
+                        "error": "internal_error",
+                        "message": "An error occurred"
+                    })),
+                )
+                    .into_response();
+            }
+        }
+        let valid = issues.is_empty();
+        let rows = import_row_results(&parsed.rows, issues);
+        if dry_run {
+            return (
+                StatusCode::OK,
+                Json(json!({
+                    "dry_run": true,
+                    "valid": valid,
+                    "rows": rows
+                })),
+            )
+                .into_response();
+        }
+        return invalid_import_response(&rows);
+    }
+
+    match account_repo
+        .create_accounts_batch(org_id.into_inner(), inputs)
+        .await
+    {
+        Ok(accounts) => {
+            info!(count = accounts.len(), "Accounts imported");
+
+            let accounts: Vec<serde_json::Value> = accounts
+                .iter()
+                .map(|account| {
+                    json!({
+                        "id": account.id,
+                        "code": account.code,
+                        "name": account.name,
+                        "type": account_type_to_string(&account.account_type),
+                        "parent_id": account.parent_id
+                    })
+                })
+                .collect();
+            (
+                StatusCode::CREATED,
+                Json(json!({
+                    "created": accounts.len(),
+                    "accounts": accounts
+                })),
+            )
+                .into_response()
+        }
+        Err(AccountError::InvalidImport(issues)) => {
+            invalid_import_response(&import_row_results(&parsed.rows, issues))
+        }
+        Err(e) => {
+            error!(error = %e, "Failed to import accounts");
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({
+                    "error": "internal_error",
+                    "message": "An error occurred"
+                })),
+            )
+                .into_response()
+        }
+    }
+}
+
 /// GET `/organizations/{org_id}/accounts/{account_id}` - Get account detail.
+///
+/// The balance counts posted transactions only; `include_status_breakdown`
+/// adds the amounts still in the approval workflow.
 async fn get_account(
     State(state): State<AppState>,
     auth: AuthUser,
-    Path((org_id, account_id)): Path<(Uuid, Uuid)>,
+    Path((org_id, account_id)): Path<(OrganizationId, AccountId)>,
+    Query(query): Query<AccountDetailQuery>,
 ) -> impl IntoResponse {
-    let org_repo = OrganizationRepository::new((*state.db).clone());
-
     // Check membership
-    if let Err(response) = check_membership(&org_repo, org_id, auth.user_id()).await {
+    if let Err(response) = check_membership(&state, org_id, auth.user_id()).await {
         return response;
     }
 
     let account_repo = AccountRepository::new((*state.db).clone());
 
     match account_repo.find_account_by_id(account_id).await {
-        Ok(Some(a)) if a.account.organization_id == org_id => (
-            StatusCode::OK,
-            Json(json!({
+        Ok(Some(a)) if a.account.organization_id == org_id.into_inner() => {
+            let mut body = json!({
                 "id": a.account.id,
                 "code": a.account.code,
                 "name": a.account.name,
                 "description": a.account.description,
                 "type": account_type_to_string(&a.account.account_type),
-                "subtype": a.account.account_subtype.map(|s| account_subtype_to_string(&s)),
+                "subtype": a.account.account_subtype.as_ref().map(account_subtype_to_string),
                 "parent_id": a.account.parent_id,
                 "currency": a.account.currency,
                 "balance": a.balance.to_string(),
@@ -417,9 +769,35 @@ async fn get_account(
                 "bank_account_number": a.account.bank_account_number,
                 "created_at": a.account.created_at,
                 "updated_at": a.account.updated_at
-            })),
-        )
-            .into_response(),
+            });
+            if query.include_status_breakdown {
+                match account_repo.get_status_breakdown(&a.account).await {
+                    Ok(breakdown) => {
+                        body["status_breakdown"] = json!({
+                            "posted": breakdown.posted.to_string(),
+                            "approved": breakdown.approved.to_string(),
+                            "pending": breakdown.pending.to_string(),
+                            "draft": breakdown.draft.to_string()
+                        });
+                    }
+                    Err(e) => {
+                        error!(error = %e, "Failed to get account status breakdown");
+                        return (
+                            StatusCode::INTERNAL_SERVER_ERROR,
+                            Json(json!({
+                                "error": "internal_error",
+                                "message": "An error occurred"
+                            })),
+                        )
+                            .into_response();
+                    }
+                }
+            }
+            with_last_modified(
+                (StatusCode::OK, Json(body)).into_response(),
+                &a.account.updated_at,
+            )
+        }
         Ok(Some(_)) => (
             StatusCode::FORBIDDEN,
             Json(json!({
@@ -454,22 +832,22 @@ async fn get_account(
 #[allow(clippy::too_many_lines)]
 async fn update_account(
     State(state): State<AppState>,
-    auth: AuthUser,
-    Path((org_id, account_id)): Path<(Uuid, Uuid)>,
-    Json(payload): Json<UpdateAccountRequest>,
+    auth: AuthMember,
+    Path((org_id, account_id)): Path<(OrganizationId, AccountId)>,
+    StrictJson(payload): StrictJson<UpdateAccountRequest>,
 ) -> impl IntoResponse {
-    let org_repo = OrganizationRepository::new((*state.db).clone());
-
-    // Check admin/owner role
-    if let Err(response) = check_admin_role(&org_repo, org_id, auth.user_id()).await {
-        return response;
-    }
+    let role = match check_chart_of_accounts_role(&state, org_id, &auth).await {
+        Ok(role) => role,
+        Err(response) => return response,
+    };
 
     let account_repo = AccountRepository::new((*state.db).clone());
 
     // Verify account belongs to this organization
-    match account_repo.find_account_by_id(account_id).await {
-        Ok(Some(a)) if a.account.organization_id == org_id => {}
+    let is_system_account = match account_repo.find_account_by_id(account_id).await {
+        Ok(Some(a)) if a.account.organization_id == org_id.into_inner() => {
+            a.account.is_system_account
+        }
         Ok(Some(_)) => {
             return (
                 StatusCode::FORBIDDEN,
@@ -501,6 +879,18 @@ async fn update_account(
             )
                 .into_response();
         }
+    };
+
+    // Deactivating and editing system accounts stay admin-only
+    if (is_system_account || payload.is_active == Some(false)) && !role.can_modify_settings() {
+        return (
+            StatusCode::FORBIDDEN,
+            Json(json!({
+                "error": "forbidden",
+                "message": "You need admin or owner role to deactivate or edit system accounts"
+            })),
+        )
+            .into_response();
     }
 
     // Parse account type if provided
@@ -538,11 +928,7 @@ async fn update_account(
 
     match account_repo.update_account(account_id, input).await {
         Ok(account) => {
-            info!(
-                org_id = %org_id,
-                account_id = %account_id,
-                "Account updated"
-            );
+            info!(account_id = %account_id, "Account updated");
 
             // Get updated balance
             let balance = match account_repo.find_account_by_id(account_id).await {
@@ -612,13 +998,13 @@ async fn update_account(
 /// DELETE `/organizations/{org_id}/accounts/{account_id}` - Delete (deactivate) account.
 async fn delete_account(
     State(state): State<AppState>,
-    auth: AuthUser,
-    Path((org_id, account_id)): Path<(Uuid, Uuid)>,
+    auth: AuthMember,
+    Path((org_id, account_id)): Path<(OrganizationId, AccountId)>,
 ) -> impl IntoResponse {
     let org_repo = OrganizationRepository::new((*state.db).clone());
 
     // Check admin/owner role
-    if let Err(response) = check_admin_role(&org_repo, org_id, auth.user_id()).await {
+    if let Err(response) = check_admin_role(&org_repo, org_id, &auth).await {
         return response;
     }
 
@@ -626,7 +1012,7 @@ async fn delete_account(
 
     // Verify account belongs to this organization
     match account_repo.find_account_by_id(account_id).await {
-        Ok(Some(a)) if a.account.organization_id == org_id => {}
+        Ok(Some(a)) if a.account.organization_id == org_id.into_inner() => {}
         Ok(Some(_)) => {
             return (
                 StatusCode::FORBIDDEN,
@@ -662,11 +1048,7 @@ async fn delete_account(
 
     match account_repo.delete_account(account_id).await {
         Ok(()) => {
-            info!(
-                org_id = %org_id,
-                account_id = %account_id,
-                "Account deleted (deactivated)"
-            );
+            info!(account_id = %account_id, "Account deleted (deactivated)");
 
             (StatusCode::NO_CONTENT, ()).into_response()
         }
@@ -698,43 +1080,166 @@ async fn delete_account(
 async fn get_account_balance(
     State(state): State<AppState>,
     auth: AuthUser,
-    Path((org_id, account_id)): Path<(Uuid, Uuid)>,
+    Path((org_id, account_id)): Path<(OrganizationId, AccountId)>,
     Query(query): Query<BalanceQuery>,
+    headers: HeaderMap,
 ) -> impl IntoResponse {
-    let org_repo = OrganizationRepository::new((*state.db).clone());
-
     // Check membership
-    if let Err(response) = check_membership(&org_repo, org_id, auth.user_id()).await {
+    if let Err(response) = check_membership(&state, org_id, auth.user_id()).await {
         return response;
     }
 
-    let account_repo = AccountRepository::new((*state.db).clone());
+    // Use provided date or default to today
+    let as_of = query
+        .as_of
+        .unwrap_or_else(|| chrono::Utc::now().date_naive());
 
-    // Verify account belongs to this organization
-    let account = match account_repo.find_account_by_id(account_id).await {
-        Ok(Some(a)) if a.account.organization_id == org_id => a,
-        Ok(Some(_)) => {
-            return (
-                StatusCode::FORBIDDEN,
+    let etag = match org_data_etag(
+        &state.db,
+        org_id.into_inner(),
+        "account_balance",
+        &[&account_id.to_string(), &as_of.to_string()],
+    )
+    .await
+    {
+        Ok(etag) => etag,
+        Err(response) => return response,
+    };
+
+    respond_cached(&headers, &etag, move || async move {
+        let account_repo = AccountRepository::new((*state.db).clone());
+
+        // Verify account belongs to this organization
+        let account = match account_repo.find_account_by_id(account_id).await {
+            Ok(Some(a)) if a.account.organization_id == org_id.into_inner() => a,
+            Ok(Some(_)) => {
+                return (
+                    StatusCode::FORBIDDEN,
+                    Json(json!({
+                        "error": "forbidden",
+                        "message": "Account does not belong to this organization"
+                    })),
+                )
+                    .into_response();
+            }
+            Ok(None) => {
+                return (
+                    StatusCode::NOT_FOUND,
+                    Json(json!({
+                        "error": "not_found",
+                        "message": "Account not found"
+                    })),
+                )
+                    .into_response();
+            }
+            Err(e) => {
+                error!(error = %e, "Failed to find account");
+                return (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    Json(json!({
+                        "error": "internal_error",
+                        "message": "An error occurred"
+                    })),
+                )
+                    .into_response();
+            }
+        };
+
+        match account_repo.get_balance_at_date(account_id, as_of).await {
+            Ok(balance) => (
+                StatusCode::OK,
                 Json(json!({
-                    "error": "forbidden",
-                    "message": "Account does not belong to this organization"
+                    "account_id": account_id,
+                    "account_code": account.account.code,
+                    "account_name": account.account.name,
+                    "currency": account.account.currency,
+                    "as_of": as_of.to_string(),
+                    "balance": balance.to_string()
                 })),
             )
-                .into_response();
+                .into_response(),
+            Err(e) => {
+                error!(error = %e, "Failed to get account balance");
+                (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    Json(json!({
+                        "error": "internal_error",
+                        "message": "An error occurred"
+                    })),
+                )
+                    .into_response()
+            }
         }
-        Ok(None) => {
+    })
+    .await
+}
+
+/// POST `/organizations/{org_id}/accounts/balance-matrix` - Posted balances of
+/// many accounts at several dates.
+#[allow(clippy::too_many_lines)]
+async fn get_balance_matrix(
+    State(state): State<AppState>,
+    auth: AuthMember,
+    Path(org_id): Path<OrganizationId>,
+    Query(query): Query<BalanceMatrixQuery>,
+    StrictJson(payload): StrictJson<BalanceMatrixRequest>,
+) -> impl IntoResponse {
+    if let Err(response) = check_report_access(&state, org_id, &auth).await {
+        return response;
+    }
+
+    let dates = payload.dates;
+    let invalid_dates = if dates.is_empty() {
+        Some("At least one date is required".to_string())
+    } else if dates.len() > MAX_BALANCE_MATRIX_DATES {
+        Some(format!(
+            "At most {MAX_BALANCE_MATRIX_DATES} dates are allowed, got {}",
+            dates.len()
+        ))
+    } else if dates.iter().collect::<HashSet<_>>().len() < dates.len() {
+        Some("Dates must not repeat".to_string())
+    } else {
+        None
+    };
+    if let Some(message) = invalid_dates {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(json!({
+                "error": "invalid_dates",
+                "message": message
+            })),
+        )
+            .into_response();
+    }
+
+    let filter = BalanceMatrixFilter {
+        account_ids: payload.account_ids,
+        account_types: payload
+            .account_types
+            .iter()
+            .filter_map(|t| string_to_account_type(t))
+            .collect(),
+        include_inactive: payload.include_inactive,
+    };
+    let account_repo = AccountRepository::new((*state.db).clone());
+    let accounts = match account_repo
+        .get_balance_matrix(org_id.into_inner(), &dates, &filter)
+        .await
+    {
+        Ok(accounts) => accounts,
+        Err(AccountError::DatesOutsideFiscalYears(outside)) => {
             return (
-                StatusCode::NOT_FOUND,
+                StatusCode::BAD_REQUEST,
                 Json(json!({
-                    "error": "not_found",
-                    "message": "Account not found"
+                    "error": "date_outside_fiscal_year",
+                    "message": "Every date must fall within a fiscal year",
+                    "dates": outside
                 })),
             )
                 .into_response();
         }
         Err(e) => {
-            error!(error = %e, "Failed to find account");
+            error!(error = %e, "Failed to build balance matrix");
             return (
                 StatusCode::INTERNAL_SERVER_ERROR,
                 Json(json!({
@@ -746,32 +1251,38 @@ async fn get_account_balance(
         }
     };
 
-    // Use provided date or default to today
-    let as_of = query
-        .as_of
-        .unwrap_or_else(|| chrono::Utc::now().date_naive());
-
-    match account_repo.get_balance_at_date(account_id, as_of).await {
-        Ok(balance) => (
-            StatusCode::OK,
-            Json(json!({
-                "account_id": account_id,
-                "account_code": account.account.code,
-                "account_name": account.account.name,
-                "currency": account.account.currency,
-                "as_of": as_of.to_string(),
-                "balance": balance.to_string()
-            })),
-        )
-            .into_response(),
-        Err(e) => {
-            error!(error = %e, "Failed to get account balance");
+    let matrix = BalanceMatrix::new(
+        dates,
+        accounts
+            .into_iter()
+            .map(|a| BalanceMatrixRow {
+                account_id: a.account.id,
+                account_type: account_type_to_string(&a.account.account_type),
+                code: a.account.code,
+                name: a.account.name,
+                currency: a.account.currency,
+                balances: a.balances,
+            })
+            .collect(),
+    );
+
+    match query.format {
+        ExportFormat::Json => (StatusCode::OK, Json(matrix)).into_response(),
+        ExportFormat::Csv => {
+            let style = match export_style(&state.db, org_id.into_inner(), query.formatted).await {
+                Ok(style) => style,
+                Err(response) => return response,
+            };
             (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(json!({
-                    "error": "internal_error",
-                    "message": "An error occurred"
-                })),
+                StatusCode::OK,
+                [
+                    (CONTENT_TYPE, "text/csv; charset=utf-8".to_string()),
+                    (
+                        CONTENT_DISPOSITION,
+                        format!("attachment; filename=\"balance-matrix-{org_id}.csv\""),
+                    ),
+                ],
+                matrix.to_csv(style),
             )
                 .into_response()
         }
@@ -779,16 +1290,14 @@ async fn get_account_balance(
 }
 
 /// GET `/organizations/{org_id}/accounts/{account_id}/ledger` - Get ledger entries for an account.
+#[allow(clippy::too_many_lines)]
 async fn get_account_ledger(
     State(state): State<AppState>,
-    auth: AuthUser,
-    Path((org_id, account_id)): Path<(Uuid, Uuid)>,
+    auth: AuthMember,
+    Path((org_id, account_id)): Path<(OrganizationId, AccountId)>,
     Query(query): Query<LedgerQuery>,
 ) -> impl IntoResponse {
-    let org_repo = OrganizationRepository::new((*state.db).clone());
-
-    // Check membership
-    if let Err(response) = check_membership(&org_repo, org_id, auth.user_id()).await {
+    if let Err(response) = check_report_access(&state, org_id, &auth).await {
         return response;
     }
 
@@ -796,7 +1305,7 @@ async fn get_account_ledger(
 
     // Verify account belongs to this organization
     match account_repo.find_account_by_id(account_id).await {
-        Ok(Some(a)) if a.account.organization_id == org_id => {}
+        Ok(Some(a)) if a.account.organization_id == org_id.into_inner() => {}
         Ok(Some(_)) => {
             return (
                 StatusCode::FORBIDDEN,
@@ -835,7 +1344,14 @@ async fn get_account_ledger(
     let limit = query.limit.unwrap_or(50).clamp(1, 100);
 
     match account_repo
-        .get_ledger_entries(account_id, query.from, query.to, page, limit)
+        .get_ledger_entries(
+            account_id,
+            query.from,
+            query.to,
+            query.overridden_only,
+            page,
+            limit,
+        )
         .await
     {
         Ok(result) => {
@@ -859,7 +1375,9 @@ async fn get_account_ledger(
                     memo: e.entry.memo,
                     previous_balance: e.entry.account_previous_balance.to_string(),
                     current_balance: e.entry.account_current_balance.to_string(),
+                    event_at: e.entry.event_at.to_rfc3339(),
                     created_at: e.entry.created_at.to_rfc3339(),
+                    is_override: e.entry.is_override,
                 })
                 .collect();
 
@@ -891,43 +1409,408 @@ async fn get_account_ledger(
     }
 }
 
+/// GET `/organizations/{org_id}/accounts/{account_id}/activity` - Get posted activity per week or month.
+async fn get_account_activity(
+    State(state): State<AppState>,
+    auth: AuthMember,
+    Path((org_id, account_id)): Path<(OrganizationId, AccountId)>,
+    Query(query): Query<ActivityQuery>,
+    headers: HeaderMap,
+) -> impl IntoResponse {
+    if let Err(response) = check_report_access(&state, org_id, &auth).await {
+        return response;
+    }
+
+    let (from, to, granularity) = match activity_range(&query) {
+        Ok(range) => range,
+        Err(response) => return response,
+    };
+
+    let etag = match org_data_etag(
+        &state.db,
+        org_id.into_inner(),
+        "account_activity",
+        &[
+            &account_id.to_string(),
+            &from.to_string(),
+            &to.to_string(),
+            granularity.as_str(),
+        ],
+    )
+    .await
+    {
+        Ok(etag) => etag,
+        Err(response) => return response,
+    };
+
+    respond_cached(&headers, &etag, move || async move {
+        let account_repo = AccountRepository::new((*state.db).clone());
+
+        match account_repo
+            .get_activity(org_id, account_id, from, to, granularity)
+            .await
+        {
+            Ok(activity) => {
+                let response = AccountActivityResponse {
+                    account_id: activity.account.id,
+                    account_code: activity.account.code,
+                    account_name: activity.account.name,
+                    currency: activity.account.currency,
+                    from,
+                    to,
+                    granularity,
+                    opening_balance: activity.opening_balance.to_string(),
+                    buckets: activity
+                        .buckets
+                        .into_iter()
+                        .map(|b| ActivityBucketResponse {
+                            start: b.start,
+                            end: b.end,
+                            debit: b.debit.to_string(),
+                            credit: b.credit.to_string(),
+                            net_movement: b.net_movement.to_string(),
+                            ending_balance: b.ending_balance.to_string(),
+                        })
+                        .collect(),
+                };
+                (StatusCode::OK, Json(response)).into_response()
+            }
+            Err(zeltra_db::repositories::account::AccountError::AccountNotFound(_)) => (
+                StatusCode::NOT_FOUND,
+                Json(json!({
+                    "error": "not_found",
+                    "message": "Account not found"
+                })),
+            )
+                .into_response(),
+            Err(e) => {
+                error!(error = %e, "Failed to get account activity");
+                (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    Json(json!({
+                        "error": "internal_error",
+                        "message": "An error occurred"
+                    })),
+                )
+                    .into_response()
+            }
+        }
+    })
+    .await
+}
+
 // Helper functions
 
-async fn check_membership(
-    org_repo: &OrganizationRepository,
-    org_id: Uuid,
-    user_id: Uuid,
-) -> Result<(), axum::response::Response> {
-    match org_repo.is_member(org_id, user_id).await {
-        Ok(true) => Ok(()),
-        Ok(false) => Err((
-            StatusCode::FORBIDDEN,
-            Json(json!({
-                "error": "forbidden",
-                "message": "You are not a member of this organization"
-            })),
+/// GET `/organizations/{org_id}/accounts/{account_id}/default-dimensions` - List default dimension values.
+async fn list_default_dimensions(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path((org_id, account_id)): Path<(OrganizationId, AccountId)>,
+) -> impl IntoResponse {
+    // Check membership
+    if let Err(response) = check_membership(&state, org_id, auth.user_id()).await {
+        return response;
+    }
+
+    let dimension_repo = DimensionRepository::new((*state.db).clone());
+    match dimension_repo
+        .list_account_defaults(org_id.into_inner(), account_id.into_inner())
+        .await
+    {
+        Ok(defaults) => {
+            let defaults: Vec<DefaultDimensionResponse> =
+                defaults.into_iter().map(Into::into).collect();
+            (StatusCode::OK, Json(json!({ "defaults": defaults }))).into_response()
+        }
+        Err(e) => default_dimension_error_response(&e),
+    }
+}
+
+/// POST `/organizations/{org_id}/accounts/{account_id}/default-dimensions` - Add a default dimension value.
+async fn add_default_dimension(
+    State(state): State<AppState>,
+    auth: AuthMember,
+    Path((org_id, account_id)): Path<(OrganizationId, AccountId)>,
+    StrictJson(payload): StrictJson<AddDefaultDimensionRequest>,
+) -> impl IntoResponse {
+    let org_repo = OrganizationRepository::new((*state.db).clone());
+
+    // Check admin/owner role
+    if let Err(response) = check_admin_role(&org_repo, org_id, &auth).await {
+        return response;
+    }
+
+    let dimension_repo = DimensionRepository::new((*state.db).clone());
+    match dimension_repo
+        .add_account_default(
+            org_id.into_inner(),
+            account_id.into_inner(),
+            payload.dimension_value_id,
         )
-            .into_response()),
-        Err(e) => {
-            error!(error = %e, "Database error checking membership");
-            Err((
+        .await
+    {
+        Ok(default) => {
+            info!(
+                account_id = %account_id,
+                dimension_value_id = %payload.dimension_value_id,
+                "Account default dimension added"
+            );
+            (
+                StatusCode::CREATED,
+                Json(DefaultDimensionResponse::from(default)),
+            )
+                .into_response()
+        }
+        Err(e) => default_dimension_error_response(&e),
+    }
+}
+
+/// DELETE `/organizations/{org_id}/accounts/{account_id}/default-dimensions/{dimension_value_id}` - Remove a default dimension value.
+async fn remove_default_dimension(
+    State(state): State<AppState>,
+    auth: AuthMember,
+    Path((org_id, account_id, dimension_value_id)): Path<(OrganizationId, AccountId, Uuid)>,
+) -> impl IntoResponse {
+    let org_repo = OrganizationRepository::new((*state.db).clone());
+
+    // Check admin/owner role
+    if let Err(response) = check_admin_role(&org_repo, org_id, &auth).await {
+        return response;
+    }
+
+    let dimension_repo = DimensionRepository::new((*state.db).clone());
+    match dimension_repo
+        .remove_account_default(
+            org_id.into_inner(),
+            account_id.into_inner(),
+            dimension_value_id,
+        )
+        .await
+    {
+        Ok(()) => {
+            info!(
+                account_id = %account_id,
+                dimension_value_id = %dimension_value_id,
+                "Account default dimension removed"
+            );
+            (StatusCode::NO_CONTENT, ()).into_response()
+        }
+        Err(e) => default_dimension_error_response(&e),
+    }
+}
+
+/// Maps a default dimension error to an API response.
+fn default_dimension_error_response(e: &DimensionError) -> axum::response::Response {
+    let (status, code) = match e {
+        DimensionError::AccountNotFound(_) | DimensionError::DefaultNotFound(_) => {
+            (StatusCode::NOT_FOUND, "not_found")
+        }
+        DimensionError::ValueNotFound(_) => (StatusCode::BAD_REQUEST, "dimension_value_not_found"),
+        DimensionError::ValueInactive(_) => (StatusCode::BAD_REQUEST, "dimension_value_inactive"),
+        DimensionError::DefaultAlreadySet(_) => (StatusCode::CONFLICT, "default_already_set"),
+        _ => {
+            error!(error = %e, "Failed to manage account default dimensions");
+            return (
                 StatusCode::INTERNAL_SERVER_ERROR,
                 Json(json!({
                     "error": "internal_error",
                     "message": "An error occurred"
                 })),
             )
-                .into_response())
+                .into_response();
         }
+    };
+
+    (
+        status,
+        Json(json!({
+            "error": code,
+            "message": e.to_string()
+        })),
+    )
+        .into_response()
+}
+
+/// Validates activity query parameters, applying defaults.
+#[allow(clippy::result_large_err)]
+fn activity_range(
+    query: &ActivityQuery,
+) -> Result<(NaiveDate, NaiveDate, ActivityGranularity), axum::response::Response> {
+    let granularity_param = query.granularity.as_deref().unwrap_or("month");
+    let Some(granularity) = ActivityGranularity::parse(granularity_param) else {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(json!({
+                "error": "invalid_granularity",
+                "message": format!("Unknown granularity '{granularity_param}'. Allowed: month, week")
+            })),
+        )
+            .into_response());
+    };
+
+    let to = query.to.unwrap_or_else(|| chrono::Utc::now().date_naive());
+    let from = query
+        .from
+        .unwrap_or_else(|| ActivityGranularity::Month.bucket_start(to) - chrono::Months::new(11));
+    if from > to {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(json!({
+                "error": "invalid_date_range",
+                "message": "from must be on or before to"
+            })),
+        )
+            .into_response());
     }
+    if granularity.bucket_starts(from, to).len() > MAX_ACTIVITY_BUCKETS {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(json!({
+                "error": "range_too_large",
+                "message": format!("The range spans more than {MAX_ACTIVITY_BUCKETS} buckets")
+            })),
+        )
+            .into_response());
+    }
+
+    Ok((from, to, granularity))
+}
+
+/// Converts a parsed CSV row into account input, checking type and subtype.
+fn import_row_to_input(
+    org_id: OrganizationId,
+    row: &ImportRow,
+) -> Result<ImportAccountInput, Vec<ImportIssue>> {
+    let mut issues = Vec::new();
+    let account_type = string_to_account_type(&row.account_type);
+    if account_type.is_none() {
+        issues.push(ImportIssue::new(
+            row.row,
+            "type",
+            format!(
+                "Invalid account type '{}'. Must be one of: asset, liability, equity, revenue, expense",
+                row.account_type
+            ),
+        ));
+    }
+    let account_subtype = match &row.subtype {
+        None => None,
+        Some(subtype) => {
+            let parsed = string_to_account_subtype(subtype);
+            if parsed.is_none() {
+                issues.push(ImportIssue::new(
+                    row.row,
+                    "subtype",
+                    format!("Invalid account subtype '{subtype}'"),
+                ));
+            }
+            parsed
+        }
+    };
+
+    match account_type {
+        Some(account_type) if issues.is_empty() => Ok(ImportAccountInput {
+            row: row.row,
+            account: CreateAccountInput {
+                organization_id: org_id.into_inner(),
+                code: row.code.clone(),
+                name: row.name.clone(),
+                description: None,
+                account_type,
+                account_subtype,
+                parent_id: None,
+                currency: row.currency.clone(),
+                is_active: true,
+                allow_direct_posting: row.allow_direct_posting,
+                is_bank_account: false,
+                bank_account_number: None,
+            },
+            parent_code: row.parent_code.clone(),
+        }),
+        _ => Err(issues),
+    }
+}
+
+/// Groups import issues by row, listing every row that was read or has
+/// problems.
+fn import_row_results(rows: &[ImportRow], issues: Vec<ImportIssue>) -> Vec<ImportRowResult> {
+    let mut results: std::collections::BTreeMap<usize, ImportRowResult> = rows
+        .iter()
+        .map(|row| {
+            (
+                row.row,
+                ImportRowResult {
+                    row: row.row,
+                    code: Some(row.code.clone()),
+                    valid: true,
+                    errors: Vec::new(),
+                },
+            )
+        })
+        .collect();
+    for issue in issues {
+        let result = results.entry(issue.row).or_insert_with(|| ImportRowResult {
+            row: issue.row,
+            code: None,
+            valid: true,
+            errors: Vec::new(),
+        });
+        result.valid = false;
+        result.errors.push(issue);
+    }
+    results.into_values().collect()
+}
+
+fn invalid_import_response(rows: &[ImportRowResult]) -> axum::response::Response {
+    let invalid = rows.iter().filter(|r| !r.valid).count();
+    (
+        StatusCode::BAD_REQUEST,
+        Json(json!({
+            "error": "invalid_import",
+            "message": format!("{invalid} rows have errors; no accounts were created"),
+            "rows": rows
+        })),
+    )
+        .into_response()
+}
+
+/// Checks that the caller may create and edit accounts, returning their role.
+async fn check_chart_of_accounts_role(
+    state: &AppState,
+    org_id: OrganizationId,
+    auth: &AuthMember,
+) -> Result<CoreUserRole, axum::response::Response> {
+    let role = auth.role_in(state, org_id).await?;
+    if role.can_manage_chart_of_accounts() {
+        return Ok(role);
+    }
+
+    Err((
+        StatusCode::FORBIDDEN,
+        Json(json!({
+            "error": "forbidden",
+            "message": "You need accountant role or above to perform this action"
+        })),
+    )
+        .into_response())
 }
 
 async fn check_admin_role(
     org_repo: &OrganizationRepository,
-    org_id: Uuid,
-    user_id: Uuid,
+    org_id: OrganizationId,
+    auth: &AuthMember,
 ) -> Result<(), axum::response::Response> {
-    match org_repo.has_role(org_id, user_id, UserRole::Admin).await {
+    let allowed = match auth.has_role(org_id, &UserRole::Admin) {
+        Some(allowed) => Ok(allowed),
+        // Token was issued for another organization
+        None => {
+            org_repo
+                .has_role(org_id.into_inner(), auth.user_id(), UserRole::Admin)
+                .await
+        }
+    };
+
+    match allowed {
         Ok(true) => Ok(()),
         Ok(false) => Err((
             StatusCode::FORBIDDEN,
@@ -972,6 +1855,39 @@ fn string_to_account_type(s: &str) -> Option<AccountType> {
     }
 }
 
+/// Accepts the account types [`string_to_account_type`] knows.
+fn deserialize_account_type<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<String, D::Error> {
+    enum_string(deserializer, string_to_account_type, || {
+        AccountType::iter()
+            .map(|t| account_type_to_string(&t))
+            .collect()
+    })
+}
+
+/// Accepts a list of the account types [`string_to_account_type`] knows.
+fn deserialize_account_types<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Vec<String>, D::Error> {
+    let values = Vec::<String>::deserialize(deserializer)?;
+    for value in &values {
+        deserialize_account_type(serde::de::value::StrDeserializer::<D::Error>::new(value))?;
+    }
+    Ok(values)
+}
+
+/// Accepts the subtypes [`string_to_account_subtype`] knows, or null.
+fn deserialize_account_subtype<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<String>, D::Error> {
+    optional_enum_string(deserializer, string_to_account_subtype, || {
+        AccountSubtype::iter()
+            .map(|s| account_subtype_to_string(&s))
+            .collect()
+    })
+}
+
 fn account_subtype_to_string(s: &AccountSubtype) -> String {
     match s {
         AccountSubtype::Cash => "cash".to_string(),
@@ -1036,3 +1952,368 @@ fn string_to_account_subtype(s: &str) -> Option<AccountSubtype> {
         _ => None,
     }
 }
+
+#[cfg(test)]
+mod integration_tests {
+    use super::*;
+    use zeltra_test_support::{
+        OrgFixture, TestDb, access_token, json_request, jwt_service, send_request, test_app_state,
+    };
+
+    async fn send(
+        state: &AppState,
+        method: &str,
+        uri: &str,
+        token: &str,
+        body: serde_json::Value,
+    ) -> (StatusCode, serde_json::Value) {
+        zeltra_test_support::send(state.authenticated(routes()), method, uri, token, body).await
+    }
+
+    #[tokio::test]
+    async fn test_accountant_can_create_but_not_delete_account() {
+        let test_db = TestDb::new().await;
+        let state: AppState = test_app_state(&test_db);
+        let org = OrgFixture::new()
+            .with_member(UserRole::Accountant)
+            .with_member(UserRole::Viewer)
+            .create(test_db.conn())
+            .await;
+        let jwt = jwt_service();
+        let accountant = access_token(&jwt, org.id, org.member(&UserRole::Accountant));
+        let uri = format!("/organizations/{}/accounts", org.id);
+
+        let (status, account) = send(
+            &state,
+            "POST",
+            &uri,
+            &accountant,
+            json!({ "code": "6150", "name": "Software", "type": "expense", "currency": "USD" }),
+        )
+        .await;
+        assert_eq!(status, StatusCode::CREATED);
+        let account_uri = format!("{uri}/{}", account["id"].as_str().unwrap());
+
+        let (status, updated) = send(
+            &state,
+            "PUT",
+            &account_uri,
+            &accountant,
+            json!({ "name": "Software subscriptions" }),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(updated["name"], "Software subscriptions");
+
+        // Deactivating and deleting stay admin-only
+        let (status, _) = send(
+            &state,
+            "PUT",
+            &account_uri,
+            &accountant,
+            json!({ "is_active": false }),
+        )
+        .await;
+        assert_eq!(status, StatusCode::FORBIDDEN);
+        let (status, _) = send(&state, "DELETE", &account_uri, &accountant, json!({})).await;
+        assert_eq!(status, StatusCode::FORBIDDEN);
+
+        // Viewers still cannot create
+        let viewer = access_token(&jwt, org.id, org.member(&UserRole::Viewer));
+        let (status, _) = send(
+            &state,
+            "POST",
+            &uri,
+            &viewer,
+            json!({ "code": "6160", "name": "Hosting", "type": "expense", "currency": "USD" }),
+        )
+        .await;
+        assert_eq!(status, StatusCode::FORBIDDEN);
+
+        let owner = access_token(&jwt, org.id, &org.owner);
+        let (status, _) = send(&state, "DELETE", &account_uri, &owner, json!({})).await;
+        assert_eq!(status, StatusCode::NO_CONTENT);
+    }
+
+    #[tokio::test]
+    async fn test_import_accounts_dry_run_then_create() {
+        let test_db = TestDb::new().await;
+        let state: AppState = test_app_state(&test_db);
+        let org = OrgFixture::new()
+            .with_member(UserRole::Accountant)
+            .create(test_db.conn())
+            .await;
+        let token = access_token(&jwt_service(), org.id, org.member(&UserRole::Accountant));
+        let uri = format!("/organizations/{}/accounts/import", org.id);
+
+        let csv = "code,name,type,subtype,parent_code,currency\n\
+                   6110,Cloud hosting,expense,operating_expense,6100,USD\n\
+                   6100,Technology,expense,,,USD\n\
+                   6120,Licences,expenses,,6100,USD\n";
+        let (status, body) = send(
+            &state,
+            "POST",
+            &format!("{uri}?dry_run=true"),
+            &token,
+            json!({ "csv": csv }),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["valid"], false);
+        assert_eq!(body["rows"][0]["valid"], true);
+        assert_eq!(body["rows"][2]["row"], 4);
+        assert_eq!(body["rows"][2]["errors"][0]["column"], "type");
+
+        // Nothing was written by the dry run
+        let list_uri = format!("/organizations/{}/accounts", org.id);
+        let (_, accounts) = send(&state, "GET", &list_uri, &token, json!(null)).await;
+        assert_eq!(accounts["accounts"].as_array().map(Vec::len), Some(0));
+
+        let (status, body) = send(&state, "POST", &uri, &token, json!({ "csv": csv })).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body["error"], "invalid_import");
+
+        let csv = csv.replace("expenses", "expense");
+        let (status, body) = send(&state, "POST", &uri, &token, json!({ "csv": csv })).await;
+        assert_eq!(status, StatusCode::CREATED);
+        assert_eq!(body["created"], 3);
+        assert_eq!(body["accounts"][0]["code"], "6100");
+        assert_eq!(body["accounts"][1]["parent_id"], body["accounts"][0]["id"]);
+    }
+
+    #[tokio::test]
+    async fn test_account_detail_has_last_modified_and_list_syncs_changes() {
+        let test_db = TestDb::new().await;
+        let state: AppState = test_app_state(&test_db);
+        let org = OrgFixture::new()
+            .with_accounts(&[("1000", AccountType::Asset), ("5000", AccountType::Expense)])
+            .create(test_db.conn())
+            .await;
+        let token = access_token(&jwt_service(), org.id, &org.owner);
+        let uri = format!("/organizations/{}/accounts", org.id);
+        let account_uri = format!("{uri}/{}", org.account("5000"));
+
+        let request = json_request("GET", &account_uri, &token, &json!(null));
+        let response = send_request(state.authenticated(routes()), request).await;
+        assert_eq!(response.status, StatusCode::OK);
+        let last_modified = response
+            .header(axum::http::header::LAST_MODIFIED.as_str())
+            .unwrap()
+            .to_string();
+        assert!(last_modified.ends_with(" GMT"));
+
+        let (_, all) = send(&state, "GET", &uri, &token, json!(null)).await;
+        let watermark = all["accounts"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .filter_map(|a| a["updated_at"].as_str())
+            .filter_map(|t| DateTime::parse_from_rfc3339(t).ok())
+            .max()
+            .unwrap()
+            .with_timezone(&Utc);
+
+        let (status, _) = send(
+            &state,
+            "PUT",
+            &account_uri,
+            &token,
+            json!({ "name": "Travel" }),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+
+        let since = watermark.format("%Y-%m-%dT%H:%M:%S%.6fZ");
+        let (status, changed) = send(
+            &state,
+            "GET",
+            &format!("{uri}?modified_since={since}"),
+            &token,
+            json!(null),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        let changed = changed["accounts"].as_array().unwrap();
+        assert_eq!(changed.len(), 1);
+        assert_eq!(changed[0]["code"], "5000");
+        assert_eq!(changed[0]["name"], "Travel");
+    }
+
+    #[tokio::test]
+    async fn test_balance_matrix_json_csv_and_date_checks() {
+        let test_db = TestDb::new().await;
+        let state: AppState = test_app_state(&test_db);
+        let org = OrgFixture::new()
+            .with_fiscal_year_2025()
+            .with_accounts(&[("1000", AccountType::Asset), ("5000", AccountType::Expense)])
+            .with_member(UserRole::Viewer)
+            .create(test_db.conn())
+            .await;
+        let token = access_token(&jwt_service(), org.id, org.member(&UserRole::Viewer));
+        let uri = format!("/organizations/{}/accounts/balance-matrix", org.id);
+
+        let (status, body) = send(
+            &state,
+            "POST",
+            &uri,
+            &token,
+            json!({ "dates": ["2025-01-31", "2025-02-28"], "account_types": ["expense"] }),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["dates"], json!(["2025-01-31", "2025-02-28"]));
+        assert_eq!(body["rows"].as_array().unwrap().len(), 1);
+        assert_eq!(body["rows"][0]["code"], "5000");
+        assert_eq!(body["rows"][0]["balances"], json!(["0", "0"]));
+        assert_eq!(body["totals"][0]["account_type"], "expense");
+
+        let csv_uri = format!("{uri}?format=csv");
+        let request = json_request(
+            "POST",
+            &csv_uri,
+            &token,
+            &json!({ "dates": ["2025-01-31"] }),
+        );
+        let response = send_request(state.authenticated(routes()), request).await;
+        assert_eq!(response.status, StatusCode::OK);
+        assert_eq!(response.headers[CONTENT_TYPE], "text/csv; charset=utf-8");
+        assert_eq!(
+            response.text(),
+            "code,name,type,currency,2025-01-31\n\
+             1000,Account 1000,asset,USD,0\n\
+             5000,Account 5000,expense,USD,0\n\
+             ,Total asset,asset,,0\n\
+             ,Total expense,expense,,0\n"
+        );
+
+        let too_many: Vec<_> = (1..=14).map(|d| format!("2025-01-{d:02}")).collect();
+        for dates in [
+            json!([]),
+            json!(too_many),
+            json!(["2025-01-31", "2025-01-31"]),
+        ] {
+            let (status, body) =
+                send(&state, "POST", &uri, &token, json!({ "dates": dates })).await;
+            assert_eq!(status, StatusCode::BAD_REQUEST);
+            assert_eq!(body["error"], "invalid_dates");
+        }
+
+        let (status, body) = send(
+            &state,
+            "POST",
+            &uri,
+            &token,
+            json!({ "dates": ["2024-12-31", "2025-12-31"] }),
+        )
+        .await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body["error"], "date_outside_fiscal_year");
+        assert_eq!(body["dates"], json!(["2024-12-31"]));
+
+        let (status, _) = send(
+            &state,
+            "POST",
+            &uri,
+            &token,
+            json!({ "dates": ["2025-01-31"], "account_types": ["expenses"] }),
+        )
+        .await;
+        assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
+    #[tokio::test]
+    async fn test_balance_matrix_formatted_csv_and_submitter_access() {
+        let test_db = TestDb::new().await;
+        let state: AppState = test_app_state(&test_db);
+        let org = OrgFixture::new()
+            .with_fiscal_year_2025()
+            .with_accounts(&[("1000", AccountType::Asset)])
+            .with_member(UserRole::Submitter)
+            .with_member(UserRole::Viewer)
+            .create(test_db.conn())
+            .await;
+        OrganizationRepository::new(test_db.conn().clone())
+            .update_settings(
+                org.id.into_inner(),
+                &json!({"formatting": {"locale": "de-DE"}}),
+            )
+            .await
+            .unwrap();
+        let jwt = jwt_service();
+        let uri = format!("/organizations/{}/accounts/balance-matrix", org.id);
+        let body = json!({ "dates": ["2025-01-31"] });
+
+        let submitter = access_token(&jwt, org.id, org.member(&UserRole::Submitter));
+        let (status, response) = send(&state, "POST", &uri, &submitter, body.clone()).await;
+        assert_eq!(status, StatusCode::FORBIDDEN);
+        assert_eq!(response["error"], "report_access_restricted");
+
+        let viewer = access_token(&jwt, org.id, org.member(&UserRole::Viewer));
+        let csv_uri = format!("{uri}?format=csv&formatted=true");
+        let request = json_request("POST", &csv_uri, &viewer, &body);
+        let response = send_request(state.authenticated(routes()), request).await;
+        assert_eq!(response.status, StatusCode::OK);
+        assert_eq!(
+            response.text(),
+            "code,name,type,currency,31.01.2025\n\
+             1000,Account 1000,asset,USD,\"0,00\"\n\
+             ,Total asset,asset,,\"0,00\"\n"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_submitter_cannot_read_account_ledger() {
+        let test_db = TestDb::new().await;
+        let state: AppState = test_app_state(&test_db);
+        let org = OrgFixture::new()
+            .with_fiscal_year_2025()
+            .with_accounts(&[("5000", AccountType::Expense)])
+            .with_member(UserRole::Submitter)
+            .with_member(UserRole::Viewer)
+            .create(test_db.conn())
+            .await;
+        let jwt = jwt_service();
+        let uri = format!(
+            "/organizations/{}/accounts/{}/ledger",
+            org.id,
+            org.account("5000")
+        );
+
+        let submitter = access_token(&jwt, org.id, org.member(&UserRole::Submitter));
+        let (status, body) = send(&state, "GET", &uri, &submitter, json!(null)).await;
+        assert_eq!(status, StatusCode::FORBIDDEN);
+        assert_eq!(body["error"], "report_access_restricted");
+
+        let viewer = access_token(&jwt, org.id, org.member(&UserRole::Viewer));
+        let (status, _) = send(&state, "GET", &uri, &viewer, json!(null)).await;
+        assert_eq!(status, StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_submitter_cannot_read_account_activity() {
+        let test_db = TestDb::new().await;
+        let state: AppState = test_app_state(&test_db);
+        let org = OrgFixture::new()
+            .with_fiscal_year_2025()
+            .with_accounts(&[("5000", AccountType::Expense)])
+            .with_member(UserRole::Submitter)
+            .with_member(UserRole::Viewer)
+            .create(test_db.conn())
+            .await;
+        let jwt = jwt_service();
+        let uri = format!(
+            "/organizations/{}/accounts/{}/activity?from=2025-01-01&to=2025-03-31",
+            org.id,
+            org.account("5000")
+        );
+
+        let submitter = access_token(&jwt, org.id, org.member(&UserRole::Submitter));
+        let (status, body) = send(&state, "GET", &uri, &submitter, json!(null)).await;
+        assert_eq!(status, StatusCode::FORBIDDEN);
+        assert_eq!(body["error"], "report_access_restricted");
+
+        let viewer = access_token(&jwt, org.id, org.member(&UserRole::Viewer));
+        let (status, _) = send(&state, "GET", &uri, &viewer, json!(null)).await;
+        assert_eq!(status, StatusCode::OK);
+    }
+}
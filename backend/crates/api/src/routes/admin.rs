@@ -0,0 +1,441 @@
+//! Operator endpoints.
+//!
+//! Guarded by [`require_admin`](crate::middleware::admin::require_admin)
+//! rather than the regular organization-scoped authentication.
+
+use axum::{
+    Extension, Json, Router,
+    extract::{Path, State},
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    routing::{get, post},
+};
+use serde::Serialize;
+use serde_json::json;
+use tracing::{error, info};
+use uuid::Uuid;
+use zeltra_db::repositories::{
+    BalanceSnapshotRepository, OperatorActionInput, OperatorAuditRepository, UserError,
+    UserRepository,
+};
+use zeltra_jobs::JobStatus;
+
+use crate::{AppState, middleware::admin::AdminOperator};
+
+/// Background job status listing.
+#[derive(Debug, Serialize)]
+pub struct JobsResponse {
+    /// Status of each registered job, in registration order.
+    pub jobs: Vec<JobStatus>,
+}
+
+/// GET `/admin/jobs` - Last run, last error and next run of every background job.
+async fn list_jobs(State(state): State<AppState>) -> Json<JobsResponse> {
+    let jobs = state
+        .jobs
+        .as_ref()
+        .map(zeltra_jobs::JobBoard::snapshot)
+        .unwrap_or_default();
+    Json(JobsResponse { jobs })
+}
+
+/// Balance snapshot backfill result.
+#[derive(Debug, Serialize)]
+pub struct BackfillResponse {
+    /// Organizations whose snapshots were rebuilt.
+    pub organizations: u64,
+    /// Snapshot rows written.
+    pub snapshots: u64,
+}
+
+/// POST `/admin/balance-snapshots/backfill` - Rebuild the period-end balance
+/// snapshots of every organization from the ledger.
+async fn backfill_balance_snapshots(State(state): State<AppState>) -> Response {
+    match BalanceSnapshotRepository::new((*state.db).clone())
+        .backfill_all()
+        .await
+    {
+        Ok(summary) => {
+            info!(
+                organizations = summary.organizations,
+                snapshots = summary.snapshots,
+                "Balance snapshots backfilled"
+            );
+            Json(BackfillResponse {
+                organizations: summary.organizations,
+                snapshots: summary.snapshots,
+            })
+            .into_response()
+        }
+        Err(e) => {
+            error!(error = %e, "Failed to backfill balance snapshots");
+            internal_error()
+        }
+    }
+}
+
+/// User deactivation result.
+#[derive(Debug, Serialize)]
+pub struct DeactivateUserResponse {
+    /// The deactivated user.
+    pub user_id: Uuid,
+    /// Whether the account was active before this request.
+    pub was_active: bool,
+}
+
+/// POST `/auth/admin/users/{id}/deactivate` - Disable a user's account,
+/// revoking their sessions and access tokens.
+///
+/// The user keeps their memberships and data; erasing personal data is a
+/// separate operator command (`admin user erase`).
+async fn deactivate_user(
+    State(state): State<AppState>,
+    Extension(operator): Extension<AdminOperator>,
+    Path(user_id): Path<Uuid>,
+) -> Response {
+    let was_active = match UserRepository::new((*state.db).clone())
+        .deactivate(user_id)
+        .await
+    {
+        Ok(was_active) => was_active,
+        Err(UserError::NotFound(_)) => {
+            return (
+                StatusCode::NOT_FOUND,
+                Json(json!({
+                    "error": "user_not_found",
+                    "message": "User not found"
+                })),
+            )
+                .into_response();
+        }
+        Err(e) => {
+            error!(error = %e, %user_id, "Failed to deactivate user");
+            return internal_error();
+        }
+    };
+
+    let recorded = OperatorAuditRepository::new((*state.db).clone())
+        .record(OperatorActionInput {
+            operator: operator.0,
+            command: "user deactivate".to_string(),
+            target: user_id.to_string(),
+            organization_id: None,
+            executed: true,
+            reason: None,
+            details: json!({ "user_id": user_id, "was_active": was_active }),
+        })
+        .await;
+    if let Err(e) = recorded {
+        error!(error = %e, %user_id, "Failed to audit user deactivation");
+        return internal_error();
+    }
+
+    info!(%user_id, was_active, "User deactivated");
+    Json(DeactivateUserResponse {
+        user_id,
+        was_active,
+    })
+    .into_response()
+}
+
+fn internal_error() -> Response {
+    (
+        StatusCode::INTERNAL_SERVER_ERROR,
+        Json(json!({
+            "error": "internal_error",
+            "message": "An error occurred"
+        })),
+    )
+        .into_response()
+}
+
+/// Creates the admin routes.
+pub fn routes() -> Router<AppState> {
+    Router::new()
+        .route("/admin/jobs", get(list_jobs))
+        .route(
+            "/admin/balance-snapshots/backfill",
+            post(backfill_balance_snapshots),
+        )
+        .route("/auth/admin/users/{id}/deactivate", post(deactivate_user))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use axum::{
+        body::{Body, to_bytes},
+        http::{
+            Request, StatusCode,
+            header::{AUTHORIZATION, CONTENT_TYPE},
+        },
+    };
+    use sea_orm::{
+        ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, Set,
+    };
+    use tower::ServiceExt;
+    use uuid::Uuid;
+    use zeltra_core::auth::{ApiKeyRole, ApiKeyScope, hash_password};
+    use zeltra_db::entities::{operator_audit_log, sea_orm_active_enums::UserRole, users};
+    use zeltra_db::repositories::{ApiKeyRepository, CreateApiKeyInput, Stores};
+    use zeltra_shared::{AdminConfig, EmailConfig, EmailService, JwtConfig, JwtService};
+    use zeltra_test_support::{OrgFixture, TestDb, test_app_state};
+
+    use super::*;
+    use crate::{
+        cache::{DashboardCache, MembershipCache},
+        create_router,
+        middleware::BodyLimits,
+    };
+
+    fn test_state(admin: AdminConfig) -> AppState {
+        AppState {
+            db: Arc::new(DatabaseConnection::Disconnected),
+            jwt_service: Arc::new(JwtService::new(JwtConfig::default())),
+            email_service: Arc::new(EmailService::new(EmailConfig::default())),
+            storage: None,
+            metrics: None,
+            jobs: Some(zeltra_jobs::JobBoard::default()),
+            admin,
+            dashboard_cache: DashboardCache::default(),
+            membership_cache: MembershipCache::default(),
+            currencies: Arc::default(),
+            stores: Stores::postgres(&DatabaseConnection::Disconnected),
+        }
+    }
+
+    async fn get_jobs(state: AppState, bearer: Option<&str>) -> (StatusCode, serde_json::Value) {
+        let mut request = Request::builder().uri("/api/v1/admin/jobs");
+        if let Some(bearer) = bearer {
+            request = request.header(AUTHORIZATION, format!("Bearer {bearer}"));
+        }
+        let response = create_router(state, BodyLimits::default())
+            .oneshot(request.body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        let status = response.status();
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        (status, serde_json::from_slice(&body).unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_admin_token_lists_jobs() {
+        let state = test_state(AdminConfig {
+            organization_id: None,
+            token: Some("operator-secret".to_string()),
+        });
+
+        let (status, body) = get_jobs(state, Some("operator-secret")).await;
+
+        assert_eq!(status, StatusCode::OK);
+        assert!(body["jobs"].as_array().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_missing_token_is_unauthorized() {
+        let (status, body) = get_jobs(test_state(AdminConfig::default()), None).await;
+
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+        assert_eq!(body["error"], "missing_token");
+    }
+
+    #[tokio::test]
+    async fn test_admin_org_owner_is_allowed() {
+        let admin_org = Uuid::new_v4();
+        let state = test_state(AdminConfig {
+            organization_id: Some(admin_org),
+            token: None,
+        });
+        let token = state
+            .jwt_service
+            .generate_access_token(Uuid::new_v4(), admin_org, "owner")
+            .unwrap();
+
+        let (status, _) = get_jobs(state, Some(&token)).await;
+
+        assert_eq!(status, StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_owner_of_other_org_is_forbidden() {
+        let state = test_state(AdminConfig {
+            organization_id: Some(Uuid::new_v4()),
+            token: None,
+        });
+        let token = state
+            .jwt_service
+            .generate_access_token(Uuid::new_v4(), Uuid::new_v4(), "owner")
+            .unwrap();
+
+        let (status, body) = get_jobs(state, Some(&token)).await;
+
+        assert_eq!(status, StatusCode::FORBIDDEN);
+        assert_eq!(body["error"], "forbidden");
+    }
+
+    async fn post_json(
+        state: &AppState,
+        uri: &str,
+        bearer: Option<&str>,
+        body: serde_json::Value,
+    ) -> (StatusCode, serde_json::Value) {
+        let mut request = Request::builder()
+            .method("POST")
+            .uri(uri)
+            .header(CONTENT_TYPE, "application/json");
+        if let Some(bearer) = bearer {
+            request = request.header(AUTHORIZATION, format!("Bearer {bearer}"));
+        }
+        let response = create_router(state.clone(), BodyLimits::default())
+            .oneshot(request.body(Body::from(body.to_string())).unwrap())
+            .await
+            .unwrap();
+        let status = response.status();
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        (status, serde_json::from_slice(&body).unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_deactivated_and_erased_users_cannot_log_in() {
+        let test_db = TestDb::new().await;
+        let db = test_db.conn();
+        let org = OrgFixture::new()
+            .with_member(UserRole::Viewer)
+            .create(db)
+            .await;
+        let viewer = org.member(&UserRole::Viewer).user_id.into_inner();
+        let password = "correct horse battery staple";
+        let user = users::Entity::find_by_id(viewer)
+            .one(db)
+            .await
+            .unwrap()
+            .unwrap();
+        let email = user.email.clone();
+        let mut active: users::ActiveModel = user.into();
+        active.password_hash = Set(hash_password(password).unwrap());
+        active.update(db).await.unwrap();
+
+        let state = AppState {
+            admin: AdminConfig {
+                organization_id: None,
+                token: Some("operator-secret".to_string()),
+            },
+            ..test_app_state(&test_db)
+        };
+        let login = json!({ "email": email, "password": password });
+        let (status, _) = post_json(&state, "/api/v1/auth/login", None, login.clone()).await;
+        assert_eq!(status, StatusCode::OK);
+
+        let deactivate = format!("/api/v1/auth/admin/users/{viewer}/deactivate");
+        let (status, _) = post_json(&state, &deactivate, None, json!({})).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+        let (status, body) =
+            post_json(&state, &deactivate, Some("operator-secret"), json!({})).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["was_active"], true);
+
+        let (status, body) = post_json(&state, "/api/v1/auth/login", None, login.clone()).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+        assert_eq!(body["error"], "account_disabled");
+
+        let audit = operator_audit_log::Entity::find()
+            .filter(operator_audit_log::Column::Target.eq(viewer.to_string()))
+            .one(db)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(audit.command, "user deactivate");
+        assert_eq!(audit.operator, "admin token");
+
+        // After erasure the old address no longer matches anyone
+        UserRepository::new(db.clone())
+            .anonymize(viewer)
+            .await
+            .unwrap();
+        let (status, body) = post_json(&state, "/api/v1/auth/login", None, login).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+        assert_eq!(body["error"], "invalid_credentials");
+
+        let unknown = format!("/api/v1/auth/admin/users/{}/deactivate", Uuid::new_v4());
+        let (status, body) = post_json(&state, &unknown, Some("operator-secret"), json!({})).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        assert_eq!(body["error"], "user_not_found");
+    }
+
+    #[tokio::test]
+    async fn test_deactivation_revokes_the_users_api_keys() {
+        let test_db = TestDb::new().await;
+        let db = test_db.conn();
+        let org = OrgFixture::new()
+            .with_member(UserRole::Admin)
+            .create(db)
+            .await;
+        let admin = org.member(&UserRole::Admin).user_id.into_inner();
+        let key = ApiKeyRepository::new(db.clone())
+            .create(
+                org.id.into_inner(),
+                CreateApiKeyInput {
+                    name: "Sync".to_string(),
+                    role: ApiKeyRole::Viewer,
+                    scopes: vec![ApiKeyScope::ReadTransactions],
+                    expires_at: None,
+                    created_by: admin,
+                },
+            )
+            .await
+            .unwrap()
+            .key;
+
+        let state = AppState {
+            admin: AdminConfig {
+                organization_id: None,
+                token: Some("operator-secret".to_string()),
+            },
+            ..test_app_state(&test_db)
+        };
+        let list = || async {
+            let request = Request::builder()
+                .uri(format!("/api/v1/organizations/{}/transactions", org.id))
+                .header(AUTHORIZATION, format!("Bearer {key}"))
+                .body(Body::empty())
+                .unwrap();
+            let response = create_router(state.clone(), BodyLimits::default())
+                .oneshot(request)
+                .await
+                .unwrap();
+            let status = response.status();
+            let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+            (
+                status,
+                serde_json::from_slice::<serde_json::Value>(&body).unwrap(),
+            )
+        };
+        assert_eq!(list().await.0, StatusCode::OK);
+
+        let deactivate = format!("/api/v1/auth/admin/users/{admin}/deactivate");
+        let (status, _) = post_json(&state, &deactivate, Some("operator-secret"), json!({})).await;
+        assert_eq!(status, StatusCode::OK);
+
+        let (status, body) = list().await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+        assert_eq!(body["error"], "invalid_api_key");
+    }
+
+    #[tokio::test]
+    async fn test_admin_org_non_owner_is_forbidden() {
+        let admin_org = Uuid::new_v4();
+        let state = test_state(AdminConfig {
+            organization_id: Some(admin_org),
+            token: None,
+        });
+        let token = state
+            .jwt_service
+            .generate_access_token(Uuid::new_v4(), admin_org, "admin")
+            .unwrap();
+
+        let (status, _) = get_jobs(state, Some(&token)).await;
+
+        assert_eq!(status, StatusCode::FORBIDDEN);
+    }
+}
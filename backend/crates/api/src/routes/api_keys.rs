@@ -0,0 +1,511 @@
+//! API key routes.
+//!
+//! Owners and Admins create and revoke the keys integrations use to call
+//! the API. The full key is only returned by the create call.
+
+use axum::{
+    Json, Router,
+    extract::{Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+    routing::get,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use tracing::{error, info};
+use uuid::Uuid;
+
+use crate::{AppState, middleware::AuthUser};
+use zeltra_core::auth::{ApiKeyError, ApiKeyRole, ApiKeyScope};
+use zeltra_db::{
+    OrganizationRepository,
+    entities::{api_keys, sea_orm_active_enums::UserRole},
+    repositories::{
+        ApiKeyRepoError, ApiKeyRepository, CreateApiKeyInput, Feature, SubscriptionRepository,
+    },
+};
+
+/// Creates the API key routes.
+pub fn routes() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/organizations/{org_id}/api-keys",
+            get(list_api_keys).post(create_api_key),
+        )
+        .route(
+            "/organizations/{org_id}/api-keys/{key_id}",
+            get(get_api_key).delete(revoke_api_key),
+        )
+}
+
+// ============================================================================
+// Request/Response Types
+// ============================================================================
+
+/// Request body for creating an API key.
+#[derive(Debug, Deserialize)]
+pub struct CreateApiKeyRequest {
+    /// Name shown in listings.
+    pub name: String,
+    /// Role the key acts with: `viewer` or `integration`.
+    pub role: String,
+    /// Scopes the key holds.
+    pub scopes: Vec<String>,
+    /// When the key stops working. Keys without one never expire.
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+/// Response for an API key.
+#[derive(Debug, Serialize)]
+pub struct ApiKeyResponse {
+    /// Key ID.
+    pub id: Uuid,
+    /// Organization ID.
+    pub organization_id: Uuid,
+    /// Name.
+    pub name: String,
+    /// Leading characters of the key.
+    pub key_prefix: String,
+    /// Role the key acts with.
+    pub role: String,
+    /// Scopes the key holds.
+    pub scopes: Vec<String>,
+    /// Expiry timestamp.
+    pub expires_at: Option<String>,
+    /// When the key was last used.
+    pub last_used_at: Option<String>,
+    /// Revocation timestamp.
+    pub revoked_at: Option<String>,
+    /// Whether the key is accepted now.
+    pub is_active: bool,
+    /// Creator.
+    pub created_by: Uuid,
+    /// Created at timestamp.
+    pub created_at: String,
+}
+
+/// Response for a newly created API key.
+#[derive(Debug, Serialize)]
+pub struct CreatedApiKeyResponse {
+    /// The stored key.
+    #[serde(flatten)]
+    pub api_key: ApiKeyResponse,
+    /// The full key. It is shown only once.
+    pub key: String,
+}
+
+// ============================================================================
+// Route Handlers
+// ============================================================================
+
+/// GET `/organizations/{org_id}/api-keys` - List API keys.
+async fn list_api_keys(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(org_id): Path<Uuid>,
+) -> impl IntoResponse {
+    if let Err(response) = check_admin(&state, org_id, auth.user_id()).await {
+        return response;
+    }
+
+    let repo = ApiKeyRepository::new((*state.db).clone());
+
+    match repo.list(org_id).await {
+        Ok(keys) => {
+            let items: Vec<ApiKeyResponse> = keys.iter().map(api_key_to_response).collect();
+            (StatusCode::OK, Json(json!({ "data": items }))).into_response()
+        }
+        Err(e) => {
+            error!(error = %e, "Failed to list API keys");
+            api_key_error_response(&ApiKeyRepoError::Database(e))
+        }
+    }
+}
+
+/// POST `/organizations/{org_id}/api-keys` - Create API key.
+///
+/// Requires a plan with API access.
+async fn create_api_key(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(org_id): Path<Uuid>,
+    Json(payload): Json<CreateApiKeyRequest>,
+) -> impl IntoResponse {
+    let user_id = auth.user_id();
+    if let Err(response) = check_admin(&state, org_id, user_id).await {
+        return response;
+    }
+
+    match SubscriptionRepository::has_feature(&state.db, org_id, Feature::ApiAccess).await {
+        Ok(true) => {}
+        Ok(false) => {
+            return (
+                StatusCode::FORBIDDEN,
+                Json(json!({
+                    "error": "feature_not_available",
+                    "message": "API access is not included in the organization's plan"
+                })),
+            )
+                .into_response();
+        }
+        Err(e) => {
+            error!(error = %e, "Database error checking API access");
+            return api_key_error_response(&ApiKeyRepoError::Database(e));
+        }
+    }
+
+    let name = payload.name.trim();
+    if name.is_empty() || name.len() > 100 {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(json!({
+                "error": "invalid_name",
+                "message": "Name must be between 1 and 100 characters"
+            })),
+        )
+            .into_response();
+    }
+
+    let role = match payload.role.parse::<ApiKeyRole>() {
+        Ok(role) => role,
+        Err(e) => return api_key_error_response(&e.into()),
+    };
+    let scopes = match payload
+        .scopes
+        .iter()
+        .map(|s| s.parse::<ApiKeyScope>())
+        .collect::<Result<Vec<_>, _>>()
+    {
+        Ok(scopes) => scopes,
+        Err(e) => return api_key_error_response(&e.into()),
+    };
+
+    let repo = ApiKeyRepository::new((*state.db).clone());
+    let input = CreateApiKeyInput {
+        name: name.to_string(),
+        role,
+        scopes,
+        expires_at: payload.expires_at,
+        created_by: user_id,
+    };
+
+    match repo.create(org_id, input).await {
+        Ok(created) => {
+            info!(
+                api_key_id = %created.api_key.id,
+                role = %created.api_key.role,
+                "API key created"
+            );
+
+            (
+                StatusCode::CREATED,
+                Json(CreatedApiKeyResponse {
+                    api_key: api_key_to_response(&created.api_key),
+                    key: created.key,
+                }),
+            )
+                .into_response()
+        }
+        Err(e) => {
+            error!(error = %e, "Failed to create API key");
+            api_key_error_response(&e)
+        }
+    }
+}
+
+/// GET `/organizations/{org_id}/api-keys/{key_id}` - Get API key.
+async fn get_api_key(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path((org_id, key_id)): Path<(Uuid, Uuid)>,
+) -> impl IntoResponse {
+    if let Err(response) = check_admin(&state, org_id, auth.user_id()).await {
+        return response;
+    }
+
+    let repo = ApiKeyRepository::new((*state.db).clone());
+
+    match repo.find_by_id(org_id, key_id).await {
+        Ok(Some(api_key)) => (StatusCode::OK, Json(api_key_to_response(&api_key))).into_response(),
+        Ok(None) => api_key_error_response(&ApiKeyRepoError::NotFound(key_id)),
+        Err(e) => {
+            error!(error = %e, "Failed to get API key");
+            api_key_error_response(&ApiKeyRepoError::Database(e))
+        }
+    }
+}
+
+/// DELETE `/organizations/{org_id}/api-keys/{key_id}` - Revoke API key.
+///
+/// The key is rejected from the next request on; it stays listed as revoked.
+async fn revoke_api_key(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path((org_id, key_id)): Path<(Uuid, Uuid)>,
+) -> impl IntoResponse {
+    if let Err(response) = check_admin(&state, org_id, auth.user_id()).await {
+        return response;
+    }
+
+    let repo = ApiKeyRepository::new((*state.db).clone());
+
+    match repo.revoke(org_id, key_id).await {
+        Ok(api_key) => {
+            info!(api_key_id = %key_id, "API key revoked");
+
+            (StatusCode::OK, Json(api_key_to_response(&api_key))).into_response()
+        }
+        Err(e) => {
+            error!(error = %e, "Failed to revoke API key");
+            api_key_error_response(&e)
+        }
+    }
+}
+
+// ============================================================================
+// Helper Functions
+// ============================================================================
+
+fn api_key_to_response(api_key: &api_keys::Model) -> ApiKeyResponse {
+    let is_active =
+        api_key.revoked_at.is_none() && api_key.expires_at.is_none_or(|at| at > Utc::now());
+
+    ApiKeyResponse {
+        id: api_key.id,
+        organization_id: api_key.organization_id,
+        name: api_key.name.clone(),
+        key_prefix: api_key.key_prefix.clone(),
+        role: api_key.role.clone(),
+        scopes: api_key.scopes.clone(),
+        expires_at: api_key.expires_at.map(|t| t.to_rfc3339()),
+        last_used_at: api_key.last_used_at.map(|t| t.to_rfc3339()),
+        revoked_at: api_key.revoked_at.map(|t| t.to_rfc3339()),
+        is_active,
+        created_by: api_key.created_by,
+        created_at: api_key.created_at.to_rfc3339(),
+    }
+}
+
+/// Checks that the user is an Owner or Admin of the organization.
+async fn check_admin(
+    state: &AppState,
+    org_id: Uuid,
+    user_id: Uuid,
+) -> Result<(), axum::response::Response> {
+    let org_repo = OrganizationRepository::new((*state.db).clone());
+
+    match org_repo.get_user_membership(org_id, user_id).await {
+        Ok(Some(membership)) if matches!(membership.role, UserRole::Admin | UserRole::Owner) => {
+            Ok(())
+        }
+        Ok(Some(_)) => Err((
+            StatusCode::FORBIDDEN,
+            Json(json!({
+                "error": "admin_required",
+                "message": "Only Admins and Owners can manage API keys"
+            })),
+        )
+            .into_response()),
+        Ok(None) => Err((
+            StatusCode::FORBIDDEN,
+            Json(json!({
+                "error": "forbidden",
+                "message": "You are not a member of this organization"
+            })),
+        )
+            .into_response()),
+        Err(e) => {
+            error!(error = %e, "Database error checking membership");
+            Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({
+                    "error": "internal_error",
+                    "message": "An error occurred"
+                })),
+            )
+                .into_response())
+        }
+    }
+}
+
+fn api_key_error_response(e: &ApiKeyRepoError) -> axum::response::Response {
+    let (status, code) = match e {
+        ApiKeyRepoError::NotFound(_) => (StatusCode::NOT_FOUND, "not_found"),
+        ApiKeyRepoError::ExpiryInPast => (StatusCode::BAD_REQUEST, "invalid_expiry"),
+        ApiKeyRepoError::Invalid(ApiKeyError::UnknownRole(_)) => {
+            (StatusCode::BAD_REQUEST, "invalid_role")
+        }
+        ApiKeyRepoError::Invalid(_) => (StatusCode::BAD_REQUEST, "invalid_scopes"),
+        ApiKeyRepoError::Database(_) => {
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({
+                    "error": "internal_error",
+                    "message": "An error occurred"
+                })),
+            )
+                .into_response();
+        }
+    };
+
+    (
+        status,
+        Json(json!({
+            "error": code,
+            "message": e.to_string()
+        })),
+    )
+        .into_response()
+}
+
+#[cfg(test)]
+mod integration_tests {
+    use super::*;
+    use sea_orm::{ActiveModelTrait, Set};
+    use zeltra_db::entities::{organizations, sea_orm_active_enums::SubscriptionTier};
+    use zeltra_test_support::{OrgFixture, TestDb, access_token, jwt_service, test_app_state};
+
+    use crate::routes::{reports, transactions};
+
+    async fn send(
+        state: &AppState,
+        method: &str,
+        uri: &str,
+        token: &str,
+        body: serde_json::Value,
+    ) -> (StatusCode, serde_json::Value) {
+        let app = state.authenticated(
+            Router::new()
+                .merge(routes())
+                .merge(transactions::routes())
+                .merge(reports::routes()),
+        );
+        zeltra_test_support::send(app, method, uri, token, body).await
+    }
+
+    #[tokio::test]
+    async fn test_api_key_scopes_and_revocation() {
+        let test_db = TestDb::new().await;
+        let state: AppState = test_app_state(&test_db);
+        let org = OrgFixture::new()
+            .with_fiscal_year_2025()
+            .with_member(UserRole::Accountant)
+            .create(test_db.conn())
+            .await;
+        let jwt = jwt_service();
+        let uri = format!("/organizations/{}/api-keys", org.id);
+
+        // Only Owners and Admins manage keys
+        let token = access_token(&jwt, org.id, org.member(&UserRole::Accountant));
+        let (status, _) = send(&state, "GET", &uri, &token, json!(null)).await;
+        assert_eq!(status, StatusCode::FORBIDDEN);
+
+        let token = access_token(&jwt, org.id, &org.owner);
+        let (status, body) = send(
+            &state,
+            "POST",
+            &uri,
+            &token,
+            json!({ "name": "BI", "role": "viewer", "scopes": ["write:transactions"] }),
+        )
+        .await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body["error"], "invalid_scopes");
+
+        let (status, created) = send(
+            &state,
+            "POST",
+            &uri,
+            &token,
+            json!({ "name": "BI", "role": "viewer", "scopes": ["read:transactions"] }),
+        )
+        .await;
+        assert_eq!(status, StatusCode::CREATED);
+        let key = created["key"].as_str().unwrap().to_string();
+        assert!(key.starts_with("zk_live_"));
+        assert!(key.starts_with(created["key_prefix"].as_str().unwrap()));
+
+        // The full key is not shown again
+        let item_uri = format!("{uri}/{}", created["id"].as_str().unwrap());
+        let (status, fetched) = send(&state, "GET", &item_uri, &token, json!(null)).await;
+        assert_eq!(status, StatusCode::OK);
+        assert!(fetched.get("key").is_none());
+        assert!(fetched["last_used_at"].is_null());
+
+        // The key reads transactions but nothing outside its scopes
+        let transactions_uri = format!("/organizations/{}/transactions", org.id);
+        let (status, _) = send(&state, "GET", &transactions_uri, &key, json!(null)).await;
+        assert_eq!(status, StatusCode::OK);
+        let (status, body) = send(
+            &state,
+            "GET",
+            &format!("/organizations/{}/reports/trial-balance", org.id),
+            &key,
+            json!(null),
+        )
+        .await;
+        assert_eq!(status, StatusCode::FORBIDDEN);
+        assert_eq!(body["error"], "insufficient_scope");
+        let (status, body) = send(&state, "GET", &uri, &key, json!(null)).await;
+        assert_eq!(status, StatusCode::FORBIDDEN);
+        assert_eq!(body["error"], "api_key_not_allowed");
+
+        let (_, fetched) = send(&state, "GET", &item_uri, &token, json!(null)).await;
+        assert!(!fetched["last_used_at"].is_null());
+
+        // Revocation takes effect immediately
+        let (status, revoked) = send(&state, "DELETE", &item_uri, &token, json!(null)).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(revoked["is_active"], false);
+        let (status, body) = send(&state, "GET", &transactions_uri, &key, json!(null)).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+        assert_eq!(body["error"], "invalid_api_key");
+    }
+
+    #[tokio::test]
+    async fn test_api_keys_require_api_access() {
+        let test_db = TestDb::new().await;
+        let state: AppState = test_app_state(&test_db);
+        let org = OrgFixture::new().create(test_db.conn()).await;
+        let token = access_token(&jwt_service(), org.id, &org.owner);
+        let uri = format!("/organizations/{}/api-keys", org.id);
+
+        let (status, created) = send(
+            &state,
+            "POST",
+            &uri,
+            &token,
+            json!({ "name": "Sync", "role": "integration", "scopes": ["read:transactions"] }),
+        )
+        .await;
+        assert_eq!(status, StatusCode::CREATED);
+        let key = created["key"].as_str().unwrap().to_string();
+
+        // Downgrading to a plan without API access turns existing keys off
+        organizations::ActiveModel {
+            id: Set(org.id.into_inner()),
+            subscription_tier: Set(SubscriptionTier::Starter),
+            ..Default::default()
+        }
+        .update(test_db.conn())
+        .await
+        .unwrap();
+
+        let transactions_uri = format!("/organizations/{}/transactions", org.id);
+        let (status, body) = send(&state, "GET", &transactions_uri, &key, json!(null)).await;
+        assert_eq!(status, StatusCode::FORBIDDEN);
+        assert_eq!(body["error"], "feature_not_available");
+
+        let (status, body) = send(
+            &state,
+            "POST",
+            &uri,
+            &token,
+            json!({ "name": "Sync", "role": "viewer", "scopes": ["read:reports"] }),
+        )
+        .await;
+        assert_eq!(status, StatusCode::FORBIDDEN);
+        assert_eq!(body["error"], "feature_not_available");
+    }
+}
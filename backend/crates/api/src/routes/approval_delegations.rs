@@ -0,0 +1,552 @@
+//! Approval delegation routes.
+//!
+//! Members manage delegations of their own approval authority; Owners and
+//! Admins can manage anyone's.
+
+use axum::{
+    Json, Router,
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::IntoResponse,
+    routing::get,
+};
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::str::FromStr;
+use tracing::{error, info};
+use uuid::Uuid;
+
+use crate::{
+    AppState,
+    middleware::{AuthUser, with_last_modified},
+};
+use zeltra_db::{
+    OrganizationRepository,
+    entities::{approval_delegations, sea_orm_active_enums::UserRole},
+    repositories::approval_delegation::{
+        ApprovalDelegationError, ApprovalDelegationRepository, CreateApprovalDelegationInput,
+        UpdateApprovalDelegationInput,
+    },
+};
+
+/// Creates the approval delegation routes.
+pub fn routes() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/organizations/{org_id}/approval-delegations",
+            get(list_delegations).post(create_delegation),
+        )
+        .route(
+            "/organizations/{org_id}/approval-delegations/{delegation_id}",
+            get(get_delegation)
+                .patch(update_delegation)
+                .delete(delete_delegation),
+        )
+}
+
+// ============================================================================
+// Request/Response Types
+// ============================================================================
+
+/// Query parameters for listing delegations.
+#[derive(Debug, Default, Deserialize)]
+pub struct ListDelegationsQuery {
+    /// Include delegations that have already ended.
+    #[serde(default)]
+    pub include_expired: bool,
+}
+
+/// Request body for creating a delegation.
+#[derive(Debug, Deserialize)]
+pub struct CreateDelegationRequest {
+    /// User whose authority is delegated. Defaults to the caller.
+    pub delegator_id: Option<Uuid>,
+    /// User who may approve on the delegator's behalf.
+    pub delegate_id: Uuid,
+    /// When the delegation takes effect. Defaults to now.
+    pub starts_at: Option<DateTime<Utc>>,
+    /// When the delegation ends.
+    pub ends_at: DateTime<Utc>,
+    /// Largest transaction the delegate may approve.
+    pub amount_cap: Option<String>,
+}
+
+/// Request body for updating a delegation.
+#[derive(Debug, Deserialize)]
+pub struct UpdateDelegationRequest {
+    /// New start.
+    pub starts_at: Option<DateTime<Utc>>,
+    /// New end.
+    pub ends_at: Option<DateTime<Utc>>,
+    /// New amount cap. An empty string removes the cap.
+    pub amount_cap: Option<String>,
+}
+
+/// Response for a delegation.
+#[derive(Debug, Serialize)]
+pub struct DelegationResponse {
+    /// Delegation ID.
+    pub id: Uuid,
+    /// Organization ID.
+    pub organization_id: Uuid,
+    /// User whose authority is delegated.
+    pub delegator_id: Uuid,
+    /// User who may approve on the delegator's behalf.
+    pub delegate_id: Uuid,
+    /// Start timestamp.
+    pub starts_at: String,
+    /// End timestamp.
+    pub ends_at: String,
+    /// Amount cap.
+    pub amount_cap: Option<String>,
+    /// Whether the delegation is in effect now.
+    pub is_active: bool,
+    /// Creator.
+    pub created_by: Uuid,
+    /// Created at timestamp.
+    pub created_at: String,
+    /// Updated at timestamp.
+    pub updated_at: String,
+}
+
+// ============================================================================
+// Route Handlers
+// ============================================================================
+
+/// GET `/organizations/{org_id}/approval-delegations` - List delegations.
+///
+/// Owners and Admins see every delegation; other members see the ones they
+/// give or receive.
+async fn list_delegations(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(org_id): Path<Uuid>,
+    Query(query): Query<ListDelegationsQuery>,
+) -> impl IntoResponse {
+    let is_admin = match membership(&state, org_id, auth.user_id()).await {
+        Ok(is_admin) => is_admin,
+        Err(response) => return response,
+    };
+
+    let repo = ApprovalDelegationRepository::new((*state.db).clone());
+    let user_filter = (!is_admin).then(|| auth.user_id());
+
+    match repo
+        .list_delegations(org_id, user_filter, query.include_expired)
+        .await
+    {
+        Ok(delegations) => {
+            let items: Vec<DelegationResponse> = delegations
+                .into_iter()
+                .map(|d| delegation_to_response(&d))
+                .collect();
+
+            (StatusCode::OK, Json(json!({ "data": items }))).into_response()
+        }
+        Err(e) => {
+            error!(error = %e, "Failed to list approval delegations");
+            delegation_error_response(&e)
+        }
+    }
+}
+
+/// POST `/organizations/{org_id}/approval-delegations` - Create delegation.
+async fn create_delegation(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(org_id): Path<Uuid>,
+    Json(payload): Json<CreateDelegationRequest>,
+) -> impl IntoResponse {
+    let user_id = auth.user_id();
+    let is_admin = match membership(&state, org_id, user_id).await {
+        Ok(is_admin) => is_admin,
+        Err(response) => return response,
+    };
+
+    let delegator_id = payload.delegator_id.unwrap_or(user_id);
+    if delegator_id != user_id && !is_admin {
+        return not_delegator_response();
+    }
+
+    let amount_cap = match parse_amount_cap(payload.amount_cap.as_deref()) {
+        Ok(cap) => cap,
+        Err(e) => return e,
+    };
+
+    let repo = ApprovalDelegationRepository::new((*state.db).clone());
+    let input = CreateApprovalDelegationInput {
+        delegator_id,
+        delegate_id: payload.delegate_id,
+        starts_at: payload.starts_at.unwrap_or_else(Utc::now),
+        ends_at: payload.ends_at,
+        amount_cap,
+        created_by: user_id,
+    };
+
+    match repo.create_delegation(org_id, input).await {
+        Ok(delegation) => {
+            info!(
+                delegation_id = %delegation.id,
+                delegator_id = %delegation.delegator_id,
+                delegate_id = %delegation.delegate_id,
+                "Approval delegation created"
+            );
+
+            (
+                StatusCode::CREATED,
+                Json(delegation_to_response(&delegation)),
+            )
+                .into_response()
+        }
+        Err(e) => {
+            error!(error = %e, "Failed to create approval delegation");
+            delegation_error_response(&e)
+        }
+    }
+}
+
+/// GET `/organizations/{org_id}/approval-delegations/{delegation_id}` - Get delegation.
+async fn get_delegation(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path((org_id, delegation_id)): Path<(Uuid, Uuid)>,
+) -> impl IntoResponse {
+    let user_id = auth.user_id();
+    let is_admin = match membership(&state, org_id, user_id).await {
+        Ok(is_admin) => is_admin,
+        Err(response) => return response,
+    };
+
+    let repo = ApprovalDelegationRepository::new((*state.db).clone());
+
+    match repo.get_delegation(org_id, delegation_id).await {
+        Ok(delegation)
+            if is_admin
+                || delegation.delegator_id == user_id
+                || delegation.delegate_id == user_id =>
+        {
+            with_last_modified(
+                (StatusCode::OK, Json(delegation_to_response(&delegation))).into_response(),
+                &delegation.updated_at,
+            )
+        }
+        Ok(_) => delegation_error_response(&ApprovalDelegationError::NotFound(delegation_id)),
+        Err(e) => {
+            error!(error = %e, "Failed to get approval delegation");
+            delegation_error_response(&e)
+        }
+    }
+}
+
+/// PATCH `/organizations/{org_id}/approval-delegations/{delegation_id}` - Update delegation.
+///
+/// Ending a delegation early is done by moving `ends_at` to now.
+async fn update_delegation(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path((org_id, delegation_id)): Path<(Uuid, Uuid)>,
+    Json(payload): Json<UpdateDelegationRequest>,
+) -> impl IntoResponse {
+    let repo = ApprovalDelegationRepository::new((*state.db).clone());
+    if let Err(response) = check_can_manage(&state, &repo, org_id, delegation_id, &auth).await {
+        return response;
+    }
+
+    let amount_cap = match payload.amount_cap.as_deref() {
+        Some(s) => match parse_amount_cap(Some(s)) {
+            Ok(cap) => Some(cap),
+            Err(e) => return e,
+        },
+        None => None,
+    };
+
+    let input = UpdateApprovalDelegationInput {
+        starts_at: payload.starts_at,
+        ends_at: payload.ends_at,
+        amount_cap,
+    };
+
+    match repo.update_delegation(org_id, delegation_id, input).await {
+        Ok(delegation) => {
+            info!(delegation_id = %delegation_id, "Approval delegation updated");
+
+            (StatusCode::OK, Json(delegation_to_response(&delegation))).into_response()
+        }
+        Err(e) => {
+            error!(error = %e, "Failed to update approval delegation");
+            delegation_error_response(&e)
+        }
+    }
+}
+
+/// DELETE `/organizations/{org_id}/approval-delegations/{delegation_id}` - Delete delegation.
+async fn delete_delegation(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path((org_id, delegation_id)): Path<(Uuid, Uuid)>,
+) -> impl IntoResponse {
+    let repo = ApprovalDelegationRepository::new((*state.db).clone());
+    if let Err(response) = check_can_manage(&state, &repo, org_id, delegation_id, &auth).await {
+        return response;
+    }
+
+    match repo.delete_delegation(org_id, delegation_id).await {
+        Ok(()) => {
+            info!(delegation_id = %delegation_id, "Approval delegation deleted");
+
+            (StatusCode::NO_CONTENT, ()).into_response()
+        }
+        Err(e) => {
+            error!(error = %e, "Failed to delete approval delegation");
+            delegation_error_response(&e)
+        }
+    }
+}
+
+// ============================================================================
+// Helper Functions
+// ============================================================================
+
+fn delegation_to_response(delegation: &approval_delegations::Model) -> DelegationResponse {
+    let now = Utc::now();
+    let is_active = delegation.starts_at <= now && now < delegation.ends_at;
+
+    DelegationResponse {
+        id: delegation.id,
+        organization_id: delegation.organization_id,
+        delegator_id: delegation.delegator_id,
+        delegate_id: delegation.delegate_id,
+        starts_at: delegation.starts_at.to_rfc3339(),
+        ends_at: delegation.ends_at.to_rfc3339(),
+        amount_cap: delegation.amount_cap.map(|c| c.to_string()),
+        is_active,
+        created_by: delegation.created_by,
+        created_at: delegation.created_at.to_rfc3339(),
+        updated_at: delegation.updated_at.to_rfc3339(),
+    }
+}
+
+#[allow(clippy::result_large_err)]
+fn parse_amount_cap(s: Option<&str>) -> Result<Option<Decimal>, axum::response::Response> {
+    match s {
+        Some(s) if !s.is_empty() => Decimal::from_str(s).map(Some).map_err(|_| {
+            (
+                StatusCode::BAD_REQUEST,
+                Json(json!({
+                    "error": "invalid_amount",
+                    "message": "Invalid amount format"
+                })),
+            )
+                .into_response()
+        }),
+        _ => Ok(None),
+    }
+}
+
+/// Checks membership and returns whether the user is an Owner or Admin.
+async fn membership(
+    state: &AppState,
+    org_id: Uuid,
+    user_id: Uuid,
+) -> Result<bool, axum::response::Response> {
+    let org_repo = OrganizationRepository::new((*state.db).clone());
+
+    match org_repo.get_user_membership(org_id, user_id).await {
+        Ok(Some(membership)) => Ok(matches!(membership.role, UserRole::Admin | UserRole::Owner)),
+        Ok(None) => Err((
+            StatusCode::FORBIDDEN,
+            Json(json!({
+                "error": "forbidden",
+                "message": "You are not a member of this organization"
+            })),
+        )
+            .into_response()),
+        Err(e) => {
+            error!(error = %e, "Database error checking membership");
+            Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({
+                    "error": "internal_error",
+                    "message": "An error occurred"
+                })),
+            )
+                .into_response())
+        }
+    }
+}
+
+/// Checks that the user is an Owner or Admin, or the delegation's delegator.
+async fn check_can_manage(
+    state: &AppState,
+    repo: &ApprovalDelegationRepository,
+    org_id: Uuid,
+    delegation_id: Uuid,
+    auth: &AuthUser,
+) -> Result<(), axum::response::Response> {
+    let user_id = auth.user_id();
+    let is_admin = membership(state, org_id, user_id).await?;
+
+    match repo.get_delegation(org_id, delegation_id).await {
+        Ok(_) if is_admin => Ok(()),
+        Ok(delegation) if delegation.delegator_id == user_id => Ok(()),
+        // The delegate can see the delegation but not change it
+        Ok(delegation) if delegation.delegate_id == user_id => Err(not_delegator_response()),
+        Ok(_) => Err(delegation_error_response(
+            &ApprovalDelegationError::NotFound(delegation_id),
+        )),
+        Err(e) => Err(delegation_error_response(&e)),
+    }
+}
+
+fn not_delegator_response() -> axum::response::Response {
+    (
+        StatusCode::FORBIDDEN,
+        Json(json!({
+            "error": "admin_required",
+            "message": "Only Admins and Owners can manage other members' delegations"
+        })),
+    )
+        .into_response()
+}
+
+fn delegation_error_response(e: &ApprovalDelegationError) -> axum::response::Response {
+    let (status, code) = match e {
+        ApprovalDelegationError::NotFound(_) => (StatusCode::NOT_FOUND, "not_found"),
+        ApprovalDelegationError::SelfDelegation => (StatusCode::BAD_REQUEST, "self_delegation"),
+        ApprovalDelegationError::InvalidPeriod => (StatusCode::BAD_REQUEST, "invalid_period"),
+        ApprovalDelegationError::InvalidAmountCap => (StatusCode::BAD_REQUEST, "invalid_amount"),
+        ApprovalDelegationError::NotMember(_) => (StatusCode::BAD_REQUEST, "not_a_member"),
+        ApprovalDelegationError::Database(_) => {
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({
+                    "error": "internal_error",
+                    "message": "An error occurred"
+                })),
+            )
+                .into_response();
+        }
+    };
+
+    (
+        status,
+        Json(json!({
+            "error": code,
+            "message": e.to_string()
+        })),
+    )
+        .into_response()
+}
+
+#[cfg(test)]
+mod integration_tests {
+    use super::*;
+    use zeltra_test_support::{OrgFixture, TestDb, access_token, jwt_service, test_app_state};
+
+    async fn send(
+        state: &AppState,
+        method: &str,
+        uri: &str,
+        token: &str,
+        body: serde_json::Value,
+    ) -> (StatusCode, serde_json::Value) {
+        zeltra_test_support::send(state.authenticated(routes()), method, uri, token, body).await
+    }
+
+    #[tokio::test]
+    async fn test_members_manage_only_their_own_delegations() {
+        let test_db = TestDb::new().await;
+        let state: AppState = test_app_state(&test_db);
+        let org = OrgFixture::new()
+            .with_member(UserRole::Approver)
+            .with_member(UserRole::Accountant)
+            .create(test_db.conn())
+            .await;
+        let jwt = jwt_service();
+        let approver = org.member(&UserRole::Approver);
+        let accountant = org.member(&UserRole::Accountant);
+        let uri = format!("/organizations/{}/approval-delegations", org.id);
+        let ends_at = (Utc::now() + chrono::Duration::days(7)).to_rfc3339();
+
+        // An approver cannot delegate the owner's authority
+        let token = access_token(&jwt, org.id, approver);
+        let (status, _) = send(
+            &state,
+            "POST",
+            &uri,
+            &token,
+            json!({
+                "delegator_id": org.owner.user_id,
+                "delegate_id": accountant.user_id,
+                "ends_at": ends_at
+            }),
+        )
+        .await;
+        assert_eq!(status, StatusCode::FORBIDDEN);
+
+        // ...but can delegate their own
+        let (status, created) = send(
+            &state,
+            "POST",
+            &uri,
+            &token,
+            json!({
+                "delegate_id": accountant.user_id,
+                "ends_at": ends_at,
+                "amount_cap": "500"
+            }),
+        )
+        .await;
+        assert_eq!(status, StatusCode::CREATED);
+        assert_eq!(created["delegator_id"], json!(approver.user_id));
+        assert_eq!(created["is_active"], true);
+
+        // The delegate sees it but cannot change it
+        let token = access_token(&jwt, org.id, accountant);
+        let (status, listed) = send(&state, "GET", &uri, &token, json!(null)).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(listed["data"].as_array().unwrap().len(), 1);
+
+        let item_uri = format!("{uri}/{}", created["id"].as_str().unwrap());
+        let (status, _) = send(&state, "DELETE", &item_uri, &token, json!(null)).await;
+        assert_eq!(status, StatusCode::FORBIDDEN);
+
+        // The owner can end it early
+        let token = access_token(&jwt, org.id, &org.owner);
+        let (status, updated) = send(
+            &state,
+            "PATCH",
+            &item_uri,
+            &token,
+            json!({ "ends_at": Utc::now().to_rfc3339() }),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(updated["is_active"], false);
+    }
+
+    #[tokio::test]
+    async fn test_self_delegation_is_rejected() {
+        let test_db = TestDb::new().await;
+        let state: AppState = test_app_state(&test_db);
+        let org = OrgFixture::new().create(test_db.conn()).await;
+        let token = access_token(&jwt_service(), org.id, &org.owner);
+        let uri = format!("/organizations/{}/approval-delegations", org.id);
+
+        let (status, body) = send(
+            &state,
+            "POST",
+            &uri,
+            &token,
+            json!({
+                "delegate_id": org.owner.user_id,
+                "ends_at": (Utc::now() + chrono::Duration::days(1)).to_rfc3339()
+            }),
+        )
+        .await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body["error"], "self_delegation");
+    }
+}
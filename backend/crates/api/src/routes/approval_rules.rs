@@ -16,11 +16,19 @@ use std::str::FromStr;
 use tracing::{error, info};
 use uuid::Uuid;
 
-use crate::{AppState, middleware::AuthUser};
+use crate::{
+    AppState,
+    middleware::{AuthUser, auth::check_membership, with_last_modified},
+    routes::{tier_limit_response, transactions::string_to_tx_type},
+};
 use zeltra_db::{
     OrganizationRepository,
-    repositories::approval_rule::{
-        ApprovalRuleError, ApprovalRuleRepository, CreateApprovalRuleInput, UpdateApprovalRuleInput,
+    repositories::{
+        approval_rule::{
+            ApprovalRuleError, ApprovalRuleRepository, CreateApprovalRuleInput,
+            UpdateApprovalRuleInput,
+        },
+        workflow::{ApprovalPreview, WorkflowRepository},
     },
 };
 
@@ -35,6 +43,10 @@ pub fn routes() -> Router<AppState> {
             "/organizations/{org_id}/approval-rules",
             post(create_approval_rule),
         )
+        .route(
+            "/organizations/{org_id}/approval-rules/preview",
+            post(preview_approval_rules),
+        )
         .route(
             "/organizations/{org_id}/approval-rules/{rule_id}",
             get(get_approval_rule),
@@ -70,6 +82,13 @@ pub struct CreateApprovalRuleRequest {
     pub required_role: String,
     /// Priority (lower = higher priority).
     pub priority: i16,
+    /// Number of different users who must approve (defaults to 1).
+    #[serde(default = "default_required_approvals")]
+    pub required_approvals: i16,
+}
+
+fn default_required_approvals() -> i16 {
+    1
 }
 
 /// Request body for updating an approval rule.
@@ -89,6 +108,8 @@ pub struct UpdateApprovalRuleRequest {
     pub required_role: Option<String>,
     /// New priority.
     pub priority: Option<i16>,
+    /// New required approvals count.
+    pub required_approvals: Option<i16>,
     /// Active status.
     pub is_active: Option<bool>,
 }
@@ -114,6 +135,8 @@ pub struct ApprovalRuleResponse {
     pub required_role: String,
     /// Priority.
     pub priority: i16,
+    /// Number of different users who must approve.
+    pub required_approvals: i16,
     /// Active status.
     pub is_active: bool,
     /// Created at timestamp.
@@ -122,6 +145,60 @@ pub struct ApprovalRuleResponse {
     pub updated_at: String,
 }
 
+/// Request body for previewing which rule a transaction would hit.
+#[derive(Debug, Deserialize)]
+pub struct PreviewApprovalRequest {
+    /// Transaction type.
+    pub transaction_type: String,
+    /// Transaction total.
+    pub amount: String,
+}
+
+/// Member who could approve a previewed transaction.
+#[derive(Debug, Serialize)]
+pub struct PreviewApproverResponse {
+    /// User ID.
+    pub user_id: Uuid,
+    /// Email address.
+    pub email: String,
+    /// Display name.
+    pub full_name: String,
+    /// Role in the organization.
+    pub role: String,
+    /// Approval limit, null when unlimited.
+    pub approval_limit: Option<String>,
+}
+
+/// Matched approval rule in a preview.
+#[derive(Debug, Serialize)]
+pub struct PreviewRuleResponse {
+    /// Rule ID.
+    pub id: Uuid,
+    /// Name.
+    pub name: String,
+    /// Priority.
+    pub priority: i16,
+}
+
+/// Response for an approval rule preview.
+#[derive(Debug, Serialize)]
+pub struct ApprovalPreviewResponse {
+    /// Whether an approval rule matched.
+    pub matched: bool,
+    /// `rule` when a rule matched, `default` otherwise.
+    pub policy: &'static str,
+    /// Matched rule, null under the default policy.
+    pub rule: Option<PreviewRuleResponse>,
+    /// Explanation of the outcome.
+    pub message: String,
+    /// Minimum role required to approve.
+    pub required_role: String,
+    /// Number of approvals required.
+    pub required_approvals: u32,
+    /// Members who could approve, by email.
+    pub approvers: Vec<PreviewApproverResponse>,
+}
+
 // ============================================================================
 // Route Handlers
 // ============================================================================
@@ -134,9 +211,7 @@ async fn list_approval_rules(
     auth: AuthUser,
     Path(org_id): Path<Uuid>,
 ) -> impl IntoResponse {
-    let org_repo = OrganizationRepository::new((*state.db).clone());
-
-    if let Err(response) = check_membership(&org_repo, org_id, auth.user_id()).await {
+    if let Err(response) = check_membership(&state, org_id, auth.user_id()).await {
         return response;
     }
 
@@ -231,15 +306,12 @@ async fn create_approval_rule(
         transaction_types: payload.transaction_types,
         required_role: payload.required_role,
         priority: payload.priority,
+        required_approvals: payload.required_approvals,
     };
 
     match rule_repo.create_rule(org_id, input).await {
         Ok(rule) => {
-            info!(
-                org_id = %org_id,
-                rule_id = %rule.id,
-                "Approval rule created"
-            );
+            info!(rule_id = %rule.id, "Approval rule created");
 
             (StatusCode::CREATED, Json(rule_to_response(rule))).into_response()
         }
@@ -256,16 +328,20 @@ async fn get_approval_rule(
     auth: AuthUser,
     Path((org_id, rule_id)): Path<(Uuid, Uuid)>,
 ) -> impl IntoResponse {
-    let org_repo = OrganizationRepository::new((*state.db).clone());
-
-    if let Err(response) = check_membership(&org_repo, org_id, auth.user_id()).await {
+    if let Err(response) = check_membership(&state, org_id, auth.user_id()).await {
         return response;
     }
 
     let rule_repo = ApprovalRuleRepository::new((*state.db).clone());
 
     match rule_repo.get_rule(org_id, rule_id).await {
-        Ok(rule) => (StatusCode::OK, Json(rule_to_response(rule))).into_response(),
+        Ok(rule) => {
+            let updated_at = rule.updated_at;
+            with_last_modified(
+                (StatusCode::OK, Json(rule_to_response(rule))).into_response(),
+                &updated_at,
+            )
+        }
         Err(e) => {
             error!(error = %e, "Failed to get approval rule");
             approval_rule_error_response(e)
@@ -314,16 +390,13 @@ async fn update_approval_rule(
         transaction_types: payload.transaction_types,
         required_role: payload.required_role,
         priority: payload.priority,
+        required_approvals: payload.required_approvals,
         is_active: payload.is_active,
     };
 
     match rule_repo.update_rule(org_id, rule_id, input).await {
         Ok(rule) => {
-            info!(
-                org_id = %org_id,
-                rule_id = %rule_id,
-                "Approval rule updated"
-            );
+            info!(rule_id = %rule_id, "Approval rule updated");
 
             (StatusCode::OK, Json(rule_to_response(rule))).into_response()
         }
@@ -351,11 +424,7 @@ async fn delete_approval_rule(
 
     match rule_repo.delete_rule(org_id, rule_id).await {
         Ok(()) => {
-            info!(
-                org_id = %org_id,
-                rule_id = %rule_id,
-                "Approval rule deleted"
-            );
+            info!(rule_id = %rule_id, "Approval rule deleted");
 
             (StatusCode::NO_CONTENT, ()).into_response()
         }
@@ -366,10 +435,104 @@ async fn delete_approval_rule(
     }
 }
 
+/// POST `/organizations/{org_id}/approval-rules/preview` - Preview which rule
+/// a transaction would hit and who could approve it.
+async fn preview_approval_rules(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(org_id): Path<Uuid>,
+    Json(payload): Json<PreviewApprovalRequest>,
+) -> impl IntoResponse {
+    let org_repo = OrganizationRepository::new((*state.db).clone());
+
+    if let Err(response) = check_admin_membership(&org_repo, org_id, auth.user_id()).await {
+        return response;
+    }
+
+    let Some(transaction_type) = string_to_tx_type(&payload.transaction_type) else {
+        return approval_rule_error_response(ApprovalRuleError::InvalidTransactionType(
+            payload.transaction_type,
+        ));
+    };
+    let amount = match parse_optional_decimal(Some(&payload.amount)) {
+        Ok(Some(amount)) => amount,
+        Ok(None) => {
+            return (
+                StatusCode::BAD_REQUEST,
+                Json(json!({
+                    "error": "invalid_amount",
+                    "message": "Amount is required"
+                })),
+            )
+                .into_response();
+        }
+        Err(response) => return response,
+    };
+
+    let workflow_repo = WorkflowRepository::new((*state.db).clone());
+
+    match workflow_repo
+        .preview_approval(org_id.into(), &transaction_type, amount)
+        .await
+    {
+        Ok(preview) => (StatusCode::OK, Json(preview_to_response(preview))).into_response(),
+        Err(e) => {
+            error!(error = %e, "Failed to preview approval rules");
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({
+                    "error": "internal_error",
+                    "message": "An error occurred"
+                })),
+            )
+                .into_response()
+        }
+    }
+}
+
 // ============================================================================
 // Helper Functions
 // ============================================================================
 
+fn preview_to_response(preview: ApprovalPreview) -> ApprovalPreviewResponse {
+    let approvers = preview
+        .approvers
+        .into_iter()
+        .map(|a| PreviewApproverResponse {
+            user_id: a.user_id,
+            email: a.email,
+            full_name: a.full_name,
+            role: a.role,
+            approval_limit: a.approval_limit.map(|l| l.to_string()),
+        })
+        .collect();
+
+    match preview.rule {
+        Some(rule) => ApprovalPreviewResponse {
+            matched: true,
+            policy: "rule",
+            message: format!("Rule \"{}\" applies", rule.name),
+            rule: Some(PreviewRuleResponse {
+                id: rule.id,
+                name: rule.name,
+                priority: rule.priority,
+            }),
+            required_role: preview.required_role,
+            required_approvals: preview.required_approvals,
+            approvers,
+        },
+        None => ApprovalPreviewResponse {
+            matched: false,
+            policy: "default",
+            rule: None,
+            message: "No rule matches — default policy applies".to_string(),
+            required_role: preview.required_role,
+            required_approvals: preview.required_approvals,
+            approvers,
+        },
+    }
+}
+
 fn rule_to_response(rule: zeltra_db::entities::approval_rules::Model) -> ApprovalRuleResponse {
     use zeltra_db::entities::sea_orm_active_enums::{TransactionType, UserRole};
 
@@ -408,6 +571,7 @@ fn rule_to_response(rule: zeltra_db::entities::approval_rules::Model) -> Approva
         transaction_types,
         required_role,
         priority: rule.priority,
+        required_approvals: rule.required_approvals,
         is_active: rule.is_active,
         created_at: rule.created_at.to_rfc3339(),
         updated_at: rule.updated_at.to_rfc3339(),
@@ -440,35 +604,6 @@ fn parse_optional_decimal(s: Option<&str>) -> Result<Option<Decimal>, axum::resp
     }
 }
 
-async fn check_membership(
-    org_repo: &OrganizationRepository,
-    org_id: Uuid,
-    user_id: Uuid,
-) -> Result<(), axum::response::Response> {
-    match org_repo.is_member(org_id, user_id).await {
-        Ok(true) => Ok(()),
-        Ok(false) => Err((
-            StatusCode::FORBIDDEN,
-            Json(json!({
-                "error": "forbidden",
-                "message": "You are not a member of this organization"
-            })),
-        )
-            .into_response()),
-        Err(e) => {
-            error!(error = %e, "Database error checking membership");
-            Err((
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(json!({
-                    "error": "internal_error",
-                    "message": "An error occurred"
-                })),
-            )
-                .into_response())
-        }
-    }
-}
-
 async fn check_admin_membership(
     org_repo: &OrganizationRepository,
     org_id: Uuid,
@@ -538,6 +673,15 @@ fn approval_rule_error_response(e: ApprovalRuleError) -> axum::response::Respons
             })),
         )
             .into_response(),
+        ApprovalRuleError::InvalidRequiredApprovals(_) => (
+            StatusCode::BAD_REQUEST,
+            Json(json!({
+                "error": "invalid_required_approvals",
+                "message": "required_approvals must be at least 1"
+            })),
+        )
+            .into_response(),
+        ApprovalRuleError::TierLimitExceeded(limit) => tier_limit_response(&limit),
         ApprovalRuleError::Database(_) => (
             StatusCode::INTERNAL_SERVER_ERROR,
             Json(json!({
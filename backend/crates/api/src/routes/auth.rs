@@ -1,18 +1,29 @@
-//! Authentication routes for login, register, token refresh, and logout.
-
-use axum::{Json, Router, extract::State, http::StatusCode, response::IntoResponse, routing::post};
+//! Authentication routes for login, register, token refresh, and logout,
+//! plus the authenticated user's own profile.
+
+use axum::{
+    Json, Router,
+    extract::State,
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    routing::{get, post},
+};
+use sea_orm::DbErr;
 use serde_json::json;
 use tracing::{error, info, warn};
+use uuid::Uuid;
 
-use crate::AppState;
-use zeltra_core::auth::{hash_password, verify_password};
+use crate::{AppState, middleware::AuthUser};
+use zeltra_core::auth::{SUPPORTED_LOCALES, hash_password, verify_password};
 use zeltra_db::{
-    EmailVerificationRepository, SessionRepository, UserRepository,
-    entities::sea_orm_active_enums::UserRole,
+    EmailVerificationRepository, OrganizationRepository, SessionRepository, UserRepository,
+    entities::{sea_orm_active_enums::UserRole, users},
+    repositories::{UpdateProfileInput, UserError},
 };
 use zeltra_shared::auth::{
     LoginRequest, LoginResponse, LogoutRequest, RefreshRequest, RegisterRequest,
-    ResendVerificationRequest, UserInfo, UserOrganization, VerifyEmailRequest, VerifyEmailResponse,
+    ResendVerificationRequest, TokenMember, UpdateProfileRequest, UserInfo, UserOrganization,
+    UserProfile, VerifyEmailRequest, VerifyEmailResponse,
 };
 
 /// Creates the auth router.
@@ -26,6 +37,11 @@ pub fn routes() -> Router<AppState> {
         .route("/auth/resend-verification", post(resend_verification))
 }
 
+/// Creates the profile router; these routes need an authenticated user.
+pub fn profile_routes() -> Router<AppState> {
+    Router::new().route("/auth/me", get(get_profile).patch(update_profile))
+}
+
 /// POST /auth/login - Authenticate user and return tokens.
 #[allow(clippy::too_many_lines)]
 async fn login(
@@ -117,8 +133,12 @@ async fn login(
         }
     };
 
-    // Get default organization for token (first one)
-    let (default_org, default_membership) = match orgs.first() {
+    // Get default organization for token: the user's choice, else the first
+    let preferred = orgs
+        .iter()
+        .find(|(org, _)| Some(org.id) == user.default_organization_id)
+        .or_else(|| orgs.first());
+    let (default_org, default_membership) = match preferred {
         Some((org, membership)) => (org.clone(), membership.clone()),
         None => {
             // User has no organizations - this shouldn't happen normally
@@ -135,44 +155,42 @@ async fn login(
     };
 
     // Generate tokens
-    let role_str = role_to_string(&default_membership.role);
-    let access_token =
-        match state
-            .jwt_service
-            .generate_access_token(user.id, default_org.id, &role_str)
-        {
-            Ok(t) => t,
-            Err(e) => {
-                error!(error = %e, "Failed to generate access token");
-                return (
-                    StatusCode::INTERNAL_SERVER_ERROR,
-                    Json(json!({
-                        "error": "internal_error",
-                        "message": "An error occurred during login"
-                    })),
-                )
-                    .into_response();
-            }
-        };
-
-    let refresh_token =
-        match state
-            .jwt_service
-            .generate_refresh_token(user.id, default_org.id, &role_str)
-        {
-            Ok(t) => t,
-            Err(e) => {
-                error!(error = %e, "Failed to generate refresh token");
-                return (
-                    StatusCode::INTERNAL_SERVER_ERROR,
-                    Json(json!({
-                        "error": "internal_error",
-                        "message": "An error occurred during login"
-                    })),
-                )
-                    .into_response();
-            }
-        };
+    let member = TokenMember {
+        user_id: user.id,
+        org_id: default_org.id,
+        role: role_to_string(&default_membership.role),
+        approval_limit: default_membership.approval_limit,
+        token_version: user.token_version,
+    };
+    let access_token = match state.jwt_service.generate_member_access_token(&member) {
+        Ok(t) => t,
+        Err(e) => {
+            error!(error = %e, "Failed to generate access token");
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({
+                    "error": "internal_error",
+                    "message": "An error occurred during login"
+                })),
+            )
+                .into_response();
+        }
+    };
+
+    let refresh_token = match state.jwt_service.generate_member_refresh_token(&member) {
+        Ok(t) => t,
+        Err(e) => {
+            error!(error = %e, "Failed to generate refresh token");
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({
+                    "error": "internal_error",
+                    "message": "An error occurred during login"
+                })),
+            )
+                .into_response();
+        }
+    };
 
     // Store session in database
     let expires_at =
@@ -385,12 +403,34 @@ async fn refresh(
         }
     };
 
+    // Re-read membership so role changes take effect on refresh
+    let member = match load_token_member(&state, claims.user_id(), claims.organization_id()).await {
+        Ok(Some(m)) => m,
+        Ok(None) => {
+            return (
+                StatusCode::FORBIDDEN,
+                Json(json!({
+                    "error": "not_member",
+                    "message": "User is no longer a member of this organization"
+                })),
+            )
+                .into_response();
+        }
+        Err(e) => {
+            error!(error = %e, "Database error loading membership");
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({
+                    "error": "internal_error",
+                    "message": "An error occurred during token refresh"
+                })),
+            )
+                .into_response();
+        }
+    };
+
     // Generate new access token
-    let access_token = match state.jwt_service.generate_access_token(
-        claims.user_id(),
-        claims.organization_id(),
-        &claims.role,
-    ) {
+    let access_token = match state.jwt_service.generate_member_access_token(&member) {
         Ok(t) => t,
         Err(e) => {
             error!(error = %e, "Failed to generate access token");
@@ -458,6 +498,33 @@ async fn logout(
     }
 }
 
+/// Loads the membership details embedded in tokens for a user and organization.
+///
+/// Returns `None` if the user no longer exists or is not a member.
+async fn load_token_member(
+    state: &AppState,
+    user_id: Uuid,
+    org_id: Uuid,
+) -> Result<Option<TokenMember>, DbErr> {
+    let user_repo = UserRepository::new((*state.db).clone());
+    let org_repo = OrganizationRepository::new((*state.db).clone());
+
+    let Some(token_version) = user_repo.get_token_version(user_id).await? else {
+        return Ok(None);
+    };
+    let Some(membership) = org_repo.get_user_membership(org_id, user_id).await? else {
+        return Ok(None);
+    };
+
+    Ok(Some(TokenMember {
+        user_id,
+        org_id,
+        role: role_to_string(&membership.role),
+        approval_limit: membership.approval_limit,
+        token_version,
+    }))
+}
+
 /// Converts `UserRole` enum to string.
 fn role_to_string(role: &UserRole) -> String {
     match role {
@@ -478,8 +545,19 @@ async fn verify_email(
     let email_verification_repo = EmailVerificationRepository::new((*state.db).clone());
 
     match email_verification_repo.verify_token(&payload.token).await {
-        Ok(user) => {
+        Ok(verified) => {
+            let user = verified.user;
             info!(user_id = %user.id, "Email verified successfully");
+            if let Some(previous_email) = verified.previous_email {
+                info!(user_id = %user.id, "Email address changed");
+                if let Err(e) = state
+                    .email_service
+                    .send_email_changed_notice(&previous_email, &user.full_name, &user.email)
+                    .await
+                {
+                    warn!(error = %e, user_id = %user.id, "Failed to notify previous email");
+                }
+            }
             (
                 StatusCode::OK,
                 Json(VerifyEmailResponse {
@@ -491,7 +569,16 @@ async fn verify_email(
         }
         Err(e) => {
             let error_msg = e.to_string();
-            if error_msg.contains("Invalid or expired") {
+            if error_msg.contains("Email already in use") {
+                (
+                    StatusCode::CONFLICT,
+                    Json(json!({
+                        "error": "email_exists",
+                        "message": "An account with this email already exists"
+                    })),
+                )
+                    .into_response()
+            } else if error_msg.contains("Invalid or expired") {
                 (
                     StatusCode::BAD_REQUEST,
                     Json(json!({
@@ -604,3 +691,222 @@ async fn resend_verification(
     )
         .into_response()
 }
+
+/// GET /auth/me - Get the authenticated user's profile.
+async fn get_profile(State(state): State<AppState>, auth: AuthUser) -> impl IntoResponse {
+    let user_repo = UserRepository::new((*state.db).clone());
+
+    let user = match user_repo.find_by_id(auth.user_id()).await {
+        Ok(Some(u)) => u,
+        Ok(None) => return user_error_response(UserError::NotFound(auth.user_id())),
+        Err(e) => return user_error_response(e.into()),
+    };
+
+    match profile(&user_repo, user).await {
+        Ok(profile) => (StatusCode::OK, Json(profile)).into_response(),
+        Err(e) => user_error_response(e.into()),
+    }
+}
+
+/// PATCH /auth/me - Update the authenticated user's profile.
+///
+/// A new email is only requested here: the response is 202 and the address
+/// changes once the link sent to it is confirmed via /auth/verify-email.
+async fn update_profile(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Json(payload): Json<UpdateProfileRequest>,
+) -> impl IntoResponse {
+    let user_repo = UserRepository::new((*state.db).clone());
+
+    let user = match user_repo
+        .update_profile(
+            auth.user_id(),
+            UpdateProfileInput {
+                full_name: payload.full_name,
+                locale: payload.locale,
+                default_organization_id: payload.default_organization_id,
+            },
+        )
+        .await
+    {
+        Ok(u) => u,
+        Err(e) => return user_error_response(e),
+    };
+
+    let new_email = payload
+        .email
+        .map(|email| email.trim().to_string())
+        .filter(|email| !email.eq_ignore_ascii_case(&user.email));
+    let status = match new_email {
+        Some(new_email) => match request_email_change(&state, &user, &new_email).await {
+            Ok(()) => StatusCode::ACCEPTED,
+            Err(response) => return response,
+        },
+        None => StatusCode::OK,
+    };
+
+    info!(user_id = %user.id, "Profile updated");
+
+    match profile(&user_repo, user).await {
+        Ok(profile) => (status, Json(profile)).into_response(),
+        Err(e) => user_error_response(e.into()),
+    }
+}
+
+/// Emails a confirmation link to `new_email`; the user's address is
+/// unchanged until it is used.
+async fn request_email_change(
+    state: &AppState,
+    user: &users::Model,
+    new_email: &str,
+) -> Result<(), Response> {
+    let user_repo = UserRepository::new((*state.db).clone());
+    let email_verification_repo = EmailVerificationRepository::new((*state.db).clone());
+
+    match user_repo.email_exists(new_email).await {
+        Ok(false) => {}
+        Ok(true) => {
+            return Err((
+                StatusCode::CONFLICT,
+                Json(json!({
+                    "error": "email_exists",
+                    "message": "An account with this email already exists"
+                })),
+            )
+                .into_response());
+        }
+        Err(e) => return Err(user_error_response(e.into())),
+    }
+
+    let token = email_verification_repo
+        .create_email_change_token(user.id, new_email)
+        .await
+        .map_err(|e| user_error_response(e.into()))?;
+
+    if let Err(e) = state
+        .email_service
+        .send_email_change_email(new_email, &user.full_name, &token)
+        .await
+    {
+        error!(error = %e, user_id = %user.id, "Failed to send email change confirmation");
+        return Err((
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({
+                "error": "email_error",
+                "message": "Failed to send confirmation email"
+            })),
+        )
+            .into_response());
+    }
+
+    info!(user_id = %user.id, "Email change requested");
+    Ok(())
+}
+
+/// Builds the profile response for a user.
+async fn profile(user_repo: &UserRepository, user: users::Model) -> Result<UserProfile, DbErr> {
+    let orgs = user_repo.get_user_organizations(user.id).await?;
+
+    Ok(UserProfile {
+        id: user.id,
+        email: user.email,
+        full_name: user.full_name,
+        email_verified: user.email_verified_at.is_some(),
+        locale: user.locale,
+        default_organization_id: user.default_organization_id,
+        organizations: orgs
+            .into_iter()
+            .map(|(org, membership)| UserOrganization {
+                id: org.id,
+                name: org.name,
+                slug: org.slug,
+                role: role_to_string(&membership.role),
+            })
+            .collect(),
+    })
+}
+
+/// Maps a profile error to a JSON response.
+fn user_error_response(e: UserError) -> Response {
+    match e {
+        UserError::NotFound(_) => (
+            StatusCode::NOT_FOUND,
+            Json(json!({
+                "error": "not_found",
+                "message": "User not found"
+            })),
+        )
+            .into_response(),
+        UserError::EmptyFullName => (
+            StatusCode::BAD_REQUEST,
+            Json(json!({
+                "error": "validation_error",
+                "message": e.to_string()
+            })),
+        )
+            .into_response(),
+        UserError::UnsupportedLocale(_) => (
+            StatusCode::BAD_REQUEST,
+            Json(json!({
+                "error": "unsupported_locale",
+                "message": e.to_string(),
+                "supported": SUPPORTED_LOCALES
+            })),
+        )
+            .into_response(),
+        UserError::NotMember(_) => (
+            StatusCode::BAD_REQUEST,
+            Json(json!({
+                "error": "not_member",
+                "message": "You are not a member of that organization"
+            })),
+        )
+            .into_response(),
+        UserError::SoleOwner(_) => (
+            StatusCode::CONFLICT,
+            Json(json!({
+                "error": "sole_owner",
+                "message": e.to_string()
+            })),
+        )
+            .into_response(),
+        UserError::Database(e) => {
+            error!(error = %e, "Database error updating profile");
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({
+                    "error": "internal_error",
+                    "message": "An error occurred"
+                })),
+            )
+                .into_response()
+        }
+    }
+}
+
+#[cfg(test)]
+mod integration_tests {
+    use super::*;
+    use zeltra_test_support::{OrgFixture, TestDb, access_token, send, test_app_state};
+
+    #[tokio::test]
+    async fn test_patch_profile_rejects_default_org_without_membership() {
+        let test_db = TestDb::new().await;
+        let state: AppState = test_app_state(&test_db);
+        let org = OrgFixture::new().create(test_db.conn()).await;
+        let other = OrgFixture::new().create(test_db.conn()).await;
+        let token = access_token(&state.jwt_service, org.id, &org.owner);
+        let (status, body) = send(
+            state.authenticated(profile_routes()),
+            "PATCH",
+            "/auth/me",
+            &token,
+            json!({ "default_organization_id": other.id }),
+        )
+        .await;
+
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body["error"], "not_member");
+    }
+}
@@ -0,0 +1,271 @@
+//! Organization backup routes.
+//!
+//! Export streams the organization's data as an NDJSON bundle; import loads
+//! such a bundle into an empty organization. Both are owner-only and work
+//! row by row, so bundle size is bounded by the body limit, not memory.
+
+use axum::{
+    Json, Router,
+    body::Body,
+    extract::{Path, State},
+    http::{
+        StatusCode,
+        header::{CONTENT_DISPOSITION, CONTENT_TYPE},
+    },
+    response::{IntoResponse, Response},
+    routing::{get, post},
+};
+use futures::{StreamExt, TryStreamExt};
+use serde_json::json;
+use tokio_util::io::{ReaderStream, StreamReader};
+use tracing::{error, info};
+use uuid::Uuid;
+
+use crate::{AppState, middleware::AuthUser};
+use zeltra_db::{
+    OrganizationRepository,
+    entities::sea_orm_active_enums::UserRole,
+    repositories::backup::{BackupError, BackupRepository},
+};
+
+/// Bytes buffered between the export query and the response body.
+const EXPORT_BUFFER_BYTES: usize = 64 * 1024;
+
+/// Creates the export route.
+pub fn export_routes() -> Router<AppState> {
+    Router::new().route("/organizations/{org_id}/export", get(export_organization))
+}
+
+/// Creates the import route, which gets its own body limit.
+pub fn import_routes() -> Router<AppState> {
+    Router::new().route("/organizations/{org_id}/import", post(import_organization))
+}
+
+/// Checks that the user owns the organization.
+async fn check_owner(state: &AppState, org_id: Uuid, user_id: Uuid) -> Result<(), Response> {
+    let org_repo = OrganizationRepository::new((*state.db).clone());
+
+    match org_repo.has_role(org_id, user_id, UserRole::Owner).await {
+        Ok(true) => Ok(()),
+        Ok(false) => Err((
+            StatusCode::FORBIDDEN,
+            Json(json!({
+                "error": "forbidden",
+                "message": "Only the organization owner can export or import data"
+            })),
+        )
+            .into_response()),
+        Err(e) => {
+            error!(error = %e, "Database error checking role");
+            Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({
+                    "error": "internal_error",
+                    "message": "An error occurred"
+                })),
+            )
+                .into_response())
+        }
+    }
+}
+
+/// GET `/organizations/{org_id}/export` - Stream a backup bundle.
+///
+/// The body is written while the export runs. If the export fails part way
+/// the body ends with an error, so clients never mistake it for a complete
+/// bundle.
+async fn export_organization(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(org_id): Path<Uuid>,
+) -> Response {
+    if let Err(response) = check_owner(&state, org_id, auth.user_id()).await {
+        return response;
+    }
+
+    let repo = BackupRepository::new((*state.db).clone());
+    let (writer, reader) = tokio::io::duplex(EXPORT_BUFFER_BYTES);
+    let export = tokio::spawn(async move { repo.export(org_id.into(), writer).await });
+
+    let outcome = futures::stream::once(async move {
+        match export.await {
+            Ok(Ok(manifest)) => {
+                info!(counts = ?manifest.counts, "Organization exported");
+                None
+            }
+            Ok(Err(e)) => {
+                error!(error = %e, "Organization export failed");
+                Some(Err(std::io::Error::other(e)))
+            }
+            Err(e) => {
+                error!(error = %e, "Organization export task failed");
+                Some(Err(std::io::Error::other(e)))
+            }
+        }
+    })
+    .filter_map(futures::future::ready);
+
+    let filename = format!(
+        "attachment; filename=\"zeltra-{org_id}-{}.ndjson\"",
+        chrono::Utc::now().format("%Y%m%d")
+    );
+
+    (
+        StatusCode::OK,
+        [
+            (CONTENT_TYPE, "application/x-ndjson".to_string()),
+            (CONTENT_DISPOSITION, filename),
+        ],
+        Body::from_stream(ReaderStream::new(reader).chain(outcome)),
+    )
+        .into_response()
+}
+
+/// POST `/organizations/{org_id}/import` - Load a backup bundle into an empty organization.
+async fn import_organization(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(org_id): Path<Uuid>,
+    body: Body,
+) -> Response {
+    let user_id = auth.user_id();
+    if let Err(response) = check_owner(&state, org_id, user_id).await {
+        return response;
+    }
+
+    let input = StreamReader::new(body.into_data_stream().map_err(std::io::Error::other));
+    let repo = BackupRepository::new((*state.db).clone());
+
+    match repo.import(org_id.into(), user_id, input).await {
+        Ok(summary) => {
+            info!(counts = ?summary.counts, "Organization imported");
+            (
+                StatusCode::OK,
+                Json(json!({
+                    "organization_id": org_id,
+                    "counts": summary.counts
+                })),
+            )
+                .into_response()
+        }
+        Err(e) => import_error_response(&e),
+    }
+}
+
+/// Maps import errors to responses.
+fn import_error_response(err: &BackupError) -> Response {
+    let (status, code) = match err {
+        BackupError::OrganizationNotFound(_) => (StatusCode::NOT_FOUND, "not_found"),
+        BackupError::NotEmpty(_) => (StatusCode::CONFLICT, "organization_not_empty"),
+        BackupError::UnsupportedSchema(_) => (StatusCode::BAD_REQUEST, "unsupported_schema"),
+        BackupError::CurrencyMismatch { .. } => (StatusCode::BAD_REQUEST, "currency_mismatch"),
+        BackupError::Unbalanced { .. } => (StatusCode::BAD_REQUEST, "unbalanced_transaction"),
+        // Io means reading the body failed: the client went away or sent too much
+        BackupError::Malformed { .. }
+        | BackupError::DanglingReference { .. }
+        | BackupError::CountMismatch { .. }
+        | BackupError::Io(_) => (StatusCode::BAD_REQUEST, "invalid_backup"),
+        // Only cloning selects sections or reads settings
+        BackupError::MissingDependency { .. }
+        | BackupError::InvalidSettings(_)
+        | BackupError::Database(_) => {
+            error!(error = %err, "Organization import failed");
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({
+                    "error": "internal_error",
+                    "message": "An error occurred"
+                })),
+            )
+                .into_response();
+        }
+    };
+
+    (
+        status,
+        Json(json!({
+            "error": code,
+            "message": err.to_string()
+        })),
+    )
+        .into_response()
+}
+
+#[cfg(test)]
+mod integration_tests {
+    use super::*;
+    use axum::http::{Request, header::AUTHORIZATION};
+    use zeltra_db::entities::sea_orm_active_enums::AccountType;
+    use zeltra_test_support::{
+        OrgFixture, TestDb, access_token, jwt_service, send_request, test_app_state,
+    };
+
+    async fn send(
+        state: &AppState,
+        method: &str,
+        uri: &str,
+        token: &str,
+        body: Vec<u8>,
+    ) -> (StatusCode, Vec<u8>) {
+        let app = state.authenticated(Router::new().merge(export_routes()).merge(import_routes()));
+        let request = Request::builder()
+            .method(method)
+            .uri(uri)
+            .header(AUTHORIZATION, format!("Bearer {token}"))
+            .body(Body::from(body))
+            .unwrap();
+        let response = send_request(app, request).await;
+        (response.status, response.body.to_vec())
+    }
+
+    #[tokio::test]
+    async fn test_export_then_import_into_empty_organization() {
+        let test_db = TestDb::new().await;
+        let state: AppState = test_app_state(&test_db);
+        let source = OrgFixture::new()
+            .with_fiscal_year_2025()
+            .with_accounts(&[("1000", AccountType::Asset), ("4000", AccountType::Revenue)])
+            .create(test_db.conn())
+            .await;
+        let target = OrgFixture::new().create(test_db.conn()).await;
+        let jwt = jwt_service();
+
+        let token = access_token(&jwt, source.id, &source.owner);
+        let uri = format!("/organizations/{}/export", source.id);
+        let (status, bundle) = send(&state, "GET", &uri, &token, Vec::new()).await;
+        assert_eq!(status, StatusCode::OK);
+        assert!(bundle.starts_with(b"{\"kind\":\"manifest\""));
+
+        let token = access_token(&jwt, target.id, &target.owner);
+        let uri = format!("/organizations/{}/import", target.id);
+        let (status, body) = send(&state, "POST", &uri, &token, bundle.clone()).await;
+        assert_eq!(status, StatusCode::OK);
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(body["counts"]["accounts"], 2);
+
+        // The target is no longer empty
+        let (status, body) = send(&state, "POST", &uri, &token, bundle).await;
+        assert_eq!(status, StatusCode::CONFLICT);
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(body["error"], "organization_not_empty");
+    }
+
+    #[tokio::test]
+    async fn test_export_and_import_require_owner() {
+        let test_db = TestDb::new().await;
+        let state: AppState = test_app_state(&test_db);
+        let org = OrgFixture::new()
+            .with_member(UserRole::Admin)
+            .create(test_db.conn())
+            .await;
+        let token = access_token(&jwt_service(), org.id, org.member(&UserRole::Admin));
+
+        let uri = format!("/organizations/{}/export", org.id);
+        let (status, _) = send(&state, "GET", &uri, &token, Vec::new()).await;
+        assert_eq!(status, StatusCode::FORBIDDEN);
+
+        let uri = format!("/organizations/{}/import", org.id);
+        let (status, _) = send(&state, "POST", &uri, &token, Vec::new()).await;
+        assert_eq!(status, StatusCode::FORBIDDEN);
+    }
+}
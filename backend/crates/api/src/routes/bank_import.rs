@@ -0,0 +1,665 @@
+//! Bank statement import routes.
+//!
+//! Turns bank statement CSV lines into draft two-entry journals against a bank
+//! account. The preview call suggests counter-accounts from posting history;
+//! the confirm call creates the selected drafts in one database transaction.
+
+use axum::{
+    Json, Router,
+    extract::{Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+    routing::post,
+};
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::str::FromStr;
+use tracing::{error, info};
+use uuid::Uuid;
+
+use crate::{
+    AppState,
+    middleware::{AuthUser, auth::check_membership},
+    routes::transactions::entry_account_error_response,
+};
+use zeltra_core::bank_import::{
+    AccountMatcher, BankImportService, MatchKind, ProposedJournal, parse_statement_csv,
+};
+use zeltra_core::ledger::types::EntryType;
+use zeltra_core::settings::OrganizationSettings;
+use zeltra_db::{
+    OrganizationRepository,
+    entities::{chart_of_accounts, organizations, sea_orm_active_enums::TransactionType},
+    repositories::account::AccountRepository,
+    repositories::transaction::{
+        CreateLedgerEntryInput, CreateTransactionInput, TransactionError, TransactionRepository,
+    },
+};
+
+/// Maximum number of statement lines accepted in one import.
+const MAX_IMPORT_LINES: usize = 1000;
+
+/// Number of recent posted transactions used as matching history.
+const HISTORY_LIMIT: u64 = 1000;
+
+/// Creates the bank import routes.
+pub fn routes() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/organizations/{org_id}/bank-import",
+            post(preview_bank_import),
+        )
+        .route(
+            "/organizations/{org_id}/bank-import/confirm",
+            post(confirm_bank_import),
+        )
+}
+
+// ============================================================================
+// Request/Response Types
+// ============================================================================
+
+/// Request body for previewing a bank statement import.
+#[derive(Debug, Deserialize)]
+pub struct PreviewBankImportRequest {
+    /// Bank account the statement belongs to.
+    pub bank_account_id: Uuid,
+    /// CSV with a header row: date, description, amount, optional reference.
+    pub csv: String,
+    /// Account for unmatched lines. Falls back to the organization's
+    /// `bank_import.suspense_account_id` setting.
+    pub suspense_account_id: Option<Uuid>,
+}
+
+/// A proposed draft journal for one statement line.
+#[derive(Debug, Serialize)]
+pub struct BankImportLineResponse {
+    /// Source line number in the CSV.
+    pub line_number: usize,
+    /// Transaction date.
+    pub date: String,
+    /// Statement description.
+    pub description: String,
+    /// Bank reference.
+    pub reference: Option<String>,
+    /// Absolute amount.
+    pub amount: String,
+    /// Side of the bank account entry: "debit" or "credit".
+    pub bank_entry_type: EntryType,
+    /// Suggested counter-account.
+    pub counter_account_id: Option<Uuid>,
+    /// How the counter-account was chosen.
+    pub match_kind: MatchKind,
+}
+
+/// Response for a bank statement import preview.
+#[derive(Debug, Serialize)]
+pub struct BankImportPreviewResponse {
+    /// Bank account ID.
+    pub bank_account_id: Uuid,
+    /// Currency of the bank account.
+    pub currency: String,
+    /// Proposed journals, one per statement line.
+    pub lines: Vec<BankImportLineResponse>,
+    /// Lines matched from history.
+    pub matched_count: usize,
+    /// Lines that fell back to suspense or have no counter-account.
+    pub unmatched_count: usize,
+}
+
+/// A statement line selected for creation.
+#[derive(Debug, Deserialize)]
+pub struct ConfirmBankImportLine {
+    /// Transaction date.
+    pub date: NaiveDate,
+    /// Transaction description.
+    pub description: String,
+    /// Bank reference.
+    pub reference: Option<String>,
+    /// Absolute amount.
+    pub amount: String,
+    /// Side of the bank account entry: "debit" or "credit".
+    pub bank_entry_type: EntryType,
+    /// Counter-account for the other side.
+    pub counter_account_id: Uuid,
+    /// Optional memo.
+    pub memo: Option<String>,
+}
+
+/// Request body for confirming a bank statement import.
+#[derive(Debug, Deserialize)]
+pub struct ConfirmBankImportRequest {
+    /// Bank account the statement belongs to.
+    pub bank_account_id: Uuid,
+    /// Lines to create as draft transactions.
+    pub lines: Vec<ConfirmBankImportLine>,
+    /// Import lines even if they look like existing transactions.
+    #[serde(default)]
+    pub allow_duplicate: bool,
+}
+
+/// A draft transaction created by the import.
+#[derive(Debug, Serialize)]
+pub struct CreatedDraftResponse {
+    /// Transaction ID.
+    pub id: Uuid,
+    /// Transaction date.
+    pub transaction_date: String,
+    /// Description.
+    pub description: String,
+    /// Reference number.
+    pub reference_number: Option<String>,
+    /// Amount.
+    pub amount: String,
+}
+
+/// Response for a confirmed bank statement import.
+#[derive(Debug, Serialize)]
+pub struct ConfirmBankImportResponse {
+    /// Created draft transactions.
+    pub transactions: Vec<CreatedDraftResponse>,
+    /// Number of drafts created.
+    pub created_count: usize,
+}
+
+// ============================================================================
+// Route Handlers
+// ============================================================================
+
+/// POST `/organizations/{org_id}/bank-import` - Preview draft journals for a bank statement.
+#[allow(clippy::too_many_lines)]
+async fn preview_bank_import(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(org_id): Path<Uuid>,
+    Json(payload): Json<PreviewBankImportRequest>,
+) -> impl IntoResponse {
+    let org_repo = OrganizationRepository::new((*state.db).clone());
+
+    if let Err(response) = check_membership(&state, org_id, auth.user_id()).await {
+        return response;
+    }
+
+    let lines = match parse_statement_csv(&payload.csv) {
+        Ok(lines) => lines,
+        Err(e) => {
+            return (
+                StatusCode::BAD_REQUEST,
+                Json(json!({
+                    "error": "invalid_csv",
+                    "message": e.to_string()
+                })),
+            )
+                .into_response();
+        }
+    };
+
+    if lines.len() > MAX_IMPORT_LINES {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(json!({
+                "error": "too_many_lines",
+                "message": format!("A statement may contain at most {MAX_IMPORT_LINES} lines")
+            })),
+        )
+            .into_response();
+    }
+
+    let account_repo = AccountRepository::new((*state.db).clone());
+    let (org, bank_account) =
+        match load_bank_account(&org_repo, &account_repo, org_id, payload.bank_account_id).await {
+            Ok(loaded) => loaded,
+            Err(response) => return response,
+        };
+
+    // Request override first, then the organization setting
+    let suspense_account_id = match payload.suspense_account_id {
+        Some(id) => Some(id),
+        None => match OrganizationSettings::from_value(&org.settings) {
+            Ok(settings) => settings.bank_import.suspense_account_id,
+            Err(e) => {
+                error!(error = %e, "Stored organization settings are invalid");
+                return (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    Json(json!({
+                        "error": "internal_error",
+                        "message": "An error occurred"
+                    })),
+                )
+                    .into_response();
+            }
+        },
+    };
+
+    if let Some(suspense_id) = suspense_account_id {
+        match account_repo.find_account_by_id(suspense_id.into()).await {
+            Ok(Some(a)) if a.account.organization_id == org_id && a.account.is_active => {}
+            Ok(_) => {
+                return (
+                    StatusCode::BAD_REQUEST,
+                    Json(json!({
+                        "error": "invalid_suspense_account",
+                        "message": format!("Suspense account not found or inactive: {}", suspense_id)
+                    })),
+                )
+                    .into_response();
+            }
+            Err(e) => {
+                error!(error = %e, "Failed to get suspense account");
+                return (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    Json(json!({
+                        "error": "internal_error",
+                        "message": "An error occurred"
+                    })),
+                )
+                    .into_response();
+            }
+        }
+    }
+
+    let tx_repo = TransactionRepository::new((*state.db).clone());
+    let history = match tx_repo
+        .list_posting_history(org_id.into(), HISTORY_LIMIT)
+        .await
+    {
+        Ok(h) => h,
+        Err(e) => {
+            error!(error = %e, "Failed to load posting history");
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({
+                    "error": "internal_error",
+                    "message": "An error occurred"
+                })),
+            )
+                .into_response();
+        }
+    };
+
+    let matcher = AccountMatcher::new(&history);
+    let proposals =
+        BankImportService::propose(&lines, bank_account.id, &matcher, suspense_account_id);
+
+    let matched_count = proposals
+        .iter()
+        .filter(|p| {
+            matches!(
+                p.match_kind,
+                MatchKind::Exact | MatchKind::Substring | MatchKind::Keyword
+            )
+        })
+        .count();
+
+    let response = BankImportPreviewResponse {
+        bank_account_id: bank_account.id,
+        currency: bank_account.currency,
+        unmatched_count: proposals.len() - matched_count,
+        matched_count,
+        lines: proposals.into_iter().map(proposal_to_response).collect(),
+    };
+
+    (StatusCode::OK, Json(response)).into_response()
+}
+
+/// POST `/organizations/{org_id}/bank-import/confirm` - Create selected drafts in bulk.
+#[allow(clippy::too_many_lines)]
+async fn confirm_bank_import(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(org_id): Path<Uuid>,
+    Json(payload): Json<ConfirmBankImportRequest>,
+) -> impl IntoResponse {
+    let org_repo = OrganizationRepository::new((*state.db).clone());
+
+    if let Err(response) = check_membership(&state, org_id, auth.user_id()).await {
+        return response;
+    }
+
+    if payload.lines.is_empty() || payload.lines.len() > MAX_IMPORT_LINES {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(json!({
+                "error": "invalid_line_count",
+                "message": format!("Select between 1 and {MAX_IMPORT_LINES} lines to import")
+            })),
+        )
+            .into_response();
+    }
+
+    let account_repo = AccountRepository::new((*state.db).clone());
+    let (org, bank_account) =
+        match load_bank_account(&org_repo, &account_repo, org_id, payload.bank_account_id).await {
+            Ok(loaded) => loaded,
+            Err(response) => return response,
+        };
+
+    let window_days = match OrganizationSettings::from_value(&org.settings) {
+        Ok(settings) => settings.duplicate_detection.window_days,
+        Err(e) => {
+            error!(error = %e, "Stored organization settings are invalid");
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({
+                    "error": "internal_error",
+                    "message": "An error occurred"
+                })),
+            )
+                .into_response();
+        }
+    };
+
+    let currency = org.base_currency;
+    let mut inputs = Vec::with_capacity(payload.lines.len());
+
+    for (idx, line) in payload.lines.into_iter().enumerate() {
+        let amount = match Decimal::from_str(&line.amount) {
+            Ok(a) if a > Decimal::ZERO => a,
+            _ => {
+                return (
+                    StatusCode::BAD_REQUEST,
+                    Json(json!({
+                        "error": "invalid_amount",
+                        "message": format!("Line {}: amount must be a positive number", idx + 1)
+                    })),
+                )
+                    .into_response();
+            }
+        };
+
+        if line.counter_account_id == bank_account.id {
+            return (
+                StatusCode::BAD_REQUEST,
+                Json(json!({
+                    "error": "invalid_counter_account",
+                    "message": format!("Line {}: counter-account cannot be the bank account", idx + 1)
+                })),
+            )
+                .into_response();
+        }
+
+        match account_repo
+            .find_account_by_id(line.counter_account_id.into())
+            .await
+        {
+            Ok(Some(a)) if a.account.organization_id == org_id => {}
+            Ok(_) => {
+                return (
+                    StatusCode::BAD_REQUEST,
+                    Json(json!({
+                        "error": "account_not_found",
+                        "message": format!("Account not found: {}", line.counter_account_id)
+                    })),
+                )
+                    .into_response();
+            }
+            Err(e) => {
+                error!(error = %e, "Failed to get counter-account");
+                return (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    Json(json!({
+                        "error": "internal_error",
+                        "message": "An error occurred"
+                    })),
+                )
+                    .into_response();
+            }
+        }
+
+        let entry = |account_id: Uuid, entry_type: EntryType| {
+            let (debit, credit) = match entry_type {
+                EntryType::Debit => (amount, Decimal::ZERO),
+                EntryType::Credit => (Decimal::ZERO, amount),
+            };
+            CreateLedgerEntryInput {
+                account_id,
+                source_currency: currency.clone(),
+                source_amount: amount,
+                exchange_rate: Decimal::ONE,
+                functional_currency: currency.clone(),
+                functional_amount: amount,
+                debit,
+                credit,
+                memo: line.memo.clone(),
+                dimensions: vec![],
+                rate_override: None,
+                event_at: None,
+            }
+        };
+
+        let counter_entry_type = match line.bank_entry_type {
+            EntryType::Debit => EntryType::Credit,
+            EntryType::Credit => EntryType::Debit,
+        };
+
+        let entries = vec![
+            entry(bank_account.id, line.bank_entry_type),
+            entry(line.counter_account_id, counter_entry_type),
+        ];
+
+        inputs.push(CreateTransactionInput {
+            organization_id: org_id,
+            transaction_type: TransactionType::Journal,
+            transaction_date: line.date,
+            description: line.description,
+            reference_number: line.reference,
+            memo: None,
+            entries,
+            created_by: auth.user_id(),
+        });
+    }
+
+    let tx_repo = TransactionRepository::new((*state.db).clone());
+
+    if !payload.allow_duplicate {
+        let mut duplicates = Vec::new();
+        for (idx, input) in inputs.iter().enumerate() {
+            match tx_repo
+                .find_possible_duplicates(
+                    org_id.into(),
+                    input.transaction_date,
+                    &input.entries,
+                    window_days,
+                )
+                .await
+            {
+                Ok(ids) if ids.is_empty() => {}
+                Ok(ids) => duplicates.push(json!({ "line": idx + 1, "transaction_ids": ids })),
+                Err(e) => {
+                    error!(error = %e, "Failed to check for duplicate transactions");
+                    return (
+                        StatusCode::INTERNAL_SERVER_ERROR,
+                        Json(json!({
+                            "error": "internal_error",
+                            "message": "An error occurred"
+                        })),
+                    )
+                        .into_response();
+                }
+            }
+        }
+
+        if !duplicates.is_empty() {
+            return (
+                StatusCode::CONFLICT,
+                Json(json!({
+                    "error": "possible_duplicate",
+                    "message": format!(
+                        "{} line(s) match existing transactions; resend with allow_duplicate to import them anyway",
+                        duplicates.len()
+                    ),
+                    "duplicates": duplicates
+                })),
+            )
+                .into_response();
+        }
+    }
+
+    match tx_repo.create_transactions(inputs).await {
+        Ok(created) => {
+            info!(
+                bank_account_id = %bank_account.id,
+                count = created.len(),
+                "Bank statement imported"
+            );
+
+            let transactions: Vec<CreatedDraftResponse> = created
+                .into_iter()
+                .map(|t| CreatedDraftResponse {
+                    id: t.transaction.id,
+                    transaction_date: t.transaction.transaction_date.to_string(),
+                    description: t.transaction.description,
+                    reference_number: t.transaction.reference_number,
+                    amount: t
+                        .entries
+                        .iter()
+                        .map(|e| e.entry.debit)
+                        .sum::<Decimal>()
+                        .to_string(),
+                })
+                .collect();
+
+            let response = ConfirmBankImportResponse {
+                created_count: transactions.len(),
+                transactions,
+            };
+
+            (StatusCode::CREATED, Json(response)).into_response()
+        }
+        Err(TransactionError::NoFiscalPeriod(date)) => (
+            StatusCode::BAD_REQUEST,
+            Json(json!({
+                "error": "no_fiscal_period",
+                "message": format!("No fiscal period found for date {}", date)
+            })),
+        )
+            .into_response(),
+        Err(TransactionError::AccountNotFound(id)) => (
+            StatusCode::BAD_REQUEST,
+            Json(json!({
+                "error": "account_not_found",
+                "message": format!("Account not found: {}", id)
+            })),
+        )
+            .into_response(),
+        Err(e) => {
+            if let Some(response) = entry_account_error_response(&e) {
+                return response;
+            }
+            error!(error = %e, "Failed to import bank statement");
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({
+                    "error": "internal_error",
+                    "message": "An error occurred"
+                })),
+            )
+                .into_response()
+        }
+    }
+}
+
+// ============================================================================
+// Helper Functions
+// ============================================================================
+
+/// Loads the organization and validates the target bank account.
+///
+/// The account must belong to the organization, be flagged as a bank account,
+/// and be in the organization's base currency.
+async fn load_bank_account(
+    org_repo: &OrganizationRepository,
+    account_repo: &AccountRepository,
+    org_id: Uuid,
+    bank_account_id: Uuid,
+) -> Result<(organizations::Model, chart_of_accounts::Model), axum::response::Response> {
+    let internal_error = || {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({
+                "error": "internal_error",
+                "message": "An error occurred"
+            })),
+        )
+            .into_response()
+    };
+
+    let org = match org_repo.find_by_id(org_id).await {
+        Ok(Some(o)) => o,
+        Ok(None) => {
+            return Err((
+                StatusCode::NOT_FOUND,
+                Json(json!({
+                    "error": "organization_not_found",
+                    "message": "Organization not found"
+                })),
+            )
+                .into_response());
+        }
+        Err(e) => {
+            error!(error = %e, "Failed to get organization");
+            return Err(internal_error());
+        }
+    };
+
+    let account = match account_repo
+        .find_account_by_id(bank_account_id.into())
+        .await
+    {
+        Ok(Some(a)) if a.account.organization_id == org_id => a.account,
+        Ok(_) => {
+            return Err((
+                StatusCode::NOT_FOUND,
+                Json(json!({
+                    "error": "account_not_found",
+                    "message": format!("Account not found: {}", bank_account_id)
+                })),
+            )
+                .into_response());
+        }
+        Err(e) => {
+            error!(error = %e, "Failed to get bank account");
+            return Err(internal_error());
+        }
+    };
+
+    if !account.is_bank_account {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(json!({
+                "error": "not_a_bank_account",
+                "message": format!("Account {} is not a bank account", account.code)
+            })),
+        )
+            .into_response());
+    }
+
+    // Same limitation as manual entry: no exchange rate lookup yet
+    if account.currency != org.base_currency {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(json!({
+                "error": "no_exchange_rate",
+                "message": format!("No exchange rate found for {} to {}", account.currency, org.base_currency)
+            })),
+        )
+            .into_response());
+    }
+
+    Ok((org, account))
+}
+
+fn proposal_to_response(p: ProposedJournal) -> BankImportLineResponse {
+    BankImportLineResponse {
+        line_number: p.line_number,
+        date: p.date.to_string(),
+        description: p.description,
+        reference: p.reference,
+        amount: p.amount.to_string(),
+        bank_entry_type: p.bank_entry_type,
+        counter_account_id: p.counter_account_id,
+        match_kind: p.match_kind,
+    }
+}
@@ -0,0 +1,245 @@
+//! Budget line comment routes.
+//!
+//! Members explain a line's variance for its fiscal period. Anyone who can
+//! read budgets may comment; authors edit their own comments and
+//! Accountants and above edit any. Edits are recorded as new versions.
+
+use axum::{
+    Json, Router,
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    routing::{get, patch},
+};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use tracing::{error, info};
+use uuid::Uuid;
+
+use crate::{AppState, middleware::AuthMember, routes::budgets::check_budget_access};
+use zeltra_core::comment::CommentError;
+use zeltra_db::repositories::{
+    BudgetCommentError, BudgetCommentRepository, BudgetCommentWithAuthor, CreateBudgetCommentInput,
+    EditBudgetCommentInput,
+};
+
+/// Creates the budget line comment routes.
+pub fn routes() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/organizations/{org_id}/budgets/{budget_id}/lines/{line_id}/comments",
+            get(list_comments).post(create_comment),
+        )
+        .route(
+            "/organizations/{org_id}/budgets/{budget_id}/lines/{line_id}/comments/{comment_id}",
+            patch(edit_comment),
+        )
+}
+
+// ============================================================================
+// Request/Response Types
+// ============================================================================
+
+/// Query parameters for listing comments.
+#[derive(Debug, Deserialize)]
+pub struct ListBudgetCommentsQuery {
+    /// Include earlier versions of edited comments (default: false).
+    #[serde(default)]
+    pub include_history: bool,
+}
+
+/// Request body for posting or editing a comment.
+#[derive(Debug, Deserialize)]
+pub struct BudgetCommentRequest {
+    /// Comment text.
+    pub body: String,
+}
+
+/// A comment's author.
+#[derive(Debug, Serialize)]
+pub struct BudgetCommentAuthorResponse {
+    /// User ID.
+    pub id: Uuid,
+    /// Display name.
+    pub full_name: String,
+    /// Email address.
+    pub email: String,
+}
+
+/// Response for one version of a comment.
+#[derive(Debug, Serialize)]
+pub struct BudgetCommentResponse {
+    /// Comment ID, shared by all versions of the comment.
+    pub id: Uuid,
+    /// ID of this version.
+    pub version_id: Uuid,
+    /// Budget line ID.
+    pub budget_line_id: Uuid,
+    /// Fiscal period the comment explains.
+    pub fiscal_period_id: Uuid,
+    /// Comment text.
+    pub body: String,
+    /// Author.
+    pub author: BudgetCommentAuthorResponse,
+    /// Member who made this edit; `None` for the original version.
+    pub edited_by: Option<Uuid>,
+    /// Whether this is the comment's latest version.
+    pub is_current: bool,
+    /// When this version was written.
+    pub created_at: String,
+}
+
+// ============================================================================
+// Handlers
+// ============================================================================
+
+/// GET `/organizations/{org_id}/budgets/{budget_id}/lines/{line_id}/comments` -
+/// List a budget line's comments, newest first.
+async fn list_comments(
+    State(state): State<AppState>,
+    auth: AuthMember,
+    Path((org_id, budget_id, line_id)): Path<(Uuid, Uuid, Uuid)>,
+    Query(query): Query<ListBudgetCommentsQuery>,
+) -> impl IntoResponse {
+    if let Err(response) = check_budget_access(&state, org_id, &auth).await {
+        return response;
+    }
+
+    let repo = BudgetCommentRepository::new((*state.db).clone());
+    if let Err(e) = repo.find_line(org_id, budget_id, line_id).await {
+        return comment_error_response(&e);
+    }
+
+    match repo.list(org_id, line_id, query.include_history).await {
+        Ok(comments) => {
+            let items: Vec<BudgetCommentResponse> =
+                comments.iter().map(comment_to_response).collect();
+            (StatusCode::OK, Json(json!({ "data": items }))).into_response()
+        }
+        Err(e) => comment_error_response(&e.into()),
+    }
+}
+
+/// POST `/organizations/{org_id}/budgets/{budget_id}/lines/{line_id}/comments` -
+/// Comment on a budget line's variance.
+async fn create_comment(
+    State(state): State<AppState>,
+    auth: AuthMember,
+    Path((org_id, budget_id, line_id)): Path<(Uuid, Uuid, Uuid)>,
+    Json(payload): Json<BudgetCommentRequest>,
+) -> impl IntoResponse {
+    if let Err(response) = check_budget_access(&state, org_id, &auth).await {
+        return response;
+    }
+
+    let repo = BudgetCommentRepository::new((*state.db).clone());
+    match repo
+        .create(CreateBudgetCommentInput {
+            organization_id: org_id,
+            budget_id,
+            budget_line_id: line_id,
+            author_id: auth.user_id(),
+            body: payload.body,
+        })
+        .await
+    {
+        Ok(comment) => {
+            info!(budget_line_id = %line_id, comment_id = %comment.comment.comment_id, "Budget comment posted");
+            (StatusCode::CREATED, Json(comment_to_response(&comment))).into_response()
+        }
+        Err(e) => comment_error_response(&e),
+    }
+}
+
+/// PATCH `/organizations/{org_id}/budgets/{budget_id}/lines/{line_id}/comments/{comment_id}` -
+/// Edit a comment.
+///
+/// Authors may edit their own comments; Accountants and above any comment.
+/// The previous text is kept as an earlier version.
+async fn edit_comment(
+    State(state): State<AppState>,
+    auth: AuthMember,
+    Path((org_id, budget_id, line_id, comment_id)): Path<(Uuid, Uuid, Uuid, Uuid)>,
+    Json(payload): Json<BudgetCommentRequest>,
+) -> impl IntoResponse {
+    let role = match check_budget_access(&state, org_id, &auth).await {
+        Ok(role) => role,
+        Err(response) => return response,
+    };
+
+    let repo = BudgetCommentRepository::new((*state.db).clone());
+    match repo
+        .edit(EditBudgetCommentInput {
+            organization_id: org_id,
+            budget_id,
+            budget_line_id: line_id,
+            comment_id,
+            editor_id: auth.user_id(),
+            editor_role: role,
+            body: payload.body,
+        })
+        .await
+    {
+        Ok(comment) => {
+            info!(comment_id = %comment_id, "Budget comment edited");
+            (StatusCode::OK, Json(comment_to_response(&comment))).into_response()
+        }
+        Err(e) => comment_error_response(&e),
+    }
+}
+
+// ============================================================================
+// Helper Functions
+// ============================================================================
+
+/// Builds the response for a comment version.
+pub(crate) fn comment_to_response(comment: &BudgetCommentWithAuthor) -> BudgetCommentResponse {
+    let c = &comment.comment;
+    BudgetCommentResponse {
+        id: c.comment_id,
+        version_id: c.id,
+        budget_line_id: c.budget_line_id,
+        fiscal_period_id: c.fiscal_period_id,
+        body: c.body.clone(),
+        author: BudgetCommentAuthorResponse {
+            id: c.author_id,
+            full_name: comment.author_name.clone(),
+            email: comment.author_email.clone(),
+        },
+        edited_by: c.edited_by,
+        is_current: c.is_current,
+        created_at: c.created_at.to_rfc3339(),
+    }
+}
+
+fn comment_error_response(e: &BudgetCommentError) -> Response {
+    let (status, code) = match e {
+        BudgetCommentError::LineNotFound(_) | BudgetCommentError::CommentNotFound(_) => {
+            (StatusCode::NOT_FOUND, "not_found")
+        }
+        BudgetCommentError::Comment(CommentError::EmptyBody | CommentError::BodyTooLong) => {
+            (StatusCode::BAD_REQUEST, "invalid_comment")
+        }
+        BudgetCommentError::NotAuthor => (StatusCode::FORBIDDEN, "forbidden"),
+        BudgetCommentError::Comment(_) | BudgetCommentError::Database(_) => {
+            error!(error = %e, "Database error in budget comments");
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({
+                    "error": "internal_error",
+                    "message": "An error occurred"
+                })),
+            )
+                .into_response();
+        }
+    };
+
+    (
+        status,
+        Json(json!({
+            "error": code,
+            "message": e.to_string()
+        })),
+    )
+        .into_response()
+}
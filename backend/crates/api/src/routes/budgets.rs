@@ -10,18 +10,30 @@ use axum::{
     routing::{get, post, put},
 };
 use rust_decimal::Decimal;
-use serde::{Deserialize, Serialize};
+use sea_orm::Iterable;
+use serde::{Deserialize, Deserializer, Serialize};
 use serde_json::json;
 use tracing::{error, info};
 use uuid::Uuid;
 
-use crate::{AppState, middleware::AuthUser};
+use crate::{
+    AppState,
+    extractors::{StrictJson, json::enum_string},
+    middleware::{AuthMember, with_last_modified},
+    routes::{budget_comments, tier_limit_response, transactions::amount_too_precise_response},
+};
+use zeltra_core::{
+    auth::UserRole as CoreUserRole,
+    budget::{ConvertSide, variance_exceeds},
+};
 use zeltra_db::{
     OrganizationRepository,
     entities::sea_orm_active_enums::UserRole,
     repositories::budget::{
-        BudgetError, BudgetRepository, CreateBudgetInput, CreateBudgetLineInput, UpdateBudgetInput,
+        AssignBudgetInput, BudgetError, BudgetRepository, CreateBudgetInput, CreateBudgetLineInput,
+        MAX_REPLACE_LINES, UpdateBudgetInput,
     },
+    repositories::budget_comment::BudgetCommentRepository,
 };
 
 /// Creates the budget routes (requires auth middleware to be applied externally).
@@ -35,7 +47,7 @@ pub fn routes() -> Router<AppState> {
         )
         .route(
             "/organizations/{org_id}/budgets/{budget_id}",
-            put(update_budget),
+            put(update_budget).patch(assign_budget),
         )
         .route(
             "/organizations/{org_id}/budgets/{budget_id}/lines",
@@ -45,6 +57,10 @@ pub fn routes() -> Router<AppState> {
             "/organizations/{org_id}/budgets/{budget_id}/lines",
             post(create_budget_lines),
         )
+        .route(
+            "/organizations/{org_id}/budgets/{budget_id}/lines",
+            put(replace_budget_lines),
+        )
         .route(
             "/organizations/{org_id}/budgets/{budget_id}/lock",
             post(lock_budget),
@@ -53,6 +69,10 @@ pub fn routes() -> Router<AppState> {
             "/organizations/{org_id}/budgets/{budget_id}/vs-actual",
             get(get_budget_vs_actual),
         )
+        .route(
+            "/organizations/{org_id}/budgets/{budget_id}/trend",
+            get(get_budget_trend),
+        )
 }
 
 // ============================================================================
@@ -61,6 +81,7 @@ pub fn routes() -> Router<AppState> {
 
 /// Request body for creating a budget.
 #[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct CreateBudgetRequest {
     /// Budget name.
     pub name: String,
@@ -69,11 +90,13 @@ pub struct CreateBudgetRequest {
     /// Fiscal year ID.
     pub fiscal_year_id: Uuid,
     /// Budget type: annual, quarterly, monthly, project.
+    #[serde(deserialize_with = "deserialize_budget_type")]
     pub budget_type: String,
 }
 
 /// Request body for updating a budget.
 #[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct UpdateBudgetRequest {
     /// Budget name.
     pub name: Option<String>,
@@ -83,15 +106,36 @@ pub struct UpdateBudgetRequest {
     pub is_active: Option<bool>,
 }
 
+/// Request body for reassigning a budget's owner and editors.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct AssignBudgetRequest {
+    /// New owner; `null` leaves the budget without one.
+    #[serde(default, deserialize_with = "present")]
+    pub owner_user_id: Option<Option<Uuid>>,
+    /// New editors, replacing the current ones.
+    pub editors: Option<Vec<Uuid>>,
+}
+
 /// Request body for creating budget lines in bulk.
 #[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct CreateBudgetLinesRequest {
     /// Budget lines to create.
     pub lines: Vec<BudgetLineInput>,
 }
 
+/// Request body for replacing all lines of a budget.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ReplaceBudgetLinesRequest {
+    /// The complete desired set of budget lines.
+    pub lines: Vec<BudgetLineInput>,
+}
+
 /// Input for a single budget line.
 #[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct BudgetLineInput {
     /// Account ID.
     pub account_id: Uuid,
@@ -128,6 +172,12 @@ pub struct BudgetResponse {
     pub is_locked: bool,
     /// Total budgeted amount.
     pub total_budgeted: String,
+    /// Owner, `None` when only Finance edits the budget.
+    pub owner_user_id: Option<Uuid>,
+    /// Members listed as editors besides the owner.
+    pub editors: Vec<Uuid>,
+    /// Whether the requesting user may edit the budget.
+    pub can_edit: bool,
     /// Created at timestamp.
     pub created_at: String,
     /// Updated at timestamp.
@@ -138,43 +188,66 @@ pub struct BudgetResponse {
 // Helper Functions
 // ============================================================================
 
-/// Checks if user is a member of the organization.
-async fn check_membership(
-    org_repo: &OrganizationRepository,
+/// Checks that the user may read budgets in the organization, returning
+/// their role.
+pub(crate) async fn check_budget_access(
+    state: &AppState,
+    org_id: Uuid,
+    auth: &AuthMember,
+) -> Result<CoreUserRole, axum::response::Response> {
+    let role = auth.role_in(state, org_id).await?;
+    if role.can_view_budgets() {
+        return Ok(role);
+    }
+
+    Err((
+        StatusCode::FORBIDDEN,
+        Json(json!({
+            "error": "budget_access_restricted",
+            "message": "Accountant role or above required to view budgets"
+        })),
+    )
+        .into_response())
+}
+
+/// Checks that the user may reassign budgets in the organization.
+async fn check_budget_assignment(
+    state: &AppState,
     org_id: Uuid,
-    user_id: Uuid,
+    auth: &AuthMember,
 ) -> Result<(), axum::response::Response> {
-    match org_repo.is_member(org_id, user_id).await {
-        Ok(true) => Ok(()),
-        Ok(false) => Err((
-            StatusCode::FORBIDDEN,
-            Json(json!({
-                "error": "forbidden",
-                "message": "You are not a member of this organization"
-            })),
-        )
-            .into_response()),
-        Err(e) => {
-            error!(error = %e, "Failed to check membership");
-            Err((
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(json!({
-                    "error": "internal_error",
-                    "message": "An error occurred"
-                })),
-            )
-                .into_response())
-        }
+    let role = auth.role_in(state, org_id).await?;
+    if role.can_edit_any_budget() {
+        return Ok(());
     }
+
+    Err((
+        StatusCode::FORBIDDEN,
+        Json(json!({
+            "error": "forbidden",
+            "message": "Accountant role or above required to reassign budgets"
+        })),
+    )
+        .into_response())
 }
 
 /// Checks if user has admin or owner role.
 async fn check_admin_role(
     org_repo: &OrganizationRepository,
     org_id: Uuid,
-    user_id: Uuid,
+    auth: &AuthMember,
 ) -> Result<(), axum::response::Response> {
-    match org_repo.has_role(org_id, user_id, UserRole::Admin).await {
+    let allowed = match auth.has_role(org_id, &UserRole::Admin) {
+        Some(allowed) => Ok(allowed),
+        // Token was issued for another organization
+        None => {
+            org_repo
+                .has_role(org_id, auth.user_id(), UserRole::Admin)
+                .await
+        }
+    };
+
+    match allowed {
         Ok(true) => Ok(()),
         Ok(false) => Err((
             StatusCode::FORBIDDEN,
@@ -198,6 +271,68 @@ async fn check_admin_role(
     }
 }
 
+/// Keeps line amounts to the budget currency's decimal places.
+///
+/// More precise amounts are rounded, or rejected when the organization has
+/// `amount_precision.strict` set.
+async fn apply_amount_precision(
+    state: &AppState,
+    org_repo: &OrganizationRepository,
+    budget_repo: &BudgetRepository,
+    (org_id, budget_id): (Uuid, Uuid),
+    lines: &mut [BudgetLineInput],
+) -> Result<(), axum::response::Response> {
+    let budget = budget_repo
+        .get_budget(org_id, budget_id)
+        .await
+        .map_err(|e| map_budget_error(&e))?;
+    let settings = org_repo.get_settings(org_id).await.map_err(|e| {
+        error!(error = %e, "Failed to load organization settings");
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({
+                "error": "internal_error",
+                "message": "An error occurred"
+            })),
+        )
+            .into_response()
+    })?;
+
+    for line in lines {
+        match state
+            .currencies
+            .check_precision(&budget.currency, line.amount)
+        {
+            Ok(()) => {}
+            Err(e) if settings.amount_precision.strict => {
+                return Err(amount_too_precise_response(&e));
+            }
+            Err(_) => line.amount = state.currencies.round_for(&budget.currency, line.amount),
+        }
+    }
+    Ok(())
+}
+
+/// Deserializes a field that is present, so an explicit `null` becomes
+/// `Some(None)` while an absent field stays `None`.
+fn present<'de, D, T>(deserializer: D) -> Result<Option<T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    T::deserialize(deserializer).map(Some)
+}
+
+/// Accepts the budget types [`parse_budget_type`] knows.
+fn deserialize_budget_type<'de, D: Deserializer<'de>>(deserializer: D) -> Result<String, D::Error> {
+    use zeltra_db::entities::sea_orm_active_enums::BudgetType;
+    enum_string(deserializer, parse_budget_type, || {
+        BudgetType::iter()
+            .map(|t| budget_type_to_string(&t))
+            .collect()
+    })
+}
+
 /// Converts budget type string to enum value.
 fn parse_budget_type(s: &str) -> Option<zeltra_db::entities::sea_orm_active_enums::BudgetType> {
     use zeltra_db::entities::sea_orm_active_enums::BudgetType;
@@ -230,15 +365,14 @@ fn budget_type_to_string(bt: &zeltra_db::entities::sea_orm_active_enums::BudgetT
 /// Requirements: 13.2
 async fn list_budgets(
     State(state): State<AppState>,
-    auth: AuthUser,
+    auth: AuthMember,
     Path(org_id): Path<Uuid>,
 ) -> impl IntoResponse {
-    let org_repo = OrganizationRepository::new((*state.db).clone());
-
-    // Check membership
-    if let Err(response) = check_membership(&org_repo, org_id, auth.user_id()).await {
-        return response;
-    }
+    // Check budget read access
+    let role = match check_budget_access(&state, org_id, &auth).await {
+        Ok(role) => role,
+        Err(response) => return response,
+    };
 
     let budget_repo = BudgetRepository::new((*state.db).clone());
 
@@ -247,6 +381,9 @@ async fn list_budgets(
             let response: Vec<BudgetResponse> = budgets
                 .into_iter()
                 .map(|b| BudgetResponse {
+                    can_edit: b.can_edit(auth.user_id(), role),
+                    owner_user_id: b.budget.owner_user_id,
+                    editors: b.editors,
                     id: b.budget.id,
                     name: b.budget.name,
                     description: b.budget.description,
@@ -283,14 +420,14 @@ async fn list_budgets(
 /// Requirements: 13.1
 async fn create_budget(
     State(state): State<AppState>,
-    auth: AuthUser,
+    auth: AuthMember,
     Path(org_id): Path<Uuid>,
-    Json(payload): Json<CreateBudgetRequest>,
+    StrictJson(payload): StrictJson<CreateBudgetRequest>,
 ) -> impl IntoResponse {
     let org_repo = OrganizationRepository::new((*state.db).clone());
 
     // Check admin/owner role
-    if let Err(response) = check_admin_role(&org_repo, org_id, auth.user_id()).await {
+    if let Err(response) = check_admin_role(&org_repo, org_id, &auth).await {
         return response;
     }
 
@@ -346,12 +483,7 @@ async fn create_budget(
 
     match budget_repo.create_budget(input).await {
         Ok(budget) => {
-            info!(
-                org_id = %org_id,
-                budget_id = %budget.id,
-                name = %budget.name,
-                "Budget created"
-            );
+            info!(budget_id = %budget.id, name = %budget.name, "Budget created");
 
             (
                 StatusCode::CREATED,
@@ -364,6 +496,7 @@ async fn create_budget(
                     "currency": budget.currency,
                     "is_active": budget.is_active,
                     "is_locked": budget.is_locked,
+                    "owner_user_id": budget.owner_user_id,
                     "created_at": budget.created_at,
                     "updated_at": budget.updated_at
                 })),
@@ -382,13 +515,11 @@ async fn create_budget(
 /// Requirements: 13.3
 async fn get_budget(
     State(state): State<AppState>,
-    auth: AuthUser,
+    auth: AuthMember,
     Path((org_id, budget_id)): Path<(Uuid, Uuid)>,
 ) -> impl IntoResponse {
-    let org_repo = OrganizationRepository::new((*state.db).clone());
-
-    // Check membership
-    if let Err(response) = check_membership(&org_repo, org_id, auth.user_id()).await {
+    // Check budget read access
+    if let Err(response) = check_budget_access(&state, org_id, &auth).await {
         return response;
     }
 
@@ -426,7 +557,7 @@ async fn get_budget(
                 })
                 .collect();
 
-            (
+            let response = (
                 StatusCode::OK,
                 Json(json!({
                     "id": budget.id,
@@ -437,12 +568,14 @@ async fn get_budget(
                     "currency": budget.currency,
                     "is_active": budget.is_active,
                     "is_locked": budget.is_locked,
+                    "owner_user_id": budget.owner_user_id,
                     "created_at": budget.created_at,
                     "updated_at": budget.updated_at,
                     "lines": line_responses
                 })),
             )
-                .into_response()
+                .into_response();
+            with_last_modified(response, &budget.updated_at)
         }
         Err(BudgetError::NotFound(_)) => (
             StatusCode::NOT_FOUND,
@@ -469,14 +602,12 @@ async fn get_budget(
 /// PUT `/organizations/{org_id}/budgets/{budget_id}` - Update budget.
 async fn update_budget(
     State(state): State<AppState>,
-    auth: AuthUser,
+    auth: AuthMember,
     Path((org_id, budget_id)): Path<(Uuid, Uuid)>,
-    Json(payload): Json<UpdateBudgetRequest>,
+    StrictJson(payload): StrictJson<UpdateBudgetRequest>,
 ) -> impl IntoResponse {
-    let org_repo = OrganizationRepository::new((*state.db).clone());
-
-    // Check admin/owner role
-    if let Err(response) = check_admin_role(&org_repo, org_id, auth.user_id()).await {
+    // The repository checks the user may edit this budget
+    if let Err(response) = auth.role_in(&state, org_id).await {
         return response;
     }
 
@@ -488,13 +619,12 @@ async fn update_budget(
         is_active: payload.is_active,
     };
 
-    match budget_repo.update_budget(org_id, budget_id, input).await {
+    match budget_repo
+        .update_budget(org_id, budget_id, auth.user_id(), input)
+        .await
+    {
         Ok(budget) => {
-            info!(
-                org_id = %org_id,
-                budget_id = %budget_id,
-                "Budget updated"
-            );
+            info!(budget_id = %budget_id, "Budget updated");
 
             (
                 StatusCode::OK,
@@ -507,6 +637,7 @@ async fn update_budget(
                     "currency": budget.currency,
                     "is_active": budget.is_active,
                     "is_locked": budget.is_locked,
+                    "owner_user_id": budget.owner_user_id,
                     "updated_at": budget.updated_at
                 })),
             )
@@ -519,18 +650,61 @@ async fn update_budget(
     }
 }
 
+/// PATCH `/organizations/{org_id}/budgets/{budget_id}` - Reassign owner and editors.
+async fn assign_budget(
+    State(state): State<AppState>,
+    auth: AuthMember,
+    Path((org_id, budget_id)): Path<(Uuid, Uuid)>,
+    StrictJson(payload): StrictJson<AssignBudgetRequest>,
+) -> impl IntoResponse {
+    if let Err(response) = check_budget_assignment(&state, org_id, &auth).await {
+        return response;
+    }
+
+    let budget_repo = BudgetRepository::new((*state.db).clone());
+
+    let input = AssignBudgetInput {
+        owner_user_id: payload.owner_user_id,
+        editors: payload.editors,
+    };
+
+    match budget_repo.assign_budget(org_id, budget_id, input).await {
+        Ok(assignment) => {
+            info!(
+                budget_id = %budget_id,
+                owner_user_id = ?assignment.budget.owner_user_id,
+                editors = assignment.editors.len(),
+                "Budget reassigned"
+            );
+
+            (
+                StatusCode::OK,
+                Json(json!({
+                    "id": assignment.budget.id,
+                    "owner_user_id": assignment.budget.owner_user_id,
+                    "editors": assignment.editors,
+                    "updated_at": assignment.budget.updated_at
+                })),
+            )
+                .into_response()
+        }
+        Err(e) => {
+            error!(error = %e, "Failed to reassign budget");
+            map_budget_error(&e)
+        }
+    }
+}
+
 /// GET `/organizations/{org_id}/budgets/{budget_id}/lines` - Get budget lines.
 ///
 /// Requirements: 13.5
 async fn list_budget_lines(
     State(state): State<AppState>,
-    auth: AuthUser,
+    auth: AuthMember,
     Path((org_id, budget_id)): Path<(Uuid, Uuid)>,
 ) -> impl IntoResponse {
-    let org_repo = OrganizationRepository::new((*state.db).clone());
-
-    // Check membership
-    if let Err(response) = check_membership(&org_repo, org_id, auth.user_id()).await {
+    // Check budget read access
+    if let Err(response) = check_budget_access(&state, org_id, &auth).await {
         return response;
     }
 
@@ -596,22 +770,33 @@ async fn list_budget_lines(
 /// Requirements: 13.4
 async fn create_budget_lines(
     State(state): State<AppState>,
-    auth: AuthUser,
+    auth: AuthMember,
     Path((org_id, budget_id)): Path<(Uuid, Uuid)>,
-    Json(payload): Json<CreateBudgetLinesRequest>,
+    StrictJson(payload): StrictJson<CreateBudgetLinesRequest>,
 ) -> impl IntoResponse {
     let org_repo = OrganizationRepository::new((*state.db).clone());
 
-    // Check admin/owner role
-    if let Err(response) = check_admin_role(&org_repo, org_id, auth.user_id()).await {
+    // The repository checks the user may edit this budget
+    if let Err(response) = auth.role_in(&state, org_id).await {
         return response;
     }
 
     let budget_repo = BudgetRepository::new((*state.db).clone());
+    let mut lines = payload.lines;
+    if let Err(response) = apply_amount_precision(
+        &state,
+        &org_repo,
+        &budget_repo,
+        (org_id, budget_id),
+        &mut lines,
+    )
+    .await
+    {
+        return response;
+    }
 
     // Convert input
-    let inputs: Vec<CreateBudgetLineInput> = payload
-        .lines
+    let inputs: Vec<CreateBudgetLineInput> = lines
         .into_iter()
         .map(|l| CreateBudgetLineInput {
             account_id: l.account_id,
@@ -623,12 +808,11 @@ async fn create_budget_lines(
         .collect();
 
     match budget_repo
-        .create_budget_lines(org_id, budget_id, inputs)
+        .create_budget_lines(org_id, budget_id, auth.user_id(), inputs)
         .await
     {
         Ok(lines) => {
             info!(
-                org_id = %org_id,
                 budget_id = %budget_id,
                 count = lines.len(),
                 "Budget lines created"
@@ -657,18 +841,95 @@ async fn create_budget_lines(
     }
 }
 
+/// PUT `/organizations/{org_id}/budgets/{budget_id}/lines` - Replace all budget lines.
+///
+/// Lines are matched by account and period: missing ones are created,
+/// changed ones updated and absent ones deleted, all in one transaction.
+async fn replace_budget_lines(
+    State(state): State<AppState>,
+    auth: AuthMember,
+    Path((org_id, budget_id)): Path<(Uuid, Uuid)>,
+    StrictJson(payload): StrictJson<ReplaceBudgetLinesRequest>,
+) -> impl IntoResponse {
+    let org_repo = OrganizationRepository::new((*state.db).clone());
+
+    // The repository checks the user may edit this budget
+    if let Err(response) = auth.role_in(&state, org_id).await {
+        return response;
+    }
+
+    if payload.lines.len() > MAX_REPLACE_LINES {
+        return map_budget_error(&BudgetError::TooManyLines(payload.lines.len()));
+    }
+
+    let budget_repo = BudgetRepository::new((*state.db).clone());
+    let mut lines = payload.lines;
+    if let Err(response) = apply_amount_precision(
+        &state,
+        &org_repo,
+        &budget_repo,
+        (org_id, budget_id),
+        &mut lines,
+    )
+    .await
+    {
+        return response;
+    }
+
+    let inputs: Vec<CreateBudgetLineInput> = lines
+        .into_iter()
+        .map(|l| CreateBudgetLineInput {
+            account_id: l.account_id,
+            fiscal_period_id: l.fiscal_period_id,
+            amount: l.amount,
+            notes: l.notes,
+            dimensions: l.dimensions.unwrap_or_default(),
+        })
+        .collect();
+
+    match budget_repo
+        .replace_lines(org_id, budget_id, auth.user_id(), inputs)
+        .await
+    {
+        Ok(diff) => {
+            info!(
+                budget_id = %budget_id,
+                created = diff.created,
+                updated = diff.updated,
+                deleted = diff.deleted,
+                "Budget lines replaced"
+            );
+
+            (
+                StatusCode::OK,
+                Json(json!({
+                    "created": diff.created,
+                    "updated": diff.updated,
+                    "deleted": diff.deleted,
+                    "unchanged": diff.unchanged
+                })),
+            )
+                .into_response()
+        }
+        Err(e) => {
+            error!(error = %e, "Failed to replace budget lines");
+            map_budget_error(&e)
+        }
+    }
+}
+
 /// POST `/organizations/{org_id}/budgets/{budget_id}/lock` - Lock budget.
 ///
 /// Requirements: 13.6
 async fn lock_budget(
     State(state): State<AppState>,
-    auth: AuthUser,
+    auth: AuthMember,
     Path((org_id, budget_id)): Path<(Uuid, Uuid)>,
 ) -> impl IntoResponse {
     let org_repo = OrganizationRepository::new((*state.db).clone());
 
     // Check admin/owner role
-    if let Err(response) = check_admin_role(&org_repo, org_id, auth.user_id()).await {
+    if let Err(response) = check_admin_role(&org_repo, org_id, &auth).await {
         return response;
     }
 
@@ -676,11 +937,7 @@ async fn lock_budget(
 
     match budget_repo.lock_budget(org_id, budget_id).await {
         Ok(budget) => {
-            info!(
-                org_id = %org_id,
-                budget_id = %budget_id,
-                "Budget locked"
-            );
+            info!(budget_id = %budget_id, "Budget locked");
 
             (
                 StatusCode::OK,
@@ -707,24 +964,47 @@ pub struct BudgetVsActualQuery {
     pub fiscal_period_id: Option<Uuid>,
     /// Filter by dimension value IDs (comma-separated).
     pub dimensions: Option<String>,
+    /// Side converted when the budget is not in the functional currency.
+    #[serde(default)]
+    pub convert: ConvertSide,
+    /// Only return lines whose variance exceeds this percentage of the
+    /// budgeted amount and that have no comment.
+    pub uncommented_over_percent: Option<Decimal>,
 }
 
 /// GET `/organizations/{org_id}/budgets/{budget_id}/vs-actual` - Get budget vs actual comparison.
 ///
+/// Each line carries its latest comment. With `uncommented_over_percent`,
+/// only significant variances nobody has explained yet are listed; the
+/// summary still covers every line.
+///
 /// Requirements: 13.7
+#[allow(clippy::too_many_lines)]
 async fn get_budget_vs_actual(
     State(state): State<AppState>,
-    auth: AuthUser,
+    auth: AuthMember,
     Path((org_id, budget_id)): Path<(Uuid, Uuid)>,
     axum::extract::Query(query): axum::extract::Query<BudgetVsActualQuery>,
 ) -> impl IntoResponse {
-    let org_repo = OrganizationRepository::new((*state.db).clone());
-
-    // Check membership
-    if let Err(response) = check_membership(&org_repo, org_id, auth.user_id()).await {
+    // Check budget read access
+    if let Err(response) = check_budget_access(&state, org_id, &auth).await {
         return response;
     }
 
+    if query
+        .uncommented_over_percent
+        .is_some_and(|threshold| threshold.is_sign_negative())
+    {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(json!({
+                "error": "validation_error",
+                "message": "uncommented_over_percent must not be negative"
+            })),
+        )
+            .into_response();
+    }
+
     let budget_repo = BudgetRepository::new((*state.db).clone());
 
     // Parse dimension filters
@@ -744,13 +1024,33 @@ async fn get_budget_vs_actual(
             budget_id,
             query.fiscal_period_id,
             &dimension_filters,
+            query.convert,
         )
         .await
     {
         Ok((lines, summary)) => {
+            let line_ids: Vec<Uuid> = lines.iter().map(|l| l.line.id).collect();
+            let comments = match BudgetCommentRepository::new((*state.db).clone())
+                .latest_for_lines(org_id, &line_ids)
+                .await
+            {
+                Ok(comments) => comments,
+                Err(e) => {
+                    error!(error = %e, "Failed to load budget comments");
+                    return map_budget_error(&e.into());
+                }
+            };
+
             let line_responses: Vec<serde_json::Value> = lines
                 .into_iter()
+                .filter(|l| {
+                    query.uncommented_over_percent.is_none_or(|threshold| {
+                        !comments.contains_key(&l.line.id)
+                            && variance_exceeds(l.budgeted, l.variance, threshold)
+                    })
+                })
                 .map(|l| {
+                    let comment = comments.get(&l.line.id);
                     json!({
                         "id": l.line.id,
                         "account_id": l.line.account_id,
@@ -758,16 +1058,25 @@ async fn get_budget_vs_actual(
                         "account_name": l.account_name,
                         "fiscal_period_id": l.line.fiscal_period_id,
                         "period_name": l.period_name,
-                        "budgeted": l.line.amount.to_string(),
+                        "budgeted": l.budgeted.to_string(),
                         "actual": l.actual.to_string(),
-                        "variance": l.variance.to_string(),
-                        "utilization_percent": l.utilization_percent.to_string(),
+                        "variance": l.variance.map(|v| v.to_string()),
+                        "utilization_percent": l.utilization_percent.map(|u| u.to_string()),
                         "status": l.status,
+                        "exchange_rate": l.rate.map(|r| json!({
+                            "from_currency": r.from_currency,
+                            "to_currency": r.to_currency,
+                            "rate": r.rate.to_string(),
+                            "effective_date": r.effective_date,
+                            "rate_date": r.rate_date
+                        })),
+                        "warning": l.warning,
                         "dimensions": l.dimensions.iter().map(|d| json!({
                             "dimension_type": d.dimension_type,
                             "code": d.code,
                             "name": d.name
-                        })).collect::<Vec<_>>()
+                        })).collect::<Vec<_>>(),
+                        "comment": comment.map(budget_comments::comment_to_response)
                     })
                 })
                 .collect();
@@ -776,8 +1085,10 @@ async fn get_budget_vs_actual(
                 StatusCode::OK,
                 Json(json!({
                     "budget_id": budget_id,
+                    "currency": summary.currency,
                     "lines": line_responses,
                     "summary": {
+                        "unconverted_lines": summary.unconverted_lines,
                         "total_budgeted": summary.total_budgeted.to_string(),
                         "total_actual": summary.total_actual.to_string(),
                         "total_variance": summary.total_variance.to_string(),
@@ -794,11 +1105,104 @@ async fn get_budget_vs_actual(
     }
 }
 
+/// Query parameters for the budget trend.
+#[derive(Debug, serde::Deserialize)]
+pub struct BudgetTrendQuery {
+    /// Filter by account ID.
+    pub account_id: Option<Uuid>,
+    /// Filter by dimension value IDs (comma-separated).
+    pub dimensions: Option<String>,
+    /// Side converted when the budget is not in the functional currency.
+    #[serde(default)]
+    pub convert: ConvertSide,
+}
+
+/// GET `/organizations/{org_id}/budgets/{budget_id}/trend` - Get budget vs actual per period
+/// with running totals.
+async fn get_budget_trend(
+    State(state): State<AppState>,
+    auth: AuthMember,
+    Path((org_id, budget_id)): Path<(Uuid, Uuid)>,
+    axum::extract::Query(query): axum::extract::Query<BudgetTrendQuery>,
+) -> impl IntoResponse {
+    // Check budget read access
+    if let Err(response) = check_budget_access(&state, org_id, &auth).await {
+        return response;
+    }
+
+    let budget_repo = BudgetRepository::new((*state.db).clone());
+
+    // Parse dimension filters
+    let dimension_filters: Vec<Uuid> = query
+        .dimensions
+        .as_ref()
+        .map(|s| {
+            s.split(',')
+                .filter_map(|id| Uuid::parse_str(id.trim()).ok())
+                .collect()
+        })
+        .unwrap_or_default();
+
+    match budget_repo
+        .get_budget_trend(
+            org_id,
+            budget_id,
+            query.account_id,
+            &dimension_filters,
+            query.convert,
+        )
+        .await
+    {
+        Ok(trend) => {
+            let periods: Vec<serde_json::Value> = trend
+                .periods
+                .into_iter()
+                .map(|p| {
+                    json!({
+                        "fiscal_period_id": p.fiscal_period_id,
+                        "period_name": p.period_name,
+                        "start_date": p.start_date,
+                        "end_date": p.end_date,
+                        "budgeted": p.point.budgeted.to_string(),
+                        "actual": p.point.actual.to_string(),
+                        "cumulative_budgeted": p.point.cumulative_budgeted.to_string(),
+                        "cumulative_actual": p.point.cumulative_actual.to_string(),
+                        "cumulative_variance": p.point.cumulative_variance.to_string(),
+                        "exchange_rate": p.rate.map(|r| json!({
+                            "from_currency": r.from_currency,
+                            "to_currency": r.to_currency,
+                            "rate": r.rate.to_string(),
+                            "effective_date": r.effective_date,
+                            "rate_date": r.rate_date
+                        })),
+                        "warning": p.warning
+                    })
+                })
+                .collect();
+
+            (
+                StatusCode::OK,
+                Json(json!({
+                    "budget_id": budget_id,
+                    "currency": trend.currency,
+                    "periods": periods
+                })),
+            )
+                .into_response()
+        }
+        Err(e) => {
+            error!(error = %e, "Failed to get budget trend");
+            map_budget_error(&e)
+        }
+    }
+}
+
 // ============================================================================
 // Error Mapping
 // ============================================================================
 
 /// Maps budget errors to HTTP responses.
+#[allow(clippy::too_many_lines)]
 fn map_budget_error(e: &BudgetError) -> axum::response::Response {
     match e {
         BudgetError::NotFound(id) => (
@@ -889,6 +1293,43 @@ fn map_budget_error(e: &BudgetError) -> axum::response::Response {
             })),
         )
             .into_response(),
+        BudgetError::TooManyLines(_) => (
+            StatusCode::BAD_REQUEST,
+            Json(json!({
+                "error": "too_many_lines",
+                "message": format!("At most {MAX_REPLACE_LINES} budget lines can be sent at once")
+            })),
+        )
+            .into_response(),
+        BudgetError::InvalidLines(errors) => (
+            StatusCode::UNPROCESSABLE_ENTITY,
+            Json(json!({
+                "error": "invalid_lines",
+                "message": "One or more budget lines are invalid; no changes were made",
+                "errors": errors
+                    .iter()
+                    .map(|e| json!({ "index": e.index, "message": e.message }))
+                    .collect::<Vec<_>>()
+            })),
+        )
+            .into_response(),
+        BudgetError::TierLimitExceeded(limit) => tier_limit_response(limit),
+        BudgetError::NotBudgetEditor => (
+            StatusCode::FORBIDDEN,
+            Json(json!({
+                "error": "not_budget_editor",
+                "message": "Only the budget's owner, its editors or an accountant can edit it"
+            })),
+        )
+            .into_response(),
+        BudgetError::NotAMember(id) => (
+            StatusCode::BAD_REQUEST,
+            Json(json!({
+                "error": "not_a_member",
+                "message": format!("User is not a member of this organization: {}", id)
+            })),
+        )
+            .into_response(),
         BudgetError::Database(_) => (
             StatusCode::INTERNAL_SERVER_ERROR,
             Json(json!({
@@ -4,8 +4,8 @@
 
 use axum::{
     Json, Router,
-    extract::{Path, Query, State},
-    http::StatusCode,
+    extract::{Path, Query, RawQuery, State},
+    http::{HeaderMap, StatusCode},
     response::IntoResponse,
     routing::get,
 };
@@ -15,7 +15,11 @@ use serde_json::json;
 use tracing::error;
 use uuid::Uuid;
 
-use crate::{AppState, middleware::AuthUser};
+use crate::{
+    AppState,
+    middleware::{AuthMember, auth::check_report_access, query_etag, respond_cached},
+    routes::reports::{FormattingMetadata, formatting_metadata},
+};
 use zeltra_db::{OrganizationRepository, repositories::dashboard::DashboardRepository};
 
 /// Creates the dashboard routes (requires auth middleware to be applied externally).
@@ -71,6 +75,8 @@ pub struct DashboardMetricsResponse {
     pub runway_days: i32,
     /// Pending approvals.
     pub pending_approvals: PendingApprovalsResponse,
+    /// Presentation hints for amounts and dates.
+    pub formatting: FormattingMetadata,
 }
 
 /// Period info.
@@ -172,36 +178,6 @@ pub struct PaginationInfo {
 // Helper Functions
 // ============================================================================
 
-/// Checks if user is a member of the organization.
-async fn check_membership(
-    org_repo: &OrganizationRepository,
-    org_id: Uuid,
-    user_id: Uuid,
-) -> Result<(), axum::response::Response> {
-    match org_repo.is_member(org_id, user_id).await {
-        Ok(true) => Ok(()),
-        Ok(false) => Err((
-            StatusCode::FORBIDDEN,
-            Json(json!({
-                "error": "forbidden",
-                "message": "You are not a member of this organization"
-            })),
-        )
-            .into_response()),
-        Err(e) => {
-            error!(error = %e, "Failed to check membership");
-            Err((
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(json!({
-                    "error": "internal_error",
-                    "message": "An error occurred"
-                })),
-            )
-                .into_response())
-        }
-    }
-}
-
 /// Formats a Decimal as a string with 4 decimal places.
 fn format_money(amount: Decimal) -> String {
     format!("{amount:.4}")
@@ -214,100 +190,156 @@ fn format_money(amount: Decimal) -> String {
 /// GET /organizations/{org_id}/dashboard/metrics
 ///
 /// Requirement 16.1: Dashboard metrics endpoint
+///
+/// Sections are served from the per-organization [`DashboardCache`], which
+/// the transaction workflow handlers invalidate.
+///
+/// [`DashboardCache`]: crate::cache::DashboardCache
+#[allow(clippy::too_many_lines)]
 #[axum::debug_handler]
 async fn get_dashboard_metrics(
     State(state): State<AppState>,
     Path(org_id): Path<Uuid>,
     Query(query): Query<DashboardMetricsQuery>,
-    auth_user: AuthUser,
+    auth: AuthMember,
+    RawQuery(raw_query): RawQuery,
+    headers: HeaderMap,
 ) -> impl IntoResponse {
     let org_repo = OrganizationRepository::new((*state.db).clone());
 
-    // Check membership
-    if let Err(response) = check_membership(&org_repo, org_id, auth_user.user_id()).await {
+    if let Err(response) = check_report_access(&state, org_id, &auth).await {
         return response;
     }
 
-    let dashboard_repo = DashboardRepository::new((*state.db).clone());
-    let today = chrono::Utc::now().date_naive();
-
-    // Query cash position
-    let cash_position = match dashboard_repo.query_cash_position(org_id, today).await {
-        Ok(cp) => cp,
-        Err(e) => {
-            error!(error = %e, "Failed to query cash position");
-            return (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(json!({
-                    "error": "internal_error",
-                    "message": "Failed to get dashboard metrics"
-                })),
-            )
-                .into_response();
-        }
-    };
-
-    // Query pending approvals
-    let pending_approvals = match dashboard_repo.query_pending_approvals(org_id).await {
-        Ok(pa) => pa,
-        Err(e) => {
-            error!(error = %e, "Failed to query pending approvals");
-            return (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(json!({
-                    "error": "internal_error",
-                    "message": "Failed to get dashboard metrics"
-                })),
-            )
-                .into_response();
-        }
-    };
-
-    // Calculate burn rate (simplified: monthly expenses / 30)
-    // In a real implementation, this would query actual expense data
-    let monthly_burn = Decimal::ZERO; // TODO: Calculate from actual expenses
-    let daily_burn = monthly_burn / Decimal::from(30);
-
-    // Calculate runway (cash / daily burn)
-    let runway_days = if daily_burn.is_zero() {
-        999 // Infinite runway if no burn
-    } else {
-        (cash_position.balance / daily_burn)
-            .to_string()
-            .parse::<i32>()
-            .unwrap_or(999)
+    let etag = match query_etag(&state.db, org_id, "dashboard_metrics", raw_query.as_deref()).await
+    {
+        Ok(etag) => etag,
+        Err(response) => return response,
     };
 
-    // Get period info if provided
-    let period_info = query.period_id.map(|id| PeriodInfo {
-        id,
-        name: "Current Period".to_string(), // TODO: Query actual period name
-    });
-
-    let response = DashboardMetricsResponse {
-        period: period_info,
-        cash_position: CashPositionResponse {
-            balance: format_money(cash_position.balance),
-            currency: cash_position.currency,
-            change_from_last_period: format_money(cash_position.change_from_last_period),
-            change_percent: cash_position
-                .change_percent
+    respond_cached(&headers, &etag, move || async move {
+        let org = match org_repo.find_by_id(org_id).await {
+            Ok(Some(org)) => org,
+            Ok(None) => {
+                return (
+                    StatusCode::NOT_FOUND,
+                    Json(json!({
+                        "error": "not_found",
+                        "message": "Organization not found"
+                    })),
+                )
+                    .into_response();
+            }
+            Err(e) => {
+                error!(error = %e, "Failed to get organization");
+                return (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    Json(json!({
+                        "error": "internal_error",
+                        "message": "An error occurred"
+                    })),
+                )
+                    .into_response();
+            }
+        };
+        let formatting = match formatting_metadata(&state.db, &org).await {
+            Ok(formatting) => formatting,
+            Err(response) => return response,
+        };
+
+        let dashboard_repo = DashboardRepository::new((*state.db).clone());
+        let today = chrono::Utc::now().date_naive();
+
+        // Query cash position
+        let cash_position = match state
+            .dashboard_cache
+            .cash_position(org_id, today, || {
+                dashboard_repo.query_cash_position(org_id, today)
+            })
+            .await
+        {
+            Ok(cp) => cp,
+            Err(e) => {
+                error!(error = %e, "Failed to query cash position");
+                return (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    Json(json!({
+                        "error": "internal_error",
+                        "message": "Failed to get dashboard metrics"
+                    })),
+                )
+                    .into_response();
+            }
+        };
+
+        // Query pending approvals
+        let pending_approvals = match state
+            .dashboard_cache
+            .pending_approvals(org_id, || dashboard_repo.query_pending_approvals(org_id))
+            .await
+        {
+            Ok(pa) => pa,
+            Err(e) => {
+                error!(error = %e, "Failed to query pending approvals");
+                return (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    Json(json!({
+                        "error": "internal_error",
+                        "message": "Failed to get dashboard metrics"
+                    })),
+                )
+                    .into_response();
+            }
+        };
+
+        // Calculate burn rate (simplified: monthly expenses / 30)
+        // In a real implementation, this would query actual expense data
+        let monthly_burn = Decimal::ZERO; // TODO: Calculate from actual expenses
+        let daily_burn = monthly_burn / Decimal::from(30);
+
+        // Calculate runway (cash / daily burn)
+        let runway_days = if daily_burn.is_zero() {
+            999 // Infinite runway if no burn
+        } else {
+            (cash_position.balance / daily_burn)
                 .to_string()
-                .parse::<f64>()
-                .unwrap_or(0.0),
-        },
-        burn_rate: BurnRateResponse {
-            daily: format_money(daily_burn),
-            monthly: format_money(monthly_burn),
-        },
-        runway_days,
-        pending_approvals: PendingApprovalsResponse {
-            count: pending_approvals.count,
-            total_amount: format_money(pending_approvals.total_amount),
-        },
-    };
-
-    (StatusCode::OK, Json(response)).into_response()
+                .parse::<i32>()
+                .unwrap_or(999)
+        };
+
+        // Get period info if provided
+        let period_info = query.period_id.map(|id| PeriodInfo {
+            id,
+            name: "Current Period".to_string(), // TODO: Query actual period name
+        });
+
+        let response = DashboardMetricsResponse {
+            period: period_info,
+            cash_position: CashPositionResponse {
+                balance: format_money(cash_position.balance),
+                currency: cash_position.currency,
+                change_from_last_period: format_money(cash_position.change_from_last_period),
+                change_percent: cash_position
+                    .change_percent
+                    .to_string()
+                    .parse::<f64>()
+                    .unwrap_or(0.0),
+            },
+            burn_rate: BurnRateResponse {
+                daily: format_money(daily_burn),
+                monthly: format_money(monthly_burn),
+            },
+            runway_days,
+            pending_approvals: PendingApprovalsResponse {
+                count: pending_approvals.count,
+                total_amount: format_money(pending_approvals.total_amount),
+            },
+            formatting,
+        };
+
+        (StatusCode::OK, Json(response)).into_response()
+    })
+    .await
 }
 
 /// GET /organizations/{org_id}/dashboard/recent-activity
@@ -318,12 +350,9 @@ async fn get_recent_activity(
     State(state): State<AppState>,
     Path(org_id): Path<Uuid>,
     Query(query): Query<RecentActivityQuery>,
-    auth_user: AuthUser,
+    auth: AuthMember,
 ) -> impl IntoResponse {
-    let org_repo = OrganizationRepository::new((*state.db).clone());
-
-    // Check membership
-    if let Err(response) = check_membership(&org_repo, org_id, auth_user.user_id()).await {
+    if let Err(response) = check_report_access(&state, org_id, &auth).await {
         return response;
     }
 
@@ -391,3 +420,155 @@ async fn get_recent_activity(
 
     (StatusCode::OK, Json(response)).into_response()
 }
+
+#[cfg(test)]
+mod integration_tests {
+    use super::*;
+    use chrono::NaiveDate;
+    use sea_orm::{ActiveModelTrait, Set};
+    use zeltra_db::entities::chart_of_accounts;
+    use zeltra_db::entities::sea_orm_active_enums::{
+        AccountSubtype, AccountType, TransactionType, UserRole,
+    };
+    use zeltra_db::repositories::transaction::{
+        CreateLedgerEntryInput, CreateTransactionInput, TransactionRepository,
+    };
+    use zeltra_db::repositories::workflow::WorkflowRepository;
+    use zeltra_shared::types::TransactionId;
+    use zeltra_test_support::{Org, OrgFixture, TestDb, access_token, test_app_state};
+
+    use crate::routes::transactions;
+
+    /// Organization whose account 1000 is a bank account.
+    async fn org_with_bank(test_db: &TestDb) -> Org {
+        let org = OrgFixture::new()
+            .with_fiscal_year_2025()
+            .with_accounts(&[("1000", AccountType::Asset), ("4000", AccountType::Revenue)])
+            .create(test_db.conn())
+            .await;
+        chart_of_accounts::ActiveModel {
+            id: Set(org.account("1000").into_inner()),
+            account_subtype: Set(Some(AccountSubtype::Bank)),
+            ..Default::default()
+        }
+        .update(test_db.conn())
+        .await
+        .unwrap();
+        org
+    }
+
+    /// Creates an approved 100.00 invoice ready to post.
+    async fn approved_invoice(test_db: &TestDb, org: &Org) -> TransactionId {
+        let amount = Decimal::new(100, 0);
+        let entry = |account: Uuid, debit: Decimal, credit: Decimal| CreateLedgerEntryInput {
+            account_id: account,
+            source_currency: "USD".to_string(),
+            source_amount: amount,
+            exchange_rate: Decimal::ONE,
+            functional_currency: "USD".to_string(),
+            functional_amount: amount,
+            debit,
+            credit,
+            memo: None,
+            dimensions: vec![],
+            rate_override: None,
+            event_at: None,
+        };
+        let created = TransactionRepository::new(test_db.conn().clone())
+            .create_transaction(CreateTransactionInput {
+                organization_id: org.id.into_inner(),
+                transaction_type: TransactionType::Invoice,
+                transaction_date: NaiveDate::from_ymd_opt(2025, 3, 15).unwrap(),
+                description: "Invoice".to_string(),
+                reference_number: None,
+                memo: None,
+                entries: vec![
+                    entry(org.account("1000").into_inner(), amount, Decimal::ZERO),
+                    entry(org.account("4000").into_inner(), Decimal::ZERO, amount),
+                ],
+                created_by: org.owner.user_id.into_inner(),
+            })
+            .await
+            .unwrap();
+
+        let id = TransactionId::from(created.transaction.id);
+        let user_id = org.owner.user_id.into_inner();
+        let workflow = WorkflowRepository::new(test_db.conn().clone());
+        workflow
+            .submit_transaction(org.id, id, user_id)
+            .await
+            .unwrap();
+        workflow
+            .approve_transaction(org.id, id, user_id, None)
+            .await
+            .unwrap();
+        id
+    }
+
+    async fn send(state: &AppState, org: &Org, method: &str, uri: String) -> serde_json::Value {
+        let app = state.authenticated(routes().merge(transactions::routes()));
+        let token = access_token(&state.jwt_service, org.id, &org.owner);
+        let (status, body) =
+            zeltra_test_support::send(app, method, &uri, &token, serde_json::Value::Null).await;
+        assert_eq!(status, StatusCode::OK);
+        body
+    }
+
+    async fn cash_balance(state: &AppState, org: &Org) -> serde_json::Value {
+        let uri = format!("/organizations/{}/dashboard/metrics", org.id);
+        send(state, org, "GET", uri).await["cash_position"]["balance"].clone()
+    }
+
+    #[tokio::test]
+    async fn test_posting_invalidates_cached_dashboard_sections() {
+        let test_db = TestDb::new().await;
+        let state: AppState = test_app_state(&test_db);
+        let org = org_with_bank(&test_db).await;
+        let first = approved_invoice(&test_db, &org).await;
+        let second = approved_invoice(&test_db, &org).await;
+
+        assert_eq!(cash_balance(&state, &org).await, "0.0000");
+
+        // Posted behind the cache's back: the cached section is still served
+        WorkflowRepository::new(test_db.conn().clone())
+            .post_transaction(org.id, first, org.owner.user_id.into_inner())
+            .await
+            .unwrap();
+        assert_eq!(cash_balance(&state, &org).await, "0.0000");
+
+        let uri = format!("/organizations/{}/transactions/{second}/post", org.id);
+        send(&state, &org, "POST", uri).await;
+
+        assert_eq!(cash_balance(&state, &org).await, "200.0000");
+    }
+
+    #[tokio::test]
+    async fn test_submitter_cannot_read_dashboard() {
+        let test_db = TestDb::new().await;
+        let state: AppState = test_app_state(&test_db);
+        let org = OrgFixture::new()
+            .with_fiscal_year_2025()
+            .with_accounts(&[("1000", AccountType::Asset)])
+            .with_member(UserRole::Submitter)
+            .with_member(UserRole::Viewer)
+            .create(test_db.conn())
+            .await;
+        let submitter = access_token(&state.jwt_service, org.id, org.member(&UserRole::Submitter));
+        let viewer = access_token(&state.jwt_service, org.id, org.member(&UserRole::Viewer));
+
+        for section in ["metrics", "recent-activity"] {
+            let uri = format!("/organizations/{}/dashboard/{section}", org.id);
+            let null = serde_json::Value::Null;
+
+            let app = state.authenticated(routes());
+            let (status, body) =
+                zeltra_test_support::send(app, "GET", &uri, &submitter, null.clone()).await;
+            assert_eq!(status, StatusCode::FORBIDDEN, "{section}");
+            assert_eq!(body["error"], "report_access_restricted");
+
+            let app = state.authenticated(routes());
+            let (status, _) = zeltra_test_support::send(app, "GET", &uri, &viewer, null).await;
+            assert_eq!(status, StatusCode::OK, "{section}");
+        }
+    }
+}
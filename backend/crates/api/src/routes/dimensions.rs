@@ -7,20 +7,20 @@ use axum::{
     response::IntoResponse,
     routing::{get, post},
 };
-use chrono::NaiveDate;
+use chrono::{DateTime, NaiveDate, Utc};
 use serde::{Deserialize, Serialize};
 use serde_json::json;
 use tracing::{error, info};
 use uuid::Uuid;
 
-use crate::{AppState, middleware::AuthUser};
-use zeltra_db::{
-    OrganizationRepository,
-    entities::sea_orm_active_enums::UserRole,
-    repositories::dimension::{
-        CreateDimensionTypeInput, CreateDimensionValueInput, DimensionRepository,
-        DimensionTypeFilter, DimensionValueFilter,
-    },
+use crate::{
+    AppState,
+    middleware::{AuthMember, AuthUser, auth::check_membership},
+    routes::tier_limit_response,
+};
+use zeltra_db::repositories::dimension::{
+    CreateDimensionTypeInput, CreateDimensionValueInput, DimensionError, DimensionRepository,
+    DimensionTypeFilter, DimensionValueFilter, RetagEntriesInput, RetagTransaction,
 };
 
 /// Creates the dimension routes (requires auth middleware to be applied externally).
@@ -42,6 +42,10 @@ pub fn routes() -> Router<AppState> {
             "/organizations/{org_id}/dimension-values",
             post(create_dimension_value),
         )
+        .route(
+            "/organizations/{org_id}/dimensions/retag",
+            post(retag_dimension_entries),
+        )
 }
 
 /// Query parameters for listing dimension types.
@@ -49,6 +53,8 @@ pub fn routes() -> Router<AppState> {
 pub struct ListDimensionTypesQuery {
     /// Filter by active status.
     pub active: Option<bool>,
+    /// Only dimension types changed after this time (RFC 3339).
+    pub modified_since: Option<DateTime<Utc>>,
 }
 
 /// Query parameters for listing dimension values.
@@ -59,6 +65,8 @@ pub struct ListDimensionValuesQuery {
     pub dimension_type_id: Option<Uuid>,
     /// Filter by active status.
     pub active: Option<bool>,
+    /// Only dimension values changed after this time (RFC 3339).
+    pub modified_since: Option<DateTime<Utc>>,
 }
 
 /// Request body for creating a dimension type.
@@ -99,6 +107,27 @@ pub struct CreateDimensionValueRequest {
     pub effective_to: Option<NaiveDate>,
 }
 
+/// Request body for re-tagging ledger entries.
+#[derive(Debug, Deserialize)]
+pub struct RetagEntriesRequest {
+    /// Value the entries are tagged with now.
+    pub from_value_id: Uuid,
+    /// Value to tag them with instead (same dimension type).
+    pub to_value_id: Uuid,
+    /// Only transactions dated on or after this date.
+    pub date_from: Option<NaiveDate>,
+    /// Only transactions dated on or before this date.
+    pub date_to: Option<NaiveDate>,
+    /// Only entries on these accounts.
+    #[serde(default)]
+    pub account_ids: Vec<Uuid>,
+    /// Why the entries are re-tagged.
+    pub reason: Option<String>,
+    /// Count and sample the entries without changing them.
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
 /// Response for a dimension type.
 #[derive(Debug, Serialize)]
 pub struct DimensionTypeResponse {
@@ -116,6 +145,8 @@ pub struct DimensionTypeResponse {
     pub is_active: bool,
     /// Sort order.
     pub sort_order: i16,
+    /// When the dimension type last changed.
+    pub updated_at: String,
 }
 
 /// Response for a dimension value.
@@ -139,6 +170,8 @@ pub struct DimensionValueResponse {
     pub effective_from: Option<NaiveDate>,
     /// Effective to date.
     pub effective_to: Option<NaiveDate>,
+    /// When the dimension value last changed.
+    pub updated_at: String,
 }
 
 /// GET `/organizations/{org_id}/dimension-types` - List dimension types.
@@ -148,10 +181,8 @@ async fn list_dimension_types(
     Path(org_id): Path<Uuid>,
     Query(query): Query<ListDimensionTypesQuery>,
 ) -> impl IntoResponse {
-    let org_repo = OrganizationRepository::new((*state.db).clone());
-
     // Check membership
-    if let Err(response) = check_membership(&org_repo, org_id, auth.user_id()).await {
+    if let Err(response) = check_membership(&state, org_id, auth.user_id()).await {
         return response;
     }
 
@@ -159,6 +190,7 @@ async fn list_dimension_types(
 
     let filter = DimensionTypeFilter {
         is_active: query.active,
+        modified_since: query.modified_since,
     };
 
     match dim_repo.list_dimension_types(org_id, filter).await {
@@ -173,6 +205,7 @@ async fn list_dimension_types(
                     is_required: t.is_required,
                     is_active: t.is_active,
                     sort_order: t.sort_order,
+                    updated_at: t.updated_at.to_rfc3339(),
                 })
                 .collect();
 
@@ -195,14 +228,11 @@ async fn list_dimension_types(
 /// POST `/organizations/{org_id}/dimension-types` - Create a dimension type.
 async fn create_dimension_type(
     State(state): State<AppState>,
-    auth: AuthUser,
+    auth: AuthMember,
     Path(org_id): Path<Uuid>,
     Json(payload): Json<CreateDimensionTypeRequest>,
 ) -> impl IntoResponse {
-    let org_repo = OrganizationRepository::new((*state.db).clone());
-
-    // Check admin/owner role
-    if let Err(response) = check_admin_role(&org_repo, org_id, auth.user_id()).await {
+    if let Err(response) = check_chart_of_accounts_role(&state, org_id, &auth).await {
         return response;
     }
 
@@ -221,7 +251,6 @@ async fn create_dimension_type(
     match dim_repo.create_dimension_type(input).await {
         Ok(dim_type) => {
             info!(
-                org_id = %org_id,
                 dimension_type_id = %dim_type.id,
                 code = %dim_type.code,
                 "Dimension type created"
@@ -253,6 +282,9 @@ async fn create_dimension_type(
                     })),
                 )
                     .into_response(),
+                zeltra_db::repositories::dimension::DimensionError::TierLimitExceeded(limit) => {
+                    tier_limit_response(&limit)
+                }
                 _ => (
                     StatusCode::INTERNAL_SERVER_ERROR,
                     Json(json!({
@@ -273,10 +305,8 @@ async fn list_dimension_values(
     Path(org_id): Path<Uuid>,
     Query(query): Query<ListDimensionValuesQuery>,
 ) -> impl IntoResponse {
-    let org_repo = OrganizationRepository::new((*state.db).clone());
-
     // Check membership
-    if let Err(response) = check_membership(&org_repo, org_id, auth.user_id()).await {
+    if let Err(response) = check_membership(&state, org_id, auth.user_id()).await {
         return response;
     }
 
@@ -286,6 +316,7 @@ async fn list_dimension_values(
         dimension_type_id: query.dimension_type_id,
         is_active: query.active,
         parent_id: None,
+        modified_since: query.modified_since,
     };
 
     match dim_repo.list_dimension_values(org_id, filter).await {
@@ -302,6 +333,7 @@ async fn list_dimension_values(
                     is_active: v.is_active,
                     effective_from: v.effective_from,
                     effective_to: v.effective_to,
+                    updated_at: v.updated_at.to_rfc3339(),
                 })
                 .collect();
 
@@ -328,14 +360,11 @@ async fn list_dimension_values(
 /// POST `/organizations/{org_id}/dimension-values` - Create a dimension value.
 async fn create_dimension_value(
     State(state): State<AppState>,
-    auth: AuthUser,
+    auth: AuthMember,
     Path(org_id): Path<Uuid>,
     Json(payload): Json<CreateDimensionValueRequest>,
 ) -> impl IntoResponse {
-    let org_repo = OrganizationRepository::new((*state.db).clone());
-
-    // Check admin/owner role
-    if let Err(response) = check_admin_role(&org_repo, org_id, auth.user_id()).await {
+    if let Err(response) = check_chart_of_accounts_role(&state, org_id, &auth).await {
         return response;
     }
 
@@ -356,7 +385,6 @@ async fn create_dimension_value(
     match dim_repo.create_dimension_value(input).await {
         Ok(dim_value) => {
             info!(
-                org_id = %org_id,
                 dimension_value_id = %dim_value.id,
                 code = %dim_value.code,
                 "Dimension value created"
@@ -427,62 +455,254 @@ async fn create_dimension_value(
     }
 }
 
-// Helper functions
+/// POST `/organizations/{org_id}/dimensions/retag` - Move ledger entries
+/// from one dimension value to another of the same type.
+///
+/// Only the tags change; amounts and balances are left alone. With
+/// `dry_run` the matching entries are counted and sampled but not changed.
+async fn retag_dimension_entries(
+    State(state): State<AppState>,
+    auth: AuthMember,
+    Path(org_id): Path<Uuid>,
+    Json(payload): Json<RetagEntriesRequest>,
+) -> impl IntoResponse {
+    match auth.role_in(&state, org_id).await {
+        Ok(role) if role.can_modify_settings() => {}
+        Ok(_) => {
+            return (
+                StatusCode::FORBIDDEN,
+                Json(json!({
+                    "error": "forbidden",
+                    "message": "You need admin or owner role to re-tag entries"
+                })),
+            )
+                .into_response();
+        }
+        Err(response) => return response,
+    }
 
-async fn check_membership(
-    org_repo: &OrganizationRepository,
-    org_id: Uuid,
-    user_id: Uuid,
-) -> Result<(), axum::response::Response> {
-    match org_repo.is_member(org_id, user_id).await {
-        Ok(true) => Ok(()),
-        Ok(false) => Err((
-            StatusCode::FORBIDDEN,
-            Json(json!({
-                "error": "forbidden",
-                "message": "You are not a member of this organization"
-            })),
-        )
-            .into_response()),
-        Err(e) => {
-            error!(error = %e, "Database error checking membership");
-            Err((
-                StatusCode::INTERNAL_SERVER_ERROR,
+    let input = RetagEntriesInput {
+        from_value_id: payload.from_value_id,
+        to_value_id: payload.to_value_id,
+        date_from: payload.date_from,
+        date_to: payload.date_to,
+        account_ids: payload.account_ids,
+        reason: payload.reason,
+        retagged_by: auth.user_id(),
+        dry_run: payload.dry_run,
+    };
+
+    match DimensionRepository::new((*state.db).clone())
+        .retag_entries(org_id, input)
+        .await
+    {
+        Ok(result) => {
+            let sample: Vec<_> = result.sample.iter().map(retag_transaction_json).collect();
+            let Some(retag) = result.retag else {
+                return (
+                    StatusCode::OK,
+                    Json(json!({
+                        "dry_run": true,
+                        "affected_entries": result.affected_entries,
+                        "sample": sample,
+                    })),
+                )
+                    .into_response();
+            };
+
+            info!(
+                retag_id = %retag.id,
+                from_value_id = %retag.from_value_id,
+                to_value_id = %retag.to_value_id,
+                affected_entries = result.affected_entries,
+                "Dimension entries re-tagged"
+            );
+            state.dashboard_cache.invalidate(org_id).await;
+
+            (
+                StatusCode::OK,
                 Json(json!({
-                    "error": "internal_error",
-                    "message": "An error occurred"
+                    "dry_run": false,
+                    "retag_id": retag.id,
+                    "from_value_id": retag.from_value_id,
+                    "to_value_id": retag.to_value_id,
+                    "date_from": retag.date_from,
+                    "date_to": retag.date_to,
+                    "account_ids": retag.account_ids,
+                    "reason": retag.reason,
+                    "affected_entries": result.affected_entries,
+                    "sample": sample,
+                    "created_at": retag.created_at,
                 })),
             )
-                .into_response())
+                .into_response()
         }
+        Err(e) => retag_error_response(&e),
     }
 }
 
-async fn check_admin_role(
-    org_repo: &OrganizationRepository,
-    org_id: Uuid,
-    user_id: Uuid,
-) -> Result<(), axum::response::Response> {
-    match org_repo.has_role(org_id, user_id, UserRole::Admin).await {
-        Ok(true) => Ok(()),
-        Ok(false) => Err((
-            StatusCode::FORBIDDEN,
-            Json(json!({
-                "error": "forbidden",
-                "message": "You need admin or owner role to perform this action"
-            })),
-        )
-            .into_response()),
-        Err(e) => {
-            error!(error = %e, "Database error checking role");
-            Err((
+// Helper functions
+
+fn retag_transaction_json(transaction: &RetagTransaction) -> serde_json::Value {
+    json!({
+        "transaction_id": transaction.transaction_id,
+        "reference_number": transaction.reference_number,
+        "transaction_date": transaction.transaction_date,
+        "description": transaction.description,
+        "entry_count": transaction.entry_count,
+    })
+}
+
+fn retag_error_response(error: &DimensionError) -> axum::response::Response {
+    let (status, code) = match error {
+        DimensionError::ValueNotFound(_) => (StatusCode::NOT_FOUND, "value_not_found"),
+        DimensionError::AccountNotFound(_) => (StatusCode::BAD_REQUEST, "account_not_found"),
+        DimensionError::ValueInactive(_) => (StatusCode::BAD_REQUEST, "value_inactive"),
+        DimensionError::RetagSameValue => (StatusCode::BAD_REQUEST, "same_value"),
+        DimensionError::RetagTypeMismatch => (StatusCode::BAD_REQUEST, "type_mismatch"),
+        DimensionError::InvalidDateRange => (StatusCode::BAD_REQUEST, "invalid_date_range"),
+        e => {
+            error!(error = %e, "Failed to re-tag dimension entries");
+            return (
                 StatusCode::INTERNAL_SERVER_ERROR,
                 Json(json!({
                     "error": "internal_error",
                     "message": "An error occurred"
                 })),
             )
-                .into_response())
+                .into_response();
+        }
+    };
+    (
+        status,
+        Json(json!({
+            "error": code,
+            "message": error.to_string()
+        })),
+    )
+        .into_response()
+}
+
+/// Checks that the caller may create and edit dimension types and values.
+async fn check_chart_of_accounts_role(
+    state: &AppState,
+    org_id: Uuid,
+    auth: &AuthMember,
+) -> Result<(), axum::response::Response> {
+    let role = auth.role_in(state, org_id).await?;
+    if role.can_manage_chart_of_accounts() {
+        return Ok(());
+    }
+
+    Err((
+        StatusCode::FORBIDDEN,
+        Json(json!({
+            "error": "forbidden",
+            "message": "You need accountant role or above to perform this action"
+        })),
+    )
+        .into_response())
+}
+
+#[cfg(test)]
+mod integration_tests {
+    use super::*;
+    use zeltra_db::entities::sea_orm_active_enums::UserRole;
+    use zeltra_test_support::{
+        OrgFixture, TestDb, access_token, jwt_service, send, test_app_state,
+    };
+
+    async fn post_json(
+        state: &AppState,
+        uri: &str,
+        token: &str,
+        body: serde_json::Value,
+    ) -> (StatusCode, serde_json::Value) {
+        send(state.authenticated(routes()), "POST", uri, token, body).await
+    }
+
+    #[tokio::test]
+    async fn test_retag_requires_admin_and_matching_types() {
+        let test_db = TestDb::new().await;
+        let state: AppState = test_app_state(&test_db);
+        let org = OrgFixture::new()
+            .with_member(UserRole::Accountant)
+            .create(test_db.conn())
+            .await;
+        let jwt = jwt_service();
+        let owner = access_token(&jwt, org.id, &org.owner);
+        let accountant = access_token(&jwt, org.id, org.member(&UserRole::Accountant));
+        let types_uri = format!("/organizations/{}/dimension-types", org.id);
+        let values_uri = format!("/organizations/{}/dimension-values", org.id);
+
+        let mut value_ids = Vec::new();
+        for (type_code, value_codes) in [("DEPT", &["OPS", "FIN"][..]), ("PROJECT", &["APOLLO"])] {
+            let (_, dimension_type) = post_json(
+                &state,
+                &types_uri,
+                &owner,
+                json!({ "code": type_code, "name": type_code }),
+            )
+            .await;
+            for code in value_codes {
+                let (status, value) = post_json(
+                    &state,
+                    &values_uri,
+                    &owner,
+                    json!({ "dimension_type_id": dimension_type["id"], "code": code, "name": code }),
+                )
+                .await;
+                assert_eq!(status, StatusCode::CREATED);
+                value_ids.push(value["id"].clone());
+            }
         }
+        let [ops, fin, apollo] = &value_ids[..] else {
+            panic!("expected three values");
+        };
+        let retag_uri = format!("/organizations/{}/dimensions/retag", org.id);
+
+        let (status, body) = post_json(
+            &state,
+            &retag_uri,
+            &accountant,
+            json!({ "from_value_id": ops, "to_value_id": fin, "dry_run": true }),
+        )
+        .await;
+        assert_eq!(status, StatusCode::FORBIDDEN);
+        assert_eq!(body["error"], "forbidden");
+
+        let (status, body) = post_json(
+            &state,
+            &retag_uri,
+            &owner,
+            json!({ "from_value_id": ops, "to_value_id": apollo }),
+        )
+        .await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body["error"], "type_mismatch");
+
+        let (status, body) = post_json(
+            &state,
+            &retag_uri,
+            &owner,
+            json!({ "from_value_id": ops, "to_value_id": fin, "dry_run": true }),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["dry_run"], true);
+        assert_eq!(body["affected_entries"], 0);
+        assert!(body.get("retag_id").is_none());
+
+        let (status, body) = post_json(
+            &state,
+            &retag_uri,
+            &owner,
+            json!({ "from_value_id": ops, "to_value_id": fin, "reason": "Merged" }),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["dry_run"], false);
+        assert_eq!(body["reason"], "Merged");
+        assert!(body["retag_id"].is_string());
     }
 }
@@ -5,7 +5,7 @@ use axum::{
     extract::{Path, Query, State},
     http::StatusCode,
     response::IntoResponse,
-    routing::{get, post},
+    routing::{get, patch, post},
 };
 use chrono::NaiveDate;
 use rust_decimal::Decimal;
@@ -14,12 +14,20 @@ use serde_json::json;
 use tracing::{error, info};
 use uuid::Uuid;
 
-use crate::{AppState, middleware::AuthUser};
+use crate::{
+    AppState,
+    middleware::{
+        AuthMember, AuthUser,
+        auth::{check_membership, check_report_access},
+    },
+};
+use zeltra_core::currency::RateLookupPolicy;
 use zeltra_db::{
     OrganizationRepository,
     entities::sea_orm_active_enums::{RateSource, UserRole},
     repositories::exchange_rate::{
-        CreateExchangeRateInput, ExchangeRateRepository, RateLookupMethod,
+        CorrectExchangeRateInput, CreateExchangeRateInput, ExchangeRateError,
+        ExchangeRateRepository, RateLookupMethod, RateUsage,
     },
 };
 
@@ -34,6 +42,14 @@ pub fn routes() -> Router<AppState> {
             "/organizations/{org_id}/exchange-rates",
             post(create_exchange_rate),
         )
+        .route(
+            "/organizations/{org_id}/exchange-rates/{id}",
+            patch(correct_exchange_rate).delete(delete_exchange_rate),
+        )
+        .route(
+            "/organizations/{org_id}/exchange-rates/{id}/usage",
+            get(get_exchange_rate_usage),
+        )
 }
 
 /// Query parameters for getting an exchange rate.
@@ -45,6 +61,8 @@ pub struct GetExchangeRateQuery {
     pub to: String,
     /// Date for the rate lookup (defaults to today).
     pub date: Option<NaiveDate>,
+    /// Which stored rate to use: "daily_latest" (default) or "monthly_fixed".
+    pub policy: Option<RateLookupPolicy>,
 }
 
 /// Request body for creating/updating an exchange rate.
@@ -64,6 +82,23 @@ pub struct CreateExchangeRateRequest {
     pub source_reference: Option<String>,
 }
 
+/// Request body for correcting a stored exchange rate.
+#[derive(Debug, Deserialize)]
+pub struct CorrectExchangeRateRequest {
+    /// The corrected rate.
+    pub rate: Decimal,
+    /// Why the rate is being corrected.
+    pub reason: Option<String>,
+}
+
+/// Query parameters for deleting an exchange rate.
+#[derive(Debug, Deserialize)]
+pub struct DeleteExchangeRateQuery {
+    /// Delete even if posted entries used the rate.
+    #[serde(default)]
+    pub force: bool,
+}
+
 /// Response for an exchange rate lookup.
 #[derive(Debug, Serialize)]
 pub struct ExchangeRateResponse {
@@ -86,10 +121,8 @@ async fn get_exchange_rate(
     Path(org_id): Path<Uuid>,
     Query(query): Query<GetExchangeRateQuery>,
 ) -> impl IntoResponse {
-    let org_repo = OrganizationRepository::new((*state.db).clone());
-
     // Check membership
-    if let Err(response) = check_membership(&org_repo, org_id, auth.user_id()).await {
+    if let Err(response) = check_membership(&state, org_id, auth.user_id()).await {
         return response;
     }
 
@@ -100,7 +133,13 @@ async fn get_exchange_rate(
         .unwrap_or_else(|| chrono::Utc::now().date_naive());
 
     match rate_repo
-        .find_rate(org_id, &query.from, &query.to, date)
+        .find_rate(
+            org_id,
+            &query.from,
+            &query.to,
+            date,
+            query.policy.unwrap_or_default(),
+        )
         .await
     {
         Ok(lookup) => {
@@ -141,14 +180,14 @@ async fn get_exchange_rate(
 /// POST `/organizations/{org_id}/exchange-rates` - Create or update an exchange rate.
 async fn create_exchange_rate(
     State(state): State<AppState>,
-    auth: AuthUser,
+    auth: AuthMember,
     Path(org_id): Path<Uuid>,
     Json(payload): Json<CreateExchangeRateRequest>,
 ) -> impl IntoResponse {
     let org_repo = OrganizationRepository::new((*state.db).clone());
 
     // Check admin/owner role
-    if let Err(response) = check_admin_role(&org_repo, org_id, auth.user_id()).await {
+    if let Err(response) = check_role(&org_repo, org_id, &auth, &UserRole::Admin).await {
         return response;
     }
 
@@ -174,7 +213,6 @@ async fn create_exchange_rate(
     match rate_repo.create_or_update_rate(input).await {
         Ok(rate) => {
             info!(
-                org_id = %org_id,
                 from = %rate.from_currency,
                 to = %rate.to_currency,
                 rate = %rate.rate,
@@ -225,6 +263,16 @@ async fn create_exchange_rate(
                     })),
                 )
                     .into_response(),
+                zeltra_db::repositories::exchange_rate::ExchangeRateError::FutureEffectiveDate(
+                    date,
+                ) => (
+                    StatusCode::BAD_REQUEST,
+                    Json(json!({
+                        "error": "future_effective_date",
+                        "message": format!("Exchange rate effective date {} is in the future", date)
+                    })),
+                )
+                    .into_response(),
                 _ => (
                     StatusCode::INTERNAL_SERVER_ERROR,
                     Json(json!({
@@ -238,25 +286,173 @@ async fn create_exchange_rate(
     }
 }
 
+/// PATCH `/organizations/{org_id}/exchange-rates/{id}` - Correct a stored rate.
+///
+/// Posted entries keep the rate they were converted at; the response says
+/// how many used the old value so revaluation adjustments can be posted.
+async fn correct_exchange_rate(
+    State(state): State<AppState>,
+    auth: AuthMember,
+    Path((org_id, id)): Path<(Uuid, Uuid)>,
+    Json(payload): Json<CorrectExchangeRateRequest>,
+) -> impl IntoResponse {
+    let org_repo = OrganizationRepository::new((*state.db).clone());
+
+    if let Err(response) = check_role(&org_repo, org_id, &auth, &UserRole::Accountant).await {
+        return response;
+    }
+
+    let input = CorrectExchangeRateInput {
+        rate: payload.rate,
+        reason: payload.reason,
+        corrected_by: auth.user_id(),
+    };
+
+    match ExchangeRateRepository::new((*state.db).clone())
+        .correct_rate(org_id, id, input)
+        .await
+    {
+        Ok(corrected) => {
+            info!(
+                rate_id = %id,
+                old_rate = %corrected.correction.old_rate,
+                new_rate = %corrected.rate.rate,
+                affected_entries = corrected.usage.entry_count,
+                "Exchange rate corrected"
+            );
+
+            (
+                StatusCode::OK,
+                Json(json!({
+                    "id": corrected.rate.id,
+                    "from_currency": corrected.rate.from_currency,
+                    "to_currency": corrected.rate.to_currency,
+                    "rate": corrected.rate.rate.to_string(),
+                    "previous_rate": corrected.correction.old_rate.to_string(),
+                    "effective_date": corrected.rate.effective_date,
+                    "correction_id": corrected.correction.id,
+                    "usage": usage_json(&corrected.usage),
+                })),
+            )
+                .into_response()
+        }
+        Err(e) => rate_error_response(&e),
+    }
+}
+
+/// DELETE `/organizations/{org_id}/exchange-rates/{id}` - Delete a stored rate.
+///
+/// Refused with 409 when posted entries used the rate, unless `force=true`.
+async fn delete_exchange_rate(
+    State(state): State<AppState>,
+    auth: AuthMember,
+    Path((org_id, id)): Path<(Uuid, Uuid)>,
+    Query(query): Query<DeleteExchangeRateQuery>,
+) -> impl IntoResponse {
+    let org_repo = OrganizationRepository::new((*state.db).clone());
+
+    if let Err(response) = check_role(&org_repo, org_id, &auth, &UserRole::Accountant).await {
+        return response;
+    }
+
+    match ExchangeRateRepository::new((*state.db).clone())
+        .delete_rate(org_id, id, query.force)
+        .await
+    {
+        Ok(usage) => {
+            info!(
+                rate_id = %id,
+                affected_entries = usage.entry_count,
+                "Exchange rate deleted"
+            );
+
+            (
+                StatusCode::OK,
+                Json(json!({
+                    "id": id,
+                    "deleted": true,
+                    "usage": usage_json(&usage),
+                })),
+            )
+                .into_response()
+        }
+        Err(e) => rate_error_response(&e),
+    }
+}
+
+/// GET `/organizations/{org_id}/exchange-rates/{id}/usage` - Posted
+/// transactions with entries converted at a stored rate.
+async fn get_exchange_rate_usage(
+    State(state): State<AppState>,
+    auth: AuthMember,
+    Path((org_id, id)): Path<(Uuid, Uuid)>,
+) -> impl IntoResponse {
+    if let Err(response) = check_report_access(&state, org_id, &auth).await {
+        return response;
+    }
+
+    match ExchangeRateRepository::new((*state.db).clone())
+        .list_rate_usage(org_id, id)
+        .await
+    {
+        Ok((usage, transactions)) => (
+            StatusCode::OK,
+            Json(json!({
+                "exchange_rate_id": id,
+                "usage": usage_json(&usage),
+                "transactions": transactions
+                    .iter()
+                    .map(|t| json!({
+                        "transaction_id": t.transaction_id,
+                        "reference_number": t.reference_number,
+                        "transaction_date": t.transaction_date,
+                        "description": t.description,
+                        "entry_count": t.entry_count,
+                        "source_amount": t.source_amount.to_string(),
+                        "functional_amount": t.functional_amount.to_string(),
+                    }))
+                    .collect::<Vec<_>>(),
+            })),
+        )
+            .into_response(),
+        Err(e) => rate_error_response(&e),
+    }
+}
+
 // Helper functions
 
-async fn check_membership(
+async fn check_role(
     org_repo: &OrganizationRepository,
     org_id: Uuid,
-    user_id: Uuid,
+    auth: &AuthMember,
+    required: &UserRole,
 ) -> Result<(), axum::response::Response> {
-    match org_repo.is_member(org_id, user_id).await {
+    let allowed = match auth.has_role(org_id, required) {
+        Some(allowed) => Ok(allowed),
+        // Token was issued for another organization
+        None => {
+            org_repo
+                .has_role(org_id, auth.user_id(), required.clone())
+                .await
+        }
+    };
+
+    let message = match required {
+        UserRole::Accountant => "You need accountant, admin or owner role to perform this action",
+        _ => "You need admin or owner role to perform this action",
+    };
+    match allowed {
         Ok(true) => Ok(()),
         Ok(false) => Err((
             StatusCode::FORBIDDEN,
             Json(json!({
                 "error": "forbidden",
-                "message": "You are not a member of this organization"
+                "message": message
             })),
         )
             .into_response()),
         Err(e) => {
-            error!(error = %e, "Database error checking membership");
+            error!(error = %e, "Database error checking role");
             Err((
                 StatusCode::INTERNAL_SERVER_ERROR,
                 Json(json!({
@@ -269,35 +465,58 @@ async fn check_membership(
     }
 }
 
-async fn check_admin_role(
-    org_repo: &OrganizationRepository,
-    org_id: Uuid,
-    user_id: Uuid,
-) -> Result<(), axum::response::Response> {
-    match org_repo.has_role(org_id, user_id, UserRole::Admin).await {
-        Ok(true) => Ok(()),
-        Ok(false) => Err((
-            StatusCode::FORBIDDEN,
+/// Maps errors from the stored-rate operations to responses.
+fn rate_error_response(error: &ExchangeRateError) -> axum::response::Response {
+    match error {
+        ExchangeRateError::NotFound(_) => (
+            StatusCode::NOT_FOUND,
             Json(json!({
-                "error": "forbidden",
-                "message": "You need admin or owner role to perform this action"
+                "error": "not_found",
+                "message": "Exchange rate not found"
             })),
         )
-            .into_response()),
-        Err(e) => {
-            error!(error = %e, "Database error checking role");
-            Err((
+            .into_response(),
+        ExchangeRateError::NonPositiveRate => (
+            StatusCode::BAD_REQUEST,
+            Json(json!({
+                "error": "invalid_rate",
+                "message": "Exchange rate must be positive"
+            })),
+        )
+            .into_response(),
+        ExchangeRateError::InUse(count) => (
+            StatusCode::CONFLICT,
+            Json(json!({
+                "error": "rate_in_use",
+                "message": format!(
+                    "{count} posted ledger entries used this rate; pass force=true to delete it anyway"
+                ),
+                "affected_entries": count
+            })),
+        )
+            .into_response(),
+        e => {
+            error!(error = %e, "Exchange rate operation failed");
+            (
                 StatusCode::INTERNAL_SERVER_ERROR,
                 Json(json!({
                     "error": "internal_error",
                     "message": "An error occurred"
                 })),
             )
-                .into_response())
+                .into_response()
         }
     }
 }
 
+fn usage_json(usage: &RateUsage) -> serde_json::Value {
+    json!({
+        "window_start": usage.window_start,
+        "window_end": usage.window_end,
+        "affected_entries": usage.entry_count,
+    })
+}
+
 fn lookup_method_to_string(method: RateLookupMethod) -> String {
     match method {
         RateLookupMethod::Direct => "direct".to_string(),
@@ -322,3 +541,60 @@ fn string_to_rate_source(s: &str) -> Option<RateSource> {
         _ => None,
     }
 }
+
+#[cfg(test)]
+mod integration_tests {
+    use super::*;
+    use zeltra_db::entities::sea_orm_active_enums::AccountType;
+    use zeltra_test_support::{OrgFixture, TestDb, access_token, send, test_app_state};
+
+    #[tokio::test]
+    async fn test_submitter_cannot_read_rate_usage() {
+        let test_db = TestDb::new().await;
+        let state: AppState = test_app_state(&test_db);
+        let org = OrgFixture::new()
+            .with_fiscal_year_2025()
+            .with_accounts(&[("1000", AccountType::Asset)])
+            .with_member(UserRole::Submitter)
+            .with_member(UserRole::Viewer)
+            .create(test_db.conn())
+            .await;
+        let rate = ExchangeRateRepository::new(test_db.conn().clone())
+            .create_or_update_rate(CreateExchangeRateInput {
+                organization_id: org.id.into_inner(),
+                from_currency: "EUR".to_string(),
+                to_currency: "USD".to_string(),
+                rate: Decimal::new(110, 2),
+                effective_date: NaiveDate::from_ymd_opt(2025, 3, 1).unwrap(),
+                source: RateSource::Manual,
+                source_reference: None,
+                created_by: None,
+            })
+            .await
+            .unwrap();
+        let uri = format!("/organizations/{}/exchange-rates/{}/usage", org.id, rate.id);
+
+        let submitter = access_token(&state.jwt_service, org.id, org.member(&UserRole::Submitter));
+        let (status, body) = send(
+            state.authenticated(routes()),
+            "GET",
+            &uri,
+            &submitter,
+            json!(null),
+        )
+        .await;
+        assert_eq!(status, StatusCode::FORBIDDEN);
+        assert_eq!(body["error"], "report_access_restricted");
+
+        let viewer = access_token(&state.jwt_service, org.id, org.member(&UserRole::Viewer));
+        let (status, _) = send(
+            state.authenticated(routes()),
+            "GET",
+            &uri,
+            &viewer,
+            json!(null),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+    }
+}
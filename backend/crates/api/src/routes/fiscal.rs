@@ -10,14 +10,30 @@ use axum::{
 use chrono::NaiveDate;
 use serde::{Deserialize, Serialize};
 use serde_json::json;
-use tracing::{error, info};
+use tracing::{error, info, warn};
 use uuid::Uuid;
 
-use crate::{AppState, middleware::AuthUser};
+use crate::{
+    AppState,
+    middleware::{
+        AuthMember, AuthUser,
+        auth::{check_membership, check_report_access},
+    },
+};
+use zeltra_core::fiscal::{CheckStatus, PeriodCoverageFinding, overall_status};
 use zeltra_db::{
     OrganizationRepository,
-    entities::sea_orm_active_enums::{FiscalPeriodStatus, UserRole},
-    repositories::fiscal::{CreateFiscalYearInput, FiscalRepository},
+    entities::{
+        fiscal_periods,
+        sea_orm_active_enums::{FiscalPeriodStatus, UserRole},
+    },
+    repositories::closing::{ClosingChecklist, ClosingRepository},
+    repositories::fiscal::{
+        CreateFiscalYearInput, FiscalError, FiscalRepository, FiscalYearWithPeriods,
+    },
+    repositories::period_close::{
+        MAX_GRACE_EXTENSION_DAYS, PeriodCloseError, PeriodCloseRepository,
+    },
 };
 
 /// Creates the fiscal routes (requires auth middleware to be applied externally).
@@ -31,10 +47,22 @@ pub fn routes() -> Router<AppState> {
             "/organizations/{org_id}/fiscal-years",
             post(create_fiscal_year),
         )
+        .route(
+            "/organizations/{org_id}/fiscal-years/{year_id}/validate",
+            get(validate_fiscal_year),
+        )
         .route(
             "/organizations/{org_id}/fiscal-periods/{period_id}/status",
             patch(update_period_status),
         )
+        .route(
+            "/organizations/{org_id}/fiscal-periods/{period_id}/closing-checklist",
+            get(get_closing_checklist),
+        )
+        .route(
+            "/organizations/{org_id}/fiscal-periods/{period_id}/extend-grace",
+            post(extend_grace_period),
+        )
 }
 
 /// Request body for creating a fiscal year.
@@ -53,6 +81,19 @@ pub struct CreateFiscalYearRequest {
 pub struct UpdatePeriodStatusRequest {
     /// New status: "open", "soft_close", or "closed".
     pub status: String,
+    /// Refuse to close while any closing checklist item fails.
+    #[serde(default)]
+    pub strict: bool,
+}
+
+/// Request body for extending a period's auto-close grace period.
+#[derive(Debug, Deserialize)]
+pub struct ExtendGraceRequest {
+    /// Days to push the soft close and close dates back by.
+    pub days: u32,
+    /// Why the extension was granted.
+    #[serde(default)]
+    pub reason: Option<String>,
 }
 
 /// Response for a fiscal period.
@@ -89,6 +130,34 @@ pub struct FiscalYearResponse {
     pub status: String,
     /// Nested periods.
     pub periods: Vec<FiscalPeriodResponse>,
+    /// Gaps and overlaps in the periods' coverage of the year. Warnings only.
+    pub coverage_warnings: Vec<PeriodCoverageFinding>,
+}
+
+impl From<FiscalYearWithPeriods> for FiscalYearResponse {
+    fn from(fy: FiscalYearWithPeriods) -> Self {
+        Self {
+            id: fy.fiscal_year.id,
+            name: fy.fiscal_year.name,
+            start_date: fy.fiscal_year.start_date,
+            end_date: fy.fiscal_year.end_date,
+            status: fiscal_year_status_to_string(&fy.fiscal_year.status),
+            periods: fy
+                .periods
+                .into_iter()
+                .map(|p| FiscalPeriodResponse {
+                    id: p.id,
+                    name: p.name,
+                    period_number: p.period_number,
+                    start_date: p.start_date,
+                    end_date: p.end_date,
+                    status: period_status_to_string(&p.status),
+                    is_adjustment_period: p.is_adjustment_period,
+                })
+                .collect(),
+            coverage_warnings: fy.coverage,
+        }
+    }
 }
 
 /// GET `/organizations/{org_id}/fiscal-years` - List fiscal years with nested periods.
@@ -97,10 +166,8 @@ async fn list_fiscal_years(
     auth: AuthUser,
     Path(org_id): Path<Uuid>,
 ) -> impl IntoResponse {
-    let org_repo = OrganizationRepository::new((*state.db).clone());
-
     // Check membership
-    if let Err(response) = check_membership(&org_repo, org_id, auth.user_id()).await {
+    if let Err(response) = check_membership(&state, org_id, auth.user_id()).await {
         return response;
     }
 
@@ -108,29 +175,8 @@ async fn list_fiscal_years(
 
     match fiscal_repo.list_fiscal_years(org_id).await {
         Ok(years) => {
-            let response: Vec<FiscalYearResponse> = years
-                .into_iter()
-                .map(|fy| FiscalYearResponse {
-                    id: fy.fiscal_year.id,
-                    name: fy.fiscal_year.name,
-                    start_date: fy.fiscal_year.start_date,
-                    end_date: fy.fiscal_year.end_date,
-                    status: fiscal_year_status_to_string(&fy.fiscal_year.status),
-                    periods: fy
-                        .periods
-                        .into_iter()
-                        .map(|p| FiscalPeriodResponse {
-                            id: p.id,
-                            name: p.name,
-                            period_number: p.period_number,
-                            start_date: p.start_date,
-                            end_date: p.end_date,
-                            status: period_status_to_string(&p.status),
-                            is_adjustment_period: p.is_adjustment_period,
-                        })
-                        .collect(),
-                })
-                .collect();
+            let response: Vec<FiscalYearResponse> =
+                years.into_iter().map(FiscalYearResponse::from).collect();
 
             (StatusCode::OK, Json(json!({ "fiscal_years": response }))).into_response()
         }
@@ -151,14 +197,14 @@ async fn list_fiscal_years(
 /// POST `/organizations/{org_id}/fiscal-years` - Create a fiscal year with auto-generated periods.
 async fn create_fiscal_year(
     State(state): State<AppState>,
-    auth: AuthUser,
+    auth: AuthMember,
     Path(org_id): Path<Uuid>,
     Json(payload): Json<CreateFiscalYearRequest>,
 ) -> impl IntoResponse {
     let org_repo = OrganizationRepository::new((*state.db).clone());
 
     // Check admin/owner role
-    if let Err(response) = check_admin_role(&org_repo, org_id, auth.user_id()).await {
+    if let Err(response) = check_admin_role(&org_repo, org_id, &auth).await {
         return response;
     }
 
@@ -173,32 +219,17 @@ async fn create_fiscal_year(
 
     match fiscal_repo.create_fiscal_year(input).await {
         Ok(fy) => {
-            info!(
-                org_id = %org_id,
-                fiscal_year_id = %fy.fiscal_year.id,
-                "Fiscal year created"
-            );
+            info!(fiscal_year_id = %fy.fiscal_year.id, "Fiscal year created");
+
+            if !fy.coverage.is_empty() {
+                warn!(
+                    fiscal_year_id = %fy.fiscal_year.id,
+                    findings = fy.coverage.len(),
+                    "Fiscal year periods have gaps or overlaps"
+                );
+            }
 
-            let response = FiscalYearResponse {
-                id: fy.fiscal_year.id,
-                name: fy.fiscal_year.name,
-                start_date: fy.fiscal_year.start_date,
-                end_date: fy.fiscal_year.end_date,
-                status: fiscal_year_status_to_string(&fy.fiscal_year.status),
-                periods: fy
-                    .periods
-                    .into_iter()
-                    .map(|p| FiscalPeriodResponse {
-                        id: p.id,
-                        name: p.name,
-                        period_number: p.period_number,
-                        start_date: p.start_date,
-                        end_date: p.end_date,
-                        status: period_status_to_string(&p.status),
-                        is_adjustment_period: p.is_adjustment_period,
-                    })
-                    .collect(),
-            };
+            let response = FiscalYearResponse::from(fy);
 
             (StatusCode::CREATED, Json(json!(response))).into_response()
         }
@@ -234,58 +265,75 @@ async fn create_fiscal_year(
     }
 }
 
-/// PATCH `/organizations/{org_id}/fiscal-periods/{period_id}/status` - Update period status.
-#[allow(clippy::too_many_lines)]
-async fn update_period_status(
+/// GET `/organizations/{org_id}/fiscal-years/{year_id}/validate` - Check the year's periods for
+/// gaps, overlaps and dates outside the year.
+async fn validate_fiscal_year(
     State(state): State<AppState>,
     auth: AuthUser,
-    Path((org_id, period_id)): Path<(Uuid, Uuid)>,
-    Json(payload): Json<UpdatePeriodStatusRequest>,
+    Path((org_id, year_id)): Path<(Uuid, Uuid)>,
 ) -> impl IntoResponse {
-    let org_repo = OrganizationRepository::new((*state.db).clone());
-
-    // Check admin/owner role
-    if let Err(response) = check_admin_role(&org_repo, org_id, auth.user_id()).await {
+    // Check membership
+    if let Err(response) = check_membership(&state, org_id, auth.user_id()).await {
         return response;
     }
 
     let fiscal_repo = FiscalRepository::new((*state.db).clone());
 
-    // Verify period belongs to this organization
-    match fiscal_repo.find_period_by_id(period_id).await {
-        Ok(Some(p)) if p.organization_id == org_id => {}
-        Ok(Some(_)) => {
-            return (
-                StatusCode::FORBIDDEN,
-                Json(json!({
-                    "error": "forbidden",
-                    "message": "Period does not belong to this organization"
-                })),
-            )
-                .into_response();
-        }
-        Ok(None) => {
-            return (
-                StatusCode::NOT_FOUND,
-                Json(json!({
-                    "error": "not_found",
-                    "message": "Fiscal period not found"
-                })),
-            )
-                .into_response();
-        }
+    match fiscal_repo.validate_fiscal_year(org_id, year_id).await {
+        Ok(fy) => (
+            StatusCode::OK,
+            Json(json!({
+                "fiscal_year_id": fy.fiscal_year.id,
+                "start_date": fy.fiscal_year.start_date,
+                "end_date": fy.fiscal_year.end_date,
+                "valid": fy.coverage.is_empty(),
+                "findings": fy.coverage
+            })),
+        )
+            .into_response(),
+        Err(FiscalError::YearNotFound(_)) => (
+            StatusCode::NOT_FOUND,
+            Json(json!({
+                "error": "not_found",
+                "message": "Fiscal year not found"
+            })),
+        )
+            .into_response(),
         Err(e) => {
-            error!(error = %e, "Database error finding period");
-            return (
+            error!(error = %e, "Failed to validate fiscal year");
+            (
                 StatusCode::INTERNAL_SERVER_ERROR,
                 Json(json!({
                     "error": "internal_error",
                     "message": "An error occurred"
                 })),
             )
-                .into_response();
+                .into_response()
         }
     }
+}
+
+/// PATCH `/organizations/{org_id}/fiscal-periods/{period_id}/status` - Update period status.
+#[allow(clippy::too_many_lines)]
+async fn update_period_status(
+    State(state): State<AppState>,
+    auth: AuthMember,
+    Path((org_id, period_id)): Path<(Uuid, Uuid)>,
+    Json(payload): Json<UpdatePeriodStatusRequest>,
+) -> impl IntoResponse {
+    let org_repo = OrganizationRepository::new((*state.db).clone());
+
+    // Check admin/owner role
+    if let Err(response) = check_admin_role(&org_repo, org_id, &auth).await {
+        return response;
+    }
+
+    let fiscal_repo = FiscalRepository::new((*state.db).clone());
+
+    let period = match find_org_period(&fiscal_repo, org_id, period_id).await {
+        Ok(period) => period,
+        Err(response) => return response,
+    };
 
     // Parse status
     let Some(new_status) = string_to_period_status(&payload.status) else {
@@ -299,15 +347,49 @@ async fn update_period_status(
             .into_response();
     };
 
-    // Determine closed_by
-    let closed_by = if matches!(
+    let closing = matches!(
         new_status,
         FiscalPeriodStatus::SoftClose | FiscalPeriodStatus::Closed
-    ) {
-        Some(auth.user_id())
-    } else {
-        None
-    };
+    );
+
+    if closing && payload.strict {
+        let checklist = match ClosingRepository::new((*state.db).clone())
+            .checklist(&period)
+            .await
+        {
+            Ok(checklist) => checklist,
+            Err(e) => {
+                error!(error = %e, "Failed to run closing checklist");
+                return (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    Json(json!({
+                        "error": "internal_error",
+                        "message": "An error occurred"
+                    })),
+                )
+                    .into_response();
+            }
+        };
+        if overall_status(&checklist.checks) == CheckStatus::Fail {
+            let failed: Vec<_> = checklist
+                .checks
+                .into_iter()
+                .filter(|c| c.status == CheckStatus::Fail)
+                .collect();
+            return (
+                StatusCode::CONFLICT,
+                Json(json!({
+                    "error": "closing_checks_failed",
+                    "message": "Cannot close period: closing checklist has failing checks",
+                    "checks": failed
+                })),
+            )
+                .into_response();
+        }
+    }
+
+    // Determine closed_by
+    let closed_by = if closing { Some(auth.user_id()) } else { None };
 
     match fiscal_repo
         .update_period_status(period_id, new_status, closed_by)
@@ -315,7 +397,6 @@ async fn update_period_status(
     {
         Ok(updated) => {
             info!(
-                org_id = %org_id,
                 period_id = %period_id,
                 new_status = %payload.status,
                 "Fiscal period status updated"
@@ -372,25 +453,156 @@ async fn update_period_status(
     }
 }
 
+/// GET `/organizations/{org_id}/fiscal-periods/{period_id}/closing-checklist` - Check whether a
+/// period is ready to close.
+async fn get_closing_checklist(
+    State(state): State<AppState>,
+    auth: AuthMember,
+    Path((org_id, period_id)): Path<(Uuid, Uuid)>,
+) -> impl IntoResponse {
+    // The checks list offending transactions from across the organization
+    if let Err(response) = check_report_access(&state, org_id, &auth).await {
+        return response;
+    }
+
+    let fiscal_repo = FiscalRepository::new((*state.db).clone());
+    let period = match find_org_period(&fiscal_repo, org_id, period_id).await {
+        Ok(period) => period,
+        Err(response) => return response,
+    };
+
+    match ClosingRepository::new((*state.db).clone())
+        .checklist(&period)
+        .await
+    {
+        Ok(checklist) => {
+            (StatusCode::OK, Json(checklist_json(&period, &checklist))).into_response()
+        }
+        Err(e) => {
+            error!(error = %e, "Failed to run closing checklist");
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({
+                    "error": "internal_error",
+                    "message": "An error occurred"
+                })),
+            )
+                .into_response()
+        }
+    }
+}
+
+/// POST `/organizations/{org_id}/fiscal-periods/{period_id}/extend-grace` - Push back a period's
+/// automatic soft close and close.
+async fn extend_grace_period(
+    State(state): State<AppState>,
+    auth: AuthMember,
+    Path((org_id, period_id)): Path<(Uuid, Uuid)>,
+    Json(payload): Json<ExtendGraceRequest>,
+) -> impl IntoResponse {
+    let org_repo = OrganizationRepository::new((*state.db).clone());
+
+    // Check admin/owner role
+    if let Err(response) = check_admin_role(&org_repo, org_id, &auth).await {
+        return response;
+    }
+
+    match PeriodCloseRepository::new((*state.db).clone())
+        .extend_grace(
+            org_id,
+            period_id,
+            payload.days,
+            payload.reason,
+            auth.user_id(),
+        )
+        .await
+    {
+        Ok(extended) => {
+            info!(
+                period_id = %period_id,
+                days = payload.days,
+                "Fiscal period grace extended"
+            );
+
+            (
+                StatusCode::OK,
+                Json(json!({
+                    "period_id": period_id,
+                    "auto_close_enabled": extended.enabled,
+                    "extension_days": extended.schedule.extension_days,
+                    "soft_close_on": extended.schedule.soft_close_on,
+                    "close_on": extended.schedule.close_on
+                })),
+            )
+                .into_response()
+        }
+        Err(PeriodCloseError::InvalidExtension) => (
+            StatusCode::BAD_REQUEST,
+            Json(json!({
+                "error": "invalid_days",
+                "message": format!("Days must be between 1 and {MAX_GRACE_EXTENSION_DAYS}")
+            })),
+        )
+            .into_response(),
+        Err(PeriodCloseError::PeriodNotFound(_)) => (
+            StatusCode::NOT_FOUND,
+            Json(json!({
+                "error": "not_found",
+                "message": "Fiscal period not found"
+            })),
+        )
+            .into_response(),
+        Err(PeriodCloseError::PeriodClosed(_)) => (
+            StatusCode::CONFLICT,
+            Json(json!({
+                "error": "period_closed",
+                "message": "Fiscal period is already closed"
+            })),
+        )
+            .into_response(),
+        Err(e) => {
+            error!(error = %e, "Failed to extend grace period");
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({
+                    "error": "internal_error",
+                    "message": "An error occurred"
+                })),
+            )
+                .into_response()
+        }
+    }
+}
+
 // Helper functions
 
-async fn check_membership(
+async fn check_admin_role(
     org_repo: &OrganizationRepository,
     org_id: Uuid,
-    user_id: Uuid,
+    auth: &AuthMember,
 ) -> Result<(), axum::response::Response> {
-    match org_repo.is_member(org_id, user_id).await {
+    let allowed = match auth.has_role(org_id, &UserRole::Admin) {
+        Some(allowed) => Ok(allowed),
+        // Token was issued for another organization
+        None => {
+            org_repo
+                .has_role(org_id, auth.user_id(), UserRole::Admin)
+                .await
+        }
+    };
+
+    match allowed {
         Ok(true) => Ok(()),
         Ok(false) => Err((
             StatusCode::FORBIDDEN,
             Json(json!({
                 "error": "forbidden",
-                "message": "You are not a member of this organization"
+                "message": "You need admin or owner role to perform this action"
             })),
         )
             .into_response()),
         Err(e) => {
-            error!(error = %e, "Database error checking membership");
+            error!(error = %e, "Database error checking role");
             Err((
                 StatusCode::INTERNAL_SERVER_ERROR,
                 Json(json!({
@@ -403,23 +615,32 @@ async fn check_membership(
     }
 }
 
-async fn check_admin_role(
-    org_repo: &OrganizationRepository,
+/// Loads a period, rejecting one that belongs to another organization.
+async fn find_org_period(
+    fiscal_repo: &FiscalRepository,
     org_id: Uuid,
-    user_id: Uuid,
-) -> Result<(), axum::response::Response> {
-    match org_repo.has_role(org_id, user_id, UserRole::Admin).await {
-        Ok(true) => Ok(()),
-        Ok(false) => Err((
+    period_id: Uuid,
+) -> Result<fiscal_periods::Model, axum::response::Response> {
+    match fiscal_repo.find_period_by_id(period_id).await {
+        Ok(Some(p)) if p.organization_id == org_id => Ok(p),
+        Ok(Some(_)) => Err((
             StatusCode::FORBIDDEN,
             Json(json!({
                 "error": "forbidden",
-                "message": "You need admin or owner role to perform this action"
+                "message": "Period does not belong to this organization"
+            })),
+        )
+            .into_response()),
+        Ok(None) => Err((
+            StatusCode::NOT_FOUND,
+            Json(json!({
+                "error": "not_found",
+                "message": "Fiscal period not found"
             })),
         )
             .into_response()),
         Err(e) => {
-            error!(error = %e, "Database error checking role");
+            error!(error = %e, "Database error finding period");
             Err((
                 StatusCode::INTERNAL_SERVER_ERROR,
                 Json(json!({
@@ -432,6 +653,22 @@ async fn check_admin_role(
     }
 }
 
+fn checklist_json(
+    period: &fiscal_periods::Model,
+    checklist: &ClosingChecklist,
+) -> serde_json::Value {
+    json!({
+        "period_id": period.id,
+        "name": period.name,
+        "start_date": period.start_date,
+        "end_date": period.end_date,
+        "period_status": period_status_to_string(&period.status),
+        "status": overall_status(&checklist.checks),
+        "transaction_counts": checklist.transaction_counts,
+        "checks": checklist.checks
+    })
+}
+
 fn fiscal_year_status_to_string(
     status: &zeltra_db::entities::sea_orm_active_enums::FiscalYearStatus,
 ) -> String {
@@ -457,3 +694,51 @@ fn string_to_period_status(s: &str) -> Option<FiscalPeriodStatus> {
         _ => None,
     }
 }
+
+#[cfg(test)]
+mod integration_tests {
+    use super::*;
+    use zeltra_db::entities::sea_orm_active_enums::AccountType;
+    use zeltra_test_support::{OrgFixture, TestDb, access_token, send, test_app_state};
+
+    #[tokio::test]
+    async fn test_submitter_cannot_read_closing_checklist() {
+        let test_db = TestDb::new().await;
+        let state: AppState = test_app_state(&test_db);
+        let org = OrgFixture::new()
+            .with_fiscal_year_2025()
+            .with_accounts(&[("1000", AccountType::Asset)])
+            .with_member(UserRole::Submitter)
+            .with_member(UserRole::Viewer)
+            .create(test_db.conn())
+            .await;
+        let period_id = org.fiscal_year.as_ref().unwrap().periods[0];
+        let uri = format!(
+            "/organizations/{}/fiscal-periods/{period_id}/closing-checklist",
+            org.id
+        );
+
+        let submitter = access_token(&state.jwt_service, org.id, org.member(&UserRole::Submitter));
+        let (status, body) = send(
+            state.authenticated(routes()),
+            "GET",
+            &uri,
+            &submitter,
+            json!(null),
+        )
+        .await;
+        assert_eq!(status, StatusCode::FORBIDDEN);
+        assert_eq!(body["error"], "report_access_restricted");
+
+        let viewer = access_token(&state.jwt_service, org.id, org.member(&UserRole::Viewer));
+        let (status, _) = send(
+            state.authenticated(routes()),
+            "GET",
+            &uri,
+            &viewer,
+            json!(null),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+    }
+}
@@ -0,0 +1,282 @@
+//! Ledger integrity routes.
+//!
+//! Replays every account's running balance and reports where the stored
+//! balances disagree. Large ledgers can be checked in the background: the run
+//! is kept in process memory for an hour and polled by job id, so a restart
+//! loses runs that have not been collected.
+
+use std::sync::{Arc, LazyLock};
+use std::time::Duration;
+
+use axum::{
+    Json, Router,
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    routing::get,
+};
+use chrono::{DateTime, Utc};
+use moka::future::Cache;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use tracing::{error, info};
+use uuid::Uuid;
+
+use crate::{AppState, middleware::AuthMember};
+use zeltra_core::ledger::{AccountIntegrity, IntegrityStatus};
+use zeltra_db::repositories::{AccountRepository, LedgerIntegrityAccount};
+
+/// How long a background run is kept after it starts.
+const RUN_TTL: Duration = Duration::from_secs(60 * 60);
+
+/// Upper bound on kept background runs.
+const MAX_RUNS: u64 = 1_000;
+
+/// Background runs by job id.
+static RUNS: LazyLock<Cache<Uuid, IntegrityRun>> = LazyLock::new(|| {
+    Cache::builder()
+        .max_capacity(MAX_RUNS)
+        .time_to_live(RUN_TTL)
+        .build()
+});
+
+/// Creates integrity routes.
+pub fn routes() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/organizations/{org_id}/integrity/ledger",
+            get(verify_ledger),
+        )
+        .route(
+            "/organizations/{org_id}/integrity/ledger/jobs/{job_id}",
+            get(get_verification_job),
+        )
+}
+
+/// Query parameters for a ledger check.
+#[derive(Debug, Default, Deserialize)]
+pub struct VerifyLedgerQuery {
+    /// Run in the background and return a job id.
+    #[serde(default, rename = "async")]
+    pub run_async: bool,
+}
+
+/// One account in the report.
+#[derive(Debug, Clone, Serialize)]
+struct AccountReport {
+    account_id: Uuid,
+    code: String,
+    name: String,
+    #[serde(flatten)]
+    integrity: AccountIntegrity,
+}
+
+impl From<LedgerIntegrityAccount> for AccountReport {
+    fn from(account: LedgerIntegrityAccount) -> Self {
+        Self {
+            account_id: account.account_id,
+            code: account.code,
+            name: account.name,
+            integrity: account.integrity,
+        }
+    }
+}
+
+/// Result of checking an organization's ledger.
+#[derive(Debug, Clone, Serialize)]
+struct LedgerIntegrityReport {
+    organization_id: Uuid,
+    checked_at: DateTime<Utc>,
+    accounts_checked: usize,
+    entries_checked: u64,
+    diverged_accounts: usize,
+    accounts_with_gaps: usize,
+    accounts: Vec<AccountReport>,
+}
+
+impl LedgerIntegrityReport {
+    fn new(organization_id: Uuid, accounts: Vec<LedgerIntegrityAccount>) -> Self {
+        let accounts: Vec<AccountReport> = accounts.into_iter().map(Into::into).collect();
+        Self {
+            organization_id,
+            checked_at: Utc::now(),
+            accounts_checked: accounts.len(),
+            entries_checked: accounts.iter().map(|a| a.integrity.entries_checked).sum(),
+            diverged_accounts: accounts
+                .iter()
+                .filter(|a| a.integrity.status == IntegrityStatus::Diverged)
+                .count(),
+            accounts_with_gaps: accounts
+                .iter()
+                .filter(|a| a.integrity.first_gap.is_some())
+                .count(),
+            accounts,
+        }
+    }
+}
+
+/// State of a background run.
+#[derive(Debug, Clone)]
+enum RunState {
+    Running,
+    Completed(Arc<LedgerIntegrityReport>),
+    Failed,
+}
+
+/// A background run.
+#[derive(Debug, Clone)]
+struct IntegrityRun {
+    organization_id: Uuid,
+    started_at: DateTime<Utc>,
+    finished_at: Option<DateTime<Utc>>,
+    state: RunState,
+}
+
+impl IntegrityRun {
+    fn to_json(&self, job_id: Uuid) -> serde_json::Value {
+        let (status, report) = match &self.state {
+            RunState::Running => ("running", None),
+            RunState::Completed(report) => ("completed", Some(report.as_ref())),
+            RunState::Failed => ("failed", None),
+        };
+        json!({
+            "job_id": job_id,
+            "status": status,
+            "started_at": self.started_at,
+            "finished_at": self.finished_at,
+            "report": report,
+        })
+    }
+}
+
+/// Checks that the user is an owner or admin of the organization.
+async fn require_settings_role(
+    state: &AppState,
+    auth: &AuthMember,
+    org_id: Uuid,
+) -> Result<(), Response> {
+    let role = auth.role_in(state, org_id).await?;
+    if role.can_modify_settings() {
+        return Ok(());
+    }
+
+    Err((
+        StatusCode::FORBIDDEN,
+        Json(json!({
+            "error": "forbidden",
+            "message": "Only owners and admins can verify ledger integrity"
+        })),
+    )
+        .into_response())
+}
+
+fn internal_error() -> Response {
+    (
+        StatusCode::INTERNAL_SERVER_ERROR,
+        Json(json!({
+            "error": "internal_error",
+            "message": "An error occurred"
+        })),
+    )
+        .into_response()
+}
+
+/// GET `/organizations/{org_id}/integrity/ledger` - Verify stored running balances.
+///
+/// With `?async=true` the check runs in the background and the response is
+/// 202 with a job id to poll.
+async fn verify_ledger(
+    State(state): State<AppState>,
+    auth: AuthMember,
+    Path(org_id): Path<Uuid>,
+    Query(query): Query<VerifyLedgerQuery>,
+) -> Response {
+    if let Err(response) = require_settings_role(&state, &auth, org_id).await {
+        return response;
+    }
+
+    let repo = AccountRepository::new((*state.db).clone());
+
+    if !query.run_async {
+        return match repo.verify_ledger_integrity(org_id.into()).await {
+            Ok(accounts) => {
+                let report = LedgerIntegrityReport::new(org_id, accounts);
+                info!(
+                    diverged_accounts = report.diverged_accounts,
+                    entries_checked = report.entries_checked,
+                    "Ledger integrity verified"
+                );
+                (StatusCode::OK, Json(report)).into_response()
+            }
+            Err(e) => {
+                error!(error = %e, "Failed to verify ledger integrity");
+                internal_error()
+            }
+        };
+    }
+
+    let job_id = Uuid::now_v7();
+    let run = IntegrityRun {
+        organization_id: org_id,
+        started_at: Utc::now(),
+        finished_at: None,
+        state: RunState::Running,
+    };
+    RUNS.insert(job_id, run.clone()).await;
+
+    tokio::spawn(async move {
+        let state = match repo.verify_ledger_integrity(org_id.into()).await {
+            Ok(accounts) => {
+                RunState::Completed(Arc::new(LedgerIntegrityReport::new(org_id, accounts)))
+            }
+            Err(e) => {
+                error!(error = %e, %job_id, "Background ledger verification failed");
+                RunState::Failed
+            }
+        };
+        RUNS.insert(
+            job_id,
+            IntegrityRun {
+                finished_at: Some(Utc::now()),
+                state,
+                ..run
+            },
+        )
+        .await;
+    });
+
+    (
+        StatusCode::ACCEPTED,
+        Json(json!({
+            "job_id": job_id,
+            "status": "running",
+            "status_url": format!("/api/v1/organizations/{org_id}/integrity/ledger/jobs/{job_id}"),
+        })),
+    )
+        .into_response()
+}
+
+/// GET `/organizations/{org_id}/integrity/ledger/jobs/{job_id}` - Poll a background check.
+async fn get_verification_job(
+    State(state): State<AppState>,
+    auth: AuthMember,
+    Path((org_id, job_id)): Path<(Uuid, Uuid)>,
+) -> Response {
+    if let Err(response) = require_settings_role(&state, &auth, org_id).await {
+        return response;
+    }
+
+    match RUNS.get(&job_id).await {
+        Some(run) if run.organization_id == org_id => {
+            (StatusCode::OK, Json(run.to_json(job_id))).into_response()
+        }
+        _ => (
+            StatusCode::NOT_FOUND,
+            Json(json!({
+                "error": "not_found",
+                "message": "Verification job not found or expired"
+            })),
+        )
+            .into_response(),
+    }
+}
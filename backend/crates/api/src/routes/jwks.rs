@@ -0,0 +1,19 @@
+//! JSON Web Key Set endpoint.
+//!
+//! Publishes the public halves of asymmetric signing keys so other services
+//! can verify access tokens. HS256 secrets are never exposed.
+
+use axum::{Json, Router, extract::State, routing::get};
+use jsonwebtoken::jwk::JwkSet;
+
+use crate::AppState;
+
+/// GET `/.well-known/jwks.json` - Public verification keys.
+async fn get_jwks(State(state): State<AppState>) -> Json<JwkSet> {
+    Json(state.jwt_service.jwks())
+}
+
+/// Creates the JWKS routes (mounted at the server root, outside `/api/v1`).
+pub fn routes() -> Router<AppState> {
+    Router::new().route("/.well-known/jwks.json", get(get_jwks))
+}
@@ -0,0 +1,393 @@
+//! Ledger entry stream routes.
+//!
+//! Lets analytics tools pull a fiscal period's entries incrementally, flat
+//! rather than grouped by transaction, with cursor pagination.
+
+use axum::{
+    Json, Router,
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    routing::get,
+};
+use sea_orm::ActiveEnum;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use tracing::error;
+use uuid::Uuid;
+
+use crate::{
+    AppState,
+    middleware::AuthMember,
+    routes::transactions::{status_to_string, string_to_status},
+};
+use zeltra_db::{
+    entities::sea_orm_active_enums::TransactionStatus,
+    repositories::{
+        FiscalRepository, LedgerEntryRepository, LedgerStreamFilter, StreamedLedgerEntry,
+    },
+};
+use zeltra_shared::types::Cursor;
+
+/// Entries per page when no limit is given.
+const DEFAULT_LIMIT: u64 = 500;
+
+/// Largest page a client can ask for.
+const MAX_LIMIT: u64 = 1_000;
+
+/// Creates the ledger entry routes.
+pub fn routes() -> Router<AppState> {
+    Router::new().route(
+        "/organizations/{org_id}/ledger-entries",
+        get(list_ledger_entries),
+    )
+}
+
+/// Query parameters for the entry stream.
+#[derive(Debug, Deserialize)]
+pub struct LedgerEntriesQuery {
+    /// Fiscal period to stream.
+    pub period_id: Uuid,
+    /// Cursor from the previous page.
+    pub cursor: Option<String>,
+    /// Entries per page.
+    pub limit: Option<u64>,
+    /// Comma-separated statuses to include besides posted.
+    pub include_status: Option<String>,
+    /// Only entries booked at an overridden exchange rate.
+    #[serde(default)]
+    pub overridden_only: bool,
+}
+
+/// A dimension value on an entry.
+#[derive(Debug, Serialize)]
+pub struct EntryDimensionResponse {
+    /// Dimension value ID.
+    pub id: Uuid,
+    /// Dimension value code.
+    pub code: String,
+}
+
+/// One flattened ledger entry.
+#[derive(Debug, Serialize)]
+pub struct StreamedEntryResponse {
+    /// Entry ID.
+    pub id: Uuid,
+    /// Transaction ID.
+    pub transaction_id: Uuid,
+    /// Transaction date.
+    pub transaction_date: String,
+    /// Transaction reference number.
+    pub reference_number: Option<String>,
+    /// Transaction status.
+    pub status: String,
+    /// Account code.
+    pub account_code: String,
+    /// Account type.
+    pub account_type: String,
+    /// Debit amount.
+    pub debit: String,
+    /// Credit amount.
+    pub credit: String,
+    /// Source currency.
+    pub source_currency: String,
+    /// Amount in the source currency.
+    pub source_amount: String,
+    /// Rate from source to functional currency.
+    pub exchange_rate: String,
+    /// Functional currency.
+    pub functional_currency: String,
+    /// Amount in the functional currency.
+    pub functional_amount: String,
+    /// Whether the exchange rate was entered rather than looked up.
+    pub is_override: bool,
+    /// Dimension values.
+    pub dimensions: Vec<EntryDimensionResponse>,
+    /// When the entry was recorded.
+    pub created_at: String,
+    /// When the entry's transaction was posted.
+    pub posted_at: Option<String>,
+}
+
+impl From<StreamedLedgerEntry> for StreamedEntryResponse {
+    fn from(entry: StreamedLedgerEntry) -> Self {
+        Self {
+            id: entry.id,
+            transaction_id: entry.transaction_id,
+            transaction_date: entry.transaction_date.to_string(),
+            reference_number: entry.reference_number,
+            status: status_to_string(&entry.status),
+            account_code: entry.account_code,
+            account_type: entry.account_type.to_value(),
+            debit: entry.debit.to_string(),
+            credit: entry.credit.to_string(),
+            source_currency: entry.source_currency,
+            source_amount: entry.source_amount.to_string(),
+            exchange_rate: entry.exchange_rate.to_string(),
+            functional_currency: entry.functional_currency,
+            functional_amount: entry.functional_amount.to_string(),
+            is_override: entry.is_override,
+            dimensions: entry
+                .dimensions
+                .into_iter()
+                .map(|d| EntryDimensionResponse {
+                    id: d.id,
+                    code: d.code,
+                })
+                .collect(),
+            created_at: entry.created_at.to_rfc3339(),
+            posted_at: entry.posted_at.map(|at| at.to_rfc3339()),
+        }
+    }
+}
+
+fn bad_request(error: &str, message: &str) -> Response {
+    (
+        StatusCode::BAD_REQUEST,
+        Json(json!({ "error": error, "message": message })),
+    )
+        .into_response()
+}
+
+fn internal_error() -> Response {
+    (
+        StatusCode::INTERNAL_SERVER_ERROR,
+        Json(json!({
+            "error": "internal_error",
+            "message": "An error occurred"
+        })),
+    )
+        .into_response()
+}
+
+/// Posted plus any statuses listed in `include_status`.
+#[allow(clippy::result_large_err)]
+fn parse_statuses(include_status: Option<&str>) -> Result<Vec<TransactionStatus>, Response> {
+    let mut statuses = vec![TransactionStatus::Posted];
+    for name in include_status
+        .unwrap_or_default()
+        .split(',')
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+    {
+        let status = string_to_status(name).ok_or_else(|| {
+            bad_request(
+                "invalid_status",
+                &format!("Unknown transaction status '{name}'"),
+            )
+        })?;
+        if !statuses.contains(&status) {
+            statuses.push(status);
+        }
+    }
+    Ok(statuses)
+}
+
+/// GET `/organizations/{org_id}/ledger-entries` - Stream a fiscal period's entries.
+///
+/// Entries come in the order they were posted, unposted ones by when they
+/// were recorded. Pass `next_cursor` back as `cursor` to get the following
+/// page.
+async fn list_ledger_entries(
+    State(state): State<AppState>,
+    auth: AuthMember,
+    Path(org_id): Path<Uuid>,
+    Query(query): Query<LedgerEntriesQuery>,
+) -> Response {
+    let role = match auth.role_in(&state, org_id).await {
+        Ok(role) => role,
+        Err(response) => return response,
+    };
+    if !role.can_view_reports() {
+        return (
+            StatusCode::FORBIDDEN,
+            Json(json!({
+                "error": "report_access_restricted",
+                "message": "Submitters cannot read the organization ledger"
+            })),
+        )
+            .into_response();
+    }
+
+    let statuses = match parse_statuses(query.include_status.as_deref()) {
+        Ok(statuses) => statuses,
+        Err(response) => return response,
+    };
+    let after = match query.cursor.as_deref().map(Cursor::decode).transpose() {
+        Ok(after) => after,
+        Err(e) => return bad_request("invalid_cursor", &e.to_string()),
+    };
+
+    match FiscalRepository::new((*state.db).clone())
+        .find_period_by_id(query.period_id)
+        .await
+    {
+        Ok(Some(period)) if period.organization_id == org_id => {}
+        Ok(_) => {
+            return (
+                StatusCode::NOT_FOUND,
+                Json(json!({
+                    "error": "not_found",
+                    "message": "Fiscal period not found"
+                })),
+            )
+                .into_response();
+        }
+        Err(e) => {
+            error!(error = %e, "Failed to find fiscal period");
+            return internal_error();
+        }
+    }
+
+    let limit = query.limit.unwrap_or(DEFAULT_LIMIT).clamp(1, MAX_LIMIT);
+    let filter = LedgerStreamFilter {
+        fiscal_period_id: query.period_id,
+        statuses,
+        overridden_only: query.overridden_only,
+        after,
+        limit,
+    };
+
+    match LedgerEntryRepository::new((*state.db).clone())
+        .stream_entries(org_id.into(), filter)
+        .await
+    {
+        Ok(page) => {
+            let entries: Vec<StreamedEntryResponse> =
+                page.entries.into_iter().map(Into::into).collect();
+            (
+                StatusCode::OK,
+                Json(json!({
+                    "entries": entries,
+                    "pagination": {
+                        "limit": limit,
+                        "has_more": page.next_cursor.is_some(),
+                        "next_cursor": page.next_cursor.map(|c| c.encode()),
+                    }
+                })),
+            )
+                .into_response()
+        }
+        Err(e) => {
+            error!(error = %e, "Failed to stream ledger entries");
+            internal_error()
+        }
+    }
+}
+
+#[cfg(test)]
+mod integration_tests {
+    use super::*;
+    use chrono::NaiveDate;
+    use rust_decimal::Decimal;
+    use zeltra_db::entities::sea_orm_active_enums::{AccountType, TransactionType, UserRole};
+    use zeltra_db::repositories::{
+        CreateLedgerEntryInput, CreateTransactionInput, TransactionRepository,
+    };
+    use zeltra_test_support::{Org, OrgFixture, TestDb, access_token, send, test_app_state};
+
+    async fn get_json(state: &AppState, uri: &str, token: &str) -> (StatusCode, serde_json::Value) {
+        let app = state.authenticated(routes());
+        send(app, "GET", uri, token, serde_json::Value::Null).await
+    }
+
+    /// Creates a draft expense in March 2025.
+    async fn create_draft(state: &AppState, org: &Org, reference_number: &str) {
+        let amount = Decimal::new(40, 0);
+        let entry = |account: &str, debit: Decimal, credit: Decimal| CreateLedgerEntryInput {
+            account_id: org.account(account).into_inner(),
+            source_currency: "USD".to_string(),
+            source_amount: amount,
+            exchange_rate: Decimal::ONE,
+            functional_currency: "USD".to_string(),
+            functional_amount: amount,
+            debit,
+            credit,
+            memo: None,
+            dimensions: vec![],
+            rate_override: None,
+            event_at: None,
+        };
+        TransactionRepository::new((*state.db).clone())
+            .create_transaction(CreateTransactionInput {
+                organization_id: org.id.into_inner(),
+                transaction_type: TransactionType::Expense,
+                transaction_date: NaiveDate::from_ymd_opt(2025, 3, 10).unwrap(),
+                description: "Team lunch".to_string(),
+                reference_number: Some(reference_number.to_string()),
+                memo: None,
+                entries: vec![
+                    entry("5000", amount, Decimal::ZERO),
+                    entry("1000", Decimal::ZERO, amount),
+                ],
+                created_by: org.owner.user_id.into_inner(),
+            })
+            .await
+            .expect("Failed to create transaction");
+    }
+
+    #[tokio::test]
+    async fn test_stream_pages_by_cursor_and_filters_status() {
+        let test_db = TestDb::new().await;
+        let state: AppState = test_app_state(&test_db);
+        let org = OrgFixture::new()
+            .with_fiscal_year_2025()
+            .with_accounts(&[("1000", AccountType::Asset), ("5000", AccountType::Expense)])
+            .with_member(UserRole::Submitter)
+            .create(test_db.conn())
+            .await;
+        let march = org.fiscal_year.as_ref().unwrap().periods[2];
+        let token = access_token(&state.jwt_service, org.id, &org.owner);
+        let uri = format!("/organizations/{}/ledger-entries?period_id={march}", org.id);
+        create_draft(&state, &org, "EXP-1").await;
+        create_draft(&state, &org, "EXP-2").await;
+
+        // Drafts are left out by default
+        let (status, body) = get_json(&state, &uri, &token).await;
+        assert_eq!(status, StatusCode::OK);
+        assert!(body["entries"].as_array().unwrap().is_empty());
+        assert_eq!(body["pagination"]["has_more"], false);
+
+        let (status, first) = get_json(
+            &state,
+            &format!("{uri}&include_status=draft&limit=3"),
+            &token,
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        let entries = first["entries"].as_array().unwrap();
+        assert_eq!(entries.len(), 3);
+        assert_eq!(entries[0]["status"], "draft");
+        assert!(
+            entries[0]["reference_number"]
+                .as_str()
+                .unwrap()
+                .starts_with("EXP-")
+        );
+        assert_eq!(entries[0]["dimensions"], json!([]));
+        assert_eq!(first["pagination"]["has_more"], true);
+
+        let cursor = first["pagination"]["next_cursor"].as_str().unwrap();
+        let (_, second) = get_json(
+            &state,
+            &format!("{uri}&include_status=draft&limit=3&cursor={cursor}"),
+            &token,
+        )
+        .await;
+        let rest = second["entries"].as_array().unwrap();
+        assert_eq!(rest.len(), 1);
+        assert!(entries.iter().all(|e| e["id"] != rest[0]["id"]));
+        assert_eq!(second["pagination"]["next_cursor"], serde_json::Value::Null);
+
+        let (status, body) = get_json(&state, &format!("{uri}&cursor=bogus"), &token).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body["error"], "invalid_cursor");
+        let (status, body) = get_json(&state, &format!("{uri}&include_status=paid"), &token).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body["error"], "invalid_status");
+
+        let submitter = access_token(&state.jwt_service, org.id, org.member(&UserRole::Submitter));
+        let (status, _) = get_json(&state, &uri, &submitter).await;
+        assert_eq!(status, StatusCode::FORBIDDEN);
+    }
+}
@@ -0,0 +1,177 @@
+//! Prometheus scrape endpoint.
+//!
+//! Mounted at the server root, outside `/api/v1`. When a metrics token is
+//! configured, scrapes must send it as a bearer token.
+
+use axum::{
+    Json, Router,
+    extract::State,
+    http::{HeaderMap, StatusCode, header},
+    response::{IntoResponse, Response},
+    routing::get,
+};
+use serde_json::json;
+
+use crate::{AppState, middleware::auth::extract_bearer_token};
+
+/// Content type of the Prometheus text exposition format.
+const EXPOSITION_CONTENT_TYPE: &str = "text/plain; version=0.0.4; charset=utf-8";
+
+/// GET `/metrics` - Prometheus exposition of all recorded series.
+async fn get_metrics(State(state): State<AppState>, headers: HeaderMap) -> Response {
+    let Some(metrics) = state.metrics.as_ref() else {
+        return (
+            StatusCode::NOT_FOUND,
+            Json(json!({
+                "error": "metrics_disabled",
+                "message": "Metrics are not exposed on this listener"
+            })),
+        )
+            .into_response();
+    };
+
+    let bearer = headers
+        .get(header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(extract_bearer_token);
+
+    if !metrics.authorize(bearer) {
+        return (
+            StatusCode::UNAUTHORIZED,
+            Json(json!({
+                "error": "unauthorized",
+                "message": "A valid metrics token is required"
+            })),
+        )
+            .into_response();
+    }
+
+    (
+        [(header::CONTENT_TYPE, EXPOSITION_CONTENT_TYPE)],
+        metrics.render(),
+    )
+        .into_response()
+}
+
+/// Creates the metrics routes (mounted at the server root, outside `/api/v1`).
+pub fn routes() -> Router<AppState> {
+    Router::new().route("/metrics", get(get_metrics))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use axum::{
+        body::{Body, to_bytes},
+        http::Request,
+    };
+    use metrics_exporter_prometheus::PrometheusRecorder;
+    use sea_orm::DatabaseConnection;
+    use tower::ServiceExt;
+    use uuid::Uuid;
+    use zeltra_db::repositories::Stores;
+    use zeltra_shared::{AdminConfig, EmailConfig, EmailService, JwtConfig, JwtService};
+
+    use super::*;
+    use crate::{
+        cache::{DashboardCache, MembershipCache},
+        create_router,
+        middleware::{BodyLimits, Metrics, metrics::prometheus_builder},
+    };
+
+    fn test_state(recorder: &PrometheusRecorder, token: Option<&str>) -> AppState {
+        AppState {
+            db: Arc::new(DatabaseConnection::Disconnected),
+            jwt_service: Arc::new(JwtService::new(JwtConfig::default())),
+            email_service: Arc::new(EmailService::new(EmailConfig::default())),
+            storage: None,
+            metrics: Some(Arc::new(Metrics::new(
+                recorder.handle(),
+                token.map(str::to_string),
+            ))),
+            jobs: None,
+            admin: AdminConfig::default(),
+            dashboard_cache: DashboardCache::default(),
+            membership_cache: MembershipCache::default(),
+            currencies: Arc::default(),
+            stores: Stores::postgres(&DatabaseConnection::Disconnected),
+        }
+    }
+
+    async fn get(router: Router, uri: &str, token: Option<&str>) -> (StatusCode, String) {
+        let mut request = Request::builder().uri(uri);
+        if let Some(token) = token {
+            request = request.header(header::AUTHORIZATION, format!("Bearer {token}"));
+        }
+        let response = router
+            .oneshot(request.body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        let status = response.status();
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        (status, String::from_utf8(body.to_vec()).unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_metrics_record_requests_by_route_template() {
+        let recorder = prometheus_builder().unwrap().build_recorder();
+        let router = create_router(test_state(&recorder, None), BodyLimits::default());
+        let _guard = metrics::set_default_local_recorder(&recorder);
+
+        let (status, _) = get(router.clone(), "/api/v1/health", None).await;
+        assert_eq!(status, StatusCode::OK);
+        let accounts = format!("/api/v1/organizations/{}/accounts", Uuid::new_v4());
+        let (status, _) = get(router.clone(), &accounts, None).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+        let (status, body) = get(router, "/metrics", None).await;
+
+        assert_eq!(status, StatusCode::OK);
+        assert!(body.contains(
+            r#"http_requests_total{method="GET",route="/api/v1/health",status="200"} 1"#
+        ));
+        assert!(body.contains(
+            r#"http_requests_total{method="GET",route="/api/v1/organizations/{org_id}/accounts",status="401"} 1"#
+        ));
+        assert!(body.contains(
+            r#"http_request_duration_seconds_bucket{method="GET",route="/api/v1/health",status="200",le="10"} 1"#
+        ));
+        assert!(!body.contains(&accounts));
+    }
+
+    #[tokio::test]
+    async fn test_metrics_require_configured_token() {
+        let recorder = prometheus_builder().unwrap().build_recorder();
+        let router = create_router(
+            test_state(&recorder, Some("scrape-secret")),
+            BodyLimits::default(),
+        );
+
+        let (status, _) = get(router.clone(), "/metrics", None).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+        let (status, _) = get(router.clone(), "/metrics", Some("wrong")).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+        let (status, _) = get(router, "/metrics", Some("scrape-secret")).await;
+        assert_eq!(status, StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_metrics_disabled_returns_not_found() {
+        let recorder = prometheus_builder().unwrap().build_recorder();
+        let state = AppState {
+            metrics: None,
+            ..test_state(&recorder, None)
+        };
+
+        let (status, _) = get(
+            create_router(state, BodyLimits::default()),
+            "/metrics",
+            None,
+        )
+        .await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+    }
+}
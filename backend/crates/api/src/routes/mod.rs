@@ -1,13 +1,31 @@
 //! API route definitions.
 
-use axum::{Router, middleware};
+use axum::{
+    Json, Router,
+    http::StatusCode,
+    middleware,
+    response::{IntoResponse, Response},
+};
+use sea_orm::ActiveEnum;
+use serde_json::json;
+use zeltra_db::repositories::LimitCheckResult;
+use zeltra_shared::types::UnknownSortField;
 
-use crate::{AppState, middleware::auth::auth_middleware};
+use crate::{
+    AppState,
+    middleware::{BodyLimits, auth::auth_middleware, limit_body, require_admin},
+};
 
 pub mod accounts;
+pub mod admin;
+pub mod api_keys;
+pub mod approval_delegations;
 pub mod approval_rules;
 pub mod attachments;
 pub mod auth;
+pub mod backup;
+pub mod bank_import;
+pub mod budget_comments;
 pub mod budgets;
 pub mod currencies;
 pub mod dashboard;
@@ -15,10 +33,21 @@ pub mod dimensions;
 pub mod exchange_rates;
 pub mod fiscal;
 pub mod health;
+pub mod integrity;
+pub mod jwks;
+pub mod ledger_entries;
+pub mod metrics;
+pub mod notifications;
 pub mod organizations;
+pub mod reconciliations;
+pub mod report_definitions;
+pub mod report_mappings;
 pub mod reports;
 pub mod simulation;
+pub mod transaction_comments;
+pub mod transaction_templates;
 pub mod transactions;
+pub mod transfers;
 
 /// Creates the API router with all routes.
 pub fn api_routes() -> Router<AppState> {
@@ -26,31 +55,91 @@ pub fn api_routes() -> Router<AppState> {
 }
 
 /// Creates the API router with protected routes that need state for middleware.
+///
+/// Import endpoints get the larger body limits; everything else the default.
 #[allow(clippy::needless_pass_by_value)]
-pub fn api_routes_with_state(state: AppState) -> Router<AppState> {
+pub fn api_routes_with_state(state: AppState, limits: BodyLimits) -> Router<AppState> {
     // Protected routes that require authentication
-    let protected_routes = Router::new()
+    let standard_routes = Router::new()
+        .merge(auth::profile_routes())
         .merge(organizations::routes())
         .merge(fiscal::routes())
         .merge(accounts::routes())
+        .merge(ledger_entries::routes())
         .merge(dimensions::routes())
         .merge(exchange_rates::routes())
         .merge(currencies::routes())
         .merge(transactions::routes())
+        .merge(transaction_comments::routes())
+        .merge(transaction_templates::routes())
+        .merge(transfers::routes())
+        .merge(reconciliations::routes())
         .merge(approval_rules::routes())
+        .merge(approval_delegations::routes())
+        .merge(api_keys::routes())
         .merge(budgets::routes())
+        .merge(budget_comments::routes())
         .merge(reports::routes())
+        .merge(report_definitions::routes())
+        .merge(report_mappings::routes())
         .merge(simulation::routes())
         .merge(dashboard::routes())
+        .merge(notifications::routes())
         .merge(attachments::routes())
+        .merge(integrity::routes())
+        .merge(backup::export_routes());
+
+    let protected_routes = limit_body(standard_routes, limits.default_bytes)
+        .merge(limit_body(bank_import::routes(), limits.import_bytes))
+        .merge(limit_body(backup::import_routes(), limits.backup_bytes))
         .layer(middleware::from_fn_with_state(
             state.clone(),
             auth_middleware,
         ));
 
-    // Combine public and protected routes
-    Router::new()
-        .merge(health::routes())
-        .merge(auth::routes())
+    // Operator routes authenticate on their own terms
+    let admin_routes = limit_body(admin::routes(), limits.default_bytes)
+        .layer(middleware::from_fn_with_state(state.clone(), require_admin));
+
+    let public_routes = Router::new().merge(health::routes()).merge(auth::routes());
+
+    // Combine public, protected and admin routes
+    limit_body(public_routes, limits.default_bytes)
         .merge(protected_routes)
+        .merge(admin_routes)
+}
+
+/// 400 response for a `sort` parameter outside the endpoint's whitelist.
+pub(crate) fn invalid_sort_response(err: &UnknownSortField) -> Response {
+    (
+        StatusCode::BAD_REQUEST,
+        Json(json!({
+            "error": "invalid_sort",
+            "message": err.to_string(),
+            "allowed": err.allowed,
+        })),
+    )
+        .into_response()
+}
+
+/// 402 response for a create that the organization's plan does not allow.
+pub(crate) fn tier_limit_response(limit: &LimitCheckResult) -> Response {
+    let upgrade_tiers: Vec<String> = limit
+        .upgrade_tiers
+        .iter()
+        .map(ActiveEnum::to_value)
+        .collect();
+    (
+        StatusCode::PAYMENT_REQUIRED,
+        Json(json!({
+            "error": "tier_limit_exceeded",
+            "message": limit.message.as_deref().unwrap_or("Tier limit exceeded"),
+            "resource": limit.resource.as_str(),
+            "current": limit.current,
+            "limit": limit.limit,
+            "remaining": limit.remaining,
+            "upgrade_tiers": upgrade_tiers,
+        })),
+    )
+        .into_response()
 }
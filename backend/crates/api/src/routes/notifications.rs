@@ -0,0 +1,553 @@
+//! Notification routes: the in-app inbox and delivery preferences.
+//!
+//! Members read their own notifications per organization; preferences are
+//! per user and decide, for each notification type, whether it arrives by
+//! email, in the inbox, or both. Workflow handlers call the `notify_*`
+//! helpers here after a successful transition.
+
+use axum::{
+    Json, Router,
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    routing::{get, post},
+};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::collections::BTreeMap;
+use tracing::{Instrument, error, info, warn};
+use uuid::Uuid;
+
+use crate::{
+    AppState,
+    middleware::{AuthUser, auth::check_membership},
+};
+use zeltra_core::notification::{Delivery, NotificationType};
+use zeltra_db::{
+    OrganizationRepository, UserRepository,
+    entities::{notifications, transactions},
+    repositories::{
+        CommentWithAuthor, EscalationRecipient, NotificationRepoError, NotificationRepository,
+        WorkflowRepository,
+    },
+};
+use zeltra_shared::types::{OrganizationId, TransactionId};
+
+/// Creates the notification routes.
+pub fn routes() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/auth/me/notification-preferences",
+            get(get_preferences).patch(update_preferences),
+        )
+        .route(
+            "/organizations/{org_id}/notifications",
+            get(list_notifications),
+        )
+        .route(
+            "/organizations/{org_id}/notifications/read-all",
+            post(mark_all_read),
+        )
+        .route(
+            "/organizations/{org_id}/notifications/{notification_id}/read",
+            post(mark_read),
+        )
+}
+
+// ============================================================================
+// Request/Response Types
+// ============================================================================
+
+/// Query parameters for listing notifications.
+#[derive(Debug, Deserialize)]
+pub struct ListNotificationsQuery {
+    /// Page number (1-indexed, default: 1).
+    pub page: Option<u64>,
+    /// Number of notifications per page (default: 20, max: 100).
+    pub limit: Option<u64>,
+}
+
+/// Response for a notification.
+#[derive(Debug, Serialize)]
+pub struct NotificationResponse {
+    /// Notification ID.
+    pub id: Uuid,
+    /// Notification type, e.g. `approval_requested`.
+    #[serde(rename = "type")]
+    pub notification_type: String,
+    /// Details of what happened; the fields depend on the type.
+    pub payload: serde_json::Value,
+    /// Whether the notification has been read.
+    pub read: bool,
+    /// When it was first marked read.
+    pub read_at: Option<String>,
+    /// Created at timestamp.
+    pub created_at: String,
+}
+
+/// Request body for updating notification preferences: notification type
+/// to `email`, `in_app` or `both`. Types left out are unchanged.
+pub type UpdatePreferencesRequest = BTreeMap<String, String>;
+
+// ============================================================================
+// Route Handlers
+// ============================================================================
+
+/// GET `/auth/me/notification-preferences` - Get delivery preferences.
+async fn get_preferences(State(state): State<AppState>, auth: AuthUser) -> impl IntoResponse {
+    let repo = NotificationRepository::new((*state.db).clone());
+
+    match repo.preferences(auth.user_id()).await {
+        Ok(preferences) => (StatusCode::OK, Json(preferences_json(&preferences))).into_response(),
+        Err(e) => notification_error_response(&e.into()),
+    }
+}
+
+/// PATCH `/auth/me/notification-preferences` - Update delivery preferences.
+async fn update_preferences(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Json(payload): Json<UpdatePreferencesRequest>,
+) -> impl IntoResponse {
+    let mut changes = Vec::with_capacity(payload.len());
+    for (notification_type, delivery) in &payload {
+        let parsed = notification_type
+            .parse::<NotificationType>()
+            .and_then(|t| delivery.parse::<Delivery>().map(|d| (t, d)));
+        match parsed {
+            Ok(change) => changes.push(change),
+            Err(e) => {
+                return (
+                    StatusCode::BAD_REQUEST,
+                    Json(json!({
+                        "error": "invalid_preference",
+                        "message": e.to_string()
+                    })),
+                )
+                    .into_response();
+            }
+        }
+    }
+
+    let repo = NotificationRepository::new((*state.db).clone());
+
+    match repo.set_preferences(auth.user_id(), &changes).await {
+        Ok(preferences) => {
+            info!(user_id = %auth.user_id(), "Notification preferences updated");
+            (StatusCode::OK, Json(preferences_json(&preferences))).into_response()
+        }
+        Err(e) => notification_error_response(&e.into()),
+    }
+}
+
+/// GET `/organizations/{org_id}/notifications` - List the caller's
+/// notifications, unread first.
+async fn list_notifications(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(org_id): Path<OrganizationId>,
+    Query(query): Query<ListNotificationsQuery>,
+) -> impl IntoResponse {
+    if let Err(response) = check_membership(&state, org_id, auth.user_id()).await {
+        return response;
+    }
+
+    let page = query.page.unwrap_or(1).max(1);
+    let limit = query.limit.unwrap_or(20).clamp(1, 100);
+    let repo = NotificationRepository::new((*state.db).clone());
+
+    match repo
+        .list(org_id.into_inner(), auth.user_id(), page, limit)
+        .await
+    {
+        Ok(result) => {
+            let items: Vec<NotificationResponse> = result
+                .notifications
+                .iter()
+                .map(notification_to_response)
+                .collect();
+            let total_pages = if result.total == 0 {
+                1
+            } else {
+                result.total.div_ceil(limit)
+            };
+
+            (
+                StatusCode::OK,
+                Json(json!({
+                    "data": items,
+                    "unread_count": result.unread,
+                    "pagination": {
+                        "total": result.total,
+                        "page": page,
+                        "limit": limit,
+                        "total_pages": total_pages
+                    }
+                })),
+            )
+                .into_response()
+        }
+        Err(e) => notification_error_response(&e.into()),
+    }
+}
+
+/// POST `/organizations/{org_id}/notifications/{notification_id}/read` -
+/// Mark a notification read.
+async fn mark_read(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path((org_id, notification_id)): Path<(OrganizationId, Uuid)>,
+) -> impl IntoResponse {
+    if let Err(response) = check_membership(&state, org_id, auth.user_id()).await {
+        return response;
+    }
+
+    let repo = NotificationRepository::new((*state.db).clone());
+
+    match repo
+        .mark_read(org_id.into_inner(), auth.user_id(), notification_id)
+        .await
+    {
+        Ok(notification) => (
+            StatusCode::OK,
+            Json(notification_to_response(&notification)),
+        )
+            .into_response(),
+        Err(e) => notification_error_response(&e),
+    }
+}
+
+/// POST `/organizations/{org_id}/notifications/read-all` - Mark every
+/// notification read.
+async fn mark_all_read(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(org_id): Path<OrganizationId>,
+) -> impl IntoResponse {
+    if let Err(response) = check_membership(&state, org_id, auth.user_id()).await {
+        return response;
+    }
+
+    let repo = NotificationRepository::new((*state.db).clone());
+
+    match repo
+        .mark_all_read(org_id.into_inner(), auth.user_id())
+        .await
+    {
+        Ok(updated) => (StatusCode::OK, Json(json!({ "updated": updated }))).into_response(),
+        Err(e) => notification_error_response(&e.into()),
+    }
+}
+
+// ============================================================================
+// Workflow Notifications
+// ============================================================================
+
+/// Notifies the members who can approve a newly submitted transaction.
+///
+/// Failures are logged; the submission has already succeeded.
+pub(crate) async fn notify_approval_requested(
+    state: &AppState,
+    org_id: OrganizationId,
+    transaction: &transactions::Model,
+) {
+    let workflow_repo = WorkflowRepository::new((*state.db).clone());
+    let approvers = match workflow_repo
+        .find_eligible_approvers(org_id, TransactionId::from(transaction.id))
+        .await
+    {
+        Ok(approvers) => approvers,
+        Err(e) => {
+            warn!(error = %e, transaction_id = %transaction.id, "Failed to find approvers to notify");
+            return;
+        }
+    };
+    let payload = transaction_payload(
+        transaction,
+        json!({ "submitted_by": transaction.submitted_by }),
+    );
+
+    let Some(email_to) = notify(
+        state,
+        org_id,
+        NotificationType::ApprovalRequested,
+        payload,
+        &approvers,
+    )
+    .await
+    else {
+        return;
+    };
+
+    let label = transaction_label(transaction);
+    let (email, organization_name) = (state.email_service.clone(), email_to.organization_name);
+    let send = async move {
+        for approver in email_to.recipients {
+            if let Err(e) = email
+                .send_approval_requested_email(
+                    &approver.email,
+                    &approver.full_name,
+                    &organization_name,
+                    &label,
+                )
+                .await
+            {
+                warn!(user_id = %approver.user_id, error = %e, "Failed to send approval request email");
+            }
+        }
+    };
+    tokio::spawn(send.in_current_span());
+}
+
+/// Notifies the submitter that their transaction was rejected.
+///
+/// Failures are logged; the rejection has already succeeded.
+pub(crate) async fn notify_transaction_rejected(
+    state: &AppState,
+    org_id: OrganizationId,
+    transaction: &transactions::Model,
+    rejected_by: Uuid,
+    reason: &str,
+) {
+    let submitter_id = transaction.submitted_by.unwrap_or(transaction.created_by);
+    if submitter_id == rejected_by {
+        return;
+    }
+    let submitter = match UserRepository::new((*state.db).clone())
+        .find_by_id(submitter_id)
+        .await
+    {
+        Ok(Some(user)) if user.is_active => EscalationRecipient {
+            user_id: user.id,
+            email: user.email,
+            full_name: user.full_name,
+        },
+        Ok(_) => return,
+        Err(e) => {
+            warn!(error = %e, transaction_id = %transaction.id, "Failed to find submitter to notify");
+            return;
+        }
+    };
+    let payload = transaction_payload(
+        transaction,
+        json!({ "rejected_by": rejected_by, "reason": reason }),
+    );
+
+    let Some(email_to) = notify(
+        state,
+        org_id,
+        NotificationType::TransactionRejected,
+        payload,
+        std::slice::from_ref(&submitter),
+    )
+    .await
+    else {
+        return;
+    };
+
+    let label = transaction_label(transaction);
+    let (email, organization_name, reason) = (
+        state.email_service.clone(),
+        email_to.organization_name,
+        reason.to_string(),
+    );
+    let send = async move {
+        for submitter in email_to.recipients {
+            if let Err(e) = email
+                .send_transaction_rejected_email(
+                    &submitter.email,
+                    &submitter.full_name,
+                    &organization_name,
+                    &label,
+                    &reason,
+                )
+                .await
+            {
+                warn!(user_id = %submitter.user_id, error = %e, "Failed to send rejection email");
+            }
+        }
+    };
+    tokio::spawn(send.in_current_span());
+}
+
+/// Notifies members mentioned in a transaction comment.
+///
+/// Failures are logged; the comment has already been posted.
+pub(crate) async fn notify_comment_mention(
+    state: &AppState,
+    org_id: OrganizationId,
+    transaction: &transactions::Model,
+    comment: &CommentWithAuthor,
+    mentioned: &[EscalationRecipient],
+) {
+    let payload = transaction_payload(
+        transaction,
+        json!({
+            "comment_id": comment.comment.id,
+            "author_id": comment.comment.author_id,
+            "author_name": comment.author_name,
+        }),
+    );
+
+    let Some(email_to) = notify(
+        state,
+        org_id,
+        NotificationType::CommentMention,
+        payload,
+        mentioned,
+    )
+    .await
+    else {
+        return;
+    };
+
+    let label = transaction_label(transaction);
+    let (email, organization_name, author_name, body) = (
+        state.email_service.clone(),
+        email_to.organization_name,
+        comment.author_name.clone(),
+        comment.comment.body.clone(),
+    );
+    let send = async move {
+        for member in email_to.recipients {
+            if let Err(e) = email
+                .send_comment_mention_email(
+                    &member.email,
+                    &member.full_name,
+                    &organization_name,
+                    &label,
+                    &author_name,
+                    &body,
+                )
+                .await
+            {
+                warn!(user_id = %member.user_id, error = %e, "Failed to send mention email");
+            }
+        }
+    };
+    tokio::spawn(send.in_current_span());
+}
+
+/// Who to email after the in-app notifications are written.
+struct EmailRecipients {
+    organization_name: String,
+    recipients: Vec<EscalationRecipient>,
+}
+
+/// Writes the in-app notifications and picks out the recipients who also
+/// want an email. Returns `None` when nothing is left to send or on error.
+async fn notify(
+    state: &AppState,
+    org_id: OrganizationId,
+    notification_type: NotificationType,
+    payload: serde_json::Value,
+    contacts: &[EscalationRecipient],
+) -> Option<EmailRecipients> {
+    let recipients: Vec<Uuid> = contacts.iter().map(|c| c.user_id).collect();
+    let repo = NotificationRepository::new((*state.db).clone());
+    let outcome = match repo
+        .notify(org_id.into_inner(), notification_type, payload, &recipients)
+        .await
+    {
+        Ok(outcome) => outcome,
+        Err(e) => {
+            warn!(error = %e, notification_type = %notification_type, "Failed to write notifications");
+            return None;
+        }
+    };
+    if outcome.email_recipients.is_empty() {
+        return None;
+    }
+
+    let organization_name = match OrganizationRepository::new((*state.db).clone())
+        .find_by_id(org_id.into_inner())
+        .await
+    {
+        Ok(Some(org)) => org.name,
+        Ok(None) => return None,
+        Err(e) => {
+            warn!(error = %e, "Failed to load organization for notification email");
+            return None;
+        }
+    };
+
+    Some(EmailRecipients {
+        organization_name,
+        recipients: contacts
+            .iter()
+            .filter(|c| outcome.email_recipients.contains(&c.user_id))
+            .cloned()
+            .collect(),
+    })
+}
+
+// ============================================================================
+// Helper Functions
+// ============================================================================
+
+/// The reference number, or the description when there is none.
+fn transaction_label(transaction: &transactions::Model) -> String {
+    transaction
+        .reference_number
+        .clone()
+        .unwrap_or_else(|| transaction.description.clone())
+}
+
+/// Payload naming the transaction, merged with `extra`.
+fn transaction_payload(
+    transaction: &transactions::Model,
+    extra: serde_json::Value,
+) -> serde_json::Value {
+    let mut payload = json!({
+        "transaction_id": transaction.id,
+        "reference_number": transaction.reference_number,
+        "description": transaction.description,
+    });
+    if let (Some(payload), serde_json::Value::Object(extra)) = (payload.as_object_mut(), extra) {
+        payload.extend(extra);
+    }
+    payload
+}
+
+fn preferences_json(preferences: &[(NotificationType, Delivery)]) -> serde_json::Value {
+    let preferences: serde_json::Map<String, serde_json::Value> = preferences
+        .iter()
+        .map(|(t, d)| (t.as_str().to_string(), json!(d.as_str())))
+        .collect();
+    json!({ "preferences": preferences })
+}
+
+fn notification_to_response(notification: &notifications::Model) -> NotificationResponse {
+    NotificationResponse {
+        id: notification.id,
+        notification_type: notification.notification_type.clone(),
+        payload: notification.payload.clone(),
+        read: notification.read_at.is_some(),
+        read_at: notification.read_at.map(|t| t.to_rfc3339()),
+        created_at: notification.created_at.to_rfc3339(),
+    }
+}
+
+fn notification_error_response(e: &NotificationRepoError) -> Response {
+    match e {
+        NotificationRepoError::NotFound(_) => (
+            StatusCode::NOT_FOUND,
+            Json(json!({
+                "error": "not_found",
+                "message": e.to_string()
+            })),
+        )
+            .into_response(),
+        NotificationRepoError::Database(db_error) => {
+            error!(error = %db_error, "Database error in notifications");
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({
+                    "error": "internal_error",
+                    "message": "An error occurred"
+                })),
+            )
+                .into_response()
+        }
+    }
+}
@@ -10,14 +10,25 @@ use axum::{
 use serde_json::json;
 use tracing::{error, info};
 
-use crate::{AppState, middleware::AuthUser};
+use crate::{
+    AppState,
+    middleware::{AuthUser, with_last_modified},
+    routes::tier_limit_response,
+};
+use zeltra_core::settings::{FieldError, SettingsError};
 use zeltra_db::repositories::organization::OrganizationError;
+use zeltra_db::repositories::subscription::{Feature, SubscriptionRepository};
+use zeltra_db::repositories::{
+    BackupError, BackupRepository, BootstrapSummary, ChartTemplate, CloneOrganizationInput,
+    CloneSections, OrganizationBootstrap,
+};
 use zeltra_db::{
     OrganizationRepository, SessionRepository, UserRepository,
     entities::sea_orm_active_enums::UserRole,
 };
 use zeltra_shared::auth::{
-    AddUserRequest, CreateOrganizationRequest, UpdateMemberRequest, UpdateOrganizationRequest,
+    AddUserRequest, CloneOrganizationRequest, CreateOrganizationRequest, UpdateMemberRequest,
+    UpdateOrganizationRequest,
 };
 
 /// Creates the organizations router (requires auth middleware to be applied externally).
@@ -26,6 +37,9 @@ pub fn routes() -> Router<AppState> {
         .route("/organizations", post(create_organization))
         .route("/organizations/{org_id}", get(get_organization))
         .route("/organizations/{org_id}", patch(update_organization))
+        .route("/organizations/{org_id}/clone", post(clone_organization))
+        .route("/organizations/{org_id}/settings", get(get_settings))
+        .route("/organizations/{org_id}/settings", patch(update_settings))
         .route("/organizations/{org_id}/users", get(list_users))
         .route("/organizations/{org_id}/users", post(add_user))
         .route(
@@ -39,6 +53,7 @@ pub fn routes() -> Router<AppState> {
 }
 
 /// POST /organizations - Create a new organization.
+#[allow(clippy::too_many_lines)]
 async fn create_organization(
     State(state): State<AppState>,
     auth: AuthUser,
@@ -46,6 +61,41 @@ async fn create_organization(
 ) -> impl IntoResponse {
     let org_repo = OrganizationRepository::new((*state.db).clone());
 
+    match org_repo
+        .validate_new_organization(
+            &payload.name,
+            &payload.slug,
+            &payload.base_currency,
+            &payload.timezone,
+        )
+        .await
+    {
+        Ok(mut fields) => {
+            if let Some(code) = &payload.bootstrap.chart_of_accounts
+                && ChartTemplate::from_code(code).is_none()
+            {
+                fields.push(FieldError::new(
+                    "bootstrap.chart_of_accounts",
+                    format!("Unknown chart of accounts template: {code}"),
+                ));
+            }
+            if !fields.is_empty() {
+                return validation_failed_response(&fields);
+            }
+        }
+        Err(e) => {
+            error!(error = %e, "Database error validating organization");
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({
+                    "error": "internal_error",
+                    "message": "An error occurred"
+                })),
+            )
+                .into_response();
+        }
+    }
+
     // Check if slug is available
     match org_repo.slug_exists(&payload.slug).await {
         Ok(true) => {
@@ -72,18 +122,41 @@ async fn create_organization(
         }
     }
 
-    // Create organization with current user as owner
-    let org = match org_repo
-        .create_with_owner(
+    let bootstrap = OrganizationBootstrap {
+        fiscal_year: payload.bootstrap.fiscal_year,
+        chart_template: payload
+            .bootstrap
+            .chart_of_accounts
+            .as_deref()
+            .and_then(ChartTemplate::from_code),
+        dimension_types: payload.bootstrap.dimension_types,
+    };
+
+    // Create organization with current user as owner, plus anything
+    // requested, in one database transaction
+    let (org, summary) = match org_repo
+        .create_bootstrapped(
             &payload.name,
             &payload.slug,
             &payload.base_currency,
             &payload.timezone,
             auth.user_id(),
+            bootstrap,
         )
         .await
     {
-        Ok(o) => o,
+        Ok(created) => created,
+        Err(OrganizationError::BootstrapFailed { step, message }) => {
+            error!(step, error = %message, "Failed to bootstrap organization");
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({
+                    "error": "bootstrap_failed",
+                    "message": format!("Could not set up {step}; the organization was not created")
+                })),
+            )
+                .into_response();
+        }
         Err(e) => {
             error!(error = %e, "Failed to create organization");
             return (
@@ -101,6 +174,8 @@ async fn create_organization(
         org_id = %org.id,
         slug = %org.slug,
         owner_id = %auth.user_id(),
+        accounts = summary.accounts.len(),
+        dimension_types = summary.dimension_types.len(),
         "Organization created"
     );
 
@@ -114,7 +189,234 @@ async fn create_organization(
             "timezone": org.timezone,
             "subscription_tier": format!("{:?}", org.subscription_tier).to_lowercase(),
             "subscription_status": format!("{:?}", org.subscription_status).to_lowercase(),
-            "created_at": org.created_at
+            "created_at": org.created_at,
+            "bootstrap": bootstrap_summary_json(&summary)
+        })),
+    )
+        .into_response()
+}
+
+/// What bootstrapping created, for the create response.
+fn bootstrap_summary_json(summary: &BootstrapSummary) -> serde_json::Value {
+    json!({
+        "fiscal_year": summary.fiscal_year.as_ref().map(|fy| json!({
+            "id": fy.fiscal_year.id,
+            "name": fy.fiscal_year.name,
+            "start_date": fy.fiscal_year.start_date,
+            "end_date": fy.fiscal_year.end_date,
+            "periods": fy.periods.len()
+        })),
+        "accounts": summary.accounts.iter().map(|a| json!({
+            "id": a.id,
+            "code": a.code,
+            "name": a.name
+        })).collect::<Vec<_>>(),
+        "dimension_types": summary.dimension_types.iter().map(|d| json!({
+            "id": d.id,
+            "code": d.code,
+            "name": d.name
+        })).collect::<Vec<_>>()
+    })
+}
+
+/// POST `/organizations/{org_id}/clone` - Create a sandbox copy of an organization's configuration.
+///
+/// Owner only, on plans with multi-entity support. The caller becomes the
+/// sole owner of the copy; transactions and other members are left behind.
+#[allow(clippy::too_many_lines)]
+async fn clone_organization(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(org_id): Path<uuid::Uuid>,
+    Json(payload): Json<CloneOrganizationRequest>,
+) -> impl IntoResponse {
+    let org_repo = OrganizationRepository::new((*state.db).clone());
+    let user_id = auth.user_id();
+
+    match org_repo.has_role(org_id, user_id, UserRole::Owner).await {
+        Ok(true) => {}
+        Ok(false) => {
+            return (
+                StatusCode::FORBIDDEN,
+                Json(json!({
+                    "error": "forbidden",
+                    "message": "Only the organization owner can clone it"
+                })),
+            )
+                .into_response();
+        }
+        Err(e) => {
+            error!(error = %e, "Database error checking role");
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({
+                    "error": "internal_error",
+                    "message": "An error occurred"
+                })),
+            )
+                .into_response();
+        }
+    }
+
+    match SubscriptionRepository::has_feature(&state.db, org_id, Feature::MultiEntity).await {
+        Ok(true) => {}
+        Ok(false) => {
+            return (
+                StatusCode::FORBIDDEN,
+                Json(json!({
+                    "error": "feature_not_available",
+                    "message": "Organization clones are not included in the organization's plan"
+                })),
+            )
+                .into_response();
+        }
+        Err(e) => {
+            error!(error = %e, "Database error checking multi-entity access");
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({
+                    "error": "internal_error",
+                    "message": "An error occurred"
+                })),
+            )
+                .into_response();
+        }
+    }
+
+    let source = match org_repo.find_by_id(org_id).await {
+        Ok(Some(org)) => org,
+        Ok(None) => {
+            return (
+                StatusCode::NOT_FOUND,
+                Json(json!({
+                    "error": "not_found",
+                    "message": "Organization not found"
+                })),
+            )
+                .into_response();
+        }
+        Err(e) => {
+            error!(error = %e, "Database error fetching organization");
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({
+                    "error": "internal_error",
+                    "message": "An error occurred"
+                })),
+            )
+                .into_response();
+        }
+    };
+
+    let sections = CloneSections {
+        accounts: payload.copy.accounts,
+        dimensions: payload.copy.dimensions,
+        fiscal_calendar: payload.copy.fiscal_calendar,
+        approval_rules: payload.copy.approval_rules,
+        budgets: payload.copy.budgets,
+        settings: payload.copy.settings,
+    };
+
+    match org_repo
+        .validate_new_organization(
+            &payload.name,
+            &payload.slug,
+            &source.base_currency,
+            &source.timezone,
+        )
+        .await
+    {
+        Ok(fields) if !fields.is_empty() => return validation_failed_response(&fields),
+        Ok(_) => {}
+        Err(e) => {
+            error!(error = %e, "Database error validating organization");
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({
+                    "error": "internal_error",
+                    "message": "An error occurred"
+                })),
+            )
+                .into_response();
+        }
+    }
+
+    match org_repo.slug_exists(&payload.slug).await {
+        Ok(true) => {
+            return (
+                StatusCode::CONFLICT,
+                Json(json!({
+                    "error": "slug_exists",
+                    "message": "An organization with this slug already exists"
+                })),
+            )
+                .into_response();
+        }
+        Ok(false) => {}
+        Err(e) => {
+            error!(error = %e, "Database error checking slug");
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({
+                    "error": "internal_error",
+                    "message": "An error occurred"
+                })),
+            )
+                .into_response();
+        }
+    }
+
+    let input = CloneOrganizationInput {
+        name: payload.name,
+        slug: payload.slug,
+        sections,
+    };
+    let summary = match BackupRepository::new((*state.db).clone())
+        .clone_organization(org_id.into(), user_id, input)
+        .await
+    {
+        Ok(summary) => summary,
+        Err(e @ BackupError::MissingDependency { section, .. }) => {
+            return validation_failed_response(&[FieldError::new(
+                format!("copy.{section}"),
+                e.to_string(),
+            )]);
+        }
+        Err(e) => {
+            error!(error = %e, "Failed to clone organization");
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({
+                    "error": "internal_error",
+                    "message": "An error occurred cloning the organization"
+                })),
+            )
+                .into_response();
+        }
+    };
+
+    let org = summary.organization;
+    info!(
+        source_id = %org_id,
+        org_id = %org.id,
+        owner_id = %user_id,
+        counts = ?summary.counts,
+        "Organization cloned"
+    );
+
+    (
+        StatusCode::CREATED,
+        Json(json!({
+            "id": org.id,
+            "name": org.name,
+            "slug": org.slug,
+            "base_currency": org.base_currency,
+            "timezone": org.timezone,
+            "subscription_tier": format!("{:?}", org.subscription_tier).to_lowercase(),
+            "subscription_status": format!("{:?}", org.subscription_status).to_lowercase(),
+            "created_at": org.created_at,
+            "source_organization_id": org_id,
+            "counts": summary.counts
         })),
     )
         .into_response()
@@ -180,7 +482,7 @@ async fn get_organization(
         }
     };
 
-    (
+    let response = (
         StatusCode::OK,
         Json(json!({
             "id": org.id,
@@ -190,14 +492,15 @@ async fn get_organization(
             "timezone": org.timezone,
             "subscription_tier": format!("{:?}", org.subscription_tier).to_lowercase(),
             "subscription_status": format!("{:?}", org.subscription_status).to_lowercase(),
-            "created_at": org.created_at
+            "created_at": org.created_at,
+            "updated_at": org.updated_at
         })),
     )
-        .into_response()
+        .into_response();
+    with_last_modified(response, &org.updated_at)
 }
 
 /// PATCH `/organizations/{org_id}` - Update organization settings.
-#[allow(clippy::too_many_lines)]
 async fn update_organization(
     State(state): State<AppState>,
     auth: AuthUser,
@@ -235,106 +538,257 @@ async fn update_organization(
         Ok(true) => {}
     }
 
+    let renamed = match payload.slug.as_deref() {
+        Some(slug) => match org_repo.rename_slug(org_id, slug).await {
+            Ok(org) => {
+                info!(slug = %org.slug, "Organization slug renamed");
+                Some(org)
+            }
+            Err(e) => return organization_error_response(e),
+        },
+        None => None,
+    };
+    let slug_only =
+        payload.name.is_none() && payload.base_currency.is_none() && payload.timezone.is_none();
+
     // Update organization with full validation
-    let org = match org_repo
-        .update_organization(
-            org_id,
-            payload.name.as_deref(),
-            payload.base_currency.as_deref(),
-            payload.timezone.as_deref(),
-        )
-        .await
-    {
-        Ok(o) => o,
-        Err(OrganizationError::NotFound) => {
-            return (
-                StatusCode::NOT_FOUND,
-                Json(json!({
-                    "error": "not_found",
-                    "message": "Organization not found"
-                })),
+    let org = match renamed {
+        Some(org) if slug_only => org,
+        _ => match org_repo
+            .update_organization(
+                org_id,
+                payload.name.as_deref(),
+                payload.base_currency.as_deref(),
+                payload.timezone.as_deref(),
             )
-                .into_response();
+            .await
+        {
+            Ok(o) => o,
+            Err(e) => return organization_error_response(e),
+        },
+    };
+
+    info!("Organization updated");
+
+    (
+        StatusCode::OK,
+        Json(json!({
+            "id": org.id,
+            "name": org.name,
+            "slug": org.slug,
+            "base_currency": org.base_currency,
+            "timezone": org.timezone,
+            "subscription_tier": format!("{:?}", org.subscription_tier).to_lowercase(),
+            "subscription_status": format!("{:?}", org.subscription_status).to_lowercase(),
+            "updated_at": org.updated_at
+        })),
+    )
+        .into_response()
+}
+
+/// Responds 422 with the invalid fields.
+fn validation_failed_response(fields: &[FieldError]) -> axum::response::Response {
+    (
+        StatusCode::UNPROCESSABLE_ENTITY,
+        Json(json!({
+            "error": "validation_failed",
+            "message": "One or more fields are invalid",
+            "fields": fields
+        })),
+    )
+        .into_response()
+}
+
+/// Maps organization update errors to responses.
+fn organization_error_response(err: OrganizationError) -> axum::response::Response {
+    let (status, code, message) = match err {
+        OrganizationError::NotFound => (
+            StatusCode::NOT_FOUND,
+            "not_found",
+            "Organization not found".to_string(),
+        ),
+        OrganizationError::EmptyUpdate => (
+            StatusCode::BAD_REQUEST,
+            "empty_update",
+            "No fields provided for update".to_string(),
+        ),
+        OrganizationError::InvalidName => (
+            StatusCode::BAD_REQUEST,
+            "invalid_name",
+            "Name must be between 1 and 255 characters".to_string(),
+        ),
+        OrganizationError::InvalidCurrency(code) => (
+            StatusCode::BAD_REQUEST,
+            "invalid_currency",
+            format!("Invalid currency code: {code}"),
+        ),
+        OrganizationError::CurrencyChangeNotAllowed => (
+            StatusCode::BAD_REQUEST,
+            "currency_change_not_allowed",
+            "Cannot change base currency after posting transactions".to_string(),
+        ),
+        OrganizationError::InvalidTimezone(tz) => (
+            StatusCode::BAD_REQUEST,
+            "invalid_timezone",
+            format!("Invalid timezone: {tz}"),
+        ),
+        OrganizationError::InvalidSlug(e) => {
+            return validation_failed_response(&[FieldError::new("slug", e.to_string())]);
         }
-        Err(OrganizationError::EmptyUpdate) => {
-            return (
-                StatusCode::BAD_REQUEST,
-                Json(json!({
-                    "error": "empty_update",
-                    "message": "No fields provided for update"
-                })),
+        OrganizationError::SlugTaken => (
+            StatusCode::CONFLICT,
+            "slug_exists",
+            "An organization with this slug already exists".to_string(),
+        ),
+        e => {
+            error!(error = %e, "Failed to update organization");
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "internal_error",
+                "An error occurred updating the organization".to_string(),
             )
-                .into_response();
         }
-        Err(OrganizationError::InvalidName) => {
+    };
+
+    (
+        status,
+        Json(json!({
+            "error": code,
+            "message": message
+        })),
+    )
+        .into_response()
+}
+
+/// GET `/organizations/{org_id}/settings` - Get organization settings with defaults applied.
+async fn get_settings(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(org_id): Path<uuid::Uuid>,
+) -> impl IntoResponse {
+    let org_repo = OrganizationRepository::new((*state.db).clone());
+
+    match org_repo.is_member(org_id, auth.user_id()).await {
+        Ok(false) => {
             return (
-                StatusCode::BAD_REQUEST,
+                StatusCode::FORBIDDEN,
                 Json(json!({
-                    "error": "invalid_name",
-                    "message": "Name must be between 1 and 255 characters"
+                    "error": "forbidden",
+                    "message": "You are not a member of this organization"
                 })),
             )
                 .into_response();
         }
-        Err(OrganizationError::InvalidCurrency(code)) => {
+        Err(e) => {
+            error!(error = %e, "Database error checking membership");
             return (
-                StatusCode::BAD_REQUEST,
+                StatusCode::INTERNAL_SERVER_ERROR,
                 Json(json!({
-                    "error": "invalid_currency",
-                    "message": format!("Invalid currency code: {}", code)
+                    "error": "internal_error",
+                    "message": "An error occurred"
                 })),
             )
                 .into_response();
         }
-        Err(OrganizationError::CurrencyChangeNotAllowed) => {
-            return (
-                StatusCode::BAD_REQUEST,
+        Ok(true) => {}
+    }
+
+    match org_repo.get_settings(org_id).await {
+        Ok(settings) => (StatusCode::OK, Json(settings)).into_response(),
+        // A stored document that fails the schema is a server-side problem
+        Err(OrganizationError::InvalidSettings(e)) => {
+            error!(error = %e, "Stored organization settings are invalid");
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
                 Json(json!({
-                    "error": "currency_change_not_allowed",
-                    "message": "Cannot change base currency after posting transactions"
+                    "error": "internal_error",
+                    "message": "An error occurred"
                 })),
             )
-                .into_response();
+                .into_response()
         }
-        Err(OrganizationError::InvalidTimezone(tz)) => {
+        Err(e) => settings_error_response(e),
+    }
+}
+
+/// PATCH `/organizations/{org_id}/settings` - Deep-merge changes into organization settings.
+async fn update_settings(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(org_id): Path<uuid::Uuid>,
+    Json(patch): Json<serde_json::Value>,
+) -> impl IntoResponse {
+    let org_repo = OrganizationRepository::new((*state.db).clone());
+
+    match org_repo
+        .has_role(org_id, auth.user_id(), UserRole::Admin)
+        .await
+    {
+        Ok(false) => {
             return (
-                StatusCode::BAD_REQUEST,
+                StatusCode::FORBIDDEN,
                 Json(json!({
-                    "error": "invalid_timezone",
-                    "message": format!("Invalid timezone: {}", tz)
+                    "error": "forbidden",
+                    "message": "You need admin or owner role to update organization settings"
                 })),
             )
                 .into_response();
         }
         Err(e) => {
-            error!(error = %e, "Failed to update organization");
+            error!(error = %e, "Database error checking role");
             return (
                 StatusCode::INTERNAL_SERVER_ERROR,
                 Json(json!({
                     "error": "internal_error",
-                    "message": "An error occurred updating the organization"
+                    "message": "An error occurred"
                 })),
             )
                 .into_response();
         }
-    };
+        Ok(true) => {}
+    }
 
-    info!(org_id = %org_id, "Organization updated");
+    match org_repo.update_settings(org_id, &patch).await {
+        Ok(settings) => {
+            info!("Organization settings updated");
+            (StatusCode::OK, Json(settings)).into_response()
+        }
+        Err(e) => settings_error_response(e),
+    }
+}
 
-    (
-        StatusCode::OK,
-        Json(json!({
-            "id": org.id,
-            "name": org.name,
-            "slug": org.slug,
-            "base_currency": org.base_currency,
-            "timezone": org.timezone,
-            "subscription_tier": format!("{:?}", org.subscription_tier).to_lowercase(),
-            "subscription_status": format!("{:?}", org.subscription_status).to_lowercase(),
-            "updated_at": org.updated_at
-        })),
-    )
-        .into_response()
+/// Maps settings repository errors to responses.
+fn settings_error_response(err: OrganizationError) -> axum::response::Response {
+    match err {
+        OrganizationError::NotFound => (
+            StatusCode::NOT_FOUND,
+            Json(json!({
+                "error": "not_found",
+                "message": "Organization not found"
+            })),
+        )
+            .into_response(),
+        OrganizationError::InvalidSettings(SettingsError::Invalid(fields)) => (
+            StatusCode::BAD_REQUEST,
+            Json(json!({
+                "error": "invalid_settings",
+                "message": "One or more settings are invalid",
+                "fields": fields
+            })),
+        )
+            .into_response(),
+        e => {
+            error!(error = %e, "Failed to access organization settings");
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({
+                    "error": "internal_error",
+                    "message": "An error occurred"
+                })),
+            )
+                .into_response()
+        }
+    }
 }
 
 /// GET `/organizations/{org_id}/users` - List organization users.
@@ -517,6 +971,7 @@ async fn add_user(
         .await
     {
         Ok(m) => m,
+        Err(OrganizationError::TierLimitExceeded(limit)) => return tier_limit_response(&limit),
         Err(e) => {
             error!(error = %e, "Failed to add user to organization");
             return (
@@ -530,12 +985,7 @@ async fn add_user(
         }
     };
 
-    info!(
-        org_id = %org_id,
-        user_id = %user.id,
-        role = %payload.role,
-        "User added to organization"
-    );
+    info!(user_id = %user.id, role = %payload.role, "User added to organization");
 
     (
         StatusCode::CREATED,
@@ -682,6 +1132,9 @@ async fn remove_user(
         }
     }
 
+    // Stop cached memberships from letting the removed user back in
+    state.membership_cache.invalidate(org_id, user_id).await;
+
     // Revoke all sessions for the removed user in this organization
     if let Err(e) = session_repo.revoke_user_org_sessions(user_id, org_id).await {
         error!(error = %e, "Failed to revoke sessions for removed user");
@@ -689,7 +1142,6 @@ async fn remove_user(
     }
 
     info!(
-        org_id = %org_id,
         user_id = %user_id,
         removed_by = %auth.user_id(),
         "User removed from organization"
@@ -845,8 +1297,9 @@ async fn update_member(
         }
     };
 
+    state.membership_cache.invalidate(org_id, user_id).await;
+
     info!(
-        org_id = %org_id,
         user_id = %user_id,
         updated_by = %auth.user_id(),
         "Member updated"
@@ -864,3 +1317,260 @@ async fn update_member(
     )
         .into_response()
 }
+
+#[cfg(test)]
+mod integration_tests {
+    use super::*;
+    use zeltra_db::entities::sea_orm_active_enums::{AccountType, SubscriptionTier};
+    use zeltra_test_support::{OrgFixture, TestDb, access_token, test_app_state};
+
+    use crate::middleware::auth::check_membership;
+
+    async fn send(
+        state: &AppState,
+        method: &str,
+        uri: &str,
+        token: &str,
+        body: serde_json::Value,
+    ) -> (StatusCode, serde_json::Value) {
+        zeltra_test_support::send(state.authenticated(routes()), method, uri, token, body).await
+    }
+
+    #[tokio::test]
+    async fn test_create_reports_every_invalid_field() {
+        let test_db = TestDb::new().await;
+        let state: AppState = test_app_state(&test_db);
+        let org = OrgFixture::new().create(test_db.conn()).await;
+        let token = access_token(&state.jwt_service, org.id, &org.owner);
+
+        let (status, body) = send(
+            &state,
+            "POST",
+            "/organizations",
+            &token,
+            json!({
+                "name": "Acme",
+                "slug": "Acme Corp",
+                "base_currency": "XXX",
+                "timezone": "Mars/Olympus"
+            }),
+        )
+        .await;
+
+        assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(body["error"], "validation_failed");
+        let fields: Vec<&str> = body["fields"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|f| f["field"].as_str().unwrap())
+            .collect();
+        assert_eq!(fields, ["slug", "base_currency", "timezone"]);
+    }
+
+    #[tokio::test]
+    async fn test_create_bootstraps_requested_defaults() {
+        let test_db = TestDb::new().await;
+        let state: AppState = test_app_state(&test_db);
+        let org = OrgFixture::new().create(test_db.conn()).await;
+        let token = access_token(&state.jwt_service, org.id, &org.owner);
+        let slug = format!("boot-{}", &uuid::Uuid::new_v4().simple().to_string()[..12]);
+
+        let (status, body) = send(
+            &state,
+            "POST",
+            "/organizations",
+            &token,
+            json!({
+                "name": "Acme",
+                "slug": slug,
+                "base_currency": "USD",
+                "bootstrap": { "chart_of_accounts": "retail" }
+            }),
+        )
+        .await;
+        assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(body["fields"][0]["field"], "bootstrap.chart_of_accounts");
+
+        let (status, body) = send(
+            &state,
+            "POST",
+            "/organizations",
+            &token,
+            json!({
+                "name": "Acme",
+                "slug": slug,
+                "base_currency": "USD",
+                "bootstrap": {
+                    "fiscal_year": true,
+                    "chart_of_accounts": "service",
+                    "dimension_types": true
+                }
+            }),
+        )
+        .await;
+        assert_eq!(status, StatusCode::CREATED);
+        let bootstrap = &body["bootstrap"];
+        assert!(bootstrap["fiscal_year"]["periods"].as_u64().unwrap() >= 12);
+        assert_eq!(
+            bootstrap["accounts"].as_array().unwrap().len(),
+            ChartTemplate::Service.accounts().len()
+        );
+        assert_eq!(bootstrap["dimension_types"][0]["code"], "DEPARTMENT");
+        assert_eq!(bootstrap["dimension_types"][1]["code"], "PROJECT");
+    }
+
+    #[tokio::test]
+    async fn test_renamed_slug_stays_reserved_and_resolvable() {
+        let test_db = TestDb::new().await;
+        let state: AppState = test_app_state(&test_db);
+        let org = OrgFixture::new().create(test_db.conn()).await;
+        let token = access_token(&state.jwt_service, org.id, &org.owner);
+        let repo = OrganizationRepository::new(test_db.conn().clone());
+        let old_slug = repo
+            .find_by_id(org.id.into_inner())
+            .await
+            .unwrap()
+            .unwrap()
+            .slug;
+        let new_slug = format!("renamed-{}", &old_slug[5..20]);
+
+        let (status, body) = send(
+            &state,
+            "PATCH",
+            &format!("/organizations/{}", org.id),
+            &token,
+            json!({ "slug": new_slug }),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["slug"], new_slug.as_str());
+
+        let found = repo.find_by_slug(&old_slug).await.unwrap().unwrap();
+        assert_eq!(found.id, org.id.into_inner());
+        assert_eq!(found.slug, new_slug);
+
+        // Nobody else can pick up the old slug
+        let (status, body) = send(
+            &state,
+            "POST",
+            "/organizations",
+            &token,
+            json!({
+                "name": "Copycat",
+                "slug": old_slug,
+                "base_currency": "USD"
+            }),
+        )
+        .await;
+        assert_eq!(status, StatusCode::CONFLICT);
+        assert_eq!(body["error"], "slug_exists");
+
+        let (status, _) = send(
+            &state,
+            "PATCH",
+            &format!("/organizations/{}", org.id),
+            &token,
+            json!({ "slug": "api" }),
+        )
+        .await;
+        assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
+    #[tokio::test]
+    async fn test_member_changes_invalidate_cached_membership() {
+        let test_db = TestDb::new().await;
+        let state: AppState = test_app_state(&test_db);
+        let org = OrgFixture::new()
+            .with_member(UserRole::Approver)
+            .create(test_db.conn())
+            .await;
+        let token = access_token(&state.jwt_service, org.id, &org.owner);
+        let member_id = org.member(&UserRole::Approver).user_id.into_inner();
+        let uri = format!("/organizations/{}/users/{member_id}", org.id);
+
+        // Warm the cache the way a route guard would
+        let cached = check_membership(&state, org.id, member_id).await.unwrap();
+        assert_eq!(cached.role, UserRole::Approver);
+
+        let (status, _) = send(&state, "PATCH", &uri, &token, json!({ "role": "viewer" })).await;
+        assert_eq!(status, StatusCode::OK);
+        let cached = check_membership(&state, org.id, member_id).await.unwrap();
+        assert_eq!(cached.role, UserRole::Viewer);
+
+        let (status, _) = send(&state, "DELETE", &uri, &token, json!({})).await;
+        assert_eq!(status, StatusCode::NO_CONTENT);
+        let denied = check_membership(&state, org.id, member_id)
+            .await
+            .unwrap_err();
+        assert_eq!(denied.status(), StatusCode::FORBIDDEN);
+    }
+
+    #[tokio::test]
+    async fn test_clone_is_owner_only_and_plan_gated() {
+        let test_db = TestDb::new().await;
+        let state: AppState = test_app_state(&test_db);
+        let org = OrgFixture::new()
+            .with_fiscal_year_2025()
+            .with_member(UserRole::Admin)
+            .with_accounts(&[("1000", AccountType::Asset)])
+            .create(test_db.conn())
+            .await;
+        let starter = OrgFixture::new()
+            .with_tier(SubscriptionTier::Starter)
+            .create(test_db.conn())
+            .await;
+        let uri = format!("/organizations/{}/clone", org.id);
+        let slug = format!(
+            "sandbox-{}",
+            &uuid::Uuid::new_v4().simple().to_string()[..12]
+        );
+        let request = json!({ "name": "Sandbox", "slug": slug });
+
+        let admin = access_token(&state.jwt_service, org.id, org.member(&UserRole::Admin));
+        let (status, body) = send(&state, "POST", &uri, &admin, request.clone()).await;
+        assert_eq!(status, StatusCode::FORBIDDEN);
+        assert_eq!(body["error"], "forbidden");
+
+        let token = access_token(&state.jwt_service, starter.id, &starter.owner);
+        let (status, body) = send(
+            &state,
+            "POST",
+            &format!("/organizations/{}/clone", starter.id),
+            &token,
+            request.clone(),
+        )
+        .await;
+        assert_eq!(status, StatusCode::FORBIDDEN);
+        assert_eq!(body["error"], "feature_not_available");
+
+        let token = access_token(&state.jwt_service, org.id, &org.owner);
+        let (status, body) = send(
+            &state,
+            "POST",
+            &uri,
+            &token,
+            json!({
+                "name": "Sandbox",
+                "slug": slug,
+                "copy": { "fiscal_calendar": false }
+            }),
+        )
+        .await;
+        assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(body["fields"][0]["field"], "copy.budgets");
+
+        let (status, body) = send(&state, "POST", &uri, &token, request.clone()).await;
+        assert_eq!(status, StatusCode::CREATED);
+        assert_eq!(body["slug"], slug.as_str());
+        assert_eq!(body["source_organization_id"], org.id.to_string());
+        assert_eq!(body["subscription_tier"], "enterprise");
+        assert_eq!(body["counts"]["accounts"], 1);
+        assert_eq!(body["counts"]["fiscal_periods"], 12);
+        assert!(body["counts"].get("transactions").is_none());
+
+        let (status, body) = send(&state, "POST", &uri, &token, request).await;
+        assert_eq!(status, StatusCode::CONFLICT);
+        assert_eq!(body["error"], "slug_exists");
+    }
+}
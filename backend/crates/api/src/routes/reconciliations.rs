@@ -0,0 +1,575 @@
+//! Bank account reconciliation routes.
+
+use axum::{
+    Json, Router,
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::IntoResponse,
+    routing::{get, patch, post},
+};
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::str::FromStr;
+use tracing::{error, info};
+use uuid::Uuid;
+
+use crate::{
+    AppState,
+    middleware::{
+        AuthMember, AuthUser,
+        auth::{check_membership, check_report_access},
+        with_last_modified,
+    },
+};
+use zeltra_core::reconciliation::ReconciliationError as RuleError;
+use zeltra_db::{
+    OrganizationRepository,
+    entities::{
+        reconciliations,
+        sea_orm_active_enums::{ReconciliationStatus, UserRole},
+    },
+    repositories::reconciliation::{
+        ReconciliationEntry, ReconciliationError, ReconciliationRepository,
+        ReconciliationWithCheck, StartReconciliationInput,
+    },
+};
+
+/// Creates the reconciliation routes.
+pub fn routes() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/organizations/{org_id}/reconciliations",
+            get(list_reconciliations),
+        )
+        .route(
+            "/organizations/{org_id}/reconciliations",
+            post(start_reconciliation),
+        )
+        .route(
+            "/organizations/{org_id}/reconciliations/{reconciliation_id}",
+            get(get_reconciliation),
+        )
+        .route(
+            "/organizations/{org_id}/reconciliations/{reconciliation_id}/entries",
+            get(list_entries),
+        )
+        .route(
+            "/organizations/{org_id}/reconciliations/{reconciliation_id}/entries/{entry_id}",
+            patch(set_entry_cleared),
+        )
+        .route(
+            "/organizations/{org_id}/reconciliations/{reconciliation_id}/complete",
+            post(complete_reconciliation),
+        )
+        .route(
+            "/organizations/{org_id}/reconciliations/{reconciliation_id}/reopen",
+            post(reopen_reconciliation),
+        )
+}
+
+// ============================================================================
+// Request/Response Types
+// ============================================================================
+
+/// Query parameters for listing reconciliations.
+#[derive(Debug, Deserialize)]
+pub struct ListReconciliationsQuery {
+    /// Filter by bank account.
+    pub account_id: Option<Uuid>,
+}
+
+/// Request body for starting a reconciliation.
+#[derive(Debug, Deserialize)]
+pub struct StartReconciliationRequest {
+    /// Bank account to reconcile.
+    pub account_id: Uuid,
+    /// Statement date.
+    pub statement_date: NaiveDate,
+    /// Ending balance printed on the statement.
+    pub statement_ending_balance: String,
+}
+
+/// Request body for clearing or unclearing an entry.
+#[derive(Debug, Deserialize)]
+pub struct SetClearedRequest {
+    /// Whether the entry is cleared.
+    pub cleared: bool,
+}
+
+/// Reconciliation response.
+#[derive(Debug, Serialize)]
+pub struct ReconciliationResponse {
+    /// Reconciliation ID.
+    pub id: Uuid,
+    /// Bank account ID.
+    pub account_id: Uuid,
+    /// Statement date.
+    pub statement_date: String,
+    /// Statement ending balance.
+    pub statement_ending_balance: String,
+    /// Ending balance of the previous completed reconciliation.
+    pub opening_balance: String,
+    /// Status: "in_progress" or "completed".
+    pub status: String,
+    /// User who started the reconciliation.
+    pub created_by: Uuid,
+    /// User who completed the reconciliation.
+    pub completed_by: Option<Uuid>,
+    /// Completion timestamp.
+    pub completed_at: Option<String>,
+    /// Net of cleared entries (only on detail responses).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cleared_net: Option<String>,
+    /// Amount still unexplained (only on detail responses).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub difference: Option<String>,
+    /// Number of cleared entries (only on detail responses).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cleared_count: Option<usize>,
+}
+
+/// Ledger entry available for clearing.
+#[derive(Debug, Serialize)]
+pub struct ReconciliationEntryResponse {
+    /// Ledger entry ID.
+    pub id: Uuid,
+    /// Transaction ID.
+    pub transaction_id: Uuid,
+    /// Transaction date.
+    pub transaction_date: String,
+    /// Transaction description.
+    pub description: String,
+    /// Transaction reference number.
+    pub reference_number: Option<String>,
+    /// Debit amount.
+    pub debit: String,
+    /// Credit amount.
+    pub credit: String,
+    /// Whether the entry is cleared in this reconciliation.
+    pub cleared: bool,
+}
+
+// ============================================================================
+// Route Handlers
+// ============================================================================
+
+/// GET `/organizations/{org_id}/reconciliations` - List reconciliations.
+async fn list_reconciliations(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(org_id): Path<Uuid>,
+    Query(query): Query<ListReconciliationsQuery>,
+) -> impl IntoResponse {
+    if let Err(response) = check_membership(&state, org_id, auth.user_id()).await {
+        return response;
+    }
+
+    let repo = ReconciliationRepository::new((*state.db).clone());
+
+    match repo.list_reconciliations(org_id, query.account_id).await {
+        Ok(list) => {
+            let data: Vec<ReconciliationResponse> =
+                list.iter().map(reconciliation_to_response).collect();
+            (StatusCode::OK, Json(json!({ "data": data }))).into_response()
+        }
+        Err(e) => reconciliation_error_response(&e),
+    }
+}
+
+/// POST `/organizations/{org_id}/reconciliations` - Start a reconciliation for a bank account.
+async fn start_reconciliation(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(org_id): Path<Uuid>,
+    Json(payload): Json<StartReconciliationRequest>,
+) -> impl IntoResponse {
+    let org_repo = OrganizationRepository::new((*state.db).clone());
+
+    if let Err(response) = check_accountant_role(&org_repo, org_id, auth.user_id()).await {
+        return response;
+    }
+
+    let Ok(statement_ending_balance) = Decimal::from_str(&payload.statement_ending_balance) else {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(json!({
+                "error": "invalid_amount",
+                "message": "Invalid statement ending balance"
+            })),
+        )
+            .into_response();
+    };
+
+    let repo = ReconciliationRepository::new((*state.db).clone());
+    let input = StartReconciliationInput {
+        organization_id: org_id,
+        account_id: payload.account_id,
+        statement_date: payload.statement_date,
+        statement_ending_balance,
+        created_by: auth.user_id(),
+    };
+
+    match repo.start_reconciliation(input).await {
+        Ok(reconciliation) => {
+            info!(
+                reconciliation_id = %reconciliation.id,
+                account_id = %reconciliation.account_id,
+                "Reconciliation started"
+            );
+            (
+                StatusCode::CREATED,
+                Json(reconciliation_to_response(&reconciliation)),
+            )
+                .into_response()
+        }
+        Err(e) => reconciliation_error_response(&e),
+    }
+}
+
+/// GET `/organizations/{org_id}/reconciliations/{reconciliation_id}` - Get a reconciliation
+/// with its current balance check.
+async fn get_reconciliation(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path((org_id, reconciliation_id)): Path<(Uuid, Uuid)>,
+) -> impl IntoResponse {
+    if let Err(response) = check_membership(&state, org_id, auth.user_id()).await {
+        return response;
+    }
+
+    let repo = ReconciliationRepository::new((*state.db).clone());
+
+    match repo.get_reconciliation(org_id, reconciliation_id).await {
+        Ok(r) => with_last_modified(
+            (StatusCode::OK, Json(checked_to_response(&r))).into_response(),
+            &r.reconciliation.updated_at,
+        ),
+        Err(e) => reconciliation_error_response(&e),
+    }
+}
+
+/// GET `/organizations/{org_id}/reconciliations/{reconciliation_id}/entries` - List
+/// unreconciled ledger entries up to the statement date. Submitters get 403, since the
+/// entries come from every transaction on the account.
+async fn list_entries(
+    State(state): State<AppState>,
+    auth: AuthMember,
+    Path((org_id, reconciliation_id)): Path<(Uuid, Uuid)>,
+) -> impl IntoResponse {
+    if let Err(response) = check_report_access(&state, org_id, &auth).await {
+        return response;
+    }
+
+    let repo = ReconciliationRepository::new((*state.db).clone());
+
+    match repo
+        .list_unreconciled_entries(org_id, reconciliation_id)
+        .await
+    {
+        Ok(entries) => {
+            let data: Vec<ReconciliationEntryResponse> =
+                entries.into_iter().map(entry_to_response).collect();
+            (StatusCode::OK, Json(json!({ "data": data }))).into_response()
+        }
+        Err(e) => reconciliation_error_response(&e),
+    }
+}
+
+/// PATCH `/organizations/{org_id}/reconciliations/{reconciliation_id}/entries/{entry_id}` -
+/// Mark a ledger entry as cleared or uncleared.
+async fn set_entry_cleared(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path((org_id, reconciliation_id, entry_id)): Path<(Uuid, Uuid, Uuid)>,
+    Json(payload): Json<SetClearedRequest>,
+) -> impl IntoResponse {
+    let org_repo = OrganizationRepository::new((*state.db).clone());
+
+    if let Err(response) = check_accountant_role(&org_repo, org_id, auth.user_id()).await {
+        return response;
+    }
+
+    let repo = ReconciliationRepository::new((*state.db).clone());
+
+    match repo
+        .set_cleared(org_id, reconciliation_id, entry_id, payload.cleared)
+        .await
+    {
+        Ok(r) => (StatusCode::OK, Json(checked_to_response(&r))).into_response(),
+        Err(e) => reconciliation_error_response(&e),
+    }
+}
+
+/// POST `/organizations/{org_id}/reconciliations/{reconciliation_id}/complete` - Complete
+/// a reconciliation once the cleared entries match the statement.
+async fn complete_reconciliation(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path((org_id, reconciliation_id)): Path<(Uuid, Uuid)>,
+) -> impl IntoResponse {
+    let org_repo = OrganizationRepository::new((*state.db).clone());
+
+    if let Err(response) = check_accountant_role(&org_repo, org_id, auth.user_id()).await {
+        return response;
+    }
+
+    let repo = ReconciliationRepository::new((*state.db).clone());
+
+    match repo
+        .complete_reconciliation(org_id, reconciliation_id, auth.user_id())
+        .await
+    {
+        Ok(r) => {
+            info!(reconciliation_id = %reconciliation_id, "Reconciliation completed");
+            (StatusCode::OK, Json(checked_to_response(&r))).into_response()
+        }
+        Err(e) => reconciliation_error_response(&e),
+    }
+}
+
+/// POST `/organizations/{org_id}/reconciliations/{reconciliation_id}/reopen` - Reopen the
+/// latest completed reconciliation, unlocking its entries.
+async fn reopen_reconciliation(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path((org_id, reconciliation_id)): Path<(Uuid, Uuid)>,
+) -> impl IntoResponse {
+    let org_repo = OrganizationRepository::new((*state.db).clone());
+
+    if let Err(response) = check_accountant_role(&org_repo, org_id, auth.user_id()).await {
+        return response;
+    }
+
+    let repo = ReconciliationRepository::new((*state.db).clone());
+
+    match repo.reopen_reconciliation(org_id, reconciliation_id).await {
+        Ok(reconciliation) => {
+            info!(reconciliation_id = %reconciliation_id, "Reconciliation reopened");
+            (
+                StatusCode::OK,
+                Json(reconciliation_to_response(&reconciliation)),
+            )
+                .into_response()
+        }
+        Err(e) => reconciliation_error_response(&e),
+    }
+}
+
+// ============================================================================
+// Error Mapping
+// ============================================================================
+
+/// Maps reconciliation errors to HTTP responses.
+fn reconciliation_error_response(e: &ReconciliationError) -> axum::response::Response {
+    let (status, code) = match e {
+        ReconciliationError::NotFound(_) => (StatusCode::NOT_FOUND, "not_found"),
+        ReconciliationError::AccountNotFound(_) => (StatusCode::BAD_REQUEST, "account_not_found"),
+        ReconciliationError::NotBankAccount(_) => (StatusCode::BAD_REQUEST, "not_a_bank_account"),
+        ReconciliationError::NotBaseCurrency(_) => {
+            (StatusCode::BAD_REQUEST, "unsupported_account_currency")
+        }
+        ReconciliationError::AlreadyInProgress(_) => {
+            (StatusCode::CONFLICT, "reconciliation_in_progress")
+        }
+        ReconciliationError::StatementDateNotAfterPrevious(_) => {
+            (StatusCode::BAD_REQUEST, "invalid_statement_date")
+        }
+        ReconciliationError::EntryNotFound(_) => (StatusCode::NOT_FOUND, "entry_not_found"),
+        ReconciliationError::EntryNotPosted(_) => (StatusCode::BAD_REQUEST, "entry_not_posted"),
+        ReconciliationError::EntryAlreadyReconciled(_) => {
+            (StatusCode::CONFLICT, "entry_already_reconciled")
+        }
+        ReconciliationError::NotLatest => (StatusCode::CONFLICT, "not_latest_reconciliation"),
+        ReconciliationError::Rule(RuleError::AlreadyCompleted) => {
+            (StatusCode::CONFLICT, "reconciliation_completed")
+        }
+        ReconciliationError::Rule(RuleError::NotCompleted) => {
+            (StatusCode::BAD_REQUEST, "reconciliation_not_completed")
+        }
+        ReconciliationError::Rule(RuleError::EntryAfterStatementDate) => {
+            (StatusCode::BAD_REQUEST, "entry_after_statement_date")
+        }
+        ReconciliationError::Rule(RuleError::OutOfBalance { difference }) => {
+            return (
+                StatusCode::UNPROCESSABLE_ENTITY,
+                Json(json!({
+                    "error": "out_of_balance",
+                    "message": e.to_string(),
+                    "difference": difference.to_string()
+                })),
+            )
+                .into_response();
+        }
+        ReconciliationError::Database(db_err) => {
+            error!(error = %db_err, "Reconciliation database error");
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({
+                    "error": "internal_error",
+                    "message": "An error occurred"
+                })),
+            )
+                .into_response();
+        }
+    };
+
+    (
+        status,
+        Json(json!({
+            "error": code,
+            "message": e.to_string()
+        })),
+    )
+        .into_response()
+}
+
+// ============================================================================
+// Helper Functions
+// ============================================================================
+
+async fn check_accountant_role(
+    org_repo: &OrganizationRepository,
+    org_id: Uuid,
+    user_id: Uuid,
+) -> Result<(), axum::response::Response> {
+    match org_repo
+        .has_role(org_id, user_id, UserRole::Accountant)
+        .await
+    {
+        Ok(true) => Ok(()),
+        Ok(false) => Err((
+            StatusCode::FORBIDDEN,
+            Json(json!({
+                "error": "forbidden",
+                "message": "You need accountant role or higher to perform this action"
+            })),
+        )
+            .into_response()),
+        Err(e) => {
+            error!(error = %e, "Database error checking role");
+            Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({
+                    "error": "internal_error",
+                    "message": "An error occurred"
+                })),
+            )
+                .into_response())
+        }
+    }
+}
+
+fn status_to_string(status: &ReconciliationStatus) -> String {
+    match status {
+        ReconciliationStatus::InProgress => "in_progress".to_string(),
+        ReconciliationStatus::Completed => "completed".to_string(),
+    }
+}
+
+fn reconciliation_to_response(r: &reconciliations::Model) -> ReconciliationResponse {
+    ReconciliationResponse {
+        id: r.id,
+        account_id: r.account_id,
+        statement_date: r.statement_date.to_string(),
+        statement_ending_balance: r.statement_ending_balance.to_string(),
+        opening_balance: r.opening_balance.to_string(),
+        status: status_to_string(&r.status),
+        created_by: r.created_by,
+        completed_by: r.completed_by,
+        completed_at: r.completed_at.map(|t| t.to_rfc3339()),
+        cleared_net: None,
+        difference: None,
+        cleared_count: None,
+    }
+}
+
+fn checked_to_response(r: &ReconciliationWithCheck) -> ReconciliationResponse {
+    ReconciliationResponse {
+        cleared_net: Some(r.check.cleared_net.to_string()),
+        difference: Some(r.check.difference.to_string()),
+        cleared_count: Some(r.cleared_count),
+        ..reconciliation_to_response(&r.reconciliation)
+    }
+}
+
+fn entry_to_response(e: ReconciliationEntry) -> ReconciliationEntryResponse {
+    ReconciliationEntryResponse {
+        id: e.entry.id,
+        transaction_id: e.entry.transaction_id,
+        transaction_date: e.transaction_date.to_string(),
+        description: e.description,
+        reference_number: e.reference_number,
+        debit: e.entry.debit.to_string(),
+        credit: e.entry.credit.to_string(),
+        cleared: e.cleared,
+    }
+}
+
+#[cfg(test)]
+mod integration_tests {
+    use super::*;
+    use sea_orm::{ActiveModelTrait, Set};
+    use zeltra_db::entities::{chart_of_accounts, sea_orm_active_enums::AccountType};
+    use zeltra_test_support::{OrgFixture, TestDb, access_token, send, test_app_state};
+
+    #[tokio::test]
+    async fn test_submitter_cannot_list_reconciliation_entries() {
+        let test_db = TestDb::new().await;
+        let state: AppState = test_app_state(&test_db);
+        let org = OrgFixture::new()
+            .with_fiscal_year_2025()
+            .with_accounts(&[("1000", AccountType::Asset)])
+            .with_member(UserRole::Submitter)
+            .with_member(UserRole::Viewer)
+            .create(test_db.conn())
+            .await;
+        chart_of_accounts::ActiveModel {
+            id: Set(org.account("1000").into_inner()),
+            is_bank_account: Set(true),
+            ..Default::default()
+        }
+        .update(test_db.conn())
+        .await
+        .unwrap();
+        let reconciliation = ReconciliationRepository::new(test_db.conn().clone())
+            .start_reconciliation(StartReconciliationInput {
+                organization_id: org.id.into_inner(),
+                account_id: org.account("1000").into_inner(),
+                statement_date: NaiveDate::from_ymd_opt(2025, 3, 31).unwrap(),
+                statement_ending_balance: Decimal::ZERO,
+                created_by: org.owner.user_id.into_inner(),
+            })
+            .await
+            .unwrap();
+        let uri = format!(
+            "/organizations/{}/reconciliations/{}/entries",
+            org.id, reconciliation.id
+        );
+
+        let submitter = access_token(&state.jwt_service, org.id, org.member(&UserRole::Submitter));
+        let (status, body) = send(
+            state.authenticated(routes()),
+            "GET",
+            &uri,
+            &submitter,
+            json!(null),
+        )
+        .await;
+        assert_eq!(status, StatusCode::FORBIDDEN);
+        assert_eq!(body["error"], "report_access_restricted");
+
+        let viewer = access_token(&state.jwt_service, org.id, org.member(&UserRole::Viewer));
+        let (status, body) = send(
+            state.authenticated(routes()),
+            "GET",
+            &uri,
+            &viewer,
+            json!(null),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["data"], json!([]));
+    }
+}
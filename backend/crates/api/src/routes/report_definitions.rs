@@ -0,0 +1,568 @@
+//! Custom report definition routes.
+//!
+//! Organizations on a plan with custom reports save report definitions and
+//! run them on demand. Anyone who can read reports can list and run them;
+//! Owners, Admins and Accountants create, edit and delete them.
+
+use axum::{
+    Json, Router,
+    extract::{Path, Query, State},
+    http::{
+        StatusCode,
+        header::{CONTENT_DISPOSITION, CONTENT_TYPE},
+    },
+    response::{IntoResponse, Response},
+    routing::{get, post},
+};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use tracing::{error, info};
+use uuid::Uuid;
+
+use super::accounts::ExportFormat;
+use super::reports::export_style;
+use crate::{AppState, middleware::AuthMember};
+use zeltra_core::reports::ReportDefinition;
+use zeltra_db::repositories::{
+    Feature, ReportDefinitionError, ReportDefinitionRepository, SavedReportDefinition,
+    SubscriptionRepository,
+    report::{ReportError, ReportRepository},
+};
+
+/// Creates the report definition routes.
+pub fn routes() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/organizations/{org_id}/report-definitions",
+            get(list_report_definitions).post(create_report_definition),
+        )
+        .route(
+            "/organizations/{org_id}/report-definitions/{definition_id}",
+            get(get_report_definition)
+                .put(update_report_definition)
+                .delete(delete_report_definition),
+        )
+        .route(
+            "/organizations/{org_id}/report-definitions/{definition_id}/run",
+            post(run_report_definition),
+        )
+}
+
+/// Query parameters for running a report.
+#[derive(Debug, Deserialize)]
+pub struct RunReportQuery {
+    /// Response format (default: json).
+    #[serde(default)]
+    pub format: ExportFormat,
+    /// Format CSV amounts and dates for the organization's locale.
+    #[serde(default)]
+    pub formatted: bool,
+}
+
+/// Saved report definition response.
+#[derive(Debug, Serialize)]
+pub struct ReportDefinitionResponse {
+    /// Definition ID.
+    pub id: Uuid,
+    /// The definition.
+    #[serde(flatten)]
+    pub definition: ReportDefinition,
+    /// Who created it.
+    pub created_by: Option<Uuid>,
+    /// Created at timestamp.
+    pub created_at: String,
+    /// Updated at timestamp.
+    pub updated_at: String,
+}
+
+impl From<SavedReportDefinition> for ReportDefinitionResponse {
+    fn from(saved: SavedReportDefinition) -> Self {
+        Self {
+            id: saved.id,
+            definition: saved.definition,
+            created_by: saved.created_by,
+            created_at: saved.created_at.to_rfc3339(),
+            updated_at: saved.updated_at.to_rfc3339(),
+        }
+    }
+}
+
+/// GET /organizations/{org_id}/report-definitions
+async fn list_report_definitions(
+    State(state): State<AppState>,
+    Path(org_id): Path<Uuid>,
+    auth: AuthMember,
+) -> impl IntoResponse {
+    if let Err(response) = check_access(&state, &auth, org_id, false).await {
+        return response;
+    }
+
+    match ReportDefinitionRepository::new((*state.db).clone())
+        .list(org_id)
+        .await
+    {
+        Ok(saved) => {
+            let items: Vec<ReportDefinitionResponse> = saved
+                .into_iter()
+                .map(ReportDefinitionResponse::from)
+                .collect();
+            (StatusCode::OK, Json(json!({ "data": items }))).into_response()
+        }
+        Err(e) => definition_error_response(&e),
+    }
+}
+
+/// POST /organizations/{org_id}/report-definitions
+///
+/// Saves a new definition. Every problem with it is reported together.
+async fn create_report_definition(
+    State(state): State<AppState>,
+    Path(org_id): Path<Uuid>,
+    auth: AuthMember,
+    Json(definition): Json<ReportDefinition>,
+) -> impl IntoResponse {
+    if let Err(response) = check_access(&state, &auth, org_id, true).await {
+        return response;
+    }
+
+    match ReportDefinitionRepository::new((*state.db).clone())
+        .create(org_id, &definition, auth.user_id())
+        .await
+    {
+        Ok(saved) => {
+            info!(org_id = %org_id, definition_id = %saved.id, "Report definition created");
+            (
+                StatusCode::CREATED,
+                Json(ReportDefinitionResponse::from(saved)),
+            )
+                .into_response()
+        }
+        Err(e) => definition_error_response(&e),
+    }
+}
+
+/// GET /organizations/{org_id}/report-definitions/{definition_id}
+async fn get_report_definition(
+    State(state): State<AppState>,
+    Path((org_id, definition_id)): Path<(Uuid, Uuid)>,
+    auth: AuthMember,
+) -> impl IntoResponse {
+    if let Err(response) = check_access(&state, &auth, org_id, false).await {
+        return response;
+    }
+
+    match ReportDefinitionRepository::new((*state.db).clone())
+        .get(org_id, definition_id)
+        .await
+    {
+        Ok(saved) => (StatusCode::OK, Json(ReportDefinitionResponse::from(saved))).into_response(),
+        Err(e) => definition_error_response(&e),
+    }
+}
+
+/// PUT /organizations/{org_id}/report-definitions/{definition_id}
+///
+/// Replaces a saved definition.
+async fn update_report_definition(
+    State(state): State<AppState>,
+    Path((org_id, definition_id)): Path<(Uuid, Uuid)>,
+    auth: AuthMember,
+    Json(definition): Json<ReportDefinition>,
+) -> impl IntoResponse {
+    if let Err(response) = check_access(&state, &auth, org_id, true).await {
+        return response;
+    }
+
+    match ReportDefinitionRepository::new((*state.db).clone())
+        .update(org_id, definition_id, &definition)
+        .await
+    {
+        Ok(saved) => {
+            info!(org_id = %org_id, definition_id = %saved.id, "Report definition updated");
+            (StatusCode::OK, Json(ReportDefinitionResponse::from(saved))).into_response()
+        }
+        Err(e) => definition_error_response(&e),
+    }
+}
+
+/// DELETE /organizations/{org_id}/report-definitions/{definition_id}
+async fn delete_report_definition(
+    State(state): State<AppState>,
+    Path((org_id, definition_id)): Path<(Uuid, Uuid)>,
+    auth: AuthMember,
+) -> impl IntoResponse {
+    if let Err(response) = check_access(&state, &auth, org_id, true).await {
+        return response;
+    }
+
+    match ReportDefinitionRepository::new((*state.db).clone())
+        .delete(org_id, definition_id)
+        .await
+    {
+        Ok(()) => {
+            info!(org_id = %org_id, definition_id = %definition_id, "Report definition deleted");
+            StatusCode::NO_CONTENT.into_response()
+        }
+        Err(e) => definition_error_response(&e),
+    }
+}
+
+/// POST /organizations/{org_id}/report-definitions/{definition_id}/run
+///
+/// Runs a saved definition as JSON or CSV. Results longer than the row cap
+/// are cut off and flagged as truncated.
+async fn run_report_definition(
+    State(state): State<AppState>,
+    Path((org_id, definition_id)): Path<(Uuid, Uuid)>,
+    Query(query): Query<RunReportQuery>,
+    auth: AuthMember,
+) -> impl IntoResponse {
+    if let Err(response) = check_access(&state, &auth, org_id, false).await {
+        return response;
+    }
+
+    let saved = match ReportDefinitionRepository::new((*state.db).clone())
+        .get(org_id, definition_id)
+        .await
+    {
+        Ok(saved) => saved,
+        Err(e) => return definition_error_response(&e),
+    };
+    // Definitions are validated when saved; this only resolves the fields
+    let plan = match saved.definition.validate() {
+        Ok(plan) => plan,
+        Err(fields) => return definition_error_response(&ReportDefinitionError::Invalid(fields)),
+    };
+
+    let result = match ReportRepository::new((*state.db).clone())
+        .run_custom_report(org_id, &plan)
+        .await
+    {
+        Ok(result) => result,
+        Err(ReportError::DimensionValueNotFound(id)) => {
+            return (
+                StatusCode::BAD_REQUEST,
+                Json(json!({
+                    "error": "invalid_dimension",
+                    "message": format!("Dimension value not found: {id}")
+                })),
+            )
+                .into_response();
+        }
+        Err(e) => {
+            error!(error = %e, "Failed to run custom report");
+            return internal_error();
+        }
+    };
+
+    match query.format {
+        ExportFormat::Json => (StatusCode::OK, Json(result)).into_response(),
+        ExportFormat::Csv => {
+            let style = match export_style(&state.db, org_id, query.formatted).await {
+                Ok(style) => style,
+                Err(response) => return response,
+            };
+            (
+                StatusCode::OK,
+                [
+                    (CONTENT_TYPE, "text/csv; charset=utf-8".to_string()),
+                    (
+                        CONTENT_DISPOSITION,
+                        format!("attachment; filename=\"report-{definition_id}.csv\""),
+                    ),
+                ],
+                result.to_csv(style),
+            )
+                .into_response()
+        }
+    }
+}
+
+/// Checks the caller may read, or with `manage` change, the organization's
+/// definitions, and that its plan includes custom reports.
+async fn check_access(
+    state: &AppState,
+    auth: &AuthMember,
+    org_id: Uuid,
+    manage: bool,
+) -> Result<(), Response> {
+    let role = auth.role_in(state, org_id).await?;
+    let allowed = if manage {
+        role.can_manage_custom_reports()
+    } else {
+        role.can_view_reports()
+    };
+    if !allowed {
+        return Err((
+            StatusCode::FORBIDDEN,
+            Json(json!({
+                "error": "forbidden",
+                "message": if manage {
+                    "You need accountant, admin or owner role to change report definitions"
+                } else {
+                    "You do not have permission to view reports"
+                }
+            })),
+        )
+            .into_response());
+    }
+
+    match SubscriptionRepository::has_feature(&state.db, org_id, Feature::CustomReports).await {
+        Ok(true) => Ok(()),
+        Ok(false) => Err((
+            StatusCode::FORBIDDEN,
+            Json(json!({
+                "error": "feature_not_available",
+                "message": "Custom reports are not included in the organization's plan"
+            })),
+        )
+            .into_response()),
+        Err(e) => {
+            error!(error = %e, "Database error checking custom reports");
+            Err(internal_error())
+        }
+    }
+}
+
+fn definition_error_response(e: &ReportDefinitionError) -> Response {
+    match e {
+        ReportDefinitionError::NotFound(_) => (
+            StatusCode::NOT_FOUND,
+            Json(json!({
+                "error": "not_found",
+                "message": "Report definition not found"
+            })),
+        )
+            .into_response(),
+        ReportDefinitionError::Invalid(fields) => (
+            StatusCode::BAD_REQUEST,
+            Json(json!({
+                "error": "invalid_report_definition",
+                "message": "The report definition is invalid",
+                "fields": fields
+            })),
+        )
+            .into_response(),
+        ReportDefinitionError::DuplicateName(_) => (
+            StatusCode::CONFLICT,
+            Json(json!({
+                "error": "duplicate_name",
+                "message": e.to_string()
+            })),
+        )
+            .into_response(),
+        ReportDefinitionError::Database(db_err) => {
+            error!(error = %db_err, "Report definition database error");
+            internal_error()
+        }
+    }
+}
+
+fn internal_error() -> Response {
+    (
+        StatusCode::INTERNAL_SERVER_ERROR,
+        Json(json!({
+            "error": "internal_error",
+            "message": "An error occurred"
+        })),
+    )
+        .into_response()
+}
+
+#[cfg(test)]
+mod integration_tests {
+    use super::*;
+    use chrono::NaiveDate;
+    use rust_decimal::Decimal;
+    use zeltra_db::{
+        OrganizationRepository,
+        entities::sea_orm_active_enums::{AccountType, SubscriptionTier, UserRole},
+    };
+    use zeltra_test_support::{
+        Line, OrgFixture, TestDb, access_token, json_request, jwt_service, post_transaction, send,
+        send_request, test_app_state,
+    };
+
+    /// Sends a JSON request and returns the status, content type and body.
+    async fn send_text(
+        state: &AppState,
+        method: &str,
+        uri: &str,
+        token: &str,
+        body: serde_json::Value,
+    ) -> (StatusCode, String, String) {
+        let request = json_request(method, uri, token, &body);
+        let response = send_request(state.authenticated(routes()), request).await;
+        let content_type = response
+            .header(CONTENT_TYPE.as_str())
+            .unwrap_or_default()
+            .to_string();
+        (response.status, content_type, response.text())
+    }
+
+    async fn send_json(
+        state: &AppState,
+        method: &str,
+        uri: &str,
+        token: &str,
+        body: serde_json::Value,
+    ) -> (StatusCode, serde_json::Value) {
+        send(state.authenticated(routes()), method, uri, token, body).await
+    }
+
+    #[tokio::test]
+    async fn test_report_definitions_are_role_and_plan_gated() {
+        let test_db = TestDb::new().await;
+        let state: AppState = test_app_state(&test_db);
+        let org = OrgFixture::new()
+            .with_tier(SubscriptionTier::Growth)
+            .with_member(UserRole::Viewer)
+            .with_member(UserRole::Accountant)
+            .create(test_db.conn())
+            .await;
+        let jwt = jwt_service();
+        let uri = format!("/organizations/{}/report-definitions", org.id);
+        let definition = json!({
+            "name": "Entries by account",
+            "dataset": "ledger_entries",
+            "columns": ["account_code"],
+            "group_by": ["account_code"],
+            "aggregate": { "function": "count" }
+        });
+
+        let viewer = access_token(&jwt, org.id, org.member(&UserRole::Viewer));
+        let (status, _) = send_json(&state, "POST", &uri, &viewer, definition.clone()).await;
+        assert_eq!(status, StatusCode::FORBIDDEN);
+
+        let accountant = access_token(&jwt, org.id, org.member(&UserRole::Accountant));
+        let (status, body) = send_json(
+            &state,
+            "POST",
+            &uri,
+            &accountant,
+            json!({
+                "name": "Smuggled",
+                "dataset": "ledger_entries",
+                "columns": ["account_code", "password_hash"]
+            }),
+        )
+        .await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body["error"], "invalid_report_definition");
+        assert_eq!(body["fields"][0]["field"], "columns[1]");
+
+        let (status, created) =
+            send_json(&state, "POST", &uri, &accountant, definition.clone()).await;
+        assert_eq!(status, StatusCode::CREATED);
+        let (status, _) = send_json(&state, "POST", &uri, &accountant, definition).await;
+        assert_eq!(status, StatusCode::CONFLICT);
+
+        // Viewers list and run; the account has no entries yet
+        let (status, listed) = send_json(&state, "GET", &uri, &viewer, json!(null)).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(listed["data"][0]["id"], created["id"]);
+        let run_uri = format!("{uri}/{}/run", created["id"].as_str().unwrap());
+        let (status, result) = send_json(&state, "POST", &run_uri, &viewer, json!(null)).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(result["columns"], json!(["account_code", "count"]));
+        assert_eq!(result["truncated"], false);
+        let (status, content_type, csv) = send_text(
+            &state,
+            "POST",
+            &format!("{run_uri}?format=csv"),
+            &viewer,
+            json!(null),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(content_type, "text/csv; charset=utf-8");
+        assert_eq!(csv.lines().next(), Some("account_code,count"));
+
+        let other = OrgFixture::new()
+            .with_tier(SubscriptionTier::Starter)
+            .create(test_db.conn())
+            .await;
+        let token = access_token(&jwt, other.id, &other.owner);
+        let (status, body) = send_json(
+            &state,
+            "GET",
+            &format!("/organizations/{}/report-definitions", other.id),
+            &token,
+            json!(null),
+        )
+        .await;
+        assert_eq!(status, StatusCode::FORBIDDEN);
+        assert_eq!(body["error"], "feature_not_available");
+    }
+
+    #[tokio::test]
+    async fn test_run_csv_is_formatted_for_the_organization_locale() {
+        let test_db = TestDb::new().await;
+        let state: AppState = test_app_state(&test_db);
+        let org = OrgFixture::new()
+            .with_fiscal_year_2025()
+            .with_accounts(&[("1000", AccountType::Asset), ("6000", AccountType::Expense)])
+            .create(test_db.conn())
+            .await;
+        OrganizationRepository::new(test_db.conn().clone())
+            .update_settings(
+                org.id.into_inner(),
+                &json!({"formatting": {"locale": "de-DE"}}),
+            )
+            .await
+            .unwrap();
+        post_transaction(
+            test_db.conn(),
+            &org,
+            NaiveDate::from_ymd_opt(2025, 3, 10).unwrap(),
+            vec![
+                Line::debit("6000", Decimal::new(12345, 1)),
+                Line::credit("1000", Decimal::new(12345, 1)),
+            ],
+        )
+        .await;
+        let token = access_token(&jwt_service(), org.id, &org.owner);
+        let uri = format!("/organizations/{}/report-definitions", org.id);
+        let (status, created) = send_json(
+            &state,
+            "POST",
+            &uri,
+            &token,
+            json!({
+                "name": "Spend by day",
+                "dataset": "ledger_entries",
+                "columns": ["transaction_date"],
+                "group_by": ["transaction_date"],
+                "aggregate": { "function": "sum", "field": "debit" }
+            }),
+        )
+        .await;
+        assert_eq!(status, StatusCode::CREATED);
+        let run_uri = format!("{uri}/{}/run", created["id"].as_str().unwrap());
+
+        let (status, _, plain) = send_text(
+            &state,
+            "POST",
+            &format!("{run_uri}?format=csv"),
+            &token,
+            json!(null),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(plain, "transaction_date,sum_debit\n2025-03-10,1234.5000\n");
+
+        let (status, _, formatted) = send_text(
+            &state,
+            "POST",
+            &format!("{run_uri}?format=csv&formatted=true"),
+            &token,
+            json!(null),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(
+            formatted,
+            "transaction_date,sum_debit\n10.03.2025,\"1.234,50\"\n"
+        );
+    }
+}
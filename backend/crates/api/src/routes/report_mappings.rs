@@ -0,0 +1,149 @@
+//! Financial statement mapping routes.
+//!
+//! The mapping decides which balance sheet or income statement section each
+//! account subtype is shown in. Any member can read it; Owners and Admins
+//! can replace it.
+
+use axum::{
+    Json, Router,
+    extract::{Path, State},
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    routing::get,
+};
+use serde::Serialize;
+use serde_json::json;
+use tracing::{error, info};
+use uuid::Uuid;
+
+use crate::{AppState, middleware::AuthMember};
+use zeltra_core::reports::StatementMapping;
+use zeltra_db::repositories::{ReportMappingError, ReportMappingRepository, ResolvedReportMapping};
+
+/// Creates the report mapping routes.
+pub fn routes() -> Router<AppState> {
+    Router::new().route(
+        "/organizations/{org_id}/report-mappings",
+        get(get_report_mapping).put(replace_report_mapping),
+    )
+}
+
+/// Statement mapping response.
+#[derive(Debug, Serialize)]
+pub struct ReportMappingResponse {
+    /// Sections and the section of each subtype.
+    #[serde(flatten)]
+    pub mapping: StatementMapping,
+    /// Whether the organization uses the default mapping.
+    pub is_default: bool,
+    /// When the saved mapping was last changed.
+    pub updated_at: Option<String>,
+    /// Who last changed the saved mapping.
+    pub updated_by: Option<Uuid>,
+}
+
+impl From<ResolvedReportMapping> for ReportMappingResponse {
+    fn from(resolved: ResolvedReportMapping) -> Self {
+        Self {
+            mapping: resolved.mapping,
+            is_default: resolved.is_default,
+            updated_at: resolved.updated_at.map(|t| t.to_rfc3339()),
+            updated_by: resolved.updated_by,
+        }
+    }
+}
+
+/// GET /organizations/{org_id}/report-mappings
+///
+/// Returns the mapping the organization's reports use; the default when none
+/// is saved.
+async fn get_report_mapping(
+    State(state): State<AppState>,
+    Path(org_id): Path<Uuid>,
+    auth: AuthMember,
+) -> impl IntoResponse {
+    if let Err(response) = auth.role_in(&state, org_id).await {
+        return response;
+    }
+
+    match ReportMappingRepository::new((*state.db).clone())
+        .resolve(org_id)
+        .await
+    {
+        Ok(resolved) => {
+            (StatusCode::OK, Json(ReportMappingResponse::from(resolved))).into_response()
+        }
+        Err(e) => {
+            error!(error = %e, "Failed to load report mapping");
+            internal_error()
+        }
+    }
+}
+
+/// PUT /organizations/{org_id}/report-mappings
+///
+/// Replaces the organization's mapping. Every account subtype must be mapped
+/// to exactly one section; all problems are reported together.
+async fn replace_report_mapping(
+    State(state): State<AppState>,
+    Path(org_id): Path<Uuid>,
+    auth: AuthMember,
+    Json(mapping): Json<StatementMapping>,
+) -> impl IntoResponse {
+    match auth.role_in(&state, org_id).await {
+        Ok(role) if role.can_modify_settings() => {}
+        Ok(_) => {
+            return (
+                StatusCode::FORBIDDEN,
+                Json(json!({
+                    "error": "forbidden",
+                    "message": "You need admin or owner role to change report mappings"
+                })),
+            )
+                .into_response();
+        }
+        Err(response) => return response,
+    }
+
+    match ReportMappingRepository::new((*state.db).clone())
+        .save(org_id, &mapping, auth.user_id())
+        .await
+    {
+        Ok(resolved) => {
+            info!(org_id = %org_id, "Report mapping replaced");
+            (StatusCode::OK, Json(ReportMappingResponse::from(resolved))).into_response()
+        }
+        Err(ReportMappingError::Invalid(fields)) => (
+            StatusCode::BAD_REQUEST,
+            Json(json!({
+                "error": "invalid_report_mapping",
+                "message": "The report mapping is invalid",
+                "fields": fields
+            })),
+        )
+            .into_response(),
+        Err(ReportMappingError::OrganizationNotFound(_)) => (
+            StatusCode::NOT_FOUND,
+            Json(json!({
+                "error": "not_found",
+                "message": "Organization not found"
+            })),
+        )
+            .into_response(),
+        Err(e) => {
+            error!(error = %e, "Failed to save report mapping");
+            internal_error()
+        }
+    }
+}
+
+fn internal_error() -> Response {
+    (
+        StatusCode::INTERNAL_SERVER_ERROR,
+        Json(json!({
+            "error": "internal_error",
+            "message": "An error occurred"
+        })),
+    )
+        .into_response()
+}
@@ -4,24 +4,49 @@
 
 use axum::{
     Json, Router,
-    extract::{Path, Query, State},
-    http::StatusCode,
-    response::IntoResponse,
-    routing::get,
+    extract::{Path, Query, RawQuery, State},
+    http::{HeaderMap, StatusCode},
+    response::{IntoResponse, Response},
+    routing::{get, post},
 };
+use std::collections::HashMap;
+
 use chrono::NaiveDate;
 use rust_decimal::Decimal;
+use sea_orm::{DatabaseConnection, EntityTrait};
 use serde::{Deserialize, Serialize};
 use serde_json::json;
-use tracing::error;
+use tracing::{error, info, warn};
 use uuid::Uuid;
+use zeltra_shared::format::{ExportStyle, Locale};
 
-use crate::{AppState, middleware::AuthUser};
-use zeltra_core::reports::{BalanceSheetSection, IncomeStatementSection, ReportService};
+use crate::{
+    AppState,
+    middleware::{AuthMember, auth::check_report_access, query_etag, respond_cached},
+    routes::transactions::entry_account_error_response,
+};
+use zeltra_core::reports::{
+    BalanceSheetSection, DataQualityCheck, DataQualityFinding, DimensionAllocation,
+    IncomeStatementReport, IncomeStatementSection, ReportService, StatementMapping,
+    StatementSubsection,
+};
+use zeltra_core::settings::OrganizationSettings;
 use zeltra_db::{
     OrganizationRepository,
-    entities::sea_orm_active_enums::AccountType,
-    repositories::report::{AccountBalance, ReportRepository},
+    entities::{
+        currencies, organizations,
+        sea_orm_active_enums::{AccountType, TransactionType},
+    },
+    repositories::{
+        AccountRepository, CreateTransactionInput, DimensionRepository, FxExposure,
+        FxRevaluationError, FxRevaluationRepository, PeriodEndOutcome, ReportMappingRepository,
+        RevaluationSkip, TransactionError, TransactionRepository, core_account_subtype,
+        report::{
+            AccountBalance, AccountLookup, AccountPeriodMovement, DimensionValueFilter,
+            ForeignCurrencyBalance, ReportError, ReportRepository,
+        },
+        revaluation_entries,
+    },
 };
 
 /// Creates the report routes (requires auth middleware to be applied externally).
@@ -39,13 +64,37 @@ pub fn routes() -> Router<AppState> {
             "/organizations/{org_id}/reports/income-statement",
             get(get_income_statement),
         )
+        .route(
+            "/organizations/{org_id}/reports/income-statement/by-dimension",
+            get(get_income_statement_by_dimension),
+        )
         .route(
             "/organizations/{org_id}/reports/dimensional",
             get(get_dimensional_report),
         )
         .route(
-            "/organizations/{org_id}/accounts/{account_id}/ledger",
-            get(get_account_ledger),
+            "/organizations/{org_id}/reports/voids",
+            get(get_void_report),
+        )
+        .route(
+            "/organizations/{org_id}/reports/account-movement",
+            get(get_account_movement_report),
+        )
+        .route(
+            "/organizations/{org_id}/reports/data-quality",
+            get(get_data_quality_report),
+        )
+        .route(
+            "/organizations/{org_id}/reports/fx-exposure",
+            get(get_fx_exposure_report),
+        )
+        .route(
+            "/organizations/{org_id}/reports/fx-exposure/revalue",
+            post(revalue_fx_exposure),
+        )
+        .route(
+            "/organizations/{org_id}/reports/fx-exposure/period-end",
+            post(run_period_end_revaluation),
         )
 }
 
@@ -80,6 +129,20 @@ pub struct IncomeStatementQuery {
     pub dimensions: Option<String>,
 }
 
+/// Query parameters for the income statement by dimension.
+#[derive(Debug, Deserialize)]
+pub struct DimensionIncomeStatementQuery {
+    /// Start date.
+    pub from: Option<NaiveDate>,
+    /// End date.
+    pub to: Option<NaiveDate>,
+    /// Code of the dimension type to report by.
+    pub dimension_type: String,
+    /// Shares for spreading unallocated amounts, as comma-separated
+    /// `CODE:percent` pairs.
+    pub allocation: Option<String>,
+}
+
 /// Query parameters for dimensional report.
 #[derive(Debug, Deserialize)]
 pub struct DimensionalReportQuery {
@@ -91,27 +154,95 @@ pub struct DimensionalReportQuery {
     pub group_by: String,
     /// Account type filter.
     pub account_type: Option<String>,
-    /// Dimension value IDs to filter by (comma-separated).
+    /// Dimension value IDs to filter by (comma-separated); entries must
+    /// carry all of them.
     pub dimensions: Option<String>,
+    /// Whether entries with no value of an included dimension type are kept.
+    /// The repeatable `dimension` and `exclude_dimension` parameters are read
+    /// from the raw query string.
+    #[serde(default)]
+    pub include_untagged: bool,
 }
 
-/// Query parameters for account ledger.
+/// Most dimension values a dimensional report can include or exclude.
+const MAX_DIMENSION_VALUE_FILTERS: usize = 50;
+
+/// Query parameters for void analysis report.
 #[derive(Debug, Deserialize)]
-pub struct AccountLedgerQuery {
+pub struct VoidReportQuery {
     /// Start date.
     pub from: Option<NaiveDate>,
     /// End date.
     pub to: Option<NaiveDate>,
-    /// Page number (0-indexed).
-    pub page: Option<u64>,
-    /// Items per page.
+}
+
+/// Query parameters for the account movement report.
+#[derive(Debug, Deserialize)]
+pub struct AccountMovementQuery {
+    /// Account ID; give this or `account_code`.
+    pub account_id: Option<Uuid>,
+    /// Account code; give this or `account_id`.
+    pub account_code: Option<String>,
+    /// Fiscal period being explained.
+    pub period_id: Uuid,
+    /// Fiscal period it is compared with.
+    pub compare_period_id: Uuid,
+    /// Number of contributing transactions to list (default: 10, max: 50).
     pub limit: Option<u64>,
 }
 
+/// Query parameters for the FX exposure report.
+#[derive(Debug, Deserialize)]
+pub struct FxExposureQuery {
+    /// Revaluation date (defaults to today).
+    pub as_of: Option<NaiveDate>,
+}
+
+/// Request body for booking an FX revaluation.
+#[derive(Debug, Default, Deserialize)]
+pub struct RevalueFxRequest {
+    /// Revaluation date (defaults to today).
+    pub as_of: Option<NaiveDate>,
+    /// Gain account, overriding the organization setting.
+    pub gain_account_id: Option<Uuid>,
+    /// Loss account, overriding the organization setting.
+    pub loss_account_id: Option<Uuid>,
+}
+
+/// Request body for running the period-end FX revaluation by hand.
+#[derive(Debug, Default, Deserialize)]
+pub struct PeriodEndRevaluationRequest {
+    /// Day the run is for (defaults to today); periods ending on it or the
+    /// day after are revalued.
+    pub as_of: Option<NaiveDate>,
+}
+
 // ============================================================================
 // Response Types
 // ============================================================================
 
+/// How a client should present amounts and dates in a report.
+///
+/// Amounts stay plain decimal strings; this block tells the reader how the
+/// organization wants them shown.
+#[derive(Debug, Clone, Serialize)]
+pub struct FormattingMetadata {
+    /// Base currency code.
+    pub currency: String,
+    /// Base currency symbol.
+    pub symbol: String,
+    /// Decimal places used by the base currency.
+    pub decimal_places: u32,
+    /// Organization timezone (IANA name).
+    pub timezone: String,
+    /// Locale hint from the organization settings.
+    pub locale: Locale,
+    /// Decimal separator for the locale.
+    pub decimal_separator: char,
+    /// Digit group separator for the locale.
+    pub group_separator: char,
+}
+
 /// Response for trial balance report.
 #[derive(Debug, Serialize)]
 pub struct TrialBalanceResponse {
@@ -121,6 +252,8 @@ pub struct TrialBalanceResponse {
     pub as_of: String,
     /// Currency.
     pub currency: String,
+    /// Presentation hints for amounts and dates.
+    pub formatting: FormattingMetadata,
     /// Account balances.
     pub accounts: Vec<AccountBalanceResponse>,
     /// Totals.
@@ -166,6 +299,8 @@ pub struct BalanceSheetResponse {
     pub as_of: String,
     /// Currency.
     pub currency: String,
+    /// Presentation hints for amounts and dates.
+    pub formatting: FormattingMetadata,
     /// Assets section.
     pub assets: BalanceSheetSectionResponse,
     /// Liabilities section.
@@ -187,6 +322,21 @@ pub struct BalanceSheetSectionResponse {
     pub accounts: Vec<AccountBalanceResponse>,
     /// Section total.
     pub total: String,
+    /// Sections from the organization's report mapping, in sort order.
+    pub subsections: Vec<StatementSubsectionResponse>,
+}
+
+/// A mapped section within a statement section.
+#[derive(Debug, Serialize)]
+pub struct StatementSubsectionResponse {
+    /// Section key.
+    pub key: String,
+    /// Section name.
+    pub name: String,
+    /// Section total.
+    pub total: String,
+    /// Accounts in the section, by ID.
+    pub account_ids: Vec<Uuid>,
 }
 
 /// Response for income statement report.
@@ -200,6 +350,8 @@ pub struct IncomeStatementResponse {
     pub period_end: String,
     /// Currency.
     pub currency: String,
+    /// Presentation hints for amounts and dates.
+    pub formatting: FormattingMetadata,
     /// Revenue section.
     pub revenue: IncomeStatementSectionResponse,
     /// Cost of goods sold section.
@@ -223,6 +375,64 @@ pub struct IncomeStatementSectionResponse {
     pub accounts: Vec<AccountBalanceResponse>,
     /// Section total.
     pub total: String,
+    /// Sections from the organization's report mapping, in sort order.
+    pub subsections: Vec<StatementSubsectionResponse>,
+}
+
+/// Income statement figures in a comparative report.
+#[derive(Debug, Serialize)]
+pub struct IncomeStatementFiguresResponse {
+    /// Revenue section.
+    pub revenue: IncomeStatementSectionResponse,
+    /// Cost of goods sold section.
+    pub cost_of_goods_sold: IncomeStatementSectionResponse,
+    /// Gross profit.
+    pub gross_profit: String,
+    /// Operating expenses section.
+    pub operating_expenses: IncomeStatementSectionResponse,
+    /// Operating income.
+    pub operating_income: String,
+    /// Other income/expenses section.
+    pub other_income_expenses: IncomeStatementSectionResponse,
+    /// Net income.
+    pub net_income: String,
+}
+
+/// One dimension value's column in the income statement by dimension.
+#[derive(Debug, Serialize)]
+pub struct DimensionIncomeStatementColumnResponse {
+    /// Dimension value ID, null for the unallocated column.
+    pub dimension_value_id: Option<Uuid>,
+    /// Dimension value code.
+    pub code: String,
+    /// Dimension value name.
+    pub name: String,
+    /// Income statement for this column.
+    #[serde(flatten)]
+    pub statement: IncomeStatementFiguresResponse,
+}
+
+/// Response for the income statement by dimension.
+#[derive(Debug, Serialize)]
+pub struct DimensionIncomeStatementResponse {
+    /// Report type.
+    pub report_type: String,
+    /// Period start.
+    pub period_start: String,
+    /// Period end.
+    pub period_end: String,
+    /// Currency.
+    pub currency: String,
+    /// Presentation hints for amounts and dates.
+    pub formatting: FormattingMetadata,
+    /// Code of the dimension type reported by.
+    pub dimension_type: String,
+    /// Whether unallocated amounts were spread over the values.
+    pub allocated: bool,
+    /// One column per active dimension value, then unallocated.
+    pub columns: Vec<DimensionIncomeStatementColumnResponse>,
+    /// Income statement across all columns.
+    pub total: IncomeStatementFiguresResponse,
 }
 
 /// Response for dimensional report.
@@ -236,6 +446,8 @@ pub struct DimensionalReportResponse {
     pub period_end: String,
     /// Currency.
     pub currency: String,
+    /// Presentation hints for amounts and dates.
+    pub formatting: FormattingMetadata,
     /// Grouped by dimensions.
     pub group_by: Vec<String>,
     /// Report rows.
@@ -268,102 +480,452 @@ pub struct DimensionValueResponse {
     pub name: String,
 }
 
-/// Response for account ledger.
+/// Response for void analysis report.
+#[derive(Debug, Serialize)]
+pub struct VoidReportResponse {
+    /// Report type.
+    pub report_type: String,
+    /// Period start.
+    pub from: String,
+    /// Period end.
+    pub to: String,
+    /// Currency.
+    pub currency: String,
+    /// Presentation hints for amounts and dates.
+    pub formatting: FormattingMetadata,
+    /// Voids grouped by reason code.
+    pub reasons: Vec<VoidReasonResponse>,
+    /// Totals across all reasons.
+    pub totals: VoidReportTotals,
+}
+
+/// Void count and amount for one reason code.
+#[derive(Debug, Serialize)]
+pub struct VoidReasonResponse {
+    /// Reason code, or `unspecified` for voids recorded without one.
+    pub reason_code: String,
+    /// Number of voided transactions.
+    pub count: u64,
+    /// Total voided amount.
+    pub total: String,
+}
+
+/// Void report totals.
 #[derive(Debug, Serialize)]
-pub struct AccountLedgerResponse {
+pub struct VoidReportTotals {
+    /// Number of voided transactions.
+    pub count: u64,
+    /// Total voided amount.
+    pub total: String,
+}
+
+/// Response for the account movement report.
+#[derive(Debug, Serialize)]
+pub struct AccountMovementResponse {
+    /// Report type.
+    pub report_type: String,
+    /// Currency.
+    pub currency: String,
+    /// Presentation hints for amounts and dates.
+    pub formatting: FormattingMetadata,
+    /// The account.
+    pub account: MovementAccountResponse,
+    /// Movement in the period being explained.
+    pub current: PeriodMovementResponse,
+    /// Movement in the comparison period.
+    pub compare: PeriodMovementResponse,
+    /// Change in net movement from the comparison period.
+    pub change: MovementChangeResponse,
+    /// Largest contributors to the current period's movement.
+    pub top_transactions: Vec<MovementTransactionResponse>,
+}
+
+/// Account in the movement report.
+#[derive(Debug, Serialize)]
+pub struct MovementAccountResponse {
     /// Account ID.
-    pub account_id: Uuid,
+    pub id: Uuid,
     /// Account code.
     pub code: String,
     /// Account name.
     pub name: String,
-    /// Entries.
-    pub entries: Vec<LedgerEntryResponse>,
-    /// Pagination.
-    pub pagination: PaginationResponse,
+    /// Account type.
+    pub account_type: String,
 }
 
-/// Ledger entry response.
+/// An account's movement over one fiscal period.
 #[derive(Debug, Serialize)]
-pub struct LedgerEntryResponse {
-    /// Entry ID.
-    pub id: Uuid,
+pub struct PeriodMovementResponse {
+    /// Fiscal period ID.
+    pub period_id: Uuid,
+    /// Fiscal period name.
+    pub period_name: String,
+    /// Period start.
+    pub start_date: String,
+    /// Period end.
+    pub end_date: String,
+    /// Balance before the period.
+    pub opening_balance: String,
+    /// Debits in the period.
+    pub total_debit: String,
+    /// Credits in the period.
+    pub total_credit: String,
+    /// Balance at the end of the period.
+    pub closing_balance: String,
+    /// Closing less opening balance.
+    pub net_change: String,
+}
+
+/// Change between the two periods.
+#[derive(Debug, Serialize)]
+pub struct MovementChangeResponse {
+    /// Difference in net movement.
+    pub absolute: String,
+    /// Difference as a percentage of the comparison period's movement;
+    /// `None` when that period had none.
+    pub percent: Option<String>,
+}
+
+/// A transaction contributing to the current period.
+#[derive(Debug, Serialize)]
+pub struct MovementTransactionResponse {
     /// Transaction ID.
-    pub transaction_id: Uuid,
+    pub id: Uuid,
     /// Transaction date.
     pub transaction_date: String,
+    /// Reference number.
+    pub reference_number: Option<String>,
     /// Description.
     pub description: String,
+    /// Net amount on the account's normal side.
+    pub amount: String,
+}
+
+/// Data-quality report response.
+#[derive(Debug, Serialize)]
+pub struct DataQualityReportResponse {
+    /// Report type identifier.
+    pub report_type: String,
+    /// Finding count per check, including checks with none.
+    pub checks: Vec<DataQualityCheckSummary>,
+    /// Every finding, grouped by check.
+    pub findings: Vec<DataQualityFinding>,
+}
+
+/// Number of findings for one check.
+#[derive(Debug, Serialize)]
+pub struct DataQualityCheckSummary {
+    /// Check code.
+    pub check: DataQualityCheck,
+    /// Number of findings.
+    pub count: usize,
+}
+
+/// FX exposure report response.
+#[derive(Debug, Serialize)]
+pub struct FxExposureResponse {
+    /// Report type identifier.
+    pub report_type: String,
+    /// Revaluation date.
+    pub as_of: String,
+    /// Functional currency.
+    pub currency: String,
+    /// Presentation hints for amounts and dates.
+    pub formatting: FormattingMetadata,
+    /// Exposure and unrealized gain/loss per currency.
+    pub currencies: Vec<FxCurrencyResponse>,
+    /// Exposure and unrealized gain/loss per account and currency.
+    pub accounts: Vec<FxAccountResponse>,
+    /// Net unrealized gain (positive) or loss (negative).
+    pub total_unrealized: String,
+    /// Currencies with balances but no rate on or before `as_of`.
+    pub missing_rates: Vec<String>,
+}
+
+/// Exposure in one currency.
+#[derive(Debug, Serialize)]
+pub struct FxCurrencyResponse {
     /// Source currency.
-    pub source_currency: String,
-    /// Source amount.
-    pub source_amount: String,
-    /// Exchange rate.
-    pub exchange_rate: String,
-    /// Functional amount.
-    pub functional_amount: String,
-    /// Debit.
-    pub debit: String,
-    /// Credit.
-    pub credit: String,
-    /// Running balance.
-    pub running_balance: String,
-    /// Dimensions.
-    pub dimensions: Vec<DimensionValueResponse>,
+    pub currency: String,
+    /// Rate to the functional currency used for revaluation.
+    pub rate: String,
+    /// Effective date of that rate.
+    pub rate_date: String,
+    /// Net balance in the source currency.
+    pub source_balance: String,
+    /// Functional value at historical rates.
+    pub booked_functional: String,
+    /// Functional value at the revaluation rate.
+    pub revalued_functional: String,
+    /// Unrealized gain (positive) or loss (negative).
+    pub unrealized: String,
+}
+
+/// Exposure of one account in one currency.
+#[derive(Debug, Serialize)]
+pub struct FxAccountResponse {
+    /// Account ID.
+    pub account_id: Uuid,
+    /// Account code.
+    pub code: String,
+    /// Account name.
+    pub name: String,
+    /// Account type.
+    pub account_type: String,
+    /// Source currency.
+    pub currency: String,
+    /// Net balance in the source currency.
+    pub source_balance: String,
+    /// Rate to the functional currency used for revaluation.
+    pub rate: String,
+    /// Functional value at historical rates.
+    pub booked_functional: String,
+    /// Functional value at the revaluation rate.
+    pub revalued_functional: String,
+    /// Unrealized gain (positive) or loss (negative).
+    pub unrealized: String,
+}
+
+/// Draft revaluation transaction created from the FX exposure report.
+#[derive(Debug, Serialize)]
+pub struct RevalueFxResponse {
+    /// Draft transaction ID.
+    pub transaction_id: Uuid,
+    /// Transaction status (always `draft`).
+    pub status: String,
+    /// Transaction date.
+    pub transaction_date: String,
+    /// Net unrealized gain (positive) or loss (negative) booked.
+    pub total_unrealized: String,
+    /// Adjustment lines.
+    pub entries: Vec<RevaluationEntryResponse>,
+}
+
+/// Result of a manual period-end FX revaluation run.
+#[derive(Debug, Serialize)]
+pub struct PeriodEndRevaluationResponse {
+    /// Day the run was for.
+    pub as_of: String,
+    /// One result per fiscal period due.
+    pub periods: Vec<PeriodEndPeriodResponse>,
+}
+
+/// What the period-end run did for one fiscal period.
+#[derive(Debug, Serialize)]
+pub struct PeriodEndPeriodResponse {
+    /// Fiscal period ID.
+    pub fiscal_period_id: Uuid,
+    /// Fiscal period name.
+    pub period_name: String,
+    /// Last day of the period.
+    pub period_end: String,
+    /// Reference of the revaluation draft.
+    pub reference: String,
+    /// `created`, `exists`, `nothing_to_revalue`, `skipped` or
+    /// `multi_currency_disabled`.
+    pub outcome: &'static str,
+    /// The draft created, or the existing transaction with the reference.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub transaction_id: Option<Uuid>,
+    /// Net unrealized gain (positive) or loss (negative) of a new draft.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub total_unrealized: Option<String>,
+    /// Why the period was skipped.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub skip: Option<RevaluationSkip>,
 }
 
-/// Pagination response.
+/// One line of a revaluation transaction.
 #[derive(Debug, Serialize)]
-pub struct PaginationResponse {
-    /// Current page.
-    pub page: u64,
-    /// Items per page.
-    pub limit: u64,
-    /// Total items.
-    pub total: u64,
-    /// Total pages.
-    pub total_pages: u64,
+pub struct RevaluationEntryResponse {
+    /// Account ID.
+    pub account_id: Uuid,
+    /// Currency whose balance is restated; absent for the gain/loss side.
+    pub currency: Option<String>,
+    /// Debit amount.
+    pub debit: String,
+    /// Credit amount.
+    pub credit: String,
 }
 
 // ============================================================================
 // Helper Functions
 // ============================================================================
 
-/// Checks if user is a member of the organization.
-async fn check_membership(
-    org_repo: &OrganizationRepository,
+/// Loads the statement mapping the organization's reports use.
+async fn load_report_mapping(
+    state: &AppState,
     org_id: Uuid,
-    user_id: Uuid,
-) -> Result<(), axum::response::Response> {
-    match org_repo.is_member(org_id, user_id).await {
-        Ok(true) => Ok(()),
-        Ok(false) => Err((
-            StatusCode::FORBIDDEN,
+) -> Result<StatementMapping, axum::response::Response> {
+    ReportMappingRepository::new((*state.db).clone())
+        .resolve(org_id)
+        .await
+        .map(|resolved| resolved.mapping)
+        .map_err(|e| {
+            error!(error = %e, "Failed to load report mapping");
+            internal_error()
+        })
+}
+
+/// Parses comma-separated UUIDs from a string.
+fn parse_uuid_list(s: &str) -> Vec<Uuid> {
+    s.split(',')
+        .filter_map(|part| Uuid::parse_str(part.trim()).ok())
+        .collect()
+}
+
+/// Values of a repeatable query parameter, e.g. `?dimension=a&dimension=b`.
+fn repeated_param<'a>(raw_query: Option<&'a str>, key: &str) -> Vec<&'a str> {
+    raw_query
+        .unwrap_or_default()
+        .split('&')
+        .filter_map(|pair| pair.split_once('='))
+        .filter(|(name, _)| *name == key)
+        .map(|(_, value)| value)
+        .collect()
+}
+
+/// Builds the dimension value filter of a dimensional report query.
+#[allow(clippy::result_large_err)]
+fn dimension_value_filter(
+    query: &DimensionalReportQuery,
+    raw_query: Option<&str>,
+) -> Result<DimensionValueFilter, Response> {
+    let include = repeated_param(raw_query, "dimension");
+    let exclude = repeated_param(raw_query, "exclude_dimension");
+    if include.len() + exclude.len() > MAX_DIMENSION_VALUE_FILTERS {
+        return Err((
+            StatusCode::BAD_REQUEST,
             Json(json!({
-                "error": "forbidden",
-                "message": "You are not a member of this organization"
+                "error": "too_many_dimensions",
+                "message": format!(
+                    "At most {MAX_DIMENSION_VALUE_FILTERS} dimension values can be included or excluded"
+                )
             })),
         )
-            .into_response()),
-        Err(e) => {
-            error!(error = %e, "Failed to check membership");
-            Err((
+            .into_response());
+    }
+
+    let parse = |values: Vec<&str>| {
+        values
+            .into_iter()
+            .map(|value| {
+                Uuid::parse_str(value).map_err(|_| {
+                    (
+                        StatusCode::BAD_REQUEST,
+                        Json(json!({
+                            "error": "invalid_dimension",
+                            "message": format!("Invalid dimension value ID: {value}")
+                        })),
+                    )
+                        .into_response()
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()
+    };
+
+    Ok(DimensionValueFilter {
+        all_of: query
+            .dimensions
+            .as_ref()
+            .map(|s| parse_uuid_list(s))
+            .unwrap_or_default(),
+        include: parse(include)?,
+        exclude: parse(exclude)?,
+        include_untagged: query.include_untagged,
+    })
+}
+
+/// Builds the formatting metadata for an organization's reports.
+///
+/// The locale falls back to the default if the stored settings cannot be
+/// read, so a bad settings document never blocks a report.
+pub(crate) async fn formatting_metadata(
+    db: &DatabaseConnection,
+    org: &organizations::Model,
+) -> Result<FormattingMetadata, Response> {
+    let currency = currencies::Entity::find_by_id(&org.base_currency)
+        .one(db)
+        .await
+        .map_err(|e| {
+            error!(error = %e, "Failed to get base currency");
+            (
                 StatusCode::INTERNAL_SERVER_ERROR,
                 Json(json!({
                     "error": "internal_error",
                     "message": "An error occurred"
                 })),
             )
-                .into_response())
+                .into_response()
+        })?;
+    let locale = match OrganizationSettings::from_value(&org.settings) {
+        Ok(settings) => settings.formatting.locale,
+        Err(e) => {
+            warn!(error = %e, "Stored organization settings are invalid");
+            Locale::default()
         }
-    }
+    };
+
+    Ok(FormattingMetadata {
+        currency: org.base_currency.clone(),
+        symbol: currency
+            .as_ref()
+            .map_or_else(|| org.base_currency.clone(), |c| c.symbol.clone()),
+        decimal_places: currency
+            .and_then(|c| u32::try_from(c.decimal_places).ok())
+            .unwrap_or(2),
+        timezone: org.timezone.clone(),
+        locale,
+        decimal_separator: locale.decimal_separator(),
+        group_separator: locale.group_separator(),
+    })
 }
 
-/// Parses comma-separated UUIDs from a string.
-fn parse_uuid_list(s: &str) -> Vec<Uuid> {
-    s.split(',')
-        .filter_map(|part| Uuid::parse_str(part.trim()).ok())
-        .collect()
+/// Chooses how a CSV export writes amounts and dates.
+///
+/// Exports are plain unless `formatted` is set, in which case they follow
+/// the organization's locale and round to its base currency's decimal
+/// places.
+pub(crate) async fn export_style(
+    db: &DatabaseConnection,
+    org_id: Uuid,
+    formatted: bool,
+) -> Result<ExportStyle, Response> {
+    if !formatted {
+        return Ok(ExportStyle::Plain);
+    }
+    let org = match OrganizationRepository::new(db.clone())
+        .find_by_id(org_id)
+        .await
+    {
+        Ok(Some(org)) => org,
+        Ok(None) => {
+            return Err((
+                StatusCode::NOT_FOUND,
+                Json(json!({
+                    "error": "not_found",
+                    "message": "Organization not found"
+                })),
+            )
+                .into_response());
+        }
+        Err(e) => {
+            error!(error = %e, "Failed to get organization");
+            return Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({
+                    "error": "internal_error",
+                    "message": "An error occurred"
+                })),
+            )
+                .into_response());
+        }
+    };
+    let formatting = formatting_metadata(db, &org).await?;
+    Ok(ExportStyle::Localized {
+        locale: formatting.locale,
+        decimal_places: formatting.decimal_places,
+    })
 }
 
 /// Formats a Decimal as a string with 4 decimal places.
@@ -424,6 +986,7 @@ fn balance_sheet_section_to_response(section: &BalanceSheetSection) -> BalanceSh
             })
             .collect(),
         total: format_money(section.total),
+        subsections: subsections_to_response(&section.subsections),
     }
 }
 
@@ -446,9 +1009,62 @@ fn income_statement_section_to_response(
             })
             .collect(),
         total: format_money(section.total),
+        subsections: subsections_to_response(&section.subsections),
+    }
+}
+
+/// Converts mapped subsections to responses.
+fn subsections_to_response(
+    subsections: &[StatementSubsection],
+) -> Vec<StatementSubsectionResponse> {
+    subsections
+        .iter()
+        .map(|s| StatementSubsectionResponse {
+            key: s.key.clone(),
+            name: s.name.clone(),
+            total: format_money(s.total),
+            account_ids: s.accounts.iter().map(|a| a.account_id).collect(),
+        })
+        .collect()
+}
+
+/// Converts an income statement to its figures in a comparative response.
+fn income_statement_figures(report: &IncomeStatementReport) -> IncomeStatementFiguresResponse {
+    IncomeStatementFiguresResponse {
+        revenue: income_statement_section_to_response(&report.revenue),
+        cost_of_goods_sold: income_statement_section_to_response(&report.cost_of_goods_sold),
+        gross_profit: format_money(report.gross_profit),
+        operating_expenses: income_statement_section_to_response(&report.operating_expenses),
+        operating_income: format_money(report.operating_income),
+        other_income_expenses: income_statement_section_to_response(&report.other_income_expense),
+        net_income: format_money(report.net_income),
     }
 }
 
+/// Parses `CODE:percent` pairs, resolving codes against the reported values.
+fn parse_allocation(
+    spec: &str,
+    values: &[zeltra_core::reports::ReportDimensionValue],
+) -> Result<DimensionAllocation, String> {
+    let mut shares = Vec::new();
+    for pair in spec.split(',').map(str::trim).filter(|p| !p.is_empty()) {
+        let Some((code, percentage)) = pair.split_once(':') else {
+            return Err(format!("'{pair}' is not CODE:percent"));
+        };
+        let Some(value) = values.iter().find(|v| v.code == code.trim()) else {
+            return Err(format!(
+                "'{}' is not an active value of the dimension type",
+                code.trim()
+            ));
+        };
+        let Ok(percentage) = percentage.trim().parse::<Decimal>() else {
+            return Err(format!("'{}' is not a percentage", percentage.trim()));
+        };
+        shares.push((value.id, percentage));
+    }
+    Ok(DimensionAllocation { shares })
+}
+
 // ============================================================================
 // Route Handlers
 // ============================================================================
@@ -461,74 +1077,539 @@ async fn get_trial_balance(
     State(state): State<AppState>,
     Path(org_id): Path<Uuid>,
     Query(query): Query<TrialBalanceQuery>,
-    auth_user: AuthUser,
+    auth_user: AuthMember,
+    RawQuery(raw_query): RawQuery,
+    headers: HeaderMap,
 ) -> impl IntoResponse {
     let org_repo = OrganizationRepository::new((*state.db).clone());
 
-    // Check membership
-    if let Err(response) = check_membership(&org_repo, org_id, auth_user.user_id()).await {
+    // Check report read access
+    if let Err(response) = check_report_access(&state, org_id, &auth_user).await {
         return response;
     }
 
-    // Get organization for currency
-    let org = match org_repo.find_by_id(org_id).await {
-        Ok(Some(org)) => org,
-        Ok(None) => {
-            return (
-                StatusCode::NOT_FOUND,
-                Json(json!({
-                    "error": "not_found",
-                    "message": "Organization not found"
-                })),
-            )
-                .into_response();
-        }
-        Err(e) => {
-            error!(error = %e, "Failed to get organization");
+    let etag = match query_etag(&state.db, org_id, "trial_balance", raw_query.as_deref()).await {
+        Ok(etag) => etag,
+        Err(response) => return response,
+    };
+
+    respond_cached(&headers, &etag, move || async move {
+        // Get organization for currency
+        let org = match org_repo.find_by_id(org_id).await {
+            Ok(Some(org)) => org,
+            Ok(None) => {
+                return (
+                    StatusCode::NOT_FOUND,
+                    Json(json!({
+                        "error": "not_found",
+                        "message": "Organization not found"
+                    })),
+                )
+                    .into_response();
+            }
+            Err(e) => {
+                error!(error = %e, "Failed to get organization");
+                return (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    Json(json!({
+                        "error": "internal_error",
+                        "message": "An error occurred"
+                    })),
+                )
+                    .into_response();
+            }
+        };
+
+        let as_of = query
+            .as_of
+            .unwrap_or_else(|| chrono::Utc::now().date_naive());
+        let dimension_filters = query
+            .dimensions
+            .as_ref()
+            .map(|s| parse_uuid_list(s))
+            .unwrap_or_default();
+
+        let report_repo = ReportRepository::new((*state.db).clone());
+
+        // Query account balances
+        let balances = match report_repo
+            .query_trial_balance(org_id, as_of, &dimension_filters)
+            .await
+        {
+            Ok(b) => b,
+            Err(e) => {
+                error!(error = %e, "Failed to query trial balance");
+                return (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    Json(json!({
+                        "error": "internal_error",
+                        "message": "Failed to generate trial balance"
+                    })),
+                )
+                    .into_response();
+            }
+        };
+
+        // Generate trial balance report using core service
+        let mut report = ReportService::generate_trial_balance(
+            balances
+                .iter()
+                .map(|ab| zeltra_core::reports::AccountBalance {
+                    account_id: ab.account_id,
+                    code: ab.code.clone(),
+                    name: ab.name.clone(),
+                    account_type: account_type_to_string(&ab.account_type),
+                    account_subtype: ab.account_subtype.as_ref().map(account_subtype_to_string),
+                    total_debit: ab.total_debit,
+                    total_credit: ab.total_credit,
+                    balance: ab.balance,
+                })
+                .collect(),
+        );
+        report.round_totals(state.currencies.decimal_places(&org.base_currency));
+
+        let formatting = match formatting_metadata(&state.db, &org).await {
+            Ok(formatting) => formatting,
+            Err(response) => return response,
+        };
+
+        let response = TrialBalanceResponse {
+            report_type: "trial_balance".to_string(),
+            as_of: as_of.to_string(),
+            currency: org.base_currency,
+            formatting,
+            accounts: balances.iter().map(account_balance_to_response).collect(),
+            totals: TrialBalanceTotals {
+                total_debit: format_money(report.totals.total_debit),
+                total_credit: format_money(report.totals.total_credit),
+                is_balanced: report.totals.is_balanced,
+            },
+        };
+
+        (StatusCode::OK, Json(response)).into_response()
+    })
+    .await
+}
+
+/// GET /organizations/{org_id}/reports/balance-sheet
+///
+/// Requirement 14.2: Balance sheet report endpoint
+#[axum::debug_handler]
+async fn get_balance_sheet(
+    State(state): State<AppState>,
+    Path(org_id): Path<Uuid>,
+    Query(query): Query<BalanceSheetQuery>,
+    auth_user: AuthMember,
+    RawQuery(raw_query): RawQuery,
+    headers: HeaderMap,
+) -> impl IntoResponse {
+    let org_repo = OrganizationRepository::new((*state.db).clone());
+
+    // Check report read access
+    if let Err(response) = check_report_access(&state, org_id, &auth_user).await {
+        return response;
+    }
+
+    let etag = match query_etag(&state.db, org_id, "balance_sheet", raw_query.as_deref()).await {
+        Ok(etag) => etag,
+        Err(response) => return response,
+    };
+
+    respond_cached(&headers, &etag, move || async move {
+        // Get organization for currency
+        let org = match org_repo.find_by_id(org_id).await {
+            Ok(Some(org)) => org,
+            Ok(None) => {
+                return (
+                    StatusCode::NOT_FOUND,
+                    Json(json!({
+                        "error": "not_found",
+                        "message": "Organization not found"
+                    })),
+                )
+                    .into_response();
+            }
+            Err(e) => {
+                error!(error = %e, "Failed to get organization");
+                return (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    Json(json!({
+                        "error": "internal_error",
+                        "message": "An error occurred"
+                    })),
+                )
+                    .into_response();
+            }
+        };
+
+        let as_of = query
+            .as_of
+            .unwrap_or_else(|| chrono::Utc::now().date_naive());
+
+        let report_repo = ReportRepository::new((*state.db).clone());
+
+        // Query account balances
+        let balances = match report_repo.query_balance_sheet(org_id, as_of).await {
+            Ok(b) => b,
+            Err(e) => {
+                error!(error = %e, "Failed to query balance sheet");
+                return (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    Json(json!({
+                        "error": "internal_error",
+                        "message": "Failed to generate balance sheet"
+                    })),
+                )
+                    .into_response();
+            }
+        };
+
+        let mapping = match load_report_mapping(&state, org_id).await {
+            Ok(mapping) => mapping,
+            Err(response) => return response,
+        };
+
+        // Generate balance sheet report using core service
+        let mut report = ReportService::generate_balance_sheet_with_mapping(
+            balances
+                .iter()
+                .map(|ab| zeltra_core::reports::AccountBalance {
+                    account_id: ab.account_id,
+                    code: ab.code.clone(),
+                    name: ab.name.clone(),
+                    account_type: account_type_to_string(&ab.account_type),
+                    account_subtype: ab.account_subtype.as_ref().map(account_subtype_to_string),
+                    total_debit: ab.total_debit,
+                    total_credit: ab.total_credit,
+                    balance: ab.balance,
+                })
+                .collect(),
+            &mapping,
+        );
+        report.round_totals(state.currencies.decimal_places(&org.base_currency));
+
+        let formatting = match formatting_metadata(&state.db, &org).await {
+            Ok(formatting) => formatting,
+            Err(response) => return response,
+        };
+
+        let response = BalanceSheetResponse {
+            report_type: "balance_sheet".to_string(),
+            as_of: as_of.to_string(),
+            currency: org.base_currency,
+            formatting,
+            assets: balance_sheet_section_to_response(&report.assets),
+            liabilities: balance_sheet_section_to_response(&report.liabilities),
+            equity: balance_sheet_section_to_response(&report.equity),
+            total_assets: format_money(report.total_assets),
+            total_liabilities_and_equity: format_money(report.liabilities_and_equity),
+            is_balanced: report.is_balanced,
+        };
+
+        (StatusCode::OK, Json(response)).into_response()
+    })
+    .await
+}
+
+/// GET /organizations/{org_id}/reports/income-statement
+///
+/// Requirement 14.3: Income statement report endpoint
+#[allow(clippy::too_many_lines)]
+#[axum::debug_handler]
+async fn get_income_statement(
+    State(state): State<AppState>,
+    Path(org_id): Path<Uuid>,
+    Query(query): Query<IncomeStatementQuery>,
+    auth_user: AuthMember,
+    RawQuery(raw_query): RawQuery,
+    headers: HeaderMap,
+) -> impl IntoResponse {
+    let org_repo = OrganizationRepository::new((*state.db).clone());
+
+    // Check report read access
+    if let Err(response) = check_report_access(&state, org_id, &auth_user).await {
+        return response;
+    }
+
+    let etag = match query_etag(&state.db, org_id, "income_statement", raw_query.as_deref()).await {
+        Ok(etag) => etag,
+        Err(response) => return response,
+    };
+
+    respond_cached(&headers, &etag, move || async move {
+        // Get organization for currency
+        let org = match org_repo.find_by_id(org_id).await {
+            Ok(Some(org)) => org,
+            Ok(None) => {
+                return (
+                    StatusCode::NOT_FOUND,
+                    Json(json!({
+                        "error": "not_found",
+                        "message": "Organization not found"
+                    })),
+                )
+                    .into_response();
+            }
+            Err(e) => {
+                error!(error = %e, "Failed to get organization");
+                return (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    Json(json!({
+                        "error": "internal_error",
+                        "message": "An error occurred"
+                    })),
+                )
+                    .into_response();
+            }
+        };
+
+        // Default to current month if not specified
+        let today = chrono::Utc::now().date_naive();
+        let from = query.from.unwrap_or_else(|| {
+            NaiveDate::from_ymd_opt(today.year(), today.month(), 1).unwrap_or(today)
+        });
+        let to = query.to.unwrap_or(today);
+
+        // Validate date range
+        if from > to {
             return (
-                StatusCode::INTERNAL_SERVER_ERROR,
+                StatusCode::BAD_REQUEST,
                 Json(json!({
-                    "error": "internal_error",
-                    "message": "An error occurred"
+                    "error": "invalid_date_range",
+                    "message": "Start date must be before or equal to end date"
                 })),
             )
                 .into_response();
         }
-    };
 
-    let as_of = query
-        .as_of
-        .unwrap_or_else(|| chrono::Utc::now().date_naive());
-    let dimension_filters = query
-        .dimensions
-        .as_ref()
-        .map(|s| parse_uuid_list(s))
-        .unwrap_or_default();
+        let dimension_filters = query
+            .dimensions
+            .as_ref()
+            .map(|s| parse_uuid_list(s))
+            .unwrap_or_default();
+
+        let report_repo = ReportRepository::new((*state.db).clone());
+
+        // Query account balances
+        let balances = match report_repo
+            .query_income_statement(org_id, from, to, &dimension_filters)
+            .await
+        {
+            Ok(b) => b,
+            Err(e) => {
+                error!(error = %e, "Failed to query income statement");
+                return (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    Json(json!({
+                        "error": "internal_error",
+                        "message": "Failed to generate income statement"
+                    })),
+                )
+                    .into_response();
+            }
+        };
+
+        let mapping = match load_report_mapping(&state, org_id).await {
+            Ok(mapping) => mapping,
+            Err(response) => return response,
+        };
+
+        // Generate income statement report using core service
+        let mut report = ReportService::generate_income_statement_with_mapping(
+            balances
+                .iter()
+                .map(|ab| zeltra_core::reports::AccountBalance {
+                    account_id: ab.account_id,
+                    code: ab.code.clone(),
+                    name: ab.name.clone(),
+                    account_type: account_type_to_string(&ab.account_type),
+                    account_subtype: ab.account_subtype.as_ref().map(account_subtype_to_string),
+                    total_debit: ab.total_debit,
+                    total_credit: ab.total_credit,
+                    balance: ab.balance,
+                })
+                .collect(),
+            &mapping,
+        );
+        report.round_totals(state.currencies.decimal_places(&org.base_currency));
+
+        let formatting = match formatting_metadata(&state.db, &org).await {
+            Ok(formatting) => formatting,
+            Err(response) => return response,
+        };
+
+        let response = IncomeStatementResponse {
+            report_type: "income_statement".to_string(),
+            period_start: from.to_string(),
+            period_end: to.to_string(),
+            currency: org.base_currency,
+            formatting,
+            revenue: income_statement_section_to_response(&report.revenue),
+            cost_of_goods_sold: income_statement_section_to_response(&report.cost_of_goods_sold),
+            gross_profit: format_money(report.gross_profit),
+            operating_expenses: income_statement_section_to_response(&report.operating_expenses),
+            operating_income: format_money(report.operating_income),
+            other_income_expenses: income_statement_section_to_response(
+                &report.other_income_expense,
+            ),
+            net_income: format_money(report.net_income),
+        };
+
+        (StatusCode::OK, Json(response)).into_response()
+    })
+    .await
+}
 
-    let report_repo = ReportRepository::new((*state.db).clone());
+use chrono::Datelike;
 
-    // Query account balances
-    let balances = match report_repo
-        .query_trial_balance(org_id, as_of, &dimension_filters)
-        .await
+/// GET /organizations/{org_id}/reports/income-statement/by-dimension
+///
+/// Income statement with one column per active value of a dimension type,
+/// such as a P&L per department.
+#[allow(clippy::too_many_lines)]
+#[axum::debug_handler]
+async fn get_income_statement_by_dimension(
+    State(state): State<AppState>,
+    Path(org_id): Path<Uuid>,
+    Query(query): Query<DimensionIncomeStatementQuery>,
+    auth_user: AuthMember,
+    RawQuery(raw_query): RawQuery,
+    headers: HeaderMap,
+) -> impl IntoResponse {
+    let org_repo = OrganizationRepository::new((*state.db).clone());
+
+    // Check report read access
+    if let Err(response) = check_report_access(&state, org_id, &auth_user).await {
+        return response;
+    }
+
+    let etag = match query_etag(
+        &state.db,
+        org_id,
+        "income_statement_by_dimension",
+        raw_query.as_deref(),
+    )
+    .await
     {
-        Ok(b) => b,
-        Err(e) => {
-            error!(error = %e, "Failed to query trial balance");
+        Ok(etag) => etag,
+        Err(response) => return response,
+    };
+
+    respond_cached(&headers, &etag, move || async move {
+        let org = match org_repo.find_by_id(org_id).await {
+            Ok(Some(org)) => org,
+            Ok(None) => {
+                return (
+                    StatusCode::NOT_FOUND,
+                    Json(json!({
+                        "error": "not_found",
+                        "message": "Organization not found"
+                    })),
+                )
+                    .into_response();
+            }
+            Err(e) => {
+                error!(error = %e, "Failed to get organization");
+                return (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    Json(json!({
+                        "error": "internal_error",
+                        "message": "An error occurred"
+                    })),
+                )
+                    .into_response();
+            }
+        };
+
+        // Default to current month if not specified
+        let today = chrono::Utc::now().date_naive();
+        let from = query.from.unwrap_or_else(|| {
+            NaiveDate::from_ymd_opt(today.year(), today.month(), 1).unwrap_or(today)
+        });
+        let to = query.to.unwrap_or(today);
+
+        if from > to {
             return (
-                StatusCode::INTERNAL_SERVER_ERROR,
+                StatusCode::BAD_REQUEST,
                 Json(json!({
-                    "error": "internal_error",
-                    "message": "Failed to generate trial balance"
+                    "error": "invalid_date_range",
+                    "message": "Start date must be before or equal to end date"
                 })),
             )
                 .into_response();
         }
-    };
 
-    // Generate trial balance report using core service
-    let report = ReportService::generate_trial_balance(
-        balances
+        let invalid_dimension_type = || {
+            (
+                StatusCode::BAD_REQUEST,
+                Json(json!({
+                    "error": "invalid_dimension_type",
+                    "message": format!("Dimension type not found: {}", query.dimension_type)
+                })),
+            )
+                .into_response()
+        };
+
+        let dimension_type = match DimensionRepository::new((*state.db).clone())
+            .find_dimension_type_by_code(org_id, &query.dimension_type)
+            .await
+        {
+            Ok(Some(dimension_type)) => dimension_type,
+            Ok(None) => return invalid_dimension_type(),
+            Err(e) => {
+                error!(error = %e, "Failed to find dimension type");
+                return (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    Json(json!({
+                        "error": "internal_error",
+                        "message": "An error occurred"
+                    })),
+                )
+                    .into_response();
+            }
+        };
+
+        let data = match ReportRepository::new((*state.db).clone())
+            .query_income_statement_by_dimension(org_id, from, to, dimension_type.id)
+            .await
+        {
+            Ok(data) => data,
+            Err(ReportError::InvalidDimensionType(_)) => return invalid_dimension_type(),
+            Err(e) => {
+                error!(error = %e, "Failed to query income statement by dimension");
+                return (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    Json(json!({
+                        "error": "internal_error",
+                        "message": "Failed to generate income statement"
+                    })),
+                )
+                    .into_response();
+            }
+        };
+
+        let invalid_allocation = |message: String| {
+            (
+                StatusCode::BAD_REQUEST,
+                Json(json!({
+                    "error": "invalid_allocation",
+                    "message": message
+                })),
+            )
+                .into_response()
+        };
+        let allocation = match query
+            .allocation
+            .as_deref()
+            .map(|spec| parse_allocation(spec, &data.values))
+            .transpose()
+        {
+            Ok(allocation) => allocation,
+            Err(message) => return invalid_allocation(message),
+        };
+
+        let accounts: Vec<zeltra_core::reports::AccountBalance> = data
+            .accounts
             .iter()
             .map(|ab| zeltra_core::reports::AccountBalance {
                 account_id: ab.account_id,
@@ -540,535 +1621,976 @@ async fn get_trial_balance(
                 total_credit: ab.total_credit,
                 balance: ab.balance,
             })
-            .collect(),
-    );
-
-    let response = TrialBalanceResponse {
-        report_type: "trial_balance".to_string(),
-        as_of: as_of.to_string(),
-        currency: org.base_currency,
-        accounts: balances.iter().map(account_balance_to_response).collect(),
-        totals: TrialBalanceTotals {
-            total_debit: format_money(report.totals.total_debit),
-            total_credit: format_money(report.totals.total_credit),
-            is_balanced: report.totals.is_balanced,
-        },
-    };
-
-    (StatusCode::OK, Json(response)).into_response()
+            .collect();
+        let mapping = match load_report_mapping(&state, org_id).await {
+            Ok(mapping) => mapping,
+            Err(response) => return response,
+        };
+        let mut report = match ReportService::income_statement_by_dimension(
+            &accounts,
+            &data.values,
+            &data.totals,
+            allocation.as_ref(),
+            &mapping,
+        ) {
+            Ok(report) => report,
+            Err(e) => return invalid_allocation(e.to_string()),
+        };
+        report.round_totals(state.currencies.decimal_places(&org.base_currency));
+
+        let formatting = match formatting_metadata(&state.db, &org).await {
+            Ok(formatting) => formatting,
+            Err(response) => return response,
+        };
+
+        let response = DimensionIncomeStatementResponse {
+            report_type: report.report_type,
+            period_start: from.to_string(),
+            period_end: to.to_string(),
+            currency: org.base_currency,
+            formatting,
+            dimension_type: dimension_type.code,
+            allocated: report.allocated,
+            columns: report
+                .columns
+                .into_iter()
+                .map(|column| DimensionIncomeStatementColumnResponse {
+                    statement: income_statement_figures(&column.statement),
+                    dimension_value_id: column.dimension_value_id,
+                    code: column.code,
+                    name: column.name,
+                })
+                .collect(),
+            total: income_statement_figures(&report.total),
+        };
+
+        (StatusCode::OK, Json(response)).into_response()
+    })
+    .await
 }
 
-/// GET /organizations/{org_id}/reports/balance-sheet
+/// GET /organizations/{org_id}/reports/dimensional
 ///
-/// Requirement 14.2: Balance sheet report endpoint
+/// Requirement 14.5: Dimensional report endpoint
+#[allow(clippy::too_many_lines)]
 #[axum::debug_handler]
-async fn get_balance_sheet(
+async fn get_dimensional_report(
     State(state): State<AppState>,
     Path(org_id): Path<Uuid>,
-    Query(query): Query<BalanceSheetQuery>,
-    auth_user: AuthUser,
+    Query(query): Query<DimensionalReportQuery>,
+    auth_user: AuthMember,
+    RawQuery(raw_query): RawQuery,
+    headers: HeaderMap,
 ) -> impl IntoResponse {
     let org_repo = OrganizationRepository::new((*state.db).clone());
 
-    // Check membership
-    if let Err(response) = check_membership(&org_repo, org_id, auth_user.user_id()).await {
+    // Check report read access
+    if let Err(response) = check_report_access(&state, org_id, &auth_user).await {
         return response;
     }
 
-    // Get organization for currency
-    let org = match org_repo.find_by_id(org_id).await {
-        Ok(Some(org)) => org,
-        Ok(None) => {
-            return (
-                StatusCode::NOT_FOUND,
-                Json(json!({
-                    "error": "not_found",
-                    "message": "Organization not found"
-                })),
-            )
-                .into_response();
-        }
-        Err(e) => {
-            error!(error = %e, "Failed to get organization");
+    let etag = match query_etag(&state.db, org_id, "dimensional", raw_query.as_deref()).await {
+        Ok(etag) => etag,
+        Err(response) => return response,
+    };
+
+    respond_cached(&headers, &etag, move || async move {
+        // Get organization for currency
+        let org = match org_repo.find_by_id(org_id).await {
+            Ok(Some(org)) => org,
+            Ok(None) => {
+                return (
+                    StatusCode::NOT_FOUND,
+                    Json(json!({
+                        "error": "not_found",
+                        "message": "Organization not found"
+                    })),
+                )
+                    .into_response();
+            }
+            Err(e) => {
+                error!(error = %e, "Failed to get organization");
+                return (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    Json(json!({
+                        "error": "internal_error",
+                        "message": "An error occurred"
+                    })),
+                )
+                    .into_response();
+            }
+        };
+
+        // Default to current month if not specified
+        let today = chrono::Utc::now().date_naive();
+        let from = query.from.unwrap_or_else(|| {
+            NaiveDate::from_ymd_opt(today.year(), today.month(), 1).unwrap_or(today)
+        });
+        let to = query.to.unwrap_or(today);
+
+        // Validate date range
+        if from > to {
             return (
-                StatusCode::INTERNAL_SERVER_ERROR,
+                StatusCode::BAD_REQUEST,
                 Json(json!({
-                    "error": "internal_error",
-                    "message": "An error occurred"
+                    "error": "invalid_date_range",
+                    "message": "Start date must be before or equal to end date"
                 })),
             )
                 .into_response();
         }
-    };
-
-    let as_of = query
-        .as_of
-        .unwrap_or_else(|| chrono::Utc::now().date_naive());
 
-    let report_repo = ReportRepository::new((*state.db).clone());
+        // Parse group_by dimensions
+        let group_by: Vec<String> = query
+            .group_by
+            .split(',')
+            .map(|s| s.trim().to_string())
+            .filter(|s| !s.is_empty())
+            .collect();
 
-    // Query account balances
-    let balances = match report_repo.query_balance_sheet(org_id, as_of).await {
-        Ok(b) => b,
-        Err(e) => {
-            error!(error = %e, "Failed to query balance sheet");
+        if group_by.is_empty() {
             return (
-                StatusCode::INTERNAL_SERVER_ERROR,
+                StatusCode::BAD_REQUEST,
                 Json(json!({
-                    "error": "internal_error",
-                    "message": "Failed to generate balance sheet"
+                    "error": "invalid_group_by",
+                    "message": "At least one group_by dimension is required"
                 })),
             )
                 .into_response();
         }
-    };
 
-    // Generate balance sheet report using core service
-    let report = ReportService::generate_balance_sheet(
-        balances
-            .iter()
-            .map(|ab| zeltra_core::reports::AccountBalance {
-                account_id: ab.account_id,
-                code: ab.code.clone(),
-                name: ab.name.clone(),
-                account_type: account_type_to_string(&ab.account_type),
-                account_subtype: ab.account_subtype.as_ref().map(account_subtype_to_string),
-                total_debit: ab.total_debit,
-                total_credit: ab.total_credit,
-                balance: ab.balance,
-            })
-            .collect(),
-    );
-
-    let response = BalanceSheetResponse {
-        report_type: "balance_sheet".to_string(),
-        as_of: as_of.to_string(),
-        currency: org.base_currency,
-        assets: balance_sheet_section_to_response(&report.assets),
-        liabilities: balance_sheet_section_to_response(&report.liabilities),
-        equity: balance_sheet_section_to_response(&report.equity),
-        total_assets: format_money(report.total_assets),
-        total_liabilities_and_equity: format_money(report.liabilities_and_equity),
-        is_balanced: report.is_balanced,
-    };
-
-    (StatusCode::OK, Json(response)).into_response()
+        let account_type_filter = query
+            .account_type
+            .as_ref()
+            .and_then(|s| parse_account_type(s));
+        let dimension_filter = match dimension_value_filter(&query, raw_query.as_deref()) {
+            Ok(filter) => filter,
+            Err(response) => return response,
+        };
+
+        let report_repo = ReportRepository::new((*state.db).clone());
+
+        // Query dimensional report
+        let (rows, grand_total) = match report_repo
+            .query_dimensional_report(
+                org_id,
+                from,
+                to,
+                &group_by,
+                account_type_filter,
+                &dimension_filter,
+            )
+            .await
+        {
+            Ok(r) => r,
+            Err(ReportError::DimensionValueNotFound(id)) => {
+                return (
+                    StatusCode::BAD_REQUEST,
+                    Json(json!({
+                        "error": "invalid_dimension",
+                        "message": format!("Dimension value not found: {id}")
+                    })),
+                )
+                    .into_response();
+            }
+            Err(e) => {
+                error!(error = %e, "Failed to query dimensional report");
+                return (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    Json(json!({
+                        "error": "internal_error",
+                        "message": "Failed to generate dimensional report"
+                    })),
+                )
+                    .into_response();
+            }
+        };
+
+        let formatting = match formatting_metadata(&state.db, &org).await {
+            Ok(formatting) => formatting,
+            Err(response) => return response,
+        };
+
+        let response = DimensionalReportResponse {
+            report_type: "dimensional".to_string(),
+            period_start: from.to_string(),
+            period_end: to.to_string(),
+            currency: org.base_currency,
+            formatting,
+            group_by: group_by.clone(),
+            rows: rows
+                .iter()
+                .map(|row| DimensionalReportRowResponse {
+                    dimensions: row
+                        .dimensions
+                        .iter()
+                        .map(|d| DimensionValueResponse {
+                            dimension_type: d.dimension_type.clone(),
+                            code: d.code.clone(),
+                            name: d.name.clone(),
+                        })
+                        .collect(),
+                    total_debit: format_money(row.total_debit),
+                    total_credit: format_money(row.total_credit),
+                    balance: format_money(row.balance),
+                })
+                .collect(),
+            grand_total: format_money(grand_total),
+        };
+
+        (StatusCode::OK, Json(response)).into_response()
+    })
+    .await
 }
 
-/// GET /organizations/{org_id}/reports/income-statement
+/// GET /organizations/{org_id}/reports/voids
 ///
-/// Requirement 14.3: Income statement report endpoint
+/// Counts and totals voided transactions by reason code.
+#[allow(clippy::too_many_lines)]
 #[axum::debug_handler]
-async fn get_income_statement(
+async fn get_void_report(
     State(state): State<AppState>,
     Path(org_id): Path<Uuid>,
-    Query(query): Query<IncomeStatementQuery>,
-    auth_user: AuthUser,
+    Query(query): Query<VoidReportQuery>,
+    auth_user: AuthMember,
+    RawQuery(raw_query): RawQuery,
+    headers: HeaderMap,
 ) -> impl IntoResponse {
     let org_repo = OrganizationRepository::new((*state.db).clone());
 
-    // Check membership
-    if let Err(response) = check_membership(&org_repo, org_id, auth_user.user_id()).await {
+    // Check report read access
+    if let Err(response) = check_report_access(&state, org_id, &auth_user).await {
         return response;
     }
 
-    // Get organization for currency
-    let org = match org_repo.find_by_id(org_id).await {
-        Ok(Some(org)) => org,
-        Ok(None) => {
-            return (
-                StatusCode::NOT_FOUND,
-                Json(json!({
-                    "error": "not_found",
-                    "message": "Organization not found"
-                })),
-            )
-                .into_response();
-        }
-        Err(e) => {
-            error!(error = %e, "Failed to get organization");
+    let etag = match query_etag(&state.db, org_id, "void_analysis", raw_query.as_deref()).await {
+        Ok(etag) => etag,
+        Err(response) => return response,
+    };
+
+    respond_cached(&headers, &etag, move || async move {
+        // Get organization for currency
+        let org = match org_repo.find_by_id(org_id).await {
+            Ok(Some(org)) => org,
+            Ok(None) => {
+                return (
+                    StatusCode::NOT_FOUND,
+                    Json(json!({
+                        "error": "not_found",
+                        "message": "Organization not found"
+                    })),
+                )
+                    .into_response();
+            }
+            Err(e) => {
+                error!(error = %e, "Failed to get organization");
+                return (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    Json(json!({
+                        "error": "internal_error",
+                        "message": "An error occurred"
+                    })),
+                )
+                    .into_response();
+            }
+        };
+
+        // Default to current month if not specified
+        let today = chrono::Utc::now().date_naive();
+        let from = query.from.unwrap_or_else(|| {
+            NaiveDate::from_ymd_opt(today.year(), today.month(), 1).unwrap_or(today)
+        });
+        let to = query.to.unwrap_or(today);
+
+        // Validate date range
+        if from > to {
             return (
-                StatusCode::INTERNAL_SERVER_ERROR,
+                StatusCode::BAD_REQUEST,
                 Json(json!({
-                    "error": "internal_error",
-                    "message": "An error occurred"
+                    "error": "invalid_date_range",
+                    "message": "Start date must be before or equal to end date"
                 })),
             )
                 .into_response();
         }
-    };
 
-    // Default to current month if not specified
-    let today = chrono::Utc::now().date_naive();
-    let from = query.from.unwrap_or_else(|| {
-        NaiveDate::from_ymd_opt(today.year(), today.month(), 1).unwrap_or(today)
-    });
-    let to = query.to.unwrap_or(today);
+        let report_repo = ReportRepository::new((*state.db).clone());
+
+        let summaries = match report_repo.query_void_summary(org_id, from, to).await {
+            Ok(s) => s,
+            Err(e) => {
+                error!(error = %e, "Failed to query void summary");
+                return (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    Json(json!({
+                        "error": "internal_error",
+                        "message": "Failed to generate void report"
+                    })),
+                )
+                    .into_response();
+            }
+        };
+
+        let count = summaries.iter().map(|s| s.count).sum();
+        let total: Decimal = summaries.iter().map(|s| s.total).sum();
+
+        let formatting = match formatting_metadata(&state.db, &org).await {
+            Ok(formatting) => formatting,
+            Err(response) => return response,
+        };
+
+        let response = VoidReportResponse {
+            report_type: "void_analysis".to_string(),
+            from: from.to_string(),
+            to: to.to_string(),
+            currency: org.base_currency,
+            formatting,
+            reasons: summaries
+                .into_iter()
+                .map(|s| VoidReasonResponse {
+                    reason_code: s
+                        .reason_code
+                        .map_or("unspecified", |code| code.as_str())
+                        .to_string(),
+                    count: s.count,
+                    total: format_money(s.total),
+                })
+                .collect(),
+            totals: VoidReportTotals {
+                count,
+                total: format_money(total),
+            },
+        };
+
+        (StatusCode::OK, Json(response)).into_response()
+    })
+    .await
+}
 
-    // Validate date range
-    if from > to {
-        return (
-            StatusCode::BAD_REQUEST,
-            Json(json!({
-                "error": "invalid_date_range",
-                "message": "Start date must be before or equal to end date"
-            })),
-        )
-            .into_response();
+/// GET /organizations/{org_id}/reports/account-movement
+///
+/// Explains an account's change against another period: balances and
+/// activity for both, the change in net movement, and the transactions that
+/// contributed most to the current period.
+#[allow(clippy::too_many_lines)]
+async fn get_account_movement_report(
+    State(state): State<AppState>,
+    Path(org_id): Path<Uuid>,
+    Query(query): Query<AccountMovementQuery>,
+    auth_user: AuthMember,
+    RawQuery(raw_query): RawQuery,
+    headers: HeaderMap,
+) -> impl IntoResponse {
+    // Check report read access
+    if let Err(response) = check_report_access(&state, org_id, &auth_user).await {
+        return response;
     }
 
-    let dimension_filters = query
-        .dimensions
-        .as_ref()
-        .map(|s| parse_uuid_list(s))
-        .unwrap_or_default();
-
-    let report_repo = ReportRepository::new((*state.db).clone());
-
-    // Query account balances
-    let balances = match report_repo
-        .query_income_statement(org_id, from, to, &dimension_filters)
-        .await
-    {
-        Ok(b) => b,
-        Err(e) => {
-            error!(error = %e, "Failed to query income statement");
+    let account = match (query.account_id, query.account_code) {
+        (Some(id), None) => AccountLookup::Id(id),
+        (None, Some(code)) if !code.trim().is_empty() => {
+            AccountLookup::Code(code.trim().to_string())
+        }
+        _ => {
             return (
-                StatusCode::INTERNAL_SERVER_ERROR,
+                StatusCode::BAD_REQUEST,
                 Json(json!({
-                    "error": "internal_error",
-                    "message": "Failed to generate income statement"
+                    "error": "validation_error",
+                    "message": "Provide either account_id or account_code"
                 })),
             )
                 .into_response();
         }
     };
+    let limit = query.limit.unwrap_or(10).clamp(1, 50);
 
-    // Generate income statement report using core service
-    let report = ReportService::generate_income_statement(
-        balances
-            .iter()
-            .map(|ab| zeltra_core::reports::AccountBalance {
-                account_id: ab.account_id,
-                code: ab.code.clone(),
-                name: ab.name.clone(),
-                account_type: account_type_to_string(&ab.account_type),
-                account_subtype: ab.account_subtype.as_ref().map(account_subtype_to_string),
-                total_debit: ab.total_debit,
-                total_credit: ab.total_credit,
-                balance: ab.balance,
-            })
-            .collect(),
-    );
-
-    let response = IncomeStatementResponse {
-        report_type: "income_statement".to_string(),
-        period_start: from.to_string(),
-        period_end: to.to_string(),
-        currency: org.base_currency,
-        revenue: income_statement_section_to_response(&report.revenue),
-        cost_of_goods_sold: income_statement_section_to_response(&report.cost_of_goods_sold),
-        gross_profit: format_money(report.gross_profit),
-        operating_expenses: income_statement_section_to_response(&report.operating_expenses),
-        operating_income: format_money(report.operating_income),
-        other_income_expenses: income_statement_section_to_response(&report.other_income_expense),
-        net_income: format_money(report.net_income),
+    let etag = match query_etag(&state.db, org_id, "account_movement", raw_query.as_deref()).await {
+        Ok(etag) => etag,
+        Err(response) => return response,
     };
 
-    (StatusCode::OK, Json(response)).into_response()
+    respond_cached(&headers, &etag, move || async move {
+        let org = match OrganizationRepository::new((*state.db).clone())
+            .find_by_id(org_id)
+            .await
+        {
+            Ok(Some(org)) => org,
+            Ok(None) => {
+                return (
+                    StatusCode::NOT_FOUND,
+                    Json(json!({
+                        "error": "not_found",
+                        "message": "Organization not found"
+                    })),
+                )
+                    .into_response();
+            }
+            Err(e) => {
+                error!(error = %e, "Failed to get organization");
+                return internal_error();
+            }
+        };
+
+        let movement = match ReportRepository::new((*state.db).clone())
+            .query_account_movement(
+                org_id,
+                &account,
+                query.period_id,
+                query.compare_period_id,
+                limit,
+            )
+            .await
+        {
+            Ok(movement) => movement,
+            Err(
+                e @ (ReportError::AccountNotFound(_)
+                | ReportError::AccountCodeNotFound(_)
+                | ReportError::PeriodNotFound(_)),
+            ) => {
+                return (
+                    StatusCode::NOT_FOUND,
+                    Json(json!({
+                        "error": "not_found",
+                        "message": e.to_string()
+                    })),
+                )
+                    .into_response();
+            }
+            Err(e @ ReportError::AccountNotPostable(_)) => {
+                return (
+                    StatusCode::BAD_REQUEST,
+                    Json(json!({
+                        "error": "account_not_postable",
+                        "message": e.to_string()
+                    })),
+                )
+                    .into_response();
+            }
+            Err(e) => {
+                error!(error = %e, "Failed to query account movement");
+                return internal_error();
+            }
+        };
+
+        let formatting = match formatting_metadata(&state.db, &org).await {
+            Ok(formatting) => formatting,
+            Err(response) => return response,
+        };
+
+        let response = AccountMovementResponse {
+            report_type: "account_movement".to_string(),
+            currency: org.base_currency,
+            formatting,
+            account: MovementAccountResponse {
+                id: movement.account.id,
+                code: movement.account.code,
+                name: movement.account.name,
+                account_type: account_type_to_string(&movement.account.account_type),
+            },
+            current: period_movement_to_response(&movement.current),
+            compare: period_movement_to_response(&movement.compare),
+            change: MovementChangeResponse {
+                absolute: format_money(movement.change.absolute),
+                percent: movement.change.percent.map(|p| p.to_string()),
+            },
+            top_transactions: movement
+                .top_transactions
+                .into_iter()
+                .map(|t| MovementTransactionResponse {
+                    id: t.transaction_id,
+                    transaction_date: t.transaction_date.to_string(),
+                    reference_number: t.reference_number,
+                    description: t.description,
+                    amount: format_money(t.amount),
+                })
+                .collect(),
+        };
+
+        (StatusCode::OK, Json(response)).into_response()
+    })
+    .await
 }
 
-use chrono::Datelike;
+fn period_movement_to_response(period: &AccountPeriodMovement) -> PeriodMovementResponse {
+    let movement = &period.movement;
+    PeriodMovementResponse {
+        period_id: period.period.id,
+        period_name: period.period.name.clone(),
+        start_date: period.period.start_date.to_string(),
+        end_date: period.period.end_date.to_string(),
+        opening_balance: format_money(movement.opening_balance),
+        total_debit: format_money(movement.total_debit),
+        total_credit: format_money(movement.total_credit),
+        closing_balance: format_money(movement.closing_balance),
+        net_change: format_money(movement.net_change()),
+    }
+}
 
-/// GET /organizations/{org_id}/reports/dimensional
+/// GET /organizations/{org_id}/reports/data-quality
 ///
-/// Requirement 14.5: Dimensional report endpoint
-#[allow(clippy::too_many_lines)]
-#[axum::debug_handler]
-async fn get_dimensional_report(
+/// Runs the data-quality checks over the organization's ledger.
+async fn get_data_quality_report(
     State(state): State<AppState>,
     Path(org_id): Path<Uuid>,
-    Query(query): Query<DimensionalReportQuery>,
-    auth_user: AuthUser,
+    auth_user: AuthMember,
+    headers: HeaderMap,
 ) -> impl IntoResponse {
-    let org_repo = OrganizationRepository::new((*state.db).clone());
-
-    // Check membership
-    if let Err(response) = check_membership(&org_repo, org_id, auth_user.user_id()).await {
+    // Check report read access
+    if let Err(response) = check_report_access(&state, org_id, &auth_user).await {
         return response;
     }
 
-    // Get organization for currency
+    let etag = match query_etag(&state.db, org_id, "data_quality", None).await {
+        Ok(etag) => etag,
+        Err(response) => return response,
+    };
+
+    respond_cached(&headers, &etag, move || async move {
+        let report_repo = ReportRepository::new((*state.db).clone());
+
+        let findings = match report_repo.query_data_quality(org_id).await {
+            Ok(f) => f,
+            Err(e) => {
+                error!(error = %e, "Failed to run data-quality checks");
+                return (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    Json(json!({
+                        "error": "internal_error",
+                        "message": "Failed to generate data-quality report"
+                    })),
+                )
+                    .into_response();
+            }
+        };
+
+        let response = DataQualityReportResponse {
+            report_type: "data_quality".to_string(),
+            checks: DataQualityCheck::ALL
+                .into_iter()
+                .map(|check| DataQualityCheckSummary {
+                    check,
+                    count: findings.iter().filter(|f| f.check == check).count(),
+                })
+                .collect(),
+            findings,
+        };
+
+        (StatusCode::OK, Json(response)).into_response()
+    })
+    .await
+}
+
+/// Builds a generic 500 response.
+fn internal_error() -> Response {
+    (
+        StatusCode::INTERNAL_SERVER_ERROR,
+        Json(json!({
+            "error": "internal_error",
+            "message": "An error occurred"
+        })),
+    )
+        .into_response()
+}
+
+/// Revalues an organization's foreign balances at the rates in effect on `as_of`.
+async fn load_fx_exposure(
+    state: &AppState,
+    org_repo: &OrganizationRepository,
+    org_id: Uuid,
+    as_of: NaiveDate,
+) -> Result<(organizations::Model, FxExposure), Response> {
     let org = match org_repo.find_by_id(org_id).await {
         Ok(Some(org)) => org,
         Ok(None) => {
-            return (
+            return Err((
                 StatusCode::NOT_FOUND,
                 Json(json!({
                     "error": "not_found",
                     "message": "Organization not found"
                 })),
             )
-                .into_response();
+                .into_response());
         }
         Err(e) => {
             error!(error = %e, "Failed to get organization");
-            return (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(json!({
-                    "error": "internal_error",
-                    "message": "An error occurred"
-                })),
-            )
-                .into_response();
+            return Err(internal_error());
         }
     };
 
-    // Default to current month if not specified
-    let today = chrono::Utc::now().date_naive();
-    let from = query.from.unwrap_or_else(|| {
-        NaiveDate::from_ymd_opt(today.year(), today.month(), 1).unwrap_or(today)
-    });
-    let to = query.to.unwrap_or(today);
+    match FxRevaluationRepository::new((*state.db).clone())
+        .exposure(&org, as_of)
+        .await
+    {
+        Ok(exposure) => Ok((org, exposure)),
+        Err(e) => {
+            error!(error = %e, "Failed to revalue foreign currency balances");
+            Err(internal_error())
+        }
+    }
+}
 
-    // Validate date range
-    if from > to {
-        return (
+/// Resolves the gain and loss accounts for a revaluation.
+///
+/// Request overrides win over the organization settings; both accounts must
+/// be active accounts of the organization.
+async fn resolve_fx_accounts(
+    state: &AppState,
+    org: &organizations::Model,
+    payload: &RevalueFxRequest,
+) -> Result<(Uuid, Uuid), Response> {
+    let settings = OrganizationSettings::from_value(&org.settings).map_err(|e| {
+        error!(error = %e, "Stored organization settings are invalid");
+        internal_error()
+    })?;
+    let gain = payload
+        .gain_account_id
+        .or(settings.fx_revaluation.gain_account_id);
+    let loss = payload
+        .loss_account_id
+        .or(settings.fx_revaluation.loss_account_id);
+    let (Some(gain), Some(loss)) = (gain, loss) else {
+        return Err((
             StatusCode::BAD_REQUEST,
             Json(json!({
-                "error": "invalid_date_range",
-                "message": "Start date must be before or equal to end date"
+                "error": "fx_accounts_not_configured",
+                "message": "Set fx_revaluation.gain_account_id and loss_account_id in the organization settings or pass them in the request"
             })),
         )
-            .into_response();
+            .into_response());
+    };
+
+    let account_repo = AccountRepository::new((*state.db).clone());
+    for account_id in [gain, loss] {
+        match account_repo.find_account_by_id(account_id.into()).await {
+            Ok(Some(a)) if a.account.organization_id == org.id && a.account.is_active => {}
+            Ok(_) => {
+                return Err((
+                    StatusCode::BAD_REQUEST,
+                    Json(json!({
+                        "error": "invalid_fx_account",
+                        "message": format!("Account {account_id} is not an active account of this organization")
+                    })),
+                )
+                    .into_response());
+            }
+            Err(e) => {
+                error!(error = %e, "Failed to load FX gain/loss account");
+                return Err(internal_error());
+            }
+        }
     }
 
-    // Parse group_by dimensions
-    let group_by: Vec<String> = query
-        .group_by
-        .split(',')
-        .map(|s| s.trim().to_string())
-        .filter(|s| !s.is_empty())
-        .collect();
+    Ok((gain, loss))
+}
 
-    if group_by.is_empty() {
-        return (
-            StatusCode::BAD_REQUEST,
-            Json(json!({
-                "error": "invalid_group_by",
-                "message": "At least one group_by dimension is required"
-            })),
-        )
-            .into_response();
+/// GET /organizations/{org_id}/reports/fx-exposure
+///
+/// Foreign currency exposure of monetary accounts and the unrealized gain or
+/// loss from revaluing it at the rates in effect on `as_of`.
+async fn get_fx_exposure_report(
+    State(state): State<AppState>,
+    Path(org_id): Path<Uuid>,
+    Query(query): Query<FxExposureQuery>,
+    auth_user: AuthMember,
+) -> impl IntoResponse {
+    let org_repo = OrganizationRepository::new((*state.db).clone());
+
+    // Check report read access
+    if let Err(response) = check_report_access(&state, org_id, &auth_user).await {
+        return response;
     }
 
-    let account_type_filter = query
-        .account_type
-        .as_ref()
-        .and_then(|s| parse_account_type(s));
-    let dimension_filters = query
-        .dimensions
-        .as_ref()
-        .map(|s| parse_uuid_list(s))
-        .unwrap_or_default();
-
-    let report_repo = ReportRepository::new((*state.db).clone());
-
-    // Query dimensional report
-    let (rows, grand_total) = match report_repo
-        .query_dimensional_report(
-            org_id,
-            from,
-            to,
-            &group_by,
-            account_type_filter,
-            &dimension_filters,
-        )
-        .await
-    {
-        Ok(r) => r,
-        Err(e) => {
-            error!(error = %e, "Failed to query dimensional report");
-            return (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(json!({
-                    "error": "internal_error",
-                    "message": "Failed to generate dimensional report"
-                })),
-            )
-                .into_response();
-        }
+    let as_of = query
+        .as_of
+        .unwrap_or_else(|| chrono::Utc::now().date_naive());
+    let (org, exposure) = match load_fx_exposure(&state, &org_repo, org_id, as_of).await {
+        Ok(loaded) => loaded,
+        Err(response) => return response,
+    };
+
+    let accounts_by_id: HashMap<Uuid, &ForeignCurrencyBalance> = exposure
+        .balances
+        .iter()
+        .map(|b| (b.balance.account_id, b))
+        .collect();
+
+    let formatting = match formatting_metadata(&state.db, &org).await {
+        Ok(formatting) => formatting,
+        Err(response) => return response,
     };
 
-    let response = DimensionalReportResponse {
-        report_type: "dimensional".to_string(),
-        period_start: from.to_string(),
-        period_end: to.to_string(),
-        currency: org.base_currency,
-        group_by: group_by.clone(),
-        rows: rows
+    let response = FxExposureResponse {
+        report_type: "fx_exposure".to_string(),
+        as_of: as_of.to_string(),
+        currency: org.base_currency.clone(),
+        formatting,
+        currencies: exposure
+            .result
+            .currencies
             .iter()
-            .map(|row| DimensionalReportRowResponse {
-                dimensions: row
-                    .dimensions
-                    .iter()
-                    .map(|d| DimensionValueResponse {
-                        dimension_type: d.dimension_type.clone(),
-                        code: d.code.clone(),
-                        name: d.name.clone(),
-                    })
-                    .collect(),
-                total_debit: format_money(row.total_debit),
-                total_credit: format_money(row.total_credit),
-                balance: format_money(row.balance),
+            .map(|c| FxCurrencyResponse {
+                currency: c.currency.clone(),
+                rate: c.rate.normalize().to_string(),
+                rate_date: exposure
+                    .rate_dates
+                    .get(&c.currency)
+                    .map(ToString::to_string)
+                    .unwrap_or_default(),
+                source_balance: format_money(c.source_balance),
+                booked_functional: format_money(c.booked_functional),
+                revalued_functional: format_money(c.revalued_functional),
+                unrealized: format_money(c.unrealized),
+            })
+            .collect(),
+        accounts: exposure
+            .result
+            .accounts
+            .iter()
+            .filter_map(|a| {
+                let account = accounts_by_id.get(&a.account_id)?;
+                Some(FxAccountResponse {
+                    account_id: a.account_id,
+                    code: account.code.clone(),
+                    name: account.name.clone(),
+                    account_type: account_type_to_string(&account.account_type),
+                    currency: a.currency.clone(),
+                    source_balance: format_money(a.source_balance),
+                    rate: a.rate.normalize().to_string(),
+                    booked_functional: format_money(a.booked_functional),
+                    revalued_functional: format_money(a.revalued_functional),
+                    unrealized: format_money(a.unrealized),
+                })
             })
             .collect(),
-        grand_total: format_money(grand_total),
+        total_unrealized: format_money(exposure.result.total_unrealized),
+        missing_rates: exposure.result.missing_rates,
     };
 
     (StatusCode::OK, Json(response)).into_response()
 }
 
-/// GET /organizations/{org_id}/accounts/{account_id}/ledger
+/// POST /organizations/{org_id}/reports/fx-exposure/revalue
 ///
-/// Requirement 14.4: Account ledger endpoint
+/// Creates a draft journal booking the unrealized FX gain/loss as of
+/// `as_of`. The draft goes through the normal approval and posting flow.
 #[allow(clippy::too_many_lines)]
-#[axum::debug_handler]
-async fn get_account_ledger(
+async fn revalue_fx_exposure(
     State(state): State<AppState>,
-    Path((org_id, account_id)): Path<(Uuid, Uuid)>,
-    Query(query): Query<AccountLedgerQuery>,
-    auth_user: AuthUser,
+    Path(org_id): Path<Uuid>,
+    auth_user: AuthMember,
+    payload: Option<Json<RevalueFxRequest>>,
 ) -> impl IntoResponse {
+    let payload = payload.map(|Json(p)| p).unwrap_or_default();
     let org_repo = OrganizationRepository::new((*state.db).clone());
 
-    // Check membership
-    if let Err(response) = check_membership(&org_repo, org_id, auth_user.user_id()).await {
+    // Check report read access
+    if let Err(response) = check_report_access(&state, org_id, &auth_user).await {
         return response;
     }
 
-    // Default to current month if not specified
-    let today = chrono::Utc::now().date_naive();
-    let from = query.from.unwrap_or_else(|| {
-        NaiveDate::from_ymd_opt(today.year(), today.month(), 1).unwrap_or(today)
-    });
-    let to = query.to.unwrap_or(today);
+    let as_of = payload
+        .as_of
+        .unwrap_or_else(|| chrono::Utc::now().date_naive());
+    let (org, exposure) = match load_fx_exposure(&state, &org_repo, org_id, as_of).await {
+        Ok(loaded) => loaded,
+        Err(response) => return response,
+    };
 
-    // Validate date range
-    if from > to {
+    if !exposure.result.missing_rates.is_empty() {
         return (
             StatusCode::BAD_REQUEST,
             Json(json!({
-                "error": "invalid_date_range",
-                "message": "Start date must be before or equal to end date"
+                "error": "no_exchange_rate",
+                "message": format!(
+                    "No exchange rate to {} on or before {} for: {}",
+                    org.base_currency,
+                    as_of,
+                    exposure.result.missing_rates.join(", ")
+                ),
+                "missing_rates": exposure.result.missing_rates
             })),
         )
             .into_response();
     }
 
-    let page = query.page.unwrap_or(0);
-    let limit = query.limit.unwrap_or(50).min(100);
+    let (gain_account_id, loss_account_id) = match resolve_fx_accounts(&state, &org, &payload).await
+    {
+        Ok(accounts) => accounts,
+        Err(response) => return response,
+    };
 
-    let report_repo = ReportRepository::new((*state.db).clone());
+    let lines = exposure
+        .result
+        .adjustment_lines(gain_account_id, loss_account_id);
+    if lines.is_empty() {
+        return (
+            StatusCode::CONFLICT,
+            Json(json!({
+                "error": "nothing_to_revalue",
+                "message": format!("No unrealized FX gain or loss as of {as_of}")
+            })),
+        )
+            .into_response();
+    }
+
+    let entries = revaluation_entries(&exposure, &lines);
+
+    let input = CreateTransactionInput {
+        organization_id: org_id,
+        transaction_type: TransactionType::Journal,
+        transaction_date: as_of,
+        description: format!("FX revaluation as of {as_of}"),
+        reference_number: None,
+        memo: None,
+        entries,
+        created_by: auth_user.user_id(),
+    };
 
-    // Query account ledger
-    let (entries, total) = match report_repo
-        .query_account_ledger(org_id, account_id, from, to, page, limit)
+    match TransactionRepository::new((*state.db).clone())
+        .create_transaction(input)
         .await
     {
-        Ok(r) => r,
-        Err(zeltra_db::repositories::report::ReportError::AccountNotFound(_)) => {
-            return (
-                StatusCode::NOT_FOUND,
-                Json(json!({
-                    "error": "not_found",
-                    "message": "Account not found"
-                })),
-            )
-                .into_response();
+        Ok(created) => {
+            info!(transaction_id = %created.transaction.id, "FX revaluation draft created");
+            let response = RevalueFxResponse {
+                transaction_id: created.transaction.id,
+                status: "draft".to_string(),
+                transaction_date: as_of.to_string(),
+                total_unrealized: format_money(exposure.result.total_unrealized),
+                entries: lines
+                    .into_iter()
+                    .map(|line| RevaluationEntryResponse {
+                        account_id: line.account_id,
+                        currency: line.currency,
+                        debit: format_money(line.debit),
+                        credit: format_money(line.credit),
+                    })
+                    .collect(),
+            };
+            (StatusCode::CREATED, Json(response)).into_response()
         }
+        Err(TransactionError::NoFiscalPeriod(date)) => (
+            StatusCode::BAD_REQUEST,
+            Json(json!({
+                "error": "no_fiscal_period",
+                "message": format!("No fiscal period found for date {date}")
+            })),
+        )
+            .into_response(),
+        Err(TransactionError::PeriodClosed) => (
+            StatusCode::BAD_REQUEST,
+            Json(json!({
+                "error": "period_closed",
+                "message": "Fiscal period is closed, no posting allowed"
+            })),
+        )
+            .into_response(),
         Err(e) => {
-            error!(error = %e, "Failed to query account ledger");
-            return (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(json!({
-                    "error": "internal_error",
-                    "message": "Failed to get account ledger"
-                })),
-            )
-                .into_response();
+            if let Some(response) = entry_account_error_response(&e) {
+                return response;
+            }
+            error!(error = %e, "Failed to create FX revaluation transaction");
+            internal_error()
         }
-    };
+    }
+}
 
-    // Get account info
-    let account = match zeltra_db::entities::chart_of_accounts::Entity::find_by_id(account_id)
-        .one(&*state.db)
-        .await
-    {
-        Ok(Some(a)) => a,
-        Ok(None) => {
+/// POST /organizations/{org_id}/reports/fx-exposure/period-end
+///
+/// Runs the scheduled period-end FX revaluation for this organization now,
+/// for periods ending on `as_of` or the day after. Owners and admins only.
+#[allow(clippy::too_many_lines)]
+async fn run_period_end_revaluation(
+    State(state): State<AppState>,
+    Path(org_id): Path<Uuid>,
+    auth_user: AuthMember,
+    payload: Option<Json<PeriodEndRevaluationRequest>>,
+) -> impl IntoResponse {
+    let payload = payload.map(|Json(p)| p).unwrap_or_default();
+
+    match auth_user.role_in(&state, org_id).await {
+        Ok(role) if role.can_modify_settings() => {}
+        Ok(_) => {
             return (
-                StatusCode::NOT_FOUND,
+                StatusCode::FORBIDDEN,
                 Json(json!({
-                    "error": "not_found",
-                    "message": "Account not found"
+                    "error": "forbidden",
+                    "message": "Only owners and admins can run the period-end revaluation"
                 })),
             )
                 .into_response();
         }
+        Err(response) => return response,
+    }
+
+    let as_of = payload
+        .as_of
+        .unwrap_or_else(|| chrono::Utc::now().date_naive());
+    let repo = FxRevaluationRepository::new((*state.db).clone());
+    let periods = match repo.due_periods(as_of, Some(org_id)).await {
+        Ok(periods) => periods,
         Err(e) => {
-            error!(error = %e, "Failed to get account");
-            return (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(json!({
-                    "error": "internal_error",
-                    "message": "An error occurred"
-                })),
-            )
-                .into_response();
+            error!(error = %e, "Failed to list fiscal periods due for revaluation");
+            return internal_error();
         }
     };
 
-    let total_pages = if total == 0 { 0 } else { total.div_ceil(limit) };
-
-    let response = AccountLedgerResponse {
-        account_id,
-        code: account.code,
-        name: account.name,
-        entries: entries
-            .iter()
-            .map(|e| LedgerEntryResponse {
-                id: e.id,
-                transaction_id: e.transaction_id,
-                transaction_date: e.transaction_date.to_string(),
-                description: e.description.clone(),
-                source_currency: e.source_currency.clone(),
-                source_amount: format_money(e.source_amount),
-                exchange_rate: format!("{:.10}", e.exchange_rate),
-                functional_amount: format_money(e.functional_amount),
-                debit: format_money(e.debit),
-                credit: format_money(e.credit),
-                running_balance: format_money(e.running_balance),
-                dimensions: e
-                    .dimensions
-                    .iter()
-                    .map(|d| DimensionValueResponse {
-                        dimension_type: d.dimension_type.clone(),
-                        code: d.code.clone(),
-                        name: d.name.clone(),
-                    })
-                    .collect(),
-            })
-            .collect(),
-        pagination: PaginationResponse {
-            page,
-            limit,
-            total,
-            total_pages,
-        },
-    };
+    let mut results = Vec::with_capacity(periods.len());
+    for period in &periods {
+        let run = match repo.revalue_period_end(period).await {
+            Ok(run) => run,
+            Err(FxRevaluationError::Transaction(e)) => {
+                if let Some(response) = entry_account_error_response(&e) {
+                    return response;
+                }
+                error!(error = %e, "Failed to create FX revaluation transaction");
+                return internal_error();
+            }
+            Err(e) => {
+                error!(error = %e, "Failed to prepare FX revaluation");
+                return internal_error();
+            }
+        };
+        let (outcome, transaction_id, total_unrealized, skip) = match run.outcome {
+            PeriodEndOutcome::Created {
+                transaction_id,
+                total_unrealized,
+            } => (
+                "created",
+                Some(transaction_id),
+                Some(format_money(total_unrealized)),
+                None,
+            ),
+            PeriodEndOutcome::Exists { transaction_id } => {
+                ("exists", Some(transaction_id), None, None)
+            }
+            PeriodEndOutcome::NothingToRevalue => ("nothing_to_revalue", None, None, None),
+            PeriodEndOutcome::Skipped(reason) => ("skipped", None, None, Some(reason)),
+            PeriodEndOutcome::MultiCurrencyDisabled => {
+                ("multi_currency_disabled", None, None, None)
+            }
+        };
+        results.push(PeriodEndPeriodResponse {
+            fiscal_period_id: run.fiscal_period_id,
+            period_name: run.period_name,
+            period_end: run.period_end.to_string(),
+            reference: run.reference,
+            outcome,
+            transaction_id,
+            total_unrealized,
+            skip,
+        });
+    }
 
-    (StatusCode::OK, Json(response)).into_response()
+    info!(org_id = %org_id, periods = results.len(), "Period-end FX revaluation run by hand");
+    (
+        StatusCode::OK,
+        Json(PeriodEndRevaluationResponse {
+            as_of: as_of.to_string(),
+            periods: results,
+        }),
+    )
+        .into_response()
 }
 
 // ============================================================================
@@ -1079,37 +2601,251 @@ async fn get_account_ledger(
 fn account_subtype_to_string(
     db_subtype: &zeltra_db::entities::sea_orm_active_enums::AccountSubtype,
 ) -> String {
-    use zeltra_db::entities::sea_orm_active_enums::AccountSubtype as DbSubtype;
-
-    match db_subtype {
-        DbSubtype::Cash => "cash".to_string(),
-        DbSubtype::Bank => "bank".to_string(),
-        DbSubtype::AccountsReceivable => "accounts_receivable".to_string(),
-        DbSubtype::Inventory => "inventory".to_string(),
-        DbSubtype::Prepaid => "prepaid".to_string(),
-        DbSubtype::FixedAsset => "fixed_asset".to_string(),
-        DbSubtype::AccumulatedDepreciation => "accumulated_depreciation".to_string(),
-        DbSubtype::OtherAsset => "other_asset".to_string(),
-        DbSubtype::AccountsPayable => "accounts_payable".to_string(),
-        DbSubtype::CreditCard => "credit_card".to_string(),
-        DbSubtype::AccruedLiability => "accrued_liability".to_string(),
-        DbSubtype::ShortTermDebt => "short_term_debt".to_string(),
-        DbSubtype::LongTermDebt => "long_term_debt".to_string(),
-        DbSubtype::OtherLiability => "other_liability".to_string(),
-        DbSubtype::OwnerEquity => "owner_equity".to_string(),
-        DbSubtype::RetainedEarnings => "retained_earnings".to_string(),
-        DbSubtype::CommonStock => "common_stock".to_string(),
-        DbSubtype::OtherEquity => "other_equity".to_string(),
-        DbSubtype::OperatingRevenue => "operating_revenue".to_string(),
-        DbSubtype::OtherRevenue => "other_revenue".to_string(),
-        DbSubtype::CostOfGoodsSold => "cost_of_goods_sold".to_string(),
-        DbSubtype::OperatingExpense => "operating_expense".to_string(),
-        DbSubtype::PayrollExpense => "payroll_expense".to_string(),
-        DbSubtype::DepreciationExpense => "depreciation_expense".to_string(),
-        DbSubtype::InterestExpense => "interest_expense".to_string(),
-        DbSubtype::TaxExpense => "tax_expense".to_string(),
-        DbSubtype::OtherExpense => "other_expense".to_string(),
-    }
+    core_account_subtype(db_subtype).as_str().to_string()
 }
 
-use sea_orm::EntityTrait;
+#[cfg(test)]
+mod integration_tests {
+    use super::*;
+    use axum::http::{
+        HeaderValue,
+        header::{CACHE_CONTROL, ETAG, IF_NONE_MATCH},
+    };
+    use zeltra_db::entities::sea_orm_active_enums::AccountType;
+    use zeltra_test_support::{
+        OrgFixture, TestDb, TestResponse, access_token, json_request, send_request, test_app_state,
+    };
+
+    async fn get_trial_balance(
+        state: &AppState,
+        uri: &str,
+        token: &str,
+        if_none_match: Option<&str>,
+    ) -> TestResponse {
+        let mut request = json_request("GET", uri, token, &serde_json::Value::Null);
+        if let Some(tag) = if_none_match {
+            request
+                .headers_mut()
+                .insert(IF_NONE_MATCH, HeaderValue::from_str(tag).unwrap());
+        }
+        send_request(state.authenticated(routes()), request).await
+    }
+
+    #[tokio::test]
+    async fn test_trial_balance_etag_round_trip() {
+        let test_db = TestDb::new().await;
+        let state: AppState = test_app_state(&test_db);
+        let org = OrgFixture::new()
+            .with_accounts(&[("1000", AccountType::Asset)])
+            .create(test_db.conn())
+            .await;
+        let token = access_token(&state.jwt_service, org.id, &org.owner);
+        let uri = format!(
+            "/organizations/{}/reports/trial-balance?as_of=2025-12-31",
+            org.id
+        );
+
+        let first = get_trial_balance(&state, &uri, &token, None).await;
+        assert_eq!(first.status, StatusCode::OK);
+        assert_eq!(first.headers[CACHE_CONTROL], "private, no-cache");
+        let etag = first.headers[ETAG].to_str().unwrap().to_string();
+
+        let cached = get_trial_balance(&state, &uri, &token, Some(&etag)).await;
+        assert_eq!(cached.status, StatusCode::NOT_MODIFIED);
+        assert_eq!(cached.headers[ETAG], etag.as_str());
+
+        // A different filter is a different representation
+        let other_uri = format!(
+            "/organizations/{}/reports/trial-balance?as_of=2025-06-30",
+            org.id
+        );
+        let other = get_trial_balance(&state, &other_uri, &token, Some(&etag)).await;
+        assert_eq!(other.status, StatusCode::OK);
+
+        // Changing the chart of accounts invalidates the tag
+        zeltra_db::repositories::AccountRepository::new(test_db.conn().clone())
+            .create_account(zeltra_db::repositories::CreateAccountInput {
+                organization_id: org.id.into_inner(),
+                code: "2000".to_string(),
+                name: "Payables".to_string(),
+                description: None,
+                account_type: AccountType::Liability,
+                account_subtype: None,
+                parent_id: None,
+                currency: "USD".to_string(),
+                is_active: true,
+                allow_direct_posting: true,
+                is_bank_account: false,
+                bank_account_number: None,
+            })
+            .await
+            .unwrap();
+
+        let changed = get_trial_balance(&state, &uri, &token, Some(&etag)).await;
+        assert_eq!(changed.status, StatusCode::OK);
+        assert_ne!(changed.headers[ETAG], etag.as_str());
+    }
+
+    #[tokio::test]
+    async fn test_trial_balance_formatting_follows_locale_setting() {
+        let test_db = TestDb::new().await;
+        let state: AppState = test_app_state(&test_db);
+        let org = OrgFixture::new().create(test_db.conn()).await;
+        let token = access_token(&state.jwt_service, org.id, &org.owner);
+        let uri = format!("/organizations/{}/reports/trial-balance", org.id);
+
+        let response = get_trial_balance(&state, &uri, &token, None).await;
+        assert_eq!(response.status, StatusCode::OK);
+        let etag = response.headers[ETAG].to_str().unwrap().to_string();
+        let body = response.json();
+        assert_eq!(
+            body["formatting"],
+            json!({
+                "currency": "USD",
+                "symbol": "$",
+                "decimal_places": 2,
+                "timezone": "UTC",
+                "locale": "en-US",
+                "decimal_separator": ".",
+                "group_separator": ","
+            })
+        );
+
+        OrganizationRepository::new(test_db.conn().clone())
+            .update_settings(
+                org.id.into_inner(),
+                &json!({ "formatting": { "locale": "de-DE" } }),
+            )
+            .await
+            .unwrap();
+
+        // The settings change invalidates the cached report
+        let response = get_trial_balance(&state, &uri, &token, Some(&etag)).await;
+        assert_eq!(response.status, StatusCode::OK);
+        let body = response.json();
+        assert_eq!(body["formatting"]["locale"], "de-DE");
+        assert_eq!(body["formatting"]["decimal_separator"], ",");
+        assert_eq!(body["formatting"]["group_separator"], ".");
+    }
+
+    #[tokio::test]
+    async fn test_trial_balance_rejects_non_members_before_etag() {
+        let test_db = TestDb::new().await;
+        let state: AppState = test_app_state(&test_db);
+        let org = OrgFixture::new().create(test_db.conn()).await;
+        let outsider = OrgFixture::new().create(test_db.conn()).await;
+        let token = access_token(&state.jwt_service, outsider.id, &outsider.owner);
+        let uri = format!("/organizations/{}/reports/trial-balance", org.id);
+
+        let response = get_trial_balance(&state, &uri, &token, Some("*")).await;
+        assert_eq!(response.status, StatusCode::FORBIDDEN);
+        assert!(response.headers.get(ETAG).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_dimensional_report_caps_dimension_values() {
+        let test_db = TestDb::new().await;
+        let state: AppState = test_app_state(&test_db);
+        let org = OrgFixture::new().create(test_db.conn()).await;
+        let token = access_token(&state.jwt_service, org.id, &org.owner);
+        let values = (0..=MAX_DIMENSION_VALUE_FILTERS)
+            .map(|i| {
+                let key = if i % 2 == 0 {
+                    "dimension"
+                } else {
+                    "exclude_dimension"
+                };
+                format!("&{key}={}", Uuid::new_v4())
+            })
+            .collect::<Vec<_>>()
+            .concat();
+        let uri = format!(
+            "/organizations/{}/reports/dimensional?group_by=Project{values}",
+            org.id
+        );
+
+        let response = get_trial_balance(&state, &uri, &token, None).await;
+        assert_eq!(response.status, StatusCode::BAD_REQUEST);
+        let body = response.json();
+        assert_eq!(body["error"], "too_many_dimensions");
+    }
+
+    #[tokio::test]
+    async fn test_income_statement_by_dimension_validates_type_and_allocation() {
+        let test_db = TestDb::new().await;
+        let state: AppState = test_app_state(&test_db);
+        let org = OrgFixture::new()
+            .with_accounts(&[("6000", AccountType::Expense)])
+            .create(test_db.conn())
+            .await;
+        let token = access_token(&state.jwt_service, org.id, &org.owner);
+        let dimensions = DimensionRepository::new(test_db.conn().clone());
+        let dept = dimensions
+            .create_dimension_type(zeltra_db::repositories::CreateDimensionTypeInput {
+                organization_id: org.id.into_inner(),
+                code: "DEPT".to_string(),
+                name: "Department".to_string(),
+                description: None,
+                is_required: false,
+                is_active: true,
+                sort_order: 0,
+            })
+            .await
+            .unwrap();
+        for code in ["ENG", "SALES"] {
+            dimensions
+                .create_dimension_value(zeltra_db::repositories::CreateDimensionValueInput {
+                    organization_id: org.id.into_inner(),
+                    dimension_type_id: dept.id,
+                    code: code.to_string(),
+                    name: code.to_string(),
+                    description: None,
+                    parent_id: None,
+                    is_active: true,
+                    effective_from: None,
+                    effective_to: None,
+                })
+                .await
+                .unwrap();
+        }
+        let uri = |params: &str| {
+            format!(
+                "/organizations/{}/reports/income-statement/by-dimension?from=2025-01-01&to=2025-12-31&{params}",
+                org.id
+            )
+        };
+        let get = |params: &str| {
+            let uri = uri(params);
+            let state = state.clone();
+            let token = token.clone();
+            async move {
+                let response = get_trial_balance(&state, &uri, &token, None).await;
+                let status = response.status;
+                let body = response.json();
+                (status, body)
+            }
+        };
+
+        let (status, body) = get("dimension_type=DEPT&allocation=ENG:60,SALES:40").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["allocated"], true);
+        let codes: Vec<&str> = body["columns"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|c| c["code"].as_str().unwrap())
+            .collect();
+        assert_eq!(codes, ["ENG", "SALES", "unallocated"]);
+        assert!(body["columns"][2]["dimension_value_id"].is_null());
+
+        let (status, body) = get("dimension_type=REGION").await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body["error"], "invalid_dimension_type");
+
+        for allocation in ["ENG:60,SALES:30", "ENG:100,OPS:0", "ENG"] {
+            let (status, body) = get(&format!("dimension_type=DEPT&allocation={allocation}")).await;
+            assert_eq!(status, StatusCode::BAD_REQUEST, "{allocation}");
+            assert_eq!(body["error"], "invalid_allocation");
+        }
+    }
+}
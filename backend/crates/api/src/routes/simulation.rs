@@ -18,11 +18,15 @@ use std::str::FromStr;
 use tracing::error;
 use uuid::Uuid;
 
-use crate::{AppState, middleware::AuthUser};
+use crate::{
+    AppState,
+    middleware::{AuthMember, auth::check_report_access},
+};
 use zeltra_core::simulation::{
-    HistoricalAccountData as CoreHistoricalData, SimulationEngine, SimulationParams,
+    DimensionAdjustment, HistoricalAccountData as CoreHistoricalData, SimulationEngine,
+    SimulationParams,
 };
-use zeltra_db::{OrganizationRepository, repositories::simulation::SimulationRepository};
+use zeltra_db::repositories::simulation::SimulationRepository;
 
 /// Creates the simulation routes (requires auth middleware to be applied externally).
 pub fn routes() -> Router<AppState> {
@@ -57,6 +61,20 @@ pub struct RunSimulationRequest {
     /// Filter historical data by dimension value IDs.
     #[serde(default)]
     pub dimension_filters: Option<Vec<Uuid>>,
+    /// Growth rate overrides for the part of an account tagged with a dimension value.
+    #[serde(default)]
+    pub dimension_adjustments: Option<Vec<DimensionAdjustmentRequest>>,
+}
+
+/// Growth rate override scoped to an account and dimension value.
+#[derive(Debug, Deserialize)]
+pub struct DimensionAdjustmentRequest {
+    /// Account to adjust.
+    pub account_id: Uuid,
+    /// Dimension value the adjustment applies to.
+    pub dimension_value_id: Uuid,
+    /// Growth rate for the tagged slice (decimal string).
+    pub growth_rate: String,
 }
 
 /// Response for simulation result.
@@ -93,6 +111,8 @@ pub struct AccountProjectionResponse {
     pub account_name: String,
     /// Account type.
     pub account_type: String,
+    /// Dimension value of the projected slice, null for account-level rows.
+    pub dimension_value_id: Option<Uuid>,
     /// Average monthly amount from historical period.
     pub baseline_amount: String,
     /// Projected amount after applying growth rate.
@@ -133,36 +153,6 @@ pub struct MonthlySummaryResponse {
 // Helper Functions
 // ============================================================================
 
-/// Checks if user is a member of the organization.
-async fn check_membership(
-    org_repo: &OrganizationRepository,
-    org_id: Uuid,
-    user_id: Uuid,
-) -> Result<(), axum::response::Response> {
-    match org_repo.is_member(org_id, user_id).await {
-        Ok(true) => Ok(()),
-        Ok(false) => Err((
-            StatusCode::FORBIDDEN,
-            Json(json!({
-                "error": "forbidden",
-                "message": "You are not a member of this organization"
-            })),
-        )
-            .into_response()),
-        Err(e) => {
-            error!(error = %e, "Failed to check membership");
-            Err((
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(json!({
-                    "error": "internal_error",
-                    "message": "An error occurred"
-                })),
-            )
-                .into_response())
-        }
-    }
-}
-
 /// Formats a Decimal as a string with 4 decimal places.
 fn format_money(amount: Decimal) -> String {
     format!("{amount:.4}")
@@ -193,6 +183,23 @@ fn parse_account_adjustments(
         .collect()
 }
 
+/// Parses dimension adjustments, skipping entries with an unparseable rate.
+fn parse_dimension_adjustments(
+    adjustments: Option<Vec<DimensionAdjustmentRequest>>,
+) -> Vec<DimensionAdjustment> {
+    adjustments
+        .unwrap_or_default()
+        .into_iter()
+        .filter_map(|a| {
+            Some(DimensionAdjustment {
+                account_id: a.account_id,
+                dimension_value_id: a.dimension_value_id,
+                growth_rate: Decimal::from_str(&a.growth_rate).ok()?,
+            })
+        })
+        .collect()
+}
+
 /// Gets month abbreviation from period name (YYYY-MM).
 fn get_month_abbrev(period_name: &str) -> String {
     let parts: Vec<&str> = period_name.split('-').collect();
@@ -229,13 +236,11 @@ fn get_month_abbrev(period_name: &str) -> String {
 async fn run_simulation(
     State(state): State<AppState>,
     Path(org_id): Path<Uuid>,
-    auth_user: AuthUser,
+    auth: AuthMember,
     Json(request): Json<RunSimulationRequest>,
 ) -> impl IntoResponse {
-    let org_repo = OrganizationRepository::new((*state.db).clone());
-
-    // Check membership
-    if let Err(response) = check_membership(&org_repo, org_id, auth_user.user_id()).await {
+    // Projections are built from the organization's full ledger history
+    if let Err(response) = check_report_access(&state, org_id, &auth).await {
         return response;
     }
 
@@ -317,7 +322,27 @@ async fn run_simulation(
         }
     }
 
+    let dimension_adjustments = parse_dimension_adjustments(request.dimension_adjustments);
+
+    if dimension_adjustments
+        .iter()
+        .any(|a| a.growth_rate < min_rate || a.growth_rate > max_rate)
+    {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(json!({
+                "error": "invalid_growth_rate",
+                "message": "Dimension adjustment rates must be between -1 (−100%) and 10 (1000%)"
+            })),
+        )
+            .into_response();
+    }
+
     let dimension_filters = request.dimension_filters.unwrap_or_default();
+    let dimension_scopes: Vec<(Uuid, Uuid)> = dimension_adjustments
+        .iter()
+        .map(|a| (a.account_id, a.dimension_value_id))
+        .collect();
 
     // Build simulation params
     let params = SimulationParams {
@@ -328,6 +353,7 @@ async fn run_simulation(
         expense_growth_rate,
         account_adjustments,
         dimension_filters: dimension_filters.clone(),
+        dimension_adjustments,
     };
 
     // Query historical data
@@ -338,6 +364,7 @@ async fn run_simulation(
             request.base_period_start,
             request.base_period_end,
             &dimension_filters,
+            &dimension_scopes,
         )
         .await
     {
@@ -364,6 +391,7 @@ async fn run_simulation(
             account_name: h.account_name,
             account_type: h.account_type,
             monthly_amounts: h.monthly_amounts,
+            dimension_value_id: h.dimension_value_id,
         })
         .collect();
 
@@ -424,6 +452,7 @@ async fn run_simulation(
                 account_code: p.account_code.clone(),
                 account_name: p.account_name.clone(),
                 account_type: p.account_type.clone(),
+                dimension_value_id: p.dimension_value_id,
                 baseline_amount: format_money(p.baseline_amount),
                 projected_amount: format_money(p.projected_amount),
                 change_percent: format_percent(p.change_percent),
@@ -440,3 +469,52 @@ async fn run_simulation(
 
     (StatusCode::OK, Json(response)).into_response()
 }
+
+#[cfg(test)]
+mod integration_tests {
+    use super::*;
+    use zeltra_db::entities::sea_orm_active_enums::{AccountType, UserRole};
+    use zeltra_test_support::{OrgFixture, TestDb, access_token, send, test_app_state};
+
+    #[tokio::test]
+    async fn test_submitter_cannot_run_simulation() {
+        let test_db = TestDb::new().await;
+        let state: AppState = test_app_state(&test_db);
+        let org = OrgFixture::new()
+            .with_fiscal_year_2025()
+            .with_accounts(&[("4000", AccountType::Revenue)])
+            .with_member(UserRole::Submitter)
+            .with_member(UserRole::Viewer)
+            .create(test_db.conn())
+            .await;
+        let uri = format!("/organizations/{}/simulation/run", org.id);
+        let request = json!({
+            "base_period_start": "2025-01-01",
+            "base_period_end": "2025-12-31",
+            "projection_months": 12
+        });
+
+        let submitter = access_token(&state.jwt_service, org.id, org.member(&UserRole::Submitter));
+        let (status, body) = send(
+            state.authenticated(routes()),
+            "POST",
+            &uri,
+            &submitter,
+            request.clone(),
+        )
+        .await;
+        assert_eq!(status, StatusCode::FORBIDDEN);
+        assert_eq!(body["error"], "report_access_restricted");
+
+        let viewer = access_token(&state.jwt_service, org.id, org.member(&UserRole::Viewer));
+        let (status, _) = send(
+            state.authenticated(routes()),
+            "POST",
+            &uri,
+            &viewer,
+            request,
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+    }
+}
@@ -0,0 +1,351 @@
+//! Transaction comment routes.
+//!
+//! Members discuss a transaction in a thread that also shows approval notes
+//! and rejection reasons. Submitters only see threads on transactions they
+//! created. `@email` mentions notify the mentioned members.
+
+use axum::{
+    Json, Router,
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    routing::{delete, get},
+};
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use tracing::{error, info, warn};
+use uuid::Uuid;
+
+use crate::{AppState, middleware::AuthMember, routes::notifications};
+use zeltra_core::auth::UserRole as CoreUserRole;
+use zeltra_core::comment::{CommentError, parse_mentions};
+use zeltra_db::{
+    entities::{transaction_comments, transactions},
+    repositories::{CommentRepoError, CommentRepository, CommentWithAuthor, CreateCommentInput},
+};
+use zeltra_shared::types::{OrganizationId, TransactionId};
+
+/// Creates the transaction comment routes.
+pub fn routes() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/organizations/{org_id}/transactions/{transaction_id}/comments",
+            get(list_comments).post(create_comment),
+        )
+        .route(
+            "/organizations/{org_id}/transactions/{transaction_id}/comments/{comment_id}",
+            delete(delete_comment),
+        )
+}
+
+// ============================================================================
+// Request/Response Types
+// ============================================================================
+
+/// Query parameters for listing comments.
+#[derive(Debug, Deserialize)]
+pub struct ListCommentsQuery {
+    /// Page number (1-indexed, default: 1).
+    pub page: Option<u64>,
+    /// Number of comments per page (default: 50, max: 100).
+    pub limit: Option<u64>,
+}
+
+/// Request body for posting a comment.
+#[derive(Debug, Deserialize)]
+pub struct CreateCommentRequest {
+    /// Comment text; `@email` mentions notify those members.
+    pub body: String,
+    /// Comment being replied to.
+    pub parent_comment_id: Option<Uuid>,
+}
+
+/// A comment's author.
+#[derive(Debug, Serialize)]
+pub struct CommentAuthorResponse {
+    /// User ID.
+    pub id: Uuid,
+    /// Display name.
+    pub full_name: String,
+    /// Email address.
+    pub email: String,
+}
+
+/// Response for a comment.
+#[derive(Debug, Serialize)]
+pub struct CommentResponse {
+    /// Comment ID.
+    pub id: Uuid,
+    /// Transaction ID.
+    pub transaction_id: Uuid,
+    /// Comment being replied to.
+    pub parent_comment_id: Option<Uuid>,
+    /// `comment`, or `approval`/`rejection` for notes copied from the workflow.
+    pub kind: String,
+    /// Comment text; `None` once deleted.
+    pub body: Option<String>,
+    /// Author.
+    pub author: CommentAuthorResponse,
+    /// When the comment was posted.
+    pub created_at: String,
+    /// Whether the comment was deleted.
+    pub deleted: bool,
+    /// When the comment was deleted.
+    pub deleted_at: Option<String>,
+}
+
+// ============================================================================
+// Handlers
+// ============================================================================
+
+/// GET `/organizations/{org_id}/transactions/{transaction_id}/comments` -
+/// List a transaction's comments, oldest first.
+async fn list_comments(
+    State(state): State<AppState>,
+    auth: AuthMember,
+    Path((org_id, transaction_id)): Path<(OrganizationId, TransactionId)>,
+    Query(query): Query<ListCommentsQuery>,
+) -> impl IntoResponse {
+    let repo = CommentRepository::new((*state.db).clone());
+    if let Err(response) = check_thread_access(&state, &repo, &auth, org_id, transaction_id).await {
+        return response;
+    }
+
+    let page = query.page.unwrap_or(1).max(1);
+    let limit = query.limit.unwrap_or(50).clamp(1, 100);
+
+    match repo
+        .list(
+            org_id.into_inner(),
+            transaction_id.into_inner(),
+            page,
+            limit,
+        )
+        .await
+    {
+        Ok(result) => {
+            let items: Vec<CommentResponse> =
+                result.comments.iter().map(comment_to_response).collect();
+            let total_pages = if result.total == 0 {
+                1
+            } else {
+                result.total.div_ceil(limit)
+            };
+
+            (
+                StatusCode::OK,
+                Json(json!({
+                    "data": items,
+                    "pagination": {
+                        "total": result.total,
+                        "page": page,
+                        "limit": limit,
+                        "total_pages": total_pages
+                    }
+                })),
+            )
+                .into_response()
+        }
+        Err(e) => comment_error_response(&e.into()),
+    }
+}
+
+/// POST `/organizations/{org_id}/transactions/{transaction_id}/comments` -
+/// Post a comment, notifying mentioned members.
+async fn create_comment(
+    State(state): State<AppState>,
+    auth: AuthMember,
+    Path((org_id, transaction_id)): Path<(OrganizationId, TransactionId)>,
+    Json(payload): Json<CreateCommentRequest>,
+) -> impl IntoResponse {
+    let repo = CommentRepository::new((*state.db).clone());
+    let (_, transaction) =
+        match check_thread_access(&state, &repo, &auth, org_id, transaction_id).await {
+            Ok(access) => access,
+            Err(response) => return response,
+        };
+
+    let comment = match repo
+        .create(CreateCommentInput {
+            organization_id: org_id.into_inner(),
+            transaction_id: transaction.id,
+            author_id: auth.user_id(),
+            body: payload.body,
+            parent_comment_id: payload.parent_comment_id,
+        })
+        .await
+    {
+        Ok(comment) => comment,
+        Err(e) => return comment_error_response(&e),
+    };
+
+    info!(transaction_id = %transaction.id, comment_id = %comment.comment.id, "Comment posted");
+
+    let emails = parse_mentions(&comment.comment.body);
+    let mentioned = match repo
+        .find_mentioned_members(&transaction, &emails, auth.user_id())
+        .await
+    {
+        Ok(mentioned) => mentioned,
+        Err(e) => {
+            warn!(error = %e, comment_id = %comment.comment.id, "Failed to resolve mentions");
+            Vec::new()
+        }
+    };
+    if !mentioned.is_empty() {
+        notifications::notify_comment_mention(&state, org_id, &transaction, &comment, &mentioned)
+            .await;
+    }
+    let unresolved: Vec<&String> = emails
+        .iter()
+        .filter(|email| {
+            !mentioned
+                .iter()
+                .any(|m| m.email.eq_ignore_ascii_case(email))
+        })
+        .collect();
+
+    let mut body = json!(comment_to_response(&comment));
+    body["mentions"] = json!(
+        mentioned
+            .iter()
+            .map(|m| json!({ "user_id": m.user_id, "email": m.email }))
+            .collect::<Vec<_>>()
+    );
+    body["unresolved_mentions"] = json!(unresolved);
+
+    (StatusCode::CREATED, Json(body)).into_response()
+}
+
+/// DELETE `/organizations/{org_id}/transactions/{transaction_id}/comments/{comment_id}` -
+/// Delete a comment.
+///
+/// Authors may delete their own comments for 15 minutes; Owners and Admins
+/// at any time. The comment stays in the thread without its text.
+async fn delete_comment(
+    State(state): State<AppState>,
+    auth: AuthMember,
+    Path((org_id, transaction_id, comment_id)): Path<(OrganizationId, TransactionId, Uuid)>,
+) -> impl IntoResponse {
+    let repo = CommentRepository::new((*state.db).clone());
+    let (role, _) = match check_thread_access(&state, &repo, &auth, org_id, transaction_id).await {
+        Ok(access) => access,
+        Err(response) => return response,
+    };
+
+    match repo
+        .delete(
+            org_id.into_inner(),
+            transaction_id.into_inner(),
+            comment_id,
+            auth.user_id(),
+            role,
+            Utc::now(),
+        )
+        .await
+    {
+        Ok(comment) => {
+            info!(comment_id = %comment_id, "Comment deleted");
+            (StatusCode::OK, Json(deleted_comment_response(&comment))).into_response()
+        }
+        Err(e) => comment_error_response(&e),
+    }
+}
+
+// ============================================================================
+// Helper Functions
+// ============================================================================
+
+/// Checks that the caller can read the transaction's thread, returning
+/// their role and the transaction.
+async fn check_thread_access(
+    state: &AppState,
+    repo: &CommentRepository,
+    auth: &AuthMember,
+    org_id: OrganizationId,
+    transaction_id: TransactionId,
+) -> Result<(CoreUserRole, transactions::Model), Response> {
+    let role = auth.role_in(state, org_id).await?;
+    let transaction = repo
+        .find_transaction(org_id.into_inner(), transaction_id.into_inner())
+        .await
+        .map_err(|e| comment_error_response(&e))?;
+
+    if !role.can_view_all_transactions() && transaction.created_by != auth.user_id() {
+        return Err((
+            StatusCode::FORBIDDEN,
+            Json(json!({
+                "error": "transaction_access_restricted",
+                "message": "Submitters can only view transactions they created"
+            })),
+        )
+            .into_response());
+    }
+
+    Ok((role, transaction))
+}
+
+fn comment_to_response(comment: &CommentWithAuthor) -> CommentResponse {
+    let c = &comment.comment;
+    CommentResponse {
+        id: c.id,
+        transaction_id: c.transaction_id,
+        parent_comment_id: c.parent_comment_id,
+        kind: c.kind.clone(),
+        body: c.deleted_at.is_none().then(|| c.body.clone()),
+        author: CommentAuthorResponse {
+            id: c.author_id,
+            full_name: comment.author_name.clone(),
+            email: comment.author_email.clone(),
+        },
+        created_at: c.created_at.to_rfc3339(),
+        deleted: c.deleted_at.is_some(),
+        deleted_at: c.deleted_at.map(|t| t.to_rfc3339()),
+    }
+}
+
+fn deleted_comment_response(comment: &transaction_comments::Model) -> serde_json::Value {
+    json!({
+        "id": comment.id,
+        "deleted": true,
+        "deleted_at": comment.deleted_at.map(|t| t.to_rfc3339()),
+        "deleted_by": comment.deleted_by
+    })
+}
+
+fn comment_error_response(e: &CommentRepoError) -> Response {
+    let (status, code) = match e {
+        CommentRepoError::TransactionNotFound(_) | CommentRepoError::CommentNotFound(_) => {
+            (StatusCode::NOT_FOUND, "not_found")
+        }
+        CommentRepoError::ParentNotFound(_) => (StatusCode::BAD_REQUEST, "parent_not_found"),
+        CommentRepoError::Comment(CommentError::EmptyBody | CommentError::BodyTooLong) => {
+            (StatusCode::BAD_REQUEST, "invalid_comment")
+        }
+        CommentRepoError::Comment(CommentError::NotAuthor) => (StatusCode::FORBIDDEN, "forbidden"),
+        CommentRepoError::Comment(CommentError::DeleteWindowPassed) => {
+            (StatusCode::FORBIDDEN, "delete_window_passed")
+        }
+        CommentRepoError::Comment(CommentError::UnknownKind(_)) | CommentRepoError::Database(_) => {
+            error!(error = %e, "Database error in transaction comments");
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({
+                    "error": "internal_error",
+                    "message": "An error occurred"
+                })),
+            )
+                .into_response();
+        }
+    };
+
+    (
+        status,
+        Json(json!({
+            "error": code,
+            "message": e.to_string()
+        })),
+    )
+        .into_response()
+}
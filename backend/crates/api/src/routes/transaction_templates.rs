@@ -0,0 +1,735 @@
+//! Transaction template routes.
+//!
+//! Templates are saved per user and turned into draft transactions through
+//! the same path as `POST /organizations/{org_id}/transactions`.
+
+use axum::{
+    Json, Router,
+    extract::{Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+    routing::{get, post},
+};
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::str::FromStr;
+use tracing::{error, info};
+use uuid::Uuid;
+
+use crate::{
+    AppState,
+    middleware::{AuthUser, auth::check_membership, with_last_modified},
+    routes::transactions::{
+        CreateEntryRequest, CreateTransactionRequest, create_draft_transaction, string_to_tx_type,
+        tx_type_to_string,
+    },
+};
+use zeltra_core::template::{InstantiateAmounts, TemplateError, TemplateService};
+use zeltra_db::{
+    OrganizationRepository,
+    entities::sea_orm_active_enums::EntryType,
+    repositories::transaction_template::{
+        CreateTransactionTemplateInput, TemplateLineInput, TemplateWithLines,
+        TransactionTemplateError, TransactionTemplateRepository, UpdateTransactionTemplateInput,
+    },
+};
+use zeltra_shared::types::OrganizationId;
+
+/// Creates the transaction template routes.
+pub fn routes() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/organizations/{org_id}/transaction-templates",
+            get(list_templates).post(create_template),
+        )
+        .route(
+            "/organizations/{org_id}/transaction-templates/{template_id}",
+            get(get_template)
+                .patch(update_template)
+                .delete(delete_template),
+        )
+        .route(
+            "/organizations/{org_id}/transaction-templates/{template_id}/instantiate",
+            post(instantiate_template),
+        )
+}
+
+// ============================================================================
+// Request/Response Types
+// ============================================================================
+
+/// Request body for a template entry.
+#[derive(Debug, Deserialize)]
+pub struct TemplateEntryRequest {
+    /// Account ID.
+    pub account_id: Uuid,
+    /// Entry type: "debit" or "credit".
+    pub entry_type: String,
+    /// Fixed amount used every time.
+    pub amount: Option<String>,
+    /// Share of the instantiation total (0-100).
+    pub percentage: Option<String>,
+    /// Optional memo.
+    pub memo: Option<String>,
+    /// Dimension value IDs.
+    #[serde(default)]
+    pub dimensions: Vec<Uuid>,
+}
+
+/// Request body for creating a template.
+#[derive(Debug, Deserialize)]
+pub struct CreateTemplateRequest {
+    /// Template name.
+    pub name: String,
+    /// Transaction type.
+    #[serde(rename = "type")]
+    pub transaction_type: String,
+    /// Transaction description.
+    pub description: String,
+    /// Optional transaction memo.
+    pub memo: Option<String>,
+    /// Currency of every entry. Defaults to the organization's base currency.
+    pub currency: Option<String>,
+    /// Entry skeleton.
+    pub entries: Vec<TemplateEntryRequest>,
+}
+
+/// Request body for updating a template.
+#[derive(Debug, Deserialize)]
+pub struct UpdateTemplateRequest {
+    /// New name.
+    pub name: Option<String>,
+    /// New transaction type.
+    #[serde(rename = "type")]
+    pub transaction_type: Option<String>,
+    /// New description.
+    pub description: Option<String>,
+    /// New memo.
+    pub memo: Option<String>,
+    /// New currency.
+    pub currency: Option<String>,
+    /// Replacement entry skeleton.
+    pub entries: Option<Vec<TemplateEntryRequest>>,
+}
+
+/// Request body for instantiating a template.
+#[derive(Debug, Deserialize)]
+pub struct InstantiateTemplateRequest {
+    /// Transaction date (YYYY-MM-DD).
+    pub transaction_date: NaiveDate,
+    /// Total split across percentage entries.
+    pub total_amount: Option<String>,
+    /// One amount per template entry, in order.
+    pub amounts: Option<Vec<String>>,
+    /// Overrides the template's description.
+    pub description: Option<String>,
+    /// Optional reference number.
+    pub reference_number: Option<String>,
+    /// Overrides the template's memo.
+    pub memo: Option<String>,
+    /// Create the draft even if it looks like a duplicate.
+    #[serde(default)]
+    pub allow_duplicate: bool,
+}
+
+/// Response for a template entry.
+#[derive(Debug, Serialize)]
+pub struct TemplateEntryResponse {
+    /// 1-based line number.
+    pub line_number: i16,
+    /// Account ID.
+    pub account_id: Uuid,
+    /// Entry type.
+    pub entry_type: String,
+    /// Fixed amount.
+    pub amount: Option<String>,
+    /// Percentage of the total.
+    pub percentage: Option<String>,
+    /// Memo.
+    pub memo: Option<String>,
+    /// Dimension value IDs.
+    pub dimensions: Vec<Uuid>,
+}
+
+/// Response for a template.
+#[derive(Debug, Serialize)]
+pub struct TemplateResponse {
+    /// Template ID.
+    pub id: Uuid,
+    /// Template name.
+    pub name: String,
+    /// Transaction type.
+    #[serde(rename = "type")]
+    pub transaction_type: String,
+    /// Transaction description.
+    pub description: String,
+    /// Transaction memo.
+    pub memo: Option<String>,
+    /// Currency of every entry.
+    pub currency: String,
+    /// Entry skeleton.
+    pub entries: Vec<TemplateEntryResponse>,
+    /// Created at timestamp.
+    pub created_at: String,
+    /// Updated at timestamp.
+    pub updated_at: String,
+}
+
+// ============================================================================
+// Route Handlers
+// ============================================================================
+
+/// GET `/organizations/{org_id}/transaction-templates` - List the caller's templates.
+async fn list_templates(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(org_id): Path<OrganizationId>,
+) -> impl IntoResponse {
+    if let Err(response) = check_membership(&state, org_id, auth.user_id()).await {
+        return response;
+    }
+
+    let repo = TransactionTemplateRepository::new((*state.db).clone());
+
+    match repo
+        .list_templates(org_id.into_inner(), auth.user_id())
+        .await
+    {
+        Ok(templates) => {
+            let items: Vec<TemplateResponse> = templates.iter().map(template_to_response).collect();
+            (StatusCode::OK, Json(json!({ "data": items }))).into_response()
+        }
+        Err(e) => {
+            error!(error = %e, "Failed to list transaction templates");
+            template_error_response(&e)
+        }
+    }
+}
+
+/// POST `/organizations/{org_id}/transaction-templates` - Create template.
+async fn create_template(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(org_id): Path<OrganizationId>,
+    Json(payload): Json<CreateTemplateRequest>,
+) -> impl IntoResponse {
+    let org_repo = OrganizationRepository::new((*state.db).clone());
+    if let Err(response) = check_membership(&state, org_id, auth.user_id()).await {
+        return response;
+    }
+
+    let Some(transaction_type) = string_to_tx_type(&payload.transaction_type) else {
+        return invalid_transaction_type_response();
+    };
+    let lines = match parse_entries(&payload.entries) {
+        Ok(lines) => lines,
+        Err(response) => return response,
+    };
+
+    let currency = match payload.currency {
+        Some(currency) => currency,
+        None => match org_repo.find_by_id(org_id.into_inner()).await {
+            Ok(Some(org)) => org.base_currency,
+            Ok(None) => {
+                return (
+                    StatusCode::NOT_FOUND,
+                    Json(json!({
+                        "error": "organization_not_found",
+                        "message": "Organization not found"
+                    })),
+                )
+                    .into_response();
+            }
+            Err(e) => {
+                error!(error = %e, "Failed to get organization");
+                return internal_error_response();
+            }
+        },
+    };
+
+    let repo = TransactionTemplateRepository::new((*state.db).clone());
+    let input = CreateTransactionTemplateInput {
+        user_id: auth.user_id(),
+        name: payload.name,
+        transaction_type,
+        description: payload.description,
+        memo: payload.memo,
+        currency,
+        lines,
+    };
+
+    match repo.create_template(org_id.into_inner(), input).await {
+        Ok(template) => {
+            info!(template_id = %template.template.id, "Transaction template created");
+
+            (StatusCode::CREATED, Json(template_to_response(&template))).into_response()
+        }
+        Err(e) => {
+            error!(error = %e, "Failed to create transaction template");
+            template_error_response(&e)
+        }
+    }
+}
+
+/// GET `/organizations/{org_id}/transaction-templates/{template_id}` - Get template.
+async fn get_template(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path((org_id, template_id)): Path<(OrganizationId, Uuid)>,
+) -> impl IntoResponse {
+    if let Err(response) = check_membership(&state, org_id, auth.user_id()).await {
+        return response;
+    }
+
+    let repo = TransactionTemplateRepository::new((*state.db).clone());
+
+    match repo
+        .get_template(org_id.into_inner(), auth.user_id(), template_id)
+        .await
+    {
+        Ok(template) => with_last_modified(
+            (StatusCode::OK, Json(template_to_response(&template))).into_response(),
+            &template.template.updated_at,
+        ),
+        Err(e) => {
+            error!(error = %e, "Failed to get transaction template");
+            template_error_response(&e)
+        }
+    }
+}
+
+/// PATCH `/organizations/{org_id}/transaction-templates/{template_id}` - Update template.
+///
+/// `entries`, when given, replaces the whole skeleton.
+async fn update_template(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path((org_id, template_id)): Path<(OrganizationId, Uuid)>,
+    Json(payload): Json<UpdateTemplateRequest>,
+) -> impl IntoResponse {
+    if let Err(response) = check_membership(&state, org_id, auth.user_id()).await {
+        return response;
+    }
+
+    let transaction_type = match payload.transaction_type.as_deref() {
+        Some(t) => match string_to_tx_type(t) {
+            Some(t) => Some(t),
+            None => return invalid_transaction_type_response(),
+        },
+        None => None,
+    };
+    let lines = match payload.entries.as_deref().map(parse_entries).transpose() {
+        Ok(lines) => lines,
+        Err(response) => return response,
+    };
+
+    let repo = TransactionTemplateRepository::new((*state.db).clone());
+    let input = UpdateTransactionTemplateInput {
+        name: payload.name,
+        transaction_type,
+        description: payload.description,
+        memo: payload.memo.map(Some),
+        currency: payload.currency,
+        lines,
+    };
+
+    match repo
+        .update_template(org_id.into_inner(), auth.user_id(), template_id, input)
+        .await
+    {
+        Ok(template) => {
+            info!(template_id = %template_id, "Transaction template updated");
+
+            (StatusCode::OK, Json(template_to_response(&template))).into_response()
+        }
+        Err(e) => {
+            error!(error = %e, "Failed to update transaction template");
+            template_error_response(&e)
+        }
+    }
+}
+
+/// DELETE `/organizations/{org_id}/transaction-templates/{template_id}` - Delete template.
+async fn delete_template(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path((org_id, template_id)): Path<(OrganizationId, Uuid)>,
+) -> impl IntoResponse {
+    if let Err(response) = check_membership(&state, org_id, auth.user_id()).await {
+        return response;
+    }
+
+    let repo = TransactionTemplateRepository::new((*state.db).clone());
+
+    match repo
+        .delete_template(org_id.into_inner(), auth.user_id(), template_id)
+        .await
+    {
+        Ok(()) => {
+            info!(template_id = %template_id, "Transaction template deleted");
+
+            (StatusCode::NO_CONTENT, ()).into_response()
+        }
+        Err(e) => {
+            error!(error = %e, "Failed to delete transaction template");
+            template_error_response(&e)
+        }
+    }
+}
+
+/// POST `/organizations/{org_id}/transaction-templates/{template_id}/instantiate` - Create a draft.
+///
+/// With `total_amount`, percentage entries split the total exactly; with
+/// `amounts`, every entry takes the given amount. Templates with only fixed
+/// amounts need neither.
+async fn instantiate_template(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path((org_id, template_id)): Path<(OrganizationId, Uuid)>,
+    Json(payload): Json<InstantiateTemplateRequest>,
+) -> impl IntoResponse {
+    if let Err(response) = check_membership(&state, org_id, auth.user_id()).await {
+        return response;
+    }
+
+    let amounts = match (payload.total_amount.as_deref(), payload.amounts.as_deref()) {
+        (Some(_), Some(_)) => {
+            return (
+                StatusCode::BAD_REQUEST,
+                Json(json!({
+                    "error": "invalid_amounts",
+                    "message": "Provide either total_amount or amounts, not both"
+                })),
+            )
+                .into_response();
+        }
+        (Some(total), None) => match parse_amount(total) {
+            Ok(total) => InstantiateAmounts::Total(total),
+            Err(response) => return response,
+        },
+        (None, Some(values)) => match values.iter().map(|v| parse_amount(v)).collect() {
+            Ok(values) => InstantiateAmounts::PerLine(values),
+            Err(response) => return response,
+        },
+        (None, None) => InstantiateAmounts::FixedOnly,
+    };
+
+    let repo = TransactionTemplateRepository::new((*state.db).clone());
+    let template = match repo
+        .get_template(org_id.into_inner(), auth.user_id(), template_id)
+        .await
+    {
+        Ok(template) => template,
+        Err(e) => return template_error_response(&e),
+    };
+
+    if let Err(e) = repo
+        .check_accounts_active(org_id.into_inner(), &template)
+        .await
+    {
+        return template_error_response(&e);
+    }
+
+    let decimal_places = match repo
+        .currency_decimal_places(&template.template.currency)
+        .await
+    {
+        Ok(places) => places,
+        Err(e) => return template_error_response(&e),
+    };
+
+    let resolved =
+        match TemplateService::resolve_amounts(&template.core_lines(), &amounts, decimal_places) {
+            Ok(resolved) => resolved,
+            Err(e) => return template_error_response(&TransactionTemplateError::Template(e)),
+        };
+
+    let entries = template
+        .lines
+        .iter()
+        .zip(resolved)
+        .map(|(line, amount)| CreateEntryRequest {
+            account_id: line.account_id,
+            source_currency: template.template.currency.clone(),
+            source_amount: amount.to_string(),
+            entry_type: entry_type_to_string(&line.entry_type).to_string(),
+            memo: line.memo.clone(),
+            dimensions: line.dimension_value_ids.clone(),
+            event_at: None,
+            override_exchange_rate: None,
+        })
+        .collect();
+
+    let request = CreateTransactionRequest {
+        transaction_type: tx_type_to_string(&template.template.transaction_type),
+        transaction_date: payload.transaction_date,
+        description: payload
+            .description
+            .unwrap_or_else(|| template.template.description.clone()),
+        reference_number: payload.reference_number,
+        memo: payload.memo.or_else(|| template.template.memo.clone()),
+        entries,
+        allow_duplicate: payload.allow_duplicate,
+    };
+
+    info!(template_id = %template_id, "Instantiating transaction template");
+    create_draft_transaction(&state, auth.user_id(), org_id, request).await
+}
+
+// ============================================================================
+// Helper Functions
+// ============================================================================
+
+fn template_to_response(template: &TemplateWithLines) -> TemplateResponse {
+    TemplateResponse {
+        id: template.template.id,
+        name: template.template.name.clone(),
+        transaction_type: tx_type_to_string(&template.template.transaction_type),
+        description: template.template.description.clone(),
+        memo: template.template.memo.clone(),
+        currency: template.template.currency.clone(),
+        entries: template
+            .lines
+            .iter()
+            .map(|line| TemplateEntryResponse {
+                line_number: line.line_number,
+                account_id: line.account_id,
+                entry_type: entry_type_to_string(&line.entry_type).to_string(),
+                amount: line.amount.map(|a| a.to_string()),
+                percentage: line.percentage.map(|p| p.to_string()),
+                memo: line.memo.clone(),
+                dimensions: line.dimension_value_ids.clone(),
+            })
+            .collect(),
+        created_at: template.template.created_at.to_rfc3339(),
+        updated_at: template.template.updated_at.to_rfc3339(),
+    }
+}
+
+#[allow(clippy::result_large_err)]
+fn parse_entries(
+    entries: &[TemplateEntryRequest],
+) -> Result<Vec<TemplateLineInput>, axum::response::Response> {
+    entries
+        .iter()
+        .map(|entry| {
+            let entry_type = match entry.entry_type.to_lowercase().as_str() {
+                "debit" => EntryType::Debit,
+                "credit" => EntryType::Credit,
+                _ => {
+                    return Err((
+                        StatusCode::BAD_REQUEST,
+                        Json(json!({
+                            "error": "invalid_entry_type",
+                            "message": "Entry type must be 'debit' or 'credit'"
+                        })),
+                    )
+                        .into_response());
+                }
+            };
+
+            Ok(TemplateLineInput {
+                account_id: entry.account_id,
+                entry_type,
+                amount: entry.amount.as_deref().map(parse_amount).transpose()?,
+                percentage: entry.percentage.as_deref().map(parse_amount).transpose()?,
+                memo: entry.memo.clone(),
+                dimensions: entry.dimensions.clone(),
+            })
+        })
+        .collect()
+}
+
+#[allow(clippy::result_large_err)]
+fn parse_amount(s: &str) -> Result<Decimal, axum::response::Response> {
+    Decimal::from_str(s).map_err(|_| {
+        (
+            StatusCode::BAD_REQUEST,
+            Json(json!({
+                "error": "invalid_amount",
+                "message": "Invalid amount format"
+            })),
+        )
+            .into_response()
+    })
+}
+
+const fn entry_type_to_string(entry_type: &EntryType) -> &'static str {
+    match entry_type {
+        EntryType::Debit => "debit",
+        EntryType::Credit => "credit",
+    }
+}
+
+fn invalid_transaction_type_response() -> axum::response::Response {
+    (
+        StatusCode::BAD_REQUEST,
+        Json(json!({
+            "error": "invalid_transaction_type",
+            "message": "Invalid transaction type"
+        })),
+    )
+        .into_response()
+}
+
+fn internal_error_response() -> axum::response::Response {
+    (
+        StatusCode::INTERNAL_SERVER_ERROR,
+        Json(json!({
+            "error": "internal_error",
+            "message": "An error occurred"
+        })),
+    )
+        .into_response()
+}
+
+fn template_error_response(e: &TransactionTemplateError) -> axum::response::Response {
+    let (status, code) = match e {
+        TransactionTemplateError::NotFound(_) => (StatusCode::NOT_FOUND, "not_found"),
+        TransactionTemplateError::DuplicateName(_) => (StatusCode::CONFLICT, "duplicate_name"),
+        TransactionTemplateError::AmountAndPercentage(_) => {
+            (StatusCode::BAD_REQUEST, "invalid_template")
+        }
+        TransactionTemplateError::Template(TemplateError::Unbalanced { .. }) => {
+            (StatusCode::BAD_REQUEST, "unbalanced_transaction")
+        }
+        TransactionTemplateError::Template(
+            TemplateError::InsufficientLines
+            | TemplateError::InvalidFixedAmount(_)
+            | TemplateError::InvalidPercentage(_)
+            | TemplateError::PercentageSum { .. },
+        ) => (StatusCode::BAD_REQUEST, "invalid_template"),
+        TransactionTemplateError::Template(_) => (StatusCode::BAD_REQUEST, "invalid_amounts"),
+        TransactionTemplateError::AccountNotFound(_) => {
+            (StatusCode::BAD_REQUEST, "account_not_found")
+        }
+        TransactionTemplateError::InactiveAccount { .. } => {
+            (StatusCode::BAD_REQUEST, "inactive_account")
+        }
+        TransactionTemplateError::CurrencyNotFound(_) => {
+            (StatusCode::BAD_REQUEST, "currency_not_found")
+        }
+        TransactionTemplateError::Database(_) => return internal_error_response(),
+    };
+
+    (
+        status,
+        Json(json!({
+            "error": code,
+            "message": e.to_string()
+        })),
+    )
+        .into_response()
+}
+
+#[cfg(test)]
+mod integration_tests {
+    use super::*;
+    use zeltra_db::{
+        entities::sea_orm_active_enums::{AccountType, UserRole},
+        repositories::account::{AccountRepository, UpdateAccountInput},
+    };
+    use zeltra_test_support::{OrgFixture, TestDb, access_token, jwt_service, test_app_state};
+
+    async fn send(
+        state: &AppState,
+        method: &str,
+        uri: &str,
+        token: &str,
+        body: serde_json::Value,
+    ) -> (StatusCode, serde_json::Value) {
+        zeltra_test_support::send(state.authenticated(routes()), method, uri, token, body).await
+    }
+
+    #[tokio::test]
+    async fn test_instantiate_allocates_total_and_rejects_inactive_accounts() {
+        let test_db = TestDb::new().await;
+        let state: AppState = test_app_state(&test_db);
+        let org = OrgFixture::new()
+            .with_fiscal_year_2025()
+            .with_accounts(&[
+                ("1900", AccountType::Asset),
+                ("6100", AccountType::Expense),
+                ("6200", AccountType::Expense),
+                ("6300", AccountType::Expense),
+            ])
+            .with_member(UserRole::Accountant)
+            .create(test_db.conn())
+            .await;
+        let jwt = jwt_service();
+        let token = access_token(&jwt, org.id, &org.owner);
+        let uri = format!("/organizations/{}/transaction-templates", org.id);
+
+        let (status, template) = send(
+            &state,
+            "POST",
+            &uri,
+            &token,
+            json!({
+                "name": "Weekly allocation",
+                "type": "journal",
+                "description": "Intercompany allocation",
+                "entries": [
+                    { "account_id": org.account("1900"), "entry_type": "credit", "percentage": "100" },
+                    { "account_id": org.account("6100"), "entry_type": "debit", "percentage": "33.33" },
+                    { "account_id": org.account("6200"), "entry_type": "debit", "percentage": "33.33" },
+                    { "account_id": org.account("6300"), "entry_type": "debit", "percentage": "33.34" }
+                ]
+            }),
+        )
+        .await;
+        assert_eq!(status, StatusCode::CREATED);
+        assert_eq!(template["currency"], "USD");
+        let instantiate_uri = format!("{uri}/{}/instantiate", template["id"].as_str().unwrap());
+
+        // Templates are private to their owner
+        let other = access_token(&jwt, org.id, org.member(&UserRole::Accountant));
+        let (status, _) = send(
+            &state,
+            "POST",
+            &instantiate_uri,
+            &other,
+            json!({ "transaction_date": "2025-03-10", "total_amount": "100" }),
+        )
+        .await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+
+        let (status, created) = send(
+            &state,
+            "POST",
+            &instantiate_uri,
+            &token,
+            json!({ "transaction_date": "2025-03-10", "total_amount": "1000.01" }),
+        )
+        .await;
+        assert_eq!(status, StatusCode::CREATED);
+        assert_eq!(created["status"], "draft");
+        assert_eq!(created["total_debit"], created["total_credit"]);
+        assert_eq!(created["entries"].as_array().unwrap().len(), 4);
+
+        AccountRepository::new(test_db.conn().clone())
+            .update_account(
+                org.account("6200"),
+                UpdateAccountInput {
+                    is_active: Some(false),
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
+
+        let (status, body) = send(
+            &state,
+            "POST",
+            &instantiate_uri,
+            &token,
+            json!({ "transaction_date": "2025-03-17", "total_amount": "500" }),
+        )
+        .await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body["error"], "inactive_account");
+        assert!(body["message"].as_str().unwrap().contains("6200"));
+    }
+}
@@ -6,27 +6,58 @@
 use axum::{
     Json, Router,
     extract::{Path, Query, State},
-    http::StatusCode,
+    http::{
+        StatusCode,
+        header::{CONTENT_DISPOSITION, CONTENT_TYPE},
+    },
     response::IntoResponse,
     routing::{delete, get, patch, post},
 };
-use chrono::NaiveDate;
+use chrono::{DateTime, FixedOffset, NaiveDate};
 use rust_decimal::Decimal;
-use serde::{Deserialize, Serialize};
+use sea_orm::Iterable;
+use serde::{Deserialize, Deserializer, Serialize};
 use serde_json::json;
+use std::collections::BTreeMap;
 use std::str::FromStr;
 use tracing::{error, info};
 use uuid::Uuid;
 
-use crate::{AppState, middleware::AuthUser};
+use crate::{
+    AppState,
+    extractors::{StrictJson, json::enum_string},
+    middleware::{AuthMember, AuthUser, auth::check_membership, with_last_modified},
+    routes::{attachments::move_error_response, invalid_sort_response, notifications},
+};
+use zeltra_core::auth::UserRole as CoreUserRole;
+use zeltra_core::currency::{PrecisionError, STORED_DECIMAL_PLACES};
+use zeltra_core::ledger::{
+    ApplicationError, CreateTransactionInput as LedgerTransactionInput, CurrencyLine, EntryAmounts,
+    InputEntryType, LedgerEntryInput, LedgerError, LedgerService, SettlementStatus,
+    TransactionType as CoreTransactionType, validate_currency_balance,
+};
+use zeltra_core::settings::OrganizationSettings;
+use zeltra_core::workflow::{
+    ActionAvailability, CheckedEntry, ReversalComparison, ReviewClaim,
+    TransactionStatus as CoreTransactionStatus, VoidReasonCode, compare_reversal,
+};
 use zeltra_db::{
-    OrganizationRepository,
-    entities::sea_orm_active_enums::{TransactionStatus, TransactionType},
-    repositories::WorkflowRepository,
+    entities::sea_orm_active_enums::{TransactionStatus, TransactionType, UserRole},
+    repositories::exchange_rate::{ExchangeRateError, ExchangeRateLookup},
+    repositories::organization::get_role_level,
+    repositories::payment::{ApplyPaymentInput, PaymentError, Settlement, is_settleable},
     repositories::transaction::{
-        CreateLedgerEntryInput, CreateTransactionInput, TransactionFilter, TransactionRepository,
+        CreateLedgerEntryInput, CreateTransactionInput, EntryReferences, LedgerEntryWithDimensions,
+        RateOverrideInput, TransactionError, TransactionFilter, TransactionSortField,
+        TransactionWithEntries, UpdateTransactionInput,
+    },
+    repositories::{
+        ApprovalOutcome, ApprovalRequirement, AttachmentRepository, BudgetOverrun,
+        BudgetRepository, OrganizationRepository, PendingSortField, TransactionStore,
+        VoucherRepository,
     },
 };
+use zeltra_shared::types::{OrganizationId, SortParams, TransactionId};
 
 /// Creates the transaction routes.
 pub fn routes() -> Router<AppState> {
@@ -39,6 +70,10 @@ pub fn routes() -> Router<AppState> {
             "/organizations/{org_id}/transactions",
             post(create_transaction),
         )
+        .route(
+            "/organizations/{org_id}/transactions/summary",
+            get(get_transaction_summary),
+        )
         .route(
             "/organizations/{org_id}/transactions/pending",
             get(get_pending_transactions),
@@ -47,6 +82,10 @@ pub fn routes() -> Router<AppState> {
             "/organizations/{org_id}/transactions/bulk-approve",
             post(bulk_approve_transactions),
         )
+        .route(
+            "/organizations/{org_id}/transactions/bulk-void",
+            post(bulk_void_transactions),
+        )
         .route(
             "/organizations/{org_id}/transactions/{transaction_id}",
             get(get_transaction),
@@ -59,6 +98,22 @@ pub fn routes() -> Router<AppState> {
             "/organizations/{org_id}/transactions/{transaction_id}",
             delete(delete_transaction),
         )
+        .route(
+            "/organizations/{org_id}/transactions/{transaction_id}/actions",
+            get(get_transaction_actions),
+        )
+        .route(
+            "/organizations/{org_id}/transactions/{transaction_id}/history",
+            get(get_transaction_history),
+        )
+        .route(
+            "/organizations/{org_id}/transactions/{transaction_id}/voucher",
+            get(get_transaction_voucher),
+        )
+        .route(
+            "/organizations/{org_id}/transactions/{transaction_id}/reversal-check",
+            get(get_reversal_check),
+        )
         .route(
             "/organizations/{org_id}/transactions/{transaction_id}/submit",
             post(submit_transaction),
@@ -71,6 +126,14 @@ pub fn routes() -> Router<AppState> {
             "/organizations/{org_id}/transactions/{transaction_id}/reject",
             post(reject_transaction),
         )
+        .route(
+            "/organizations/{org_id}/transactions/{transaction_id}/claim",
+            post(claim_transaction),
+        )
+        .route(
+            "/organizations/{org_id}/transactions/{transaction_id}/claim",
+            delete(release_claim),
+        )
         .route(
             "/organizations/{org_id}/transactions/{transaction_id}/post",
             post(post_transaction),
@@ -79,6 +142,14 @@ pub fn routes() -> Router<AppState> {
             "/organizations/{org_id}/transactions/{transaction_id}/void",
             post(void_transaction),
         )
+        .route(
+            "/organizations/{org_id}/transactions/{transaction_id}/apply",
+            post(apply_payment),
+        )
+        .route(
+            "/organizations/{org_id}/transactions/{transaction_id}/apply/{settles_transaction_id}",
+            delete(unapply_payment),
+        )
 }
 
 // ============================================================================
@@ -99,17 +170,53 @@ pub struct ListTransactionsQuery {
     pub to: Option<NaiveDate>,
     /// Filter by dimension value ID.
     pub dimension: Option<Uuid>,
+    /// Only transactions with an entry on this account.
+    pub account_id: Option<Uuid>,
+    /// Minimum total debit in the functional currency, inclusive.
+    pub min_amount: Option<String>,
+    /// Maximum total debit in the functional currency, inclusive.
+    pub max_amount: Option<String>,
+    /// Only transactions created by this user. Ignored for submitters, who
+    /// only see their own.
+    pub created_by: Option<Uuid>,
     /// Page number (1-indexed).
     pub page: Option<u64>,
     /// Page size (default: 50, max: 100).
     pub limit: Option<u64>,
 }
 
+/// Query parameters for the approval queue.
+#[derive(Debug, Deserialize)]
+pub struct PendingTransactionsQuery {
+    /// Only transactions pending at least this many whole days.
+    pub min_days: Option<u32>,
+}
+
+/// Query parameters for a printable voucher.
+#[derive(Debug, Deserialize)]
+pub struct VoucherQuery {
+    /// Output format (default: pdf).
+    #[serde(default)]
+    pub format: VoucherFormat,
+}
+
+/// Output format of a printable voucher.
+#[derive(Debug, Clone, Copy, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum VoucherFormat {
+    /// PDF document.
+    #[default]
+    Pdf,
+    /// Standalone HTML page, for printing from the browser.
+    Html,
+}
+
 /// Request body for creating a transaction.
 #[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct CreateTransactionRequest {
     /// Transaction type.
-    #[serde(rename = "type")]
+    #[serde(rename = "type", deserialize_with = "deserialize_tx_type")]
     pub transaction_type: String,
     /// Transaction date (YYYY-MM-DD).
     pub transaction_date: NaiveDate,
@@ -121,10 +228,14 @@ pub struct CreateTransactionRequest {
     pub memo: Option<String>,
     /// Ledger entries.
     pub entries: Vec<CreateEntryRequest>,
+    /// Create the transaction even if it looks like a duplicate.
+    #[serde(default)]
+    pub allow_duplicate: bool,
 }
 
 /// Request body for a single ledger entry.
 #[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct CreateEntryRequest {
     /// Account ID.
     pub account_id: Uuid,
@@ -133,12 +244,20 @@ pub struct CreateEntryRequest {
     /// Source amount (positive).
     pub source_amount: String,
     /// Entry type: "debit" or "credit".
+    #[serde(deserialize_with = "deserialize_entry_type")]
     pub entry_type: String,
     /// Optional memo.
     pub memo: Option<String>,
     /// Dimension value IDs.
     #[serde(default)]
     pub dimensions: Vec<Uuid>,
+    /// When the entry happened (RFC 3339), for ordering within the day.
+    /// Must fall on the transaction date in the organization's timezone.
+    pub event_at: Option<DateTime<FixedOffset>>,
+    /// Rate to book a foreign-currency entry at instead of the looked-up
+    /// one. Accountant or above only; the entry is flagged and the override
+    /// logged.
+    pub override_exchange_rate: Option<String>,
 }
 
 /// Request body for updating a transaction.
@@ -150,6 +269,8 @@ pub struct UpdateTransactionRequest {
     pub memo: Option<String>,
     /// Reference number.
     pub reference_number: Option<String>,
+    /// Transaction date; moving it re-assigns the fiscal period.
+    pub transaction_date: Option<NaiveDate>,
 }
 
 /// Response for a transaction.
@@ -184,6 +305,150 @@ pub struct TransactionResponse {
     pub total_debit: String,
     /// Total credits in functional currency.
     pub total_credit: String,
+    /// Totals per source currency, in order of first appearance.
+    pub source_totals: Vec<SourceTotalResponse>,
+    /// How far payments settle the transaction; invoices and bills only.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub settlement_status: Option<SettlementStatus>,
+    /// Approval requirement recorded at submission; omitted for drafts.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub approval_requirement: Option<ApprovalRequirementResponse>,
+    /// Whether the transaction took a budget line past the enforcement
+    /// threshold when it was submitted.
+    pub budget_warning: bool,
+    /// Non-fatal problems found while creating the transaction.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<TransactionWarning>,
+}
+
+/// What approving a transaction requires.
+#[derive(Debug, Serialize)]
+pub struct ApprovalRequirementResponse {
+    /// Minimum role required to approve.
+    pub required_role: String,
+    /// Number of different users who must approve.
+    pub required_approvals: u32,
+    /// Approval rule matched, `null` under the default policy.
+    pub matched_rule_id: Option<Uuid>,
+    /// Whether this was recorded at submission; `false` only for
+    /// transactions submitted before requirements were recorded.
+    pub from_snapshot: bool,
+}
+
+impl From<ApprovalRequirement> for ApprovalRequirementResponse {
+    fn from(requirement: ApprovalRequirement) -> Self {
+        Self {
+            required_role: requirement.required_role,
+            required_approvals: requirement.required_approvals,
+            matched_rule_id: requirement.matched_rule_id,
+            from_snapshot: requirement.from_snapshot,
+        }
+    }
+}
+
+/// A transaction's entries summed in one source currency.
+#[derive(Debug, Serialize)]
+pub struct SourceTotalResponse {
+    /// Source currency.
+    pub currency: String,
+    /// Total debits in source currency.
+    pub debit: String,
+    /// Total credits in source currency.
+    pub credit: String,
+    /// Total debits of these entries in functional currency.
+    pub functional_debit: String,
+    /// Total credits of these entries in functional currency.
+    pub functional_credit: String,
+}
+
+/// A non-fatal problem with a created transaction.
+#[derive(Debug, Serialize)]
+pub struct TransactionWarning {
+    /// Warning code.
+    pub code: String,
+    /// Human-readable message.
+    pub message: String,
+    /// The exchange rate the warning concerns.
+    pub rate_used: RateUsedResponse,
+}
+
+/// A budget line that a submitted transaction takes past the enforcement
+/// threshold.
+#[derive(Debug, Serialize)]
+pub struct BudgetLineImpactResponse {
+    /// Budget line ID.
+    pub budget_line_id: Uuid,
+    /// Budget ID.
+    pub budget_id: Uuid,
+    /// Budget name.
+    pub budget_name: String,
+    /// Account ID.
+    pub account_id: Uuid,
+    /// Account code.
+    pub account_code: String,
+    /// Fiscal period ID.
+    pub fiscal_period_id: Uuid,
+    /// Period name.
+    pub period_name: String,
+    /// Budgeted amount in functional currency.
+    pub budgeted: String,
+    /// Posted actuals before this transaction.
+    pub actual: String,
+    /// Budget left before this transaction.
+    pub remaining: String,
+    /// This transaction's amount against the line.
+    pub impact: String,
+    /// Utilization including this transaction, `null` for a zero budget.
+    pub projected_utilization_percent: Option<String>,
+}
+
+impl From<&BudgetOverrun> for BudgetLineImpactResponse {
+    fn from(overrun: &BudgetOverrun) -> Self {
+        let utilization = &overrun.utilization;
+        Self {
+            budget_line_id: overrun.budget_line_id,
+            budget_id: overrun.budget_id,
+            budget_name: overrun.budget_name.clone(),
+            account_id: overrun.account_id,
+            account_code: overrun.account_code.clone(),
+            fiscal_period_id: overrun.fiscal_period_id,
+            period_name: overrun.period_name.clone(),
+            budgeted: utilization.budgeted.to_string(),
+            actual: utilization.actual.to_string(),
+            remaining: utilization.remaining().to_string(),
+            impact: utilization.impact.to_string(),
+            projected_utilization_percent: utilization.percent().map(|p| p.to_string()),
+        }
+    }
+}
+
+/// A budget line a submitted transaction takes past the threshold, when
+/// enforcement only warns.
+#[derive(Debug, Serialize)]
+pub struct BudgetWarningResponse {
+    /// Warning code.
+    pub code: String,
+    /// Human-readable message.
+    pub message: String,
+    /// The budget line the warning concerns.
+    pub budget_line: BudgetLineImpactResponse,
+}
+
+/// An exchange rate applied to a transaction's entries.
+#[derive(Debug, Serialize)]
+pub struct RateUsedResponse {
+    /// Source currency code.
+    pub from_currency: String,
+    /// Functional currency code.
+    pub to_currency: String,
+    /// Exchange rate.
+    pub rate: String,
+    /// Effective date of the rate.
+    pub effective_date: NaiveDate,
+    /// Days between the rate's effective date and the transaction date.
+    pub staleness_days: i64,
+    /// Whether the rate is older than the organization allows.
+    pub stale: bool,
 }
 
 /// Response for a ledger entry.
@@ -211,6 +476,70 @@ pub struct EntryResponse {
     pub memo: Option<String>,
     /// Dimension value IDs.
     pub dimensions: Vec<Uuid>,
+    /// Dimension value IDs added from the account's defaults. Only present
+    /// in the response to creating the transaction.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub defaulted_dimensions: Vec<Uuid>,
+    /// Number of attachments on this entry.
+    pub attachment_count: i64,
+    /// When the entry happened.
+    pub event_at: String,
+    /// Whether the exchange rate was entered rather than looked up.
+    pub is_override: bool,
+}
+
+impl From<LedgerEntryWithDimensions> for EntryResponse {
+    fn from(e: LedgerEntryWithDimensions) -> Self {
+        Self {
+            id: e.entry.id,
+            account_id: e.entry.account_id,
+            source_currency: e.entry.source_currency,
+            source_amount: e.entry.source_amount.to_string(),
+            exchange_rate: e.entry.exchange_rate.to_string(),
+            functional_currency: e.entry.functional_currency,
+            functional_amount: e.entry.functional_amount.to_string(),
+            debit: e.entry.debit.to_string(),
+            credit: e.entry.credit.to_string(),
+            memo: e.entry.memo,
+            dimensions: e.dimensions,
+            defaulted_dimensions: e.defaulted_dimensions,
+            attachment_count: e.attachment_count,
+            event_at: e.entry.event_at.to_rfc3339(),
+            is_override: e.entry.is_override,
+        }
+    }
+}
+
+impl TransactionResponse {
+    /// Builds the response for a transaction, totalling its entries.
+    /// Settlement status and warnings are left for the caller to fill in.
+    fn new(result: TransactionWithEntries, source_totals: Vec<SourceTotalResponse>) -> Self {
+        let total_debit: Decimal = result.entries.iter().map(|e| e.entry.debit).sum();
+        let total_credit: Decimal = result.entries.iter().map(|e| e.entry.credit).sum();
+        let approval_requirement = ApprovalRequirement::snapshot(&result.transaction);
+        let transaction = result.transaction;
+        Self {
+            id: transaction.id,
+            reference_number: transaction.reference_number,
+            transaction_type: tx_type_to_string(&transaction.transaction_type),
+            transaction_date: transaction.transaction_date.to_string(),
+            description: transaction.description,
+            memo: transaction.memo,
+            status: status_to_string(&transaction.status),
+            fiscal_period_id: transaction.fiscal_period_id,
+            created_by: transaction.created_by,
+            created_at: transaction.created_at.to_rfc3339(),
+            updated_at: transaction.updated_at.to_rfc3339(),
+            entries: result.entries.into_iter().map(Into::into).collect(),
+            total_debit: total_debit.to_string(),
+            total_credit: total_credit.to_string(),
+            source_totals,
+            settlement_status: None,
+            approval_requirement: approval_requirement.map(Into::into),
+            budget_warning: transaction.budget_warning,
+            warnings: Vec::new(),
+        }
+    }
 }
 
 /// Response for transaction list item (without entries).
@@ -254,19 +583,93 @@ pub struct RejectRequest {
 /// Request body for voiding a transaction.
 #[derive(Debug, Deserialize)]
 pub struct VoidRequest {
-    /// Void reason (required).
+    /// Why the transaction is being voided.
+    pub reason_code: VoidReasonCode,
+    /// Free-text explanation, required when `reason_code` is `other`.
+    #[serde(default)]
     pub reason: String,
+    /// Transaction to move the voided transaction's attachments to, usually
+    /// its corrected re-entry.
+    #[serde(default)]
+    pub move_attachments_to: Option<Uuid>,
 }
 
 /// Request body for bulk approval.
 #[derive(Debug, Deserialize)]
 pub struct BulkApproveRequest {
     /// Transaction IDs to approve.
-    pub transaction_ids: Vec<Uuid>,
+    pub transaction_ids: Vec<TransactionId>,
     /// Optional approval notes.
     pub approval_notes: Option<String>,
 }
 
+/// Request body for bulk void.
+#[derive(Debug, Deserialize)]
+pub struct BulkVoidRequest {
+    /// Transaction IDs to void.
+    pub transaction_ids: Vec<TransactionId>,
+    /// Why the transactions are being voided.
+    pub reason_code: VoidReasonCode,
+    /// Free-text explanation, required when `reason_code` is `other`.
+    #[serde(default)]
+    pub reason: String,
+}
+
+/// Request body for applying a payment to an invoice or bill.
+#[derive(Debug, Deserialize)]
+pub struct ApplyPaymentRequest {
+    /// Invoice or bill the payment settles.
+    pub settles_transaction_id: Uuid,
+    /// Amount to apply, in functional currency.
+    pub amount: String,
+}
+
+/// Response for a payment application.
+#[derive(Debug, Serialize)]
+pub struct PaymentApplicationResponse {
+    /// Application ID.
+    pub id: Uuid,
+    /// Payment applied from.
+    pub payment_transaction_id: Uuid,
+    /// Invoice or bill applied to.
+    pub settles_transaction_id: Uuid,
+    /// Amount applied, in functional currency.
+    pub applied_amount: String,
+    /// User who applied the payment.
+    pub created_by: Uuid,
+    /// Created at timestamp.
+    pub created_at: String,
+    /// Settlement of the invoice or bill afterwards.
+    pub settlement: SettlementResponse,
+}
+
+/// Settlement of an invoice or bill.
+#[derive(Debug, Serialize)]
+pub struct SettlementResponse {
+    /// Invoice or bill ID.
+    pub transaction_id: Uuid,
+    /// Settlement status.
+    pub status: SettlementStatus,
+    /// Functional total.
+    pub total: String,
+    /// Amount applied by payments.
+    pub applied: String,
+    /// Amount left to settle.
+    pub outstanding: String,
+}
+
+impl SettlementResponse {
+    fn new(transaction_id: Uuid, settlement: &Settlement) -> Self {
+        Self {
+            transaction_id,
+            status: settlement.status,
+            total: settlement.total.to_string(),
+            applied: settlement.applied.to_string(),
+            outstanding: settlement.outstanding().to_string(),
+        }
+    }
+}
+
 /// Response for void operation.
 #[derive(Debug, Serialize)]
 pub struct VoidResponse {
@@ -298,6 +701,45 @@ pub struct BulkApproveItemResponse {
     pub error: Option<String>,
 }
 
+/// Response for bulk void.
+#[derive(Debug, Serialize)]
+pub struct BulkVoidResponse {
+    /// Results for each transaction.
+    pub results: Vec<BulkVoidItemResponse>,
+    /// Number of successful voids.
+    pub success_count: usize,
+    /// Number of failed voids.
+    pub failure_count: usize,
+}
+
+/// Response for a single bulk void item.
+#[derive(Debug, Serialize)]
+pub struct BulkVoidItemResponse {
+    /// Transaction ID.
+    pub transaction_id: Uuid,
+    /// Whether the void succeeded.
+    pub success: bool,
+    /// Reversing transaction created by the void.
+    pub reversing_transaction_id: Option<Uuid>,
+    /// Error code if failed.
+    pub error_code: Option<&'static str>,
+    /// Error message if failed.
+    pub error: Option<String>,
+}
+
+/// Response for the transaction summary.
+#[derive(Debug, Serialize)]
+pub struct TransactionSummaryResponse {
+    /// Number of transactions per status.
+    pub by_status: BTreeMap<String, u64>,
+    /// Number of transactions per type.
+    pub by_type: BTreeMap<String, u64>,
+    /// Total number of transactions matching the filter.
+    pub total_count: u64,
+    /// Sum of functional debits across posted transactions.
+    pub posted_debit_total: String,
+}
+
 /// Response for pending transaction in approval queue.
 #[derive(Debug, Serialize)]
 pub struct PendingTransactionResponse {
@@ -318,8 +760,98 @@ pub struct PendingTransactionResponse {
     pub total_amount: String,
     /// Submitted at timestamp.
     pub submitted_at: Option<String>,
+    /// Whole days since submission.
+    pub days_pending: u32,
     /// Whether the current user can approve this transaction.
     pub can_approve: bool,
+    /// Delegator the user would approve for, when only a delegation allows it.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub on_behalf_of: Option<Uuid>,
+    /// Approvals recorded so far.
+    pub approvals: u32,
+    /// Approvals needed before the transaction is approved.
+    pub required_approvals: u32,
+    /// The requirement the transaction is held to.
+    pub approval_requirement: ApprovalRequirementResponse,
+    /// Whether the transaction took a budget line past the enforcement
+    /// threshold when it was submitted.
+    pub budget_warning: bool,
+    /// Active review claim, `null` when nobody is reviewing it.
+    pub claim: Option<ClaimResponse>,
+}
+
+/// Review claim on a pending transaction.
+#[derive(Debug, Serialize)]
+pub struct ClaimResponse {
+    /// User reviewing the transaction.
+    pub reviewer_id: Uuid,
+    /// Reviewer's display name.
+    pub reviewer_name: String,
+    /// When the claim lapses.
+    pub reviewing_until: String,
+}
+
+impl From<ReviewClaim> for ClaimResponse {
+    fn from(claim: ReviewClaim) -> Self {
+        Self {
+            reviewer_id: claim.reviewer_id,
+            reviewer_name: claim.reviewer_name,
+            reviewing_until: claim.expires_at.to_rfc3339(),
+        }
+    }
+}
+
+/// Workflow actions the current user may take on a transaction.
+#[derive(Debug, Serialize)]
+pub struct TransactionActionsResponse {
+    /// Transaction ID.
+    pub transaction_id: Uuid,
+    /// Current status.
+    pub status: String,
+    /// Every workflow action, with a reason when it is not allowed.
+    pub actions: Vec<ActionAvailability>,
+}
+
+/// A transaction's workflow transitions, oldest first.
+#[derive(Debug, Serialize)]
+pub struct TransactionHistoryResponse {
+    /// Transaction ID.
+    pub transaction_id: Uuid,
+    /// Current status.
+    pub status: String,
+    /// Every recorded transition.
+    pub history: Vec<StatusHistoryItemResponse>,
+}
+
+/// One workflow transition.
+#[derive(Debug, Serialize)]
+pub struct StatusHistoryItemResponse {
+    /// Status before the transition; absent when it created the transaction.
+    pub from_status: Option<String>,
+    /// Status after the transition.
+    pub to_status: String,
+    /// User who made the transition.
+    pub actor_id: Uuid,
+    /// That user's full name.
+    pub actor_name: Option<String>,
+    /// Approval notes, rejection reason or void reason.
+    pub notes: Option<String>,
+    /// When the transition happened.
+    pub at: String,
+}
+
+/// Comparison of a voided transaction with its reversal.
+#[derive(Debug, Serialize)]
+pub struct ReversalCheckResponse {
+    /// The voided transaction.
+    pub original_transaction_id: Uuid,
+    /// The transaction reversing it.
+    pub reversal_transaction_id: Uuid,
+    /// Whether every entry has an exact mirror on the other side.
+    pub is_exact_mirror: bool,
+    /// Matched pairs, mismatches and unpaired entries.
+    #[serde(flatten)]
+    pub comparison: ReversalComparison,
 }
 
 // ============================================================================
@@ -331,31 +863,28 @@ pub struct PendingTransactionResponse {
 /// Requirements: 10.2
 async fn list_transactions(
     State(state): State<AppState>,
-    auth: AuthUser,
-    Path(org_id): Path<Uuid>,
+    auth: AuthMember,
+    Path(org_id): Path<OrganizationId>,
     Query(query): Query<ListTransactionsQuery>,
+    Query(sort): Query<SortParams>,
 ) -> impl IntoResponse {
-    let org_repo = OrganizationRepository::new((*state.db).clone());
-
-    // Check membership
-    if let Err(response) = check_membership(&org_repo, org_id, auth.user_id()).await {
-        return response;
-    }
+    let role = match auth.role_in(&state, org_id).await {
+        Ok(role) => role,
+        Err(response) => return response,
+    };
 
-    let tx_repo = TransactionRepository::new((*state.db).clone());
+    let sort = match sort.resolve::<TransactionSortField>() {
+        Ok(sort) => sort,
+        Err(e) => return invalid_sort_response(&e),
+    };
 
-    // Build filter
-    let filter = TransactionFilter {
-        status: query.status.as_ref().and_then(|s| string_to_status(s)),
-        transaction_type: query
-            .transaction_type
-            .as_ref()
-            .and_then(|t| string_to_tx_type(t)),
-        date_from: query.from,
-        date_to: query.to,
-        dimension_value_id: query.dimension,
+    let filter = match build_filter(&query, role, auth.user_id()) {
+        Ok(filter) => TransactionFilter { sort, ..filter },
+        Err(response) => return response,
     };
 
+    let tx_repo = state.stores.transactions.as_ref();
+
     match tx_repo.list_transactions(org_id, filter).await {
         Ok(transactions) => {
             let items: Vec<TransactionListItem> = transactions
@@ -387,23 +916,160 @@ async fn list_transactions(
     }
 }
 
+/// GET `/organizations/{org_id}/transactions/summary` - Counts and totals for list views.
+///
+/// Accepts the same filters as the list endpoint.
+async fn get_transaction_summary(
+    State(state): State<AppState>,
+    auth: AuthMember,
+    Path(org_id): Path<OrganizationId>,
+    Query(query): Query<ListTransactionsQuery>,
+) -> impl IntoResponse {
+    let role = match auth.role_in(&state, org_id).await {
+        Ok(role) => role,
+        Err(response) => return response,
+    };
+
+    let filter = match build_filter(&query, role, auth.user_id()) {
+        Ok(filter) => filter,
+        Err(response) => return response,
+    };
+
+    let tx_repo = state.stores.transactions.as_ref();
+    match tx_repo.summarize(org_id, filter).await {
+        Ok(summary) => {
+            let by_status: BTreeMap<String, u64> = summary
+                .by_status
+                .iter()
+                .map(|(status, count)| (status_to_string(status), *count))
+                .collect();
+            let by_type: BTreeMap<String, u64> = summary
+                .by_type
+                .iter()
+                .map(|(tx_type, count)| (tx_type_to_string(tx_type), *count))
+                .collect();
+
+            let response = TransactionSummaryResponse {
+                total_count: by_status.values().sum(),
+                by_status,
+                by_type,
+                posted_debit_total: summary.posted_debit_total.to_string(),
+            };
+
+            (StatusCode::OK, Json(response)).into_response()
+        }
+        Err(e) => {
+            error!(error = %e, "Failed to summarize transactions");
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({
+                    "error": "internal_error",
+                    "message": "An error occurred"
+                })),
+            )
+                .into_response()
+        }
+    }
+}
+
 /// POST `/organizations/{org_id}/transactions` - Create a new transaction.
 ///
 /// Requirements: 10.1
-#[allow(clippy::too_many_lines)]
 async fn create_transaction(
     State(state): State<AppState>,
     auth: AuthUser,
-    Path(org_id): Path<Uuid>,
-    Json(payload): Json<CreateTransactionRequest>,
+    Path(org_id): Path<OrganizationId>,
+    StrictJson(payload): StrictJson<CreateTransactionRequest>,
 ) -> impl IntoResponse {
-    let org_repo = OrganizationRepository::new((*state.db).clone());
+    create_draft_transaction(&state, auth.user_id(), org_id, payload).await
+}
 
-    // Check membership
-    if let Err(response) = check_membership(&org_repo, org_id, auth.user_id()).await {
-        return response;
+/// Parses requested entries into ledger input; only accountants and above
+/// may override exchange rates.
+#[allow(clippy::result_large_err)]
+fn parse_entries(
+    entries: &[CreateEntryRequest],
+    role: &UserRole,
+) -> Result<Vec<LedgerEntryInput>, axum::response::Response> {
+    if entries.iter().any(|e| e.override_exchange_rate.is_some())
+        && get_role_level(role) < get_role_level(&UserRole::Accountant)
+    {
+        return Err((
+            StatusCode::FORBIDDEN,
+            Json(json!({
+                "error": "forbidden",
+                "message": "Only accountants and above can override exchange rates"
+            })),
+        )
+            .into_response());
     }
 
+    entries
+        .iter()
+        .map(|entry_req| {
+            let Ok(source_amount) = Decimal::from_str(&entry_req.source_amount) else {
+                return Err((
+                    StatusCode::BAD_REQUEST,
+                    Json(json!({
+                        "error": "invalid_amount",
+                        "message": "Invalid amount format"
+                    })),
+                )
+                    .into_response());
+            };
+            let entry_type = entry_req
+                .entry_type
+                .parse::<InputEntryType>()
+                .map_err(|e| ledger_error_response(&e))?;
+            let exchange_rate = match entry_req.override_exchange_rate.as_deref() {
+                None => None,
+                Some(rate) => match Decimal::from_str(rate) {
+                    // Stored rates have 10 decimal places
+                    Ok(rate) if rate > Decimal::ZERO => Some(rate.round_dp(10)),
+                    _ => {
+                        return Err((
+                            StatusCode::BAD_REQUEST,
+                            Json(json!({
+                                "error": "invalid_exchange_rate",
+                                "message": "Override exchange rate must be a positive number"
+                            })),
+                        )
+                            .into_response());
+                    }
+                },
+            };
+            Ok(LedgerEntryInput {
+                account_id: entry_req.account_id,
+                source_currency: entry_req.source_currency.clone(),
+                source_amount,
+                entry_type,
+                memo: entry_req.memo.clone(),
+                dimensions: entry_req.dimensions.clone(),
+                exchange_rate,
+            })
+        })
+        .collect()
+}
+
+/// Validates a create request and saves it as a draft transaction.
+///
+/// Also used to instantiate transaction templates, so both paths apply the
+/// same exchange-rate, balance, and fiscal period checks.
+#[allow(clippy::too_many_lines)]
+pub(crate) async fn create_draft_transaction(
+    state: &AppState,
+    user_id: Uuid,
+    org_id: OrganizationId,
+    payload: CreateTransactionRequest,
+) -> axum::response::Response {
+    let org_repo = state.stores.organizations.as_ref();
+
+    // Check membership
+    let membership = match check_membership(state, org_id, user_id).await {
+        Ok(membership) => membership,
+        Err(response) => return response,
+    };
+
     // Parse transaction type
     let Some(transaction_type) = string_to_tx_type(&payload.transaction_type) else {
         return (
@@ -416,20 +1082,23 @@ async fn create_transaction(
             .into_response();
     };
 
-    // Validate minimum entries
-    if payload.entries.len() < 2 {
-        return (
-            StatusCode::BAD_REQUEST,
-            Json(json!({
-                "error": "insufficient_entries",
-                "message": "Transaction must have at least 2 entries"
-            })),
-        )
-            .into_response();
-    }
+    let ledger_entries = match parse_entries(&payload.entries, &membership.role) {
+        Ok(entries) => entries,
+        Err(response) => return response,
+    };
+    let mut ledger_input = LedgerTransactionInput {
+        organization_id: org_id.into_inner(),
+        transaction_type: core_tx_type(&transaction_type),
+        transaction_date: payload.transaction_date,
+        description: payload.description.clone(),
+        reference_number: payload.reference_number.clone(),
+        memo: payload.memo.clone(),
+        entries: ledger_entries,
+        created_by: user_id,
+    };
 
     // Get organization's base currency
-    let org = match org_repo.find_by_id(org_id).await {
+    let org = match org_repo.find_by_id(org_id.into_inner()).await {
         Ok(Some(o)) => o,
         Ok(None) => {
             return (
@@ -454,197 +1123,305 @@ async fn create_transaction(
         }
     };
 
-    let functional_currency = org.base_currency;
+    let settings = match OrganizationSettings::from_value(&org.settings) {
+        Ok(settings) => settings,
+        Err(e) => {
+            error!(error = %e, "Stored organization settings are invalid");
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({
+                    "error": "internal_error",
+                    "message": "An error occurred"
+                })),
+            )
+                .into_response();
+        }
+    };
 
-    // Parse and resolve entries
-    let mut entries = Vec::with_capacity(payload.entries.len());
-    let mut total_debit = Decimal::ZERO;
-    let mut total_credit = Decimal::ZERO;
+    // Amounts are kept to their currency's decimal places; strict mode
+    // rejects more precise ones instead of rounding them
+    for entry in &mut ledger_input.entries {
+        let currency = &entry.source_currency;
+        match state
+            .currencies
+            .check_precision(currency, entry.source_amount)
+        {
+            Ok(()) => {}
+            Err(e) if settings.amount_precision.strict => return amount_too_precise_response(&e),
+            Err(_) => {
+                entry.source_amount = state.currencies.round_for(currency, entry.source_amount);
+            }
+        }
+    }
 
-    for entry_req in &payload.entries {
-        // Parse source amount
-        let source_amount = match Decimal::from_str(&entry_req.source_amount) {
-            Ok(a) if a > Decimal::ZERO => a,
-            Ok(_) => {
-                return (
-                    StatusCode::BAD_REQUEST,
-                    Json(json!({
-                        "error": "invalid_amount",
-                        "message": "Amount must be positive"
-                    })),
+    let rate_settings = settings.exchange_rates;
+    let functional_currency = org.base_currency;
+    let rate_repo = state.stores.exchange_rates.as_ref();
+    let mut foreign_rates: BTreeMap<String, ExchangeRateLookup> = BTreeMap::new();
+
+    if ledger_input
+        .entries
+        .iter()
+        .any(|entry| entry.exchange_rate.is_some() && entry.source_currency == functional_currency)
+    {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(json!({
+                "error": "invalid_exchange_rate",
+                "message": format!(
+                    "Exchange rates can only be overridden on entries not in {functional_currency}"
                 )
-                    .into_response();
-            }
-            Err(_) => {
+            })),
+        )
+            .into_response();
+    }
+
+    // Staleness only matters for currencies some entry is booked at the
+    // looked-up rate in
+    let uses_lookup = |currency: &str| {
+        ledger_input
+            .entries
+            .iter()
+            .any(|entry| entry.source_currency == currency && entry.exchange_rate.is_none())
+    };
+
+    // The rate the organization's policy picks, once per currency. Overridden
+    // entries still look it up so the override log shows what was replaced.
+    // A missing rate is left for the ledger service to report.
+    for entry in &ledger_input.entries {
+        let currency = &entry.source_currency;
+        if *currency == functional_currency || foreign_rates.contains_key(currency) {
+            continue;
+        }
+        let lookup = match rate_repo
+            .find_rate(
+                org_id.into_inner(),
+                currency,
+                &functional_currency,
+                payload.transaction_date,
+                rate_settings.lookup_policy,
+            )
+            .await
+        {
+            Ok(lookup) => ExchangeRateLookup {
+                // Stored rates have 10 decimal places; inverted ones may not
+                rate: lookup.rate.round_dp(10),
+                ..lookup
+            },
+            Err(ExchangeRateError::RateNotFound(..)) => continue,
+            Err(e) => {
+                error!(error = %e, "Failed to look up exchange rate");
                 return (
-                    StatusCode::BAD_REQUEST,
+                    StatusCode::INTERNAL_SERVER_ERROR,
                     Json(json!({
-                        "error": "invalid_amount",
-                        "message": "Invalid amount format"
+                        "error": "internal_error",
+                        "message": "An error occurred"
                     })),
                 )
                     .into_response();
             }
         };
 
-        // Get exchange rate (simplified - same currency = 1.0)
-        // TODO: Lookup from exchange_rates table for different currencies
-        let exchange_rate = if entry_req.source_currency == functional_currency {
-            Decimal::ONE
-        } else {
-            // For now, require same currency or return error
-            // In production, lookup from ExchangeRateRepository
+        let rate_used = rate_used_response(
+            currency,
+            &functional_currency,
+            &lookup,
+            rate_settings.max_staleness_days,
+        );
+        if rate_used.stale && rate_settings.strict_staleness && uses_lookup(currency) {
             return (
                 StatusCode::BAD_REQUEST,
                 Json(json!({
-                    "error": "no_exchange_rate",
-                    "message": format!("No exchange rate found for {} to {}", entry_req.source_currency, functional_currency)
+                    "error": "stale_exchange_rate",
+                    "message": stale_rate_message(&rate_used, rate_settings.max_staleness_days),
+                    "rate_used": rate_used
                 })),
             )
                 .into_response();
-        };
+        }
+        foreign_rates.insert(currency.clone(), lookup);
+    }
+
+    let account_ids: Vec<Uuid> = ledger_input.entries.iter().map(|e| e.account_id).collect();
+    let dimension_value_ids: Vec<Uuid> = ledger_input
+        .entries
+        .iter()
+        .flat_map(|e| e.dimensions.iter().copied())
+        .collect();
+    let references = match state
+        .stores
+        .entry_references
+        .find_entry_references(org_id, &account_ids, &dimension_value_ids)
+        .await
+    {
+        Ok(references) => references,
+        Err(e) => {
+            error!(error = %e, "Failed to load entry accounts and dimensions");
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({
+                    "error": "internal_error",
+                    "message": "An error occurred"
+                })),
+            )
+                .into_response();
+        }
+    };
+
+    let (resolved, totals) = match LedgerService::validate_and_resolve_with_precision(
+        &ledger_input,
+        &functional_currency,
+        state.currencies.decimal_places(&functional_currency),
+        |from, _, _| foreign_rates.get(from).map(|lookup| lookup.rate),
+        |id| references.account_info(id),
+        |ids| references.check_dimensions(ids),
+    ) {
+        Ok(resolved) => resolved,
+        Err(e) => return draft_ledger_error_response(&e, &references),
+    };
+
+    // Each source currency must balance on its own in strict mode
+    if settings.currency_balance.strict {
+        let currency_lines: Vec<CurrencyLine> = ledger_input
+            .entries
+            .iter()
+            .map(|entry| CurrencyLine {
+                account_id: entry.account_id,
+                source_currency: entry.source_currency.clone(),
+                entry_type: entry.entry_type.into(),
+                source_amount: entry.source_amount,
+            })
+            .collect();
+        if let Err(imbalances) = validate_currency_balance(
+            &currency_lines,
+            settings.currency_balance.fx_clearing_account_id,
+        ) {
+            let currencies: Vec<&str> = imbalances.iter().map(|i| i.currency.as_str()).collect();
+            return (
+                StatusCode::BAD_REQUEST,
+                Json(json!({
+                    "error": "unbalanced_currency",
+                    "message": format!("Transaction is not balanced in {}", currencies.join(", ")),
+                    "imbalances": imbalances
+                })),
+            )
+                .into_response();
+        }
+    }
+
+    let rate_date = rate_settings
+        .lookup_policy
+        .rate_date(payload.transaction_date);
+    let entries: Vec<CreateLedgerEntryInput> = resolved
+        .into_iter()
+        .zip(&payload.entries)
+        .map(|(entry, entry_req)| {
+            let rate_override =
+                entry_req
+                    .override_exchange_rate
+                    .as_ref()
+                    .map(|_| RateOverrideInput {
+                        rate_date,
+                        looked_up_rate: foreign_rates
+                            .get(&entry.source_currency)
+                            .map(|lookup| lookup.rate),
+                    });
+            CreateLedgerEntryInput {
+                account_id: entry.account_id,
+                source_currency: entry.source_currency,
+                source_amount: entry.source_amount,
+                exchange_rate: entry.exchange_rate,
+                functional_currency: entry.functional_currency,
+                functional_amount: entry.functional_amount,
+                debit: entry.debit,
+                credit: entry.credit,
+                memo: entry.memo,
+                dimensions: entry.dimensions,
+                rate_override,
+                event_at: entry_req.event_at,
+            }
+        })
+        .collect();
+
+    let warnings: Vec<TransactionWarning> = foreign_rates
+        .iter()
+        .filter(|(currency, _)| uses_lookup(currency))
+        .map(|(currency, lookup)| {
+            rate_used_response(
+                currency,
+                &functional_currency,
+                lookup,
+                rate_settings.max_staleness_days,
+            )
+        })
+        .filter(|rate_used| rate_used.stale)
+        .map(|rate_used| TransactionWarning {
+            code: "stale_exchange_rate".to_string(),
+            message: stale_rate_message(&rate_used, rate_settings.max_staleness_days),
+            rate_used,
+        })
+        .collect();
 
-        let functional_amount = source_amount * exchange_rate;
+    let tx_repo = state.stores.transactions.as_ref();
 
-        // Determine debit/credit
-        let (debit, credit) = match entry_req.entry_type.to_lowercase().as_str() {
-            "debit" => (functional_amount, Decimal::ZERO),
-            "credit" => (Decimal::ZERO, functional_amount),
-            _ => {
+    if !payload.allow_duplicate {
+        match tx_repo
+            .find_possible_duplicates(
+                org_id,
+                payload.transaction_date,
+                &entries,
+                settings.duplicate_detection.window_days,
+            )
+            .await
+        {
+            Ok(duplicates) if duplicates.is_empty() => {}
+            Ok(duplicates) => return possible_duplicate_response(&duplicates),
+            Err(e) => {
+                error!(error = %e, "Failed to check for duplicate transactions");
                 return (
-                    StatusCode::BAD_REQUEST,
+                    StatusCode::INTERNAL_SERVER_ERROR,
                     Json(json!({
-                        "error": "invalid_entry_type",
-                        "message": "Entry type must be 'debit' or 'credit'"
+                        "error": "internal_error",
+                        "message": "An error occurred"
                     })),
                 )
                     .into_response();
             }
-        };
-
-        total_debit += debit;
-        total_credit += credit;
-
-        entries.push(CreateLedgerEntryInput {
-            account_id: entry_req.account_id,
-            source_currency: entry_req.source_currency.clone(),
-            source_amount,
-            exchange_rate,
-            functional_currency: functional_currency.clone(),
-            functional_amount,
-            debit,
-            credit,
-            memo: entry_req.memo.clone(),
-            dimensions: entry_req.dimensions.clone(),
-        });
-    }
-
-    // Validate balance (debits must equal credits)
-    if total_debit != total_credit {
-        return (
-            StatusCode::BAD_REQUEST,
-            Json(json!({
-                "error": "unbalanced_transaction",
-                "message": format!("Transaction is not balanced. Debit: {}, Credit: {}", total_debit, total_credit)
-            })),
-        )
-            .into_response();
+        }
     }
 
-    let tx_repo = TransactionRepository::new((*state.db).clone());
-
     let input = CreateTransactionInput {
-        organization_id: org_id,
+        organization_id: org_id.into_inner(),
         transaction_type,
         transaction_date: payload.transaction_date,
-        description: payload.description,
-        reference_number: payload.reference_number,
-        memo: payload.memo,
+        description: ledger_input.description,
+        reference_number: ledger_input.reference_number,
+        memo: ledger_input.memo,
         entries,
-        created_by: auth.user_id(),
+        created_by: user_id,
     };
 
     match tx_repo.create_transaction(input).await {
         Ok(result) => {
-            info!(
-                org_id = %org_id,
-                transaction_id = %result.transaction.id,
-                "Transaction created"
-            );
-
-            let entry_responses: Vec<EntryResponse> = result
-                .entries
-                .into_iter()
-                .map(|e| EntryResponse {
-                    id: e.entry.id,
-                    account_id: e.entry.account_id,
-                    source_currency: e.entry.source_currency,
-                    source_amount: e.entry.source_amount.to_string(),
-                    exchange_rate: e.entry.exchange_rate.to_string(),
-                    functional_currency: e.entry.functional_currency,
-                    functional_amount: e.entry.functional_amount.to_string(),
-                    debit: e.entry.debit.to_string(),
-                    credit: e.entry.credit.to_string(),
-                    memo: e.entry.memo,
-                    dimensions: e.dimensions,
-                })
-                .collect();
+            info!(transaction_id = %result.transaction.id, "Transaction created");
 
+            let source_totals = source_totals_response(tx_repo, &result.entries).await;
+            // Nothing can be applied to a draft yet
+            let settlement_status = is_settleable(&result.transaction.transaction_type)
+                .then_some(SettlementStatus::Open);
             let response = TransactionResponse {
-                id: result.transaction.id,
-                reference_number: result.transaction.reference_number,
-                transaction_type: tx_type_to_string(&result.transaction.transaction_type),
-                transaction_date: result.transaction.transaction_date.to_string(),
-                description: result.transaction.description,
-                memo: result.transaction.memo,
-                status: status_to_string(&result.transaction.status),
-                fiscal_period_id: result.transaction.fiscal_period_id,
-                created_by: result.transaction.created_by,
-                created_at: result.transaction.created_at.to_rfc3339(),
-                updated_at: result.transaction.updated_at.to_rfc3339(),
-                entries: entry_responses,
-                total_debit: total_debit.to_string(),
-                total_credit: total_credit.to_string(),
+                total_debit: totals.functional_debit.to_string(),
+                total_credit: totals.functional_credit.to_string(),
+                settlement_status,
+                warnings,
+                ..TransactionResponse::new(result, source_totals)
             };
 
             (StatusCode::CREATED, Json(response)).into_response()
         }
         Err(e) => {
             error!(error = %e, "Failed to create transaction");
-            match e {
-                zeltra_db::repositories::transaction::TransactionError::NoFiscalPeriod(date) => (
-                    StatusCode::BAD_REQUEST,
-                    Json(json!({
-                        "error": "no_fiscal_period",
-                        "message": format!("No fiscal period found for date {}", date)
-                    })),
-                )
-                    .into_response(),
-                zeltra_db::repositories::transaction::TransactionError::PeriodClosed => (
-                    StatusCode::BAD_REQUEST,
-                    Json(json!({
-                        "error": "period_closed",
-                        "message": "Fiscal period is closed, no posting allowed"
-                    })),
-                )
-                    .into_response(),
-                zeltra_db::repositories::transaction::TransactionError::AccountNotFound(id) => (
-                    StatusCode::BAD_REQUEST,
-                    Json(json!({
-                        "error": "account_not_found",
-                        "message": format!("Account not found: {}", id)
-                    })),
-                )
-                    .into_response(),
-                _ => (
-                    StatusCode::INTERNAL_SERVER_ERROR,
-                    Json(json!({
-                        "error": "internal_error",
-                        "message": "An error occurred"
-                    })),
-                )
-                    .into_response(),
-            }
+            create_error_response(&e)
         }
     }
 }
@@ -654,60 +1431,68 @@ async fn create_transaction(
 /// Requirements: 10.3
 async fn get_transaction(
     State(state): State<AppState>,
-    auth: AuthUser,
-    Path((org_id, transaction_id)): Path<(Uuid, Uuid)>,
+    auth: AuthMember,
+    Path((org_id, transaction_id)): Path<(OrganizationId, TransactionId)>,
 ) -> impl IntoResponse {
-    let org_repo = OrganizationRepository::new((*state.db).clone());
-
-    // Check membership
-    if let Err(response) = check_membership(&org_repo, org_id, auth.user_id()).await {
-        return response;
-    }
+    let role = match auth.role_in(&state, org_id).await {
+        Ok(role) => role,
+        Err(response) => return response,
+    };
 
-    let tx_repo = TransactionRepository::new((*state.db).clone());
+    let tx_repo = state.stores.transactions.as_ref();
 
     match tx_repo.get_transaction(org_id, transaction_id).await {
+        Ok(result)
+            if !role.can_view_all_transactions()
+                && result.transaction.created_by != auth.user_id() =>
+        {
+            (
+                StatusCode::FORBIDDEN,
+                Json(json!({
+                    "error": "transaction_access_restricted",
+                    "message": "Submitters can only view transactions they created"
+                })),
+            )
+                .into_response()
+        }
         Ok(result) => {
-            // Calculate totals
             let total_debit: Decimal = result.entries.iter().map(|e| e.entry.debit).sum();
-            let total_credit: Decimal = result.entries.iter().map(|e| e.entry.credit).sum();
+            let source_totals = source_totals_response(tx_repo, &result.entries).await;
 
-            let entry_responses: Vec<EntryResponse> = result
-                .entries
-                .into_iter()
-                .map(|e| EntryResponse {
-                    id: e.entry.id,
-                    account_id: e.entry.account_id,
-                    source_currency: e.entry.source_currency,
-                    source_amount: e.entry.source_amount.to_string(),
-                    exchange_rate: e.entry.exchange_rate.to_string(),
-                    functional_currency: e.entry.functional_currency,
-                    functional_amount: e.entry.functional_amount.to_string(),
-                    debit: e.entry.debit.to_string(),
-                    credit: e.entry.credit.to_string(),
-                    memo: e.entry.memo,
-                    dimensions: e.dimensions,
-                })
-                .collect();
+            let settlement_status = if is_settleable(&result.transaction.transaction_type) {
+                match state
+                    .stores
+                    .payments
+                    .settled_amount(org_id, transaction_id)
+                    .await
+                {
+                    Ok(applied) => Some(SettlementStatus::from_amounts(total_debit, applied)),
+                    Err(e) => {
+                        error!(error = %e, "Failed to load settled amount");
+                        return (
+                            StatusCode::INTERNAL_SERVER_ERROR,
+                            Json(json!({
+                                "error": "internal_error",
+                                "message": "An error occurred"
+                            })),
+                        )
+                            .into_response();
+                    }
+                }
+            } else {
+                None
+            };
 
+            let updated_at = result.transaction.updated_at;
             let response = TransactionResponse {
-                id: result.transaction.id,
-                reference_number: result.transaction.reference_number,
-                transaction_type: tx_type_to_string(&result.transaction.transaction_type),
-                transaction_date: result.transaction.transaction_date.to_string(),
-                description: result.transaction.description,
-                memo: result.transaction.memo,
-                status: status_to_string(&result.transaction.status),
-                fiscal_period_id: result.transaction.fiscal_period_id,
-                created_by: result.transaction.created_by,
-                created_at: result.transaction.created_at.to_rfc3339(),
-                updated_at: result.transaction.updated_at.to_rfc3339(),
-                entries: entry_responses,
-                total_debit: total_debit.to_string(),
-                total_credit: total_credit.to_string(),
+                settlement_status,
+                ..TransactionResponse::new(result, source_totals)
             };
 
-            (StatusCode::OK, Json(response)).into_response()
+            with_last_modified(
+                (StatusCode::OK, Json(response)).into_response(),
+                &updated_at,
+            )
         }
         Err(e) => {
             error!(error = %e, "Failed to get transaction");
@@ -739,34 +1524,30 @@ async fn get_transaction(
 async fn update_transaction(
     State(state): State<AppState>,
     auth: AuthUser,
-    Path((org_id, transaction_id)): Path<(Uuid, Uuid)>,
-    Json(payload): Json<UpdateTransactionRequest>,
+    Path((org_id, transaction_id)): Path<(OrganizationId, TransactionId)>,
+    StrictJson(payload): StrictJson<UpdateTransactionRequest>,
 ) -> impl IntoResponse {
-    let org_repo = OrganizationRepository::new((*state.db).clone());
-
     // Check membership
-    if let Err(response) = check_membership(&org_repo, org_id, auth.user_id()).await {
+    if let Err(response) = check_membership(&state, org_id, auth.user_id()).await {
         return response;
     }
 
-    let tx_repo = TransactionRepository::new((*state.db).clone());
+    let tx_repo = state.stores.transactions.as_ref();
+
+    let input = UpdateTransactionInput {
+        description: payload.description,
+        memo: payload.memo,
+        reference_number: payload.reference_number,
+        transaction_date: payload.transaction_date,
+        updated_by: auth.user_id(),
+    };
 
     match tx_repo
-        .update_transaction(
-            org_id,
-            transaction_id,
-            payload.description,
-            payload.memo,
-            payload.reference_number,
-        )
+        .update_transaction(org_id, transaction_id, input)
         .await
     {
         Ok(transaction) => {
-            info!(
-                org_id = %org_id,
-                transaction_id = %transaction_id,
-                "Transaction updated"
-            );
+            info!(transaction_id = %transaction_id, "Transaction updated");
 
             (
                 StatusCode::OK,
@@ -775,6 +1556,7 @@ async fn update_transaction(
                     "reference_number": transaction.reference_number,
                     "type": tx_type_to_string(&transaction.transaction_type),
                     "transaction_date": transaction.transaction_date.to_string(),
+                    "fiscal_period_id": transaction.fiscal_period_id,
                     "description": transaction.description,
                     "memo": transaction.memo,
                     "status": status_to_string(&transaction.status),
@@ -785,41 +1567,189 @@ async fn update_transaction(
         }
         Err(e) => {
             error!(error = %e, "Failed to update transaction");
-            match e {
-                zeltra_db::repositories::transaction::TransactionError::NotFound(_) => (
-                    StatusCode::NOT_FOUND,
-                    Json(json!({
-                        "error": "not_found",
-                        "message": "Transaction not found"
-                    })),
-                )
-                    .into_response(),
-                zeltra_db::repositories::transaction::TransactionError::CannotModifyPosted => (
-                    StatusCode::BAD_REQUEST,
-                    Json(json!({
-                        "error": "cannot_modify_posted",
-                        "message": "Cannot modify posted transaction"
-                    })),
-                )
-                    .into_response(),
-                zeltra_db::repositories::transaction::TransactionError::CannotModifyVoided => (
-                    StatusCode::BAD_REQUEST,
-                    Json(json!({
-                        "error": "cannot_modify_voided",
-                        "message": "Cannot modify voided transaction"
-                    })),
-                )
-                    .into_response(),
-                _ => (
-                    StatusCode::INTERNAL_SERVER_ERROR,
-                    Json(json!({
-                        "error": "internal_error",
-                        "message": "An error occurred"
-                    })),
-                )
-                    .into_response(),
-            }
+            update_error_response(&e)
+        }
+    }
+}
+
+/// Maps an entry rejected because of its account to 400, naming the account
+/// code. Returns `None` for other errors.
+pub(crate) fn entry_account_error_response(
+    error: &TransactionError,
+) -> Option<axum::response::Response> {
+    let (code, message) = match error {
+        TransactionError::AccountInactive(account) => {
+            ("account_inactive", format!("Account {account} is inactive"))
+        }
+        TransactionError::DirectPostingNotAllowed(account) => (
+            "direct_posting_not_allowed",
+            format!("Account {account} does not allow direct posting"),
+        ),
+        TransactionError::CurrencyMismatch { .. } => ("currency_mismatch", error.to_string()),
+        _ => return None,
+    };
+    Some(
+        (
+            StatusCode::BAD_REQUEST,
+            Json(json!({
+                "error": code,
+                "message": message
+            })),
+        )
+            .into_response(),
+    )
+}
+
+/// Maps a transaction creation error to an HTTP response.
+fn create_error_response(error: &TransactionError) -> axum::response::Response {
+    if let Some(response) = entry_account_error_response(error) {
+        return response;
+    }
+    let code = match error {
+        TransactionError::AccountNotFound(_) => "account_not_found",
+        TransactionError::EventOutsideTransactionDate { .. } => "invalid_event_at",
+        _ => return update_error_response(error),
+    };
+    (
+        StatusCode::BAD_REQUEST,
+        Json(json!({
+            "error": code,
+            "message": error.to_string()
+        })),
+    )
+        .into_response()
+}
+
+/// Maps a ledger validation error on a new transaction to an HTTP response.
+/// Account errors are reported as the repository reports them, naming the
+/// account code.
+fn draft_ledger_error_response(
+    error: &LedgerError,
+    references: &EntryReferences,
+) -> axum::response::Response {
+    let error = match *error {
+        LedgerError::AccountNotFound(id) => TransactionError::AccountNotFound(id),
+        LedgerError::AccountInactive(id) => {
+            TransactionError::AccountInactive(references.account_code(id))
         }
+        LedgerError::AccountNoDirectPosting(id) => {
+            TransactionError::DirectPostingNotAllowed(references.account_code(id))
+        }
+        _ => return ledger_error_response(error),
+    };
+    create_error_response(&error)
+}
+
+/// Maps a ledger validation error to an HTTP response.
+fn ledger_error_response(error: &LedgerError) -> axum::response::Response {
+    let code = match error {
+        LedgerError::ZeroAmount => "zero_amount_not_allowed".to_string(),
+        LedgerError::AccountNoDirectPosting(_) => "direct_posting_not_allowed".to_string(),
+        _ => error.error_code().to_ascii_lowercase(),
+    };
+    let status = StatusCode::from_u16(error.http_status_code()).unwrap_or(StatusCode::BAD_REQUEST);
+    if status.is_server_error() {
+        error!(error = %error, "Failed to validate transaction");
+        return (
+            status,
+            Json(json!({
+                "error": "internal_error",
+                "message": "An error occurred"
+            })),
+        )
+            .into_response();
+    }
+    (
+        status,
+        Json(json!({
+            "error": code,
+            "message": error.to_string()
+        })),
+    )
+        .into_response()
+}
+
+/// 409 for a transaction that matches existing ones, listing their IDs.
+fn possible_duplicate_response(duplicates: &[Uuid]) -> axum::response::Response {
+    (
+        StatusCode::CONFLICT,
+        Json(json!({
+            "error": "possible_duplicate",
+            "message": "A transaction with the same date, accounts and amounts already exists; \
+                        resend with allow_duplicate to create it anyway",
+            "duplicate_transaction_ids": duplicates
+        })),
+    )
+        .into_response()
+}
+
+/// Maps a transaction update error to an HTTP response.
+pub(crate) fn update_error_response(error: &TransactionError) -> axum::response::Response {
+    match error {
+        TransactionError::NotFound(_) => (
+            StatusCode::NOT_FOUND,
+            Json(json!({
+                "error": "not_found",
+                "message": "Transaction not found"
+            })),
+        )
+            .into_response(),
+        TransactionError::CannotModifyPosted => (
+            StatusCode::BAD_REQUEST,
+            Json(json!({
+                "error": "cannot_modify_posted",
+                "message": "Cannot modify posted transaction"
+            })),
+        )
+            .into_response(),
+        TransactionError::CannotModifyVoided => (
+            StatusCode::BAD_REQUEST,
+            Json(json!({
+                "error": "cannot_modify_voided",
+                "message": "Cannot modify voided transaction"
+            })),
+        )
+            .into_response(),
+        TransactionError::NoFiscalPeriod(date) => (
+            StatusCode::BAD_REQUEST,
+            Json(json!({
+                "error": "no_fiscal_period",
+                "message": format!("No fiscal period found for date {}", date)
+            })),
+        )
+            .into_response(),
+        TransactionError::PeriodClosed => (
+            StatusCode::BAD_REQUEST,
+            Json(json!({
+                "error": "period_closed",
+                "message": "Fiscal period is closed, no posting allowed"
+            })),
+        )
+            .into_response(),
+        TransactionError::PeriodSoftClosed => (
+            StatusCode::FORBIDDEN,
+            Json(json!({
+                "error": "period_soft_closed",
+                "message": "Fiscal period is soft-closed, only accountants can post"
+            })),
+        )
+            .into_response(),
+        TransactionError::NotDraft => (
+            StatusCode::CONFLICT,
+            Json(json!({
+                "error": "not_draft",
+                "message": "Only draft transactions can be changed this way"
+            })),
+        )
+            .into_response(),
+        _ => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({
+                "error": "internal_error",
+                "message": "An error occurred"
+            })),
+        )
+            .into_response(),
     }
 }
 
@@ -829,54 +1759,33 @@ async fn update_transaction(
 async fn delete_transaction(
     State(state): State<AppState>,
     auth: AuthUser,
-    Path((org_id, transaction_id)): Path<(Uuid, Uuid)>,
+    Path((org_id, transaction_id)): Path<(OrganizationId, TransactionId)>,
 ) -> impl IntoResponse {
-    let org_repo = OrganizationRepository::new((*state.db).clone());
-
     // Check membership
-    if let Err(response) = check_membership(&org_repo, org_id, auth.user_id()).await {
+    if let Err(response) = check_membership(&state, org_id, auth.user_id()).await {
         return response;
     }
 
-    let tx_repo = TransactionRepository::new((*state.db).clone());
+    let tx_repo = state.stores.transactions.as_ref();
 
     match tx_repo.delete_transaction(org_id, transaction_id).await {
         Ok(()) => {
-            info!(
-                org_id = %org_id,
-                transaction_id = %transaction_id,
-                "Transaction deleted"
-            );
+            info!(transaction_id = %transaction_id, "Transaction deleted");
 
             (StatusCode::NO_CONTENT, ()).into_response()
         }
         Err(e) => {
             error!(error = %e, "Failed to delete transaction");
             match e {
-                zeltra_db::repositories::transaction::TransactionError::NotFound(_) => (
-                    StatusCode::NOT_FOUND,
-                    Json(json!({
-                        "error": "not_found",
-                        "message": "Transaction not found"
-                    })),
-                )
-                    .into_response(),
-                zeltra_db::repositories::transaction::TransactionError::CanOnlyDeleteDraft => (
-                    StatusCode::BAD_REQUEST,
+                TransactionError::CanOnlyDeleteDraft => (
+                    StatusCode::BAD_REQUEST,
                     Json(json!({
                         "error": "can_only_delete_draft",
                         "message": "Can only delete draft transactions"
                     })),
                 )
                     .into_response(),
-                _ => (
-                    StatusCode::INTERNAL_SERVER_ERROR,
-                    Json(json!({
-                        "error": "internal_error",
-                        "message": "An error occurred"
-                    })),
-                )
-                    .into_response(),
+                _ => update_error_response(&e),
             }
         }
     }
@@ -892,42 +1801,63 @@ async fn delete_transaction(
 async fn submit_transaction(
     State(state): State<AppState>,
     auth: AuthUser,
-    Path((org_id, transaction_id)): Path<(Uuid, Uuid)>,
+    Path((org_id, transaction_id)): Path<(OrganizationId, TransactionId)>,
 ) -> impl IntoResponse {
-    let org_repo = OrganizationRepository::new((*state.db).clone());
-
-    if let Err(response) = check_membership(&org_repo, org_id, auth.user_id()).await {
+    if let Err(response) = check_membership(&state, org_id, auth.user_id()).await {
         return response;
     }
 
-    let workflow_repo = WorkflowRepository::new((*state.db).clone());
+    let (overruns, threshold_percent) = match check_budgets(&state, org_id, transaction_id).await {
+        Ok(checked) => checked,
+        Err(response) => return response,
+    };
+
+    let workflow_repo = state.stores.workflow.as_ref();
 
     match workflow_repo
         .submit_transaction(org_id, transaction_id, auth.user_id())
         .await
     {
         Ok(transaction) => {
-            info!(
-                org_id = %org_id,
-                transaction_id = %transaction_id,
-                "Transaction submitted for approval"
-            );
+            state.dashboard_cache.invalidate(org_id.into_inner()).await;
+            info!(transaction_id = %transaction_id, "Transaction submitted for approval");
+            notifications::notify_approval_requested(&state, org_id, &transaction).await;
+
+            let budget_warning = !overruns.is_empty();
+            if let Err(e) = BudgetRepository::new((*state.db).clone())
+                .record_budget_warning(org_id.into_inner(), transaction.id, budget_warning)
+                .await
+            {
+                error!(error = %e, "Failed to record budget warning");
+            }
+            let warnings: Vec<BudgetWarningResponse> = overruns
+                .iter()
+                .map(|overrun| BudgetWarningResponse {
+                    code: "budget_threshold_exceeded".to_string(),
+                    message: budget_overrun_message(overrun, threshold_percent),
+                    budget_line: overrun.into(),
+                })
+                .collect();
 
             let submitted_at = transaction
                 .submitted_at
                 .as_ref()
                 .map(chrono::DateTime::to_rfc3339);
 
-            (
-                StatusCode::OK,
-                Json(json!({
-                    "id": transaction.id,
-                    "status": status_to_string(&transaction.status),
-                    "submitted_at": submitted_at,
-                    "submitted_by": transaction.submitted_by
-                })),
-            )
-                .into_response()
+            let mut body = json!({
+                "id": transaction.id,
+                "status": status_to_string(&transaction.status),
+                "submitted_at": submitted_at,
+                "submitted_by": transaction.submitted_by,
+                "approval_requirement": ApprovalRequirement::snapshot(&transaction)
+                    .map(ApprovalRequirementResponse::from),
+                "budget_warning": budget_warning
+            });
+            if !warnings.is_empty() {
+                body["warnings"] = json!(warnings);
+            }
+
+            (StatusCode::OK, Json(body)).into_response()
         }
         Err(e) => {
             error!(error = %e, "Failed to submit transaction");
@@ -936,33 +1866,115 @@ async fn submit_transaction(
     }
 }
 
+/// Checks a transaction about to be submitted against the organization's
+/// budgets.
+///
+/// Returns the budget lines it takes past the threshold, none when budget
+/// enforcement is off, with the threshold applied. With strict enforcement
+/// any such line fails the submission with 409 `budget_exceeded`.
+async fn check_budgets(
+    state: &AppState,
+    org_id: OrganizationId,
+    transaction_id: TransactionId,
+) -> Result<(Vec<BudgetOverrun>, u32), axum::response::Response> {
+    let internal_error = || {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({
+                "error": "internal_error",
+                "message": "An error occurred"
+            })),
+        )
+            .into_response()
+    };
+    let enforcement = OrganizationRepository::new((*state.db).clone())
+        .get_settings(org_id.into_inner())
+        .await
+        .map_err(|e| {
+            error!(error = %e, "Failed to load organization settings");
+            internal_error()
+        })?
+        .budget_enforcement;
+    if !enforcement.enabled {
+        return Ok((vec![], enforcement.threshold_percent));
+    }
+
+    let overruns = BudgetRepository::new((*state.db).clone())
+        .check_transaction_budgets(
+            org_id.into_inner(),
+            transaction_id.into_inner(),
+            enforcement.threshold_percent,
+        )
+        .await
+        .map_err(|e| {
+            error!(error = %e, "Failed to check transaction against budgets");
+            internal_error()
+        })?;
+    if enforcement.strict && !overruns.is_empty() {
+        return Err((
+            StatusCode::CONFLICT,
+            Json(json!({
+                "error": "budget_exceeded",
+                "message": format!(
+                    "Transaction would take {} budget line(s) past {}% of budget",
+                    overruns.len(),
+                    enforcement.threshold_percent
+                ),
+                "budget_lines": overruns
+                    .iter()
+                    .map(BudgetLineImpactResponse::from)
+                    .collect::<Vec<_>>()
+            })),
+        )
+            .into_response());
+    }
+    Ok((overruns, enforcement.threshold_percent))
+}
+
+/// Message for a budget line a transaction takes past the threshold.
+fn budget_overrun_message(overrun: &BudgetOverrun, threshold_percent: u32) -> String {
+    let utilization = overrun.utilization.percent().map_or_else(
+        || "over its zero budget".to_string(),
+        |percent| format!("at {percent}% of budget"),
+    );
+    format!(
+        "Budget {} for account {} in {} would be {utilization}, past the {threshold_percent}% threshold",
+        overrun.budget_name, overrun.account_code, overrun.period_name
+    )
+}
+
 /// POST `/organizations/{org_id}/transactions/{transaction_id}/approve` - Approve transaction.
 ///
+/// When the matched rule requires more approvals than have been recorded,
+/// the transaction stays pending and the response reports the progress.
+///
 /// Requirements: 6.2
 async fn approve_transaction(
     State(state): State<AppState>,
     auth: AuthUser,
-    Path((org_id, transaction_id)): Path<(Uuid, Uuid)>,
-    payload: Option<Json<ApproveRequest>>,
+    Path((org_id, transaction_id)): Path<(OrganizationId, TransactionId)>,
+    payload: Option<StrictJson<ApproveRequest>>,
 ) -> impl IntoResponse {
-    let org_repo = OrganizationRepository::new((*state.db).clone());
-
-    if let Err(response) = check_membership(&org_repo, org_id, auth.user_id()).await {
+    if let Err(response) = check_membership(&state, org_id, auth.user_id()).await {
         return response;
     }
 
-    let approval_notes = payload.and_then(|p| p.approval_notes.clone());
-    let workflow_repo = WorkflowRepository::new((*state.db).clone());
+    let approval_notes = payload.and_then(|StrictJson(p)| p.approval_notes);
+    let workflow_repo = state.stores.workflow.as_ref();
 
     match workflow_repo
         .approve_transaction(org_id, transaction_id, auth.user_id(), approval_notes)
         .await
     {
-        Ok(transaction) => {
+        Ok(ApprovalOutcome {
+            transaction,
+            progress,
+        }) => {
+            state.dashboard_cache.invalidate(org_id.into_inner()).await;
             info!(
-                org_id = %org_id,
                 transaction_id = %transaction_id,
-                "Transaction approved"
+                approvals = %progress,
+                "Transaction approval recorded"
             );
 
             let approved_at = transaction
@@ -977,7 +1989,10 @@ async fn approve_transaction(
                     "status": status_to_string(&transaction.status),
                     "approved_at": approved_at,
                     "approved_by": transaction.approved_by,
-                    "approval_notes": transaction.approval_notes
+                    "approved_on_behalf_of": transaction.approved_on_behalf_of,
+                    "approval_notes": transaction.approval_notes,
+                    "approvals": progress.approvals,
+                    "required_approvals": progress.required
                 })),
             )
                 .into_response()
@@ -995,12 +2010,10 @@ async fn approve_transaction(
 async fn reject_transaction(
     State(state): State<AppState>,
     auth: AuthUser,
-    Path((org_id, transaction_id)): Path<(Uuid, Uuid)>,
-    Json(payload): Json<RejectRequest>,
+    Path((org_id, transaction_id)): Path<(OrganizationId, TransactionId)>,
+    StrictJson(payload): StrictJson<RejectRequest>,
 ) -> impl IntoResponse {
-    let org_repo = OrganizationRepository::new((*state.db).clone());
-
-    if let Err(response) = check_membership(&org_repo, org_id, auth.user_id()).await {
+    if let Err(response) = check_membership(&state, org_id, auth.user_id()).await {
         return response;
     }
 
@@ -1015,18 +2028,24 @@ async fn reject_transaction(
             .into_response();
     }
 
-    let workflow_repo = WorkflowRepository::new((*state.db).clone());
+    let workflow_repo = state.stores.workflow.as_ref();
+    let reason = payload.reason.clone();
 
     match workflow_repo
-        .reject_transaction(org_id, transaction_id, payload.reason)
+        .reject_transaction(org_id, transaction_id, auth.user_id(), payload.reason)
         .await
     {
         Ok(transaction) => {
-            info!(
-                org_id = %org_id,
-                transaction_id = %transaction_id,
-                "Transaction rejected"
-            );
+            state.dashboard_cache.invalidate(org_id.into_inner()).await;
+            info!(transaction_id = %transaction_id, "Transaction rejected");
+            notifications::notify_transaction_rejected(
+                &state,
+                org_id,
+                &transaction,
+                auth.user_id(),
+                &reason,
+            )
+            .await;
 
             (
                 StatusCode::OK,
@@ -1045,32 +2064,80 @@ async fn reject_transaction(
     }
 }
 
+/// POST `/organizations/{org_id}/transactions/{transaction_id}/claim` - Claim for review.
+///
+/// Marks the caller as reviewing the pending transaction for the next ten
+/// minutes. Claiming again renews the claim.
+async fn claim_transaction(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path((org_id, transaction_id)): Path<(OrganizationId, TransactionId)>,
+) -> impl IntoResponse {
+    if let Err(response) = check_membership(&state, org_id, auth.user_id()).await {
+        return response;
+    }
+
+    match state
+        .stores
+        .workflow
+        .claim_transaction(org_id, transaction_id, auth.user_id())
+        .await
+    {
+        Ok(claim) => (StatusCode::OK, Json(ClaimResponse::from(claim))).into_response(),
+        Err(e) => {
+            error!(error = %e, "Failed to claim transaction");
+            workflow_error_response(e)
+        }
+    }
+}
+
+/// DELETE `/organizations/{org_id}/transactions/{transaction_id}/claim` - Release a claim.
+///
+/// The reviewer, an owner or an admin may release an active claim.
+async fn release_claim(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path((org_id, transaction_id)): Path<(OrganizationId, TransactionId)>,
+) -> impl IntoResponse {
+    if let Err(response) = check_membership(&state, org_id, auth.user_id()).await {
+        return response;
+    }
+
+    match state
+        .stores
+        .workflow
+        .release_claim(org_id, transaction_id, auth.user_id())
+        .await
+    {
+        Ok(()) => (StatusCode::NO_CONTENT, ()).into_response(),
+        Err(e) => {
+            error!(error = %e, "Failed to release transaction claim");
+            workflow_error_response(e)
+        }
+    }
+}
+
 /// POST `/organizations/{org_id}/transactions/{transaction_id}/post` - Post to ledger.
 ///
 /// Requirements: 6.4
 async fn post_transaction(
     State(state): State<AppState>,
     auth: AuthUser,
-    Path((org_id, transaction_id)): Path<(Uuid, Uuid)>,
+    Path((org_id, transaction_id)): Path<(OrganizationId, TransactionId)>,
 ) -> impl IntoResponse {
-    let org_repo = OrganizationRepository::new((*state.db).clone());
-
-    if let Err(response) = check_membership(&org_repo, org_id, auth.user_id()).await {
+    if let Err(response) = check_membership(&state, org_id, auth.user_id()).await {
         return response;
     }
 
-    let workflow_repo = WorkflowRepository::new((*state.db).clone());
+    let workflow_repo = state.stores.workflow.as_ref();
 
     match workflow_repo
         .post_transaction(org_id, transaction_id, auth.user_id())
         .await
     {
         Ok(transaction) => {
-            info!(
-                org_id = %org_id,
-                transaction_id = %transaction_id,
-                "Transaction posted"
-            );
+            state.dashboard_cache.invalidate(org_id.into_inner()).await;
+            info!(transaction_id = %transaction_id, "Transaction posted");
 
             let posted_at = transaction
                 .posted_at
@@ -1098,43 +2165,99 @@ async fn post_transaction(
 /// POST `/organizations/{org_id}/transactions/{transaction_id}/void` - Void transaction.
 ///
 /// Requirements: 6.5
+#[allow(clippy::too_many_lines)]
 async fn void_transaction(
     State(state): State<AppState>,
     auth: AuthUser,
-    Path((org_id, transaction_id)): Path<(Uuid, Uuid)>,
-    Json(payload): Json<VoidRequest>,
+    Path((org_id, transaction_id)): Path<(OrganizationId, TransactionId)>,
+    StrictJson(payload): StrictJson<VoidRequest>,
 ) -> impl IntoResponse {
-    let org_repo = OrganizationRepository::new((*state.db).clone());
-
-    if let Err(response) = check_membership(&org_repo, org_id, auth.user_id()).await {
+    if let Err(response) = check_membership(&state, org_id, auth.user_id()).await {
         return response;
     }
 
-    if payload.reason.trim().is_empty() {
+    if payload.reason_code.requires_text() && payload.reason.trim().is_empty() {
         return (
             StatusCode::BAD_REQUEST,
             Json(json!({
                 "error": "void_reason_required",
-                "message": "Void reason is required"
+                "message": "Void reason is required when reason_code is other"
             })),
         )
             .into_response();
     }
 
-    let workflow_repo = WorkflowRepository::new((*state.db).clone());
+    // Check the attachment target before voiding, so a bad target leaves
+    // the transaction untouched
+    let attachment_repo = AttachmentRepository::new((*state.db).clone());
+    if let Some(target) = payload.move_attachments_to {
+        if target == transaction_id.into_inner() {
+            return (
+                StatusCode::BAD_REQUEST,
+                Json(json!({
+                    "error": "invalid_move_target",
+                    "message": "Attachments cannot be moved to the transaction being voided"
+                })),
+            )
+                .into_response();
+        }
+        if let Err(e) = attachment_repo
+            .check_move_target(org_id.into_inner(), target)
+            .await
+        {
+            return move_error_response(&e);
+        }
+    }
+
+    let workflow_repo = state.stores.workflow.as_ref();
 
     match workflow_repo
-        .void_transaction(org_id, transaction_id, auth.user_id(), payload.reason)
+        .void_transaction(
+            org_id,
+            transaction_id,
+            auth.user_id(),
+            payload.reason_code,
+            payload.reason,
+        )
         .await
     {
         Ok(result) => {
+            state.dashboard_cache.invalidate(org_id.into_inner()).await;
             info!(
-                org_id = %org_id,
                 transaction_id = %transaction_id,
                 reversing_id = %result.reversing_transaction.id,
                 "Transaction voided"
             );
 
+            // The void stands even if the move fails; the attachments can
+            // still be moved one by one
+            let attachments_moved = match payload.move_attachments_to {
+                Some(target) => match attachment_repo
+                    .move_all(
+                        org_id.into_inner(),
+                        transaction_id.into_inner(),
+                        target,
+                        auth.user_id(),
+                    )
+                    .await
+                {
+                    Ok(moved) => {
+                        info!(
+                            transaction_id = %transaction_id,
+                            to_transaction_id = %target,
+                            moved,
+                            "Attachments moved"
+                        );
+                        Some(moved)
+                    }
+                    Err(e) => {
+                        error!(error = %e, "Failed to move attachments of voided transaction");
+                        None
+                    }
+                },
+                None => Some(0),
+            };
+
             let voided_at = result
                 .original_transaction
                 .voided_at
@@ -1149,6 +2272,7 @@ async fn void_transaction(
                         "status": status_to_string(&result.original_transaction.status),
                         "voided_at": voided_at,
                         "voided_by": result.original_transaction.voided_by,
+                        "void_reason_code": payload.reason_code,
                         "void_reason": result.original_transaction.void_reason,
                         "reversed_by_transaction_id": result.original_transaction.reversed_by_transaction_id
                     },
@@ -1156,8 +2280,10 @@ async fn void_transaction(
                         "id": result.reversing_transaction.id,
                         "status": status_to_string(&result.reversing_transaction.status),
                         "transaction_type": tx_type_to_string(&result.reversing_transaction.transaction_type),
+                        "description": result.reversing_transaction.description,
                         "reverses_transaction_id": result.reversing_transaction.reverses_transaction_id
-                    }
+                    },
+                    "attachments_moved": attachments_moved
                 })),
             )
                 .into_response()
@@ -1169,151 +2295,660 @@ async fn void_transaction(
     }
 }
 
-/// GET `/organizations/{org_id}/transactions/pending` - Get pending transactions.
-///
-/// Requirements: 6.6
-async fn get_pending_transactions(
+/// POST `/organizations/{org_id}/transactions/{transaction_id}/apply` - Apply part of a
+/// posted payment to a posted invoice or bill.
+async fn apply_payment(
     State(state): State<AppState>,
     auth: AuthUser,
-    Path(org_id): Path<Uuid>,
+    Path((org_id, payment_id)): Path<(OrganizationId, TransactionId)>,
+    StrictJson(payload): StrictJson<ApplyPaymentRequest>,
 ) -> impl IntoResponse {
-    let org_repo = OrganizationRepository::new((*state.db).clone());
-
-    if let Err(response) = check_membership(&org_repo, org_id, auth.user_id()).await {
+    if let Err(response) = check_membership(&state, org_id, auth.user_id()).await {
         return response;
     }
 
-    let workflow_repo = WorkflowRepository::new((*state.db).clone());
+    let Ok(applied_amount) = Decimal::from_str(&payload.amount) else {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(json!({
+                "error": "invalid_amount",
+                "message": "Invalid amount format"
+            })),
+        )
+            .into_response();
+    };
 
-    match workflow_repo
-        .get_pending_transactions(org_id, auth.user_id())
+    let input = ApplyPaymentInput {
+        settles_transaction_id: payload.settles_transaction_id,
+        applied_amount,
+        created_by: auth.user_id(),
+    };
+
+    match state
+        .stores
+        .payments
+        .apply_payment(org_id, payment_id, input)
         .await
     {
-        Ok(pending) => {
-            let items: Vec<PendingTransactionResponse> = pending
-                .into_iter()
-                .map(|p| {
-                    let submitted_at = p
-                        .transaction
-                        .submitted_at
-                        .as_ref()
-                        .map(chrono::DateTime::to_rfc3339);
-                    PendingTransactionResponse {
-                        id: p.transaction.id,
-                        reference_number: p.transaction.reference_number,
-                        transaction_type: tx_type_to_string(&p.transaction.transaction_type),
-                        transaction_date: p.transaction.transaction_date.to_string(),
-                        description: p.transaction.description,
-                        status: status_to_string(&p.transaction.status),
-                        total_amount: "0.0000".to_string(), // TODO: Calculate from entries
-                        submitted_at,
-                        can_approve: p.can_approve,
-                    }
-                })
-                .collect();
+        Ok(result) => {
+            info!(
+                payment_id = %payment_id,
+                settles_transaction_id = %result.application.settles_transaction_id,
+                "Payment applied"
+            );
 
-            (StatusCode::OK, Json(json!({ "data": items }))).into_response()
-        }
-        Err(e) => {
-            error!(error = %e, "Failed to get pending transactions");
-            workflow_error_response(e)
+            let response = PaymentApplicationResponse {
+                id: result.application.id,
+                payment_transaction_id: result.application.payment_transaction_id,
+                settles_transaction_id: result.application.settles_transaction_id,
+                applied_amount: result.application.applied_amount.to_string(),
+                created_by: result.application.created_by,
+                created_at: result.application.created_at.to_rfc3339(),
+                settlement: SettlementResponse::new(
+                    result.application.settles_transaction_id,
+                    &result.settlement,
+                ),
+            };
+
+            (StatusCode::CREATED, Json(response)).into_response()
         }
+        Err(e) => payment_error_response(&e),
     }
 }
 
-/// POST `/organizations/{org_id}/transactions/bulk-approve` - Bulk approve transactions.
-///
-/// Requirements: 6.7
-async fn bulk_approve_transactions(
+/// DELETE `/organizations/{org_id}/transactions/{transaction_id}/apply/{settles_transaction_id}` -
+/// Remove a payment's application to an invoice or bill.
+async fn unapply_payment(
     State(state): State<AppState>,
     auth: AuthUser,
-    Path(org_id): Path<Uuid>,
-    Json(payload): Json<BulkApproveRequest>,
+    Path((org_id, payment_id, settles_transaction_id)): Path<(OrganizationId, TransactionId, Uuid)>,
 ) -> impl IntoResponse {
-    let org_repo = OrganizationRepository::new((*state.db).clone());
-
-    if let Err(response) = check_membership(&org_repo, org_id, auth.user_id()).await {
+    if let Err(response) = check_membership(&state, org_id, auth.user_id()).await {
         return response;
     }
 
-    if payload.transaction_ids.is_empty() {
-        return (
-            StatusCode::BAD_REQUEST,
-            Json(json!({
-                "error": "empty_transaction_ids",
-                "message": "At least one transaction ID is required"
-            })),
-        )
-            .into_response();
+    match state
+        .stores
+        .payments
+        .unapply_payment(org_id, payment_id, settles_transaction_id)
+        .await
+    {
+        Ok(settlement) => {
+            info!(
+                payment_id = %payment_id,
+                settles_transaction_id = %settles_transaction_id,
+                "Payment unapplied"
+            );
+            (
+                StatusCode::OK,
+                Json(SettlementResponse::new(settles_transaction_id, &settlement)),
+            )
+                .into_response()
+        }
+        Err(e) => payment_error_response(&e),
     }
+}
 
-    if payload.transaction_ids.len() > 50 {
-        return (
-            StatusCode::BAD_REQUEST,
-            Json(json!({
-                "error": "too_many_transactions",
-                "message": "Maximum 50 transactions per bulk approval"
-            })),
-        )
-            .into_response();
-    }
+/// GET `/organizations/{org_id}/transactions/{transaction_id}/actions` - Workflow actions
+/// the current user may take, so clients can enable or explain each button.
+async fn get_transaction_actions(
+    State(state): State<AppState>,
+    auth: AuthMember,
+    Path((org_id, transaction_id)): Path<(OrganizationId, TransactionId)>,
+) -> impl IntoResponse {
+    let role = match auth.role_in(&state, org_id).await {
+        Ok(role) => role,
+        Err(response) => return response,
+    };
 
-    let workflow_repo = WorkflowRepository::new((*state.db).clone());
+    let workflow_repo = state.stores.workflow.as_ref();
 
     match workflow_repo
-        .bulk_approve(
-            org_id,
-            payload.transaction_ids,
-            auth.user_id(),
-            payload.approval_notes,
-        )
+        .get_available_actions(org_id, transaction_id, auth.user_id())
         .await
     {
-        Ok(result) => {
-            info!(
-                org_id = %org_id,
-                success_count = result.success_count,
-                failure_count = result.failure_count,
-                "Bulk approval completed"
-            );
-
-            let response = BulkApproveResponse {
-                results: result
-                    .results
-                    .into_iter()
-                    .map(|r| BulkApproveItemResponse {
-                        transaction_id: r.transaction_id,
-                        success: r.success,
-                        error: r.error,
-                    })
-                    .collect(),
-                success_count: result.success_count,
-                failure_count: result.failure_count,
-            };
-
-            (StatusCode::OK, Json(response)).into_response()
+        Ok(available)
+            if !role.can_view_all_transactions()
+                && available.transaction.created_by != auth.user_id() =>
+        {
+            update_error_response(&TransactionError::NotFound(transaction_id.into_inner()))
         }
+        Ok(available) => (
+            StatusCode::OK,
+            Json(TransactionActionsResponse {
+                transaction_id: available.transaction.id,
+                status: status_to_string(&available.transaction.status),
+                actions: available.actions,
+            }),
+        )
+            .into_response(),
         Err(e) => {
-            error!(error = %e, "Failed to bulk approve transactions");
+            error!(error = %e, "Failed to get transaction actions");
             workflow_error_response(e)
         }
     }
 }
 
-/// Convert WorkflowError to HTTP response.
-fn workflow_error_response(e: zeltra_core::workflow::WorkflowError) -> axum::response::Response {
-    use zeltra_core::workflow::WorkflowError;
+/// GET `/organizations/{org_id}/transactions/{transaction_id}/history` - Workflow
+/// transitions of a transaction, oldest first.
+///
+/// Unlike the timestamp columns on the transaction, the history keeps every
+/// round of a transaction that was rejected and resubmitted.
+async fn get_transaction_history(
+    State(state): State<AppState>,
+    auth: AuthMember,
+    Path((org_id, transaction_id)): Path<(OrganizationId, TransactionId)>,
+) -> impl IntoResponse {
+    let role = match auth.role_in(&state, org_id).await {
+        Ok(role) => role,
+        Err(response) => return response,
+    };
 
-    match e {
-        WorkflowError::InvalidTransition { from, to } => (
-            StatusCode::BAD_REQUEST,
-            Json(json!({
-                "error": "invalid_transition",
-                "message": format!("Invalid status transition from {:?} to {:?}", from, to)
-            })),
-        )
-            .into_response(),
-        WorkflowError::TransactionNotFound(_) => (
+    let history = match state
+        .stores
+        .workflow
+        .get_status_history(org_id, transaction_id)
+        .await
+    {
+        Ok(history) => history,
+        Err(e) => {
+            error!(error = %e, "Failed to get transaction history");
+            return workflow_error_response(e);
+        }
+    };
+
+    if !role.can_view_all_transactions() && history.transaction.created_by != auth.user_id() {
+        return (
+            StatusCode::FORBIDDEN,
+            Json(json!({
+                "error": "transaction_access_restricted",
+                "message": "Submitters can only view transactions they created"
+            })),
+        )
+            .into_response();
+    }
+
+    (
+        StatusCode::OK,
+        Json(TransactionHistoryResponse {
+            transaction_id: history.transaction.id,
+            status: status_to_string(&history.transaction.status),
+            history: history
+                .entries
+                .into_iter()
+                .map(|e| StatusHistoryItemResponse {
+                    from_status: e.history.from_status.as_ref().map(status_to_string),
+                    to_status: status_to_string(&e.history.to_status),
+                    actor_id: e.history.actor_id,
+                    actor_name: e.actor_name,
+                    notes: e.history.notes,
+                    at: e.history.created_at.to_rfc3339(),
+                })
+                .collect(),
+        }),
+    )
+        .into_response()
+}
+
+/// GET `/organizations/{org_id}/transactions/{transaction_id}/voucher` - Renders a
+/// printable journal voucher.
+///
+/// The voucher shows the entries with their accounts and dimension tags, the
+/// total in figures and in words, and the approval trail. Voided
+/// transactions are watermarked VOID.
+async fn get_transaction_voucher(
+    State(state): State<AppState>,
+    auth: AuthMember,
+    Path((org_id, transaction_id)): Path<(OrganizationId, TransactionId)>,
+    Query(query): Query<VoucherQuery>,
+) -> impl IntoResponse {
+    let role = match auth.role_in(&state, org_id).await {
+        Ok(role) => role,
+        Err(response) => return response,
+    };
+
+    if !role.can_view_all_transactions() {
+        let transaction = match state
+            .stores
+            .transactions
+            .get_transaction(org_id, transaction_id)
+            .await
+        {
+            Ok(result) => result.transaction,
+            Err(e) => return update_error_response(&e),
+        };
+        if transaction.created_by != auth.user_id() {
+            return (
+                StatusCode::FORBIDDEN,
+                Json(json!({
+                    "error": "transaction_access_restricted",
+                    "message": "Submitters can only view transactions they created"
+                })),
+            )
+                .into_response();
+        }
+    }
+
+    let voucher = match VoucherRepository::new((*state.db).clone())
+        .voucher(org_id, transaction_id)
+        .await
+    {
+        Ok(voucher) => voucher,
+        Err(e) => {
+            error!(error = %e, "Failed to build voucher");
+            return update_error_response(&e);
+        }
+    };
+
+    match query.format {
+        VoucherFormat::Pdf => (
+            StatusCode::OK,
+            [
+                (CONTENT_TYPE, "application/pdf".to_string()),
+                (
+                    CONTENT_DISPOSITION,
+                    format!("inline; filename=\"voucher-{transaction_id}.pdf\""),
+                ),
+            ],
+            voucher.to_pdf(),
+        )
+            .into_response(),
+        VoucherFormat::Html => (
+            StatusCode::OK,
+            [(CONTENT_TYPE, "text/html; charset=utf-8")],
+            voucher.to_html(),
+        )
+            .into_response(),
+    }
+}
+
+/// GET `/organizations/{org_id}/transactions/{transaction_id}/reversal-check` - Compares
+/// a voided transaction with its reversal.
+///
+/// Either side of the pair can be given. Entries are paired by account and
+/// amount; anything that does not mirror exactly is listed.
+async fn get_reversal_check(
+    State(state): State<AppState>,
+    auth: AuthMember,
+    Path((org_id, transaction_id)): Path<(OrganizationId, TransactionId)>,
+) -> impl IntoResponse {
+    let role = match auth.role_in(&state, org_id).await {
+        Ok(role) => role,
+        Err(response) => return response,
+    };
+
+    let tx_repo = state.stores.transactions.as_ref();
+
+    let requested = match tx_repo.get_transaction(org_id, transaction_id).await {
+        Ok(result) => result,
+        Err(e) => {
+            error!(error = %e, "Failed to get transaction");
+            return update_error_response(&e);
+        }
+    };
+
+    let (linked_id, requested_is_original) = match (
+        requested.transaction.reversed_by_transaction_id,
+        requested.transaction.reverses_transaction_id,
+    ) {
+        (Some(reversal_id), _) => (reversal_id, true),
+        (None, Some(original_id)) => (original_id, false),
+        (None, None) => {
+            return (
+                StatusCode::BAD_REQUEST,
+                Json(json!({
+                    "error": "no_reversal",
+                    "message": "Transaction has not been reversed and is not a reversal"
+                })),
+            )
+                .into_response();
+        }
+    };
+
+    let linked = match tx_repo
+        .get_transaction(org_id, TransactionId::from(linked_id))
+        .await
+    {
+        Ok(result) => result,
+        Err(e) => {
+            error!(error = %e, "Failed to get linked transaction");
+            return update_error_response(&e);
+        }
+    };
+    let (original, reversal) = if requested_is_original {
+        (requested, linked)
+    } else {
+        (linked, requested)
+    };
+
+    if !role.can_view_all_transactions() && original.transaction.created_by != auth.user_id() {
+        return (
+            StatusCode::FORBIDDEN,
+            Json(json!({
+                "error": "transaction_access_restricted",
+                "message": "Submitters can only view transactions they created"
+            })),
+        )
+            .into_response();
+    }
+
+    let comparison = compare_reversal(
+        &checked_entries(&original.entries),
+        &checked_entries(&reversal.entries),
+    );
+
+    (
+        StatusCode::OK,
+        Json(ReversalCheckResponse {
+            original_transaction_id: original.transaction.id,
+            reversal_transaction_id: reversal.transaction.id,
+            is_exact_mirror: comparison.is_exact_mirror(),
+            comparison,
+        }),
+    )
+        .into_response()
+}
+
+/// GET `/organizations/{org_id}/transactions/pending` - Get pending transactions.
+///
+/// `min_days` limits the queue to transactions waiting at least that long;
+/// `sort=days_pending` orders by age.
+///
+/// Requirements: 6.6
+async fn get_pending_transactions(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(org_id): Path<OrganizationId>,
+    Query(query): Query<PendingTransactionsQuery>,
+    Query(sort): Query<SortParams>,
+) -> impl IntoResponse {
+    if let Err(response) = check_membership(&state, org_id, auth.user_id()).await {
+        return response;
+    }
+
+    let sort = match sort.resolve::<PendingSortField>() {
+        Ok(sort) => sort,
+        Err(e) => return invalid_sort_response(&e),
+    };
+
+    let workflow_repo = state.stores.workflow.as_ref();
+
+    match workflow_repo
+        .get_pending_transactions(org_id, auth.user_id(), query.min_days, sort)
+        .await
+    {
+        Ok(pending) => {
+            let items: Vec<PendingTransactionResponse> = pending
+                .into_iter()
+                .map(|p| {
+                    let submitted_at = p
+                        .transaction
+                        .submitted_at
+                        .as_ref()
+                        .map(chrono::DateTime::to_rfc3339);
+                    PendingTransactionResponse {
+                        id: p.transaction.id,
+                        reference_number: p.transaction.reference_number,
+                        transaction_type: tx_type_to_string(&p.transaction.transaction_type),
+                        transaction_date: p.transaction.transaction_date.to_string(),
+                        description: p.transaction.description,
+                        status: status_to_string(&p.transaction.status),
+                        total_amount: p.total_amount.to_string(),
+                        submitted_at,
+                        days_pending: p.days_pending,
+                        can_approve: p.can_approve,
+                        on_behalf_of: p.on_behalf_of,
+                        approvals: p.progress.approvals,
+                        required_approvals: p.progress.required,
+                        approval_requirement: p.requirement.into(),
+                        budget_warning: p.transaction.budget_warning,
+                        claim: p.claim.map(ClaimResponse::from),
+                    }
+                })
+                .collect();
+
+            (StatusCode::OK, Json(json!({ "data": items }))).into_response()
+        }
+        Err(e) => {
+            error!(error = %e, "Failed to get pending transactions");
+            workflow_error_response(e)
+        }
+    }
+}
+
+/// POST `/organizations/{org_id}/transactions/bulk-approve` - Bulk approve transactions.
+///
+/// Requirements: 6.7
+async fn bulk_approve_transactions(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(org_id): Path<OrganizationId>,
+    StrictJson(payload): StrictJson<BulkApproveRequest>,
+) -> impl IntoResponse {
+    if let Err(response) = check_membership(&state, org_id, auth.user_id()).await {
+        return response;
+    }
+
+    if payload.transaction_ids.is_empty() {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(json!({
+                "error": "empty_transaction_ids",
+                "message": "At least one transaction ID is required"
+            })),
+        )
+            .into_response();
+    }
+
+    if payload.transaction_ids.len() > 50 {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(json!({
+                "error": "too_many_transactions",
+                "message": "Maximum 50 transactions per bulk approval"
+            })),
+        )
+            .into_response();
+    }
+
+    let workflow_repo = state.stores.workflow.as_ref();
+
+    match workflow_repo
+        .bulk_approve(
+            org_id,
+            payload.transaction_ids,
+            auth.user_id(),
+            payload.approval_notes,
+        )
+        .await
+    {
+        Ok(result) => {
+            if result.success_count > 0 {
+                state.dashboard_cache.invalidate(org_id.into_inner()).await;
+            }
+            info!(
+                success_count = result.success_count,
+                failure_count = result.failure_count,
+                "Bulk approval completed"
+            );
+
+            let response = BulkApproveResponse {
+                results: result
+                    .results
+                    .into_iter()
+                    .map(|r| BulkApproveItemResponse {
+                        transaction_id: r.transaction_id,
+                        success: r.success,
+                        error: r.error,
+                    })
+                    .collect(),
+                success_count: result.success_count,
+                failure_count: result.failure_count,
+            };
+
+            (StatusCode::OK, Json(response)).into_response()
+        }
+        Err(e) => {
+            error!(error = %e, "Failed to bulk approve transactions");
+            workflow_error_response(e)
+        }
+    }
+}
+
+/// POST `/organizations/{org_id}/transactions/bulk-void` - Bulk void posted transactions.
+///
+/// Items are voided one by one with a shared reason; failures are reported
+/// per item and do not undo the voids that succeeded.
+async fn bulk_void_transactions(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(org_id): Path<OrganizationId>,
+    StrictJson(payload): StrictJson<BulkVoidRequest>,
+) -> impl IntoResponse {
+    if let Err(response) = check_membership(&state, org_id, auth.user_id()).await {
+        return response;
+    }
+
+    if payload.transaction_ids.is_empty() {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(json!({
+                "error": "empty_transaction_ids",
+                "message": "At least one transaction ID is required"
+            })),
+        )
+            .into_response();
+    }
+
+    if payload.transaction_ids.len() > 50 {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(json!({
+                "error": "too_many_transactions",
+                "message": "Maximum 50 transactions per bulk void"
+            })),
+        )
+            .into_response();
+    }
+
+    if payload.reason_code.requires_text() && payload.reason.trim().is_empty() {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(json!({
+                "error": "void_reason_required",
+                "message": "Void reason is required when reason_code is other"
+            })),
+        )
+            .into_response();
+    }
+
+    let workflow_repo = state.stores.workflow.as_ref();
+
+    match workflow_repo
+        .bulk_void(
+            org_id,
+            payload.transaction_ids,
+            auth.user_id(),
+            payload.reason_code,
+            payload.reason,
+        )
+        .await
+    {
+        Ok(result) => {
+            if result.success_count > 0 {
+                state.dashboard_cache.invalidate(org_id.into_inner()).await;
+            }
+            info!(
+                success_count = result.success_count,
+                failure_count = result.failure_count,
+                "Bulk void completed"
+            );
+
+            let response = BulkVoidResponse {
+                results: result
+                    .results
+                    .into_iter()
+                    .map(|r| BulkVoidItemResponse {
+                        transaction_id: r.transaction_id,
+                        success: r.success,
+                        reversing_transaction_id: r.reversing_transaction_id,
+                        error_code: r.error_code,
+                        error: r.error,
+                    })
+                    .collect(),
+                success_count: result.success_count,
+                failure_count: result.failure_count,
+            };
+
+            (StatusCode::OK, Json(response)).into_response()
+        }
+        Err(e) => {
+            error!(error = %e, "Failed to bulk void transactions");
+            workflow_error_response(e)
+        }
+    }
+}
+
+/// Convert WorkflowError to HTTP response.
+#[allow(clippy::too_many_lines)]
+/// Maps a payment application error to an HTTP response.
+fn payment_error_response(e: &PaymentError) -> axum::response::Response {
+    let (status, code) = match e {
+        PaymentError::PaymentNotFound(_) | PaymentError::DocumentNotFound(_) => {
+            (StatusCode::NOT_FOUND, "not_found")
+        }
+        PaymentError::ApplicationNotFound(_) => (StatusCode::NOT_FOUND, "application_not_found"),
+        PaymentError::NotAPayment(_) => (StatusCode::BAD_REQUEST, "not_a_payment"),
+        PaymentError::NotSettleable(_) => (StatusCode::BAD_REQUEST, "not_settleable"),
+        PaymentError::NotPosted(_) => (StatusCode::BAD_REQUEST, "transaction_not_posted"),
+        PaymentError::PaymentVoided(_) => (StatusCode::BAD_REQUEST, "payment_voided"),
+        PaymentError::AlreadyApplied(_) => (StatusCode::CONFLICT, "already_applied"),
+        PaymentError::Application(ApplicationError::NonPositiveAmount) => {
+            (StatusCode::BAD_REQUEST, "invalid_amount")
+        }
+        PaymentError::Application(ApplicationError::ExceedsPayment { .. }) => {
+            (StatusCode::BAD_REQUEST, "exceeds_payment")
+        }
+        PaymentError::Application(ApplicationError::ExceedsOutstanding { .. }) => {
+            (StatusCode::BAD_REQUEST, "exceeds_outstanding")
+        }
+        PaymentError::Database(_) => {
+            error!(error = %e, "Failed to apply payment");
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({
+                    "error": "internal_error",
+                    "message": "An error occurred"
+                })),
+            )
+                .into_response();
+        }
+    };
+
+    (
+        status,
+        Json(json!({
+            "error": code,
+            "message": e.to_string()
+        })),
+    )
+        .into_response()
+}
+
+#[allow(clippy::too_many_lines)]
+fn workflow_error_response(e: zeltra_core::workflow::WorkflowError) -> axum::response::Response {
+    use zeltra_core::workflow::WorkflowError;
+
+    match e {
+        WorkflowError::InvalidTransition { from, to } => (
+            StatusCode::BAD_REQUEST,
+            Json(json!({
+                "error": "invalid_transition",
+                "message": format!("Invalid status transition from {:?} to {:?}", from, to)
+            })),
+        )
+            .into_response(),
+        WorkflowError::TransactionNotFound(_) => (
             StatusCode::NOT_FOUND,
             Json(json!({
                 "error": "not_found",
@@ -1380,6 +3015,62 @@ fn workflow_error_response(e: zeltra_core::workflow::WorkflowError) -> axum::res
             })),
         )
             .into_response(),
+        WorkflowError::CanOnlyDeleteDraft => (
+            StatusCode::BAD_REQUEST,
+            Json(json!({
+                "error": "can_only_delete_draft",
+                "message": "Can only delete draft transactions"
+            })),
+        )
+            .into_response(),
+        WorkflowError::AlreadyApproved { .. } => (
+            StatusCode::CONFLICT,
+            Json(json!({
+                "error": "already_approved",
+                "message": "You have already approved this transaction"
+            })),
+        )
+            .into_response(),
+        WorkflowError::PeriodClosed => (
+            StatusCode::BAD_REQUEST,
+            Json(json!({
+                "error": "period_closed",
+                "message": "Fiscal period is closed; the transaction cannot be voided"
+            })),
+        )
+            .into_response(),
+        WorkflowError::EntryReconciled { reconciliation_id } => (
+            StatusCode::CONFLICT,
+            Json(json!({
+                "error": "entry_reconciled",
+                "message": "Transaction has reconciled entries; reopen the reconciliation first",
+                "reconciliation_id": reconciliation_id
+            })),
+        )
+            .into_response(),
+        WorkflowError::ClaimedByOther {
+            reviewer_id,
+            reviewer_name,
+            until,
+        } => (
+            StatusCode::CONFLICT,
+            Json(json!({
+                "error": "claimed",
+                "message": format!("{reviewer_name} is reviewing this transaction"),
+                "reviewer_id": reviewer_id,
+                "reviewer_name": reviewer_name,
+                "reviewing_until": until.to_rfc3339()
+            })),
+        )
+            .into_response(),
+        WorkflowError::NotPending { status } => (
+            StatusCode::BAD_REQUEST,
+            Json(json!({
+                "error": "not_pending",
+                "message": format!("Transaction is {status}; only pending transactions can be claimed")
+            })),
+        )
+            .into_response(),
         _ => (
             StatusCode::INTERNAL_SERVER_ERROR,
             Json(json!({
@@ -1391,61 +3082,194 @@ fn workflow_error_response(e: zeltra_core::workflow::WorkflowError) -> axum::res
     }
 }
 
-// ============================================================================
-// Helper Functions
-// ============================================================================
+// ============================================================================
+// Helper Functions
+// ============================================================================
+
+/// 422 response for an amount with more decimal places than its currency.
+pub(crate) fn amount_too_precise_response(e: &PrecisionError) -> axum::response::Response {
+    (
+        StatusCode::UNPROCESSABLE_ENTITY,
+        Json(json!({
+            "error": "amount_too_precise",
+            "message": e.to_string(),
+            "currency": e.currency,
+            "decimal_places": e.decimal_places
+        })),
+    )
+        .into_response()
+}
+
+/// Sums a transaction's entries per source currency.
+///
+/// Source amounts are rounded to their currency's decimal places; if those
+/// cannot be loaded the stored amounts are returned unrounded.
+async fn source_totals_response(
+    tx_repo: &dyn TransactionStore,
+    entries: &[LedgerEntryWithDimensions],
+) -> Vec<SourceTotalResponse> {
+    let mut codes: Vec<&str> = entries
+        .iter()
+        .map(|e| e.entry.source_currency.as_str())
+        .collect();
+    codes.sort_unstable();
+    codes.dedup();
+    let decimal_places = tx_repo
+        .currency_decimal_places(&codes)
+        .await
+        .unwrap_or_else(|e| {
+            error!(error = %e, "Failed to load currency decimal places");
+            std::collections::HashMap::new()
+        });
+
+    let amounts = entries.iter().map(|e| EntryAmounts {
+        source_currency: &e.entry.source_currency,
+        source_amount: e.entry.source_amount,
+        functional_amount: e.entry.functional_amount,
+        entry_type: if e.entry.debit.is_zero() {
+            InputEntryType::Credit
+        } else {
+            InputEntryType::Debit
+        },
+    });
+    LedgerService::source_totals(amounts, |currency| {
+        decimal_places
+            .get(currency)
+            .copied()
+            .unwrap_or(STORED_DECIMAL_PLACES)
+    })
+    .into_iter()
+    .map(|t| SourceTotalResponse {
+        currency: t.currency,
+        debit: t.debit.to_string(),
+        credit: t.credit.to_string(),
+        functional_debit: t.functional_debit.to_string(),
+        functional_credit: t.functional_credit.to_string(),
+    })
+    .collect()
+}
+
+/// Describes an exchange rate applied to a transaction.
+/// Stored entries in the shape the reversal check compares.
+fn checked_entries(entries: &[LedgerEntryWithDimensions]) -> Vec<CheckedEntry> {
+    entries
+        .iter()
+        .map(|e| CheckedEntry {
+            entry_id: e.entry.id,
+            account_id: e.entry.account_id,
+            source_currency: e.entry.source_currency.clone(),
+            source_amount: e.entry.source_amount,
+            functional_amount: e.entry.functional_amount,
+            entry_type: if e.entry.debit > Decimal::ZERO {
+                InputEntryType::Debit
+            } else {
+                InputEntryType::Credit
+            },
+        })
+        .collect()
+}
+
+fn rate_used_response(
+    from_currency: &str,
+    to_currency: &str,
+    lookup: &ExchangeRateLookup,
+    max_staleness_days: u32,
+) -> RateUsedResponse {
+    RateUsedResponse {
+        from_currency: from_currency.to_string(),
+        to_currency: to_currency.to_string(),
+        rate: lookup.rate.to_string(),
+        effective_date: lookup.effective_date,
+        staleness_days: lookup.staleness_days,
+        stale: lookup.is_stale(max_staleness_days),
+    }
+}
+
+/// Message for a rate older than the organization allows.
+fn stale_rate_message(rate_used: &RateUsedResponse, max_staleness_days: u32) -> String {
+    format!(
+        "Exchange rate for {} to {} is from {}, {} days before the transaction (maximum {})",
+        rate_used.from_currency,
+        rate_used.to_currency,
+        rate_used.effective_date,
+        rate_used.staleness_days,
+        max_staleness_days
+    )
+}
 
-async fn check_membership(
-    org_repo: &OrganizationRepository,
-    org_id: Uuid,
+/// Builds the list filter, limiting roles that cannot see every transaction
+/// to their own.
+#[allow(clippy::result_large_err)]
+fn build_filter(
+    query: &ListTransactionsQuery,
+    role: CoreUserRole,
     user_id: Uuid,
-) -> Result<(), axum::response::Response> {
-    match org_repo.is_member(org_id, user_id).await {
-        Ok(true) => Ok(()),
-        Ok(false) => Err((
-            StatusCode::FORBIDDEN,
+) -> Result<TransactionFilter, axum::response::Response> {
+    let min_amount = parse_amount_filter("min_amount", query.min_amount.as_deref())?;
+    let max_amount = parse_amount_filter("max_amount", query.max_amount.as_deref())?;
+    if let (Some(min), Some(max)) = (min_amount, max_amount)
+        && min > max
+    {
+        return Err((
+            StatusCode::BAD_REQUEST,
             Json(json!({
-                "error": "forbidden",
-                "message": "You are not a member of this organization"
+                "error": "invalid_amount_range",
+                "message": "min_amount must not be greater than max_amount"
             })),
         )
-            .into_response()),
-        Err(e) => {
-            error!(error = %e, "Database error checking membership");
-            Err((
-                StatusCode::INTERNAL_SERVER_ERROR,
+            .into_response());
+    }
+
+    Ok(TransactionFilter {
+        status: query.status.as_ref().and_then(|s| string_to_status(s)),
+        transaction_type: query
+            .transaction_type
+            .as_ref()
+            .and_then(|t| string_to_tx_type(t)),
+        date_from: query.from,
+        date_to: query.to,
+        dimension_value_id: query.dimension,
+        created_by: if role.can_view_all_transactions() {
+            query.created_by
+        } else {
+            Some(user_id)
+        },
+        account_id: query.account_id,
+        min_amount,
+        max_amount,
+        sort: None,
+    })
+}
+
+#[allow(clippy::result_large_err)]
+fn parse_amount_filter(
+    name: &str,
+    value: Option<&str>,
+) -> Result<Option<Decimal>, axum::response::Response> {
+    match value {
+        Some(s) if !s.is_empty() => Decimal::from_str(s).map(Some).map_err(|_| {
+            (
+                StatusCode::BAD_REQUEST,
                 Json(json!({
-                    "error": "internal_error",
-                    "message": "An error occurred"
+                    "error": "invalid_amount",
+                    "message": format!("Invalid {name}: {s}")
                 })),
             )
-                .into_response())
-        }
+                .into_response()
+        }),
+        _ => Ok(None),
     }
 }
 
-fn status_to_string(status: &TransactionStatus) -> String {
-    match status {
-        TransactionStatus::Draft => "draft".to_string(),
-        TransactionStatus::Pending => "pending".to_string(),
-        TransactionStatus::Approved => "approved".to_string(),
-        TransactionStatus::Posted => "posted".to_string(),
-        TransactionStatus::Voided => "voided".to_string(),
-    }
+pub(crate) fn status_to_string(status: &TransactionStatus) -> String {
+    CoreTransactionStatus::from(status).to_string()
 }
 
-fn string_to_status(s: &str) -> Option<TransactionStatus> {
-    match s.to_lowercase().as_str() {
-        "draft" => Some(TransactionStatus::Draft),
-        "pending" => Some(TransactionStatus::Pending),
-        "approved" => Some(TransactionStatus::Approved),
-        "posted" => Some(TransactionStatus::Posted),
-        "voided" => Some(TransactionStatus::Voided),
-        _ => None,
-    }
+pub(crate) fn string_to_status(s: &str) -> Option<TransactionStatus> {
+    CoreTransactionStatus::parse(s).map(Into::into)
 }
 
-fn tx_type_to_string(tx_type: &TransactionType) -> String {
+pub(crate) fn tx_type_to_string(tx_type: &TransactionType) -> String {
     match tx_type {
         TransactionType::Journal => "journal".to_string(),
         TransactionType::Expense => "expense".to_string(),
@@ -1459,7 +3283,25 @@ fn tx_type_to_string(tx_type: &TransactionType) -> String {
     }
 }
 
-fn string_to_tx_type(s: &str) -> Option<TransactionType> {
+/// Accepts the transaction types [`string_to_tx_type`] knows.
+fn deserialize_tx_type<'de, D: Deserializer<'de>>(deserializer: D) -> Result<String, D::Error> {
+    enum_string(deserializer, string_to_tx_type, || {
+        TransactionType::iter()
+            .map(|t| tx_type_to_string(&t))
+            .collect()
+    })
+}
+
+/// Accepts `debit` or `credit`.
+fn deserialize_entry_type<'de, D: Deserializer<'de>>(deserializer: D) -> Result<String, D::Error> {
+    enum_string(
+        deserializer,
+        |s| s.parse::<InputEntryType>().ok(),
+        || vec!["debit".to_string(), "credit".to_string()],
+    )
+}
+
+pub(crate) fn string_to_tx_type(s: &str) -> Option<TransactionType> {
     match s.to_lowercase().as_str() {
         "journal" => Some(TransactionType::Journal),
         "expense" => Some(TransactionType::Expense),
@@ -1473,3 +3315,1365 @@ fn string_to_tx_type(s: &str) -> Option<TransactionType> {
         _ => None,
     }
 }
+
+/// Maps a stored transaction type to the ledger's.
+const fn core_tx_type(t: &TransactionType) -> CoreTransactionType {
+    match t {
+        TransactionType::Journal => CoreTransactionType::Journal,
+        TransactionType::Expense => CoreTransactionType::Expense,
+        TransactionType::Invoice => CoreTransactionType::Invoice,
+        TransactionType::Bill => CoreTransactionType::Bill,
+        TransactionType::Payment => CoreTransactionType::Payment,
+        TransactionType::Transfer => CoreTransactionType::Transfer,
+        TransactionType::Adjustment => CoreTransactionType::Adjustment,
+        TransactionType::OpeningBalance => CoreTransactionType::OpeningBalance,
+        TransactionType::Reversal => CoreTransactionType::Reversal,
+    }
+}
+
+#[cfg(test)]
+mod handler_tests {
+    use super::*;
+    use async_trait::async_trait;
+    use http_body_util::BodyExt;
+    use sea_orm::{DatabaseConnection, DbErr};
+    use std::sync::Arc;
+    use zeltra_core::currency::RateLookupPolicy;
+    use zeltra_db::entities::sea_orm_active_enums::{
+        AccountType, SubscriptionStatus, SubscriptionTier, UserRole,
+    };
+    use zeltra_db::entities::{chart_of_accounts, organization_users, organizations};
+    use zeltra_db::repositories::{
+        EntryReferenceStore, ExchangeRateStore, OrganizationStore, Stores,
+    };
+    use zeltra_shared::{AdminConfig, EmailConfig, EmailService, JwtConfig, JwtService};
+
+    use crate::cache::{DashboardCache, MembershipCache};
+
+    /// Organization store where every user is a member of a USD organization,
+    /// with the given role.
+    struct FakeOrganizations(UserRole);
+
+    #[async_trait]
+    impl OrganizationStore for FakeOrganizations {
+        async fn find_by_id(&self, id: Uuid) -> Result<Option<organizations::Model>, DbErr> {
+            let now = chrono::Utc::now().fixed_offset();
+            Ok(Some(organizations::Model {
+                id,
+                name: "Acme".to_string(),
+                slug: "acme".to_string(),
+                base_currency: "USD".to_string(),
+                timezone: "UTC".to_string(),
+                settings: OrganizationSettings::default().to_value(),
+                is_active: true,
+                subscription_tier: SubscriptionTier::Starter,
+                subscription_status: SubscriptionStatus::Active,
+                trial_ends_at: None,
+                subscription_ends_at: None,
+                payment_provider: None,
+                payment_customer_id: None,
+                payment_subscription_id: None,
+                created_at: now,
+                updated_at: now,
+            }))
+        }
+
+        async fn is_member(&self, _org_id: Uuid, _user_id: Uuid) -> Result<bool, DbErr> {
+            Ok(true)
+        }
+
+        async fn get_user_membership(
+            &self,
+            org_id: Uuid,
+            user_id: Uuid,
+        ) -> Result<Option<organization_users::Model>, DbErr> {
+            let now = chrono::Utc::now().fixed_offset();
+            Ok(Some(organization_users::Model {
+                user_id,
+                organization_id: org_id,
+                role: self.0.clone(),
+                approval_limit: None,
+                created_at: now,
+                updated_at: now,
+            }))
+        }
+    }
+
+    /// Entry references where every requested account is an active USD
+    /// account open to direct posting.
+    struct OpenAccounts;
+
+    #[async_trait]
+    impl EntryReferenceStore for OpenAccounts {
+        async fn find_entry_references(
+            &self,
+            organization_id: OrganizationId,
+            account_ids: &[Uuid],
+            _dimension_value_ids: &[Uuid],
+        ) -> Result<EntryReferences, TransactionError> {
+            let now = chrono::Utc::now().fixed_offset();
+            let accounts = account_ids
+                .iter()
+                .map(|&id| {
+                    let account = chart_of_accounts::Model {
+                        id,
+                        organization_id: organization_id.into_inner(),
+                        code: "1000".to_string(),
+                        name: "Cash".to_string(),
+                        description: None,
+                        account_type: AccountType::Asset,
+                        account_subtype: None,
+                        parent_id: None,
+                        currency: "USD".to_string(),
+                        is_active: true,
+                        is_system_account: false,
+                        allow_direct_posting: true,
+                        is_bank_account: false,
+                        bank_account_number: None,
+                        created_at: now,
+                        updated_at: now,
+                    };
+                    (id, account)
+                })
+                .collect();
+            Ok(EntryReferences {
+                accounts,
+                ..EntryReferences::default()
+            })
+        }
+    }
+
+    /// Exchange rate store without any rates.
+    struct NoRates;
+
+    #[async_trait]
+    impl ExchangeRateStore for NoRates {
+        async fn find_rate(
+            &self,
+            _organization_id: Uuid,
+            from_currency: &str,
+            to_currency: &str,
+            date: NaiveDate,
+            _policy: RateLookupPolicy,
+        ) -> Result<ExchangeRateLookup, ExchangeRateError> {
+            Err(ExchangeRateError::RateNotFound(
+                from_currency.to_string(),
+                to_currency.to_string(),
+                date,
+            ))
+        }
+    }
+
+    /// State whose remaining stores sit on a disconnected database, so any
+    /// query the handler should not reach fails the test.
+    fn fake_state(role: UserRole) -> AppState {
+        let db = DatabaseConnection::Disconnected;
+        AppState {
+            stores: Stores {
+                organizations: Arc::new(FakeOrganizations(role)),
+                entry_references: Arc::new(OpenAccounts),
+                exchange_rates: Arc::new(NoRates),
+                ..Stores::postgres(&db)
+            },
+            db: Arc::new(db),
+            jwt_service: Arc::new(JwtService::new(JwtConfig::default())),
+            email_service: Arc::new(EmailService::new(EmailConfig::default())),
+            storage: None,
+            metrics: None,
+            jobs: None,
+            admin: AdminConfig::default(),
+            dashboard_cache: DashboardCache::default(),
+            membership_cache: MembershipCache::default(),
+            currencies: Arc::default(),
+        }
+    }
+
+    fn entry(currency: &str, amount: &str, entry_type: &str) -> CreateEntryRequest {
+        CreateEntryRequest {
+            account_id: Uuid::new_v4(),
+            source_currency: currency.to_string(),
+            source_amount: amount.to_string(),
+            entry_type: entry_type.to_string(),
+            memo: None,
+            dimensions: Vec::new(),
+            event_at: None,
+            override_exchange_rate: None,
+        }
+    }
+
+    fn request(
+        transaction_type: &str,
+        entries: Vec<CreateEntryRequest>,
+    ) -> CreateTransactionRequest {
+        CreateTransactionRequest {
+            transaction_type: transaction_type.to_string(),
+            transaction_date: NaiveDate::from_ymd_opt(2025, 3, 14).unwrap(),
+            description: "Office supplies".to_string(),
+            reference_number: None,
+            memo: None,
+            entries,
+            allow_duplicate: false,
+        }
+    }
+
+    async fn create(payload: CreateTransactionRequest) -> (StatusCode, serde_json::Value) {
+        create_as(UserRole::Accountant, payload).await
+    }
+
+    async fn create_as(
+        role: UserRole,
+        payload: CreateTransactionRequest,
+    ) -> (StatusCode, serde_json::Value) {
+        let org_id = OrganizationId::new();
+        let response =
+            create_draft_transaction(&fake_state(role), Uuid::new_v4(), org_id, payload).await;
+        let status = response.status();
+        let bytes = response.into_body().collect().await.unwrap().to_bytes();
+        (status, serde_json::from_slice(&bytes).unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_create_rejects_unknown_transaction_type() {
+        let (status, body) = create(request(
+            "gift",
+            vec![entry("USD", "10", "debit"), entry("USD", "10", "credit")],
+        ))
+        .await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body["error"], "invalid_transaction_type");
+    }
+
+    #[tokio::test]
+    async fn test_create_requires_two_entries() {
+        let (status, body) = create(request("expense", vec![entry("USD", "10", "debit")])).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body["error"], "insufficient_entries");
+    }
+
+    #[tokio::test]
+    async fn test_create_rejects_negative_amount() {
+        let (status, body) = create(request(
+            "adjustment",
+            vec![entry("USD", "-10", "debit"), entry("USD", "10", "credit")],
+        ))
+        .await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body["error"], "negative_amount");
+        assert!(
+            body["message"]
+                .as_str()
+                .unwrap()
+                .contains("flip the entry type")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_create_rejects_zero_amount_outside_adjustments() {
+        let (status, body) = create(request(
+            "expense",
+            vec![
+                entry("USD", "10", "debit"),
+                entry("USD", "10", "credit"),
+                entry("USD", "0", "debit"),
+            ],
+        ))
+        .await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body["error"], "zero_amount_not_allowed");
+    }
+
+    #[tokio::test]
+    async fn test_create_rejects_unbalanced_entries() {
+        let (status, body) = create(request(
+            "expense",
+            vec![entry("USD", "10", "debit"), entry("USD", "9.99", "credit")],
+        ))
+        .await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body["error"], "unbalanced_transaction");
+    }
+
+    #[tokio::test]
+    async fn test_create_rejects_foreign_currency_without_rate() {
+        let (status, body) = create(request(
+            "expense",
+            vec![entry("EUR", "10", "debit"), entry("EUR", "10", "credit")],
+        ))
+        .await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body["error"], "no_exchange_rate");
+    }
+
+    #[tokio::test]
+    async fn test_rate_override_requires_accountant() {
+        let overridden = || {
+            let mut debit = entry("EUR", "10", "debit");
+            debit.override_exchange_rate = Some("1.2".to_string());
+            request("expense", vec![debit, entry("USD", "12", "credit")])
+        };
+
+        for role in [UserRole::Submitter, UserRole::Viewer] {
+            let (status, body) = create_as(role, overridden()).await;
+            assert_eq!(status, StatusCode::FORBIDDEN);
+            assert_eq!(body["error"], "forbidden");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_rate_override_rejects_functional_currency_and_bad_rates() {
+        let with_rate = |currency: &str, rate: &str| {
+            let mut debit = entry(currency, "10", "debit");
+            debit.override_exchange_rate = Some(rate.to_string());
+            request("expense", vec![debit, entry("USD", "10", "credit")])
+        };
+
+        let (status, body) = create(with_rate("USD", "1")).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body["error"], "invalid_exchange_rate");
+
+        for rate in ["0", "-1.1", "abc"] {
+            let (status, body) = create(with_rate("EUR", rate)).await;
+            assert_eq!(status, StatusCode::BAD_REQUEST);
+            assert_eq!(body["error"], "invalid_exchange_rate");
+        }
+    }
+}
+
+#[cfg(test)]
+mod integration_tests {
+    use super::*;
+    use axum::http::header::CONTENT_TYPE;
+    use sea_orm::{ActiveModelTrait, EntityTrait, Set};
+    use std::sync::Arc;
+    use zeltra_db::OrganizationRepository;
+    use zeltra_db::entities::sea_orm_active_enums::BudgetType;
+    use zeltra_db::entities::sea_orm_active_enums::{AccountType, RateSource, UserRole};
+    use zeltra_db::entities::{chart_of_accounts, exchange_rate_overrides};
+    use zeltra_db::repositories::budget::{CreateBudgetInput, CreateBudgetLineInput};
+    use zeltra_db::repositories::exchange_rate::{CreateExchangeRateInput, ExchangeRateRepository};
+    use zeltra_db::repositories::{CurrencyRepository, WorkflowRepository};
+    use zeltra_test_support::{
+        Member, Org, OrgFixture, TestDb, TestResponse, access_token, json_request, post_draft,
+        send, send_request, test_app_state,
+    };
+
+    /// Sends a request with no payload as `member` to the transaction routes.
+    async fn get_as(state: &AppState, org: &Org, member: &Member, uri: &str) -> TestResponse {
+        let token = access_token(&state.jwt_service, org.id, member);
+        let request = json_request("GET", uri, &token, &serde_json::Value::Null);
+        send_request(state.authenticated(routes()), request).await
+    }
+
+    /// Organization with a EUR/USD rate effective 2025-03-08.
+    async fn org_with_rate(test_db: &TestDb, settings: serde_json::Value) -> Org {
+        let org = OrgFixture::new()
+            .with_fiscal_year_2025()
+            .with_accounts(&[("1000", AccountType::Asset), ("4000", AccountType::Revenue)])
+            .create(test_db.conn())
+            .await;
+        OrganizationRepository::new(test_db.conn().clone())
+            .update_settings(org.id.into_inner(), &settings)
+            .await
+            .unwrap();
+        add_eur_rate(test_db, &org, 8, Decimal::new(110, 2)).await;
+        org
+    }
+
+    /// Stores a EUR/USD rate effective on `day` of March 2025.
+    async fn add_eur_rate(test_db: &TestDb, org: &Org, day: u32, rate: Decimal) {
+        ExchangeRateRepository::new(test_db.conn().clone())
+            .create_or_update_rate(CreateExchangeRateInput {
+                organization_id: org.id.into_inner(),
+                from_currency: "EUR".to_string(),
+                to_currency: "USD".to_string(),
+                rate,
+                effective_date: NaiveDate::from_ymd_opt(2025, 3, day).unwrap(),
+                source: RateSource::Manual,
+                source_reference: None,
+                created_by: None,
+            })
+            .await
+            .unwrap();
+    }
+
+    /// Creates a EUR invoice dated `date` and returns the status and body.
+    async fn create_eur_invoice(
+        state: &AppState,
+        org: &Org,
+        date: &str,
+    ) -> (StatusCode, serde_json::Value) {
+        let entry = |account: Uuid, entry_type: &str| {
+            json!({
+                "account_id": account,
+                "source_currency": "EUR",
+                "source_amount": "100.00",
+                "entry_type": entry_type
+            })
+        };
+        let body = json!({
+            "type": "invoice",
+            "transaction_date": date,
+            "description": "EUR invoice",
+            "entries": [
+                entry(org.account("1000").into_inner(), "debit"),
+                entry(org.account("4000").into_inner(), "credit")
+            ]
+        });
+        post_transaction(state, org, body).await
+    }
+
+    /// Posts a create request as the owner and returns the status and body.
+    async fn post_transaction(
+        state: &AppState,
+        org: &Org,
+        body: serde_json::Value,
+    ) -> (StatusCode, serde_json::Value) {
+        post_transaction_as(state, org, &org.owner, body).await
+    }
+
+    /// Posts a create request as `member` and returns the status and body.
+    async fn post_transaction_as(
+        state: &AppState,
+        org: &Org,
+        member: &Member,
+        body: serde_json::Value,
+    ) -> (StatusCode, serde_json::Value) {
+        let token = access_token(&state.jwt_service, org.id, member);
+        let uri = format!("/organizations/{}/transactions", org.id);
+        send(state.authenticated(routes()), "POST", &uri, &token, body).await
+    }
+
+    #[tokio::test]
+    async fn test_rate_at_max_staleness_is_not_stale() {
+        let test_db = TestDb::new().await;
+        let state: AppState = test_app_state(&test_db);
+        let org = org_with_rate(&test_db, json!({})).await;
+
+        let (status, body) = create_eur_invoice(&state, &org, "2025-03-15").await;
+
+        assert_eq!(status, StatusCode::CREATED);
+        assert_eq!(body["entries"][0]["exchange_rate"], "1.1000000000");
+        assert_eq!(body["entries"][0]["functional_amount"], "110.0000");
+        assert!(body.get("warnings").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_response_includes_source_currency_totals() {
+        let test_db = TestDb::new().await;
+        let state: AppState = test_app_state(&test_db);
+        let org = org_with_rate(&test_db, json!({})).await;
+
+        let (status, body) = create_eur_invoice(&state, &org, "2025-03-15").await;
+
+        assert_eq!(status, StatusCode::CREATED);
+        assert_eq!(
+            body["source_totals"],
+            json!([{
+                "currency": "EUR",
+                "debit": "100.00",
+                "credit": "100.00",
+                "functional_debit": "110.0000",
+                "functional_credit": "110.0000"
+            }])
+        );
+    }
+
+    #[tokio::test]
+    async fn test_stale_rate_warns_in_lenient_mode() {
+        let test_db = TestDb::new().await;
+        let state: AppState = test_app_state(&test_db);
+        let org = org_with_rate(&test_db, json!({})).await;
+
+        let (status, body) = create_eur_invoice(&state, &org, "2025-03-16").await;
+
+        assert_eq!(status, StatusCode::CREATED);
+        let warning = &body["warnings"][0];
+        assert_eq!(warning["code"], "stale_exchange_rate");
+        assert_eq!(warning["rate_used"]["effective_date"], "2025-03-08");
+        assert_eq!(warning["rate_used"]["staleness_days"], 8);
+        assert_eq!(warning["rate_used"]["stale"], true);
+    }
+
+    #[tokio::test]
+    async fn test_stale_rate_rejected_in_strict_mode() {
+        let test_db = TestDb::new().await;
+        let state: AppState = test_app_state(&test_db);
+        let org = org_with_rate(
+            &test_db,
+            json!({ "exchange_rates": { "strict_staleness": true } }),
+        )
+        .await;
+
+        let (status, _) = create_eur_invoice(&state, &org, "2025-03-15").await;
+        assert_eq!(status, StatusCode::CREATED);
+
+        let (status, body) = create_eur_invoice(&state, &org, "2025-03-16").await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body["error"], "stale_exchange_rate");
+        assert_eq!(body["rate_used"]["effective_date"], "2025-03-08");
+    }
+
+    #[tokio::test]
+    async fn test_monthly_fixed_policy_uses_first_of_month_rate() {
+        let test_db = TestDb::new().await;
+        let state: AppState = test_app_state(&test_db);
+        let org = org_with_rate(
+            &test_db,
+            json!({ "exchange_rates": { "lookup_policy": "monthly_fixed" } }),
+        )
+        .await;
+
+        // The 8 March rate is not the month's booking rate
+        let (status, body) = create_eur_invoice(&state, &org, "2025-03-15").await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body["error"], "no_exchange_rate");
+
+        add_eur_rate(&test_db, &org, 1, Decimal::new(105, 2)).await;
+        let (status, body) = create_eur_invoice(&state, &org, "2025-03-31").await;
+        assert_eq!(status, StatusCode::CREATED);
+        assert_eq!(body["entries"][0]["exchange_rate"], "1.0500000000");
+        assert_eq!(body["entries"][0]["functional_amount"], "105.0000");
+        assert_eq!(body["entries"][0]["is_override"], false);
+        assert!(body.get("warnings").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_rate_override_is_flagged_and_logged() {
+        let test_db = TestDb::new().await;
+        let state: AppState = test_app_state(&test_db);
+        let org = OrgFixture::new()
+            .with_fiscal_year_2025()
+            .with_accounts(&[("1000", AccountType::Asset), ("4000", AccountType::Revenue)])
+            .with_member(UserRole::Accountant)
+            .with_member(UserRole::Submitter)
+            .create(test_db.conn())
+            .await;
+        add_eur_rate(&test_db, &org, 8, Decimal::new(110, 2)).await;
+        let body = json!({
+            "type": "invoice",
+            "transaction_date": "2025-03-15",
+            "description": "EUR invoice at the treasury rate",
+            "entries": [
+                { "account_id": org.account("1000"), "source_currency": "EUR", "source_amount": "100.00", "entry_type": "debit", "override_exchange_rate": "1.2" },
+                { "account_id": org.account("4000"), "source_currency": "USD", "source_amount": "120.00", "entry_type": "credit" }
+            ]
+        });
+
+        let submitter = org.member(&UserRole::Submitter);
+        let (status, rejected) = post_transaction_as(&state, &org, submitter, body.clone()).await;
+        assert_eq!(status, StatusCode::FORBIDDEN);
+        assert_eq!(rejected["error"], "forbidden");
+
+        let accountant = org.member(&UserRole::Accountant);
+        let (status, created) = post_transaction_as(&state, &org, accountant, body).await;
+        assert_eq!(status, StatusCode::CREATED);
+        assert_eq!(created["entries"][0]["exchange_rate"], "1.2000000000");
+        assert_eq!(created["entries"][0]["functional_amount"], "120.0000");
+        assert_eq!(created["entries"][0]["is_override"], true);
+        assert_eq!(created["entries"][1]["is_override"], false);
+        // The overridden rate is 7 days old but was not used
+        assert!(created.get("warnings").is_none());
+
+        let entry_id: Uuid = serde_json::from_value(created["entries"][0]["id"].clone()).unwrap();
+        let logged = exchange_rate_overrides::Entity::find()
+            .all(test_db.conn())
+            .await
+            .unwrap();
+        assert_eq!(logged.len(), 1);
+        assert_eq!(logged[0].ledger_entry_id, entry_id);
+        assert_eq!(logged[0].looked_up_rate, Some(Decimal::new(110, 2)));
+        assert_eq!(logged[0].override_rate, Decimal::new(12, 1));
+        assert_eq!(
+            logged[0].rate_date,
+            NaiveDate::from_ymd_opt(2025, 3, 15).unwrap()
+        );
+        assert_eq!(
+            logged[0].overridden_by,
+            Some(accountant.user_id.into_inner())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_currency_imbalance_rejected_in_strict_mode() {
+        let test_db = TestDb::new().await;
+        let state: AppState = test_app_state(&test_db);
+        let lenient = org_with_rate(&test_db, json!({})).await;
+        let strict =
+            org_with_rate(&test_db, json!({ "currency_balance": { "strict": true } })).await;
+        // Balanced in USD at the 1.10 rate, but not within either currency
+        let mixed = |org: &Org| {
+            json!({
+                "type": "journal",
+                "transaction_date": "2025-03-15",
+                "description": "Mixed currency sale",
+                "entries": [
+                    { "account_id": org.account("1000"), "source_currency": "EUR", "source_amount": "100.00", "entry_type": "debit" },
+                    { "account_id": org.account("4000"), "source_currency": "USD", "source_amount": "110.00", "entry_type": "credit" }
+                ]
+            })
+        };
+
+        let (status, _) = post_transaction(&state, &lenient, mixed(&lenient)).await;
+        assert_eq!(status, StatusCode::CREATED);
+
+        let (status, body) = post_transaction(&state, &strict, mixed(&strict)).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body["error"], "unbalanced_currency");
+        assert_eq!(body["imbalances"][0]["currency"], "EUR");
+        assert_eq!(body["imbalances"][0]["difference"], "100.00");
+        assert_eq!(body["imbalances"][1]["currency"], "USD");
+        assert_eq!(body["imbalances"][1]["difference"], "-110.00");
+
+        let (status, _) = create_eur_invoice(&state, &strict, "2025-03-15").await;
+        assert_eq!(status, StatusCode::CREATED);
+    }
+
+    #[tokio::test]
+    async fn test_over_precise_amounts_rounded_or_rejected() {
+        let test_db = TestDb::new().await;
+        let mut state: AppState = test_app_state(&test_db);
+        state.currencies = Arc::new(
+            CurrencyRepository::new(test_db.conn().clone())
+                .registry()
+                .await
+                .unwrap(),
+        );
+        let lenient = org_with_rate(&test_db, json!({})).await;
+        let strict =
+            org_with_rate(&test_db, json!({ "amount_precision": { "strict": true } })).await;
+        let sale = |org: &Org| {
+            json!({
+                "type": "journal",
+                "transaction_date": "2025-03-15",
+                "description": "Cash sale",
+                "entries": [
+                    { "account_id": org.account("1000"), "source_currency": "USD", "source_amount": "10.005", "entry_type": "debit" },
+                    { "account_id": org.account("4000"), "source_currency": "USD", "source_amount": "10.005", "entry_type": "credit" }
+                ]
+            })
+        };
+
+        let (status, body) = post_transaction(&state, &lenient, sale(&lenient)).await;
+        assert_eq!(status, StatusCode::CREATED);
+        // Half to even
+        assert_eq!(body["entries"][0]["source_amount"], "10.0000");
+        assert_eq!(body["total_debit"], "10.00");
+
+        let (status, body) = post_transaction(&state, &strict, sale(&strict)).await;
+        assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(body["error"], "amount_too_precise");
+        assert_eq!(body["currency"], "USD");
+        assert_eq!(body["decimal_places"], 2);
+    }
+
+    #[tokio::test]
+    async fn test_duplicate_rejected_unless_allowed() {
+        let test_db = TestDb::new().await;
+        let state: AppState = test_app_state(&test_db);
+        let org = org_with_rate(&test_db, json!({})).await;
+        let sale = |amount: &str, allow_duplicate: bool| {
+            json!({
+                "type": "journal",
+                "transaction_date": "2025-03-15",
+                "description": "Cash sale",
+                "allow_duplicate": allow_duplicate,
+                "entries": [
+                    { "account_id": org.account("1000"), "source_currency": "USD", "source_amount": amount, "entry_type": "debit" },
+                    { "account_id": org.account("4000"), "source_currency": "USD", "source_amount": amount, "entry_type": "credit" }
+                ]
+            })
+        };
+
+        let (status, first) = post_transaction(&state, &org, sale("250.00", false)).await;
+        assert_eq!(status, StatusCode::CREATED);
+
+        let (status, body) = post_transaction(&state, &org, sale("250", false)).await;
+        assert_eq!(status, StatusCode::CONFLICT);
+        assert_eq!(body["error"], "possible_duplicate");
+        assert_eq!(body["duplicate_transaction_ids"], json!([first["id"]]));
+
+        let (status, _) = post_transaction(&state, &org, sale("250.01", false)).await;
+        assert_eq!(status, StatusCode::CREATED);
+
+        let (status, second) = post_transaction(&state, &org, sale("250.00", true)).await;
+        assert_eq!(status, StatusCode::CREATED);
+        assert_ne!(second["id"], first["id"]);
+    }
+
+    /// Updates the posting flags of an organization's account.
+    async fn set_account_flags(
+        test_db: &TestDb,
+        org: &Org,
+        code: &str,
+        is_active: bool,
+        allow_direct_posting: bool,
+    ) {
+        chart_of_accounts::ActiveModel {
+            id: Set(org.account(code).into_inner()),
+            is_active: Set(is_active),
+            allow_direct_posting: Set(allow_direct_posting),
+            ..Default::default()
+        }
+        .update(test_db.conn())
+        .await
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_entry_accounts_and_dimensions_are_validated() {
+        let test_db = TestDb::new().await;
+        let state: AppState = test_app_state(&test_db);
+        let org = org_with_rate(&test_db, json!({})).await;
+        let other = OrgFixture::new()
+            .with_accounts(&[("1000", AccountType::Asset)])
+            .create(test_db.conn())
+            .await;
+        let sale = |debit_account: Uuid, dimensions: Vec<Uuid>| {
+            json!({
+                "type": "journal",
+                "transaction_date": "2025-03-15",
+                "description": "Cash sale",
+                "entries": [
+                    { "account_id": debit_account, "source_currency": "USD", "source_amount": "10", "entry_type": "debit", "dimensions": dimensions },
+                    { "account_id": org.account("4000"), "source_currency": "USD", "source_amount": "10", "entry_type": "credit" }
+                ]
+            })
+        };
+        let cash = org.account("1000").into_inner();
+
+        let (status, body) = post_transaction(
+            &state,
+            &org,
+            sale(other.account("1000").into_inner(), vec![]),
+        )
+        .await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body["error"], "account_not_found");
+
+        let (status, body) = post_transaction(&state, &org, sale(cash, vec![Uuid::new_v4()])).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body["error"], "invalid_dimension");
+
+        set_account_flags(&test_db, &org, "4000", true, false).await;
+        let (status, body) = post_transaction(&state, &org, sale(cash, vec![])).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body["error"], "direct_posting_not_allowed");
+        assert_eq!(
+            body["message"],
+            "Account 4000 does not allow direct posting"
+        );
+
+        set_account_flags(&test_db, &org, "1000", false, true).await;
+        let (status, body) = post_transaction(&state, &org, sale(cash, vec![])).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body["error"], "account_inactive");
+        assert_eq!(body["message"], "Account 1000 is inactive");
+    }
+
+    #[tokio::test]
+    async fn test_actions_for_pending_transaction() {
+        let test_db = TestDb::new().await;
+        let state: AppState = test_app_state(&test_db);
+        let org = org_with_rate(&test_db, json!({})).await;
+        let (_, created) = create_eur_invoice(&state, &org, "2025-03-15").await;
+        let transaction_id: Uuid = serde_json::from_value(created["id"].clone()).unwrap();
+        WorkflowRepository::new(test_db.conn().clone())
+            .submit_transaction(
+                org.id,
+                transaction_id.into(),
+                org.owner.user_id.into_inner(),
+            )
+            .await
+            .unwrap();
+
+        let uri = format!(
+            "/organizations/{}/transactions/{transaction_id}/actions",
+            org.id
+        );
+        let response = get_as(&state, &org, &org.owner, &uri).await;
+        assert_eq!(response.status, StatusCode::OK);
+        let body = response.json();
+
+        assert_eq!(body["status"], "pending");
+        let action = |name: &str| {
+            body["actions"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .find(|a| a["action"] == name)
+                .cloned()
+                .unwrap()
+        };
+        assert_eq!(action("approve")["allowed"], true);
+        assert_eq!(action("reject")["allowed"], true);
+        assert_eq!(action("submit")["allowed"], false);
+        assert_eq!(
+            action("post")["reason"],
+            "cannot post a pending transaction"
+        );
+    }
+
+    /// GETs `/transactions/{transaction_id}/{view}` as the owner.
+    async fn get_transaction_view(
+        state: &AppState,
+        org: &Org,
+        transaction_id: Uuid,
+        view: &str,
+    ) -> (StatusCode, serde_json::Value) {
+        let uri = format!(
+            "/organizations/{}/transactions/{transaction_id}/{view}",
+            org.id
+        );
+        let response = get_as(state, org, &org.owner, &uri).await;
+        (response.status, response.json())
+    }
+
+    #[tokio::test]
+    async fn test_reversal_check_of_voided_foreign_currency_invoice() {
+        let test_db = TestDb::new().await;
+        let state: AppState = test_app_state(&test_db);
+        let org = org_with_rate(&test_db, json!({})).await;
+        let (_, created) = create_eur_invoice(&state, &org, "2025-03-15").await;
+        let transaction_id: Uuid = serde_json::from_value(created["id"].clone()).unwrap();
+        post_draft(test_db.conn(), &org, transaction_id.into()).await;
+
+        let (status, body) =
+            get_transaction_view(&state, &org, transaction_id, "reversal-check").await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body["error"], "no_reversal");
+
+        let voided = WorkflowRepository::new(test_db.conn().clone())
+            .void_transaction(
+                org.id,
+                transaction_id.into(),
+                org.owner.user_id.into_inner(),
+                VoidReasonCode::WrongAmount,
+                String::new(),
+            )
+            .await
+            .unwrap();
+        let reversal_id = voided.reversing_transaction.id;
+
+        // Either side of the pair gives the same comparison
+        for id in [transaction_id, reversal_id] {
+            let (status, body) = get_transaction_view(&state, &org, id, "reversal-check").await;
+            assert_eq!(status, StatusCode::OK);
+            assert_eq!(body["original_transaction_id"], json!(transaction_id));
+            assert_eq!(body["reversal_transaction_id"], json!(reversal_id));
+            assert_eq!(body["is_exact_mirror"], true);
+            assert_eq!(body["matched"].as_array().unwrap().len(), 2);
+            assert_eq!(body["mismatched"], json!([]));
+            assert_eq!(body["only_in_original"], json!([]));
+            assert_eq!(body["only_in_reversal"], json!([]));
+        }
+    }
+
+    /// Gets a voucher as `member` and returns the status, content type and
+    /// body.
+    async fn get_voucher(
+        state: &AppState,
+        org: &Org,
+        member: &Member,
+        transaction_id: Uuid,
+        format: &str,
+    ) -> (StatusCode, String, String) {
+        let uri = format!(
+            "/organizations/{}/transactions/{transaction_id}/voucher?format={format}",
+            org.id
+        );
+        let response = get_as(state, org, member, &uri).await;
+        let content_type = response
+            .header(CONTENT_TYPE.as_str())
+            .unwrap_or_default()
+            .to_string();
+        (
+            response.status,
+            content_type,
+            String::from_utf8_lossy(&response.body).into_owned(),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_voucher_renders_pdf_and_html() {
+        let test_db = TestDb::new().await;
+        let state: AppState = test_app_state(&test_db);
+        let org = OrgFixture::new()
+            .with_member(UserRole::Submitter)
+            .with_fiscal_year_2025()
+            .with_accounts(&[("1000", AccountType::Asset), ("4000", AccountType::Revenue)])
+            .create(test_db.conn())
+            .await;
+        let line = |account: &str, entry_type: &str| {
+            json!({
+                "account_id": org.account(account),
+                "source_currency": "USD",
+                "source_amount": "1000000.00",
+                "entry_type": entry_type
+            })
+        };
+        let (status, created) = post_transaction(
+            &state,
+            &org,
+            json!({
+                "type": "invoice",
+                "transaction_date": "2025-03-15",
+                "description": "Annual licence",
+                "reference_number": "INV-7",
+                "entries": [line("1000", "debit"), line("4000", "credit")]
+            }),
+        )
+        .await;
+        assert_eq!(status, StatusCode::CREATED);
+        let transaction_id: Uuid = serde_json::from_value(created["id"].clone()).unwrap();
+
+        let (status, content_type, pdf) =
+            get_voucher(&state, &org, &org.owner, transaction_id, "pdf").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(content_type, "application/pdf");
+        assert!(pdf.starts_with("%PDF-"));
+        assert!(pdf.contains("(INV-7) Tj"));
+        assert!(pdf.contains("(One million and 00/100 US dollars) Tj"));
+
+        let (status, content_type, html) =
+            get_voucher(&state, &org, &org.owner, transaction_id, "html").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(content_type, "text/html; charset=utf-8");
+        assert!(html.contains("<td>INV-7</td>"));
+        assert!(html.contains("<td class=\"num\">1,000,000.00</td>"));
+        assert!(html.contains("One million and 00/100 US dollars"));
+        assert!(!html.contains("class=\"void\""));
+
+        let (status, _, _) = get_voucher(&state, &org, &org.owner, transaction_id, "docx").await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        let (status, _, _) = get_voucher(&state, &org, &org.owner, Uuid::new_v4(), "pdf").await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+
+        // Submitters only print their own transactions
+        let submitter = org.member(&UserRole::Submitter);
+        let (status, _, body) = get_voucher(&state, &org, submitter, transaction_id, "pdf").await;
+        assert_eq!(status, StatusCode::FORBIDDEN);
+        assert!(body.contains("transaction_access_restricted"));
+    }
+
+    /// Voids a transaction as the owner and returns the status and body.
+    async fn void_request(
+        state: &AppState,
+        org: &Org,
+        transaction_id: Uuid,
+        body: serde_json::Value,
+    ) -> (StatusCode, serde_json::Value) {
+        let token = access_token(&state.jwt_service, org.id, &org.owner);
+        let uri = format!(
+            "/organizations/{}/transactions/{transaction_id}/void",
+            org.id
+        );
+        send(state.authenticated(routes()), "POST", &uri, &token, body).await
+    }
+
+    #[tokio::test]
+    async fn test_void_moves_attachments_to_reentry() {
+        use zeltra_core::attachment::{
+            AttachmentRepository as _, AttachmentType, CreateAttachmentInput,
+        };
+
+        let test_db = TestDb::new().await;
+        let state: AppState = test_app_state(&test_db);
+        let org = org_with_rate(&test_db, json!({})).await;
+        let (_, created) = create_eur_invoice(&state, &org, "2025-03-15").await;
+        let original: Uuid = serde_json::from_value(created["id"].clone()).unwrap();
+        // A day later, so it is not held as a possible duplicate
+        let (_, created) = create_eur_invoice(&state, &org, "2025-03-16").await;
+        let reentry: Uuid = serde_json::from_value(created["id"].clone()).unwrap();
+        post_draft(test_db.conn(), &org, original.into()).await;
+        let user_id = org.owner.user_id.into_inner();
+        let attachments = AttachmentRepository::new(test_db.conn().clone());
+        let id = Uuid::new_v4();
+        attachments
+            .create(CreateAttachmentInput {
+                id,
+                organization_id: org.id.into_inner(),
+                transaction_id: Some(original),
+                ledger_entry_id: None,
+                attachment_type: AttachmentType::Receipt,
+                filename: "receipt.pdf".to_string(),
+                file_size: 1024,
+                mime_type: "application/pdf".to_string(),
+                checksum_sha256: None,
+                storage_provider: "local".to_string(),
+                storage_bucket: "local".to_string(),
+                storage_key: format!("{}/{id}/receipt.pdf", org.id),
+                storage_region: None,
+                uploaded_by: user_id,
+            })
+            .await
+            .unwrap();
+
+        // A bad target is rejected before anything is voided
+        let (status, body) = void_request(
+            &state,
+            &org,
+            original,
+            json!({"reason_code": "wrong_amount", "move_attachments_to": original}),
+        )
+        .await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body["error"], "invalid_move_target");
+        let (status, body) = void_request(
+            &state,
+            &org,
+            original,
+            json!({"reason_code": "wrong_amount", "move_attachments_to": Uuid::new_v4()}),
+        )
+        .await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        assert_eq!(body["error"], "transaction_not_found");
+
+        let (status, body) = void_request(
+            &state,
+            &org,
+            original,
+            json!({"reason_code": "wrong_amount", "move_attachments_to": reentry}),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["original_transaction"]["status"], "voided");
+        assert_eq!(body["attachments_moved"], 1);
+        let moved = attachments
+            .find_by_id(id, org.id.into_inner())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(moved.transaction_id, Some(reentry));
+        assert_eq!(moved.moved_from_transaction_id, Some(original));
+    }
+
+    #[tokio::test]
+    async fn test_history_lists_rejected_round() {
+        let test_db = TestDb::new().await;
+        let state: AppState = test_app_state(&test_db);
+        let org = org_with_rate(&test_db, json!({})).await;
+        let (_, created) = create_eur_invoice(&state, &org, "2025-03-15").await;
+        let transaction_id: Uuid = serde_json::from_value(created["id"].clone()).unwrap();
+        let user_id = org.owner.user_id.into_inner();
+        let workflow = WorkflowRepository::new(test_db.conn().clone());
+        for _ in 0..2 {
+            workflow
+                .submit_transaction(org.id, transaction_id.into(), user_id)
+                .await
+                .unwrap();
+            workflow
+                .reject_transaction(
+                    org.id,
+                    transaction_id.into(),
+                    user_id,
+                    "Missing receipt".to_string(),
+                )
+                .await
+                .unwrap();
+        }
+
+        let (status, body) = get_transaction_view(&state, &org, transaction_id, "history").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["status"], "draft");
+        let history = body["history"].as_array().unwrap();
+        let steps: Vec<_> = history
+            .iter()
+            .map(|h| (h["from_status"].as_str(), h["to_status"].as_str()))
+            .collect();
+        assert_eq!(
+            steps,
+            [
+                (Some("draft"), Some("pending")),
+                (Some("pending"), Some("draft")),
+                (Some("draft"), Some("pending")),
+                (Some("pending"), Some("draft")),
+            ]
+        );
+        assert_eq!(history[1]["notes"], "Missing receipt");
+        assert_eq!(history[1]["actor_id"], json!(user_id));
+    }
+
+    /// Sends a request with no payload as `member` and returns the status
+    /// and body, `null` when empty.
+    async fn send_as(
+        state: &AppState,
+        org: &Org,
+        member: &Member,
+        method: &str,
+        uri: &str,
+    ) -> (StatusCode, serde_json::Value) {
+        let token = access_token(&state.jwt_service, org.id, member);
+        let app = state.authenticated(routes());
+        send(app, method, uri, &token, serde_json::Value::Null).await
+    }
+
+    #[tokio::test]
+    async fn test_claim_is_listed_blocks_others_and_releases() {
+        let test_db = TestDb::new().await;
+        let state: AppState = test_app_state(&test_db);
+        let org = OrgFixture::new()
+            .with_fiscal_year_2025()
+            .with_accounts(&[("1000", AccountType::Asset), ("4000", AccountType::Revenue)])
+            .with_member(UserRole::Approver)
+            .create(test_db.conn())
+            .await;
+        let approver = &org.members[0];
+        let body = json!({
+            "type": "invoice",
+            "transaction_date": "2025-03-15",
+            "description": "Consulting",
+            "entries": [
+                {"account_id": org.account("1000"), "source_currency": "USD", "source_amount": "100.00", "entry_type": "debit"},
+                {"account_id": org.account("4000"), "source_currency": "USD", "source_amount": "100.00", "entry_type": "credit"}
+            ]
+        });
+        let (_, created) = post_transaction(&state, &org, body).await;
+        let transaction_id: Uuid = serde_json::from_value(created["id"].clone()).unwrap();
+        WorkflowRepository::new(test_db.conn().clone())
+            .submit_transaction(
+                org.id,
+                transaction_id.into(),
+                org.owner.user_id.into_inner(),
+            )
+            .await
+            .unwrap();
+        let claim_uri = format!(
+            "/organizations/{}/transactions/{transaction_id}/claim",
+            org.id
+        );
+        let pending_uri = format!("/organizations/{}/transactions/pending", org.id);
+
+        let (status, claim) = send_as(&state, &org, approver, "POST", &claim_uri).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(claim["reviewer_id"], json!(approver.user_id.into_inner()));
+        assert!(claim["reviewing_until"].is_string());
+
+        let (status, conflict) = send_as(&state, &org, &org.owner, "POST", &claim_uri).await;
+        assert_eq!(status, StatusCode::CONFLICT);
+        assert_eq!(conflict["error"], "claimed");
+        assert_eq!(conflict["reviewer_name"], claim["reviewer_name"]);
+
+        let (_, pending) = send_as(&state, &org, &org.owner, "GET", &pending_uri).await;
+        assert_eq!(
+            pending["data"][0]["claim"]["reviewer_id"],
+            claim["reviewer_id"]
+        );
+
+        let (status, _) = send_as(&state, &org, approver, "DELETE", &claim_uri).await;
+        assert_eq!(status, StatusCode::NO_CONTENT);
+        let (_, pending) = send_as(&state, &org, &org.owner, "GET", &pending_uri).await;
+        assert!(pending["data"][0]["claim"].is_null());
+    }
+
+    #[tokio::test]
+    #[allow(clippy::too_many_lines)]
+    async fn test_budget_overrun_warns_or_blocks_submission() {
+        let test_db = TestDb::new().await;
+        let state: AppState = test_app_state(&test_db);
+        let org = OrgFixture::new()
+            .with_fiscal_year_2025()
+            .with_accounts(&[("1000", AccountType::Asset), ("6100", AccountType::Expense)])
+            .create(test_db.conn())
+            .await;
+        let org_id = org.id.into_inner();
+        let user_id = org.owner.user_id.into_inner();
+        let fiscal_year = org.fiscal_year.as_ref().unwrap();
+        let budgets = BudgetRepository::new(test_db.conn().clone());
+        let budget = budgets
+            .create_budget(CreateBudgetInput {
+                organization_id: org_id,
+                fiscal_year_id: fiscal_year.id.into_inner(),
+                name: "Travel".to_string(),
+                description: None,
+                budget_type: BudgetType::Monthly,
+                currency: "USD".to_string(),
+                created_by: user_id,
+            })
+            .await
+            .unwrap();
+        budgets
+            .replace_lines(
+                org_id,
+                budget.id,
+                user_id,
+                vec![CreateBudgetLineInput {
+                    account_id: org.account("6100").into_inner(),
+                    fiscal_period_id: fiscal_year.periods[2].into_inner(),
+                    amount: Decimal::new(100, 0),
+                    notes: None,
+                    dimensions: vec![],
+                }],
+            )
+            .await
+            .unwrap();
+        let submit = async |amount: &str| {
+            let (status, created) = post_transaction(
+                &state,
+                &org,
+                json!({
+                    "type": "expense",
+                    "transaction_date": "2025-03-12",
+                    "description": "Flights",
+                    "entries": [
+                        { "account_id": org.account("6100"), "source_currency": "USD", "source_amount": amount, "entry_type": "debit" },
+                        { "account_id": org.account("1000"), "source_currency": "USD", "source_amount": amount, "entry_type": "credit" }
+                    ]
+                }),
+            )
+            .await;
+            assert_eq!(status, StatusCode::CREATED);
+            let uri = format!(
+                "/organizations/{}/transactions/{}/submit",
+                org.id,
+                created["id"].as_str().unwrap()
+            );
+            let (status, body) = send_as(&state, &org, &org.owner, "POST", &uri).await;
+            (status, body, created["id"].clone())
+        };
+        let amount =
+            |value: &serde_json::Value| Decimal::from_str(value.as_str().unwrap()).unwrap();
+
+        let (status, body, _) = submit("60.00").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["budget_warning"], false);
+        assert!(body.get("warnings").is_none());
+
+        // Warn mode: submitted, with the line it takes past its budget
+        let (status, body, id) = submit("150.00").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["status"], "pending");
+        assert_eq!(body["budget_warning"], true);
+        let warning = &body["warnings"][0];
+        assert_eq!(warning["code"], "budget_threshold_exceeded");
+        let line = &warning["budget_line"];
+        assert_eq!(line["budget_id"], json!(budget.id));
+        assert_eq!(line["account_code"], "6100");
+        assert_eq!(line["period_name"], "March 2025");
+        assert_eq!(amount(&line["remaining"]), Decimal::new(100, 0));
+        assert_eq!(amount(&line["impact"]), Decimal::new(150, 0));
+        assert_eq!(
+            amount(&line["projected_utilization_percent"]),
+            Decimal::new(150, 0)
+        );
+        let (_, pending) = send_as(
+            &state,
+            &org,
+            &org.owner,
+            "GET",
+            &format!("/organizations/{}/transactions/pending", org.id),
+        )
+        .await;
+        let flagged: Vec<_> = pending["data"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .filter(|t| t["budget_warning"] == true)
+            .map(|t| t["id"].clone())
+            .collect();
+        assert_eq!(flagged, [id]);
+
+        // Block mode: rejected, and left as a draft
+        OrganizationRepository::new(test_db.conn().clone())
+            .update_settings(org_id, &json!({ "budget_enforcement": { "strict": true } }))
+            .await
+            .unwrap();
+        let (status, body, id) = submit("120.00").await;
+        assert_eq!(status, StatusCode::CONFLICT);
+        assert_eq!(body["error"], "budget_exceeded");
+        let line = &body["budget_lines"][0];
+        assert_eq!(amount(&line["budgeted"]), Decimal::new(100, 0));
+        assert_eq!(amount(&line["impact"]), Decimal::new(120, 0));
+        let id: Uuid = serde_json::from_value(id).unwrap();
+        let (status, body) = get_transaction_view(&state, &org, id, "actions").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["status"], "draft");
+    }
+
+    #[tokio::test]
+    async fn test_submitter_pending_queue_lists_only_own_transactions() {
+        let test_db = TestDb::new().await;
+        let state: AppState = test_app_state(&test_db);
+        let org = OrgFixture::new()
+            .with_fiscal_year_2025()
+            .with_accounts(&[("1000", AccountType::Asset), ("4000", AccountType::Revenue)])
+            .with_member(UserRole::Submitter)
+            .create(test_db.conn())
+            .await;
+        let submitter = org.member(&UserRole::Submitter);
+        let body = |amount: &str| {
+            json!({
+                "type": "invoice",
+                "transaction_date": "2025-03-15",
+                "description": "Consulting",
+                "entries": [
+                    {"account_id": org.account("1000"), "source_currency": "USD", "source_amount": amount, "entry_type": "debit"},
+                    {"account_id": org.account("4000"), "source_currency": "USD", "source_amount": amount, "entry_type": "credit"}
+                ]
+            })
+        };
+        let workflow = WorkflowRepository::new(test_db.conn().clone());
+        let mut ids = Vec::new();
+        for (member, amount) in [(&org.owner, "100.00"), (submitter, "200.00")] {
+            let (_, created) = post_transaction_as(&state, &org, member, body(amount)).await;
+            let id: Uuid = serde_json::from_value(created["id"].clone()).unwrap();
+            workflow
+                .submit_transaction(org.id, id.into(), member.user_id.into_inner())
+                .await
+                .unwrap();
+            ids.push(id);
+        }
+        let uri = format!("/organizations/{}/transactions/pending", org.id);
+
+        let (status, pending) = send_as(&state, &org, submitter, "GET", &uri).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(pending["data"].as_array().unwrap().len(), 1);
+        assert_eq!(pending["data"][0]["id"], json!(ids[1]));
+
+        let (_, pending) = send_as(&state, &org, &org.owner, "GET", &uri).await;
+        assert_eq!(pending["data"].as_array().unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_submitter_cannot_see_actions_of_others_transactions() {
+        let test_db = TestDb::new().await;
+        let state: AppState = test_app_state(&test_db);
+        let org = OrgFixture::new()
+            .with_fiscal_year_2025()
+            .with_accounts(&[("1000", AccountType::Asset), ("4000", AccountType::Revenue)])
+            .with_member(UserRole::Submitter)
+            .create(test_db.conn())
+            .await;
+        let submitter = org.member(&UserRole::Submitter);
+        let body = |amount: &str| {
+            json!({
+                "type": "invoice",
+                "transaction_date": "2025-03-15",
+                "description": "Consulting",
+                "entries": [
+                    {"account_id": org.account("1000"), "source_currency": "USD", "source_amount": amount, "entry_type": "debit"},
+                    {"account_id": org.account("4000"), "source_currency": "USD", "source_amount": amount, "entry_type": "credit"}
+                ]
+            })
+        };
+        let mut uris = Vec::new();
+        for (member, amount) in [(&org.owner, "100.00"), (submitter, "200.00")] {
+            let (_, created) = post_transaction_as(&state, &org, member, body(amount)).await;
+            uris.push(format!(
+                "/organizations/{}/transactions/{}/actions",
+                org.id,
+                created["id"].as_str().unwrap()
+            ));
+        }
+
+        let (status, actions) = send_as(&state, &org, submitter, "GET", &uris[0]).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        assert_eq!(actions["error"], "not_found");
+
+        let (status, actions) = send_as(&state, &org, submitter, "GET", &uris[1]).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(actions["status"], "draft");
+
+        let (status, _) = send_as(&state, &org, &org.owner, "GET", &uris[0]).await;
+        assert_eq!(status, StatusCode::OK);
+    }
+}
@@ -0,0 +1,351 @@
+//! Transfer routes.
+//!
+//! A transfer moves money between two of the organization's cash or bank
+//! accounts. The handler picks the debit and credit sides so callers cannot
+//! invert them, then saves a draft through the same path as
+//! `POST /organizations/{org_id}/transactions`.
+
+use axum::{
+    Json, Router,
+    extract::{Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+    routing::post,
+};
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use serde::Deserialize;
+use serde_json::json;
+use std::str::FromStr;
+use tracing::{error, info};
+use uuid::Uuid;
+
+use crate::{
+    AppState,
+    middleware::{AuthUser, auth::check_membership},
+    routes::transactions::{
+        CreateEntryRequest, CreateTransactionRequest, create_draft_transaction,
+    },
+};
+use zeltra_db::{
+    entities::{
+        chart_of_accounts,
+        sea_orm_active_enums::{AccountSubtype, AccountType},
+    },
+    repositories::account::AccountRepository,
+};
+use zeltra_shared::types::OrganizationId;
+
+/// Creates the transfer routes.
+pub fn routes() -> Router<AppState> {
+    Router::new().route("/organizations/{org_id}/transfers", post(create_transfer))
+}
+
+/// Request body for a transfer.
+#[derive(Debug, Deserialize)]
+pub struct CreateTransferRequest {
+    /// Account the money leaves (credited).
+    pub from_account_id: Uuid,
+    /// Account the money arrives in (debited).
+    pub to_account_id: Uuid,
+    /// Amount moved (positive).
+    pub amount: String,
+    /// Currency of the amount; both accounts must be held in it.
+    pub currency: String,
+    /// Transfer date (YYYY-MM-DD).
+    pub date: NaiveDate,
+    /// Optional memo.
+    pub memo: Option<String>,
+    /// Create the transfer even if it looks like a duplicate.
+    #[serde(default)]
+    pub allow_duplicate: bool,
+}
+
+/// Whether money can be transferred into or out of the account.
+fn is_transfer_account(account: &chart_of_accounts::Model) -> bool {
+    account.account_type == AccountType::Asset
+        && (account.is_bank_account
+            || matches!(
+                account.account_subtype,
+                Some(AccountSubtype::Cash | AccountSubtype::Bank)
+            ))
+}
+
+/// Loads one side of a transfer and checks it can take part in one.
+async fn transfer_account(
+    repo: &AccountRepository,
+    org_id: OrganizationId,
+    account_id: Uuid,
+) -> Result<chart_of_accounts::Model, axum::response::Response> {
+    let account = match repo.find_account_by_id(account_id.into()).await {
+        Ok(Some(a)) if a.account.organization_id == org_id.into_inner() => a.account,
+        Ok(_) => {
+            return Err((
+                StatusCode::BAD_REQUEST,
+                Json(json!({
+                    "error": "account_not_found",
+                    "message": format!("Account not found: {}", account_id)
+                })),
+            )
+                .into_response());
+        }
+        Err(e) => {
+            error!(error = %e, "Failed to get transfer account");
+            return Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({
+                    "error": "internal_error",
+                    "message": "An error occurred"
+                })),
+            )
+                .into_response());
+        }
+    };
+
+    if !account.is_active {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(json!({
+                "error": "account_inactive",
+                "message": format!("Account {} is inactive", account.code)
+            })),
+        )
+            .into_response());
+    }
+
+    if !is_transfer_account(&account) {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(json!({
+                "error": "invalid_transfer_account",
+                "message": format!("Account {} is not a cash or bank account", account.code)
+            })),
+        )
+            .into_response());
+    }
+
+    Ok(account)
+}
+
+/// POST `/organizations/{org_id}/transfers` - Move money between two cash or bank accounts.
+///
+/// Debits the destination and credits the source, and returns the draft
+/// transaction as `POST /transactions` does.
+async fn create_transfer(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(org_id): Path<OrganizationId>,
+    Json(payload): Json<CreateTransferRequest>,
+) -> impl IntoResponse {
+    if let Err(response) = check_membership(&state, org_id, auth.user_id()).await {
+        return response;
+    }
+
+    if payload.from_account_id == payload.to_account_id {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(json!({
+                "error": "same_account_transfer",
+                "message": "Cannot transfer from an account to itself"
+            })),
+        )
+            .into_response();
+    }
+
+    if !Decimal::from_str(&payload.amount).is_ok_and(|amount| amount > Decimal::ZERO) {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(json!({
+                "error": "invalid_amount",
+                "message": "Transfer amount must be a positive number"
+            })),
+        )
+            .into_response();
+    }
+
+    let repo = AccountRepository::new((*state.db).clone());
+    let from = match transfer_account(&repo, org_id, payload.from_account_id).await {
+        Ok(account) => account,
+        Err(response) => return response,
+    };
+    let to = match transfer_account(&repo, org_id, payload.to_account_id).await {
+        Ok(account) => account,
+        Err(response) => return response,
+    };
+
+    // A cross-currency transfer needs an amount on each side; one amount
+    // cannot describe it.
+    if from.currency != to.currency {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(json!({
+                "error": "cross_currency_transfer",
+                "message": format!(
+                    "Cannot transfer between {} ({}) and {} ({})",
+                    from.code, from.currency, to.code, to.currency
+                )
+            })),
+        )
+            .into_response();
+    }
+    if payload.currency != from.currency {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(json!({
+                "error": "currency_mismatch",
+                "message": format!(
+                    "Transfer currency {} does not match account currency {}",
+                    payload.currency, from.currency
+                )
+            })),
+        )
+            .into_response();
+    }
+
+    let entry = |account_id: Uuid, entry_type: &str| CreateEntryRequest {
+        account_id,
+        source_currency: payload.currency.clone(),
+        source_amount: payload.amount.clone(),
+        entry_type: entry_type.to_string(),
+        memo: payload.memo.clone(),
+        dimensions: vec![],
+        event_at: None,
+        override_exchange_rate: None,
+    };
+    let request = CreateTransactionRequest {
+        transaction_type: "transfer".to_string(),
+        transaction_date: payload.date,
+        description: format!("Transfer from {} to {}", from.name, to.name),
+        reference_number: None,
+        memo: payload.memo.clone(),
+        entries: vec![entry(to.id, "debit"), entry(from.id, "credit")],
+        allow_duplicate: payload.allow_duplicate,
+    };
+
+    info!(from = %from.id, to = %to.id, "Creating transfer");
+    create_draft_transaction(&state, auth.user_id(), org_id, request).await
+}
+
+#[cfg(test)]
+mod integration_tests {
+    use super::*;
+    use sea_orm::{ColumnTrait, EntityTrait, QueryFilter, sea_query::Expr};
+    use zeltra_db::repositories::account::UpdateAccountInput;
+    use zeltra_test_support::{Org, OrgFixture, TestDb, access_token, send, test_app_state};
+
+    async fn transfer(
+        state: &AppState,
+        org: &Org,
+        body: serde_json::Value,
+    ) -> (StatusCode, serde_json::Value) {
+        let token = access_token(&state.jwt_service, org.id, &org.owner);
+        let uri = format!("/organizations/{}/transfers", org.id);
+        send(state.authenticated(routes()), "POST", &uri, &token, body).await
+    }
+
+    async fn org_with_bank_accounts(test_db: &TestDb) -> Org {
+        let org = OrgFixture::new()
+            .with_fiscal_year_2025()
+            .with_accounts(&[
+                ("1010", AccountType::Asset),
+                ("1020", AccountType::Asset),
+                ("1200", AccountType::Asset),
+            ])
+            .create(test_db.conn())
+            .await;
+        let repo = AccountRepository::new(test_db.conn().clone());
+        for code in ["1010", "1020"] {
+            repo.update_account(
+                org.account(code),
+                UpdateAccountInput {
+                    account_subtype: Some(Some(AccountSubtype::Bank)),
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
+        }
+        org
+    }
+
+    fn body(org: &Org, from: &str, to: &str, amount: &str) -> serde_json::Value {
+        json!({
+            "from_account_id": org.account(from),
+            "to_account_id": org.account(to),
+            "amount": amount,
+            "currency": "USD",
+            "date": "2025-03-10",
+            "memo": "Fund payroll"
+        })
+    }
+
+    /// Money arrives in the destination, a debit to an asset account, and
+    /// leaves the source, a credit to an asset account.
+    #[tokio::test]
+    async fn test_transfer_debits_destination_and_credits_source() {
+        let test_db = TestDb::new().await;
+        let state: AppState = test_app_state(&test_db);
+        let org = org_with_bank_accounts(&test_db).await;
+
+        let (status, response) = transfer(&state, &org, body(&org, "1010", "1020", "5000")).await;
+
+        assert_eq!(status, StatusCode::CREATED, "{response}");
+        assert_eq!(response["type"], "transfer");
+        assert_eq!(response["status"], "draft");
+        assert_eq!(response["memo"], "Fund payroll");
+        let entries = response["entries"].as_array().unwrap();
+        assert_eq!(entries.len(), 2);
+        let entry_for = |code: &str| {
+            let id = org.account(code).to_string();
+            entries
+                .iter()
+                .find(|e| e["account_id"] == id.as_str())
+                .unwrap()
+        };
+        // Zero comes back from the database without its scale
+        let amount =
+            |value: &serde_json::Value| Decimal::from_str(value.as_str().unwrap()).unwrap();
+        let destination = entry_for("1020");
+        assert_eq!(destination["debit"], "5000.0000");
+        assert_eq!(amount(&destination["credit"]), Decimal::ZERO);
+        let source = entry_for("1010");
+        assert_eq!(amount(&source["debit"]), Decimal::ZERO);
+        assert_eq!(source["credit"], "5000.0000");
+    }
+
+    #[tokio::test]
+    async fn test_invalid_transfers_are_rejected() {
+        let test_db = TestDb::new().await;
+        let state: AppState = test_app_state(&test_db);
+        let org = org_with_bank_accounts(&test_db).await;
+
+        let (status, response) = transfer(&state, &org, body(&org, "1010", "1010", "10")).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(response["error"], "same_account_transfer");
+
+        let (status, response) = transfer(&state, &org, body(&org, "1010", "1020", "-10")).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(response["error"], "invalid_amount");
+
+        // Plain asset accounts are not cash or bank accounts
+        let (status, response) = transfer(&state, &org, body(&org, "1010", "1200", "10")).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(response["error"], "invalid_transfer_account");
+
+        let mut other = body(&org, "1010", "1020", "10");
+        other["to_account_id"] = json!(Uuid::new_v4());
+        let (status, response) = transfer(&state, &org, other).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(response["error"], "account_not_found");
+
+        chart_of_accounts::Entity::update_many()
+            .col_expr(chart_of_accounts::Column::Currency, Expr::value("EUR"))
+            .filter(chart_of_accounts::Column::Id.eq(org.account("1020").into_inner()))
+            .exec(test_db.conn())
+            .await
+            .unwrap();
+        let (status, response) = transfer(&state, &org, body(&org, "1010", "1020", "10")).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(response["error"], "cross_currency_transfer");
+    }
+}
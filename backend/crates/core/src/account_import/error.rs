@@ -0,0 +1,31 @@
+//! Chart of accounts import error types.
+
+use thiserror::Error;
+
+/// Errors that make a whole chart of accounts file unreadable.
+///
+/// Problems with individual rows are reported as
+/// [`ImportIssue`](super::ImportIssue)s instead.
+#[derive(Debug, Clone, Error, PartialEq, Eq)]
+pub enum AccountImportError {
+    /// The file has no header row.
+    #[error("Chart of accounts file is empty")]
+    EmptyFile,
+
+    /// The header row has an unterminated quoted field.
+    #[error("Header row has an unterminated quoted field")]
+    InvalidHeader,
+
+    /// A required column is missing from the header row.
+    #[error("Missing required column: {0}")]
+    MissingColumn(String),
+
+    /// The file has more rows than one import accepts.
+    #[error("Too many rows: {got}, at most {max} per import")]
+    TooManyRows {
+        /// Number of data rows in the file.
+        got: usize,
+        /// Maximum rows accepted.
+        max: usize,
+    },
+}
@@ -0,0 +1,97 @@
+//! Parent code resolution and parent-first ordering.
+
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::hash::BuildHasher;
+
+use super::types::{HierarchyNode, ImportIssue};
+
+/// Orders imported accounts so every parent comes before its children.
+///
+/// A parent code may name another row in the file, before or after the
+/// child, or an account already in the chart (`existing_codes`). Returns
+/// indices into `nodes`; rows without an in-file parent keep their file
+/// order.
+///
+/// # Errors
+///
+/// Returns one issue per row, sorted by row, whose parent cannot be found,
+/// which is part of a parent cycle, or which descends from such a row.
+pub fn order_by_hierarchy<S: BuildHasher>(
+    nodes: &[HierarchyNode<'_>],
+    existing_codes: &HashSet<String, S>,
+) -> Result<Vec<usize>, Vec<ImportIssue>> {
+    let mut index: HashMap<&str, usize> = HashMap::with_capacity(nodes.len());
+    for (idx, node) in nodes.iter().enumerate() {
+        index.entry(node.code).or_insert(idx);
+    }
+
+    let mut issues = Vec::new();
+    let mut parent_of: Vec<Option<usize>> = vec![None; nodes.len()];
+    let mut children: Vec<Vec<usize>> = vec![Vec::new(); nodes.len()];
+    let mut queue = VecDeque::new();
+    for (idx, node) in nodes.iter().enumerate() {
+        match node.parent_code {
+            Some(parent) if index.contains_key(parent) => {
+                parent_of[idx] = Some(index[parent]);
+                children[index[parent]].push(idx);
+            }
+            Some(parent) if !existing_codes.contains(parent) => {
+                issues.push(ImportIssue::new(
+                    node.row,
+                    "parent_code",
+                    format!(
+                        "Parent account '{parent}' was not found in the file or the chart of accounts"
+                    ),
+                ));
+            }
+            _ => queue.push_back(idx),
+        }
+    }
+
+    let mut order = Vec::with_capacity(nodes.len());
+    while let Some(idx) = queue.pop_front() {
+        order.push(idx);
+        queue.extend(children[idx].iter().copied());
+    }
+
+    if order.len() + issues.len() < nodes.len() {
+        let mut placed = vec![false; nodes.len()];
+        for &idx in &order {
+            placed[idx] = true;
+        }
+        for (idx, node) in nodes.iter().enumerate() {
+            let Some(parent) = parent_of[idx] else {
+                continue;
+            };
+            if placed[idx] {
+                continue;
+            }
+            let message = if in_cycle(idx, &parent_of) {
+                format!("Account '{}' is part of a parent cycle", node.code)
+            } else {
+                format!("Parent account '{}' has errors", nodes[parent].code)
+            };
+            issues.push(ImportIssue::new(node.row, "parent_code", message));
+        }
+    }
+
+    if issues.is_empty() {
+        Ok(order)
+    } else {
+        issues.sort_by_key(|issue| issue.row);
+        Err(issues)
+    }
+}
+
+/// Whether following parents from `start` leads back to it.
+fn in_cycle(start: usize, parent_of: &[Option<usize>]) -> bool {
+    let mut current = parent_of[start];
+    for _ in 0..parent_of.len() {
+        match current {
+            Some(idx) if idx == start => return true,
+            Some(idx) => current = parent_of[idx],
+            None => return false,
+        }
+    }
+    false
+}
@@ -0,0 +1,19 @@
+//! Chart of accounts import.
+//!
+//! This module turns a chart of accounts CSV into rows ready to create:
+//! - CSV parsing with per-row, per-column validation issues
+//! - Parent code resolution, including parents defined later in the file
+//! - Parent-first ordering so every parent exists before its children
+
+pub mod error;
+pub mod hierarchy;
+pub mod parser;
+pub mod types;
+
+#[cfg(test)]
+mod tests;
+
+pub use error::AccountImportError;
+pub use hierarchy::order_by_hierarchy;
+pub use parser::parse_chart_csv;
+pub use types::{HierarchyNode, ImportIssue, ImportRow, ParsedChart};
@@ -0,0 +1,178 @@
+//! Chart of accounts CSV parsing.
+//!
+//! The first row is a header naming the columns. `code`, `name`, `type` and
+//! `currency` are required; `subtype`, `parent_code` and
+//! `allow_direct_posting` are optional, and column order is free. Quoting
+//! follows the bank statement parser.
+
+use std::collections::HashSet;
+
+use super::error::AccountImportError;
+use super::types::{ImportIssue, ImportRow, ParsedChart};
+use crate::bank_import::parser::split_row;
+
+/// Maximum number of data rows accepted in one import.
+pub const MAX_IMPORT_ROWS: usize = 2000;
+
+/// Maximum length of an account code.
+pub const MAX_CODE_LENGTH: usize = 20;
+
+/// Maximum length of an account name.
+pub const MAX_NAME_LENGTH: usize = 255;
+
+/// Parses a chart of accounts CSV.
+///
+/// Every row is checked and all problems are collected, so one pass reports
+/// the whole file. A row with any problem is left out of
+/// [`ParsedChart::rows`]. A code used more than once is reported on every
+/// row after the first.
+///
+/// # Errors
+///
+/// Returns an error if the file is empty, lacks a required column or has
+/// more than [`MAX_IMPORT_ROWS`] rows.
+#[allow(clippy::too_many_lines)]
+pub fn parse_chart_csv(input: &str) -> Result<ParsedChart, AccountImportError> {
+    let mut lines = input
+        .lines()
+        .enumerate()
+        .map(|(idx, line)| (idx + 1, line.trim_end_matches('\r')))
+        .filter(|(_, line)| !line.trim().is_empty());
+
+    let (header_line, header) = lines.next().ok_or(AccountImportError::EmptyFile)?;
+    let columns: Vec<String> = split_row(header, header_line)
+        .map_err(|_| AccountImportError::InvalidHeader)?
+        .into_iter()
+        .map(|c| c.trim().to_lowercase())
+        .collect();
+    let position = |name: &str| columns.iter().position(|c| c == name);
+    let required = |name: &str| {
+        position(name).ok_or_else(|| AccountImportError::MissingColumn(name.to_string()))
+    };
+    let code_idx = required("code")?;
+    let name_idx = required("name")?;
+    let type_idx = required("type")?;
+    let currency_idx = required("currency")?;
+    let subtype_idx = position("subtype");
+    let parent_idx = position("parent_code");
+    let posting_idx = position("allow_direct_posting");
+
+    let lines: Vec<(usize, &str)> = lines.collect();
+    if lines.len() > MAX_IMPORT_ROWS {
+        return Err(AccountImportError::TooManyRows {
+            got: lines.len(),
+            max: MAX_IMPORT_ROWS,
+        });
+    }
+
+    let mut parsed = ParsedChart::default();
+    let mut seen_codes = HashSet::new();
+    for (row, line) in lines {
+        let Ok(fields) = split_row(line, row) else {
+            parsed
+                .issues
+                .push(ImportIssue::new(row, "code", "Unterminated quoted field"));
+            continue;
+        };
+        let field = |idx: usize| fields.get(idx).map_or("", |f| f.trim());
+        let optional = |idx: Option<usize>| {
+            idx.map(field)
+                .filter(|f| !f.is_empty())
+                .map(ToString::to_string)
+        };
+        let issues_before = parsed.issues.len();
+        let mut issue = |column: &str, message: String| {
+            parsed.issues.push(ImportIssue::new(row, column, message));
+        };
+
+        let code = field(code_idx).to_string();
+        if let Err(message) = check_code(&code) {
+            issue("code", message);
+        } else if !seen_codes.insert(code.clone()) {
+            issue(
+                "code",
+                format!("Code '{code}' appears more than once in the file"),
+            );
+        }
+
+        let name = field(name_idx).to_string();
+        if name.is_empty() {
+            issue("name", "Name is required".to_string());
+        } else if name.chars().count() > MAX_NAME_LENGTH {
+            issue(
+                "name",
+                format!("Name is longer than {MAX_NAME_LENGTH} characters"),
+            );
+        }
+
+        let account_type = field(type_idx).to_lowercase();
+        if account_type.is_empty() {
+            issue("type", "Type is required".to_string());
+        }
+
+        let currency = field(currency_idx).to_uppercase();
+        if currency.len() != 3 || !currency.chars().all(|c| c.is_ascii_alphabetic()) {
+            issue(
+                "currency",
+                format!("Invalid currency '{currency}', expected a 3-letter code"),
+            );
+        }
+
+        let parent_code = optional(parent_idx);
+        if parent_code.as_deref() == Some(code.as_str()) {
+            issue(
+                "parent_code",
+                "An account cannot be its own parent".to_string(),
+            );
+        }
+
+        let allow_direct_posting = match optional(posting_idx) {
+            None => true,
+            Some(value) => match value.to_lowercase().as_str() {
+                "true" | "yes" | "1" => true,
+                "false" | "no" | "0" => false,
+                _ => {
+                    issue(
+                        "allow_direct_posting",
+                        format!("Invalid value '{value}', expected true or false"),
+                    );
+                    true
+                }
+            },
+        };
+
+        if parsed.issues.len() == issues_before {
+            parsed.rows.push(ImportRow {
+                row,
+                code,
+                name,
+                account_type,
+                subtype: optional(subtype_idx).map(|s| s.to_lowercase()),
+                parent_code,
+                currency,
+                allow_direct_posting,
+            });
+        }
+    }
+
+    Ok(parsed)
+}
+
+/// Checks an account code: 1 to 20 letters, digits, dots or dashes.
+fn check_code(code: &str) -> Result<(), String> {
+    if code.is_empty() {
+        return Err("Code is required".to_string());
+    }
+    if code.len() > MAX_CODE_LENGTH {
+        return Err(format!("Code is longer than {MAX_CODE_LENGTH} characters"));
+    }
+    if !code
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || c == '.' || c == '-')
+    {
+        return Err(format!(
+            "Invalid code '{code}', use letters, digits, dots or dashes"
+        ));
+    }
+    Ok(())
+}
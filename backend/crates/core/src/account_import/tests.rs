@@ -0,0 +1,131 @@
+//! Tests for chart of accounts parsing and hierarchy ordering.
+
+use std::collections::HashSet;
+
+use super::error::AccountImportError;
+use super::hierarchy::order_by_hierarchy;
+use super::parser::parse_chart_csv;
+use super::types::{HierarchyNode, ImportIssue};
+
+fn node<'a>(row: usize, code: &'a str, parent_code: Option<&'a str>) -> HierarchyNode<'a> {
+    HierarchyNode {
+        row,
+        code,
+        parent_code,
+    }
+}
+
+// ============================================================================
+// Parser
+// ============================================================================
+
+#[test]
+fn test_parse_chart() {
+    let csv = "code,name,type,subtype,parent_code,currency,allow_direct_posting\n\
+               1000,Assets,Asset,,,usd,false\n\
+               1100,\"Cash, on hand\",asset,cash,1000,USD,\n";
+
+    let parsed = parse_chart_csv(csv).unwrap();
+    assert!(parsed.issues.is_empty());
+    assert_eq!(parsed.rows.len(), 2);
+    let assets = &parsed.rows[0];
+    assert_eq!(assets.row, 2);
+    assert_eq!(assets.account_type, "asset");
+    assert_eq!(assets.currency, "USD");
+    assert_eq!(assets.subtype, None);
+    assert!(!assets.allow_direct_posting);
+    let cash = &parsed.rows[1];
+    assert_eq!(cash.name, "Cash, on hand");
+    assert_eq!(cash.subtype.as_deref(), Some("cash"));
+    assert_eq!(cash.parent_code.as_deref(), Some("1000"));
+    assert!(cash.allow_direct_posting);
+}
+
+#[test]
+fn test_parse_reports_every_issue_with_row_and_column() {
+    let csv = "name,code,type,currency,allow_direct_posting\n\
+               Cash,1100,asset,USD,\n\
+               ,1100,asset,US,maybe\n\
+               Bank,11 00,asset,USD,\n";
+
+    let parsed = parse_chart_csv(csv).unwrap();
+    assert_eq!(parsed.rows.len(), 1);
+    assert_eq!(
+        parsed.issues,
+        vec![
+            ImportIssue::new(3, "code", "Code '1100' appears more than once in the file"),
+            ImportIssue::new(3, "name", "Name is required"),
+            ImportIssue::new(
+                3,
+                "currency",
+                "Invalid currency 'US', expected a 3-letter code"
+            ),
+            ImportIssue::new(
+                3,
+                "allow_direct_posting",
+                "Invalid value 'maybe', expected true or false"
+            ),
+            ImportIssue::new(
+                4,
+                "code",
+                "Invalid code '11 00', use letters, digits, dots or dashes"
+            ),
+        ]
+    );
+}
+
+#[test]
+fn test_parse_file_errors() {
+    assert_eq!(parse_chart_csv("\n\n"), Err(AccountImportError::EmptyFile));
+    assert_eq!(
+        parse_chart_csv("code,name,currency\n1000,Cash,USD\n"),
+        Err(AccountImportError::MissingColumn("type".to_string()))
+    );
+}
+
+// ============================================================================
+// Hierarchy
+// ============================================================================
+
+#[test]
+fn test_parent_defined_after_child() {
+    let nodes = [
+        node(2, "1110", Some("1100")),
+        node(3, "1100", Some("1000")),
+        node(4, "1000", None),
+        node(5, "6100", Some("6000")),
+    ];
+    let existing: HashSet<String> = HashSet::from(["6000".to_string()]);
+
+    let order = order_by_hierarchy(&nodes, &existing).unwrap();
+    let codes: Vec<&str> = order.iter().map(|&idx| nodes[idx].code).collect();
+    assert_eq!(codes, vec!["1000", "6100", "1100", "1110"]);
+}
+
+#[test]
+fn test_cycle_rejected() {
+    let nodes = [
+        node(2, "1000", None),
+        node(3, "1100", Some("1200")),
+        node(4, "1200", Some("1100")),
+        node(5, "1210", Some("1200")),
+        node(6, "1300", Some("9999")),
+        node(7, "1310", Some("1300")),
+    ];
+
+    let issues = order_by_hierarchy(&nodes, &HashSet::new()).unwrap_err();
+    assert_eq!(
+        issues,
+        vec![
+            ImportIssue::new(3, "parent_code", "Account '1100' is part of a parent cycle"),
+            ImportIssue::new(4, "parent_code", "Account '1200' is part of a parent cycle"),
+            ImportIssue::new(5, "parent_code", "Parent account '1200' has errors"),
+            ImportIssue::new(
+                6,
+                "parent_code",
+                "Parent account '9999' was not found in the file or the chart of accounts"
+            ),
+            ImportIssue::new(7, "parent_code", "Parent account '1300' has errors"),
+        ]
+    );
+}
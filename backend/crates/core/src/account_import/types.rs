@@ -0,0 +1,70 @@
+//! Chart of accounts import data types.
+
+use serde::{Deserialize, Serialize};
+
+/// A parsed chart of accounts row.
+///
+/// Type, subtype and currency are kept as written; they are checked against
+/// the database enums and currencies by the caller.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ImportRow {
+    /// 1-based line number in the source file (header is line 1).
+    pub row: usize,
+    /// Account code.
+    pub code: String,
+    /// Account name.
+    pub name: String,
+    /// Account type, lowercased.
+    pub account_type: String,
+    /// Account subtype, lowercased.
+    pub subtype: Option<String>,
+    /// Code of the parent account, in the file or already in the chart.
+    pub parent_code: Option<String>,
+    /// Currency code, uppercased.
+    pub currency: String,
+    /// Whether direct posting is allowed (default: true).
+    pub allow_direct_posting: bool,
+}
+
+/// A problem with one column of one row.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ImportIssue {
+    /// 1-based line number in the source file.
+    pub row: usize,
+    /// Column the problem is in.
+    pub column: String,
+    /// What is wrong.
+    pub message: String,
+}
+
+impl ImportIssue {
+    /// Creates an issue for a row and column.
+    #[must_use]
+    pub fn new(row: usize, column: &str, message: impl Into<String>) -> Self {
+        Self {
+            row,
+            column: column.to_string(),
+            message: message.into(),
+        }
+    }
+}
+
+/// Result of parsing a chart of accounts file.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ParsedChart {
+    /// Every data row that could be read, in file order.
+    pub rows: Vec<ImportRow>,
+    /// Problems found while reading, in file order.
+    pub issues: Vec<ImportIssue>,
+}
+
+/// An account to place in the hierarchy.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HierarchyNode<'a> {
+    /// 1-based line number in the source file.
+    pub row: usize,
+    /// Account code.
+    pub code: &'a str,
+    /// Code of the parent account.
+    pub parent_code: Option<&'a str>,
+}
@@ -16,6 +16,14 @@ pub enum AttachmentError {
     #[error("transaction not found: {0}")]
     TransactionNotFound(Uuid),
 
+    /// Transaction is voided and cannot take attachments.
+    #[error("transaction is voided: {0}")]
+    TransactionVoided(Uuid),
+
+    /// Ledger entry not found in the transaction.
+    #[error("ledger entry not found in transaction: {0}")]
+    EntryNotFound(Uuid),
+
     /// Upload not verified - file not found in storage.
     #[error("upload not verified: file not found in storage")]
     UploadNotVerified,
@@ -68,6 +76,18 @@ impl AttachmentError {
         Self::TransactionNotFound(id)
     }
 
+    /// Create a transaction voided error.
+    #[must_use]
+    pub fn transaction_voided(id: Uuid) -> Self {
+        Self::TransactionVoided(id)
+    }
+
+    /// Create a ledger entry not found error.
+    #[must_use]
+    pub fn entry_not_found(id: Uuid) -> Self {
+        Self::EntryNotFound(id)
+    }
+
     /// Create a file size mismatch error.
     #[must_use]
     pub fn file_size_mismatch(expected: u64, actual: u64) -> Self {
@@ -6,12 +6,20 @@
 //! - Upload confirmation
 //! - Download URL generation
 //! - Attachment deletion
+//! - OCR extraction of receipts and invoices
 
 mod error;
+mod ocr;
 mod service;
 mod types;
 
 pub use error::AttachmentError;
+#[cfg(feature = "ocr-http")]
+pub use ocr::HttpOcrProvider;
+pub use ocr::{
+    ExtractedDocument, ExtractedLineItem, ExtractionRecord, OcrError, OcrProvider, StubOcrProvider,
+    is_ocr_candidate,
+};
 pub use service::{AttachmentRepository, AttachmentService};
 pub use types::{
     Attachment, AttachmentType, ConfirmUploadInput, CreateAttachmentInput, RequestUploadInput,
@@ -0,0 +1,331 @@
+//! OCR extraction of receipt and invoice attachments.
+//!
+//! An [`OcrProvider`] turns a stored file into an [`ExtractedDocument`].
+//! The outcome of each attempt is kept on the attachment as an
+//! [`ExtractionRecord`] so failures are not retried forever.
+
+use std::future::Future;
+
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use super::types::AttachmentType;
+
+/// OCR provider errors.
+#[derive(Debug, Error)]
+pub enum OcrError {
+    /// The provider does not handle this file type.
+    #[error("unsupported MIME type for OCR: {0}")]
+    UnsupportedMimeType(String),
+
+    /// The provider could not be reached or is temporarily failing.
+    #[error("OCR provider unavailable: {0}")]
+    Unavailable(String),
+
+    /// The provider refused the document.
+    #[error("OCR provider rejected the document: {0}")]
+    Rejected(String),
+
+    /// The provider answered with something that is not a document.
+    #[error("invalid OCR response: {0}")]
+    InvalidResponse(String),
+}
+
+impl OcrError {
+    /// Whether the same document may succeed on a later attempt.
+    #[must_use]
+    pub fn is_transient(&self) -> bool {
+        matches!(self, Self::Unavailable(_))
+    }
+}
+
+/// One line item read from a document.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ExtractedLineItem {
+    /// Item description.
+    pub description: String,
+    /// Quantity, if printed.
+    #[serde(default)]
+    pub quantity: Option<Decimal>,
+    /// Line amount, if printed.
+    #[serde(default)]
+    pub amount: Option<Decimal>,
+}
+
+/// Fields read from a receipt or invoice.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ExtractedDocument {
+    /// Vendor or merchant name.
+    #[serde(default)]
+    pub vendor: Option<String>,
+    /// Document date.
+    #[serde(default)]
+    pub date: Option<NaiveDate>,
+    /// Grand total.
+    #[serde(default)]
+    pub total: Option<Decimal>,
+    /// ISO 4217 currency code of the total.
+    #[serde(default)]
+    pub currency: Option<String>,
+    /// Line items.
+    #[serde(default)]
+    pub line_items: Vec<ExtractedLineItem>,
+    /// Provider confidence in the extraction, from 0 to 1.
+    #[serde(default)]
+    pub confidence: f64,
+}
+
+impl ExtractedDocument {
+    /// Returns the trimmed vendor name, if any.
+    #[must_use]
+    pub fn vendor_name(&self) -> Option<&str> {
+        self.vendor
+            .as_deref()
+            .map(str::trim)
+            .filter(|v| !v.is_empty())
+    }
+}
+
+/// Stored outcome of an extraction attempt.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum ExtractionRecord {
+    /// The provider returned a document.
+    Completed(ExtractedDocument),
+    /// The provider failed permanently for this file.
+    Failed {
+        /// Why extraction failed.
+        error: String,
+    },
+}
+
+/// Extracts structured data from document files.
+pub trait OcrProvider: Send + Sync {
+    /// Short provider name for logs.
+    fn name(&self) -> &'static str;
+
+    /// Extracts fields from a file.
+    fn extract(
+        &self,
+        bytes: &[u8],
+        mime_type: &str,
+    ) -> impl Future<Output = Result<ExtractedDocument, OcrError>> + Send;
+}
+
+/// Returns whether an attachment should be sent for OCR.
+///
+/// Only receipts and invoices stored as PDF or images qualify.
+#[must_use]
+pub fn is_ocr_candidate(attachment_type: AttachmentType, mime_type: &str) -> bool {
+    matches!(
+        attachment_type,
+        AttachmentType::Receipt | AttachmentType::Invoice
+    ) && (mime_type == "application/pdf" || mime_type.starts_with("image/"))
+}
+
+/// Provider that returns a fixed document without reading the file.
+///
+/// Used when no OCR service is configured and in tests.
+#[derive(Debug, Clone, Default)]
+pub struct StubOcrProvider {
+    document: ExtractedDocument,
+}
+
+impl StubOcrProvider {
+    /// Creates a stub that returns `document` for every file.
+    #[must_use]
+    pub fn new(document: ExtractedDocument) -> Self {
+        Self { document }
+    }
+}
+
+impl OcrProvider for StubOcrProvider {
+    fn name(&self) -> &'static str {
+        "stub"
+    }
+
+    async fn extract(
+        &self,
+        _bytes: &[u8],
+        _mime_type: &str,
+    ) -> Result<ExtractedDocument, OcrError> {
+        Ok(self.document.clone())
+    }
+}
+
+/// Provider that posts files to an external OCR endpoint.
+///
+/// The file is sent as the request body with its MIME type as
+/// `Content-Type`; the endpoint must answer with an [`ExtractedDocument`]
+/// as JSON.
+#[cfg(feature = "ocr-http")]
+#[derive(Debug, Clone)]
+pub struct HttpOcrProvider {
+    client: reqwest::Client,
+    endpoint: String,
+    api_key: Option<String>,
+}
+
+#[cfg(feature = "ocr-http")]
+impl HttpOcrProvider {
+    /// Creates a provider for `endpoint`, sending `api_key` as a bearer token.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the HTTP client cannot be built.
+    pub fn new(
+        endpoint: impl Into<String>,
+        api_key: Option<String>,
+        timeout: std::time::Duration,
+    ) -> Result<Self, OcrError> {
+        let client = reqwest::Client::builder()
+            .timeout(timeout)
+            .build()
+            .map_err(|e| OcrError::Unavailable(e.to_string()))?;
+        Ok(Self {
+            client,
+            endpoint: endpoint.into(),
+            api_key,
+        })
+    }
+}
+
+#[cfg(feature = "ocr-http")]
+impl OcrProvider for HttpOcrProvider {
+    fn name(&self) -> &'static str {
+        "http"
+    }
+
+    async fn extract(&self, bytes: &[u8], mime_type: &str) -> Result<ExtractedDocument, OcrError> {
+        let mut request = self
+            .client
+            .post(&self.endpoint)
+            .header(reqwest::header::CONTENT_TYPE, mime_type)
+            .body(bytes.to_vec());
+        if let Some(key) = &self.api_key {
+            request = request.bearer_auth(key);
+        }
+
+        let response = request
+            .send()
+            .await
+            .map_err(|e| OcrError::Unavailable(e.to_string()))?;
+        let status = response.status();
+        if status.is_server_error() || status == reqwest::StatusCode::TOO_MANY_REQUESTS {
+            return Err(OcrError::Unavailable(format!("endpoint returned {status}")));
+        }
+        if status == reqwest::StatusCode::UNSUPPORTED_MEDIA_TYPE {
+            return Err(OcrError::UnsupportedMimeType(mime_type.to_string()));
+        }
+        if !status.is_success() {
+            return Err(OcrError::Rejected(format!("endpoint returned {status}")));
+        }
+
+        response
+            .json::<ExtractedDocument>()
+            .await
+            .map_err(|e| OcrError::InvalidResponse(e.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+    use rust_decimal_macros::dec;
+
+    #[rstest]
+    #[case(AttachmentType::Receipt, "image/jpeg", true)]
+    #[case(AttachmentType::Invoice, "application/pdf", true)]
+    #[case(AttachmentType::Receipt, "text/csv", false)]
+    #[case(AttachmentType::Contract, "application/pdf", false)]
+    #[case(AttachmentType::Other, "image/png", false)]
+    fn test_ocr_candidates(
+        #[case] attachment_type: AttachmentType,
+        #[case] mime_type: &str,
+        #[case] expected: bool,
+    ) {
+        assert_eq!(is_ocr_candidate(attachment_type, mime_type), expected);
+    }
+
+    #[test]
+    fn test_record_round_trips_with_status_tag() {
+        let record = ExtractionRecord::Completed(ExtractedDocument {
+            vendor: Some("Blue Bottle".to_string()),
+            date: NaiveDate::from_ymd_opt(2025, 6, 10),
+            total: Some(dec!(12.50)),
+            currency: Some("USD".to_string()),
+            line_items: vec![ExtractedLineItem {
+                description: "Latte".to_string(),
+                quantity: Some(dec!(2)),
+                amount: Some(dec!(12.50)),
+            }],
+            confidence: 0.9,
+        });
+
+        let json = serde_json::to_value(&record).unwrap();
+        assert_eq!(json["status"], "completed");
+        assert_eq!(json["vendor"], "Blue Bottle");
+        assert_eq!(
+            serde_json::from_value::<ExtractionRecord>(json).unwrap(),
+            record
+        );
+
+        let failed = serde_json::to_value(ExtractionRecord::Failed {
+            error: "unreadable".to_string(),
+        })
+        .unwrap();
+        assert_eq!(
+            failed,
+            serde_json::json!({"status": "failed", "error": "unreadable"})
+        );
+    }
+
+    #[test]
+    fn test_partial_provider_response_parses() {
+        let doc: ExtractedDocument =
+            serde_json::from_str(r#"{"total": "9.99", "confidence": 0.4}"#).unwrap();
+
+        assert_eq!(doc.total, Some(dec!(9.99)));
+        assert!(doc.vendor.is_none());
+        assert!(doc.line_items.is_empty());
+    }
+
+    #[test]
+    fn test_blank_vendor_is_ignored() {
+        let doc = ExtractedDocument {
+            vendor: Some("  ".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(doc.vendor_name(), None);
+
+        let doc = ExtractedDocument {
+            vendor: Some(" Acme ".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(doc.vendor_name(), Some("Acme"));
+    }
+
+    #[test]
+    fn test_only_unavailable_is_transient() {
+        assert!(OcrError::Unavailable("timeout".to_string()).is_transient());
+        assert!(!OcrError::Rejected("400".to_string()).is_transient());
+        assert!(!OcrError::InvalidResponse("eof".to_string()).is_transient());
+    }
+
+    #[tokio::test]
+    async fn test_stub_returns_configured_document() {
+        let doc = ExtractedDocument {
+            total: Some(dec!(5)),
+            ..Default::default()
+        };
+        let provider = StubOcrProvider::new(doc.clone());
+
+        assert_eq!(
+            provider.extract(b"%PDF", "application/pdf").await.unwrap(),
+            doc
+        );
+    }
+}
@@ -27,10 +27,11 @@ pub trait AttachmentRepository: Send + Sync {
         organization_id: Uuid,
     ) -> impl std::future::Future<Output = Result<Option<Attachment>, AttachmentError>> + Send;
 
-    /// List attachments for a transaction.
+    /// List attachments for a transaction, optionally only those on one entry.
     fn list_by_transaction(
         &self,
         transaction_id: Uuid,
+        ledger_entry_id: Option<Uuid>,
         organization_id: Uuid,
     ) -> impl std::future::Future<Output = Result<Vec<Attachment>, AttachmentError>> + Send;
 
@@ -47,6 +48,14 @@ pub trait AttachmentRepository: Send + Sync {
         transaction_id: Uuid,
         organization_id: Uuid,
     ) -> impl std::future::Future<Output = Result<bool, AttachmentError>> + Send;
+
+    /// Check if a ledger entry belongs to the transaction.
+    fn entry_exists(
+        &self,
+        ledger_entry_id: Uuid,
+        transaction_id: Uuid,
+        organization_id: Uuid,
+    ) -> impl std::future::Future<Output = Result<bool, AttachmentError>> + Send;
 }
 
 /// Attachment service for managing file attachments.
@@ -70,6 +79,7 @@ impl<R: AttachmentRepository> AttachmentService<R> {
     ///
     /// Returns an error if:
     /// - Transaction does not exist
+    /// - Ledger entry is given but not part of the transaction
     /// - File size exceeds limit
     /// - MIME type is not allowed
     /// - Storage service fails
@@ -87,6 +97,13 @@ impl<R: AttachmentRepository> AttachmentService<R> {
             return Err(AttachmentError::transaction_not_found(input.transaction_id));
         }
 
+        self.verify_entry(
+            input.ledger_entry_id,
+            input.transaction_id,
+            input.organization_id,
+        )
+        .await?;
+
         // Generate attachment ID
         let attachment_id = Uuid::new_v4();
 
@@ -122,6 +139,7 @@ impl<R: AttachmentRepository> AttachmentService<R> {
     /// # Errors
     ///
     /// Returns an error if:
+    /// - Ledger entry is given but not part of the transaction
     /// - File not found in storage
     /// - File size mismatch
     /// - Database operation fails
@@ -129,6 +147,13 @@ impl<R: AttachmentRepository> AttachmentService<R> {
         &self,
         input: ConfirmUploadInput,
     ) -> Result<Attachment, AttachmentError> {
+        self.verify_entry(
+            input.ledger_entry_id,
+            input.transaction_id,
+            input.organization_id,
+        )
+        .await?;
+
         // Verify file exists in storage
         let metadata = self
             .storage
@@ -152,6 +177,7 @@ impl<R: AttachmentRepository> AttachmentService<R> {
             id: input.attachment_id,
             organization_id: input.organization_id,
             transaction_id: Some(input.transaction_id),
+            ledger_entry_id: input.ledger_entry_id,
             attachment_type: input.attachment_type,
             filename: input.filename,
             file_size: input.file_size,
@@ -226,7 +252,7 @@ impl<R: AttachmentRepository> AttachmentService<R> {
         Ok(())
     }
 
-    /// List attachments for a transaction.
+    /// List attachments for a transaction, optionally only those on one entry.
     ///
     /// # Errors
     ///
@@ -234,10 +260,11 @@ impl<R: AttachmentRepository> AttachmentService<R> {
     pub async fn list_by_transaction(
         &self,
         transaction_id: Uuid,
+        ledger_entry_id: Option<Uuid>,
         organization_id: Uuid,
     ) -> Result<Vec<Attachment>, AttachmentError> {
         self.repo
-            .list_by_transaction(transaction_id, organization_id)
+            .list_by_transaction(transaction_id, ledger_entry_id, organization_id)
             .await
     }
 
@@ -256,6 +283,27 @@ impl<R: AttachmentRepository> AttachmentService<R> {
             .await?
             .ok_or_else(|| AttachmentError::not_found(attachment_id))
     }
+
+    /// Checks that `ledger_entry_id`, when given, belongs to the transaction.
+    async fn verify_entry(
+        &self,
+        ledger_entry_id: Option<Uuid>,
+        transaction_id: Uuid,
+        organization_id: Uuid,
+    ) -> Result<(), AttachmentError> {
+        let Some(entry_id) = ledger_entry_id else {
+            return Ok(());
+        };
+        if self
+            .repo
+            .entry_exists(entry_id, transaction_id, organization_id)
+            .await?
+        {
+            Ok(())
+        } else {
+            Err(AttachmentError::entry_not_found(entry_id))
+        }
+    }
 }
 
 #[cfg(test)]
@@ -270,6 +318,7 @@ mod tests {
     struct MockAttachmentRepository {
         attachments: Mutex<HashMap<Uuid, Attachment>>,
         transactions: Mutex<std::collections::HashSet<Uuid>>,
+        entries: Mutex<HashMap<Uuid, Uuid>>,
     }
 
     impl MockAttachmentRepository {
@@ -277,13 +326,20 @@ mod tests {
             Self {
                 attachments: Mutex::new(HashMap::new()),
                 transactions: Mutex::new(std::collections::HashSet::new()),
+                entries: Mutex::new(HashMap::new()),
             }
         }
 
-        #[allow(dead_code)]
         fn add_transaction(&self, id: Uuid) {
             self.transactions.lock().unwrap().insert(id);
         }
+
+        fn add_entry(&self, entry_id: Uuid, transaction_id: Uuid) {
+            self.entries
+                .lock()
+                .unwrap()
+                .insert(entry_id, transaction_id);
+        }
     }
 
     impl AttachmentRepository for MockAttachmentRepository {
@@ -295,6 +351,8 @@ mod tests {
                 id: input.id,
                 organization_id: input.organization_id,
                 transaction_id: input.transaction_id,
+                ledger_entry_id: input.ledger_entry_id,
+                moved_from_transaction_id: None,
                 attachment_type: input.attachment_type,
                 filename: input.filename,
                 file_size: input.file_size,
@@ -325,6 +383,7 @@ mod tests {
         async fn list_by_transaction(
             &self,
             transaction_id: Uuid,
+            ledger_entry_id: Option<Uuid>,
             _organization_id: Uuid,
         ) -> Result<Vec<Attachment>, AttachmentError> {
             Ok(self
@@ -333,6 +392,7 @@ mod tests {
                 .unwrap()
                 .values()
                 .filter(|a| a.transaction_id == Some(transaction_id))
+                .filter(|a| ledger_entry_id.is_none() || a.ledger_entry_id == ledger_entry_id)
                 .cloned()
                 .collect())
         }
@@ -348,6 +408,15 @@ mod tests {
         ) -> Result<bool, AttachmentError> {
             Ok(self.transactions.lock().unwrap().contains(&transaction_id))
         }
+
+        async fn entry_exists(
+            &self,
+            ledger_entry_id: Uuid,
+            transaction_id: Uuid,
+            _organization_id: Uuid,
+        ) -> Result<bool, AttachmentError> {
+            Ok(self.entries.lock().unwrap().get(&ledger_entry_id) == Some(&transaction_id))
+        }
     }
 
     #[tokio::test]
@@ -360,6 +429,7 @@ mod tests {
         let input = RequestUploadInput {
             organization_id: Uuid::new_v4(),
             transaction_id: Uuid::new_v4(),
+            ledger_entry_id: None,
             filename: "test.pdf".to_string(),
             content_type: "application/pdf".to_string(),
             file_size: 1024,
@@ -384,4 +454,74 @@ mod tests {
         let result = service.get_by_id(Uuid::new_v4(), Uuid::new_v4()).await;
         assert!(matches!(result, Err(AttachmentError::NotFound(_))));
     }
+
+    #[tokio::test]
+    async fn test_request_upload_entry_from_other_transaction() {
+        let config = StorageConfig::new(StorageProvider::local_fs("./test"));
+        let storage = Arc::new(StorageService::from_config(config).unwrap());
+        let repo = Arc::new(MockAttachmentRepository::new());
+        let (transaction_id, other_transaction_id) = (Uuid::new_v4(), Uuid::new_v4());
+        let entry_id = Uuid::new_v4();
+        repo.add_transaction(transaction_id);
+        repo.add_transaction(other_transaction_id);
+        repo.add_entry(entry_id, other_transaction_id);
+        let service = AttachmentService::new(storage, repo);
+
+        let input = RequestUploadInput {
+            organization_id: Uuid::new_v4(),
+            transaction_id,
+            ledger_entry_id: Some(entry_id),
+            filename: "receipt.pdf".to_string(),
+            content_type: "application/pdf".to_string(),
+            file_size: 1024,
+            attachment_type: AttachmentType::Receipt,
+            user_id: Uuid::new_v4(),
+        };
+
+        let result = service.request_upload(input).await;
+        assert!(matches!(result, Err(AttachmentError::EntryNotFound(id)) if id == entry_id));
+    }
+
+    #[tokio::test]
+    async fn test_list_by_transaction_filters_by_entry() {
+        let config = StorageConfig::new(StorageProvider::local_fs("./test"));
+        let storage = Arc::new(StorageService::from_config(config).unwrap());
+        let repo = Arc::new(MockAttachmentRepository::new());
+        let (organization_id, transaction_id) = (Uuid::new_v4(), Uuid::new_v4());
+        let entry_id = Uuid::new_v4();
+        for ledger_entry_id in [None, Some(entry_id), Some(Uuid::new_v4())] {
+            repo.create(CreateAttachmentInput {
+                id: Uuid::new_v4(),
+                organization_id,
+                transaction_id: Some(transaction_id),
+                ledger_entry_id,
+                attachment_type: AttachmentType::Receipt,
+                filename: "receipt.pdf".to_string(),
+                file_size: 1024,
+                mime_type: "application/pdf".to_string(),
+                checksum_sha256: None,
+                storage_provider: "local".to_string(),
+                storage_bucket: "local".to_string(),
+                storage_key: "key".to_string(),
+                storage_region: None,
+                uploaded_by: Uuid::new_v4(),
+            })
+            .await
+            .unwrap();
+        }
+        let service = AttachmentService::new(storage, repo);
+
+        let all = service
+            .list_by_transaction(transaction_id, None, organization_id)
+            .await
+            .unwrap();
+        let on_entry = service
+            .list_by_transaction(transaction_id, Some(entry_id), organization_id)
+            .await
+            .unwrap();
+
+        assert_eq!(all.len(), 3);
+        assert_eq!(on_entry.len(), 1);
+        assert_eq!(on_entry[0].ledger_entry_id, Some(entry_id));
+    }
 }
@@ -55,6 +55,8 @@ pub struct RequestUploadInput {
     pub organization_id: Uuid,
     /// Transaction ID to attach to.
     pub transaction_id: Uuid,
+    /// Ledger entry ID within the transaction, for line-level attachments.
+    pub ledger_entry_id: Option<Uuid>,
     /// Original filename.
     pub filename: String,
     /// MIME type of the file.
@@ -93,6 +95,8 @@ pub struct ConfirmUploadInput {
     pub organization_id: Uuid,
     /// Transaction ID.
     pub transaction_id: Uuid,
+    /// Ledger entry ID within the transaction, for line-level attachments.
+    pub ledger_entry_id: Option<Uuid>,
     /// Original filename.
     pub filename: String,
     /// MIME type.
@@ -116,6 +120,8 @@ pub struct CreateAttachmentInput {
     pub organization_id: Uuid,
     /// Transaction ID.
     pub transaction_id: Option<Uuid>,
+    /// Ledger entry ID (optional).
+    pub ledger_entry_id: Option<Uuid>,
     /// Attachment type.
     pub attachment_type: AttachmentType,
     /// Original filename.
@@ -147,6 +153,10 @@ pub struct Attachment {
     pub organization_id: Uuid,
     /// Transaction ID (optional).
     pub transaction_id: Option<Uuid>,
+    /// Ledger entry ID (optional).
+    pub ledger_entry_id: Option<Uuid>,
+    /// Transaction the attachment was last moved from (optional).
+    pub moved_from_transaction_id: Option<Uuid>,
     /// Attachment type.
     pub attachment_type: AttachmentType,
     /// Original filename.
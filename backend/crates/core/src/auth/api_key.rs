@@ -0,0 +1,305 @@
+//! API keys for machine-to-machine integrations.
+//!
+//! A key belongs to one organization and carries a role and a set of scopes.
+//! Keys are deny-by-default: a request is only let through when its route
+//! maps to a scope the key holds, see [`route_access`].
+
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use uuid::Uuid;
+
+use super::UserRole;
+
+/// Prefix of every API key, used to tell keys apart from JWTs.
+pub const API_KEY_PREFIX: &str = "zk_live_";
+
+/// Number of leading key characters kept for display.
+pub const API_KEY_DISPLAY_LEN: usize = 12;
+
+/// What an API key is allowed to do.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ApiKeyScope {
+    /// Read transactions.
+    #[serde(rename = "read:transactions")]
+    ReadTransactions,
+    /// Create, edit, delete and submit draft transactions.
+    #[serde(rename = "write:transactions")]
+    WriteTransactions,
+    /// Read financial reports.
+    #[serde(rename = "read:reports")]
+    ReadReports,
+}
+
+impl ApiKeyScope {
+    /// Returns the scope as its API string.
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::ReadTransactions => "read:transactions",
+            Self::WriteTransactions => "write:transactions",
+            Self::ReadReports => "read:reports",
+        }
+    }
+
+    /// Returns true if the scope only reads data.
+    #[must_use]
+    pub const fn is_read_only(self) -> bool {
+        matches!(self, Self::ReadTransactions | Self::ReadReports)
+    }
+}
+
+impl fmt::Display for ApiKeyScope {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for ApiKeyScope {
+    type Err = ApiKeyError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "read:transactions" => Ok(Self::ReadTransactions),
+            "write:transactions" => Ok(Self::WriteTransactions),
+            "read:reports" => Ok(Self::ReadReports),
+            _ => Err(ApiKeyError::UnknownScope(s.to_string())),
+        }
+    }
+}
+
+/// Role an API key acts with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ApiKeyRole {
+    /// Read-only access.
+    Viewer,
+    /// Read access and draft transaction writes.
+    Integration,
+}
+
+impl ApiKeyRole {
+    /// Returns the role as its API string.
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Viewer => "viewer",
+            Self::Integration => "integration",
+        }
+    }
+
+    /// Member role requests made with the key are checked against.
+    ///
+    /// Integration keys act as accountants so they can read the whole ledger
+    /// and draft transactions; scopes keep them away from everything else.
+    #[must_use]
+    pub const fn acting_role(self) -> UserRole {
+        match self {
+            Self::Viewer => UserRole::Viewer,
+            Self::Integration => UserRole::Accountant,
+        }
+    }
+}
+
+impl FromStr for ApiKeyRole {
+    type Err = ApiKeyError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "viewer" => Ok(Self::Viewer),
+            "integration" => Ok(Self::Integration),
+            _ => Err(ApiKeyError::UnknownRole(s.to_string())),
+        }
+    }
+}
+
+/// Why an API key's settings were rejected.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum ApiKeyError {
+    /// The scope string is not recognized.
+    #[error("Unknown API key scope: {0}")]
+    UnknownScope(String),
+
+    /// The role string is not recognized.
+    #[error("Unknown API key role: {0}")]
+    UnknownRole(String),
+
+    /// The key has no scopes.
+    #[error("An API key needs at least one scope")]
+    NoScopes,
+
+    /// A viewer key asked for a write scope.
+    #[error("Viewer keys cannot hold the {0} scope")]
+    ScopeNotAllowed(ApiKeyScope),
+}
+
+/// Checks that `role` may hold every scope in `scopes`.
+///
+/// # Errors
+///
+/// Returns an error if `scopes` is empty or a viewer key asks for a write
+/// scope.
+pub fn validate_scopes(role: ApiKeyRole, scopes: &[ApiKeyScope]) -> Result<(), ApiKeyError> {
+    if scopes.is_empty() {
+        return Err(ApiKeyError::NoScopes);
+    }
+    if role == ApiKeyRole::Viewer
+        && let Some(scope) = scopes.iter().find(|s| !s.is_read_only())
+    {
+        return Err(ApiKeyError::ScopeNotAllowed(*scope));
+    }
+    Ok(())
+}
+
+/// Returns the part of a key shown in listings.
+#[must_use]
+pub fn display_prefix(key: &str) -> &str {
+    key.get(..API_KEY_DISPLAY_LEN).unwrap_or(key)
+}
+
+/// Organization and scope an API key needs to call a route.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RouteAccess {
+    /// Organization named in the path.
+    pub organization_id: Uuid,
+    /// Scope the key must hold.
+    pub scope: ApiKeyScope,
+}
+
+/// Resolves the access an API key needs for `method` on `path`.
+///
+/// Returns `None` for routes API keys cannot call at all, which includes
+/// approving, posting and voiding transactions.
+#[must_use]
+pub fn route_access(method: &str, path: &str) -> Option<RouteAccess> {
+    let mut segments = path
+        .split('/')
+        .filter(|s| !s.is_empty())
+        .skip_while(|s| *s != "organizations")
+        .skip(1);
+    let organization_id = segments.next()?.parse().ok()?;
+    let resource = segments.next()?;
+    let rest: Vec<&str> = segments.collect();
+
+    let scope = match (resource, method, rest.as_slice()) {
+        ("transactions", "GET", _) => ApiKeyScope::ReadTransactions,
+        ("transactions", "POST", [] | [_, "submit"])
+        | ("transactions", "PATCH" | "DELETE", [_]) => ApiKeyScope::WriteTransactions,
+        ("reports", "GET", _) => ApiKeyScope::ReadReports,
+        _ => return None,
+    };
+
+    Some(RouteAccess {
+        organization_id,
+        scope,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    const ORG: &str = "0192d1f4-5c3a-7b2e-9f10-0a1b2c3d4e5f";
+
+    #[rstest]
+    #[case(
+        "GET",
+        "/organizations/{org}/transactions",
+        Some(ApiKeyScope::ReadTransactions)
+    )]
+    #[case(
+        "GET",
+        "/api/v1/organizations/{org}/transactions/summary",
+        Some(ApiKeyScope::ReadTransactions)
+    )]
+    #[case(
+        "POST",
+        "/organizations/{org}/transactions",
+        Some(ApiKeyScope::WriteTransactions)
+    )]
+    #[case(
+        "PATCH",
+        "/organizations/{org}/transactions/abc",
+        Some(ApiKeyScope::WriteTransactions)
+    )]
+    #[case(
+        "DELETE",
+        "/organizations/{org}/transactions/abc",
+        Some(ApiKeyScope::WriteTransactions)
+    )]
+    #[case(
+        "POST",
+        "/organizations/{org}/transactions/abc/submit",
+        Some(ApiKeyScope::WriteTransactions)
+    )]
+    #[case("POST", "/organizations/{org}/transactions/abc/approve", None)]
+    #[case("POST", "/organizations/{org}/transactions/abc/post", None)]
+    #[case("POST", "/organizations/{org}/transactions/bulk-void", None)]
+    #[case(
+        "GET",
+        "/organizations/{org}/reports/trial-balance",
+        Some(ApiKeyScope::ReadReports)
+    )]
+    #[case("POST", "/organizations/{org}/reports/fx-exposure/revalue", None)]
+    #[case("GET", "/organizations/{org}/accounts", None)]
+    #[case("GET", "/organizations/{org}", None)]
+    #[case("GET", "/organizations/not-a-uuid/transactions", None)]
+    #[case("GET", "/auth/me", None)]
+    fn test_route_access(
+        #[case] method: &str,
+        #[case] path: &str,
+        #[case] expected: Option<ApiKeyScope>,
+    ) {
+        let access = route_access(method, &path.replace("{org}", ORG));
+        assert_eq!(access.map(|a| a.scope), expected);
+        if let Some(access) = access {
+            assert_eq!(access.organization_id, ORG.parse::<Uuid>().unwrap());
+        }
+    }
+
+    #[test]
+    fn test_viewer_keys_hold_read_scopes_only() {
+        assert_eq!(
+            validate_scopes(
+                ApiKeyRole::Viewer,
+                &[ApiKeyScope::ReadTransactions, ApiKeyScope::ReadReports]
+            ),
+            Ok(())
+        );
+        assert_eq!(
+            validate_scopes(ApiKeyRole::Viewer, &[ApiKeyScope::WriteTransactions]),
+            Err(ApiKeyError::ScopeNotAllowed(ApiKeyScope::WriteTransactions))
+        );
+        assert_eq!(
+            validate_scopes(ApiKeyRole::Integration, &[ApiKeyScope::WriteTransactions]),
+            Ok(())
+        );
+        assert_eq!(
+            validate_scopes(ApiKeyRole::Integration, &[]),
+            Err(ApiKeyError::NoScopes)
+        );
+    }
+
+    #[test]
+    fn test_scope_strings_round_trip() {
+        for scope in [
+            ApiKeyScope::ReadTransactions,
+            ApiKeyScope::WriteTransactions,
+            ApiKeyScope::ReadReports,
+        ] {
+            assert_eq!(scope.as_str().parse::<ApiKeyScope>(), Ok(scope));
+            let json = serde_json::to_string(&scope).unwrap();
+            assert_eq!(json, format!("\"{scope}\""));
+        }
+        assert!("write:reports".parse::<ApiKeyScope>().is_err());
+    }
+
+    #[test]
+    fn test_display_prefix() {
+        assert_eq!(display_prefix("zk_live_abcdef123456"), "zk_live_abcd");
+        assert_eq!(display_prefix("zk_live"), "zk_live");
+    }
+}
@@ -4,9 +4,16 @@
 //! - Password hashing with Argon2id
 //! - Password verification
 //! - User role definitions
+//! - Supported profile locales
+//! - API key roles and scopes
 
+mod api_key;
 mod password;
 
+pub use api_key::{
+    API_KEY_DISPLAY_LEN, API_KEY_PREFIX, ApiKeyError, ApiKeyRole, ApiKeyScope, RouteAccess,
+    display_prefix, route_access, validate_scopes,
+};
 pub use password::{PasswordError, hash_password, verify_password};
 
 use serde::{Deserialize, Serialize};
@@ -53,6 +60,75 @@ impl UserRole {
     pub const fn can_modify_settings(&self) -> bool {
         matches!(self, Self::Owner | Self::Admin)
     }
+
+    /// Returns true if this role can create and edit accounts, dimension
+    /// types and dimension values.
+    ///
+    /// Deleting or deactivating them, and editing system accounts, still
+    /// needs [`Self::can_modify_settings`].
+    #[must_use]
+    pub const fn can_manage_chart_of_accounts(&self) -> bool {
+        matches!(self, Self::Owner | Self::Admin | Self::Accountant)
+    }
+
+    /// Returns true if this role can list every attachment in the
+    /// organization, e.g. to clean up storage.
+    #[must_use]
+    pub const fn can_review_attachments(&self) -> bool {
+        matches!(self, Self::Owner | Self::Admin | Self::Accountant)
+    }
+
+    /// Returns true if this role can read every transaction in the
+    /// organization. Submitters only see the transactions they created.
+    #[must_use]
+    pub const fn can_view_all_transactions(&self) -> bool {
+        !matches!(self, Self::Submitter)
+    }
+
+    /// Returns true if this role can read budgets and budget vs actual.
+    ///
+    /// Accountant or above; viewers keep their full read access.
+    #[must_use]
+    pub const fn can_view_budgets(&self) -> bool {
+        matches!(
+            self,
+            Self::Owner | Self::Admin | Self::Accountant | Self::Viewer
+        )
+    }
+
+    /// Returns true if this role can edit every budget and reassign budget
+    /// owners and editors.
+    ///
+    /// Other members edit only the budgets they own or are listed on.
+    #[must_use]
+    pub const fn can_edit_any_budget(&self) -> bool {
+        matches!(self, Self::Owner | Self::Admin | Self::Accountant)
+    }
+
+    /// Returns true if this role can read financial reports, which summarize
+    /// the whole ledger.
+    #[must_use]
+    pub const fn can_view_reports(&self) -> bool {
+        self.can_view_all_transactions()
+    }
+
+    /// Returns true if this role can create, edit and delete saved custom
+    /// report definitions.
+    #[must_use]
+    pub const fn can_manage_custom_reports(&self) -> bool {
+        matches!(self, Self::Owner | Self::Admin | Self::Accountant)
+    }
+}
+
+/// Locales a user can pick for their profile, as BCP 47 tags.
+pub const SUPPORTED_LOCALES: &[&str] = &["en-US", "en-GB", "id-ID"];
+
+/// Returns true if `locale` is one of [`SUPPORTED_LOCALES`].
+///
+/// Matching is exact, so clients must send the canonical casing.
+#[must_use]
+pub fn is_supported_locale(locale: &str) -> bool {
+    SUPPORTED_LOCALES.contains(&locale)
 }
 
 impl std::fmt::Display for UserRole {
@@ -132,6 +208,72 @@ mod tests {
         assert_eq!(role.can_modify_settings(), expected);
     }
 
+    #[rstest]
+    #[case(UserRole::Owner, true)]
+    #[case(UserRole::Admin, true)]
+    #[case(UserRole::Accountant, true)]
+    #[case(UserRole::Approver, false)]
+    #[case(UserRole::Viewer, false)]
+    #[case(UserRole::Submitter, false)]
+    fn role_can_manage_chart_of_accounts_matrix(#[case] role: UserRole, #[case] expected: bool) {
+        assert_eq!(role.can_manage_chart_of_accounts(), expected);
+    }
+
+    #[rstest]
+    #[case(UserRole::Owner, true)]
+    #[case(UserRole::Admin, true)]
+    #[case(UserRole::Accountant, true)]
+    #[case(UserRole::Approver, false)]
+    #[case(UserRole::Viewer, false)]
+    #[case(UserRole::Submitter, false)]
+    fn role_can_review_attachments_matrix(#[case] role: UserRole, #[case] expected: bool) {
+        assert_eq!(role.can_review_attachments(), expected);
+    }
+
+    #[rstest]
+    #[case(UserRole::Owner, true)]
+    #[case(UserRole::Admin, true)]
+    #[case(UserRole::Accountant, true)]
+    #[case(UserRole::Approver, true)]
+    #[case(UserRole::Viewer, true)]
+    #[case(UserRole::Submitter, false)]
+    fn role_can_view_all_transactions_matrix(#[case] role: UserRole, #[case] expected: bool) {
+        assert_eq!(role.can_view_all_transactions(), expected);
+    }
+
+    #[rstest]
+    #[case(UserRole::Owner, true)]
+    #[case(UserRole::Admin, true)]
+    #[case(UserRole::Accountant, true)]
+    #[case(UserRole::Approver, false)]
+    #[case(UserRole::Viewer, true)]
+    #[case(UserRole::Submitter, false)]
+    fn role_can_view_budgets_matrix(#[case] role: UserRole, #[case] expected: bool) {
+        assert_eq!(role.can_view_budgets(), expected);
+    }
+
+    #[rstest]
+    #[case(UserRole::Owner, true)]
+    #[case(UserRole::Admin, true)]
+    #[case(UserRole::Accountant, true)]
+    #[case(UserRole::Approver, false)]
+    #[case(UserRole::Viewer, false)]
+    #[case(UserRole::Submitter, false)]
+    fn role_can_edit_any_budget_matrix(#[case] role: UserRole, #[case] expected: bool) {
+        assert_eq!(role.can_edit_any_budget(), expected);
+    }
+
+    #[rstest]
+    #[case(UserRole::Owner, true)]
+    #[case(UserRole::Admin, true)]
+    #[case(UserRole::Accountant, true)]
+    #[case(UserRole::Approver, true)]
+    #[case(UserRole::Viewer, true)]
+    #[case(UserRole::Submitter, false)]
+    fn role_can_view_reports_matrix(#[case] role: UserRole, #[case] expected: bool) {
+        assert_eq!(role.can_view_reports(), expected);
+    }
+
     #[rstest]
     #[case(UserRole::Owner, "owner")]
     #[case(UserRole::Admin, "admin")]
@@ -155,6 +297,16 @@ mod tests {
         assert_eq!(serialized, expected);
     }
 
+    #[rstest]
+    #[case("en-US", true)]
+    #[case("id-ID", true)]
+    #[case("en-us", false)]
+    #[case("fr-FR", false)]
+    #[case("", false)]
+    fn locale_support(#[case] locale: &str, #[case] expected: bool) {
+        assert_eq!(is_supported_locale(locale), expected);
+    }
+
     #[rstest]
     #[case("owner", UserRole::Owner)]
     #[case("admin", UserRole::Admin)]
@@ -0,0 +1,56 @@
+//! Bank import error types.
+
+use thiserror::Error;
+
+/// Errors raised while parsing a bank statement.
+#[derive(Debug, Clone, Error, PartialEq, Eq)]
+pub enum BankImportError {
+    /// The statement has no header row.
+    #[error("Bank statement is empty")]
+    EmptyStatement,
+
+    /// A required column is missing from the header row.
+    #[error("Missing required column: {0}")]
+    MissingColumn(String),
+
+    /// A row has fewer columns than the header requires.
+    #[error("Line {line}: expected at least {expected} columns, got {got}")]
+    ColumnCount {
+        /// 1-based line number in the file.
+        line: usize,
+        /// Number of columns required by the header.
+        expected: usize,
+        /// Number of columns found on the line.
+        got: usize,
+    },
+
+    /// A quoted field was never closed.
+    #[error("Line {0}: unterminated quoted field")]
+    UnterminatedQuote(usize),
+
+    /// The date column could not be parsed.
+    #[error("Line {line}: invalid date '{value}', expected YYYY-MM-DD")]
+    InvalidDate {
+        /// 1-based line number in the file.
+        line: usize,
+        /// Raw date value.
+        value: String,
+    },
+
+    /// The amount column could not be parsed.
+    #[error("Line {line}: invalid amount '{value}'")]
+    InvalidAmount {
+        /// 1-based line number in the file.
+        line: usize,
+        /// Raw amount value.
+        value: String,
+    },
+
+    /// The amount is zero.
+    #[error("Line {0}: amount cannot be zero")]
+    ZeroAmount(usize),
+
+    /// The description is blank.
+    #[error("Line {0}: description is required")]
+    MissingDescription(usize),
+}
@@ -0,0 +1,173 @@
+//! Counter-account suggestion from posting history.
+//!
+//! Descriptions are normalized (lowercased, punctuation and purely numeric
+//! tokens dropped) and compared against prior posted descriptions in three
+//! tiers, stopping at the first tier that finds anything:
+//!
+//! 1. Exact match of the normalized text
+//! 2. Whole-word substring match in either direction
+//! 3. Shared keywords (tokens of at least three characters)
+//!
+//! Within a tier the account used most often wins, with ties broken by
+//! account ID so results are deterministic.
+
+use std::collections::{BTreeMap, HashSet};
+
+use uuid::Uuid;
+
+use super::types::{AccountSuggestion, HistoricalPosting, MatchKind};
+
+/// Minimum token length considered a keyword.
+const MIN_KEYWORD_LEN: usize = 3;
+
+/// A history posting with its description pre-normalized.
+#[derive(Debug, Clone)]
+struct IndexedPosting {
+    normalized: String,
+    keywords: HashSet<String>,
+    account_id: Uuid,
+}
+
+/// Suggests counter-accounts by matching descriptions against posting history.
+#[derive(Debug, Clone, Default)]
+pub struct AccountMatcher {
+    history: Vec<IndexedPosting>,
+}
+
+impl AccountMatcher {
+    /// Builds a matcher over the given history.
+    #[must_use]
+    pub fn new(history: &[HistoricalPosting]) -> Self {
+        let history = history
+            .iter()
+            .filter_map(|posting| {
+                let normalized = normalize(&posting.description);
+                (!normalized.is_empty()).then(|| IndexedPosting {
+                    keywords: keywords(&normalized),
+                    normalized,
+                    account_id: posting.account_id,
+                })
+            })
+            .collect();
+
+        Self { history }
+    }
+
+    /// Returns the number of usable history postings.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.history.len()
+    }
+
+    /// Returns true if there is no usable history.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.history.is_empty()
+    }
+
+    /// Suggests a counter-account for a statement description.
+    ///
+    /// Returns `None` when nothing in the history is similar.
+    #[must_use]
+    pub fn suggest(&self, description: &str) -> Option<AccountSuggestion> {
+        self.suggest_among(description, None)
+    }
+
+    /// Like [`Self::suggest`], but never suggests `excluded`.
+    ///
+    /// Postings to `excluded` are left out before accounts are ranked, so the
+    /// best of the remaining accounts is suggested even when `excluded` was
+    /// used more often.
+    #[must_use]
+    pub fn suggest_excluding(
+        &self,
+        description: &str,
+        excluded: Uuid,
+    ) -> Option<AccountSuggestion> {
+        self.suggest_among(description, Some(excluded))
+    }
+
+    fn suggest_among(
+        &self,
+        description: &str,
+        excluded: Option<Uuid>,
+    ) -> Option<AccountSuggestion> {
+        let normalized = normalize(description);
+        if normalized.is_empty() {
+            return None;
+        }
+
+        let exact = self.tally(excluded, |p| usize::from(p.normalized == normalized));
+        if let Some(suggestion) = best(exact, MatchKind::Exact) {
+            return Some(suggestion);
+        }
+
+        // Pad with spaces so substrings only match on whole words.
+        let padded = format!(" {normalized} ");
+        let substring = self.tally(excluded, |p| {
+            let other = format!(" {} ", p.normalized);
+            usize::from(other.contains(&padded) || padded.contains(&other))
+        });
+        if let Some(suggestion) = best(substring, MatchKind::Substring) {
+            return Some(suggestion);
+        }
+
+        let line_keywords = keywords(&normalized);
+        let keyword = self.tally(excluded, |p| {
+            p.keywords.intersection(&line_keywords).count()
+        });
+        best(keyword, MatchKind::Keyword)
+    }
+
+    /// Sums a per-posting weight by account, skipping postings to `excluded`.
+    fn tally(
+        &self,
+        excluded: Option<Uuid>,
+        weight: impl Fn(&IndexedPosting) -> usize,
+    ) -> BTreeMap<Uuid, usize> {
+        let mut totals = BTreeMap::new();
+        for posting in &self.history {
+            if Some(posting.account_id) == excluded {
+                continue;
+            }
+            let w = weight(posting);
+            if w > 0 {
+                *totals.entry(posting.account_id).or_insert(0) += w;
+            }
+        }
+        totals
+    }
+}
+
+/// Picks the highest-weighted account; ties go to the lowest account ID.
+fn best(totals: BTreeMap<Uuid, usize>, match_kind: MatchKind) -> Option<AccountSuggestion> {
+    totals
+        .into_iter()
+        .fold(None, |acc: Option<(Uuid, usize)>, (id, score)| match acc {
+            Some((_, best_score)) if best_score >= score => acc,
+            _ => Some((id, score)),
+        })
+        .map(|(account_id, score)| AccountSuggestion {
+            account_id,
+            match_kind,
+            score,
+        })
+}
+
+/// Lowercases, strips punctuation and drops purely numeric tokens.
+fn normalize(text: &str) -> String {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|t| !t.is_empty() && !t.chars().all(|c| c.is_ascii_digit()))
+        .map(str::to_lowercase)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Extracts keyword tokens from normalized text.
+fn keywords(normalized: &str) -> HashSet<String> {
+    normalized
+        .split(' ')
+        .filter(|t| t.chars().count() >= MIN_KEYWORD_LEN)
+        .map(str::to_string)
+        .collect()
+}
@@ -0,0 +1,21 @@
+//! Bank statement import.
+//!
+//! This module turns bank statement CSV exports into proposed draft journals:
+//! - CSV parsing of statement lines (date, description, amount, reference)
+//! - Counter-account suggestion from prior posted transaction descriptions
+//! - Two-entry journal proposals against the bank account
+
+pub mod error;
+pub mod matcher;
+pub mod parser;
+pub mod service;
+pub mod types;
+
+#[cfg(test)]
+mod tests;
+
+pub use error::BankImportError;
+pub use matcher::AccountMatcher;
+pub use parser::parse_statement_csv;
+pub use service::BankImportService;
+pub use types::{AccountSuggestion, HistoricalPosting, MatchKind, ProposedJournal, StatementLine};
@@ -0,0 +1,123 @@
+//! Bank statement CSV parsing.
+//!
+//! The first row is a header naming the columns. `date`, `description` and
+//! `amount` are required, `reference` is optional, and column order is free.
+//! Fields may be double-quoted; a doubled quote inside a quoted field is a
+//! literal quote.
+
+use std::str::FromStr;
+
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+
+use super::error::BankImportError;
+use super::types::StatementLine;
+
+/// Parses a bank statement CSV into statement lines.
+///
+/// Blank lines are skipped. Amounts may contain thousands separators (`1,250.00`)
+/// and use a leading minus sign for withdrawals.
+///
+/// # Errors
+///
+/// Returns the first parse error encountered, tagged with its line number.
+pub fn parse_statement_csv(input: &str) -> Result<Vec<StatementLine>, BankImportError> {
+    let mut rows = input
+        .lines()
+        .enumerate()
+        .map(|(idx, line)| (idx + 1, line.trim_end_matches('\r')))
+        .filter(|(_, line)| !line.trim().is_empty());
+
+    let (header_line, header) = rows.next().ok_or(BankImportError::EmptyStatement)?;
+    let columns: Vec<String> = split_row(header, header_line)?
+        .into_iter()
+        .map(|c| c.trim().to_lowercase())
+        .collect();
+
+    let position = |name: &str| columns.iter().position(|c| c == name);
+    let date_idx = position("date").ok_or_else(|| BankImportError::MissingColumn("date".into()))?;
+    let desc_idx = position("description")
+        .ok_or_else(|| BankImportError::MissingColumn("description".into()))?;
+    let amount_idx =
+        position("amount").ok_or_else(|| BankImportError::MissingColumn("amount".into()))?;
+    let reference_idx = position("reference");
+    let required = date_idx.max(desc_idx).max(amount_idx) + 1;
+
+    let mut lines = Vec::new();
+    for (line_number, row) in rows {
+        let fields = split_row(row, line_number)?;
+        if fields.len() < required {
+            return Err(BankImportError::ColumnCount {
+                line: line_number,
+                expected: required,
+                got: fields.len(),
+            });
+        }
+
+        let raw_date = fields[date_idx].trim();
+        let date = NaiveDate::parse_from_str(raw_date, "%Y-%m-%d").map_err(|_| {
+            BankImportError::InvalidDate {
+                line: line_number,
+                value: raw_date.to_string(),
+            }
+        })?;
+
+        let description = fields[desc_idx].trim().to_string();
+        if description.is_empty() {
+            return Err(BankImportError::MissingDescription(line_number));
+        }
+
+        let raw_amount = fields[amount_idx].trim();
+        let amount = Decimal::from_str(&raw_amount.replace(',', "")).map_err(|_| {
+            BankImportError::InvalidAmount {
+                line: line_number,
+                value: raw_amount.to_string(),
+            }
+        })?;
+        if amount.is_zero() {
+            return Err(BankImportError::ZeroAmount(line_number));
+        }
+
+        let reference = reference_idx
+            .and_then(|idx| fields.get(idx))
+            .map(|r| r.trim().to_string())
+            .filter(|r| !r.is_empty());
+
+        lines.push(StatementLine {
+            line_number,
+            date,
+            description,
+            amount,
+            reference,
+        });
+    }
+
+    Ok(lines)
+}
+
+/// Splits a single CSV row into fields, honouring double quotes.
+pub(crate) fn split_row(row: &str, line_number: usize) -> Result<Vec<String>, BankImportError> {
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut chars = row.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match (c, in_quotes) {
+            ('"', true) if chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            ('"', _) => in_quotes = !in_quotes,
+            (',', false) => fields.push(std::mem::take(&mut field)),
+            _ => field.push(c),
+        }
+    }
+
+    if in_quotes {
+        return Err(BankImportError::UnterminatedQuote(line_number));
+    }
+    fields.push(field);
+
+    Ok(fields)
+}
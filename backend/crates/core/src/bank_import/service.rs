@@ -0,0 +1,58 @@
+//! Bank import service for building draft journal proposals.
+
+use rust_decimal::Decimal;
+use uuid::Uuid;
+
+use super::matcher::AccountMatcher;
+use super::types::{MatchKind, ProposedJournal, StatementLine};
+use crate::ledger::types::EntryType;
+
+/// Bank import service for business logic.
+pub struct BankImportService;
+
+impl BankImportService {
+    /// Builds one proposed two-entry journal per statement line.
+    ///
+    /// Deposits (positive amounts) debit the bank account; withdrawals credit it.
+    /// The other side uses the matcher's suggestion, falling back to
+    /// `suspense_account_id` when nothing matches.
+    #[must_use]
+    pub fn propose(
+        lines: &[StatementLine],
+        bank_account_id: Uuid,
+        matcher: &AccountMatcher,
+        suspense_account_id: Option<Uuid>,
+    ) -> Vec<ProposedJournal> {
+        lines
+            .iter()
+            .map(|line| {
+                let bank_entry_type = if line.amount > Decimal::ZERO {
+                    EntryType::Debit
+                } else {
+                    EntryType::Credit
+                };
+
+                // Never suggest the bank account against itself.
+                let suggestion = matcher.suggest_excluding(&line.description, bank_account_id);
+
+                let (counter_account_id, match_kind) = match (suggestion, suspense_account_id) {
+                    (Some(s), _) => (Some(s.account_id), s.match_kind),
+                    (None, Some(suspense)) => (Some(suspense), MatchKind::Suspense),
+                    (None, None) => (None, MatchKind::Unmatched),
+                };
+
+                ProposedJournal {
+                    line_number: line.line_number,
+                    date: line.date,
+                    description: line.description.clone(),
+                    reference: line.reference.clone(),
+                    amount: line.amount.abs(),
+                    bank_account_id,
+                    bank_entry_type,
+                    counter_account_id,
+                    match_kind,
+                }
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,259 @@
+//! Tests for bank statement parsing and account matching.
+
+use chrono::NaiveDate;
+use rust_decimal_macros::dec;
+use uuid::Uuid;
+
+use super::error::BankImportError;
+use super::matcher::AccountMatcher;
+use super::parser::parse_statement_csv;
+use super::service::BankImportService;
+use super::types::{HistoricalPosting, MatchKind, StatementLine};
+use crate::ledger::types::EntryType;
+
+fn posting(description: &str, account_id: Uuid) -> HistoricalPosting {
+    HistoricalPosting {
+        description: description.to_string(),
+        account_id,
+    }
+}
+
+fn line(description: &str, amount: rust_decimal::Decimal) -> StatementLine {
+    StatementLine {
+        line_number: 2,
+        date: NaiveDate::from_ymd_opt(2026, 1, 15).unwrap(),
+        description: description.to_string(),
+        amount,
+        reference: None,
+    }
+}
+
+// ============================================================================
+// Parser
+// ============================================================================
+
+#[test]
+fn test_parse_basic_statement() {
+    let csv = "date,description,amount,reference\n\
+               2026-01-15,Coffee Shop,-4.50,REF1\n\
+               2026-01-16,Client payment,1500.00,\n";
+
+    let lines = parse_statement_csv(csv).unwrap();
+
+    assert_eq!(lines.len(), 2);
+    assert_eq!(lines[0].line_number, 2);
+    assert_eq!(lines[0].amount, dec!(-4.50));
+    assert_eq!(lines[0].reference.as_deref(), Some("REF1"));
+    assert_eq!(lines[1].amount, dec!(1500.00));
+    assert_eq!(lines[1].reference, None);
+}
+
+#[test]
+fn test_parse_reordered_columns_and_quotes() {
+    let csv = "Amount,Date,Description\r\n\"-1,250.00\",2026-02-01,\"Rent, \"\"Feb\"\"\"\r\n";
+
+    let lines = parse_statement_csv(csv).unwrap();
+
+    assert_eq!(lines.len(), 1);
+    assert_eq!(lines[0].amount, dec!(-1250.00));
+    assert_eq!(lines[0].description, "Rent, \"Feb\"");
+}
+
+#[test]
+fn test_parse_skips_blank_lines() {
+    let csv = "date,description,amount\n\n2026-01-15,Fee,-1\n   \n";
+    let lines = parse_statement_csv(csv).unwrap();
+    assert_eq!(lines.len(), 1);
+    assert_eq!(lines[0].line_number, 3);
+}
+
+#[test]
+fn test_parse_errors() {
+    assert_eq!(
+        parse_statement_csv(""),
+        Err(BankImportError::EmptyStatement)
+    );
+    assert_eq!(
+        parse_statement_csv("date,description\n"),
+        Err(BankImportError::MissingColumn("amount".to_string()))
+    );
+    assert_eq!(
+        parse_statement_csv("date,description,amount\n15/01/2026,Fee,-1\n"),
+        Err(BankImportError::InvalidDate {
+            line: 2,
+            value: "15/01/2026".to_string()
+        })
+    );
+    assert_eq!(
+        parse_statement_csv("date,description,amount\n2026-01-15,Fee,abc\n"),
+        Err(BankImportError::InvalidAmount {
+            line: 2,
+            value: "abc".to_string()
+        })
+    );
+    assert_eq!(
+        parse_statement_csv("date,description,amount\n2026-01-15,Fee,0\n"),
+        Err(BankImportError::ZeroAmount(2))
+    );
+    assert_eq!(
+        parse_statement_csv("date,description,amount\n2026-01-15, ,5\n"),
+        Err(BankImportError::MissingDescription(2))
+    );
+    assert_eq!(
+        parse_statement_csv("date,description,amount\n2026-01-15,Fee\n"),
+        Err(BankImportError::ColumnCount {
+            line: 2,
+            expected: 3,
+            got: 2
+        })
+    );
+    assert_eq!(
+        parse_statement_csv("date,description,amount\n2026-01-15,\"Fee,5\n"),
+        Err(BankImportError::UnterminatedQuote(2))
+    );
+}
+
+// ============================================================================
+// Matcher
+// ============================================================================
+
+#[test]
+fn test_exact_match_prefers_most_used_account() {
+    let meals = Uuid::from_u128(1);
+    let office = Uuid::from_u128(2);
+    let history = vec![
+        posting("Starbucks", meals),
+        posting("STARBUCKS", meals),
+        posting("starbucks", office),
+    ];
+
+    let matcher = AccountMatcher::new(&history);
+    let suggestion = matcher.suggest("Starbucks #4411").unwrap();
+
+    assert_eq!(suggestion.account_id, meals);
+    assert_eq!(suggestion.match_kind, MatchKind::Exact);
+    assert_eq!(suggestion.score, 2);
+}
+
+#[test]
+fn test_substring_match() {
+    let software = Uuid::from_u128(3);
+    let history = vec![posting("GitHub", software)];
+
+    let matcher = AccountMatcher::new(&history);
+    let suggestion = matcher.suggest("POS 20260115 GITHUB").unwrap();
+
+    assert_eq!(suggestion.account_id, software);
+    assert_eq!(suggestion.match_kind, MatchKind::Substring);
+}
+
+#[test]
+fn test_keyword_match() {
+    let travel = Uuid::from_u128(4);
+    let utilities = Uuid::from_u128(5);
+    let history = vec![
+        posting("Uber trip to airport", travel),
+        posting("Electricity bill January", utilities),
+    ];
+
+    let matcher = AccountMatcher::new(&history);
+    let suggestion = matcher.suggest("UBER BV airport transfer").unwrap();
+
+    assert_eq!(suggestion.account_id, travel);
+    assert_eq!(suggestion.match_kind, MatchKind::Keyword);
+    assert_eq!(suggestion.score, 2);
+}
+
+#[test]
+fn test_no_match() {
+    let history = vec![posting("Office rent", Uuid::from_u128(6))];
+    let matcher = AccountMatcher::new(&history);
+
+    assert!(matcher.suggest("Wire transfer 12345").is_none());
+    assert!(matcher.suggest("12345").is_none());
+    assert!(AccountMatcher::default().suggest("Office rent").is_none());
+}
+
+#[test]
+fn test_tie_breaks_on_account_id() {
+    let a = Uuid::from_u128(10);
+    let b = Uuid::from_u128(11);
+    let history = vec![posting("Amazon", b), posting("Amazon", a)];
+
+    let suggestion = AccountMatcher::new(&history).suggest("amazon").unwrap();
+    assert_eq!(suggestion.account_id, a);
+}
+
+// ============================================================================
+// Service
+// ============================================================================
+
+#[test]
+fn test_propose_sides_and_suspense() {
+    let bank = Uuid::from_u128(100);
+    let sales = Uuid::from_u128(101);
+    let suspense = Uuid::from_u128(999);
+    let matcher = AccountMatcher::new(&[posting("Acme Corp payment", sales)]);
+
+    let lines = vec![
+        line("Acme Corp payment", dec!(250.00)),
+        line("Unknown charge", dec!(-12.34)),
+    ];
+
+    let proposals = BankImportService::propose(&lines, bank, &matcher, Some(suspense));
+
+    assert_eq!(proposals[0].bank_entry_type, EntryType::Debit);
+    assert_eq!(proposals[0].counter_entry_type(), EntryType::Credit);
+    assert_eq!(proposals[0].counter_account_id, Some(sales));
+    assert_eq!(proposals[0].amount, dec!(250.00));
+
+    assert_eq!(proposals[1].bank_entry_type, EntryType::Credit);
+    assert_eq!(proposals[1].counter_account_id, Some(suspense));
+    assert_eq!(proposals[1].match_kind, MatchKind::Suspense);
+    assert_eq!(proposals[1].amount, dec!(12.34));
+}
+
+#[test]
+fn test_propose_without_suspense_is_unmatched() {
+    let bank = Uuid::from_u128(100);
+    let proposals = BankImportService::propose(
+        &[line("Mystery", dec!(-1))],
+        bank,
+        &AccountMatcher::default(),
+        None,
+    );
+
+    assert_eq!(proposals[0].counter_account_id, None);
+    assert_eq!(proposals[0].match_kind, MatchKind::Unmatched);
+}
+
+#[test]
+fn test_propose_never_suggests_bank_account() {
+    let bank = Uuid::from_u128(100);
+    let matcher = AccountMatcher::new(&[posting("Transfer", bank)]);
+
+    let proposals = BankImportService::propose(&[line("Transfer", dec!(5))], bank, &matcher, None);
+
+    assert_eq!(proposals[0].counter_account_id, None);
+}
+
+#[test]
+fn test_propose_skips_bank_account_when_ranking() {
+    let bank = Uuid::from_u128(100);
+    let fees = Uuid::from_u128(102);
+    let matcher = AccountMatcher::new(&[
+        posting("Monthly service fee", bank),
+        posting("Monthly service fee", bank),
+        posting("Monthly service fee", fees),
+    ]);
+
+    let proposals = BankImportService::propose(
+        &[line("Monthly service fee", dec!(-15))],
+        bank,
+        &matcher,
+        None,
+    );
+
+    assert_eq!(proposals[0].counter_account_id, Some(fees));
+    assert_eq!(proposals[0].match_kind, MatchKind::Exact);
+}
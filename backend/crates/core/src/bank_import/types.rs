@@ -0,0 +1,93 @@
+//! Bank import data types.
+
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::ledger::types::EntryType;
+
+/// A single parsed bank statement line.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StatementLine {
+    /// 1-based line number in the source file (header is line 1).
+    pub line_number: usize,
+    /// Statement date.
+    pub date: NaiveDate,
+    /// Statement description as printed by the bank.
+    pub description: String,
+    /// Signed amount: positive for deposits, negative for withdrawals.
+    pub amount: Decimal,
+    /// Optional bank reference.
+    pub reference: Option<String>,
+}
+
+/// A prior posted transaction description and the counterparty account it used.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HistoricalPosting {
+    /// Transaction description.
+    pub description: String,
+    /// Counterparty (non-bank) account ID.
+    pub account_id: Uuid,
+}
+
+/// How a counter-account suggestion was found.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MatchKind {
+    /// A prior description matched exactly (after normalization).
+    Exact,
+    /// A prior description contains, or is contained in, the line description.
+    Substring,
+    /// A prior description shares keywords with the line description.
+    Keyword,
+    /// Nothing matched; the suspense account was used.
+    Suspense,
+    /// Nothing matched and no suspense account is configured.
+    Unmatched,
+}
+
+/// A suggested counter-account for a statement line.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AccountSuggestion {
+    /// Suggested account ID.
+    pub account_id: Uuid,
+    /// How the suggestion was found.
+    pub match_kind: MatchKind,
+    /// Weight of the winning account among the matching history.
+    pub score: usize,
+}
+
+/// A proposed two-entry draft journal for a statement line.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProposedJournal {
+    /// Source statement line number.
+    pub line_number: usize,
+    /// Transaction date.
+    pub date: NaiveDate,
+    /// Transaction description.
+    pub description: String,
+    /// Optional bank reference.
+    pub reference: Option<String>,
+    /// Absolute amount of the journal.
+    pub amount: Decimal,
+    /// Bank account ID.
+    pub bank_account_id: Uuid,
+    /// Side of the bank account entry (debit for deposits, credit for withdrawals).
+    pub bank_entry_type: EntryType,
+    /// Suggested counter-account, if any.
+    pub counter_account_id: Option<Uuid>,
+    /// How the counter-account was chosen.
+    pub match_kind: MatchKind,
+}
+
+impl ProposedJournal {
+    /// Side of the counter-account entry.
+    #[must_use]
+    pub const fn counter_entry_type(&self) -> EntryType {
+        match self.bank_entry_type {
+            EntryType::Debit => EntryType::Credit,
+            EntryType::Credit => EntryType::Debit,
+        }
+    }
+}
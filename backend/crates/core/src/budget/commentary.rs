@@ -0,0 +1,38 @@
+//! Variance commentary on budget lines.
+//!
+//! During monthly reviews managers explain lines whose variance is large.
+//! Comments are versioned rather than overwritten, so an edit never loses
+//! the explanation that was on record before it.
+
+use rust_decimal::Decimal;
+
+use crate::auth::UserRole;
+
+/// Checks whether a member may edit a variance comment.
+///
+/// The author may always edit their comment; otherwise Accountant or above
+/// is required.
+#[must_use]
+pub const fn can_edit_commentary(role: UserRole, is_author: bool) -> bool {
+    is_author || role.can_edit_any_budget()
+}
+
+/// Checks whether a line's variance is larger than `threshold_percent` of
+/// its budgeted amount.
+///
+/// Any variance on a zero budget counts as exceeding the threshold. Lines
+/// without a variance, because they could not be converted, never do.
+#[must_use]
+pub fn variance_exceeds(
+    budgeted: Decimal,
+    variance: Option<Decimal>,
+    threshold_percent: Decimal,
+) -> bool {
+    let Some(variance) = variance else {
+        return false;
+    };
+    if budgeted.is_zero() {
+        return !variance.is_zero();
+    }
+    variance.abs() * Decimal::ONE_HUNDRED > threshold_percent * budgeted.abs()
+}
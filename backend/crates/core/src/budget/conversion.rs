@@ -0,0 +1,188 @@
+//! Currency conversion for budget vs actual.
+//!
+//! Budgets carry their own currency while actuals are summed in the
+//! organization's functional currency. When the two differ, one side is
+//! converted with the rate effective at the end of each line's fiscal period.
+
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+/// Decimal places kept on converted amounts.
+const CONVERTED_DECIMAL_PLACES: u32 = 4;
+
+/// Which side of a budget vs actual comparison is converted.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConvertSide {
+    /// Convert actuals into the budget currency, reporting in the currency
+    /// the budget was entered in.
+    #[default]
+    Actual,
+    /// Convert budgeted amounts into the functional currency, reporting in
+    /// the currency of the ledger.
+    Budget,
+}
+
+impl ConvertSide {
+    /// Currency pair `(from, to)` whose rate is needed.
+    #[must_use]
+    pub fn rate_pair<'a>(
+        self,
+        budget_currency: &'a str,
+        functional_currency: &'a str,
+    ) -> (&'a str, &'a str) {
+        match self {
+            Self::Actual => (functional_currency, budget_currency),
+            Self::Budget => (budget_currency, functional_currency),
+        }
+    }
+
+    /// Currency the converted lines are reported in.
+    #[must_use]
+    pub fn reporting_currency<'a>(
+        self,
+        budget_currency: &'a str,
+        functional_currency: &'a str,
+    ) -> &'a str {
+        self.rate_pair(budget_currency, functional_currency).1
+    }
+}
+
+/// Rate applied to one line.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AppliedRate {
+    /// Currency converted from.
+    pub from_currency: String,
+    /// Currency converted to.
+    pub to_currency: String,
+    /// Rate such that `from * rate = to`.
+    pub rate: Decimal,
+    /// Date the rate took effect.
+    pub effective_date: NaiveDate,
+    /// Period end date the rate was looked up for.
+    pub rate_date: NaiveDate,
+}
+
+/// Budgeted and actual amounts of a line in a common currency.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConvertedLine {
+    /// Budgeted amount.
+    pub budgeted: Decimal,
+    /// Actual amount.
+    pub actual: Decimal,
+}
+
+/// Converts one side of a line with `rate`.
+///
+/// The converted side is rounded to four decimal places; the other side is
+/// returned as is.
+#[must_use]
+pub fn convert_line(
+    side: ConvertSide,
+    budgeted: Decimal,
+    actual: Decimal,
+    rate: Decimal,
+) -> ConvertedLine {
+    let convert = |amount: Decimal| (amount * rate).round_dp(CONVERTED_DECIMAL_PLACES);
+    match side {
+        ConvertSide::Actual => ConvertedLine {
+            budgeted,
+            actual: convert(actual),
+        },
+        ConvertSide::Budget => ConvertedLine {
+            budgeted: convert(budgeted),
+            actual,
+        },
+    }
+}
+
+/// Warning attached to a line whose rate could not be found.
+#[must_use]
+pub fn missing_rate_warning(from_currency: &str, to_currency: &str, date: NaiveDate) -> String {
+    format!(
+        "No {from_currency}/{to_currency} exchange rate on or before {date}; \
+         line left unconverted and excluded from totals"
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    fn date(month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2025, month, day).unwrap()
+    }
+
+    /// A EUR budget in a USD organization over January and February, with
+    /// EUR/USD at 1.10 and 1.20 at the two period ends.
+    #[test]
+    fn test_two_periods_use_their_own_rates() {
+        let periods = [
+            (date(1, 31), dec!(1000), dec!(1210), dec!(1.10)),
+            (date(2, 28), dec!(1000), dec!(1080), dec!(1.20)),
+        ];
+
+        // Actuals into EUR with the inverse USD/EUR rate
+        let (from, to) = ConvertSide::Actual.rate_pair("EUR", "USD");
+        assert_eq!((from, to), ("USD", "EUR"));
+        let actuals: Vec<_> = periods
+            .iter()
+            .map(|&(_, budgeted, actual, eur_usd)| {
+                convert_line(
+                    ConvertSide::Actual,
+                    budgeted,
+                    actual,
+                    Decimal::ONE / eur_usd,
+                )
+            })
+            .collect();
+        assert_eq!(
+            actuals,
+            [
+                ConvertedLine {
+                    budgeted: dec!(1000),
+                    actual: dec!(1100.0000),
+                },
+                ConvertedLine {
+                    budgeted: dec!(1000),
+                    actual: dec!(900.0000),
+                },
+            ]
+        );
+
+        // Budget into USD with the EUR/USD rate
+        assert_eq!(ConvertSide::Budget.reporting_currency("EUR", "USD"), "USD");
+        let budgets: Vec<_> = periods
+            .iter()
+            .map(|&(_, budgeted, actual, eur_usd)| {
+                convert_line(ConvertSide::Budget, budgeted, actual, eur_usd)
+            })
+            .collect();
+        assert_eq!(budgets[0].budgeted, dec!(1100.00));
+        assert_eq!(budgets[0].actual, dec!(1210));
+        assert_eq!(budgets[1].budgeted, dec!(1200.00));
+        assert_eq!(budgets[1].actual, dec!(1080));
+    }
+
+    #[test]
+    fn test_converted_side_is_rounded() {
+        let line = convert_line(ConvertSide::Actual, dec!(10), dec!(10), dec!(0.333333));
+        assert_eq!(line.actual, dec!(3.3333));
+        assert_eq!(line.budgeted, dec!(10));
+    }
+
+    #[test]
+    fn test_missing_rate_warning_names_pair_and_date() {
+        let warning = missing_rate_warning("USD", "EUR", date(2, 28));
+        assert!(warning.starts_with("No USD/EUR exchange rate on or before 2025-02-28"));
+    }
+
+    #[test]
+    fn test_default_converts_actuals() {
+        assert_eq!(ConvertSide::default(), ConvertSide::Actual);
+        let side: ConvertSide = serde_json::from_str("\"budget\"").unwrap();
+        assert_eq!(side, ConvertSide::Budget);
+    }
+}
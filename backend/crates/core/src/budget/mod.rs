@@ -1,18 +1,24 @@
 //! Budget tracking and variance analysis.
 
+pub mod commentary;
+pub mod conversion;
 pub mod error;
 pub mod service;
+pub mod trend;
 pub mod types;
 pub mod variance;
 
 #[cfg(test)]
 mod tests;
 
+pub use commentary::{can_edit_commentary, variance_exceeds};
+pub use conversion::{AppliedRate, ConvertSide, ConvertedLine, convert_line, missing_rate_warning};
 pub use error::BudgetError;
 pub use service::BudgetService;
+pub use trend::{PeriodTotals, TrendPoint, cumulative_trend};
 pub use types::{
     Budget, BudgetLine, BudgetLineWithActual, BudgetSummary, BudgetType, BudgetVsActualReport,
     BudgetVsActualSummary, CreateBudgetInput, CreateBudgetLineInput, DimensionInfo, VarianceResult,
     VarianceStatus,
 };
-pub use variance::{BudgetVariance, VarianceType};
+pub use variance::{BudgetVariance, ProjectedUtilization, VarianceType};
@@ -1,5 +1,7 @@
 //! Property-based tests for budget module.
 
+use std::cmp::Ordering;
+
 use proptest::prelude::*;
 use rust_decimal::Decimal;
 use rust_decimal_macros::dec;
@@ -24,13 +26,12 @@ proptest! {
         prop_assert_eq!(result.variance, budgeted - actual);
 
         // Favorable if under budget (variance > 0)
-        if result.variance > Decimal::ZERO {
-            prop_assert_eq!(result.status, VarianceStatus::Favorable);
-        } else if result.variance < Decimal::ZERO {
-            prop_assert_eq!(result.status, VarianceStatus::Unfavorable);
-        } else {
-            prop_assert_eq!(result.status, VarianceStatus::OnBudget);
-        }
+        let expected = match result.variance.cmp(&Decimal::ZERO) {
+            Ordering::Greater => VarianceStatus::Favorable,
+            Ordering::Less => VarianceStatus::Unfavorable,
+            Ordering::Equal => VarianceStatus::OnBudget,
+        };
+        prop_assert_eq!(result.status, expected);
     }
 
     /// Feature: reports-simulation, Property 4: Budget Variance Calculation by Account Type
@@ -49,13 +50,12 @@ proptest! {
         prop_assert_eq!(result.variance, actual - budgeted);
 
         // Favorable if over target (variance > 0)
-        if result.variance > Decimal::ZERO {
-            prop_assert_eq!(result.status, VarianceStatus::Favorable);
-        } else if result.variance < Decimal::ZERO {
-            prop_assert_eq!(result.status, VarianceStatus::Unfavorable);
-        } else {
-            prop_assert_eq!(result.status, VarianceStatus::OnBudget);
-        }
+        let expected = match result.variance.cmp(&Decimal::ZERO) {
+            Ordering::Greater => VarianceStatus::Favorable,
+            Ordering::Less => VarianceStatus::Unfavorable,
+            Ordering::Equal => VarianceStatus::OnBudget,
+        };
+        prop_assert_eq!(result.status, expected);
     }
 
     /// Feature: reports-simulation, Property 6: Utilization Percent Calculation
@@ -108,8 +108,11 @@ proptest! {
 #[cfg(test)]
 mod unit_tests {
     use super::*;
+    use crate::auth::UserRole;
+    use crate::budget::commentary::{can_edit_commentary, variance_exceeds};
     use crate::budget::error::BudgetError;
     use crate::budget::types::{Budget, BudgetType};
+    use crate::budget::variance::ProjectedUtilization;
     use chrono::Utc;
     use uuid::Uuid;
 
@@ -163,4 +166,74 @@ mod unit_tests {
 
         assert!(result.is_ok());
     }
+
+    #[test]
+    fn test_variance_threshold_applies_to_both_directions() {
+        let threshold = dec!(10);
+
+        assert!(!variance_exceeds(dec!(1000), Some(dec!(100)), threshold));
+        assert!(variance_exceeds(dec!(1000), Some(dec!(100.01)), threshold));
+        assert!(variance_exceeds(dec!(1000), Some(dec!(-250)), threshold));
+        assert!(!variance_exceeds(dec!(1000), Some(dec!(-99)), threshold));
+    }
+
+    #[test]
+    fn test_variance_threshold_edge_cases() {
+        // Anything spent against a zero budget needs explaining
+        assert!(variance_exceeds(dec!(0), Some(dec!(-5)), dec!(10)));
+        assert!(!variance_exceeds(dec!(0), Some(dec!(0)), dec!(10)));
+        // Unconverted lines have no variance to explain
+        assert!(!variance_exceeds(dec!(1000), None, dec!(0)));
+        // A zero threshold flags every line off budget
+        assert!(variance_exceeds(dec!(1000), Some(dec!(0.01)), dec!(0)));
+    }
+
+    #[test]
+    fn test_projected_utilization_crosses_threshold() {
+        let line = |actual, impact| ProjectedUtilization {
+            budgeted: dec!(1000),
+            actual,
+            impact,
+        };
+
+        assert!(!line(dec!(600), dec!(400)).exceeds(100));
+        assert!(line(dec!(600), dec!(400.01)).exceeds(100));
+        assert!(line(dec!(600), dec!(250)).exceeds(80));
+        assert!(!line(dec!(600), dec!(200)).exceeds(80));
+        // Already over, and spending more
+        assert!(line(dec!(1200), dec!(1)).exceeds(100));
+
+        let projected = line(dec!(600), dec!(650));
+        assert_eq!(projected.remaining(), dec!(400));
+        assert_eq!(projected.projected(), dec!(1250));
+        assert_eq!(projected.percent(), Some(dec!(125.00)));
+    }
+
+    #[test]
+    fn test_projected_utilization_edge_cases() {
+        // Reducing actuals never crosses, even on a line already over
+        let refund = ProjectedUtilization {
+            budgeted: dec!(1000),
+            actual: dec!(1500),
+            impact: dec!(-100),
+        };
+        assert!(!refund.exceeds(100));
+        // Anything spent against a zero budget crosses every threshold
+        let unbudgeted = ProjectedUtilization {
+            budgeted: dec!(0),
+            actual: dec!(0),
+            impact: dec!(0.01),
+        };
+        assert!(unbudgeted.exceeds(100));
+        assert_eq!(unbudgeted.percent(), None);
+    }
+
+    #[test]
+    fn test_commentary_is_edited_by_author_or_accountant() {
+        assert!(can_edit_commentary(UserRole::Approver, true));
+        assert!(!can_edit_commentary(UserRole::Approver, false));
+        assert!(!can_edit_commentary(UserRole::Viewer, false));
+        assert!(can_edit_commentary(UserRole::Accountant, false));
+        assert!(can_edit_commentary(UserRole::Owner, false));
+    }
 }
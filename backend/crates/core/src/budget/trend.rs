@@ -0,0 +1,152 @@
+//! Cumulative budget vs actual over a fiscal year.
+//!
+//! The trend lists every period of the budget's fiscal year in order with its
+//! own totals and running totals, for charting consumption against plan.
+
+use rust_decimal::Decimal;
+use serde::Serialize;
+
+/// Budgeted and actual amounts of one period, in a common currency.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct PeriodTotals {
+    /// Budgeted amount.
+    pub budgeted: Decimal,
+    /// Actual amount.
+    pub actual: Decimal,
+    /// Whether the amounts count toward the running totals. `false` for a
+    /// period that could not be converted to the reporting currency.
+    pub included: bool,
+}
+
+impl PeriodTotals {
+    /// Totals that count toward the running totals.
+    #[must_use]
+    pub const fn new(budgeted: Decimal, actual: Decimal) -> Self {
+        Self {
+            budgeted,
+            actual,
+            included: true,
+        }
+    }
+}
+
+/// One period of a budget trend.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct TrendPoint {
+    /// Budgeted amount of the period.
+    pub budgeted: Decimal,
+    /// Actual amount of the period.
+    pub actual: Decimal,
+    /// Budgeted amount from the first period through this one.
+    pub cumulative_budgeted: Decimal,
+    /// Actual amount from the first period through this one.
+    pub cumulative_actual: Decimal,
+    /// `cumulative_budgeted - cumulative_actual`; positive while under plan.
+    pub cumulative_variance: Decimal,
+}
+
+/// Builds the running totals for periods given in fiscal order.
+///
+/// Excluded periods still get a point, carrying the running totals forward
+/// unchanged.
+#[must_use]
+pub fn cumulative_trend(periods: &[PeriodTotals]) -> Vec<TrendPoint> {
+    let mut cumulative_budgeted = Decimal::ZERO;
+    let mut cumulative_actual = Decimal::ZERO;
+
+    periods
+        .iter()
+        .map(|period| {
+            if period.included {
+                cumulative_budgeted += period.budgeted;
+                cumulative_actual += period.actual;
+            }
+            TrendPoint {
+                budgeted: period.budgeted,
+                actual: period.actual,
+                cumulative_budgeted,
+                cumulative_actual,
+                cumulative_variance: cumulative_budgeted - cumulative_actual,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    /// Twelve monthly periods budgeted at 1,000 each, with nothing budgeted
+    /// for August, and actuals running 900, 950, ... up to month 7.
+    fn fiscal_year() -> Vec<PeriodTotals> {
+        (1..=12)
+            .map(|month| {
+                let budgeted = if month == 8 { dec!(0) } else { dec!(1000) };
+                let actual = if month <= 7 {
+                    dec!(850) + Decimal::from(month) * dec!(50)
+                } else {
+                    Decimal::ZERO
+                };
+                PeriodTotals::new(budgeted, actual)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_cumulative_trend_over_twelve_periods() {
+        let trend = cumulative_trend(&fiscal_year());
+
+        assert_eq!(trend.len(), 12);
+        assert_eq!(trend[0].cumulative_budgeted, dec!(1000));
+        assert_eq!(trend[0].cumulative_actual, dec!(900));
+        assert_eq!(trend[0].cumulative_variance, dec!(100));
+
+        // Months 1-7: actuals 900 through 1200 sum to 7350
+        assert_eq!(trend[6].cumulative_budgeted, dec!(7000));
+        assert_eq!(trend[6].cumulative_actual, dec!(7350));
+        assert_eq!(trend[6].cumulative_variance, dec!(-350));
+
+        // An unbudgeted period keeps its place with zeros
+        assert_eq!(trend[7].budgeted, dec!(0));
+        assert_eq!(trend[7].actual, dec!(0));
+        assert_eq!(trend[7].cumulative_budgeted, dec!(7000));
+
+        assert_eq!(trend[11].cumulative_budgeted, dec!(11000));
+        assert_eq!(trend[11].cumulative_actual, dec!(7350));
+        assert_eq!(trend[11].cumulative_variance, dec!(3650));
+
+        // Running totals never decrease when all amounts are non-negative
+        assert!(
+            trend
+                .windows(2)
+                .all(|w| w[1].cumulative_budgeted >= w[0].cumulative_budgeted
+                    && w[1].cumulative_actual >= w[0].cumulative_actual)
+        );
+        for point in &trend {
+            assert_eq!(
+                point.cumulative_variance,
+                point.cumulative_budgeted - point.cumulative_actual
+            );
+        }
+    }
+
+    #[test]
+    fn test_excluded_period_carries_totals_forward() {
+        let mut periods = fiscal_year();
+        periods[1].included = false;
+
+        let trend = cumulative_trend(&periods);
+
+        assert_eq!(trend[1].budgeted, dec!(1000));
+        assert_eq!(trend[1].actual, dec!(950));
+        assert_eq!(trend[1].cumulative_budgeted, dec!(1000));
+        assert_eq!(trend[1].cumulative_actual, dec!(900));
+        assert_eq!(trend[11].cumulative_budgeted, dec!(10000));
+    }
+
+    #[test]
+    fn test_empty_year_has_no_points() {
+        assert!(cumulative_trend(&[]).is_empty());
+    }
+}
@@ -89,3 +89,52 @@ impl BudgetVariance {
         }
     }
 }
+
+/// Utilization of a budget line once a transaction is added to its actuals.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProjectedUtilization {
+    /// Budgeted amount.
+    pub budgeted: Decimal,
+    /// Actual amount before the transaction.
+    pub actual: Decimal,
+    /// The transaction's amount in the line's normal direction.
+    pub impact: Decimal,
+}
+
+impl ProjectedUtilization {
+    /// Budget left before the transaction (budget - actual).
+    #[must_use]
+    pub fn remaining(&self) -> Decimal {
+        self.budgeted - self.actual
+    }
+
+    /// Actual amount including the transaction.
+    #[must_use]
+    pub fn projected(&self) -> Decimal {
+        self.actual + self.impact
+    }
+
+    /// Projected actual as a percentage of the budget, `None` for a zero
+    /// budget.
+    #[must_use]
+    pub fn percent(&self) -> Option<Decimal> {
+        if self.budgeted.is_zero() {
+            None
+        } else {
+            Some((self.projected() / self.budgeted * Decimal::ONE_HUNDRED).round_dp(2))
+        }
+    }
+
+    /// Whether the transaction takes utilization past `threshold_percent`
+    /// of the budget.
+    ///
+    /// Only a positive impact can cross it, so a transaction that reduces
+    /// actuals never does, even on a line that is already over. Any spend
+    /// against a zero budget crosses every threshold.
+    #[must_use]
+    pub fn exceeds(&self, threshold_percent: u32) -> bool {
+        self.impact > Decimal::ZERO
+            && self.projected() * Decimal::ONE_HUNDRED
+                > self.budgeted * Decimal::from(threshold_percent)
+    }
+}
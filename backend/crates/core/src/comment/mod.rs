@@ -0,0 +1,238 @@
+//! Transaction comments.
+//!
+//! This module provides:
+//! - Comment body validation
+//! - `@email` mention parsing
+//! - The rule for who may delete a comment, and until when
+
+use std::fmt;
+use std::str::FromStr;
+
+use chrono::{DateTime, Duration, Utc};
+use thiserror::Error;
+
+use crate::auth::UserRole;
+
+/// Maximum length of a comment body, in characters.
+pub const MAX_COMMENT_LENGTH: usize = 5000;
+
+/// How long an author may delete their own comment.
+pub const AUTHOR_DELETE_WINDOW_MINUTES: i64 = 15;
+
+/// What a comment records.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CommentKind {
+    /// Written by a member.
+    Comment,
+    /// Notes left with an approval.
+    Approval,
+    /// The reason given for a rejection.
+    Rejection,
+}
+
+impl CommentKind {
+    /// Returns the kind as its API string.
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Comment => "comment",
+            Self::Approval => "approval",
+            Self::Rejection => "rejection",
+        }
+    }
+}
+
+impl fmt::Display for CommentKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for CommentKind {
+    type Err = CommentError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "comment" => Ok(Self::Comment),
+            "approval" => Ok(Self::Approval),
+            "rejection" => Ok(Self::Rejection),
+            _ => Err(CommentError::UnknownKind(s.to_string())),
+        }
+    }
+}
+
+/// Why a comment was rejected or cannot be deleted.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum CommentError {
+    /// The body is blank.
+    #[error("Comment body is required")]
+    EmptyBody,
+
+    /// The body is longer than [`MAX_COMMENT_LENGTH`].
+    #[error("Comment body is longer than 5000 characters")]
+    BodyTooLong,
+
+    /// The kind string is not recognized.
+    #[error("Unknown comment kind: {0}")]
+    UnknownKind(String),
+
+    /// Only the author, or an Owner or Admin, may delete a comment.
+    #[error("Only the author or an owner or admin can delete this comment")]
+    NotAuthor,
+
+    /// The author's delete window has passed.
+    #[error("Comments can only be deleted by their author within 15 minutes")]
+    DeleteWindowPassed,
+}
+
+/// Trims a comment body and checks its length.
+///
+/// # Errors
+///
+/// Returns an error if the body is blank or too long.
+pub fn validate_body(body: &str) -> Result<String, CommentError> {
+    let body = body.trim();
+    if body.is_empty() {
+        return Err(CommentError::EmptyBody);
+    }
+    if body.chars().count() > MAX_COMMENT_LENGTH {
+        return Err(CommentError::BodyTooLong);
+    }
+    Ok(body.to_string())
+}
+
+/// Checks whether a member may delete a comment.
+///
+/// Owners and Admins may delete any comment at any time; authors may delete
+/// their own within [`AUTHOR_DELETE_WINDOW_MINUTES`] of posting.
+///
+/// # Errors
+///
+/// Returns an error if the member may not delete the comment.
+pub fn can_delete(
+    role: UserRole,
+    is_author: bool,
+    created_at: DateTime<Utc>,
+    now: DateTime<Utc>,
+) -> Result<(), CommentError> {
+    if role.can_modify_settings() {
+        return Ok(());
+    }
+    if !is_author {
+        return Err(CommentError::NotAuthor);
+    }
+    if now - created_at > Duration::minutes(AUTHOR_DELETE_WINDOW_MINUTES) {
+        return Err(CommentError::DeleteWindowPassed);
+    }
+    Ok(())
+}
+
+/// Finds the `@email` mentions in a comment body.
+///
+/// A mention is `@` followed by an email address, and the `@` must not
+/// follow a letter or digit, so a plain address like `ops@example.com` is
+/// not a mention. Punctuation ending a sentence is not part of the address.
+/// Emails are lowercased and returned once each, in order of first mention.
+#[must_use]
+pub fn parse_mentions(body: &str) -> Vec<String> {
+    let chars: Vec<char> = body.chars().collect();
+    let mut mentions: Vec<String> = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        let starts_mention = chars[i] == '@' && (i == 0 || !is_email_char(chars[i - 1]));
+        if !starts_mention {
+            i += 1;
+            continue;
+        }
+
+        let start = i + 1;
+        let mut end = start;
+        while end < chars.len() && (is_email_char(chars[end]) || chars[end] == '@') {
+            end += 1;
+        }
+        let candidate: String = chars[start..end].iter().collect();
+        let candidate = candidate.trim_end_matches(['.', '-', '_', '+', '%']);
+        if is_email(candidate) {
+            let email = candidate.to_lowercase();
+            if !mentions.contains(&email) {
+                mentions.push(email);
+            }
+        }
+        i = end;
+    }
+    mentions
+}
+
+/// Characters allowed in an email address, apart from the `@`.
+fn is_email_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || matches!(c, '.' | '_' | '%' | '+' | '-')
+}
+
+/// Whether `s` looks like `local@domain.tld`.
+fn is_email(s: &str) -> bool {
+    let Some((local, domain)) = s.split_once('@') else {
+        return false;
+    };
+    !local.is_empty()
+        && !domain.contains('@')
+        && domain.contains('.')
+        && !domain.starts_with(['.', '-'])
+        && !domain.ends_with(['.', '-'])
+        && !domain.contains("..")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mentions_in_sentences() {
+        assert_eq!(
+            parse_mentions("@ana@example.com please fix the dimension, then ping @Bo@Corp.co.id."),
+            vec!["ana@example.com", "bo@corp.co.id"]
+        );
+        assert_eq!(
+            parse_mentions("Thanks (@ana@example.com)! Also @ana@example.com?"),
+            vec!["ana@example.com"]
+        );
+    }
+
+    #[test]
+    fn test_not_mentions() {
+        // Plain addresses, bare handles and malformed domains
+        assert!(parse_mentions("Send it to ops@example.com").is_empty());
+        assert!(parse_mentions("@ana please check").is_empty());
+        assert!(parse_mentions("@ana@localhost and @ana@.com and @@").is_empty());
+        assert!(parse_mentions("").is_empty());
+    }
+
+    #[test]
+    fn test_body_validation() {
+        assert_eq!(
+            validate_body("  Looks good  "),
+            Ok("Looks good".to_string())
+        );
+        assert_eq!(validate_body(" \n "), Err(CommentError::EmptyBody));
+        assert_eq!(
+            validate_body(&"x".repeat(MAX_COMMENT_LENGTH + 1)),
+            Err(CommentError::BodyTooLong)
+        );
+    }
+
+    #[test]
+    fn test_delete_rules() {
+        let posted = Utc::now();
+        let later = posted + Duration::minutes(AUTHOR_DELETE_WINDOW_MINUTES + 1);
+
+        assert_eq!(can_delete(UserRole::Viewer, true, posted, posted), Ok(()));
+        assert_eq!(
+            can_delete(UserRole::Viewer, true, posted, later),
+            Err(CommentError::DeleteWindowPassed)
+        );
+        assert_eq!(
+            can_delete(UserRole::Approver, false, posted, posted),
+            Err(CommentError::NotAuthor)
+        );
+        assert_eq!(can_delete(UserRole::Admin, false, posted, later), Ok(()));
+    }
+}
@@ -1,6 +1,6 @@
 //! Exchange rate types and logic.
 
-use chrono::NaiveDate;
+use chrono::{Datelike, NaiveDate};
 use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 
@@ -45,3 +45,59 @@ impl ExchangeRate {
         }
     }
 }
+
+/// Which stored rate converts a transaction's foreign amounts.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RateLookupPolicy {
+    /// The most recent rate on or before the transaction date.
+    #[default]
+    DailyLatest,
+    /// The rate dated the first day of the transaction's month, such as a
+    /// booking rate set by treasury for the whole month.
+    MonthlyFixed,
+}
+
+impl RateLookupPolicy {
+    /// Returns the date whose rate converts a transaction dated `date`.
+    #[must_use]
+    pub fn rate_date(self, date: NaiveDate) -> NaiveDate {
+        match self {
+            Self::DailyLatest => date,
+            Self::MonthlyFixed => date.with_day(1).unwrap_or(date),
+        }
+    }
+
+    /// Returns true if only a rate dated exactly on [`Self::rate_date`]
+    /// qualifies, rather than the latest one before it.
+    #[must_use]
+    pub const fn exact_date(self) -> bool {
+        matches!(self, Self::MonthlyFixed)
+    }
+
+    /// Returns the policy's name as stored in settings.
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::DailyLatest => "daily_latest",
+            Self::MonthlyFixed => "monthly_fixed",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rate_date_by_policy() {
+        let date = NaiveDate::from_ymd_opt(2025, 3, 17).unwrap();
+        assert_eq!(RateLookupPolicy::DailyLatest.rate_date(date), date);
+        assert_eq!(
+            RateLookupPolicy::MonthlyFixed.rate_date(date),
+            NaiveDate::from_ymd_opt(2025, 3, 1).unwrap()
+        );
+        assert!(RateLookupPolicy::MonthlyFixed.exact_date());
+        assert!(!RateLookupPolicy::DailyLatest.exact_date());
+    }
+}
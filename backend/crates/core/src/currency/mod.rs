@@ -4,10 +4,14 @@
 //! - Currency conversion with Banker's Rounding
 //! - Exchange rate types and operations
 //! - Amount allocation using Largest Remainder Method
+//! - Revaluation of foreign balances for unrealized FX gain/loss
+//! - Per-currency decimal places for rounding amounts at every boundary
 
 pub mod allocation;
 pub mod conversion;
 pub mod exchange;
+pub mod registry;
+pub mod revaluation;
 pub mod service;
 
 #[cfg(test)]
@@ -15,5 +19,7 @@ mod props;
 
 pub use allocation::AllocationUtil;
 pub use conversion::convert_amount;
-pub use exchange::ExchangeRate;
+pub use exchange::{ExchangeRate, RateLookupPolicy};
+pub use registry::{CurrencyRegistry, PrecisionError, STORED_DECIMAL_PLACES};
+pub use revaluation::{ForeignBalance, RevaluationResult, revaluation_reference, revalue};
 pub use service::CurrencyService;
@@ -3,11 +3,13 @@
 //! Feature: ledger-core
 //! - Property 6: Banker's Rounding Correctness
 //! - Property 7: Allocation Sum Invariant
+//! - Property 8: Currency Precision of Allocations
 
 use proptest::prelude::*;
 use rust_decimal::Decimal;
 
 use super::allocation::AllocationUtil;
+use super::registry::CurrencyRegistry;
 use super::service::CurrencyService;
 
 /// Strategy to generate positive decimal amounts (0.01 to 1,000,000.00).
@@ -30,6 +32,20 @@ fn decimal_places() -> impl Strategy<Value = u32> {
     0u32..=4
 }
 
+/// Strategy to pick a currency with 0, 2 or 4 decimal places.
+fn currency_code() -> impl Strategy<Value = &'static str> {
+    prop::sample::select(vec!["JPY", "USD", "CLF"])
+}
+
+/// Registry of the currencies `currency_code()` picks from.
+fn registry() -> CurrencyRegistry {
+    CurrencyRegistry::new([
+        ("JPY".to_string(), 0),
+        ("USD".to_string(), 2),
+        ("CLF".to_string(), 4),
+    ])
+}
+
 /// Strategy to generate percentages that sum to 100.
 fn percentages_summing_to_100() -> impl Strategy<Value = Vec<Decimal>> {
     // Generate 2-10 random values, then normalize to sum to 100
@@ -235,6 +251,52 @@ proptest! {
             );
         }
     }
+
+    // =========================================================================
+    // Property 8: Currency Precision of Allocations
+    // =========================================================================
+
+    /// Property 8.1: Equal allocation in a currency sums exactly.
+    ///
+    /// *For any* amount, count and currency with 0, 2 or 4 decimal places,
+    /// the allocations SHALL sum to the amount rounded for the currency and
+    /// none SHALL have more decimal places than the currency.
+    #[test]
+    fn prop_registry_allocate_equal_sums_in_currency(
+        total in positive_amount(),
+        count in allocation_count(),
+        code in currency_code(),
+    ) {
+        let registry = registry();
+        let result = registry.allocate_equal(code, total, count);
+
+        let sum: Decimal = result.iter().copied().sum();
+        prop_assert_eq!(sum, registry.round_for(code, total));
+        for alloc in &result {
+            prop_assert!(registry.check_precision(code, *alloc).is_ok());
+        }
+    }
+
+    /// Property 8.2: Percentage allocation in a currency sums exactly.
+    ///
+    /// *For any* amount, percentages summing to 100 and currency with 0, 2 or
+    /// 4 decimal places, the allocations SHALL sum to the amount rounded for
+    /// the currency and none SHALL have more decimal places than the currency.
+    #[test]
+    fn prop_registry_allocate_by_percentages_sums_in_currency(
+        total in positive_amount(),
+        percentages in percentages_summing_to_100(),
+        code in currency_code(),
+    ) {
+        let registry = registry();
+        let result = registry.allocate_by_percentages(code, total, &percentages);
+
+        let sum: Decimal = result.iter().copied().sum();
+        prop_assert_eq!(sum, registry.round_for(code, total));
+        for alloc in &result {
+            prop_assert!(registry.check_precision(code, *alloc).is_ok());
+        }
+    }
 }
 
 #[cfg(test)]
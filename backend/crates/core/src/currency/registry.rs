@@ -0,0 +1,177 @@
+//! Decimal places of known currencies.
+//!
+//! Amounts are stored with four decimal places, but each currency has its
+//! own precision: JPY has none, USD two, KWD three. The registry rounds an
+//! amount to the precision of its currency wherever one enters the ledger.
+
+use std::collections::HashMap;
+
+use rust_decimal::Decimal;
+use thiserror::Error;
+
+use super::allocation::AllocationUtil;
+use super::conversion::convert_amount;
+use super::service::CurrencyService;
+
+/// Decimal places amounts are stored with.
+///
+/// Also used for codes the registry does not know, so they are never
+/// rounded more than storage would round them.
+pub const STORED_DECIMAL_PLACES: u32 = 4;
+
+/// An amount with more decimal places than its currency has.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[error("{currency} amounts allow at most {decimal_places} decimal places, got {amount}")]
+pub struct PrecisionError {
+    /// Currency code.
+    pub currency: String,
+    /// Decimal places of the currency.
+    pub decimal_places: u32,
+    /// The amount as given.
+    pub amount: Decimal,
+}
+
+/// Decimal places of each known currency.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CurrencyRegistry {
+    decimal_places: HashMap<String, u32>,
+}
+
+impl CurrencyRegistry {
+    /// Creates a registry from `(code, decimal_places)` pairs.
+    #[must_use]
+    pub fn new<I>(currencies: I) -> Self
+    where
+        I: IntoIterator<Item = (String, u32)>,
+    {
+        Self {
+            decimal_places: currencies.into_iter().collect(),
+        }
+    }
+
+    /// Whether the currency is known.
+    #[must_use]
+    pub fn contains(&self, code: &str) -> bool {
+        self.decimal_places.contains_key(code)
+    }
+
+    /// Decimal places of a currency; [`STORED_DECIMAL_PLACES`] when unknown.
+    #[must_use]
+    pub fn decimal_places(&self, code: &str) -> u32 {
+        self.decimal_places
+            .get(code)
+            .copied()
+            .unwrap_or(STORED_DECIMAL_PLACES)
+    }
+
+    /// Rounds an amount to its currency's decimal places with Banker's
+    /// Rounding.
+    #[must_use]
+    pub fn round_for(&self, code: &str, amount: Decimal) -> Decimal {
+        CurrencyService::round(amount, self.decimal_places(code))
+    }
+
+    /// Checks that an amount has no more decimal places than its currency.
+    ///
+    /// Trailing zeros do not count: `100.00` is a valid JPY amount.
+    ///
+    /// # Errors
+    ///
+    /// Returns `PrecisionError` if rounding would change the amount.
+    pub fn check_precision(&self, code: &str, amount: Decimal) -> Result<(), PrecisionError> {
+        let decimal_places = self.decimal_places(code);
+        if amount.normalize().scale() > decimal_places {
+            return Err(PrecisionError {
+                currency: code.to_string(),
+                decimal_places,
+                amount,
+            });
+        }
+        Ok(())
+    }
+
+    /// Converts an amount into currency `to` and rounds it to that
+    /// currency's decimal places.
+    #[must_use]
+    pub fn convert(&self, amount: Decimal, rate: Decimal, to: &str) -> Decimal {
+        convert_amount(amount, rate, self.decimal_places(to))
+    }
+
+    /// Allocates an amount equally in its currency's decimal places.
+    ///
+    /// See [`AllocationUtil::allocate_equal`].
+    #[must_use]
+    pub fn allocate_equal(&self, code: &str, total: Decimal, count: usize) -> Vec<Decimal> {
+        AllocationUtil::allocate_equal(total, count, self.decimal_places(code))
+    }
+
+    /// Allocates an amount by percentages in its currency's decimal places.
+    ///
+    /// See [`AllocationUtil::allocate_by_percentages`].
+    #[must_use]
+    pub fn allocate_by_percentages(
+        &self,
+        code: &str,
+        total: Decimal,
+        percentages: &[Decimal],
+    ) -> Vec<Decimal> {
+        AllocationUtil::allocate_by_percentages(total, percentages, self.decimal_places(code))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    fn registry() -> CurrencyRegistry {
+        CurrencyRegistry::new([
+            ("JPY".to_string(), 0),
+            ("USD".to_string(), 2),
+            ("KWD".to_string(), 3),
+        ])
+    }
+
+    #[test]
+    fn test_round_for_uses_currency_places() {
+        let registry = registry();
+        assert_eq!(registry.round_for("JPY", dec!(1234.5)), dec!(1234));
+        assert_eq!(registry.round_for("JPY", dec!(1235.5)), dec!(1236));
+        assert_eq!(registry.round_for("USD", dec!(10.125)), dec!(10.12));
+        assert_eq!(registry.round_for("KWD", dec!(10.1235)), dec!(10.124));
+    }
+
+    #[test]
+    fn test_unknown_currency_keeps_stored_precision() {
+        let registry = registry();
+        assert!(!registry.contains("XTS"));
+        assert_eq!(registry.decimal_places("XTS"), STORED_DECIMAL_PLACES);
+        assert_eq!(registry.round_for("XTS", dec!(1.23456)), dec!(1.2346));
+    }
+
+    #[test]
+    fn test_check_precision_ignores_trailing_zeros() {
+        let registry = registry();
+        assert_eq!(registry.check_precision("JPY", dec!(100.00)), Ok(()));
+        assert_eq!(registry.check_precision("USD", dec!(10.50)), Ok(()));
+
+        let err = registry.check_precision("USD", dec!(10.005)).unwrap_err();
+        assert_eq!(err.currency, "USD");
+        assert_eq!(err.decimal_places, 2);
+        assert_eq!(
+            err.to_string(),
+            "USD amounts allow at most 2 decimal places, got 10.005"
+        );
+    }
+
+    #[test]
+    fn test_convert_rounds_to_target_currency() {
+        let registry = registry();
+        // 100 USD at 151.235 JPY = 15123.5 JPY, half to even
+        assert_eq!(
+            registry.convert(dec!(100), dec!(151.235), "JPY"),
+            dec!(15124)
+        );
+        assert_eq!(registry.convert(dec!(3), dec!(1.0835), "USD"), dec!(3.25));
+    }
+}
@@ -0,0 +1,373 @@
+//! Unrealized FX gain/loss on foreign currency balances.
+//!
+//! Monetary balances held in a foreign currency are carried at the rates
+//! they were booked at. Revaluing them at the period-end rate gives their
+//! current functional value; the difference is the unrealized gain or loss.
+//!
+//! All balances are signed debit-positive, so a positive difference is a
+//! gain for both assets and liabilities.
+
+use std::collections::{BTreeMap, HashMap};
+use std::hash::BuildHasher;
+
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use serde::Serialize;
+use uuid::Uuid;
+
+use super::conversion::convert_amount;
+
+/// Foreign currency balance of one account at its historical rates.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ForeignBalance {
+    /// Account holding the balance.
+    pub account_id: Uuid,
+    /// Source currency of the balance.
+    pub currency: String,
+    /// Net balance in the source currency, debit-positive.
+    pub source_balance: Decimal,
+    /// Net functional amount booked for it, debit-positive.
+    pub booked_functional: Decimal,
+}
+
+/// Revaluation of one account's balance in one currency.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct AccountRevaluation {
+    /// Account holding the balance.
+    pub account_id: Uuid,
+    /// Source currency of the balance.
+    pub currency: String,
+    /// Net balance in the source currency, debit-positive.
+    pub source_balance: Decimal,
+    /// Functional value at historical rates.
+    pub booked_functional: Decimal,
+    /// Period-end rate to the functional currency.
+    pub rate: Decimal,
+    /// Functional value at the period-end rate.
+    pub revalued_functional: Decimal,
+    /// Unrealized gain (positive) or loss (negative).
+    pub unrealized: Decimal,
+}
+
+/// Revaluation totals for one currency across accounts.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct CurrencyRevaluation {
+    /// Source currency.
+    pub currency: String,
+    /// Period-end rate to the functional currency.
+    pub rate: Decimal,
+    /// Net exposure in the source currency.
+    pub source_balance: Decimal,
+    /// Functional value at historical rates.
+    pub booked_functional: Decimal,
+    /// Functional value at the period-end rate.
+    pub revalued_functional: Decimal,
+    /// Unrealized gain (positive) or loss (negative).
+    pub unrealized: Decimal,
+}
+
+/// Outcome of revaluing a set of foreign balances.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct RevaluationResult {
+    /// Per-account results, in input order.
+    pub accounts: Vec<AccountRevaluation>,
+    /// Per-currency totals, ordered by currency code.
+    pub currencies: Vec<CurrencyRevaluation>,
+    /// Net unrealized gain (positive) or loss (negative).
+    pub total_unrealized: Decimal,
+    /// Currencies left out because no period-end rate was given.
+    pub missing_rates: Vec<String>,
+}
+
+/// One line of a revaluation adjustment, in the functional currency.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AdjustmentLine {
+    /// Account to post to.
+    pub account_id: Uuid,
+    /// Currency whose balance is restated, or `None` for the gain/loss side.
+    pub currency: Option<String>,
+    /// Debit amount.
+    pub debit: Decimal,
+    /// Credit amount.
+    pub credit: Decimal,
+}
+
+/// Reference number of the revaluation draft for the period ending on
+/// `period_end`, e.g. `FXREVAL-2025-06`.
+///
+/// Transaction references are unique per organization, so the reference
+/// also marks a period as already revalued.
+#[must_use]
+pub fn revaluation_reference(period_end: NaiveDate) -> String {
+    format!("FXREVAL-{}", period_end.format("%Y-%m"))
+}
+
+/// Revalues `balances` at the period-end `rates`.
+///
+/// `rates` maps a source currency to its rate into the functional currency.
+/// Revalued amounts are rounded to `decimal_places` with banker's rounding.
+/// Balances in a currency without a rate are skipped and the currency is
+/// reported in [`RevaluationResult::missing_rates`].
+#[must_use]
+pub fn revalue<S: BuildHasher>(
+    balances: &[ForeignBalance],
+    rates: &HashMap<String, Decimal, S>,
+    decimal_places: u32,
+) -> RevaluationResult {
+    let mut result = RevaluationResult::default();
+    let mut by_currency: BTreeMap<&str, CurrencyRevaluation> = BTreeMap::new();
+
+    for balance in balances {
+        let Some(&rate) = rates.get(&balance.currency) else {
+            if !result.missing_rates.contains(&balance.currency) {
+                result.missing_rates.push(balance.currency.clone());
+            }
+            continue;
+        };
+
+        let revalued = convert_amount(balance.source_balance, rate, decimal_places);
+        let unrealized = revalued - balance.booked_functional;
+
+        let totals = by_currency
+            .entry(&balance.currency)
+            .or_insert_with(|| CurrencyRevaluation {
+                currency: balance.currency.clone(),
+                rate,
+                source_balance: Decimal::ZERO,
+                booked_functional: Decimal::ZERO,
+                revalued_functional: Decimal::ZERO,
+                unrealized: Decimal::ZERO,
+            });
+        totals.source_balance += balance.source_balance;
+        totals.booked_functional += balance.booked_functional;
+        totals.revalued_functional += revalued;
+        totals.unrealized += unrealized;
+
+        result.total_unrealized += unrealized;
+        result.accounts.push(AccountRevaluation {
+            account_id: balance.account_id,
+            currency: balance.currency.clone(),
+            source_balance: balance.source_balance,
+            booked_functional: balance.booked_functional,
+            rate,
+            revalued_functional: revalued,
+            unrealized,
+        });
+    }
+
+    result.currencies = by_currency.into_values().collect();
+    result.missing_rates.sort();
+    result
+}
+
+impl RevaluationResult {
+    /// Builds the balanced adjustment that books the unrealized amounts.
+    ///
+    /// Each revalued account is debited with its gain or credited with its
+    /// loss; total gains are credited to `gain_account_id` and total losses
+    /// debited to `loss_account_id`. Accounts without a difference are left
+    /// out, so the result is empty when there is nothing to book.
+    #[must_use]
+    pub fn adjustment_lines(
+        &self,
+        gain_account_id: Uuid,
+        loss_account_id: Uuid,
+    ) -> Vec<AdjustmentLine> {
+        let mut lines = Vec::new();
+        let mut gains = Decimal::ZERO;
+        let mut losses = Decimal::ZERO;
+
+        for account in self.accounts.iter().filter(|a| !a.unrealized.is_zero()) {
+            let (debit, credit) = if account.unrealized > Decimal::ZERO {
+                gains += account.unrealized;
+                (account.unrealized, Decimal::ZERO)
+            } else {
+                losses -= account.unrealized;
+                (Decimal::ZERO, -account.unrealized)
+            };
+            lines.push(AdjustmentLine {
+                account_id: account.account_id,
+                currency: Some(account.currency.clone()),
+                debit,
+                credit,
+            });
+        }
+
+        if gains > Decimal::ZERO {
+            lines.push(AdjustmentLine {
+                account_id: gain_account_id,
+                currency: None,
+                debit: Decimal::ZERO,
+                credit: gains,
+            });
+        }
+        if losses > Decimal::ZERO {
+            lines.push(AdjustmentLine {
+                account_id: loss_account_id,
+                currency: None,
+                debit: losses,
+                credit: Decimal::ZERO,
+            });
+        }
+        lines
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+    use rust_decimal_macros::dec;
+
+    fn balance(account: u128, currency: &str, source: Decimal, booked: Decimal) -> ForeignBalance {
+        ForeignBalance {
+            account_id: Uuid::from_u128(account),
+            currency: currency.to_string(),
+            source_balance: source,
+            booked_functional: booked,
+        }
+    }
+
+    fn rates(pairs: &[(&str, Decimal)]) -> HashMap<String, Decimal> {
+        pairs.iter().map(|(c, r)| ((*c).to_string(), *r)).collect()
+    }
+
+    #[test]
+    fn test_asset_gains_and_liability_loses_when_rate_rises() {
+        // EUR receivable of 1,000 booked at 1.08, EUR payable of 400 booked at 1.10
+        let balances = [
+            balance(1, "EUR", dec!(1000), dec!(1080)),
+            balance(2, "EUR", dec!(-400), dec!(-440)),
+        ];
+
+        let result = revalue(&balances, &rates(&[("EUR", dec!(1.12))]), 2);
+
+        assert_eq!(result.accounts[0].revalued_functional, dec!(1120));
+        assert_eq!(result.accounts[0].unrealized, dec!(40));
+        assert_eq!(result.accounts[1].revalued_functional, dec!(-448));
+        assert_eq!(result.accounts[1].unrealized, dec!(-8));
+        assert_eq!(
+            result.currencies,
+            vec![CurrencyRevaluation {
+                currency: "EUR".to_string(),
+                rate: dec!(1.12),
+                source_balance: dec!(600),
+                booked_functional: dec!(640),
+                revalued_functional: dec!(672),
+                unrealized: dec!(32),
+            }]
+        );
+        assert_eq!(result.total_unrealized, dec!(32));
+        assert!(result.missing_rates.is_empty());
+    }
+
+    #[rstest]
+    // 0.125 * 100 = 12.5 -> 12 (half to even)
+    #[case(dec!(0.125), dec!(100), 0, dec!(12))]
+    // 0.135 * 100 = 13.5 -> 14 (half to even)
+    #[case(dec!(0.135), dec!(100), 0, dec!(14))]
+    // -12.345 -> -12.34 (half to even, symmetric for negatives)
+    #[case(dec!(-12.345), dec!(1), 2, dec!(-12.34))]
+    fn test_revalued_amounts_use_bankers_rounding(
+        #[case] source: Decimal,
+        #[case] rate: Decimal,
+        #[case] dp: u32,
+        #[case] expected: Decimal,
+    ) {
+        let balances = [balance(1, "JPY", source, Decimal::ZERO)];
+        let result = revalue(&balances, &rates(&[("JPY", rate)]), dp);
+
+        assert_eq!(result.accounts[0].revalued_functional, expected);
+        assert_eq!(result.accounts[0].unrealized, expected);
+    }
+
+    #[test]
+    fn test_missing_rates_are_reported_once_and_skipped() {
+        let balances = [
+            balance(1, "GBP", dec!(10), dec!(12)),
+            balance(2, "EUR", dec!(10), dec!(11)),
+            balance(3, "GBP", dec!(5), dec!(6)),
+        ];
+
+        let result = revalue(&balances, &rates(&[("EUR", dec!(1.1))]), 2);
+
+        assert_eq!(result.accounts.len(), 1);
+        assert_eq!(result.missing_rates, vec!["GBP".to_string()]);
+        assert_eq!(result.total_unrealized, Decimal::ZERO);
+    }
+
+    #[test]
+    fn test_currencies_are_ordered_by_code() {
+        let balances = [
+            balance(1, "JPY", dec!(1000), dec!(7)),
+            balance(2, "EUR", dec!(10), dec!(11)),
+        ];
+
+        let result = revalue(
+            &balances,
+            &rates(&[("EUR", dec!(1.1)), ("JPY", dec!(0.0067))]),
+            2,
+        );
+
+        let codes: Vec<&str> = result
+            .currencies
+            .iter()
+            .map(|c| c.currency.as_str())
+            .collect();
+        assert_eq!(codes, vec!["EUR", "JPY"]);
+        assert_eq!(result.total_unrealized, dec!(-0.30));
+    }
+
+    #[test]
+    fn test_adjustment_books_gains_and_losses_separately() {
+        let gain = Uuid::from_u128(90);
+        let loss = Uuid::from_u128(91);
+        let balances = [
+            balance(1, "EUR", dec!(1000), dec!(1080)),
+            balance(2, "EUR", dec!(-400), dec!(-440)),
+            balance(3, "GBP", dec!(100), dec!(127)),
+        ];
+        let result = revalue(
+            &balances,
+            &rates(&[("EUR", dec!(1.12)), ("GBP", dec!(1.27))]),
+            2,
+        );
+
+        let lines = result.adjustment_lines(gain, loss);
+
+        let summary: Vec<(Uuid, Decimal, Decimal)> = lines
+            .iter()
+            .map(|l| (l.account_id, l.debit, l.credit))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (Uuid::from_u128(1), dec!(40), Decimal::ZERO),
+                (Uuid::from_u128(2), Decimal::ZERO, dec!(8)),
+                (gain, Decimal::ZERO, dec!(40)),
+                (loss, dec!(8), Decimal::ZERO),
+            ]
+        );
+        let debits: Decimal = lines.iter().map(|l| l.debit).sum();
+        let credits: Decimal = lines.iter().map(|l| l.credit).sum();
+        assert_eq!(debits, credits);
+    }
+
+    #[test]
+    fn test_revaluation_reference_names_the_month() {
+        let june = NaiveDate::from_ymd_opt(2025, 6, 30).unwrap();
+        assert_eq!(revaluation_reference(june), "FXREVAL-2025-06");
+    }
+
+    #[test]
+    fn test_nothing_to_book_gives_no_lines() {
+        let balances = [balance(1, "EUR", dec!(100), dec!(110))];
+        let result = revalue(&balances, &rates(&[("EUR", dec!(1.1))]), 2);
+
+        assert!(
+            result
+                .adjustment_lines(Uuid::from_u128(90), Uuid::from_u128(91))
+                .is_empty()
+        );
+    }
+}
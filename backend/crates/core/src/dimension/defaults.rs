@@ -0,0 +1,172 @@
+//! Default dimension values configured per account.
+//!
+//! An account can carry at most one default value per dimension type. When an
+//! entry on that account is created without any value of the type, the
+//! default is added; a value given explicitly always wins.
+
+use std::collections::HashMap;
+use std::hash::BuildHasher;
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// A default dimension value of an account.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DimensionDefault {
+    /// Dimension type the default fills in.
+    pub dimension_type_id: Uuid,
+    /// Value applied when the type is missing.
+    pub dimension_value_id: Uuid,
+}
+
+/// An entry's dimensions after applying account defaults.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MergedDimensions {
+    /// Explicit values followed by the defaults that were added.
+    pub dimensions: Vec<Uuid>,
+    /// Values added from the defaults.
+    pub defaulted: Vec<Uuid>,
+}
+
+/// Appends the defaults whose dimension type has no explicit value.
+///
+/// `value_types` maps each explicit value to its dimension type; explicit
+/// values missing from it cover no type. Explicit values are kept in order.
+#[must_use]
+pub fn apply_defaults<S: BuildHasher>(
+    explicit: &[Uuid],
+    value_types: &HashMap<Uuid, Uuid, S>,
+    defaults: &[DimensionDefault],
+) -> MergedDimensions {
+    let mut dimensions = explicit.to_vec();
+    let mut defaulted = Vec::new();
+
+    for default in defaults {
+        let covered = explicit
+            .iter()
+            .any(|value| value_types.get(value) == Some(&default.dimension_type_id));
+        if !covered && !dimensions.contains(&default.dimension_value_id) {
+            dimensions.push(default.dimension_value_id);
+            defaulted.push(default.dimension_value_id);
+        }
+    }
+
+    MergedDimensions {
+        dimensions,
+        defaulted,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Fixture {
+        department: Uuid,
+        project: Uuid,
+        dept_eng: Uuid,
+        dept_sales: Uuid,
+        project_alpha: Uuid,
+        value_types: HashMap<Uuid, Uuid>,
+    }
+
+    fn fixture() -> Fixture {
+        let department = Uuid::new_v4();
+        let project = Uuid::new_v4();
+        let dept_eng = Uuid::new_v4();
+        let dept_sales = Uuid::new_v4();
+        let project_alpha = Uuid::new_v4();
+        let value_types = HashMap::from([
+            (dept_eng, department),
+            (dept_sales, department),
+            (project_alpha, project),
+        ]);
+        Fixture {
+            department,
+            project,
+            dept_eng,
+            dept_sales,
+            project_alpha,
+            value_types,
+        }
+    }
+
+    #[test]
+    fn test_default_fills_missing_type() {
+        let f = fixture();
+        let defaults = [DimensionDefault {
+            dimension_type_id: f.department,
+            dimension_value_id: f.dept_eng,
+        }];
+
+        let merged = apply_defaults(&[f.project_alpha], &f.value_types, &defaults);
+        assert_eq!(merged.dimensions, [f.project_alpha, f.dept_eng]);
+        assert_eq!(merged.defaulted, [f.dept_eng]);
+    }
+
+    #[test]
+    fn test_explicit_value_wins() {
+        let f = fixture();
+        let defaults = [DimensionDefault {
+            dimension_type_id: f.department,
+            dimension_value_id: f.dept_eng,
+        }];
+
+        let merged = apply_defaults(&[f.dept_sales], &f.value_types, &defaults);
+        assert_eq!(merged.dimensions, [f.dept_sales]);
+        assert!(merged.defaulted.is_empty());
+    }
+
+    #[test]
+    fn test_mixed_explicit_and_default_entries() {
+        let f = fixture();
+        let project_beta = Uuid::new_v4();
+        let defaults = [
+            DimensionDefault {
+                dimension_type_id: f.department,
+                dimension_value_id: f.dept_eng,
+            },
+            DimensionDefault {
+                dimension_type_id: f.project,
+                dimension_value_id: project_beta,
+            },
+        ];
+
+        // Department given, project defaulted
+        let merged = apply_defaults(&[f.dept_sales], &f.value_types, &defaults);
+        assert_eq!(merged.dimensions, [f.dept_sales, project_beta]);
+        assert_eq!(merged.defaulted, [project_beta]);
+
+        // Both given
+        let merged = apply_defaults(&[f.project_alpha, f.dept_sales], &f.value_types, &defaults);
+        assert_eq!(merged.dimensions, [f.project_alpha, f.dept_sales]);
+        assert!(merged.defaulted.is_empty());
+
+        // Neither given
+        let merged = apply_defaults(&[], &f.value_types, &defaults);
+        assert_eq!(merged.dimensions, [f.dept_eng, project_beta]);
+        assert_eq!(merged.defaulted, [f.dept_eng, project_beta]);
+    }
+
+    #[test]
+    fn test_default_already_given_is_not_flagged() {
+        let f = fixture();
+        let defaults = [DimensionDefault {
+            dimension_type_id: f.department,
+            dimension_value_id: f.dept_eng,
+        }];
+
+        // The value's type is unknown to the caller but the value is present
+        let merged = apply_defaults(&[f.dept_eng], &HashMap::new(), &defaults);
+        assert_eq!(merged.dimensions, [f.dept_eng]);
+        assert!(merged.defaulted.is_empty());
+    }
+
+    #[test]
+    fn test_no_defaults_keeps_explicit_values() {
+        let f = fixture();
+        let merged = apply_defaults(&[f.dept_sales, f.project_alpha], &f.value_types, &[]);
+        assert_eq!(merged.dimensions, [f.dept_sales, f.project_alpha]);
+        assert!(merged.defaulted.is_empty());
+    }
+}
@@ -1,5 +1,7 @@
 //! Dimensional reporting and filtering.
 
+pub mod defaults;
 pub mod filter;
 
+pub use defaults::{DimensionDefault, MergedDimensions, apply_defaults};
 pub use filter::DimensionFilter;
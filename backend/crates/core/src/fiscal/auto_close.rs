@@ -0,0 +1,130 @@
+//! Automatic closing of fiscal periods.
+//!
+//! A period gets a number of grace days after it ends, then is soft-closed,
+//! and is closed a further number of days later. Grace extensions granted
+//! for the period push both dates. One step is taken at a time, so a period
+//! found open past both dates is soft-closed first and closed on a later run.
+
+use chrono::{Days, NaiveDate};
+use serde::Serialize;
+
+use crate::settings::AutoCloseSettings;
+
+/// What the auto-close run should do with a period.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AutoCloseStep {
+    /// Move the open period to soft close.
+    SoftClose,
+    /// Close the soft-closed period, if its checklist passes.
+    Close,
+}
+
+/// When a period is due for each automatic step.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct AutoCloseSchedule {
+    /// First day the period may be soft-closed.
+    pub soft_close_on: NaiveDate,
+    /// First day the period may be closed.
+    pub close_on: NaiveDate,
+    /// Total grace extension granted for the period, in days.
+    pub extension_days: u32,
+}
+
+impl AutoCloseSchedule {
+    /// Schedule of a period ending on `period_end`, with `extension_days` of
+    /// grace granted on top of `settings`.
+    #[must_use]
+    pub fn new(period_end: NaiveDate, settings: &AutoCloseSettings, extension_days: u32) -> Self {
+        let soft_close_on = add_days(
+            period_end,
+            1 + u64::from(settings.soft_close_after_days) + u64::from(extension_days),
+        );
+        Self {
+            soft_close_on,
+            close_on: add_days(soft_close_on, u64::from(settings.close_after_days)),
+            extension_days,
+        }
+    }
+
+    /// The step due on `today` for a period that is open, or soft-closed
+    /// when `soft_closed` is set.
+    #[must_use]
+    pub fn due_step(&self, soft_closed: bool, today: NaiveDate) -> Option<AutoCloseStep> {
+        if soft_closed {
+            (today >= self.close_on).then_some(AutoCloseStep::Close)
+        } else {
+            (today >= self.soft_close_on).then_some(AutoCloseStep::SoftClose)
+        }
+    }
+}
+
+fn add_days(date: NaiveDate, days: u64) -> NaiveDate {
+    date.checked_add_days(Days::new(days))
+        .unwrap_or(NaiveDate::MAX)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, m, d).unwrap()
+    }
+
+    fn settings(soft: u32, close: u32) -> AutoCloseSettings {
+        AutoCloseSettings {
+            enabled: true,
+            soft_close_after_days: soft,
+            close_after_days: close,
+        }
+    }
+
+    #[test]
+    fn test_schedule_counts_from_period_end() {
+        let schedule = AutoCloseSchedule::new(date(1, 31), &settings(5, 10), 0);
+        assert_eq!(schedule.soft_close_on, date(2, 6));
+        assert_eq!(schedule.close_on, date(2, 16));
+    }
+
+    #[test]
+    fn test_extension_pushes_both_dates() {
+        let schedule = AutoCloseSchedule::new(date(1, 31), &settings(5, 10), 7);
+        assert_eq!(schedule.soft_close_on, date(2, 13));
+        assert_eq!(schedule.close_on, date(2, 23));
+        assert_eq!(schedule.extension_days, 7);
+    }
+
+    #[test]
+    fn test_due_step_takes_one_step_at_a_time() {
+        let schedule = AutoCloseSchedule::new(date(1, 31), &settings(5, 10), 0);
+
+        assert_eq!(schedule.due_step(false, date(2, 5)), None);
+        assert_eq!(
+            schedule.due_step(false, date(2, 6)),
+            Some(AutoCloseStep::SoftClose)
+        );
+        // Open long after both dates: soft close first
+        assert_eq!(
+            schedule.due_step(false, date(3, 31)),
+            Some(AutoCloseStep::SoftClose)
+        );
+        assert_eq!(schedule.due_step(true, date(2, 15)), None);
+        assert_eq!(
+            schedule.due_step(true, date(2, 16)),
+            Some(AutoCloseStep::Close)
+        );
+    }
+
+    #[test]
+    fn test_zero_days_is_due_the_day_after_the_period() {
+        let schedule = AutoCloseSchedule::new(date(1, 31), &settings(0, 0), 0);
+        assert_eq!(schedule.soft_close_on, date(2, 1));
+        assert_eq!(schedule.close_on, date(2, 1));
+        assert_eq!(schedule.due_step(false, date(1, 31)), None);
+        assert_eq!(
+            schedule.due_step(true, date(2, 1)),
+            Some(AutoCloseStep::Close)
+        );
+    }
+}
@@ -0,0 +1,118 @@
+//! Closing readiness checklist for a fiscal period.
+//!
+//! Each check looks for one kind of loose end in the period and reports the
+//! offending records. A `fail` means closing would lock in something wrong;
+//! a `warn` is worth reviewing but does not stop the close.
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Most offending IDs listed per check; `total` still counts all of them.
+pub const MAX_OFFENDING_IDS: usize = 50;
+
+/// Outcome of one check.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CheckStatus {
+    /// Nothing found.
+    Pass,
+    /// Something to review.
+    Warn,
+    /// Should be fixed before closing.
+    Fail,
+}
+
+/// One check in the checklist.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ClosingCheck {
+    /// Stable identifier of the check.
+    pub check: String,
+    /// Outcome.
+    pub status: CheckStatus,
+    /// What the check looks for.
+    pub description: String,
+    /// Number of offending records.
+    pub total: usize,
+    /// Offending record IDs, at most [`MAX_OFFENDING_IDS`].
+    pub ids: Vec<Uuid>,
+}
+
+impl ClosingCheck {
+    /// Builds a check that passes when `offenders` is empty and otherwise
+    /// reports `severity`.
+    #[must_use]
+    pub fn from_offenders(
+        check: &str,
+        description: &str,
+        severity: CheckStatus,
+        mut offenders: Vec<Uuid>,
+    ) -> Self {
+        let total = offenders.len();
+        offenders.truncate(MAX_OFFENDING_IDS);
+        Self {
+            check: check.to_string(),
+            status: if total == 0 {
+                CheckStatus::Pass
+            } else {
+                severity
+            },
+            description: description.to_string(),
+            total,
+            ids: offenders,
+        }
+    }
+}
+
+/// Worst status among `checks`, or `Pass` when there are none.
+#[must_use]
+pub fn overall_status(checks: &[ClosingCheck]) -> CheckStatus {
+    checks
+        .iter()
+        .map(|c| c.status)
+        .max()
+        .unwrap_or(CheckStatus::Pass)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ids(n: usize) -> Vec<Uuid> {
+        (0..n).map(|_| Uuid::new_v4()).collect()
+    }
+
+    #[test]
+    fn test_no_offenders_passes() {
+        let check = ClosingCheck::from_offenders("drafts", "", CheckStatus::Fail, vec![]);
+        assert_eq!(check.status, CheckStatus::Pass);
+        assert_eq!(check.total, 0);
+    }
+
+    #[test]
+    fn test_offenders_take_severity_and_are_capped() {
+        let offenders = ids(MAX_OFFENDING_IDS + 7);
+        let check =
+            ClosingCheck::from_offenders("drafts", "", CheckStatus::Warn, offenders.clone());
+        assert_eq!(check.status, CheckStatus::Warn);
+        assert_eq!(check.total, MAX_OFFENDING_IDS + 7);
+        assert_eq!(check.ids, offenders[..MAX_OFFENDING_IDS]);
+    }
+
+    #[test]
+    fn test_overall_status_is_worst() {
+        let pass = ClosingCheck::from_offenders("a", "", CheckStatus::Fail, vec![]);
+        let warn = ClosingCheck::from_offenders("b", "", CheckStatus::Warn, ids(1));
+        let fail = ClosingCheck::from_offenders("c", "", CheckStatus::Fail, ids(1));
+
+        assert_eq!(overall_status(&[]), CheckStatus::Pass);
+        assert_eq!(
+            overall_status(std::slice::from_ref(&pass)),
+            CheckStatus::Pass
+        );
+        assert_eq!(
+            overall_status(&[pass.clone(), warn.clone()]),
+            CheckStatus::Warn
+        );
+        assert_eq!(overall_status(&[warn, fail, pass]), CheckStatus::Fail);
+    }
+}
@@ -1,5 +1,15 @@
 //! Fiscal year and period management.
 
+pub mod auto_close;
+pub mod closing;
 pub mod period;
 
-pub use period::{FiscalPeriod, FiscalPeriodStatus, FiscalYear};
+#[cfg(test)]
+mod props;
+
+pub use auto_close::{AutoCloseSchedule, AutoCloseStep};
+pub use closing::{CheckStatus, ClosingCheck, MAX_OFFENDING_IDS, overall_status};
+pub use period::{
+    DateRange, FiscalPeriod, FiscalPeriodStatus, FiscalYear, PeriodCoverageFinding,
+    validate_period_coverage,
+};
@@ -67,3 +67,252 @@ impl FiscalPeriod {
         date >= self.start_date && date <= self.end_date
     }
 }
+
+/// An inclusive range of dates covered by a fiscal period.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DateRange {
+    /// First day of the range.
+    pub start_date: NaiveDate,
+    /// Last day of the range.
+    pub end_date: NaiveDate,
+}
+
+impl DateRange {
+    /// Creates a range from its first and last day.
+    #[must_use]
+    pub const fn new(start_date: NaiveDate, end_date: NaiveDate) -> Self {
+        Self {
+            start_date,
+            end_date,
+        }
+    }
+
+    /// The part of this range inside `bounds`; empty (start after end) when
+    /// they do not meet.
+    fn clamp_to(self, bounds: Self) -> Self {
+        Self::new(
+            self.start_date.max(bounds.start_date),
+            self.end_date.min(bounds.end_date),
+        )
+    }
+}
+
+/// A problem with how a fiscal year's periods cover the year.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum PeriodCoverageFinding {
+    /// Dates within the year that no period covers. Transactions dated in a
+    /// gap fail with no fiscal period.
+    Gap {
+        /// First uncovered day.
+        start_date: NaiveDate,
+        /// Last uncovered day.
+        end_date: NaiveDate,
+    },
+    /// Dates covered by more than one period.
+    Overlap {
+        /// First day covered twice.
+        start_date: NaiveDate,
+        /// Last day covered twice.
+        end_date: NaiveDate,
+    },
+    /// Dates of a period that fall outside the fiscal year.
+    OutsideYear {
+        /// First day outside the year.
+        start_date: NaiveDate,
+        /// Last day outside the year.
+        end_date: NaiveDate,
+    },
+    /// A period that ends before it starts.
+    InvalidRange {
+        /// The period's start date.
+        start_date: NaiveDate,
+        /// The period's end date.
+        end_date: NaiveDate,
+    },
+}
+
+impl PeriodCoverageFinding {
+    /// First day the finding applies to.
+    #[must_use]
+    pub const fn start_date(&self) -> NaiveDate {
+        match self {
+            Self::Gap { start_date, .. }
+            | Self::Overlap { start_date, .. }
+            | Self::OutsideYear { start_date, .. }
+            | Self::InvalidRange { start_date, .. } => *start_date,
+        }
+    }
+}
+
+/// Checks that `periods` cover every day of the fiscal year exactly once.
+///
+/// Periods may be given in any order. Adjustment periods, which share dates
+/// with the last regular period on purpose, should be left out. Findings
+/// are returned in date order; an empty list means the year is covered
+/// without gaps or overlaps.
+#[must_use]
+pub fn validate_period_coverage(
+    year: DateRange,
+    periods: &[DateRange],
+) -> Vec<PeriodCoverageFinding> {
+    let mut findings = Vec::new();
+    let mut sorted: Vec<DateRange> = Vec::with_capacity(periods.len());
+
+    for period in periods {
+        if period.end_date < period.start_date {
+            findings.push(PeriodCoverageFinding::InvalidRange {
+                start_date: period.start_date,
+                end_date: period.end_date,
+            });
+            continue;
+        }
+        if period.start_date < year.start_date {
+            findings.push(PeriodCoverageFinding::OutsideYear {
+                start_date: period.start_date,
+                end_date: period
+                    .end_date
+                    .min(year.start_date.pred_opt().unwrap_or(year.start_date)),
+            });
+        }
+        if period.end_date > year.end_date {
+            findings.push(PeriodCoverageFinding::OutsideYear {
+                start_date: period
+                    .start_date
+                    .max(year.end_date.succ_opt().unwrap_or(year.end_date)),
+                end_date: period.end_date,
+            });
+        }
+        sorted.push(period.clamp_to(year));
+    }
+    sorted.retain(|period| period.start_date <= period.end_date);
+    sorted.sort_by_key(|period| (period.start_date, period.end_date));
+
+    // First day not yet covered, `None` once the year's last day is covered
+    let mut next_uncovered = Some(year.start_date);
+    let mut covered_through: Option<NaiveDate> = None;
+    for period in sorted {
+        if let Some(through) = covered_through
+            && period.start_date <= through
+        {
+            findings.push(PeriodCoverageFinding::Overlap {
+                start_date: period.start_date,
+                end_date: period.end_date.min(through),
+            });
+        }
+        if let Some(next) = next_uncovered
+            && period.start_date > next
+        {
+            findings.push(PeriodCoverageFinding::Gap {
+                start_date: next,
+                end_date: period.start_date.pred_opt().unwrap_or(next),
+            });
+        }
+        let through = covered_through.map_or(period.end_date, |t| t.max(period.end_date));
+        covered_through = Some(through);
+        next_uncovered = through.succ_opt().filter(|d| *d <= year.end_date);
+    }
+    if let Some(next) = next_uncovered {
+        findings.push(PeriodCoverageFinding::Gap {
+            start_date: next,
+            end_date: year.end_date,
+        });
+    }
+
+    findings.sort_by_key(PeriodCoverageFinding::start_date);
+    findings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2025, month, day).unwrap()
+    }
+
+    fn year() -> DateRange {
+        DateRange::new(date(1, 1), date(12, 31))
+    }
+
+    fn quarters() -> Vec<DateRange> {
+        vec![
+            DateRange::new(date(1, 1), date(3, 31)),
+            DateRange::new(date(4, 1), date(6, 30)),
+            DateRange::new(date(7, 1), date(9, 30)),
+            DateRange::new(date(10, 1), date(12, 31)),
+        ]
+    }
+
+    #[test]
+    fn test_contiguous_quarters_validate_clean() {
+        assert!(validate_period_coverage(year(), &quarters()).is_empty());
+    }
+
+    #[test]
+    fn test_gap_between_quarters() {
+        let mut periods = quarters();
+        periods[2].start_date = date(7, 4);
+
+        assert_eq!(
+            validate_period_coverage(year(), &periods),
+            vec![PeriodCoverageFinding::Gap {
+                start_date: date(7, 1),
+                end_date: date(7, 3),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_overlap_between_quarters() {
+        let mut periods = quarters();
+        periods[0].end_date = date(4, 2);
+
+        assert_eq!(
+            validate_period_coverage(year(), &periods),
+            vec![PeriodCoverageFinding::Overlap {
+                start_date: date(4, 1),
+                end_date: date(4, 2),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_year_boundaries() {
+        let periods = vec![
+            DateRange::new(date(1, 5), date(6, 30)),
+            DateRange::new(date(7, 1), NaiveDate::from_ymd_opt(2026, 1, 3).unwrap()),
+        ];
+
+        assert_eq!(
+            validate_period_coverage(year(), &periods),
+            vec![
+                PeriodCoverageFinding::Gap {
+                    start_date: date(1, 1),
+                    end_date: date(1, 4),
+                },
+                PeriodCoverageFinding::OutsideYear {
+                    start_date: NaiveDate::from_ymd_opt(2026, 1, 1).unwrap(),
+                    end_date: NaiveDate::from_ymd_opt(2026, 1, 3).unwrap(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_inverted_period_and_empty_year() {
+        assert_eq!(
+            validate_period_coverage(year(), &[DateRange::new(date(2, 1), date(1, 31))]),
+            vec![
+                PeriodCoverageFinding::Gap {
+                    start_date: date(1, 1),
+                    end_date: date(12, 31),
+                },
+                PeriodCoverageFinding::InvalidRange {
+                    start_date: date(2, 1),
+                    end_date: date(1, 31),
+                },
+            ]
+        );
+    }
+}
@@ -0,0 +1,62 @@
+//! Property-based tests for fiscal period coverage.
+
+use chrono::{Duration, NaiveDate};
+use proptest::prelude::*;
+
+use super::period::{DateRange, PeriodCoverageFinding, validate_period_coverage};
+
+/// Strategy for a fiscal year and a partition of it into contiguous periods.
+///
+/// Years start on any day from 2000 through 2099 and last 28 to 400 days;
+/// period boundaries are drawn at random offsets within the year.
+fn partitioned_year() -> impl Strategy<Value = (DateRange, Vec<DateRange>)> {
+    (0i64..36_500, 28i64..=400).prop_flat_map(|(start_offset, length)| {
+        let cuts = prop::collection::btree_set(1..length, 0..24);
+        cuts.prop_map(move |cuts| {
+            let start = NaiveDate::from_ymd_opt(2000, 1, 1).unwrap() + Duration::days(start_offset);
+            let year = DateRange::new(start, start + Duration::days(length - 1));
+
+            let mut periods = Vec::with_capacity(cuts.len() + 1);
+            let mut period_start = 0;
+            for cut in cuts.into_iter().chain(std::iter::once(length)) {
+                periods.push(DateRange::new(
+                    start + Duration::days(period_start),
+                    start + Duration::days(cut - 1),
+                ));
+                period_start = cut;
+            }
+            (year, periods)
+        })
+    })
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(200))]
+
+    /// A partition of the year validates clean in any order.
+    #[test]
+    fn prop_partition_validates_clean(
+        (year, mut periods) in partitioned_year(),
+        rotate in 0usize..24,
+    ) {
+        let len = periods.len();
+        periods.rotate_left(rotate % len);
+        prop_assert!(validate_period_coverage(year, &periods).is_empty());
+    }
+
+    /// Removing one period leaves exactly one gap, spanning that period.
+    #[test]
+    fn prop_removed_period_is_one_gap(
+        (year, mut periods) in partitioned_year(),
+        index in 0usize..25,
+    ) {
+        let removed = periods.remove(index % periods.len());
+        prop_assert_eq!(
+            validate_period_coverage(year, &periods),
+            vec![PeriodCoverageFinding::Gap {
+                start_date: removed.start_date,
+                end_date: removed.end_date,
+            }]
+        );
+    }
+}
@@ -0,0 +1,284 @@
+//! Account activity over time, bucketed for charts.
+//!
+//! The database sums posted entries per bucket; this module lays those sums
+//! out over every bucket in the requested range and carries the running
+//! balance forward from the opening balance.
+
+use chrono::{Datelike, Days, Months, NaiveDate};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+use super::balance::AccountTypeForBalance;
+
+/// Most buckets a single activity request may span.
+pub const MAX_ACTIVITY_BUCKETS: usize = 366;
+
+/// Width of an activity bucket.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ActivityGranularity {
+    /// ISO weeks, starting on Monday.
+    Week,
+    /// Calendar months.
+    Month,
+}
+
+impl ActivityGranularity {
+    /// Parses a granularity from its query-string form.
+    #[must_use]
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "week" => Some(Self::Week),
+            "month" => Some(Self::Month),
+            _ => None,
+        }
+    }
+
+    /// Returns the unit name used by PostgreSQL's `date_trunc`.
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Week => "week",
+            Self::Month => "month",
+        }
+    }
+
+    /// Returns the first day of the bucket containing `date`.
+    #[must_use]
+    pub fn bucket_start(self, date: NaiveDate) -> NaiveDate {
+        match self {
+            Self::Week => date - Days::new(u64::from(date.weekday().num_days_from_monday())),
+            Self::Month => date.with_day(1).unwrap_or(date),
+        }
+    }
+
+    /// Returns the first day of the bucket after the one starting at `start`.
+    fn next_start(self, start: NaiveDate) -> NaiveDate {
+        match self {
+            Self::Week => start + Days::new(7),
+            Self::Month => start + Months::new(1),
+        }
+    }
+
+    /// Returns the start of every bucket overlapping `from..=to`.
+    #[must_use]
+    pub fn bucket_starts(self, from: NaiveDate, to: NaiveDate) -> Vec<NaiveDate> {
+        let mut starts = Vec::new();
+        let mut start = self.bucket_start(from);
+        while start <= to {
+            starts.push(start);
+            start = self.next_start(start);
+        }
+        starts
+    }
+}
+
+/// Posted debits and credits summed over one bucket.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ActivityTotals {
+    /// First day of the bucket, as truncated by the database.
+    pub bucket_start: NaiveDate,
+    /// Total debits.
+    pub debit: Decimal,
+    /// Total credits.
+    pub credit: Decimal,
+}
+
+/// One bucket of account activity, clipped to the requested range.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ActivityBucket {
+    /// First day of the bucket within the range.
+    pub start: NaiveDate,
+    /// Last day of the bucket within the range.
+    pub end: NaiveDate,
+    /// Total debits.
+    pub debit: Decimal,
+    /// Total credits.
+    pub credit: Decimal,
+    /// Change in balance, signed by the account's normal balance.
+    pub net_movement: Decimal,
+    /// Balance at the end of the bucket.
+    pub ending_balance: Decimal,
+}
+
+/// Lays out activity over every bucket in `from..=to`.
+///
+/// Buckets without totals have zero movement and carry the previous balance.
+/// Totals for buckets outside the range are ignored.
+#[must_use]
+pub fn build_activity_buckets(
+    from: NaiveDate,
+    to: NaiveDate,
+    granularity: ActivityGranularity,
+    normal_balance: AccountTypeForBalance,
+    opening_balance: Decimal,
+    totals: &[ActivityTotals],
+) -> Vec<ActivityBucket> {
+    let mut balance = opening_balance;
+    granularity
+        .bucket_starts(from, to)
+        .into_iter()
+        .map(|start| {
+            let (debit, credit) = totals
+                .iter()
+                .filter(|t| t.bucket_start == start)
+                .fold((Decimal::ZERO, Decimal::ZERO), |(d, c), t| {
+                    (d + t.debit, c + t.credit)
+                });
+            let net_movement = normal_balance.calculate_balance_change(debit, credit);
+            balance += net_movement;
+
+            let next = granularity.next_start(start);
+            ActivityBucket {
+                start: start.max(from),
+                end: next.pred_opt().unwrap_or(next).min(to),
+                debit,
+                credit,
+                net_movement,
+                ending_balance: balance,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    #[test]
+    fn test_parse_granularity() {
+        assert_eq!(
+            ActivityGranularity::parse("month"),
+            Some(ActivityGranularity::Month)
+        );
+        assert_eq!(
+            ActivityGranularity::parse("week"),
+            Some(ActivityGranularity::Week)
+        );
+        assert_eq!(ActivityGranularity::parse("day"), None);
+        assert_eq!(ActivityGranularity::parse("Month"), None);
+    }
+
+    #[test]
+    fn test_week_starts_on_monday() {
+        // 2026-01-01 is a Thursday
+        assert_eq!(
+            ActivityGranularity::Week.bucket_start(date(2026, 1, 1)),
+            date(2025, 12, 29)
+        );
+        assert_eq!(
+            ActivityGranularity::Week.bucket_start(date(2025, 12, 29)),
+            date(2025, 12, 29)
+        );
+    }
+
+    #[test]
+    fn test_bucket_starts_cover_partial_months() {
+        let starts = ActivityGranularity::Month.bucket_starts(date(2025, 11, 15), date(2026, 2, 3));
+        assert_eq!(
+            starts,
+            vec![
+                date(2025, 11, 1),
+                date(2025, 12, 1),
+                date(2026, 1, 1),
+                date(2026, 2, 1)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_entries_straddling_month_boundary() {
+        let totals = vec![
+            ActivityTotals {
+                bucket_start: date(2026, 1, 1),
+                debit: dec!(100),
+                credit: dec!(0),
+            },
+            ActivityTotals {
+                bucket_start: date(2026, 2, 1),
+                debit: dec!(0),
+                credit: dec!(30),
+            },
+        ];
+
+        let buckets = build_activity_buckets(
+            date(2026, 1, 1),
+            date(2026, 2, 28),
+            ActivityGranularity::Month,
+            AccountTypeForBalance::DebitNormal,
+            dec!(50),
+            &totals,
+        );
+
+        assert_eq!(buckets.len(), 2);
+        assert_eq!(buckets[0].end, date(2026, 1, 31));
+        assert_eq!(buckets[0].net_movement, dec!(100));
+        assert_eq!(buckets[0].ending_balance, dec!(150));
+        assert_eq!(buckets[1].start, date(2026, 2, 1));
+        assert_eq!(buckets[1].net_movement, dec!(-30));
+        assert_eq!(buckets[1].ending_balance, dec!(120));
+    }
+
+    #[test]
+    fn test_credit_normal_account_movement() {
+        let totals = vec![ActivityTotals {
+            bucket_start: date(2026, 1, 1),
+            debit: dec!(10),
+            credit: dec!(250),
+        }];
+
+        let buckets = build_activity_buckets(
+            date(2026, 1, 1),
+            date(2026, 1, 31),
+            ActivityGranularity::Month,
+            AccountTypeForBalance::CreditNormal,
+            Decimal::ZERO,
+            &totals,
+        );
+
+        assert_eq!(buckets[0].net_movement, dec!(240));
+        assert_eq!(buckets[0].ending_balance, dec!(240));
+    }
+
+    #[test]
+    fn test_empty_range_has_zero_buckets_at_opening_balance() {
+        let buckets = build_activity_buckets(
+            date(2026, 1, 5),
+            date(2026, 1, 18),
+            ActivityGranularity::Week,
+            AccountTypeForBalance::DebitNormal,
+            dec!(75),
+            &[],
+        );
+
+        assert_eq!(buckets.len(), 2);
+        assert!(
+            buckets
+                .iter()
+                .all(|b| b.debit.is_zero() && b.credit.is_zero() && b.ending_balance == dec!(75))
+        );
+        assert_eq!(buckets[1].start, date(2026, 1, 12));
+        assert_eq!(buckets[1].end, date(2026, 1, 18));
+    }
+
+    #[test]
+    fn test_buckets_are_clipped_to_range() {
+        let buckets = build_activity_buckets(
+            date(2026, 1, 15),
+            date(2026, 1, 20),
+            ActivityGranularity::Month,
+            AccountTypeForBalance::DebitNormal,
+            Decimal::ZERO,
+            &[],
+        );
+
+        assert_eq!(buckets.len(), 1);
+        assert_eq!(buckets[0].start, date(2026, 1, 15));
+        assert_eq!(buckets[0].end, date(2026, 1, 20));
+    }
+}
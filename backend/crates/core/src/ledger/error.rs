@@ -9,6 +9,8 @@ use rust_decimal::Decimal;
 use thiserror::Error;
 use uuid::Uuid;
 
+use super::validation::EntryAmountError;
+
 /// Errors that can occur during ledger operations.
 #[derive(Debug, Error)]
 pub enum LedgerError {
@@ -26,16 +28,20 @@ pub enum LedgerError {
         credit: Decimal,
     },
 
-    /// Entry amount cannot be zero.
-    #[error("Entry amount cannot be zero")]
+    /// Entry amount is zero on a transaction type that needs a real amount.
+    #[error(
+        "Zero amount is not allowed for this transaction type; only adjustment entries can be zero"
+    )]
     ZeroAmount,
 
     /// Entry amount cannot be negative.
-    #[error("Entry amount cannot be negative")]
+    #[error(
+        "Entry amount cannot be negative; enter it as a positive amount and flip the entry type between debit and credit"
+    )]
     NegativeAmount,
 
-    /// Entry must specify either debit or credit, not both.
-    #[error("Entry must specify either debit or credit, not both")]
+    /// Entry type is neither debit nor credit.
+    #[error("Entry type must be 'debit' or 'credit'")]
     InvalidEntryType,
 
     // ========== Account Errors ==========
@@ -152,6 +158,15 @@ pub enum LedgerError {
     Internal(String),
 }
 
+impl From<EntryAmountError> for LedgerError {
+    fn from(error: EntryAmountError) -> Self {
+        match error {
+            EntryAmountError::Negative => Self::NegativeAmount,
+            EntryAmountError::ZeroNotAllowed(_) => Self::ZeroAmount,
+        }
+    }
+}
+
 impl LedgerError {
     /// Returns the error code for API responses.
     #[must_use]
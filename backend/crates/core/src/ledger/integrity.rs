@@ -0,0 +1,315 @@
+//! Verification of stored running balances.
+//!
+//! Every ledger entry stores the account's version and the balance before and
+//! after it. [`RunningBalanceCheck`] replays an account's entries in version
+//! order with the same [`RunningBalance`] rules used when they were written,
+//! and reports the first entry whose stored values disagree with the replay.
+//!
+//! Deleting a draft removes its entries and leaves a hole in the version
+//! sequence. Such a hole is reported as a version gap and the replay resumes
+//! from the stored balance of the entry after it, so a legitimate gap does not
+//! turn every later entry into a divergence.
+
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::balance::{AccountTypeForBalance, RunningBalance};
+
+/// Outcome of checking one account.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IntegrityStatus {
+    /// Every stored balance matches the replay.
+    Ok,
+    /// Balances match, but versions are not consecutive.
+    VersionGap,
+    /// At least one stored balance differs from the replay.
+    Diverged,
+}
+
+/// A stored ledger entry as read for verification.
+#[derive(Debug, Clone, Copy)]
+pub struct StoredEntry {
+    /// Ledger entry ID.
+    pub entry_id: Uuid,
+    /// Stored account version.
+    pub account_version: i64,
+    /// Debit amount.
+    pub debit: Decimal,
+    /// Credit amount.
+    pub credit: Decimal,
+    /// Stored balance before the entry.
+    pub previous_balance: Decimal,
+    /// Stored balance after the entry.
+    pub current_balance: Decimal,
+}
+
+/// The first entry whose stored balance disagrees with the replay.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BalanceDivergence {
+    /// Ledger entry ID.
+    pub entry_id: Uuid,
+    /// Stored account version of the entry.
+    pub account_version: i64,
+    /// Balance after the entry as stored.
+    pub stored_balance: Decimal,
+    /// Balance after the entry as replayed.
+    pub expected_balance: Decimal,
+    /// `stored_balance - expected_balance`.
+    pub delta: Decimal,
+}
+
+/// The first break in an account's version sequence.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VersionGap {
+    /// The entry after the gap.
+    pub entry_id: Uuid,
+    /// Version the entry should have had.
+    pub expected_version: i64,
+    /// Version the entry has.
+    pub actual_version: i64,
+}
+
+/// Verification result for one account.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AccountIntegrity {
+    /// Overall status.
+    pub status: IntegrityStatus,
+    /// Number of entries replayed.
+    pub entries_checked: u64,
+    /// Number of entries whose stored balance disagrees with the replay.
+    pub divergent_entries: u64,
+    /// Balance after the last entry as stored.
+    pub stored_balance: Decimal,
+    /// Balance after the last entry as replayed.
+    pub expected_balance: Decimal,
+    /// First balance divergence, if any.
+    pub first_divergence: Option<BalanceDivergence>,
+    /// First version gap, if any.
+    pub first_gap: Option<VersionGap>,
+}
+
+/// Replays one account's entries and compares them to what is stored.
+///
+/// Entries must be pushed in ascending `account_version` order.
+#[derive(Debug, Clone)]
+pub struct RunningBalanceCheck {
+    balance_type: AccountTypeForBalance,
+    last: Option<RunningBalance>,
+    stored_balance: Decimal,
+    entries_checked: u64,
+    divergent_entries: u64,
+    first_divergence: Option<BalanceDivergence>,
+    first_gap: Option<VersionGap>,
+}
+
+impl RunningBalanceCheck {
+    /// Starts a check for an account with the given normal balance.
+    #[must_use]
+    pub const fn new(balance_type: AccountTypeForBalance) -> Self {
+        Self {
+            balance_type,
+            last: None,
+            stored_balance: Decimal::ZERO,
+            entries_checked: 0,
+            divergent_entries: 0,
+            first_divergence: None,
+            first_gap: None,
+        }
+    }
+
+    /// Replays the next entry.
+    pub fn push(&mut self, entry: &StoredEntry) {
+        let change = self
+            .balance_type
+            .calculate_balance_change(entry.debit, entry.credit);
+        let expected_version = self.last.as_ref().map_or(1, |l| l.account_version + 1);
+
+        let expected = if entry.account_version == expected_version {
+            match &self.last {
+                Some(last) => RunningBalance::next_entry(last, change),
+                None => RunningBalance::first_entry(change),
+            }
+        } else {
+            if self.first_gap.is_none() {
+                self.first_gap = Some(VersionGap {
+                    entry_id: entry.entry_id,
+                    expected_version,
+                    actual_version: entry.account_version,
+                });
+            }
+            // The missing entries cannot be replayed; resume from what the
+            // entry after the gap says it started from.
+            RunningBalance {
+                account_version: entry.account_version,
+                previous_balance: entry.previous_balance,
+                current_balance: entry.previous_balance + change,
+            }
+        };
+
+        if entry.current_balance != expected.current_balance {
+            self.divergent_entries += 1;
+            if self.first_divergence.is_none() {
+                self.first_divergence = Some(BalanceDivergence {
+                    entry_id: entry.entry_id,
+                    account_version: entry.account_version,
+                    stored_balance: entry.current_balance,
+                    expected_balance: expected.current_balance,
+                    delta: entry.current_balance - expected.current_balance,
+                });
+            }
+        }
+
+        self.stored_balance = entry.current_balance;
+        self.entries_checked += 1;
+        self.last = Some(expected);
+    }
+
+    /// Finishes the check.
+    #[must_use]
+    pub fn finish(self) -> AccountIntegrity {
+        let status = if self.first_divergence.is_some() {
+            IntegrityStatus::Diverged
+        } else if self.first_gap.is_some() {
+            IntegrityStatus::VersionGap
+        } else {
+            IntegrityStatus::Ok
+        };
+        AccountIntegrity {
+            status,
+            entries_checked: self.entries_checked,
+            divergent_entries: self.divergent_entries,
+            stored_balance: self.stored_balance,
+            expected_balance: self.last.map_or(Decimal::ZERO, |l| l.current_balance),
+            first_divergence: self.first_divergence,
+            first_gap: self.first_gap,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    /// Builds entries the way the insert trigger would.
+    fn chain(changes: &[(Decimal, Decimal)]) -> Vec<StoredEntry> {
+        let mut balance = Decimal::ZERO;
+        changes
+            .iter()
+            .enumerate()
+            .map(|(i, &(debit, credit))| {
+                let previous = balance;
+                balance += debit - credit;
+                StoredEntry {
+                    entry_id: Uuid::new_v4(),
+                    account_version: i64::try_from(i).unwrap() + 1,
+                    debit,
+                    credit,
+                    previous_balance: previous,
+                    current_balance: balance,
+                }
+            })
+            .collect()
+    }
+
+    fn check(entries: &[StoredEntry]) -> AccountIntegrity {
+        let mut check = RunningBalanceCheck::new(AccountTypeForBalance::DebitNormal);
+        for entry in entries {
+            check.push(entry);
+        }
+        check.finish()
+    }
+
+    #[test]
+    fn test_consistent_chain_is_ok() {
+        let entries = chain(&[
+            (dec!(100), dec!(0)),
+            (dec!(0), dec!(30)),
+            (dec!(5.25), dec!(0)),
+        ]);
+        let result = check(&entries);
+        assert_eq!(result.status, IntegrityStatus::Ok);
+        assert_eq!(result.entries_checked, 3);
+        assert_eq!(result.expected_balance, dec!(75.25));
+        assert_eq!(result.stored_balance, dec!(75.25));
+    }
+
+    #[test]
+    fn test_no_entries_is_ok() {
+        let result = check(&[]);
+        assert_eq!(result.status, IntegrityStatus::Ok);
+        assert_eq!(result.entries_checked, 0);
+        assert_eq!(result.expected_balance, Decimal::ZERO);
+    }
+
+    #[test]
+    fn test_corrupted_balance_is_pinpointed() {
+        let mut entries = chain(&[
+            (dec!(100), dec!(0)),
+            (dec!(0), dec!(30)),
+            (dec!(10), dec!(0)),
+        ]);
+        entries[1].current_balance = dec!(71);
+        let result = check(&entries);
+
+        assert_eq!(result.status, IntegrityStatus::Diverged);
+        assert_eq!(result.divergent_entries, 1);
+        let divergence = result.first_divergence.unwrap();
+        assert_eq!(divergence.entry_id, entries[1].entry_id);
+        assert_eq!(divergence.expected_balance, dec!(70));
+        assert_eq!(divergence.delta, dec!(1));
+        // Later entries were written from the correct balance.
+        assert_eq!(result.expected_balance, dec!(80));
+    }
+
+    #[test]
+    fn test_credit_normal_replay() {
+        let mut check = RunningBalanceCheck::new(AccountTypeForBalance::CreditNormal);
+        check.push(&StoredEntry {
+            entry_id: Uuid::new_v4(),
+            account_version: 1,
+            debit: dec!(0),
+            credit: dec!(50),
+            previous_balance: dec!(0),
+            current_balance: dec!(50),
+        });
+        assert_eq!(check.finish().status, IntegrityStatus::Ok);
+    }
+
+    #[test]
+    fn test_deleted_draft_is_a_gap_not_a_divergence() {
+        let mut entries = chain(&[
+            (dec!(100), dec!(0)),
+            (dec!(20), dec!(0)),
+            (dec!(0), dec!(10)),
+        ]);
+        let removed = entries.remove(1);
+        let result = check(&entries);
+
+        assert_eq!(result.status, IntegrityStatus::VersionGap);
+        let gap = result.first_gap.unwrap();
+        assert_eq!(gap.entry_id, entries[1].entry_id);
+        assert_eq!(gap.expected_version, removed.account_version);
+        assert_eq!(gap.actual_version, 3);
+        assert!(result.first_divergence.is_none());
+    }
+
+    #[test]
+    fn test_divergence_after_gap_is_still_reported() {
+        let mut entries = chain(&[
+            (dec!(100), dec!(0)),
+            (dec!(20), dec!(0)),
+            (dec!(0), dec!(10)),
+            (dec!(1), dec!(0)),
+        ]);
+        entries.remove(1);
+        entries[2].current_balance += dec!(-2);
+        let result = check(&entries);
+
+        assert_eq!(result.status, IntegrityStatus::Diverged);
+        assert_eq!(result.first_divergence.unwrap().delta, dec!(-2));
+        assert!(result.first_gap.is_some());
+    }
+}
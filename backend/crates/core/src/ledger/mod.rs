@@ -9,12 +9,18 @@
 //! - Error types for ledger operations
 //! - Ledger service for transaction validation
 //! - Fiscal period validation
+//! - Account activity buckets for charts
+//! - Settlement of invoices and bills by payments
+//! - Verification of stored running balances
 
+pub mod activity;
 pub mod balance;
 pub mod entry;
 pub mod error;
 pub mod fiscal;
+pub mod integrity;
 pub mod service;
+pub mod settlement;
 pub mod transaction;
 pub mod types;
 pub mod validation;
@@ -24,15 +30,29 @@ mod service_props;
 #[cfg(test)]
 mod validation_props;
 
-pub use balance::AccountBalance;
+pub use activity::{
+    ActivityBucket, ActivityGranularity, ActivityTotals, MAX_ACTIVITY_BUCKETS,
+    build_activity_buckets,
+};
+pub use balance::{AccountBalance, AccountTypeForBalance};
 pub use entry::{EntryType, LedgerEntry};
 pub use error::LedgerError;
 pub use fiscal::{
     period_allows_posting, period_requires_elevated_privileges, validate_posting_permission,
 };
+pub use integrity::{
+    AccountIntegrity, BalanceDivergence, IntegrityStatus, RunningBalanceCheck, StoredEntry,
+    VersionGap,
+};
 pub use service::{AccountInfo, LedgerService};
+pub use settlement::{ApplicationError, ApplicationLimits, SettlementStatus};
 pub use transaction::{Transaction, TransactionStatus};
 pub use types::{
-    CreateTransactionInput, EntryType as InputEntryType, FiscalPeriodStatus, LedgerEntryInput,
-    ResolvedEntry, TransactionResult, TransactionTotals, TransactionType,
+    CreateTransactionInput, EntryAmounts, EntryType as InputEntryType, FiscalPeriodStatus,
+    LedgerEntryInput, ResolvedEntry, SourceCurrencyTotal, TransactionResult, TransactionTotals,
+    TransactionType,
+};
+pub use validation::{
+    CurrencyImbalance, CurrencyLine, EntryAmountError, allows_zero_amount,
+    validate_currency_balance, validate_entry_amount,
 };
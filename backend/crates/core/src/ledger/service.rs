@@ -9,9 +9,11 @@ use uuid::Uuid;
 
 use super::error::LedgerError;
 use super::types::{
-    CreateTransactionInput, EntryType, LedgerEntryInput, ResolvedEntry, TransactionTotals,
+    CreateTransactionInput, EntryAmounts, EntryType, LedgerEntryInput, ResolvedEntry,
+    SourceCurrencyTotal, TransactionTotals, TransactionType,
 };
-use crate::currency::CurrencyService;
+use super::validation::validate_entry_amount;
+use crate::currency::{CurrencyService, STORED_DECIMAL_PLACES};
 
 /// Information about an account needed for validation.
 #[derive(Debug, Clone)]
@@ -37,7 +39,7 @@ impl LedgerService {
     ///
     /// This function performs all validation and resolution steps:
     /// 1. Validates minimum entries (at least 2)
-    /// 2. Validates each entry's amount (positive, non-zero)
+    /// 2. Validates each entry's amount (never negative, zero only on adjustments)
     /// 3. Validates accounts (exist, active, allow direct posting)
     /// 4. Validates dimensions (exist, active)
     /// 5. Resolves exchange rates for multi-currency entries
@@ -66,6 +68,39 @@ impl LedgerService {
         account_validator: A,
         dimension_validator: D,
     ) -> Result<(Vec<ResolvedEntry>, TransactionTotals), LedgerError>
+    where
+        F: Fn(&str, &str, NaiveDate) -> Option<Decimal>,
+        A: Fn(Uuid) -> Result<AccountInfo, LedgerError>,
+        D: Fn(&[Uuid]) -> Result<(), LedgerError>,
+    {
+        Self::validate_and_resolve_with_precision(
+            input,
+            org_base_currency,
+            STORED_DECIMAL_PLACES,
+            exchange_rate_lookup,
+            account_validator,
+            dimension_validator,
+        )
+    }
+
+    /// Validate and resolve a transaction, rounding functional amounts to
+    /// `functional_decimal_places`.
+    ///
+    /// Functional amounts are rounded before the balance check, so a
+    /// transaction balances in the precision of the functional currency.
+    /// See [`Self::validate_and_resolve`] for the steps performed.
+    ///
+    /// # Errors
+    ///
+    /// Returns `LedgerError` if validation fails.
+    pub fn validate_and_resolve_with_precision<F, A, D>(
+        input: &CreateTransactionInput,
+        org_base_currency: &str,
+        functional_decimal_places: u32,
+        exchange_rate_lookup: F,
+        account_validator: A,
+        dimension_validator: D,
+    ) -> Result<(Vec<ResolvedEntry>, TransactionTotals), LedgerError>
     where
         F: Fn(&str, &str, NaiveDate) -> Option<Decimal>,
         A: Fn(Uuid) -> Result<AccountInfo, LedgerError>,
@@ -82,8 +117,10 @@ impl LedgerService {
         for entry in &input.entries {
             let resolved_entry = Self::resolve_entry(
                 entry,
+                input.transaction_type,
                 input.transaction_date,
                 org_base_currency,
+                functional_decimal_places,
                 &exchange_rate_lookup,
                 &account_validator,
                 &dimension_validator,
@@ -105,10 +142,13 @@ impl LedgerService {
     }
 
     /// Resolve a single entry with exchange rate lookup.
+    #[allow(clippy::too_many_arguments)]
     fn resolve_entry<F, A, D>(
         entry: &LedgerEntryInput,
+        transaction_type: TransactionType,
         transaction_date: NaiveDate,
         org_base_currency: &str,
+        functional_decimal_places: u32,
         exchange_rate_lookup: &F,
         account_validator: &A,
         dimension_validator: &D,
@@ -118,13 +158,9 @@ impl LedgerService {
         A: Fn(Uuid) -> Result<AccountInfo, LedgerError>,
         D: Fn(&[Uuid]) -> Result<(), LedgerError>,
     {
-        // Validate amount
-        if entry.source_amount == Decimal::ZERO {
-            return Err(LedgerError::ZeroAmount);
-        }
-        if entry.source_amount < Decimal::ZERO {
-            return Err(LedgerError::NegativeAmount);
-        }
+        // Validate amount; a zero adjustment line resolves to zero on both
+        // sides and so never affects the balance check
+        validate_entry_amount(transaction_type, entry.source_amount)?;
 
         // Validate account
         let account_info = account_validator(entry.account_id)?;
@@ -140,9 +176,11 @@ impl LedgerService {
             dimension_validator(&entry.dimensions)?;
         }
 
-        // Get exchange rate
+        // Get exchange rate; an entered rate takes precedence over the lookup
         let exchange_rate = if entry.source_currency == org_base_currency {
             Decimal::ONE
+        } else if let Some(rate) = entry.exchange_rate {
+            rate
         } else {
             exchange_rate_lookup(&entry.source_currency, org_base_currency, transaction_date)
                 .ok_or_else(|| LedgerError::NoExchangeRate {
@@ -152,8 +190,12 @@ impl LedgerService {
                 })?
         };
 
-        // Calculate functional amount with Banker's Rounding (4 decimal places)
-        let functional_amount = CurrencyService::convert(entry.source_amount, exchange_rate);
+        // Calculate functional amount with Banker's Rounding
+        let functional_amount = CurrencyService::convert_with_precision(
+            entry.source_amount,
+            exchange_rate,
+            functional_decimal_places,
+        );
 
         // Determine debit/credit amounts
         let (debit, credit) = match entry.entry_type {
@@ -184,6 +226,61 @@ impl LedgerService {
         TransactionTotals::new(functional_debit, functional_credit)
     }
 
+    /// Sums entries per source currency, in order of first appearance.
+    ///
+    /// Source totals are rounded to `decimal_places` of their currency, so a
+    /// JPY subtotal has no fraction. Functional totals are left as stored.
+    #[must_use]
+    pub fn source_totals<'a, I, P>(entries: I, decimal_places: P) -> Vec<SourceCurrencyTotal>
+    where
+        I: IntoIterator<Item = EntryAmounts<'a>>,
+        P: Fn(&str) -> u32,
+    {
+        let mut totals: Vec<SourceCurrencyTotal> = Vec::new();
+        for entry in entries {
+            let index = if let Some(index) = totals
+                .iter()
+                .position(|t| t.currency == entry.source_currency)
+            {
+                index
+            } else {
+                totals.push(SourceCurrencyTotal {
+                    currency: entry.source_currency.to_string(),
+                    debit: Decimal::ZERO,
+                    credit: Decimal::ZERO,
+                    functional_debit: Decimal::ZERO,
+                    functional_credit: Decimal::ZERO,
+                });
+                totals.len() - 1
+            };
+            let total = &mut totals[index];
+            match entry.entry_type {
+                EntryType::Debit => {
+                    total.debit += entry.source_amount;
+                    total.functional_debit += entry.functional_amount;
+                }
+                EntryType::Credit => {
+                    total.credit += entry.source_amount;
+                    total.functional_credit += entry.functional_amount;
+                }
+            }
+        }
+
+        for total in &mut totals {
+            let places = decimal_places(&total.currency);
+            total.debit = Self::round_to_places(total.debit, places);
+            total.credit = Self::round_to_places(total.credit, places);
+        }
+        totals
+    }
+
+    /// Rounds with Banker's Rounding and pads to exactly `places` decimals.
+    fn round_to_places(amount: Decimal, places: u32) -> Decimal {
+        let mut rounded = CurrencyService::round(amount, places);
+        rounded.rescale(places);
+        rounded
+    }
+
     /// Validate that a transaction can be modified.
     ///
     /// # Errors
@@ -238,6 +335,7 @@ mod tests {
             entry_type,
             memo: None,
             dimensions: vec![],
+            exchange_rate: None,
         }
     }
 
@@ -351,6 +449,31 @@ mod tests {
         assert!(matches!(result, Err(LedgerError::ZeroAmount)));
     }
 
+    #[test]
+    fn test_validate_zero_amount_adjustment_line() {
+        let entries = vec![
+            make_entry(EntryType::Debit, dec!(100)),
+            make_entry(EntryType::Credit, dec!(100)),
+            make_entry(EntryType::Debit, dec!(0)),
+        ];
+        let mut input = make_input(entries);
+        input.transaction_type = TransactionType::Adjustment;
+
+        let (resolved, totals) = LedgerService::validate_and_resolve(
+            &input,
+            "USD",
+            same_currency_rate_lookup,
+            ok_account_validator,
+            ok_dimension_validator,
+        )
+        .unwrap();
+
+        assert_eq!(resolved[2].debit, Decimal::ZERO);
+        assert_eq!(resolved[2].credit, Decimal::ZERO);
+        assert!(totals.is_balanced);
+        assert_eq!(totals.functional_debit, dec!(100));
+    }
+
     #[test]
     fn test_validate_negative_amount() {
         let entries = vec![
@@ -451,6 +574,26 @@ mod tests {
         assert!(matches!(result, Err(LedgerError::NoExchangeRate { .. })));
     }
 
+    #[test]
+    fn test_validate_amount_before_exchange_rate() {
+        let mut entries = vec![
+            make_entry(EntryType::Debit, dec!(-100)),
+            make_entry(EntryType::Credit, dec!(100)),
+        ];
+        entries[0].source_currency = "EUR".to_string();
+        let input = make_input(entries);
+
+        let result = LedgerService::validate_and_resolve(
+            &input,
+            "USD",
+            |_: &str, _: &str, _: NaiveDate| None,
+            ok_account_validator,
+            ok_dimension_validator,
+        );
+
+        assert!(matches!(result, Err(LedgerError::NegativeAmount)));
+    }
+
     #[test]
     fn test_multi_currency_conversion() {
         let mut entries = vec![
@@ -486,6 +629,67 @@ mod tests {
         assert!(totals.is_balanced);
     }
 
+    #[test]
+    fn test_entered_rate_replaces_lookup() {
+        let mut entries = vec![
+            make_entry(EntryType::Debit, dec!(100)),
+            make_entry(EntryType::Credit, dec!(120)),
+        ];
+        entries[0].source_currency = "EUR".to_string();
+        entries[0].exchange_rate = Some(dec!(1.2));
+        let input = make_input(entries);
+
+        let (resolved, totals) = LedgerService::validate_and_resolve(
+            &input,
+            "USD",
+            |from: &str, _to: &str, _date: NaiveDate| (from != "EUR").then_some(Decimal::ONE),
+            ok_account_validator,
+            ok_dimension_validator,
+        )
+        .unwrap();
+
+        assert_eq!(resolved[0].exchange_rate, dec!(1.2));
+        assert_eq!(resolved[0].functional_amount, dec!(120));
+        assert!(totals.is_balanced);
+    }
+
+    #[test]
+    fn test_functional_amounts_round_to_functional_places() {
+        // USD 10.01 at 151.25 = JPY 1514.0125, resolved to whole yen
+        let mut entries = vec![
+            make_entry(EntryType::Debit, dec!(10.01)),
+            make_entry(EntryType::Credit, dec!(1514)),
+        ];
+        entries[1].source_currency = "JPY".to_string();
+        let input = make_input(entries);
+        let rate_lookup = |_from: &str, _to: &str, _date: NaiveDate| Some(dec!(151.25));
+
+        let (resolved, totals) = LedgerService::validate_and_resolve_with_precision(
+            &input,
+            "JPY",
+            0,
+            rate_lookup,
+            ok_account_validator,
+            ok_dimension_validator,
+        )
+        .unwrap();
+        assert_eq!(resolved[0].functional_amount, dec!(1514));
+        assert!(totals.is_balanced);
+
+        // At the stored precision the same entries do not balance
+        let result = LedgerService::validate_and_resolve(
+            &input,
+            "JPY",
+            rate_lookup,
+            ok_account_validator,
+            ok_dimension_validator,
+        );
+        assert!(matches!(
+            result,
+            Err(LedgerError::UnbalancedTransaction { .. })
+        ));
+    }
+
     #[test]
     fn test_same_currency_rate_is_one() {
         let entries = vec![
@@ -548,4 +752,85 @@ mod tests {
             Err(LedgerError::CanOnlyDeleteDraft)
         ));
     }
+
+    fn amounts(
+        currency: &str,
+        entry_type: EntryType,
+        source_amount: Decimal,
+        functional_amount: Decimal,
+    ) -> EntryAmounts<'_> {
+        EntryAmounts {
+            source_currency: currency,
+            source_amount,
+            functional_amount,
+            entry_type,
+        }
+    }
+
+    fn places(currency: &str) -> u32 {
+        if currency == "JPY" { 0 } else { 2 }
+    }
+
+    #[test]
+    fn test_source_totals_single_currency() {
+        let entries = [
+            amounts("USD", EntryType::Debit, dec!(60.0000), dec!(60.0000)),
+            amounts("USD", EntryType::Debit, dec!(40.0000), dec!(40.0000)),
+            amounts("USD", EntryType::Credit, dec!(100.0000), dec!(100.0000)),
+        ];
+
+        let totals = LedgerService::source_totals(entries, places);
+
+        assert_eq!(
+            totals,
+            vec![SourceCurrencyTotal {
+                currency: "USD".to_string(),
+                debit: dec!(100.00),
+                credit: dec!(100.00),
+                functional_debit: dec!(100.0000),
+                functional_credit: dec!(100.0000),
+            }]
+        );
+        assert_eq!(totals[0].debit.to_string(), "100.00");
+    }
+
+    #[test]
+    fn test_source_totals_multi_currency_transfer() {
+        // Paid 1,000 EUR from a EUR bank account into a USD account
+        let entries = [
+            amounts("USD", EntryType::Debit, dec!(1087.0000), dec!(1087.0000)),
+            amounts("EUR", EntryType::Credit, dec!(1000.0000), dec!(1087.0000)),
+        ];
+
+        let totals = LedgerService::source_totals(entries, places);
+
+        assert_eq!(totals.len(), 2);
+        assert_eq!(totals[0].currency, "USD");
+        assert_eq!(totals[1].currency, "EUR");
+        assert_eq!(totals[1].debit, Decimal::ZERO);
+        assert_eq!(totals[1].credit.to_string(), "1000.00");
+        assert_eq!(totals[1].functional_credit, dec!(1087.0000));
+    }
+
+    #[test]
+    fn test_source_totals_round_to_currency_places() {
+        let entries = [
+            amounts("JPY", EntryType::Debit, dec!(1000.0000), dec!(6.6700)),
+            amounts("JPY", EntryType::Debit, dec!(500.5000), dec!(3.3300)),
+            amounts("USD", EntryType::Credit, dec!(10.0050), dec!(10.0000)),
+        ];
+
+        let totals = LedgerService::source_totals(entries, places);
+
+        // 1500.5 rounds half to even
+        assert_eq!(totals[0].debit.to_string(), "1500");
+        assert_eq!(totals[0].credit.to_string(), "0");
+        assert_eq!(totals[0].functional_debit, dec!(10.0000));
+        assert_eq!(totals[1].credit.to_string(), "10.00");
+    }
+
+    #[test]
+    fn test_source_totals_empty() {
+        assert!(LedgerService::source_totals([], places).is_empty());
+    }
 }
@@ -49,6 +49,7 @@ fn make_entry(entry_type: EntryType, amount: Decimal, currency: &str) -> LedgerE
         entry_type,
         memo: None,
         dimensions: vec![],
+        exchange_rate: None,
     }
 }
 
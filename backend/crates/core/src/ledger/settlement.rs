@@ -0,0 +1,201 @@
+//! Settlement of invoices and bills by payments.
+//!
+//! A posted payment can be applied against one or more invoices or bills,
+//! each with a partial amount. A payment cannot apply more than its own
+//! functional total, and an invoice cannot receive more than its outstanding
+//! balance. The invoice's settlement status follows from what was applied.
+
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+/// How far an invoice or bill has been settled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SettlementStatus {
+    /// Nothing applied yet.
+    Open,
+    /// Some, but not all, of the total applied.
+    PartiallyPaid,
+    /// The whole total applied.
+    Paid,
+}
+
+impl SettlementStatus {
+    /// Status of a document with `total` of which `applied` was paid.
+    #[must_use]
+    pub fn from_amounts(total: Decimal, applied: Decimal) -> Self {
+        if applied <= Decimal::ZERO {
+            Self::Open
+        } else if applied < total {
+            Self::PartiallyPaid
+        } else {
+            Self::Paid
+        }
+    }
+
+    /// Returns the status as its API string.
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Open => "open",
+            Self::PartiallyPaid => "partially_paid",
+            Self::Paid => "paid",
+        }
+    }
+}
+
+/// Why a payment application was rejected.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum ApplicationError {
+    /// The applied amount is zero or negative.
+    #[error("Applied amount must be positive")]
+    NonPositiveAmount,
+
+    /// The payment does not have enough left to apply.
+    #[error("Applied amount {requested} exceeds the payment's unapplied {available}")]
+    ExceedsPayment {
+        /// Amount asked for.
+        requested: Decimal,
+        /// Payment total not yet applied elsewhere.
+        available: Decimal,
+    },
+
+    /// The invoice or bill has less outstanding than the amount.
+    #[error("Applied amount {requested} exceeds the outstanding balance {outstanding}")]
+    ExceedsOutstanding {
+        /// Amount asked for.
+        requested: Decimal,
+        /// Balance left to settle.
+        outstanding: Decimal,
+    },
+}
+
+/// Totals of a payment and the document it settles, before an application.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ApplicationLimits {
+    /// Functional total of the payment.
+    pub payment_total: Decimal,
+    /// Amount of the payment already applied.
+    pub payment_applied: Decimal,
+    /// Functional total of the invoice or bill.
+    pub document_total: Decimal,
+    /// Amount of the invoice or bill already settled.
+    pub document_applied: Decimal,
+}
+
+impl ApplicationLimits {
+    /// Payment total not yet applied.
+    #[must_use]
+    pub fn payment_available(&self) -> Decimal {
+        (self.payment_total - self.payment_applied).max(Decimal::ZERO)
+    }
+
+    /// Invoice or bill total not yet settled.
+    #[must_use]
+    pub fn outstanding(&self) -> Decimal {
+        (self.document_total - self.document_applied).max(Decimal::ZERO)
+    }
+
+    /// Checks that `amount` fits both the payment and the document.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first limit `amount` breaks.
+    pub fn check(&self, amount: Decimal) -> Result<(), ApplicationError> {
+        if amount <= Decimal::ZERO {
+            return Err(ApplicationError::NonPositiveAmount);
+        }
+        let available = self.payment_available();
+        if amount > available {
+            return Err(ApplicationError::ExceedsPayment {
+                requested: amount,
+                available,
+            });
+        }
+        let outstanding = self.outstanding();
+        if amount > outstanding {
+            return Err(ApplicationError::ExceedsOutstanding {
+                requested: amount,
+                outstanding,
+            });
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+    use rust_decimal_macros::dec;
+
+    #[rstest]
+    #[case(dec!(0), SettlementStatus::Open)]
+    #[case(dec!(0.0001), SettlementStatus::PartiallyPaid)]
+    #[case(dec!(999.9999), SettlementStatus::PartiallyPaid)]
+    #[case(dec!(1000), SettlementStatus::Paid)]
+    fn test_status_from_amounts(#[case] applied: Decimal, #[case] expected: SettlementStatus) {
+        assert_eq!(
+            SettlementStatus::from_amounts(dec!(1000), applied),
+            expected
+        );
+    }
+
+    #[test]
+    fn test_status_serializes_snake_case() {
+        let json = serde_json::to_string(&SettlementStatus::PartiallyPaid).unwrap();
+        assert_eq!(json, "\"partially_paid\"");
+        assert_eq!(SettlementStatus::PartiallyPaid.as_str(), "partially_paid");
+    }
+
+    fn limits() -> ApplicationLimits {
+        // A 600 payment with 100 already applied, against a 1000 invoice
+        // with 700 already settled
+        ApplicationLimits {
+            payment_total: dec!(600),
+            payment_applied: dec!(100),
+            document_total: dec!(1000),
+            document_applied: dec!(700),
+        }
+    }
+
+    #[test]
+    fn test_check_accepts_amount_within_both_limits() {
+        assert_eq!(limits().check(dec!(300)), Ok(()));
+    }
+
+    #[test]
+    fn test_check_rejects_non_positive_amount() {
+        assert_eq!(
+            limits().check(Decimal::ZERO),
+            Err(ApplicationError::NonPositiveAmount)
+        );
+    }
+
+    #[test]
+    fn test_check_rejects_more_than_payment_has_left() {
+        let limits = ApplicationLimits {
+            document_applied: Decimal::ZERO,
+            ..limits()
+        };
+        assert_eq!(
+            limits.check(dec!(500.01)),
+            Err(ApplicationError::ExceedsPayment {
+                requested: dec!(500.01),
+                available: dec!(500),
+            })
+        );
+    }
+
+    #[test]
+    fn test_check_rejects_more_than_outstanding() {
+        assert_eq!(
+            limits().check(dec!(300.01)),
+            Err(ApplicationError::ExceedsOutstanding {
+                requested: dec!(300.01),
+                outstanding: dec!(300),
+            })
+        );
+    }
+}
@@ -3,11 +3,15 @@
 //! This module defines the core types used for creating and validating
 //! financial transactions in the double-entry bookkeeping system.
 
+use std::str::FromStr;
+
 use chrono::NaiveDate;
 use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use super::error::LedgerError;
+
 /// Entry type: either Debit or Credit.
 ///
 /// In double-entry bookkeeping:
@@ -22,6 +26,30 @@ pub enum EntryType {
     Credit,
 }
 
+impl FromStr for EntryType {
+    type Err = LedgerError;
+
+    /// Parses `debit` or `credit`, ignoring ASCII case.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.eq_ignore_ascii_case("debit") {
+            Ok(Self::Debit)
+        } else if s.eq_ignore_ascii_case("credit") {
+            Ok(Self::Credit)
+        } else {
+            Err(LedgerError::InvalidEntryType)
+        }
+    }
+}
+
+impl From<EntryType> for super::entry::EntryType {
+    fn from(entry_type: EntryType) -> Self {
+        match entry_type {
+            EntryType::Debit => Self::Debit,
+            EntryType::Credit => Self::Credit,
+        }
+    }
+}
+
 /// Transaction type classification.
 ///
 /// Categorizes transactions for reporting and workflow purposes.
@@ -126,6 +154,8 @@ pub struct LedgerEntryInput {
     pub memo: Option<String>,
     /// Dimension value IDs to tag this entry with.
     pub dimensions: Vec<Uuid>,
+    /// Rate entered for this entry instead of looking one up.
+    pub exchange_rate: Option<Decimal>,
 }
 
 /// Input for creating a new transaction.
@@ -228,10 +258,52 @@ impl TransactionTotals {
     }
 }
 
+/// The amounts of one entry, as grouped by [`LedgerService::source_totals`].
+///
+/// [`LedgerService::source_totals`]: super::LedgerService::source_totals
+#[derive(Debug, Clone, Copy)]
+pub struct EntryAmounts<'a> {
+    /// The source currency code.
+    pub source_currency: &'a str,
+    /// The amount in source currency.
+    pub source_amount: Decimal,
+    /// The amount in functional currency.
+    pub functional_amount: Decimal,
+    /// Whether the entry is a debit or a credit.
+    pub entry_type: EntryType,
+}
+
+/// A transaction's entries summed per source currency.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SourceCurrencyTotal {
+    /// The source currency code.
+    pub currency: String,
+    /// Total debits in source currency.
+    pub debit: Decimal,
+    /// Total credits in source currency.
+    pub credit: Decimal,
+    /// Total debits of these entries in functional currency.
+    pub functional_debit: Decimal,
+    /// Total credits of these entries in functional currency.
+    pub functional_credit: Decimal,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_entry_type_from_str() {
+        assert_eq!("debit".parse::<EntryType>().unwrap(), EntryType::Debit);
+        assert_eq!("Credit".parse::<EntryType>().unwrap(), EntryType::Credit);
+        for invalid in ["", "dr", "debits", " debit"] {
+            assert!(matches!(
+                invalid.parse::<EntryType>(),
+                Err(LedgerError::InvalidEntryType)
+            ));
+        }
+    }
+
     #[test]
     fn test_transaction_status_editable() {
         assert!(TransactionStatus::Draft.is_editable());
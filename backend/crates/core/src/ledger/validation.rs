@@ -1,9 +1,23 @@
 //! Business rule validation for ledger operations.
+//!
+//! Entry amounts are always entered as positive numbers; the entry type says
+//! which side they land on. The one exception is an adjustment line with a
+//! zero amount, which posts nothing and is used as a reclassification
+//! placeholder.
+//!
+//! Organizations can also require each source currency to balance on its own,
+//! so a foreign-currency debit cannot be offset by a functional-currency
+//! credit at an arbitrary rate.
+
+use std::collections::BTreeMap;
 
 use rust_decimal::Decimal;
+use serde::Serialize;
 use thiserror::Error;
+use uuid::Uuid;
 
 use super::entry::{EntryType, LedgerEntry};
+use super::types::TransactionType;
 
 /// Validation errors for ledger operations.
 #[derive(Debug, Error)]
@@ -30,6 +44,52 @@ pub enum LedgerValidationError {
     InvalidAmount,
 }
 
+/// Why a single entry amount was rejected.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Error)]
+pub enum EntryAmountError {
+    /// The amount is below zero.
+    #[error(
+        "Entry amount cannot be negative; enter it as a positive amount and flip the entry type between debit and credit"
+    )]
+    Negative,
+
+    /// The amount is zero on a transaction type that needs a real amount.
+    #[error(
+        "Zero amount is not allowed for this transaction type; only adjustment entries can be zero"
+    )]
+    ZeroNotAllowed(TransactionType),
+}
+
+/// Returns true if entries of `transaction_type` may have a zero amount.
+#[must_use]
+pub const fn allows_zero_amount(transaction_type: TransactionType) -> bool {
+    matches!(transaction_type, TransactionType::Adjustment)
+}
+
+/// Validates one entry's source amount for a transaction of
+/// `transaction_type`.
+///
+/// Negative amounts are rejected for every type. Zero is accepted only on
+/// adjustments; such a line has neither a debit nor a credit and does not
+/// count towards the balance check.
+///
+/// # Errors
+///
+/// Returns an error if the amount is negative, or zero on a type that does
+/// not allow it.
+pub fn validate_entry_amount(
+    transaction_type: TransactionType,
+    amount: Decimal,
+) -> Result<(), EntryAmountError> {
+    if amount < Decimal::ZERO {
+        return Err(EntryAmountError::Negative);
+    }
+    if amount.is_zero() && !allows_zero_amount(transaction_type) {
+        return Err(EntryAmountError::ZeroNotAllowed(transaction_type));
+    }
+    Ok(())
+}
+
 /// Validates that a set of ledger entries is balanced.
 ///
 /// # Errors
@@ -76,6 +136,72 @@ pub fn validate_entries(entries: &[LedgerEntry]) -> Result<(), LedgerValidationE
     Ok(())
 }
 
+/// A line as seen by the per-currency balance check.
+#[derive(Debug, Clone)]
+pub struct CurrencyLine {
+    /// Account the line posts to.
+    pub account_id: Uuid,
+    /// Currency the line was entered in.
+    pub source_currency: String,
+    /// Whether the line is a debit or credit.
+    pub entry_type: EntryType,
+    /// Amount in the source currency.
+    pub source_amount: Decimal,
+}
+
+/// A source currency whose lines do not balance.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct CurrencyImbalance {
+    /// The source currency.
+    pub currency: String,
+    /// Total of its debit lines.
+    pub debits: Decimal,
+    /// Total of its credit lines.
+    pub credits: Decimal,
+    /// Debits minus credits.
+    pub difference: Decimal,
+}
+
+/// Validates that each source currency balances in source amounts.
+///
+/// A currency with a line on `fx_clearing_account_id` is exempt: posting
+/// through the clearing account is how a currency is explicitly paired with
+/// another one.
+///
+/// # Errors
+///
+/// Returns the unbalanced currencies, in alphabetical order.
+pub fn validate_currency_balance(
+    lines: &[CurrencyLine],
+    fx_clearing_account_id: Option<Uuid>,
+) -> Result<(), Vec<CurrencyImbalance>> {
+    let mut groups: BTreeMap<&str, (Decimal, Decimal, bool)> = BTreeMap::new();
+    for line in lines {
+        let (debits, credits, cleared) = groups.entry(&line.source_currency).or_default();
+        match line.entry_type {
+            EntryType::Debit => *debits += line.source_amount,
+            EntryType::Credit => *credits += line.source_amount,
+        }
+        *cleared |= Some(line.account_id) == fx_clearing_account_id;
+    }
+
+    let imbalances: Vec<CurrencyImbalance> = groups
+        .into_iter()
+        .filter(|(_, (debits, credits, cleared))| debits != credits && !cleared)
+        .map(|(currency, (debits, credits, _))| CurrencyImbalance {
+            currency: currency.to_string(),
+            debits,
+            credits,
+            difference: debits - credits,
+        })
+        .collect();
+    if imbalances.is_empty() {
+        Ok(())
+    } else {
+        Err(imbalances)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -123,6 +249,27 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn test_entry_amount_rules() {
+        let amount = Decimal::new(2500, 2);
+        assert_eq!(
+            validate_entry_amount(TransactionType::Journal, amount),
+            Ok(())
+        );
+        assert_eq!(
+            validate_entry_amount(TransactionType::Adjustment, Decimal::ZERO),
+            Ok(())
+        );
+        assert_eq!(
+            validate_entry_amount(TransactionType::Journal, Decimal::ZERO),
+            Err(EntryAmountError::ZeroNotAllowed(TransactionType::Journal))
+        );
+        assert_eq!(
+            validate_entry_amount(TransactionType::Adjustment, -amount),
+            Err(EntryAmountError::Negative)
+        );
+    }
+
     #[test]
     fn test_single_sided() {
         let entries = vec![
@@ -134,4 +281,40 @@ mod tests {
             Err(LedgerValidationError::SingleSided)
         ));
     }
+
+    fn line(account_id: Uuid, currency: &str, entry_type: EntryType, cents: i64) -> CurrencyLine {
+        CurrencyLine {
+            account_id,
+            source_currency: currency.to_string(),
+            entry_type,
+            source_amount: Decimal::new(cents, 2),
+        }
+    }
+
+    #[test]
+    fn test_currency_paired_through_clearing_account() {
+        let (expense, cash, clearing) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+        // A EUR expense paid in USD, balanced in functional currency only
+        let fudged = [
+            line(expense, "EUR", EntryType::Debit, 10_000),
+            line(cash, "USD", EntryType::Credit, 11_000),
+        ];
+        let imbalances = validate_currency_balance(&fudged, Some(clearing)).unwrap_err();
+        assert_eq!(imbalances.len(), 2);
+        assert_eq!(imbalances[0].currency, "EUR");
+        assert_eq!(imbalances[0].difference, Decimal::new(10_000, 2));
+        assert_eq!(imbalances[1].difference, Decimal::new(-11_000, 2));
+
+        let through_clearing = [
+            line(expense, "EUR", EntryType::Debit, 10_000),
+            line(clearing, "EUR", EntryType::Credit, 9_000),
+            line(clearing, "USD", EntryType::Debit, 11_000),
+            line(cash, "USD", EntryType::Credit, 11_000),
+        ];
+        assert_eq!(
+            validate_currency_balance(&through_clearing, Some(clearing)),
+            Ok(())
+        );
+        assert!(validate_currency_balance(&through_clearing, None).is_err());
+    }
 }
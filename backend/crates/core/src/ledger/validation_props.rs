@@ -5,10 +5,15 @@
 
 use proptest::prelude::*;
 use rust_decimal::Decimal;
+use uuid::Uuid;
 use zeltra_shared::types::{AccountId, LedgerEntryId, TransactionId};
 
 use super::entry::{EntryType, LedgerEntry};
-use super::validation::{LedgerValidationError, validate_entries};
+use super::types::TransactionType;
+use super::validation::{
+    CurrencyLine, EntryAmountError, LedgerValidationError, validate_currency_balance,
+    validate_entries, validate_entry_amount,
+};
 
 /// Strategy to generate a valid positive amount (> 0).
 fn positive_amount() -> impl Strategy<Value = Decimal> {
@@ -27,6 +32,21 @@ fn entry_type_strategy() -> impl Strategy<Value = EntryType> {
     prop_oneof![Just(EntryType::Debit), Just(EntryType::Credit)]
 }
 
+/// Strategy to generate any transaction type.
+fn transaction_type_strategy() -> impl Strategy<Value = TransactionType> {
+    prop_oneof![
+        Just(TransactionType::Journal),
+        Just(TransactionType::Expense),
+        Just(TransactionType::Invoice),
+        Just(TransactionType::Bill),
+        Just(TransactionType::Payment),
+        Just(TransactionType::Transfer),
+        Just(TransactionType::Adjustment),
+        Just(TransactionType::OpeningBalance),
+        Just(TransactionType::Reversal),
+    ]
+}
+
 /// Helper to create a ledger entry for testing.
 fn make_entry(entry_type: EntryType, amount: Decimal) -> LedgerEntry {
     LedgerEntry {
@@ -190,6 +210,114 @@ proptest! {
     }
 }
 
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(100))]
+
+    // =========================================================================
+    // Property 13 (continued): Entry amount rules per transaction type
+    // =========================================================================
+
+    /// Property 13.7: Negative amounts are rejected for every type.
+    ///
+    /// *For any* transaction type and amount < 0, the error SHALL be
+    /// `Negative`, never the zero-amount error.
+    #[test]
+    fn prop_negative_amount_rejected_for_every_type(
+        transaction_type in transaction_type_strategy(),
+        amount in negative_amount(),
+    ) {
+        prop_assert_eq!(
+            validate_entry_amount(transaction_type, amount),
+            Err(EntryAmountError::Negative)
+        );
+    }
+
+    /// Property 13.8: Zero amounts are accepted only on adjustments.
+    ///
+    /// *For any* transaction type, a zero amount SHALL be accepted if and
+    /// only if the type is adjustment.
+    #[test]
+    fn prop_zero_amount_only_for_adjustments(
+        transaction_type in transaction_type_strategy(),
+    ) {
+        let result = validate_entry_amount(transaction_type, Decimal::ZERO);
+        if transaction_type == TransactionType::Adjustment {
+            prop_assert_eq!(result, Ok(()));
+        } else {
+            prop_assert_eq!(result, Err(EntryAmountError::ZeroNotAllowed(transaction_type)));
+        }
+    }
+
+    /// Property 13.9: Positive amounts are accepted for every type.
+    #[test]
+    fn prop_positive_amount_accepted_for_every_type(
+        transaction_type in transaction_type_strategy(),
+        amount in positive_amount(),
+    ) {
+        prop_assert_eq!(validate_entry_amount(transaction_type, amount), Ok(()));
+    }
+}
+
+/// Strategy to generate a source currency.
+fn currency_strategy() -> impl Strategy<Value = &'static str> {
+    prop_oneof![Just("USD"), Just("EUR"), Just("JPY"), Just("IDR")]
+}
+
+/// Mirrors each (currency, amount) pair as a debit and a credit line.
+fn mirrored_lines(pairs: &[(&str, Decimal)]) -> Vec<CurrencyLine> {
+    pairs
+        .iter()
+        .flat_map(|(currency, amount)| {
+            [EntryType::Debit, EntryType::Credit].map(|entry_type| CurrencyLine {
+                account_id: Uuid::new_v4(),
+                source_currency: (*currency).to_string(),
+                entry_type,
+                source_amount: *amount,
+            })
+        })
+        .collect()
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(100))]
+
+    // =========================================================================
+    // Property 13 (continued): Per-currency balance
+    // =========================================================================
+
+    /// Property 13.9: Mirrored entries balance in every currency.
+    ///
+    /// *For any* transaction built by mirroring entries per currency, the
+    /// per-currency check SHALL pass.
+    #[test]
+    fn prop_mirrored_entries_balance_per_currency(
+        pairs in prop::collection::vec((currency_strategy(), positive_amount()), 1..8),
+    ) {
+        let lines = mirrored_lines(&pairs);
+        prop_assert_eq!(validate_currency_balance(&lines, None), Ok(()));
+    }
+
+    /// Property 13.10: Perturbing one source amount names its currency.
+    ///
+    /// *For any* mirrored transaction with one line's amount changed, the
+    /// check SHALL fail with exactly that line's currency.
+    #[test]
+    fn prop_perturbed_amount_names_its_currency(
+        pairs in prop::collection::vec((currency_strategy(), positive_amount()), 1..8),
+        index in any::<prop::sample::Index>(),
+        delta in positive_amount(),
+    ) {
+        let mut lines = mirrored_lines(&pairs);
+        let perturbed = index.index(lines.len());
+        lines[perturbed].source_amount += delta;
+
+        let imbalances = validate_currency_balance(&lines, None).unwrap_err();
+        prop_assert_eq!(imbalances.len(), 1);
+        prop_assert_eq!(&imbalances[0].currency, &lines[perturbed].source_currency);
+        prop_assert_eq!(imbalances[0].difference.abs(), delta);
+    }
+}
+
 #[cfg(test)]
 mod unit_tests {
     use super::*;
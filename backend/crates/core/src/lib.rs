@@ -5,7 +5,9 @@
 //!
 //! # Modules
 //!
+//! - `account_import` - Chart of accounts CSV import and hierarchy ordering
 //! - `auth` - Authentication and password hashing
+//! - `bank_import` - Bank statement import and account matching
 //! - `ledger` - Double-entry bookkeeping logic
 //! - `currency` - Multi-currency handling and exchange rates
 //! - `fiscal` - Fiscal year and period management
@@ -13,20 +15,34 @@
 //! - `simulation` - What-if scenario projections
 //! - `dimension` - Dimensional reporting and filtering
 //! - `workflow` - Transaction workflow and approval management
+//! - `comment` - Transaction comments and mentions
 //! - `reports` - Financial report generation
+//! - `reconciliation` - Bank account reconciliation rules
+//! - `organization` - Organization slug rules
+//! - `settings` - Organization settings schema and validation
+//! - `template` - Transaction templates for quick entry
 //! - `dashboard` - Dashboard metrics and activity types
+//! - `notification` - Notification types and delivery preferences
 //! - `storage` - File attachment storage (OpenDAL)
 //! - `attachment` - Attachment service and types
 
+pub mod account_import;
 pub mod attachment;
 pub mod auth;
+pub mod bank_import;
 pub mod budget;
+pub mod comment;
 pub mod currency;
 pub mod dashboard;
 pub mod dimension;
 pub mod fiscal;
 pub mod ledger;
+pub mod notification;
+pub mod organization;
+pub mod reconciliation;
 pub mod reports;
+pub mod settings;
 pub mod simulation;
 pub mod storage;
+pub mod template;
 pub mod workflow;
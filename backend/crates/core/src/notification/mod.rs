@@ -0,0 +1,174 @@
+//! Notification types and delivery preferences.
+//!
+//! This module provides:
+//! - The kinds of notification users receive
+//! - How each kind is delivered: by email, in the app, or both
+
+use std::fmt;
+use std::str::FromStr;
+
+use thiserror::Error;
+
+/// Something a user is notified about.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum NotificationType {
+    /// A transaction was submitted that the user can approve.
+    ApprovalRequested,
+    /// A transaction the user can approve has been waiting too long.
+    ApprovalReminder,
+    /// Approvers were reminded about a transaction the user submitted.
+    ApprovalPending,
+    /// A transaction the user submitted was rejected.
+    TransactionRejected,
+    /// The user was mentioned in a transaction comment.
+    CommentMention,
+    /// The period-end FX revaluation could not be prepared.
+    FxRevaluationSkipped,
+    /// A fiscal period was soft-closed automatically.
+    PeriodSoftClosed,
+    /// A fiscal period was closed automatically.
+    PeriodClosed,
+    /// A fiscal period due to close automatically has failing closing checks.
+    PeriodCloseBlocked,
+}
+
+impl NotificationType {
+    /// Every notification type, in the order preferences are listed.
+    pub const ALL: [Self; 9] = [
+        Self::ApprovalRequested,
+        Self::ApprovalReminder,
+        Self::ApprovalPending,
+        Self::TransactionRejected,
+        Self::CommentMention,
+        Self::FxRevaluationSkipped,
+        Self::PeriodSoftClosed,
+        Self::PeriodClosed,
+        Self::PeriodCloseBlocked,
+    ];
+
+    /// Returns the type as its API string.
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::ApprovalRequested => "approval_requested",
+            Self::ApprovalReminder => "approval_reminder",
+            Self::ApprovalPending => "approval_pending",
+            Self::TransactionRejected => "transaction_rejected",
+            Self::CommentMention => "comment_mention",
+            Self::FxRevaluationSkipped => "fx_revaluation_skipped",
+            Self::PeriodSoftClosed => "period_soft_closed",
+            Self::PeriodClosed => "period_closed",
+            Self::PeriodCloseBlocked => "period_close_blocked",
+        }
+    }
+}
+
+impl fmt::Display for NotificationType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for NotificationType {
+    type Err = NotificationError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|t| t.as_str() == s)
+            .ok_or_else(|| NotificationError::UnknownType(s.to_string()))
+    }
+}
+
+/// Where a notification type is delivered.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Delivery {
+    /// Email only.
+    Email,
+    /// The in-app inbox only.
+    InApp,
+    /// Email and the in-app inbox.
+    #[default]
+    Both,
+}
+
+impl Delivery {
+    /// Returns the delivery as its API string.
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Email => "email",
+            Self::InApp => "in_app",
+            Self::Both => "both",
+        }
+    }
+
+    /// Whether an email is sent.
+    #[must_use]
+    pub const fn email(self) -> bool {
+        matches!(self, Self::Email | Self::Both)
+    }
+
+    /// Whether the notification is written to the inbox.
+    #[must_use]
+    pub const fn in_app(self) -> bool {
+        matches!(self, Self::InApp | Self::Both)
+    }
+}
+
+impl fmt::Display for Delivery {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for Delivery {
+    type Err = NotificationError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "email" => Ok(Self::Email),
+            "in_app" => Ok(Self::InApp),
+            "both" => Ok(Self::Both),
+            _ => Err(NotificationError::UnknownDelivery(s.to_string())),
+        }
+    }
+}
+
+/// Why a notification setting was rejected.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum NotificationError {
+    /// The notification type string is not recognized.
+    #[error("Unknown notification type: {0}")]
+    UnknownType(String),
+
+    /// The delivery string is not recognized.
+    #[error("Unknown delivery: {0}, expected email, in_app or both")]
+    UnknownDelivery(String),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_type_round_trip() {
+        for t in NotificationType::ALL {
+            assert_eq!(t.as_str().parse::<NotificationType>(), Ok(t));
+        }
+        assert_eq!(
+            "mention".parse::<NotificationType>(),
+            Err(NotificationError::UnknownType("mention".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_delivery_channels() {
+        assert!(Delivery::Email.email() && !Delivery::Email.in_app());
+        assert!(!Delivery::InApp.email() && Delivery::InApp.in_app());
+        assert!(Delivery::Both.email() && Delivery::Both.in_app());
+        assert_eq!(Delivery::default(), Delivery::Both);
+        assert_eq!("in_app".parse::<Delivery>(), Ok(Delivery::InApp));
+        assert!("none".parse::<Delivery>().is_err());
+    }
+}
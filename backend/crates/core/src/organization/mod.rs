@@ -0,0 +1,129 @@
+//! Organization identity rules.
+//!
+//! This module provides:
+//! - Slug validation (lowercase kebab-case, length limits, reserved words)
+
+use thiserror::Error;
+
+/// Minimum slug length.
+pub const SLUG_MIN_LEN: usize = 3;
+
+/// Maximum slug length.
+pub const SLUG_MAX_LEN: usize = 50;
+
+/// Slugs that collide with application routes or could impersonate staff.
+pub const RESERVED_SLUGS: &[&str] = &[
+    "admin",
+    "api",
+    "app",
+    "assets",
+    "auth",
+    "billing",
+    "dashboard",
+    "docs",
+    "help",
+    "login",
+    "logout",
+    "new",
+    "organizations",
+    "register",
+    "settings",
+    "signup",
+    "static",
+    "status",
+    "support",
+    "www",
+];
+
+/// Why a slug was rejected.
+#[derive(Debug, Clone, Copy, Error, PartialEq, Eq)]
+pub enum SlugError {
+    /// Shorter than [`SLUG_MIN_LEN`].
+    #[error("Slug must be at least {SLUG_MIN_LEN} characters")]
+    TooShort,
+    /// Longer than [`SLUG_MAX_LEN`].
+    #[error("Slug must be at most {SLUG_MAX_LEN} characters")]
+    TooLong,
+    /// Not lowercase kebab-case.
+    #[error("Slug may only contain lowercase letters, digits and single hyphens between them")]
+    InvalidFormat,
+    /// One of [`RESERVED_SLUGS`].
+    #[error("Slug is reserved")]
+    Reserved,
+}
+
+/// Validates an organization slug.
+///
+/// A valid slug is lowercase kebab-case: ASCII lowercase letters and digits
+/// in groups joined by single hyphens, e.g. `acme-2026`.
+///
+/// # Errors
+///
+/// Returns the first rule the slug breaks.
+pub fn validate_slug(slug: &str) -> Result<(), SlugError> {
+    if slug.len() < SLUG_MIN_LEN {
+        return Err(SlugError::TooShort);
+    }
+    if slug.len() > SLUG_MAX_LEN {
+        return Err(SlugError::TooLong);
+    }
+
+    let well_formed = slug.split('-').all(|part| {
+        !part.is_empty() && part.bytes().all(|b| matches!(b, b'a'..=b'z' | b'0'..=b'9'))
+    });
+    if !well_formed {
+        return Err(SlugError::InvalidFormat);
+    }
+
+    if RESERVED_SLUGS.contains(&slug) {
+        return Err(SlugError::Reserved);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("acme")]
+    #[case("acme-corp")]
+    #[case("acme-2026")]
+    #[case("a1b")]
+    #[case("x-y-z")]
+    fn test_valid_slugs(#[case] slug: &str) {
+        assert_eq!(validate_slug(slug), Ok(()));
+    }
+
+    #[rstest]
+    #[case("ab", SlugError::TooShort)]
+    #[case("", SlugError::TooShort)]
+    #[case(&"a".repeat(51), SlugError::TooLong)]
+    #[case("Acme", SlugError::InvalidFormat)]
+    #[case("acme corp", SlugError::InvalidFormat)]
+    #[case("acme_corp", SlugError::InvalidFormat)]
+    #[case("-acme", SlugError::InvalidFormat)]
+    #[case("acme-", SlugError::InvalidFormat)]
+    #[case("acme--corp", SlugError::InvalidFormat)]
+    #[case("açme", SlugError::InvalidFormat)]
+    #[case("api", SlugError::Reserved)]
+    #[case("admin", SlugError::Reserved)]
+    fn test_invalid_slugs(#[case] slug: &str, #[case] expected: SlugError) {
+        assert_eq!(validate_slug(slug), Err(expected));
+    }
+
+    #[test]
+    fn test_length_limits_are_inclusive() {
+        assert_eq!(validate_slug(&"a".repeat(SLUG_MIN_LEN)), Ok(()));
+        assert_eq!(validate_slug(&"a".repeat(SLUG_MAX_LEN)), Ok(()));
+    }
+
+    #[test]
+    fn test_every_reserved_slug_is_rejected_as_reserved() {
+        // Reserved words must pass the other rules, or the list is dead weight
+        for slug in RESERVED_SLUGS {
+            assert_eq!(validate_slug(slug), Err(SlugError::Reserved), "{slug}");
+        }
+    }
+}
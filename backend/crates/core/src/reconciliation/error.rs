@@ -0,0 +1,27 @@
+//! Reconciliation error types.
+
+use rust_decimal::Decimal;
+use thiserror::Error;
+
+/// Reconciliation rule violations.
+#[derive(Debug, Clone, Error, PartialEq, Eq)]
+pub enum ReconciliationError {
+    /// The reconciliation has been completed and is locked.
+    #[error("Reconciliation is completed and cannot be modified")]
+    AlreadyCompleted,
+
+    /// The reconciliation is not completed, so it cannot be reopened.
+    #[error("Reconciliation is not completed")]
+    NotCompleted,
+
+    /// Cleared entries do not account for the statement balance.
+    #[error("Reconciliation is out of balance by {difference}")]
+    OutOfBalance {
+        /// Statement change minus the net of cleared entries.
+        difference: Decimal,
+    },
+
+    /// The entry is dated after the statement date.
+    #[error("Entry is dated after the statement date")]
+    EntryAfterStatementDate,
+}
@@ -0,0 +1,17 @@
+//! Bank account reconciliation.
+//!
+//! This module provides pure business logic for reconciling an account
+//! against a bank statement:
+//! - Reconciliation status rules (what can change while in progress)
+//! - Balance check of cleared entries against the statement ending balance
+
+pub mod error;
+pub mod service;
+pub mod types;
+
+#[cfg(test)]
+mod tests;
+
+pub use error::ReconciliationError;
+pub use service::ReconciliationService;
+pub use types::{ReconciliationCheck, ReconciliationStatus};
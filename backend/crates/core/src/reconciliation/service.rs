@@ -0,0 +1,90 @@
+//! Reconciliation service for balance checks and status rules.
+
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+
+use super::error::ReconciliationError;
+use super::types::{ReconciliationCheck, ReconciliationStatus};
+
+/// Reconciliation service for business logic.
+pub struct ReconciliationService;
+
+impl ReconciliationService {
+    /// Compares the net of cleared entries with the statement movement.
+    ///
+    /// The statement movement is the ending balance minus the ending balance of
+    /// the previous completed reconciliation (the opening balance).
+    #[must_use]
+    pub fn check(
+        opening_balance: Decimal,
+        statement_ending_balance: Decimal,
+        cleared_net: Decimal,
+    ) -> ReconciliationCheck {
+        ReconciliationCheck {
+            opening_balance,
+            statement_ending_balance,
+            cleared_net,
+            difference: statement_ending_balance - opening_balance - cleared_net,
+        }
+    }
+
+    /// Validates that entries may still be cleared or uncleared.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ReconciliationError::AlreadyCompleted` for completed reconciliations.
+    pub fn validate_can_modify(status: ReconciliationStatus) -> Result<(), ReconciliationError> {
+        match status {
+            ReconciliationStatus::InProgress => Ok(()),
+            ReconciliationStatus::Completed => Err(ReconciliationError::AlreadyCompleted),
+        }
+    }
+
+    /// Validates that an entry may be cleared against a statement.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ReconciliationError::EntryAfterStatementDate` if the entry's
+    /// transaction date is after the statement date.
+    pub fn validate_entry_date(
+        transaction_date: NaiveDate,
+        statement_date: NaiveDate,
+    ) -> Result<(), ReconciliationError> {
+        if transaction_date > statement_date {
+            return Err(ReconciliationError::EntryAfterStatementDate);
+        }
+        Ok(())
+    }
+
+    /// Validates that a reconciliation can be completed.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ReconciliationError::AlreadyCompleted` if it is already completed,
+    /// or `ReconciliationError::OutOfBalance` with the difference if the cleared
+    /// entries do not match the statement.
+    pub fn validate_complete(
+        status: ReconciliationStatus,
+        check: &ReconciliationCheck,
+    ) -> Result<(), ReconciliationError> {
+        Self::validate_can_modify(status)?;
+        if !check.is_balanced() {
+            return Err(ReconciliationError::OutOfBalance {
+                difference: check.difference,
+            });
+        }
+        Ok(())
+    }
+
+    /// Validates that a reconciliation can be reopened.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ReconciliationError::NotCompleted` if it is still in progress.
+    pub fn validate_reopen(status: ReconciliationStatus) -> Result<(), ReconciliationError> {
+        match status {
+            ReconciliationStatus::Completed => Ok(()),
+            ReconciliationStatus::InProgress => Err(ReconciliationError::NotCompleted),
+        }
+    }
+}
@@ -0,0 +1,98 @@
+//! Tests for reconciliation rules.
+
+use chrono::NaiveDate;
+use proptest::prelude::*;
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+
+use super::error::ReconciliationError;
+use super::service::ReconciliationService;
+use super::types::ReconciliationStatus;
+
+#[test]
+fn test_check_balanced() {
+    let check = ReconciliationService::check(dec!(1000.00), dec!(1250.50), dec!(250.50));
+    assert!(check.is_balanced());
+    assert_eq!(check.difference, Decimal::ZERO);
+}
+
+#[test]
+fn test_check_reports_difference() {
+    let check = ReconciliationService::check(dec!(1000.00), dec!(1250.50), dec!(200.00));
+    assert!(!check.is_balanced());
+    assert_eq!(check.difference, dec!(50.50));
+
+    assert_eq!(
+        ReconciliationService::validate_complete(ReconciliationStatus::InProgress, &check),
+        Err(ReconciliationError::OutOfBalance {
+            difference: dec!(50.50)
+        })
+    );
+}
+
+#[test]
+fn test_completed_is_locked() {
+    let check = ReconciliationService::check(Decimal::ZERO, Decimal::ZERO, Decimal::ZERO);
+    assert_eq!(
+        ReconciliationService::validate_can_modify(ReconciliationStatus::Completed),
+        Err(ReconciliationError::AlreadyCompleted)
+    );
+    assert_eq!(
+        ReconciliationService::validate_complete(ReconciliationStatus::Completed, &check),
+        Err(ReconciliationError::AlreadyCompleted)
+    );
+    assert!(
+        ReconciliationService::validate_complete(ReconciliationStatus::InProgress, &check).is_ok()
+    );
+}
+
+#[test]
+fn test_reopen_requires_completed() {
+    assert!(ReconciliationService::validate_reopen(ReconciliationStatus::Completed).is_ok());
+    assert_eq!(
+        ReconciliationService::validate_reopen(ReconciliationStatus::InProgress),
+        Err(ReconciliationError::NotCompleted)
+    );
+}
+
+#[test]
+fn test_entry_date_validation() {
+    let statement = NaiveDate::from_ymd_opt(2026, 1, 31).unwrap();
+    let on = statement;
+    let after = NaiveDate::from_ymd_opt(2026, 2, 1).unwrap();
+
+    assert!(ReconciliationService::validate_entry_date(on, statement).is_ok());
+    assert_eq!(
+        ReconciliationService::validate_entry_date(after, statement),
+        Err(ReconciliationError::EntryAfterStatementDate)
+    );
+}
+
+proptest! {
+    /// Clearing exactly the statement movement always balances.
+    #[test]
+    fn prop_exact_movement_balances(
+        opening in -1_000_000_000i64..1_000_000_000,
+        movement in -1_000_000_000i64..1_000_000_000,
+    ) {
+        let opening = Decimal::new(opening, 2);
+        let movement = Decimal::new(movement, 2);
+        let check = ReconciliationService::check(opening, opening + movement, movement);
+        prop_assert!(check.is_balanced());
+    }
+
+    /// The difference is what remains to be cleared.
+    #[test]
+    fn prop_difference_closes_gap(
+        opening in -1_000_000_000i64..1_000_000_000,
+        ending in -1_000_000_000i64..1_000_000_000,
+        cleared in -1_000_000_000i64..1_000_000_000,
+    ) {
+        let opening = Decimal::new(opening, 2);
+        let ending = Decimal::new(ending, 2);
+        let cleared = Decimal::new(cleared, 2);
+        let check = ReconciliationService::check(opening, ending, cleared);
+        let rechecked = ReconciliationService::check(opening, ending, cleared + check.difference);
+        prop_assert!(rechecked.is_balanced());
+    }
+}
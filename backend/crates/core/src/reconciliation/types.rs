@@ -0,0 +1,35 @@
+//! Reconciliation data types.
+
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+/// Lifecycle status of a reconciliation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReconciliationStatus {
+    /// Entries can still be cleared or uncleared.
+    InProgress,
+    /// The reconciliation balanced and its items are locked.
+    Completed,
+}
+
+/// Result of comparing cleared entries against a bank statement.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReconciliationCheck {
+    /// Ending balance of the previous completed reconciliation.
+    pub opening_balance: Decimal,
+    /// Ending balance printed on the statement.
+    pub statement_ending_balance: Decimal,
+    /// Net movement of the cleared entries.
+    pub cleared_net: Decimal,
+    /// `statement_ending_balance - opening_balance - cleared_net`.
+    pub difference: Decimal,
+}
+
+impl ReconciliationCheck {
+    /// Returns true when the cleared entries explain the statement exactly.
+    #[must_use]
+    pub fn is_balanced(&self) -> bool {
+        self.difference.is_zero()
+    }
+}
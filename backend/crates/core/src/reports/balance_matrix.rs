@@ -0,0 +1,155 @@
+//! Account balances at several dates, for month-end reconciliation.
+//!
+//! One row per account and one column per requested date, with per-type
+//! totals as a checksum. [`BalanceMatrix::to_csv`] renders it for pasting
+//! into a spreadsheet, with plain or locale-formatted numbers.
+
+use std::fmt::Write as _;
+
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use zeltra_shared::format::ExportStyle;
+
+/// Most dates a balance matrix may have: a year of month ends plus the
+/// prior year end.
+pub const MAX_BALANCE_MATRIX_DATES: usize = 13;
+
+/// Account type order of the totals rows.
+const TYPE_ORDER: [&str; 5] = ["asset", "liability", "equity", "revenue", "expense"];
+
+/// One account's balances, in the matrix's date order.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BalanceMatrixRow {
+    /// Account ID.
+    pub account_id: Uuid,
+    /// Account code.
+    pub code: String,
+    /// Account name.
+    pub name: String,
+    /// Account type (asset, liability, equity, revenue, expense).
+    pub account_type: String,
+    /// Account currency.
+    pub currency: String,
+    /// Posted balance at each date, in the account's normal direction.
+    pub balances: Vec<Decimal>,
+}
+
+/// Sum of one account type's balances at each date.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BalanceMatrixTotal {
+    /// Account type.
+    pub account_type: String,
+    /// Total at each date.
+    pub balances: Vec<Decimal>,
+}
+
+/// Balances of many accounts at several dates.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BalanceMatrix {
+    /// Column dates, as requested.
+    pub dates: Vec<NaiveDate>,
+    /// Account rows.
+    pub rows: Vec<BalanceMatrixRow>,
+    /// Totals per account type present in `rows`, in chart order.
+    pub totals: Vec<BalanceMatrixTotal>,
+}
+
+impl BalanceMatrix {
+    /// Builds the matrix and its per-type totals.
+    #[must_use]
+    pub fn new(dates: Vec<NaiveDate>, rows: Vec<BalanceMatrixRow>) -> Self {
+        let totals = TYPE_ORDER
+            .iter()
+            .filter_map(|&account_type| {
+                let mut of_type = rows
+                    .iter()
+                    .filter(|r| r.account_type == account_type)
+                    .peekable();
+                of_type.peek()?;
+                let mut balances = vec![Decimal::ZERO; dates.len()];
+                for row in of_type {
+                    for (total, balance) in balances.iter_mut().zip(&row.balances) {
+                        *total += balance;
+                    }
+                }
+                Some(BalanceMatrixTotal {
+                    account_type: account_type.to_string(),
+                    balances,
+                })
+            })
+            .collect();
+        Self {
+            dates,
+            rows,
+            totals,
+        }
+    }
+
+    /// Renders the matrix as CSV: a header row, the account rows, then one
+    /// `Total <type>` row per account type. Dates and balances are written
+    /// in `style`.
+    #[must_use]
+    pub fn to_csv(&self, style: ExportStyle) -> String {
+        let mut csv = String::from("code,name,type,currency");
+        for date in &self.dates {
+            let _ = write!(csv, ",{}", csv_value(&style.date(*date)));
+        }
+        csv.push('\n');
+
+        for row in &self.rows {
+            push_csv_line(
+                &mut csv,
+                [
+                    row.code.as_str(),
+                    row.name.as_str(),
+                    row.account_type.as_str(),
+                    row.currency.as_str(),
+                ],
+                &row.balances,
+                style,
+            );
+        }
+        for total in &self.totals {
+            let label = format!("Total {}", total.account_type);
+            push_csv_line(
+                &mut csv,
+                ["", label.as_str(), total.account_type.as_str(), ""],
+                &total.balances,
+                style,
+            );
+        }
+        csv
+    }
+}
+
+/// Appends one CSV line of text fields followed by amounts.
+fn push_csv_line(csv: &mut String, fields: [&str; 4], amounts: &[Decimal], style: ExportStyle) {
+    let fields: Vec<_> = fields.iter().map(|f| csv_field(f)).collect();
+    csv.push_str(&fields.join(","));
+    for amount in amounts {
+        let _ = write!(csv, ",{}", csv_value(&style.amount(*amount)));
+    }
+    csv.push('\n');
+}
+
+/// Quotes a text field like [`csv_value`]. A field a spreadsheet would read
+/// as a formula gets a leading apostrophe.
+pub(crate) fn csv_field(field: &str) -> String {
+    if field.starts_with(['=', '+', '-', '@']) {
+        csv_value(&format!("'{field}"))
+    } else {
+        csv_value(field)
+    }
+}
+
+/// Quotes a value containing a separator, quote or line break, such as an
+/// amount with a comma group or decimal separator.
+pub(crate) fn csv_value(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
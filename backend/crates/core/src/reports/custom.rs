@@ -0,0 +1,502 @@
+//! Saved custom report definitions.
+//!
+//! A definition picks a base dataset, columns from that dataset's fixed
+//! field list, filters, up to two group-by fields and an aggregate per
+//! group. Field names are only ever matched against [`ReportField`]; the
+//! query is built from the matched fields, never from the names themselves.
+
+use std::collections::HashSet;
+use std::fmt::Write as _;
+
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use zeltra_shared::format::ExportStyle;
+
+use super::balance_matrix::{csv_field, csv_value};
+use crate::settings::FieldError;
+
+/// Most rows a custom report returns; longer results are cut off and
+/// flagged as truncated.
+pub const MAX_CUSTOM_REPORT_ROWS: usize = 5_000;
+
+/// Most fields a custom report may group by.
+pub const MAX_GROUP_BY_FIELDS: usize = 2;
+
+/// Longest definition name, in characters.
+const MAX_NAME_LENGTH: usize = 100;
+
+/// Account types a definition may filter on.
+const ACCOUNT_TYPES: [&str; 5] = ["asset", "liability", "equity", "revenue", "expense"];
+
+/// The rows a custom report is built from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReportDataset {
+    /// Posted ledger entries, with their transaction and account.
+    LedgerEntries,
+    /// Budget lines, with the posted actual of their account and period.
+    BudgetVsActual,
+}
+
+/// The kind of value a field holds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FieldKind {
+    /// Free text or an enum label.
+    Text,
+    /// A calendar date.
+    Date,
+    /// An amount.
+    Number,
+}
+
+/// A field a custom report can select, filter or group on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ReportField {
+    /// Account code.
+    AccountCode,
+    /// Account name.
+    AccountName,
+    /// Account type.
+    AccountType,
+    /// Fiscal period name.
+    PeriodName,
+    /// Transaction date.
+    TransactionDate,
+    /// Transaction type.
+    TransactionType,
+    /// Transaction reference number.
+    ReferenceNumber,
+    /// Transaction description.
+    Description,
+    /// Entry source currency.
+    Currency,
+    /// Functional debit.
+    Debit,
+    /// Functional credit.
+    Credit,
+    /// Functional debit minus credit.
+    Amount,
+    /// Budget name.
+    BudgetName,
+    /// Budgeted amount.
+    Budgeted,
+    /// Posted actual, in the account's normal direction.
+    Actual,
+    /// Budgeted minus actual.
+    Variance,
+}
+
+impl ReportField {
+    /// Every field.
+    pub const ALL: [Self; 16] = [
+        Self::AccountCode,
+        Self::AccountName,
+        Self::AccountType,
+        Self::PeriodName,
+        Self::TransactionDate,
+        Self::TransactionType,
+        Self::ReferenceNumber,
+        Self::Description,
+        Self::Currency,
+        Self::Debit,
+        Self::Credit,
+        Self::Amount,
+        Self::BudgetName,
+        Self::Budgeted,
+        Self::Actual,
+        Self::Variance,
+    ];
+
+    /// The field's name in definitions and report headers.
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::AccountCode => "account_code",
+            Self::AccountName => "account_name",
+            Self::AccountType => "account_type",
+            Self::PeriodName => "period_name",
+            Self::TransactionDate => "transaction_date",
+            Self::TransactionType => "transaction_type",
+            Self::ReferenceNumber => "reference_number",
+            Self::Description => "description",
+            Self::Currency => "currency",
+            Self::Debit => "debit",
+            Self::Credit => "credit",
+            Self::Amount => "amount",
+            Self::BudgetName => "budget_name",
+            Self::Budgeted => "budgeted",
+            Self::Actual => "actual",
+            Self::Variance => "variance",
+        }
+    }
+
+    /// The kind of value the field holds.
+    #[must_use]
+    pub const fn kind(self) -> FieldKind {
+        match self {
+            Self::TransactionDate => FieldKind::Date,
+            Self::Debit
+            | Self::Credit
+            | Self::Amount
+            | Self::Budgeted
+            | Self::Actual
+            | Self::Variance => FieldKind::Number,
+            _ => FieldKind::Text,
+        }
+    }
+
+    /// Whether the field exists in `dataset`.
+    #[must_use]
+    pub const fn in_dataset(self, dataset: ReportDataset) -> bool {
+        match self {
+            Self::AccountCode | Self::AccountName | Self::AccountType | Self::PeriodName => true,
+            Self::TransactionDate
+            | Self::TransactionType
+            | Self::ReferenceNumber
+            | Self::Description
+            | Self::Currency
+            | Self::Debit
+            | Self::Credit
+            | Self::Amount => matches!(dataset, ReportDataset::LedgerEntries),
+            Self::BudgetName | Self::Budgeted | Self::Actual | Self::Variance => {
+                matches!(dataset, ReportDataset::BudgetVsActual)
+            }
+        }
+    }
+
+    /// The field of `dataset` with this name.
+    #[must_use]
+    pub fn from_name(dataset: ReportDataset, name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|f| f.name() == name && f.in_dataset(dataset))
+    }
+}
+
+/// How a group's rows are combined.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AggregateFunction {
+    /// Sum of the field.
+    Sum,
+    /// Number of rows; takes no field.
+    Count,
+    /// Average of the field.
+    Avg,
+    /// Smallest value of the field.
+    Min,
+    /// Largest value of the field.
+    Max,
+}
+
+impl AggregateFunction {
+    /// The function's name in definitions.
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Sum => "sum",
+            Self::Count => "count",
+            Self::Avg => "avg",
+            Self::Min => "min",
+            Self::Max => "max",
+        }
+    }
+}
+
+/// The aggregate computed per group.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReportAggregate {
+    /// Aggregate function.
+    pub function: AggregateFunction,
+    /// Numeric field to aggregate; omitted for `count`.
+    #[serde(default)]
+    pub field: Option<String>,
+}
+
+/// Row filters of a definition; an empty filter keeps every row.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReportFilters {
+    /// First transaction date, or first period start for budgets.
+    #[serde(default)]
+    pub date_from: Option<NaiveDate>,
+    /// Last transaction date, or last period start for budgets.
+    #[serde(default)]
+    pub date_to: Option<NaiveDate>,
+    /// Only accounts of these types.
+    #[serde(default)]
+    pub account_types: Vec<String>,
+    /// Only rows tagged with these dimension values; per dimension type, a
+    /// row must carry one of them.
+    #[serde(default)]
+    pub dimension_value_ids: Vec<Uuid>,
+}
+
+impl ReportFilters {
+    fn validate(&self, errors: &mut Vec<FieldError>) {
+        if let (Some(from), Some(to)) = (self.date_from, self.date_to)
+            && from > to
+        {
+            errors.push(FieldError::new(
+                "filters.date_to",
+                "must not be before date_from",
+            ));
+        }
+        for (i, account_type) in self.account_types.iter().enumerate() {
+            if !ACCOUNT_TYPES.contains(&account_type.as_str()) {
+                errors.push(FieldError::new(
+                    format!("filters.account_types[{i}]"),
+                    "unknown account type",
+                ));
+            }
+        }
+    }
+}
+
+/// A saved custom report, as entered by the user.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReportDefinition {
+    /// Display name, unique within the organization.
+    pub name: String,
+    /// Base dataset.
+    pub dataset: ReportDataset,
+    /// Fields shown, in order. When aggregating, only group-by fields.
+    #[serde(default)]
+    pub columns: Vec<String>,
+    /// Row filters.
+    #[serde(default)]
+    pub filters: ReportFilters,
+    /// Fields to group by, at most [`MAX_GROUP_BY_FIELDS`].
+    #[serde(default)]
+    pub group_by: Vec<String>,
+    /// Aggregate per group; required when grouping.
+    #[serde(default)]
+    pub aggregate: Option<ReportAggregate>,
+}
+
+/// A validated definition, with every name resolved to a known field.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReportPlan {
+    /// Base dataset.
+    pub dataset: ReportDataset,
+    /// Fields selected as-is: the columns, or the group-by fields when
+    /// aggregating.
+    pub fields: Vec<ReportField>,
+    /// Whether `fields` are grouped and the aggregate computed per group.
+    pub grouped: bool,
+    /// Aggregate, appended as the last column; `None` field means `count`.
+    pub aggregate: Option<(AggregateFunction, Option<ReportField>)>,
+    /// First date kept.
+    pub date_from: Option<NaiveDate>,
+    /// Last date kept.
+    pub date_to: Option<NaiveDate>,
+    /// Account types kept, as stored names; all when empty.
+    pub account_types: Vec<String>,
+    /// Dimension values a row must carry, per type one of.
+    pub dimension_value_ids: Vec<Uuid>,
+}
+
+impl ReportPlan {
+    /// Header names of the report's columns.
+    #[must_use]
+    pub fn column_names(&self) -> Vec<String> {
+        let mut names: Vec<String> = self.fields.iter().map(|f| f.name().to_string()).collect();
+        if let Some((function, field)) = self.aggregate {
+            names.push(match field {
+                Some(field) => format!("{}_{}", function.as_str(), field.name()),
+                None => function.as_str().to_string(),
+            });
+        }
+        names
+    }
+}
+
+impl ReportDefinition {
+    /// Checks the definition and resolves its field names.
+    ///
+    /// # Errors
+    ///
+    /// Returns every problem found, each with the path of the offending
+    /// field. Unknown field names are rejected here, so nothing but a
+    /// [`ReportField`] ever reaches a query.
+    #[allow(clippy::too_many_lines)]
+    pub fn validate(&self) -> Result<ReportPlan, Vec<FieldError>> {
+        let mut errors = Vec::new();
+        let dataset = self.dataset;
+        let resolve = |path: String, name: &str, errors: &mut Vec<FieldError>| {
+            let field = ReportField::from_name(dataset, name);
+            if field.is_none() {
+                errors.push(FieldError::new(
+                    path,
+                    format!("unknown field '{name}' for this dataset"),
+                ));
+            }
+            field
+        };
+
+        let name = self.name.trim();
+        if name.is_empty() || name.chars().count() > MAX_NAME_LENGTH {
+            errors.push(FieldError::new(
+                "name",
+                format!("must be 1-{MAX_NAME_LENGTH} characters"),
+            ));
+        }
+
+        let mut seen = HashSet::new();
+        let mut columns = Vec::new();
+        for (i, column) in self.columns.iter().enumerate() {
+            let path = format!("columns[{i}]");
+            if let Some(field) = resolve(path.clone(), column, &mut errors) {
+                if seen.insert(field) {
+                    columns.push(field);
+                } else {
+                    errors.push(FieldError::new(path, "column is listed more than once"));
+                }
+            }
+        }
+
+        if self.group_by.len() > MAX_GROUP_BY_FIELDS {
+            errors.push(FieldError::new(
+                "group_by",
+                format!("at most {MAX_GROUP_BY_FIELDS} fields are allowed"),
+            ));
+        }
+        let mut group_by = Vec::new();
+        for (i, name) in self.group_by.iter().enumerate() {
+            let path = format!("group_by[{i}]");
+            let Some(field) = resolve(path.clone(), name, &mut errors) else {
+                continue;
+            };
+            if field.kind() == FieldKind::Number {
+                errors.push(FieldError::new(path, "amounts cannot be grouped by"));
+            } else if group_by.contains(&field) {
+                errors.push(FieldError::new(path, "field is listed more than once"));
+            } else {
+                group_by.push(field);
+            }
+        }
+
+        let aggregate = self.aggregate.as_ref().and_then(|aggregate| {
+            let field = match (aggregate.function, &aggregate.field) {
+                (AggregateFunction::Count, None) => return Some((aggregate.function, None)),
+                (AggregateFunction::Count, Some(_)) => {
+                    errors.push(FieldError::new("aggregate.field", "count takes no field"));
+                    return None;
+                }
+                (_, None) => {
+                    errors.push(FieldError::new(
+                        "aggregate.field",
+                        "a numeric field is required",
+                    ));
+                    return None;
+                }
+                (_, Some(name)) => resolve("aggregate.field".to_string(), name, &mut errors)?,
+            };
+            if field.kind() == FieldKind::Number {
+                Some((aggregate.function, Some(field)))
+            } else {
+                errors.push(FieldError::new(
+                    "aggregate.field",
+                    "only amounts can be aggregated",
+                ));
+                None
+            }
+        });
+
+        let grouped = self.aggregate.is_some();
+        if grouped {
+            for (i, column) in columns.iter().enumerate() {
+                if !group_by.contains(column) {
+                    errors.push(FieldError::new(
+                        format!("columns[{i}]"),
+                        "only group-by fields can be shown when aggregating",
+                    ));
+                }
+            }
+        } else {
+            if !self.group_by.is_empty() {
+                errors.push(FieldError::new(
+                    "aggregate",
+                    "an aggregate is required when grouping",
+                ));
+            }
+            if self.columns.is_empty() {
+                errors.push(FieldError::new(
+                    "columns",
+                    "at least one column is required",
+                ));
+            }
+        }
+
+        let filters = &self.filters;
+        filters.validate(&mut errors);
+
+        if !errors.is_empty() {
+            return Err(errors);
+        }
+        Ok(ReportPlan {
+            dataset,
+            fields: if grouped { group_by } else { columns },
+            grouped,
+            aggregate,
+            date_from: filters.date_from,
+            date_to: filters.date_to,
+            account_types: filters.account_types.clone(),
+            dimension_value_ids: filters.dimension_value_ids.clone(),
+        })
+    }
+}
+
+/// One cell of a custom report.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(untagged)]
+pub enum ReportValue {
+    /// No value.
+    Null,
+    /// Text.
+    Text(String),
+    /// Date.
+    Date(NaiveDate),
+    /// Amount or count.
+    Number(Decimal),
+}
+
+/// The rows produced by running a custom report.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct CustomReportResult {
+    /// Column headers.
+    pub columns: Vec<String>,
+    /// Rows, each with one value per column.
+    pub rows: Vec<Vec<ReportValue>>,
+    /// Whether rows past [`MAX_CUSTOM_REPORT_ROWS`] were left out.
+    pub truncated: bool,
+}
+
+impl CustomReportResult {
+    /// Renders the result as CSV: a header row, then the rows. Dates and
+    /// numbers are written in `style`.
+    #[must_use]
+    pub fn to_csv(&self, style: ExportStyle) -> String {
+        let header: Vec<_> = self.columns.iter().map(|c| csv_field(c)).collect();
+        let mut csv = header.join(",");
+        csv.push('\n');
+        for row in &self.rows {
+            for (i, value) in row.iter().enumerate() {
+                if i > 0 {
+                    csv.push(',');
+                }
+                let _ = match value {
+                    ReportValue::Null => Ok(()),
+                    ReportValue::Text(text) => write!(csv, "{}", csv_field(text)),
+                    ReportValue::Date(date) => write!(csv, "{}", csv_value(&style.date(*date))),
+                    ReportValue::Number(amount) => {
+                        write!(csv, "{}", csv_value(&style.amount(*amount)))
+                    }
+                };
+            }
+            csv.push('\n');
+        }
+        csv
+    }
+}
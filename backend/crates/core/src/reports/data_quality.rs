@@ -0,0 +1,358 @@
+//! Data-quality checks over ledger data.
+//!
+//! Each check is a pure function over rows the repository has already loaded,
+//! returning one [`DataQualityFinding`] per problem. New checks add a
+//! [`DataQualityCheck`] variant and a function alongside the others.
+
+use std::collections::HashSet;
+use std::fmt;
+
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Largest difference between `functional_amount` and
+/// `source_amount * exchange_rate` treated as rounding.
+pub const FUNCTIONAL_AMOUNT_TOLERANCE: Decimal = Decimal::from_parts(1, 0, 0, false, 2);
+
+/// The kind of problem a finding reports.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DataQualityCheck {
+    /// A draft, pending or approved transaction whose debits and credits differ.
+    UnbalancedTransaction,
+    /// A posted entry whose functional amount disagrees with its conversion.
+    FunctionalAmountMismatch,
+    /// A transaction without a value for a required dimension type.
+    MissingRequiredDimension,
+    /// Entries booked directly to an account that disallows direct posting.
+    DirectPostingNotAllowed,
+}
+
+impl DataQualityCheck {
+    /// Every check, in report order.
+    pub const ALL: [Self; 4] = [
+        Self::UnbalancedTransaction,
+        Self::FunctionalAmountMismatch,
+        Self::MissingRequiredDimension,
+        Self::DirectPostingNotAllowed,
+    ];
+
+    /// Returns the check code used in API responses.
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::UnbalancedTransaction => "unbalanced_transaction",
+            Self::FunctionalAmountMismatch => "functional_amount_mismatch",
+            Self::MissingRequiredDimension => "missing_required_dimension",
+            Self::DirectPostingNotAllowed => "direct_posting_not_allowed",
+        }
+    }
+}
+
+impl fmt::Display for DataQualityCheck {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// A single data-quality problem.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DataQualityFinding {
+    /// Which check produced the finding.
+    pub check: DataQualityCheck,
+    /// Affected transaction, if the finding is about one.
+    pub transaction_id: Option<Uuid>,
+    /// Affected account, if the finding is about one.
+    pub account_id: Option<Uuid>,
+    /// Human-readable description of the problem.
+    pub details: String,
+}
+
+/// Debit and credit totals of one transaction.
+#[derive(Debug, Clone)]
+pub struct TransactionTotals {
+    /// Transaction ID.
+    pub transaction_id: Uuid,
+    /// Sum of functional debits.
+    pub total_debit: Decimal,
+    /// Sum of functional credits.
+    pub total_credit: Decimal,
+}
+
+/// Conversion amounts of one ledger entry.
+#[derive(Debug, Clone)]
+pub struct EntryConversion {
+    /// Ledger entry ID.
+    pub entry_id: Uuid,
+    /// Transaction ID.
+    pub transaction_id: Uuid,
+    /// Account ID.
+    pub account_id: Uuid,
+    /// Amount in the source currency.
+    pub source_amount: Decimal,
+    /// Rate from source to functional currency.
+    pub exchange_rate: Decimal,
+    /// Amount in the functional currency.
+    pub functional_amount: Decimal,
+}
+
+/// A dimension type every transaction must carry.
+#[derive(Debug, Clone)]
+pub struct RequiredDimension {
+    /// Dimension type ID.
+    pub dimension_type_id: Uuid,
+    /// Dimension type code.
+    pub code: String,
+}
+
+/// The dimension types tagged on any entry of a transaction.
+#[derive(Debug, Clone)]
+pub struct TransactionDimensions {
+    /// Transaction ID.
+    pub transaction_id: Uuid,
+    /// Dimension type IDs present on at least one entry.
+    pub dimension_type_ids: HashSet<Uuid>,
+}
+
+/// Direct entries on an account that disallows direct posting.
+#[derive(Debug, Clone)]
+pub struct RestrictedAccountUsage {
+    /// Account ID.
+    pub account_id: Uuid,
+    /// Account code.
+    pub code: String,
+    /// Number of entries booked to the account.
+    pub entry_count: u64,
+}
+
+/// Flags transactions whose debits and credits differ.
+#[must_use]
+pub fn check_unbalanced(totals: &[TransactionTotals]) -> Vec<DataQualityFinding> {
+    totals
+        .iter()
+        .filter(|t| t.total_debit != t.total_credit)
+        .map(|t| DataQualityFinding {
+            check: DataQualityCheck::UnbalancedTransaction,
+            transaction_id: Some(t.transaction_id),
+            account_id: None,
+            details: format!(
+                "debits {} do not equal credits {} (difference {})",
+                t.total_debit,
+                t.total_credit,
+                t.total_debit - t.total_credit
+            ),
+        })
+        .collect()
+}
+
+/// Flags entries whose functional amount differs from
+/// `source_amount * exchange_rate` by more than `tolerance`.
+///
+/// Entries without a source amount are FX revaluation adjustments, which
+/// restate only the functional value, and are not checked.
+#[must_use]
+pub fn check_functional_amounts(
+    entries: &[EntryConversion],
+    tolerance: Decimal,
+) -> Vec<DataQualityFinding> {
+    entries
+        .iter()
+        .filter(|e| !e.source_amount.is_zero())
+        .filter_map(|e| {
+            let expected = e.source_amount * e.exchange_rate;
+            let difference = e.functional_amount - expected;
+            (difference.abs() > tolerance).then(|| DataQualityFinding {
+                check: DataQualityCheck::FunctionalAmountMismatch,
+                transaction_id: Some(e.transaction_id),
+                account_id: Some(e.account_id),
+                details: format!(
+                    "entry {} has functional amount {} but {} x {} = {}",
+                    e.entry_id,
+                    e.functional_amount,
+                    e.source_amount,
+                    e.exchange_rate,
+                    expected.normalize()
+                ),
+            })
+        })
+        .collect()
+}
+
+/// Flags transactions missing any of the `required` dimension types.
+///
+/// A dimension counts as present when any entry of the transaction carries
+/// a value of that type.
+#[must_use]
+pub fn check_required_dimensions(
+    transactions: &[TransactionDimensions],
+    required: &[RequiredDimension],
+) -> Vec<DataQualityFinding> {
+    if required.is_empty() {
+        return Vec::new();
+    }
+
+    transactions
+        .iter()
+        .filter_map(|t| {
+            let missing: Vec<&str> = required
+                .iter()
+                .filter(|r| !t.dimension_type_ids.contains(&r.dimension_type_id))
+                .map(|r| r.code.as_str())
+                .collect();
+            (!missing.is_empty()).then(|| DataQualityFinding {
+                check: DataQualityCheck::MissingRequiredDimension,
+                transaction_id: Some(t.transaction_id),
+                account_id: None,
+                details: format!("missing required dimensions: {}", missing.join(", ")),
+            })
+        })
+        .collect()
+}
+
+/// Flags accounts that disallow direct posting but have entries anyway.
+#[must_use]
+pub fn check_direct_posting(accounts: &[RestrictedAccountUsage]) -> Vec<DataQualityFinding> {
+    accounts
+        .iter()
+        .filter(|a| a.entry_count > 0)
+        .map(|a| DataQualityFinding {
+            check: DataQualityCheck::DirectPostingNotAllowed,
+            transaction_id: None,
+            account_id: Some(a.account_id),
+            details: format!(
+                "account {} does not allow direct posting but has {} entries",
+                a.code, a.entry_count
+            ),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    fn conversion(source: Decimal, rate: Decimal, functional: Decimal) -> EntryConversion {
+        EntryConversion {
+            entry_id: Uuid::new_v4(),
+            transaction_id: Uuid::new_v4(),
+            account_id: Uuid::new_v4(),
+            source_amount: source,
+            exchange_rate: rate,
+            functional_amount: functional,
+        }
+    }
+
+    #[test]
+    fn test_unbalanced_only_flags_differences() {
+        let balanced = TransactionTotals {
+            transaction_id: Uuid::new_v4(),
+            total_debit: dec!(100),
+            total_credit: dec!(100),
+        };
+        let unbalanced = TransactionTotals {
+            transaction_id: Uuid::new_v4(),
+            total_debit: dec!(100),
+            total_credit: dec!(90),
+        };
+
+        let findings = check_unbalanced(&[balanced, unbalanced.clone()]);
+
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].check, DataQualityCheck::UnbalancedTransaction);
+        assert_eq!(findings[0].transaction_id, Some(unbalanced.transaction_id));
+        assert!(findings[0].details.contains("difference 10"));
+    }
+
+    #[test]
+    fn test_functional_amount_within_tolerance_passes() {
+        let rounded = conversion(dec!(33.33), dec!(1.085), dec!(36.16));
+        let exact = conversion(dec!(100), dec!(1.085), dec!(108.5));
+
+        let findings = check_functional_amounts(&[rounded, exact], FUNCTIONAL_AMOUNT_TOLERANCE);
+
+        assert!(findings.is_empty());
+    }
+
+    #[test]
+    fn test_functional_amount_beyond_tolerance_flagged() {
+        let wrong = conversion(dec!(100), dec!(1.085), dec!(100));
+
+        let findings =
+            check_functional_amounts(std::slice::from_ref(&wrong), FUNCTIONAL_AMOUNT_TOLERANCE);
+
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].account_id, Some(wrong.account_id));
+        assert!(findings[0].details.contains("108.5"));
+    }
+
+    #[test]
+    fn test_revaluation_adjustment_not_flagged() {
+        let adjustment = conversion(Decimal::ZERO, dec!(1.12), dec!(40));
+
+        let findings = check_functional_amounts(&[adjustment], FUNCTIONAL_AMOUNT_TOLERANCE);
+
+        assert!(findings.is_empty());
+    }
+
+    #[test]
+    fn test_required_dimensions_lists_missing_codes() {
+        let department = Uuid::new_v4();
+        let project = Uuid::new_v4();
+        let required = [
+            RequiredDimension {
+                dimension_type_id: department,
+                code: "DEPT".to_string(),
+            },
+            RequiredDimension {
+                dimension_type_id: project,
+                code: "PROJECT".to_string(),
+            },
+        ];
+        let complete = TransactionDimensions {
+            transaction_id: Uuid::new_v4(),
+            dimension_type_ids: HashSet::from([department, project]),
+        };
+        let partial = TransactionDimensions {
+            transaction_id: Uuid::new_v4(),
+            dimension_type_ids: HashSet::from([department]),
+        };
+
+        let findings = check_required_dimensions(&[complete, partial.clone()], &required);
+
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].transaction_id, Some(partial.transaction_id));
+        assert_eq!(findings[0].details, "missing required dimensions: PROJECT");
+    }
+
+    #[test]
+    fn test_no_required_dimensions_means_no_findings() {
+        let bare = TransactionDimensions {
+            transaction_id: Uuid::new_v4(),
+            dimension_type_ids: HashSet::new(),
+        };
+
+        assert!(check_required_dimensions(&[bare], &[]).is_empty());
+    }
+
+    #[test]
+    fn test_direct_posting_ignores_unused_accounts() {
+        let used = RestrictedAccountUsage {
+            account_id: Uuid::new_v4(),
+            code: "1000".to_string(),
+            entry_count: 3,
+        };
+        let unused = RestrictedAccountUsage {
+            account_id: Uuid::new_v4(),
+            code: "1100".to_string(),
+            entry_count: 0,
+        };
+
+        let findings = check_direct_posting(&[used.clone(), unused]);
+
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].account_id, Some(used.account_id));
+        assert_eq!(findings[0].check.as_str(), "direct_posting_not_allowed");
+    }
+}
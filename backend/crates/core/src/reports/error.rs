@@ -28,6 +28,10 @@ pub enum ReportError {
     #[error("Invalid dimension type: {0}")]
     InvalidDimensionType(String),
 
+    /// Invalid allocation of unallocated amounts.
+    #[error("Invalid allocation: {0}")]
+    InvalidAllocation(String),
+
     /// No data found.
     #[error("No data found for the specified criteria")]
     NoDataFound,
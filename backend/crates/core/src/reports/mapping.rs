@@ -0,0 +1,527 @@
+//! Mapping of account subtypes to financial statement sections.
+//!
+//! Statement totals are computed per [`SectionKind`]: assets, liabilities
+//! and equity on the balance sheet, and revenue, cost of goods sold,
+//! operating expenses and other income/expense on the income statement. A
+//! [`StatementMapping`] splits those into named, ordered sections and puts
+//! every account subtype in exactly one of them.
+//!
+//! The default mapping reproduces the built-in grouping. Organizations can
+//! store their own, e.g. to move accrued liabilities or to call cost of
+//! goods sold "Cost of Revenue".
+
+use std::collections::{HashMap, HashSet};
+
+use serde::{Deserialize, Serialize};
+
+use crate::settings::FieldError;
+
+/// Maximum length of a section key.
+const MAX_KEY_LENGTH: usize = 50;
+
+/// Maximum length of a section name.
+const MAX_NAME_LENGTH: usize = 100;
+
+/// Account subtype, as stored on chart of accounts entries.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AccountSubtype {
+    /// Cash on hand.
+    Cash,
+    /// Bank account.
+    Bank,
+    /// Amounts owed by customers.
+    AccountsReceivable,
+    /// Inventory.
+    Inventory,
+    /// Prepaid expenses.
+    Prepaid,
+    /// Property, plant and equipment.
+    FixedAsset,
+    /// Accumulated depreciation (contra asset).
+    AccumulatedDepreciation,
+    /// Any other asset.
+    OtherAsset,
+    /// Amounts owed to suppliers.
+    AccountsPayable,
+    /// Credit card balance.
+    CreditCard,
+    /// Accrued liabilities.
+    AccruedLiability,
+    /// Debt due within a year.
+    ShortTermDebt,
+    /// Debt due after a year.
+    LongTermDebt,
+    /// Any other liability.
+    OtherLiability,
+    /// Owner's equity.
+    OwnerEquity,
+    /// Retained earnings.
+    RetainedEarnings,
+    /// Common stock.
+    CommonStock,
+    /// Any other equity.
+    OtherEquity,
+    /// Revenue from operations.
+    OperatingRevenue,
+    /// Any other revenue.
+    OtherRevenue,
+    /// Cost of goods sold.
+    CostOfGoodsSold,
+    /// Operating expenses.
+    OperatingExpense,
+    /// Payroll expenses.
+    PayrollExpense,
+    /// Depreciation expense.
+    DepreciationExpense,
+    /// Interest expense.
+    InterestExpense,
+    /// Tax expense.
+    TaxExpense,
+    /// Any other expense.
+    OtherExpense,
+}
+
+impl AccountSubtype {
+    /// Every subtype.
+    pub const ALL: [Self; 27] = [
+        Self::Cash,
+        Self::Bank,
+        Self::AccountsReceivable,
+        Self::Inventory,
+        Self::Prepaid,
+        Self::FixedAsset,
+        Self::AccumulatedDepreciation,
+        Self::OtherAsset,
+        Self::AccountsPayable,
+        Self::CreditCard,
+        Self::AccruedLiability,
+        Self::ShortTermDebt,
+        Self::LongTermDebt,
+        Self::OtherLiability,
+        Self::OwnerEquity,
+        Self::RetainedEarnings,
+        Self::CommonStock,
+        Self::OtherEquity,
+        Self::OperatingRevenue,
+        Self::OtherRevenue,
+        Self::CostOfGoodsSold,
+        Self::OperatingExpense,
+        Self::PayrollExpense,
+        Self::DepreciationExpense,
+        Self::InterestExpense,
+        Self::TaxExpense,
+        Self::OtherExpense,
+    ];
+
+    /// Returns the subtype as stored.
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Cash => "cash",
+            Self::Bank => "bank",
+            Self::AccountsReceivable => "accounts_receivable",
+            Self::Inventory => "inventory",
+            Self::Prepaid => "prepaid",
+            Self::FixedAsset => "fixed_asset",
+            Self::AccumulatedDepreciation => "accumulated_depreciation",
+            Self::OtherAsset => "other_asset",
+            Self::AccountsPayable => "accounts_payable",
+            Self::CreditCard => "credit_card",
+            Self::AccruedLiability => "accrued_liability",
+            Self::ShortTermDebt => "short_term_debt",
+            Self::LongTermDebt => "long_term_debt",
+            Self::OtherLiability => "other_liability",
+            Self::OwnerEquity => "owner_equity",
+            Self::RetainedEarnings => "retained_earnings",
+            Self::CommonStock => "common_stock",
+            Self::OtherEquity => "other_equity",
+            Self::OperatingRevenue => "operating_revenue",
+            Self::OtherRevenue => "other_revenue",
+            Self::CostOfGoodsSold => "cost_of_goods_sold",
+            Self::OperatingExpense => "operating_expense",
+            Self::PayrollExpense => "payroll_expense",
+            Self::DepreciationExpense => "depreciation_expense",
+            Self::InterestExpense => "interest_expense",
+            Self::TaxExpense => "tax_expense",
+            Self::OtherExpense => "other_expense",
+        }
+    }
+
+    /// Parses a stored subtype.
+    #[must_use]
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|s| s.as_str() == name)
+    }
+
+    /// Account type the subtype belongs to.
+    #[must_use]
+    pub const fn account_type(self) -> &'static str {
+        match self {
+            Self::Cash
+            | Self::Bank
+            | Self::AccountsReceivable
+            | Self::Inventory
+            | Self::Prepaid
+            | Self::FixedAsset
+            | Self::AccumulatedDepreciation
+            | Self::OtherAsset => "asset",
+            Self::AccountsPayable
+            | Self::CreditCard
+            | Self::AccruedLiability
+            | Self::ShortTermDebt
+            | Self::LongTermDebt
+            | Self::OtherLiability => "liability",
+            Self::OwnerEquity | Self::RetainedEarnings | Self::CommonStock | Self::OtherEquity => {
+                "equity"
+            }
+            Self::OperatingRevenue | Self::OtherRevenue => "revenue",
+            Self::CostOfGoodsSold
+            | Self::OperatingExpense
+            | Self::PayrollExpense
+            | Self::DepreciationExpense
+            | Self::InterestExpense
+            | Self::TaxExpense
+            | Self::OtherExpense => "expense",
+        }
+    }
+
+    /// Key of the section the default mapping puts the subtype in.
+    const fn default_section(self) -> &'static str {
+        match self {
+            Self::Cash
+            | Self::Bank
+            | Self::AccountsReceivable
+            | Self::Inventory
+            | Self::Prepaid => "current_assets",
+            Self::FixedAsset | Self::AccumulatedDepreciation => "fixed_assets",
+            Self::OtherAsset => "other_assets",
+            Self::AccountsPayable
+            | Self::CreditCard
+            | Self::AccruedLiability
+            | Self::ShortTermDebt => "current_liabilities",
+            Self::LongTermDebt | Self::OtherLiability => "long_term_liabilities",
+            Self::OwnerEquity | Self::RetainedEarnings | Self::CommonStock | Self::OtherEquity => {
+                "equity"
+            }
+            Self::OperatingRevenue => "revenue",
+            Self::OtherRevenue => "other_revenue",
+            Self::CostOfGoodsSold => "cost_of_goods_sold",
+            Self::OperatingExpense => "operating_expenses",
+            Self::PayrollExpense
+            | Self::DepreciationExpense
+            | Self::InterestExpense
+            | Self::TaxExpense
+            | Self::OtherExpense => "other_expenses",
+        }
+    }
+}
+
+/// Financial statement a section appears on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Statement {
+    /// Balance sheet.
+    BalanceSheet,
+    /// Income statement.
+    IncomeStatement,
+}
+
+/// Part of a statement that sections roll up into.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SectionKind {
+    /// Balance sheet assets.
+    Assets,
+    /// Balance sheet liabilities.
+    Liabilities,
+    /// Balance sheet equity.
+    Equity,
+    /// Income statement revenue.
+    Revenue,
+    /// Income statement cost of goods sold, deducted for gross profit.
+    CostOfGoodsSold,
+    /// Income statement operating expenses, deducted for operating income.
+    OperatingExpenses,
+    /// Income statement items below operating income.
+    OtherIncomeExpense,
+}
+
+impl SectionKind {
+    /// Returns the kind as its API string.
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Assets => "assets",
+            Self::Liabilities => "liabilities",
+            Self::Equity => "equity",
+            Self::Revenue => "revenue",
+            Self::CostOfGoodsSold => "cost_of_goods_sold",
+            Self::OperatingExpenses => "operating_expenses",
+            Self::OtherIncomeExpense => "other_income_expense",
+        }
+    }
+
+    /// Statement the kind appears on.
+    #[must_use]
+    pub const fn statement(self) -> Statement {
+        match self {
+            Self::Assets | Self::Liabilities | Self::Equity => Statement::BalanceSheet,
+            Self::Revenue
+            | Self::CostOfGoodsSold
+            | Self::OperatingExpenses
+            | Self::OtherIncomeExpense => Statement::IncomeStatement,
+        }
+    }
+
+    /// Whether accounts of `account_type` can be shown in this kind.
+    ///
+    /// Balance sheet kinds take their own account type only, so the sheet
+    /// still balances. Revenue can also be shown as other income.
+    #[must_use]
+    pub fn accepts(self, account_type: &str) -> bool {
+        matches!(
+            (self, account_type),
+            (Self::Assets, "asset")
+                | (Self::Liabilities, "liability")
+                | (Self::Equity, "equity")
+                | (Self::Revenue | Self::OtherIncomeExpense, "revenue")
+                | (
+                    Self::CostOfGoodsSold | Self::OperatingExpenses | Self::OtherIncomeExpense,
+                    "expense"
+                )
+        )
+    }
+
+    /// Kind for accounts of `account_type` without a usable mapping, such as
+    /// accounts without a subtype.
+    #[must_use]
+    pub fn fallback(account_type: &str) -> Option<Self> {
+        match account_type {
+            "asset" => Some(Self::Assets),
+            "liability" => Some(Self::Liabilities),
+            "equity" => Some(Self::Equity),
+            "revenue" => Some(Self::Revenue),
+            "expense" => Some(Self::OtherIncomeExpense),
+            _ => None,
+        }
+    }
+}
+
+/// A named section of a statement.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StatementSection {
+    /// Stable identifier, lowercase letters, digits and underscores.
+    pub key: String,
+    /// Display name, e.g. "Cost of Revenue".
+    pub name: String,
+    /// What the section rolls up into.
+    pub kind: SectionKind,
+    /// Position on the statement; unique per statement.
+    pub sort_order: i32,
+}
+
+/// Assignment of an account subtype to a section.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SubtypeSection {
+    /// Account subtype, e.g. `accrued_liability`.
+    pub subtype: String,
+    /// Key of the section.
+    pub section: String,
+}
+
+/// Sections of both statements and the subtype of each.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StatementMapping {
+    /// Sections of both statements.
+    pub sections: Vec<StatementSection>,
+    /// Section of each account subtype.
+    pub subtypes: Vec<SubtypeSection>,
+}
+
+/// Sections of the default mapping: key, name, kind and sort order.
+const DEFAULT_SECTIONS: [(&str, &str, SectionKind, i32); 11] = [
+    ("current_assets", "Current Assets", SectionKind::Assets, 10),
+    ("fixed_assets", "Fixed Assets", SectionKind::Assets, 20),
+    ("other_assets", "Other Assets", SectionKind::Assets, 30),
+    (
+        "current_liabilities",
+        "Current Liabilities",
+        SectionKind::Liabilities,
+        40,
+    ),
+    (
+        "long_term_liabilities",
+        "Long-Term Liabilities",
+        SectionKind::Liabilities,
+        50,
+    ),
+    ("equity", "Equity", SectionKind::Equity, 60),
+    ("revenue", "Revenue", SectionKind::Revenue, 10),
+    ("other_revenue", "Other Revenue", SectionKind::Revenue, 20),
+    (
+        "cost_of_goods_sold",
+        "Cost of Goods Sold",
+        SectionKind::CostOfGoodsSold,
+        30,
+    ),
+    (
+        "operating_expenses",
+        "Operating Expenses",
+        SectionKind::OperatingExpenses,
+        40,
+    ),
+    (
+        "other_expenses",
+        "Other Expenses",
+        SectionKind::OtherIncomeExpense,
+        50,
+    ),
+];
+
+impl Default for StatementMapping {
+    /// The built-in grouping.
+    fn default() -> Self {
+        Self {
+            sections: DEFAULT_SECTIONS
+                .iter()
+                .map(|&(key, name, kind, sort_order)| StatementSection {
+                    key: key.to_string(),
+                    name: name.to_string(),
+                    kind,
+                    sort_order,
+                })
+                .collect(),
+            subtypes: AccountSubtype::ALL
+                .into_iter()
+                .map(|subtype| SubtypeSection {
+                    subtype: subtype.as_str().to_string(),
+                    section: subtype.default_section().to_string(),
+                })
+                .collect(),
+        }
+    }
+}
+
+impl StatementMapping {
+    /// Checks the mapping, reporting every problem found.
+    ///
+    /// Section keys must be unique and well-formed, names non-empty, and sort
+    /// orders unique within each statement. Every account subtype must be
+    /// assigned exactly once, to an existing section that can show its
+    /// account type.
+    ///
+    /// # Errors
+    ///
+    /// Returns the field-level problems if the mapping is invalid.
+    pub fn validate(&self) -> Result<(), Vec<FieldError>> {
+        let mut errors = Vec::new();
+
+        let mut keys: HashMap<&str, SectionKind> = HashMap::new();
+        let mut orders: HashSet<(Statement, i32)> = HashSet::new();
+        for (i, section) in self.sections.iter().enumerate() {
+            let field = |name: &str| format!("sections[{i}].{name}");
+            if !is_valid_key(&section.key) {
+                errors.push(FieldError::new(
+                    field("key"),
+                    format!("must be 1-{MAX_KEY_LENGTH} lowercase letters, digits or underscores"),
+                ));
+            } else if keys.insert(&section.key, section.kind).is_some() {
+                errors.push(FieldError::new(field("key"), "duplicate section key"));
+            }
+            let name = section.name.trim();
+            if name.is_empty() || name.chars().count() > MAX_NAME_LENGTH {
+                errors.push(FieldError::new(
+                    field("name"),
+                    format!("must be 1-{MAX_NAME_LENGTH} characters"),
+                ));
+            }
+            if !orders.insert((section.kind.statement(), section.sort_order)) {
+                errors.push(FieldError::new(
+                    field("sort_order"),
+                    "another section of the statement has this sort order",
+                ));
+            }
+        }
+
+        let mut assigned = HashSet::new();
+        for (i, mapping) in self.subtypes.iter().enumerate() {
+            let field = |name: &str| format!("subtypes[{i}].{name}");
+            let Some(subtype) = AccountSubtype::from_name(&mapping.subtype) else {
+                errors.push(FieldError::new(field("subtype"), "unknown account subtype"));
+                continue;
+            };
+            if !assigned.insert(subtype) {
+                errors.push(FieldError::new(
+                    field("subtype"),
+                    "subtype is mapped more than once",
+                ));
+            }
+            match keys.get(mapping.section.as_str()) {
+                None => errors.push(FieldError::new(field("section"), "unknown section")),
+                Some(kind) if !kind.accepts(subtype.account_type()) => {
+                    errors.push(FieldError::new(
+                        field("section"),
+                        format!(
+                            "{} subtypes cannot be shown in {} sections",
+                            subtype.account_type(),
+                            kind.as_str()
+                        ),
+                    ));
+                }
+                Some(_) => {}
+            }
+        }
+        for subtype in AccountSubtype::ALL {
+            if !assigned.contains(&subtype) {
+                errors.push(FieldError::new(
+                    format!("subtypes.{}", subtype.as_str()),
+                    "not mapped to a section",
+                ));
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// Where an account is shown: the kind it counts towards and, when its
+    /// subtype is mapped to a section that can show it, that section.
+    ///
+    /// Returns `None` for an unknown account type.
+    #[must_use]
+    pub fn place(
+        &self,
+        account_type: &str,
+        subtype: Option<&str>,
+    ) -> Option<(SectionKind, Option<&StatementSection>)> {
+        let section = subtype
+            .and_then(|s| self.subtypes.iter().find(|m| m.subtype == s))
+            .and_then(|m| self.sections.iter().find(|s| s.key == m.section))
+            .filter(|s| s.kind.accepts(account_type));
+        match section {
+            Some(section) => Some((section.kind, Some(section))),
+            None => SectionKind::fallback(account_type).map(|kind| (kind, None)),
+        }
+    }
+
+    /// Sections of `kind` in sort order.
+    #[must_use]
+    pub fn sections_of(&self, kind: SectionKind) -> Vec<&StatementSection> {
+        let mut sections: Vec<&StatementSection> =
+            self.sections.iter().filter(|s| s.kind == kind).collect();
+        sections.sort_by(|a, b| a.sort_order.cmp(&b.sort_order).then(a.key.cmp(&b.key)));
+        sections
+    }
+}
+
+fn is_valid_key(key: &str) -> bool {
+    !key.is_empty()
+        && key.len() <= MAX_KEY_LENGTH
+        && key
+            .bytes()
+            .all(|b| b.is_ascii_lowercase() || b.is_ascii_digit() || b == b'_')
+}
@@ -6,14 +6,37 @@
 //! - Income Statement
 //! - Account Ledger
 //! - Dimensional Reports
+//! - Statement section mapping
+//! - Data-quality checks
+//! - Balance matrices at several dates
+//! - Saved custom report definitions
+//! - Printable journal vouchers, as HTML or PDF
 
+pub mod balance_matrix;
+pub mod custom;
+pub mod data_quality;
 pub mod error;
+pub mod mapping;
+pub mod pdf;
 pub mod service;
 pub mod types;
+pub mod voucher;
 
 #[cfg(test)]
 mod tests;
 
+pub use balance_matrix::{
+    BalanceMatrix, BalanceMatrixRow, BalanceMatrixTotal, MAX_BALANCE_MATRIX_DATES,
+};
+pub use custom::{
+    AggregateFunction, CustomReportResult, FieldKind, MAX_CUSTOM_REPORT_ROWS, MAX_GROUP_BY_FIELDS,
+    ReportAggregate, ReportDataset, ReportDefinition, ReportField, ReportFilters, ReportPlan,
+    ReportValue,
+};
+pub use data_quality::{DataQualityCheck, DataQualityFinding};
 pub use error::ReportError;
+pub use mapping::{
+    AccountSubtype, SectionKind, Statement, StatementMapping, StatementSection, SubtypeSection,
+};
 pub use service::ReportService;
 pub use types::*;
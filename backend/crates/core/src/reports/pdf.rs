@@ -0,0 +1,384 @@
+//! Minimal PDF output for printable reports.
+//!
+//! Pages are A4 portrait and text is set in the standard Helvetica fonts
+//! with WinAnsi encoding, so no font is embedded and files stay small.
+//! Characters outside that encoding print as `?`.
+//!
+//! Layout works in whole points from the top-left corner of the page; a
+//! cursor moves down as lines are written and a new page starts when it
+//! reaches the bottom margin.
+
+use std::fmt::Write as _;
+
+/// A4 page width in points.
+pub const PAGE_WIDTH: i32 = 595;
+/// A4 page height in points.
+pub const PAGE_HEIGHT: i32 = 842;
+/// Margin on every side of the page, in points.
+pub const MARGIN: i32 = 48;
+
+/// Typeface of a run of text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Font {
+    /// Helvetica.
+    Regular,
+    /// Helvetica-Bold.
+    Bold,
+}
+
+impl Font {
+    const fn resource(self) -> &'static str {
+        match self {
+            Self::Regular => "F1",
+            Self::Bold => "F2",
+        }
+    }
+}
+
+/// Which edge of the text sits at the given x position.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Align {
+    /// Text starts at x.
+    Left,
+    /// Text ends at x.
+    Right,
+}
+
+/// Helvetica advance widths of the printable ASCII characters, in
+/// thousandths of the font size.
+const HELVETICA_WIDTHS: [u32; 95] = [
+    278, 278, 355, 556, 556, 889, 667, 191, 333, 333, 389, 584, 278, 333, 278,
+    278, // space to /
+    556, 556, 556, 556, 556, 556, 556, 556, 556, 556, 278, 278, 584, 584, 584, 556, // 0 to ?
+    1015, 667, 667, 722, 722, 667, 611, 778, 722, 278, 500, 667, 556, 833, 722, 778, // @ to O
+    667, 778, 722, 667, 611, 722, 667, 944, 667, 667, 611, 278, 278, 278, 469, 556, // P to _
+    333, 556, 556, 500, 556, 556, 278, 556, 556, 222, 222, 500, 222, 833, 556, 556, // ` to o
+    556, 556, 333, 500, 278, 556, 500, 722, 500, 500, 500, 334, 260, 334, 584, // p to ~
+];
+
+/// Width of `text` at `size` points, rounded up to whole points.
+///
+/// Bold text is measured with the regular widths, which are close enough
+/// for fitting text into columns.
+#[must_use]
+pub fn text_width(text: &str, size: u32) -> i32 {
+    let thousandths: u32 = text
+        .chars()
+        .map(|c| {
+            let code = c as u32;
+            if (32..127).contains(&code) {
+                HELVETICA_WIDTHS[(code - 32) as usize]
+            } else {
+                556
+            }
+        })
+        .sum();
+    i32::try_from((thousandths * size).div_ceil(1000)).unwrap_or(i32::MAX)
+}
+
+/// Cuts `text` down to `width` points, ending it with `...` when cut.
+#[must_use]
+pub fn fit(text: &str, width: i32, size: u32) -> String {
+    if text_width(text, size) <= width {
+        return text.to_string();
+    }
+    let mut fitted = String::new();
+    for c in text.chars() {
+        fitted.push(c);
+        if text_width(&fitted, size) + text_width("...", size) > width {
+            fitted.pop();
+            break;
+        }
+    }
+    format!("{}...", fitted.trim_end())
+}
+
+/// Breaks `text` into lines no wider than `width` points, at spaces.
+///
+/// A single word wider than the line is cut with [`fit`].
+#[must_use]
+pub fn wrap(text: &str, width: i32, size: u32) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut line = String::new();
+    for word in text.split_whitespace() {
+        let candidate = if line.is_empty() {
+            word.to_string()
+        } else {
+            format!("{line} {word}")
+        };
+        if text_width(&candidate, size) <= width {
+            line = candidate;
+        } else {
+            if !line.is_empty() {
+                lines.push(std::mem::take(&mut line));
+            }
+            line = fit(word, width, size);
+        }
+    }
+    if !line.is_empty() || lines.is_empty() {
+        lines.push(line);
+    }
+    lines
+}
+
+/// A PDF being laid out page by page.
+#[derive(Debug, Clone)]
+pub struct PdfDocument {
+    pages: Vec<String>,
+    current: String,
+    y: i32,
+    watermark: Option<String>,
+}
+
+impl Default for PdfDocument {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PdfDocument {
+    /// An empty document with the cursor at the top margin of page one.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            pages: Vec::new(),
+            current: String::new(),
+            y: MARGIN,
+            watermark: None,
+        }
+    }
+
+    /// Prints `text` large, diagonally and faintly across every page.
+    #[must_use]
+    pub fn with_watermark(mut self, text: impl Into<String>) -> Self {
+        self.watermark = Some(text.into());
+        self
+    }
+
+    /// Distance of the cursor from the top of the page, in points.
+    #[must_use]
+    pub const fn y(&self) -> i32 {
+        self.y
+    }
+
+    /// Writes `text` with its baseline on the cursor line.
+    pub fn text(&mut self, x: i32, text: &str, size: u32, font: Font, align: Align) {
+        let x = match align {
+            Align::Left => x,
+            Align::Right => x - text_width(text, size),
+        };
+        let _ = writeln!(
+            self.current,
+            "BT /{} {size} Tf {x} {} Td ({}) Tj ET",
+            font.resource(),
+            PAGE_HEIGHT - self.y,
+            encode(text)
+        );
+    }
+
+    /// Draws a horizontal rule from `x1` to `x2` on the cursor line.
+    pub fn rule(&mut self, x1: i32, x2: i32) {
+        let y = PAGE_HEIGHT - self.y;
+        let _ = writeln!(self.current, "0.5 w {x1} {y} m {x2} {y} l S");
+    }
+
+    /// Moves the cursor down by `dy` points, starting a new page when it
+    /// passes the bottom margin.
+    pub fn advance(&mut self, dy: i32) {
+        self.y += dy;
+        if self.y > PAGE_HEIGHT - MARGIN {
+            self.new_page();
+            self.y += dy;
+        }
+    }
+
+    /// Starts a new page unless `height` points still fit on this one, and
+    /// returns whether it did.
+    pub fn ensure_space(&mut self, height: i32) -> bool {
+        let full = self.y + height > PAGE_HEIGHT - MARGIN;
+        if full {
+            self.new_page();
+        }
+        full
+    }
+
+    /// Ends the current page and puts the cursor at the top of the next.
+    pub fn new_page(&mut self) {
+        self.pages.push(std::mem::take(&mut self.current));
+        self.y = MARGIN;
+    }
+
+    /// Number of pages, counting the one being written.
+    #[must_use]
+    pub fn page_count(&self) -> usize {
+        self.pages.len() + 1
+    }
+
+    /// Serializes the document.
+    #[must_use]
+    pub fn finish(mut self) -> Vec<u8> {
+        self.pages.push(std::mem::take(&mut self.current));
+
+        // Objects 1-5 are fixed; each page then has a page and a content object
+        let page_ids: Vec<usize> = (0..self.pages.len()).map(|i| 6 + 2 * i).collect();
+        let kids: Vec<String> = page_ids.iter().map(|id| format!("{id} 0 R")).collect();
+        let mut objects = vec![
+            "<< /Type /Catalog /Pages 2 0 R >>".to_string(),
+            format!(
+                "<< /Type /Pages /Kids [{}] /Count {} >>",
+                kids.join(" "),
+                page_ids.len()
+            ),
+            "<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica /Encoding /WinAnsiEncoding >>"
+                .to_string(),
+            "<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica-Bold /Encoding /WinAnsiEncoding >>"
+                .to_string(),
+            "<< /Type /ExtGState /ca 0.15 /CA 0.15 >>".to_string(),
+        ];
+        for (page, id) in self.pages.iter().zip(&page_ids) {
+            let mut content = String::new();
+            if let Some(watermark) = &self.watermark {
+                let _ = writeln!(
+                    content,
+                    "q /GS1 gs 0.8 0 0 rg BT /F2 140 Tf 0.7071 0.7071 -0.7071 0.7071 150 230 Tm ({}) Tj ET Q",
+                    encode(watermark)
+                );
+            }
+            content.push_str(page);
+            objects.push(format!(
+                "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 {PAGE_WIDTH} {PAGE_HEIGHT}] \
+                 /Resources << /Font << /F1 3 0 R /F2 4 0 R >> /ExtGState << /GS1 5 0 R >> >> \
+                 /Contents {} 0 R >>",
+                id + 1
+            ));
+            objects.push(format!(
+                "<< /Length {} >>\nstream\n{content}endstream",
+                content.len()
+            ));
+        }
+
+        let mut pdf = b"%PDF-1.4\n%\xE2\xE3\xCF\xD3\n".to_vec();
+        let mut offsets = Vec::with_capacity(objects.len());
+        for (i, object) in objects.iter().enumerate() {
+            offsets.push(pdf.len());
+            pdf.extend_from_slice(format!("{} 0 obj\n{object}\nendobj\n", i + 1).as_bytes());
+        }
+        let xref = pdf.len();
+        let mut trailer = format!("xref\n0 {}\n0000000000 65535 f \n", objects.len() + 1);
+        for offset in offsets {
+            let _ = writeln!(trailer, "{offset:010} 00000 n ");
+        }
+        let _ = write!(
+            trailer,
+            "trailer\n<< /Size {} /Root 1 0 R >>\nstartxref\n{xref}\n%%EOF\n",
+            objects.len() + 1
+        );
+        pdf.extend_from_slice(trailer.as_bytes());
+        pdf
+    }
+}
+
+/// Escapes `text` as the body of a PDF string in WinAnsi encoding.
+fn encode(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '(' | ')' | '\\' => {
+                out.push('\\');
+                out.push(c);
+            }
+            ' '..='~' => out.push(c),
+            '\u{a0}'..='\u{ff}' => {
+                let _ = write!(out, "\\{:03o}", c as u32);
+            }
+            '€' => out.push_str("\\200"),
+            _ => out.push('?'),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_text_width_uses_helvetica_metrics() {
+        assert_eq!(text_width("", 10), 0);
+        // Digits are 556/1000 em wide
+        assert_eq!(text_width("1000", 10), 23);
+        assert_eq!(text_width("iii", 10), 7);
+    }
+
+    #[test]
+    fn test_fit_and_wrap() {
+        assert_eq!(fit("Cash", 100, 10), "Cash");
+        let fitted = fit("Accumulated depreciation of equipment", 80, 10);
+        assert!(fitted.ends_with("..."));
+        assert!(text_width(&fitted, 10) <= 80);
+
+        let lines = wrap("one thousand two hundred thirty-four US dollars", 100, 10);
+        assert!(lines.len() > 1);
+        assert!(lines.iter().all(|line| text_width(line, 10) <= 100));
+        assert_eq!(
+            lines.join(" "),
+            "one thousand two hundred thirty-four US dollars"
+        );
+        assert_eq!(wrap("", 100, 10), [""]);
+    }
+
+    #[test]
+    fn test_encode_escapes_and_maps_to_winansi() {
+        assert_eq!(encode(r"a (b) \c"), r"a \(b\) \\c");
+        assert_eq!(encode("café €5 ✓"), r"caf\351 \2005 ?");
+    }
+
+    #[test]
+    fn test_finish_writes_pages_and_cross_reference() {
+        let mut doc = PdfDocument::new().with_watermark("VOID");
+        doc.advance(12);
+        doc.text(MARGIN, "Page one", 10, Font::Regular, Align::Left);
+        doc.new_page();
+        doc.advance(12);
+        doc.text(
+            PAGE_WIDTH - MARGIN,
+            "Page two",
+            10,
+            Font::Bold,
+            Align::Right,
+        );
+        assert_eq!(doc.page_count(), 2);
+
+        let pdf = String::from_utf8_lossy(&doc.finish()).into_owned();
+        assert!(pdf.starts_with("%PDF-1.4"));
+        assert!(pdf.ends_with("%%EOF\n"));
+        assert!(pdf.contains("/Count 2"));
+        assert!(pdf.contains("(Page one) Tj"));
+        assert!(pdf.contains("/F2 10 Tf"));
+        assert_eq!(pdf.matches("(VOID) Tj").count(), 2);
+
+        // Every cross-reference offset points at its object
+        let xref = pdf.rfind("xref\n").unwrap();
+        let bytes = pdf.as_bytes();
+        for (i, line) in pdf[xref..].lines().skip(3).take(9).enumerate() {
+            let offset: usize = line[..10].parse().unwrap();
+            let header = format!("{} 0 obj", i + 1);
+            assert_eq!(&bytes[offset..offset + header.len()], header.as_bytes());
+        }
+    }
+
+    #[test]
+    fn test_advance_breaks_pages_at_bottom_margin() {
+        let mut doc = PdfDocument::new();
+        for _ in 0..100 {
+            doc.advance(12);
+        }
+        assert!(doc.page_count() > 1);
+        assert!(doc.y() <= PAGE_HEIGHT - MARGIN);
+
+        let mut doc = PdfDocument::new();
+        assert!(!doc.ensure_space(100));
+        assert!(doc.ensure_space(PAGE_HEIGHT));
+        assert_eq!(doc.page_count(), 2);
+    }
+}
@@ -1,11 +1,25 @@
 //! Report generation service.
 
+use std::collections::{HashMap, HashSet};
+
 use rust_decimal::Decimal;
+use uuid::Uuid;
 
+use super::error::ReportError;
+use super::mapping::{SectionKind, StatementMapping};
 use super::types::{
-    AccountBalance, BalanceSheetReport, BalanceSheetSection, IncomeStatementReport,
-    IncomeStatementSection, TrialBalanceReport, TrialBalanceTotals,
+    AccountBalance, BalanceSheetReport, BalanceSheetSection, DimensionAllocation,
+    DimensionIncomeStatementColumn, DimensionIncomeStatementReport, DimensionTaggedTotals,
+    IncomeStatementReport, IncomeStatementSection, ReportDimensionValue, StatementSubsection,
+    TrialBalanceReport, TrialBalanceTotals,
 };
+use crate::currency::AllocationUtil;
+
+/// Decimal places amounts are split to, matching ledger amounts.
+const SPLIT_DECIMAL_PLACES: u32 = 4;
+
+/// Debit and credit totals per account.
+type ColumnTotals = HashMap<Uuid, (Decimal, Decimal)>;
 
 /// Service for generating financial reports.
 pub struct ReportService;
@@ -32,22 +46,51 @@ impl ReportService {
         }
     }
 
-    /// Generates a balance sheet report from account balances.
+    /// Generates a balance sheet report from account balances, grouped by
+    /// the default statement mapping.
     ///
     /// The balance sheet verifies that Assets = Liabilities + Equity.
     #[must_use]
     pub fn generate_balance_sheet(accounts: Vec<AccountBalance>) -> BalanceSheetReport {
-        let mut assets = BalanceSheetSection::default();
-        let mut liabilities = BalanceSheetSection::default();
-        let mut equity = BalanceSheetSection::default();
+        Self::generate_balance_sheet_with_mapping(accounts, &StatementMapping::default())
+    }
+
+    /// Generates a balance sheet report, grouping accounts into the sections
+    /// of `mapping`.
+    #[must_use]
+    pub fn generate_balance_sheet_with_mapping(
+        accounts: Vec<AccountBalance>,
+        mapping: &StatementMapping,
+    ) -> BalanceSheetReport {
+        let section = |kind| BalanceSheetSection {
+            subsections: subsections(mapping, kind),
+            ..Default::default()
+        };
+        let mut assets = section(SectionKind::Assets);
+        let mut liabilities = section(SectionKind::Liabilities);
+        let mut equity = section(SectionKind::Equity);
 
         for account in accounts {
-            match account.account_type.as_str() {
-                "asset" => Self::add_to_section(&mut assets, account),
-                "liability" => Self::add_to_section(&mut liabilities, account),
-                "equity" => Self::add_to_section(&mut equity, account),
-                _ => {}
+            let Some((kind, mapped)) =
+                mapping.place(&account.account_type, account.account_subtype.as_deref())
+            else {
+                continue;
+            };
+            let target = match kind {
+                SectionKind::Assets => &mut assets,
+                SectionKind::Liabilities => &mut liabilities,
+                SectionKind::Equity => &mut equity,
+                _ => continue,
+            };
+            if let Some(mapped) = mapped {
+                add_to_subsection(
+                    &mut target.subsections,
+                    &mapped.key,
+                    &account,
+                    account.balance,
+                );
             }
+            Self::add_to_section(target, account);
         }
 
         let total_assets = assets.total;
@@ -70,31 +113,57 @@ impl ReportService {
         }
     }
 
-    /// Generates an income statement report from account balances.
+    /// Generates an income statement report from account balances, grouped
+    /// by the default statement mapping.
     ///
     /// Calculates gross profit, operating income, and net income.
     #[must_use]
     pub fn generate_income_statement(accounts: Vec<AccountBalance>) -> IncomeStatementReport {
-        let mut revenue = IncomeStatementSection::default();
-        let mut cogs = IncomeStatementSection::default();
-        let mut operating_expenses = IncomeStatementSection::default();
-        let mut other = IncomeStatementSection::default();
+        Self::generate_income_statement_with_mapping(accounts, &StatementMapping::default())
+    }
+
+    /// Generates an income statement report, grouping accounts into the
+    /// sections of `mapping`.
+    ///
+    /// Revenue mapped to other income reduces the other income/expense total.
+    #[must_use]
+    pub fn generate_income_statement_with_mapping(
+        accounts: Vec<AccountBalance>,
+        mapping: &StatementMapping,
+    ) -> IncomeStatementReport {
+        let section = |kind| IncomeStatementSection {
+            subsections: subsections(mapping, kind),
+            ..Default::default()
+        };
+        let mut revenue = section(SectionKind::Revenue);
+        let mut cogs = section(SectionKind::CostOfGoodsSold);
+        let mut operating_expenses = section(SectionKind::OperatingExpenses);
+        let mut other = section(SectionKind::OtherIncomeExpense);
 
         for account in accounts {
-            match (
-                account.account_type.as_str(),
-                account.account_subtype.as_deref(),
-            ) {
-                ("revenue", _) => Self::add_to_income_section(&mut revenue, account),
-                ("expense", Some("cost_of_goods_sold")) => {
-                    Self::add_to_income_section(&mut cogs, account);
-                }
-                ("expense", Some("operating_expense")) => {
-                    Self::add_to_income_section(&mut operating_expenses, account);
-                }
-                ("expense", _) => Self::add_to_income_section(&mut other, account),
-                _ => {}
+            let Some((kind, mapped)) =
+                mapping.place(&account.account_type, account.account_subtype.as_deref())
+            else {
+                continue;
+            };
+            let target = match kind {
+                SectionKind::Revenue => &mut revenue,
+                SectionKind::CostOfGoodsSold => &mut cogs,
+                SectionKind::OperatingExpenses => &mut operating_expenses,
+                SectionKind::OtherIncomeExpense => &mut other,
+                _ => continue,
+            };
+            let amount =
+                if kind == SectionKind::OtherIncomeExpense && account.account_type == "revenue" {
+                    -account.balance.abs()
+                } else {
+                    account.balance.abs()
+                };
+            if let Some(mapped) = mapped {
+                add_to_subsection(&mut target.subsections, &mapped.key, &account, amount);
             }
+            target.total += amount;
+            target.accounts.push(account);
         }
 
         let gross_profit = revenue.total - cogs.total;
@@ -116,13 +185,214 @@ impl ReportService {
         }
     }
 
+    /// Generates an income statement per dimension value, plus an
+    /// unallocated column for entries without one.
+    ///
+    /// An entry carrying several values of the reported type is split evenly
+    /// between them rather than counted in each, so the columns always add up
+    /// to the total. Values that are not columns, such as inactive ones, count
+    /// as unallocated. With an `allocation`, the unallocated amounts of each
+    /// account are then spread over the values by percentage.
+    ///
+    /// `accounts` supplies the rows every column lists; their totals are
+    /// ignored. Every column is grouped by `mapping`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ReportError::InvalidAllocation`] if the allocation names a
+    /// value that is not a column or names one twice, has a negative share,
+    /// or does not sum to 100.
+    pub fn income_statement_by_dimension(
+        accounts: &[AccountBalance],
+        values: &[ReportDimensionValue],
+        totals: &[DimensionTaggedTotals],
+        allocation: Option<&DimensionAllocation>,
+        mapping: &StatementMapping,
+    ) -> Result<DimensionIncomeStatementReport, ReportError> {
+        let column_of: HashMap<Uuid, usize> =
+            values.iter().enumerate().map(|(i, v)| (v.id, i)).collect();
+        let unallocated = values.len();
+        let shares = allocation
+            .map(|a| Self::allocation_columns(a, &column_of))
+            .transpose()?;
+
+        let mut columns: Vec<ColumnTotals> = vec![HashMap::new(); values.len() + 1];
+        for row in totals {
+            let mut targets: Vec<usize> = row
+                .dimension_value_ids
+                .iter()
+                .filter_map(|id| column_of.get(id).copied())
+                .collect();
+            targets.sort_unstable();
+            targets.dedup();
+            if targets.is_empty() {
+                targets.push(unallocated);
+            }
+            let debits = AllocationUtil::allocate_equal(
+                row.total_debit,
+                targets.len(),
+                SPLIT_DECIMAL_PLACES,
+            );
+            let credits = AllocationUtil::allocate_equal(
+                row.total_credit,
+                targets.len(),
+                SPLIT_DECIMAL_PLACES,
+            );
+            for ((column, debit), credit) in targets.into_iter().zip(debits).zip(credits) {
+                add_totals(&mut columns[column], row.account_id, debit, credit);
+            }
+        }
+
+        if let Some(shares) = &shares {
+            let percentages: Vec<Decimal> = shares.iter().map(|(_, p)| *p).collect();
+            for (account_id, (debit, credit)) in std::mem::take(&mut columns[unallocated]) {
+                let debits = AllocationUtil::allocate_by_percentages(
+                    debit,
+                    &percentages,
+                    SPLIT_DECIMAL_PLACES,
+                );
+                let credits = AllocationUtil::allocate_by_percentages(
+                    credit,
+                    &percentages,
+                    SPLIT_DECIMAL_PLACES,
+                );
+                for (((column, _), debit), credit) in shares.iter().zip(debits).zip(credits) {
+                    add_totals(&mut columns[*column], account_id, debit, credit);
+                }
+            }
+        }
+
+        let mut overall: ColumnTotals = HashMap::new();
+        for column in &columns {
+            for (account_id, (debit, credit)) in column {
+                add_totals(&mut overall, *account_id, *debit, *credit);
+            }
+        }
+
+        let headings = values
+            .iter()
+            .map(|v| (Some(v.id), v.code.clone(), v.name.clone()))
+            .chain([(None, "unallocated".to_string(), "Unallocated".to_string())]);
+        let columns = headings
+            .zip(&columns)
+            .map(
+                |((dimension_value_id, code, name), column)| DimensionIncomeStatementColumn {
+                    dimension_value_id,
+                    code,
+                    name,
+                    statement: Self::generate_income_statement_with_mapping(
+                        with_totals(accounts, column),
+                        mapping,
+                    ),
+                },
+            )
+            .collect();
+
+        Ok(DimensionIncomeStatementReport {
+            report_type: "income_statement_by_dimension".to_string(),
+            columns,
+            allocated: shares.is_some(),
+            total: Self::generate_income_statement_with_mapping(
+                with_totals(accounts, &overall),
+                mapping,
+            ),
+        })
+    }
+
+    /// Resolves allocation shares to column indexes, checking they are valid.
+    fn allocation_columns(
+        allocation: &DimensionAllocation,
+        column_of: &HashMap<Uuid, usize>,
+    ) -> Result<Vec<(usize, Decimal)>, ReportError> {
+        let mut seen = HashSet::new();
+        let mut shares = Vec::with_capacity(allocation.shares.len());
+        for (value_id, percentage) in &allocation.shares {
+            let Some(column) = column_of.get(value_id) else {
+                return Err(ReportError::InvalidAllocation(format!(
+                    "{value_id} is not an active value of the dimension type"
+                )));
+            };
+            if !seen.insert(*value_id) {
+                return Err(ReportError::InvalidAllocation(format!(
+                    "{value_id} is listed more than once"
+                )));
+            }
+            if percentage.is_sign_negative() {
+                return Err(ReportError::InvalidAllocation(format!(
+                    "share for {value_id} is negative"
+                )));
+            }
+            shares.push((*column, *percentage));
+        }
+
+        let sum: Decimal = shares.iter().map(|(_, p)| *p).sum();
+        if sum != Decimal::ONE_HUNDRED {
+            return Err(ReportError::InvalidAllocation(format!(
+                "shares sum to {sum}, not 100"
+            )));
+        }
+        Ok(shares)
+    }
+
     fn add_to_section(section: &mut BalanceSheetSection, account: AccountBalance) {
         section.total += account.balance;
         section.accounts.push(account);
     }
+}
 
-    fn add_to_income_section(section: &mut IncomeStatementSection, account: AccountBalance) {
-        section.total += account.balance.abs();
-        section.accounts.push(account);
+/// Empty subsections for the mapped sections of `kind`.
+fn subsections(mapping: &StatementMapping, kind: SectionKind) -> Vec<StatementSubsection> {
+    mapping
+        .sections_of(kind)
+        .into_iter()
+        .map(|section| StatementSubsection {
+            key: section.key.clone(),
+            name: section.name.clone(),
+            total: Decimal::ZERO,
+            accounts: Vec::new(),
+        })
+        .collect()
+}
+
+fn add_to_subsection(
+    subsections: &mut [StatementSubsection],
+    key: &str,
+    account: &AccountBalance,
+    amount: Decimal,
+) {
+    if let Some(subsection) = subsections.iter_mut().find(|s| s.key == key) {
+        subsection.total += amount;
+        subsection.accounts.push(account.clone());
     }
 }
+
+fn add_totals(totals: &mut ColumnTotals, account_id: Uuid, debit: Decimal, credit: Decimal) {
+    let entry = totals
+        .entry(account_id)
+        .or_insert((Decimal::ZERO, Decimal::ZERO));
+    entry.0 += debit;
+    entry.1 += credit;
+}
+
+/// Copies `accounts` with their debits and credits taken from `totals`.
+fn with_totals(accounts: &[AccountBalance], totals: &ColumnTotals) -> Vec<AccountBalance> {
+    accounts
+        .iter()
+        .map(|account| {
+            let (total_debit, total_credit) = totals
+                .get(&account.account_id)
+                .copied()
+                .unwrap_or((Decimal::ZERO, Decimal::ZERO));
+            let balance = match account.account_type.as_str() {
+                "asset" | "expense" => total_debit - total_credit,
+                _ => total_credit - total_debit,
+            };
+            AccountBalance {
+                total_debit,
+                total_credit,
+                balance,
+                ..account.clone()
+            }
+        })
+        .collect()
+}
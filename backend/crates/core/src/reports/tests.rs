@@ -4,9 +4,20 @@ use proptest::prelude::*;
 use rust_decimal::Decimal;
 use rust_decimal_macros::dec;
 use uuid::Uuid;
+use zeltra_shared::format::{ExportStyle, Locale};
 
+use super::balance_matrix::{BalanceMatrix, BalanceMatrixRow};
+use super::custom::{
+    AggregateFunction, CustomReportResult, ReportAggregate, ReportDataset, ReportDefinition,
+    ReportField, ReportFilters, ReportValue,
+};
+use super::error::ReportError;
+use super::mapping::{AccountSubtype, StatementMapping, SubtypeSection};
 use super::service::ReportService;
-use super::types::AccountBalance;
+use super::types::{
+    AccountBalance, DimensionAllocation, DimensionTaggedTotals, MovementChange, PeriodMovement,
+    ReportDimensionValue,
+};
 
 proptest! {
     /// Feature: reports-simulation, Property 1: Trial Balance Debits Equal Credits
@@ -19,12 +30,12 @@ proptest! {
         // Generate accounts with random debits and credits
         let accounts: Vec<AccountBalance> = (0..num_accounts)
             .map(|i| {
-                let debit = Decimal::from(i as i64 * 1000 + 500);
-                let credit = Decimal::from(i as i64 * 800 + 300);
+                let debit = Decimal::from(i * 1000 + 500);
+                let credit = Decimal::from(i * 800 + 300);
                 AccountBalance {
                     account_id: Uuid::new_v4(),
                     code: format!("{}", 1000 + i),
-                    name: format!("Account {}", i),
+                    name: format!("Account {i}"),
                     account_type: if i % 2 == 0 { "asset" } else { "liability" }.to_string(),
                     account_subtype: None,
                     total_debit: debit,
@@ -62,15 +73,15 @@ proptest! {
         let mut running_credit = Decimal::ZERO;
 
         for i in 0..num_accounts - 1 {
-            let debit = Decimal::from(i as i64 * 1000 + 500);
-            let credit = Decimal::from(i as i64 * 800 + 300);
+            let debit = Decimal::from(i * 1000 + 500);
+            let credit = Decimal::from(i * 800 + 300);
             running_debit += debit;
             running_credit += credit;
 
             accounts.push(AccountBalance {
                 account_id: Uuid::new_v4(),
                 code: format!("{}", 1000 + i),
-                name: format!("Account {}", i),
+                name: format!("Account {i}"),
                 account_type: if i % 2 == 0 { "asset" } else { "liability" }.to_string(),
                 account_subtype: None,
                 total_debit: debit,
@@ -171,12 +182,12 @@ proptest! {
         let mut expected_equity = Decimal::ZERO;
 
         for i in 0..num_assets {
-            let balance = Decimal::from(i as i64 * 1000 + 100);
+            let balance = Decimal::from(i * 1000 + 100);
             expected_assets += balance;
             accounts.push(AccountBalance {
                 account_id: Uuid::new_v4(),
-                code: format!("1{:03}", i),
-                name: format!("Asset {}", i),
+                code: format!("1{i:03}"),
+                name: format!("Asset {i}"),
                 account_type: "asset".to_string(),
                 account_subtype: None,
                 total_debit: balance,
@@ -186,12 +197,12 @@ proptest! {
         }
 
         for i in 0..num_liabilities {
-            let balance = Decimal::from(i as i64 * 500 + 50);
+            let balance = Decimal::from(i * 500 + 50);
             expected_liabilities += balance;
             accounts.push(AccountBalance {
                 account_id: Uuid::new_v4(),
-                code: format!("2{:03}", i),
-                name: format!("Liability {}", i),
+                code: format!("2{i:03}"),
+                name: format!("Liability {i}"),
                 account_type: "liability".to_string(),
                 account_subtype: None,
                 total_debit: Decimal::ZERO,
@@ -201,12 +212,12 @@ proptest! {
         }
 
         for i in 0..num_equity {
-            let balance = Decimal::from(i as i64 * 200 + 20);
+            let balance = Decimal::from(i * 200 + 20);
             expected_equity += balance;
             accounts.push(AccountBalance {
                 account_id: Uuid::new_v4(),
-                code: format!("3{:03}", i),
-                name: format!("Equity {}", i),
+                code: format!("3{i:03}"),
+                name: format!("Equity {i}"),
                 account_type: "equity".to_string(),
                 account_subtype: None,
                 total_debit: Decimal::ZERO,
@@ -305,12 +316,12 @@ proptest! {
         let mut expected_opex = Decimal::ZERO;
 
         for i in 0..num_revenue {
-            let balance = Decimal::from(i as i64 * 10000 + 5000);
+            let balance = Decimal::from(i * 10000 + 5000);
             expected_revenue += balance;
             accounts.push(AccountBalance {
                 account_id: Uuid::new_v4(),
-                code: format!("4{:03}", i),
-                name: format!("Revenue {}", i),
+                code: format!("4{i:03}"),
+                name: format!("Revenue {i}"),
                 account_type: "revenue".to_string(),
                 account_subtype: None,
                 total_debit: Decimal::ZERO,
@@ -320,12 +331,12 @@ proptest! {
         }
 
         for i in 0..num_cogs {
-            let balance = Decimal::from(i as i64 * 3000 + 1000);
+            let balance = Decimal::from(i * 3000 + 1000);
             expected_cogs += balance;
             accounts.push(AccountBalance {
                 account_id: Uuid::new_v4(),
-                code: format!("5{:03}", i),
-                name: format!("COGS {}", i),
+                code: format!("5{i:03}"),
+                name: format!("COGS {i}"),
                 account_type: "expense".to_string(),
                 account_subtype: Some("cost_of_goods_sold".to_string()),
                 total_debit: balance,
@@ -335,12 +346,12 @@ proptest! {
         }
 
         for i in 0..num_opex {
-            let balance = Decimal::from(i as i64 * 2000 + 500);
+            let balance = Decimal::from(i * 2000 + 500);
             expected_opex += balance;
             accounts.push(AccountBalance {
                 account_id: Uuid::new_v4(),
-                code: format!("6{:03}", i),
-                name: format!("OpEx {}", i),
+                code: format!("6{i:03}"),
+                name: format!("OpEx {i}"),
                 account_type: "expense".to_string(),
                 account_subtype: Some("operating_expense".to_string()),
                 total_debit: balance,
@@ -360,6 +371,7 @@ proptest! {
 #[cfg(test)]
 mod unit_tests {
     use super::*;
+    use chrono::NaiveDate;
 
     #[test]
     fn test_trial_balance_empty_accounts() {
@@ -456,4 +468,631 @@ mod unit_tests {
         assert_eq!(report.cost_of_goods_sold.total, dec!(0));
         assert_eq!(report.net_income, dec!(0));
     }
+
+    fn expense_account(code: &str) -> AccountBalance {
+        AccountBalance {
+            account_id: Uuid::new_v4(),
+            code: code.to_string(),
+            name: code.to_string(),
+            account_type: "expense".to_string(),
+            account_subtype: Some("operating_expense".to_string()),
+            total_debit: Decimal::ZERO,
+            total_credit: Decimal::ZERO,
+            balance: Decimal::ZERO,
+        }
+    }
+
+    fn value(code: &str) -> ReportDimensionValue {
+        ReportDimensionValue {
+            id: Uuid::new_v4(),
+            code: code.to_string(),
+            name: code.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_income_statement_by_dimension_splits_multi_tagged_entries() {
+        let rent = expense_account("6100");
+        let (eng, ops, sales) = (value("ENG"), value("OPS"), value("SALES"));
+        let expense = |ids: Vec<Uuid>, amount: Decimal| DimensionTaggedTotals {
+            account_id: rent.account_id,
+            dimension_value_ids: ids,
+            total_debit: amount,
+            total_credit: Decimal::ZERO,
+        };
+        let totals = [
+            expense(vec![eng.id], dec!(100)),
+            expense(vec![eng.id, ops.id, sales.id], dec!(10)),
+            expense(vec![], dec!(50)),
+            // A value that is not a column counts as unallocated
+            expense(vec![Uuid::new_v4()], dec!(7)),
+        ];
+        let values = [eng, ops, sales];
+
+        let report = ReportService::income_statement_by_dimension(
+            std::slice::from_ref(&rent),
+            &values,
+            &totals,
+            None,
+            &StatementMapping::default(),
+        )
+        .unwrap();
+
+        let net: Vec<(&str, Decimal)> = report
+            .columns
+            .iter()
+            .map(|c| (c.code.as_str(), c.statement.operating_expenses.total))
+            .collect();
+        assert_eq!(
+            net,
+            [
+                ("ENG", dec!(103.3334)),
+                ("OPS", dec!(3.3333)),
+                ("SALES", dec!(3.3333)),
+                ("unallocated", dec!(57)),
+            ]
+        );
+        assert_eq!(report.columns[3].dimension_value_id, None);
+        assert_eq!(report.total.operating_expenses.total, dec!(167));
+        assert!(!report.allocated);
+
+        let allocation = DimensionAllocation {
+            shares: vec![(values[0].id, dec!(50)), (values[2].id, dec!(50))],
+        };
+        let report = ReportService::income_statement_by_dimension(
+            std::slice::from_ref(&rent),
+            &values,
+            &totals,
+            Some(&allocation),
+            &StatementMapping::default(),
+        )
+        .unwrap();
+        assert_eq!(report.columns[0].statement.net_income, dec!(-131.8334));
+        assert_eq!(report.columns[2].statement.net_income, dec!(-31.8333));
+        assert_eq!(report.columns[3].statement.net_income, dec!(0));
+        assert_eq!(report.total.net_income, dec!(-167));
+        assert!(report.allocated);
+    }
+
+    #[test]
+    fn test_income_statement_by_dimension_rejects_bad_allocation() {
+        let values = [value("ENG"), value("SALES")];
+        let run = |shares: Vec<(Uuid, Decimal)>| {
+            ReportService::income_statement_by_dimension(
+                &[],
+                &values,
+                &[],
+                Some(&DimensionAllocation { shares }),
+                &StatementMapping::default(),
+            )
+        };
+
+        for shares in [
+            vec![(values[0].id, dec!(60)), (values[1].id, dec!(30))],
+            vec![(values[0].id, dec!(120)), (values[1].id, dec!(-20))],
+            vec![(values[0].id, dec!(50)), (values[0].id, dec!(50))],
+            vec![(Uuid::new_v4(), dec!(100))],
+        ] {
+            assert!(matches!(
+                run(shares),
+                Err(ReportError::InvalidAllocation(_))
+            ));
+        }
+        assert!(run(vec![(values[1].id, dec!(100))]).is_ok());
+    }
+
+    fn subtype_account(account_type: &str, subtype: &str, balance: Decimal) -> AccountBalance {
+        AccountBalance {
+            account_id: Uuid::new_v4(),
+            code: subtype.to_string(),
+            name: subtype.to_string(),
+            account_type: account_type.to_string(),
+            account_subtype: Some(subtype.to_string()),
+            total_debit: Decimal::ZERO,
+            total_credit: Decimal::ZERO,
+            balance,
+        }
+    }
+
+    fn assign(mapping: &mut StatementMapping, subtype: &str, section: &str) {
+        let entry = mapping
+            .subtypes
+            .iter_mut()
+            .find(|m| m.subtype == subtype)
+            .unwrap();
+        entry.section = section.to_string();
+    }
+
+    #[test]
+    fn test_default_mapping_covers_every_subtype() {
+        let mapping = StatementMapping::default();
+        mapping.validate().unwrap();
+
+        for subtype in AccountSubtype::ALL {
+            assert_eq!(AccountSubtype::from_name(subtype.as_str()), Some(subtype));
+            let (kind, section) = mapping
+                .place(subtype.account_type(), Some(subtype.as_str()))
+                .unwrap();
+            assert!(section.is_some(), "{} has no section", subtype.as_str());
+            assert!(kind.accepts(subtype.account_type()));
+        }
+    }
+
+    #[test]
+    fn test_default_mapping_keeps_builtin_grouping() {
+        let report = ReportService::generate_income_statement(vec![
+            subtype_account("revenue", "operating_revenue", dec!(1000)),
+            subtype_account("revenue", "other_revenue", dec!(50)),
+            subtype_account("expense", "cost_of_goods_sold", dec!(400)),
+            subtype_account("expense", "operating_expense", dec!(200)),
+            subtype_account("expense", "payroll_expense", dec!(100)),
+        ]);
+
+        assert_eq!(report.revenue.total, dec!(1050));
+        assert_eq!(report.cost_of_goods_sold.total, dec!(400));
+        assert_eq!(report.operating_expenses.total, dec!(200));
+        assert_eq!(report.other_income_expense.total, dec!(100));
+        assert_eq!(report.net_income, dec!(350));
+        let names: Vec<&str> = report
+            .revenue
+            .subsections
+            .iter()
+            .map(|s| s.name.as_str())
+            .collect();
+        assert_eq!(names, ["Revenue", "Other Revenue"]);
+    }
+
+    #[test]
+    fn test_custom_mapping_renames_and_moves_sections() {
+        let mut mapping = StatementMapping::default();
+        mapping
+            .sections
+            .iter_mut()
+            .find(|s| s.key == "cost_of_goods_sold")
+            .unwrap()
+            .name = "Cost of Revenue".to_string();
+        assign(&mut mapping, "accrued_liability", "long_term_liabilities");
+        assign(&mut mapping, "payroll_expense", "operating_expenses");
+        mapping.validate().unwrap();
+
+        let sheet = ReportService::generate_balance_sheet_with_mapping(
+            vec![
+                subtype_account("liability", "accrued_liability", dec!(300)),
+                subtype_account("liability", "accounts_payable", dec!(200)),
+            ],
+            &mapping,
+        );
+        let [current, long_term] = &sheet.liabilities.subsections[..] else {
+            panic!("expected two liability sections");
+        };
+        assert_eq!(
+            (current.key.as_str(), current.total),
+            ("current_liabilities", dec!(200))
+        );
+        assert_eq!(
+            (long_term.key.as_str(), long_term.total),
+            ("long_term_liabilities", dec!(300))
+        );
+        assert_eq!(sheet.total_liabilities, dec!(500));
+
+        let income = ReportService::generate_income_statement_with_mapping(
+            vec![
+                subtype_account("expense", "cost_of_goods_sold", dec!(400)),
+                subtype_account("expense", "payroll_expense", dec!(100)),
+            ],
+            &mapping,
+        );
+        assert_eq!(
+            income.cost_of_goods_sold.subsections[0].name,
+            "Cost of Revenue"
+        );
+        assert_eq!(income.operating_expenses.total, dec!(100));
+        assert_eq!(income.other_income_expense.total, dec!(0));
+    }
+
+    #[test]
+    fn test_revenue_mapped_to_other_income_reduces_other_total() {
+        let mut mapping = StatementMapping::default();
+        assign(&mut mapping, "other_revenue", "other_expenses");
+        mapping.validate().unwrap();
+
+        let report = ReportService::generate_income_statement_with_mapping(
+            vec![
+                subtype_account("revenue", "operating_revenue", dec!(1000)),
+                subtype_account("revenue", "other_revenue", dec!(50)),
+                subtype_account("expense", "interest_expense", dec!(80)),
+            ],
+            &mapping,
+        );
+
+        assert_eq!(report.revenue.total, dec!(1000));
+        assert_eq!(report.other_income_expense.total, dec!(30));
+        assert_eq!(report.net_income, dec!(970));
+    }
+
+    #[test]
+    fn test_mapping_validation_reports_every_problem() {
+        let mut mapping = StatementMapping::default();
+        mapping
+            .subtypes
+            .retain(|m| m.subtype != "accrued_liability");
+        mapping.subtypes.push(SubtypeSection {
+            subtype: "cash".to_string(),
+            section: "fixed_assets".to_string(),
+        });
+        mapping.subtypes.push(SubtypeSection {
+            subtype: "goodwill".to_string(),
+            section: "other_assets".to_string(),
+        });
+        assign(&mut mapping, "bank", "equity");
+        assign(&mut mapping, "inventory", "missing");
+        mapping.sections[1].sort_order = mapping.sections[0].sort_order;
+        mapping.sections[2].key = "Other Assets".to_string();
+
+        let errors = mapping.validate().unwrap_err();
+        let fields: Vec<&str> = errors.iter().map(|e| e.field.as_str()).collect();
+
+        assert!(fields.contains(&"subtypes.accrued_liability"));
+        assert!(fields.contains(&"sections[1].sort_order"));
+        assert!(fields.contains(&"sections[2].key"));
+        let message = |field: &str| {
+            errors
+                .iter()
+                .find(|e| e.field == field)
+                .map(|e| e.message.as_str())
+        };
+        let last = mapping.subtypes.len() - 1;
+        assert_eq!(
+            message(&format!("subtypes[{}].subtype", last - 1)),
+            Some("subtype is mapped more than once")
+        );
+        assert_eq!(
+            message(&format!("subtypes[{last}].subtype")),
+            Some("unknown account subtype")
+        );
+        assert_eq!(
+            message("subtypes[1].section"),
+            Some("asset subtypes cannot be shown in equity sections")
+        );
+        assert_eq!(message("subtypes[3].section"), Some("unknown section"));
+        // other_asset points at the section whose key is invalid
+        assert_eq!(message("subtypes[7].section"), Some("unknown section"));
+        assert_eq!(errors.len(), 8);
+    }
+
+    #[test]
+    fn test_unusable_mapping_falls_back_to_account_type() {
+        let mut mapping = StatementMapping::default();
+        mapping.subtypes.clear();
+
+        let report = ReportService::generate_income_statement_with_mapping(
+            vec![
+                subtype_account("revenue", "operating_revenue", dec!(1000)),
+                subtype_account("expense", "cost_of_goods_sold", dec!(400)),
+            ],
+            &mapping,
+        );
+
+        assert_eq!(report.revenue.total, dec!(1000));
+        assert_eq!(report.other_income_expense.total, dec!(400));
+        assert!(
+            report
+                .revenue
+                .subsections
+                .iter()
+                .all(|s| s.accounts.is_empty())
+        );
+    }
+
+    #[test]
+    fn test_round_totals_to_currency_places() {
+        let mut report = ReportService::generate_income_statement(vec![
+            subtype_account("revenue", "operating_revenue", dec!(1000.5)),
+            subtype_account("expense", "cost_of_goods_sold", dec!(400.2500)),
+        ]);
+
+        report.round_totals(0);
+
+        // Half to even: 1000.5 -> 1000, 600.25 -> 600
+        assert_eq!(report.revenue.total, dec!(1000));
+        assert_eq!(report.revenue.subsections[0].total, dec!(1000));
+        assert_eq!(report.cost_of_goods_sold.total, dec!(400));
+        assert_eq!(report.gross_profit, dec!(600));
+        assert_eq!(report.net_income, dec!(600));
+        // Accounts keep the stored precision
+        assert_eq!(report.revenue.accounts[0].balance, dec!(1000.5));
+    }
+
+    #[test]
+    fn test_period_movement_uses_normal_side() {
+        let expense = PeriodMovement::new(true, (dec!(300), dec!(100)), (dec!(500), dec!(50)));
+        assert_eq!(expense.opening_balance, dec!(200));
+        assert_eq!(expense.closing_balance, dec!(650));
+        assert_eq!(expense.net_change(), dec!(450));
+
+        let revenue = PeriodMovement::new(false, (dec!(0), dec!(1000)), (dec!(100), dec!(400)));
+        assert_eq!(revenue.opening_balance, dec!(1000));
+        assert_eq!(revenue.closing_balance, dec!(1300));
+    }
+
+    #[test]
+    fn test_movement_change_percent() {
+        let last_month = PeriodMovement::new(true, (dec!(0), dec!(0)), (dec!(1000), dec!(0)));
+        let this_month = PeriodMovement::new(true, (dec!(1000), dec!(0)), (dec!(1400), dec!(0)));
+
+        let change = MovementChange::between(&last_month, &this_month);
+        assert_eq!(change.absolute, dec!(400));
+        assert_eq!(change.percent, Some(dec!(40)));
+
+        // A fall from a negative movement is measured against its size
+        let refunds = PeriodMovement::new(true, (dec!(0), dec!(0)), (dec!(0), dec!(300)));
+        let change = MovementChange::between(&refunds, &last_month);
+        assert_eq!(change.absolute, dec!(1300));
+        assert_eq!(change.percent, Some(dec!(433.33)));
+    }
+
+    #[test]
+    fn test_movement_change_without_compare_activity() {
+        let quiet = PeriodMovement::new(true, (dec!(500), dec!(0)), (dec!(0), dec!(0)));
+        let busy = PeriodMovement::new(true, (dec!(500), dec!(0)), (dec!(250), dec!(0)));
+
+        let change = MovementChange::between(&quiet, &busy);
+        assert_eq!(change.absolute, dec!(250));
+        assert_eq!(change.percent, None);
+        assert_eq!(MovementChange::between(&quiet, &quiet).percent, None);
+    }
+
+    fn matrix_row(
+        code: &str,
+        name: &str,
+        account_type: &str,
+        balances: &[Decimal],
+    ) -> BalanceMatrixRow {
+        BalanceMatrixRow {
+            account_id: Uuid::new_v4(),
+            code: code.to_string(),
+            name: name.to_string(),
+            account_type: account_type.to_string(),
+            currency: "USD".to_string(),
+            balances: balances.to_vec(),
+        }
+    }
+
+    #[test]
+    fn test_balance_matrix_totals_by_type() {
+        let dates = vec![
+            NaiveDate::from_ymd_opt(2025, 1, 31).unwrap(),
+            NaiveDate::from_ymd_opt(2025, 2, 28).unwrap(),
+        ];
+        let matrix = BalanceMatrix::new(
+            dates,
+            vec![
+                matrix_row("1000", "Cash", "asset", &[dec!(100), dec!(250)]),
+                matrix_row("5000", "Rent", "expense", &[dec!(40), dec!(80)]),
+                matrix_row("1100", "Bank", "asset", &[dec!(-10), dec!(0)]),
+            ],
+        );
+
+        let totals: Vec<_> = matrix
+            .totals
+            .iter()
+            .map(|t| (t.account_type.as_str(), t.balances.clone()))
+            .collect();
+        assert_eq!(
+            totals,
+            vec![
+                ("asset", vec![dec!(90), dec!(250)]),
+                ("expense", vec![dec!(40), dec!(80)])
+            ]
+        );
+    }
+
+    #[test]
+    fn test_balance_matrix_csv() {
+        let matrix = BalanceMatrix::new(
+            vec![NaiveDate::from_ymd_opt(2025, 1, 31).unwrap()],
+            vec![
+                matrix_row("1000", "Cash, petty", "asset", &[dec!(100.50)]),
+                matrix_row("2000", "=HYPERLINK(\"x\")", "liability", &[dec!(20)]),
+            ],
+        );
+
+        assert_eq!(
+            matrix.to_csv(ExportStyle::Plain),
+            "code,name,type,currency,2025-01-31\n\
+             1000,\"Cash, petty\",asset,USD,100.50\n\
+             2000,\"'=HYPERLINK(\"\"x\"\")\",liability,USD,20\n\
+             ,Total asset,asset,,100.50\n\
+             ,Total liability,liability,,20\n"
+        );
+    }
+
+    #[test]
+    fn test_balance_matrix_csv_formatted() {
+        let matrix = BalanceMatrix::new(
+            vec![NaiveDate::from_ymd_opt(2025, 1, 31).unwrap()],
+            vec![matrix_row("1000", "Cash", "asset", &[dec!(-1234.5)])],
+        );
+        let style = ExportStyle::Localized {
+            locale: Locale::EnUs,
+            decimal_places: 2,
+        };
+
+        // Group separators are quoted; negative amounts keep their sign
+        assert_eq!(
+            matrix.to_csv(style),
+            "code,name,type,currency,01/31/2025\n\
+             1000,Cash,asset,USD,\"-1,234.50\"\n\
+             ,Total asset,asset,,\"-1,234.50\"\n"
+        );
+    }
+
+    fn definition(dataset: ReportDataset) -> ReportDefinition {
+        ReportDefinition {
+            name: "Spend by account".to_string(),
+            dataset,
+            columns: vec![],
+            filters: ReportFilters::default(),
+            group_by: vec![],
+            aggregate: None,
+        }
+    }
+
+    #[test]
+    fn test_custom_report_plan_with_every_filter() {
+        let value_id = Uuid::new_v4();
+        let plan = ReportDefinition {
+            columns: vec!["account_code".to_string(), "period_name".to_string()],
+            filters: ReportFilters {
+                date_from: NaiveDate::from_ymd_opt(2025, 1, 1),
+                date_to: NaiveDate::from_ymd_opt(2025, 3, 31),
+                account_types: vec!["expense".to_string()],
+                dimension_value_ids: vec![value_id],
+            },
+            group_by: vec!["account_code".to_string(), "period_name".to_string()],
+            aggregate: Some(ReportAggregate {
+                function: AggregateFunction::Sum,
+                field: Some("amount".to_string()),
+            }),
+            ..definition(ReportDataset::LedgerEntries)
+        }
+        .validate()
+        .unwrap();
+
+        assert!(plan.grouped);
+        assert_eq!(
+            plan.fields,
+            vec![ReportField::AccountCode, ReportField::PeriodName]
+        );
+        assert_eq!(
+            plan.aggregate,
+            Some((AggregateFunction::Sum, Some(ReportField::Amount)))
+        );
+        assert_eq!(plan.account_types, vec!["expense".to_string()]);
+        assert_eq!(plan.dimension_value_ids, vec![value_id]);
+        assert_eq!(
+            plan.column_names(),
+            vec!["account_code", "period_name", "sum_amount"]
+        );
+    }
+
+    #[test]
+    fn test_custom_report_rejects_unknown_and_misplaced_fields() {
+        let errors = ReportDefinition {
+            columns: vec![
+                "account_code".to_string(),
+                "account_code\" FROM users --".to_string(),
+                "budgeted".to_string(),
+            ],
+            filters: ReportFilters {
+                account_types: vec!["assets".to_string()],
+                ..ReportFilters::default()
+            },
+            group_by: vec!["debit".to_string()],
+            ..definition(ReportDataset::LedgerEntries)
+        }
+        .validate()
+        .unwrap_err();
+
+        let fields: Vec<_> = errors.iter().map(|e| e.field.as_str()).collect();
+        assert_eq!(
+            fields,
+            vec![
+                "columns[1]",
+                "columns[2]",
+                "group_by[0]",
+                "aggregate",
+                "filters.account_types[0]"
+            ]
+        );
+        assert!(errors[0].message.contains("unknown field"));
+    }
+
+    #[test]
+    fn test_custom_report_aggregate_rules() {
+        let count = ReportDefinition {
+            aggregate: Some(ReportAggregate {
+                function: AggregateFunction::Count,
+                field: None,
+            }),
+            ..definition(ReportDataset::BudgetVsActual)
+        };
+        let plan = count.validate().unwrap();
+        assert!(plan.fields.is_empty());
+        assert_eq!(plan.column_names(), vec!["count"]);
+
+        let errors = ReportDefinition {
+            name: " ".to_string(),
+            columns: vec!["account_name".to_string()],
+            group_by: vec![
+                "account_code".to_string(),
+                "period_name".to_string(),
+                "budget_name".to_string(),
+            ],
+            aggregate: Some(ReportAggregate {
+                function: AggregateFunction::Avg,
+                field: Some("budget_name".to_string()),
+            }),
+            filters: ReportFilters {
+                date_from: NaiveDate::from_ymd_opt(2025, 2, 1),
+                date_to: NaiveDate::from_ymd_opt(2025, 1, 1),
+                ..ReportFilters::default()
+            },
+            ..definition(ReportDataset::BudgetVsActual)
+        }
+        .validate()
+        .unwrap_err();
+        let fields: Vec<_> = errors.iter().map(|e| e.field.as_str()).collect();
+        assert_eq!(
+            fields,
+            vec![
+                "name",
+                "group_by",
+                "aggregate.field",
+                "columns[0]",
+                "filters.date_to"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_custom_report_csv() {
+        let result = CustomReportResult {
+            columns: vec![
+                "account_name".to_string(),
+                "transaction_date".to_string(),
+                "debit".to_string(),
+            ],
+            rows: vec![
+                vec![
+                    ReportValue::Text("Travel, local".to_string()),
+                    ReportValue::Date(NaiveDate::from_ymd_opt(2025, 1, 31).unwrap()),
+                    ReportValue::Number(dec!(12.50)),
+                ],
+                vec![
+                    ReportValue::Text("=SUM(A1)".to_string()),
+                    ReportValue::Null,
+                    ReportValue::Number(dec!(0)),
+                ],
+            ],
+            truncated: false,
+        };
+
+        assert_eq!(
+            result.to_csv(ExportStyle::Plain),
+            "account_name,transaction_date,debit\n\
+             \"Travel, local\",2025-01-31,12.50\n\
+             '=SUM(A1),,0\n"
+        );
+        let style = ExportStyle::Localized {
+            locale: Locale::DeDe,
+            decimal_places: 2,
+        };
+        assert_eq!(
+            result.to_csv(style),
+            "account_name,transaction_date,debit\n\
+             \"Travel, local\",31.01.2025,\"12,50\"\n\
+             '=SUM(A1),,\"0,00\"\n"
+        );
+    }
 }
@@ -5,6 +5,8 @@ use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use crate::currency::CurrencyService;
+
 /// Account balance for reports.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AccountBalance {
@@ -41,6 +43,18 @@ pub struct TrialBalanceReport {
     pub totals: TrialBalanceTotals,
 }
 
+impl TrialBalanceReport {
+    /// Rounds the totals to the report currency's decimal places.
+    ///
+    /// Account balances keep the stored precision; `is_balanced` is left as
+    /// computed from them.
+    pub fn round_totals(&mut self, decimal_places: u32) {
+        let totals = &mut self.totals;
+        totals.total_debit = CurrencyService::round(totals.total_debit, decimal_places);
+        totals.total_credit = CurrencyService::round(totals.total_credit, decimal_places);
+    }
+}
+
 /// Trial balance totals.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TrialBalanceTotals {
@@ -59,14 +73,27 @@ pub struct BalanceSheetSection {
     pub total: Decimal,
     /// Accounts in this section.
     pub accounts: Vec<AccountBalance>,
-    /// Subsections (current assets, fixed assets, etc.).
-    pub subsections: Vec<BalanceSheetSubsection>,
+    /// Mapped sections (current assets, fixed assets, etc.) in sort order.
+    pub subsections: Vec<StatementSubsection>,
 }
 
-/// Balance sheet subsection.
+impl BalanceSheetSection {
+    fn round_totals(&mut self, decimal_places: u32) {
+        self.total = CurrencyService::round(self.total, decimal_places);
+        for subsection in &mut self.subsections {
+            subsection.total = CurrencyService::round(subsection.total, decimal_places);
+        }
+    }
+}
+
+/// One mapped section of a statement section.
+///
+/// Accounts without a mapped subtype count in the parent section only.
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct BalanceSheetSubsection {
-    /// Subsection name.
+pub struct StatementSubsection {
+    /// Section key from the statement mapping.
+    pub key: String,
+    /// Section name from the statement mapping.
     pub name: String,
     /// Subsection total.
     pub total: Decimal,
@@ -101,6 +128,27 @@ pub struct BalanceSheetReport {
     pub is_balanced: bool,
 }
 
+impl BalanceSheetReport {
+    /// Rounds section and statement totals to the report currency's decimal
+    /// places.
+    ///
+    /// Account balances keep the stored precision; `is_balanced` is left as
+    /// computed from them.
+    pub fn round_totals(&mut self, decimal_places: u32) {
+        for section in [&mut self.assets, &mut self.liabilities, &mut self.equity] {
+            section.round_totals(decimal_places);
+        }
+        for total in [
+            &mut self.total_assets,
+            &mut self.total_liabilities,
+            &mut self.total_equity,
+            &mut self.liabilities_and_equity,
+        ] {
+            *total = CurrencyService::round(*total, decimal_places);
+        }
+    }
+}
+
 /// Income statement section.
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct IncomeStatementSection {
@@ -108,6 +156,17 @@ pub struct IncomeStatementSection {
     pub total: Decimal,
     /// Accounts in this section.
     pub accounts: Vec<AccountBalance>,
+    /// Mapped sections in sort order.
+    pub subsections: Vec<StatementSubsection>,
+}
+
+impl IncomeStatementSection {
+    fn round_totals(&mut self, decimal_places: u32) {
+        self.total = CurrencyService::round(self.total, decimal_places);
+        for subsection in &mut self.subsections {
+            subsection.total = CurrencyService::round(subsection.total, decimal_places);
+        }
+    }
 }
 
 /// Income statement report.
@@ -137,6 +196,28 @@ pub struct IncomeStatementReport {
     pub net_income: Decimal,
 }
 
+impl IncomeStatementReport {
+    /// Rounds section totals and profit lines to the report currency's
+    /// decimal places. Account balances keep the stored precision.
+    pub fn round_totals(&mut self, decimal_places: u32) {
+        for section in [
+            &mut self.revenue,
+            &mut self.cost_of_goods_sold,
+            &mut self.operating_expenses,
+            &mut self.other_income_expense,
+        ] {
+            section.round_totals(decimal_places);
+        }
+        for total in [
+            &mut self.gross_profit,
+            &mut self.operating_income,
+            &mut self.net_income,
+        ] {
+            *total = CurrencyService::round(*total, decimal_places);
+        }
+    }
+}
+
 /// Account ledger entry.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AccountLedgerEntry {
@@ -204,3 +285,138 @@ pub struct DimensionalReport {
     /// Grand total.
     pub grand_total: Decimal,
 }
+
+/// A dimension value reported as its own income statement column.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReportDimensionValue {
+    /// Dimension value ID.
+    pub id: Uuid,
+    /// Dimension value code.
+    pub code: String,
+    /// Dimension value name.
+    pub name: String,
+}
+
+/// Posted totals on one account for entries carrying the same values of the
+/// reported dimension type.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DimensionTaggedTotals {
+    /// Account ID.
+    pub account_id: Uuid,
+    /// Values of the reported type on the entries; empty when untagged.
+    pub dimension_value_ids: Vec<Uuid>,
+    /// Total debit amount.
+    pub total_debit: Decimal,
+    /// Total credit amount.
+    pub total_credit: Decimal,
+}
+
+/// Percentage shares for spreading unallocated amounts over dimension values.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DimensionAllocation {
+    /// Dimension value ID and its percentage; percentages sum to 100.
+    pub shares: Vec<(Uuid, Decimal)>,
+}
+
+/// One column of an income statement by dimension.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DimensionIncomeStatementColumn {
+    /// Dimension value ID, `None` for the unallocated column.
+    pub dimension_value_id: Option<Uuid>,
+    /// Dimension value code, `unallocated` for the unallocated column.
+    pub code: String,
+    /// Dimension value name.
+    pub name: String,
+    /// Income statement for this column.
+    pub statement: IncomeStatementReport,
+}
+
+/// Income statement with one column per dimension value.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DimensionIncomeStatementReport {
+    /// Report type identifier.
+    pub report_type: String,
+    /// Columns in dimension value order, unallocated last.
+    pub columns: Vec<DimensionIncomeStatementColumn>,
+    /// Whether unallocated amounts were spread over the values.
+    pub allocated: bool,
+    /// Income statement across all columns.
+    pub total: IncomeStatementReport,
+}
+
+impl DimensionIncomeStatementReport {
+    /// Rounds the totals of every column and of the overall statement.
+    ///
+    /// See [`IncomeStatementReport::round_totals`].
+    pub fn round_totals(&mut self, decimal_places: u32) {
+        for column in &mut self.columns {
+            column.statement.round_totals(decimal_places);
+        }
+        self.total.round_totals(decimal_places);
+    }
+}
+
+/// An account's activity over one fiscal period, in the functional currency.
+///
+/// Balances are on the account's normal side, as in the other reports.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PeriodMovement {
+    /// Balance before the period starts.
+    pub opening_balance: Decimal,
+    /// Debits posted in the period.
+    pub total_debit: Decimal,
+    /// Credits posted in the period.
+    pub total_credit: Decimal,
+    /// Balance at the end of the period.
+    pub closing_balance: Decimal,
+}
+
+impl PeriodMovement {
+    /// Builds the movement from the posted `(debit, credit)` totals before
+    /// and within the period.
+    #[must_use]
+    pub fn new(debit_normal: bool, before: (Decimal, Decimal), within: (Decimal, Decimal)) -> Self {
+        let net = |(debit, credit): (Decimal, Decimal)| {
+            if debit_normal {
+                debit - credit
+            } else {
+                credit - debit
+            }
+        };
+        let opening_balance = net(before);
+        Self {
+            opening_balance,
+            total_debit: within.0,
+            total_credit: within.1,
+            closing_balance: opening_balance + net(within),
+        }
+    }
+
+    /// Net change over the period on the account's normal side.
+    #[must_use]
+    pub fn net_change(&self) -> Decimal {
+        self.closing_balance - self.opening_balance
+    }
+}
+
+/// How a period's net movement compares with another period's.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MovementChange {
+    /// Current net movement less the comparison period's.
+    pub absolute: Decimal,
+    /// `absolute` as a percentage of the comparison period's net movement,
+    /// rounded to two places; `None` when that movement was zero.
+    pub percent: Option<Decimal>,
+}
+
+impl MovementChange {
+    /// Compares the current period's movement with the comparison period's.
+    #[must_use]
+    pub fn between(compare: &PeriodMovement, current: &PeriodMovement) -> Self {
+        let base = compare.net_change();
+        let absolute = current.net_change() - base;
+        let percent =
+            (!base.is_zero()).then(|| (absolute * Decimal::ONE_HUNDRED / base.abs()).round_dp(2));
+        Self { absolute, percent }
+    }
+}
@@ -0,0 +1,604 @@
+//! Printable journal vouchers.
+//!
+//! A voucher is the paper form of one transaction: its header, the entries
+//! with their accounts and dimension tags, the total in figures and in
+//! words, and who submitted, approved and posted it. Amounts are shown in
+//! the functional currency, formatted for the organization's locale, and
+//! the total in words follows the locale's language. Voided transactions
+//! carry a VOID watermark.
+
+use std::fmt::Write as _;
+
+use chrono::{DateTime, NaiveDate, Utc};
+use rust_decimal::Decimal;
+use serde::Serialize;
+use uuid::Uuid;
+use zeltra_shared::format::{Locale, format_amount, format_date};
+use zeltra_shared::words::{FractionStyle, Language, amount_in_words};
+
+use crate::workflow::TransactionStatus;
+
+use super::pdf::{Align, Font, MARGIN, PAGE_WIDTH, PdfDocument, fit, wrap};
+
+/// Watermark printed across voided vouchers.
+pub const VOID_WATERMARK: &str = "VOID";
+
+/// One step of a transaction's approval trail.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum VoucherStep {
+    /// Submitted for approval.
+    Submitted,
+    /// Approved.
+    Approved,
+    /// Posted to the ledger.
+    Posted,
+    /// Voided.
+    Voided,
+}
+
+impl VoucherStep {
+    /// Label printed on the voucher.
+    #[must_use]
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::Submitted => "Submitted",
+            Self::Approved => "Approved",
+            Self::Posted => "Posted",
+            Self::Voided => "Voided",
+        }
+    }
+}
+
+/// Who took a step of the approval trail, and when.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct VoucherSignature {
+    /// The step taken.
+    pub step: VoucherStep,
+    /// Full name of the user who took it.
+    pub name: String,
+    /// When it was taken.
+    pub at: DateTime<Utc>,
+}
+
+/// A dimension value an entry is tagged with.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct VoucherTag {
+    /// Dimension type name, such as "Department".
+    pub dimension: String,
+    /// Dimension value name, such as "Sales".
+    pub value: String,
+}
+
+/// One ledger entry of a voucher.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct VoucherLine {
+    /// Account code.
+    pub account_code: String,
+    /// Account name.
+    pub account_name: String,
+    /// Entry memo.
+    pub memo: Option<String>,
+    /// Dimension tags.
+    pub tags: Vec<VoucherTag>,
+    /// Debit in the functional currency.
+    pub debit: Decimal,
+    /// Credit in the functional currency.
+    pub credit: Decimal,
+}
+
+/// A transaction laid out for printing.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Voucher {
+    /// Organization name printed in the header.
+    pub organization_name: String,
+    /// Transaction ID.
+    pub transaction_id: Uuid,
+    /// Reference number, if the transaction has one.
+    pub reference_number: Option<String>,
+    /// Transaction date.
+    pub transaction_date: NaiveDate,
+    /// Transaction type.
+    pub transaction_type: String,
+    /// Transaction status.
+    pub status: TransactionStatus,
+    /// Description.
+    pub description: String,
+    /// Transaction memo.
+    pub memo: Option<String>,
+    /// Reason given when the transaction was voided.
+    pub void_reason: Option<String>,
+    /// Functional currency code.
+    pub currency: String,
+    /// Decimal places of the functional currency.
+    pub decimal_places: u32,
+    /// Locale the amounts and dates are formatted for.
+    pub locale: Locale,
+    /// Ledger entries, debits first.
+    pub lines: Vec<VoucherLine>,
+    /// Approval trail in the order the steps were taken.
+    pub trail: Vec<VoucherSignature>,
+}
+
+impl Voucher {
+    /// Reference printed on the voucher: the reference number, or the
+    /// transaction ID when there is none.
+    #[must_use]
+    pub fn reference(&self) -> String {
+        self.reference_number
+            .clone()
+            .unwrap_or_else(|| self.transaction_id.to_string())
+    }
+
+    /// Whether the transaction has been voided.
+    #[must_use]
+    pub fn is_void(&self) -> bool {
+        self.status == TransactionStatus::Voided
+    }
+
+    /// Sum of the debits.
+    #[must_use]
+    pub fn total_debit(&self) -> Decimal {
+        self.lines.iter().map(|l| l.debit).sum()
+    }
+
+    /// Sum of the credits.
+    #[must_use]
+    pub fn total_credit(&self) -> Decimal {
+        self.lines.iter().map(|l| l.credit).sum()
+    }
+
+    /// The total written out in the locale's language, starting with a
+    /// capital letter.
+    #[must_use]
+    pub fn total_in_words(&self) -> String {
+        let words = amount_in_words(
+            self.total_debit(),
+            &self.currency,
+            self.decimal_places,
+            Language::from(self.locale),
+            FractionStyle::Ratio,
+        );
+        let mut chars = words.chars();
+        chars.next().map_or_else(String::new, |first| {
+            first.to_uppercase().chain(chars).collect()
+        })
+    }
+
+    fn amount(&self, amount: Decimal) -> String {
+        if amount.is_zero() {
+            String::new()
+        } else {
+            format_amount(amount, self.decimal_places, self.locale)
+        }
+    }
+
+    fn timestamp(&self, at: DateTime<Utc>) -> String {
+        format!(
+            "{} {}",
+            format_date(at.date_naive(), self.locale),
+            at.format("%H:%M UTC")
+        )
+    }
+
+    /// Renders the voucher as a standalone HTML page.
+    #[must_use]
+    pub fn to_html(&self) -> String {
+        let mut html = String::from("<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n");
+        let _ = writeln!(
+            html,
+            "<title>Journal voucher {}</title>",
+            escape(&self.reference())
+        );
+        html.push_str(
+            "<style>\
+             body{font-family:Helvetica,Arial,sans-serif;font-size:12px;margin:32px;position:relative}\
+             table{border-collapse:collapse;width:100%;margin:12px 0}\
+             th,td{border-bottom:1px solid #ccc;padding:4px;text-align:left;vertical-align:top}\
+             .num{text-align:right;white-space:nowrap}\
+             .memo,.tags{color:#555;font-size:11px}\
+             .void{position:fixed;top:35%;left:0;right:0;text-align:center;font-size:160px;\
+             font-weight:bold;color:rgba(204,0,0,0.15);transform:rotate(-45deg);pointer-events:none}\
+             </style>\n</head>\n<body>\n",
+        );
+        if self.is_void() {
+            let _ = writeln!(html, "<div class=\"void\">{VOID_WATERMARK}</div>");
+        }
+
+        let _ = writeln!(html, "<h1>{}</h1>", escape(&self.organization_name));
+        html.push_str("<h2>Journal Voucher</h2>\n<table class=\"header\">\n");
+        for (label, value) in self.header_fields() {
+            let _ = writeln!(html, "<tr><th>{label}</th><td>{}</td></tr>", escape(&value));
+        }
+        html.push_str("</table>\n");
+
+        html.push_str(
+            "<table class=\"entries\">\n<tr><th>Code</th><th>Account</th><th>Dimensions</th>\
+             <th class=\"num\">Debit</th><th class=\"num\">Credit</th></tr>\n",
+        );
+        for line in &self.lines {
+            let _ = write!(
+                html,
+                "<tr><td>{}</td><td>{}",
+                escape(&line.account_code),
+                escape(&line.account_name)
+            );
+            if let Some(memo) = &line.memo {
+                let _ = write!(html, "<div class=\"memo\">{}</div>", escape(memo));
+            }
+            html.push_str("</td><td class=\"tags\">");
+            let tags: Vec<String> = line.tags.iter().map(|t| escape(&tag(t))).collect();
+            html.push_str(&tags.join("<br>"));
+            let _ = writeln!(
+                html,
+                "</td><td class=\"num\">{}</td><td class=\"num\">{}</td></tr>",
+                self.amount(line.debit),
+                self.amount(line.credit)
+            );
+        }
+        let _ = writeln!(
+            html,
+            "<tr><th colspan=\"3\">Total ({})</th><th class=\"num\">{}</th><th class=\"num\">{}</th></tr>\n</table>",
+            escape(&self.currency),
+            format_amount(self.total_debit(), self.decimal_places, self.locale),
+            format_amount(self.total_credit(), self.decimal_places, self.locale)
+        );
+        let _ = writeln!(
+            html,
+            "<p class=\"words\"><strong>Amount in words:</strong> {}</p>",
+            escape(&self.total_in_words())
+        );
+
+        html.push_str("<table class=\"trail\">\n<tr><th>Step</th><th>By</th><th>At</th></tr>\n");
+        for signature in &self.trail {
+            let _ = writeln!(
+                html,
+                "<tr><td>{}</td><td>{}</td><td>{}</td></tr>",
+                signature.step.label(),
+                escape(&signature.name),
+                self.timestamp(signature.at)
+            );
+        }
+        html.push_str("</table>\n</body>\n</html>\n");
+        html
+    }
+
+    /// Renders the voucher as a PDF.
+    #[must_use]
+    #[allow(clippy::too_many_lines)]
+    pub fn to_pdf(&self) -> Vec<u8> {
+        const RIGHT: i32 = PAGE_WIDTH - MARGIN;
+        const ACCOUNT_X: i32 = MARGIN + 62;
+        const ACCOUNT_WIDTH: i32 = 170;
+        const TAGS_X: i32 = ACCOUNT_X + ACCOUNT_WIDTH + 8;
+        const TAGS_WIDTH: i32 = 120;
+        const DEBIT_RIGHT: i32 = RIGHT - 84;
+        const LINE: i32 = 12;
+
+        let mut pdf = PdfDocument::new();
+        if self.is_void() {
+            pdf = pdf.with_watermark(VOID_WATERMARK);
+        }
+
+        pdf.advance(16);
+        pdf.text(
+            MARGIN,
+            &fit(&self.organization_name, RIGHT - MARGIN, 16),
+            16,
+            Font::Bold,
+            Align::Left,
+        );
+        pdf.advance(20);
+        pdf.text(MARGIN, "Journal Voucher", 12, Font::Bold, Align::Left);
+        pdf.advance(8);
+        for (label, value) in self.header_fields() {
+            for (i, text) in wrap(&value, RIGHT - ACCOUNT_X, 10).iter().enumerate() {
+                pdf.advance(14);
+                if i == 0 {
+                    pdf.text(MARGIN, label, 10, Font::Bold, Align::Left);
+                }
+                pdf.text(ACCOUNT_X, text, 10, Font::Regular, Align::Left);
+            }
+        }
+
+        let heading = |pdf: &mut PdfDocument| {
+            pdf.advance(22);
+            pdf.text(MARGIN, "Code", 9, Font::Bold, Align::Left);
+            pdf.text(ACCOUNT_X, "Account", 9, Font::Bold, Align::Left);
+            pdf.text(TAGS_X, "Dimensions", 9, Font::Bold, Align::Left);
+            pdf.text(DEBIT_RIGHT, "Debit", 9, Font::Bold, Align::Right);
+            pdf.text(RIGHT, "Credit", 9, Font::Bold, Align::Right);
+            pdf.advance(4);
+            pdf.rule(MARGIN, RIGHT);
+        };
+        heading(&mut pdf);
+
+        for line in &self.lines {
+            let mut account = wrap(&line.account_name, ACCOUNT_WIDTH, 9);
+            let memo = line
+                .memo
+                .as_ref()
+                .map(|m| wrap(m, ACCOUNT_WIDTH, 8))
+                .unwrap_or_default();
+            let tags: Vec<String> = line
+                .tags
+                .iter()
+                .map(|t| fit(&tag(t), TAGS_WIDTH, 8))
+                .collect();
+            let rows = (account.len() + memo.len()).max(tags.len());
+            let height = i32::try_from(rows).unwrap_or(i32::MAX).saturating_mul(LINE);
+            if pdf.ensure_space(LINE + height) {
+                heading(&mut pdf);
+            }
+
+            account.extend(memo.iter().cloned());
+            for row in 0..rows {
+                pdf.advance(LINE);
+                if row == 0 {
+                    pdf.text(
+                        MARGIN,
+                        &fit(&line.account_code, ACCOUNT_X - MARGIN - 4, 9),
+                        9,
+                        Font::Regular,
+                        Align::Left,
+                    );
+                    pdf.text(
+                        DEBIT_RIGHT,
+                        &self.amount(line.debit),
+                        9,
+                        Font::Regular,
+                        Align::Right,
+                    );
+                    pdf.text(
+                        RIGHT,
+                        &self.amount(line.credit),
+                        9,
+                        Font::Regular,
+                        Align::Right,
+                    );
+                }
+                if let Some(text) = account.get(row) {
+                    let size = if row < account.len() - memo.len() {
+                        9
+                    } else {
+                        8
+                    };
+                    pdf.text(ACCOUNT_X, text, size, Font::Regular, Align::Left);
+                }
+                if let Some(text) = tags.get(row) {
+                    pdf.text(TAGS_X, text, 8, Font::Regular, Align::Left);
+                }
+            }
+            pdf.advance(4);
+            pdf.rule(MARGIN, RIGHT);
+        }
+
+        pdf.advance(LINE + 2);
+        pdf.text(
+            MARGIN,
+            &format!("Total ({})", self.currency),
+            9,
+            Font::Bold,
+            Align::Left,
+        );
+        pdf.text(
+            DEBIT_RIGHT,
+            &format_amount(self.total_debit(), self.decimal_places, self.locale),
+            9,
+            Font::Bold,
+            Align::Right,
+        );
+        pdf.text(
+            RIGHT,
+            &format_amount(self.total_credit(), self.decimal_places, self.locale),
+            9,
+            Font::Bold,
+            Align::Right,
+        );
+
+        pdf.advance(22);
+        pdf.text(MARGIN, "Amount in words", 10, Font::Bold, Align::Left);
+        for text in wrap(&self.total_in_words(), RIGHT - MARGIN, 10) {
+            pdf.advance(14);
+            pdf.text(MARGIN, &text, 10, Font::Regular, Align::Left);
+        }
+
+        pdf.ensure_space(36 + LINE * i32::try_from(self.trail.len()).unwrap_or(0));
+        pdf.advance(26);
+        pdf.text(MARGIN, "Step", 9, Font::Bold, Align::Left);
+        pdf.text(ACCOUNT_X, "By", 9, Font::Bold, Align::Left);
+        pdf.text(TAGS_X + 40, "At", 9, Font::Bold, Align::Left);
+        pdf.advance(4);
+        pdf.rule(MARGIN, RIGHT);
+        for signature in &self.trail {
+            pdf.advance(LINE + 2);
+            pdf.text(
+                MARGIN,
+                signature.step.label(),
+                9,
+                Font::Regular,
+                Align::Left,
+            );
+            pdf.text(
+                ACCOUNT_X,
+                &fit(&signature.name, TAGS_X + 40 - ACCOUNT_X - 8, 9),
+                9,
+                Font::Regular,
+                Align::Left,
+            );
+            pdf.text(
+                TAGS_X + 40,
+                &self.timestamp(signature.at),
+                9,
+                Font::Regular,
+                Align::Left,
+            );
+        }
+
+        pdf.finish()
+    }
+
+    /// Label and value pairs of the voucher header.
+    fn header_fields(&self) -> Vec<(&'static str, String)> {
+        let mut fields = vec![
+            ("Reference", self.reference()),
+            ("Date", format_date(self.transaction_date, self.locale)),
+            ("Type", self.transaction_type.clone()),
+            ("Status", self.status.as_str().to_string()),
+            ("Description", self.description.clone()),
+        ];
+        if let Some(memo) = &self.memo {
+            fields.push(("Memo", memo.clone()));
+        }
+        if let Some(reason) = &self.void_reason {
+            fields.push(("Void reason", reason.clone()));
+        }
+        fields
+    }
+}
+
+fn tag(tag: &VoucherTag) -> String {
+    format!("{}: {}", tag.dimension, tag.value)
+}
+
+/// Escapes text for HTML element content and attribute values.
+fn escape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+    use rust_decimal_macros::dec;
+
+    use super::*;
+
+    fn voucher(status: TransactionStatus, locale: Locale) -> Voucher {
+        let line = |code: &str, name: &str, debit, credit| VoucherLine {
+            account_code: code.to_string(),
+            account_name: name.to_string(),
+            memo: None,
+            tags: vec![],
+            debit,
+            credit,
+        };
+        let mut expense = line("6100", "Travel <domestic>", dec!(1234.56), Decimal::ZERO);
+        expense.memo = Some("Jakarta trip".to_string());
+        expense.tags = vec![VoucherTag {
+            dimension: "Department".to_string(),
+            value: "Sales & Marketing".to_string(),
+        }];
+        Voucher {
+            organization_name: "Acme & Co".to_string(),
+            transaction_id: Uuid::nil(),
+            reference_number: Some("JV-2025-0042".to_string()),
+            transaction_date: NaiveDate::from_ymd_opt(2025, 3, 15).unwrap(),
+            transaction_type: "expense".to_string(),
+            status,
+            description: "Travel reimbursement".to_string(),
+            memo: None,
+            void_reason: None,
+            currency: "USD".to_string(),
+            decimal_places: 2,
+            locale,
+            lines: vec![expense, line("1000", "Cash", Decimal::ZERO, dec!(1234.56))],
+            trail: vec![
+                VoucherSignature {
+                    step: VoucherStep::Submitted,
+                    name: "Ana Putri".to_string(),
+                    at: Utc.with_ymd_and_hms(2025, 3, 15, 9, 0, 0).unwrap(),
+                },
+                VoucherSignature {
+                    step: VoucherStep::Approved,
+                    name: "Budi".to_string(),
+                    at: Utc.with_ymd_and_hms(2025, 3, 15, 10, 30, 0).unwrap(),
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn test_total_in_words_follows_locale() {
+        let english = voucher(TransactionStatus::Posted, Locale::EnUs);
+        assert_eq!(
+            english.total_in_words(),
+            "One thousand two hundred thirty-four and 56/100 US dollars"
+        );
+        let indonesian = voucher(TransactionStatus::Posted, Locale::IdId);
+        assert_eq!(
+            indonesian.total_in_words(),
+            "Seribu dua ratus tiga puluh empat dan 56/100 dolar Amerika Serikat"
+        );
+    }
+
+    #[test]
+    fn test_html_escapes_and_lays_out_voucher() {
+        let html = voucher(TransactionStatus::Posted, Locale::EnUs).to_html();
+        assert!(html.contains("<h1>Acme &amp; Co</h1>"));
+        assert!(html.contains("<td>JV-2025-0042</td>"));
+        assert!(html.contains("<td>03/15/2025</td>"));
+        assert!(html.contains("Travel &lt;domestic&gt;"));
+        assert!(html.contains("<div class=\"memo\">Jakarta trip</div>"));
+        assert!(html.contains("Department: Sales &amp; Marketing"));
+        assert!(html.contains("<td class=\"num\">1,234.56</td><td class=\"num\"></td>"));
+        assert!(html.contains("and 56/100 US dollars"));
+        assert!(html.contains("<td>Approved</td><td>Budi</td><td>03/15/2025 10:30 UTC</td>"));
+        assert!(!html.contains("class=\"void\""));
+
+        let html = voucher(TransactionStatus::Posted, Locale::IdId).to_html();
+        assert!(html.contains("<td class=\"num\">1.234,56</td>"));
+        assert!(html.contains("<td>15/03/2025</td>"));
+    }
+
+    #[test]
+    fn test_voided_voucher_is_watermarked() {
+        let mut voided = voucher(TransactionStatus::Voided, Locale::EnUs);
+        voided.void_reason = Some("Duplicate entry".to_string());
+
+        let html = voided.to_html();
+        assert!(html.contains("<div class=\"void\">VOID</div>"));
+        assert!(html.contains("<th>Void reason</th><td>Duplicate entry</td>"));
+
+        let pdf = String::from_utf8_lossy(&voided.to_pdf()).into_owned();
+        assert!(pdf.contains("(VOID) Tj"));
+        let pdf = voucher(TransactionStatus::Posted, Locale::EnUs).to_pdf();
+        assert!(!String::from_utf8_lossy(&pdf).contains("(VOID) Tj"));
+    }
+
+    #[test]
+    fn test_pdf_contains_entries_and_trail() {
+        let pdf = voucher(TransactionStatus::Posted, Locale::EnUs).to_pdf();
+        let pdf = String::from_utf8_lossy(&pdf).into_owned();
+        assert!(pdf.starts_with("%PDF-1.4"));
+        assert!(pdf.contains("(Acme & Co) Tj"));
+        assert!(pdf.contains("(6100) Tj"));
+        assert!(pdf.contains("(1,234.56) Tj"));
+        assert!(pdf.contains("(Department: Sales & Marketing) Tj"));
+        assert!(pdf.contains("(Ana Putri) Tj"));
+        assert!(pdf.contains("/Count 1"));
+    }
+
+    #[test]
+    fn test_long_vouchers_continue_on_new_pages() {
+        let mut long = voucher(TransactionStatus::Posted, Locale::EnUs);
+        let line = long.lines[0].clone();
+        long.lines = vec![line; 80];
+        let pdf = String::from_utf8_lossy(&long.to_pdf()).into_owned();
+        assert!(!pdf.contains("/Count 1 "));
+        // The column headings are repeated on every page
+        let pages = pdf.matches("/Type /Page ").count();
+        assert!(pages > 1);
+        assert_eq!(pdf.matches("(Dimensions) Tj").count(), pages);
+    }
+}
@@ -0,0 +1,50 @@
+//! Settings error types.
+
+use serde::Serialize;
+use thiserror::Error;
+
+/// A problem with one settings field.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct FieldError {
+    /// Dotted path of the field, e.g. `bank_import.suspense_account_id`.
+    pub field: String,
+    /// What is wrong with the value.
+    pub message: String,
+}
+
+impl FieldError {
+    /// Creates a field error.
+    #[must_use]
+    pub fn new(field: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            field: field.into(),
+            message: message.into(),
+        }
+    }
+}
+
+/// Errors raised while reading or updating organization settings.
+#[derive(Debug, Clone, Error, PartialEq, Eq)]
+pub enum SettingsError {
+    /// One or more fields are unknown or have invalid values.
+    #[error("Invalid settings: {}", summarize(.0))]
+    Invalid(Vec<FieldError>),
+}
+
+impl SettingsError {
+    /// Returns the field-level errors.
+    #[must_use]
+    pub fn fields(&self) -> &[FieldError] {
+        match self {
+            Self::Invalid(fields) => fields,
+        }
+    }
+}
+
+fn summarize(fields: &[FieldError]) -> String {
+    fields
+        .iter()
+        .map(|e| format!("{}: {}", e.field, e.message))
+        .collect::<Vec<_>>()
+        .join("; ")
+}
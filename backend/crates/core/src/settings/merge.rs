@@ -0,0 +1,145 @@
+//! Partial updates to organization settings.
+//!
+//! Patches follow JSON merge patch semantics (RFC 7396): objects merge
+//! recursively, other values replace, and `null` removes a key so the field
+//! falls back to its default.
+
+use chrono::{DateTime, Utc};
+use serde_json::{Map, Value};
+
+use super::error::{FieldError, SettingsError};
+use super::types::OrganizationSettings;
+
+/// Merges `patch` into the `current` stored document and validates the result.
+///
+/// Every unknown key and invalid value in the patch is reported, not just the
+/// first. On success the returned settings have `updated_at` set to `now`.
+///
+/// # Errors
+///
+/// Returns `SettingsError::Invalid` with one entry per offending field.
+pub fn apply_patch(
+    current: &Value,
+    patch: &Value,
+    now: DateTime<Utc>,
+) -> Result<OrganizationSettings, SettingsError> {
+    if !patch.is_object() {
+        return Err(SettingsError::Invalid(vec![FieldError::new(
+            "",
+            "settings patch must be a JSON object",
+        )]));
+    }
+
+    let schema = OrganizationSettings::default().to_value();
+    let mut errors = Vec::new();
+    let mut leaves = Vec::new();
+    check_keys(&schema, patch, &mut Vec::new(), &mut leaves, &mut errors);
+
+    // Validate each changed value on its own, against defaults, so one bad
+    // field does not hide another
+    for (path, value) in leaves {
+        if value.is_null() {
+            continue;
+        }
+        let mut probe = schema.clone();
+        set_path(&mut probe, &path, value.clone());
+        if let Err(SettingsError::Invalid(field_errors)) = OrganizationSettings::from_value(&probe)
+        {
+            errors.extend(
+                field_errors
+                    .into_iter()
+                    .map(|e| FieldError::new(path.join("."), e.message)),
+            );
+        }
+    }
+
+    if !errors.is_empty() {
+        return Err(SettingsError::Invalid(errors));
+    }
+
+    let mut merged = if current.is_object() {
+        current.clone()
+    } else {
+        Value::Object(Map::new())
+    };
+    merge(&mut merged, patch);
+
+    let mut settings = OrganizationSettings::from_value(&merged)?;
+    settings.updated_at = Some(now);
+    Ok(settings)
+}
+
+/// Checks patch keys against the schema, collecting the values to validate.
+fn check_keys<'a>(
+    schema: &Value,
+    patch: &'a Value,
+    keys: &mut Vec<String>,
+    leaves: &mut Vec<(Vec<String>, &'a Value)>,
+    errors: &mut Vec<FieldError>,
+) {
+    let Some(fields) = patch.as_object() else {
+        return;
+    };
+
+    for (key, value) in fields {
+        keys.push(key.clone());
+        let field = keys.join(".");
+
+        match schema.get(key) {
+            None => errors.push(FieldError::new(field, "unknown field")),
+            Some(section @ Value::Object(_)) => {
+                if value.is_object() {
+                    check_keys(section, value, keys, leaves, errors);
+                } else {
+                    errors.push(FieldError::new(field, "must be an object"));
+                }
+            }
+            Some(_)
+                if keys.len() == 1
+                    && OrganizationSettings::READ_ONLY_FIELDS.contains(&key.as_str()) =>
+            {
+                errors.push(FieldError::new(field, "is read-only"));
+            }
+            Some(_) => leaves.push((keys.clone(), value)),
+        }
+
+        keys.pop();
+    }
+}
+
+/// Applies a JSON merge patch to `target`.
+fn merge(target: &mut Value, patch: &Value) {
+    let Value::Object(patch_fields) = patch else {
+        *target = patch.clone();
+        return;
+    };
+    if !target.is_object() {
+        *target = Value::Object(Map::new());
+    }
+    let Value::Object(target_fields) = target else {
+        return;
+    };
+
+    for (key, value) in patch_fields {
+        if value.is_null() {
+            target_fields.remove(key);
+        } else {
+            merge(
+                target_fields.entry(key.clone()).or_insert(Value::Null),
+                value,
+            );
+        }
+    }
+}
+
+/// Replaces the value at `path`, which must exist in `target`.
+fn set_path(target: &mut Value, path: &[String], value: Value) {
+    let mut slot = target;
+    for key in path {
+        let Some(next) = slot.get_mut(key) else {
+            return;
+        };
+        slot = next;
+    }
+    *slot = value;
+}
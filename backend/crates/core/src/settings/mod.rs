@@ -0,0 +1,26 @@
+//! Organization settings.
+//!
+//! `organizations.settings` is a JSONB document. This module gives it a
+//! schema:
+//! - [`OrganizationSettings`], the typed view with defaults for missing keys
+//! - [`apply_patch`], which deep-merges a partial update into stored settings,
+//!   rejecting unknown keys and invalid values with field-level errors
+//!
+//! Stored documents are read leniently: unknown keys are ignored so older
+//! documents keep loading. Building with the `strict-settings` feature makes
+//! reads reject unknown keys as well.
+
+pub mod error;
+pub mod merge;
+pub mod types;
+
+#[cfg(test)]
+mod tests;
+
+pub use error::{FieldError, SettingsError};
+pub use merge::apply_patch;
+pub use types::{
+    AmountPrecisionSettings, ApprovalEscalationSettings, AutoCloseSettings, BankImportSettings,
+    BudgetEnforcementSettings, CurrencyBalanceSettings, DuplicateDetectionSettings,
+    ExchangeRateSettings, FormattingSettings, FxRevaluationSettings, OrganizationSettings,
+};
@@ -0,0 +1,302 @@
+//! Tests for organization settings parsing and patching.
+
+use chrono::{TimeZone, Utc};
+use serde_json::json;
+use uuid::Uuid;
+use zeltra_shared::format::Locale;
+
+use crate::currency::RateLookupPolicy;
+
+use super::error::{FieldError, SettingsError};
+use super::merge::apply_patch;
+use super::types::OrganizationSettings;
+
+fn now() -> chrono::DateTime<Utc> {
+    Utc.with_ymd_and_hms(2026, 3, 1, 12, 0, 0).unwrap()
+}
+
+fn fields(err: &SettingsError) -> Vec<&str> {
+    err.fields().iter().map(|e| e.field.as_str()).collect()
+}
+
+#[test]
+fn test_empty_document_uses_defaults() {
+    let settings = OrganizationSettings::from_value(&json!({})).unwrap();
+    assert_eq!(settings, OrganizationSettings::default());
+}
+
+#[test]
+fn test_reads_stored_values() {
+    let account = Uuid::new_v4();
+    let settings = OrganizationSettings::from_value(&json!({
+        "bank_import": { "suspense_account_id": account.to_string() }
+    }))
+    .unwrap();
+
+    assert_eq!(settings.bank_import.suspense_account_id, Some(account));
+}
+
+#[cfg(not(feature = "strict-settings"))]
+#[test]
+fn test_lenient_read_ignores_unknown_keys() {
+    let settings = OrganizationSettings::from_value(&json!({ "legacy_flag": true })).unwrap();
+    assert_eq!(settings, OrganizationSettings::default());
+}
+
+#[cfg(feature = "strict-settings")]
+#[test]
+fn test_strict_read_rejects_unknown_keys() {
+    let err = OrganizationSettings::from_value(&json!({ "legacy_flag": true })).unwrap_err();
+    assert!(matches!(err, SettingsError::Invalid(_)));
+}
+
+#[test]
+fn test_read_reports_path_of_invalid_value() {
+    let err = OrganizationSettings::from_value(&json!({
+        "bank_import": { "suspense_account_id": 42 }
+    }))
+    .unwrap_err();
+
+    assert_eq!(fields(&err), ["bank_import.suspense_account_id"]);
+}
+
+#[test]
+fn test_patch_sets_value_and_marker() {
+    let account = Uuid::new_v4();
+    let settings = apply_patch(
+        &json!({}),
+        &json!({ "bank_import": { "suspense_account_id": account } }),
+        now(),
+    )
+    .unwrap();
+
+    assert_eq!(settings.bank_import.suspense_account_id, Some(account));
+    assert_eq!(settings.updated_at, Some(now()));
+}
+
+#[test]
+fn test_patch_null_resets_to_default() {
+    let current = json!({ "bank_import": { "suspense_account_id": Uuid::new_v4() } });
+    let settings = apply_patch(
+        &current,
+        &json!({ "bank_import": { "suspense_account_id": null } }),
+        now(),
+    )
+    .unwrap();
+
+    assert_eq!(settings.bank_import.suspense_account_id, None);
+}
+
+#[test]
+fn test_patch_keeps_untouched_values() {
+    let account = Uuid::new_v4();
+    let current = json!({ "bank_import": { "suspense_account_id": account } });
+    let settings = apply_patch(&current, &json!({ "bank_import": {} }), now()).unwrap();
+
+    assert_eq!(settings.bank_import.suspense_account_id, Some(account));
+}
+
+#[test]
+fn test_patch_reports_every_bad_field() {
+    let err = apply_patch(
+        &json!({}),
+        &json!({
+            "theme": "dark",
+            "bank_import": { "suspense_account_id": "not-a-uuid", "auto_post": true },
+            "updated_at": "2026-01-01T00:00:00Z"
+        }),
+        now(),
+    )
+    .unwrap_err();
+
+    let mut reported = fields(&err);
+    reported.sort_unstable();
+    assert_eq!(
+        reported,
+        [
+            "bank_import.auto_post",
+            "bank_import.suspense_account_id",
+            "theme",
+            "updated_at"
+        ]
+    );
+}
+
+#[test]
+fn test_exchange_rate_defaults_and_patch() {
+    let defaults = OrganizationSettings::default().exchange_rates;
+    assert_eq!(defaults.max_staleness_days, 7);
+    assert!(!defaults.strict_staleness);
+    assert_eq!(defaults.lookup_policy, RateLookupPolicy::DailyLatest);
+
+    let settings = apply_patch(
+        &json!({}),
+        &json!({ "exchange_rates": { "strict_staleness": true } }),
+        now(),
+    )
+    .unwrap();
+    assert!(settings.exchange_rates.strict_staleness);
+    assert_eq!(settings.exchange_rates.max_staleness_days, 7);
+
+    let settings = apply_patch(
+        &settings.to_value(),
+        &json!({ "exchange_rates": { "lookup_policy": "monthly_fixed" } }),
+        now(),
+    )
+    .unwrap();
+    assert_eq!(
+        settings.exchange_rates.lookup_policy,
+        RateLookupPolicy::MonthlyFixed
+    );
+    assert!(settings.exchange_rates.strict_staleness);
+
+    let err = apply_patch(
+        &json!({}),
+        &json!({ "exchange_rates": { "lookup_policy": "weekly" } }),
+        now(),
+    )
+    .unwrap_err();
+    assert_eq!(fields(&err), ["exchange_rates.lookup_policy"]);
+
+    let err = apply_patch(
+        &json!({}),
+        &json!({ "exchange_rates": { "max_staleness_days": -1 } }),
+        now(),
+    )
+    .unwrap_err();
+    assert_eq!(fields(&err), ["exchange_rates.max_staleness_days"]);
+}
+
+#[test]
+fn test_fx_revaluation_accounts_patch_independently() {
+    let gain = Uuid::new_v4();
+    let loss = Uuid::new_v4();
+    let current = json!({ "fx_revaluation": { "gain_account_id": gain } });
+
+    let settings = apply_patch(
+        &current,
+        &json!({ "fx_revaluation": { "loss_account_id": loss } }),
+        now(),
+    )
+    .unwrap();
+
+    assert_eq!(settings.fx_revaluation.gain_account_id, Some(gain));
+    assert_eq!(settings.fx_revaluation.loss_account_id, Some(loss));
+}
+
+#[test]
+fn test_approval_escalation_defaults_and_patch() {
+    let defaults = OrganizationSettings::default().approval_escalation;
+    assert!(defaults.enabled);
+    assert_eq!(defaults.after_business_days, 3);
+
+    let settings = apply_patch(
+        &json!({}),
+        &json!({ "approval_escalation": { "after_business_days": 5 } }),
+        now(),
+    )
+    .unwrap();
+    assert!(settings.approval_escalation.enabled);
+    assert_eq!(settings.approval_escalation.after_business_days, 5);
+}
+
+#[test]
+fn test_budget_enforcement_defaults_and_patch() {
+    let defaults = OrganizationSettings::default().budget_enforcement;
+    assert!(defaults.enabled);
+    assert_eq!(defaults.threshold_percent, 100);
+    assert!(!defaults.strict);
+
+    let settings = apply_patch(
+        &json!({}),
+        &json!({ "budget_enforcement": { "threshold_percent": 90, "strict": true } }),
+        now(),
+    )
+    .unwrap();
+    assert!(settings.budget_enforcement.enabled);
+    assert_eq!(settings.budget_enforcement.threshold_percent, 90);
+    assert!(settings.budget_enforcement.strict);
+}
+
+#[test]
+fn test_period_auto_close_defaults_and_patch() {
+    let defaults = OrganizationSettings::default().period_auto_close;
+    assert!(!defaults.enabled);
+    assert_eq!(defaults.soft_close_after_days, 5);
+    assert_eq!(defaults.close_after_days, 10);
+
+    let settings = apply_patch(
+        &json!({}),
+        &json!({ "period_auto_close": { "enabled": true, "close_after_days": 3 } }),
+        now(),
+    )
+    .unwrap();
+    assert!(settings.period_auto_close.enabled);
+    assert_eq!(settings.period_auto_close.soft_close_after_days, 5);
+    assert_eq!(settings.period_auto_close.close_after_days, 3);
+
+    let err = apply_patch(
+        &json!({}),
+        &json!({ "period_auto_close": { "soft_close_after_days": -1 } }),
+        now(),
+    )
+    .unwrap_err();
+    assert_eq!(fields(&err), ["period_auto_close.soft_close_after_days"]);
+}
+
+#[test]
+fn test_formatting_locale_patch() {
+    assert_eq!(
+        OrganizationSettings::default().formatting.locale,
+        Locale::EnUs
+    );
+
+    let settings = apply_patch(
+        &json!({}),
+        &json!({ "formatting": { "locale": "id-ID" } }),
+        now(),
+    )
+    .unwrap();
+    assert_eq!(settings.formatting.locale, Locale::IdId);
+    assert_eq!(settings.to_value()["formatting"]["locale"], "id-ID");
+
+    let err = apply_patch(
+        &json!({}),
+        &json!({ "formatting": { "locale": "fr-FR" } }),
+        now(),
+    )
+    .unwrap_err();
+    assert_eq!(fields(&err), ["formatting.locale"]);
+}
+
+#[test]
+fn test_patch_rejects_non_object_section() {
+    let err = apply_patch(&json!({}), &json!({ "bank_import": "none" }), now()).unwrap_err();
+    assert_eq!(
+        err.fields(),
+        [FieldError::new("bank_import", "must be an object")]
+    );
+}
+
+#[test]
+fn test_patch_must_be_object() {
+    let err = apply_patch(&json!({}), &json!([1, 2]), now()).unwrap_err();
+    assert_eq!(fields(&err), [""]);
+}
+
+#[test]
+fn test_patch_drops_unknown_stored_keys() {
+    let settings = apply_patch(&json!({ "legacy_flag": true }), &json!({}), now());
+
+    #[cfg(not(feature = "strict-settings"))]
+    assert!(
+        !settings
+            .unwrap()
+            .to_value()
+            .as_object()
+            .unwrap()
+            .contains_key("legacy_flag")
+    );
+    #[cfg(feature = "strict-settings")]
+    assert!(settings.is_err());
+}
@@ -0,0 +1,239 @@
+//! Typed organization settings.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use uuid::Uuid;
+use zeltra_shared::format::Locale;
+
+use crate::currency::RateLookupPolicy;
+
+use super::error::{FieldError, SettingsError};
+
+/// Settings stored in `organizations.settings`.
+///
+/// Every field has a default, so an empty document is valid.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+#[cfg_attr(feature = "strict-settings", serde(deny_unknown_fields))]
+pub struct OrganizationSettings {
+    /// Bank statement import.
+    pub bank_import: BankImportSettings,
+
+    /// Exchange rate lookup for foreign currency entries.
+    pub exchange_rates: ExchangeRateSettings,
+
+    /// Revaluation of foreign currency balances.
+    pub fx_revaluation: FxRevaluationSettings,
+
+    /// Reminders for transactions waiting on approval.
+    pub approval_escalation: ApprovalEscalationSettings,
+
+    /// Checks for transactions that repeat an existing one.
+    pub duplicate_detection: DuplicateDetectionSettings,
+
+    /// How amounts and dates are presented to readers.
+    pub formatting: FormattingSettings,
+
+    /// Per-currency balance checks on new transactions.
+    pub currency_balance: CurrencyBalanceSettings,
+
+    /// Handling of amounts more precise than their currency.
+    pub amount_precision: AmountPrecisionSettings,
+
+    /// Budget checks on submitted transactions.
+    pub budget_enforcement: BudgetEnforcementSettings,
+
+    /// Automatic soft close and close of ended fiscal periods.
+    pub period_auto_close: AutoCloseSettings,
+
+    /// When the settings were last changed. Maintained by the server.
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+/// Bank statement import settings.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+#[cfg_attr(feature = "strict-settings", serde(deny_unknown_fields))]
+pub struct BankImportSettings {
+    /// Account that unmatched statement lines are proposed against.
+    pub suspense_account_id: Option<Uuid>,
+}
+
+/// Foreign currency revaluation settings.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+#[cfg_attr(feature = "strict-settings", serde(deny_unknown_fields))]
+pub struct FxRevaluationSettings {
+    /// Account credited with unrealized exchange gains.
+    pub gain_account_id: Option<Uuid>,
+
+    /// Account debited with unrealized exchange losses.
+    pub loss_account_id: Option<Uuid>,
+}
+
+/// Exchange rate lookup settings.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+#[cfg_attr(feature = "strict-settings", serde(deny_unknown_fields))]
+pub struct ExchangeRateSettings {
+    /// Days a rate may predate the transaction before it is stale.
+    pub max_staleness_days: u32,
+
+    /// Reject transactions that would use a stale rate instead of warning.
+    pub strict_staleness: bool,
+
+    /// Which stored rate converts a transaction's foreign amounts.
+    pub lookup_policy: RateLookupPolicy,
+}
+
+impl Default for ExchangeRateSettings {
+    fn default() -> Self {
+        Self {
+            max_staleness_days: 7,
+            strict_staleness: false,
+            lookup_policy: RateLookupPolicy::DailyLatest,
+        }
+    }
+}
+
+/// Approval reminder settings.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+#[cfg_attr(feature = "strict-settings", serde(deny_unknown_fields))]
+pub struct ApprovalEscalationSettings {
+    /// Send reminders for transactions left pending.
+    pub enabled: bool,
+
+    /// Business days a transaction may stay pending before reminders start.
+    pub after_business_days: u32,
+}
+
+impl Default for ApprovalEscalationSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            after_business_days: 3,
+        }
+    }
+}
+
+/// Duplicate transaction detection settings.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+#[cfg_attr(feature = "strict-settings", serde(deny_unknown_fields))]
+pub struct DuplicateDetectionSettings {
+    /// Days either side of the transaction date searched for duplicates;
+    /// zero compares the same date only.
+    pub window_days: u32,
+}
+
+/// Display formatting settings.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+#[cfg_attr(feature = "strict-settings", serde(deny_unknown_fields))]
+pub struct FormattingSettings {
+    /// Locale whose number and date conventions reports follow.
+    pub locale: Locale,
+}
+
+/// Per-currency balance settings.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+#[cfg_attr(feature = "strict-settings", serde(deny_unknown_fields))]
+pub struct CurrencyBalanceSettings {
+    /// Require each source currency to balance in source amounts.
+    pub strict: bool,
+
+    /// Account through which one currency is paired with another; a currency
+    /// with a line on it is exempt from the check.
+    pub fx_clearing_account_id: Option<Uuid>,
+}
+
+/// Amount precision settings.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+#[cfg_attr(feature = "strict-settings", serde(deny_unknown_fields))]
+pub struct AmountPrecisionSettings {
+    /// Reject amounts with more decimal places than their currency instead
+    /// of rounding them.
+    pub strict: bool,
+}
+
+/// Budget enforcement settings.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+#[cfg_attr(feature = "strict-settings", serde(deny_unknown_fields))]
+pub struct BudgetEnforcementSettings {
+    /// Check submitted transactions against active budgets.
+    pub enabled: bool,
+
+    /// Utilization, in percent of the budgeted amount, past which a budget
+    /// line is flagged.
+    pub threshold_percent: u32,
+
+    /// Reject submissions that would take a budget line past the threshold
+    /// instead of warning.
+    pub strict: bool,
+}
+
+impl Default for BudgetEnforcementSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            threshold_percent: 100,
+            strict: false,
+        }
+    }
+}
+
+/// Fiscal period auto-close settings.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+#[cfg_attr(feature = "strict-settings", serde(deny_unknown_fields))]
+pub struct AutoCloseSettings {
+    /// Close ended periods automatically.
+    pub enabled: bool,
+
+    /// Days after a period ends before it is soft-closed.
+    pub soft_close_after_days: u32,
+
+    /// Further days after the soft close before the period is closed.
+    pub close_after_days: u32,
+}
+
+impl Default for AutoCloseSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            soft_close_after_days: 5,
+            close_after_days: 10,
+        }
+    }
+}
+
+impl OrganizationSettings {
+    /// Fields clients may not set.
+    pub const READ_ONLY_FIELDS: &'static [&'static str] = &["updated_at"];
+
+    /// Reads a stored settings document, applying defaults for missing keys.
+    ///
+    /// # Errors
+    ///
+    /// Returns `SettingsError::Invalid` if a value has the wrong type, or with
+    /// the `strict-settings` feature, if the document has unknown keys.
+    pub fn from_value(value: &Value) -> Result<Self, SettingsError> {
+        serde_path_to_error::deserialize(value).map_err(|e| {
+            let field = e.path().to_string();
+            let message = e.into_inner().to_string();
+            SettingsError::Invalid(vec![FieldError::new(field, message)])
+        })
+    }
+
+    /// Serializes the settings for storage.
+    #[must_use]
+    pub fn to_value(&self) -> Value {
+        // Serializing plain structs of options and scalars cannot fail
+        serde_json::to_value(self).unwrap_or_default()
+    }
+}
@@ -21,15 +21,16 @@ mod tests {
 
             // Generate monthly amounts with some variance
             let monthly_amounts: Vec<Decimal> = (0..num_months)
-                .map(|m| base_amount + Decimal::from(m as i64 * 100))
+                .map(|m| base_amount + Decimal::from(m * 100))
                 .collect();
 
             data.push(HistoricalAccountData {
                 account_id: Uuid::new_v4(),
                 account_code: format!("{:04}", 4000 + i),
-                account_name: format!("Account {}", i),
+                account_name: format!("Account {i}"),
                 account_type: account_type.to_string(),
                 monthly_amounts,
+                dimension_value_id: None,
             });
         }
 
@@ -46,6 +47,7 @@ mod tests {
             expense_growth_rate: dec!(0.05),
             account_adjustments: HashMap::new(),
             dimension_filters: vec![],
+            dimension_adjustments: vec![],
         };
 
         // 100 accounts with 12 months of historical data
@@ -56,7 +58,7 @@ mod tests {
         let duration = start.elapsed();
 
         println!("\n=== BENCHMARK: 100 accounts, 12-month projection ===");
-        println!("Duration: {:?}", duration);
+        println!("Duration: {duration:?}");
         println!("Projections generated: {}", result.projections.len());
         println!("Expected projections: {}", 100 * 12);
 
@@ -78,6 +80,7 @@ mod tests {
             expense_growth_rate: dec!(0.05),
             account_adjustments: HashMap::new(),
             dimension_filters: vec![],
+            dimension_adjustments: vec![],
         };
 
         // 500 accounts - stress test
@@ -88,7 +91,7 @@ mod tests {
         let duration = start.elapsed();
 
         println!("\n=== BENCHMARK: 500 accounts, 12-month projection ===");
-        println!("Duration: {:?}", duration);
+        println!("Duration: {duration:?}");
         println!("Projections generated: {}", result.projections.len());
 
         assert_eq!(result.projections.len(), 500 * 12);
@@ -109,6 +112,7 @@ mod tests {
             expense_growth_rate: dec!(0.05),
             account_adjustments: HashMap::new(),
             dimension_filters: vec![],
+            dimension_adjustments: vec![],
         };
 
         let data = generate_test_data(100, 12);
@@ -118,7 +122,7 @@ mod tests {
         let duration = start.elapsed();
 
         println!("\n=== BENCHMARK: 100 accounts, 60-month projection (5 years) ===");
-        println!("Duration: {:?}", duration);
+        println!("Duration: {duration:?}");
         println!("Projections generated: {}", result.projections.len());
 
         assert_eq!(result.projections.len(), 100 * 60);
@@ -139,6 +143,7 @@ mod tests {
             expense_growth_rate: dec!(0.05),
             account_adjustments: HashMap::new(),
             dimension_filters: vec![],
+            dimension_adjustments: vec![],
         };
 
         // Worst case: 1000 accounts, 60 months projection
@@ -149,11 +154,11 @@ mod tests {
         let duration = start.elapsed();
 
         println!("\n=== BENCHMARK: WORST CASE - 1000 accounts, 60-month projection ===");
-        println!("Duration: {:?}", duration);
+        println!("Duration: {duration:?}");
         println!("Projections generated: {}", result.projections.len());
         println!(
             "Throughput: {:.0} projections/sec",
-            result.projections.len() as f64 / duration.as_secs_f64()
+            f64::from(u32::try_from(result.projections.len()).unwrap()) / duration.as_secs_f64()
         );
 
         assert_eq!(result.projections.len(), 1000 * 60);
@@ -125,6 +125,7 @@ impl Default for SimulationCache {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::simulation::types::DimensionAdjustment;
     use chrono::NaiveDate;
     use rust_decimal_macros::dec;
     use std::collections::HashMap;
@@ -139,6 +140,7 @@ mod tests {
             expense_growth_rate: dec!(0.05),
             account_adjustments: HashMap::new(),
             dimension_filters: vec![],
+            dimension_adjustments: vec![],
         }
     }
 
@@ -149,6 +151,7 @@ mod tests {
             account_name: "Revenue".to_string(),
             account_type: "revenue".to_string(),
             monthly_amounts: vec![dec!(1000)],
+            dimension_value_id: None,
         }]
     }
 
@@ -193,6 +196,26 @@ mod tests {
         assert!(result3.cached, "Same params should hit cache");
     }
 
+    #[test]
+    fn test_dimension_scoping_not_cached_as_account_level() {
+        let cache = SimulationCache::new();
+        let data = create_test_data();
+        let account_level = create_test_params();
+        let mut scoped = create_test_params();
+        scoped.dimension_adjustments = vec![DimensionAdjustment {
+            account_id: data[0].account_id,
+            dimension_value_id: Uuid::new_v4(),
+            growth_rate: dec!(0.20),
+        }];
+
+        let _ = cache.run_cached(&data, &account_level);
+        let result = cache.run_cached(&data, &scoped);
+        assert!(
+            !result.cached,
+            "Dimension-scoped run should not reuse the account-level result"
+        );
+    }
+
     #[test]
     fn test_invalidate_all() {
         let cache = SimulationCache::new();
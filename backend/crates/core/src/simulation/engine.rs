@@ -50,7 +50,8 @@ impl SimulationEngine {
             return Err(SimulationError::InvalidGrowthRate);
         }
 
-        for rate in params.account_adjustments.values() {
+        let dimension_rates = params.dimension_adjustments.iter().map(|a| &a.growth_rate);
+        for rate in params.account_adjustments.values().chain(dimension_rates) {
             if *rate < min_rate || *rate > max_rate {
                 return Err(SimulationError::InvalidGrowthRate);
             }
@@ -104,19 +105,7 @@ impl SimulationEngine {
         params: &SimulationParams,
     ) -> Vec<AccountProjection> {
         let baseline = Self::calculate_baseline(&data.monthly_amounts);
-
-        // Get growth rate (account-specific or global)
-        let growth_rate = params
-            .account_adjustments
-            .get(&data.account_id)
-            .copied()
-            .unwrap_or_else(|| {
-                if data.account_type == "revenue" {
-                    params.revenue_growth_rate
-                } else {
-                    params.expense_growth_rate
-                }
-            });
+        let growth_rate = Self::growth_rate(data, params);
 
         let mut projections = Vec::with_capacity(params.projection_months as usize);
         let mut current_date = params.base_period_end;
@@ -136,6 +125,7 @@ impl SimulationEngine {
                 account_code: data.account_code.clone(),
                 account_name: data.account_name.clone(),
                 account_type: data.account_type.clone(),
+                dimension_value_id: data.dimension_value_id,
                 baseline_amount: baseline,
                 projected_amount: projected,
                 change_percent: if baseline.is_zero() {
@@ -149,6 +139,33 @@ impl SimulationEngine {
         projections
     }
 
+    /// Picks the growth rate for an account or account slice.
+    ///
+    /// A slice tagged with a dimension value uses the matching dimension
+    /// adjustment; everything else falls back to the account-specific rate,
+    /// then the global rate for the account type.
+    fn growth_rate(data: &HistoricalAccountData, params: &SimulationParams) -> Decimal {
+        let dimension_rate = data.dimension_value_id.and_then(|dimension_value_id| {
+            params
+                .dimension_adjustments
+                .iter()
+                .find(|a| {
+                    a.account_id == data.account_id && a.dimension_value_id == dimension_value_id
+                })
+                .map(|a| a.growth_rate)
+        });
+
+        dimension_rate
+            .or_else(|| params.account_adjustments.get(&data.account_id).copied())
+            .unwrap_or_else(|| {
+                if data.account_type == "revenue" {
+                    params.revenue_growth_rate
+                } else {
+                    params.expense_growth_rate
+                }
+            })
+    }
+
     /// Calculates the baseline (average) from monthly amounts.
     ///
     /// Returns zero if the input is empty.
@@ -198,6 +215,16 @@ impl SimulationEngine {
             rate.to_string().hash(&mut hasher);
         }
 
+        // Hash dimension adjustments (sorted for consistency)
+        let mut dimension_adjustments: Vec<_> = params.dimension_adjustments.iter().collect();
+        dimension_adjustments.sort_by_key(|a| (a.account_id, a.dimension_value_id));
+        dimension_adjustments.len().hash(&mut hasher);
+        for adjustment in dimension_adjustments {
+            adjustment.account_id.hash(&mut hasher);
+            adjustment.dimension_value_id.hash(&mut hasher);
+            adjustment.growth_rate.to_string().hash(&mut hasher);
+        }
+
         // Hash dimension filters (sorted for consistency)
         let mut filters = params.dimension_filters.clone();
         filters.sort();
@@ -236,6 +263,7 @@ impl Default for SimulationEngine {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::simulation::types::DimensionAdjustment;
     use rust_decimal_macros::dec;
     use std::collections::HashMap;
 
@@ -248,6 +276,7 @@ mod tests {
             expense_growth_rate: dec!(0.05),
             account_adjustments: HashMap::new(),
             dimension_filters: vec![],
+            dimension_adjustments: vec![],
         }
     }
 
@@ -344,6 +373,7 @@ mod tests {
             expense_growth_rate: dec!(0),
             account_adjustments: HashMap::new(),
             dimension_filters: vec![],
+            dimension_adjustments: vec![],
         };
 
         let data = vec![HistoricalAccountData {
@@ -352,6 +382,7 @@ mod tests {
             account_name: "Revenue".to_string(),
             account_type: "revenue".to_string(),
             monthly_amounts: vec![dec!(1000)],
+            dimension_value_id: None,
         }];
 
         let result = SimulationEngine::run(&data, &params);
@@ -383,4 +414,47 @@ mod tests {
 
         assert_ne!(hash1, hash2);
     }
+
+    #[test]
+    fn test_hash_params_includes_dimension_scoping() {
+        let account_id = Uuid::new_v4();
+        let scoped = |dimension_value_id| {
+            let mut params = create_test_params();
+            params.dimension_adjustments = vec![DimensionAdjustment {
+                account_id,
+                dimension_value_id,
+                growth_rate: dec!(0.20),
+            }];
+            params
+        };
+        let mut account_level = create_test_params();
+        account_level
+            .account_adjustments
+            .insert(account_id, dec!(0.20));
+
+        let marketing = SimulationEngine::hash_params(&scoped(Uuid::new_v4()));
+        let sales = SimulationEngine::hash_params(&scoped(Uuid::new_v4()));
+
+        assert_ne!(marketing, sales);
+        assert_ne!(marketing, SimulationEngine::hash_params(&account_level));
+        assert_ne!(
+            marketing,
+            SimulationEngine::hash_params(&create_test_params())
+        );
+    }
+
+    #[test]
+    fn test_validate_params_invalid_dimension_rate() {
+        let mut params = create_test_params();
+        params.dimension_adjustments = vec![DimensionAdjustment {
+            account_id: Uuid::new_v4(),
+            dimension_value_id: Uuid::new_v4(),
+            growth_rate: dec!(-2),
+        }];
+
+        assert!(matches!(
+            SimulationEngine::validate_params(&params),
+            Err(SimulationError::InvalidGrowthRate)
+        ));
+    }
 }
@@ -17,5 +17,6 @@ pub use engine::SimulationEngine;
 pub use error::SimulationError;
 pub use scenario::{Scenario, ScenarioResult};
 pub use types::{
-    AccountProjection, AnnualSummary, HistoricalAccountData, SimulationParams, SimulationResult,
+    AccountProjection, AnnualSummary, DimensionAdjustment, HistoricalAccountData, SimulationParams,
+    SimulationResult,
 };
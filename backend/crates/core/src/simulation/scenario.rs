@@ -3,7 +3,7 @@
 use chrono::NaiveDate;
 use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
-use zeltra_shared::types::AccountId;
+use zeltra_shared::types::{AccountId, DimensionValueId};
 
 /// A what-if scenario for simulation.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -25,6 +25,9 @@ pub struct Scenario {
 pub struct ScenarioAdjustment {
     /// Account to adjust.
     pub account_id: AccountId,
+    /// Restricts the adjustment to entries tagged with this dimension value.
+    #[serde(default)]
+    pub dimension_value_id: Option<DimensionValueId>,
     /// Type of adjustment.
     pub adjustment_type: AdjustmentType,
     /// Adjustment value.
@@ -7,7 +7,9 @@ use std::collections::HashMap;
 use uuid::Uuid;
 
 use super::engine::SimulationEngine;
-use super::types::{HistoricalAccountData, SimulationParams};
+use super::types::{
+    DimensionAdjustment, HistoricalAccountData, SimulationParams, SimulationResult,
+};
 
 fn create_base_params(projection_months: u32) -> SimulationParams {
     SimulationParams {
@@ -18,6 +20,7 @@ fn create_base_params(projection_months: u32) -> SimulationParams {
         expense_growth_rate: dec!(0.05),
         account_adjustments: HashMap::new(),
         dimension_filters: vec![],
+        dimension_adjustments: vec![],
     }
 }
 
@@ -56,10 +59,11 @@ proptest! {
         let historical_data: Vec<HistoricalAccountData> = (0..num_accounts)
             .map(|i| HistoricalAccountData {
                 account_id: Uuid::new_v4(),
-                account_code: format!("ACC{}", i),
-                account_name: format!("Account {}", i),
+                account_code: format!("ACC{i}"),
+                account_name: format!("Account {i}"),
                 account_type: "expense".to_string(),
                 monthly_amounts: vec![dec!(1000)],
+                dimension_value_id: None,
             })
             .collect();
 
@@ -88,6 +92,7 @@ proptest! {
             account_name: "Test Account".to_string(),
             account_type: "expense".to_string(),
             monthly_amounts: vec![baseline],
+            dimension_value_id: None,
         }];
 
         let params = SimulationParams {
@@ -98,6 +103,7 @@ proptest! {
             expense_growth_rate: growth_rate,
             account_adjustments: HashMap::new(),
             dimension_filters: vec![],
+            dimension_adjustments: vec![],
         };
 
         let result = SimulationEngine::run(&historical_data, &params);
@@ -135,6 +141,7 @@ proptest! {
             account_name: "Test Account".to_string(),
             account_type: "expense".to_string(),
             monthly_amounts: vec![baseline],
+            dimension_value_id: None,
         }];
 
         let mut account_adjustments = HashMap::new();
@@ -148,6 +155,7 @@ proptest! {
             expense_growth_rate: global_rate,
             account_adjustments,
             dimension_filters: vec![],
+            dimension_adjustments: vec![],
         };
 
         let result = SimulationEngine::run(&historical_data, &params);
@@ -175,10 +183,11 @@ proptest! {
         for i in 0..num_revenue {
             historical_data.push(HistoricalAccountData {
                 account_id: Uuid::new_v4(),
-                account_code: format!("REV{}", i),
-                account_name: format!("Revenue {}", i),
+                account_code: format!("REV{i}"),
+                account_name: format!("Revenue {i}"),
                 account_type: "revenue".to_string(),
                 monthly_amounts: vec![dec!(1000)],
+                dimension_value_id: None,
             });
         }
 
@@ -186,10 +195,11 @@ proptest! {
         for i in 0..num_expense {
             historical_data.push(HistoricalAccountData {
                 account_id: Uuid::new_v4(),
-                account_code: format!("EXP{}", i),
-                account_name: format!("Expense {}", i),
+                account_code: format!("EXP{i}"),
+                account_name: format!("Expense {i}"),
                 account_type: "expense".to_string(),
                 monthly_amounts: vec![dec!(500)],
+                dimension_value_id: None,
             });
         }
 
@@ -201,6 +211,7 @@ proptest! {
             expense_growth_rate: dec!(0),
             account_adjustments: HashMap::new(),
             dimension_filters: vec![],
+            dimension_adjustments: vec![],
         };
 
         let result = SimulationEngine::run(&historical_data, &params);
@@ -300,6 +311,7 @@ mod unit_tests {
             account_name: "Revenue".to_string(),
             account_type: "revenue".to_string(),
             monthly_amounts: vec![dec!(1000)],
+            dimension_value_id: None,
         }];
 
         let params = SimulationParams {
@@ -310,6 +322,7 @@ mod unit_tests {
             expense_growth_rate: dec!(0.05), // 5%
             account_adjustments: HashMap::new(),
             dimension_filters: vec![],
+            dimension_adjustments: vec![],
         };
 
         let result = SimulationEngine::run(&historical_data, &params);
@@ -326,6 +339,7 @@ mod unit_tests {
             account_name: "Expense".to_string(),
             account_type: "expense".to_string(),
             monthly_amounts: vec![dec!(1000)],
+            dimension_value_id: None,
         }];
 
         let params = SimulationParams {
@@ -336,6 +350,7 @@ mod unit_tests {
             expense_growth_rate: dec!(0.05), // 5%
             account_adjustments: HashMap::new(),
             dimension_filters: vec![],
+            dimension_adjustments: vec![],
         };
 
         let result = SimulationEngine::run(&historical_data, &params);
@@ -343,4 +358,103 @@ mod unit_tests {
         // Expense should use 5% growth rate
         assert_eq!(result.projections[0].projected_amount, dec!(1050));
     }
+
+    fn expense_slice(
+        account_id: Uuid,
+        dimension_value_id: Option<Uuid>,
+        monthly_amounts: Vec<Decimal>,
+    ) -> HistoricalAccountData {
+        HistoricalAccountData {
+            account_id,
+            account_code: "6100".to_string(),
+            account_name: "Advertising".to_string(),
+            account_type: "expense".to_string(),
+            monthly_amounts,
+            dimension_value_id,
+        }
+    }
+
+    #[test]
+    fn test_dimension_scoped_run_matches_manual_split() {
+        let account_id = Uuid::new_v4();
+        let marketing = Uuid::new_v4();
+
+        // Marketing-tagged spend grows 20%, the rest of the account follows
+        // the 5% expense rate
+        let mut scoped_params = create_base_params(6);
+        scoped_params.dimension_adjustments = vec![DimensionAdjustment {
+            account_id,
+            dimension_value_id: marketing,
+            growth_rate: dec!(0.20),
+        }];
+        let scoped = SimulationEngine::run(
+            &[
+                expense_slice(account_id, Some(marketing), vec![dec!(400), dec!(600)]),
+                expense_slice(account_id, None, vec![dec!(1000), dec!(1000)]),
+            ],
+            &scoped_params,
+        );
+
+        // The same history as two separate accounts with an account override
+        let tagged_id = Uuid::new_v4();
+        let remainder_id = Uuid::new_v4();
+        let mut split_params = create_base_params(6);
+        split_params
+            .account_adjustments
+            .insert(tagged_id, dec!(0.20));
+        let split = SimulationEngine::run(
+            &[
+                expense_slice(tagged_id, None, vec![dec!(400), dec!(600)]),
+                expense_slice(remainder_id, None, vec![dec!(1000), dec!(1000)]),
+            ],
+            &split_params,
+        );
+
+        let amounts = |result: &SimulationResult, tagged: bool| {
+            result
+                .projections
+                .iter()
+                .filter(|p| {
+                    (p.dimension_value_id == Some(marketing) || p.account_id == tagged_id) == tagged
+                })
+                .map(|p| (p.period_name.clone(), p.baseline_amount, p.projected_amount))
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(scoped.projections.len(), split.projections.len());
+        assert_eq!(amounts(&scoped, true), amounts(&split, true));
+        assert_eq!(amounts(&scoped, false), amounts(&split, false));
+        assert_eq!(
+            scoped.annual_summary.total_projected_expenses,
+            split.annual_summary.total_projected_expenses
+        );
+        assert!(
+            scoped
+                .projections
+                .iter()
+                .all(|p| p.account_id == account_id)
+        );
+    }
+
+    #[test]
+    fn test_dimension_adjustment_only_matches_its_account() {
+        let account_id = Uuid::new_v4();
+        let marketing = Uuid::new_v4();
+
+        let mut params = create_base_params(1);
+        params.account_adjustments.insert(account_id, dec!(0.10));
+        params.dimension_adjustments = vec![DimensionAdjustment {
+            account_id: Uuid::new_v4(),
+            dimension_value_id: marketing,
+            growth_rate: dec!(0.50),
+        }];
+
+        let result = SimulationEngine::run(
+            &[expense_slice(account_id, Some(marketing), vec![dec!(1000)])],
+            &params,
+        );
+
+        // Falls back to the account-level rate
+        assert_eq!(result.projections[0].projected_amount, dec!(1100));
+    }
 }
@@ -23,6 +23,23 @@ pub struct SimulationParams {
     pub account_adjustments: HashMap<Uuid, Decimal>,
     /// Dimension value IDs to filter by.
     pub dimension_filters: Vec<Uuid>,
+    /// Growth rate overrides for the slice of an account tagged with a
+    /// dimension value.
+    #[serde(default)]
+    pub dimension_adjustments: Vec<DimensionAdjustment>,
+}
+
+/// Growth rate for the part of an account tagged with a dimension value.
+///
+/// The rest of the account keeps its account-level or global rate.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DimensionAdjustment {
+    /// Account to adjust.
+    pub account_id: Uuid,
+    /// Dimension value the adjustment is scoped to.
+    pub dimension_value_id: Uuid,
+    /// Growth rate for the tagged slice.
+    pub growth_rate: Decimal,
 }
 
 /// Historical account data for baseline calculation.
@@ -38,6 +55,11 @@ pub struct HistoricalAccountData {
     pub account_type: String,
     /// Monthly amounts from the base period.
     pub monthly_amounts: Vec<Decimal>,
+    /// Dimension value this slice of the account is tagged with.
+    ///
+    /// `None` for the whole account, or for the untagged remainder when the
+    /// account is split by a dimension adjustment.
+    pub dimension_value_id: Option<Uuid>,
 }
 
 /// Projected amount for a single account and period.
@@ -57,6 +79,8 @@ pub struct AccountProjection {
     pub account_name: String,
     /// Account type.
     pub account_type: String,
+    /// Dimension value of the projected slice, `None` for account-level rows.
+    pub dimension_value_id: Option<Uuid>,
     /// Baseline amount (average from historical data).
     pub baseline_amount: Decimal,
     /// Projected amount after applying growth.
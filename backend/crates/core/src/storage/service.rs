@@ -229,6 +229,19 @@ impl StorageService {
         })
     }
 
+    /// Read a file's contents from storage.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file does not exist or cannot be read.
+    pub async fn read(&self, key: &str) -> Result<Vec<u8>, StorageError> {
+        self.operator
+            .read(key)
+            .await
+            .map(|buffer| buffer.to_vec())
+            .map_err(StorageError::from)
+    }
+
     /// Delete a file from storage.
     ///
     /// # Errors
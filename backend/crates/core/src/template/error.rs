@@ -0,0 +1,63 @@
+//! Transaction template error types.
+
+use rust_decimal::Decimal;
+use thiserror::Error;
+
+/// Errors raised while validating or instantiating a transaction template.
+#[derive(Debug, Clone, Error, PartialEq, Eq)]
+pub enum TemplateError {
+    /// The template has fewer than two lines.
+    #[error("Template must have at least 2 entries")]
+    InsufficientLines,
+
+    /// A fixed amount is zero or negative.
+    #[error("Line {0}: fixed amount must be positive")]
+    InvalidFixedAmount(usize),
+
+    /// A percentage is outside (0, 100].
+    #[error("Line {0}: percentage must be greater than 0 and at most 100")]
+    InvalidPercentage(usize),
+
+    /// The percentage lines on one side do not add up to 100.
+    #[error("Percentages on the {side} side sum to {sum}, expected 100")]
+    PercentageSum {
+        /// "debit" or "credit".
+        side: &'static str,
+        /// Sum of the side's percentages.
+        sum: Decimal,
+    },
+
+    /// A line without a fixed amount was instantiated without per-line amounts.
+    #[error("Line {0} has no fixed amount; provide per-line amounts")]
+    AmountRequired(usize),
+
+    /// A percentage template was instantiated without a total.
+    #[error("Template has percentage lines; provide a total amount")]
+    TotalRequired,
+
+    /// The total amount is zero or negative.
+    #[error("Total amount must be positive")]
+    InvalidTotal,
+
+    /// Per-line amounts do not match the template's lines.
+    #[error("Expected {expected} line amounts, got {got}")]
+    AmountCountMismatch {
+        /// Number of template lines.
+        expected: usize,
+        /// Number of amounts supplied.
+        got: usize,
+    },
+
+    /// A per-line amount is zero or negative.
+    #[error("Line {0}: amount must be positive")]
+    InvalidLineAmount(usize),
+
+    /// The resolved amounts do not balance.
+    #[error("Template amounts are not balanced. Debit: {debit}, Credit: {credit}")]
+    Unbalanced {
+        /// Sum of debit amounts.
+        debit: Decimal,
+        /// Sum of credit amounts.
+        credit: Decimal,
+    },
+}
@@ -0,0 +1,17 @@
+//! Transaction templates.
+//!
+//! This module turns a saved entry skeleton into concrete entry amounts:
+//! - Template lines with a fixed amount, a percentage of the total, or no amount
+//! - Validation of percentage lines (each side sums to 100%)
+//! - Amount resolution from a total (Largest Remainder allocation) or per-line amounts
+
+pub mod error;
+pub mod service;
+pub mod types;
+
+#[cfg(test)]
+mod tests;
+
+pub use error::TemplateError;
+pub use service::TemplateService;
+pub use types::{InstantiateAmounts, LineAmount, TemplateLine};
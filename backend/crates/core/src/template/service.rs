@@ -0,0 +1,151 @@
+//! Transaction template service for validating and resolving entry amounts.
+
+use rust_decimal::Decimal;
+
+use super::error::TemplateError;
+use super::types::{InstantiateAmounts, LineAmount, TemplateLine};
+use crate::currency::AllocationUtil;
+use crate::ledger::types::EntryType;
+
+/// Transaction template service for business logic.
+pub struct TemplateService;
+
+impl TemplateService {
+    /// Validates a template's entry skeleton.
+    ///
+    /// Line numbers in errors are 1-based.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if there are fewer than two lines, a fixed amount or
+    /// percentage is out of range, or one side's percentages do not sum to 100.
+    pub fn validate_lines(lines: &[TemplateLine]) -> Result<(), TemplateError> {
+        if lines.len() < 2 {
+            return Err(TemplateError::InsufficientLines);
+        }
+
+        let hundred = Decimal::from(100);
+        for (i, line) in lines.iter().enumerate() {
+            match line.amount {
+                LineAmount::Fixed(amount) if amount <= Decimal::ZERO => {
+                    return Err(TemplateError::InvalidFixedAmount(i + 1));
+                }
+                LineAmount::Percentage(pct) if pct <= Decimal::ZERO || pct > hundred => {
+                    return Err(TemplateError::InvalidPercentage(i + 1));
+                }
+                _ => {}
+            }
+        }
+
+        for (side, entry_type) in [("debit", EntryType::Debit), ("credit", EntryType::Credit)] {
+            let percentages = side_percentages(lines, entry_type);
+            if percentages.is_empty() {
+                continue;
+            }
+            let sum: Decimal = percentages.iter().map(|(_, p)| *p).sum();
+            if sum != hundred {
+                return Err(TemplateError::PercentageSum { side, sum });
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Resolves the amount of every template line.
+    ///
+    /// With a total, each side's percentage lines split it using the Largest
+    /// Remainder Method, so they add up to the total exactly. Per-line amounts
+    /// replace every stored amount.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the supplied amounts do not fit the template, or
+    /// the resolved debits and credits differ.
+    pub fn resolve_amounts(
+        lines: &[TemplateLine],
+        amounts: &InstantiateAmounts,
+        decimal_places: u32,
+    ) -> Result<Vec<Decimal>, TemplateError> {
+        let resolved = match amounts {
+            InstantiateAmounts::PerLine(values) => {
+                if values.len() != lines.len() {
+                    return Err(TemplateError::AmountCountMismatch {
+                        expected: lines.len(),
+                        got: values.len(),
+                    });
+                }
+                if let Some(i) = values.iter().position(|v| *v <= Decimal::ZERO) {
+                    return Err(TemplateError::InvalidLineAmount(i + 1));
+                }
+                values.clone()
+            }
+            InstantiateAmounts::Total(total) => {
+                if *total <= Decimal::ZERO {
+                    return Err(TemplateError::InvalidTotal);
+                }
+                let mut resolved = fixed_amounts(lines)?;
+                for entry_type in [EntryType::Debit, EntryType::Credit] {
+                    let (indices, percentages): (Vec<_>, Vec<_>) =
+                        side_percentages(lines, entry_type).into_iter().unzip();
+                    let allocated = AllocationUtil::allocate_by_percentages(
+                        *total,
+                        &percentages,
+                        decimal_places,
+                    );
+                    for (i, amount) in indices.into_iter().zip(allocated) {
+                        resolved[i] = amount;
+                    }
+                }
+                resolved
+            }
+            InstantiateAmounts::FixedOnly => {
+                if lines
+                    .iter()
+                    .any(|l| matches!(l.amount, LineAmount::Percentage(_)))
+                {
+                    return Err(TemplateError::TotalRequired);
+                }
+                fixed_amounts(lines)?
+            }
+        };
+
+        let (debit, credit) = lines.iter().zip(&resolved).fold(
+            (Decimal::ZERO, Decimal::ZERO),
+            |(debit, credit), (line, amount)| match line.entry_type {
+                EntryType::Debit => (debit + amount, credit),
+                EntryType::Credit => (debit, credit + amount),
+            },
+        );
+        if debit != credit {
+            return Err(TemplateError::Unbalanced { debit, credit });
+        }
+
+        Ok(resolved)
+    }
+}
+
+/// Returns the fixed amounts, leaving percentage lines at zero.
+fn fixed_amounts(lines: &[TemplateLine]) -> Result<Vec<Decimal>, TemplateError> {
+    lines
+        .iter()
+        .enumerate()
+        .map(|(i, line)| match line.amount {
+            LineAmount::Fixed(amount) => Ok(amount),
+            LineAmount::Percentage(_) => Ok(Decimal::ZERO),
+            LineAmount::Open => Err(TemplateError::AmountRequired(i + 1)),
+        })
+        .collect()
+}
+
+/// Returns the index and percentage of each percentage line on one side.
+fn side_percentages(lines: &[TemplateLine], entry_type: EntryType) -> Vec<(usize, Decimal)> {
+    lines
+        .iter()
+        .enumerate()
+        .filter(|(_, line)| line.entry_type == entry_type)
+        .filter_map(|(i, line)| match line.amount {
+            LineAmount::Percentage(pct) => Some((i, pct)),
+            _ => None,
+        })
+        .collect()
+}
@@ -0,0 +1,204 @@
+//! Tests for transaction template validation and amount resolution.
+
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use uuid::Uuid;
+
+use super::error::TemplateError;
+use super::service::TemplateService;
+use super::types::{InstantiateAmounts, LineAmount, TemplateLine};
+use crate::ledger::types::EntryType;
+
+fn line(entry_type: EntryType, amount: LineAmount) -> TemplateLine {
+    TemplateLine {
+        account_id: Uuid::new_v4(),
+        entry_type,
+        amount,
+    }
+}
+
+/// One clearing credit split across three debits, like an intercompany allocation.
+fn allocation_template() -> Vec<TemplateLine> {
+    vec![
+        line(EntryType::Credit, LineAmount::Percentage(dec!(100))),
+        line(EntryType::Debit, LineAmount::Percentage(dec!(33.33))),
+        line(EntryType::Debit, LineAmount::Percentage(dec!(33.33))),
+        line(EntryType::Debit, LineAmount::Percentage(dec!(33.34))),
+    ]
+}
+
+// ============================================================================
+// Validation
+// ============================================================================
+
+#[test]
+fn test_validate_allocation_template() {
+    assert!(TemplateService::validate_lines(&allocation_template()).is_ok());
+}
+
+#[test]
+fn test_validate_requires_two_lines() {
+    let lines = vec![line(EntryType::Debit, LineAmount::Open)];
+    assert_eq!(
+        TemplateService::validate_lines(&lines),
+        Err(TemplateError::InsufficientLines)
+    );
+}
+
+#[test]
+fn test_validate_rejects_out_of_range_values() {
+    let lines = vec![
+        line(EntryType::Debit, LineAmount::Fixed(dec!(0))),
+        line(EntryType::Credit, LineAmount::Open),
+    ];
+    assert_eq!(
+        TemplateService::validate_lines(&lines),
+        Err(TemplateError::InvalidFixedAmount(1))
+    );
+
+    let lines = vec![
+        line(EntryType::Debit, LineAmount::Open),
+        line(EntryType::Credit, LineAmount::Percentage(dec!(120))),
+    ];
+    assert_eq!(
+        TemplateService::validate_lines(&lines),
+        Err(TemplateError::InvalidPercentage(2))
+    );
+}
+
+#[test]
+fn test_validate_percentages_must_sum_to_100_per_side() {
+    let lines = vec![
+        line(EntryType::Credit, LineAmount::Percentage(dec!(100))),
+        line(EntryType::Debit, LineAmount::Percentage(dec!(60))),
+        line(EntryType::Debit, LineAmount::Percentage(dec!(30))),
+    ];
+    assert_eq!(
+        TemplateService::validate_lines(&lines),
+        Err(TemplateError::PercentageSum {
+            side: "debit",
+            sum: dec!(90)
+        })
+    );
+}
+
+// ============================================================================
+// Amount resolution
+// ============================================================================
+
+#[test]
+fn test_total_is_allocated_exactly() {
+    let amounts = TemplateService::resolve_amounts(
+        &allocation_template(),
+        &InstantiateAmounts::Total(dec!(1000.01)),
+        2,
+    )
+    .unwrap();
+
+    assert_eq!(amounts[0], dec!(1000.01));
+    let debits: Decimal = amounts[1..].iter().copied().sum();
+    assert_eq!(debits, dec!(1000.01));
+}
+
+#[test]
+fn test_total_keeps_fixed_lines() {
+    let lines = vec![
+        line(EntryType::Debit, LineAmount::Fixed(dec!(50))),
+        line(EntryType::Debit, LineAmount::Percentage(dec!(100))),
+        line(EntryType::Credit, LineAmount::Fixed(dec!(50))),
+        line(EntryType::Credit, LineAmount::Percentage(dec!(100))),
+    ];
+
+    let amounts =
+        TemplateService::resolve_amounts(&lines, &InstantiateAmounts::Total(dec!(200)), 2).unwrap();
+    assert_eq!(amounts, vec![dec!(50), dec!(200), dec!(50), dec!(200)]);
+}
+
+#[test]
+fn test_total_needs_amounts_for_open_lines() {
+    let lines = vec![
+        line(EntryType::Debit, LineAmount::Open),
+        line(EntryType::Credit, LineAmount::Percentage(dec!(100))),
+    ];
+    assert_eq!(
+        TemplateService::resolve_amounts(&lines, &InstantiateAmounts::Total(dec!(10)), 2),
+        Err(TemplateError::AmountRequired(1))
+    );
+}
+
+#[test]
+fn test_total_must_be_positive() {
+    assert_eq!(
+        TemplateService::resolve_amounts(
+            &allocation_template(),
+            &InstantiateAmounts::Total(dec!(0)),
+            2
+        ),
+        Err(TemplateError::InvalidTotal)
+    );
+}
+
+#[test]
+fn test_per_line_amounts_replace_template_amounts() {
+    let amounts = TemplateService::resolve_amounts(
+        &allocation_template(),
+        &InstantiateAmounts::PerLine(vec![dec!(90), dec!(30), dec!(30), dec!(30)]),
+        2,
+    )
+    .unwrap();
+    assert_eq!(amounts, vec![dec!(90), dec!(30), dec!(30), dec!(30)]);
+}
+
+#[test]
+fn test_per_line_amounts_must_match_lines() {
+    assert_eq!(
+        TemplateService::resolve_amounts(
+            &allocation_template(),
+            &InstantiateAmounts::PerLine(vec![dec!(10), dec!(10)]),
+            2
+        ),
+        Err(TemplateError::AmountCountMismatch {
+            expected: 4,
+            got: 2
+        })
+    );
+    assert_eq!(
+        TemplateService::resolve_amounts(
+            &allocation_template(),
+            &InstantiateAmounts::PerLine(vec![dec!(30), dec!(10), dec!(-10), dec!(30)]),
+            2
+        ),
+        Err(TemplateError::InvalidLineAmount(3))
+    );
+}
+
+#[test]
+fn test_unbalanced_amounts_are_rejected() {
+    assert_eq!(
+        TemplateService::resolve_amounts(
+            &allocation_template(),
+            &InstantiateAmounts::PerLine(vec![dec!(100), dec!(30), dec!(30), dec!(30)]),
+            2
+        ),
+        Err(TemplateError::Unbalanced {
+            debit: dec!(90),
+            credit: dec!(100)
+        })
+    );
+}
+
+#[test]
+fn test_fixed_only_template() {
+    let lines = vec![
+        line(EntryType::Debit, LineAmount::Fixed(dec!(250))),
+        line(EntryType::Credit, LineAmount::Fixed(dec!(250))),
+    ];
+    assert_eq!(
+        TemplateService::resolve_amounts(&lines, &InstantiateAmounts::FixedOnly, 2),
+        Ok(vec![dec!(250), dec!(250)])
+    );
+    assert_eq!(
+        TemplateService::resolve_amounts(&allocation_template(), &InstantiateAmounts::FixedOnly, 2),
+        Err(TemplateError::TotalRequired)
+    );
+}
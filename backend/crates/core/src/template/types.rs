@@ -0,0 +1,41 @@
+//! Transaction template data types.
+
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::ledger::types::EntryType;
+
+/// How a template line's amount is determined at instantiation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LineAmount {
+    /// The same amount every time.
+    Fixed(Decimal),
+    /// A share of the total amount given at instantiation (0-100).
+    Percentage(Decimal),
+    /// No amount stored; it must be supplied per line.
+    Open,
+}
+
+/// A single line of a template's entry skeleton.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TemplateLine {
+    /// Account ID.
+    pub account_id: Uuid,
+    /// Debit or credit.
+    pub entry_type: EntryType,
+    /// How the amount is determined.
+    pub amount: LineAmount,
+}
+
+/// Amounts supplied when instantiating a template.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InstantiateAmounts {
+    /// A total split across percentage lines; fixed lines keep their amount.
+    Total(Decimal),
+    /// One amount per template line, in line order.
+    PerLine(Vec<Decimal>),
+    /// Only fixed amounts are used.
+    FixedOnly,
+}
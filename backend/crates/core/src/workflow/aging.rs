@@ -0,0 +1,141 @@
+//! Approval queue aging.
+//!
+//! Pending transactions are aged two ways: calendar days since submission,
+//! shown in the approval queue, and business days (Monday to Friday), used
+//! to decide when approvers are reminded. Public holidays are not
+//! considered.
+
+use chrono::{DateTime, Datelike, Days, NaiveDate, Utc, Weekday};
+
+/// Whether `date` falls on Monday to Friday.
+#[must_use]
+pub fn is_business_day(date: NaiveDate) -> bool {
+    !matches!(date.weekday(), Weekday::Sat | Weekday::Sun)
+}
+
+/// Counts business days after `start` up to and including `end`.
+///
+/// A transaction submitted on a Friday has been waiting one business day on
+/// Monday. Returns 0 when `end` is not after `start`.
+#[must_use]
+pub fn business_days_between(start: NaiveDate, end: NaiveDate) -> u32 {
+    if end <= start {
+        return 0;
+    }
+
+    let days = u32::try_from((end - start).num_days()).unwrap_or(u32::MAX);
+    let full_weeks = days / 7;
+    let mut count = full_weeks * 5;
+
+    // Walk the remaining partial week, at most six days
+    let mut date = start
+        .checked_add_days(Days::new(u64::from(full_weeks) * 7))
+        .unwrap_or(end);
+    while date < end {
+        date = date.succ_opt().unwrap_or(end);
+        if is_business_day(date) {
+            count += 1;
+        }
+    }
+    count
+}
+
+/// Whole calendar days since `submitted_at`.
+#[must_use]
+pub fn days_pending(submitted_at: DateTime<Utc>, now: DateTime<Utc>) -> u32 {
+    u32::try_from((now - submitted_at).num_days()).unwrap_or(0)
+}
+
+/// Whether a pending transaction should be escalated at `now`.
+///
+/// Due once it has waited at least `after_business_days` business days,
+/// and at most once per UTC calendar day after that.
+#[must_use]
+pub fn escalation_due(
+    submitted_at: DateTime<Utc>,
+    last_escalated_at: Option<DateTime<Utc>>,
+    now: DateTime<Utc>,
+    after_business_days: u32,
+) -> bool {
+    let waited = business_days_between(submitted_at.date_naive(), now.date_naive());
+    let escalated_today = last_escalated_at.is_some_and(|at| at.date_naive() >= now.date_naive());
+    waited >= after_business_days && !escalated_today
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use rstest::rstest;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    fn at(y: i32, m: u32, d: u32, h: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(y, m, d, h, 0, 0).unwrap()
+    }
+
+    // 2026-03-06 is a Friday
+    #[rstest]
+    #[case::same_day(date(2026, 3, 6), date(2026, 3, 6), 0)]
+    #[case::friday_to_saturday(date(2026, 3, 6), date(2026, 3, 7), 0)]
+    #[case::friday_to_sunday(date(2026, 3, 6), date(2026, 3, 8), 0)]
+    #[case::friday_to_monday(date(2026, 3, 6), date(2026, 3, 9), 1)]
+    #[case::friday_to_wednesday(date(2026, 3, 6), date(2026, 3, 11), 3)]
+    #[case::saturday_to_monday(date(2026, 3, 7), date(2026, 3, 9), 1)]
+    #[case::monday_to_friday(date(2026, 3, 9), date(2026, 3, 13), 4)]
+    #[case::one_week(date(2026, 3, 4), date(2026, 3, 11), 5)]
+    #[case::two_weeks_and_weekend(date(2026, 3, 6), date(2026, 3, 22), 10)]
+    #[case::end_before_start(date(2026, 3, 11), date(2026, 3, 6), 0)]
+    fn test_business_days_between(
+        #[case] start: NaiveDate,
+        #[case] end: NaiveDate,
+        #[case] expected: u32,
+    ) {
+        assert_eq!(business_days_between(start, end), expected);
+    }
+
+    #[test]
+    fn test_business_days_match_day_by_day_count() {
+        let start = date(2026, 1, 1);
+        for offset in 0..60 {
+            let end = start + chrono::Days::new(offset);
+            let expected = (1..=offset)
+                .filter(|d| is_business_day(start + chrono::Days::new(*d)))
+                .count();
+            assert_eq!(
+                business_days_between(start, end) as usize,
+                expected,
+                "{start} -> {end}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_days_pending_counts_whole_days() {
+        let submitted = at(2026, 3, 6, 15);
+        assert_eq!(days_pending(submitted, at(2026, 3, 7, 14)), 0);
+        assert_eq!(days_pending(submitted, at(2026, 3, 7, 15)), 1);
+        assert_eq!(days_pending(submitted, at(2026, 3, 16, 9)), 9);
+        assert_eq!(days_pending(submitted, at(2026, 3, 1, 0)), 0);
+    }
+
+    #[test]
+    fn test_escalation_waits_for_business_days_over_weekend() {
+        // Submitted Friday; three business days are reached on Wednesday
+        let submitted = at(2026, 3, 6, 17);
+        assert!(!escalation_due(submitted, None, at(2026, 3, 9, 9), 3));
+        assert!(!escalation_due(submitted, None, at(2026, 3, 10, 9), 3));
+        assert!(escalation_due(submitted, None, at(2026, 3, 11, 9), 3));
+    }
+
+    #[test]
+    fn test_escalation_at_most_once_per_day() {
+        let submitted = at(2026, 3, 2, 9);
+        let now = at(2026, 3, 11, 16);
+
+        assert!(!escalation_due(submitted, Some(at(2026, 3, 11, 8)), now, 3));
+        assert!(escalation_due(submitted, Some(at(2026, 3, 10, 23)), now, 3));
+    }
+}
@@ -58,10 +58,24 @@ impl UserRole {
     }
 }
 
+impl From<UserRole> for crate::auth::UserRole {
+    fn from(role: UserRole) -> Self {
+        match role {
+            UserRole::Viewer => Self::Viewer,
+            UserRole::Submitter => Self::Submitter,
+            UserRole::Approver => Self::Approver,
+            UserRole::Accountant => Self::Accountant,
+            UserRole::Admin => Self::Admin,
+            UserRole::Owner => Self::Owner,
+        }
+    }
+}
+
 /// An approval rule that determines who can approve transactions.
 ///
 /// Rules are matched by transaction type and amount range.
-/// When multiple rules match, the one with lowest priority value wins.
+/// When multiple rules match, the one with lowest priority value wins; rules
+/// with equal priority are ordered by name, then ID.
 #[derive(Debug, Clone)]
 pub struct ApprovalRule {
     /// Unique identifier for the rule.
@@ -78,6 +92,32 @@ pub struct ApprovalRule {
     pub required_role: String,
     /// Priority for rule selection (lower = higher priority).
     pub priority: i16,
+    /// Number of different users who must approve matching transactions.
+    pub required_approvals: u32,
+}
+
+/// Approval authority a member holds on another member's behalf.
+#[derive(Debug, Clone)]
+pub struct DelegatedAuthority {
+    /// The delegator's role as a string.
+    pub delegator_role: String,
+    /// The delegator's approval limit.
+    pub delegator_limit: Option<Decimal>,
+    /// The delegate's own approval limit.
+    pub delegate_limit: Option<Decimal>,
+    /// Cap set on the delegation itself.
+    pub amount_cap: Option<Decimal>,
+}
+
+/// What a transaction needs before it is approved.
+#[derive(Debug, Clone, Copy)]
+pub struct RequiredApproval<'a> {
+    /// The rule that applies, `None` when the default policy does.
+    pub rule: Option<&'a ApprovalRule>,
+    /// The role required to approve.
+    pub role: &'a str,
+    /// Number of different users who must approve.
+    pub approvals: u32,
 }
 
 /// Stateless engine for evaluating approval rules.
@@ -99,7 +139,46 @@ impl ApprovalEngine {
         transaction_type: &str,
         total_amount: Decimal,
     ) -> Option<String> {
-        let mut applicable: Vec<_> = rules
+        Self::find_rule(rules, transaction_type, total_amount).map(|r| r.required_role.clone())
+    }
+
+    /// Determine what a transaction requires before it is approved.
+    ///
+    /// Uses the matching rule; when none matches, the default policy of one
+    /// Approver applies. Approval and its previews both go through here.
+    #[must_use]
+    pub fn required_approval<'a>(
+        rules: &'a [ApprovalRule],
+        transaction_type: &str,
+        total_amount: Decimal,
+    ) -> RequiredApproval<'a> {
+        Self::find_rule(rules, transaction_type, total_amount).map_or(
+            RequiredApproval {
+                rule: None,
+                role: UserRole::Approver.as_str(),
+                approvals: 1,
+            },
+            |rule| RequiredApproval {
+                rule: Some(rule),
+                role: &rule.required_role,
+                approvals: rule.required_approvals,
+            },
+        )
+    }
+
+    /// Find the approval rule that applies to a transaction.
+    ///
+    /// # Returns
+    /// The matching rule with the lowest priority value, None if none match.
+    /// Ties go to the rule whose name sorts first, then to the lowest ID, so
+    /// the outcome does not depend on the order `rules` were loaded in.
+    #[must_use]
+    pub fn find_rule<'a>(
+        rules: &'a [ApprovalRule],
+        transaction_type: &str,
+        total_amount: Decimal,
+    ) -> Option<&'a ApprovalRule> {
+        rules
             .iter()
             .filter(|r| {
                 r.transaction_types.is_empty()
@@ -110,11 +189,8 @@ impl ApprovalEngine {
                 let below_max = r.max_amount.is_none_or(|max| total_amount <= max);
                 above_min && below_max
             })
-            .collect();
-
-        // Sort by priority (lower = higher priority)
-        applicable.sort_by_key(|r| r.priority);
-        applicable.first().map(|r| r.required_role.clone())
+            // Lower priority value = higher priority
+            .min_by(|a, b| (a.priority, &a.name, a.id).cmp(&(b.priority, &b.name, b.id)))
     }
 
     /// Check if a user can approve a transaction.
@@ -168,6 +244,42 @@ impl ApprovalEngine {
 
         Ok(())
     }
+
+    /// Check if a delegate can approve a transaction on the delegator's behalf.
+    ///
+    /// The delegator must be able to approve it, and the amount must also fit
+    /// the delegate's own approval limit and the delegation's cap.
+    ///
+    /// # Errors
+    /// * `Err(WorkflowError::InsufficientRole)` if the delegator's role is too low
+    /// * `Err(WorkflowError::ExceedsApprovalLimit)` if the amount exceeds any limit
+    pub fn can_approve_delegated(
+        authority: &DelegatedAuthority,
+        required_role: &str,
+        transaction_amount: Decimal,
+    ) -> Result<(), WorkflowError> {
+        Self::can_approve(
+            &authority.delegator_role,
+            authority.delegator_limit,
+            required_role,
+            transaction_amount,
+        )?;
+
+        let limit = [authority.delegate_limit, authority.amount_cap]
+            .into_iter()
+            .flatten()
+            .min();
+        if let Some(limit) = limit
+            && transaction_amount > limit
+        {
+            return Err(WorkflowError::ExceedsApprovalLimit {
+                amount: transaction_amount,
+                limit,
+            });
+        }
+
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -214,6 +326,7 @@ mod tests {
             transaction_types: vec!["expense".to_string()],
             required_role: "approver".to_string(),
             priority: 1,
+            required_approvals: 1,
         }];
 
         let result = ApprovalEngine::get_required_approval(&rules, "expense", Decimal::new(100, 0));
@@ -230,6 +343,7 @@ mod tests {
             transaction_types: vec!["expense".to_string()],
             required_role: "approver".to_string(),
             priority: 1,
+            required_approvals: 1,
         }];
 
         let result = ApprovalEngine::get_required_approval(&rules, "invoice", Decimal::new(100, 0));
@@ -247,6 +361,7 @@ mod tests {
                 transaction_types: vec!["expense".to_string()],
                 required_role: "approver".to_string(),
                 priority: 1,
+                required_approvals: 1,
             },
             ApprovalRule {
                 id: Uuid::new_v4(),
@@ -256,6 +371,7 @@ mod tests {
                 transaction_types: vec!["expense".to_string()],
                 required_role: "admin".to_string(),
                 priority: 2,
+                required_approvals: 1,
             },
         ];
 
@@ -280,6 +396,7 @@ mod tests {
                 transaction_types: vec!["expense".to_string()],
                 required_role: "admin".to_string(),
                 priority: 10,
+                required_approvals: 1,
             },
             ApprovalRule {
                 id: Uuid::new_v4(),
@@ -289,6 +406,7 @@ mod tests {
                 transaction_types: vec!["expense".to_string()],
                 required_role: "approver".to_string(),
                 priority: 1,
+                required_approvals: 1,
             },
         ];
 
@@ -296,6 +414,55 @@ mod tests {
         assert_eq!(result, Some("approver".to_string()));
     }
 
+    #[test]
+    fn test_find_rule_breaks_priority_ties_by_name_then_id() {
+        let rule = |name: &str, id: u128, role: &str| ApprovalRule {
+            id: Uuid::from_u128(id),
+            name: name.to_string(),
+            min_amount: Some(Decimal::new(10_000, 0)),
+            max_amount: None,
+            transaction_types: vec!["bill".to_string()],
+            required_role: role.to_string(),
+            priority: 5,
+            required_approvals: 1,
+        };
+        let mut rules = vec![
+            rule("Large bills", 2, "admin"),
+            rule("Bills over 10k", 3, "accountant"),
+            rule("Bills over 10k", 1, "owner"),
+        ];
+
+        for _ in 0..rules.len() {
+            let found = ApprovalEngine::find_rule(&rules, "bill", Decimal::new(75_000, 0));
+            assert_eq!(found.map(|r| r.id), Some(Uuid::from_u128(1)));
+            rules.rotate_left(1);
+        }
+    }
+
+    #[test]
+    fn test_required_approval_falls_back_to_one_approver() {
+        let rules = vec![ApprovalRule {
+            id: Uuid::new_v4(),
+            name: "Dual control".to_string(),
+            min_amount: Some(Decimal::new(50_000, 0)),
+            max_amount: None,
+            transaction_types: vec!["bill".to_string()],
+            required_role: "accountant".to_string(),
+            priority: 1,
+            required_approvals: 2,
+        }];
+
+        let matched = ApprovalEngine::required_approval(&rules, "bill", Decimal::new(75_000, 0));
+        assert_eq!(matched.rule.map(|r| r.id), Some(rules[0].id));
+        assert_eq!(matched.role, "accountant");
+        assert_eq!(matched.approvals, 2);
+
+        let default = ApprovalEngine::required_approval(&rules, "bill", Decimal::new(500, 0));
+        assert!(default.rule.is_none());
+        assert_eq!(default.role, "approver");
+        assert_eq!(default.approvals, 1);
+    }
+
     #[test]
     fn test_can_approve_sufficient_role() {
         let result = ApprovalEngine::can_approve("admin", None, "approver", Decimal::new(1000, 0));
@@ -359,4 +526,61 @@ mod tests {
         );
         assert!(result.is_ok());
     }
+
+    fn delegation(
+        delegator_role: &str,
+        delegator_limit: Option<i64>,
+        delegate_limit: Option<i64>,
+        amount_cap: Option<i64>,
+    ) -> DelegatedAuthority {
+        DelegatedAuthority {
+            delegator_role: delegator_role.to_string(),
+            delegator_limit: delegator_limit.map(|l| Decimal::new(l, 0)),
+            delegate_limit: delegate_limit.map(|l| Decimal::new(l, 0)),
+            amount_cap: amount_cap.map(|l| Decimal::new(l, 0)),
+        }
+    }
+
+    #[test]
+    fn test_can_approve_delegated_uses_delegator_role() {
+        let authority = delegation("accountant", None, None, None);
+        assert!(
+            ApprovalEngine::can_approve_delegated(&authority, "accountant", Decimal::new(100, 0))
+                .is_ok()
+        );
+        assert!(matches!(
+            ApprovalEngine::can_approve_delegated(&authority, "admin", Decimal::new(100, 0)),
+            Err(WorkflowError::InsufficientRole { .. })
+        ));
+    }
+
+    #[test]
+    fn test_can_approve_delegated_uses_lowest_limit() {
+        // Delegator limit 5000, delegate limit 3000, cap 4000: 3000 applies
+        let authority = delegation("approver", Some(5000), Some(3000), Some(4000));
+        assert!(
+            ApprovalEngine::can_approve_delegated(&authority, "approver", Decimal::new(3000, 0))
+                .is_ok()
+        );
+        assert!(matches!(
+            ApprovalEngine::can_approve_delegated(&authority, "approver", Decimal::new(3001, 0)),
+            Err(WorkflowError::ExceedsApprovalLimit { limit, .. }) if limit == Decimal::new(3000, 0)
+        ));
+
+        // The delegator's own limit still applies
+        let authority = delegation("approver", Some(1000), None, Some(4000));
+        assert!(matches!(
+            ApprovalEngine::can_approve_delegated(&authority, "approver", Decimal::new(2000, 0)),
+            Err(WorkflowError::ExceedsApprovalLimit { limit, .. }) if limit == Decimal::new(1000, 0)
+        ));
+    }
+
+    #[test]
+    fn test_can_approve_delegated_cap_applies_to_unlimited_roles() {
+        let authority = delegation("owner", None, None, Some(500));
+        assert!(matches!(
+            ApprovalEngine::can_approve_delegated(&authority, "approver", Decimal::new(501, 0)),
+            Err(WorkflowError::ExceedsApprovalLimit { .. })
+        ));
+    }
 }
@@ -51,6 +51,7 @@ proptest! {
                 transaction_types: vec!["expense".to_string()],
                 required_role: "admin".to_string(),
                 priority: 10,
+                required_approvals: 1,
             },
             ApprovalRule {
                 id: Uuid::new_v4(),
@@ -60,6 +61,7 @@ proptest! {
                 transaction_types: vec!["expense".to_string()],
                 required_role: "approver".to_string(),
                 priority: 1,
+                required_approvals: 1,
             },
             ApprovalRule {
                 id: Uuid::new_v4(),
@@ -69,6 +71,7 @@ proptest! {
                 transaction_types: vec!["expense".to_string()],
                 required_role: "accountant".to_string(),
                 priority: 5,
+                required_approvals: 1,
             },
         ];
 
@@ -94,6 +97,7 @@ proptest! {
                 transaction_types: vec!["expense".to_string()],
                 required_role: "submitter".to_string(),
                 priority: 1,
+                required_approvals: 1,
             },
             ApprovalRule {
                 id: Uuid::new_v4(),
@@ -103,6 +107,7 @@ proptest! {
                 transaction_types: vec!["expense".to_string()],
                 required_role: "approver".to_string(),
                 priority: 1,
+                required_approvals: 1,
             },
             ApprovalRule {
                 id: Uuid::new_v4(),
@@ -112,6 +117,7 @@ proptest! {
                 transaction_types: vec!["expense".to_string()],
                 required_role: "admin".to_string(),
                 priority: 1,
+                required_approvals: 1,
             },
         ];
 
@@ -267,6 +273,7 @@ mod edge_case_tests {
             transaction_types: vec!["expense".to_string()],
             required_role: "approver".to_string(),
             priority: 1,
+            required_approvals: 1,
         }];
 
         // Invoice type doesn't match
@@ -284,6 +291,7 @@ mod edge_case_tests {
             transaction_types: vec!["expense".to_string()],
             required_role: "approver".to_string(),
             priority: 1,
+            required_approvals: 1,
         }];
 
         // Exactly at min_amount should match
@@ -305,6 +313,7 @@ mod edge_case_tests {
             transaction_types: vec!["expense".to_string()],
             required_role: "approver".to_string(),
             priority: 1,
+            required_approvals: 1,
         }];
 
         // Exactly at max_amount should match
@@ -328,6 +337,7 @@ mod edge_case_tests {
             transaction_types: vec!["expense".to_string()],
             required_role: "approver".to_string(),
             priority: 1,
+            required_approvals: 1,
         }];
 
         // Very small amount should match (no lower bound)
@@ -345,6 +355,7 @@ mod edge_case_tests {
             transaction_types: vec!["expense".to_string()],
             required_role: "approver".to_string(),
             priority: 1,
+            required_approvals: 1,
         }];
 
         // Very large amount should match (no upper bound)
@@ -363,6 +374,7 @@ mod edge_case_tests {
             transaction_types: vec![], // Empty = matches all
             required_role: "approver".to_string(),
             priority: 1,
+            required_approvals: 1,
         }];
 
         // Should match any transaction type
@@ -0,0 +1,157 @@
+//! Review claims on pending transactions.
+//!
+//! An approver claims a pending transaction while reviewing it so others can
+//! see someone is already on it. A claim lasts [`CLAIM_TTL_MINUTES`] and can
+//! be renewed by the reviewer. While it stands, only the reviewer, an owner
+//! or an admin may approve or reject the transaction. Claims are advisory:
+//! nothing else in the workflow looks at them.
+
+use chrono::{DateTime, Duration, Utc};
+use uuid::Uuid;
+
+use crate::workflow::approval::UserRole;
+use crate::workflow::error::WorkflowError;
+
+/// How long a claim lasts, in minutes.
+pub const CLAIM_TTL_MINUTES: i64 = 10;
+
+/// A reviewer's claim on a pending transaction.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReviewClaim {
+    /// User reviewing the transaction.
+    pub reviewer_id: Uuid,
+    /// Reviewer's display name, shown to those who are turned away.
+    pub reviewer_name: String,
+    /// When the claim lapses.
+    pub expires_at: DateTime<Utc>,
+}
+
+impl ReviewClaim {
+    /// Starts a claim by `reviewer_id` at `now`.
+    #[must_use]
+    pub fn new(reviewer_id: Uuid, reviewer_name: String, now: DateTime<Utc>) -> Self {
+        Self {
+            reviewer_id,
+            reviewer_name,
+            expires_at: now + Duration::minutes(CLAIM_TTL_MINUTES),
+        }
+    }
+
+    /// Whether the claim still stands at `now`.
+    #[must_use]
+    pub fn is_active(&self, now: DateTime<Utc>) -> bool {
+        now < self.expires_at
+    }
+
+    /// Whether `user_id` may take over or renew the claim at `now`.
+    #[must_use]
+    pub fn can_claim(&self, user_id: Uuid, now: DateTime<Utc>) -> bool {
+        !self.is_active(now) || self.reviewer_id == user_id
+    }
+
+    /// Checks that `user_id` may take over or renew the claim at `now`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ClaimedByOther` when someone else holds an active claim.
+    pub fn check_claim(&self, user_id: Uuid, now: DateTime<Utc>) -> Result<(), WorkflowError> {
+        if self.can_claim(user_id, now) {
+            Ok(())
+        } else {
+            Err(self.conflict())
+        }
+    }
+
+    /// Checks that `user_id` may approve or reject while the claim stands.
+    ///
+    /// Owners and admins act through another reviewer's claim.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ClaimedByOther` when someone else holds an active claim and
+    /// the user is neither an owner nor an admin.
+    pub fn check_action(
+        &self,
+        user_id: Uuid,
+        role: Option<UserRole>,
+        now: DateTime<Utc>,
+    ) -> Result<(), WorkflowError> {
+        if role.is_some_and(|r| r >= UserRole::Admin) {
+            return Ok(());
+        }
+        self.check_claim(user_id, now)
+    }
+
+    fn conflict(&self) -> WorkflowError {
+        WorkflowError::ClaimedByOther {
+            reviewer_id: self.reviewer_id,
+            reviewer_name: self.reviewer_name.clone(),
+            until: self.expires_at,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn at(minute: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2026, 3, 2, 9, minute, 0).unwrap()
+    }
+
+    #[test]
+    fn test_claim_lapses_after_ttl() {
+        let claim = ReviewClaim::new(Uuid::new_v4(), "Ana".to_string(), at(0));
+
+        assert!(claim.is_active(at(9)));
+        assert!(!claim.is_active(at(10)));
+    }
+
+    #[test]
+    fn test_only_reviewer_renews_an_active_claim() {
+        let reviewer = Uuid::new_v4();
+        let other = Uuid::new_v4();
+        let claim = ReviewClaim::new(reviewer, "Ana".to_string(), at(0));
+
+        assert!(claim.can_claim(reviewer, at(5)));
+        assert!(!claim.can_claim(other, at(5)));
+        assert!(claim.can_claim(other, at(10)));
+        assert!(claim.check_claim(other, at(5)).is_err());
+    }
+
+    #[test]
+    fn test_admins_act_through_an_active_claim() {
+        let reviewer = Uuid::new_v4();
+        let other = Uuid::new_v4();
+        let claim = ReviewClaim::new(reviewer, "Ana".to_string(), at(0));
+
+        assert!(
+            claim
+                .check_action(reviewer, Some(UserRole::Approver), at(5))
+                .is_ok()
+        );
+        assert!(
+            claim
+                .check_action(other, Some(UserRole::Admin), at(5))
+                .is_ok()
+        );
+        assert!(
+            claim
+                .check_action(other, Some(UserRole::Owner), at(5))
+                .is_ok()
+        );
+        assert!(
+            claim
+                .check_action(other, Some(UserRole::Approver), at(10))
+                .is_ok()
+        );
+
+        let err = claim
+            .check_action(other, Some(UserRole::Accountant), at(5))
+            .unwrap_err();
+        assert_eq!(err.error_code(), "CLAIMED_BY_OTHER");
+        assert_eq!(err.status_code(), 409);
+        assert!(err.to_string().contains("Ana"));
+    }
+}
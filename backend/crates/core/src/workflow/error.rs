@@ -3,6 +3,7 @@
 //! This module defines all error types that can occur during
 //! workflow operations such as status transitions, approvals, and voids.
 
+use chrono::{DateTime, Utc};
 use rust_decimal::Decimal;
 use thiserror::Error;
 use uuid::Uuid;
@@ -10,7 +11,7 @@ use uuid::Uuid;
 use crate::workflow::types::TransactionStatus;
 
 /// Errors that can occur during workflow operations.
-#[derive(Debug, Error)]
+#[derive(Debug, Clone, Error)]
 pub enum WorkflowError {
     /// Attempted an invalid status transition.
     #[error("Invalid status transition from {from} to {to}")]
@@ -29,6 +30,10 @@ pub enum WorkflowError {
     #[error("Cannot modify voided transaction")]
     CannotModifyVoided,
 
+    /// Attempted to delete a transaction that is no longer a draft.
+    #[error("Can only delete draft transactions")]
+    CanOnlyDeleteDraft,
+
     /// User is not authorized to approve the transaction.
     #[error("User is not authorized to approve this transaction")]
     NotAuthorizedToApprove,
@@ -67,6 +72,13 @@ pub enum WorkflowError {
         required_role: String,
     },
 
+    /// User has already approved the transaction.
+    #[error("User {user_id} has already approved this transaction")]
+    AlreadyApproved {
+        /// The user who attempted to approve again.
+        user_id: Uuid,
+    },
+
     /// Transaction not found.
     #[error("Transaction {0} not found")]
     TransactionNotFound(Uuid),
@@ -79,6 +91,37 @@ pub enum WorkflowError {
     #[error("Rejection reason is required")]
     RejectionReasonRequired,
 
+    /// Transaction has entries locked by a completed reconciliation.
+    #[error(
+        "Transaction has entries in completed reconciliation {reconciliation_id}; reopen the reconciliation first"
+    )]
+    EntryReconciled {
+        /// The completed reconciliation holding the entry.
+        reconciliation_id: Uuid,
+    },
+
+    /// Another reviewer holds an active claim on the transaction.
+    #[error("Transaction is being reviewed by {reviewer_name} until {until}")]
+    ClaimedByOther {
+        /// The reviewer holding the claim.
+        reviewer_id: Uuid,
+        /// The reviewer's display name.
+        reviewer_name: String,
+        /// When the claim lapses.
+        until: DateTime<Utc>,
+    },
+
+    /// Only pending transactions can be claimed for review.
+    #[error("Transaction is {status}, not pending")]
+    NotPending {
+        /// The current status.
+        status: TransactionStatus,
+    },
+
+    /// The transaction's fiscal period is closed, so it cannot be reversed.
+    #[error("Fiscal period is closed")]
+    PeriodClosed,
+
     /// Database error.
     #[error("Database error: {0}")]
     Database(String),
@@ -92,8 +135,11 @@ impl WorkflowError {
             Self::InvalidTransition { .. }
             | Self::CannotModifyPosted
             | Self::CannotModifyVoided
+            | Self::CanOnlyDeleteDraft
             | Self::VoidReasonRequired
-            | Self::RejectionReasonRequired => 400,
+            | Self::RejectionReasonRequired
+            | Self::NotPending { .. }
+            | Self::PeriodClosed => 400,
 
             Self::NotAuthorizedToApprove
             | Self::NotAuthorizedToApproveUser { .. }
@@ -102,6 +148,10 @@ impl WorkflowError {
 
             Self::TransactionNotFound(_) | Self::NoApprovalRuleFound { .. } => 404,
 
+            Self::AlreadyApproved { .. }
+            | Self::EntryReconciled { .. }
+            | Self::ClaimedByOther { .. } => 409,
+
             Self::Database(_) => 500,
         }
     }
@@ -113,15 +163,21 @@ impl WorkflowError {
             Self::InvalidTransition { .. } => "INVALID_TRANSITION",
             Self::CannotModifyPosted => "CANNOT_MODIFY_POSTED",
             Self::CannotModifyVoided => "CANNOT_MODIFY_VOIDED",
+            Self::CanOnlyDeleteDraft => "CAN_ONLY_DELETE_DRAFT",
             Self::NotAuthorizedToApprove | Self::NotAuthorizedToApproveUser { .. } => {
                 "NOT_AUTHORIZED_TO_APPROVE"
             }
             Self::ExceedsApprovalLimit { .. } => "EXCEEDS_APPROVAL_LIMIT",
             Self::NoApprovalRuleFound { .. } => "NO_APPROVAL_RULE_FOUND",
             Self::InsufficientRole { .. } => "INSUFFICIENT_ROLE",
+            Self::AlreadyApproved { .. } => "ALREADY_APPROVED",
             Self::TransactionNotFound(_) => "TRANSACTION_NOT_FOUND",
             Self::VoidReasonRequired => "VOID_REASON_REQUIRED",
             Self::RejectionReasonRequired => "REJECTION_REASON_REQUIRED",
+            Self::EntryReconciled { .. } => "ENTRY_RECONCILED",
+            Self::ClaimedByOther { .. } => "CLAIMED_BY_OTHER",
+            Self::NotPending { .. } => "NOT_PENDING",
+            Self::PeriodClosed => "PERIOD_CLOSED",
             Self::Database(_) => "DATABASE_ERROR",
         }
     }
@@ -157,6 +213,13 @@ mod tests {
         assert_eq!(err.error_code(), "CANNOT_MODIFY_VOIDED");
     }
 
+    #[test]
+    fn test_can_only_delete_draft_error() {
+        let err = WorkflowError::CanOnlyDeleteDraft;
+        assert_eq!(err.status_code(), 400);
+        assert_eq!(err.error_code(), "CAN_ONLY_DELETE_DRAFT");
+    }
+
     #[test]
     fn test_not_authorized_error() {
         let err = WorkflowError::NotAuthorizedToApprove;
@@ -213,4 +276,40 @@ mod tests {
         assert_eq!(err.status_code(), 400);
         assert_eq!(err.error_code(), "REJECTION_REASON_REQUIRED");
     }
+
+    #[test]
+    fn test_entry_reconciled_error() {
+        let err = WorkflowError::EntryReconciled {
+            reconciliation_id: Uuid::nil(),
+        };
+        assert_eq!(err.status_code(), 409);
+        assert_eq!(err.error_code(), "ENTRY_RECONCILED");
+        assert!(err.to_string().contains("reopen"));
+    }
+
+    #[test]
+    fn test_not_pending_error() {
+        let err = WorkflowError::NotPending {
+            status: TransactionStatus::Approved,
+        };
+        assert_eq!(err.status_code(), 400);
+        assert_eq!(err.error_code(), "NOT_PENDING");
+        assert!(err.to_string().contains("approved"));
+    }
+
+    #[test]
+    fn test_period_closed_error() {
+        let err = WorkflowError::PeriodClosed;
+        assert_eq!(err.status_code(), 400);
+        assert_eq!(err.error_code(), "PERIOD_CLOSED");
+    }
+
+    #[test]
+    fn test_already_approved_error() {
+        let err = WorkflowError::AlreadyApproved {
+            user_id: Uuid::nil(),
+        };
+        assert_eq!(err.status_code(), 409);
+        assert_eq!(err.error_code(), "ALREADY_APPROVED");
+    }
 }
@@ -5,13 +5,17 @@
 //!
 //! # Modules
 //!
-//! - `types` - Workflow domain types (TransactionStatus, WorkflowAction)
+//! - `types` - Workflow domain types (TransactionStatus, VoidReasonCode, WorkflowAction)
 //! - `error` - Workflow-specific error types
 //! - `service` - State transition logic
 //! - `approval` - Approval rules engine
 //! - `reversal` - Void and reversing entry creation
+//! - `aging` - Approval queue aging and escalation timing
+//! - `claim` - Review claims on pending transactions
 
+pub mod aging;
 pub mod approval;
+pub mod claim;
 pub mod error;
 pub mod reversal;
 pub mod service;
@@ -24,8 +28,19 @@ mod reversal_props;
 #[cfg(test)]
 mod service_props;
 
-pub use approval::{ApprovalEngine, ApprovalRule, UserRole};
+pub use aging::{business_days_between, days_pending, escalation_due, is_business_day};
+pub use approval::{ApprovalEngine, ApprovalRule, DelegatedAuthority, RequiredApproval, UserRole};
+pub use claim::{CLAIM_TTL_MINUTES, ReviewClaim};
 pub use error::WorkflowError;
-pub use reversal::{OriginalEntry, ReversalInput, ReversalOutput, ReversalService};
-pub use service::WorkflowService;
-pub use types::{TransactionStatus, WorkflowAction};
+pub use reversal::{
+    CheckedEntry, EntryMismatch, MatchedEntryPair, OriginalEntry, ReversalComparison,
+    ReversalInput, ReversalMismatchField, ReversalOutput, ReversalService, compare_reversal,
+};
+pub use service::{
+    ActionContext, BulkApprovalDecision, BulkApprovalItem, BulkApprover, PlannedApproval,
+    WorkflowService,
+};
+pub use types::{
+    ActionAvailability, ApprovalProgress, TransactionStatus, VoidReasonCode, WorkflowAction,
+    WorkflowActionKind,
+};
@@ -2,24 +2,32 @@
 //!
 //! This module implements the creation of reversing entries
 //! when voiding posted transactions, following accounting best practices.
+//! [`compare_reversal`] checks after the fact that a stored reversal mirrors
+//! its original.
 
 use rust_decimal::Decimal;
+use serde::Serialize;
 use uuid::Uuid;
 
 use crate::ledger::types::{EntryType, LedgerEntryInput};
+use crate::workflow::types::VoidReasonCode;
 
 /// Input for creating a reversing transaction.
 #[derive(Debug, Clone)]
 pub struct ReversalInput {
     /// The ID of the original transaction being voided.
     pub original_transaction_id: Uuid,
+    /// The original transaction's reference number, if it has one.
+    pub original_reference: Option<String>,
     /// The original ledger entries to reverse.
     pub original_entries: Vec<OriginalEntry>,
     /// The fiscal period for the reversing transaction.
     pub fiscal_period_id: Uuid,
     /// The user voiding the transaction.
     pub voided_by: Uuid,
-    /// The reason for voiding.
+    /// The categorized reason for voiding.
+    pub void_reason_code: VoidReasonCode,
+    /// The reason for voiding, possibly empty unless the code is `Other`.
     pub void_reason: String,
 }
 
@@ -55,6 +63,8 @@ pub struct ReversalOutput {
     pub reversing_entries: Vec<LedgerEntryInput>,
     /// Description for the reversing transaction.
     pub description: String,
+    /// The categorized reason for voiding, carried from the input.
+    pub void_reason_code: VoidReasonCode,
 }
 
 /// Stateless service for creating reversing entries.
@@ -97,6 +107,7 @@ impl ReversalService {
                         entry.memo.clone().unwrap_or_default()
                     )),
                     dimensions: entry.dimensions.clone(),
+                    exchange_rate: None,
                 }
             })
             .collect();
@@ -104,13 +115,33 @@ impl ReversalService {
         ReversalOutput {
             reversing_transaction_id: Uuid::new_v4(),
             reversing_entries,
-            description: format!(
-                "Reversal of transaction {}. Reason: {}",
-                input.original_transaction_id, input.void_reason
-            ),
+            description: Self::description(input),
+            void_reason_code: input.void_reason_code,
         }
     }
 
+    /// Builds the reversing transaction's description.
+    ///
+    /// The description starts with `[VOID <reference> <code>]`, where the
+    /// reference is the original reference number or, failing that, its ID,
+    /// so reversals can be found and grouped by text search alone.
+    #[must_use]
+    pub fn description(input: &ReversalInput) -> String {
+        let reference = input
+            .original_reference
+            .clone()
+            .unwrap_or_else(|| input.original_transaction_id.to_string());
+        let mut description = format!(
+            "[VOID {reference} {}] Reversal of transaction {reference}.",
+            input.void_reason_code
+        );
+        if !input.void_reason.is_empty() {
+            description.push_str(" Reason: ");
+            description.push_str(&input.void_reason);
+        }
+        description
+    }
+
     /// Validate that original entries are balanced.
     ///
     /// A valid transaction must have total debits equal to total credits.
@@ -130,6 +161,164 @@ impl ReversalService {
     }
 }
 
+/// A stored ledger entry as seen by the reversal check.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct CheckedEntry {
+    /// The ledger entry ID.
+    pub entry_id: Uuid,
+    /// The account ID.
+    pub account_id: Uuid,
+    /// The source currency code.
+    pub source_currency: String,
+    /// The amount in source currency.
+    pub source_amount: Decimal,
+    /// The amount in functional currency.
+    pub functional_amount: Decimal,
+    /// The side the entry is on.
+    pub entry_type: EntryType,
+}
+
+impl CheckedEntry {
+    /// Returns true if the entry posts nothing.
+    fn is_zero(&self) -> bool {
+        self.source_amount.is_zero() && self.functional_amount.is_zero()
+    }
+}
+
+/// A field that differs between an original entry and its reversal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReversalMismatchField {
+    /// The source currencies differ.
+    SourceCurrency,
+    /// The source amounts differ.
+    SourceAmount,
+    /// The functional amounts differ.
+    FunctionalAmount,
+    /// Both entries are on the same side.
+    EntryType,
+}
+
+/// An original entry and a reversal entry on the same account that do not
+/// mirror each other.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct EntryMismatch {
+    /// The original entry.
+    pub original: CheckedEntry,
+    /// The reversal entry it was paired with.
+    pub reversal: CheckedEntry,
+    /// The fields that differ.
+    pub fields: Vec<ReversalMismatchField>,
+}
+
+/// An original entry and the reversal entry that exactly mirrors it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct MatchedEntryPair {
+    /// The account both entries post to.
+    pub account_id: Uuid,
+    /// The original entry ID.
+    pub original_entry_id: Uuid,
+    /// The reversal entry ID.
+    pub reversal_entry_id: Uuid,
+}
+
+/// Result of comparing a voided transaction with its reversal.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct ReversalComparison {
+    /// Pairs that mirror each other exactly.
+    pub matched: Vec<MatchedEntryPair>,
+    /// Pairs on the same account that differ.
+    pub mismatched: Vec<EntryMismatch>,
+    /// Original entries with no reversal entry on their account.
+    pub only_in_original: Vec<CheckedEntry>,
+    /// Reversal entries with no original entry on their account.
+    pub only_in_reversal: Vec<CheckedEntry>,
+}
+
+impl ReversalComparison {
+    /// Returns true if every entry has an exact mirror on the other side.
+    #[must_use]
+    pub fn is_exact_mirror(&self) -> bool {
+        self.mismatched.is_empty()
+            && self.only_in_original.is_empty()
+            && self.only_in_reversal.is_empty()
+    }
+}
+
+/// Compares a transaction's entries with those of its reversal.
+///
+/// Entries are paired by account in three passes: exact mirrors first, then
+/// entries with the same source amount, then any remaining entry on the
+/// same account. Pairs from the last two passes are reported with the
+/// fields that differ. Lines that post nothing on either side are ignored.
+#[must_use]
+pub fn compare_reversal(
+    original: &[CheckedEntry],
+    reversal: &[CheckedEntry],
+) -> ReversalComparison {
+    let mut originals: Vec<Option<&CheckedEntry>> =
+        original.iter().filter(|e| !e.is_zero()).map(Some).collect();
+    let mut reversals: Vec<Option<&CheckedEntry>> =
+        reversal.iter().filter(|e| !e.is_zero()).map(Some).collect();
+    let mut comparison = ReversalComparison::default();
+
+    let passes: [fn(&CheckedEntry, &CheckedEntry) -> bool; 3] = [
+        |o, r| mismatch_fields(o, r).is_empty(),
+        |o, r| o.source_amount == r.source_amount,
+        |_, _| true,
+    ];
+    for pass in passes {
+        for slot in &mut originals {
+            let Some(o) = *slot else { continue };
+            let Some(r) = reversals
+                .iter_mut()
+                .find(|r| r.is_some_and(|r| r.account_id == o.account_id && pass(o, r)))
+                .and_then(Option::take)
+            else {
+                continue;
+            };
+            *slot = None;
+
+            let fields = mismatch_fields(o, r);
+            if fields.is_empty() {
+                comparison.matched.push(MatchedEntryPair {
+                    account_id: o.account_id,
+                    original_entry_id: o.entry_id,
+                    reversal_entry_id: r.entry_id,
+                });
+            } else {
+                comparison.mismatched.push(EntryMismatch {
+                    original: o.clone(),
+                    reversal: r.clone(),
+                    fields,
+                });
+            }
+        }
+    }
+
+    comparison.only_in_original = originals.into_iter().flatten().cloned().collect();
+    comparison.only_in_reversal = reversals.into_iter().flatten().cloned().collect();
+    comparison
+}
+
+/// Fields in which `reversal` fails to mirror `original`.
+fn mismatch_fields(original: &CheckedEntry, reversal: &CheckedEntry) -> Vec<ReversalMismatchField> {
+    let mut fields = Vec::new();
+    if original.source_currency != reversal.source_currency {
+        fields.push(ReversalMismatchField::SourceCurrency);
+    }
+    if original.source_amount != reversal.source_amount {
+        fields.push(ReversalMismatchField::SourceAmount);
+    }
+    if original.functional_amount != reversal.functional_amount {
+        fields.push(ReversalMismatchField::FunctionalAmount);
+    }
+    if original.entry_type == reversal.entry_type {
+        fields.push(ReversalMismatchField::EntryType);
+    }
+    fields
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -166,9 +355,11 @@ mod tests {
         let entries = create_balanced_entries();
         let input = ReversalInput {
             original_transaction_id: Uuid::new_v4(),
+            original_reference: None,
             original_entries: entries,
             fiscal_period_id: Uuid::new_v4(),
             voided_by: Uuid::new_v4(),
+            void_reason_code: VoidReasonCode::Other,
             void_reason: "Duplicate entry".to_string(),
         };
 
@@ -198,9 +389,11 @@ mod tests {
 
         let input = ReversalInput {
             original_transaction_id: Uuid::new_v4(),
+            original_reference: None,
             original_entries: entries,
             fiscal_period_id: Uuid::new_v4(),
             voided_by: Uuid::new_v4(),
+            void_reason_code: VoidReasonCode::Other,
             void_reason: "Error".to_string(),
         };
 
@@ -214,9 +407,11 @@ mod tests {
     fn test_create_reversing_entries_description() {
         let input = ReversalInput {
             original_transaction_id: Uuid::new_v4(),
+            original_reference: None,
             original_entries: create_balanced_entries(),
             fiscal_period_id: Uuid::new_v4(),
             voided_by: Uuid::new_v4(),
+            void_reason_code: VoidReasonCode::Other,
             void_reason: "Duplicate entry".to_string(),
         };
 
@@ -226,6 +421,36 @@ mod tests {
         assert!(output.description.contains("Duplicate entry"));
     }
 
+    #[test]
+    fn test_reversal_description_prefix() {
+        let mut input = ReversalInput {
+            original_transaction_id: Uuid::new_v4(),
+            original_reference: Some("INV-001".to_string()),
+            original_entries: create_balanced_entries(),
+            fiscal_period_id: Uuid::new_v4(),
+            voided_by: Uuid::new_v4(),
+            void_reason_code: VoidReasonCode::WrongAmount,
+            void_reason: String::new(),
+        };
+
+        let output = ReversalService::create_reversing_entries(&input);
+        assert_eq!(
+            output.description,
+            "[VOID INV-001 wrong_amount] Reversal of transaction INV-001."
+        );
+        assert_eq!(output.void_reason_code, VoidReasonCode::WrongAmount);
+
+        // Without a reference number the original ID stands in
+        input.original_reference = None;
+        input.void_reason = "Keyed 1000 instead of 100".to_string();
+        let description = ReversalService::description(&input);
+        assert!(description.starts_with(&format!(
+            "[VOID {} wrong_amount]",
+            input.original_transaction_id
+        )));
+        assert!(description.ends_with("Reason: Keyed 1000 instead of 100"));
+    }
+
     #[test]
     fn test_validate_reversal_balanced() {
         let entries = create_balanced_entries();
@@ -311,9 +536,11 @@ mod tests {
 
         let input = ReversalInput {
             original_transaction_id: Uuid::new_v4(),
+            original_reference: None,
             original_entries: entries,
             fiscal_period_id: Uuid::new_v4(),
             voided_by: Uuid::new_v4(),
+            void_reason_code: VoidReasonCode::Other,
             void_reason: "Test".to_string(),
         };
 
@@ -326,4 +553,36 @@ mod tests {
         // Third was credit, should become debit
         assert_eq!(output.reversing_entries[2].entry_type, EntryType::Debit);
     }
+
+    #[test]
+    fn test_compare_reversal_reports_amount_mismatch() {
+        let account_id = Uuid::new_v4();
+        let checked = |entry_type, functional_amount| CheckedEntry {
+            entry_id: Uuid::new_v4(),
+            account_id,
+            source_currency: "EUR".to_string(),
+            source_amount: Decimal::new(10000, 2),
+            functional_amount,
+            entry_type,
+        };
+        let original = vec![checked(EntryType::Debit, Decimal::new(10850, 2))];
+        // Reversal booked the source amount as the functional amount
+        let reversal = vec![checked(EntryType::Credit, Decimal::new(10000, 2))];
+
+        let comparison = compare_reversal(&original, &reversal);
+
+        assert!(!comparison.is_exact_mirror());
+        assert!(comparison.matched.is_empty());
+        assert_eq!(
+            comparison.mismatched[0].fields,
+            vec![ReversalMismatchField::FunctionalAmount]
+        );
+
+        // Zero lines post nothing and need no mirror
+        let mut original = original;
+        original.push(checked(EntryType::Debit, Decimal::ZERO));
+        original[1].source_amount = Decimal::ZERO;
+        let comparison = compare_reversal(&original, &[]);
+        assert_eq!(comparison.only_in_original.len(), 1);
+    }
 }
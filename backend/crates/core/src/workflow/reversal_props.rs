@@ -8,7 +8,11 @@ use rust_decimal::Decimal;
 use uuid::Uuid;
 
 use crate::ledger::types::EntryType;
-use crate::workflow::reversal::{OriginalEntry, ReversalInput, ReversalService};
+use crate::workflow::reversal::{
+    CheckedEntry, OriginalEntry, ReversalInput, ReversalMismatchField, ReversalService,
+    compare_reversal,
+};
+use crate::workflow::types::VoidReasonCode;
 
 /// Strategy for generating random UUIDs.
 fn arb_uuid() -> impl Strategy<Value = Uuid> {
@@ -69,6 +73,52 @@ fn arb_balanced_entries() -> impl Strategy<Value = Vec<OriginalEntry>> {
     ]
 }
 
+/// Original entries as stored, with fresh entry IDs.
+fn checked_originals(entries: &[OriginalEntry]) -> Vec<CheckedEntry> {
+    entries
+        .iter()
+        .map(|e| CheckedEntry {
+            entry_id: Uuid::new_v4(),
+            account_id: e.account_id,
+            source_currency: e.source_currency.clone(),
+            source_amount: e.source_amount,
+            functional_amount: e.functional_amount,
+            entry_type: if e.debit > Decimal::ZERO {
+                EntryType::Debit
+            } else {
+                EntryType::Credit
+            },
+        })
+        .collect()
+}
+
+/// Reversing entries for `entries` as the void stores them, which keeps
+/// each original's functional amount.
+fn checked_reversal(entries: &[OriginalEntry]) -> Vec<CheckedEntry> {
+    let input = ReversalInput {
+        original_transaction_id: Uuid::new_v4(),
+        original_reference: None,
+        original_entries: entries.to_vec(),
+        fiscal_period_id: Uuid::new_v4(),
+        voided_by: Uuid::new_v4(),
+        void_reason_code: VoidReasonCode::Other,
+        void_reason: String::new(),
+    };
+    ReversalService::create_reversing_entries(&input)
+        .reversing_entries
+        .into_iter()
+        .zip(entries)
+        .map(|(reversed, original)| CheckedEntry {
+            entry_id: Uuid::new_v4(),
+            account_id: reversed.account_id,
+            source_currency: reversed.source_currency,
+            source_amount: reversed.source_amount,
+            functional_amount: original.functional_amount,
+            entry_type: reversed.entry_type,
+        })
+        .collect()
+}
+
 proptest! {
     #![proptest_config(ProptestConfig::with_cases(100))]
 
@@ -85,9 +135,11 @@ proptest! {
     ) {
         let input = ReversalInput {
             original_transaction_id: Uuid::new_v4(),
+            original_reference: None,
             original_entries: entries.clone(),
             fiscal_period_id: Uuid::new_v4(),
             voided_by: Uuid::new_v4(),
+            void_reason_code: VoidReasonCode::Other,
             void_reason: "Test void".to_string(),
         };
 
@@ -108,9 +160,11 @@ proptest! {
     ) {
         let input = ReversalInput {
             original_transaction_id: Uuid::new_v4(),
+            original_reference: None,
             original_entries: entries.clone(),
             fiscal_period_id: Uuid::new_v4(),
             voided_by: Uuid::new_v4(),
+            void_reason_code: VoidReasonCode::Other,
             void_reason: "Test void".to_string(),
         };
 
@@ -128,9 +182,11 @@ proptest! {
     ) {
         let input = ReversalInput {
             original_transaction_id: Uuid::new_v4(),
+            original_reference: None,
             original_entries: entries.clone(),
             fiscal_period_id: Uuid::new_v4(),
             voided_by: Uuid::new_v4(),
+            void_reason_code: VoidReasonCode::Other,
             void_reason: "Test void".to_string(),
         };
 
@@ -157,9 +213,11 @@ proptest! {
 
         let input = ReversalInput {
             original_transaction_id: Uuid::new_v4(),
+            original_reference: None,
             original_entries: entries,
             fiscal_period_id: Uuid::new_v4(),
             voided_by: Uuid::new_v4(),
+            void_reason_code: VoidReasonCode::Other,
             void_reason: "Test void".to_string(),
         };
 
@@ -211,9 +269,11 @@ proptest! {
 
         let input = ReversalInput {
             original_transaction_id: Uuid::new_v4(),
+            original_reference: None,
             original_entries: entries,
             fiscal_period_id: Uuid::new_v4(),
             voided_by: Uuid::new_v4(),
+            void_reason_code: VoidReasonCode::Other,
             void_reason: "Test".to_string(),
         };
 
@@ -259,9 +319,11 @@ proptest! {
 
         let input = ReversalInput {
             original_transaction_id: original_id,
+            original_reference: None,
             original_entries: entries,
             fiscal_period_id: Uuid::new_v4(),
             voided_by: Uuid::new_v4(),
+            void_reason_code: VoidReasonCode::Other,
             void_reason: void_reason.clone(),
         };
 
@@ -311,9 +373,11 @@ mod edge_case_tests {
 
         let input = ReversalInput {
             original_transaction_id: Uuid::new_v4(),
+            original_reference: None,
             original_entries: entries,
             fiscal_period_id: Uuid::new_v4(),
             voided_by: Uuid::new_v4(),
+            void_reason_code: VoidReasonCode::Other,
             void_reason: "Duplicate".to_string(),
         };
 
@@ -377,9 +441,11 @@ mod edge_case_tests {
 
         let input = ReversalInput {
             original_transaction_id: Uuid::new_v4(),
+            original_reference: None,
             original_entries: entries,
             fiscal_period_id: Uuid::new_v4(),
             voided_by: Uuid::new_v4(),
+            void_reason_code: VoidReasonCode::Other,
             void_reason: "Error".to_string(),
         };
 
@@ -424,9 +490,11 @@ mod edge_case_tests {
 
         let input = ReversalInput {
             original_transaction_id: Uuid::new_v4(),
+            original_reference: None,
             original_entries: entries,
             fiscal_period_id: Uuid::new_v4(),
             voided_by: Uuid::new_v4(),
+            void_reason_code: VoidReasonCode::Other,
             void_reason: "Test".to_string(),
         };
 
@@ -471,9 +539,11 @@ mod edge_case_tests {
 
         let input = ReversalInput {
             original_transaction_id: Uuid::new_v4(),
+            original_reference: None,
             original_entries: entries.clone(),
             fiscal_period_id: Uuid::new_v4(),
             voided_by: Uuid::new_v4(),
+            void_reason_code: VoidReasonCode::Other,
             void_reason: "Test".to_string(),
         };
 
@@ -518,9 +588,11 @@ mod edge_case_tests {
 
         let input = ReversalInput {
             original_transaction_id: Uuid::new_v4(),
+            original_reference: None,
             original_entries: entries,
             fiscal_period_id: Uuid::new_v4(),
             voided_by: Uuid::new_v4(),
+            void_reason_code: VoidReasonCode::Other,
             void_reason: "Test".to_string(),
         };
 
@@ -532,3 +604,65 @@ mod edge_case_tests {
         }
     }
 }
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(100))]
+
+    // =========================================================================
+    // Reversal check: stored reversals mirror their originals
+    // =========================================================================
+
+    /// The output of ReversalService always compares as an exact mirror,
+    /// whatever order the reversal entries come back in
+    #[test]
+    fn prop_reversal_service_output_is_exact_mirror(
+        entries in arb_balanced_entries(),
+        reverse_order in any::<bool>(),
+    ) {
+        let original = checked_originals(&entries);
+        let mut reversal = checked_reversal(&entries);
+        if reverse_order {
+            reversal.reverse();
+        }
+
+        let comparison = compare_reversal(&original, &reversal);
+
+        prop_assert!(comparison.is_exact_mirror(), "{:?}", comparison);
+        prop_assert_eq!(comparison.matched.len(), entries.len());
+    }
+
+    /// Entries that were not flipped are paired but reported as mismatched
+    #[test]
+    fn prop_unflipped_entries_are_mismatched(
+        entries in arb_balanced_entries()
+    ) {
+        let original = checked_originals(&entries);
+        let copy = checked_originals(&entries);
+
+        let comparison = compare_reversal(&original, &copy);
+
+        prop_assert!(comparison.matched.is_empty());
+        prop_assert_eq!(comparison.mismatched.len(), entries.len());
+        for mismatch in &comparison.mismatched {
+            prop_assert_eq!(&mismatch.fields, &vec![ReversalMismatchField::EntryType]);
+        }
+    }
+
+    /// A missing reversal entry shows up only on the original side
+    #[test]
+    fn prop_missing_reversal_entry_is_reported(
+        entries in arb_balanced_entries(),
+        index in any::<prop::sample::Index>(),
+    ) {
+        let original = checked_originals(&entries);
+        let mut reversal = checked_reversal(&entries);
+        let removed = reversal.remove(index.index(reversal.len()));
+
+        let comparison = compare_reversal(&original, &reversal);
+
+        prop_assert!(!comparison.is_exact_mirror());
+        prop_assert!(comparison.only_in_reversal.is_empty());
+        prop_assert_eq!(comparison.only_in_original.len(), 1);
+        prop_assert_eq!(comparison.only_in_original[0].account_id, removed.account_id);
+    }
+}
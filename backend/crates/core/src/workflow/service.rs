@@ -3,11 +3,99 @@
 //! This module implements the core state machine logic for
 //! transitioning transactions through the approval workflow.
 
+use std::collections::HashMap;
+
 use chrono::Utc;
+use rust_decimal::Decimal;
 use uuid::Uuid;
 
+use crate::ledger::{FiscalPeriodStatus, LedgerError, validate_posting_permission};
+use crate::workflow::approval::{ApprovalEngine, DelegatedAuthority, UserRole};
 use crate::workflow::error::WorkflowError;
-use crate::workflow::types::{TransactionStatus, WorkflowAction};
+use crate::workflow::types::{
+    ActionAvailability, ApprovalProgress, TransactionStatus, VoidReasonCode, WorkflowAction,
+    WorkflowActionKind,
+};
+
+/// What the workflow knows about a transaction and the user looking at it.
+#[derive(Debug, Clone)]
+pub struct ActionContext<'a> {
+    /// Current status of the transaction.
+    pub status: TransactionStatus,
+    /// The user asking.
+    pub user_id: Uuid,
+    /// The user's role in the organization.
+    pub user_role: UserRole,
+    /// The user's own approval limit.
+    pub approval_limit: Option<Decimal>,
+    /// Transaction total used for approval limits.
+    pub amount: Decimal,
+    /// Role the matched approval rule requires.
+    pub required_role: &'a str,
+    /// Approvals the matched approval rule requires.
+    pub required_approvals: u32,
+    /// Users who already approved in this round.
+    pub prior_approvers: &'a [Uuid],
+    /// Authority the user holds through active delegations.
+    pub delegations: &'a [DelegatedAuthority],
+    /// Status of the transaction's fiscal period.
+    pub period_status: FiscalPeriodStatus,
+    /// Completed reconciliation holding one of the transaction's entries.
+    pub reconciliation_id: Option<Uuid>,
+}
+
+/// One transaction of a bulk approval, as loaded for planning.
+#[derive(Debug, Clone)]
+pub struct BulkApprovalItem<'a> {
+    /// The requested transaction.
+    pub transaction_id: Uuid,
+    /// Current status, `None` when the transaction was not found.
+    pub status: Option<TransactionStatus>,
+    /// Transaction total used for approval limits.
+    pub amount: Decimal,
+    /// Role the matched approval rule requires.
+    pub required_role: &'a str,
+    /// Approvals the matched approval rule requires.
+    pub required_approvals: u32,
+    /// Users who already approved in this round.
+    pub prior_approvers: &'a [Uuid],
+    /// Users behind the approvals so far: the approvers and the delegators
+    /// they approved for.
+    pub counted_approvers: &'a [Uuid],
+}
+
+/// The user approving in bulk.
+#[derive(Debug, Clone)]
+pub struct BulkApprover<'a> {
+    /// The approving user.
+    pub user_id: Uuid,
+    /// The user's role in the organization, `None` when not a member.
+    pub role: Option<&'a str>,
+    /// The user's own approval limit.
+    pub approval_limit: Option<Decimal>,
+    /// Authority held through active delegations, with the delegator's ID.
+    pub delegations: &'a [(Uuid, DelegatedAuthority)],
+}
+
+/// An approval the plan allows.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PlannedApproval {
+    /// Status after the approval; stays Pending if more are required.
+    pub new_status: TransactionStatus,
+    /// Approvals recorded including this one.
+    pub progress: ApprovalProgress,
+    /// Delegator whose authority is used, if any.
+    pub on_behalf_of: Option<Uuid>,
+}
+
+/// The plan for one requested transaction.
+#[derive(Debug, Clone)]
+pub struct BulkApprovalDecision {
+    /// The requested transaction.
+    pub transaction_id: Uuid,
+    /// The approval to apply, or why it is denied.
+    pub decision: Result<PlannedApproval, WorkflowError>,
+}
 
 /// Stateless service for managing transaction workflow transitions.
 ///
@@ -43,33 +131,174 @@ impl WorkflowService {
         }
     }
 
-    /// Approve a pending transaction.
+    /// Record an approval of a pending transaction.
+    ///
+    /// The transaction moves to Approved once `required_approvals` different
+    /// users have approved it. Earlier approvals are recorded without a
+    /// status change, so the action's new status stays Pending.
     ///
     /// # Arguments
     /// * `current_status` - The current status of the transaction
     /// * `approved_by` - The user approving the transaction
     /// * `approval_notes` - Optional notes from the approver
+    /// * `prior_approvers` - Users who already approved in this round
+    /// * `required_approvals` - Approvals the matched rule requires
     ///
     /// # Returns
     /// * `Ok(WorkflowAction::Approve)` if the transition is valid
     /// * `Err(WorkflowError::InvalidTransition)` if not in Pending status
+    /// * `Err(WorkflowError::AlreadyApproved)` if the user already approved
     pub fn approve(
         current_status: TransactionStatus,
         approved_by: Uuid,
         approval_notes: Option<String>,
+        prior_approvers: &[Uuid],
+        required_approvals: u32,
     ) -> Result<WorkflowAction, WorkflowError> {
-        match current_status {
-            TransactionStatus::Pending => Ok(WorkflowAction::Approve {
-                new_status: TransactionStatus::Approved,
-                approved_by,
-                approved_at: Utc::now(),
-                approval_notes,
-            }),
-            _ => Err(WorkflowError::InvalidTransition {
+        if current_status != TransactionStatus::Pending {
+            return Err(WorkflowError::InvalidTransition {
                 from: current_status,
                 to: TransactionStatus::Approved,
-            }),
+            });
+        }
+        if prior_approvers.contains(&approved_by) {
+            return Err(WorkflowError::AlreadyApproved {
+                user_id: approved_by,
+            });
+        }
+
+        let progress = ApprovalProgress {
+            approvals: u32::try_from(prior_approvers.len())
+                .unwrap_or(u32::MAX)
+                .saturating_add(1),
+            required: required_approvals.max(1),
+        };
+        let new_status = if progress.is_complete() {
+            TransactionStatus::Approved
+        } else {
+            TransactionStatus::Pending
+        };
+
+        Ok(WorkflowAction::Approve {
+            new_status,
+            approved_by,
+            approved_at: Utc::now(),
+            approval_notes,
+            progress,
+        })
+    }
+
+    /// Check that an approval comes from someone not yet counted.
+    ///
+    /// `counted_approvers` holds the users behind the earlier approvals of
+    /// this round, both the approvers and the delegators they approved for.
+    /// Neither the approving user nor the authority they approve under may be
+    /// among them, so a delegator and their delegate count as one approver.
+    ///
+    /// # Errors
+    ///
+    /// Returns `WorkflowError::AlreadyApproved` with the user already counted.
+    pub fn validate_distinct_approver(
+        counted_approvers: &[Uuid],
+        approved_by: Uuid,
+        on_behalf_of: Option<Uuid>,
+    ) -> Result<(), WorkflowError> {
+        for user_id in [approved_by, on_behalf_of.unwrap_or(approved_by)] {
+            if counted_approvers.contains(&user_id) {
+                return Err(WorkflowError::AlreadyApproved { user_id });
+            }
+        }
+        Ok(())
+    }
+
+    /// Plan a bulk approval without touching the database.
+    ///
+    /// Each item gets the same checks as [`Self::approve`] followed by the
+    /// approver's authority (own role first, then delegations), in that
+    /// order, so denials carry the error a one-at-a-time approval would.
+    /// A repeated ID sees the approval planned for its first occurrence.
+    #[must_use]
+    pub fn plan_bulk_approval(
+        items: &[BulkApprovalItem<'_>],
+        approver: &BulkApprover<'_>,
+    ) -> Vec<BulkApprovalDecision> {
+        let mut planned: HashMap<Uuid, TransactionStatus> = HashMap::new();
+        items
+            .iter()
+            .map(|item| {
+                let decision = match planned.get(&item.transaction_id) {
+                    Some(&TransactionStatus::Pending) => Err(WorkflowError::AlreadyApproved {
+                        user_id: approver.user_id,
+                    }),
+                    Some(&status) => Err(WorkflowError::InvalidTransition {
+                        from: status,
+                        to: TransactionStatus::Approved,
+                    }),
+                    None => Self::plan_approval(item, approver),
+                };
+                if let Ok(approval) = &decision {
+                    planned.insert(item.transaction_id, approval.new_status);
+                }
+                BulkApprovalDecision {
+                    transaction_id: item.transaction_id,
+                    decision,
+                }
+            })
+            .collect()
+    }
+
+    /// Plans the approval of a single item.
+    fn plan_approval(
+        item: &BulkApprovalItem<'_>,
+        approver: &BulkApprover<'_>,
+    ) -> Result<PlannedApproval, WorkflowError> {
+        let status = item
+            .status
+            .ok_or(WorkflowError::TransactionNotFound(item.transaction_id))?;
+        let WorkflowAction::Approve {
+            new_status,
+            progress,
+            ..
+        } = Self::approve(
+            status,
+            approver.user_id,
+            None,
+            item.prior_approvers,
+            item.required_approvals,
+        )?
+        else {
+            unreachable!("WorkflowService::approve returns an Approve action");
+        };
+
+        let role = approver.role.ok_or(WorkflowError::NotAuthorizedToApprove)?;
+        let approval = |on_behalf_of| {
+            Self::validate_distinct_approver(
+                item.counted_approvers,
+                approver.user_id,
+                on_behalf_of,
+            )?;
+            Ok(PlannedApproval {
+                new_status,
+                progress,
+                on_behalf_of,
+            })
+        };
+        let Err(mut err) = ApprovalEngine::can_approve(
+            role,
+            approver.approval_limit,
+            item.required_role,
+            item.amount,
+        ) else {
+            return approval(None);
+        };
+        for (delegator, authority) in approver.delegations {
+            match ApprovalEngine::can_approve_delegated(authority, item.required_role, item.amount)
+            {
+                Ok(()) => return approval(Some(*delegator)),
+                Err(e) => err = e,
+            }
         }
+        Err(err)
     }
 
     /// Reject a pending transaction back to draft.
@@ -133,18 +362,21 @@ impl WorkflowService {
     /// # Arguments
     /// * `current_status` - The current status of the transaction
     /// * `voided_by` - The user voiding the transaction
-    /// * `void_reason` - The reason for voiding (required)
+    /// * `void_reason_code` - The categorized reason for voiding
+    /// * `void_reason` - Free-text reason (required when the code is `Other`)
     ///
     /// # Returns
     /// * `Ok(WorkflowAction::Void)` if the transition is valid
     /// * `Err(WorkflowError::InvalidTransition)` if not in Posted status
-    /// * `Err(WorkflowError::VoidReasonRequired)` if reason is empty
+    /// * `Err(WorkflowError::VoidReasonRequired)` if the code is `Other` and
+    ///   the reason is empty
     pub fn void(
         current_status: TransactionStatus,
         voided_by: Uuid,
+        void_reason_code: VoidReasonCode,
         void_reason: String,
     ) -> Result<WorkflowAction, WorkflowError> {
-        if void_reason.trim().is_empty() {
+        if void_reason_code.requires_text() && void_reason.trim().is_empty() {
             return Err(WorkflowError::VoidReasonRequired);
         }
 
@@ -153,6 +385,7 @@ impl WorkflowService {
                 new_status: TransactionStatus::Voided,
                 voided_by,
                 voided_at: Utc::now(),
+                void_reason_code,
                 void_reason,
             }),
             _ => Err(WorkflowError::InvalidTransition {
@@ -162,6 +395,85 @@ impl WorkflowService {
         }
     }
 
+    /// Work out which workflow actions the user may perform right now.
+    ///
+    /// Runs the same checks as the transitions themselves: the status
+    /// machine for every action, approval authority (own role first, then
+    /// delegations) for approve, the period's posting rules for post, and a
+    /// closed period or a completed reconciliation for void.
+    #[must_use]
+    pub fn available_actions(ctx: &ActionContext<'_>) -> Vec<ActionAvailability> {
+        WorkflowActionKind::ALL
+            .into_iter()
+            .map(|action| {
+                let reason = Self::check_action(action, ctx).err();
+                ActionAvailability {
+                    action,
+                    allowed: reason.is_none(),
+                    reason,
+                }
+            })
+            .collect()
+    }
+
+    /// Returns why `action` is not allowed, if it isn't.
+    fn check_action(action: WorkflowActionKind, ctx: &ActionContext<'_>) -> Result<(), String> {
+        let target = action.target_status();
+        if !Self::is_valid_transition(ctx.status, target) {
+            return Err(format!("cannot {action} a {} transaction", ctx.status));
+        }
+
+        match action {
+            WorkflowActionKind::Approve => {
+                Self::approve(
+                    ctx.status,
+                    ctx.user_id,
+                    None,
+                    ctx.prior_approvers,
+                    ctx.required_approvals,
+                )
+                .map_err(|e| action_denied_reason(&e))?;
+                Self::authorize_approval(ctx).map_err(|e| action_denied_reason(&e))
+            }
+            WorkflowActionKind::Post => {
+                validate_posting_permission(&ctx.period_status, &ctx.user_role.into())
+                    .map_err(|e| posting_denied_reason(&e))
+            }
+            WorkflowActionKind::Void if ctx.period_status == FiscalPeriodStatus::Closed => {
+                Err(action_denied_reason(&WorkflowError::PeriodClosed))
+            }
+            WorkflowActionKind::Void => match ctx.reconciliation_id {
+                Some(reconciliation_id) => {
+                    Err(action_denied_reason(&WorkflowError::EntryReconciled {
+                        reconciliation_id,
+                    }))
+                }
+                None => Ok(()),
+            },
+            WorkflowActionKind::Submit | WorkflowActionKind::Reject => Ok(()),
+        }
+    }
+
+    /// Checks the user's own approval authority, then each delegation,
+    /// reporting the last one's error when none suffices.
+    fn authorize_approval(ctx: &ActionContext<'_>) -> Result<(), WorkflowError> {
+        let Err(mut err) = ApprovalEngine::can_approve(
+            ctx.user_role.as_str(),
+            ctx.approval_limit,
+            ctx.required_role,
+            ctx.amount,
+        ) else {
+            return Ok(());
+        };
+        for authority in ctx.delegations {
+            match ApprovalEngine::can_approve_delegated(authority, ctx.required_role, ctx.amount) {
+                Ok(()) => return Ok(()),
+                Err(e) => err = e,
+            }
+        }
+        Err(err)
+    }
+
     /// Check if a status transition is valid.
     ///
     /// Valid transitions:
@@ -190,6 +502,70 @@ impl WorkflowService {
                 | (TransactionStatus::Posted, TransactionStatus::Voided)
         )
     }
+
+    /// Checks that a transaction's content may still be updated.
+    ///
+    /// Anything not yet posted may be corrected; posted and voided
+    /// transactions are immutable.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CannotModifyPosted` or `CannotModifyVoided`.
+    pub fn validate_modify(status: TransactionStatus) -> Result<(), WorkflowError> {
+        match status {
+            TransactionStatus::Posted => Err(WorkflowError::CannotModifyPosted),
+            TransactionStatus::Voided => Err(WorkflowError::CannotModifyVoided),
+            TransactionStatus::Draft | TransactionStatus::Pending | TransactionStatus::Approved => {
+                Ok(())
+            }
+        }
+    }
+
+    /// Checks that a transaction may be deleted: drafts only.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CannotModifyPosted` or `CannotModifyVoided` for immutable
+    /// transactions and `CanOnlyDeleteDraft` for ones in approval.
+    pub fn validate_delete(status: TransactionStatus) -> Result<(), WorkflowError> {
+        Self::validate_modify(status)?;
+        match status {
+            TransactionStatus::Draft => Ok(()),
+            _ => Err(WorkflowError::CanOnlyDeleteDraft),
+        }
+    }
+}
+
+/// Phrases a workflow error as a reason shown next to a disabled action.
+fn action_denied_reason(err: &WorkflowError) -> String {
+    match err {
+        WorkflowError::AlreadyApproved { .. } => {
+            "you have already approved this transaction".to_string()
+        }
+        WorkflowError::ExceedsApprovalLimit { limit, .. } => {
+            format!("exceeds your approval limit of {}", limit.normalize())
+        }
+        WorkflowError::InsufficientRole { required_role, .. } => {
+            format!("requires the {required_role} role")
+        }
+        WorkflowError::PeriodClosed => "the fiscal period is closed".to_string(),
+        WorkflowError::EntryReconciled { reconciliation_id } => {
+            format!("entries are in completed reconciliation {reconciliation_id}")
+        }
+        other => other.to_string(),
+    }
+}
+
+/// Phrases a period posting error as a reason shown next to post.
+fn posting_denied_reason(err: &LedgerError) -> String {
+    match err {
+        LedgerError::PeriodClosed => "the fiscal period is closed".to_string(),
+        LedgerError::PeriodSoftClosed => {
+            "the fiscal period is soft-closed; only accountants, admins and owners can post"
+                .to_string()
+        }
+        other => other.to_string(),
+    }
 }
 
 #[cfg(test)]
@@ -218,15 +594,46 @@ mod tests {
     #[test]
     fn test_approve_from_pending() {
         let user_id = Uuid::new_v4();
-        let result = WorkflowService::approve(TransactionStatus::Pending, user_id, None);
+        let result = WorkflowService::approve(TransactionStatus::Pending, user_id, None, &[], 1);
         assert!(result.is_ok());
         assert_eq!(result.unwrap().new_status(), TransactionStatus::Approved);
     }
 
+    #[test]
+    fn test_partial_approval_stays_pending() {
+        let first = Uuid::new_v4();
+        let second = Uuid::new_v4();
+
+        let action =
+            WorkflowService::approve(TransactionStatus::Pending, first, None, &[], 2).unwrap();
+        assert_eq!(action.new_status(), TransactionStatus::Pending);
+        assert!(matches!(
+            action,
+            WorkflowAction::Approve { progress, .. }
+                if progress == ApprovalProgress { approvals: 1, required: 2 }
+        ));
+
+        let action =
+            WorkflowService::approve(TransactionStatus::Pending, second, None, &[first], 2)
+                .unwrap();
+        assert_eq!(action.new_status(), TransactionStatus::Approved);
+    }
+
+    #[test]
+    fn test_same_user_cannot_approve_twice() {
+        let user_id = Uuid::new_v4();
+        let result =
+            WorkflowService::approve(TransactionStatus::Pending, user_id, None, &[user_id], 2);
+        assert!(matches!(
+            result,
+            Err(WorkflowError::AlreadyApproved { user_id: u }) if u == user_id
+        ));
+    }
+
     #[test]
     fn test_approve_from_non_pending_fails() {
         let user_id = Uuid::new_v4();
-        let result = WorkflowService::approve(TransactionStatus::Draft, user_id, None);
+        let result = WorkflowService::approve(TransactionStatus::Draft, user_id, None, &[], 1);
         assert!(matches!(
             result,
             Err(WorkflowError::InvalidTransition { .. })
@@ -283,6 +690,7 @@ mod tests {
         let result = WorkflowService::void(
             TransactionStatus::Posted,
             user_id,
+            VoidReasonCode::Other,
             "Error found".to_string(),
         );
         assert!(result.is_ok());
@@ -292,16 +700,40 @@ mod tests {
     #[test]
     fn test_void_empty_reason_fails() {
         let user_id = Uuid::new_v4();
-        let result = WorkflowService::void(TransactionStatus::Posted, user_id, String::new());
+        let result = WorkflowService::void(
+            TransactionStatus::Posted,
+            user_id,
+            VoidReasonCode::Other,
+            String::new(),
+        );
         assert!(matches!(result, Err(WorkflowError::VoidReasonRequired)));
     }
 
+    #[test]
+    fn test_void_with_code_needs_no_reason_text() {
+        let user_id = Uuid::new_v4();
+        let result = WorkflowService::void(
+            TransactionStatus::Posted,
+            user_id,
+            VoidReasonCode::DuplicateEntry,
+            String::new(),
+        );
+        assert!(matches!(
+            result,
+            Ok(WorkflowAction::Void {
+                void_reason_code: VoidReasonCode::DuplicateEntry,
+                ..
+            })
+        ));
+    }
+
     #[test]
     fn test_void_from_non_posted_fails() {
         let user_id = Uuid::new_v4();
         let result = WorkflowService::void(
             TransactionStatus::Approved,
             user_id,
+            VoidReasonCode::Other,
             "Error found".to_string(),
         );
         assert!(matches!(
@@ -348,4 +780,269 @@ mod tests {
             TransactionStatus::Draft
         ));
     }
+
+    fn pending_context<'a>(user_id: Uuid, limit: Decimal) -> ActionContext<'a> {
+        ActionContext {
+            status: TransactionStatus::Pending,
+            user_id,
+            user_role: UserRole::Approver,
+            approval_limit: Some(limit),
+            amount: Decimal::new(25_000, 0),
+            required_role: "approver",
+            required_approvals: 1,
+            prior_approvers: &[],
+            delegations: &[],
+            period_status: FiscalPeriodStatus::Open,
+            reconciliation_id: None,
+        }
+    }
+
+    #[test]
+    fn test_available_actions_for_pending_over_limit() {
+        let ctx = pending_context(Uuid::new_v4(), Decimal::new(1_000_000, 2));
+        let actions = WorkflowService::available_actions(&ctx);
+
+        let allowed: Vec<_> = actions
+            .iter()
+            .filter(|a| a.allowed)
+            .map(|a| a.action)
+            .collect();
+        assert_eq!(allowed, vec![WorkflowActionKind::Reject]);
+
+        let approve = &actions[1];
+        assert_eq!(approve.action, WorkflowActionKind::Approve);
+        assert_eq!(
+            approve.reason.as_deref(),
+            Some("exceeds your approval limit of 10000")
+        );
+        assert_eq!(
+            actions[3].reason.as_deref(),
+            Some("cannot post a pending transaction")
+        );
+    }
+
+    #[test]
+    fn test_delegation_allows_approval_over_own_limit() {
+        let delegations = [DelegatedAuthority {
+            delegator_role: "admin".to_string(),
+            delegator_limit: None,
+            delegate_limit: None,
+            amount_cap: None,
+        }];
+        let ctx = ActionContext {
+            delegations: &delegations,
+            ..pending_context(Uuid::new_v4(), Decimal::new(100, 0))
+        };
+
+        let approve = &WorkflowService::available_actions(&ctx)[1];
+        assert!(approve.allowed);
+        assert_eq!(approve.reason, None);
+    }
+
+    #[test]
+    fn test_post_blocked_by_soft_closed_period_for_approver() {
+        let ctx = ActionContext {
+            status: TransactionStatus::Approved,
+            period_status: FiscalPeriodStatus::SoftClose,
+            ..pending_context(Uuid::new_v4(), Decimal::ZERO)
+        };
+        let post = &WorkflowService::available_actions(&ctx)[3];
+        assert!(!post.allowed);
+
+        let ctx = ActionContext {
+            user_role: UserRole::Accountant,
+            ..ctx
+        };
+        assert!(WorkflowService::available_actions(&ctx)[3].allowed);
+    }
+
+    #[test]
+    fn test_void_blocked_by_closed_period() {
+        let ctx = ActionContext {
+            status: TransactionStatus::Posted,
+            ..pending_context(Uuid::new_v4(), Decimal::ZERO)
+        };
+        assert!(WorkflowService::available_actions(&ctx)[4].allowed);
+
+        let ctx = ActionContext {
+            period_status: FiscalPeriodStatus::Closed,
+            ..ctx
+        };
+        let void = &WorkflowService::available_actions(&ctx)[4];
+        assert_eq!(void.action, WorkflowActionKind::Void);
+        assert_eq!(void.reason.as_deref(), Some("the fiscal period is closed"));
+    }
+
+    #[test]
+    fn test_void_blocked_by_completed_reconciliation() {
+        let reconciliation_id = Uuid::new_v4();
+        let ctx = ActionContext {
+            status: TransactionStatus::Posted,
+            reconciliation_id: Some(reconciliation_id),
+            ..pending_context(Uuid::new_v4(), Decimal::ZERO)
+        };
+        let void = &WorkflowService::available_actions(&ctx)[4];
+        assert!(!void.allowed);
+        assert_eq!(
+            void.reason,
+            Some(format!(
+                "entries are in completed reconciliation {reconciliation_id}"
+            ))
+        );
+    }
+
+    fn bulk_item(transaction_id: Uuid, amount: i64) -> BulkApprovalItem<'static> {
+        BulkApprovalItem {
+            transaction_id,
+            status: Some(TransactionStatus::Pending),
+            amount: Decimal::new(amount, 0),
+            required_role: "approver",
+            required_approvals: 1,
+            prior_approvers: &[],
+            counted_approvers: &[],
+        }
+    }
+
+    #[test]
+    fn test_delegate_and_delegator_count_as_one_approver() {
+        let delegator = Uuid::new_v4();
+        let delegate = Uuid::new_v4();
+
+        // The delegator approved, then the delegate approves for them
+        assert!(matches!(
+            WorkflowService::validate_distinct_approver(&[delegator], delegate, Some(delegator)),
+            Err(WorkflowError::AlreadyApproved { user_id }) if user_id == delegator
+        ));
+        // The delegate approved for the delegator, then the delegator approves
+        assert!(matches!(
+            WorkflowService::validate_distinct_approver(&[delegate, delegator], delegator, None),
+            Err(WorkflowError::AlreadyApproved { user_id }) if user_id == delegator
+        ));
+        assert!(WorkflowService::validate_distinct_approver(&[delegator], delegate, None).is_ok());
+    }
+
+    #[test]
+    fn test_plan_bulk_approval_reports_each_item() {
+        let approver_id = Uuid::new_v4();
+        let prior = [approver_id];
+        let items = [
+            bulk_item(Uuid::new_v4(), 500),
+            BulkApprovalItem {
+                status: None,
+                ..bulk_item(Uuid::new_v4(), 500)
+            },
+            BulkApprovalItem {
+                status: Some(TransactionStatus::Draft),
+                ..bulk_item(Uuid::new_v4(), 500)
+            },
+            BulkApprovalItem {
+                prior_approvers: &prior,
+                ..bulk_item(Uuid::new_v4(), 500)
+            },
+            bulk_item(Uuid::new_v4(), 5_000),
+        ];
+        let approver = BulkApprover {
+            user_id: approver_id,
+            role: Some("approver"),
+            approval_limit: Some(Decimal::new(1_000, 0)),
+            delegations: &[],
+        };
+
+        let plan = WorkflowService::plan_bulk_approval(&items, &approver);
+
+        assert_eq!(plan.len(), items.len());
+        let approval = plan[0].decision.as_ref().unwrap();
+        assert_eq!(approval.new_status, TransactionStatus::Approved);
+        assert_eq!(approval.on_behalf_of, None);
+        assert!(matches!(
+            plan[1].decision,
+            Err(WorkflowError::TransactionNotFound(id)) if id == items[1].transaction_id
+        ));
+        assert!(matches!(
+            plan[2].decision,
+            Err(WorkflowError::InvalidTransition { .. })
+        ));
+        assert!(matches!(
+            plan[3].decision,
+            Err(WorkflowError::AlreadyApproved { .. })
+        ));
+        assert!(matches!(
+            plan[4].decision,
+            Err(WorkflowError::ExceedsApprovalLimit { .. })
+        ));
+    }
+
+    #[test]
+    fn test_plan_bulk_approval_uses_delegation_and_membership() {
+        let delegator = Uuid::new_v4();
+        let delegations = [(
+            delegator,
+            DelegatedAuthority {
+                delegator_role: "admin".to_string(),
+                delegator_limit: None,
+                delegate_limit: None,
+                amount_cap: Some(Decimal::new(10_000, 0)),
+            },
+        )];
+        let items = [bulk_item(Uuid::new_v4(), 5_000)];
+        let approver = BulkApprover {
+            user_id: Uuid::new_v4(),
+            role: Some("viewer"),
+            approval_limit: None,
+            delegations: &delegations,
+        };
+
+        let plan = WorkflowService::plan_bulk_approval(&items, &approver);
+        assert_eq!(
+            plan[0].decision.as_ref().unwrap().on_behalf_of,
+            Some(delegator)
+        );
+
+        let outsider = BulkApprover {
+            role: None,
+            ..approver
+        };
+        let plan = WorkflowService::plan_bulk_approval(&items, &outsider);
+        assert!(matches!(
+            plan[0].decision,
+            Err(WorkflowError::NotAuthorizedToApprove)
+        ));
+    }
+
+    #[test]
+    fn test_plan_bulk_approval_repeated_id_sees_earlier_approval() {
+        let id = Uuid::new_v4();
+        let approver = BulkApprover {
+            user_id: Uuid::new_v4(),
+            role: Some("admin"),
+            approval_limit: None,
+            delegations: &[],
+        };
+
+        let items = [bulk_item(id, 10), bulk_item(id, 10)];
+        let plan = WorkflowService::plan_bulk_approval(&items, &approver);
+        assert!(plan[0].decision.is_ok());
+        assert!(matches!(
+            plan[1].decision,
+            Err(WorkflowError::InvalidTransition {
+                from: TransactionStatus::Approved,
+                ..
+            })
+        ));
+
+        let two_approvals = BulkApprovalItem {
+            required_approvals: 2,
+            ..bulk_item(id, 10)
+        };
+        let items = [two_approvals.clone(), two_approvals];
+        let plan = WorkflowService::plan_bulk_approval(&items, &approver);
+        assert_eq!(
+            plan[0].decision.as_ref().unwrap().new_status,
+            TransactionStatus::Pending
+        );
+        assert!(matches!(
+            plan[1].decision,
+            Err(WorkflowError::AlreadyApproved { .. })
+        ));
+    }
 }
@@ -6,11 +6,14 @@
 #![allow(clippy::uninlined_format_args)]
 
 use proptest::prelude::*;
+use rust_decimal::Decimal;
 use uuid::Uuid;
 
+use crate::ledger::{FiscalPeriodStatus, LedgerError, validate_posting_permission};
+use crate::workflow::approval::{ApprovalEngine, DelegatedAuthority, UserRole};
 use crate::workflow::error::WorkflowError;
-use crate::workflow::service::WorkflowService;
-use crate::workflow::types::TransactionStatus;
+use crate::workflow::service::{ActionContext, WorkflowService};
+use crate::workflow::types::{TransactionStatus, VoidReasonCode, WorkflowActionKind};
 
 /// Strategy for generating random TransactionStatus values.
 fn arb_status() -> impl Strategy<Value = TransactionStatus> {
@@ -38,6 +41,143 @@ fn arb_approval_notes() -> impl Strategy<Value = Option<String>> {
     prop_oneof![Just(None), arb_non_empty_string().prop_map(Some),]
 }
 
+/// Strategy for generating workflow roles.
+fn arb_role() -> impl Strategy<Value = UserRole> {
+    prop_oneof![
+        Just(UserRole::Viewer),
+        Just(UserRole::Submitter),
+        Just(UserRole::Approver),
+        Just(UserRole::Accountant),
+        Just(UserRole::Admin),
+        Just(UserRole::Owner),
+    ]
+}
+
+/// Strategy for generating fiscal period statuses.
+fn arb_period_status() -> impl Strategy<Value = FiscalPeriodStatus> {
+    prop_oneof![
+        Just(FiscalPeriodStatus::Open),
+        Just(FiscalPeriodStatus::SoftClose),
+        Just(FiscalPeriodStatus::Closed),
+    ]
+}
+
+/// Strategy for generating whole amounts up to 20,000.
+fn arb_amount() -> impl Strategy<Value = Decimal> {
+    (0i64..20_000).prop_map(Decimal::from)
+}
+
+/// Strategy for generating delegated authority.
+fn arb_delegation() -> impl Strategy<Value = DelegatedAuthority> {
+    (
+        arb_role(),
+        proptest::option::of(arb_amount()),
+        proptest::option::of(arb_amount()),
+        proptest::option::of(arb_amount()),
+    )
+        .prop_map(
+            |(role, delegator_limit, delegate_limit, amount_cap)| DelegatedAuthority {
+                delegator_role: role.as_str().to_string(),
+                delegator_limit,
+                delegate_limit,
+                amount_cap,
+            },
+        )
+}
+
+/// Why performing an action failed, as far as the availability check cares.
+#[derive(Debug)]
+enum Denied {
+    Workflow(WorkflowError),
+    Period(LedgerError),
+}
+
+/// Performs `action` through the workflow transitions and permission checks.
+fn perform(action: WorkflowActionKind, ctx: &ActionContext<'_>) -> Result<(), Denied> {
+    match action {
+        WorkflowActionKind::Submit => WorkflowService::submit(ctx.status, ctx.user_id)
+            .map(|_| ())
+            .map_err(Denied::Workflow),
+        WorkflowActionKind::Approve => {
+            WorkflowService::approve(
+                ctx.status,
+                ctx.user_id,
+                None,
+                ctx.prior_approvers,
+                ctx.required_approvals,
+            )
+            .map_err(Denied::Workflow)?;
+            let own = ApprovalEngine::can_approve(
+                ctx.user_role.as_str(),
+                ctx.approval_limit,
+                ctx.required_role,
+                ctx.amount,
+            );
+            ctx.delegations
+                .iter()
+                .fold(own, |result, authority| {
+                    result.or_else(|_| {
+                        ApprovalEngine::can_approve_delegated(
+                            authority,
+                            ctx.required_role,
+                            ctx.amount,
+                        )
+                    })
+                })
+                .map_err(Denied::Workflow)
+        }
+        WorkflowActionKind::Reject => WorkflowService::reject(ctx.status, "Wrong amount".into())
+            .map(|_| ())
+            .map_err(Denied::Workflow),
+        WorkflowActionKind::Post => {
+            WorkflowService::post(ctx.status, ctx.user_id).map_err(Denied::Workflow)?;
+            let role = crate::auth::UserRole::from(ctx.user_role);
+            validate_posting_permission(&ctx.period_status, &role).map_err(Denied::Period)
+        }
+        WorkflowActionKind::Void => {
+            WorkflowService::void(
+                ctx.status,
+                ctx.user_id,
+                VoidReasonCode::DuplicateEntry,
+                String::new(),
+            )
+            .map_err(Denied::Workflow)?;
+            if ctx.period_status == FiscalPeriodStatus::Closed {
+                return Err(Denied::Workflow(WorkflowError::PeriodClosed));
+            }
+            match ctx.reconciliation_id {
+                Some(reconciliation_id) => Err(Denied::Workflow(WorkflowError::EntryReconciled {
+                    reconciliation_id,
+                })),
+                None => Ok(()),
+            }
+        }
+    }
+}
+
+/// Returns the text a denial reason must contain for the given failure.
+fn expected_reason_fragment(denied: &Denied) -> String {
+    match denied {
+        Denied::Workflow(WorkflowError::InvalidTransition { from, .. }) => {
+            format!("a {from} transaction")
+        }
+        Denied::Workflow(WorkflowError::AlreadyApproved { .. }) => "already approved".into(),
+        Denied::Workflow(WorkflowError::ExceedsApprovalLimit { limit, .. }) => {
+            format!("approval limit of {}", limit.normalize())
+        }
+        Denied::Workflow(WorkflowError::InsufficientRole { required_role, .. }) => {
+            format!("requires the {required_role} role")
+        }
+        Denied::Workflow(WorkflowError::PeriodClosed) => "fiscal period is closed".into(),
+        Denied::Workflow(WorkflowError::EntryReconciled { reconciliation_id }) => {
+            format!("reconciliation {reconciliation_id}")
+        }
+        Denied::Period(LedgerError::PeriodClosed) => "is closed".into(),
+        Denied::Period(LedgerError::PeriodSoftClosed) => "soft-closed".into(),
+        other => panic!("unexpected denial {other:?}"),
+    }
+}
+
 proptest! {
     #![proptest_config(ProptestConfig::with_cases(100))]
 
@@ -69,7 +209,7 @@ proptest! {
         user_id in arb_uuid(),
         notes in arb_approval_notes()
     ) {
-        let result = WorkflowService::approve(TransactionStatus::Pending, user_id, notes.clone());
+        let result = WorkflowService::approve(TransactionStatus::Pending, user_id, notes.clone(), &[], 1);
         prop_assert!(result.is_ok());
         let action = result.unwrap();
         prop_assert_eq!(action.new_status(), TransactionStatus::Approved);
@@ -83,6 +223,39 @@ proptest! {
         }
     }
 
+    /// Approve stays Pending until the required number of approvals is met
+    #[test]
+    fn prop_approve_waits_for_required_approvals(
+        user_id in arb_uuid(),
+        prior in proptest::collection::vec(arb_uuid(), 0..5),
+        required in 1u32..5
+    ) {
+        prop_assume!(!prior.contains(&user_id));
+
+        let action = WorkflowService::approve(
+            TransactionStatus::Pending,
+            user_id,
+            None,
+            &prior,
+            required,
+        )
+        .unwrap();
+        let approvals = u32::try_from(prior.len()).unwrap() + 1;
+        let expected = if approvals >= required {
+            TransactionStatus::Approved
+        } else {
+            TransactionStatus::Pending
+        };
+        prop_assert_eq!(action.new_status(), expected);
+
+        if let crate::workflow::types::WorkflowAction::Approve { progress, .. } = action {
+            prop_assert_eq!(progress.approvals, approvals);
+            prop_assert_eq!(progress.required, required);
+        } else {
+            prop_assert!(false, "Expected Approve action");
+        }
+    }
+
     /// Pending + reject → Draft with rejection reason
     #[test]
     fn prop_reject_from_pending_succeeds(reason in arb_non_empty_string()) {
@@ -127,7 +300,7 @@ proptest! {
         // Skip empty strings after trim
         prop_assume!(!reason.trim().is_empty());
 
-        let result = WorkflowService::void(TransactionStatus::Posted, user_id, reason.clone());
+        let result = WorkflowService::void(TransactionStatus::Posted, user_id, VoidReasonCode::Other, reason.clone());
         prop_assert!(result.is_ok());
         let action = result.unwrap();
         prop_assert_eq!(action.new_status(), TransactionStatus::Voided);
@@ -173,7 +346,7 @@ proptest! {
     ) {
         prop_assume!(status != TransactionStatus::Pending);
 
-        let result = WorkflowService::approve(status, user_id, None);
+        let result = WorkflowService::approve(status, user_id, None, &[], 1);
         match result {
             Err(WorkflowError::InvalidTransition { from, to }) => {
                 prop_assert_eq!(from, status);
@@ -230,7 +403,7 @@ proptest! {
         prop_assume!(status != TransactionStatus::Posted);
         prop_assume!(!reason.trim().is_empty());
 
-        let result = WorkflowService::void(status, user_id, reason);
+        let result = WorkflowService::void(status, user_id, VoidReasonCode::Other, reason);
         match result {
             Err(WorkflowError::InvalidTransition { from, to }) => {
                 prop_assert_eq!(from, status);
@@ -261,6 +434,97 @@ proptest! {
             "is_valid_transition({:?}, {:?}) = {}, expected {}",
             from, to, is_valid, expected_valid);
     }
+
+    /// Only immutable statuses refuse updates, and only drafts can be
+    /// deleted; the persistence layer applies these same guards
+    #[test]
+    fn prop_modify_and_delete_guards_follow_status(status in arb_status()) {
+        let modify = WorkflowService::validate_modify(status);
+        let delete = WorkflowService::validate_delete(status);
+
+        prop_assert_eq!(modify.is_ok(), !status.is_immutable());
+        prop_assert_eq!(delete.is_ok(), status == TransactionStatus::Draft);
+        match status {
+            TransactionStatus::Posted => {
+                prop_assert!(matches!(modify, Err(WorkflowError::CannotModifyPosted)));
+                prop_assert!(matches!(delete, Err(WorkflowError::CannotModifyPosted)));
+            }
+            TransactionStatus::Voided => {
+                prop_assert!(matches!(modify, Err(WorkflowError::CannotModifyVoided)));
+                prop_assert!(matches!(delete, Err(WorkflowError::CannotModifyVoided)));
+            }
+            TransactionStatus::Pending | TransactionStatus::Approved => {
+                prop_assert!(matches!(delete, Err(WorkflowError::CanOnlyDeleteDraft)));
+            }
+            TransactionStatus::Draft => {}
+        }
+        // Statuses that can still be posted can still be corrected
+        if WorkflowService::is_valid_transition(status, TransactionStatus::Posted) {
+            prop_assert!(modify.is_ok());
+        }
+    }
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(100))]
+
+    // =========================================================================
+    // Property: Available actions agree with the transitions
+    // Feature: transaction-workflow, Property: Action Affordances
+    // =========================================================================
+
+    /// Every action reported allowed succeeds when performed, and every
+    /// denied one fails with the error its reason describes
+    #[test]
+    fn prop_available_actions_match_transitions(
+        status in arb_status(),
+        user_id in arb_uuid(),
+        user_role in arb_role(),
+        approval_limit in proptest::option::of(arb_amount()),
+        amount in arb_amount(),
+        required_role in arb_role(),
+        required_approvals in 1u32..3,
+        already_approved in any::<bool>(),
+        delegations in proptest::collection::vec(arb_delegation(), 0..3),
+        period_status in arb_period_status(),
+        reconciliation_id in proptest::option::of(arb_uuid()),
+    ) {
+        let prior_approvers = if already_approved { vec![user_id] } else { vec![] };
+        let ctx = ActionContext {
+            status,
+            user_id,
+            user_role,
+            approval_limit,
+            amount,
+            required_role: required_role.as_str(),
+            required_approvals,
+            prior_approvers: &prior_approvers,
+            delegations: &delegations,
+            period_status,
+            reconciliation_id,
+        };
+
+        let available = WorkflowService::available_actions(&ctx);
+        prop_assert_eq!(available.len(), WorkflowActionKind::ALL.len());
+
+        for availability in available {
+            match perform(availability.action, &ctx) {
+                Ok(()) => {
+                    prop_assert!(availability.allowed, "{:?} should be allowed", availability);
+                    prop_assert!(availability.reason.is_none());
+                }
+                Err(denied) => {
+                    prop_assert!(!availability.allowed, "{:?} should be denied: {:?}", availability, denied);
+                    let reason = availability.reason.clone().unwrap_or_default();
+                    let fragment = expected_reason_fragment(&denied);
+                    prop_assert!(
+                        reason.contains(&fragment),
+                        "reason {:?} should mention {:?}", reason, fragment
+                    );
+                }
+            }
+        }
+    }
 }
 
 // =========================================================================
@@ -301,21 +565,36 @@ mod edge_case_tests {
     #[test]
     fn test_void_empty_reason_fails() {
         let user_id = Uuid::new_v4();
-        let result = WorkflowService::void(TransactionStatus::Posted, user_id, String::new());
+        let result = WorkflowService::void(
+            TransactionStatus::Posted,
+            user_id,
+            VoidReasonCode::Other,
+            String::new(),
+        );
         assert!(matches!(result, Err(WorkflowError::VoidReasonRequired)));
     }
 
     #[test]
     fn test_void_whitespace_only_reason_fails() {
         let user_id = Uuid::new_v4();
-        let result = WorkflowService::void(TransactionStatus::Posted, user_id, "   ".to_string());
+        let result = WorkflowService::void(
+            TransactionStatus::Posted,
+            user_id,
+            VoidReasonCode::Other,
+            "   ".to_string(),
+        );
         assert!(matches!(result, Err(WorkflowError::VoidReasonRequired)));
     }
 
     #[test]
     fn test_void_newline_only_reason_fails() {
         let user_id = Uuid::new_v4();
-        let result = WorkflowService::void(TransactionStatus::Posted, user_id, "\n\n".to_string());
+        let result = WorkflowService::void(
+            TransactionStatus::Posted,
+            user_id,
+            VoidReasonCode::Other,
+            "\n\n".to_string(),
+        );
         assert!(matches!(result, Err(WorkflowError::VoidReasonRequired)));
     }
 
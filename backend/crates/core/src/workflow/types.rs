@@ -13,7 +13,7 @@ use uuid::Uuid;
 /// Transactions progress through these states from creation to posting.
 /// The valid transitions are:
 /// - Draft → Pending (submit)
-/// - Pending → Approved (approve)
+/// - Pending → Approved (approve, once enough approvals are recorded)
 /// - Pending → Draft (reject)
 /// - Approved → Posted (post)
 /// - Posted → Voided (void)
@@ -33,6 +33,15 @@ pub enum TransactionStatus {
 }
 
 impl TransactionStatus {
+    /// Every status, in workflow order.
+    pub const ALL: [Self; 5] = [
+        Self::Draft,
+        Self::Pending,
+        Self::Approved,
+        Self::Posted,
+        Self::Voided,
+    ];
+
     /// Returns the string representation of the status.
     #[must_use]
     pub fn as_str(&self) -> &'static str {
@@ -76,6 +85,87 @@ impl fmt::Display for TransactionStatus {
     }
 }
 
+/// Why a posted transaction was voided.
+///
+/// Codes make voids comparable across periods; the free-text reason adds
+/// detail and is only required for `Other`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum VoidReasonCode {
+    /// The transaction was entered twice.
+    DuplicateEntry,
+    /// The amount was wrong.
+    WrongAmount,
+    /// An entry was booked to the wrong account.
+    WrongAccount,
+    /// The transaction was dated in the wrong period.
+    WrongPeriod,
+    /// Correction of a fraudulent entry.
+    FraudCorrection,
+    /// Anything else; a reason text is required.
+    Other,
+}
+
+impl VoidReasonCode {
+    /// Every code, in reporting order.
+    pub const ALL: [Self; 6] = [
+        Self::DuplicateEntry,
+        Self::WrongAmount,
+        Self::WrongAccount,
+        Self::WrongPeriod,
+        Self::FraudCorrection,
+        Self::Other,
+    ];
+
+    /// Returns the string representation of the code.
+    #[must_use]
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::DuplicateEntry => "duplicate_entry",
+            Self::WrongAmount => "wrong_amount",
+            Self::WrongAccount => "wrong_account",
+            Self::WrongPeriod => "wrong_period",
+            Self::FraudCorrection => "fraud_correction",
+            Self::Other => "other",
+        }
+    }
+
+    /// Returns true if a reason text must accompany the code.
+    #[must_use]
+    pub fn requires_text(&self) -> bool {
+        matches!(self, Self::Other)
+    }
+}
+
+impl fmt::Display for VoidReasonCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// How many of a transaction's required approvals have been recorded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ApprovalProgress {
+    /// Approvals recorded so far.
+    pub approvals: u32,
+    /// Approvals needed before the transaction is approved.
+    pub required: u32,
+}
+
+impl ApprovalProgress {
+    /// Returns true once enough approvals have been recorded.
+    #[must_use]
+    pub fn is_complete(&self) -> bool {
+        self.approvals >= self.required
+    }
+}
+
+impl fmt::Display for ApprovalProgress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}", self.approvals, self.required)
+    }
+}
+
 /// Workflow action representing a state transition with audit data.
 ///
 /// Each variant captures the action performed, the resulting status,
@@ -91,9 +181,9 @@ pub enum WorkflowAction {
         /// When the transaction was submitted.
         submitted_at: DateTime<Utc>,
     },
-    /// Approve a pending transaction.
+    /// Record an approval of a pending transaction.
     Approve {
-        /// The new status after approval.
+        /// The new status after approval (Pending until enough approvals).
         new_status: TransactionStatus,
         /// The user who approved the transaction.
         approved_by: Uuid,
@@ -101,6 +191,8 @@ pub enum WorkflowAction {
         approved_at: DateTime<Utc>,
         /// Optional notes from the approver.
         approval_notes: Option<String>,
+        /// Approvals recorded, counting this one.
+        progress: ApprovalProgress,
     },
     /// Reject a pending transaction back to draft.
     Reject {
@@ -126,7 +218,9 @@ pub enum WorkflowAction {
         voided_by: Uuid,
         /// When the transaction was voided.
         voided_at: DateTime<Utc>,
-        /// The reason for voiding.
+        /// The categorized reason for voiding.
+        void_reason_code: VoidReasonCode,
+        /// The reason for voiding, possibly empty unless the code is `Other`.
         void_reason: String,
     },
 }
@@ -145,6 +239,77 @@ impl WorkflowAction {
     }
 }
 
+/// A workflow transition a user can request, without its audit data.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum WorkflowActionKind {
+    /// Submit a draft for approval.
+    Submit,
+    /// Approve a pending transaction.
+    Approve,
+    /// Reject a pending transaction back to draft.
+    Reject,
+    /// Post an approved transaction to the ledger.
+    Post,
+    /// Void a posted transaction.
+    Void,
+}
+
+impl WorkflowActionKind {
+    /// Every action, in workflow order.
+    pub const ALL: [Self; 5] = [
+        Self::Submit,
+        Self::Approve,
+        Self::Reject,
+        Self::Post,
+        Self::Void,
+    ];
+
+    /// Returns the string representation of the action.
+    #[must_use]
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Submit => "submit",
+            Self::Approve => "approve",
+            Self::Reject => "reject",
+            Self::Post => "post",
+            Self::Void => "void",
+        }
+    }
+
+    /// Returns the status the action moves a transaction to.
+    ///
+    /// An approval short of the required count leaves the transaction
+    /// pending, but Approved is the transition it is checked against.
+    #[must_use]
+    pub fn target_status(&self) -> TransactionStatus {
+        match self {
+            Self::Submit => TransactionStatus::Pending,
+            Self::Approve => TransactionStatus::Approved,
+            Self::Reject => TransactionStatus::Draft,
+            Self::Post => TransactionStatus::Posted,
+            Self::Void => TransactionStatus::Voided,
+        }
+    }
+}
+
+impl fmt::Display for WorkflowActionKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// Whether a user may perform a workflow action right now.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ActionAvailability {
+    /// The action.
+    pub action: WorkflowActionKind,
+    /// True if the action would be accepted.
+    pub allowed: bool,
+    /// Why the action is not allowed; `None` when it is.
+    pub reason: Option<String>,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,63 @@
+//! `SeaORM` Entity for `account_default_dimensions` table.
+
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize)]
+#[sea_orm(table_name = "account_default_dimensions")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: Uuid,
+    pub organization_id: Uuid,
+    pub account_id: Uuid,
+    pub dimension_value_id: Uuid,
+    pub created_at: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::chart_of_accounts::Entity",
+        from = "Column::AccountId",
+        to = "super::chart_of_accounts::Column::Id",
+        on_update = "NoAction",
+        on_delete = "Cascade"
+    )]
+    ChartOfAccounts,
+    #[sea_orm(
+        belongs_to = "super::dimension_values::Entity",
+        from = "Column::DimensionValueId",
+        to = "super::dimension_values::Column::Id",
+        on_update = "NoAction",
+        on_delete = "Cascade"
+    )]
+    DimensionValues,
+    #[sea_orm(
+        belongs_to = "super::organizations::Entity",
+        from = "Column::OrganizationId",
+        to = "super::organizations::Column::Id",
+        on_update = "NoAction",
+        on_delete = "Cascade"
+    )]
+    Organizations,
+}
+
+impl Related<super::chart_of_accounts::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::ChartOfAccounts.def()
+    }
+}
+
+impl Related<super::dimension_values::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::DimensionValues.def()
+    }
+}
+
+impl Related<super::organizations::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Organizations.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,70 @@
+//! `SeaORM` Entity for `account_period_balances` table.
+
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize)]
+#[sea_orm(table_name = "account_period_balances")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: Uuid,
+    pub organization_id: Uuid,
+    pub account_id: Uuid,
+    pub fiscal_period_id: Uuid,
+    pub period_end: Date,
+    #[sea_orm(column_type = "Decimal(Some((19, 4)))")]
+    pub total_debit: Decimal,
+    #[sea_orm(column_type = "Decimal(Some((19, 4)))")]
+    pub total_credit: Decimal,
+    #[sea_orm(column_type = "Decimal(Some((19, 4)))")]
+    pub ending_balance: Decimal,
+    pub created_at: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::chart_of_accounts::Entity",
+        from = "Column::AccountId",
+        to = "super::chart_of_accounts::Column::Id",
+        on_update = "NoAction",
+        on_delete = "Cascade"
+    )]
+    ChartOfAccounts,
+    #[sea_orm(
+        belongs_to = "super::fiscal_periods::Entity",
+        from = "Column::FiscalPeriodId",
+        to = "super::fiscal_periods::Column::Id",
+        on_update = "NoAction",
+        on_delete = "Cascade"
+    )]
+    FiscalPeriods,
+    #[sea_orm(
+        belongs_to = "super::organizations::Entity",
+        from = "Column::OrganizationId",
+        to = "super::organizations::Column::Id",
+        on_update = "NoAction",
+        on_delete = "Cascade"
+    )]
+    Organizations,
+}
+
+impl Related<super::chart_of_accounts::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::ChartOfAccounts.def()
+    }
+}
+
+impl Related<super::fiscal_periods::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::FiscalPeriods.def()
+    }
+}
+
+impl Related<super::organizations::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Organizations.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
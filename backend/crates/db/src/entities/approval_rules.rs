@@ -1,4 +1,4 @@
-//! `SeaORM` Entity, @generated by sea-orm-codegen 1.1.19
+//! `SeaORM` Entity, @generated by sea-orm-codegen 1.1.20
 
 use super::sea_orm_active_enums::TransactionType;
 use super::sea_orm_active_enums::UserRole;
@@ -24,6 +24,7 @@ pub struct Model {
     pub is_active: bool,
     pub created_at: DateTimeWithTimeZone,
     pub updated_at: DateTimeWithTimeZone,
+    pub required_approvals: i16,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -36,6 +37,8 @@ pub enum Relation {
         on_delete = "Cascade"
     )]
     Organizations,
+    #[sea_orm(has_many = "super::transactions::Entity")]
+    Transactions,
 }
 
 impl Related<super::organizations::Entity> for Entity {
@@ -44,4 +47,10 @@ impl Related<super::organizations::Entity> for Entity {
     }
 }
 
+impl Related<super::transactions::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Transactions.def()
+    }
+}
+
 impl ActiveModelBehavior for ActiveModel {}
@@ -26,10 +26,22 @@ pub struct Model {
     pub ocr_processed_at: Option<DateTimeWithTimeZone>,
     pub uploaded_by: Uuid,
     pub created_at: DateTimeWithTimeZone,
+    pub ledger_entry_id: Option<Uuid>,
+    pub moved_from_transaction_id: Option<Uuid>,
+    pub moved_by: Option<Uuid>,
+    pub moved_at: Option<DateTimeWithTimeZone>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
 pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::ledger_entries::Entity",
+        from = "Column::LedgerEntryId",
+        to = "super::ledger_entries::Column::Id",
+        on_update = "NoAction",
+        on_delete = "Cascade"
+    )]
+    LedgerEntries,
     #[sea_orm(
         belongs_to = "super::organizations::Entity",
         from = "Column::OrganizationId",
@@ -56,6 +68,12 @@ pub enum Relation {
     Users,
 }
 
+impl Related<super::ledger_entries::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::LedgerEntries.def()
+    }
+}
+
 impl Related<super::organizations::Entity> for Entity {
     fn to() -> RelationDef {
         Relation::Organizations.def()
@@ -0,0 +1,86 @@
+//! `SeaORM` Entity for `budget_line_comments` table.
+
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize)]
+#[sea_orm(table_name = "budget_line_comments")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: Uuid,
+    #[sea_orm(unique)]
+    pub comment_id: Uuid,
+    pub organization_id: Uuid,
+    pub budget_line_id: Uuid,
+    pub fiscal_period_id: Uuid,
+    pub author_id: Uuid,
+    pub edited_by: Option<Uuid>,
+    #[sea_orm(column_type = "Text")]
+    pub body: String,
+    pub is_current: bool,
+    pub created_at: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::budget_lines::Entity",
+        from = "Column::BudgetLineId",
+        to = "super::budget_lines::Column::Id",
+        on_update = "NoAction",
+        on_delete = "Cascade"
+    )]
+    BudgetLines,
+    #[sea_orm(
+        belongs_to = "super::fiscal_periods::Entity",
+        from = "Column::FiscalPeriodId",
+        to = "super::fiscal_periods::Column::Id",
+        on_update = "NoAction",
+        on_delete = "Cascade"
+    )]
+    FiscalPeriods,
+    #[sea_orm(
+        belongs_to = "super::organizations::Entity",
+        from = "Column::OrganizationId",
+        to = "super::organizations::Column::Id",
+        on_update = "NoAction",
+        on_delete = "Cascade"
+    )]
+    Organizations,
+    #[sea_orm(
+        belongs_to = "super::users::Entity",
+        from = "Column::AuthorId",
+        to = "super::users::Column::Id",
+        on_update = "NoAction",
+        on_delete = "NoAction"
+    )]
+    Users2,
+    #[sea_orm(
+        belongs_to = "super::users::Entity",
+        from = "Column::EditedBy",
+        to = "super::users::Column::Id",
+        on_update = "NoAction",
+        on_delete = "NoAction"
+    )]
+    Users1,
+}
+
+impl Related<super::budget_lines::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::BudgetLines.def()
+    }
+}
+
+impl Related<super::fiscal_periods::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::FiscalPeriods.def()
+    }
+}
+
+impl Related<super::organizations::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Organizations.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
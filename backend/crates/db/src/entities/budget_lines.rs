@@ -1,4 +1,4 @@
-//! `SeaORM` Entity, @generated by sea-orm-codegen 1.1.19
+//! `SeaORM` Entity, @generated by sea-orm-codegen 1.1.20
 
 use sea_orm::entity::prelude::*;
 use serde::{Deserialize, Serialize};
@@ -21,6 +21,8 @@ pub struct Model {
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
 pub enum Relation {
+    #[sea_orm(has_many = "super::budget_line_comments::Entity")]
+    BudgetLineComments,
     #[sea_orm(has_many = "super::budget_line_dimensions::Entity")]
     BudgetLineDimensions,
     #[sea_orm(
@@ -49,6 +51,12 @@ pub enum Relation {
     FiscalPeriods,
 }
 
+impl Related<super::budget_line_comments::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::BudgetLineComments.def()
+    }
+}
+
 impl Related<super::budget_line_dimensions::Entity> for Entity {
     fn to() -> RelationDef {
         Relation::BudgetLineDimensions.def()
@@ -1,4 +1,4 @@
-//! `SeaORM` Entity, @generated by sea-orm-codegen 1.1.19
+//! `SeaORM` Entity, @generated by sea-orm-codegen 1.1.20
 
 use super::sea_orm_active_enums::BudgetType;
 use sea_orm::entity::prelude::*;
@@ -21,10 +21,13 @@ pub struct Model {
     pub created_by: Uuid,
     pub created_at: DateTimeWithTimeZone,
     pub updated_at: DateTimeWithTimeZone,
+    pub owner_user_id: Option<Uuid>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
 pub enum Relation {
+    #[sea_orm(has_many = "super::budget_editors::Entity")]
+    BudgetEditors,
     #[sea_orm(has_many = "super::budget_lines::Entity")]
     BudgetLines,
     #[sea_orm(
@@ -58,7 +61,21 @@ pub enum Relation {
         on_update = "NoAction",
         on_delete = "NoAction"
     )]
-    Users,
+    Users2,
+    #[sea_orm(
+        belongs_to = "super::users::Entity",
+        from = "Column::OwnerUserId",
+        to = "super::users::Column::Id",
+        on_update = "NoAction",
+        on_delete = "SetNull"
+    )]
+    Users1,
+}
+
+impl Related<super::budget_editors::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::BudgetEditors.def()
+    }
 }
 
 impl Related<super::budget_lines::Entity> for Entity {
@@ -85,10 +102,4 @@ impl Related<super::organizations::Entity> for Entity {
     }
 }
 
-impl Related<super::users::Entity> for Entity {
-    fn to() -> RelationDef {
-        Relation::Users.def()
-    }
-}
-
 impl ActiveModelBehavior for ActiveModel {}
@@ -1,4 +1,4 @@
-//! `SeaORM` Entity, @generated by sea-orm-codegen 1.1.19
+//! `SeaORM` Entity, @generated by sea-orm-codegen 1.1.20
 
 use super::sea_orm_active_enums::AccountSubtype;
 use super::sea_orm_active_enums::AccountType;
@@ -30,6 +30,10 @@ pub struct Model {
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
 pub enum Relation {
+    #[sea_orm(has_many = "super::account_default_dimensions::Entity")]
+    AccountDefaultDimensions,
+    #[sea_orm(has_many = "super::account_period_balances::Entity")]
+    AccountPeriodBalances,
     #[sea_orm(has_many = "super::budget_lines::Entity")]
     BudgetLines,
     #[sea_orm(
@@ -58,6 +62,22 @@ pub enum Relation {
         on_delete = "Cascade"
     )]
     Organizations,
+    #[sea_orm(has_one = "super::reconciliations::Entity")]
+    Reconciliations,
+    #[sea_orm(has_many = "super::transaction_template_lines::Entity")]
+    TransactionTemplateLines,
+}
+
+impl Related<super::account_default_dimensions::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::AccountDefaultDimensions.def()
+    }
+}
+
+impl Related<super::account_period_balances::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::AccountPeriodBalances.def()
+    }
 }
 
 impl Related<super::budget_lines::Entity> for Entity {
@@ -84,4 +104,16 @@ impl Related<super::organizations::Entity> for Entity {
     }
 }
 
+impl Related<super::reconciliations::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Reconciliations.def()
+    }
+}
+
+impl Related<super::transaction_template_lines::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::TransactionTemplateLines.def()
+    }
+}
+
 impl ActiveModelBehavior for ActiveModel {}
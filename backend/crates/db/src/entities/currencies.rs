@@ -1,4 +1,4 @@
-//! `SeaORM` Entity, @generated by sea-orm-codegen 1.1.19
+//! `SeaORM` Entity, @generated by sea-orm-codegen 1.1.20
 
 use sea_orm::entity::prelude::*;
 use serde::{Deserialize, Serialize};
@@ -20,6 +20,8 @@ pub enum Relation {
     Budgets,
     #[sea_orm(has_many = "super::chart_of_accounts::Entity")]
     ChartOfAccounts,
+    #[sea_orm(has_many = "super::transaction_templates::Entity")]
+    TransactionTemplates,
 }
 
 impl Related<super::budgets::Entity> for Entity {
@@ -34,4 +36,10 @@ impl Related<super::chart_of_accounts::Entity> for Entity {
     }
 }
 
+impl Related<super::transaction_templates::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::TransactionTemplates.def()
+    }
+}
+
 impl ActiveModelBehavior for ActiveModel {}
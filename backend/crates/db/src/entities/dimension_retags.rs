@@ -0,0 +1,72 @@
+//! `SeaORM` Entity for `dimension_retags` table.
+
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize)]
+#[sea_orm(table_name = "dimension_retags")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: Uuid,
+    pub organization_id: Uuid,
+    pub from_value_id: Uuid,
+    pub to_value_id: Uuid,
+    pub date_from: Option<Date>,
+    pub date_to: Option<Date>,
+    pub account_ids: Vec<Uuid>,
+    pub affected_entries: i64,
+    #[sea_orm(column_type = "Text", nullable)]
+    pub reason: Option<String>,
+    pub retagged_by: Uuid,
+    pub created_at: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::dimension_values::Entity",
+        from = "Column::FromValueId",
+        to = "super::dimension_values::Column::Id",
+        on_update = "NoAction",
+        on_delete = "NoAction"
+    )]
+    DimensionValues2,
+    #[sea_orm(
+        belongs_to = "super::dimension_values::Entity",
+        from = "Column::ToValueId",
+        to = "super::dimension_values::Column::Id",
+        on_update = "NoAction",
+        on_delete = "NoAction"
+    )]
+    DimensionValues1,
+    #[sea_orm(
+        belongs_to = "super::organizations::Entity",
+        from = "Column::OrganizationId",
+        to = "super::organizations::Column::Id",
+        on_update = "NoAction",
+        on_delete = "Cascade"
+    )]
+    Organizations,
+    #[sea_orm(
+        belongs_to = "super::users::Entity",
+        from = "Column::RetaggedBy",
+        to = "super::users::Column::Id",
+        on_update = "NoAction",
+        on_delete = "NoAction"
+    )]
+    Users,
+}
+
+impl Related<super::organizations::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Organizations.def()
+    }
+}
+
+impl Related<super::users::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Users.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
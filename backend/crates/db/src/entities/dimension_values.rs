@@ -1,4 +1,4 @@
-//! `SeaORM` Entity, @generated by sea-orm-codegen 1.1.19
+//! `SeaORM` Entity, @generated by sea-orm-codegen 1.1.20
 
 use sea_orm::entity::prelude::*;
 use serde::{Deserialize, Serialize};
@@ -24,6 +24,8 @@ pub struct Model {
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
 pub enum Relation {
+    #[sea_orm(has_many = "super::account_default_dimensions::Entity")]
+    AccountDefaultDimensions,
     #[sea_orm(has_many = "super::budget_line_dimensions::Entity")]
     BudgetLineDimensions,
     #[sea_orm(
@@ -54,6 +56,12 @@ pub enum Relation {
     Organizations,
 }
 
+impl Related<super::account_default_dimensions::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::AccountDefaultDimensions.def()
+    }
+}
+
 impl Related<super::budget_line_dimensions::Entity> for Entity {
     fn to() -> RelationDef {
         Relation::BudgetLineDimensions.def()
@@ -0,0 +1,86 @@
+//! `SeaORM` Entity for `exchange_rate_overrides` table.
+
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize)]
+#[sea_orm(table_name = "exchange_rate_overrides")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: Uuid,
+    pub organization_id: Uuid,
+    pub transaction_id: Uuid,
+    #[sea_orm(unique)]
+    pub ledger_entry_id: Uuid,
+    pub from_currency: String,
+    pub to_currency: String,
+    pub rate_date: Date,
+    #[sea_orm(column_type = "Decimal(Some((19, 10)))", nullable)]
+    pub looked_up_rate: Option<Decimal>,
+    #[sea_orm(column_type = "Decimal(Some((19, 10)))")]
+    pub override_rate: Decimal,
+    pub overridden_by: Option<Uuid>,
+    pub created_at: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::ledger_entries::Entity",
+        from = "Column::LedgerEntryId",
+        to = "super::ledger_entries::Column::Id",
+        on_update = "NoAction",
+        on_delete = "Cascade"
+    )]
+    LedgerEntries,
+    #[sea_orm(
+        belongs_to = "super::organizations::Entity",
+        from = "Column::OrganizationId",
+        to = "super::organizations::Column::Id",
+        on_update = "NoAction",
+        on_delete = "Cascade"
+    )]
+    Organizations,
+    #[sea_orm(
+        belongs_to = "super::transactions::Entity",
+        from = "Column::TransactionId",
+        to = "super::transactions::Column::Id",
+        on_update = "NoAction",
+        on_delete = "Cascade"
+    )]
+    Transactions,
+    #[sea_orm(
+        belongs_to = "super::users::Entity",
+        from = "Column::OverriddenBy",
+        to = "super::users::Column::Id",
+        on_update = "NoAction",
+        on_delete = "NoAction"
+    )]
+    Users,
+}
+
+impl Related<super::ledger_entries::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::LedgerEntries.def()
+    }
+}
+
+impl Related<super::organizations::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Organizations.def()
+    }
+}
+
+impl Related<super::transactions::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Transactions.def()
+    }
+}
+
+impl Related<super::users::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Users.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -1,4 +1,4 @@
-//! `SeaORM` Entity, @generated by sea-orm-codegen 1.1.19
+//! `SeaORM` Entity, @generated by sea-orm-codegen 1.1.20
 
 use super::sea_orm_active_enums::RateSource;
 use sea_orm::entity::prelude::*;
@@ -47,6 +47,8 @@ pub enum Relation {
         on_delete = "Cascade"
     )]
     Organizations,
+    #[sea_orm(has_many = "super::rate_corrections::Entity")]
+    RateCorrections,
     #[sea_orm(
         belongs_to = "super::users::Entity",
         from = "Column::CreatedBy",
@@ -63,6 +65,12 @@ impl Related<super::organizations::Entity> for Entity {
     }
 }
 
+impl Related<super::rate_corrections::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::RateCorrections.def()
+    }
+}
+
 impl Related<super::users::Entity> for Entity {
     fn to() -> RelationDef {
         Relation::Users.def()
@@ -1,4 +1,4 @@
-//! `SeaORM` Entity, @generated by sea-orm-codegen 1.1.19
+//! `SeaORM` Entity, @generated by sea-orm-codegen 1.1.20
 
 use super::sea_orm_active_enums::FiscalPeriodStatus;
 use sea_orm::entity::prelude::*;
@@ -25,8 +25,14 @@ pub struct Model {
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
 pub enum Relation {
+    #[sea_orm(has_many = "super::account_period_balances::Entity")]
+    AccountPeriodBalances,
+    #[sea_orm(has_many = "super::budget_line_comments::Entity")]
+    BudgetLineComments,
     #[sea_orm(has_many = "super::budget_lines::Entity")]
     BudgetLines,
+    #[sea_orm(has_many = "super::fiscal_period_events::Entity")]
+    FiscalPeriodEvents,
     #[sea_orm(
         belongs_to = "super::fiscal_years::Entity",
         from = "Column::FiscalYearId",
@@ -55,12 +61,30 @@ pub enum Relation {
     Users,
 }
 
+impl Related<super::account_period_balances::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::AccountPeriodBalances.def()
+    }
+}
+
+impl Related<super::budget_line_comments::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::BudgetLineComments.def()
+    }
+}
+
 impl Related<super::budget_lines::Entity> for Entity {
     fn to() -> RelationDef {
         Relation::BudgetLines.def()
     }
 }
 
+impl Related<super::fiscal_period_events::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::FiscalPeriodEvents.def()
+    }
+}
+
 impl Related<super::fiscal_years::Entity> for Entity {
     fn to() -> RelationDef {
         Relation::FiscalYears.def()
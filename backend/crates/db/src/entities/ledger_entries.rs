@@ -1,4 +1,4 @@
-//! `SeaORM` Entity, @generated by sea-orm-codegen 1.1.19
+//! `SeaORM` Entity, @generated by sea-orm-codegen 1.1.20
 
 use sea_orm::entity::prelude::*;
 use serde::{Deserialize, Serialize};
@@ -30,10 +30,13 @@ pub struct Model {
     pub memo: Option<String>,
     pub event_at: DateTimeWithTimeZone,
     pub created_at: DateTimeWithTimeZone,
+    pub is_override: bool,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
 pub enum Relation {
+    #[sea_orm(has_many = "super::attachments::Entity")]
+    Attachments,
     #[sea_orm(
         belongs_to = "super::chart_of_accounts::Entity",
         from = "Column::AccountId",
@@ -60,6 +63,10 @@ pub enum Relation {
     Currencies1,
     #[sea_orm(has_many = "super::entry_dimensions::Entity")]
     EntryDimensions,
+    #[sea_orm(has_one = "super::exchange_rate_overrides::Entity")]
+    ExchangeRateOverrides,
+    #[sea_orm(has_many = "super::reconciliation_items::Entity")]
+    ReconciliationItems,
     #[sea_orm(
         belongs_to = "super::transactions::Entity",
         from = "Column::TransactionId",
@@ -70,6 +77,12 @@ pub enum Relation {
     Transactions,
 }
 
+impl Related<super::attachments::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Attachments.def()
+    }
+}
+
 impl Related<super::chart_of_accounts::Entity> for Entity {
     fn to() -> RelationDef {
         Relation::ChartOfAccounts.def()
@@ -82,6 +95,18 @@ impl Related<super::entry_dimensions::Entity> for Entity {
     }
 }
 
+impl Related<super::exchange_rate_overrides::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::ExchangeRateOverrides.def()
+    }
+}
+
+impl Related<super::reconciliation_items::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::ReconciliationItems.def()
+    }
+}
+
 impl Related<super::transactions::Entity> for Entity {
     fn to() -> RelationDef {
         Relation::Transactions.def()
@@ -8,26 +8,51 @@
 
 pub mod prelude;
 
+pub mod account_default_dimensions;
+pub mod account_period_balances;
+pub mod api_keys;
+pub mod approval_delegations;
 pub mod approval_rules;
 pub mod attachments;
+pub mod budget_editors;
+pub mod budget_line_comments;
 pub mod budget_line_dimensions;
 pub mod budget_lines;
 pub mod budgets;
 pub mod chart_of_accounts;
 pub mod currencies;
+pub mod dimension_retags;
 pub mod dimension_types;
 pub mod dimension_values;
 pub mod email_verification_tokens;
 pub mod entry_dimensions;
+pub mod exchange_rate_overrides;
 pub mod exchange_rates;
+pub mod fiscal_period_events;
 pub mod fiscal_periods;
 pub mod fiscal_years;
 pub mod ledger_entries;
+pub mod notification_preferences;
+pub mod notifications;
+pub mod operator_audit_log;
+pub mod organization_slug_history;
 pub mod organization_usage;
 pub mod organization_users;
 pub mod organizations;
+pub mod payment_applications;
+pub mod rate_corrections;
+pub mod reconciliation_items;
+pub mod reconciliations;
+pub mod report_definitions;
+pub mod report_mappings;
 pub mod sea_orm_active_enums;
 pub mod sessions;
 pub mod tier_limits;
+pub mod transaction_approvals;
+pub mod transaction_claims;
+pub mod transaction_comments;
+pub mod transaction_status_history;
+pub mod transaction_template_lines;
+pub mod transaction_templates;
 pub mod transactions;
 pub mod users;
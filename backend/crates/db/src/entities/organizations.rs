@@ -1,4 +1,4 @@
-//! `SeaORM` Entity, @generated by sea-orm-codegen 1.1.19
+//! `SeaORM` Entity, @generated by sea-orm-codegen 1.1.20
 
 use super::sea_orm_active_enums::SubscriptionStatus;
 use super::sea_orm_active_enums::SubscriptionTier;
@@ -31,30 +31,106 @@ pub struct Model {
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
 pub enum Relation {
+    #[sea_orm(has_many = "super::account_default_dimensions::Entity")]
+    AccountDefaultDimensions,
+    #[sea_orm(has_many = "super::account_period_balances::Entity")]
+    AccountPeriodBalances,
+    #[sea_orm(has_many = "super::api_keys::Entity")]
+    ApiKeys,
+    #[sea_orm(has_many = "super::approval_delegations::Entity")]
+    ApprovalDelegations,
     #[sea_orm(has_many = "super::approval_rules::Entity")]
     ApprovalRules,
     #[sea_orm(has_many = "super::attachments::Entity")]
     Attachments,
+    #[sea_orm(has_many = "super::budget_editors::Entity")]
+    BudgetEditors,
+    #[sea_orm(has_many = "super::budget_line_comments::Entity")]
+    BudgetLineComments,
     #[sea_orm(has_many = "super::budgets::Entity")]
     Budgets,
     #[sea_orm(has_many = "super::chart_of_accounts::Entity")]
     ChartOfAccounts,
+    #[sea_orm(has_many = "super::dimension_retags::Entity")]
+    DimensionRetags,
     #[sea_orm(has_many = "super::dimension_types::Entity")]
     DimensionTypes,
     #[sea_orm(has_many = "super::dimension_values::Entity")]
     DimensionValues,
+    #[sea_orm(has_many = "super::exchange_rate_overrides::Entity")]
+    ExchangeRateOverrides,
     #[sea_orm(has_many = "super::exchange_rates::Entity")]
     ExchangeRates,
+    #[sea_orm(has_many = "super::fiscal_period_events::Entity")]
+    FiscalPeriodEvents,
     #[sea_orm(has_many = "super::fiscal_periods::Entity")]
     FiscalPeriods,
     #[sea_orm(has_many = "super::fiscal_years::Entity")]
     FiscalYears,
+    #[sea_orm(has_many = "super::notifications::Entity")]
+    Notifications,
+    #[sea_orm(has_many = "super::operator_audit_log::Entity")]
+    OperatorAuditLog,
+    #[sea_orm(has_many = "super::organization_slug_history::Entity")]
+    OrganizationSlugHistory,
     #[sea_orm(has_many = "super::organization_usage::Entity")]
     OrganizationUsage,
     #[sea_orm(has_many = "super::organization_users::Entity")]
     OrganizationUsers,
+    #[sea_orm(has_many = "super::payment_applications::Entity")]
+    PaymentApplications,
+    #[sea_orm(has_many = "super::rate_corrections::Entity")]
+    RateCorrections,
+    #[sea_orm(has_many = "super::reconciliation_items::Entity")]
+    ReconciliationItems,
+    #[sea_orm(has_many = "super::reconciliations::Entity")]
+    Reconciliations,
+    #[sea_orm(has_many = "super::report_definitions::Entity")]
+    ReportDefinitions,
+    #[sea_orm(has_one = "super::report_mappings::Entity")]
+    ReportMappings,
+    #[sea_orm(has_many = "super::sessions::Entity")]
+    Sessions,
+    #[sea_orm(has_many = "super::transaction_approvals::Entity")]
+    TransactionApprovals,
+    #[sea_orm(has_many = "super::transaction_claims::Entity")]
+    TransactionClaims,
+    #[sea_orm(has_many = "super::transaction_comments::Entity")]
+    TransactionComments,
+    #[sea_orm(has_many = "super::transaction_status_history::Entity")]
+    TransactionStatusHistory,
+    #[sea_orm(has_many = "super::transaction_template_lines::Entity")]
+    TransactionTemplateLines,
+    #[sea_orm(has_many = "super::transaction_templates::Entity")]
+    TransactionTemplates,
     #[sea_orm(has_many = "super::transactions::Entity")]
     Transactions,
+    #[sea_orm(has_many = "super::users::Entity")]
+    Users,
+}
+
+impl Related<super::account_default_dimensions::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::AccountDefaultDimensions.def()
+    }
+}
+
+impl Related<super::account_period_balances::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::AccountPeriodBalances.def()
+    }
+}
+
+impl Related<super::api_keys::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::ApiKeys.def()
+    }
+}
+
+impl Related<super::approval_delegations::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::ApprovalDelegations.def()
+    }
 }
 
 impl Related<super::approval_rules::Entity> for Entity {
@@ -69,6 +145,18 @@ impl Related<super::attachments::Entity> for Entity {
     }
 }
 
+impl Related<super::budget_editors::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::BudgetEditors.def()
+    }
+}
+
+impl Related<super::budget_line_comments::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::BudgetLineComments.def()
+    }
+}
+
 impl Related<super::budgets::Entity> for Entity {
     fn to() -> RelationDef {
         Relation::Budgets.def()
@@ -81,6 +169,12 @@ impl Related<super::chart_of_accounts::Entity> for Entity {
     }
 }
 
+impl Related<super::dimension_retags::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::DimensionRetags.def()
+    }
+}
+
 impl Related<super::dimension_types::Entity> for Entity {
     fn to() -> RelationDef {
         Relation::DimensionTypes.def()
@@ -93,12 +187,24 @@ impl Related<super::dimension_values::Entity> for Entity {
     }
 }
 
+impl Related<super::exchange_rate_overrides::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::ExchangeRateOverrides.def()
+    }
+}
+
 impl Related<super::exchange_rates::Entity> for Entity {
     fn to() -> RelationDef {
         Relation::ExchangeRates.def()
     }
 }
 
+impl Related<super::fiscal_period_events::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::FiscalPeriodEvents.def()
+    }
+}
+
 impl Related<super::fiscal_periods::Entity> for Entity {
     fn to() -> RelationDef {
         Relation::FiscalPeriods.def()
@@ -111,6 +217,24 @@ impl Related<super::fiscal_years::Entity> for Entity {
     }
 }
 
+impl Related<super::notifications::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Notifications.def()
+    }
+}
+
+impl Related<super::operator_audit_log::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::OperatorAuditLog.def()
+    }
+}
+
+impl Related<super::organization_slug_history::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::OrganizationSlugHistory.def()
+    }
+}
+
 impl Related<super::organization_usage::Entity> for Entity {
     fn to() -> RelationDef {
         Relation::OrganizationUsage.def()
@@ -123,6 +247,84 @@ impl Related<super::organization_users::Entity> for Entity {
     }
 }
 
+impl Related<super::payment_applications::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::PaymentApplications.def()
+    }
+}
+
+impl Related<super::rate_corrections::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::RateCorrections.def()
+    }
+}
+
+impl Related<super::reconciliation_items::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::ReconciliationItems.def()
+    }
+}
+
+impl Related<super::reconciliations::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Reconciliations.def()
+    }
+}
+
+impl Related<super::report_definitions::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::ReportDefinitions.def()
+    }
+}
+
+impl Related<super::report_mappings::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::ReportMappings.def()
+    }
+}
+
+impl Related<super::sessions::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Sessions.def()
+    }
+}
+
+impl Related<super::transaction_approvals::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::TransactionApprovals.def()
+    }
+}
+
+impl Related<super::transaction_claims::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::TransactionClaims.def()
+    }
+}
+
+impl Related<super::transaction_comments::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::TransactionComments.def()
+    }
+}
+
+impl Related<super::transaction_status_history::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::TransactionStatusHistory.def()
+    }
+}
+
+impl Related<super::transaction_template_lines::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::TransactionTemplateLines.def()
+    }
+}
+
+impl Related<super::transaction_templates::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::TransactionTemplates.def()
+    }
+}
+
 impl Related<super::transactions::Entity> for Entity {
     fn to() -> RelationDef {
         Relation::Transactions.def()
@@ -0,0 +1,68 @@
+//! `SeaORM` Entity for `payment_applications` table.
+
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize)]
+#[sea_orm(table_name = "payment_applications")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: Uuid,
+    pub organization_id: Uuid,
+    pub payment_transaction_id: Uuid,
+    pub settles_transaction_id: Uuid,
+    #[sea_orm(column_type = "Decimal(Some((19, 4)))")]
+    pub applied_amount: Decimal,
+    pub created_by: Uuid,
+    pub created_at: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::organizations::Entity",
+        from = "Column::OrganizationId",
+        to = "super::organizations::Column::Id",
+        on_update = "NoAction",
+        on_delete = "Cascade"
+    )]
+    Organizations,
+    #[sea_orm(
+        belongs_to = "super::transactions::Entity",
+        from = "Column::PaymentTransactionId",
+        to = "super::transactions::Column::Id",
+        on_update = "NoAction",
+        on_delete = "Cascade"
+    )]
+    Transactions2,
+    #[sea_orm(
+        belongs_to = "super::transactions::Entity",
+        from = "Column::SettlesTransactionId",
+        to = "super::transactions::Column::Id",
+        on_update = "NoAction",
+        on_delete = "Cascade"
+    )]
+    Transactions1,
+    #[sea_orm(
+        belongs_to = "super::users::Entity",
+        from = "Column::CreatedBy",
+        to = "super::users::Column::Id",
+        on_update = "NoAction",
+        on_delete = "NoAction"
+    )]
+    Users,
+}
+
+impl Related<super::organizations::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Organizations.def()
+    }
+}
+
+impl Related<super::users::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Users.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -1,23 +1,49 @@
-//! `SeaORM` Entity, @generated by sea-orm-codegen 1.1.19
+//! `SeaORM` Entity, @generated by sea-orm-codegen 1.1.20
 
+pub use super::account_default_dimensions::Entity as AccountDefaultDimensions;
+pub use super::account_period_balances::Entity as AccountPeriodBalances;
+pub use super::api_keys::Entity as ApiKeys;
+pub use super::approval_delegations::Entity as ApprovalDelegations;
 pub use super::approval_rules::Entity as ApprovalRules;
 pub use super::attachments::Entity as Attachments;
+pub use super::budget_editors::Entity as BudgetEditors;
+pub use super::budget_line_comments::Entity as BudgetLineComments;
 pub use super::budget_line_dimensions::Entity as BudgetLineDimensions;
 pub use super::budget_lines::Entity as BudgetLines;
 pub use super::budgets::Entity as Budgets;
 pub use super::chart_of_accounts::Entity as ChartOfAccounts;
 pub use super::currencies::Entity as Currencies;
+pub use super::dimension_retags::Entity as DimensionRetags;
 pub use super::dimension_types::Entity as DimensionTypes;
 pub use super::dimension_values::Entity as DimensionValues;
 pub use super::email_verification_tokens::Entity as EmailVerificationTokens;
 pub use super::entry_dimensions::Entity as EntryDimensions;
+pub use super::exchange_rate_overrides::Entity as ExchangeRateOverrides;
 pub use super::exchange_rates::Entity as ExchangeRates;
+pub use super::fiscal_period_events::Entity as FiscalPeriodEvents;
 pub use super::fiscal_periods::Entity as FiscalPeriods;
 pub use super::fiscal_years::Entity as FiscalYears;
 pub use super::ledger_entries::Entity as LedgerEntries;
+pub use super::notification_preferences::Entity as NotificationPreferences;
+pub use super::notifications::Entity as Notifications;
+pub use super::operator_audit_log::Entity as OperatorAuditLog;
+pub use super::organization_slug_history::Entity as OrganizationSlugHistory;
 pub use super::organization_usage::Entity as OrganizationUsage;
 pub use super::organization_users::Entity as OrganizationUsers;
 pub use super::organizations::Entity as Organizations;
+pub use super::payment_applications::Entity as PaymentApplications;
+pub use super::rate_corrections::Entity as RateCorrections;
+pub use super::reconciliation_items::Entity as ReconciliationItems;
+pub use super::reconciliations::Entity as Reconciliations;
+pub use super::report_definitions::Entity as ReportDefinitions;
+pub use super::report_mappings::Entity as ReportMappings;
+pub use super::sessions::Entity as Sessions;
 pub use super::tier_limits::Entity as TierLimits;
+pub use super::transaction_approvals::Entity as TransactionApprovals;
+pub use super::transaction_claims::Entity as TransactionClaims;
+pub use super::transaction_comments::Entity as TransactionComments;
+pub use super::transaction_status_history::Entity as TransactionStatusHistory;
+pub use super::transaction_template_lines::Entity as TransactionTemplateLines;
+pub use super::transaction_templates::Entity as TransactionTemplates;
 pub use super::transactions::Entity as Transactions;
 pub use super::users::Entity as Users;
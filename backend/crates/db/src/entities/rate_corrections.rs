@@ -0,0 +1,73 @@
+//! `SeaORM` Entity for `rate_corrections` table.
+
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize)]
+#[sea_orm(table_name = "rate_corrections")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: Uuid,
+    pub organization_id: Uuid,
+    pub exchange_rate_id: Option<Uuid>,
+    pub from_currency: String,
+    pub to_currency: String,
+    pub effective_date: Date,
+    #[sea_orm(column_type = "Decimal(Some((19, 10)))")]
+    pub old_rate: Decimal,
+    #[sea_orm(column_type = "Decimal(Some((19, 10)))")]
+    pub new_rate: Decimal,
+    pub affected_entries: i64,
+    #[sea_orm(column_type = "Text", nullable)]
+    pub reason: Option<String>,
+    pub corrected_by: Option<Uuid>,
+    pub created_at: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::exchange_rates::Entity",
+        from = "Column::ExchangeRateId",
+        to = "super::exchange_rates::Column::Id",
+        on_update = "NoAction",
+        on_delete = "SetNull"
+    )]
+    ExchangeRates,
+    #[sea_orm(
+        belongs_to = "super::organizations::Entity",
+        from = "Column::OrganizationId",
+        to = "super::organizations::Column::Id",
+        on_update = "NoAction",
+        on_delete = "Cascade"
+    )]
+    Organizations,
+    #[sea_orm(
+        belongs_to = "super::users::Entity",
+        from = "Column::CorrectedBy",
+        to = "super::users::Column::Id",
+        on_update = "NoAction",
+        on_delete = "NoAction"
+    )]
+    Users,
+}
+
+impl Related<super::exchange_rates::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::ExchangeRates.def()
+    }
+}
+
+impl Related<super::organizations::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Organizations.def()
+    }
+}
+
+impl Related<super::users::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Users.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
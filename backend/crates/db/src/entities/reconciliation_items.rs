@@ -0,0 +1,63 @@
+//! `SeaORM` Entity for `reconciliation_items` table.
+
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize)]
+#[sea_orm(table_name = "reconciliation_items")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: Uuid,
+    pub organization_id: Uuid,
+    pub reconciliation_id: Uuid,
+    pub ledger_entry_id: Uuid,
+    pub created_at: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::ledger_entries::Entity",
+        from = "Column::LedgerEntryId",
+        to = "super::ledger_entries::Column::Id",
+        on_update = "NoAction",
+        on_delete = "NoAction"
+    )]
+    LedgerEntries,
+    #[sea_orm(
+        belongs_to = "super::organizations::Entity",
+        from = "Column::OrganizationId",
+        to = "super::organizations::Column::Id",
+        on_update = "NoAction",
+        on_delete = "Cascade"
+    )]
+    Organizations,
+    #[sea_orm(
+        belongs_to = "super::reconciliations::Entity",
+        from = "Column::ReconciliationId",
+        to = "super::reconciliations::Column::Id",
+        on_update = "NoAction",
+        on_delete = "Cascade"
+    )]
+    Reconciliations,
+}
+
+impl Related<super::ledger_entries::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::LedgerEntries.def()
+    }
+}
+
+impl Related<super::organizations::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Organizations.def()
+    }
+}
+
+impl Related<super::reconciliations::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Reconciliations.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,84 @@
+//! `SeaORM` Entity for reconciliations table.
+
+use super::sea_orm_active_enums::ReconciliationStatus;
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize)]
+#[sea_orm(table_name = "reconciliations")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: Uuid,
+    pub organization_id: Uuid,
+    #[sea_orm(unique)]
+    pub account_id: Uuid,
+    pub statement_date: Date,
+    #[sea_orm(column_type = "Decimal(Some((19, 4)))")]
+    pub statement_ending_balance: Decimal,
+    #[sea_orm(column_type = "Decimal(Some((19, 4)))")]
+    pub opening_balance: Decimal,
+    pub status: ReconciliationStatus,
+    pub created_by: Uuid,
+    pub completed_by: Option<Uuid>,
+    pub completed_at: Option<DateTimeWithTimeZone>,
+    pub created_at: DateTimeWithTimeZone,
+    pub updated_at: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::chart_of_accounts::Entity",
+        from = "Column::AccountId",
+        to = "super::chart_of_accounts::Column::Id",
+        on_update = "NoAction",
+        on_delete = "NoAction"
+    )]
+    ChartOfAccounts,
+    #[sea_orm(
+        belongs_to = "super::organizations::Entity",
+        from = "Column::OrganizationId",
+        to = "super::organizations::Column::Id",
+        on_update = "NoAction",
+        on_delete = "Cascade"
+    )]
+    Organizations,
+    #[sea_orm(has_many = "super::reconciliation_items::Entity")]
+    ReconciliationItems,
+    #[sea_orm(
+        belongs_to = "super::users::Entity",
+        from = "Column::CompletedBy",
+        to = "super::users::Column::Id",
+        on_update = "NoAction",
+        on_delete = "NoAction"
+    )]
+    Users2,
+    #[sea_orm(
+        belongs_to = "super::users::Entity",
+        from = "Column::CreatedBy",
+        to = "super::users::Column::Id",
+        on_update = "NoAction",
+        on_delete = "NoAction"
+    )]
+    Users1,
+}
+
+impl Related<super::chart_of_accounts::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::ChartOfAccounts.def()
+    }
+}
+
+impl Related<super::organizations::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Organizations.def()
+    }
+}
+
+impl Related<super::reconciliation_items::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::ReconciliationItems.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -102,6 +102,14 @@ pub enum BudgetType {
     Project,
 }
 #[derive(Debug, Clone, PartialEq, Eq, EnumIter, DeriveActiveEnum, Serialize, Deserialize)]
+#[sea_orm(rs_type = "String", db_type = "Enum", enum_name = "entry_type")]
+pub enum EntryType {
+    #[sea_orm(string_value = "debit")]
+    Debit,
+    #[sea_orm(string_value = "credit")]
+    Credit,
+}
+#[derive(Debug, Clone, PartialEq, Eq, EnumIter, DeriveActiveEnum, Serialize, Deserialize)]
 #[sea_orm(
     rs_type = "String",
     db_type = "Enum",
@@ -134,6 +142,18 @@ pub enum RateSource {
     BankFeed,
 }
 #[derive(Debug, Clone, PartialEq, Eq, EnumIter, DeriveActiveEnum, Serialize, Deserialize)]
+#[sea_orm(
+    rs_type = "String",
+    db_type = "Enum",
+    enum_name = "reconciliation_status"
+)]
+pub enum ReconciliationStatus {
+    #[sea_orm(string_value = "in_progress")]
+    InProgress,
+    #[sea_orm(string_value = "completed")]
+    Completed,
+}
+#[derive(Debug, Clone, PartialEq, Eq, EnumIter, DeriveActiveEnum, Serialize, Deserialize)]
 #[sea_orm(rs_type = "String", db_type = "Enum", enum_name = "storage_provider")]
 pub enum StorageProvider {
     #[sea_orm(string_value = "cloudflare_r2")]
@@ -231,3 +251,19 @@ pub enum UserRole {
     #[sea_orm(string_value = "submitter")]
     Submitter,
 }
+#[derive(Debug, Clone, PartialEq, Eq, EnumIter, DeriveActiveEnum, Serialize, Deserialize)]
+#[sea_orm(rs_type = "String", db_type = "Enum", enum_name = "void_reason_code")]
+pub enum VoidReasonCode {
+    #[sea_orm(string_value = "duplicate_entry")]
+    DuplicateEntry,
+    #[sea_orm(string_value = "wrong_amount")]
+    WrongAmount,
+    #[sea_orm(string_value = "wrong_account")]
+    WrongAccount,
+    #[sea_orm(string_value = "wrong_period")]
+    WrongPeriod,
+    #[sea_orm(string_value = "fraud_correction")]
+    FraudCorrection,
+    #[sea_orm(string_value = "other")]
+    Other,
+}
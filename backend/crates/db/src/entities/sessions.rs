@@ -11,6 +11,7 @@ pub struct Model {
     pub user_id: Uuid,
     pub organization_id: Uuid,
     pub refresh_token_hash: String,
+    #[sea_orm(column_type = "Text", nullable)]
     pub user_agent: Option<String>,
     pub ip_address: Option<String>,
     pub expires_at: DateTimeWithTimeZone,
@@ -21,29 +22,33 @@ pub struct Model {
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
 pub enum Relation {
-    #[sea_orm(
-        belongs_to = "super::users::Entity",
-        from = "Column::UserId",
-        to = "super::users::Column::Id"
-    )]
-    Users,
     #[sea_orm(
         belongs_to = "super::organizations::Entity",
         from = "Column::OrganizationId",
-        to = "super::organizations::Column::Id"
+        to = "super::organizations::Column::Id",
+        on_update = "NoAction",
+        on_delete = "Cascade"
     )]
     Organizations,
+    #[sea_orm(
+        belongs_to = "super::users::Entity",
+        from = "Column::UserId",
+        to = "super::users::Column::Id",
+        on_update = "NoAction",
+        on_delete = "Cascade"
+    )]
+    Users,
 }
 
-impl Related<super::users::Entity> for Entity {
+impl Related<super::organizations::Entity> for Entity {
     fn to() -> RelationDef {
-        Relation::Users.def()
+        Relation::Organizations.def()
     }
 }
 
-impl Related<super::organizations::Entity> for Entity {
+impl Related<super::users::Entity> for Entity {
     fn to() -> RelationDef {
-        Relation::Organizations.def()
+        Relation::Users.def()
     }
 }
 
@@ -0,0 +1,72 @@
+//! `SeaORM` Entity for `transaction_template_lines` table.
+
+use super::sea_orm_active_enums::EntryType;
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize)]
+#[sea_orm(table_name = "transaction_template_lines")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: Uuid,
+    pub organization_id: Uuid,
+    pub template_id: Uuid,
+    pub line_number: i16,
+    pub account_id: Uuid,
+    pub entry_type: EntryType,
+    #[sea_orm(column_type = "Decimal(Some((19, 4)))", nullable)]
+    pub amount: Option<Decimal>,
+    #[sea_orm(column_type = "Decimal(Some((7, 4)))", nullable)]
+    pub percentage: Option<Decimal>,
+    #[sea_orm(column_type = "Text", nullable)]
+    pub memo: Option<String>,
+    pub dimension_value_ids: Vec<Uuid>,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::chart_of_accounts::Entity",
+        from = "Column::AccountId",
+        to = "super::chart_of_accounts::Column::Id",
+        on_update = "NoAction",
+        on_delete = "NoAction"
+    )]
+    ChartOfAccounts,
+    #[sea_orm(
+        belongs_to = "super::organizations::Entity",
+        from = "Column::OrganizationId",
+        to = "super::organizations::Column::Id",
+        on_update = "NoAction",
+        on_delete = "Cascade"
+    )]
+    Organizations,
+    #[sea_orm(
+        belongs_to = "super::transaction_templates::Entity",
+        from = "Column::TemplateId",
+        to = "super::transaction_templates::Column::Id",
+        on_update = "NoAction",
+        on_delete = "Cascade"
+    )]
+    TransactionTemplates,
+}
+
+impl Related<super::chart_of_accounts::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::ChartOfAccounts.def()
+    }
+}
+
+impl Related<super::organizations::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Organizations.def()
+    }
+}
+
+impl Related<super::transaction_templates::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::TransactionTemplates.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -1,7 +1,9 @@
-//! `SeaORM` Entity, @generated by sea-orm-codegen 1.1.19
+//! `SeaORM` Entity, @generated by sea-orm-codegen 1.1.20
 
 use super::sea_orm_active_enums::TransactionStatus;
 use super::sea_orm_active_enums::TransactionType;
+use super::sea_orm_active_enums::UserRole;
+use super::sea_orm_active_enums::VoidReasonCode;
 use sea_orm::entity::prelude::*;
 use serde::{Deserialize, Serialize};
 
@@ -37,12 +39,30 @@ pub struct Model {
     pub reverses_transaction_id: Option<Uuid>,
     pub created_at: DateTimeWithTimeZone,
     pub updated_at: DateTimeWithTimeZone,
+    pub void_reason_code: Option<VoidReasonCode>,
+    pub approved_on_behalf_of: Option<Uuid>,
+    pub last_escalated_at: Option<DateTimeWithTimeZone>,
+    pub fingerprint: Option<String>,
+    pub required_role_at_submit: Option<UserRole>,
+    pub required_approvals: Option<i16>,
+    pub matched_rule_id: Option<Uuid>,
+    pub budget_warning: bool,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
 pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::approval_rules::Entity",
+        from = "Column::MatchedRuleId",
+        to = "super::approval_rules::Column::Id",
+        on_update = "NoAction",
+        on_delete = "SetNull"
+    )]
+    ApprovalRules,
     #[sea_orm(has_many = "super::attachments::Entity")]
     Attachments,
+    #[sea_orm(has_many = "super::exchange_rate_overrides::Entity")]
+    ExchangeRateOverrides,
     #[sea_orm(
         belongs_to = "super::fiscal_periods::Entity",
         from = "Column::FiscalPeriodId",
@@ -61,6 +81,14 @@ pub enum Relation {
         on_delete = "Cascade"
     )]
     Organizations,
+    #[sea_orm(has_many = "super::transaction_approvals::Entity")]
+    TransactionApprovals,
+    #[sea_orm(has_one = "super::transaction_claims::Entity")]
+    TransactionClaims,
+    #[sea_orm(has_many = "super::transaction_comments::Entity")]
+    TransactionComments,
+    #[sea_orm(has_many = "super::transaction_status_history::Entity")]
+    TransactionStatusHistory,
     #[sea_orm(
         belongs_to = "Entity",
         from = "Column::ReversedByTransactionId",
@@ -84,6 +112,14 @@ pub enum Relation {
         on_update = "NoAction",
         on_delete = "NoAction"
     )]
+    Users6,
+    #[sea_orm(
+        belongs_to = "super::users::Entity",
+        from = "Column::ApprovedOnBehalfOf",
+        to = "super::users::Column::Id",
+        on_update = "NoAction",
+        on_delete = "NoAction"
+    )]
     Users5,
     #[sea_orm(
         belongs_to = "super::users::Entity",
@@ -119,12 +155,24 @@ pub enum Relation {
     Users1,
 }
 
+impl Related<super::approval_rules::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::ApprovalRules.def()
+    }
+}
+
 impl Related<super::attachments::Entity> for Entity {
     fn to() -> RelationDef {
         Relation::Attachments.def()
     }
 }
 
+impl Related<super::exchange_rate_overrides::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::ExchangeRateOverrides.def()
+    }
+}
+
 impl Related<super::fiscal_periods::Entity> for Entity {
     fn to() -> RelationDef {
         Relation::FiscalPeriods.def()
@@ -143,4 +191,28 @@ impl Related<super::organizations::Entity> for Entity {
     }
 }
 
+impl Related<super::transaction_approvals::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::TransactionApprovals.def()
+    }
+}
+
+impl Related<super::transaction_claims::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::TransactionClaims.def()
+    }
+}
+
+impl Related<super::transaction_comments::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::TransactionComments.def()
+    }
+}
+
+impl Related<super::transaction_status_history::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::TransactionStatusHistory.def()
+    }
+}
+
 impl ActiveModelBehavior for ActiveModel {}
@@ -1,4 +1,4 @@
-//! `SeaORM` Entity, @generated by sea-orm-codegen 1.1.19
+//! `SeaORM` Entity, @generated by sea-orm-codegen 1.1.20
 
 use sea_orm::entity::prelude::*;
 use serde::{Deserialize, Serialize};
@@ -16,22 +16,69 @@ pub struct Model {
     pub email_verified_at: Option<DateTimeWithTimeZone>,
     pub created_at: DateTimeWithTimeZone,
     pub updated_at: DateTimeWithTimeZone,
+    pub token_version: i32,
+    pub locale: Option<String>,
+    pub default_organization_id: Option<Uuid>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
 pub enum Relation {
+    #[sea_orm(has_many = "super::api_keys::Entity")]
+    ApiKeys,
     #[sea_orm(has_many = "super::attachments::Entity")]
     Attachments,
-    #[sea_orm(has_many = "super::budgets::Entity")]
-    Budgets,
+    #[sea_orm(has_many = "super::budget_editors::Entity")]
+    BudgetEditors,
+    #[sea_orm(has_many = "super::dimension_retags::Entity")]
+    DimensionRetags,
+    #[sea_orm(has_many = "super::email_verification_tokens::Entity")]
+    EmailVerificationTokens,
+    #[sea_orm(has_many = "super::exchange_rate_overrides::Entity")]
+    ExchangeRateOverrides,
     #[sea_orm(has_many = "super::exchange_rates::Entity")]
     ExchangeRates,
+    #[sea_orm(has_many = "super::fiscal_period_events::Entity")]
+    FiscalPeriodEvents,
     #[sea_orm(has_many = "super::fiscal_periods::Entity")]
     FiscalPeriods,
     #[sea_orm(has_many = "super::fiscal_years::Entity")]
     FiscalYears,
+    #[sea_orm(has_many = "super::notification_preferences::Entity")]
+    NotificationPreferences,
+    #[sea_orm(has_many = "super::notifications::Entity")]
+    Notifications,
     #[sea_orm(has_many = "super::organization_users::Entity")]
     OrganizationUsers,
+    #[sea_orm(
+        belongs_to = "super::organizations::Entity",
+        from = "Column::DefaultOrganizationId",
+        to = "super::organizations::Column::Id",
+        on_update = "NoAction",
+        on_delete = "SetNull"
+    )]
+    Organizations,
+    #[sea_orm(has_many = "super::payment_applications::Entity")]
+    PaymentApplications,
+    #[sea_orm(has_many = "super::rate_corrections::Entity")]
+    RateCorrections,
+    #[sea_orm(has_many = "super::report_definitions::Entity")]
+    ReportDefinitions,
+    #[sea_orm(has_many = "super::report_mappings::Entity")]
+    ReportMappings,
+    #[sea_orm(has_many = "super::sessions::Entity")]
+    Sessions,
+    #[sea_orm(has_many = "super::transaction_claims::Entity")]
+    TransactionClaims,
+    #[sea_orm(has_many = "super::transaction_status_history::Entity")]
+    TransactionStatusHistory,
+    #[sea_orm(has_many = "super::transaction_templates::Entity")]
+    TransactionTemplates,
+}
+
+impl Related<super::api_keys::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::ApiKeys.def()
+    }
 }
 
 impl Related<super::attachments::Entity> for Entity {
@@ -40,9 +87,27 @@ impl Related<super::attachments::Entity> for Entity {
     }
 }
 
-impl Related<super::budgets::Entity> for Entity {
+impl Related<super::budget_editors::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::BudgetEditors.def()
+    }
+}
+
+impl Related<super::dimension_retags::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::DimensionRetags.def()
+    }
+}
+
+impl Related<super::email_verification_tokens::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::EmailVerificationTokens.def()
+    }
+}
+
+impl Related<super::exchange_rate_overrides::Entity> for Entity {
     fn to() -> RelationDef {
-        Relation::Budgets.def()
+        Relation::ExchangeRateOverrides.def()
     }
 }
 
@@ -52,6 +117,12 @@ impl Related<super::exchange_rates::Entity> for Entity {
     }
 }
 
+impl Related<super::fiscal_period_events::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::FiscalPeriodEvents.def()
+    }
+}
+
 impl Related<super::fiscal_periods::Entity> for Entity {
     fn to() -> RelationDef {
         Relation::FiscalPeriods.def()
@@ -64,12 +135,72 @@ impl Related<super::fiscal_years::Entity> for Entity {
     }
 }
 
+impl Related<super::notification_preferences::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::NotificationPreferences.def()
+    }
+}
+
+impl Related<super::notifications::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Notifications.def()
+    }
+}
+
 impl Related<super::organization_users::Entity> for Entity {
     fn to() -> RelationDef {
         Relation::OrganizationUsers.def()
     }
 }
 
+impl Related<super::payment_applications::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::PaymentApplications.def()
+    }
+}
+
+impl Related<super::rate_corrections::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::RateCorrections.def()
+    }
+}
+
+impl Related<super::report_definitions::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::ReportDefinitions.def()
+    }
+}
+
+impl Related<super::report_mappings::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::ReportMappings.def()
+    }
+}
+
+impl Related<super::sessions::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Sessions.def()
+    }
+}
+
+impl Related<super::transaction_claims::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::TransactionClaims.def()
+    }
+}
+
+impl Related<super::transaction_status_history::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::TransactionStatusHistory.def()
+    }
+}
+
+impl Related<super::transaction_templates::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::TransactionTemplates.def()
+    }
+}
+
 impl Related<super::organizations::Entity> for Entity {
     fn to() -> RelationDef {
         super::organization_users::Relation::Organizations.def()
@@ -5,12 +5,15 @@
 //! - Repository abstractions for data access
 //! - Database migrations
 //! - RLS (Row-Level Security) context management
+//! - Advisory locks for cross-replica mutual exclusion
 
 pub mod entities;
+pub mod lock;
 pub mod migration;
 pub mod repositories;
 pub mod rls;
 
+pub use lock::{lock_key, try_with_lock};
 pub use repositories::{
     EmailVerificationRepository, OrganizationRepository, SessionRepository, UserRepository,
 };
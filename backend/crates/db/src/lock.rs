@@ -0,0 +1,94 @@
+//! Cross-replica mutual exclusion with `PostgreSQL` advisory locks.
+//!
+//! The lock is transaction-scoped (`pg_try_advisory_xact_lock`): it is held
+//! by a transaction that stays open while the closure runs, and released when
+//! that transaction ends, including when the closure's future is dropped
+//! or panics.
+//!
+//! # Usage
+//!
+//! ```ignore
+//! use zeltra_db::lock::{lock_key, try_with_lock};
+//!
+//! let ran = try_with_lock(&db, lock_key("email_outbox"), || async {
+//!     send_pending_emails(&db).await
+//! })
+//! .await?;
+//!
+//! if ran.is_none() {
+//!     // Another replica holds the lock
+//! }
+//! ```
+
+use std::future::Future;
+
+use sea_orm::{ConnectionTrait, DatabaseConnection, DbBackend, DbErr, Statement, TransactionTrait};
+use sha2::{Digest, Sha256};
+
+/// Derives a stable advisory lock key from a name.
+///
+/// The key is the first eight bytes of the name's SHA-256 digest, so it is
+/// the same on every replica and across releases.
+#[must_use]
+pub fn lock_key(name: &str) -> i64 {
+    let digest = Sha256::digest(name.as_bytes());
+    let mut bytes = [0u8; 8];
+    bytes.copy_from_slice(&digest[..8]);
+    i64::from_be_bytes(bytes)
+}
+
+/// Runs `f` only if the advisory lock `key` is free.
+///
+/// Returns `Some` with the closure's output if it ran, or `None` without
+/// waiting if another session holds the lock. The lock is held on a separate
+/// pooled connection for as long as `f` runs.
+///
+/// # Errors
+///
+/// Returns an error if the lock transaction cannot be opened or the lock
+/// cannot be queried.
+pub async fn try_with_lock<F, Fut, T>(
+    db: &DatabaseConnection,
+    key: i64,
+    f: F,
+) -> Result<Option<T>, DbErr>
+where
+    F: FnOnce() -> Fut,
+    Fut: Future<Output = T>,
+{
+    let txn = db.begin().await?;
+
+    let acquired = txn
+        .query_one(Statement::from_sql_and_values(
+            DbBackend::Postgres,
+            "SELECT pg_try_advisory_xact_lock($1) AS acquired",
+            [key.into()],
+        ))
+        .await?
+        .map(|row| row.try_get::<bool>("", "acquired"))
+        .transpose()?
+        .unwrap_or(false);
+
+    if !acquired {
+        txn.rollback().await?;
+        return Ok(None);
+    }
+
+    let output = f().await;
+
+    // Ending the transaction releases the lock
+    txn.commit().await?;
+
+    Ok(Some(output))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lock_key_is_stable() {
+        assert_eq!(lock_key("email_outbox"), lock_key("email_outbox"));
+        assert_ne!(lock_key("email_outbox"), lock_key("usage_flush"));
+    }
+}
@@ -0,0 +1,87 @@
+//! Reconciliations migration for bank account reconciliation.
+//!
+//! Creates the reconciliations table and the reconciliation_items join table
+//! that records which ledger entries were cleared against a statement.
+
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+        db.execute_unprepared(RECONCILIATIONS_SQL).await?;
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+        db.execute_unprepared(
+            r"
+DROP TABLE IF EXISTS reconciliation_items CASCADE;
+DROP TABLE IF EXISTS reconciliations CASCADE;
+DROP TYPE IF EXISTS reconciliation_status;
+",
+        )
+        .await?;
+        Ok(())
+    }
+}
+
+const RECONCILIATIONS_SQL: &str = r"
+CREATE TYPE reconciliation_status AS ENUM ('in_progress', 'completed');
+
+-- One reconciliation per bank statement
+CREATE TABLE reconciliations (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    organization_id UUID NOT NULL REFERENCES organizations(id) ON DELETE CASCADE,
+    account_id UUID NOT NULL REFERENCES chart_of_accounts(id),
+    statement_date DATE NOT NULL,
+    statement_ending_balance NUMERIC(19, 4) NOT NULL,
+    -- Ending balance of the previous completed reconciliation at start time
+    opening_balance NUMERIC(19, 4) NOT NULL DEFAULT 0,
+    status reconciliation_status NOT NULL DEFAULT 'in_progress',
+    created_by UUID NOT NULL REFERENCES users(id),
+    completed_by UUID REFERENCES users(id),
+    completed_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    CONSTRAINT chk_completed_fields CHECK (
+        (status = 'completed' AND completed_by IS NOT NULL AND completed_at IS NOT NULL)
+        OR (status = 'in_progress' AND completed_at IS NULL)
+    )
+);
+
+-- Only one open reconciliation per account
+CREATE UNIQUE INDEX idx_reconciliations_one_open
+    ON reconciliations(account_id) WHERE status = 'in_progress';
+
+CREATE INDEX idx_reconciliations_account
+    ON reconciliations(account_id, statement_date DESC);
+
+-- Ledger entries cleared in a reconciliation
+CREATE TABLE reconciliation_items (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    organization_id UUID NOT NULL REFERENCES organizations(id) ON DELETE CASCADE,
+    reconciliation_id UUID NOT NULL REFERENCES reconciliations(id) ON DELETE CASCADE,
+    ledger_entry_id UUID NOT NULL REFERENCES ledger_entries(id),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    CONSTRAINT uq_reconciliation_item UNIQUE (reconciliation_id, ledger_entry_id)
+);
+
+CREATE INDEX idx_reconciliation_items_entry ON reconciliation_items(ledger_entry_id);
+
+-- Tenant isolation
+ALTER TABLE reconciliations ENABLE ROW LEVEL SECURITY;
+ALTER TABLE reconciliations FORCE ROW LEVEL SECURITY;
+ALTER TABLE reconciliation_items ENABLE ROW LEVEL SECURITY;
+ALTER TABLE reconciliation_items FORCE ROW LEVEL SECURITY;
+
+CREATE POLICY tenant_isolation ON reconciliations
+    USING (organization_id = current_setting('app.current_organization_id', true)::UUID);
+
+CREATE POLICY tenant_isolation ON reconciliation_items
+    USING (organization_id = current_setting('app.current_organization_id', true)::UUID);
+";
@@ -0,0 +1,28 @@
+//! Adds a per-user token version used to invalidate issued access tokens.
+//!
+//! Access tokens embed the version at issue time; bumping it (e.g. on a role
+//! change) makes the auth middleware reject every token issued before.
+
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+        db.execute_unprepared(
+            "ALTER TABLE users ADD COLUMN IF NOT EXISTS token_version INTEGER NOT NULL DEFAULT 0;",
+        )
+        .await?;
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+        db.execute_unprepared("ALTER TABLE users DROP COLUMN IF EXISTS token_version;")
+            .await?;
+        Ok(())
+    }
+}
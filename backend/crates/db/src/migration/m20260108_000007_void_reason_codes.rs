@@ -0,0 +1,49 @@
+//! Adds a categorized reason code to voided transactions.
+//!
+//! The free-text `void_reason` stays; the code makes voids groupable for
+//! reporting. Transactions voided before this migration keep a NULL code.
+
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+        db.execute_unprepared(
+            r"
+CREATE TYPE void_reason_code AS ENUM (
+    'duplicate_entry',
+    'wrong_amount',
+    'wrong_account',
+    'wrong_period',
+    'fraud_correction',
+    'other'
+);
+
+ALTER TABLE transactions ADD COLUMN void_reason_code void_reason_code;
+
+-- Void report lookups by organization and void date
+CREATE INDEX idx_transactions_voided
+    ON transactions(organization_id, voided_at) WHERE status = 'voided';
+",
+        )
+        .await?;
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+        db.execute_unprepared(
+            r"
+DROP INDEX IF EXISTS idx_transactions_voided;
+ALTER TABLE transactions DROP COLUMN IF EXISTS void_reason_code;
+DROP TYPE IF EXISTS void_reason_code;
+",
+        )
+        .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,67 @@
+//! Approval delegations for out-of-office cover.
+//!
+//! A delegation lets one member approve on another's behalf between two
+//! timestamps. Approvals made under a delegation record the delegator in
+//! `transactions.approved_on_behalf_of`.
+
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+        db.execute_unprepared(APPROVAL_DELEGATIONS_SQL).await?;
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+        db.execute_unprepared(
+            r"
+ALTER TABLE transactions DROP COLUMN IF EXISTS approved_on_behalf_of;
+DROP TABLE IF EXISTS approval_delegations CASCADE;
+",
+        )
+        .await?;
+        Ok(())
+    }
+}
+
+const APPROVAL_DELEGATIONS_SQL: &str = r"
+CREATE TABLE approval_delegations (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    organization_id UUID NOT NULL REFERENCES organizations(id) ON DELETE CASCADE,
+    delegator_id UUID NOT NULL REFERENCES users(id),
+    delegate_id UUID NOT NULL REFERENCES users(id),
+    starts_at TIMESTAMPTZ NOT NULL,
+    ends_at TIMESTAMPTZ NOT NULL,
+    -- Largest transaction the delegate may approve; NULL means no extra cap
+    amount_cap NUMERIC(19, 4),
+    created_by UUID NOT NULL REFERENCES users(id),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    CONSTRAINT chk_delegation_distinct_users CHECK (delegator_id <> delegate_id),
+    CONSTRAINT chk_delegation_period CHECK (ends_at > starts_at),
+    CONSTRAINT chk_delegation_cap_positive CHECK (amount_cap IS NULL OR amount_cap > 0)
+);
+
+-- Active delegation lookups for an approver
+CREATE INDEX idx_approval_delegations_delegate
+    ON approval_delegations(organization_id, delegate_id, ends_at);
+
+CREATE INDEX idx_approval_delegations_delegator
+    ON approval_delegations(organization_id, delegator_id);
+
+ALTER TABLE transactions
+    ADD COLUMN approved_on_behalf_of UUID REFERENCES users(id);
+
+-- Tenant isolation
+ALTER TABLE approval_delegations ENABLE ROW LEVEL SECURITY;
+ALTER TABLE approval_delegations FORCE ROW LEVEL SECURITY;
+
+CREATE POLICY tenant_isolation ON approval_delegations
+    USING (organization_id = current_setting('app.current_organization_id', true)::UUID);
+";
@@ -0,0 +1,57 @@
+//! Multi-step approval chains.
+//!
+//! Approval rules gain a `required_approvals` count, and each approval of a
+//! pending transaction is recorded in `transaction_approvals`. A transaction
+//! becomes approved once the matched rule's count of different users have
+//! approved it.
+
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+        db.execute_unprepared(APPROVAL_CHAINS_SQL).await?;
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+        db.execute_unprepared(
+            r"
+DROP TABLE IF EXISTS transaction_approvals CASCADE;
+ALTER TABLE approval_rules DROP COLUMN IF EXISTS required_approvals;
+",
+        )
+        .await?;
+        Ok(())
+    }
+}
+
+const APPROVAL_CHAINS_SQL: &str = r"
+ALTER TABLE approval_rules
+    ADD COLUMN required_approvals SMALLINT NOT NULL DEFAULT 1,
+    ADD CONSTRAINT chk_approval_rules_required_approvals CHECK (required_approvals >= 1);
+
+-- Approvals of the current submission; cleared when the transaction is rejected
+CREATE TABLE transaction_approvals (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    organization_id UUID NOT NULL REFERENCES organizations(id) ON DELETE CASCADE,
+    transaction_id UUID NOT NULL REFERENCES transactions(id) ON DELETE CASCADE,
+    approved_by UUID NOT NULL REFERENCES users(id),
+    approved_on_behalf_of UUID REFERENCES users(id),
+    approval_notes TEXT,
+    approved_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    CONSTRAINT uq_transaction_approvals_user UNIQUE (transaction_id, approved_by)
+);
+
+-- Tenant isolation
+ALTER TABLE transaction_approvals ENABLE ROW LEVEL SECURITY;
+ALTER TABLE transaction_approvals FORCE ROW LEVEL SECURITY;
+
+CREATE POLICY tenant_isolation ON transaction_approvals
+    USING (organization_id = current_setting('app.current_organization_id', true)::UUID);
+";
@@ -0,0 +1,86 @@
+//! Transaction templates for quick entry.
+//!
+//! A template stores a transaction's type, description, and entry skeleton
+//! for the user who saved it. Each line has a fixed amount, a percentage of
+//! a total given at instantiation, or neither.
+
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+        db.execute_unprepared(TRANSACTION_TEMPLATES_SQL).await?;
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+        db.execute_unprepared(
+            r"
+DROP TABLE IF EXISTS transaction_template_lines CASCADE;
+DROP TABLE IF EXISTS transaction_templates CASCADE;
+DROP TYPE IF EXISTS entry_type;
+",
+        )
+        .await?;
+        Ok(())
+    }
+}
+
+const TRANSACTION_TEMPLATES_SQL: &str = r"
+CREATE TYPE entry_type AS ENUM ('debit', 'credit');
+
+CREATE TABLE transaction_templates (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    organization_id UUID NOT NULL REFERENCES organizations(id) ON DELETE CASCADE,
+    -- Templates are private to the user who saved them
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    name VARCHAR(100) NOT NULL,
+    transaction_type transaction_type NOT NULL,
+    description TEXT NOT NULL,
+    memo TEXT,
+    currency CHAR(3) NOT NULL REFERENCES currencies(code),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    UNIQUE (organization_id, user_id, name)
+);
+
+CREATE TABLE transaction_template_lines (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    organization_id UUID NOT NULL REFERENCES organizations(id) ON DELETE CASCADE,
+    template_id UUID NOT NULL REFERENCES transaction_templates(id) ON DELETE CASCADE,
+    line_number SMALLINT NOT NULL,
+    account_id UUID NOT NULL REFERENCES chart_of_accounts(id),
+    entry_type entry_type NOT NULL,
+    -- At most one of amount and percentage; neither means the amount is given per line
+    amount NUMERIC(19, 4),
+    percentage NUMERIC(7, 4),
+    memo TEXT,
+    dimension_value_ids UUID[] NOT NULL DEFAULT '{}',
+    UNIQUE (template_id, line_number),
+    CONSTRAINT chk_template_line_amount CHECK (amount IS NULL OR percentage IS NULL),
+    CONSTRAINT chk_template_line_amount_positive CHECK (amount IS NULL OR amount > 0),
+    CONSTRAINT chk_template_line_percentage CHECK (
+        percentage IS NULL OR (percentage > 0 AND percentage <= 100)
+    )
+);
+
+CREATE INDEX idx_transaction_templates_user
+    ON transaction_templates(organization_id, user_id);
+
+-- Tenant isolation
+ALTER TABLE transaction_templates ENABLE ROW LEVEL SECURITY;
+ALTER TABLE transaction_templates FORCE ROW LEVEL SECURITY;
+ALTER TABLE transaction_template_lines ENABLE ROW LEVEL SECURITY;
+ALTER TABLE transaction_template_lines FORCE ROW LEVEL SECURITY;
+
+CREATE POLICY tenant_isolation ON transaction_templates
+    USING (organization_id = current_setting('app.current_organization_id', true)::UUID);
+
+CREATE POLICY tenant_isolation ON transaction_template_lines
+    USING (organization_id = current_setting('app.current_organization_id', true)::UUID);
+";
@@ -0,0 +1,40 @@
+//! Lets an attachment reference a single ledger entry.
+//!
+//! Line-level attachments still carry the transaction id, so listing a
+//! transaction's attachments returns them too. Removing the entry deletes
+//! its attachments with it.
+
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+        db.execute_unprepared(
+            r"
+ALTER TABLE attachments
+    ADD COLUMN ledger_entry_id UUID REFERENCES ledger_entries(id) ON DELETE CASCADE;
+
+CREATE INDEX idx_attachments_ledger_entry
+    ON attachments(ledger_entry_id) WHERE ledger_entry_id IS NOT NULL;
+",
+        )
+        .await?;
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+        db.execute_unprepared(
+            r"
+DROP INDEX IF EXISTS idx_attachments_ledger_entry;
+ALTER TABLE attachments DROP COLUMN IF EXISTS ledger_entry_id;
+",
+        )
+        .await?;
+        Ok(())
+    }
+}
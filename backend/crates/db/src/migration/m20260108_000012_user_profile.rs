@@ -0,0 +1,43 @@
+//! Adds profile preferences to users and email-change verification tokens.
+//!
+//! A verification token with `new_email` set confirms an address change
+//! rather than a signup; the user's email only changes when it is used.
+//! Deleting an organization clears it as anyone's default.
+
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+        db.execute_unprepared(
+            r"
+ALTER TABLE users
+    ADD COLUMN locale VARCHAR(16),
+    ADD COLUMN default_organization_id UUID REFERENCES organizations(id) ON DELETE SET NULL;
+
+ALTER TABLE email_verification_tokens
+    ADD COLUMN new_email VARCHAR(255);
+",
+        )
+        .await?;
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+        db.execute_unprepared(
+            r"
+ALTER TABLE email_verification_tokens DROP COLUMN IF EXISTS new_email;
+ALTER TABLE users
+    DROP COLUMN IF EXISTS default_organization_id,
+    DROP COLUMN IF EXISTS locale;
+",
+        )
+        .await?;
+        Ok(())
+    }
+}
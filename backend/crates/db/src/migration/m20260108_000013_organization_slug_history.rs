@@ -0,0 +1,38 @@
+//! Keeps previous organization slugs so renamed organizations stay reachable.
+//!
+//! A slug stays claimed by its organization after a rename, so old links
+//! can never resolve to someone else. The table is read before a tenant is
+//! known, like `organizations.slug`, so it has no row-level security.
+
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+        db.execute_unprepared(
+            r"
+CREATE TABLE organization_slug_history (
+    slug VARCHAR(100) PRIMARY KEY,
+    organization_id UUID NOT NULL REFERENCES organizations(id) ON DELETE CASCADE,
+    renamed_at TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+
+CREATE INDEX idx_organization_slug_history_org
+    ON organization_slug_history(organization_id);
+",
+        )
+        .await?;
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+        db.execute_unprepared("DROP TABLE IF EXISTS organization_slug_history;")
+            .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,39 @@
+//! Records when approvers were last reminded about a pending transaction.
+//!
+//! The escalation job reads the column to send at most one reminder per day
+//! for each transaction waiting on approval.
+
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+        db.execute_unprepared(
+            r"
+ALTER TABLE transactions ADD COLUMN IF NOT EXISTS last_escalated_at TIMESTAMPTZ;
+
+CREATE INDEX IF NOT EXISTS idx_transactions_pending_submitted
+    ON transactions(submitted_at)
+    WHERE status = 'pending';
+",
+        )
+        .await?;
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+        db.execute_unprepared(
+            r"
+DROP INDEX IF EXISTS idx_transactions_pending_submitted;
+ALTER TABLE transactions DROP COLUMN IF EXISTS last_escalated_at;
+",
+        )
+        .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,48 @@
+//! Default dimension values per account.
+//!
+//! Entries created on an account without a value of a dimension type get the
+//! account's default for that type. At most one default per type and account
+//! is enforced by the repository, since the type lives on the value.
+
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+        db.execute_unprepared(
+            r"
+CREATE TABLE account_default_dimensions (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    organization_id UUID NOT NULL REFERENCES organizations(id) ON DELETE CASCADE,
+    account_id UUID NOT NULL REFERENCES chart_of_accounts(id) ON DELETE CASCADE,
+    dimension_value_id UUID NOT NULL REFERENCES dimension_values(id) ON DELETE CASCADE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    UNIQUE (account_id, dimension_value_id)
+);
+
+CREATE INDEX idx_account_default_dimensions_org
+    ON account_default_dimensions(organization_id);
+
+-- Tenant isolation
+ALTER TABLE account_default_dimensions ENABLE ROW LEVEL SECURITY;
+ALTER TABLE account_default_dimensions FORCE ROW LEVEL SECURITY;
+
+CREATE POLICY tenant_isolation ON account_default_dimensions
+    USING (organization_id = current_setting('app.current_organization_id', true)::UUID);
+",
+        )
+        .await?;
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+        db.execute_unprepared("DROP TABLE IF EXISTS account_default_dimensions;")
+            .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,53 @@
+//! Payment applications against invoices and bills.
+//!
+//! Each row applies part of a posted payment to one invoice or bill. Limits
+//! on the applied amounts depend on ledger totals and are enforced by the
+//! repository; voiding a payment deletes its applications.
+
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+        db.execute_unprepared(
+            r"
+CREATE TABLE payment_applications (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    organization_id UUID NOT NULL REFERENCES organizations(id) ON DELETE CASCADE,
+    payment_transaction_id UUID NOT NULL REFERENCES transactions(id) ON DELETE CASCADE,
+    settles_transaction_id UUID NOT NULL REFERENCES transactions(id) ON DELETE CASCADE,
+    applied_amount NUMERIC(19, 4) NOT NULL CHECK (applied_amount > 0),
+    created_by UUID NOT NULL REFERENCES users(id),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    UNIQUE (payment_transaction_id, settles_transaction_id),
+    CHECK (payment_transaction_id <> settles_transaction_id)
+);
+
+CREATE INDEX idx_payment_applications_org
+    ON payment_applications(organization_id);
+CREATE INDEX idx_payment_applications_settles
+    ON payment_applications(settles_transaction_id);
+
+-- Tenant isolation
+ALTER TABLE payment_applications ENABLE ROW LEVEL SECURITY;
+ALTER TABLE payment_applications FORCE ROW LEVEL SECURITY;
+
+CREATE POLICY tenant_isolation ON payment_applications
+    USING (organization_id = current_setting('app.current_organization_id', true)::UUID);
+",
+        )
+        .await?;
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+        db.execute_unprepared("DROP TABLE IF EXISTS payment_applications;")
+            .await?;
+        Ok(())
+    }
+}
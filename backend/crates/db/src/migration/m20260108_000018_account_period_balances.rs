@@ -0,0 +1,55 @@
+//! Account balance snapshots at fiscal period close.
+//!
+//! Each row holds an account's cumulative posted debits and credits through
+//! the end of a closed period. Reports start from the latest usable snapshot
+//! and only scan entries dated after it. Rows are written by the repository
+//! when a period closes and can be rebuilt from the ledger at any time.
+
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+        db.execute_unprepared(
+            r"
+CREATE TABLE account_period_balances (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    organization_id UUID NOT NULL REFERENCES organizations(id) ON DELETE CASCADE,
+    account_id UUID NOT NULL REFERENCES chart_of_accounts(id) ON DELETE CASCADE,
+    fiscal_period_id UUID NOT NULL REFERENCES fiscal_periods(id) ON DELETE CASCADE,
+    period_end DATE NOT NULL,
+    total_debit NUMERIC(19, 4) NOT NULL,
+    total_credit NUMERIC(19, 4) NOT NULL,
+    ending_balance NUMERIC(19, 4) NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    UNIQUE (account_id, fiscal_period_id)
+);
+
+CREATE INDEX idx_account_period_balances_org_end
+    ON account_period_balances(organization_id, period_end DESC);
+CREATE INDEX idx_account_period_balances_account_end
+    ON account_period_balances(account_id, period_end DESC);
+
+-- Tenant isolation
+ALTER TABLE account_period_balances ENABLE ROW LEVEL SECURITY;
+ALTER TABLE account_period_balances FORCE ROW LEVEL SECURITY;
+
+CREATE POLICY tenant_isolation ON account_period_balances
+    USING (organization_id = current_setting('app.current_organization_id', true)::UUID);
+",
+        )
+        .await?;
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+        db.execute_unprepared("DROP TABLE IF EXISTS account_period_balances;")
+            .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,76 @@
+//! Allows zero-amount ledger lines on adjustment transactions.
+//!
+//! `chk_debit_or_credit` required every line to have exactly one positive
+//! side. It now also accepts a line that is zero on both sides, and a
+//! trigger limits such lines to adjustments, since a check constraint cannot
+//! look at the parent transaction. Zero lines add nothing to either side of
+//! the balance check.
+//!
+//! Rolling back keeps existing zero lines, since deleting them would rewrite
+//! recorded transactions. The old constraint comes back `NOT VALID`, so it
+//! only applies to lines written afterwards.
+
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+        db.execute_unprepared(
+            r"
+ALTER TABLE ledger_entries DROP CONSTRAINT chk_debit_or_credit;
+ALTER TABLE ledger_entries ADD CONSTRAINT chk_debit_or_credit CHECK (
+    (debit > 0 AND credit = 0)
+    OR (debit = 0 AND credit > 0)
+    OR (debit = 0 AND credit = 0 AND source_amount = 0)
+);
+
+CREATE OR REPLACE FUNCTION check_zero_entry_transaction_type()
+RETURNS TRIGGER AS $$
+DECLARE
+    txn_type transaction_type;
+BEGIN
+    IF NEW.debit = 0 AND NEW.credit = 0 THEN
+        SELECT transaction_type INTO txn_type
+        FROM transactions
+        WHERE id = NEW.transaction_id;
+
+        IF txn_type <> 'adjustment' THEN
+            RAISE EXCEPTION 'Zero-amount entries are only allowed on adjustment transactions';
+        END IF;
+    END IF;
+
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER trg_check_zero_entry_transaction_type
+BEFORE INSERT OR UPDATE ON ledger_entries
+FOR EACH ROW
+EXECUTE FUNCTION check_zero_entry_transaction_type();
+",
+        )
+        .await?;
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+        db.execute_unprepared(
+            r"
+DROP TRIGGER IF EXISTS trg_check_zero_entry_transaction_type ON ledger_entries;
+DROP FUNCTION IF EXISTS check_zero_entry_transaction_type();
+
+ALTER TABLE ledger_entries DROP CONSTRAINT chk_debit_or_credit;
+ALTER TABLE ledger_entries ADD CONSTRAINT chk_debit_or_credit CHECK (
+    (debit > 0 AND credit = 0) OR (debit = 0 AND credit > 0)
+) NOT VALID;
+",
+        )
+        .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,51 @@
+//! Transaction status history.
+//!
+//! One row per workflow transition, so a transaction rejected and resubmitted
+//! keeps its earlier submissions. The timestamp columns on `transactions`
+//! still hold the latest values. `from_status` is null when the transition
+//! created the transaction, as for the reversal written by a void.
+
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+        db.execute_unprepared(
+            r"
+CREATE TABLE transaction_status_history (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    organization_id UUID NOT NULL REFERENCES organizations(id) ON DELETE CASCADE,
+    transaction_id UUID NOT NULL REFERENCES transactions(id) ON DELETE CASCADE,
+    from_status transaction_status,
+    to_status transaction_status NOT NULL,
+    actor_id UUID NOT NULL REFERENCES users(id),
+    notes TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+
+CREATE INDEX idx_transaction_status_history_transaction
+    ON transaction_status_history(transaction_id, created_at);
+
+-- Tenant isolation
+ALTER TABLE transaction_status_history ENABLE ROW LEVEL SECURITY;
+ALTER TABLE transaction_status_history FORCE ROW LEVEL SECURITY;
+
+CREATE POLICY tenant_isolation ON transaction_status_history
+    USING (organization_id = current_setting('app.current_organization_id', true)::UUID);
+",
+        )
+        .await?;
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+        db.execute_unprepared("DROP TABLE IF EXISTS transaction_status_history;")
+            .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,86 @@
+//! Orders ledger entries by when they happened.
+//!
+//! Entries carry an `event_at` inside their transaction date, and an
+//! account's ledger lists them by `(event_at, account_version)`. The index
+//! on `event_at` gains the version as a tie-breaker.
+//!
+//! `get_account_balance_at` selected the entry with the highest version
+//! created before the cut-off, which mixed up import time with event time.
+//! It now sums the posted entries whose `event_at` is on or before the
+//! cut-off, so the result no longer depends on the order they were inserted.
+
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+        db.execute_unprepared(
+            r"
+DROP INDEX IF EXISTS idx_le_event_at;
+CREATE INDEX idx_le_event_at ON ledger_entries(account_id, event_at, account_version);
+
+CREATE OR REPLACE FUNCTION get_account_balance_at(
+    p_account_id UUID,
+    p_as_of TIMESTAMPTZ
+) RETURNS NUMERIC(19, 4) AS $$
+DECLARE
+    balance NUMERIC(19, 4);
+BEGIN
+    SELECT SUM(
+        CASE WHEN coa.account_type IN ('asset', 'expense')
+            THEN le.debit - le.credit
+            ELSE le.credit - le.debit
+        END
+    ) INTO balance
+    FROM ledger_entries le
+    JOIN transactions t ON t.id = le.transaction_id
+    JOIN chart_of_accounts coa ON coa.id = le.account_id
+    WHERE le.account_id = p_account_id
+      AND t.status = 'posted'
+      AND le.event_at <= p_as_of;
+
+    RETURN COALESCE(balance, 0);
+END;
+$$ LANGUAGE plpgsql STABLE;
+",
+        )
+        .await?;
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+        db.execute_unprepared(
+            r"
+CREATE OR REPLACE FUNCTION get_account_balance_at(
+    p_account_id UUID,
+    p_as_of TIMESTAMPTZ
+) RETURNS NUMERIC(19, 4) AS $$
+DECLARE
+    balance NUMERIC(19, 4);
+BEGIN
+    SELECT le.account_current_balance INTO balance
+    FROM ledger_entries le
+    JOIN transactions t ON t.id = le.transaction_id
+    WHERE le.account_id = p_account_id
+      AND t.status = 'posted'
+      AND le.created_at <= p_as_of
+    ORDER BY le.account_version DESC
+    LIMIT 1;
+
+    RETURN COALESCE(balance, 0);
+END;
+$$ LANGUAGE plpgsql STABLE;
+
+DROP INDEX IF EXISTS idx_le_event_at;
+CREATE INDEX idx_le_event_at ON ledger_entries(account_id, event_at);
+",
+        )
+        .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,65 @@
+//! In-app notifications and per-user delivery preferences.
+//!
+//! A notification belongs to one user in one organization; `read_at` is set
+//! the first time it is marked read. Preferences are per user and apply in
+//! every organization. A missing row means the default, email and in-app.
+
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+        db.execute_unprepared(
+            r"
+CREATE TABLE notifications (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    organization_id UUID NOT NULL REFERENCES organizations(id) ON DELETE CASCADE,
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    notification_type VARCHAR(40) NOT NULL,
+    payload JSONB NOT NULL DEFAULT '{}',
+    read_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+
+CREATE INDEX idx_notifications_inbox
+    ON notifications(user_id, organization_id, created_at DESC);
+CREATE INDEX idx_notifications_unread
+    ON notifications(user_id, organization_id)
+    WHERE read_at IS NULL;
+
+-- Tenant isolation
+ALTER TABLE notifications ENABLE ROW LEVEL SECURITY;
+ALTER TABLE notifications FORCE ROW LEVEL SECURITY;
+
+CREATE POLICY tenant_isolation ON notifications
+    USING (organization_id = current_setting('app.current_organization_id', true)::UUID);
+
+CREATE TABLE notification_preferences (
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    notification_type VARCHAR(40) NOT NULL,
+    delivery VARCHAR(10) NOT NULL CHECK (delivery IN ('email', 'in_app', 'both')),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    PRIMARY KEY (user_id, notification_type)
+);
+",
+        )
+        .await?;
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+        db.execute_unprepared(
+            r"
+DROP TABLE IF EXISTS notification_preferences;
+DROP TABLE IF EXISTS notifications;
+",
+        )
+        .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,55 @@
+//! Comment threads on transactions.
+//!
+//! Members discuss a transaction here; approval notes and rejection reasons
+//! are copied in as comments of kind `approval` and `rejection` so the thread
+//! shows the whole conversation. A reply points at its parent comment.
+//! Deleting a comment sets `deleted_at` and keeps the row so replies keep
+//! their place.
+
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+        db.execute_unprepared(
+            r"
+CREATE TABLE transaction_comments (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    organization_id UUID NOT NULL REFERENCES organizations(id) ON DELETE CASCADE,
+    transaction_id UUID NOT NULL REFERENCES transactions(id) ON DELETE CASCADE,
+    author_id UUID NOT NULL REFERENCES users(id),
+    parent_comment_id UUID REFERENCES transaction_comments(id),
+    kind VARCHAR(20) NOT NULL DEFAULT 'comment'
+        CHECK (kind IN ('comment', 'approval', 'rejection')),
+    body TEXT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    deleted_at TIMESTAMPTZ,
+    deleted_by UUID REFERENCES users(id)
+);
+
+CREATE INDEX idx_transaction_comments_thread
+    ON transaction_comments(transaction_id, created_at);
+
+-- Tenant isolation
+ALTER TABLE transaction_comments ENABLE ROW LEVEL SECURITY;
+ALTER TABLE transaction_comments FORCE ROW LEVEL SECURITY;
+
+CREATE POLICY tenant_isolation ON transaction_comments
+    USING (organization_id = current_setting('app.current_organization_id', true)::UUID);
+",
+        )
+        .await?;
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+        db.execute_unprepared("DROP TABLE IF EXISTS transaction_comments;")
+            .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,39 @@
+//! Stores a fingerprint of each transaction's date and entries.
+//!
+//! Duplicate detection looks transactions up by fingerprint. Rows created
+//! before this migration have none; the check computes and fills it in when
+//! it first meets them.
+
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+        db.execute_unprepared(
+            r"
+ALTER TABLE transactions ADD COLUMN IF NOT EXISTS fingerprint VARCHAR(64);
+
+CREATE INDEX IF NOT EXISTS idx_transactions_fingerprint
+    ON transactions(organization_id, fingerprint, transaction_date);
+",
+        )
+        .await?;
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+        db.execute_unprepared(
+            r"
+DROP INDEX IF EXISTS idx_transactions_fingerprint;
+ALTER TABLE transactions DROP COLUMN IF EXISTS fingerprint;
+",
+        )
+        .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,66 @@
+//! Exchange rate correction history.
+//!
+//! Correcting a rate records the old and new values here, along with how
+//! many posted ledger entries had used the old rate. The rate reference is
+//! cleared if the rate is later deleted, so the pair and date are copied in
+//! to keep the history readable.
+//!
+//! Also indexes ledger entries by currency pair and rate, so finding the
+//! entries that used a rate does not scan every entry of the organization.
+
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+        db.execute_unprepared(
+            r"
+CREATE TABLE rate_corrections (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    organization_id UUID NOT NULL REFERENCES organizations(id) ON DELETE CASCADE,
+    exchange_rate_id UUID REFERENCES exchange_rates(id) ON DELETE SET NULL,
+    from_currency CHAR(3) NOT NULL,
+    to_currency CHAR(3) NOT NULL,
+    effective_date DATE NOT NULL,
+    old_rate NUMERIC(19, 10) NOT NULL,
+    new_rate NUMERIC(19, 10) NOT NULL,
+    affected_entries BIGINT NOT NULL DEFAULT 0,
+    reason TEXT,
+    corrected_by UUID REFERENCES users(id),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+
+CREATE INDEX idx_rate_corrections_rate
+    ON rate_corrections(exchange_rate_id, created_at);
+
+CREATE INDEX idx_le_rate_usage
+    ON ledger_entries(source_currency, functional_currency, exchange_rate);
+
+-- Tenant isolation
+ALTER TABLE rate_corrections ENABLE ROW LEVEL SECURITY;
+ALTER TABLE rate_corrections FORCE ROW LEVEL SECURITY;
+
+CREATE POLICY tenant_isolation ON rate_corrections
+    USING (organization_id = current_setting('app.current_organization_id', true)::UUID);
+",
+        )
+        .await?;
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+        db.execute_unprepared(
+            r"
+DROP INDEX IF EXISTS idx_le_rate_usage;
+DROP TABLE IF EXISTS rate_corrections;
+",
+        )
+        .await?;
+        Ok(())
+    }
+}
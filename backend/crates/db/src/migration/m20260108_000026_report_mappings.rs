@@ -0,0 +1,43 @@
+//! Per-organization financial statement mappings.
+//!
+//! An organization without a row uses the built-in mapping of account
+//! subtypes to statement sections. A row holds the whole replacement
+//! mapping, validated by the application before it is written.
+
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+        db.execute_unprepared(
+            r"
+CREATE TABLE report_mappings (
+    organization_id UUID PRIMARY KEY REFERENCES organizations(id) ON DELETE CASCADE,
+    mapping JSONB NOT NULL,
+    updated_by UUID REFERENCES users(id),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+
+-- Tenant isolation
+ALTER TABLE report_mappings ENABLE ROW LEVEL SECURITY;
+ALTER TABLE report_mappings FORCE ROW LEVEL SECURITY;
+
+CREATE POLICY tenant_isolation ON report_mappings
+    USING (organization_id = current_setting('app.current_organization_id', true)::UUID);
+",
+        )
+        .await?;
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+        db.execute_unprepared("DROP TABLE IF EXISTS report_mappings;")
+            .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,43 @@
+//! Attachment moves between transactions.
+//!
+//! A receipt uploaded to a transaction that is later voided and re-entered
+//! can be moved to the replacement. The attachment keeps its storage key; the
+//! columns below record the transaction it last came from, who moved it and
+//! when. They carry no foreign keys so the record outlives the source
+//! transaction.
+
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+        db.execute_unprepared(
+            r"
+ALTER TABLE attachments
+    ADD COLUMN moved_from_transaction_id UUID,
+    ADD COLUMN moved_by UUID,
+    ADD COLUMN moved_at TIMESTAMPTZ;
+",
+        )
+        .await?;
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+        db.execute_unprepared(
+            r"
+ALTER TABLE attachments
+    DROP COLUMN IF EXISTS moved_at,
+    DROP COLUMN IF EXISTS moved_by,
+    DROP COLUMN IF EXISTS moved_from_transaction_id;
+",
+        )
+        .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,79 @@
+//! `updated_at` maintained by the database.
+//!
+//! Every mutable table defaults `updated_at` to `now()` on insert, but only
+//! repository code that remembered to set it kept it current on update, so
+//! clients syncing on `updated_at > X` missed changes. A `BEFORE UPDATE`
+//! trigger on each of those tables now stamps the row whenever it changes and
+//! the statement left `updated_at` alone. Statements that set it explicitly
+//! keep their value.
+
+use sea_orm_migration::prelude::*;
+
+/// Tables with an `updated_at` column.
+const TABLES: &[&str] = &[
+    "users",
+    "organizations",
+    "organization_users",
+    "fiscal_years",
+    "fiscal_periods",
+    "dimension_types",
+    "dimension_values",
+    "chart_of_accounts",
+    "transactions",
+    "budgets",
+    "budget_lines",
+    "approval_rules",
+    "tier_limits",
+    "organization_usage",
+    "sessions",
+    "reconciliations",
+    "approval_delegations",
+    "transaction_templates",
+    "notification_preferences",
+    "report_mappings",
+];
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+        db.execute_unprepared(
+            r"
+CREATE OR REPLACE FUNCTION touch_updated_at()
+RETURNS TRIGGER AS $$
+BEGIN
+    IF NEW IS DISTINCT FROM OLD AND NEW.updated_at IS NOT DISTINCT FROM OLD.updated_at THEN
+        NEW.updated_at := now();
+    END IF;
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+",
+        )
+        .await?;
+        for table in TABLES {
+            db.execute_unprepared(&format!(
+                "CREATE TRIGGER trg_touch_updated_at BEFORE UPDATE ON {table} \
+                 FOR EACH ROW EXECUTE FUNCTION touch_updated_at();"
+            ))
+            .await?;
+        }
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+        for table in TABLES {
+            db.execute_unprepared(&format!(
+                "DROP TRIGGER IF EXISTS trg_touch_updated_at ON {table};"
+            ))
+            .await?;
+        }
+        db.execute_unprepared("DROP FUNCTION IF EXISTS touch_updated_at();")
+            .await?;
+        Ok(())
+    }
+}
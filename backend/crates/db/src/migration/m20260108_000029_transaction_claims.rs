@@ -0,0 +1,47 @@
+//! Review claims on pending transactions.
+//!
+//! An approver claims a pending transaction while reviewing it so a second
+//! approver does not act on it at the same time. A claim is one row per
+//! transaction, replaced when it is renewed or taken over after
+//! `reviewing_until` and deleted when the transaction is approved or
+//! rejected. Claims live outside `transactions` so claiming does not touch
+//! the transaction's `updated_at`.
+
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+        db.execute_unprepared(
+            r"
+CREATE TABLE transaction_claims (
+    transaction_id UUID PRIMARY KEY REFERENCES transactions(id) ON DELETE CASCADE,
+    organization_id UUID NOT NULL REFERENCES organizations(id) ON DELETE CASCADE,
+    reviewing_by UUID NOT NULL REFERENCES users(id),
+    reviewing_until TIMESTAMPTZ NOT NULL,
+    claimed_at TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+
+-- Tenant isolation
+ALTER TABLE transaction_claims ENABLE ROW LEVEL SECURITY;
+ALTER TABLE transaction_claims FORCE ROW LEVEL SECURITY;
+
+CREATE POLICY tenant_isolation ON transaction_claims
+    USING (organization_id = current_setting('app.current_organization_id', true)::UUID);
+",
+        )
+        .await?;
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+        db.execute_unprepared("DROP TABLE IF EXISTS transaction_claims;")
+            .await?;
+        Ok(())
+    }
+}
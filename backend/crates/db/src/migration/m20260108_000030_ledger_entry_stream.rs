@@ -0,0 +1,41 @@
+//! Indexes for streaming a period's ledger entries.
+//!
+//! External analytics page through a fiscal period's entries in
+//! `(created_at, id)` order with a keyset cursor. Entries are read from the
+//! cursor onwards in index order, carrying the columns the stream returns, and
+//! matched against the period's transactions by period and status.
+
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+        db.execute_unprepared(
+            r"
+CREATE INDEX idx_txn_period_status ON transactions(fiscal_period_id, status)
+    INCLUDE (transaction_date, reference_number);
+CREATE INDEX idx_le_stream ON ledger_entries(created_at, id)
+    INCLUDE (transaction_id, account_id, source_currency, source_amount, exchange_rate,
+             functional_currency, functional_amount, debit, credit);
+",
+        )
+        .await?;
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+        db.execute_unprepared(
+            r"
+DROP INDEX IF EXISTS idx_le_stream;
+DROP INDEX IF EXISTS idx_txn_period_status;
+",
+        )
+        .await?;
+        Ok(())
+    }
+}
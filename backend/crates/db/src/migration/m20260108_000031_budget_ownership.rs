@@ -0,0 +1,57 @@
+//! Budget owners and editors.
+//!
+//! A budget can belong to one member, usually a department head, and list
+//! further members allowed to edit it. Accountants and above edit every
+//! budget regardless. Existing budgets are given to their creator; a budget
+//! whose owner leaves keeps no owner.
+
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+        db.execute_unprepared(
+            r"
+ALTER TABLE budgets
+    ADD COLUMN owner_user_id UUID REFERENCES users(id) ON DELETE SET NULL;
+
+UPDATE budgets SET owner_user_id = created_by;
+
+CREATE TABLE budget_editors (
+    budget_id UUID NOT NULL REFERENCES budgets(id) ON DELETE CASCADE,
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    organization_id UUID NOT NULL REFERENCES organizations(id) ON DELETE CASCADE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    PRIMARY KEY (budget_id, user_id)
+);
+
+CREATE INDEX idx_budget_editors_user ON budget_editors(user_id);
+
+-- Tenant isolation
+ALTER TABLE budget_editors ENABLE ROW LEVEL SECURITY;
+ALTER TABLE budget_editors FORCE ROW LEVEL SECURITY;
+
+CREATE POLICY tenant_isolation ON budget_editors
+    USING (organization_id = current_setting('app.current_organization_id', true)::UUID);
+",
+        )
+        .await?;
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+        db.execute_unprepared(
+            r"
+DROP TABLE IF EXISTS budget_editors;
+ALTER TABLE budgets DROP COLUMN IF EXISTS owner_user_id;
+",
+        )
+        .await?;
+        Ok(())
+    }
+}
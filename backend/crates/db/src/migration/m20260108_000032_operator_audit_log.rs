@@ -0,0 +1,47 @@
+//! Audit log of support operations.
+//!
+//! Every command of the `admin` binary writes one row, whether it only
+//! printed its plan or was executed. The log belongs to Zeltra's operators
+//! rather than to a tenant, so it has no row level security;
+//! `organization_id` records the organization a command touched, if any.
+
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+        db.execute_unprepared(
+            r"
+CREATE TABLE operator_audit_log (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    operator VARCHAR(255) NOT NULL,
+    command VARCHAR(100) NOT NULL,
+    target TEXT NOT NULL,
+    organization_id UUID REFERENCES organizations(id) ON DELETE SET NULL,
+    executed BOOLEAN NOT NULL,
+    reason TEXT,
+    details JSONB NOT NULL DEFAULT '{}',
+    created_at TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+
+CREATE INDEX idx_operator_audit_log_created ON operator_audit_log(created_at DESC);
+CREATE INDEX idx_operator_audit_log_org
+    ON operator_audit_log(organization_id, created_at DESC)
+    WHERE organization_id IS NOT NULL;
+",
+        )
+        .await?;
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+        db.execute_unprepared("DROP TABLE IF EXISTS operator_audit_log;")
+            .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,57 @@
+//! Variance commentary on budget lines.
+//!
+//! Managers explain a line's variance for its fiscal period here instead of
+//! in a spreadsheet next to the report. Comments are append-only: an edit
+//! inserts a new version with the same `comment_id` and clears `is_current`
+//! on the previous one, so earlier explanations stay on record.
+
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+        db.execute_unprepared(
+            r"
+CREATE TABLE budget_line_comments (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    comment_id UUID NOT NULL,
+    organization_id UUID NOT NULL REFERENCES organizations(id) ON DELETE CASCADE,
+    budget_line_id UUID NOT NULL REFERENCES budget_lines(id) ON DELETE CASCADE,
+    fiscal_period_id UUID NOT NULL REFERENCES fiscal_periods(id) ON DELETE CASCADE,
+    author_id UUID NOT NULL REFERENCES users(id),
+    edited_by UUID REFERENCES users(id),
+    body TEXT NOT NULL,
+    is_current BOOLEAN NOT NULL DEFAULT true,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+
+-- One current version per comment
+CREATE UNIQUE INDEX idx_budget_line_comments_current
+    ON budget_line_comments(comment_id) WHERE is_current;
+
+CREATE INDEX idx_budget_line_comments_line
+    ON budget_line_comments(budget_line_id, fiscal_period_id, created_at);
+
+-- Tenant isolation
+ALTER TABLE budget_line_comments ENABLE ROW LEVEL SECURITY;
+ALTER TABLE budget_line_comments FORCE ROW LEVEL SECURITY;
+
+CREATE POLICY tenant_isolation ON budget_line_comments
+    USING (organization_id = current_setting('app.current_organization_id', true)::UUID);
+",
+        )
+        .await?;
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+        db.execute_unprepared("DROP TABLE IF EXISTS budget_line_comments;")
+            .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,47 @@
+//! Approval requirements snapshotted at submission.
+//!
+//! Submitting a transaction records the rule it matched, the role required
+//! to approve it and how many approvals it needs. Approval enforces these
+//! rather than the rules in force at the time, so editing a rule does not
+//! change what a transaction already waiting for approval requires.
+//! Rejection clears the snapshot and resubmission takes a fresh one.
+
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+        db.execute_unprepared(
+            r"
+ALTER TABLE transactions
+    ADD COLUMN IF NOT EXISTS required_role_at_submit user_role,
+    ADD COLUMN IF NOT EXISTS required_approvals SMALLINT,
+    -- The role and count above still govern if the rule is deleted
+    ADD COLUMN IF NOT EXISTS matched_rule_id UUID
+        REFERENCES approval_rules(id) ON DELETE SET NULL,
+    ADD CONSTRAINT chk_transactions_required_approvals CHECK (required_approvals >= 1);
+",
+        )
+        .await?;
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+        db.execute_unprepared(
+            r"
+ALTER TABLE transactions
+    DROP CONSTRAINT IF EXISTS chk_transactions_required_approvals,
+    DROP COLUMN IF EXISTS matched_rule_id,
+    DROP COLUMN IF EXISTS required_approvals,
+    DROP COLUMN IF EXISTS required_role_at_submit;
+",
+        )
+        .await?;
+        Ok(())
+    }
+}
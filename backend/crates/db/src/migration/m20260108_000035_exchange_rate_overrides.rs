@@ -0,0 +1,67 @@
+//! Per-entry exchange rate overrides.
+//!
+//! An entry may be booked at a rate entered by the user instead of the one
+//! the organization's lookup policy finds. Such entries are flagged with
+//! `is_override`, and each override is logged with the rate it replaced, or
+//! none if no rate was found, and who entered it.
+
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+        db.execute_unprepared(
+            r"
+ALTER TABLE ledger_entries
+    ADD COLUMN IF NOT EXISTS is_override BOOLEAN NOT NULL DEFAULT false;
+
+CREATE TABLE exchange_rate_overrides (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    organization_id UUID NOT NULL REFERENCES organizations(id) ON DELETE CASCADE,
+    transaction_id UUID NOT NULL REFERENCES transactions(id) ON DELETE CASCADE,
+    ledger_entry_id UUID NOT NULL REFERENCES ledger_entries(id) ON DELETE CASCADE,
+    from_currency CHAR(3) NOT NULL,
+    to_currency CHAR(3) NOT NULL,
+    -- The date the lookup policy took its rate from
+    rate_date DATE NOT NULL,
+    looked_up_rate NUMERIC(19, 10),
+    override_rate NUMERIC(19, 10) NOT NULL,
+    overridden_by UUID REFERENCES users(id),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    CONSTRAINT chk_exchange_rate_overrides_positive CHECK (override_rate > 0)
+);
+
+CREATE UNIQUE INDEX idx_exchange_rate_overrides_entry
+    ON exchange_rate_overrides(ledger_entry_id);
+
+CREATE INDEX idx_exchange_rate_overrides_org
+    ON exchange_rate_overrides(organization_id, created_at);
+
+-- Tenant isolation
+ALTER TABLE exchange_rate_overrides ENABLE ROW LEVEL SECURITY;
+ALTER TABLE exchange_rate_overrides FORCE ROW LEVEL SECURITY;
+
+CREATE POLICY tenant_isolation ON exchange_rate_overrides
+    USING (organization_id = current_setting('app.current_organization_id', true)::UUID);
+",
+        )
+        .await?;
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+        db.execute_unprepared(
+            r"
+DROP TABLE IF EXISTS exchange_rate_overrides;
+ALTER TABLE ledger_entries DROP COLUMN IF EXISTS is_override;
+",
+        )
+        .await?;
+        Ok(())
+    }
+}
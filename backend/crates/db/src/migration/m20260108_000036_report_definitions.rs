@@ -0,0 +1,50 @@
+//! Saved custom report definitions.
+//!
+//! Each row is one organization's report: a base dataset, columns, filters,
+//! grouping and an aggregate, validated by the application and stored as
+//! JSON. Names are unique within an organization.
+
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+        db.execute_unprepared(
+            r"
+CREATE TABLE report_definitions (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    organization_id UUID NOT NULL REFERENCES organizations(id) ON DELETE CASCADE,
+    name VARCHAR(100) NOT NULL,
+    definition JSONB NOT NULL,
+    created_by UUID REFERENCES users(id),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    CONSTRAINT uq_report_definitions_name UNIQUE (organization_id, name)
+);
+
+CREATE TRIGGER trg_touch_updated_at BEFORE UPDATE ON report_definitions
+    FOR EACH ROW EXECUTE FUNCTION touch_updated_at();
+
+-- Tenant isolation
+ALTER TABLE report_definitions ENABLE ROW LEVEL SECURITY;
+ALTER TABLE report_definitions FORCE ROW LEVEL SECURITY;
+
+CREATE POLICY tenant_isolation ON report_definitions
+    USING (organization_id = current_setting('app.current_organization_id', true)::UUID);
+",
+        )
+        .await?;
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+        db.execute_unprepared("DROP TABLE IF EXISTS report_definitions;")
+            .await?;
+        Ok(())
+    }
+}
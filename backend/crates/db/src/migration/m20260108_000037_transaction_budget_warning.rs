@@ -0,0 +1,36 @@
+//! Flags transactions that took a budget line past its threshold when
+//! submitted.
+//!
+//! Set at submission so approvers can see which pending transactions went
+//! over budget without re-running the check.
+
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+        db.execute_unprepared(
+            r"
+ALTER TABLE transactions
+    ADD COLUMN IF NOT EXISTS budget_warning BOOLEAN NOT NULL DEFAULT false;
+",
+        )
+        .await?;
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+        db.execute_unprepared(
+            r"
+ALTER TABLE transactions DROP COLUMN IF EXISTS budget_warning;
+",
+        )
+        .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,47 @@
+//! Database functions re-applied from [`super::replaceable`].
+//!
+//! The functions were created inline by the migrations that introduced them,
+//! `get_account_balance_at` twice over. From here on their definitions live
+//! in one place and later changes go through a new versioned constant. This
+//! migration installs the current versions, which match what is already in
+//! the database, so `down` has nothing to undo: the earlier migrations drop
+//! the functions they created.
+
+use sea_orm_migration::prelude::*;
+
+use super::replaceable::{
+    self, CHECK_TIER_LIMIT_V1, CHECK_TRANSACTION_BALANCE_V1, CHECK_ZERO_ENTRY_TRANSACTION_TYPE_V1,
+    GET_ACCOUNT_BALANCE_AT_V2, GET_EXCHANGE_RATE_V1, HAS_FEATURE_V1,
+    INCREMENT_TRANSACTION_USAGE_V1, PREVENT_POSTED_MODIFICATION_V1, TOUCH_UPDATED_AT_V1,
+    UPDATE_ACCOUNT_BALANCE_V1, VALIDATE_FISCAL_PERIOD_POSTING_V1,
+};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        replaceable::replace_all(
+            manager.get_connection(),
+            &[
+                CHECK_TRANSACTION_BALANCE_V1,
+                VALIDATE_FISCAL_PERIOD_POSTING_V1,
+                PREVENT_POSTED_MODIFICATION_V1,
+                UPDATE_ACCOUNT_BALANCE_V1,
+                INCREMENT_TRANSACTION_USAGE_V1,
+                GET_ACCOUNT_BALANCE_AT_V2,
+                GET_EXCHANGE_RATE_V1,
+                CHECK_TIER_LIMIT_V1,
+                HAS_FEATURE_V1,
+                CHECK_ZERO_ENTRY_TRANSACTION_TYPE_V1,
+                TOUCH_UPDATED_AT_V1,
+            ],
+        )
+        .await
+    }
+
+    async fn down(&self, _manager: &SchemaManager) -> Result<(), DbErr> {
+        Ok(())
+    }
+}
@@ -0,0 +1,35 @@
+//! Reporting views re-applied from [`super::replaceable`].
+//!
+//! Like the functions before them, the views' definitions now live in one
+//! place. The versions installed here are the ones the initial migration
+//! created, so `down` leaves them for that migration to drop.
+
+use sea_orm_migration::prelude::*;
+
+use super::replaceable::{
+    self, ACCOUNT_BALANCES_VIEW_V1, BUDGET_VS_ACTUAL_VIEW_V1, DIMENSIONAL_REPORT_VIEW_V1,
+    TRIAL_BALANCE_VIEW_V1,
+};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        replaceable::replace_all(
+            manager.get_connection(),
+            &[
+                ACCOUNT_BALANCES_VIEW_V1,
+                TRIAL_BALANCE_VIEW_V1,
+                BUDGET_VS_ACTUAL_VIEW_V1,
+                DIMENSIONAL_REPORT_VIEW_V1,
+            ],
+        )
+        .await
+    }
+
+    async fn down(&self, _manager: &SchemaManager) -> Result<(), DbErr> {
+        Ok(())
+    }
+}
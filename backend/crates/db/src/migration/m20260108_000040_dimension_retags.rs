@@ -0,0 +1,58 @@
+//! Dimension re-tagging history.
+//!
+//! Entry dimensions are analytical tags rather than amounts, so after a
+//! reorganization the entries tagged with one dimension value can be moved
+//! to another without voiding their transactions. Each re-tag records the
+//! values, the filters it ran with and how many entries it changed.
+
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+        db.execute_unprepared(
+            r"
+CREATE TABLE dimension_retags (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    organization_id UUID NOT NULL REFERENCES organizations(id) ON DELETE CASCADE,
+    from_value_id UUID NOT NULL REFERENCES dimension_values(id),
+    to_value_id UUID NOT NULL REFERENCES dimension_values(id),
+    date_from DATE,
+    date_to DATE,
+    account_ids UUID[] NOT NULL DEFAULT '{}',
+    affected_entries BIGINT NOT NULL,
+    reason TEXT,
+    retagged_by UUID NOT NULL REFERENCES users(id),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+
+CREATE INDEX idx_dimension_retags_org
+    ON dimension_retags(organization_id, created_at);
+
+-- Tenant isolation
+ALTER TABLE dimension_retags ENABLE ROW LEVEL SECURITY;
+ALTER TABLE dimension_retags FORCE ROW LEVEL SECURITY;
+
+CREATE POLICY tenant_isolation ON dimension_retags
+    USING (organization_id = current_setting('app.current_organization_id', true)::UUID);
+",
+        )
+        .await?;
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+        db.execute_unprepared(
+            r"
+DROP TABLE IF EXISTS dimension_retags;
+",
+        )
+        .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,69 @@
+//! Fiscal period auto-close.
+//!
+//! Adds the system user that automatic changes are attributed to, and a log
+//! of what the auto-close job did to each period: soft closes, closes,
+//! closes blocked by the checklist, and grace extensions granted by admins.
+//! A period's schedule is pushed by the sum of its extensions.
+//!
+//! The system user cannot sign in: it is inactive, has no password and is a
+//! member of no organization.
+
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+        db.execute_unprepared(
+            r"
+INSERT INTO users (id, email, password_hash, full_name, is_active)
+VALUES ('00000000-0000-0000-0000-000000000000', 'system@zeltra.invalid', '', 'Zeltra', false)
+ON CONFLICT (id) DO NOTHING;
+
+CREATE TABLE fiscal_period_events (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    organization_id UUID NOT NULL REFERENCES organizations(id) ON DELETE CASCADE,
+    fiscal_period_id UUID NOT NULL REFERENCES fiscal_periods(id) ON DELETE CASCADE,
+    event VARCHAR(20) NOT NULL
+        CHECK (event IN ('soft_closed', 'closed', 'close_blocked', 'grace_extended')),
+    actor_id UUID NOT NULL REFERENCES users(id),
+    extension_days INTEGER,
+    details JSONB NOT NULL DEFAULT '{}',
+    created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    CONSTRAINT chk_fiscal_period_events_extension CHECK (
+        (event = 'grace_extended') = (extension_days IS NOT NULL AND extension_days > 0)
+    )
+);
+
+CREATE INDEX idx_fiscal_period_events_period
+    ON fiscal_period_events(fiscal_period_id, created_at);
+
+-- Tenant isolation
+ALTER TABLE fiscal_period_events ENABLE ROW LEVEL SECURITY;
+ALTER TABLE fiscal_period_events FORCE ROW LEVEL SECURITY;
+
+CREATE POLICY tenant_isolation ON fiscal_period_events
+    USING (organization_id = current_setting('app.current_organization_id', true)::UUID);
+",
+        )
+        .await?;
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+        db.execute_unprepared(
+            r"
+DROP TABLE IF EXISTS fiscal_period_events;
+UPDATE fiscal_periods SET closed_by = NULL
+    WHERE closed_by = '00000000-0000-0000-0000-000000000000';
+DELETE FROM users WHERE id = '00000000-0000-0000-0000-000000000000';
+",
+        )
+        .await?;
+        Ok(())
+    }
+}
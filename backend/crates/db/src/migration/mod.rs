@@ -8,6 +8,44 @@ mod m20260108_000001_initial;
 mod m20260108_000002_sessions;
 mod m20260108_000003_force_rls;
 mod m20260108_000004_email_verification;
+mod m20260108_000005_reconciliations;
+mod m20260108_000006_user_token_version;
+mod m20260108_000007_void_reason_codes;
+mod m20260108_000008_approval_delegations;
+mod m20260108_000009_approval_chains;
+mod m20260108_000010_transaction_templates;
+mod m20260108_000011_attachment_entries;
+mod m20260108_000012_user_profile;
+mod m20260108_000013_organization_slug_history;
+mod m20260108_000014_transaction_escalation;
+mod m20260108_000015_account_default_dimensions;
+mod m20260108_000016_payment_applications;
+mod m20260108_000017_api_keys;
+mod m20260108_000018_account_period_balances;
+mod m20260108_000019_zero_adjustment_entries;
+mod m20260108_000020_transaction_status_history;
+mod m20260108_000021_ledger_event_order;
+mod m20260108_000022_notifications;
+mod m20260108_000023_transaction_comments;
+mod m20260108_000024_transaction_fingerprints;
+mod m20260108_000025_rate_corrections;
+mod m20260108_000026_report_mappings;
+mod m20260108_000027_attachment_moves;
+mod m20260108_000028_touch_updated_at;
+mod m20260108_000029_transaction_claims;
+mod m20260108_000030_ledger_entry_stream;
+mod m20260108_000031_budget_ownership;
+mod m20260108_000032_operator_audit_log;
+mod m20260108_000033_budget_line_comments;
+mod m20260108_000034_approval_snapshot;
+mod m20260108_000035_exchange_rate_overrides;
+mod m20260108_000036_report_definitions;
+mod m20260108_000037_transaction_budget_warning;
+mod m20260108_000038_replaceable_functions;
+mod m20260108_000039_replaceable_views;
+mod m20260108_000040_dimension_retags;
+mod m20260108_000041_period_auto_close;
+pub mod replaceable;
 
 /// Migrator for running database migrations.
 pub struct Migrator;
@@ -20,6 +58,43 @@ impl MigratorTrait for Migrator {
             Box::new(m20260108_000002_sessions::Migration),
             Box::new(m20260108_000003_force_rls::Migration),
             Box::new(m20260108_000004_email_verification::Migration),
+            Box::new(m20260108_000005_reconciliations::Migration),
+            Box::new(m20260108_000006_user_token_version::Migration),
+            Box::new(m20260108_000007_void_reason_codes::Migration),
+            Box::new(m20260108_000008_approval_delegations::Migration),
+            Box::new(m20260108_000009_approval_chains::Migration),
+            Box::new(m20260108_000010_transaction_templates::Migration),
+            Box::new(m20260108_000011_attachment_entries::Migration),
+            Box::new(m20260108_000012_user_profile::Migration),
+            Box::new(m20260108_000013_organization_slug_history::Migration),
+            Box::new(m20260108_000014_transaction_escalation::Migration),
+            Box::new(m20260108_000015_account_default_dimensions::Migration),
+            Box::new(m20260108_000016_payment_applications::Migration),
+            Box::new(m20260108_000017_api_keys::Migration),
+            Box::new(m20260108_000018_account_period_balances::Migration),
+            Box::new(m20260108_000019_zero_adjustment_entries::Migration),
+            Box::new(m20260108_000020_transaction_status_history::Migration),
+            Box::new(m20260108_000021_ledger_event_order::Migration),
+            Box::new(m20260108_000022_notifications::Migration),
+            Box::new(m20260108_000023_transaction_comments::Migration),
+            Box::new(m20260108_000024_transaction_fingerprints::Migration),
+            Box::new(m20260108_000025_rate_corrections::Migration),
+            Box::new(m20260108_000026_report_mappings::Migration),
+            Box::new(m20260108_000027_attachment_moves::Migration),
+            Box::new(m20260108_000028_touch_updated_at::Migration),
+            Box::new(m20260108_000029_transaction_claims::Migration),
+            Box::new(m20260108_000030_ledger_entry_stream::Migration),
+            Box::new(m20260108_000031_budget_ownership::Migration),
+            Box::new(m20260108_000032_operator_audit_log::Migration),
+            Box::new(m20260108_000033_budget_line_comments::Migration),
+            Box::new(m20260108_000034_approval_snapshot::Migration),
+            Box::new(m20260108_000035_exchange_rate_overrides::Migration),
+            Box::new(m20260108_000036_report_definitions::Migration),
+            Box::new(m20260108_000037_transaction_budget_warning::Migration),
+            Box::new(m20260108_000038_replaceable_functions::Migration),
+            Box::new(m20260108_000039_replaceable_views::Migration),
+            Box::new(m20260108_000040_dimension_retags::Migration),
+            Box::new(m20260108_000041_period_auto_close::Migration),
         ]
     }
 }
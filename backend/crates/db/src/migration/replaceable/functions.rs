@@ -0,0 +1,437 @@
+//! Database functions, including the ones behind triggers.
+//!
+//! Triggers themselves stay in the migrations that create them; only the
+//! functions they execute are replaced.
+
+use super::ReplaceableObject;
+
+/// Every function at its current version.
+pub const FUNCTIONS: &[ReplaceableObject] = &[
+    CHECK_TRANSACTION_BALANCE_V1,
+    VALIDATE_FISCAL_PERIOD_POSTING_V1,
+    PREVENT_POSTED_MODIFICATION_V1,
+    UPDATE_ACCOUNT_BALANCE_V1,
+    INCREMENT_TRANSACTION_USAGE_V1,
+    GET_ACCOUNT_BALANCE_AT_V2,
+    GET_EXCHANGE_RATE_V1,
+    CHECK_TIER_LIMIT_V1,
+    HAS_FEATURE_V1,
+    CHECK_ZERO_ENTRY_TRANSACTION_TYPE_V1,
+    TOUCH_UPDATED_AT_V1,
+];
+
+/// Rejects posted transactions whose debits and credits differ.
+///
+/// Runs from the deferred `trg_check_balance` constraint trigger on
+/// `ledger_entries`.
+pub const CHECK_TRANSACTION_BALANCE_V1: ReplaceableObject = ReplaceableObject::function(
+    "check_transaction_balance",
+    "",
+    r"
+CREATE OR REPLACE FUNCTION check_transaction_balance()
+RETURNS TRIGGER AS $$
+DECLARE
+    total_debit NUMERIC(19, 4);
+    total_credit NUMERIC(19, 4);
+    txn_status transaction_status;
+BEGIN
+    SELECT status INTO txn_status
+    FROM transactions
+    WHERE id = NEW.transaction_id;
+
+    IF txn_status = 'posted' THEN
+        SELECT
+            COALESCE(SUM(debit), 0),
+            COALESCE(SUM(credit), 0)
+        INTO total_debit, total_credit
+        FROM ledger_entries
+        WHERE transaction_id = NEW.transaction_id;
+
+        IF total_debit <> total_credit THEN
+            RAISE EXCEPTION 'Transaction is not balanced. Debit: %, Credit: %',
+                total_debit, total_credit;
+        END IF;
+    END IF;
+
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+",
+);
+
+/// Blocks posting into closed periods, and into soft-closed periods for
+/// anyone below accountant. Runs from `trg_validate_fiscal_period`.
+pub const VALIDATE_FISCAL_PERIOD_POSTING_V1: ReplaceableObject = ReplaceableObject::function(
+    "validate_fiscal_period_posting",
+    "",
+    r"
+CREATE OR REPLACE FUNCTION validate_fiscal_period_posting()
+RETURNS TRIGGER AS $$
+DECLARE
+    period_status fiscal_period_status;
+    user_role_val user_role;
+BEGIN
+    SELECT fp.status INTO period_status
+    FROM fiscal_periods fp
+    WHERE fp.id = NEW.fiscal_period_id;
+
+    SELECT ou.role INTO user_role_val
+    FROM organization_users ou
+    JOIN transactions t ON t.organization_id = ou.organization_id
+    WHERE t.id = NEW.id AND ou.user_id = NEW.posted_by;
+
+    IF period_status = 'CLOSED' THEN
+        RAISE EXCEPTION 'Cannot post to closed fiscal period';
+    END IF;
+
+    IF period_status = 'SOFT_CLOSE' AND user_role_val NOT IN ('owner', 'admin', 'accountant') THEN
+        RAISE EXCEPTION 'Only accountants can post to soft-closed periods';
+    END IF;
+
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+",
+);
+
+/// Blocks changes to posted transactions other than voiding, and any change
+/// to voided ones. Runs from `trg_prevent_posted_mod`.
+pub const PREVENT_POSTED_MODIFICATION_V1: ReplaceableObject = ReplaceableObject::function(
+    "prevent_posted_modification",
+    "",
+    r"
+CREATE OR REPLACE FUNCTION prevent_posted_modification()
+RETURNS TRIGGER AS $$
+BEGIN
+    IF OLD.status = 'posted' AND NEW.status NOT IN ('voided') THEN
+        RAISE EXCEPTION 'Cannot modify posted transaction. Create a reversing entry instead.';
+    END IF;
+
+    IF OLD.status = 'voided' THEN
+        RAISE EXCEPTION 'Cannot modify voided transaction.';
+    END IF;
+
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+",
+);
+
+/// Stamps each new ledger entry with the account's next version and running
+/// balance. Runs from `trg_update_account_balance`.
+pub const UPDATE_ACCOUNT_BALANCE_V1: ReplaceableObject = ReplaceableObject::function(
+    "update_account_balance",
+    "",
+    r"
+CREATE OR REPLACE FUNCTION update_account_balance()
+RETURNS TRIGGER AS $$
+DECLARE
+    current_version BIGINT;
+    current_balance NUMERIC(19, 4);
+    new_balance NUMERIC(19, 4);
+    account_type_val account_type;
+BEGIN
+    -- Lock the account row to prevent concurrent modifications
+    SELECT coa.account_type INTO account_type_val
+    FROM chart_of_accounts coa
+    WHERE coa.id = NEW.account_id
+    FOR UPDATE;
+
+    -- Get the current version and balance from the LATEST entry (by version)
+    -- Note: Using ORDER BY + LIMIT instead of MAX() because MAX(balance)
+    -- would return the highest balance value, not the balance from the
+    -- entry with the highest version number
+    SELECT account_version, account_current_balance
+    INTO current_version, current_balance
+    FROM ledger_entries
+    WHERE account_id = NEW.account_id
+    ORDER BY account_version DESC
+    LIMIT 1;
+
+    -- Default to 0 if no entries exist
+    current_version := COALESCE(current_version, 0);
+    current_balance := COALESCE(current_balance, 0);
+
+    -- Calculate new balance based on account type
+    -- Asset/Expense: balance increases with debits
+    -- Liability/Equity/Revenue: balance increases with credits
+    IF account_type_val IN ('asset', 'expense') THEN
+        new_balance := current_balance + NEW.debit - NEW.credit;
+    ELSE
+        new_balance := current_balance + NEW.credit - NEW.debit;
+    END IF;
+
+    NEW.account_version := current_version + 1;
+    NEW.account_previous_balance := current_balance;
+    NEW.account_current_balance := new_balance;
+
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+",
+);
+
+/// Counts posted transactions per month for tier limits. Runs from
+/// `trg_increment_transaction_usage`.
+pub const INCREMENT_TRANSACTION_USAGE_V1: ReplaceableObject = ReplaceableObject::function(
+    "increment_transaction_usage",
+    "",
+    r"
+CREATE OR REPLACE FUNCTION increment_transaction_usage()
+RETURNS TRIGGER AS $$
+DECLARE
+    current_month CHAR(7);
+BEGIN
+    IF NEW.status = 'posted' AND (OLD.status IS NULL OR OLD.status <> 'posted') THEN
+        current_month := to_char(now(), 'YYYY-MM');
+
+        INSERT INTO organization_usage (organization_id, year_month, transaction_count)
+        VALUES (NEW.organization_id, current_month, 1)
+        ON CONFLICT (organization_id, year_month)
+        DO UPDATE SET
+            transaction_count = organization_usage.transaction_count + 1,
+            updated_at = now();
+    END IF;
+
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+",
+);
+
+/// Account balance at a point in time, from the running balance of the
+/// latest entry created before it.
+pub const GET_ACCOUNT_BALANCE_AT_V1: ReplaceableObject = ReplaceableObject::function(
+    "get_account_balance_at",
+    "UUID, TIMESTAMPTZ",
+    r"
+CREATE OR REPLACE FUNCTION get_account_balance_at(
+    p_account_id UUID,
+    p_as_of TIMESTAMPTZ
+) RETURNS NUMERIC(19, 4) AS $$
+DECLARE
+    balance NUMERIC(19, 4);
+BEGIN
+    SELECT le.account_current_balance INTO balance
+    FROM ledger_entries le
+    JOIN transactions t ON t.id = le.transaction_id
+    WHERE le.account_id = p_account_id
+      AND t.status = 'posted'
+      AND le.created_at <= p_as_of
+    ORDER BY le.account_version DESC
+    LIMIT 1;
+
+    RETURN COALESCE(balance, 0);
+END;
+$$ LANGUAGE plpgsql STABLE;
+",
+);
+
+/// Account balance at a point in time, summing posted entries by `event_at`.
+pub const GET_ACCOUNT_BALANCE_AT_V2: ReplaceableObject = ReplaceableObject::function(
+    "get_account_balance_at",
+    "UUID, TIMESTAMPTZ",
+    r"
+CREATE OR REPLACE FUNCTION get_account_balance_at(
+    p_account_id UUID,
+    p_as_of TIMESTAMPTZ
+) RETURNS NUMERIC(19, 4) AS $$
+DECLARE
+    balance NUMERIC(19, 4);
+BEGIN
+    SELECT SUM(
+        CASE WHEN coa.account_type IN ('asset', 'expense')
+            THEN le.debit - le.credit
+            ELSE le.credit - le.debit
+        END
+    ) INTO balance
+    FROM ledger_entries le
+    JOIN transactions t ON t.id = le.transaction_id
+    JOIN chart_of_accounts coa ON coa.id = le.account_id
+    WHERE le.account_id = p_account_id
+      AND t.status = 'posted'
+      AND le.event_at <= p_as_of;
+
+    RETURN COALESCE(balance, 0);
+END;
+$$ LANGUAGE plpgsql STABLE;
+",
+);
+
+/// Latest exchange rate on or before a date.
+pub const GET_EXCHANGE_RATE_V1: ReplaceableObject = ReplaceableObject::function(
+    "get_exchange_rate",
+    "UUID, CHAR, CHAR, DATE",
+    r"
+CREATE OR REPLACE FUNCTION get_exchange_rate(
+    p_organization_id UUID,
+    p_from_currency CHAR(3),
+    p_to_currency CHAR(3),
+    p_date DATE
+) RETURNS NUMERIC(19, 10) AS $$
+DECLARE
+    rate NUMERIC(19, 10);
+BEGIN
+    IF p_from_currency = p_to_currency THEN
+        RETURN 1;
+    END IF;
+
+    SELECT er.rate INTO rate
+    FROM exchange_rates er
+    WHERE er.organization_id = p_organization_id
+      AND er.from_currency = p_from_currency
+      AND er.to_currency = p_to_currency
+      AND er.effective_date <= p_date
+    ORDER BY er.effective_date DESC
+    LIMIT 1;
+
+    IF rate IS NULL THEN
+        RAISE EXCEPTION 'No exchange rate found for % to % on %',
+            p_from_currency, p_to_currency, p_date;
+    END IF;
+
+    RETURN rate;
+END;
+$$ LANGUAGE plpgsql STABLE;
+",
+);
+
+/// Whether an organization is under one of its tier limits.
+pub const CHECK_TIER_LIMIT_V1: ReplaceableObject = ReplaceableObject::function(
+    "check_tier_limit",
+    "UUID, VARCHAR",
+    r"
+CREATE OR REPLACE FUNCTION check_tier_limit(
+    p_organization_id UUID,
+    p_limit_type VARCHAR(50)
+) RETURNS BOOLEAN AS $$
+DECLARE
+    org_tier subscription_tier;
+    org_status subscription_status;
+    limit_value INTEGER;
+    current_count INTEGER;
+    current_month CHAR(7);
+BEGIN
+    SELECT subscription_tier, subscription_status
+    INTO org_tier, org_status
+    FROM organizations
+    WHERE id = p_organization_id;
+
+    IF org_status NOT IN ('trialing', 'active') THEN
+        RETURN false;
+    END IF;
+
+    current_month := to_char(now(), 'YYYY-MM');
+
+    CASE p_limit_type
+        WHEN 'users' THEN
+            SELECT max_users INTO limit_value FROM tier_limits WHERE tier = org_tier;
+            SELECT COUNT(*) INTO current_count FROM organization_users WHERE organization_id = p_organization_id;
+
+        WHEN 'transactions' THEN
+            SELECT max_transactions_per_month INTO limit_value FROM tier_limits WHERE tier = org_tier;
+            SELECT COALESCE(transaction_count, 0) INTO current_count
+            FROM organization_usage
+            WHERE organization_id = p_organization_id AND year_month = current_month;
+
+        WHEN 'dimensions' THEN
+            SELECT max_dimensions INTO limit_value FROM tier_limits WHERE tier = org_tier;
+            SELECT COUNT(*) INTO current_count FROM dimension_types WHERE organization_id = p_organization_id AND is_active = true;
+
+        WHEN 'currencies' THEN
+            SELECT max_currencies INTO limit_value FROM tier_limits WHERE tier = org_tier;
+            SELECT COUNT(DISTINCT from_currency) + 1 INTO current_count
+            FROM exchange_rates WHERE organization_id = p_organization_id;
+
+        ELSE
+            RETURN true;
+    END CASE;
+
+    IF limit_value IS NULL THEN
+        RETURN true;
+    END IF;
+
+    RETURN current_count < limit_value;
+END;
+$$ LANGUAGE plpgsql STABLE;
+",
+);
+
+/// Whether an organization's tier includes a feature.
+pub const HAS_FEATURE_V1: ReplaceableObject = ReplaceableObject::function(
+    "has_feature",
+    "UUID, VARCHAR",
+    r"
+CREATE OR REPLACE FUNCTION has_feature(
+    p_organization_id UUID,
+    p_feature VARCHAR(50)
+) RETURNS BOOLEAN AS $$
+DECLARE
+    org_tier subscription_tier;
+    org_status subscription_status;
+    result BOOLEAN;
+BEGIN
+    SELECT subscription_tier, subscription_status
+    INTO org_tier, org_status
+    FROM organizations
+    WHERE id = p_organization_id;
+
+    IF org_status NOT IN ('trialing', 'active') THEN
+        RETURN false;
+    END IF;
+
+    EXECUTE format(
+        'SELECT %I FROM tier_limits WHERE tier = $1',
+        'has_' || p_feature
+    ) INTO result USING org_tier;
+
+    RETURN COALESCE(result, false);
+END;
+$$ LANGUAGE plpgsql STABLE;
+",
+);
+
+/// Limits zero-amount ledger entries to adjustments. Runs from
+/// `trg_check_zero_entry_transaction_type`.
+pub const CHECK_ZERO_ENTRY_TRANSACTION_TYPE_V1: ReplaceableObject = ReplaceableObject::function(
+    "check_zero_entry_transaction_type",
+    "",
+    r"
+CREATE OR REPLACE FUNCTION check_zero_entry_transaction_type()
+RETURNS TRIGGER AS $$
+DECLARE
+    txn_type transaction_type;
+BEGIN
+    IF NEW.debit = 0 AND NEW.credit = 0 THEN
+        SELECT transaction_type INTO txn_type
+        FROM transactions
+        WHERE id = NEW.transaction_id;
+
+        IF txn_type <> 'adjustment' THEN
+            RAISE EXCEPTION 'Zero-amount entries are only allowed on adjustment transactions';
+        END IF;
+    END IF;
+
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+",
+);
+
+/// Stamps `updated_at` on rows changed without setting it. Runs from
+/// `trg_touch_updated_at` on every mutable table.
+pub const TOUCH_UPDATED_AT_V1: ReplaceableObject = ReplaceableObject::function(
+    "touch_updated_at",
+    "",
+    r"
+CREATE OR REPLACE FUNCTION touch_updated_at()
+RETURNS TRIGGER AS $$
+BEGIN
+    IF NEW IS DISTINCT FROM OLD AND NEW.updated_at IS NOT DISTINCT FROM OLD.updated_at THEN
+        NEW.updated_at := now();
+    END IF;
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+",
+);
@@ -0,0 +1,124 @@
+//! Views and functions that migrations replace rather than alter.
+//!
+//! Each definition is a `CREATE OR REPLACE` statement kept as a versioned
+//! constant (`TRIAL_BALANCE_VIEW_V1`, `GET_ACCOUNT_BALANCE_AT_V2`, ...), so
+//! re-applying one is harmless and a migration can name the exact version it
+//! installs. Earlier versions stay in place after they are superseded because
+//! the migrations that installed them, and the `down` of the migration that
+//! replaced them, still refer to them.
+//!
+//! To change one:
+//!
+//! 1. Add the next version next to the current one and point [`FUNCTIONS`] or
+//!    [`VIEWS`] at it.
+//! 2. Add a migration whose `up` replaces the object with the new version and
+//!    whose `down` replaces it with the previous one.
+
+mod functions;
+mod views;
+
+use sea_orm::{ConnectionTrait, DbErr, Statement};
+
+pub use functions::*;
+pub use views::*;
+
+/// What kind of database object a [`ReplaceableObject`] is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ObjectKind {
+    /// A function, identified by its name and argument types.
+    Function {
+        /// Argument types as written in the signature, e.g. `UUID, DATE`.
+        args: &'static str,
+    },
+    /// A view.
+    View,
+}
+
+/// One version of a view or function definition.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReplaceableObject {
+    /// Object name.
+    pub name: &'static str,
+    /// Object kind.
+    pub kind: ObjectKind,
+    /// The `CREATE OR REPLACE` statement.
+    pub definition: &'static str,
+}
+
+impl ReplaceableObject {
+    /// A function taking `args`.
+    pub const fn function(
+        name: &'static str,
+        args: &'static str,
+        definition: &'static str,
+    ) -> Self {
+        Self {
+            name,
+            kind: ObjectKind::Function { args },
+            definition,
+        }
+    }
+
+    /// A view.
+    pub const fn view(name: &'static str, definition: &'static str) -> Self {
+        Self {
+            name,
+            kind: ObjectKind::View,
+            definition,
+        }
+    }
+
+    /// The name Postgres resolves the object by, with argument types for
+    /// functions.
+    pub fn signature(&self) -> String {
+        match self.kind {
+            ObjectKind::Function { args } => format!("{}({args})", self.name),
+            ObjectKind::View => self.name.to_string(),
+        }
+    }
+
+    /// Creates the object, or replaces whatever version is there.
+    pub async fn replace<C: ConnectionTrait>(&self, db: &C) -> Result<(), DbErr> {
+        db.execute_unprepared(self.definition).await?;
+        Ok(())
+    }
+
+    /// Drops the object if it exists.
+    pub async fn drop_if_exists<C: ConnectionTrait>(&self, db: &C) -> Result<(), DbErr> {
+        let kind = match self.kind {
+            ObjectKind::Function { .. } => "FUNCTION",
+            ObjectKind::View => "VIEW",
+        };
+        db.execute_unprepared(&format!("DROP {kind} IF EXISTS {};", self.signature()))
+            .await?;
+        Ok(())
+    }
+
+    /// Whether the object exists.
+    pub async fn exists<C: ConnectionTrait>(&self, db: &C) -> Result<bool, DbErr> {
+        let lookup = match self.kind {
+            ObjectKind::Function { .. } => "to_regprocedure",
+            ObjectKind::View => "to_regclass",
+        };
+        let row = db
+            .query_one(Statement::from_sql_and_values(
+                db.get_database_backend(),
+                format!("SELECT {lookup}($1) IS NOT NULL AS found"),
+                [self.signature().into()],
+            ))
+            .await?
+            .ok_or_else(|| DbErr::Custom(format!("No result looking up {}", self.name)))?;
+        row.try_get("", "found")
+    }
+}
+
+/// Replaces each object in order.
+pub async fn replace_all<C: ConnectionTrait>(
+    db: &C,
+    objects: &[ReplaceableObject],
+) -> Result<(), DbErr> {
+    for object in objects {
+        object.replace(db).await?;
+    }
+    Ok(())
+}
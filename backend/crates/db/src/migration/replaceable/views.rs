@@ -0,0 +1,146 @@
+//! Reporting views.
+//!
+//! `CREATE OR REPLACE VIEW` can add columns at the end but cannot drop,
+//! rename or retype them; a version that does must drop the view first.
+
+use super::ReplaceableObject;
+
+/// Every view at its current version.
+pub const VIEWS: &[ReplaceableObject] = &[
+    ACCOUNT_BALANCES_VIEW_V1,
+    TRIAL_BALANCE_VIEW_V1,
+    BUDGET_VS_ACTUAL_VIEW_V1,
+    DIMENSIONAL_REPORT_VIEW_V1,
+];
+
+/// Current balance of each account, from its latest posted entry.
+pub const ACCOUNT_BALANCES_VIEW_V1: ReplaceableObject = ReplaceableObject::view(
+    "account_balances_view",
+    r"
+CREATE OR REPLACE VIEW account_balances_view AS
+SELECT
+    coa.id AS account_id,
+    coa.organization_id,
+    coa.code,
+    coa.name,
+    coa.account_type,
+    coa.currency,
+    COALESCE(
+        (SELECT account_current_balance
+         FROM ledger_entries le
+         JOIN transactions t ON t.id = le.transaction_id
+         WHERE le.account_id = coa.id AND t.status = 'posted'
+         ORDER BY le.account_version DESC
+         LIMIT 1),
+        0
+    ) AS balance
+FROM chart_of_accounts coa;
+",
+);
+
+/// Debit and credit totals and balance per account.
+pub const TRIAL_BALANCE_VIEW_V1: ReplaceableObject = ReplaceableObject::view(
+    "trial_balance_view",
+    r"
+CREATE OR REPLACE VIEW trial_balance_view AS
+SELECT
+    coa.organization_id,
+    coa.id AS account_id,
+    coa.code,
+    coa.name,
+    coa.account_type,
+    COALESCE(SUM(le.debit), 0) AS total_debit,
+    COALESCE(SUM(le.credit), 0) AS total_credit,
+    CASE
+        WHEN coa.account_type IN ('asset', 'expense')
+            THEN COALESCE(SUM(le.debit), 0) - COALESCE(SUM(le.credit), 0)
+        ELSE COALESCE(SUM(le.credit), 0) - COALESCE(SUM(le.debit), 0)
+    END AS balance
+FROM chart_of_accounts coa
+LEFT JOIN ledger_entries le ON le.account_id = coa.id
+LEFT JOIN transactions t ON t.id = le.transaction_id AND t.status = 'posted'
+GROUP BY coa.id, coa.organization_id, coa.code, coa.name, coa.account_type;
+",
+);
+
+/// Budgeted against posted amounts per active budget line.
+pub const BUDGET_VS_ACTUAL_VIEW_V1: ReplaceableObject = ReplaceableObject::view(
+    "budget_vs_actual_view",
+    r"
+CREATE OR REPLACE VIEW budget_vs_actual_view AS
+SELECT
+    bl.id AS budget_line_id,
+    b.organization_id,
+    b.name AS budget_name,
+    coa.code AS account_code,
+    coa.name AS account_name,
+    fp.name AS period_name,
+    fp.start_date,
+    fp.end_date,
+    bl.amount AS budgeted,
+    COALESCE(SUM(
+        CASE
+            WHEN coa.account_type IN ('asset', 'expense') THEN le.debit - le.credit
+            ELSE le.credit - le.debit
+        END
+    ), 0) AS actual,
+    bl.amount - COALESCE(SUM(
+        CASE
+            WHEN coa.account_type IN ('asset', 'expense') THEN le.debit - le.credit
+            ELSE le.credit - le.debit
+        END
+    ), 0) AS variance,
+    CASE
+        WHEN bl.amount = 0 THEN 0
+        ELSE ROUND((COALESCE(SUM(
+            CASE
+                WHEN coa.account_type IN ('asset', 'expense') THEN le.debit - le.credit
+                ELSE le.credit - le.debit
+            END
+        ), 0) / bl.amount) * 100, 2)
+    END AS utilization_percent
+FROM budget_lines bl
+JOIN budgets b ON b.id = bl.budget_id
+JOIN chart_of_accounts coa ON coa.id = bl.account_id
+JOIN fiscal_periods fp ON fp.id = bl.fiscal_period_id
+LEFT JOIN ledger_entries le ON le.account_id = bl.account_id
+LEFT JOIN transactions t ON t.id = le.transaction_id
+    AND t.status = 'posted'
+    AND t.transaction_date BETWEEN fp.start_date AND fp.end_date
+WHERE b.is_active = true
+GROUP BY bl.id, b.organization_id, b.name, coa.code, coa.name,
+         fp.name, fp.start_date, fp.end_date, bl.amount;
+",
+);
+
+/// Posted ledger entries with their dimension tags.
+pub const DIMENSIONAL_REPORT_VIEW_V1: ReplaceableObject = ReplaceableObject::view(
+    "dimensional_report_view",
+    r"
+CREATE OR REPLACE VIEW dimensional_report_view AS
+SELECT
+    t.organization_id,
+    t.transaction_date,
+    fp.name AS fiscal_period,
+    coa.code AS account_code,
+    coa.name AS account_name,
+    coa.account_type,
+    dt.code AS dimension_type,
+    dv.code AS dimension_code,
+    dv.name AS dimension_name,
+    le.source_currency,
+    le.source_amount,
+    le.functional_currency,
+    le.functional_amount,
+    le.debit,
+    le.credit
+FROM ledger_entries le
+JOIN transactions t ON t.id = le.transaction_id
+JOIN fiscal_periods fp ON fp.id = t.fiscal_period_id
+JOIN chart_of_accounts coa ON coa.id = le.account_id
+LEFT JOIN entry_dimensions ed ON ed.ledger_entry_id = le.id
+LEFT JOIN dimension_values dv ON dv.id = ed.dimension_value_id
+LEFT JOIN dimension_types dt ON dt.id = dv.dimension_type_id
+WHERE t.status = 'posted';
+",
+);
@@ -2,20 +2,31 @@
 //!
 //! Implements Requirements 2.1-2.7 for chart of accounts management.
 
-use chrono::NaiveDate;
+use chrono::{DateTime, NaiveDate, Utc};
+use futures::TryStreamExt;
 use rust_decimal::Decimal;
 use sea_orm::{
-    ActiveModelTrait, ColumnTrait, DatabaseConnection, DbErr, EntityTrait, JoinType,
-    PaginatorTrait, QueryFilter, QueryOrder, QuerySelect, RelationTrait, Set,
+    AccessMode, ActiveModelTrait, ColumnTrait, ConnectionTrait, DatabaseConnection, DbErr,
+    EntityTrait, IsolationLevel, JoinType, PaginatorTrait, QueryFilter, QueryOrder, QuerySelect,
+    RelationTrait, Select, Set, TransactionTrait, sea_query::Expr,
 };
+use std::collections::{HashMap, HashSet};
 use uuid::Uuid;
+use zeltra_core::account_import::{HierarchyNode, ImportIssue, order_by_hierarchy};
+use zeltra_core::ledger::{
+    AccountIntegrity, AccountTypeForBalance, ActivityBucket, ActivityGranularity, ActivityTotals,
+    RunningBalanceCheck, StoredEntry, build_activity_buckets,
+};
+use zeltra_shared::types::{AccountId, OrganizationId, Sort, SortDirection, SortField};
 
 use crate::entities::{
-    chart_of_accounts, currencies, ledger_entries,
+    chart_of_accounts, currencies, fiscal_years, ledger_entries,
     sea_orm_active_enums::{AccountSubtype, AccountType, TransactionStatus},
     transactions,
 };
 
+use super::balance_snapshot::BalanceSnapshotRepository;
+
 /// Error types for account operations.
 #[derive(Debug, thiserror::Error)]
 pub enum AccountError {
@@ -47,6 +58,14 @@ pub enum AccountError {
     #[error("Cannot delete account: account has {0} ledger entries")]
     CannotDeleteWithEntries(u64),
 
+    /// Requested dates not covered by any fiscal year.
+    #[error("{} dates fall outside every fiscal year", .0.len())]
+    DatesOutsideFiscalYears(Vec<NaiveDate>),
+
+    /// An imported batch has invalid rows; nothing was created.
+    #[error("Import has {} invalid rows", .0.len())]
+    InvalidImport(Vec<ImportIssue>),
+
     /// Database error.
     #[error("Database error: {0}")]
     Database(#[from] DbErr),
@@ -57,10 +76,26 @@ pub enum AccountError {
 pub struct AccountWithBalance {
     /// The account record.
     pub account: chart_of_accounts::Model,
-    /// Current balance (from latest ledger entry, or zero if no entries).
+    /// Current balance from posted transactions only.
     pub balance: Decimal,
 }
 
+/// An account's entries split by the status of their transactions, as
+/// balance changes in the account's normal direction.
+///
+/// Voided transactions are left out.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct BalanceStatusBreakdown {
+    /// Posted; equals the account balance.
+    pub posted: Decimal,
+    /// Approved but not yet posted.
+    pub approved: Decimal,
+    /// Waiting for approval.
+    pub pending: Decimal,
+    /// Drafts.
+    pub draft: Decimal,
+}
+
 /// Ledger entry with transaction details for ledger listing.
 #[derive(Debug, Clone)]
 pub struct LedgerEntryWithTransaction {
@@ -91,6 +126,50 @@ pub struct PaginatedLedgerEntries {
     pub total_pages: u64,
 }
 
+/// Posted activity of an account over a date range.
+#[derive(Debug, Clone)]
+pub struct AccountActivity {
+    /// The account record.
+    pub account: chart_of_accounts::Model,
+    /// Balance from posted entries dated before the range.
+    pub opening_balance: Decimal,
+    /// One bucket per week or month in the range, including empty ones.
+    pub buckets: Vec<ActivityBucket>,
+}
+
+/// An account's posted balances at several dates.
+#[derive(Debug, Clone)]
+pub struct AccountBalancesAtDates {
+    /// The account record.
+    pub account: chart_of_accounts::Model,
+    /// Balance at each requested date, in request order.
+    pub balances: Vec<Decimal>,
+}
+
+/// Which accounts a balance matrix covers.
+#[derive(Debug, Clone, Default)]
+pub struct BalanceMatrixFilter {
+    /// Only these accounts; all when empty.
+    pub account_ids: Vec<Uuid>,
+    /// Only accounts of these types; all when empty.
+    pub account_types: Vec<AccountType>,
+    /// Include inactive accounts.
+    pub include_inactive: bool,
+}
+
+/// Result of replaying one account's stored running balances.
+#[derive(Debug, Clone)]
+pub struct LedgerIntegrityAccount {
+    /// Account ID.
+    pub account_id: Uuid,
+    /// Account code.
+    pub code: String,
+    /// Account name.
+    pub name: String,
+    /// Outcome of the replay.
+    pub integrity: AccountIntegrity,
+}
+
 /// Input for creating an account.
 #[derive(Debug, Clone)]
 pub struct CreateAccountInput {
@@ -114,6 +193,32 @@ pub struct CreateAccountInput {
     pub is_active: bool,
     /// Whether direct posting is allowed.
     pub allow_direct_posting: bool,
+    /// Whether this account represents a bank account.
+    pub is_bank_account: bool,
+    /// Bank account number, for bank accounts.
+    pub bank_account_number: Option<String>,
+}
+
+/// An account to create as part of an import.
+#[derive(Debug, Clone)]
+pub struct ImportAccountInput {
+    /// 1-based line number in the source file.
+    pub row: usize,
+    /// The account. `parent_id` is ignored; the parent comes from
+    /// `parent_code`.
+    pub account: CreateAccountInput,
+    /// Code of the parent account, in the same batch or already in the chart.
+    pub parent_code: Option<String>,
+}
+
+/// Validation result for an import batch.
+struct ImportPlan {
+    /// Problems found, sorted by row.
+    issues: Vec<ImportIssue>,
+    /// Indices into the batch, parents first.
+    order: Vec<usize>,
+    /// Existing account IDs by code.
+    existing: HashMap<String, Uuid>,
 }
 
 /// Input for updating an account.
@@ -146,6 +251,45 @@ pub struct AccountFilter {
     pub is_active: Option<bool>,
     /// Filter by parent ID (None = root accounts only).
     pub parent_id: Option<Option<Uuid>>,
+    /// Sort order. By code when `None`.
+    pub sort: Option<Sort<AccountSortField>>,
+    /// Only accounts changed after this time.
+    pub modified_since: Option<DateTime<Utc>>,
+}
+
+/// Fields account lists can be sorted by.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AccountSortField {
+    /// Account code.
+    Code,
+    /// Account name.
+    Name,
+    /// Current balance. Sorted in memory once balances are computed, since
+    /// balances are not a column on the accounts table.
+    Balance,
+    /// Account type, in chart order (asset, liability, equity, revenue, expense).
+    Type,
+}
+
+impl SortField for AccountSortField {
+    const ALLOWED: &'static [(&'static str, Self)] = &[
+        ("code", Self::Code),
+        ("name", Self::Name),
+        ("balance", Self::Balance),
+        ("type", Self::Type),
+    ];
+}
+
+impl AccountSortField {
+    /// Column to order by, or `None` for fields sorted after loading.
+    fn column(self) -> Option<chart_of_accounts::Column> {
+        match self {
+            Self::Code => Some(chart_of_accounts::Column::Code),
+            Self::Name => Some(chart_of_accounts::Column::Name),
+            Self::Type => Some(chart_of_accounts::Column::AccountType),
+            Self::Balance => None,
+        }
+    }
 }
 
 /// Account repository for CRUD operations.
@@ -224,8 +368,8 @@ impl AccountRepository {
             is_active: Set(input.is_active),
             is_system_account: Set(false),
             allow_direct_posting: Set(input.allow_direct_posting),
-            is_bank_account: Set(false),
-            bank_account_number: Set(None),
+            is_bank_account: Set(input.is_bank_account),
+            bank_account_number: Set(input.bank_account_number),
             created_at: Set(now),
             updated_at: Set(now),
         };
@@ -234,6 +378,45 @@ impl AccountRepository {
         Ok(account)
     }
 
+    /// Checks an import batch against the chart of accounts without writing.
+    ///
+    /// Reports, by row and column, codes already in the chart, unknown
+    /// currencies, parents that cannot be found and parent cycles.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a database query fails.
+    pub async fn validate_accounts_batch(
+        &self,
+        organization_id: Uuid,
+        inputs: &[ImportAccountInput],
+    ) -> Result<Vec<ImportIssue>, AccountError> {
+        Ok(plan_import(&self.db, organization_id, inputs).await?.issues)
+    }
+
+    /// Creates a batch of imported accounts in one database transaction.
+    ///
+    /// Parents may be other accounts in the batch, listed before or after
+    /// their children, or existing accounts. Accounts are inserted parents
+    /// first so each parent ID is known when its children are created.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InvalidImport` with every problem if any row fails
+    /// [`Self::validate_accounts_batch`], or an error if a database
+    /// operation fails. Nothing is created on error.
+    pub async fn create_accounts_batch(
+        &self,
+        organization_id: Uuid,
+        inputs: Vec<ImportAccountInput>,
+    ) -> Result<Vec<chart_of_accounts::Model>, AccountError> {
+        let txn = self.db.begin().await?;
+        let created = import_accounts(&txn, organization_id, inputs).await?;
+        txn.commit().await?;
+
+        Ok(created)
+    }
+
     /// Lists accounts for an organization with computed balances.
     ///
     /// Requirements: 2.5
@@ -243,12 +426,18 @@ impl AccountRepository {
     /// Returns an error if the database query fails.
     pub async fn list_accounts(
         &self,
-        organization_id: Uuid,
+        organization_id: OrganizationId,
         filter: AccountFilter,
     ) -> Result<Vec<AccountWithBalance>, AccountError> {
         let mut query = chart_of_accounts::Entity::find()
-            .filter(chart_of_accounts::Column::OrganizationId.eq(organization_id))
-            .order_by_asc(chart_of_accounts::Column::Code);
+            .filter(chart_of_accounts::Column::OrganizationId.eq(organization_id));
+
+        if let Some(sort) = filter.sort
+            && let Some(column) = sort.field.column()
+        {
+            query = query.order_by(column, sort.direction.into());
+        }
+        query = query.order_by_asc(chart_of_accounts::Column::Code);
 
         if let Some(account_type) = filter.account_type {
             query = query.filter(chart_of_accounts::Column::AccountType.eq(account_type));
@@ -258,6 +447,10 @@ impl AccountRepository {
             query = query.filter(chart_of_accounts::Column::IsActive.eq(is_active));
         }
 
+        if let Some(since) = filter.modified_since {
+            query = query.filter(chart_of_accounts::Column::UpdatedAt.gt(since));
+        }
+
         if let Some(parent_id) = filter.parent_id {
             match parent_id {
                 Some(pid) => {
@@ -271,11 +464,31 @@ impl AccountRepository {
 
         let accounts = query.all(&self.db).await?;
 
-        // Get balances for all accounts
-        let mut results = Vec::with_capacity(accounts.len());
-        for account in accounts {
-            let balance = self.get_account_balance(account.id).await?;
-            results.push(AccountWithBalance { account, balance });
+        // Get balances for all accounts in one query
+        let totals = self.posted_totals(organization_id, None).await?;
+        let mut results: Vec<AccountWithBalance> = accounts
+            .into_iter()
+            .map(|account| {
+                let (debit, credit) = totals
+                    .get(&account.id)
+                    .copied()
+                    .unwrap_or((Decimal::ZERO, Decimal::ZERO));
+                let balance =
+                    normal_balance(&account.account_type).calculate_balance_change(debit, credit);
+                AccountWithBalance { account, balance }
+            })
+            .collect();
+
+        if let Some(Sort {
+            field: AccountSortField::Balance,
+            direction,
+        }) = filter.sort
+        {
+            // Stable, so equal balances stay in code order
+            results.sort_by(|a, b| match direction {
+                SortDirection::Asc => a.balance.cmp(&b.balance),
+                SortDirection::Desc => b.balance.cmp(&a.balance),
+            });
         }
 
         Ok(results)
@@ -288,7 +501,7 @@ impl AccountRepository {
     /// Returns an error if the database query fails.
     pub async fn find_account_by_id(
         &self,
-        id: Uuid,
+        id: AccountId,
     ) -> Result<Option<AccountWithBalance>, AccountError> {
         let account = chart_of_accounts::Entity::find_by_id(id)
             .one(&self.db)
@@ -296,7 +509,7 @@ impl AccountRepository {
 
         match account {
             Some(acc) => {
-                let balance = self.get_account_balance(acc.id).await?;
+                let balance = self.get_account_balance(&acc).await?;
                 Ok(Some(AccountWithBalance {
                     account: acc,
                     balance,
@@ -319,19 +532,19 @@ impl AccountRepository {
     /// - Parent account validation fails
     pub async fn update_account(
         &self,
-        id: Uuid,
+        id: AccountId,
         input: UpdateAccountInput,
     ) -> Result<chart_of_accounts::Model, AccountError> {
         let account = chart_of_accounts::Entity::find_by_id(id)
             .one(&self.db)
             .await?
-            .ok_or(AccountError::AccountNotFound(id))?;
+            .ok_or(AccountError::AccountNotFound(id.into_inner()))?;
 
         // If changing account_type, check for ledger entries (Requirement 2.6)
         if let Some(new_type) = &input.account_type
             && *new_type != account.account_type
         {
-            let entry_count = self.count_ledger_entries(id).await?;
+            let entry_count = self.count_ledger_entries(id.into_inner()).await?;
             if entry_count > 0 {
                 return Err(AccountError::HasLedgerEntries(entry_count));
             }
@@ -415,14 +628,14 @@ impl AccountRepository {
     /// Returns an error if:
     /// - Account not found
     /// - Account has ledger entries
-    pub async fn delete_account(&self, id: Uuid) -> Result<(), AccountError> {
+    pub async fn delete_account(&self, id: AccountId) -> Result<(), AccountError> {
         let account = chart_of_accounts::Entity::find_by_id(id)
             .one(&self.db)
             .await?
-            .ok_or(AccountError::AccountNotFound(id))?;
+            .ok_or(AccountError::AccountNotFound(id.into_inner()))?;
 
         // Check for ledger entries
-        let entry_count = self.count_ledger_entries(id).await?;
+        let entry_count = self.count_ledger_entries(id.into_inner()).await?;
         if entry_count > 0 {
             return Err(AccountError::CannotDeleteWithEntries(entry_count));
         }
@@ -444,7 +657,7 @@ impl AccountRepository {
     /// Returns an error if the database query fails.
     pub async fn code_exists(
         &self,
-        organization_id: Uuid,
+        organization_id: OrganizationId,
         code: &str,
     ) -> Result<bool, AccountError> {
         let count = chart_of_accounts::Entity::find()
@@ -458,16 +671,113 @@ impl AccountRepository {
 
     /// Gets the current balance for an account.
     ///
-    /// Returns the `account_current_balance` from the most recent ledger entry,
-    /// or zero if no entries exist.
-    async fn get_account_balance(&self, account_id: Uuid) -> Result<Decimal, AccountError> {
-        let latest_entry = ledger_entries::Entity::find()
-            .filter(ledger_entries::Column::AccountId.eq(account_id))
-            .order_by_desc(ledger_entries::Column::AccountVersion)
-            .one(&self.db)
+    /// Sums the entries of posted transactions. The running balance on
+    /// ledger entries is not used: it also counts draft and pending
+    /// transactions, which are written to the ledger when created.
+    async fn get_account_balance(
+        &self,
+        account: &chart_of_accounts::Model,
+    ) -> Result<Decimal, AccountError> {
+        let totals = self
+            .posted_totals(
+                OrganizationId::from(account.organization_id),
+                Some(AccountId::from(account.id)),
+            )
             .await?;
+        let (debit, credit) = totals
+            .get(&account.id)
+            .copied()
+            .unwrap_or((Decimal::ZERO, Decimal::ZERO));
+
+        Ok(normal_balance(&account.account_type).calculate_balance_change(debit, credit))
+    }
+
+    /// Posted debit and credit totals per account, for one account or the
+    /// whole organization.
+    async fn posted_totals(
+        &self,
+        organization_id: OrganizationId,
+        account_id: Option<AccountId>,
+    ) -> Result<HashMap<Uuid, (Decimal, Decimal)>, AccountError> {
+        let mut query = ledger_entries::Entity::find()
+            .join(
+                JoinType::InnerJoin,
+                ledger_entries::Relation::Transactions.def(),
+            )
+            .filter(transactions::Column::OrganizationId.eq(organization_id))
+            .filter(transactions::Column::Status.eq(TransactionStatus::Posted));
+        if let Some(account_id) = account_id {
+            query = query.filter(ledger_entries::Column::AccountId.eq(account_id));
+        }
+        let rows: Vec<(Uuid, Option<Decimal>, Option<Decimal>)> = query
+            .select_only()
+            .column(ledger_entries::Column::AccountId)
+            .column_as(ledger_entries::Column::Debit.sum(), "debit")
+            .column_as(ledger_entries::Column::Credit.sum(), "credit")
+            .group_by(ledger_entries::Column::AccountId)
+            .into_tuple()
+            .all(&self.db)
+            .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|(account_id, debit, credit)| {
+                (
+                    account_id,
+                    (
+                        debit.unwrap_or(Decimal::ZERO),
+                        credit.unwrap_or(Decimal::ZERO),
+                    ),
+                )
+            })
+            .collect())
+    }
+
+    /// Splits an account's entries by the status of their transactions, so
+    /// amounts still in the approval workflow can be shown next to the
+    /// posted balance.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    pub async fn get_status_breakdown(
+        &self,
+        account: &chart_of_accounts::Model,
+    ) -> Result<BalanceStatusBreakdown, AccountError> {
+        let rows: Vec<(TransactionStatus, Option<Decimal>, Option<Decimal>)> =
+            ledger_entries::Entity::find()
+                .filter(ledger_entries::Column::AccountId.eq(account.id))
+                .join(
+                    JoinType::InnerJoin,
+                    ledger_entries::Relation::Transactions.def(),
+                )
+                .filter(transactions::Column::OrganizationId.eq(account.organization_id))
+                .filter(transactions::Column::Status.ne(TransactionStatus::Voided))
+                .select_only()
+                .column(transactions::Column::Status)
+                .column_as(ledger_entries::Column::Debit.sum(), "debit")
+                .column_as(ledger_entries::Column::Credit.sum(), "credit")
+                .group_by(transactions::Column::Status)
+                .into_tuple()
+                .all(&self.db)
+                .await?;
 
-        Ok(latest_entry.map_or(Decimal::ZERO, |e| e.account_current_balance))
+        let normal_balance = normal_balance(&account.account_type);
+        let mut breakdown = BalanceStatusBreakdown::default();
+        for (status, debit, credit) in rows {
+            let amount = normal_balance.calculate_balance_change(
+                debit.unwrap_or(Decimal::ZERO),
+                credit.unwrap_or(Decimal::ZERO),
+            );
+            match status {
+                TransactionStatus::Posted => breakdown.posted = amount,
+                TransactionStatus::Approved => breakdown.approved = amount,
+                TransactionStatus::Pending => breakdown.pending = amount,
+                TransactionStatus::Draft => breakdown.draft = amount,
+                TransactionStatus::Voided => {}
+            }
+        }
+        Ok(breakdown)
     }
 
     /// Counts ledger entries for an account.
@@ -482,8 +792,9 @@ impl AccountRepository {
 
     /// Gets the balance for an account at a specific date.
     ///
-    /// Returns the `account_current_balance` from the most recent ledger entry
-    /// on or before the given date, or zero if no entries exist before that date.
+    /// Sums the account's posted entries dated on or before the given date.
+    /// Only entries after the latest usable period snapshot are summed, on
+    /// top of the snapshot's ending balance.
     ///
     /// # Arguments
     /// * `account_id` - The account ID
@@ -494,24 +805,118 @@ impl AccountRepository {
     /// Returns an error if the database query fails.
     pub async fn get_balance_at_date(
         &self,
-        account_id: Uuid,
+        account_id: AccountId,
         as_of: NaiveDate,
     ) -> Result<Decimal, AccountError> {
-        // Join with transactions to filter by transaction_date
-        let latest_entry = ledger_entries::Entity::find()
-            .filter(ledger_entries::Column::AccountId.eq(account_id))
-            .join(
-                JoinType::InnerJoin,
-                ledger_entries::Relation::Transactions.def(),
-            )
-            .filter(transactions::Column::TransactionDate.lte(as_of))
-            .filter(transactions::Column::Status.eq(TransactionStatus::Posted))
-            .order_by_desc(transactions::Column::TransactionDate)
-            .order_by_desc(ledger_entries::Column::AccountVersion)
+        let account = chart_of_accounts::Entity::find_by_id(account_id.into_inner())
             .one(&self.db)
+            .await?
+            .ok_or(AccountError::AccountNotFound(account_id.into_inner()))?;
+
+        let snapshots = BalanceSnapshotRepository::new(self.db.clone());
+        let snapshot_end = snapshots
+            .usable_snapshot_end(account.organization_id, as_of)
+            .await?;
+        let baseline = match snapshot_end {
+            Some(end) => snapshots
+                .account_snapshot(account.id, end)
+                .await?
+                .map_or(Decimal::ZERO, |s| s.ending_balance),
+            None => Decimal::ZERO,
+        };
+
+        let mut query = Self::posted_entries(account.organization_id.into(), account_id)
+            .filter(transactions::Column::TransactionDate.lte(as_of));
+        if let Some(end) = snapshot_end {
+            query = query.filter(transactions::Column::TransactionDate.gt(end));
+        }
+        let totals: Option<(Option<Decimal>, Option<Decimal>)> = query
+            .select_only()
+            .column_as(ledger_entries::Column::Debit.sum(), "debit")
+            .column_as(ledger_entries::Column::Credit.sum(), "credit")
+            .into_tuple()
+            .one(&self.db)
+            .await?;
+        let (debit, credit) = totals.unwrap_or_default();
+
+        Ok(baseline
+            + normal_balance(&account.account_type).calculate_balance_change(
+                debit.unwrap_or(Decimal::ZERO),
+                credit.unwrap_or(Decimal::ZERO),
+            ))
+    }
+
+    /// Gets the posted balances of many accounts at several dates.
+    ///
+    /// Each date costs one totals query over all accounts, starting from the
+    /// latest usable period snapshot, rather than one lookup per account.
+    /// Accounts are ordered by code.
+    ///
+    /// # Errors
+    ///
+    /// Returns `DatesOutsideFiscalYears` if a date is not in any of the
+    /// organization's fiscal years, or an error if a database query fails.
+    pub async fn get_balance_matrix(
+        &self,
+        organization_id: Uuid,
+        dates: &[NaiveDate],
+        filter: &BalanceMatrixFilter,
+    ) -> Result<Vec<AccountBalancesAtDates>, AccountError> {
+        let fiscal_years = fiscal_years::Entity::find()
+            .filter(fiscal_years::Column::OrganizationId.eq(organization_id))
+            .all(&self.db)
             .await?;
+        let outside: Vec<NaiveDate> = dates
+            .iter()
+            .copied()
+            .filter(|date| {
+                !fiscal_years
+                    .iter()
+                    .any(|fy| fy.start_date <= *date && *date <= fy.end_date)
+            })
+            .collect();
+        if !outside.is_empty() {
+            return Err(AccountError::DatesOutsideFiscalYears(outside));
+        }
 
-        Ok(latest_entry.map_or(Decimal::ZERO, |e| e.account_current_balance))
+        let mut query = chart_of_accounts::Entity::find()
+            .filter(chart_of_accounts::Column::OrganizationId.eq(organization_id))
+            .order_by_asc(chart_of_accounts::Column::Code);
+        if !filter.account_ids.is_empty() {
+            query = query.filter(chart_of_accounts::Column::Id.is_in(filter.account_ids.clone()));
+        }
+        if !filter.account_types.is_empty() {
+            query = query
+                .filter(chart_of_accounts::Column::AccountType.is_in(filter.account_types.clone()));
+        }
+        if !filter.include_inactive {
+            query = query.filter(chart_of_accounts::Column::IsActive.eq(true));
+        }
+        let accounts = query.all(&self.db).await?;
+
+        let snapshots = BalanceSnapshotRepository::new(self.db.clone());
+        let mut totals = Vec::with_capacity(dates.len());
+        for date in dates {
+            totals.push(snapshots.totals_as_of(organization_id, *date).await?);
+        }
+
+        Ok(accounts
+            .into_iter()
+            .map(|account| {
+                let normal = normal_balance(&account.account_type);
+                let balances = totals
+                    .iter()
+                    .map(|at_date| {
+                        let (debit, credit) = at_date
+                            .get(&account.id)
+                            .copied()
+                            .unwrap_or((Decimal::ZERO, Decimal::ZERO));
+                        normal.calculate_balance_change(debit, credit)
+                    })
+                    .collect();
+                AccountBalancesAtDates { account, balances }
+            })
+            .collect())
     }
 
     /// Gets ledger entries for an account with pagination.
@@ -522,17 +927,20 @@ impl AccountRepository {
     /// * `account_id` - The account ID
     /// * `from` - Optional start date (inclusive)
     /// * `to` - Optional end date (inclusive)
+    /// * `overridden_only` - Only entries booked at an overridden exchange rate
     /// * `page` - Page number (1-indexed)
     /// * `limit` - Number of entries per page
     ///
     /// # Errors
     ///
     /// Returns an error if the database query fails.
+    #[allow(clippy::too_many_lines)]
     pub async fn get_ledger_entries(
         &self,
-        account_id: Uuid,
+        account_id: AccountId,
         from: Option<NaiveDate>,
         to: Option<NaiveDate>,
+        overridden_only: bool,
         page: u64,
         limit: u64,
     ) -> Result<PaginatedLedgerEntries, AccountError> {
@@ -558,6 +966,7 @@ impl AccountRepository {
             memo: Option<String>,
             event_at: chrono::DateTime<chrono::FixedOffset>,
             created_at: chrono::DateTime<chrono::FixedOffset>,
+            is_override: bool,
             // Transaction fields (aliased)
             txn_date: NaiveDate,
             txn_ref: Option<String>,
@@ -580,6 +989,9 @@ impl AccountRepository {
         if let Some(to_date) = to {
             count_query = count_query.filter(transactions::Column::TransactionDate.lte(to_date));
         }
+        if overridden_only {
+            count_query = count_query.filter(ledger_entries::Column::IsOverride.eq(true));
+        }
 
         // Get total count first
         let total = count_query.count(&self.db).await?;
@@ -606,9 +1018,13 @@ impl AccountRepository {
         if let Some(to_date) = to {
             query = query.filter(transactions::Column::TransactionDate.lte(to_date));
         }
+        if overridden_only {
+            query = query.filter(ledger_entries::Column::IsOverride.eq(true));
+        }
 
         let rows: Vec<LedgerEntryRow> = query
             .order_by_desc(transactions::Column::TransactionDate)
+            .order_by_desc(ledger_entries::Column::EventAt)
             .order_by_desc(ledger_entries::Column::AccountVersion)
             .offset(offset)
             .limit(limit)
@@ -637,6 +1053,7 @@ impl AccountRepository {
                     memo: row.memo,
                     event_at: row.event_at,
                     created_at: row.created_at,
+                    is_override: row.is_override,
                 },
                 transaction_date: row.txn_date,
                 reference_number: row.txn_ref,
@@ -653,12 +1070,392 @@ impl AccountRepository {
             total_pages,
         })
     }
+
+    /// Gets an account's posted activity per week or month.
+    ///
+    /// Debits and credits are summed per bucket in a single grouped query;
+    /// the opening balance sums everything posted before `from`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AccountNotFound` if the account is not in the organization,
+    /// or an error if a database query fails.
+    pub async fn get_activity(
+        &self,
+        organization_id: OrganizationId,
+        account_id: AccountId,
+        from: NaiveDate,
+        to: NaiveDate,
+        granularity: ActivityGranularity,
+    ) -> Result<AccountActivity, AccountError> {
+        let account = chart_of_accounts::Entity::find_by_id(account_id.into_inner())
+            .filter(chart_of_accounts::Column::OrganizationId.eq(organization_id))
+            .one(&self.db)
+            .await?
+            .ok_or(AccountError::AccountNotFound(account_id.into_inner()))?;
+        let normal_balance = normal_balance(&account.account_type);
+
+        let opening: Option<(Option<Decimal>, Option<Decimal>)> =
+            Self::posted_entries(organization_id, account_id)
+                .filter(transactions::Column::TransactionDate.lt(from))
+                .select_only()
+                .column_as(ledger_entries::Column::Debit.sum(), "debit")
+                .column_as(ledger_entries::Column::Credit.sum(), "credit")
+                .into_tuple()
+                .one(&self.db)
+                .await?;
+        let (opening_debit, opening_credit) = opening.unwrap_or_default();
+        let opening_balance = normal_balance.calculate_balance_change(
+            opening_debit.unwrap_or(Decimal::ZERO),
+            opening_credit.unwrap_or(Decimal::ZERO),
+        );
+
+        let bucket = Expr::cust(format!(
+            "date_trunc('{}', transactions.transaction_date::timestamp)::date",
+            granularity.as_str()
+        ));
+        let rows: Vec<(NaiveDate, Option<Decimal>, Option<Decimal>)> =
+            Self::posted_entries(organization_id, account_id)
+                .filter(transactions::Column::TransactionDate.gte(from))
+                .filter(transactions::Column::TransactionDate.lte(to))
+                .select_only()
+                .column_as(bucket.clone(), "bucket_start")
+                .column_as(ledger_entries::Column::Debit.sum(), "debit")
+                .column_as(ledger_entries::Column::Credit.sum(), "credit")
+                .group_by(bucket)
+                .into_tuple()
+                .all(&self.db)
+                .await?;
+        let totals: Vec<ActivityTotals> = rows
+            .into_iter()
+            .map(|(bucket_start, debit, credit)| ActivityTotals {
+                bucket_start,
+                debit: debit.unwrap_or(Decimal::ZERO),
+                credit: credit.unwrap_or(Decimal::ZERO),
+            })
+            .collect();
+
+        Ok(AccountActivity {
+            account,
+            opening_balance,
+            buckets: build_activity_buckets(
+                from,
+                to,
+                granularity,
+                normal_balance,
+                opening_balance,
+                &totals,
+            ),
+        })
+    }
+
+    /// Replays every account's running balance and compares it to the
+    /// balances stored on its ledger entries.
+    ///
+    /// Entries of all statuses are replayed, since the stored balances are
+    /// written on insert whatever the transaction's status. Entries are
+    /// streamed in `(account_id, account_version)` order from one read-only
+    /// snapshot, so memory use does not grow with the size of the ledger.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a database query fails.
+    pub async fn verify_ledger_integrity(
+        &self,
+        organization_id: OrganizationId,
+    ) -> Result<Vec<LedgerIntegrityAccount>, AccountError> {
+        let txn = self
+            .db
+            .begin_with_config(
+                Some(IsolationLevel::RepeatableRead),
+                Some(AccessMode::ReadOnly),
+            )
+            .await?;
+
+        let accounts = chart_of_accounts::Entity::find()
+            .filter(chart_of_accounts::Column::OrganizationId.eq(organization_id))
+            .order_by_asc(chart_of_accounts::Column::Code)
+            .all(&txn)
+            .await?;
+        let balance_types: HashMap<Uuid, AccountTypeForBalance> = accounts
+            .iter()
+            .map(|a| (a.id, normal_balance(&a.account_type)))
+            .collect();
+        let mut results: HashMap<Uuid, AccountIntegrity> = HashMap::new();
+
+        {
+            let mut rows = ledger_entries::Entity::find()
+                .join(
+                    JoinType::InnerJoin,
+                    ledger_entries::Relation::ChartOfAccounts.def(),
+                )
+                .filter(chart_of_accounts::Column::OrganizationId.eq(organization_id))
+                .select_only()
+                .column(ledger_entries::Column::AccountId)
+                .column(ledger_entries::Column::Id)
+                .column(ledger_entries::Column::AccountVersion)
+                .column(ledger_entries::Column::Debit)
+                .column(ledger_entries::Column::Credit)
+                .column(ledger_entries::Column::AccountPreviousBalance)
+                .column(ledger_entries::Column::AccountCurrentBalance)
+                .order_by_asc(ledger_entries::Column::AccountId)
+                .order_by_asc(ledger_entries::Column::AccountVersion)
+                .into_tuple::<(Uuid, Uuid, i64, Decimal, Decimal, Decimal, Decimal)>()
+                .stream(&txn)
+                .await?;
+
+            let mut current: Option<(Uuid, RunningBalanceCheck)> = None;
+            while let Some(row) = rows.try_next().await? {
+                let (account_id, entry_id, version, debit, credit, previous, balance) = row;
+                if current.as_ref().is_none_or(|(id, _)| *id != account_id) {
+                    if let Some((id, check)) = current.take() {
+                        results.insert(id, check.finish());
+                    }
+                    current = Some((
+                        account_id,
+                        RunningBalanceCheck::new(balance_types[&account_id]),
+                    ));
+                }
+                if let Some((_, check)) = current.as_mut() {
+                    check.push(&StoredEntry {
+                        entry_id,
+                        account_version: version,
+                        debit,
+                        credit,
+                        previous_balance: previous,
+                        current_balance: balance,
+                    });
+                }
+            }
+            if let Some((id, check)) = current {
+                results.insert(id, check.finish());
+            }
+        }
+        txn.commit().await?;
+
+        Ok(accounts
+            .into_iter()
+            .map(|account| {
+                let integrity = results.remove(&account.id).unwrap_or_else(|| {
+                    RunningBalanceCheck::new(balance_types[&account.id]).finish()
+                });
+                LedgerIntegrityAccount {
+                    account_id: account.id,
+                    code: account.code,
+                    name: account.name,
+                    integrity,
+                }
+            })
+            .collect())
+    }
+
+    /// Entries of an account's posted transactions within the organization.
+    fn posted_entries(
+        organization_id: OrganizationId,
+        account_id: AccountId,
+    ) -> Select<ledger_entries::Entity> {
+        ledger_entries::Entity::find()
+            .filter(ledger_entries::Column::AccountId.eq(account_id))
+            .join(
+                JoinType::InnerJoin,
+                ledger_entries::Relation::Transactions.def(),
+            )
+            .filter(transactions::Column::OrganizationId.eq(organization_id))
+            .filter(transactions::Column::Status.eq(TransactionStatus::Posted))
+    }
+
+    // ========================================================================
+    // Deprecated Uuid shims
+    // ========================================================================
+
+    /// Lists accounts for an organization given a raw `Uuid`.
+    ///
+    /// # Errors
+    ///
+    /// See [`Self::list_accounts`].
+    #[deprecated(note = "use `list_accounts` with an `OrganizationId`")]
+    pub async fn list_accounts_by_uuid(
+        &self,
+        organization_id: Uuid,
+        filter: AccountFilter,
+    ) -> Result<Vec<AccountWithBalance>, AccountError> {
+        self.list_accounts(organization_id.into(), filter).await
+    }
+
+    /// Finds an account given a raw `Uuid`.
+    ///
+    /// # Errors
+    ///
+    /// See [`Self::find_account_by_id`].
+    #[deprecated(note = "use `find_account_by_id` with an `AccountId`")]
+    pub async fn find_account_by_uuid(
+        &self,
+        id: Uuid,
+    ) -> Result<Option<AccountWithBalance>, AccountError> {
+        self.find_account_by_id(id.into()).await
+    }
+
+    /// Updates an account given a raw `Uuid`.
+    ///
+    /// # Errors
+    ///
+    /// See [`Self::update_account`].
+    #[deprecated(note = "use `update_account` with an `AccountId`")]
+    pub async fn update_account_by_uuid(
+        &self,
+        id: Uuid,
+        input: UpdateAccountInput,
+    ) -> Result<chart_of_accounts::Model, AccountError> {
+        self.update_account(id.into(), input).await
+    }
+
+    /// Deletes (deactivates) an account given a raw `Uuid`.
+    ///
+    /// # Errors
+    ///
+    /// See [`Self::delete_account`].
+    #[deprecated(note = "use `delete_account` with an `AccountId`")]
+    pub async fn delete_account_by_uuid(&self, id: Uuid) -> Result<(), AccountError> {
+        self.delete_account(id.into()).await
+    }
+}
+
+/// Validates and inserts an import batch on the given connection.
+///
+/// Shared by [`AccountRepository::create_accounts_batch`] and the
+/// chart-of-accounts templates applied when an organization is bootstrapped.
+///
+/// # Errors
+///
+/// Returns `InvalidImport` with every problem found, or an error if a
+/// database operation fails.
+pub(crate) async fn import_accounts<C: ConnectionTrait>(
+    conn: &C,
+    organization_id: Uuid,
+    inputs: Vec<ImportAccountInput>,
+) -> Result<Vec<chart_of_accounts::Model>, AccountError> {
+    let plan = plan_import(conn, organization_id, &inputs).await?;
+    if !plan.issues.is_empty() {
+        return Err(AccountError::InvalidImport(plan.issues));
+    }
+
+    let mut ids = plan.existing;
+    let mut slots: Vec<Option<ImportAccountInput>> = inputs.into_iter().map(Some).collect();
+    let mut created = Vec::with_capacity(slots.len());
+    let now = chrono::Utc::now().into();
+    for idx in plan.order {
+        let Some(input) = slots[idx].take() else {
+            continue;
+        };
+        let parent_id = input
+            .parent_code
+            .as_ref()
+            .and_then(|code| ids.get(code).copied());
+        let account = input.account;
+        let model = chart_of_accounts::ActiveModel {
+            id: Set(Uuid::new_v4()),
+            organization_id: Set(organization_id),
+            code: Set(account.code),
+            name: Set(account.name),
+            description: Set(account.description),
+            account_type: Set(account.account_type),
+            account_subtype: Set(account.account_subtype),
+            parent_id: Set(parent_id),
+            currency: Set(account.currency),
+            is_active: Set(account.is_active),
+            is_system_account: Set(false),
+            allow_direct_posting: Set(account.allow_direct_posting),
+            is_bank_account: Set(account.is_bank_account),
+            bank_account_number: Set(account.bank_account_number),
+            created_at: Set(now),
+            updated_at: Set(now),
+        }
+        .insert(conn)
+        .await?;
+        ids.insert(model.code.clone(), model.id);
+        created.push(model);
+    }
+
+    Ok(created)
+}
+
+/// Validates an import batch and works out its insert order.
+async fn plan_import<C: ConnectionTrait>(
+    conn: &C,
+    organization_id: Uuid,
+    inputs: &[ImportAccountInput],
+) -> Result<ImportPlan, DbErr> {
+    let existing: HashMap<String, Uuid> = chart_of_accounts::Entity::find()
+        .filter(chart_of_accounts::Column::OrganizationId.eq(organization_id))
+        .all(conn)
+        .await?
+        .into_iter()
+        .map(|a| (a.code, a.id))
+        .collect();
+    let currencies: HashSet<String> = currencies::Entity::find()
+        .all(conn)
+        .await?
+        .into_iter()
+        .map(|c| c.code)
+        .collect();
+
+    let mut issues = Vec::new();
+    for input in inputs {
+        if existing.contains_key(&input.account.code) {
+            issues.push(ImportIssue::new(
+                input.row,
+                "code",
+                format!("Account code '{}' already exists", input.account.code),
+            ));
+        }
+        if !currencies.contains(&input.account.currency) {
+            issues.push(ImportIssue::new(
+                input.row,
+                "currency",
+                format!("Currency '{}' not found", input.account.currency),
+            ));
+        }
+    }
+
+    let existing_codes: HashSet<String> = existing.keys().cloned().collect();
+    let nodes: Vec<HierarchyNode<'_>> = inputs
+        .iter()
+        .map(|input| HierarchyNode {
+            row: input.row,
+            code: &input.account.code,
+            parent_code: input.parent_code.as_deref(),
+        })
+        .collect();
+    let order = match order_by_hierarchy(&nodes, &existing_codes) {
+        Ok(order) => order,
+        Err(hierarchy_issues) => {
+            issues.extend(hierarchy_issues);
+            Vec::new()
+        }
+    };
+    issues.sort_by_key(|issue| issue.row);
+
+    Ok(ImportPlan {
+        issues,
+        order,
+        existing,
+    })
 }
 
 // ============================================================================
 // Pure validation functions for property testing
 // ============================================================================
 
+/// The direction an account's balance grows in.
+const fn normal_balance(account_type: &AccountType) -> AccountTypeForBalance {
+    match account_type {
+        AccountType::Asset | AccountType::Expense => AccountTypeForBalance::DebitNormal,
+        AccountType::Liability | AccountType::Equity | AccountType::Revenue => {
+            AccountTypeForBalance::CreditNormal
+        }
+    }
+}
+
 /// Represents an account code entry for uniqueness checking.
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct AccountCodeEntry {
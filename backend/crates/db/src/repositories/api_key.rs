@@ -0,0 +1,213 @@
+//! API key repository for machine-to-machine authentication.
+//!
+//! Only a SHA-256 hash of each key is stored; the full key is returned once,
+//! when it is created. Keys are looked up by hash across organizations, so
+//! every other query filters on the organization explicitly.
+
+use chrono::{DateTime, Utc};
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, Condition, DatabaseConnection, DbErr, EntityTrait, QueryFilter,
+    QueryOrder, Set,
+};
+use sha2::{Digest, Sha256};
+use uuid::Uuid;
+use zeltra_core::auth::{
+    API_KEY_PREFIX, ApiKeyError, ApiKeyRole, ApiKeyScope, display_prefix, validate_scopes,
+};
+
+use crate::entities::api_keys;
+
+/// Error types for API key operations.
+#[derive(Debug, thiserror::Error)]
+pub enum ApiKeyRepoError {
+    /// API key not found.
+    #[error("API key not found: {0}")]
+    NotFound(Uuid),
+
+    /// The expiry is not in the future.
+    #[error("API key expiry must be in the future")]
+    ExpiryInPast,
+
+    /// The role or scopes are invalid.
+    #[error(transparent)]
+    Invalid(#[from] ApiKeyError),
+
+    /// Database error.
+    #[error("Database error: {0}")]
+    Database(#[from] DbErr),
+}
+
+/// Input for creating an API key.
+#[derive(Debug, Clone)]
+pub struct CreateApiKeyInput {
+    /// Name shown in listings.
+    pub name: String,
+    /// Role the key acts with.
+    pub role: ApiKeyRole,
+    /// Scopes the key holds.
+    pub scopes: Vec<ApiKeyScope>,
+    /// When the key stops working, if ever.
+    pub expires_at: Option<DateTime<Utc>>,
+    /// User creating the key.
+    pub created_by: Uuid,
+}
+
+/// A newly created key together with its secret.
+#[derive(Debug, Clone)]
+pub struct CreatedApiKey {
+    /// The full key; it cannot be recovered later.
+    pub key: String,
+    /// The stored key.
+    pub api_key: api_keys::Model,
+}
+
+/// Repository for API keys.
+#[derive(Debug, Clone)]
+pub struct ApiKeyRepository {
+    db: DatabaseConnection,
+}
+
+impl ApiKeyRepository {
+    /// Creates a new API key repository.
+    #[must_use]
+    pub const fn new(db: DatabaseConnection) -> Self {
+        Self { db }
+    }
+
+    /// Hashes an API key for storage.
+    #[must_use]
+    pub fn hash_key(key: &str) -> String {
+        format!("{:x}", Sha256::digest(key.as_bytes()))
+    }
+
+    /// Generates a new random API key.
+    #[must_use]
+    pub fn generate_key() -> String {
+        let bytes: [u8; 32] = rand::random();
+        format!("{API_KEY_PREFIX}{}", base64_url::encode(&bytes))
+    }
+
+    /// Creates an API key for an organization.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the scopes do not suit the role, if the expiry is
+    /// not in the future, or if the insert fails.
+    pub async fn create(
+        &self,
+        organization_id: Uuid,
+        input: CreateApiKeyInput,
+    ) -> Result<CreatedApiKey, ApiKeyRepoError> {
+        let mut scopes = input.scopes;
+        scopes.sort_by_key(|s| s.as_str());
+        scopes.dedup();
+        validate_scopes(input.role, &scopes)?;
+
+        let now = Utc::now();
+        if input.expires_at.is_some_and(|at| at <= now) {
+            return Err(ApiKeyRepoError::ExpiryInPast);
+        }
+
+        let key = Self::generate_key();
+        let api_key = api_keys::ActiveModel {
+            id: Set(Uuid::new_v4()),
+            organization_id: Set(organization_id),
+            name: Set(input.name),
+            key_hash: Set(Self::hash_key(&key)),
+            key_prefix: Set(display_prefix(&key).to_string()),
+            role: Set(input.role.as_str().to_string()),
+            scopes: Set(scopes.iter().map(|s| s.as_str().to_string()).collect()),
+            expires_at: Set(input.expires_at.map(Into::into)),
+            last_used_at: Set(None),
+            revoked_at: Set(None),
+            created_by: Set(input.created_by),
+            created_at: Set(now.into()),
+        }
+        .insert(&self.db)
+        .await?;
+
+        Ok(CreatedApiKey { key, api_key })
+    }
+
+    /// Lists an organization's API keys, newest first, including revoked ones.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    pub async fn list(&self, organization_id: Uuid) -> Result<Vec<api_keys::Model>, DbErr> {
+        api_keys::Entity::find()
+            .filter(api_keys::Column::OrganizationId.eq(organization_id))
+            .order_by_desc(api_keys::Column::CreatedAt)
+            .all(&self.db)
+            .await
+    }
+
+    /// Finds an organization's API key by ID.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    pub async fn find_by_id(
+        &self,
+        organization_id: Uuid,
+        id: Uuid,
+    ) -> Result<Option<api_keys::Model>, DbErr> {
+        api_keys::Entity::find_by_id(id)
+            .filter(api_keys::Column::OrganizationId.eq(organization_id))
+            .one(&self.db)
+            .await
+    }
+
+    /// Revokes an API key; it is rejected from the next request on.
+    ///
+    /// Revoking an already revoked key keeps the original revocation time.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the key does not exist or the update fails.
+    pub async fn revoke(
+        &self,
+        organization_id: Uuid,
+        id: Uuid,
+    ) -> Result<api_keys::Model, ApiKeyRepoError> {
+        let api_key = self
+            .find_by_id(organization_id, id)
+            .await?
+            .ok_or(ApiKeyRepoError::NotFound(id))?;
+        if api_key.revoked_at.is_some() {
+            return Ok(api_key);
+        }
+
+        let mut active: api_keys::ActiveModel = api_key.into();
+        active.revoked_at = Set(Some(Utc::now().into()));
+        Ok(active.update(&self.db).await?)
+    }
+
+    /// Finds the active key matching `key` and records that it was used.
+    ///
+    /// Returns `None` for unknown, revoked or expired keys.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    pub async fn authenticate(&self, key: &str) -> Result<Option<api_keys::Model>, DbErr> {
+        let now = Utc::now();
+        let Some(api_key) = api_keys::Entity::find()
+            .filter(api_keys::Column::KeyHash.eq(Self::hash_key(key)))
+            .filter(api_keys::Column::RevokedAt.is_null())
+            .filter(
+                Condition::any()
+                    .add(api_keys::Column::ExpiresAt.is_null())
+                    .add(api_keys::Column::ExpiresAt.gt(now)),
+            )
+            .one(&self.db)
+            .await?
+        else {
+            return Ok(None);
+        };
+
+        let mut active: api_keys::ActiveModel = api_key.into();
+        active.last_used_at = Set(Some(now.into()));
+        active.update(&self.db).await.map(Some)
+    }
+}
@@ -0,0 +1,250 @@
+//! Approval delegation repository.
+//!
+//! A delegation lets a member (the delegate) approve transactions on behalf
+//! of another member (the delegator) between `starts_at` and `ends_at`.
+//! Delegations are checked against the clock when used, so expired ones stop
+//! working without being deleted.
+
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, Condition, DatabaseConnection, EntityTrait, ModelTrait,
+    QueryFilter, QueryOrder, Set,
+};
+use thiserror::Error;
+use uuid::Uuid;
+
+use crate::entities::{approval_delegations, organization_users};
+
+/// Errors that can occur during approval delegation operations.
+#[derive(Debug, Error)]
+pub enum ApprovalDelegationError {
+    /// Delegation not found.
+    #[error("Approval delegation {0} not found")]
+    NotFound(Uuid),
+
+    /// Delegator and delegate are the same user.
+    #[error("Cannot delegate approvals to yourself")]
+    SelfDelegation,
+
+    /// The delegation ends before it starts.
+    #[error("Delegation must end after it starts")]
+    InvalidPeriod,
+
+    /// The amount cap is not positive.
+    #[error("Amount cap must be positive")]
+    InvalidAmountCap,
+
+    /// A user in the delegation is not a member of the organization.
+    #[error("User {0} is not a member of this organization")]
+    NotMember(Uuid),
+
+    /// Database error.
+    #[error("Database error: {0}")]
+    Database(#[from] sea_orm::DbErr),
+}
+
+/// Input for creating an approval delegation.
+#[derive(Debug, Clone)]
+pub struct CreateApprovalDelegationInput {
+    /// User whose approval authority is delegated.
+    pub delegator_id: Uuid,
+    /// User who may approve on the delegator's behalf.
+    pub delegate_id: Uuid,
+    /// When the delegation takes effect.
+    pub starts_at: DateTime<Utc>,
+    /// When the delegation stops (exclusive).
+    pub ends_at: DateTime<Utc>,
+    /// Largest transaction the delegate may approve.
+    pub amount_cap: Option<Decimal>,
+    /// User creating the delegation.
+    pub created_by: Uuid,
+}
+
+/// Input for updating an approval delegation.
+#[derive(Debug, Clone, Default)]
+pub struct UpdateApprovalDelegationInput {
+    /// New start.
+    pub starts_at: Option<DateTime<Utc>>,
+    /// New end.
+    pub ends_at: Option<DateTime<Utc>>,
+    /// New amount cap.
+    pub amount_cap: Option<Option<Decimal>>,
+}
+
+/// Repository for approval delegation operations.
+#[derive(Debug, Clone)]
+pub struct ApprovalDelegationRepository {
+    db: DatabaseConnection,
+}
+
+impl ApprovalDelegationRepository {
+    /// Creates a new approval delegation repository.
+    #[must_use]
+    pub const fn new(db: DatabaseConnection) -> Self {
+        Self { db }
+    }
+
+    /// Creates an approval delegation.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the users are the same or not both members, the
+    /// period or cap is invalid, or the database operation fails.
+    pub async fn create_delegation(
+        &self,
+        organization_id: Uuid,
+        input: CreateApprovalDelegationInput,
+    ) -> Result<approval_delegations::Model, ApprovalDelegationError> {
+        if input.delegator_id == input.delegate_id {
+            return Err(ApprovalDelegationError::SelfDelegation);
+        }
+        validate(input.starts_at, input.ends_at, input.amount_cap)?;
+        for user_id in [input.delegator_id, input.delegate_id] {
+            self.check_member(organization_id, user_id).await?;
+        }
+
+        let now = Utc::now().into();
+        let delegation = approval_delegations::ActiveModel {
+            id: Set(Uuid::new_v4()),
+            organization_id: Set(organization_id),
+            delegator_id: Set(input.delegator_id),
+            delegate_id: Set(input.delegate_id),
+            starts_at: Set(input.starts_at.into()),
+            ends_at: Set(input.ends_at.into()),
+            amount_cap: Set(input.amount_cap),
+            created_by: Set(input.created_by),
+            created_at: Set(now),
+            updated_at: Set(now),
+        };
+
+        Ok(delegation.insert(&self.db).await?)
+    }
+
+    /// Lists an organization's delegations, newest first.
+    ///
+    /// With `user_id`, only delegations where that user is the delegator or
+    /// the delegate are returned. Expired delegations are included only when
+    /// `include_expired` is set.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    pub async fn list_delegations(
+        &self,
+        organization_id: Uuid,
+        user_id: Option<Uuid>,
+        include_expired: bool,
+    ) -> Result<Vec<approval_delegations::Model>, ApprovalDelegationError> {
+        let mut query = approval_delegations::Entity::find()
+            .filter(approval_delegations::Column::OrganizationId.eq(organization_id));
+        if let Some(user_id) = user_id {
+            query = query.filter(
+                Condition::any()
+                    .add(approval_delegations::Column::DelegatorId.eq(user_id))
+                    .add(approval_delegations::Column::DelegateId.eq(user_id)),
+            );
+        }
+        if !include_expired {
+            query = query.filter(approval_delegations::Column::EndsAt.gt(Utc::now()));
+        }
+
+        Ok(query
+            .order_by_desc(approval_delegations::Column::StartsAt)
+            .all(&self.db)
+            .await?)
+    }
+
+    /// Gets a delegation by ID.
+    ///
+    /// # Errors
+    ///
+    /// Returns `NotFound` if the delegation does not exist in the organization.
+    pub async fn get_delegation(
+        &self,
+        organization_id: Uuid,
+        delegation_id: Uuid,
+    ) -> Result<approval_delegations::Model, ApprovalDelegationError> {
+        approval_delegations::Entity::find_by_id(delegation_id)
+            .filter(approval_delegations::Column::OrganizationId.eq(organization_id))
+            .one(&self.db)
+            .await?
+            .ok_or(ApprovalDelegationError::NotFound(delegation_id))
+    }
+
+    /// Updates a delegation's period or cap.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the delegation does not exist, the resulting period
+    /// or cap is invalid, or the database operation fails.
+    pub async fn update_delegation(
+        &self,
+        organization_id: Uuid,
+        delegation_id: Uuid,
+        input: UpdateApprovalDelegationInput,
+    ) -> Result<approval_delegations::Model, ApprovalDelegationError> {
+        let existing = self.get_delegation(organization_id, delegation_id).await?;
+
+        let starts_at = input
+            .starts_at
+            .unwrap_or_else(|| existing.starts_at.with_timezone(&Utc));
+        let ends_at = input
+            .ends_at
+            .unwrap_or_else(|| existing.ends_at.with_timezone(&Utc));
+        let amount_cap = input.amount_cap.unwrap_or(existing.amount_cap);
+        validate(starts_at, ends_at, amount_cap)?;
+
+        let mut active: approval_delegations::ActiveModel = existing.into();
+        active.starts_at = Set(starts_at.into());
+        active.ends_at = Set(ends_at.into());
+        active.amount_cap = Set(amount_cap);
+        active.updated_at = Set(Utc::now().into());
+
+        Ok(active.update(&self.db).await?)
+    }
+
+    /// Deletes a delegation.
+    ///
+    /// # Errors
+    ///
+    /// Returns `NotFound` if the delegation does not exist in the organization.
+    pub async fn delete_delegation(
+        &self,
+        organization_id: Uuid,
+        delegation_id: Uuid,
+    ) -> Result<(), ApprovalDelegationError> {
+        let existing = self.get_delegation(organization_id, delegation_id).await?;
+        existing.delete(&self.db).await?;
+        Ok(())
+    }
+
+    async fn check_member(
+        &self,
+        organization_id: Uuid,
+        user_id: Uuid,
+    ) -> Result<(), ApprovalDelegationError> {
+        organization_users::Entity::find()
+            .filter(organization_users::Column::OrganizationId.eq(organization_id))
+            .filter(organization_users::Column::UserId.eq(user_id))
+            .one(&self.db)
+            .await?
+            .map(|_| ())
+            .ok_or(ApprovalDelegationError::NotMember(user_id))
+    }
+}
+
+/// Checks a delegation's period and cap.
+fn validate(
+    starts_at: DateTime<Utc>,
+    ends_at: DateTime<Utc>,
+    amount_cap: Option<Decimal>,
+) -> Result<(), ApprovalDelegationError> {
+    if ends_at <= starts_at {
+        return Err(ApprovalDelegationError::InvalidPeriod);
+    }
+    if amount_cap.is_some_and(|cap| cap <= Decimal::ZERO) {
+        return Err(ApprovalDelegationError::InvalidAmountCap);
+    }
+    Ok(())
+}
@@ -6,11 +6,13 @@
 
 use rust_decimal::Decimal;
 use sea_orm::{
-    ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, QueryOrder, Set,
+    ActiveModelTrait, ColumnTrait, ConnectionTrait, DatabaseConnection, EntityTrait, QueryFilter,
+    QueryOrder, Set, TransactionTrait,
 };
 use thiserror::Error;
 use uuid::Uuid;
 
+use super::subscription::{LimitCheckResult, ResourceLimit, SubscriptionRepository};
 use crate::entities::{
     approval_rules::{self, ActiveModel, Entity as ApprovalRuleEntity, Model as ApprovalRuleModel},
     sea_orm_active_enums::{TransactionType, UserRole},
@@ -34,6 +36,14 @@ pub enum ApprovalRuleError {
     /// Invalid role.
     #[error("Invalid role: {0}")]
     InvalidRole(String),
+
+    /// Required approvals count below one.
+    #[error("Required approvals must be at least 1, got {0}")]
+    InvalidRequiredApprovals(i16),
+
+    /// The organization's plan does not allow another one.
+    #[error("Tier limit exceeded for {}", .0.resource.as_str())]
+    TierLimitExceeded(LimitCheckResult),
 }
 
 /// Input for creating an approval rule.
@@ -53,6 +63,8 @@ pub struct CreateApprovalRuleInput {
     pub required_role: String,
     /// Priority (lower = higher priority).
     pub priority: i16,
+    /// Number of different users who must approve.
+    pub required_approvals: i16,
 }
 
 /// Input for updating an approval rule.
@@ -72,6 +84,8 @@ pub struct UpdateApprovalRuleInput {
     pub required_role: Option<String>,
     /// New priority.
     pub priority: Option<i16>,
+    /// New required approvals count.
+    pub required_approvals: Option<i16>,
     /// Active status.
     pub is_active: Option<bool>,
 }
@@ -97,6 +111,10 @@ impl ApprovalRuleRepository {
     ) -> Result<ApprovalRuleModel, ApprovalRuleError> {
         let transaction_types = Self::parse_transaction_types(&input.transaction_types)?;
         let required_role = Self::parse_role_static(&input.required_role)?;
+        Self::validate_required_approvals(input.required_approvals)?;
+
+        let txn = self.db.begin().await?;
+        Self::check_rule_limit(&txn, organization_id).await?;
 
         let rule = ActiveModel {
             id: Set(Uuid::new_v4()),
@@ -108,15 +126,32 @@ impl ApprovalRuleRepository {
             transaction_types: Set(transaction_types),
             required_role: Set(required_role),
             priority: Set(input.priority),
+            required_approvals: Set(input.required_approvals),
             is_active: Set(true),
             created_at: Set(chrono::Utc::now().into()),
             updated_at: Set(chrono::Utc::now().into()),
         };
 
-        let result = rule.insert(&self.db).await?;
+        let result = rule.insert(&txn).await?;
+        txn.commit().await?;
         Ok(result)
     }
 
+    /// Fails if the organization cannot have another active rule.
+    async fn check_rule_limit<C: ConnectionTrait>(
+        db: &C,
+        organization_id: Uuid,
+    ) -> Result<(), ApprovalRuleError> {
+        let limit =
+            SubscriptionRepository::check_limit(db, organization_id, ResourceLimit::ApprovalRules)
+                .await?;
+        if limit.allowed {
+            Ok(())
+        } else {
+            Err(ApprovalRuleError::TierLimitExceeded(limit))
+        }
+    }
+
     /// Lists all active approval rules for an organization.
     ///
     /// **Validates: Requirements 6.9**
@@ -158,6 +193,12 @@ impl ApprovalRuleRepository {
     ) -> Result<ApprovalRuleModel, ApprovalRuleError> {
         let existing = self.get_rule(organization_id, rule_id).await?;
 
+        let txn = self.db.begin().await?;
+        // Reactivating counts against the limit like creating
+        if input.is_active == Some(true) && !existing.is_active {
+            Self::check_rule_limit(&txn, organization_id).await?;
+        }
+
         let mut rule: ActiveModel = existing.into();
 
         if let Some(name) = input.name {
@@ -181,13 +222,18 @@ impl ApprovalRuleRepository {
         if let Some(priority) = input.priority {
             rule.priority = Set(priority);
         }
+        if let Some(required_approvals) = input.required_approvals {
+            Self::validate_required_approvals(required_approvals)?;
+            rule.required_approvals = Set(required_approvals);
+        }
         if let Some(is_active) = input.is_active {
             rule.is_active = Set(is_active);
         }
 
         rule.updated_at = Set(chrono::Utc::now().into());
 
-        let result = rule.update(&self.db).await?;
+        let result = rule.update(&txn).await?;
+        txn.commit().await?;
         Ok(result)
     }
 
@@ -276,6 +322,13 @@ impl ApprovalRuleRepository {
             _ => Err(ApprovalRuleError::InvalidRole(role.to_string())),
         }
     }
+
+    fn validate_required_approvals(count: i16) -> Result<(), ApprovalRuleError> {
+        if count < 1 {
+            return Err(ApprovalRuleError::InvalidRequiredApprovals(count));
+        }
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -330,6 +383,16 @@ mod tests {
         assert!(ApprovalRuleRepository::parse_role_static("superadmin").is_err());
     }
 
+    #[test]
+    fn test_validate_required_approvals() {
+        assert!(ApprovalRuleRepository::validate_required_approvals(1).is_ok());
+        assert!(ApprovalRuleRepository::validate_required_approvals(3).is_ok());
+        assert!(matches!(
+            ApprovalRuleRepository::validate_required_approvals(0),
+            Err(ApprovalRuleError::InvalidRequiredApprovals(0))
+        ));
+    }
+
     #[test]
     fn test_error_display() {
         let err = ApprovalRuleError::NotFound(Uuid::new_v4());
@@ -2,20 +2,23 @@
 //!
 //! Implements attachment CRUD operations using SeaORM.
 
-use chrono::Utc;
+use chrono::{DateTime, NaiveDate, NaiveTime, Utc};
 use sea_orm::{
-    ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, PaginatorTrait, QueryFilter,
-    QueryOrder, Set,
+    ActiveModelTrait, ColumnTrait, Condition, ConnectionTrait, DatabaseConnection, DbErr,
+    EntityTrait, PaginatorTrait, QueryFilter, QueryOrder, QuerySelect, Set, TransactionTrait,
+    sea_query::Expr,
 };
 use uuid::Uuid;
+use zeltra_shared::types::{PageRequest, PageResponse};
 
 use crate::entities::{
-    attachments, sea_orm_active_enums::AttachmentType as DbAttachmentType,
-    sea_orm_active_enums::StorageProvider as DbStorageProvider, transactions,
+    attachments, ledger_entries, sea_orm_active_enums::AttachmentType as DbAttachmentType,
+    sea_orm_active_enums::StorageProvider as DbStorageProvider,
+    sea_orm_active_enums::TransactionStatus, transactions, users,
 };
 use zeltra_core::attachment::{
     Attachment, AttachmentError, AttachmentRepository as AttachmentRepoTrait, AttachmentType,
-    CreateAttachmentInput,
+    CreateAttachmentInput, ExtractionRecord,
 };
 
 /// Attachment repository implementation.
@@ -24,12 +27,347 @@ pub struct AttachmentRepository {
     db: DatabaseConnection,
 }
 
+/// An attachment with the outcome of its OCR extraction.
+#[derive(Debug, Clone)]
+pub struct AttachmentExtraction {
+    /// The attachment.
+    pub attachment: Attachment,
+    /// Extraction outcome; `None` until the attachment has been processed.
+    pub record: Option<ExtractionRecord>,
+    /// When the extraction ran.
+    pub processed_at: Option<DateTime<Utc>>,
+}
+
+/// An attachment in a listing, with its uploader's name.
+#[derive(Debug, Clone)]
+pub struct AttachmentListItem {
+    /// The attachment.
+    pub attachment: Attachment,
+    /// Full name of the uploader; `None` if the user no longer exists.
+    pub uploader_name: Option<String>,
+}
+
+/// Filters for listing an organization's attachments.
+#[derive(Debug, Clone, Default)]
+pub struct AttachmentListFilter {
+    /// Only attachments of this transaction.
+    pub transaction_id: Option<Uuid>,
+    /// Only attachments on this ledger entry.
+    pub ledger_entry_id: Option<Uuid>,
+    /// Only attachments of this type.
+    pub attachment_type: Option<AttachmentType>,
+    /// Only attachments uploaded on or after this date (UTC).
+    pub uploaded_from: Option<NaiveDate>,
+    /// Only attachments uploaded on or before this date (UTC).
+    pub uploaded_to: Option<NaiveDate>,
+}
+
 impl AttachmentRepository {
     /// Create a new attachment repository.
     #[must_use]
     pub fn new(db: DatabaseConnection) -> Self {
         Self { db }
     }
+
+    /// Lists receipts and invoices not yet sent for OCR, oldest first.
+    ///
+    /// Only PDFs and images are returned, across all organizations.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    pub async fn list_pending_extraction(
+        &self,
+        limit: u64,
+    ) -> Result<Vec<Attachment>, AttachmentError> {
+        let models = attachments::Entity::find()
+            .filter(attachments::Column::OcrProcessedAt.is_null())
+            .filter(
+                attachments::Column::AttachmentType
+                    .is_in([DbAttachmentType::Receipt, DbAttachmentType::Invoice]),
+            )
+            .filter(
+                Condition::any()
+                    .add(attachments::Column::MimeType.eq("application/pdf"))
+                    .add(attachments::Column::MimeType.starts_with("image/")),
+            )
+            .order_by_asc(attachments::Column::CreatedAt)
+            .limit(limit)
+            .all(&self.db)
+            .await
+            .map_err(|e| AttachmentError::repository(e.to_string()))?;
+
+        Ok(models.into_iter().map(to_domain).collect())
+    }
+
+    /// Stores the outcome of an extraction and marks the attachment processed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the attachment does not exist or the update fails.
+    pub async fn save_extraction(
+        &self,
+        id: Uuid,
+        record: &ExtractionRecord,
+    ) -> Result<(), AttachmentError> {
+        let data =
+            serde_json::to_value(record).map_err(|e| AttachmentError::repository(e.to_string()))?;
+
+        attachments::ActiveModel {
+            id: Set(id),
+            extracted_data: Set(Some(data)),
+            ocr_processed_at: Set(Some(Utc::now().into())),
+            ..Default::default()
+        }
+        .update(&self.db)
+        .await
+        .map_err(|e| match e {
+            DbErr::RecordNotUpdated => AttachmentError::not_found(id),
+            e => AttachmentError::repository(e.to_string()),
+        })?;
+
+        Ok(())
+    }
+
+    /// Gets an attachment with its extraction outcome.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the query fails or the stored data is unreadable.
+    pub async fn find_extraction(
+        &self,
+        id: Uuid,
+        organization_id: Uuid,
+    ) -> Result<Option<AttachmentExtraction>, AttachmentError> {
+        let Some(model) = attachments::Entity::find_by_id(id)
+            .filter(attachments::Column::OrganizationId.eq(organization_id))
+            .one(&self.db)
+            .await
+            .map_err(|e| AttachmentError::repository(e.to_string()))?
+        else {
+            return Ok(None);
+        };
+
+        let record = model
+            .extracted_data
+            .clone()
+            .map(serde_json::from_value)
+            .transpose()
+            .map_err(|e| AttachmentError::repository(e.to_string()))?;
+        let processed_at = model.ocr_processed_at.map(|t| t.with_timezone(&Utc));
+
+        Ok(Some(AttachmentExtraction {
+            attachment: to_domain(model),
+            record,
+            processed_at,
+        }))
+    }
+
+    /// Lists an organization's attachments, newest first, one page at a time.
+    ///
+    /// Scoping the filter to a transaction gives the transaction's listing;
+    /// leaving it unscoped lists the whole organization.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    pub async fn list_attachments(
+        &self,
+        organization_id: Uuid,
+        filter: &AttachmentListFilter,
+        page: &PageRequest,
+    ) -> Result<PageResponse<AttachmentListItem>, AttachmentError> {
+        let mut query = attachments::Entity::find()
+            .filter(attachments::Column::OrganizationId.eq(organization_id));
+        if let Some(transaction_id) = filter.transaction_id {
+            query = query.filter(attachments::Column::TransactionId.eq(transaction_id));
+        }
+        if let Some(entry_id) = filter.ledger_entry_id {
+            query = query.filter(attachments::Column::LedgerEntryId.eq(entry_id));
+        }
+        if let Some(attachment_type) = filter.attachment_type {
+            query = query.filter(
+                attachments::Column::AttachmentType.eq(to_db_attachment_type(attachment_type)),
+            );
+        }
+        if let Some(from) = filter.uploaded_from {
+            query = query.filter(
+                attachments::Column::CreatedAt.gte(from.and_time(NaiveTime::MIN).and_utc()),
+            );
+        }
+        if let Some(to) = filter.uploaded_to.and_then(|to| to.succ_opt()) {
+            query = query
+                .filter(attachments::Column::CreatedAt.lt(to.and_time(NaiveTime::MIN).and_utc()));
+        }
+
+        let total = query
+            .clone()
+            .count(&self.db)
+            .await
+            .map_err(|e| AttachmentError::repository(e.to_string()))?;
+        let rows = query
+            .find_also_related(users::Entity)
+            .order_by_desc(attachments::Column::CreatedAt)
+            .order_by_desc(attachments::Column::Id)
+            .offset(page.offset())
+            .limit(page.limit())
+            .all(&self.db)
+            .await
+            .map_err(|e| AttachmentError::repository(e.to_string()))?;
+
+        let items = rows
+            .into_iter()
+            .map(|(model, uploader)| AttachmentListItem {
+                attachment: to_domain(model),
+                uploader_name: uploader.map(|u| u.full_name),
+            })
+            .collect();
+        Ok(PageResponse::new(items, page.page, page.per_page, total))
+    }
+
+    /// Checks that a transaction can receive moved attachments: it belongs
+    /// to the organization and is not voided.
+    ///
+    /// # Errors
+    ///
+    /// Returns `TransactionNotFound` if the transaction does not exist in
+    /// the organization, `TransactionVoided` if it is voided, or an error if
+    /// the query fails.
+    pub async fn check_move_target(
+        &self,
+        organization_id: Uuid,
+        transaction_id: Uuid,
+    ) -> Result<(), AttachmentError> {
+        check_move_target(&self.db, organization_id, transaction_id).await
+    }
+
+    /// Moves an attachment to another transaction of the same organization.
+    ///
+    /// The storage key is unchanged. The transaction it came from is kept in
+    /// `moved_from_transaction_id` with who moved it and when. A line-level
+    /// attachment becomes transaction-level, since its line belongs to the
+    /// old transaction. Moving to the transaction it is already on changes
+    /// nothing.
+    ///
+    /// # Errors
+    ///
+    /// Returns `NotFound` if the attachment is not in the organization,
+    /// `TransactionNotFound` or `TransactionVoided` if the target cannot take
+    /// it, or an error if a database operation fails.
+    pub async fn move_to_transaction(
+        &self,
+        id: Uuid,
+        organization_id: Uuid,
+        to_transaction_id: Uuid,
+        moved_by: Uuid,
+    ) -> Result<Attachment, AttachmentError> {
+        let txn = self
+            .db
+            .begin()
+            .await
+            .map_err(|e| AttachmentError::repository(e.to_string()))?;
+
+        let model = attachments::Entity::find_by_id(id)
+            .filter(attachments::Column::OrganizationId.eq(organization_id))
+            .one(&txn)
+            .await
+            .map_err(|e| AttachmentError::repository(e.to_string()))?
+            .ok_or_else(|| AttachmentError::not_found(id))?;
+        check_move_target(&txn, organization_id, to_transaction_id).await?;
+        if model.transaction_id == Some(to_transaction_id) {
+            return Ok(to_domain(model));
+        }
+
+        let moved_from = model.transaction_id;
+        let mut active: attachments::ActiveModel = model.into();
+        active.transaction_id = Set(Some(to_transaction_id));
+        active.ledger_entry_id = Set(None);
+        active.moved_from_transaction_id = Set(moved_from);
+        active.moved_by = Set(Some(moved_by));
+        active.moved_at = Set(Some(Utc::now().into()));
+        let model = active
+            .update(&txn)
+            .await
+            .map_err(|e| AttachmentError::repository(e.to_string()))?;
+
+        txn.commit()
+            .await
+            .map_err(|e| AttachmentError::repository(e.to_string()))?;
+        Ok(to_domain(model))
+    }
+
+    /// Moves every attachment of a transaction to another transaction of the
+    /// same organization in one database transaction, as when a voided
+    /// transaction is re-entered. Returns how many were moved.
+    ///
+    /// Each moved attachment is recorded as by [`Self::move_to_transaction`].
+    ///
+    /// # Errors
+    ///
+    /// Returns `TransactionNotFound` or `TransactionVoided` if the target
+    /// cannot take them, or an error if a database operation fails.
+    pub async fn move_all(
+        &self,
+        organization_id: Uuid,
+        from_transaction_id: Uuid,
+        to_transaction_id: Uuid,
+        moved_by: Uuid,
+    ) -> Result<u64, AttachmentError> {
+        if from_transaction_id == to_transaction_id {
+            return Ok(0);
+        }
+
+        let txn = self
+            .db
+            .begin()
+            .await
+            .map_err(|e| AttachmentError::repository(e.to_string()))?;
+        check_move_target(&txn, organization_id, to_transaction_id).await?;
+
+        let result = attachments::Entity::update_many()
+            .col_expr(
+                attachments::Column::TransactionId,
+                Expr::value(to_transaction_id),
+            )
+            .col_expr(
+                attachments::Column::LedgerEntryId,
+                Expr::value(None::<Uuid>),
+            )
+            .col_expr(
+                attachments::Column::MovedFromTransactionId,
+                Expr::value(from_transaction_id),
+            )
+            .col_expr(attachments::Column::MovedBy, Expr::value(moved_by))
+            .col_expr(attachments::Column::MovedAt, Expr::value(Utc::now()))
+            .filter(attachments::Column::OrganizationId.eq(organization_id))
+            .filter(attachments::Column::TransactionId.eq(from_transaction_id))
+            .exec(&txn)
+            .await
+            .map_err(|e| AttachmentError::repository(e.to_string()))?;
+
+        txn.commit()
+            .await
+            .map_err(|e| AttachmentError::repository(e.to_string()))?;
+        Ok(result.rows_affected)
+    }
+}
+
+/// Checks that a transaction exists in the organization and is not voided.
+async fn check_move_target<C: ConnectionTrait>(
+    conn: &C,
+    organization_id: Uuid,
+    transaction_id: Uuid,
+) -> Result<(), AttachmentError> {
+    let target = transactions::Entity::find_by_id(transaction_id)
+        .filter(transactions::Column::OrganizationId.eq(organization_id))
+        .one(conn)
+        .await
+        .map_err(|e| AttachmentError::repository(e.to_string()))?
+        .ok_or_else(|| AttachmentError::transaction_not_found(transaction_id))?;
+    if target.status == TransactionStatus::Voided {
+        return Err(AttachmentError::transaction_voided(transaction_id));
+    }
+    Ok(())
 }
 
 impl AttachmentRepoTrait for AttachmentRepository {
@@ -38,6 +376,7 @@ impl AttachmentRepoTrait for AttachmentRepository {
             id: Set(input.id),
             organization_id: Set(input.organization_id),
             transaction_id: Set(input.transaction_id),
+            ledger_entry_id: Set(input.ledger_entry_id),
             attachment_type: Set(to_db_attachment_type(input.attachment_type)),
             file_name: Set(input.filename.clone()),
             file_size: Set(input.file_size),
@@ -51,6 +390,9 @@ impl AttachmentRepoTrait for AttachmentRepository {
             ocr_processed_at: Set(None),
             uploaded_by: Set(input.uploaded_by),
             created_at: Set(Utc::now().into()),
+            moved_from_transaction_id: Set(None),
+            moved_by: Set(None),
+            moved_at: Set(None),
         };
 
         let model = active_model
@@ -78,11 +420,17 @@ impl AttachmentRepoTrait for AttachmentRepository {
     async fn list_by_transaction(
         &self,
         transaction_id: Uuid,
+        ledger_entry_id: Option<Uuid>,
         organization_id: Uuid,
     ) -> Result<Vec<Attachment>, AttachmentError> {
-        let models = attachments::Entity::find()
+        let mut query = attachments::Entity::find()
             .filter(attachments::Column::TransactionId.eq(transaction_id))
-            .filter(attachments::Column::OrganizationId.eq(organization_id))
+            .filter(attachments::Column::OrganizationId.eq(organization_id));
+        if let Some(entry_id) = ledger_entry_id {
+            query = query.filter(attachments::Column::LedgerEntryId.eq(entry_id));
+        }
+
+        let models = query
             .order_by_desc(attachments::Column::CreatedAt)
             .all(&self.db)
             .await
@@ -115,6 +463,23 @@ impl AttachmentRepoTrait for AttachmentRepository {
 
         Ok(count > 0)
     }
+
+    async fn entry_exists(
+        &self,
+        ledger_entry_id: Uuid,
+        transaction_id: Uuid,
+        organization_id: Uuid,
+    ) -> Result<bool, AttachmentError> {
+        let count: u64 = ledger_entries::Entity::find_by_id(ledger_entry_id)
+            .filter(ledger_entries::Column::TransactionId.eq(transaction_id))
+            .inner_join(transactions::Entity)
+            .filter(transactions::Column::OrganizationId.eq(organization_id))
+            .count(&self.db)
+            .await
+            .map_err(|e| AttachmentError::repository(e.to_string()))?;
+
+        Ok(count > 0)
+    }
 }
 
 /// Convert domain attachment type to database enum.
@@ -169,6 +534,8 @@ fn to_domain(model: attachments::Model) -> Attachment {
         id: model.id,
         organization_id: model.organization_id,
         transaction_id: model.transaction_id,
+        ledger_entry_id: model.ledger_entry_id,
+        moved_from_transaction_id: model.moved_from_transaction_id,
         attachment_type: from_db_attachment_type(&model.attachment_type),
         filename: model.file_name,
         file_size: model.file_size,
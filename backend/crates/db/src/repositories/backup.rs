@@ -0,0 +1,1482 @@
+//! Organization backup bundles.
+//!
+//! A bundle is newline-delimited JSON. The first line is a [`BackupManifest`]
+//! with the schema version and per-section row counts; every following line
+//! is one row tagged with its [`Section`]. Sections appear in
+//! [`Section::ALL`] order, so each one only references rows already seen
+//! (parents within a section are the exception and are linked afterwards).
+//!
+//! Export reads a single repeatable-read snapshot and streams rows as they
+//! arrive. Import reads the bundle line by line, inserts rows in batches
+//! under fresh ids, and commits only once every section is loaded and every
+//! transaction balances.
+//!
+//! Cloning feeds the configuration sections of one organization through the
+//! same import path into a new organization, without writing a bundle.
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use chrono::{DateTime, Utc};
+use futures::TryStreamExt;
+use rust_decimal::Decimal;
+use sea_orm::{
+    AccessMode, ActiveModelTrait, ActiveValue, ColumnTrait, ConnectionTrait, DatabaseConnection,
+    DatabaseTransaction, DbErr, EntityTrait, IntoActiveModel, IsolationLevel, PaginatorTrait,
+    QueryFilter, QueryOrder, QuerySelect, QueryTrait, Select, TransactionTrait,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tokio::io::{AsyncBufRead, AsyncBufReadExt, AsyncWrite, AsyncWriteExt};
+use uuid::Uuid;
+use zeltra_core::settings::{OrganizationSettings, SettingsError};
+use zeltra_shared::types::OrganizationId;
+
+use crate::entities::{
+    account_default_dimensions, approval_rules, budget_line_dimensions, budget_lines, budgets,
+    chart_of_accounts, dimension_types, dimension_values, entry_dimensions, exchange_rates,
+    fiscal_periods, fiscal_years, ledger_entries, organization_users, organizations,
+    sea_orm_active_enums::TransactionStatus, transactions,
+};
+use crate::repositories::organization::insert_with_owner;
+
+/// Bundle format version written by this build and the only one it imports.
+pub const BACKUP_SCHEMA_VERSION: u32 = 1;
+
+/// Rows inserted per statement during import.
+const IMPORT_BATCH_SIZE: usize = 500;
+
+/// Error types for backup operations.
+#[derive(Debug, thiserror::Error)]
+pub enum BackupError {
+    /// Organization not found.
+    #[error("Organization not found: {0}")]
+    OrganizationNotFound(Uuid),
+
+    /// Import target already holds data.
+    #[error("Organization already has {0}; import requires an empty organization")]
+    NotEmpty(Section),
+
+    /// Bundle was written by an incompatible version.
+    #[error("Unsupported backup schema version {0}, expected {BACKUP_SCHEMA_VERSION}")]
+    UnsupportedSchema(u32),
+
+    /// Bundle base currency differs from the target organization's.
+    #[error("Backup is in {bundle}, but the organization uses {organization}")]
+    CurrencyMismatch {
+        /// Base currency recorded in the bundle.
+        bundle: String,
+        /// Base currency of the target organization.
+        organization: String,
+    },
+
+    /// Bundle is not well-formed.
+    #[error("Invalid backup at line {line}: {message}")]
+    Malformed {
+        /// 1-based line number.
+        line: u64,
+        /// What is wrong.
+        message: String,
+    },
+
+    /// A row references an id not present earlier in the bundle.
+    #[error("{section} row references unknown id {id}")]
+    DanglingReference {
+        /// Section of the referencing row.
+        section: Section,
+        /// The unknown id.
+        id: Uuid,
+    },
+
+    /// Section row count differs from the manifest.
+    #[error("{section}: manifest lists {expected} rows, bundle has {actual}")]
+    CountMismatch {
+        /// Section.
+        section: Section,
+        /// Count from the manifest.
+        expected: u64,
+        /// Rows read.
+        actual: u64,
+    },
+
+    /// A transaction's debits and credits differ.
+    #[error("Transaction {transaction} is not balanced (debit {debit}, credit {credit})")]
+    Unbalanced {
+        /// Reference number, or id if it has none.
+        transaction: String,
+        /// Total debits.
+        debit: Decimal,
+        /// Total credits.
+        credit: Decimal,
+    },
+
+    /// A section to clone depends on one that was left out.
+    #[error("Copying {section} requires copying {requires}")]
+    MissingDependency {
+        /// Section that was asked for.
+        section: Section,
+        /// Section it references.
+        requires: Section,
+    },
+
+    /// Stored organization settings could not be read.
+    #[error("Invalid organization settings: {0}")]
+    InvalidSettings(#[from] SettingsError),
+
+    /// Reading or writing the bundle failed.
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+
+    /// Database error.
+    #[error("Database error: {0}")]
+    Database(#[from] DbErr),
+}
+
+/// A group of rows in a bundle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Section {
+    /// Chart of accounts.
+    Accounts,
+    /// Dimension types.
+    DimensionTypes,
+    /// Dimension values.
+    DimensionValues,
+    /// Fiscal years.
+    FiscalYears,
+    /// Fiscal periods.
+    FiscalPeriods,
+    /// Exchange rates.
+    ExchangeRates,
+    /// Transaction headers.
+    Transactions,
+    /// Ledger entries.
+    LedgerEntries,
+    /// Dimension tags on ledger entries.
+    EntryDimensions,
+    /// Budgets.
+    Budgets,
+    /// Budget lines.
+    BudgetLines,
+    /// Dimension tags on budget lines.
+    BudgetLineDimensions,
+    /// Approval rules.
+    ApprovalRules,
+}
+
+impl Section {
+    /// Every section, in bundle order.
+    pub const ALL: [Self; 13] = [
+        Self::Accounts,
+        Self::DimensionTypes,
+        Self::DimensionValues,
+        Self::FiscalYears,
+        Self::FiscalPeriods,
+        Self::ExchangeRates,
+        Self::Transactions,
+        Self::LedgerEntries,
+        Self::EntryDimensions,
+        Self::Budgets,
+        Self::BudgetLines,
+        Self::BudgetLineDimensions,
+        Self::ApprovalRules,
+    ];
+
+    /// Name used in bundles and responses.
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Accounts => "accounts",
+            Self::DimensionTypes => "dimension_types",
+            Self::DimensionValues => "dimension_values",
+            Self::FiscalYears => "fiscal_years",
+            Self::FiscalPeriods => "fiscal_periods",
+            Self::ExchangeRates => "exchange_rates",
+            Self::Transactions => "transactions",
+            Self::LedgerEntries => "ledger_entries",
+            Self::EntryDimensions => "entry_dimensions",
+            Self::Budgets => "budgets",
+            Self::BudgetLines => "budget_lines",
+            Self::BudgetLineDimensions => "budget_line_dimensions",
+            Self::ApprovalRules => "approval_rules",
+        }
+    }
+}
+
+impl std::fmt::Display for Section {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// First line of a bundle.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BackupManifest {
+    /// Bundle format version.
+    pub schema_version: u32,
+    /// When the export was taken.
+    pub exported_at: DateTime<Utc>,
+    /// Organization the bundle was exported from.
+    pub organization_id: Uuid,
+    /// Base currency of that organization.
+    pub base_currency: String,
+    /// Rows per section.
+    pub counts: BTreeMap<Section, u64>,
+}
+
+/// Rows loaded by an import, per section.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct ImportSummary {
+    /// Rows inserted per section.
+    pub counts: BTreeMap<Section, u64>,
+}
+
+/// Parts of an organization's configuration copied by a clone.
+///
+/// Transactions, exchange rates and attachments are never copied.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[allow(clippy::struct_excessive_bools)]
+pub struct CloneSections {
+    /// Chart of accounts, with its parent chains.
+    pub accounts: bool,
+    /// Dimension types and value trees. Account default dimensions come
+    /// along when accounts are copied too.
+    pub dimensions: bool,
+    /// Fiscal years and periods.
+    pub fiscal_calendar: bool,
+    /// Approval rules.
+    pub approval_rules: bool,
+    /// Budgets with their lines; needs accounts, dimensions and the fiscal
+    /// calendar.
+    pub budgets: bool,
+    /// Organization settings. Account references are pointed at the copied
+    /// accounts, or cleared when accounts are not copied.
+    pub settings: bool,
+}
+
+impl Default for CloneSections {
+    fn default() -> Self {
+        Self {
+            accounts: true,
+            dimensions: true,
+            fiscal_calendar: true,
+            approval_rules: true,
+            budgets: true,
+            settings: true,
+        }
+    }
+}
+
+impl CloneSections {
+    /// Whether rows of a bundle section are copied.
+    #[must_use]
+    pub const fn includes(self, section: Section) -> bool {
+        match section {
+            Section::Accounts => self.accounts,
+            Section::DimensionTypes | Section::DimensionValues => self.dimensions,
+            Section::FiscalYears | Section::FiscalPeriods => self.fiscal_calendar,
+            Section::Budgets | Section::BudgetLines | Section::BudgetLineDimensions => self.budgets,
+            Section::ApprovalRules => self.approval_rules,
+            Section::ExchangeRates
+            | Section::Transactions
+            | Section::LedgerEntries
+            | Section::EntryDimensions => false,
+        }
+    }
+
+    /// Checks that every selected section's references are selected too.
+    fn validate(self) -> Result<(), BackupError> {
+        if !self.budgets {
+            return Ok(());
+        }
+        for requires in [
+            Section::Accounts,
+            Section::DimensionValues,
+            Section::FiscalPeriods,
+        ] {
+            if !self.includes(requires) {
+                return Err(BackupError::MissingDependency {
+                    section: Section::Budgets,
+                    requires,
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Input for cloning an organization.
+#[derive(Debug, Clone)]
+pub struct CloneOrganizationInput {
+    /// Name of the new organization.
+    pub name: String,
+    /// Slug of the new organization.
+    pub slug: String,
+    /// What to copy.
+    pub sections: CloneSections,
+}
+
+/// An organization created by a clone.
+#[derive(Debug, Clone)]
+pub struct CloneSummary {
+    /// The new organization.
+    pub organization: organizations::Model,
+    /// Rows copied per table.
+    pub counts: BTreeMap<&'static str, u64>,
+}
+
+/// A bundle line as written.
+#[derive(Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+enum LineOut<'a, T> {
+    Manifest(&'a BackupManifest),
+    Row { section: Section, data: &'a T },
+}
+
+/// A bundle line as read.
+#[derive(Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+enum LineIn {
+    Manifest(BackupManifest),
+    Row { section: Section, data: Value },
+}
+
+/// Repository for exporting and importing organization bundles.
+#[derive(Debug, Clone)]
+pub struct BackupRepository {
+    db: DatabaseConnection,
+}
+
+impl BackupRepository {
+    /// Creates a new backup repository.
+    #[must_use]
+    pub const fn new(db: DatabaseConnection) -> Self {
+        Self { db }
+    }
+
+    /// Writes a bundle of the organization's data to `out`.
+    ///
+    /// Rows are streamed from the database, so memory use does not grow with
+    /// the size of the organization.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the organization does not exist, a query fails, or
+    /// writing to `out` fails.
+    pub async fn export<W>(
+        &self,
+        organization_id: OrganizationId,
+        out: W,
+    ) -> Result<BackupManifest, BackupError>
+    where
+        W: AsyncWrite + Unpin + Send,
+    {
+        let org_id = organization_id.into_inner();
+        let txn = self
+            .db
+            .begin_with_config(
+                Some(IsolationLevel::RepeatableRead),
+                Some(AccessMode::ReadOnly),
+            )
+            .await?;
+
+        let org = organizations::Entity::find_by_id(org_id)
+            .one(&txn)
+            .await?
+            .ok_or(BackupError::OrganizationNotFound(org_id))?;
+
+        let mut counts = BTreeMap::new();
+        for section in Section::ALL {
+            counts.insert(section, count_section(&txn, section, org_id).await?);
+        }
+
+        let manifest = BackupManifest {
+            schema_version: BACKUP_SCHEMA_VERSION,
+            exported_at: Utc::now(),
+            organization_id: org_id,
+            base_currency: org.base_currency,
+            counts,
+        };
+
+        let mut out = tokio::io::BufWriter::new(out);
+        write_line(&mut out, &LineOut::<()>::Manifest(&manifest)).await?;
+
+        for section in Section::ALL {
+            export_section(&txn, &mut out, section, org_id).await?;
+        }
+
+        out.flush().await?;
+        txn.commit().await?;
+
+        Ok(manifest)
+    }
+
+    /// Loads a bundle into an empty organization.
+    ///
+    /// Every row gets a new id; references are rewritten through an old to
+    /// new id map. Reference numbers are kept. Users who are not members of
+    /// the target organization are replaced by `imported_by`. Nothing is
+    /// committed unless the whole bundle loads.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the organization is not empty, the bundle is
+    /// malformed or inconsistent with its manifest, a transaction does not
+    /// balance, or a query fails.
+    pub async fn import<R>(
+        &self,
+        organization_id: OrganizationId,
+        imported_by: Uuid,
+        input: R,
+    ) -> Result<ImportSummary, BackupError>
+    where
+        R: AsyncBufRead + Unpin + Send,
+    {
+        let org_id = organization_id.into_inner();
+        let mut lines = input.lines();
+        let mut line_no = 0u64;
+
+        let manifest = loop {
+            line_no += 1;
+            let Some(line) = lines.next_line().await? else {
+                return Err(malformed(line_no, "bundle is empty"));
+            };
+            if line.trim().is_empty() {
+                continue;
+            }
+            match serde_json::from_str(&line) {
+                Ok(LineIn::Manifest(manifest)) => break manifest,
+                Ok(LineIn::Row { .. }) => {
+                    return Err(malformed(line_no, "first line must be the manifest"));
+                }
+                Err(e) => return Err(malformed(line_no, e)),
+            }
+        };
+        if manifest.schema_version != BACKUP_SCHEMA_VERSION {
+            return Err(BackupError::UnsupportedSchema(manifest.schema_version));
+        }
+
+        let txn = self.db.begin().await?;
+
+        let org = organizations::Entity::find_by_id(org_id)
+            .lock_exclusive()
+            .one(&txn)
+            .await?
+            .ok_or(BackupError::OrganizationNotFound(org_id))?;
+        if org.base_currency != manifest.base_currency {
+            return Err(BackupError::CurrencyMismatch {
+                bundle: manifest.base_currency,
+                organization: org.base_currency,
+            });
+        }
+        for section in Section::ALL {
+            if count_section(&txn, section, org_id).await? > 0 {
+                return Err(BackupError::NotEmpty(section));
+            }
+        }
+
+        let members: HashSet<Uuid> = organization_users::Entity::find()
+            .select_only()
+            .column(organization_users::Column::UserId)
+            .filter(organization_users::Column::OrganizationId.eq(org_id))
+            .into_tuple()
+            .all(&txn)
+            .await?
+            .into_iter()
+            .collect();
+
+        let mut importer = Importer::new(org_id, imported_by, members);
+
+        while let Some(line) = lines.next_line().await? {
+            line_no += 1;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let (section, data) = match serde_json::from_str(&line) {
+                Ok(LineIn::Row { section, data }) => (section, data),
+                Ok(LineIn::Manifest(_)) => {
+                    return Err(malformed(line_no, "unexpected second manifest"));
+                }
+                Err(e) => return Err(malformed(line_no, e)),
+            };
+            importer.row(&txn, line_no, section, data).await?;
+        }
+
+        let summary = importer.finish(&txn).await?;
+
+        for section in Section::ALL {
+            let expected = manifest.counts.get(&section).copied().unwrap_or(0);
+            let actual = summary.counts.get(&section).copied().unwrap_or(0);
+            if expected != actual {
+                return Err(BackupError::CountMismatch {
+                    section,
+                    expected,
+                    actual,
+                });
+            }
+        }
+
+        txn.commit().await?;
+
+        Ok(summary)
+    }
+
+    /// Creates a new organization with the configuration of an existing one.
+    ///
+    /// The selected sections go through the import path, so every row gets
+    /// a new id and account parents, dimension value trees and budget line
+    /// references are rewritten through one old to new id map. `cloned_by`
+    /// becomes the only member, as owner, and stands in for every other user
+    /// the copied rows name. The new organization keeps the source's base
+    /// currency, timezone and plan, without its payment details. Nothing is
+    /// committed unless the whole copy succeeds.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if budgets are selected without the sections they
+    /// reference, the source organization does not exist, its settings
+    /// cannot be read, or a query fails, including on a taken slug.
+    pub async fn clone_organization(
+        &self,
+        source_id: OrganizationId,
+        cloned_by: Uuid,
+        input: CloneOrganizationInput,
+    ) -> Result<CloneSummary, BackupError> {
+        let sections = input.sections;
+        sections.validate()?;
+
+        let source_id = source_id.into_inner();
+        let txn = self
+            .db
+            .begin_with_config(Some(IsolationLevel::RepeatableRead), None)
+            .await?;
+
+        let source = organizations::Entity::find_by_id(source_id)
+            .one(&txn)
+            .await?
+            .ok_or(BackupError::OrganizationNotFound(source_id))?;
+        let org = insert_with_owner(
+            &txn,
+            &input.name,
+            &input.slug,
+            &source.base_currency,
+            &source.timezone,
+            cloned_by,
+        )
+        .await?;
+
+        let mut importer = Importer::new(org.id, cloned_by, HashSet::from([cloned_by]));
+        let mut row = 0u64;
+        for section in Section::ALL {
+            if sections.includes(section) {
+                copy_section(&txn, &mut importer, section, source_id, &mut row).await?;
+            }
+        }
+        let loaded = importer.finish(&txn).await?;
+
+        let mut counts: BTreeMap<&'static str, u64> = loaded
+            .counts
+            .into_iter()
+            .filter(|(section, _)| sections.includes(*section))
+            .map(|(section, count)| (section.as_str(), count))
+            .collect();
+
+        if sections.accounts && sections.dimensions {
+            let defaults = account_default_dimensions::Entity::find()
+                .filter(account_default_dimensions::Column::OrganizationId.eq(source_id))
+                .order_by_asc(account_default_dimensions::Column::Id)
+                .all(&txn)
+                .await?;
+            counts.insert("account_default_dimensions", defaults.len() as u64);
+            let mut rows = Vec::with_capacity(defaults.len());
+            for default in defaults {
+                rows.push(
+                    account_default_dimensions::Model {
+                        id: Uuid::new_v4(),
+                        organization_id: org.id,
+                        account_id: importer.resolve(Section::Accounts, default.account_id)?,
+                        dimension_value_id: importer
+                            .resolve(Section::DimensionValues, default.dimension_value_id)?,
+                        ..default
+                    }
+                    .into_active_model(),
+                );
+            }
+            insert_batch(&txn, &mut rows).await?;
+        }
+
+        let settings = if sections.settings {
+            let mut settings = OrganizationSettings::from_value(&source.settings)?;
+            importer.remap_settings(&mut settings);
+            ActiveValue::Set(settings.to_value())
+        } else {
+            ActiveValue::NotSet
+        };
+        let organization = organizations::ActiveModel {
+            id: ActiveValue::Unchanged(org.id),
+            settings,
+            subscription_tier: ActiveValue::Set(source.subscription_tier),
+            subscription_status: ActiveValue::Set(source.subscription_status),
+            trial_ends_at: ActiveValue::Set(source.trial_ends_at),
+            subscription_ends_at: ActiveValue::Set(source.subscription_ends_at),
+            ..Default::default()
+        }
+        .update(&txn)
+        .await?;
+
+        txn.commit().await?;
+
+        Ok(CloneSummary {
+            organization,
+            counts,
+        })
+    }
+}
+
+fn malformed(line: u64, message: impl std::fmt::Display) -> BackupError {
+    BackupError::Malformed {
+        line,
+        message: message.to_string(),
+    }
+}
+
+async fn write_line<W, T>(out: &mut W, line: &LineOut<'_, T>) -> Result<(), BackupError>
+where
+    W: AsyncWrite + Unpin,
+    T: Serialize,
+{
+    let mut bytes = serde_json::to_vec(line).map_err(std::io::Error::other)?;
+    bytes.push(b'\n');
+    out.write_all(&bytes).await?;
+    Ok(())
+}
+
+// ============================================================================
+// Section queries
+// ============================================================================
+
+/// Ids of the organization's transactions, as a subquery.
+fn transaction_ids(org_id: Uuid) -> sea_orm::sea_query::SelectStatement {
+    transactions::Entity::find()
+        .select_only()
+        .column(transactions::Column::Id)
+        .filter(transactions::Column::OrganizationId.eq(org_id))
+        .into_query()
+}
+
+/// Ids of the organization's ledger entries, as a subquery.
+fn ledger_entry_ids(org_id: Uuid) -> sea_orm::sea_query::SelectStatement {
+    ledger_entries::Entity::find()
+        .select_only()
+        .column(ledger_entries::Column::Id)
+        .filter(ledger_entries::Column::TransactionId.in_subquery(transaction_ids(org_id)))
+        .into_query()
+}
+
+/// Ids of the organization's budgets, as a subquery.
+fn budget_ids(org_id: Uuid) -> sea_orm::sea_query::SelectStatement {
+    budgets::Entity::find()
+        .select_only()
+        .column(budgets::Column::Id)
+        .filter(budgets::Column::OrganizationId.eq(org_id))
+        .into_query()
+}
+
+/// Ids of the organization's budget lines, as a subquery.
+fn budget_line_ids(org_id: Uuid) -> sea_orm::sea_query::SelectStatement {
+    budget_lines::Entity::find()
+        .select_only()
+        .column(budget_lines::Column::Id)
+        .filter(budget_lines::Column::BudgetId.in_subquery(budget_ids(org_id)))
+        .into_query()
+}
+
+fn accounts_query(org_id: Uuid) -> Select<chart_of_accounts::Entity> {
+    chart_of_accounts::Entity::find()
+        .filter(chart_of_accounts::Column::OrganizationId.eq(org_id))
+        .order_by_asc(chart_of_accounts::Column::Code)
+}
+
+fn dimension_types_query(org_id: Uuid) -> Select<dimension_types::Entity> {
+    dimension_types::Entity::find()
+        .filter(dimension_types::Column::OrganizationId.eq(org_id))
+        .order_by_asc(dimension_types::Column::SortOrder)
+        .order_by_asc(dimension_types::Column::Code)
+}
+
+fn dimension_values_query(org_id: Uuid) -> Select<dimension_values::Entity> {
+    dimension_values::Entity::find()
+        .filter(dimension_values::Column::OrganizationId.eq(org_id))
+        .order_by_asc(dimension_values::Column::DimensionTypeId)
+        .order_by_asc(dimension_values::Column::Code)
+}
+
+fn fiscal_years_query(org_id: Uuid) -> Select<fiscal_years::Entity> {
+    fiscal_years::Entity::find()
+        .filter(fiscal_years::Column::OrganizationId.eq(org_id))
+        .order_by_asc(fiscal_years::Column::StartDate)
+}
+
+fn fiscal_periods_query(org_id: Uuid) -> Select<fiscal_periods::Entity> {
+    fiscal_periods::Entity::find()
+        .filter(fiscal_periods::Column::OrganizationId.eq(org_id))
+        .order_by_asc(fiscal_periods::Column::StartDate)
+        .order_by_asc(fiscal_periods::Column::PeriodNumber)
+}
+
+fn exchange_rates_query(org_id: Uuid) -> Select<exchange_rates::Entity> {
+    exchange_rates::Entity::find()
+        .filter(exchange_rates::Column::OrganizationId.eq(org_id))
+        .order_by_asc(exchange_rates::Column::EffectiveDate)
+        .order_by_asc(exchange_rates::Column::Id)
+}
+
+/// Creation order, so a reversal always follows the transaction it reverses.
+fn transactions_query(org_id: Uuid) -> Select<transactions::Entity> {
+    transactions::Entity::find()
+        .filter(transactions::Column::OrganizationId.eq(org_id))
+        .order_by_asc(transactions::Column::CreatedAt)
+        .order_by_asc(transactions::Column::Id)
+}
+
+/// Version order per account, so import rebuilds the same running balances.
+fn ledger_entries_query(org_id: Uuid) -> Select<ledger_entries::Entity> {
+    ledger_entries::Entity::find()
+        .filter(ledger_entries::Column::TransactionId.in_subquery(transaction_ids(org_id)))
+        .order_by_asc(ledger_entries::Column::AccountId)
+        .order_by_asc(ledger_entries::Column::AccountVersion)
+}
+
+fn entry_dimensions_query(org_id: Uuid) -> Select<entry_dimensions::Entity> {
+    entry_dimensions::Entity::find()
+        .filter(entry_dimensions::Column::LedgerEntryId.in_subquery(ledger_entry_ids(org_id)))
+        .order_by_asc(entry_dimensions::Column::Id)
+}
+
+fn budgets_query(org_id: Uuid) -> Select<budgets::Entity> {
+    budgets::Entity::find()
+        .filter(budgets::Column::OrganizationId.eq(org_id))
+        .order_by_asc(budgets::Column::CreatedAt)
+        .order_by_asc(budgets::Column::Id)
+}
+
+fn budget_lines_query(org_id: Uuid) -> Select<budget_lines::Entity> {
+    budget_lines::Entity::find()
+        .filter(budget_lines::Column::BudgetId.in_subquery(budget_ids(org_id)))
+        .order_by_asc(budget_lines::Column::Id)
+}
+
+fn budget_line_dimensions_query(org_id: Uuid) -> Select<budget_line_dimensions::Entity> {
+    budget_line_dimensions::Entity::find()
+        .filter(budget_line_dimensions::Column::BudgetLineId.in_subquery(budget_line_ids(org_id)))
+        .order_by_asc(budget_line_dimensions::Column::Id)
+}
+
+fn approval_rules_query(org_id: Uuid) -> Select<approval_rules::Entity> {
+    approval_rules::Entity::find()
+        .filter(approval_rules::Column::OrganizationId.eq(org_id))
+        .order_by_asc(approval_rules::Column::Priority)
+        .order_by_asc(approval_rules::Column::Id)
+}
+
+async fn count_section<C>(db: &C, section: Section, org_id: Uuid) -> Result<u64, DbErr>
+where
+    C: ConnectionTrait,
+{
+    match section {
+        Section::Accounts => accounts_query(org_id).count(db).await,
+        Section::DimensionTypes => dimension_types_query(org_id).count(db).await,
+        Section::DimensionValues => dimension_values_query(org_id).count(db).await,
+        Section::FiscalYears => fiscal_years_query(org_id).count(db).await,
+        Section::FiscalPeriods => fiscal_periods_query(org_id).count(db).await,
+        Section::ExchangeRates => exchange_rates_query(org_id).count(db).await,
+        Section::Transactions => transactions_query(org_id).count(db).await,
+        Section::LedgerEntries => ledger_entries_query(org_id).count(db).await,
+        Section::EntryDimensions => entry_dimensions_query(org_id).count(db).await,
+        Section::Budgets => budgets_query(org_id).count(db).await,
+        Section::BudgetLines => budget_lines_query(org_id).count(db).await,
+        Section::BudgetLineDimensions => budget_line_dimensions_query(org_id).count(db).await,
+        Section::ApprovalRules => approval_rules_query(org_id).count(db).await,
+    }
+}
+
+async fn export_section<W>(
+    txn: &DatabaseTransaction,
+    out: &mut W,
+    section: Section,
+    org_id: Uuid,
+) -> Result<(), BackupError>
+where
+    W: AsyncWrite + Unpin + Send,
+{
+    match section {
+        Section::Accounts => stream_rows(txn, out, section, accounts_query(org_id)).await,
+        Section::DimensionTypes => {
+            stream_rows(txn, out, section, dimension_types_query(org_id)).await
+        }
+        Section::DimensionValues => {
+            stream_rows(txn, out, section, dimension_values_query(org_id)).await
+        }
+        Section::FiscalYears => stream_rows(txn, out, section, fiscal_years_query(org_id)).await,
+        Section::FiscalPeriods => {
+            stream_rows(txn, out, section, fiscal_periods_query(org_id)).await
+        }
+        Section::ExchangeRates => {
+            stream_rows(txn, out, section, exchange_rates_query(org_id)).await
+        }
+        Section::Transactions => stream_rows(txn, out, section, transactions_query(org_id)).await,
+        Section::LedgerEntries => {
+            stream_rows(txn, out, section, ledger_entries_query(org_id)).await
+        }
+        Section::EntryDimensions => {
+            stream_rows(txn, out, section, entry_dimensions_query(org_id)).await
+        }
+        Section::Budgets => stream_rows(txn, out, section, budgets_query(org_id)).await,
+        Section::BudgetLines => stream_rows(txn, out, section, budget_lines_query(org_id)).await,
+        Section::BudgetLineDimensions => {
+            stream_rows(txn, out, section, budget_line_dimensions_query(org_id)).await
+        }
+        Section::ApprovalRules => {
+            stream_rows(txn, out, section, approval_rules_query(org_id)).await
+        }
+    }
+}
+
+/// Writes every row of `query` as it arrives from the database.
+async fn stream_rows<E, W>(
+    txn: &DatabaseTransaction,
+    out: &mut W,
+    section: Section,
+    query: Select<E>,
+) -> Result<(), BackupError>
+where
+    E: EntityTrait,
+    E::Model: Serialize + Send + Sync,
+    W: AsyncWrite + Unpin + Send,
+{
+    let mut rows = query.stream(txn).await?;
+    while let Some(row) = rows.try_next().await? {
+        write_line(
+            out,
+            &LineOut::Row {
+                section,
+                data: &row,
+            },
+        )
+        .await?;
+    }
+    Ok(())
+}
+
+/// Feeds every row of a section of `source_id` to the importer.
+async fn copy_section(
+    txn: &DatabaseTransaction,
+    importer: &mut Importer,
+    section: Section,
+    source_id: Uuid,
+    row: &mut u64,
+) -> Result<(), BackupError> {
+    match section {
+        Section::Accounts => {
+            copy_rows(txn, importer, section, accounts_query(source_id), row).await
+        }
+        Section::DimensionTypes => {
+            copy_rows(
+                txn,
+                importer,
+                section,
+                dimension_types_query(source_id),
+                row,
+            )
+            .await
+        }
+        Section::DimensionValues => {
+            copy_rows(
+                txn,
+                importer,
+                section,
+                dimension_values_query(source_id),
+                row,
+            )
+            .await
+        }
+        Section::FiscalYears => {
+            copy_rows(txn, importer, section, fiscal_years_query(source_id), row).await
+        }
+        Section::FiscalPeriods => {
+            copy_rows(txn, importer, section, fiscal_periods_query(source_id), row).await
+        }
+        Section::ExchangeRates => {
+            copy_rows(txn, importer, section, exchange_rates_query(source_id), row).await
+        }
+        Section::Transactions => {
+            copy_rows(txn, importer, section, transactions_query(source_id), row).await
+        }
+        Section::LedgerEntries => {
+            copy_rows(txn, importer, section, ledger_entries_query(source_id), row).await
+        }
+        Section::EntryDimensions => {
+            copy_rows(
+                txn,
+                importer,
+                section,
+                entry_dimensions_query(source_id),
+                row,
+            )
+            .await
+        }
+        Section::Budgets => copy_rows(txn, importer, section, budgets_query(source_id), row).await,
+        Section::BudgetLines => {
+            copy_rows(txn, importer, section, budget_lines_query(source_id), row).await
+        }
+        Section::BudgetLineDimensions => {
+            copy_rows(
+                txn,
+                importer,
+                section,
+                budget_line_dimensions_query(source_id),
+                row,
+            )
+            .await
+        }
+        Section::ApprovalRules => {
+            copy_rows(txn, importer, section, approval_rules_query(source_id), row).await
+        }
+    }
+}
+
+/// Loads `query` and passes each row to the importer as a bundle row would be.
+///
+/// Rows are read in full first; the transaction's connection cannot stream
+/// and insert at the same time.
+async fn copy_rows<E>(
+    txn: &DatabaseTransaction,
+    importer: &mut Importer,
+    section: Section,
+    query: Select<E>,
+    row: &mut u64,
+) -> Result<(), BackupError>
+where
+    E: EntityTrait,
+    E::Model: Serialize + Send + Sync,
+{
+    for model in query.all(txn).await? {
+        *row += 1;
+        let data = serde_json::to_value(&model).map_err(|e| malformed(*row, e))?;
+        importer.row(txn, *row, section, data).await?;
+    }
+    Ok(())
+}
+
+// ============================================================================
+// Import
+// ============================================================================
+
+/// A reference within a section that is linked once the section is loaded.
+struct Link {
+    id: Uuid,
+    target: Uuid,
+}
+
+/// A transaction whose reversal links are set once all transactions exist.
+struct ReversalLink {
+    id: Uuid,
+    reversed_by: Uuid,
+    status: TransactionStatus,
+}
+
+/// Import state: pending batches, the id map, and deferred links.
+struct Importer {
+    org_id: Uuid,
+    imported_by: Uuid,
+    members: HashSet<Uuid>,
+    ids: HashMap<Uuid, Uuid>,
+    current: Option<Section>,
+    counts: BTreeMap<Section, u64>,
+    parents: Vec<Link>,
+    reversals: Vec<ReversalLink>,
+    /// Debit and credit totals per imported transaction, with its label.
+    totals: HashMap<Uuid, (String, Decimal, Decimal)>,
+
+    accounts: Vec<chart_of_accounts::ActiveModel>,
+    dimension_types: Vec<dimension_types::ActiveModel>,
+    dimension_values: Vec<dimension_values::ActiveModel>,
+    fiscal_years: Vec<fiscal_years::ActiveModel>,
+    fiscal_periods: Vec<fiscal_periods::ActiveModel>,
+    exchange_rates: Vec<exchange_rates::ActiveModel>,
+    transactions: Vec<transactions::ActiveModel>,
+    ledger_entries: Vec<ledger_entries::ActiveModel>,
+    entry_dimensions: Vec<entry_dimensions::ActiveModel>,
+    budgets: Vec<budgets::ActiveModel>,
+    budget_lines: Vec<budget_lines::ActiveModel>,
+    budget_line_dimensions: Vec<budget_line_dimensions::ActiveModel>,
+    approval_rules: Vec<approval_rules::ActiveModel>,
+}
+
+impl Importer {
+    fn new(org_id: Uuid, imported_by: Uuid, members: HashSet<Uuid>) -> Self {
+        Self {
+            org_id,
+            imported_by,
+            members,
+            ids: HashMap::new(),
+            current: None,
+            counts: Section::ALL.into_iter().map(|s| (s, 0)).collect(),
+            parents: Vec::new(),
+            reversals: Vec::new(),
+            totals: HashMap::new(),
+            accounts: Vec::new(),
+            dimension_types: Vec::new(),
+            dimension_values: Vec::new(),
+            fiscal_years: Vec::new(),
+            fiscal_periods: Vec::new(),
+            exchange_rates: Vec::new(),
+            transactions: Vec::new(),
+            ledger_entries: Vec::new(),
+            entry_dimensions: Vec::new(),
+            budgets: Vec::new(),
+            budget_lines: Vec::new(),
+            budget_line_dimensions: Vec::new(),
+            approval_rules: Vec::new(),
+        }
+    }
+
+    /// Assigns a new id to a row.
+    fn assign(&mut self, line: u64, old: Uuid) -> Result<Uuid, BackupError> {
+        let new = Uuid::new_v4();
+        if self.ids.insert(old, new).is_some() {
+            return Err(malformed(line, format!("duplicate id {old}")));
+        }
+        Ok(new)
+    }
+
+    /// Looks up the new id for a row imported earlier.
+    fn resolve(&self, section: Section, old: Uuid) -> Result<Uuid, BackupError> {
+        self.ids
+            .get(&old)
+            .copied()
+            .ok_or(BackupError::DanglingReference { section, id: old })
+    }
+
+    /// Points account references at the imported accounts, clearing any
+    /// that were not imported.
+    fn remap_settings(&self, settings: &mut OrganizationSettings) {
+        for account_id in [
+            &mut settings.bank_import.suspense_account_id,
+            &mut settings.fx_revaluation.gain_account_id,
+            &mut settings.fx_revaluation.loss_account_id,
+            &mut settings.currency_balance.fx_clearing_account_id,
+        ] {
+            *account_id = account_id.and_then(|old| self.ids.get(&old).copied());
+        }
+    }
+
+    /// Keeps members of the target organization; anyone else becomes the importer.
+    fn user(&self, old: Uuid) -> Uuid {
+        if self.members.contains(&old) {
+            old
+        } else {
+            self.imported_by
+        }
+    }
+
+    #[allow(clippy::too_many_lines)]
+    async fn row(
+        &mut self,
+        txn: &DatabaseTransaction,
+        line: u64,
+        section: Section,
+        data: Value,
+    ) -> Result<(), BackupError> {
+        if self.current != Some(section) {
+            if self.current.is_some_and(|current| current > section) {
+                return Err(malformed(line, format!("{section} rows out of order")));
+            }
+            if let Some(previous) = self.current {
+                self.finish_section(txn, previous).await?;
+            }
+            self.current = Some(section);
+        }
+
+        let parse_error = |e: serde_json::Error| malformed(line, format!("{section}: {e}"));
+        match section {
+            Section::Accounts => {
+                let row: chart_of_accounts::Model =
+                    serde_json::from_value(data).map_err(parse_error)?;
+                let id = self.assign(line, row.id)?;
+                if let Some(parent) = row.parent_id {
+                    self.parents.push(Link { id, target: parent });
+                }
+                self.accounts.push(
+                    chart_of_accounts::Model {
+                        id,
+                        organization_id: self.org_id,
+                        parent_id: None,
+                        ..row
+                    }
+                    .into_active_model(),
+                );
+            }
+            Section::DimensionTypes => {
+                let row: dimension_types::Model =
+                    serde_json::from_value(data).map_err(parse_error)?;
+                let id = self.assign(line, row.id)?;
+                self.dimension_types.push(
+                    dimension_types::Model {
+                        id,
+                        organization_id: self.org_id,
+                        ..row
+                    }
+                    .into_active_model(),
+                );
+            }
+            Section::DimensionValues => {
+                let row: dimension_values::Model =
+                    serde_json::from_value(data).map_err(parse_error)?;
+                let dimension_type_id = self.resolve(section, row.dimension_type_id)?;
+                let id = self.assign(line, row.id)?;
+                if let Some(parent) = row.parent_id {
+                    self.parents.push(Link { id, target: parent });
+                }
+                self.dimension_values.push(
+                    dimension_values::Model {
+                        id,
+                        organization_id: self.org_id,
+                        dimension_type_id,
+                        parent_id: None,
+                        ..row
+                    }
+                    .into_active_model(),
+                );
+            }
+            Section::FiscalYears => {
+                let row: fiscal_years::Model = serde_json::from_value(data).map_err(parse_error)?;
+                let id = self.assign(line, row.id)?;
+                let closed_by = row.closed_by.map(|u| self.user(u));
+                self.fiscal_years.push(
+                    fiscal_years::Model {
+                        id,
+                        organization_id: self.org_id,
+                        closed_by,
+                        ..row
+                    }
+                    .into_active_model(),
+                );
+            }
+            Section::FiscalPeriods => {
+                let row: fiscal_periods::Model =
+                    serde_json::from_value(data).map_err(parse_error)?;
+                let fiscal_year_id = self.resolve(section, row.fiscal_year_id)?;
+                let id = self.assign(line, row.id)?;
+                let closed_by = row.closed_by.map(|u| self.user(u));
+                self.fiscal_periods.push(
+                    fiscal_periods::Model {
+                        id,
+                        organization_id: self.org_id,
+                        fiscal_year_id,
+                        closed_by,
+                        ..row
+                    }
+                    .into_active_model(),
+                );
+            }
+            Section::ExchangeRates => {
+                let row: exchange_rates::Model =
+                    serde_json::from_value(data).map_err(parse_error)?;
+                let id = self.assign(line, row.id)?;
+                let created_by = row.created_by.map(|u| self.user(u));
+                self.exchange_rates.push(
+                    exchange_rates::Model {
+                        id,
+                        organization_id: self.org_id,
+                        created_by,
+                        ..row
+                    }
+                    .into_active_model(),
+                );
+            }
+            Section::Transactions => self.transaction_row(line, data)?,
+            Section::LedgerEntries => {
+                let row: ledger_entries::Model =
+                    serde_json::from_value(data).map_err(parse_error)?;
+                let transaction_id = self.resolve(section, row.transaction_id)?;
+                let account_id = self.resolve(section, row.account_id)?;
+                let id = self.assign(line, row.id)?;
+                if let Some((_, debit, credit)) = self.totals.get_mut(&transaction_id) {
+                    *debit += row.debit;
+                    *credit += row.credit;
+                }
+                // Versions and running balances are recomputed by the insert trigger
+                self.ledger_entries.push(
+                    ledger_entries::Model {
+                        id,
+                        transaction_id,
+                        account_id,
+                        ..row
+                    }
+                    .into_active_model(),
+                );
+            }
+            Section::EntryDimensions => {
+                let row: entry_dimensions::Model =
+                    serde_json::from_value(data).map_err(parse_error)?;
+                let ledger_entry_id = self.resolve(section, row.ledger_entry_id)?;
+                let dimension_value_id = self.resolve(section, row.dimension_value_id)?;
+                let id = self.assign(line, row.id)?;
+                self.entry_dimensions.push(
+                    entry_dimensions::Model {
+                        id,
+                        ledger_entry_id,
+                        dimension_value_id,
+                        ..row
+                    }
+                    .into_active_model(),
+                );
+            }
+            Section::Budgets => {
+                let row: budgets::Model = serde_json::from_value(data).map_err(parse_error)?;
+                let fiscal_year_id = self.resolve(section, row.fiscal_year_id)?;
+                let id = self.assign(line, row.id)?;
+                let created_by = self.user(row.created_by);
+                let owner_user_id = row.owner_user_id.filter(|u| self.members.contains(u));
+                self.budgets.push(
+                    budgets::Model {
+                        id,
+                        organization_id: self.org_id,
+                        fiscal_year_id,
+                        created_by,
+                        owner_user_id,
+                        ..row
+                    }
+                    .into_active_model(),
+                );
+            }
+            Section::BudgetLines => {
+                let row: budget_lines::Model = serde_json::from_value(data).map_err(parse_error)?;
+                let budget_id = self.resolve(section, row.budget_id)?;
+                let account_id = self.resolve(section, row.account_id)?;
+                let fiscal_period_id = self.resolve(section, row.fiscal_period_id)?;
+                let id = self.assign(line, row.id)?;
+                self.budget_lines.push(
+                    budget_lines::Model {
+                        id,
+                        budget_id,
+                        account_id,
+                        fiscal_period_id,
+                        ..row
+                    }
+                    .into_active_model(),
+                );
+            }
+            Section::BudgetLineDimensions => {
+                let row: budget_line_dimensions::Model =
+                    serde_json::from_value(data).map_err(parse_error)?;
+                let budget_line_id = self.resolve(section, row.budget_line_id)?;
+                let dimension_value_id = self.resolve(section, row.dimension_value_id)?;
+                let id = self.assign(line, row.id)?;
+                self.budget_line_dimensions.push(
+                    budget_line_dimensions::Model {
+                        id,
+                        budget_line_id,
+                        dimension_value_id,
+                        ..row
+                    }
+                    .into_active_model(),
+                );
+            }
+            Section::ApprovalRules => {
+                let row: approval_rules::Model =
+                    serde_json::from_value(data).map_err(parse_error)?;
+                let id = self.assign(line, row.id)?;
+                self.approval_rules.push(
+                    approval_rules::Model {
+                        id,
+                        organization_id: self.org_id,
+                        ..row
+                    }
+                    .into_active_model(),
+                );
+            }
+        }
+
+        *self.counts.entry(section).or_default() += 1;
+        if self.pending(section) >= IMPORT_BATCH_SIZE {
+            self.flush(txn, section).await?;
+        }
+        Ok(())
+    }
+
+    fn transaction_row(&mut self, line: u64, data: Value) -> Result<(), BackupError> {
+        let section = Section::Transactions;
+        let row: transactions::Model =
+            serde_json::from_value(data).map_err(|e| malformed(line, format!("{section}: {e}")))?;
+        let fiscal_period_id = self.resolve(section, row.fiscal_period_id)?;
+        // Reversals follow the transaction they reverse, so that id is known
+        let reverses_transaction_id = row
+            .reverses_transaction_id
+            .map(|old| self.resolve(section, old))
+            .transpose()?;
+        let id = self.assign(line, row.id)?;
+
+        // Voided transactions point at a reversal that is not loaded yet.
+        // They go in as posted and are voided once the reversal exists,
+        // which is the one change the posted-transaction trigger allows.
+        let mut status = row.status.clone();
+        if let Some(reversed_by) = row.reversed_by_transaction_id {
+            self.reversals.push(ReversalLink {
+                id,
+                reversed_by,
+                status: row.status.clone(),
+            });
+            if status == TransactionStatus::Voided {
+                status = TransactionStatus::Posted;
+            }
+        }
+
+        let label = row
+            .reference_number
+            .clone()
+            .unwrap_or_else(|| row.id.to_string());
+        self.totals
+            .insert(id, (label, Decimal::ZERO, Decimal::ZERO));
+
+        self.transactions.push(
+            transactions::Model {
+                id,
+                organization_id: self.org_id,
+                fiscal_period_id,
+                status,
+                created_by: self.user(row.created_by),
+                submitted_by: row.submitted_by.map(|u| self.user(u)),
+                approved_by: row.approved_by.map(|u| self.user(u)),
+                approved_on_behalf_of: row.approved_on_behalf_of.map(|u| self.user(u)),
+                posted_by: row.posted_by.map(|u| self.user(u)),
+                voided_by: row.voided_by.map(|u| self.user(u)),
+                reversed_by_transaction_id: None,
+                reverses_transaction_id,
+                ..row
+            }
+            .into_active_model(),
+        );
+        Ok(())
+    }
+
+    fn pending(&self, section: Section) -> usize {
+        match section {
+            Section::Accounts => self.accounts.len(),
+            Section::DimensionTypes => self.dimension_types.len(),
+            Section::DimensionValues => self.dimension_values.len(),
+            Section::FiscalYears => self.fiscal_years.len(),
+            Section::FiscalPeriods => self.fiscal_periods.len(),
+            Section::ExchangeRates => self.exchange_rates.len(),
+            Section::Transactions => self.transactions.len(),
+            Section::LedgerEntries => self.ledger_entries.len(),
+            Section::EntryDimensions => self.entry_dimensions.len(),
+            Section::Budgets => self.budgets.len(),
+            Section::BudgetLines => self.budget_lines.len(),
+            Section::BudgetLineDimensions => self.budget_line_dimensions.len(),
+            Section::ApprovalRules => self.approval_rules.len(),
+        }
+    }
+
+    async fn flush(&mut self, txn: &DatabaseTransaction, section: Section) -> Result<(), DbErr> {
+        match section {
+            Section::Accounts => insert_batch(txn, &mut self.accounts).await,
+            Section::DimensionTypes => insert_batch(txn, &mut self.dimension_types).await,
+            Section::DimensionValues => insert_batch(txn, &mut self.dimension_values).await,
+            Section::FiscalYears => insert_batch(txn, &mut self.fiscal_years).await,
+            Section::FiscalPeriods => insert_batch(txn, &mut self.fiscal_periods).await,
+            Section::ExchangeRates => insert_batch(txn, &mut self.exchange_rates).await,
+            Section::Transactions => insert_batch(txn, &mut self.transactions).await,
+            Section::LedgerEntries => insert_batch(txn, &mut self.ledger_entries).await,
+            Section::EntryDimensions => insert_batch(txn, &mut self.entry_dimensions).await,
+            Section::Budgets => insert_batch(txn, &mut self.budgets).await,
+            Section::BudgetLines => insert_batch(txn, &mut self.budget_lines).await,
+            Section::BudgetLineDimensions => {
+                insert_batch(txn, &mut self.budget_line_dimensions).await
+            }
+            Section::ApprovalRules => insert_batch(txn, &mut self.approval_rules).await,
+        }
+    }
+
+    /// Flushes a section and resolves the links that had to wait for it.
+    async fn finish_section(
+        &mut self,
+        txn: &DatabaseTransaction,
+        section: Section,
+    ) -> Result<(), BackupError> {
+        self.flush(txn, section).await?;
+
+        match section {
+            Section::Accounts => {
+                for link in std::mem::take(&mut self.parents) {
+                    let parent = self.resolve(section, link.target)?;
+                    chart_of_accounts::Entity::update_many()
+                        .col_expr(chart_of_accounts::Column::ParentId, parent.into())
+                        .filter(chart_of_accounts::Column::Id.eq(link.id))
+                        .exec(txn)
+                        .await?;
+                }
+            }
+            Section::DimensionValues => {
+                for link in std::mem::take(&mut self.parents) {
+                    let parent = self.resolve(section, link.target)?;
+                    dimension_values::Entity::update_many()
+                        .col_expr(dimension_values::Column::ParentId, parent.into())
+                        .filter(dimension_values::Column::Id.eq(link.id))
+                        .exec(txn)
+                        .await?;
+                }
+            }
+            Section::Transactions => {
+                for link in std::mem::take(&mut self.reversals) {
+                    let reversed_by = self.resolve(section, link.reversed_by)?;
+                    transactions::ActiveModel {
+                        id: ActiveValue::Unchanged(link.id),
+                        reversed_by_transaction_id: ActiveValue::Set(Some(reversed_by)),
+                        status: ActiveValue::Set(link.status),
+                        ..Default::default()
+                    }
+                    .update(txn)
+                    .await?;
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    async fn finish(&mut self, txn: &DatabaseTransaction) -> Result<ImportSummary, BackupError> {
+        if let Some(section) = self.current.take() {
+            self.finish_section(txn, section).await?;
+        }
+
+        for (transaction, debit, credit) in std::mem::take(&mut self.totals).into_values() {
+            if debit != credit {
+                return Err(BackupError::Unbalanced {
+                    transaction,
+                    debit,
+                    credit,
+                });
+            }
+        }
+
+        Ok(ImportSummary {
+            counts: self.counts.clone(),
+        })
+    }
+}
+
+async fn insert_batch<A>(txn: &DatabaseTransaction, rows: &mut Vec<A>) -> Result<(), DbErr>
+where
+    A: ActiveModelTrait + Send,
+    <A::Entity as EntityTrait>::Model: IntoActiveModel<A>,
+{
+    if rows.is_empty() {
+        return Ok(());
+    }
+    let batch = std::mem::take(rows);
+    A::Entity::insert_many(batch)
+        .exec_without_returning(txn)
+        .await?;
+    Ok(())
+}
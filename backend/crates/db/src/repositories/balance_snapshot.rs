@@ -0,0 +1,326 @@
+//! Account balance snapshots taken at fiscal period close.
+//!
+//! A snapshot holds each account's cumulative posted totals through the end
+//! of a closed period. It is only usable while every period ending on or
+//! before it is closed, since entries can still be posted or voided in any
+//! other period. Reports start from the latest usable snapshot and add the
+//! entries dated after it.
+
+use std::collections::HashMap;
+
+use chrono::{NaiveDate, Utc};
+use rust_decimal::Decimal;
+use sea_orm::{
+    ColumnTrait, ConnectionTrait, DatabaseConnection, DbErr, EntityTrait, JoinType, QueryFilter,
+    QueryOrder, QuerySelect, RelationTrait, Set, TransactionTrait,
+};
+use uuid::Uuid;
+
+use crate::entities::{
+    account_period_balances, chart_of_accounts, fiscal_periods, ledger_entries, organizations,
+    sea_orm_active_enums::{FiscalPeriodStatus, TransactionStatus},
+    transactions,
+};
+
+use super::report::calculate_balance;
+
+/// Posted debit and credit totals per account.
+pub type AccountTotals = HashMap<Uuid, (Decimal, Decimal)>;
+
+/// Result of rebuilding snapshots.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct BackfillSummary {
+    /// Organizations processed.
+    pub organizations: u64,
+    /// Snapshot rows written.
+    pub snapshots: u64,
+}
+
+/// Repository for account balance snapshots.
+#[derive(Debug, Clone)]
+pub struct BalanceSnapshotRepository {
+    db: DatabaseConnection,
+}
+
+impl BalanceSnapshotRepository {
+    /// Creates a new balance snapshot repository.
+    #[must_use]
+    pub const fn new(db: DatabaseConnection) -> Self {
+        Self { db }
+    }
+
+    /// Rebuilds every snapshot of an organization from the ledger.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a database operation fails.
+    pub async fn backfill(&self, organization_id: Uuid) -> Result<u64, DbErr> {
+        let txn = self.db.begin().await?;
+        let written = rebuild_snapshots(&txn, organization_id, None).await?;
+        txn.commit().await?;
+        Ok(written)
+    }
+
+    /// Rebuilds the snapshots of every organization, one at a time.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a database operation fails. Organizations
+    /// processed before the failure keep their new snapshots.
+    pub async fn backfill_all(&self) -> Result<BackfillSummary, DbErr> {
+        let organization_ids: Vec<Uuid> = organizations::Entity::find()
+            .select_only()
+            .column(organizations::Column::Id)
+            .into_tuple()
+            .all(&self.db)
+            .await?;
+
+        let mut summary = BackfillSummary::default();
+        for organization_id in organization_ids {
+            summary.snapshots += self.backfill(organization_id).await?;
+            summary.organizations += 1;
+        }
+        Ok(summary)
+    }
+
+    /// End of the latest usable snapshot at or before `as_of`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    pub async fn usable_snapshot_end(
+        &self,
+        organization_id: Uuid,
+        as_of: NaiveDate,
+    ) -> Result<Option<NaiveDate>, DbErr> {
+        usable_snapshot_end(&self.db, organization_id, as_of).await
+    }
+
+    /// An account's snapshot row at `period_end`, if it had any entries.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    pub async fn account_snapshot(
+        &self,
+        account_id: Uuid,
+        period_end: NaiveDate,
+    ) -> Result<Option<account_period_balances::Model>, DbErr> {
+        account_period_balances::Entity::find()
+            .filter(account_period_balances::Column::AccountId.eq(account_id))
+            .filter(account_period_balances::Column::PeriodEnd.eq(period_end))
+            .one(&self.db)
+            .await
+    }
+
+    /// Posted totals per account through `as_of`.
+    ///
+    /// Starts from the latest usable snapshot and only scans entries dated
+    /// after it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a database query fails.
+    pub async fn totals_as_of(
+        &self,
+        organization_id: Uuid,
+        as_of: NaiveDate,
+    ) -> Result<AccountTotals, DbErr> {
+        let snapshot_end = usable_snapshot_end(&self.db, organization_id, as_of).await?;
+        let mut totals = match snapshot_end {
+            Some(end) => snapshot_totals(&self.db, organization_id, end).await?,
+            None => AccountTotals::new(),
+        };
+        let delta = posted_totals(&self.db, organization_id, snapshot_end, as_of).await?;
+        add_totals(&mut totals, delta);
+        Ok(totals)
+    }
+}
+
+/// Rebuilds an organization's snapshots ending on or after `from`.
+///
+/// Snapshots before `from` are kept and used as the starting point; `None`
+/// rebuilds everything. Returns the number of rows written.
+pub(crate) async fn rebuild_snapshots<C: ConnectionTrait>(
+    db: &C,
+    organization_id: Uuid,
+    from: Option<NaiveDate>,
+) -> Result<u64, DbErr> {
+    let mut stale = account_period_balances::Entity::delete_many()
+        .filter(account_period_balances::Column::OrganizationId.eq(organization_id));
+    if let Some(from) = from {
+        stale = stale.filter(account_period_balances::Column::PeriodEnd.gte(from));
+    }
+    stale.exec(db).await?;
+
+    let mut periods = fiscal_periods::Entity::find()
+        .filter(fiscal_periods::Column::OrganizationId.eq(organization_id))
+        .filter(fiscal_periods::Column::Status.eq(FiscalPeriodStatus::Closed))
+        .order_by_asc(fiscal_periods::Column::EndDate);
+    if let Some(from) = from {
+        periods = periods.filter(fiscal_periods::Column::EndDate.gte(from));
+    }
+    if let Some(end) = first_unclosed_end(db, organization_id).await? {
+        periods = periods.filter(fiscal_periods::Column::EndDate.lt(end));
+    }
+    let periods = periods.all(db).await?;
+    if periods.is_empty() {
+        return Ok(0);
+    }
+
+    let account_types: HashMap<Uuid, _> = chart_of_accounts::Entity::find()
+        .filter(chart_of_accounts::Column::OrganizationId.eq(organization_id))
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|a| (a.id, a.account_type))
+        .collect();
+
+    let mut through = match from {
+        Some(from) => usable_snapshot_end(db, organization_id, from).await?,
+        None => None,
+    };
+    let mut totals = match through {
+        Some(end) => snapshot_totals(db, organization_id, end).await?,
+        None => AccountTotals::new(),
+    };
+
+    let now = Utc::now();
+    let mut written = 0;
+    for period in periods {
+        // Periods sharing an end date share the same totals
+        if through != Some(period.end_date) {
+            let delta = posted_totals(db, organization_id, through, period.end_date).await?;
+            add_totals(&mut totals, delta);
+            through = Some(period.end_date);
+        }
+
+        let rows: Vec<_> = totals
+            .iter()
+            .filter_map(|(account_id, &(total_debit, total_credit))| {
+                let account_type = account_types.get(account_id)?;
+                Some(account_period_balances::ActiveModel {
+                    id: Set(Uuid::new_v4()),
+                    organization_id: Set(organization_id),
+                    account_id: Set(*account_id),
+                    fiscal_period_id: Set(period.id),
+                    period_end: Set(period.end_date),
+                    total_debit: Set(total_debit),
+                    total_credit: Set(total_credit),
+                    ending_balance: Set(calculate_balance(account_type, total_debit, total_credit)),
+                    created_at: Set(now.into()),
+                })
+            })
+            .collect();
+        if rows.is_empty() {
+            continue;
+        }
+        written += rows.len() as u64;
+        account_period_balances::Entity::insert_many(rows)
+            .exec(db)
+            .await?;
+    }
+
+    Ok(written)
+}
+
+/// End of the earliest period that is not closed.
+async fn first_unclosed_end<C: ConnectionTrait>(
+    db: &C,
+    organization_id: Uuid,
+) -> Result<Option<NaiveDate>, DbErr> {
+    let end: Option<Option<NaiveDate>> = fiscal_periods::Entity::find()
+        .select_only()
+        .column_as(fiscal_periods::Column::EndDate.min(), "end_date")
+        .filter(fiscal_periods::Column::OrganizationId.eq(organization_id))
+        .filter(fiscal_periods::Column::Status.ne(FiscalPeriodStatus::Closed))
+        .into_tuple()
+        .one(db)
+        .await?;
+
+    Ok(end.flatten())
+}
+
+/// End of the latest snapshot at or before `as_of` that no open period
+/// can still change.
+async fn usable_snapshot_end<C: ConnectionTrait>(
+    db: &C,
+    organization_id: Uuid,
+    as_of: NaiveDate,
+) -> Result<Option<NaiveDate>, DbErr> {
+    let mut query = account_period_balances::Entity::find()
+        .select_only()
+        .column_as(
+            account_period_balances::Column::PeriodEnd.max(),
+            "period_end",
+        )
+        .filter(account_period_balances::Column::OrganizationId.eq(organization_id))
+        .filter(account_period_balances::Column::PeriodEnd.lte(as_of));
+    if let Some(end) = first_unclosed_end(db, organization_id).await? {
+        query = query.filter(account_period_balances::Column::PeriodEnd.lt(end));
+    }
+    let end: Option<Option<NaiveDate>> = query.into_tuple().one(db).await?;
+
+    Ok(end.flatten())
+}
+
+/// Totals stored in the snapshot ending on `period_end`.
+async fn snapshot_totals<C: ConnectionTrait>(
+    db: &C,
+    organization_id: Uuid,
+    period_end: NaiveDate,
+) -> Result<AccountTotals, DbErr> {
+    let rows = account_period_balances::Entity::find()
+        .filter(account_period_balances::Column::OrganizationId.eq(organization_id))
+        .filter(account_period_balances::Column::PeriodEnd.eq(period_end))
+        .all(db)
+        .await?;
+
+    Ok(rows
+        .into_iter()
+        .map(|r| (r.account_id, (r.total_debit, r.total_credit)))
+        .collect())
+}
+
+/// Posted totals per account for entries dated after `after` through
+/// `through`.
+async fn posted_totals<C: ConnectionTrait>(
+    db: &C,
+    organization_id: Uuid,
+    after: Option<NaiveDate>,
+    through: NaiveDate,
+) -> Result<AccountTotals, DbErr> {
+    let mut query = ledger_entries::Entity::find()
+        .select_only()
+        .column(ledger_entries::Column::AccountId)
+        .column_as(ledger_entries::Column::Debit.sum(), "total_debit")
+        .column_as(ledger_entries::Column::Credit.sum(), "total_credit")
+        .join(
+            JoinType::InnerJoin,
+            ledger_entries::Relation::Transactions.def(),
+        )
+        .filter(transactions::Column::OrganizationId.eq(organization_id))
+        .filter(transactions::Column::Status.eq(TransactionStatus::Posted))
+        .filter(transactions::Column::TransactionDate.lte(through))
+        .group_by(ledger_entries::Column::AccountId);
+    if let Some(after) = after {
+        query = query.filter(transactions::Column::TransactionDate.gt(after));
+    }
+    let rows: Vec<(Uuid, Decimal, Decimal)> = query.into_tuple().all(db).await?;
+
+    Ok(rows
+        .into_iter()
+        .map(|(account_id, debit, credit)| (account_id, (debit, credit)))
+        .collect())
+}
+
+/// Adds `delta` into `totals`.
+fn add_totals(totals: &mut AccountTotals, delta: AccountTotals) {
+    for (account_id, (debit, credit)) in delta {
+        let entry = totals
+            .entry(account_id)
+            .or_insert((Decimal::ZERO, Decimal::ZERO));
+        entry.0 += debit;
+        entry.1 += credit;
+    }
+}
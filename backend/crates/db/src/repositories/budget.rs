@@ -2,17 +2,30 @@
 //!
 //! Implements Requirements 1.1-1.7, 2.1-2.7, 3.1-3.4, 4.1-4.9 for budget management.
 
-use chrono::Utc;
+use std::collections::{BTreeSet, HashMap, HashSet, hash_map::Entry};
+
+use chrono::{NaiveDate, Utc};
 use rust_decimal::Decimal;
 use sea_orm::{
-    ActiveModelTrait, ColumnTrait, DatabaseConnection, DatabaseTransaction, DbErr, EntityTrait,
-    QueryFilter, QueryOrder, QuerySelect, Set, TransactionTrait,
+    ActiveModelTrait, ColumnTrait, ConnectionTrait, DatabaseConnection, DatabaseTransaction, DbErr,
+    EntityTrait, JoinType, QueryFilter, QueryOrder, QuerySelect, QueryTrait, RelationTrait, Set,
+    TransactionTrait, sea_query::Expr,
 };
 use uuid::Uuid;
+use zeltra_core::auth::UserRole;
+use zeltra_core::budget::{
+    AppliedRate, ConvertSide, PeriodTotals, ProjectedUtilization, TrendPoint, convert_line,
+    cumulative_trend, missing_rate_warning,
+};
+use zeltra_core::currency::RateLookupPolicy;
 
+use super::exchange_rate::{ExchangeRateError, ExchangeRateRepository};
+use super::subscription::{LimitCheckResult, ResourceLimit, SubscriptionRepository};
+use super::transaction::db_role_to_core;
 use crate::entities::{
-    budget_line_dimensions, budget_lines, budgets, chart_of_accounts, dimension_values,
-    fiscal_periods, fiscal_years, ledger_entries,
+    budget_editors, budget_line_dimensions, budget_lines, budgets, chart_of_accounts,
+    dimension_values, fiscal_periods, fiscal_years, ledger_entries, organization_users,
+    organizations,
     sea_orm_active_enums::{AccountType, BudgetType as DbBudgetType, TransactionStatus},
     transactions,
 };
@@ -64,6 +77,26 @@ pub enum BudgetError {
     #[error("Budget line not found: {0}")]
     BudgetLineNotFound(Uuid),
 
+    /// More lines than [`MAX_REPLACE_LINES`] in one request.
+    #[error("At most {MAX_REPLACE_LINES} budget lines can be sent at once, got {0}")]
+    TooManyLines(usize),
+
+    /// One or more lines of a replacement are invalid; nothing was changed.
+    #[error("{} budget lines are invalid", .0.len())]
+    InvalidLines(Vec<BudgetLineError>),
+
+    /// The organization's plan does not allow another one.
+    #[error("Tier limit exceeded for {}", .0.resource.as_str())]
+    TierLimitExceeded(LimitCheckResult),
+
+    /// The user is not the budget's owner or editor and not an accountant.
+    #[error("Only the budget's owner, its editors or an accountant can edit it")]
+    NotBudgetEditor,
+
+    /// Assigned user is not a member of the organization.
+    #[error("User is not a member of this organization: {0}")]
+    NotAMember(Uuid),
+
     /// Database error.
     #[error("Database error: {0}")]
     Database(#[from] DbErr),
@@ -99,6 +132,15 @@ pub struct UpdateBudgetInput {
     pub is_active: Option<bool>,
 }
 
+/// Input for reassigning a budget's owner and editors.
+#[derive(Debug, Clone, Default)]
+pub struct AssignBudgetInput {
+    /// New owner, `Some(None)` to leave the budget without one.
+    pub owner_user_id: Option<Option<Uuid>>,
+    /// New editors, replacing the current ones.
+    pub editors: Option<Vec<Uuid>>,
+}
+
 /// Input for creating a budget line.
 #[derive(Debug, Clone)]
 pub struct CreateBudgetLineInput {
@@ -123,6 +165,31 @@ pub struct UpdateBudgetLineInput {
     pub notes: Option<Option<String>>,
 }
 
+/// Maximum number of lines accepted by [`BudgetRepository::replace_lines`].
+pub const MAX_REPLACE_LINES: usize = 2000;
+
+/// A problem with one line of a replacement, by its position in the request.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BudgetLineError {
+    /// Zero-based index of the line in the request.
+    pub index: usize,
+    /// What is wrong with the line.
+    pub message: String,
+}
+
+/// What [`BudgetRepository::replace_lines`] changed.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct BudgetLinesDiff {
+    /// Lines that did not exist before.
+    pub created: usize,
+    /// Existing lines whose amount, notes or dimensions changed.
+    pub updated: usize,
+    /// Existing lines absent from the request.
+    pub deleted: usize,
+    /// Existing lines sent back unchanged.
+    pub unchanged: usize,
+}
+
 /// Budget with summary totals.
 #[derive(Debug, Clone)]
 pub struct BudgetWithSummary {
@@ -132,6 +199,25 @@ pub struct BudgetWithSummary {
     pub fiscal_year_name: String,
     /// Total budgeted amount.
     pub total_budgeted: Decimal,
+    /// Members listed as editors besides the owner.
+    pub editors: Vec<Uuid>,
+}
+
+impl BudgetWithSummary {
+    /// Whether `user_id`, holding `role`, may edit the budget.
+    #[must_use]
+    pub fn can_edit(&self, user_id: Uuid, role: UserRole) -> bool {
+        may_edit(role, user_id, self.budget.owner_user_id, &self.editors)
+    }
+}
+
+/// A budget with its editors after reassignment.
+#[derive(Debug, Clone)]
+pub struct BudgetAssignment {
+    /// Budget record.
+    pub budget: budgets::Model,
+    /// Members listed as editors besides the owner.
+    pub editors: Vec<Uuid>,
 }
 
 /// Budget line with dimensions.
@@ -154,21 +240,32 @@ pub struct BudgetLineWithActual {
     pub account_name: String,
     /// Period name.
     pub period_name: String,
-    /// Actual amount.
+    /// Budgeted amount in the reporting currency.
+    pub budgeted: Decimal,
+    /// Actual amount in the reporting currency.
     pub actual: Decimal,
     /// Variance (budgeted - actual for expenses, actual - budgeted for revenue).
-    pub variance: Decimal,
-    /// Utilization percentage.
-    pub utilization_percent: Decimal,
-    /// Variance status: favorable, unfavorable, on_budget.
+    /// `None` when the line could not be converted.
+    pub variance: Option<Decimal>,
+    /// Utilization percentage, `None` when the line could not be converted.
+    pub utilization_percent: Option<Decimal>,
+    /// Variance status: favorable, unfavorable, on_budget, or unconverted.
     pub status: String,
     /// Dimension values.
     pub dimensions: Vec<DimensionValueInfo>,
+    /// Rate used to convert the line, if the budget is in another currency.
+    pub rate: Option<AppliedRate>,
+    /// Why the line could not be converted.
+    pub warning: Option<String>,
 }
 
 /// Budget vs actual summary.
 #[derive(Debug, Clone)]
 pub struct BudgetVsActualSummary {
+    /// Currency the amounts are reported in.
+    pub currency: String,
+    /// Lines left out of the totals because no rate was found.
+    pub unconverted_lines: usize,
     /// Total budgeted amount.
     pub total_budgeted: Decimal,
     /// Total actual amount.
@@ -179,6 +276,56 @@ pub struct BudgetVsActualSummary {
     pub overall_utilization: Decimal,
 }
 
+/// One fiscal period of a budget trend.
+#[derive(Debug, Clone)]
+pub struct BudgetTrendPeriod {
+    /// Fiscal period ID.
+    pub fiscal_period_id: Uuid,
+    /// Period name.
+    pub period_name: String,
+    /// First day of the period.
+    pub start_date: NaiveDate,
+    /// Last day of the period.
+    pub end_date: NaiveDate,
+    /// Period and running totals in the reporting currency.
+    pub point: TrendPoint,
+    /// Rate used to convert the period, if the budget is in another currency.
+    pub rate: Option<AppliedRate>,
+    /// Why the period could not be converted.
+    pub warning: Option<String>,
+}
+
+/// Budget vs actual per period across a fiscal year.
+#[derive(Debug, Clone)]
+pub struct BudgetTrend {
+    /// Currency the amounts are reported in.
+    pub currency: String,
+    /// Every period of the fiscal year, in order.
+    pub periods: Vec<BudgetTrendPeriod>,
+}
+
+/// A budget line that a transaction would take past the enforcement
+/// threshold.
+#[derive(Debug, Clone)]
+pub struct BudgetOverrun {
+    /// Budget line ID.
+    pub budget_line_id: Uuid,
+    /// Budget ID.
+    pub budget_id: Uuid,
+    /// Budget name.
+    pub budget_name: String,
+    /// Account ID.
+    pub account_id: Uuid,
+    /// Account code.
+    pub account_code: String,
+    /// Fiscal period ID.
+    pub fiscal_period_id: Uuid,
+    /// Period name.
+    pub period_name: String,
+    /// Budgeted, actual and transaction amounts in the functional currency.
+    pub utilization: ProjectedUtilization,
+}
+
 /// Budget repository for CRUD operations.
 #[derive(Debug, Clone)]
 pub struct BudgetRepository {
@@ -205,15 +352,18 @@ impl BudgetRepository {
     /// Returns an error if:
     /// - Fiscal year does not exist
     /// - Budget name already exists for this fiscal year
+    /// - Another budget would exceed the organization's tier limit
     /// - Database operation fails
     pub async fn create_budget(
         &self,
         input: CreateBudgetInput,
     ) -> Result<budgets::Model, BudgetError> {
+        let txn = self.db.begin().await?;
+
         // Validate fiscal year exists (Requirement 1.2)
         let _fiscal_year = fiscal_years::Entity::find_by_id(input.fiscal_year_id)
             .filter(fiscal_years::Column::OrganizationId.eq(input.organization_id))
-            .one(&self.db)
+            .one(&txn)
             .await?
             .ok_or(BudgetError::FiscalYearNotFound(input.fiscal_year_id))?;
 
@@ -222,13 +372,23 @@ impl BudgetRepository {
             .filter(budgets::Column::OrganizationId.eq(input.organization_id))
             .filter(budgets::Column::FiscalYearId.eq(input.fiscal_year_id))
             .filter(budgets::Column::Name.eq(&input.name))
-            .one(&self.db)
+            .one(&txn)
             .await?;
 
         if existing.is_some() {
             return Err(BudgetError::DuplicateName);
         }
 
+        let limit = SubscriptionRepository::check_limit(
+            &txn,
+            input.organization_id,
+            ResourceLimit::Budgets,
+        )
+        .await?;
+        if !limit.allowed {
+            return Err(BudgetError::TierLimitExceeded(limit));
+        }
+
         let now = Utc::now().into();
         let budget_id = Uuid::new_v4();
 
@@ -248,9 +408,11 @@ impl BudgetRepository {
             created_by: Set(input.created_by),
             created_at: Set(now),
             updated_at: Set(now),
+            owner_user_id: Set(Some(input.created_by)),
         };
 
-        let result = budget.insert(&self.db).await?;
+        let result = budget.insert(&txn).await?;
+        txn.commit().await?;
         Ok(result)
     }
 
@@ -290,6 +452,19 @@ impl BudgetRepository {
             .all(&self.db)
             .await?;
 
+        let mut editors: HashMap<Uuid, Vec<Uuid>> = HashMap::new();
+        for editor in budget_editors::Entity::find()
+            .filter(budget_editors::Column::OrganizationId.eq(organization_id))
+            .order_by_asc(budget_editors::Column::CreatedAt)
+            .all(&self.db)
+            .await?
+        {
+            editors
+                .entry(editor.budget_id)
+                .or_default()
+                .push(editor.user_id);
+        }
+
         let mut result = Vec::with_capacity(budgets_list.len());
 
         for budget in budgets_list {
@@ -304,6 +479,7 @@ impl BudgetRepository {
             let total_budgeted = self.calculate_total_budgeted(budget.id).await?;
 
             result.push(BudgetWithSummary {
+                editors: editors.remove(&budget.id).unwrap_or_default(),
                 budget,
                 fiscal_year_name: fiscal_year,
                 total_budgeted,
@@ -321,21 +497,36 @@ impl BudgetRepository {
     ///
     /// Returns an error if:
     /// - Budget is not found
+    /// - `user_id` may not edit the budget
     /// - Budget is locked
+    /// - Reactivating it would exceed the organization's tier limit
     /// - Database operation fails
     pub async fn update_budget(
         &self,
         organization_id: Uuid,
         budget_id: Uuid,
+        user_id: Uuid,
         input: UpdateBudgetInput,
     ) -> Result<budgets::Model, BudgetError> {
         let budget = self.get_budget(organization_id, budget_id).await?;
+        check_editor(&self.db, &budget, user_id).await?;
 
         // Check if locked (Requirement 1.7)
         if budget.is_locked {
             return Err(BudgetError::BudgetLocked);
         }
 
+        let txn = self.db.begin().await?;
+        // Reactivating counts against the limit like creating
+        if input.is_active == Some(true) && !budget.is_active {
+            let limit =
+                SubscriptionRepository::check_limit(&txn, organization_id, ResourceLimit::Budgets)
+                    .await?;
+            if !limit.allowed {
+                return Err(BudgetError::TierLimitExceeded(limit));
+            }
+        }
+
         let mut active: budgets::ActiveModel = budget.into();
 
         if let Some(name) = input.name {
@@ -349,7 +540,8 @@ impl BudgetRepository {
         }
         active.updated_at = Set(Utc::now().into());
 
-        let updated = active.update(&self.db).await?;
+        let updated = active.update(&txn).await?;
+        txn.commit().await?;
         Ok(updated)
     }
 
@@ -375,6 +567,91 @@ impl BudgetRepository {
         Ok(updated)
     }
 
+    /// Reassigns a budget's owner and editors.
+    ///
+    /// Locked budgets can still be reassigned. Callers check that the user
+    /// making the change may reassign budgets.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - Budget is not found
+    /// - The new owner or an editor is not a member of the organization
+    /// - Database operation fails
+    pub async fn assign_budget(
+        &self,
+        organization_id: Uuid,
+        budget_id: Uuid,
+        input: AssignBudgetInput,
+    ) -> Result<BudgetAssignment, BudgetError> {
+        let txn = self.db.begin().await?;
+        let budget = budgets::Entity::find_by_id(budget_id)
+            .filter(budgets::Column::OrganizationId.eq(organization_id))
+            .lock_exclusive()
+            .one(&txn)
+            .await?
+            .ok_or(BudgetError::NotFound(budget_id))?;
+
+        let mut editors = input.editors;
+        if let Some(editors) = &mut editors {
+            let mut seen = HashSet::new();
+            editors.retain(|id| seen.insert(*id));
+        }
+        let assignees: Vec<Uuid> = input
+            .owner_user_id
+            .flatten()
+            .into_iter()
+            .chain(editors.iter().flatten().copied())
+            .collect();
+        let members: HashSet<Uuid> = organization_users::Entity::find()
+            .select_only()
+            .column(organization_users::Column::UserId)
+            .filter(organization_users::Column::OrganizationId.eq(organization_id))
+            .filter(organization_users::Column::UserId.is_in(assignees.clone()))
+            .into_tuple()
+            .all(&txn)
+            .await?
+            .into_iter()
+            .collect();
+        if let Some(outsider) = assignees.into_iter().find(|id| !members.contains(id)) {
+            return Err(BudgetError::NotAMember(outsider));
+        }
+
+        let now = Utc::now().into();
+        let budget = match input.owner_user_id {
+            Some(owner_user_id) => {
+                let mut active: budgets::ActiveModel = budget.into();
+                active.owner_user_id = Set(owner_user_id);
+                active.updated_at = Set(now);
+                active.update(&txn).await?
+            }
+            None => budget,
+        };
+
+        if let Some(editors) = &editors {
+            budget_editors::Entity::delete_many()
+                .filter(budget_editors::Column::BudgetId.eq(budget_id))
+                .exec(&txn)
+                .await?;
+            if !editors.is_empty() {
+                budget_editors::Entity::insert_many(editors.iter().map(|user_id| {
+                    budget_editors::ActiveModel {
+                        budget_id: Set(budget_id),
+                        user_id: Set(*user_id),
+                        organization_id: Set(organization_id),
+                        created_at: Set(now),
+                    }
+                }))
+                .exec(&txn)
+                .await?;
+            }
+        }
+        let editors = load_editors(&txn, budget_id).await?;
+
+        txn.commit().await?;
+        Ok(BudgetAssignment { budget, editors })
+    }
+
     // ========================================================================
     // Budget Line Operations (Requirements 2.1-2.7)
     // ========================================================================
@@ -386,6 +663,7 @@ impl BudgetRepository {
     /// # Errors
     ///
     /// Returns an error if:
+    /// - `user_id` may not edit the budget
     /// - Budget is locked
     /// - Account does not exist
     /// - Fiscal period does not belong to budget's fiscal year
@@ -396,9 +674,11 @@ impl BudgetRepository {
         &self,
         organization_id: Uuid,
         budget_id: Uuid,
+        user_id: Uuid,
         lines: Vec<CreateBudgetLineInput>,
     ) -> Result<Vec<BudgetLineWithDimensions>, BudgetError> {
         let budget = self.get_budget(organization_id, budget_id).await?;
+        check_editor(&self.db, &budget, user_id).await?;
 
         // Check if locked (Requirement 1.7)
         if budget.is_locked {
@@ -547,6 +827,7 @@ impl BudgetRepository {
     /// # Errors
     ///
     /// Returns an error if:
+    /// - `user_id` may not edit the budget
     /// - Budget is locked
     /// - Budget line is not found
     /// - Amount is negative
@@ -556,9 +837,11 @@ impl BudgetRepository {
         organization_id: Uuid,
         budget_id: Uuid,
         line_id: Uuid,
+        user_id: Uuid,
         input: UpdateBudgetLineInput,
     ) -> Result<budget_lines::Model, BudgetError> {
         let budget = self.get_budget(organization_id, budget_id).await?;
+        check_editor(&self.db, &budget, user_id).await?;
 
         if budget.is_locked {
             return Err(BudgetError::BudgetLocked);
@@ -592,6 +875,7 @@ impl BudgetRepository {
     /// # Errors
     ///
     /// Returns an error if:
+    /// - `user_id` may not edit the budget
     /// - Budget is locked
     /// - Budget line is not found
     /// - Database operation fails
@@ -600,8 +884,10 @@ impl BudgetRepository {
         organization_id: Uuid,
         budget_id: Uuid,
         line_id: Uuid,
+        user_id: Uuid,
     ) -> Result<(), BudgetError> {
         let budget = self.get_budget(organization_id, budget_id).await?;
+        check_editor(&self.db, &budget, user_id).await?;
 
         if budget.is_locked {
             return Err(BudgetError::BudgetLocked);
@@ -619,6 +905,233 @@ impl BudgetRepository {
         Ok(())
     }
 
+    /// Replaces a budget's lines with the given set.
+    ///
+    /// Lines are matched to existing ones by account and fiscal period.
+    /// Unmatched lines are created, matched lines with a different amount,
+    /// notes or dimension set are updated, and existing lines missing from
+    /// the request are deleted. Every line is validated first and the
+    /// changes are applied in one transaction, so either all apply or none.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - More than [`MAX_REPLACE_LINES`] lines are given
+    /// - Budget is not found or is locked
+    /// - `user_id` may not edit the budget
+    /// - Any line is invalid ([`BudgetError::InvalidLines`] lists them all)
+    /// - Database operation fails
+    pub async fn replace_lines(
+        &self,
+        organization_id: Uuid,
+        budget_id: Uuid,
+        user_id: Uuid,
+        lines: Vec<CreateBudgetLineInput>,
+    ) -> Result<BudgetLinesDiff, BudgetError> {
+        if lines.len() > MAX_REPLACE_LINES {
+            return Err(BudgetError::TooManyLines(lines.len()));
+        }
+
+        let txn = self.db.begin().await?;
+
+        // Lock the budget so concurrent replacements and locking serialize
+        let budget = budgets::Entity::find_by_id(budget_id)
+            .filter(budgets::Column::OrganizationId.eq(organization_id))
+            .lock_exclusive()
+            .one(&txn)
+            .await?
+            .ok_or(BudgetError::NotFound(budget_id))?;
+        check_editor(&txn, &budget, user_id).await?;
+        if budget.is_locked {
+            return Err(BudgetError::BudgetLocked);
+        }
+
+        let errors = Self::validate_replacement(&txn, &budget, &lines).await?;
+        if !errors.is_empty() {
+            return Err(BudgetError::InvalidLines(errors));
+        }
+
+        let existing = budget_lines::Entity::find()
+            .filter(budget_lines::Column::BudgetId.eq(budget_id))
+            .all(&txn)
+            .await?;
+        let mut existing_dims: HashMap<Uuid, BTreeSet<Uuid>> = HashMap::new();
+        for dim in budget_line_dimensions::Entity::find()
+            .filter(
+                budget_line_dimensions::Column::BudgetLineId
+                    .is_in(existing.iter().map(|l| l.id).collect::<Vec<_>>()),
+            )
+            .all(&txn)
+            .await?
+        {
+            existing_dims
+                .entry(dim.budget_line_id)
+                .or_default()
+                .insert(dim.dimension_value_id);
+        }
+        let mut existing_by_key: HashMap<(Uuid, Uuid), budget_lines::Model> = existing
+            .into_iter()
+            .map(|l| ((l.account_id, l.fiscal_period_id), l))
+            .collect();
+
+        let now = Utc::now().into();
+        let mut diff = BudgetLinesDiff::default();
+
+        for input in lines {
+            let dimensions: BTreeSet<Uuid> = input.dimensions.iter().copied().collect();
+            let Some(line) = existing_by_key.remove(&(input.account_id, input.fiscal_period_id))
+            else {
+                let line = budget_lines::ActiveModel {
+                    id: Set(Uuid::new_v4()),
+                    budget_id: Set(budget_id),
+                    account_id: Set(input.account_id),
+                    fiscal_period_id: Set(input.fiscal_period_id),
+                    amount: Set(input.amount),
+                    notes: Set(input.notes),
+                    created_at: Set(now),
+                    updated_at: Set(now),
+                }
+                .insert(&txn)
+                .await?;
+                Self::insert_line_dimensions(&txn, line.id, &dimensions, now).await?;
+                diff.created += 1;
+                continue;
+            };
+
+            let dims_changed =
+                existing_dims.get(&line.id).cloned().unwrap_or_default() != dimensions;
+            if line.amount == input.amount && line.notes == input.notes && !dims_changed {
+                diff.unchanged += 1;
+                continue;
+            }
+
+            let line_id = line.id;
+            let mut active: budget_lines::ActiveModel = line.into();
+            active.amount = Set(input.amount);
+            active.notes = Set(input.notes);
+            active.updated_at = Set(now);
+            active.update(&txn).await?;
+            if dims_changed {
+                budget_line_dimensions::Entity::delete_many()
+                    .filter(budget_line_dimensions::Column::BudgetLineId.eq(line_id))
+                    .exec(&txn)
+                    .await?;
+                Self::insert_line_dimensions(&txn, line_id, &dimensions, now).await?;
+            }
+            diff.updated += 1;
+        }
+
+        if !existing_by_key.is_empty() {
+            let stale: Vec<Uuid> = existing_by_key.values().map(|l| l.id).collect();
+            diff.deleted = stale.len();
+            budget_lines::Entity::delete_many()
+                .filter(budget_lines::Column::Id.is_in(stale))
+                .exec(&txn)
+                .await?;
+        }
+
+        txn.commit().await?;
+        Ok(diff)
+    }
+
+    /// Validates every line of a replacement, returning all problems found.
+    async fn validate_replacement(
+        txn: &DatabaseTransaction,
+        budget: &budgets::Model,
+        lines: &[CreateBudgetLineInput],
+    ) -> Result<Vec<BudgetLineError>, BudgetError> {
+        let periods: HashSet<Uuid> = fiscal_periods::Entity::find()
+            .select_only()
+            .column(fiscal_periods::Column::Id)
+            .filter(fiscal_periods::Column::FiscalYearId.eq(budget.fiscal_year_id))
+            .into_tuple()
+            .all(txn)
+            .await?
+            .into_iter()
+            .collect();
+
+        let account_ids: HashSet<Uuid> = lines.iter().map(|l| l.account_id).collect();
+        let accounts: HashMap<Uuid, bool> = chart_of_accounts::Entity::find()
+            .select_only()
+            .column(chart_of_accounts::Column::Id)
+            .column(chart_of_accounts::Column::IsActive)
+            .filter(chart_of_accounts::Column::OrganizationId.eq(budget.organization_id))
+            .filter(chart_of_accounts::Column::Id.is_in(account_ids))
+            .into_tuple()
+            .all(txn)
+            .await?
+            .into_iter()
+            .collect();
+
+        let dimension_ids: HashSet<Uuid> = lines
+            .iter()
+            .flat_map(|l| l.dimensions.iter().copied())
+            .collect();
+        let active_dimensions: HashSet<Uuid> = dimension_values::Entity::find()
+            .select_only()
+            .column(dimension_values::Column::Id)
+            .filter(dimension_values::Column::OrganizationId.eq(budget.organization_id))
+            .filter(dimension_values::Column::IsActive.eq(true))
+            .filter(dimension_values::Column::Id.is_in(dimension_ids))
+            .into_tuple()
+            .all(txn)
+            .await?
+            .into_iter()
+            .collect();
+
+        let mut errors = Vec::new();
+        let mut seen: HashMap<(Uuid, Uuid), usize> = HashMap::with_capacity(lines.len());
+        for (index, line) in lines.iter().enumerate() {
+            let mut fail = |message: String| errors.push(BudgetLineError { index, message });
+
+            if line.amount < Decimal::ZERO {
+                fail(BudgetError::NegativeAmount.to_string());
+            }
+            match accounts.get(&line.account_id) {
+                None => fail(BudgetError::AccountNotFound(line.account_id).to_string()),
+                Some(false) => fail(format!("Account is inactive: {}", line.account_id)),
+                Some(true) => {}
+            }
+            if !periods.contains(&line.fiscal_period_id) {
+                fail(BudgetError::PeriodNotInFiscalYear.to_string());
+            }
+            for dim in line
+                .dimensions
+                .iter()
+                .filter(|d| !active_dimensions.contains(d))
+            {
+                fail(BudgetError::InvalidDimension(*dim).to_string());
+            }
+            if let Some(first) = seen.insert((line.account_id, line.fiscal_period_id), index) {
+                fail(format!(
+                    "Duplicate account and period, first given at index {first}"
+                ));
+            }
+        }
+
+        Ok(errors)
+    }
+
+    /// Links dimension values to a budget line.
+    async fn insert_line_dimensions(
+        txn: &DatabaseTransaction,
+        line_id: Uuid,
+        dimensions: &BTreeSet<Uuid>,
+        now: sea_orm::prelude::DateTimeWithTimeZone,
+    ) -> Result<(), BudgetError> {
+        for dim_id in dimensions {
+            budget_line_dimensions::ActiveModel {
+                id: Set(Uuid::new_v4()),
+                budget_line_id: Set(line_id),
+                dimension_value_id: Set(*dim_id),
+                created_at: Set(now),
+            }
+            .insert(txn)
+            .await?;
+        }
+        Ok(())
+    }
+
     // ========================================================================
     // Dimension Operations (Requirements 3.1-3.4)
     // ========================================================================
@@ -781,25 +1294,240 @@ impl BudgetRepository {
         Ok(entries)
     }
 
+    // ========================================================================
+    // Budget Enforcement
+    // ========================================================================
+
+    /// Finds the budget lines a transaction would take past
+    /// `threshold_percent` of their budgeted amount.
+    ///
+    /// Checks the lines of the organization's active budgets for the
+    /// transaction's fiscal period and the accounts of its entries. A line
+    /// with dimensions only counts entries tagged with all of them. The
+    /// transaction's impact on a line is the net of its matching entries in
+    /// the account's normal direction, added to the line's posted actuals
+    /// (see [`Self::calculate_actual_amount`]). Budgets in another currency
+    /// are converted at the period-end rate; lines without a rate are
+    /// skipped. Returns nothing for a transaction that does not exist.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a database query fails.
+    #[allow(clippy::too_many_lines)]
+    pub async fn check_transaction_budgets(
+        &self,
+        organization_id: Uuid,
+        transaction_id: Uuid,
+        threshold_percent: u32,
+    ) -> Result<Vec<BudgetOverrun>, BudgetError> {
+        use crate::entities::entry_dimensions;
+
+        let Some(transaction) = transactions::Entity::find_by_id(transaction_id)
+            .filter(transactions::Column::OrganizationId.eq(organization_id))
+            .one(&self.db)
+            .await?
+        else {
+            return Ok(vec![]);
+        };
+        let entries = ledger_entries::Entity::find()
+            .filter(ledger_entries::Column::TransactionId.eq(transaction.id))
+            .all(&self.db)
+            .await?;
+        let account_ids: BTreeSet<Uuid> = entries.iter().map(|e| e.account_id).collect();
+
+        let lines: Vec<(budget_lines::Model, budgets::Model)> = budget_lines::Entity::find()
+            .find_also_related(budgets::Entity)
+            .filter(budgets::Column::OrganizationId.eq(organization_id))
+            .filter(budgets::Column::IsActive.eq(true))
+            .filter(budget_lines::Column::FiscalPeriodId.eq(transaction.fiscal_period_id))
+            .filter(budget_lines::Column::AccountId.is_in(account_ids.iter().copied()))
+            .order_by_asc(budgets::Column::Name)
+            .order_by_asc(budget_lines::Column::CreatedAt)
+            .all(&self.db)
+            .await?
+            .into_iter()
+            .filter_map(|(line, budget)| Some((line, budget?)))
+            .collect();
+        if lines.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let mut line_dims: HashMap<Uuid, Vec<Uuid>> = HashMap::new();
+        for dim in budget_line_dimensions::Entity::find()
+            .filter(
+                budget_line_dimensions::Column::BudgetLineId
+                    .is_in(lines.iter().map(|(line, _)| line.id)),
+            )
+            .all(&self.db)
+            .await?
+        {
+            line_dims
+                .entry(dim.budget_line_id)
+                .or_default()
+                .push(dim.dimension_value_id);
+        }
+        let mut entry_dims: HashMap<Uuid, HashSet<Uuid>> = HashMap::new();
+        for dim in entry_dimensions::Entity::find()
+            .filter(entry_dimensions::Column::LedgerEntryId.is_in(entries.iter().map(|e| e.id)))
+            .all(&self.db)
+            .await?
+        {
+            entry_dims
+                .entry(dim.ledger_entry_id)
+                .or_default()
+                .insert(dim.dimension_value_id);
+        }
+
+        let accounts: HashMap<Uuid, chart_of_accounts::Model> = chart_of_accounts::Entity::find()
+            .filter(chart_of_accounts::Column::Id.is_in(account_ids))
+            .all(&self.db)
+            .await?
+            .into_iter()
+            .map(|a| (a.id, a))
+            .collect();
+        let period = fiscal_periods::Entity::find_by_id(transaction.fiscal_period_id)
+            .one(&self.db)
+            .await?
+            .ok_or(BudgetError::FiscalPeriodNotFound(
+                transaction.fiscal_period_id,
+            ))?;
+        let functional_currency = organizations::Entity::find_by_id(organization_id)
+            .one(&self.db)
+            .await?
+            .map(|org| org.base_currency);
+        let rate_repo = ExchangeRateRepository::new(self.db.clone());
+        let mut rates: HashMap<String, Option<AppliedRate>> = HashMap::new();
+
+        let mut overruns = Vec::new();
+        for (line, budget) in lines {
+            let Some(account) = accounts.get(&line.account_id) else {
+                continue;
+            };
+            let dims = line_dims.remove(&line.id).unwrap_or_default();
+            let matching: Vec<ledger_entries::Model> = entries
+                .iter()
+                .filter(|e| {
+                    e.account_id == line.account_id
+                        && dims
+                            .iter()
+                            .all(|d| entry_dims.get(&e.id).is_some_and(|ids| ids.contains(d)))
+                })
+                .cloned()
+                .collect();
+            if matching.is_empty() {
+                continue;
+            }
+            let impact = calculate_actual_by_account_type(&account.account_type, &matching);
+
+            let mut budgeted = line.amount;
+            if let Some(functional) = &functional_currency
+                && budget.currency != *functional
+            {
+                if !rates.contains_key(&budget.currency) {
+                    let rate = period_end_rate(
+                        &rate_repo,
+                        organization_id,
+                        &budget.currency,
+                        functional,
+                        period.end_date,
+                    )
+                    .await?;
+                    rates.insert(budget.currency.clone(), rate);
+                }
+                let Some(rate) = &rates[&budget.currency] else {
+                    continue;
+                };
+                budgeted =
+                    convert_line(ConvertSide::Budget, budgeted, Decimal::ZERO, rate.rate).budgeted;
+            }
+
+            let actual = self
+                .calculate_actual_amount(organization_id, line.account_id, period.id, &dims)
+                .await?
+                .actual;
+            let utilization = ProjectedUtilization {
+                budgeted,
+                actual,
+                impact,
+            };
+            if utilization.exceeds(threshold_percent) {
+                overruns.push(BudgetOverrun {
+                    budget_line_id: line.id,
+                    budget_id: budget.id,
+                    budget_name: budget.name,
+                    account_id: account.id,
+                    account_code: account.code.clone(),
+                    fiscal_period_id: period.id,
+                    period_name: period.name.clone(),
+                    utilization,
+                });
+            }
+        }
+
+        Ok(overruns)
+    }
+
+    /// Records whether a transaction took a budget line past its threshold
+    /// when submitted.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database update fails.
+    pub async fn record_budget_warning(
+        &self,
+        organization_id: Uuid,
+        transaction_id: Uuid,
+        exceeded: bool,
+    ) -> Result<(), BudgetError> {
+        transactions::Entity::update_many()
+            .col_expr(transactions::Column::BudgetWarning, Expr::value(exceeded))
+            .filter(transactions::Column::Id.eq(transaction_id))
+            .filter(transactions::Column::OrganizationId.eq(organization_id))
+            .exec(&self.db)
+            .await?;
+        Ok(())
+    }
+
     // ========================================================================
     // Budget vs Actual (Requirements 4.1-4.9)
     // ========================================================================
 
     /// Gets budget vs actual comparison with variance analysis.
     ///
+    /// When the budget currency differs from the organization's functional
+    /// currency, `convert` picks the side converted with the rate effective
+    /// at each period's end date. Lines without a rate keep their unconverted
+    /// amounts, carry a warning and are left out of the totals.
+    ///
     /// Requirements: 4.1, 4.4, 4.5, 4.6, 4.7, 4.8, 4.9
     ///
     /// # Errors
     ///
     /// Returns an error if the database query fails.
+    #[allow(clippy::too_many_lines)]
     pub async fn get_budget_vs_actual(
         &self,
         organization_id: Uuid,
         budget_id: Uuid,
         fiscal_period_id: Option<Uuid>,
         dimension_filters: &[Uuid],
+        convert: ConvertSide,
     ) -> Result<(Vec<BudgetLineWithActual>, BudgetVsActualSummary), BudgetError> {
         let budget = self.get_budget(organization_id, budget_id).await?;
+        let functional_currency = organizations::Entity::find_by_id(organization_id)
+            .one(&self.db)
+            .await?
+            .map_or_else(|| budget.currency.clone(), |org| org.base_currency);
+        let needs_conversion = budget.currency != functional_currency;
+        let (from_currency, to_currency) =
+            convert.rate_pair(&budget.currency, &functional_currency);
+        let currency = if needs_conversion {
+            to_currency.to_string()
+        } else {
+            budget.currency.clone()
+        };
+        let rate_repo = ExchangeRateRepository::new(self.db.clone());
+        let mut rates: HashMap<NaiveDate, Option<AppliedRate>> = HashMap::new();
 
         // Get budget lines, optionally filtered by period
         let mut query =
@@ -817,6 +1545,7 @@ impl BudgetRepository {
         let mut result = Vec::with_capacity(lines.len());
         let mut total_budgeted = Decimal::ZERO;
         let mut total_actual = Decimal::ZERO;
+        let mut unconverted_lines = 0;
 
         for line in lines {
             // Get account info
@@ -857,8 +1586,63 @@ impl BudgetRepository {
                 )
                 .await?;
 
-            let actual = actual_result.actual;
-            let budgeted = line.amount;
+            let mut actual = actual_result.actual;
+            let mut budgeted = line.amount;
+
+            // Bring both sides into one currency at the period-end rate
+            let rate = if needs_conversion {
+                if let Entry::Vacant(slot) = rates.entry(period.end_date) {
+                    slot.insert(
+                        period_end_rate(
+                            &rate_repo,
+                            organization_id,
+                            from_currency,
+                            to_currency,
+                            period.end_date,
+                        )
+                        .await?,
+                    );
+                }
+                rates[&period.end_date].clone()
+            } else {
+                None
+            };
+            let warning = match &rate {
+                Some(rate) => {
+                    let converted = convert_line(convert, budgeted, actual, rate.rate);
+                    budgeted = converted.budgeted;
+                    actual = converted.actual;
+                    None
+                }
+                None if needs_conversion => Some(missing_rate_warning(
+                    from_currency,
+                    to_currency,
+                    period.end_date,
+                )),
+                None => None,
+            };
+
+            // Get dimension info
+            let dimensions = self.get_dimension_info(&line_dims).await?;
+
+            if warning.is_some() {
+                unconverted_lines += 1;
+                result.push(BudgetLineWithActual {
+                    line,
+                    account_code: account.code,
+                    account_name: account.name,
+                    period_name: period.name,
+                    budgeted,
+                    actual,
+                    variance: None,
+                    utilization_percent: None,
+                    status: "unconverted".to_string(),
+                    dimensions,
+                    rate,
+                    warning,
+                });
+                continue;
+            }
 
             // Calculate variance based on account type (Requirements 4.4, 4.5)
             let variance = match account.account_type {
@@ -882,9 +1666,6 @@ impl BudgetRepository {
                 (actual / budgeted * Decimal::from(100)).round_dp(2)
             };
 
-            // Get dimension info
-            let dimensions = self.get_dimension_info(&line_dims).await?;
-
             total_budgeted += budgeted;
             total_actual += actual;
 
@@ -893,11 +1674,14 @@ impl BudgetRepository {
                 account_code: account.code,
                 account_name: account.name,
                 period_name: period.name,
+                budgeted,
                 actual,
-                variance,
-                utilization_percent,
+                variance: Some(variance),
+                utilization_percent: Some(utilization_percent),
                 status,
                 dimensions,
+                rate,
+                warning,
             });
         }
 
@@ -909,6 +1693,8 @@ impl BudgetRepository {
         };
 
         let summary = BudgetVsActualSummary {
+            currency,
+            unconverted_lines,
             total_budgeted,
             total_actual,
             total_variance,
@@ -918,6 +1704,208 @@ impl BudgetRepository {
         Ok((result, summary))
     }
 
+    /// Gets budget and actual totals for every period of the budget's fiscal
+    /// year, with running totals.
+    ///
+    /// Budgeted amounts come from one grouped query over the budget lines and
+    /// actuals from one grouped query over posted ledger entries for the
+    /// budget's accounts. Filters match [`Self::get_budget_vs_actual`]: a line
+    /// or entry must carry every dimension value in `dimension_filters`.
+    /// Periods without lines or entries are reported as zero. Each period is
+    /// converted at its end-date rate; a period without a rate carries a
+    /// warning and is left out of the running totals.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the budget does not exist or the database query
+    /// fails.
+    #[allow(clippy::too_many_lines)]
+    pub async fn get_budget_trend(
+        &self,
+        organization_id: Uuid,
+        budget_id: Uuid,
+        account_id: Option<Uuid>,
+        dimension_filters: &[Uuid],
+        convert: ConvertSide,
+    ) -> Result<BudgetTrend, BudgetError> {
+        use crate::entities::entry_dimensions;
+
+        let budget = self.get_budget(organization_id, budget_id).await?;
+        let functional_currency = organizations::Entity::find_by_id(organization_id)
+            .one(&self.db)
+            .await?
+            .map_or_else(|| budget.currency.clone(), |org| org.base_currency);
+        let needs_conversion = budget.currency != functional_currency;
+        let (from_currency, to_currency) =
+            convert.rate_pair(&budget.currency, &functional_currency);
+        let currency = if needs_conversion {
+            to_currency.to_string()
+        } else {
+            budget.currency.clone()
+        };
+
+        let periods = fiscal_periods::Entity::find()
+            .filter(fiscal_periods::Column::FiscalYearId.eq(budget.fiscal_year_id))
+            .order_by_asc(fiscal_periods::Column::StartDate)
+            .order_by_asc(fiscal_periods::Column::PeriodNumber)
+            .all(&self.db)
+            .await?;
+        let period_ids: Vec<Uuid> = periods.iter().map(|p| p.id).collect();
+
+        // Budgeted totals per period
+        let mut budget_query = budget_lines::Entity::find()
+            .select_only()
+            .column(budget_lines::Column::FiscalPeriodId)
+            .column_as(budget_lines::Column::Amount.sum(), "budgeted")
+            .filter(budget_lines::Column::BudgetId.eq(budget_id))
+            .group_by(budget_lines::Column::FiscalPeriodId);
+        if let Some(account_id) = account_id {
+            budget_query = budget_query.filter(budget_lines::Column::AccountId.eq(account_id));
+        }
+        for dimension_value_id in dimension_filters {
+            budget_query = budget_query.filter(
+                budget_lines::Column::Id.in_subquery(
+                    budget_line_dimensions::Entity::find()
+                        .select_only()
+                        .column(budget_line_dimensions::Column::BudgetLineId)
+                        .filter(
+                            budget_line_dimensions::Column::DimensionValueId
+                                .eq(*dimension_value_id),
+                        )
+                        .into_query(),
+                ),
+            );
+        }
+        let budgeted: HashMap<Uuid, Decimal> = budget_query
+            .into_tuple::<(Uuid, Decimal)>()
+            .all(&self.db)
+            .await?
+            .into_iter()
+            .collect();
+
+        // Posted debit and credit totals per period and account type
+        let mut actual_query = ledger_entries::Entity::find()
+            .select_only()
+            .join(
+                JoinType::InnerJoin,
+                ledger_entries::Relation::Transactions.def(),
+            )
+            .join(
+                JoinType::InnerJoin,
+                ledger_entries::Relation::ChartOfAccounts.def(),
+            )
+            .column(transactions::Column::FiscalPeriodId)
+            .column(chart_of_accounts::Column::AccountType)
+            .column_as(ledger_entries::Column::Debit.sum(), "debit")
+            .column_as(ledger_entries::Column::Credit.sum(), "credit")
+            .filter(transactions::Column::OrganizationId.eq(organization_id))
+            .filter(transactions::Column::Status.eq(TransactionStatus::Posted))
+            .filter(transactions::Column::FiscalPeriodId.is_in(period_ids))
+            .group_by(transactions::Column::FiscalPeriodId)
+            .group_by(chart_of_accounts::Column::AccountType);
+        actual_query = match account_id {
+            Some(account_id) => {
+                actual_query.filter(ledger_entries::Column::AccountId.eq(account_id))
+            }
+            None => actual_query.filter(
+                ledger_entries::Column::AccountId.in_subquery(
+                    budget_lines::Entity::find()
+                        .select_only()
+                        .column(budget_lines::Column::AccountId)
+                        .filter(budget_lines::Column::BudgetId.eq(budget_id))
+                        .into_query(),
+                ),
+            ),
+        };
+        for dimension_value_id in dimension_filters {
+            actual_query = actual_query.filter(
+                ledger_entries::Column::Id.in_subquery(
+                    entry_dimensions::Entity::find()
+                        .select_only()
+                        .column(entry_dimensions::Column::LedgerEntryId)
+                        .filter(entry_dimensions::Column::DimensionValueId.eq(*dimension_value_id))
+                        .into_query(),
+                ),
+            );
+        }
+        let mut actual: HashMap<Uuid, Decimal> = HashMap::new();
+        for (period_id, account_type, debit, credit) in actual_query
+            .into_tuple::<(Uuid, AccountType, Decimal, Decimal)>()
+            .all(&self.db)
+            .await?
+        {
+            let amount = if is_debit_normal_account(&account_type) {
+                debit - credit
+            } else {
+                credit - debit
+            };
+            *actual.entry(period_id).or_default() += amount;
+        }
+
+        let rate_repo = ExchangeRateRepository::new(self.db.clone());
+        let mut totals = Vec::with_capacity(periods.len());
+        let mut conversions = Vec::with_capacity(periods.len());
+        for period in &periods {
+            let mut period_totals = PeriodTotals::new(
+                budgeted.get(&period.id).copied().unwrap_or_default(),
+                actual.get(&period.id).copied().unwrap_or_default(),
+            );
+            let rate = if needs_conversion {
+                period_end_rate(
+                    &rate_repo,
+                    organization_id,
+                    from_currency,
+                    to_currency,
+                    period.end_date,
+                )
+                .await?
+            } else {
+                None
+            };
+            let warning = match &rate {
+                Some(rate) => {
+                    let converted = convert_line(
+                        convert,
+                        period_totals.budgeted,
+                        period_totals.actual,
+                        rate.rate,
+                    );
+                    period_totals.budgeted = converted.budgeted;
+                    period_totals.actual = converted.actual;
+                    None
+                }
+                None if needs_conversion => {
+                    period_totals.included = false;
+                    Some(missing_rate_warning(
+                        from_currency,
+                        to_currency,
+                        period.end_date,
+                    ))
+                }
+                None => None,
+            };
+            totals.push(period_totals);
+            conversions.push((rate, warning));
+        }
+
+        let periods = periods
+            .into_iter()
+            .zip(cumulative_trend(&totals))
+            .zip(conversions)
+            .map(|((period, point), (rate, warning))| BudgetTrendPeriod {
+                fiscal_period_id: period.id,
+                period_name: period.name,
+                start_date: period.start_date,
+                end_date: period.end_date,
+                point,
+                rate,
+                warning,
+            })
+            .collect();
+
+        Ok(BudgetTrend { currency, periods })
+    }
+
     // ========================================================================
     // Helper Methods
     // ========================================================================
@@ -961,6 +1949,85 @@ impl BudgetRepository {
     }
 }
 
+/// Looks up the rate effective on a period's end date.
+///
+/// Returns `None` when no rate is on file.
+async fn period_end_rate(
+    rate_repo: &ExchangeRateRepository,
+    organization_id: Uuid,
+    from_currency: &str,
+    to_currency: &str,
+    period_end: NaiveDate,
+) -> Result<Option<AppliedRate>, BudgetError> {
+    match rate_repo
+        .find_rate(
+            organization_id,
+            from_currency,
+            to_currency,
+            period_end,
+            RateLookupPolicy::DailyLatest,
+        )
+        .await
+    {
+        Ok(lookup) => Ok(Some(AppliedRate {
+            from_currency: from_currency.to_string(),
+            to_currency: to_currency.to_string(),
+            // Stored rates have 10 decimal places; inverted ones may not
+            rate: lookup.rate.round_dp(10),
+            effective_date: lookup.effective_date,
+            rate_date: period_end,
+        })),
+        Err(ExchangeRateError::Database(e)) => Err(e.into()),
+        Err(_) => Ok(None),
+    }
+}
+
+/// Whether `user_id`, holding `role`, may edit a budget with the given owner
+/// and editors.
+fn may_edit(role: UserRole, user_id: Uuid, owner_user_id: Option<Uuid>, editors: &[Uuid]) -> bool {
+    role.can_edit_any_budget() || owner_user_id == Some(user_id) || editors.contains(&user_id)
+}
+
+/// Checks that `user_id` may edit `budget`.
+async fn check_editor<C: ConnectionTrait>(
+    db: &C,
+    budget: &budgets::Model,
+    user_id: Uuid,
+) -> Result<(), BudgetError> {
+    let Some(member) = organization_users::Entity::find()
+        .filter(organization_users::Column::OrganizationId.eq(budget.organization_id))
+        .filter(organization_users::Column::UserId.eq(user_id))
+        .one(db)
+        .await?
+    else {
+        return Err(BudgetError::NotBudgetEditor);
+    };
+    let role = db_role_to_core(&member.role);
+    if may_edit(role, user_id, budget.owner_user_id, &[]) {
+        return Ok(());
+    }
+
+    let editors = load_editors(db, budget.id).await?;
+    if may_edit(role, user_id, budget.owner_user_id, &editors) {
+        Ok(())
+    } else {
+        Err(BudgetError::NotBudgetEditor)
+    }
+}
+
+/// Loads a budget's editors in the order they were added.
+async fn load_editors<C: ConnectionTrait>(db: &C, budget_id: Uuid) -> Result<Vec<Uuid>, DbErr> {
+    budget_editors::Entity::find()
+        .select_only()
+        .column(budget_editors::Column::UserId)
+        .filter(budget_editors::Column::BudgetId.eq(budget_id))
+        .order_by_asc(budget_editors::Column::CreatedAt)
+        .order_by_asc(budget_editors::Column::UserId)
+        .into_tuple()
+        .all(db)
+        .await
+}
+
 // ============================================================================
 // Helper Types
 // ============================================================================
@@ -0,0 +1,316 @@
+//! Budget comment repository: variance commentary on budget lines.
+//!
+//! Each comment belongs to one budget line and that line's fiscal period.
+//! Comments are never updated in place; editing inserts a new version and
+//! clears `is_current` on the old one, so the history of an explanation is
+//! kept.
+
+use std::collections::HashMap;
+
+use chrono::Utc;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, DatabaseConnection, DbErr, EntityTrait, JoinType, Linked,
+    QueryFilter, QueryOrder, QuerySelect, RelationDef, RelationTrait, Set, TransactionTrait,
+};
+use uuid::Uuid;
+use zeltra_core::auth::UserRole;
+use zeltra_core::budget::can_edit_commentary;
+use zeltra_core::comment::{CommentError, validate_body};
+
+use crate::entities::{budget_line_comments, budget_lines, budgets, users};
+
+/// Links a comment to its author; the comments table also references the
+/// user who last edited it, so there is no single `Related` impl.
+struct CommentAuthor;
+
+impl Linked for CommentAuthor {
+    type FromEntity = budget_line_comments::Entity;
+    type ToEntity = users::Entity;
+
+    fn link(&self) -> Vec<RelationDef> {
+        vec![budget_line_comments::Relation::Users2.def()]
+    }
+}
+
+/// Error types for budget comment operations.
+#[derive(Debug, thiserror::Error)]
+pub enum BudgetCommentError {
+    /// Budget line not found in the budget.
+    #[error("Budget line not found: {0}")]
+    LineNotFound(Uuid),
+
+    /// Comment not found on the budget line.
+    #[error("Comment not found: {0}")]
+    CommentNotFound(Uuid),
+
+    /// Only the author or an accountant may edit the comment.
+    #[error("Only the author or an accountant can edit this comment")]
+    NotAuthor,
+
+    /// The comment body is blank or too long.
+    #[error(transparent)]
+    Comment(#[from] CommentError),
+
+    /// Database error.
+    #[error("Database error: {0}")]
+    Database(#[from] DbErr),
+}
+
+/// Input for posting a comment on a budget line.
+#[derive(Debug, Clone)]
+pub struct CreateBudgetCommentInput {
+    /// Organization ID.
+    pub organization_id: Uuid,
+    /// Budget the line belongs to.
+    pub budget_id: Uuid,
+    /// Budget line the comment explains.
+    pub budget_line_id: Uuid,
+    /// Author.
+    pub author_id: Uuid,
+    /// Comment text.
+    pub body: String,
+}
+
+/// Input for editing a comment.
+#[derive(Debug, Clone)]
+pub struct EditBudgetCommentInput {
+    /// Organization ID.
+    pub organization_id: Uuid,
+    /// Budget the line belongs to.
+    pub budget_id: Uuid,
+    /// Budget line the comment is on.
+    pub budget_line_id: Uuid,
+    /// Comment being edited.
+    pub comment_id: Uuid,
+    /// Member making the edit.
+    pub editor_id: Uuid,
+    /// Editor's role in the organization.
+    pub editor_role: UserRole,
+    /// New comment text.
+    pub body: String,
+}
+
+/// One version of a comment with its author's details.
+#[derive(Debug, Clone)]
+pub struct BudgetCommentWithAuthor {
+    /// The comment version.
+    pub comment: budget_line_comments::Model,
+    /// Author's display name.
+    pub author_name: String,
+    /// Author's email address.
+    pub author_email: String,
+}
+
+/// Repository for budget line comments.
+#[derive(Debug, Clone)]
+pub struct BudgetCommentRepository {
+    db: DatabaseConnection,
+}
+
+impl BudgetCommentRepository {
+    /// Creates a new budget comment repository.
+    #[must_use]
+    pub const fn new(db: DatabaseConnection) -> Self {
+        Self { db }
+    }
+
+    /// Finds a line of a budget in an organization.
+    ///
+    /// # Errors
+    ///
+    /// Returns `LineNotFound` if the line is not on the budget or the budget
+    /// is not in the organization, or an error if the database query fails.
+    pub async fn find_line(
+        &self,
+        organization_id: Uuid,
+        budget_id: Uuid,
+        budget_line_id: Uuid,
+    ) -> Result<budget_lines::Model, BudgetCommentError> {
+        budget_lines::Entity::find_by_id(budget_line_id)
+            .join(JoinType::InnerJoin, budget_lines::Relation::Budgets.def())
+            .filter(budget_lines::Column::BudgetId.eq(budget_id))
+            .filter(budgets::Column::OrganizationId.eq(organization_id))
+            .one(&self.db)
+            .await?
+            .ok_or(BudgetCommentError::LineNotFound(budget_line_id))
+    }
+
+    /// Lists the comments on a budget line, newest first.
+    ///
+    /// Only current versions are returned unless `include_history` is set,
+    /// in which case earlier versions follow in the same order.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    pub async fn list(
+        &self,
+        organization_id: Uuid,
+        budget_line_id: Uuid,
+        include_history: bool,
+    ) -> Result<Vec<BudgetCommentWithAuthor>, DbErr> {
+        let mut query = budget_line_comments::Entity::find()
+            .filter(budget_line_comments::Column::OrganizationId.eq(organization_id))
+            .filter(budget_line_comments::Column::BudgetLineId.eq(budget_line_id));
+        if !include_history {
+            query = query.filter(budget_line_comments::Column::IsCurrent.eq(true));
+        }
+
+        Ok(query
+            .find_also_linked(CommentAuthor)
+            .order_by_desc(budget_line_comments::Column::CreatedAt)
+            .order_by_desc(budget_line_comments::Column::Id)
+            .all(&self.db)
+            .await?
+            .into_iter()
+            .map(|(comment, author)| with_author(comment, author))
+            .collect())
+    }
+
+    /// Posts a comment on a budget line for the line's fiscal period.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the body is blank or too long, the line is not
+    /// found, or a database operation fails.
+    pub async fn create(
+        &self,
+        input: CreateBudgetCommentInput,
+    ) -> Result<BudgetCommentWithAuthor, BudgetCommentError> {
+        let body = validate_body(&input.body)?;
+        let line = self
+            .find_line(input.organization_id, input.budget_id, input.budget_line_id)
+            .await?;
+
+        let id = Uuid::new_v4();
+        let comment = budget_line_comments::ActiveModel {
+            id: Set(id),
+            comment_id: Set(id),
+            organization_id: Set(input.organization_id),
+            budget_line_id: Set(line.id),
+            fiscal_period_id: Set(line.fiscal_period_id),
+            author_id: Set(input.author_id),
+            edited_by: Set(None),
+            body: Set(body),
+            is_current: Set(true),
+            created_at: Set(Utc::now().into()),
+        }
+        .insert(&self.db)
+        .await?;
+
+        self.load_author(comment).await
+    }
+
+    /// Edits a comment by recording a new version of it.
+    ///
+    /// The author may edit their own comment; anyone else needs Accountant
+    /// or above. The previous version is kept with `is_current` cleared.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CommentNotFound` if the comment is not on the line,
+    /// `NotAuthor` if the editor may not change it, or an error if the body
+    /// is invalid or a database operation fails.
+    pub async fn edit(
+        &self,
+        input: EditBudgetCommentInput,
+    ) -> Result<BudgetCommentWithAuthor, BudgetCommentError> {
+        let body = validate_body(&input.body)?;
+        self.find_line(input.organization_id, input.budget_id, input.budget_line_id)
+            .await?;
+
+        let txn = self.db.begin().await?;
+        let current = budget_line_comments::Entity::find()
+            .filter(budget_line_comments::Column::OrganizationId.eq(input.organization_id))
+            .filter(budget_line_comments::Column::BudgetLineId.eq(input.budget_line_id))
+            .filter(budget_line_comments::Column::CommentId.eq(input.comment_id))
+            .filter(budget_line_comments::Column::IsCurrent.eq(true))
+            .lock_exclusive()
+            .one(&txn)
+            .await?
+            .ok_or(BudgetCommentError::CommentNotFound(input.comment_id))?;
+
+        if !can_edit_commentary(input.editor_role, current.author_id == input.editor_id) {
+            return Err(BudgetCommentError::NotAuthor);
+        }
+
+        let version = budget_line_comments::ActiveModel {
+            id: Set(Uuid::new_v4()),
+            comment_id: Set(current.comment_id),
+            organization_id: Set(current.organization_id),
+            budget_line_id: Set(current.budget_line_id),
+            fiscal_period_id: Set(current.fiscal_period_id),
+            author_id: Set(current.author_id),
+            edited_by: Set(Some(input.editor_id)),
+            body: Set(body),
+            is_current: Set(true),
+            created_at: Set(Utc::now().into()),
+        };
+        let mut previous: budget_line_comments::ActiveModel = current.into();
+        previous.is_current = Set(false);
+        previous.update(&txn).await?;
+        let comment = version.insert(&txn).await?;
+        txn.commit().await?;
+
+        self.load_author(comment).await
+    }
+
+    /// Returns the most recent current comment on each of the lines.
+    ///
+    /// Lines without a comment are left out of the map.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    pub async fn latest_for_lines(
+        &self,
+        organization_id: Uuid,
+        budget_line_ids: &[Uuid],
+    ) -> Result<HashMap<Uuid, BudgetCommentWithAuthor>, DbErr> {
+        if budget_line_ids.is_empty() {
+            return Ok(HashMap::new());
+        }
+
+        let comments = budget_line_comments::Entity::find()
+            .filter(budget_line_comments::Column::OrganizationId.eq(organization_id))
+            .filter(budget_line_comments::Column::BudgetLineId.is_in(budget_line_ids.to_vec()))
+            .filter(budget_line_comments::Column::IsCurrent.eq(true))
+            .find_also_linked(CommentAuthor)
+            .order_by_desc(budget_line_comments::Column::CreatedAt)
+            .order_by_desc(budget_line_comments::Column::Id)
+            .all(&self.db)
+            .await?;
+
+        let mut latest = HashMap::new();
+        for (comment, author) in comments {
+            latest
+                .entry(comment.budget_line_id)
+                .or_insert_with(|| with_author(comment, author));
+        }
+        Ok(latest)
+    }
+
+    async fn load_author(
+        &self,
+        comment: budget_line_comments::Model,
+    ) -> Result<BudgetCommentWithAuthor, BudgetCommentError> {
+        let author = users::Entity::find_by_id(comment.author_id)
+            .one(&self.db)
+            .await?;
+        Ok(with_author(comment, author))
+    }
+}
+
+fn with_author(
+    comment: budget_line_comments::Model,
+    author: Option<users::Model>,
+) -> BudgetCommentWithAuthor {
+    let (author_name, author_email) = author
+        .map(|user| (user.full_name, user.email))
+        .unwrap_or_default();
+    BudgetCommentWithAuthor {
+        comment,
+        author_name,
+        author_email,
+    }
+}
@@ -43,6 +43,7 @@ fn mock_entry(debit: Decimal, credit: Decimal) -> ledger_entries::Model {
         source_currency: "USD".to_string(),
         source_amount: debit + credit,
         exchange_rate: dec!(1),
+        is_override: false,
         functional_currency: "USD".to_string(),
         functional_amount: debit + credit,
         debit,
@@ -0,0 +1,192 @@
+//! Chart-of-accounts templates.
+//!
+//! A template is a starting chart for a new organization. It is applied
+//! through the account import, so the same validation and parent handling
+//! apply as to an uploaded chart.
+
+use uuid::Uuid;
+
+use crate::entities::sea_orm_active_enums::{AccountSubtype, AccountType};
+use crate::repositories::account::{CreateAccountInput, ImportAccountInput};
+
+/// A chart of accounts entry.
+#[derive(Debug, Clone)]
+pub struct AccountTemplate {
+    /// Account code.
+    pub code: &'static str,
+    /// Account name.
+    pub name: &'static str,
+    /// Account type.
+    pub account_type: AccountType,
+    /// Account subtype.
+    pub subtype: Option<AccountSubtype>,
+    /// Whether the account is a bank account.
+    pub is_bank: bool,
+}
+
+/// Small service-business chart of accounts.
+const SERVICE: &[AccountTemplate] = &[
+    AccountTemplate {
+        code: "1000",
+        name: "Operating Bank Account",
+        account_type: AccountType::Asset,
+        subtype: Some(AccountSubtype::Bank),
+        is_bank: true,
+    },
+    AccountTemplate {
+        code: "1100",
+        name: "Accounts Receivable",
+        account_type: AccountType::Asset,
+        subtype: Some(AccountSubtype::AccountsReceivable),
+        is_bank: false,
+    },
+    AccountTemplate {
+        code: "1500",
+        name: "Equipment",
+        account_type: AccountType::Asset,
+        subtype: Some(AccountSubtype::FixedAsset),
+        is_bank: false,
+    },
+    AccountTemplate {
+        code: "2000",
+        name: "Accounts Payable",
+        account_type: AccountType::Liability,
+        subtype: Some(AccountSubtype::AccountsPayable),
+        is_bank: false,
+    },
+    AccountTemplate {
+        code: "3000",
+        name: "Owner's Capital",
+        account_type: AccountType::Equity,
+        subtype: None,
+        is_bank: false,
+    },
+    AccountTemplate {
+        code: "4000",
+        name: "Consulting Revenue",
+        account_type: AccountType::Revenue,
+        subtype: None,
+        is_bank: false,
+    },
+    AccountTemplate {
+        code: "4100",
+        name: "Subscription Revenue",
+        account_type: AccountType::Revenue,
+        subtype: None,
+        is_bank: false,
+    },
+    AccountTemplate {
+        code: "5000",
+        name: "Cost of Services",
+        account_type: AccountType::Expense,
+        subtype: None,
+        is_bank: false,
+    },
+    AccountTemplate {
+        code: "6000",
+        name: "Salaries",
+        account_type: AccountType::Expense,
+        subtype: None,
+        is_bank: false,
+    },
+    AccountTemplate {
+        code: "6100",
+        name: "Rent",
+        account_type: AccountType::Expense,
+        subtype: None,
+        is_bank: false,
+    },
+    AccountTemplate {
+        code: "6200",
+        name: "Software Subscriptions",
+        account_type: AccountType::Expense,
+        subtype: None,
+        is_bank: false,
+    },
+    AccountTemplate {
+        code: "6300",
+        name: "Marketing",
+        account_type: AccountType::Expense,
+        subtype: None,
+        is_bank: false,
+    },
+];
+
+/// A built-in chart of accounts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChartTemplate {
+    /// Small service business: one bank account, receivables, payables,
+    /// consulting and subscription revenue, common operating expenses.
+    Service,
+}
+
+impl ChartTemplate {
+    /// Every template.
+    pub const ALL: [Self; 1] = [Self::Service];
+
+    /// Parses a template code.
+    #[must_use]
+    pub fn from_code(code: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|t| t.code() == code)
+    }
+
+    /// Template code, as used in requests.
+    #[must_use]
+    pub const fn code(self) -> &'static str {
+        match self {
+            Self::Service => "service",
+        }
+    }
+
+    /// The template's accounts.
+    #[must_use]
+    pub const fn accounts(self) -> &'static [AccountTemplate] {
+        match self {
+            Self::Service => SERVICE,
+        }
+    }
+
+    /// The template as an import batch in `currency`.
+    #[must_use]
+    pub fn import_inputs(self, organization_id: Uuid, currency: &str) -> Vec<ImportAccountInput> {
+        self.accounts()
+            .iter()
+            .enumerate()
+            .map(|(idx, template)| ImportAccountInput {
+                row: idx + 1,
+                account: CreateAccountInput {
+                    organization_id,
+                    code: template.code.to_string(),
+                    name: template.name.to_string(),
+                    description: None,
+                    account_type: template.account_type.clone(),
+                    account_subtype: template.subtype.clone(),
+                    parent_id: None,
+                    currency: currency.to_string(),
+                    is_active: true,
+                    allow_direct_posting: true,
+                    is_bank_account: template.is_bank,
+                    bank_account_number: None,
+                },
+                parent_code: None,
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_template_codes_roundtrip_and_are_unique() {
+        for template in ChartTemplate::ALL {
+            assert_eq!(ChartTemplate::from_code(template.code()), Some(template));
+            let mut codes: Vec<_> = template.accounts().iter().map(|a| a.code).collect();
+            codes.sort_unstable();
+            codes.dedup();
+            assert_eq!(codes.len(), template.accounts().len());
+        }
+        assert_eq!(ChartTemplate::from_code("retail"), None);
+    }
+}
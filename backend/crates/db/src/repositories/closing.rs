@@ -0,0 +1,348 @@
+//! Closing checklist repository.
+//!
+//! Each check is a separate query over one fiscal period, so checks can be
+//! run and tested on their own and new ones added without touching the rest.
+//! Bank accounts count as reconciled for the period when a completed
+//! reconciliation has a statement date on or after the period's end.
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use chrono::NaiveDate;
+use sea_orm::{
+    ColumnTrait, DatabaseConnection, DbErr, EntityTrait, JoinType, QueryFilter, QueryOrder,
+    QuerySelect, RelationTrait, sea_query::Expr,
+};
+use uuid::Uuid;
+use zeltra_core::currency::RateLookupPolicy;
+use zeltra_core::fiscal::{CheckStatus, ClosingCheck};
+use zeltra_core::settings::OrganizationSettings;
+
+use crate::entities::{
+    chart_of_accounts, fiscal_periods, ledger_entries, organizations, reconciliations,
+    sea_orm_active_enums::{ReconciliationStatus, TransactionStatus},
+    transactions,
+};
+
+use super::exchange_rate::{ExchangeRateError, ExchangeRateRepository};
+
+/// Error types for closing checklist operations.
+#[derive(Debug, thiserror::Error)]
+pub enum ClosingError {
+    /// Exchange rate lookup failed for a reason other than a missing rate.
+    #[error("Exchange rate error: {0}")]
+    ExchangeRate(#[from] ExchangeRateError),
+
+    /// Stored organization settings could not be read.
+    #[error("Invalid organization settings: {0}")]
+    InvalidSettings(String),
+
+    /// Database error.
+    #[error("Database error: {0}")]
+    Database(#[from] DbErr),
+}
+
+/// The closing checklist of a fiscal period.
+#[derive(Debug, Clone)]
+pub struct ClosingChecklist {
+    /// Number of the period's transactions in each status.
+    pub transaction_counts: BTreeMap<&'static str, u64>,
+    /// Results of the individual checks.
+    pub checks: Vec<ClosingCheck>,
+}
+
+/// Repository running closing readiness checks.
+#[derive(Debug, Clone)]
+pub struct ClosingRepository {
+    db: DatabaseConnection,
+}
+
+impl ClosingRepository {
+    /// Creates a new closing repository.
+    #[must_use]
+    pub const fn new(db: DatabaseConnection) -> Self {
+        Self { db }
+    }
+
+    /// Runs every check for `period`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a database query fails.
+    pub async fn checklist(
+        &self,
+        period: &fiscal_periods::Model,
+    ) -> Result<ClosingChecklist, ClosingError> {
+        Ok(ClosingChecklist {
+            transaction_counts: self.transaction_counts(period.id).await?,
+            checks: vec![
+                self.check_unposted_drafts(period.id).await?,
+                self.check_pending_transactions(period.id).await?,
+                self.check_unbalanced_drafts(period.id).await?,
+                self.check_missing_exchange_rates(period).await?,
+                self.check_unreconciled_bank_accounts(period).await?,
+            ],
+        })
+    }
+
+    /// Counts the period's transactions by status, including zero counts.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    pub async fn transaction_counts(
+        &self,
+        period_id: Uuid,
+    ) -> Result<BTreeMap<&'static str, u64>, DbErr> {
+        let rows: Vec<(TransactionStatus, i64)> = transactions::Entity::find()
+            .filter(transactions::Column::FiscalPeriodId.eq(period_id))
+            .select_only()
+            .column(transactions::Column::Status)
+            .column_as(transactions::Column::Id.count(), "count")
+            .group_by(transactions::Column::Status)
+            .into_tuple()
+            .all(&self.db)
+            .await?;
+
+        let mut counts: BTreeMap<&'static str, u64> = [
+            TransactionStatus::Draft,
+            TransactionStatus::Pending,
+            TransactionStatus::Approved,
+            TransactionStatus::Posted,
+            TransactionStatus::Voided,
+        ]
+        .iter()
+        .map(|s| (status_key(s), 0))
+        .collect();
+        for (status, count) in rows {
+            counts.insert(status_key(&status), u64::try_from(count).unwrap_or(0));
+        }
+        Ok(counts)
+    }
+
+    /// Drafts in the period, which will be locked out once it closes.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    pub async fn check_unposted_drafts(&self, period_id: Uuid) -> Result<ClosingCheck, DbErr> {
+        let ids = self
+            .transaction_ids(period_id, &[TransactionStatus::Draft])
+            .await?;
+        Ok(ClosingCheck::from_offenders(
+            "unposted_drafts",
+            "Draft transactions dated in the period",
+            CheckStatus::Warn,
+            ids,
+        ))
+    }
+
+    /// Transactions awaiting approval, or approved but not posted.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    pub async fn check_pending_transactions(&self, period_id: Uuid) -> Result<ClosingCheck, DbErr> {
+        let ids = self
+            .transaction_ids(
+                period_id,
+                &[TransactionStatus::Pending, TransactionStatus::Approved],
+            )
+            .await?;
+        Ok(ClosingCheck::from_offenders(
+            "pending_transactions",
+            "Transactions awaiting approval or approved but not posted",
+            CheckStatus::Fail,
+            ids,
+        ))
+    }
+
+    /// Drafts whose debits and credits differ.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    pub async fn check_unbalanced_drafts(&self, period_id: Uuid) -> Result<ClosingCheck, DbErr> {
+        let ids: Vec<Uuid> = ledger_entries::Entity::find()
+            .join(
+                JoinType::InnerJoin,
+                ledger_entries::Relation::Transactions.def(),
+            )
+            .filter(transactions::Column::FiscalPeriodId.eq(period_id))
+            .filter(transactions::Column::Status.eq(TransactionStatus::Draft))
+            .select_only()
+            .column(ledger_entries::Column::TransactionId)
+            .group_by(ledger_entries::Column::TransactionId)
+            .having(Expr::cust(
+                "SUM(ledger_entries.debit) <> SUM(ledger_entries.credit)",
+            ))
+            .order_by_asc(ledger_entries::Column::TransactionId)
+            .into_tuple()
+            .all(&self.db)
+            .await?;
+        Ok(ClosingCheck::from_offenders(
+            "unbalanced_drafts",
+            "Draft transactions whose debits and credits differ",
+            CheckStatus::Fail,
+            ids,
+        ))
+    }
+
+    /// Foreign-currency entries with no exchange rate on their transaction
+    /// date, looked up the same way as when creating a transaction. Entries
+    /// booked at an overridden rate do not need one.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a database query fails.
+    pub async fn check_missing_exchange_rates(
+        &self,
+        period: &fiscal_periods::Model,
+    ) -> Result<ClosingCheck, ClosingError> {
+        let entries: Vec<(Uuid, String, String, NaiveDate)> = ledger_entries::Entity::find()
+            .join(
+                JoinType::InnerJoin,
+                ledger_entries::Relation::Transactions.def(),
+            )
+            .filter(transactions::Column::FiscalPeriodId.eq(period.id))
+            .filter(transactions::Column::Status.ne(TransactionStatus::Voided))
+            .filter(ledger_entries::Column::IsOverride.eq(false))
+            .filter(
+                Expr::col((
+                    ledger_entries::Entity,
+                    ledger_entries::Column::SourceCurrency,
+                ))
+                .ne(Expr::col((
+                    ledger_entries::Entity,
+                    ledger_entries::Column::FunctionalCurrency,
+                ))),
+            )
+            .select_only()
+            .column(ledger_entries::Column::Id)
+            .column(ledger_entries::Column::SourceCurrency)
+            .column(ledger_entries::Column::FunctionalCurrency)
+            .column(transactions::Column::TransactionDate)
+            .order_by_asc(transactions::Column::TransactionDate)
+            .order_by_asc(ledger_entries::Column::Id)
+            .into_tuple()
+            .all(&self.db)
+            .await?;
+
+        let policy = match organizations::Entity::find_by_id(period.organization_id)
+            .one(&self.db)
+            .await?
+        {
+            Some(org) => {
+                OrganizationSettings::from_value(&org.settings)
+                    .map_err(|e| ClosingError::InvalidSettings(e.to_string()))?
+                    .exchange_rates
+                    .lookup_policy
+            }
+            None => RateLookupPolicy::default(),
+        };
+        let rates = ExchangeRateRepository::new(self.db.clone());
+        let mut found: HashMap<(String, String, NaiveDate), bool> = HashMap::new();
+        let mut ids = Vec::new();
+        for (id, from, to, date) in entries {
+            let key = (from, to, date);
+            let has_rate = if let Some(has_rate) = found.get(&key) {
+                *has_rate
+            } else {
+                let has_rate = match rates
+                    .find_rate(period.organization_id, &key.0, &key.1, date, policy)
+                    .await
+                {
+                    Ok(_) => true,
+                    Err(ExchangeRateError::RateNotFound(..)) => false,
+                    Err(e) => return Err(e.into()),
+                };
+                found.insert(key, has_rate);
+                has_rate
+            };
+            if !has_rate {
+                ids.push(id);
+            }
+        }
+
+        Ok(ClosingCheck::from_offenders(
+            "missing_exchange_rates",
+            "Foreign-currency entries with no exchange rate on their date",
+            CheckStatus::Fail,
+            ids,
+        ))
+    }
+
+    /// Active bank accounts without a completed reconciliation covering the
+    /// period's end.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a database query fails.
+    pub async fn check_unreconciled_bank_accounts(
+        &self,
+        period: &fiscal_periods::Model,
+    ) -> Result<ClosingCheck, DbErr> {
+        let accounts: Vec<Uuid> = chart_of_accounts::Entity::find()
+            .filter(chart_of_accounts::Column::OrganizationId.eq(period.organization_id))
+            .filter(chart_of_accounts::Column::IsBankAccount.eq(true))
+            .filter(chart_of_accounts::Column::IsActive.eq(true))
+            .select_only()
+            .column(chart_of_accounts::Column::Id)
+            .order_by_asc(chart_of_accounts::Column::Code)
+            .into_tuple()
+            .all(&self.db)
+            .await?;
+
+        let reconciled: HashSet<Uuid> = reconciliations::Entity::find()
+            .filter(reconciliations::Column::OrganizationId.eq(period.organization_id))
+            .filter(reconciliations::Column::Status.eq(ReconciliationStatus::Completed))
+            .filter(reconciliations::Column::StatementDate.gte(period.end_date))
+            .select_only()
+            .column(reconciliations::Column::AccountId)
+            .distinct()
+            .into_tuple()
+            .all(&self.db)
+            .await?
+            .into_iter()
+            .collect();
+
+        let ids = accounts
+            .into_iter()
+            .filter(|id| !reconciled.contains(id))
+            .collect();
+        Ok(ClosingCheck::from_offenders(
+            "unreconciled_bank_accounts",
+            "Bank accounts with no completed reconciliation through the period end",
+            CheckStatus::Warn,
+            ids,
+        ))
+    }
+
+    /// IDs of the period's transactions in `statuses`, oldest first.
+    async fn transaction_ids(
+        &self,
+        period_id: Uuid,
+        statuses: &[TransactionStatus],
+    ) -> Result<Vec<Uuid>, DbErr> {
+        transactions::Entity::find()
+            .filter(transactions::Column::FiscalPeriodId.eq(period_id))
+            .filter(transactions::Column::Status.is_in(statuses.iter().cloned()))
+            .select_only()
+            .column(transactions::Column::Id)
+            .order_by_asc(transactions::Column::TransactionDate)
+            .order_by_asc(transactions::Column::Id)
+            .into_tuple()
+            .all(&self.db)
+            .await
+    }
+}
+
+/// API name of a transaction status.
+const fn status_key(status: &TransactionStatus) -> &'static str {
+    match status {
+        TransactionStatus::Draft => "draft",
+        TransactionStatus::Pending => "pending",
+        TransactionStatus::Approved => "approved",
+        TransactionStatus::Posted => "posted",
+        TransactionStatus::Voided => "voided",
+    }
+}
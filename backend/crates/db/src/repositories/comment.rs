@@ -0,0 +1,358 @@
+//! Comment repository: discussion threads on transactions.
+//!
+//! Members post comments and replies; the workflow copies approval notes and
+//! rejection reasons into the thread through [`record_workflow_comment`].
+//! Deleted comments are kept with `deleted_at` set so replies keep their
+//! place.
+
+use chrono::{DateTime, Utc};
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, ConnectionTrait, DatabaseConnection, DbErr, EntityTrait, Linked,
+    PaginatorTrait, QueryFilter, QueryOrder, QuerySelect, RelationDef, RelationTrait, Set,
+    prelude::DateTimeWithTimeZone,
+    sea_query::{Expr, Func},
+};
+use uuid::Uuid;
+use zeltra_core::auth::UserRole as CoreUserRole;
+use zeltra_core::comment::{CommentError, CommentKind, can_delete, validate_body};
+
+use crate::entities::{
+    organization_users, sea_orm_active_enums::UserRole, transaction_comments, transactions, users,
+};
+
+use super::workflow::EscalationRecipient;
+
+/// Links a comment to its author; the comments table also references the
+/// user who deleted it, so there is no single `Related` impl.
+struct CommentAuthor;
+
+impl Linked for CommentAuthor {
+    type FromEntity = transaction_comments::Entity;
+    type ToEntity = users::Entity;
+
+    fn link(&self) -> Vec<RelationDef> {
+        vec![transaction_comments::Relation::Users2.def()]
+    }
+}
+
+/// Error types for comment operations.
+#[derive(Debug, thiserror::Error)]
+pub enum CommentRepoError {
+    /// Transaction not found in the organization.
+    #[error("Transaction not found: {0}")]
+    TransactionNotFound(Uuid),
+
+    /// Comment not found on the transaction.
+    #[error("Comment not found: {0}")]
+    CommentNotFound(Uuid),
+
+    /// The comment being replied to is not on the transaction.
+    #[error("Parent comment not found: {0}")]
+    ParentNotFound(Uuid),
+
+    /// The comment is invalid or may not be deleted.
+    #[error(transparent)]
+    Comment(#[from] CommentError),
+
+    /// Database error.
+    #[error("Database error: {0}")]
+    Database(#[from] DbErr),
+}
+
+/// Input for posting a comment.
+#[derive(Debug, Clone)]
+pub struct CreateCommentInput {
+    /// Organization ID.
+    pub organization_id: Uuid,
+    /// Transaction the comment is on.
+    pub transaction_id: Uuid,
+    /// Author.
+    pub author_id: Uuid,
+    /// Comment text.
+    pub body: String,
+    /// Comment being replied to.
+    pub parent_comment_id: Option<Uuid>,
+}
+
+/// A comment with its author's details.
+#[derive(Debug, Clone)]
+pub struct CommentWithAuthor {
+    /// The comment.
+    pub comment: transaction_comments::Model,
+    /// Author's display name.
+    pub author_name: String,
+    /// Author's email address.
+    pub author_email: String,
+}
+
+/// One page of a transaction's comment thread.
+#[derive(Debug, Clone)]
+pub struct CommentPage {
+    /// Comments, oldest first.
+    pub comments: Vec<CommentWithAuthor>,
+    /// Total comments on the transaction, deleted ones included.
+    pub total: u64,
+}
+
+/// Repository for transaction comments.
+#[derive(Debug, Clone)]
+pub struct CommentRepository {
+    db: DatabaseConnection,
+}
+
+impl CommentRepository {
+    /// Creates a new comment repository.
+    #[must_use]
+    pub const fn new(db: DatabaseConnection) -> Self {
+        Self { db }
+    }
+
+    /// Finds a transaction in an organization.
+    ///
+    /// # Errors
+    ///
+    /// Returns `TransactionNotFound` if it is not in the organization, or an
+    /// error if the database query fails.
+    pub async fn find_transaction(
+        &self,
+        organization_id: Uuid,
+        transaction_id: Uuid,
+    ) -> Result<transactions::Model, CommentRepoError> {
+        transactions::Entity::find_by_id(transaction_id)
+            .filter(transactions::Column::OrganizationId.eq(organization_id))
+            .one(&self.db)
+            .await?
+            .ok_or(CommentRepoError::TransactionNotFound(transaction_id))
+    }
+
+    /// Lists a transaction's comments, oldest first.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    pub async fn list(
+        &self,
+        organization_id: Uuid,
+        transaction_id: Uuid,
+        page: u64,
+        limit: u64,
+    ) -> Result<CommentPage, DbErr> {
+        let thread = transaction_comments::Entity::find()
+            .filter(transaction_comments::Column::OrganizationId.eq(organization_id))
+            .filter(transaction_comments::Column::TransactionId.eq(transaction_id));
+
+        let total = thread.clone().count(&self.db).await?;
+        let comments = thread
+            .find_also_linked(CommentAuthor)
+            .order_by_asc(transaction_comments::Column::CreatedAt)
+            .order_by_asc(transaction_comments::Column::Id)
+            .offset(page.saturating_sub(1) * limit)
+            .limit(limit)
+            .all(&self.db)
+            .await?
+            .into_iter()
+            .map(|(comment, author)| with_author(comment, author))
+            .collect();
+
+        Ok(CommentPage { comments, total })
+    }
+
+    /// Posts a comment on a transaction.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the body is blank or too long, the transaction or
+    /// parent comment is not found, or a database operation fails.
+    pub async fn create(
+        &self,
+        input: CreateCommentInput,
+    ) -> Result<CommentWithAuthor, CommentRepoError> {
+        let body = validate_body(&input.body)?;
+        self.find_transaction(input.organization_id, input.transaction_id)
+            .await?;
+
+        if let Some(parent_id) = input.parent_comment_id {
+            transaction_comments::Entity::find_by_id(parent_id)
+                .filter(transaction_comments::Column::TransactionId.eq(input.transaction_id))
+                .one(&self.db)
+                .await?
+                .ok_or(CommentRepoError::ParentNotFound(parent_id))?;
+        }
+
+        let comment = transaction_comments::ActiveModel {
+            id: Set(Uuid::new_v4()),
+            organization_id: Set(input.organization_id),
+            transaction_id: Set(input.transaction_id),
+            author_id: Set(input.author_id),
+            parent_comment_id: Set(input.parent_comment_id),
+            kind: Set(CommentKind::Comment.as_str().to_string()),
+            body: Set(body),
+            created_at: Set(Utc::now().into()),
+            deleted_at: Set(None),
+            deleted_by: Set(None),
+        }
+        .insert(&self.db)
+        .await?;
+        let author = users::Entity::find_by_id(input.author_id)
+            .one(&self.db)
+            .await?;
+
+        Ok(with_author(comment, author))
+    }
+
+    /// Soft-deletes a comment.
+    ///
+    /// Owners and Admins may delete any comment; authors may delete their own
+    /// for a short while after posting. Deleting a deleted comment returns
+    /// it unchanged.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CommentNotFound` if the comment is not on the transaction,
+    /// `Comment` if the member may not delete it, or an error if a database
+    /// operation fails.
+    pub async fn delete(
+        &self,
+        organization_id: Uuid,
+        transaction_id: Uuid,
+        comment_id: Uuid,
+        user_id: Uuid,
+        role: CoreUserRole,
+        now: DateTime<Utc>,
+    ) -> Result<transaction_comments::Model, CommentRepoError> {
+        let comment = transaction_comments::Entity::find_by_id(comment_id)
+            .filter(transaction_comments::Column::OrganizationId.eq(organization_id))
+            .filter(transaction_comments::Column::TransactionId.eq(transaction_id))
+            .one(&self.db)
+            .await?
+            .ok_or(CommentRepoError::CommentNotFound(comment_id))?;
+        if comment.deleted_at.is_some() {
+            return Ok(comment);
+        }
+
+        can_delete(
+            role,
+            comment.author_id == user_id,
+            comment.created_at.with_timezone(&Utc),
+            now,
+        )?;
+
+        let mut active: transaction_comments::ActiveModel = comment.into();
+        active.deleted_at = Set(Some(now.into()));
+        active.deleted_by = Set(Some(user_id));
+        Ok(active.update(&self.db).await?)
+    }
+
+    /// Resolves mentioned emails to active members who can read the
+    /// transaction.
+    ///
+    /// Emails match case-insensitively. Unknown addresses, non-members and
+    /// Submitters mentioned on someone else's transaction are left out, as
+    /// is `exclude_user_id` (the author).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    pub async fn find_mentioned_members(
+        &self,
+        transaction: &transactions::Model,
+        emails: &[String],
+        exclude_user_id: Uuid,
+    ) -> Result<Vec<EscalationRecipient>, DbErr> {
+        if emails.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let members = organization_users::Entity::find()
+            .filter(organization_users::Column::OrganizationId.eq(transaction.organization_id))
+            .find_also_related(users::Entity)
+            .filter(
+                Expr::expr(Func::lower(Expr::col((
+                    users::Entity,
+                    users::Column::Email,
+                ))))
+                .is_in(emails.iter().map(|e| e.to_lowercase())),
+            )
+            .filter(users::Column::IsActive.eq(true))
+            .all(&self.db)
+            .await?;
+
+        let mut recipients: Vec<EscalationRecipient> = members
+            .into_iter()
+            .filter_map(|(member, user)| user.map(|user| (member, user)))
+            .filter(|(member, user)| {
+                user.id != exclude_user_id
+                    && (member.role != UserRole::Submitter || user.id == transaction.created_by)
+            })
+            .map(|(_, user)| EscalationRecipient {
+                user_id: user.id,
+                email: user.email,
+                full_name: user.full_name,
+            })
+            .collect();
+        recipients.sort_by(|a, b| a.email.cmp(&b.email));
+        Ok(recipients)
+    }
+}
+
+/// Copies an approval note or rejection reason into the transaction's
+/// comment thread, within the workflow's database transaction.
+pub(crate) async fn record_workflow_comment<C: ConnectionTrait>(
+    db: &C,
+    transaction: &transactions::Model,
+    author_id: Uuid,
+    kind: CommentKind,
+    body: &str,
+    at: DateTimeWithTimeZone,
+) -> Result<(), DbErr> {
+    record_workflow_comments(db, &[transaction], author_id, kind, body, at).await
+}
+
+/// Copies the same workflow note into several transactions' threads with a
+/// single insert.
+pub(crate) async fn record_workflow_comments<C: ConnectionTrait>(
+    db: &C,
+    transactions: &[&transactions::Model],
+    author_id: Uuid,
+    kind: CommentKind,
+    body: &str,
+    at: DateTimeWithTimeZone,
+) -> Result<(), DbErr> {
+    let body = body.trim();
+    if body.is_empty() || transactions.is_empty() {
+        return Ok(());
+    }
+
+    transaction_comments::Entity::insert_many(transactions.iter().map(|transaction| {
+        transaction_comments::ActiveModel {
+            id: Set(Uuid::new_v4()),
+            organization_id: Set(transaction.organization_id),
+            transaction_id: Set(transaction.id),
+            author_id: Set(author_id),
+            parent_comment_id: Set(None),
+            kind: Set(kind.as_str().to_string()),
+            body: Set(body.to_string()),
+            created_at: Set(at),
+            deleted_at: Set(None),
+            deleted_by: Set(None),
+        }
+    }))
+    .exec(db)
+    .await?;
+    Ok(())
+}
+
+fn with_author(
+    comment: transaction_comments::Model,
+    author: Option<users::Model>,
+) -> CommentWithAuthor {
+    let (author_name, author_email) = author.map_or_else(
+        || (String::new(), String::new()),
+        |user| (user.full_name, user.email),
+    );
+    CommentWithAuthor {
+        comment,
+        author_name,
+        author_email,
+    }
+}
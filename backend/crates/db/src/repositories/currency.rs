@@ -0,0 +1,35 @@
+//! Currency repository.
+//!
+//! Currencies are seeded by migrations and not changed at runtime, so the
+//! server loads them into a [`CurrencyRegistry`] once at startup.
+
+use sea_orm::{DatabaseConnection, DbErr, EntityTrait};
+use zeltra_core::currency::CurrencyRegistry;
+
+use crate::entities::currencies;
+
+/// Repository for the currencies table.
+#[derive(Debug, Clone)]
+pub struct CurrencyRepository {
+    db: DatabaseConnection,
+}
+
+impl CurrencyRepository {
+    /// Creates a new currency repository.
+    #[must_use]
+    pub const fn new(db: DatabaseConnection) -> Self {
+        Self { db }
+    }
+
+    /// Loads the decimal places of every currency.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    pub async fn registry(&self) -> Result<CurrencyRegistry, DbErr> {
+        let rows = currencies::Entity::find().all(&self.db).await?;
+        Ok(CurrencyRegistry::new(rows.into_iter().map(|c| {
+            (c.code, u32::try_from(c.decimal_places).unwrap_or(2))
+        })))
+    }
+}
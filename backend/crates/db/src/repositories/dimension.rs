@@ -2,13 +2,22 @@
 //!
 //! Implements Requirements 3.1-3.6 for dimension management.
 
+use chrono::{DateTime, NaiveDate, Utc};
 use sea_orm::{
-    ActiveModelTrait, ColumnTrait, DatabaseConnection, DbErr, EntityTrait, PaginatorTrait,
-    QueryFilter, QueryOrder, Set,
+    ActiveModelTrait, ColumnTrait, ConnectionTrait, DatabaseConnection, DbErr, EntityTrait,
+    JoinType, PaginatorTrait, QueryFilter, QueryOrder, QuerySelect, QueryTrait, RelationTrait,
+    Select, Set, TransactionTrait, sea_query::Expr,
 };
 use uuid::Uuid;
 
-use crate::entities::{dimension_types, dimension_values};
+use super::subscription::{LimitCheckResult, ResourceLimit, SubscriptionRepository};
+use crate::entities::{
+    account_default_dimensions, chart_of_accounts, dimension_retags, dimension_types,
+    dimension_values, entry_dimensions, ledger_entries, transactions,
+};
+
+/// Transactions listed in a re-tag preview.
+const RETAG_SAMPLE_SIZE: u64 = 20;
 
 /// Error types for dimension operations.
 #[derive(Debug, thiserror::Error)]
@@ -37,6 +46,38 @@ pub enum DimensionError {
     #[error("Parent dimension value belongs to different type")]
     ParentWrongType,
 
+    /// Account not found in the organization.
+    #[error("Account not found: {0}")]
+    AccountNotFound(Uuid),
+
+    /// Dimension value is inactive.
+    #[error("Dimension value is inactive: {0}")]
+    ValueInactive(Uuid),
+
+    /// Account already has a default for the value's dimension type.
+    #[error("Account already has a default for dimension type '{0}'")]
+    DefaultAlreadySet(String),
+
+    /// Account has no such default.
+    #[error("Default dimension value not found: {0}")]
+    DefaultNotFound(Uuid),
+
+    /// Re-tag source and target are the same value.
+    #[error("Source and target dimension values must be different")]
+    RetagSameValue,
+
+    /// Re-tag source and target belong to different dimension types.
+    #[error("Source and target dimension values belong to different types")]
+    RetagTypeMismatch,
+
+    /// Date range ends before it starts.
+    #[error("Date range ends before it starts")]
+    InvalidDateRange,
+
+    /// The organization's plan does not allow another one.
+    #[error("Tier limit exceeded for {}", .0.resource.as_str())]
+    TierLimitExceeded(LimitCheckResult),
+
     /// Database error.
     #[error("Database error: {0}")]
     Database(#[from] DbErr),
@@ -125,6 +166,8 @@ pub struct UpdateDimensionValueInput {
 pub struct DimensionTypeFilter {
     /// Filter by active status.
     pub is_active: Option<bool>,
+    /// Only dimension types changed after this time.
+    pub modified_since: Option<DateTime<Utc>>,
 }
 
 /// Filter options for listing dimension values.
@@ -136,6 +179,64 @@ pub struct DimensionValueFilter {
     pub is_active: Option<bool>,
     /// Filter by parent ID (None = root values only).
     pub parent_id: Option<Option<Uuid>>,
+    /// Only dimension values changed after this time.
+    pub modified_since: Option<DateTime<Utc>>,
+}
+
+/// A default dimension value of an account.
+#[derive(Debug, Clone)]
+pub struct AccountDefaultDimension {
+    /// Stored default.
+    pub default: account_default_dimensions::Model,
+    /// The dimension value applied.
+    pub value: dimension_values::Model,
+}
+
+/// Input for moving entry dimensions from one value to another.
+#[derive(Debug, Clone)]
+pub struct RetagEntriesInput {
+    /// Value the entries are tagged with now.
+    pub from_value_id: Uuid,
+    /// Value to tag them with instead; must be of the same dimension type.
+    pub to_value_id: Uuid,
+    /// Only entries of transactions dated on or after this date.
+    pub date_from: Option<NaiveDate>,
+    /// Only entries of transactions dated on or before this date.
+    pub date_to: Option<NaiveDate>,
+    /// Only entries on these accounts (empty = all accounts).
+    pub account_ids: Vec<Uuid>,
+    /// Why the entries were re-tagged.
+    pub reason: Option<String>,
+    /// User re-tagging the entries.
+    pub retagged_by: Uuid,
+    /// Count and sample the entries without changing them.
+    pub dry_run: bool,
+}
+
+/// A transaction with entries matched by a re-tag.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RetagTransaction {
+    /// Transaction ID.
+    pub transaction_id: Uuid,
+    /// Reference number.
+    pub reference_number: Option<String>,
+    /// Transaction date.
+    pub transaction_date: NaiveDate,
+    /// Description.
+    pub description: String,
+    /// Entries of the transaction tagged with the source value.
+    pub entry_count: i64,
+}
+
+/// Outcome of a re-tag or its dry run.
+#[derive(Debug, Clone)]
+pub struct RetagResult {
+    /// Entries tagged with the source value that match the filters.
+    pub affected_entries: u64,
+    /// The first matching transactions by date.
+    pub sample: Vec<RetagTransaction>,
+    /// The recorded re-tag, `None` for a dry run.
+    pub retag: Option<dimension_retags::Model>,
 }
 
 /// Dimension repository for CRUD operations.
@@ -163,36 +264,14 @@ impl DimensionRepository {
     ///
     /// Returns an error if:
     /// - Dimension type code already exists in organization
+    /// - An active type would exceed the organization's tier limit
     pub async fn create_dimension_type(
         &self,
         input: CreateDimensionTypeInput,
     ) -> Result<dimension_types::Model, DimensionError> {
-        // Validate unique code within organization (Requirement 3.2)
-        let existing = dimension_types::Entity::find()
-            .filter(dimension_types::Column::OrganizationId.eq(input.organization_id))
-            .filter(dimension_types::Column::Code.eq(&input.code))
-            .one(&self.db)
-            .await?;
-
-        if existing.is_some() {
-            return Err(DimensionError::DuplicateTypeCode(input.code));
-        }
-
-        let now = chrono::Utc::now().into();
-        let dimension_type = dimension_types::ActiveModel {
-            id: Set(Uuid::new_v4()),
-            organization_id: Set(input.organization_id),
-            code: Set(input.code),
-            name: Set(input.name),
-            description: Set(input.description),
-            is_required: Set(input.is_required),
-            is_active: Set(input.is_active),
-            sort_order: Set(input.sort_order),
-            created_at: Set(now),
-            updated_at: Set(now),
-        };
-
-        let result = dimension_type.insert(&self.db).await?;
+        let txn = self.db.begin().await?;
+        let result = insert_dimension_type(&txn, input).await?;
+        txn.commit().await?;
         Ok(result)
     }
 
@@ -217,6 +296,10 @@ impl DimensionRepository {
             query = query.filter(dimension_types::Column::IsActive.eq(is_active));
         }
 
+        if let Some(since) = filter.modified_since {
+            query = query.filter(dimension_types::Column::UpdatedAt.gt(since));
+        }
+
         let results = query.all(&self.db).await?;
         Ok(results)
     }
@@ -236,6 +319,24 @@ impl DimensionRepository {
         Ok(result)
     }
 
+    /// Finds a dimension type in an organization by code.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    pub async fn find_dimension_type_by_code(
+        &self,
+        organization_id: Uuid,
+        code: &str,
+    ) -> Result<Option<dimension_types::Model>, DimensionError> {
+        let result = dimension_types::Entity::find()
+            .filter(dimension_types::Column::OrganizationId.eq(organization_id))
+            .filter(dimension_types::Column::Code.eq(code))
+            .one(&self.db)
+            .await?;
+        Ok(result)
+    }
+
     /// Updates a dimension type.
     ///
     /// # Errors
@@ -243,13 +344,15 @@ impl DimensionRepository {
     /// Returns an error if:
     /// - Dimension type not found
     /// - New code already exists in organization
+    /// - Reactivating the type would exceed the organization's tier limit
     pub async fn update_dimension_type(
         &self,
         id: Uuid,
         input: UpdateDimensionTypeInput,
     ) -> Result<dimension_types::Model, DimensionError> {
+        let txn = self.db.begin().await?;
         let dim_type = dimension_types::Entity::find_by_id(id)
-            .one(&self.db)
+            .one(&txn)
             .await?
             .ok_or(DimensionError::TypeNotFound(id))?;
 
@@ -261,7 +364,7 @@ impl DimensionRepository {
                 .filter(dimension_types::Column::OrganizationId.eq(dim_type.organization_id))
                 .filter(dimension_types::Column::Code.eq(new_code))
                 .filter(dimension_types::Column::Id.ne(id))
-                .one(&self.db)
+                .one(&txn)
                 .await?;
 
             if existing.is_some() {
@@ -269,6 +372,19 @@ impl DimensionRepository {
             }
         }
 
+        // Reactivating counts against the limit like creating
+        if input.is_active == Some(true) && !dim_type.is_active {
+            let limit = SubscriptionRepository::check_limit(
+                &txn,
+                dim_type.organization_id,
+                ResourceLimit::Dimensions,
+            )
+            .await?;
+            if !limit.allowed {
+                return Err(DimensionError::TierLimitExceeded(limit));
+            }
+        }
+
         let now = chrono::Utc::now().into();
         let mut active: dimension_types::ActiveModel = dim_type.into();
 
@@ -292,7 +408,8 @@ impl DimensionRepository {
         }
         active.updated_at = Set(now);
 
-        let updated = active.update(&self.db).await?;
+        let updated = active.update(&txn).await?;
+        txn.commit().await?;
         Ok(updated)
     }
 
@@ -395,6 +512,10 @@ impl DimensionRepository {
             query = query.filter(dimension_values::Column::IsActive.eq(is_active));
         }
 
+        if let Some(since) = filter.modified_since {
+            query = query.filter(dimension_values::Column::UpdatedAt.gt(since));
+        }
+
         if let Some(parent_id) = filter.parent_id {
             match parent_id {
                 Some(pid) => {
@@ -506,6 +627,278 @@ impl DimensionRepository {
         Ok(updated)
     }
 
+    // ========================================================================
+    // Account Default Operations
+    // ========================================================================
+
+    /// Lists an account's default dimension values.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    pub async fn list_account_defaults(
+        &self,
+        organization_id: Uuid,
+        account_id: Uuid,
+    ) -> Result<Vec<AccountDefaultDimension>, DimensionError> {
+        let defaults = account_default_dimensions::Entity::find()
+            .filter(account_default_dimensions::Column::OrganizationId.eq(organization_id))
+            .filter(account_default_dimensions::Column::AccountId.eq(account_id))
+            .order_by_asc(account_default_dimensions::Column::CreatedAt)
+            .find_also_related(dimension_values::Entity)
+            .all(&self.db)
+            .await?
+            .into_iter()
+            .filter_map(|(default, value)| {
+                value.map(|value| AccountDefaultDimension { default, value })
+            })
+            .collect();
+        Ok(defaults)
+    }
+
+    /// Adds a default dimension value to an account.
+    ///
+    /// Values of other organizations are reported as not found.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - The account or value does not exist in the organization
+    /// - The value is inactive
+    /// - The account already has a default for the value's dimension type
+    pub async fn add_account_default(
+        &self,
+        organization_id: Uuid,
+        account_id: Uuid,
+        dimension_value_id: Uuid,
+    ) -> Result<AccountDefaultDimension, DimensionError> {
+        chart_of_accounts::Entity::find_by_id(account_id)
+            .filter(chart_of_accounts::Column::OrganizationId.eq(organization_id))
+            .one(&self.db)
+            .await?
+            .ok_or(DimensionError::AccountNotFound(account_id))?;
+
+        let value = dimension_values::Entity::find_by_id(dimension_value_id)
+            .filter(dimension_values::Column::OrganizationId.eq(organization_id))
+            .one(&self.db)
+            .await?
+            .ok_or(DimensionError::ValueNotFound(dimension_value_id))?;
+        if !value.is_active {
+            return Err(DimensionError::ValueInactive(dimension_value_id));
+        }
+
+        // One default per dimension type
+        let existing = self
+            .list_account_defaults(organization_id, account_id)
+            .await?;
+        if existing
+            .iter()
+            .any(|d| d.value.dimension_type_id == value.dimension_type_id)
+        {
+            let type_code = dimension_types::Entity::find_by_id(value.dimension_type_id)
+                .one(&self.db)
+                .await?
+                .map_or_else(|| value.dimension_type_id.to_string(), |t| t.code);
+            return Err(DimensionError::DefaultAlreadySet(type_code));
+        }
+
+        let default = account_default_dimensions::ActiveModel {
+            id: Set(Uuid::new_v4()),
+            organization_id: Set(organization_id),
+            account_id: Set(account_id),
+            dimension_value_id: Set(dimension_value_id),
+            created_at: Set(chrono::Utc::now().into()),
+        }
+        .insert(&self.db)
+        .await?;
+
+        Ok(AccountDefaultDimension { default, value })
+    }
+
+    /// Removes a default dimension value from an account.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the account has no such default or the database
+    /// operation fails.
+    pub async fn remove_account_default(
+        &self,
+        organization_id: Uuid,
+        account_id: Uuid,
+        dimension_value_id: Uuid,
+    ) -> Result<(), DimensionError> {
+        let result = account_default_dimensions::Entity::delete_many()
+            .filter(account_default_dimensions::Column::OrganizationId.eq(organization_id))
+            .filter(account_default_dimensions::Column::AccountId.eq(account_id))
+            .filter(account_default_dimensions::Column::DimensionValueId.eq(dimension_value_id))
+            .exec(&self.db)
+            .await?;
+
+        if result.rows_affected == 0 {
+            return Err(DimensionError::DefaultNotFound(dimension_value_id));
+        }
+        Ok(())
+    }
+
+    // ========================================================================
+    // Re-tagging
+    // ========================================================================
+
+    /// Moves ledger entries tagged with one dimension value to another value
+    /// of the same type, e.g. after merging two cost centers.
+    ///
+    /// Only the tags change: debits, credits and account balances stay as
+    /// they are, so transactions keep their status. Entries already tagged
+    /// with the target just lose the source tag. Each re-tag is recorded in
+    /// `dimension_retags`; a dry run only counts and samples the entries.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - Either value or a filtered account does not exist in the organization
+    /// - The values are the same or of different dimension types
+    /// - The target value is inactive
+    /// - The date range ends before it starts
+    #[allow(clippy::too_many_lines)]
+    pub async fn retag_entries(
+        &self,
+        organization_id: Uuid,
+        input: RetagEntriesInput,
+    ) -> Result<RetagResult, DimensionError> {
+        if input.from_value_id == input.to_value_id {
+            return Err(DimensionError::RetagSameValue);
+        }
+        if let (Some(from), Some(to)) = (input.date_from, input.date_to)
+            && to < from
+        {
+            return Err(DimensionError::InvalidDateRange);
+        }
+
+        let value_in_org = |id: Uuid| {
+            dimension_values::Entity::find_by_id(id)
+                .filter(dimension_values::Column::OrganizationId.eq(organization_id))
+                .one(&self.db)
+        };
+        let from = value_in_org(input.from_value_id)
+            .await?
+            .ok_or(DimensionError::ValueNotFound(input.from_value_id))?;
+        let to = value_in_org(input.to_value_id)
+            .await?
+            .ok_or(DimensionError::ValueNotFound(input.to_value_id))?;
+        if from.dimension_type_id != to.dimension_type_id {
+            return Err(DimensionError::RetagTypeMismatch);
+        }
+        if !to.is_active {
+            return Err(DimensionError::ValueInactive(to.id));
+        }
+        for &account_id in &input.account_ids {
+            chart_of_accounts::Entity::find_by_id(account_id)
+                .filter(chart_of_accounts::Column::OrganizationId.eq(organization_id))
+                .one(&self.db)
+                .await?
+                .ok_or(DimensionError::AccountNotFound(account_id))?;
+        }
+
+        let txn = self.db.begin().await?;
+        let matched = || retag_query(organization_id, &input);
+        let affected_entries = matched().count(&txn).await?;
+
+        let rows: Vec<(Uuid, Option<String>, NaiveDate, String, i64)> = matched()
+            .select_only()
+            .column(transactions::Column::Id)
+            .column(transactions::Column::ReferenceNumber)
+            .column(transactions::Column::TransactionDate)
+            .column(transactions::Column::Description)
+            .column_as(
+                Expr::col((entry_dimensions::Entity, entry_dimensions::Column::Id)).count(),
+                "entry_count",
+            )
+            .group_by(transactions::Column::Id)
+            .order_by_asc(transactions::Column::TransactionDate)
+            .order_by_asc(transactions::Column::Id)
+            .limit(RETAG_SAMPLE_SIZE)
+            .into_tuple()
+            .all(&txn)
+            .await?;
+        let sample = rows
+            .into_iter()
+            .map(
+                |(transaction_id, reference_number, transaction_date, description, entry_count)| {
+                    RetagTransaction {
+                        transaction_id,
+                        reference_number,
+                        transaction_date,
+                        description,
+                        entry_count,
+                    }
+                },
+            )
+            .collect();
+
+        if input.dry_run {
+            return Ok(RetagResult {
+                affected_entries,
+                sample,
+                retag: None,
+            });
+        }
+
+        // An entry carries a value at most once, so drop the source tag where
+        // the entry already has the target before moving the rest.
+        let matched_entries = || {
+            matched()
+                .select_only()
+                .column(entry_dimensions::Column::LedgerEntryId)
+                .into_query()
+        };
+        entry_dimensions::Entity::delete_many()
+            .filter(entry_dimensions::Column::DimensionValueId.eq(from.id))
+            .filter(entry_dimensions::Column::LedgerEntryId.in_subquery(matched_entries()))
+            .filter(
+                entry_dimensions::Column::LedgerEntryId.in_subquery(
+                    entry_dimensions::Entity::find()
+                        .select_only()
+                        .column(entry_dimensions::Column::LedgerEntryId)
+                        .filter(entry_dimensions::Column::DimensionValueId.eq(to.id))
+                        .into_query(),
+                ),
+            )
+            .exec(&txn)
+            .await?;
+        entry_dimensions::Entity::update_many()
+            .col_expr(
+                entry_dimensions::Column::DimensionValueId,
+                Expr::value(to.id),
+            )
+            .filter(entry_dimensions::Column::DimensionValueId.eq(from.id))
+            .filter(entry_dimensions::Column::LedgerEntryId.in_subquery(matched_entries()))
+            .exec(&txn)
+            .await?;
+
+        let retag = dimension_retags::ActiveModel {
+            id: Set(Uuid::new_v4()),
+            organization_id: Set(organization_id),
+            from_value_id: Set(from.id),
+            to_value_id: Set(to.id),
+            date_from: Set(input.date_from),
+            date_to: Set(input.date_to),
+            account_ids: Set(input.account_ids),
+            affected_entries: Set(i64::try_from(affected_entries).unwrap_or(i64::MAX)),
+            reason: Set(input.reason),
+            retagged_by: Set(input.retagged_by),
+            created_at: Set(Utc::now().into()),
+        }
+        .insert(&txn)
+        .await?;
+        txn.commit().await?;
+
+        Ok(RetagResult {
+            affected_entries,
+            sample,
+            retag: Some(retag),
+        })
+    }
+
     /// Checks if a dimension type code exists in an organization.
     ///
     /// # Errors
@@ -545,6 +938,35 @@ impl DimensionRepository {
     }
 }
 
+/// Entry dimensions tagged with the re-tag's source value that match its
+/// filters.
+fn retag_query(
+    organization_id: Uuid,
+    input: &RetagEntriesInput,
+) -> Select<entry_dimensions::Entity> {
+    let mut query = entry_dimensions::Entity::find()
+        .join(
+            JoinType::InnerJoin,
+            entry_dimensions::Relation::LedgerEntries.def(),
+        )
+        .join(
+            JoinType::InnerJoin,
+            ledger_entries::Relation::Transactions.def(),
+        )
+        .filter(entry_dimensions::Column::DimensionValueId.eq(input.from_value_id))
+        .filter(transactions::Column::OrganizationId.eq(organization_id));
+    if let Some(from) = input.date_from {
+        query = query.filter(transactions::Column::TransactionDate.gte(from));
+    }
+    if let Some(to) = input.date_to {
+        query = query.filter(transactions::Column::TransactionDate.lte(to));
+    }
+    if !input.account_ids.is_empty() {
+        query = query.filter(ledger_entries::Column::AccountId.is_in(input.account_ids.clone()));
+    }
+    query
+}
+
 // ============================================================================
 // Pure validation functions for property testing
 // ============================================================================
@@ -599,6 +1021,59 @@ pub fn is_value_code_unique<S: std::hash::BuildHasher>(
     !existing_codes.contains(&entry)
 }
 
+/// Creates a dimension type on the given connection.
+///
+/// Shared by [`DimensionRepository::create_dimension_type`] and organization
+/// bootstrap, which runs it inside its own database transaction.
+///
+/// # Errors
+///
+/// Returns an error if the code is taken, an active type would exceed the
+/// tier limit, or a database operation fails.
+pub(crate) async fn insert_dimension_type<C: ConnectionTrait>(
+    conn: &C,
+    input: CreateDimensionTypeInput,
+) -> Result<dimension_types::Model, DimensionError> {
+    // Validate unique code within organization (Requirement 3.2)
+    let existing = dimension_types::Entity::find()
+        .filter(dimension_types::Column::OrganizationId.eq(input.organization_id))
+        .filter(dimension_types::Column::Code.eq(&input.code))
+        .one(conn)
+        .await?;
+
+    if existing.is_some() {
+        return Err(DimensionError::DuplicateTypeCode(input.code));
+    }
+
+    if input.is_active {
+        let limit = SubscriptionRepository::check_limit(
+            conn,
+            input.organization_id,
+            ResourceLimit::Dimensions,
+        )
+        .await?;
+        if !limit.allowed {
+            return Err(DimensionError::TierLimitExceeded(limit));
+        }
+    }
+
+    let now = chrono::Utc::now().into();
+    let dimension_type = dimension_types::ActiveModel {
+        id: Set(Uuid::new_v4()),
+        organization_id: Set(input.organization_id),
+        code: Set(input.code),
+        name: Set(input.name),
+        description: Set(input.description),
+        is_required: Set(input.is_required),
+        is_active: Set(input.is_active),
+        sort_order: Set(input.sort_order),
+        created_at: Set(now),
+        updated_at: Set(now),
+    };
+
+    Ok(dimension_type.insert(conn).await?)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
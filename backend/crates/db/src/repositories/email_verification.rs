@@ -9,6 +9,15 @@ use uuid::Uuid;
 
 use crate::entities::{email_verification_tokens, users};
 
+/// Outcome of using a verification token.
+#[derive(Debug, Clone)]
+pub struct VerifiedEmail {
+    /// The user, with the verified address.
+    pub user: users::Model,
+    /// The address replaced, when the token confirmed an email change.
+    pub previous_email: Option<String>,
+}
+
 /// Email verification repository for CRUD operations.
 #[derive(Debug, Clone)]
 pub struct EmailVerificationRepository {
@@ -45,6 +54,32 @@ impl EmailVerificationRepository {
     ///
     /// Returns an error if the database insert fails.
     pub async fn create_token(&self, user_id: Uuid) -> Result<String, DbErr> {
+        self.insert_token(user_id, None).await
+    }
+
+    /// Creates a token confirming a change of the user's email to `new_email`.
+    /// Returns the raw token to be sent to the new address.
+    ///
+    /// The user's email is unchanged until the token is verified.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database insert fails.
+    pub async fn create_email_change_token(
+        &self,
+        user_id: Uuid,
+        new_email: &str,
+    ) -> Result<String, DbErr> {
+        self.insert_token(user_id, Some(new_email.to_string()))
+            .await
+    }
+
+    /// Replaces the user's outstanding tokens with a new one.
+    async fn insert_token(
+        &self,
+        user_id: Uuid,
+        new_email: Option<String>,
+    ) -> Result<String, DbErr> {
         // Invalidate any existing tokens for this user
         self.invalidate_user_tokens(user_id).await?;
 
@@ -60,6 +95,7 @@ impl EmailVerificationRepository {
             expires_at: Set(expires_at.into()),
             used_at: Set(None),
             created_at: Set(now.into()),
+            new_email: Set(new_email),
         };
 
         token.insert(&self.db).await?;
@@ -68,12 +104,14 @@ impl EmailVerificationRepository {
     }
 
     /// Verifies a token and marks the user's email as verified.
-    /// Returns the user if verification succeeds.
+    ///
+    /// An email-change token also switches the user to the new address.
     ///
     /// # Errors
     ///
-    /// Returns an error if the token is invalid, expired, or already used.
-    pub async fn verify_token(&self, raw_token: &str) -> Result<users::Model, DbErr> {
+    /// Returns an error if the token is invalid, expired, or already used,
+    /// or if the new address was registered since the change was requested.
+    pub async fn verify_token(&self, raw_token: &str) -> Result<VerifiedEmail, DbErr> {
         let token_hash = Self::hash_token(raw_token);
         let now = Utc::now();
 
@@ -91,18 +129,36 @@ impl EmailVerificationRepository {
         token_active.used_at = Set(Some(now.into()));
         token_active.update(&self.db).await?;
 
-        // Update user's email_verified_at
+        // Update user's email_verified_at, and the email itself for a change
         let user = users::Entity::find_by_id(token.user_id)
             .one(&self.db)
             .await?
             .ok_or_else(|| DbErr::Custom("User not found".to_string()))?;
 
-        let mut user_active: users::ActiveModel = user.into();
+        let mut user_active: users::ActiveModel = user.clone().into();
+        let previous_email = match token.new_email {
+            Some(new_email) => {
+                let in_use = users::Entity::find()
+                    .filter(users::Column::Email.eq(&new_email))
+                    .one(&self.db)
+                    .await?
+                    .is_some();
+                if in_use {
+                    return Err(DbErr::Custom("Email already in use".to_string()));
+                }
+                user_active.email = Set(new_email);
+                Some(user.email)
+            }
+            None => None,
+        };
         user_active.email_verified_at = Set(Some(now.into()));
         user_active.updated_at = Set(now.into());
         let updated_user = user_active.update(&self.db).await?;
 
-        Ok(updated_user)
+        Ok(VerifiedEmail {
+            user: updated_user,
+            previous_email,
+        })
     }
 
     /// Invalidates all existing tokens for a user.
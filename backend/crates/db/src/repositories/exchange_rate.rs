@@ -5,12 +5,18 @@
 use chrono::NaiveDate;
 use rust_decimal::Decimal;
 use sea_orm::{
-    ActiveModelTrait, ColumnTrait, DatabaseConnection, DbErr, EntityTrait, QueryFilter, QueryOrder,
-    Set,
+    ActiveModelTrait, ColumnTrait, ConnectionTrait, DatabaseConnection, DbErr, EntityTrait,
+    JoinType, PaginatorTrait, QueryFilter, QueryOrder, QuerySelect, RelationTrait, Select, Set,
+    TransactionTrait, sea_query::Expr,
 };
 use uuid::Uuid;
+use zeltra_core::currency::RateLookupPolicy;
 
-use crate::entities::{currencies, exchange_rates, sea_orm_active_enums::RateSource};
+use crate::entities::{
+    currencies, exchange_rates, ledger_entries, rate_corrections,
+    sea_orm_active_enums::{RateSource, TransactionStatus},
+    transactions,
+};
 
 /// Error types for exchange rate operations.
 #[derive(Debug, thiserror::Error)]
@@ -27,10 +33,22 @@ pub enum ExchangeRateError {
     #[error("Currency '{0}' not found")]
     CurrencyNotFound(String),
 
+    /// Rates may not be entered ahead of their effective date.
+    #[error("Exchange rate effective date {0} is in the future")]
+    FutureEffectiveDate(NaiveDate),
+
     /// Exchange rate not found.
     #[error("No exchange rate found for {0}/{1} on or before {2}")]
     RateNotFound(String, String, NaiveDate),
 
+    /// No stored rate with this ID in the organization.
+    #[error("Exchange rate {0} not found")]
+    NotFound(Uuid),
+
+    /// Posted entries used the rate, so it is only deleted when forced.
+    #[error("Exchange rate was used by {0} posted ledger entries")]
+    InUse(u64),
+
     /// Database error.
     #[error("Database error: {0}")]
     Database(#[from] DbErr),
@@ -57,6 +75,62 @@ pub struct CreateExchangeRateInput {
     pub created_by: Option<Uuid>,
 }
 
+/// How much a stored rate was used by posted ledger entries.
+///
+/// An entry used the rate when it converts the rate's source currency to its
+/// target currency at exactly that rate, on a transaction dated in the
+/// rate's window: from its effective date until the pair's next rate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RateUsage {
+    /// First transaction date the rate applied to.
+    pub window_start: NaiveDate,
+    /// Effective date of the next rate for the pair, if any (exclusive).
+    pub window_end: Option<NaiveDate>,
+    /// Posted ledger entries that used the rate.
+    pub entry_count: u64,
+}
+
+/// A posted transaction with entries converted at a rate.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RateUsageTransaction {
+    /// Transaction ID.
+    pub transaction_id: Uuid,
+    /// Reference number.
+    pub reference_number: Option<String>,
+    /// Transaction date.
+    pub transaction_date: NaiveDate,
+    /// Description.
+    pub description: String,
+    /// Entries converted at the rate.
+    pub entry_count: i64,
+    /// Total source amount of those entries.
+    pub source_amount: Decimal,
+    /// Total functional amount of those entries.
+    pub functional_amount: Decimal,
+}
+
+/// Input for correcting a stored rate.
+#[derive(Debug, Clone)]
+pub struct CorrectExchangeRateInput {
+    /// The corrected rate.
+    pub rate: Decimal,
+    /// Why the rate was corrected.
+    pub reason: Option<String>,
+    /// User making the correction.
+    pub corrected_by: Uuid,
+}
+
+/// A corrected rate with its history record.
+#[derive(Debug, Clone)]
+pub struct CorrectedRate {
+    /// The rate after correction.
+    pub rate: exchange_rates::Model,
+    /// The recorded correction.
+    pub correction: rate_corrections::Model,
+    /// Usage of the old value.
+    pub usage: RateUsage,
+}
+
 /// Result of an exchange rate lookup.
 #[derive(Debug, Clone)]
 pub struct ExchangeRateLookup {
@@ -66,6 +140,16 @@ pub struct ExchangeRateLookup {
     pub lookup_method: RateLookupMethod,
     /// The effective date of the rate.
     pub effective_date: NaiveDate,
+    /// Days between the effective date and the date looked up.
+    pub staleness_days: i64,
+}
+
+impl ExchangeRateLookup {
+    /// Returns true if the rate is more than `max_staleness_days` old.
+    #[must_use]
+    pub fn is_stale(&self, max_staleness_days: u32) -> bool {
+        self.staleness_days > i64::from(max_staleness_days)
+    }
 }
 
 /// How an exchange rate was obtained.
@@ -102,6 +186,7 @@ impl ExchangeRateRepository {
     /// - Rate is not positive
     /// - From and to currencies are the same
     /// - Either currency does not exist
+    /// - The effective date is after today (UTC)
     pub async fn create_or_update_rate(
         &self,
         input: CreateExchangeRateInput,
@@ -116,6 +201,10 @@ impl ExchangeRateRepository {
             return Err(ExchangeRateError::SameCurrency);
         }
 
+        if input.effective_date > chrono::Utc::now().date_naive() {
+            return Err(ExchangeRateError::FutureEffectiveDate(input.effective_date));
+        }
+
         // Validate currencies exist (Requirement 4.4)
         let from_currency = currencies::Entity::find_by_id(&input.from_currency)
             .one(&self.db)
@@ -175,10 +264,13 @@ impl ExchangeRateRepository {
         }
     }
 
-    /// Finds an exchange rate for a currency pair on or before a date.
+    /// Finds an exchange rate for a currency pair on a date.
     ///
     /// Requirements: 4.6, 4.7, 4.8
     ///
+    /// The policy picks which stored rates qualify: the most recent on or
+    /// before `date`, or only those dated the first day of its month.
+    ///
     /// Lookup priority:
     /// 1. Direct rate (from_currency -> to_currency)
     /// 2. Inverse rate (to_currency -> from_currency, then invert)
@@ -194,6 +286,7 @@ impl ExchangeRateRepository {
         from_currency: &str,
         to_currency: &str,
         date: NaiveDate,
+        policy: RateLookupPolicy,
     ) -> Result<ExchangeRateLookup, ExchangeRateError> {
         // Same currency = rate of 1
         if from_currency == to_currency {
@@ -201,24 +294,31 @@ impl ExchangeRateRepository {
                 rate: Decimal::ONE,
                 lookup_method: RateLookupMethod::Direct,
                 effective_date: date,
+                staleness_days: 0,
             });
         }
 
+        // Staleness is measured from the date the policy asks for, so a
+        // monthly rate is not stale later in its month
+        let date = policy.rate_date(date);
+        let exact = policy.exact_date();
+
         // Try direct rate first (Requirement 4.6)
         if let Some(direct) = self
-            .find_direct_rate(organization_id, from_currency, to_currency, date)
+            .find_direct_rate(organization_id, from_currency, to_currency, date, exact)
             .await?
         {
             return Ok(ExchangeRateLookup {
                 rate: direct.rate,
                 lookup_method: RateLookupMethod::Direct,
                 effective_date: direct.effective_date,
+                staleness_days: (date - direct.effective_date).num_days(),
             });
         }
 
         // Try inverse rate
         if let Some(inverse) = self
-            .find_direct_rate(organization_id, to_currency, from_currency, date)
+            .find_direct_rate(organization_id, to_currency, from_currency, date, exact)
             .await?
         {
             // Invert the rate: if USD/EUR = 0.85, then EUR/USD = 1/0.85
@@ -227,6 +327,7 @@ impl ExchangeRateRepository {
                 rate: inverted_rate,
                 lookup_method: RateLookupMethod::Inverse,
                 effective_date: inverse.effective_date,
+                staleness_days: (date - inverse.effective_date).num_days(),
             });
         }
 
@@ -234,7 +335,7 @@ impl ExchangeRateRepository {
         if from_currency != "USD"
             && to_currency != "USD"
             && let Some(triangulated) = self
-                .find_triangulated_rate(organization_id, from_currency, to_currency, date)
+                .find_triangulated_rate(organization_id, from_currency, to_currency, date, exact)
                 .await?
         {
             return Ok(triangulated);
@@ -248,19 +349,26 @@ impl ExchangeRateRepository {
         ))
     }
 
-    /// Finds a direct exchange rate (most recent on or before date).
+    /// Finds a direct exchange rate: the most recent on or before the date,
+    /// or with `exact` only one dated on it.
     async fn find_direct_rate(
         &self,
         organization_id: Uuid,
         from_currency: &str,
         to_currency: &str,
         date: NaiveDate,
+        exact: bool,
     ) -> Result<Option<exchange_rates::Model>, ExchangeRateError> {
+        let on_date = if exact {
+            exchange_rates::Column::EffectiveDate.eq(date)
+        } else {
+            exchange_rates::Column::EffectiveDate.lte(date)
+        };
         let rate = exchange_rates::Entity::find()
             .filter(exchange_rates::Column::OrganizationId.eq(organization_id))
             .filter(exchange_rates::Column::FromCurrency.eq(from_currency))
             .filter(exchange_rates::Column::ToCurrency.eq(to_currency))
-            .filter(exchange_rates::Column::EffectiveDate.lte(date))
+            .filter(on_date)
             .order_by_desc(exchange_rates::Column::EffectiveDate)
             .one(&self.db)
             .await?;
@@ -277,15 +385,16 @@ impl ExchangeRateRepository {
         from_currency: &str,
         to_currency: &str,
         date: NaiveDate,
+        exact: bool,
     ) -> Result<Option<ExchangeRateLookup>, ExchangeRateError> {
         // Get from_currency -> USD rate
         let from_to_usd = self
-            .find_rate_with_inverse(organization_id, from_currency, "USD", date)
+            .find_rate_with_inverse(organization_id, from_currency, "USD", date, exact)
             .await?;
 
         // Get USD -> to_currency rate
         let usd_to_target = self
-            .find_rate_with_inverse(organization_id, "USD", to_currency, date)
+            .find_rate_with_inverse(organization_id, "USD", to_currency, date, exact)
             .await?;
 
         match (from_to_usd, usd_to_target) {
@@ -299,6 +408,7 @@ impl ExchangeRateRepository {
                     rate: triangulated_rate,
                     lookup_method: RateLookupMethod::Triangulated,
                     effective_date,
+                    staleness_days: (date - effective_date).num_days(),
                 }))
             }
             _ => Ok(None),
@@ -312,10 +422,11 @@ impl ExchangeRateRepository {
         from_currency: &str,
         to_currency: &str,
         date: NaiveDate,
+        exact: bool,
     ) -> Result<Option<(Decimal, NaiveDate)>, ExchangeRateError> {
         // Try direct
         if let Some(direct) = self
-            .find_direct_rate(organization_id, from_currency, to_currency, date)
+            .find_direct_rate(organization_id, from_currency, to_currency, date, exact)
             .await?
         {
             return Ok(Some((direct.rate, direct.effective_date)));
@@ -323,7 +434,7 @@ impl ExchangeRateRepository {
 
         // Try inverse
         if let Some(inverse) = self
-            .find_direct_rate(organization_id, to_currency, from_currency, date)
+            .find_direct_rate(organization_id, to_currency, from_currency, date, exact)
             .await?
         {
             let inverted_rate = Decimal::ONE / inverse.rate;
@@ -352,6 +463,260 @@ impl ExchangeRateRepository {
 
         Ok(rates)
     }
+
+    /// Finds a stored rate by ID within an organization.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    pub async fn find_by_id(
+        &self,
+        organization_id: Uuid,
+        id: Uuid,
+    ) -> Result<Option<exchange_rates::Model>, ExchangeRateError> {
+        let rate = exchange_rates::Entity::find_by_id(id)
+            .filter(exchange_rates::Column::OrganizationId.eq(organization_id))
+            .one(&self.db)
+            .await?;
+        Ok(rate)
+    }
+
+    /// Counts the posted ledger entries that used a stored rate.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the rate is not found or the query fails.
+    pub async fn rate_usage(
+        &self,
+        organization_id: Uuid,
+        id: Uuid,
+    ) -> Result<RateUsage, ExchangeRateError> {
+        let rate = self
+            .find_by_id(organization_id, id)
+            .await?
+            .ok_or(ExchangeRateError::NotFound(id))?;
+        count_usage(&self.db, &rate).await
+    }
+
+    /// Lists the posted transactions with entries that used a stored rate,
+    /// oldest first.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the rate is not found or the query fails.
+    pub async fn list_rate_usage(
+        &self,
+        organization_id: Uuid,
+        id: Uuid,
+    ) -> Result<(RateUsage, Vec<RateUsageTransaction>), ExchangeRateError> {
+        let rate = self
+            .find_by_id(organization_id, id)
+            .await?
+            .ok_or(ExchangeRateError::NotFound(id))?;
+        let usage = count_usage(&self.db, &rate).await?;
+
+        let rows: Vec<UsageRow> = usage_query(&rate, usage.window_end)
+            .select_only()
+            .column(transactions::Column::Id)
+            .column(transactions::Column::ReferenceNumber)
+            .column(transactions::Column::TransactionDate)
+            .column(transactions::Column::Description)
+            .column_as(
+                Expr::col((ledger_entries::Entity, ledger_entries::Column::Id)).count(),
+                "entry_count",
+            )
+            .column_as(ledger_entries::Column::SourceAmount.sum(), "source_amount")
+            .column_as(
+                ledger_entries::Column::FunctionalAmount.sum(),
+                "functional_amount",
+            )
+            .group_by(transactions::Column::Id)
+            .order_by_asc(transactions::Column::TransactionDate)
+            .order_by_asc(transactions::Column::Id)
+            .into_tuple()
+            .all(&self.db)
+            .await?;
+
+        let transactions = rows
+            .into_iter()
+            .map(
+                |(
+                    transaction_id,
+                    reference_number,
+                    transaction_date,
+                    description,
+                    entry_count,
+                    source_amount,
+                    functional_amount,
+                )| RateUsageTransaction {
+                    transaction_id,
+                    reference_number,
+                    transaction_date,
+                    description,
+                    entry_count,
+                    source_amount: source_amount.unwrap_or(Decimal::ZERO),
+                    functional_amount: functional_amount.unwrap_or(Decimal::ZERO),
+                },
+            )
+            .collect();
+        Ok((usage, transactions))
+    }
+
+    /// Corrects a stored rate, recording the old value in the correction
+    /// history together with how many posted entries had used it.
+    ///
+    /// Posted entries keep the rate they were converted at; the usage tells
+    /// the caller whether revaluation adjustments are needed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the new rate is not positive, the rate is not
+    /// found, or the database operation fails.
+    pub async fn correct_rate(
+        &self,
+        organization_id: Uuid,
+        id: Uuid,
+        input: CorrectExchangeRateInput,
+    ) -> Result<CorrectedRate, ExchangeRateError> {
+        if input.rate <= Decimal::ZERO {
+            return Err(ExchangeRateError::NonPositiveRate);
+        }
+
+        let txn = self.db.begin().await?;
+        let existing = exchange_rates::Entity::find_by_id(id)
+            .filter(exchange_rates::Column::OrganizationId.eq(organization_id))
+            .lock_exclusive()
+            .one(&txn)
+            .await?
+            .ok_or(ExchangeRateError::NotFound(id))?;
+        let usage = count_usage(&txn, &existing).await?;
+
+        let correction = rate_corrections::ActiveModel {
+            id: Set(Uuid::new_v4()),
+            organization_id: Set(organization_id),
+            exchange_rate_id: Set(Some(existing.id)),
+            from_currency: Set(existing.from_currency.clone()),
+            to_currency: Set(existing.to_currency.clone()),
+            effective_date: Set(existing.effective_date),
+            old_rate: Set(existing.rate),
+            new_rate: Set(input.rate),
+            affected_entries: Set(i64::try_from(usage.entry_count).unwrap_or(i64::MAX)),
+            reason: Set(input.reason),
+            corrected_by: Set(Some(input.corrected_by)),
+            created_at: Set(chrono::Utc::now().into()),
+        }
+        .insert(&txn)
+        .await?;
+
+        let mut active: exchange_rates::ActiveModel = existing.into();
+        active.rate = Set(input.rate);
+        let rate = active.update(&txn).await?;
+        txn.commit().await?;
+
+        Ok(CorrectedRate {
+            rate,
+            correction,
+            usage,
+        })
+    }
+
+    /// Deletes a stored rate.
+    ///
+    /// Refuses when posted entries used the rate unless `force` is set.
+    /// Returns the usage at the time of deletion.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ExchangeRateError::InUse`] if the rate was used and `force`
+    /// is not set, or an error if the rate is not found or the database
+    /// operation fails.
+    pub async fn delete_rate(
+        &self,
+        organization_id: Uuid,
+        id: Uuid,
+        force: bool,
+    ) -> Result<RateUsage, ExchangeRateError> {
+        let txn = self.db.begin().await?;
+        let existing = exchange_rates::Entity::find_by_id(id)
+            .filter(exchange_rates::Column::OrganizationId.eq(organization_id))
+            .lock_exclusive()
+            .one(&txn)
+            .await?
+            .ok_or(ExchangeRateError::NotFound(id))?;
+        let usage = count_usage(&txn, &existing).await?;
+        if usage.entry_count > 0 && !force {
+            return Err(ExchangeRateError::InUse(usage.entry_count));
+        }
+
+        exchange_rates::Entity::delete_by_id(existing.id)
+            .exec(&txn)
+            .await?;
+        txn.commit().await?;
+        Ok(usage)
+    }
+}
+
+/// Transaction ID, reference, date, description, entry count, source and
+/// functional amounts of one transaction using a rate.
+type UsageRow = (
+    Uuid,
+    Option<String>,
+    NaiveDate,
+    String,
+    i64,
+    Option<Decimal>,
+    Option<Decimal>,
+);
+
+/// Counts posted entries that used `rate` within its window.
+async fn count_usage<C: ConnectionTrait>(
+    db: &C,
+    rate: &exchange_rates::Model,
+) -> Result<RateUsage, ExchangeRateError> {
+    let window_end: Option<NaiveDate> = exchange_rates::Entity::find()
+        .select_only()
+        .column(exchange_rates::Column::EffectiveDate)
+        .filter(exchange_rates::Column::OrganizationId.eq(rate.organization_id))
+        .filter(exchange_rates::Column::FromCurrency.eq(&rate.from_currency))
+        .filter(exchange_rates::Column::ToCurrency.eq(&rate.to_currency))
+        .filter(exchange_rates::Column::EffectiveDate.gt(rate.effective_date))
+        .order_by_asc(exchange_rates::Column::EffectiveDate)
+        .into_tuple()
+        .one(db)
+        .await?;
+
+    let entry_count = usage_query(rate, window_end).count(db).await?;
+    Ok(RateUsage {
+        window_start: rate.effective_date,
+        window_end,
+        entry_count,
+    })
+}
+
+/// Posted ledger entries converted at exactly `rate` within its window.
+///
+/// Filters on plain column values so the currency pair and rate index on
+/// ledger entries and the organization and date index on transactions can
+/// both be used.
+fn usage_query(
+    rate: &exchange_rates::Model,
+    window_end: Option<NaiveDate>,
+) -> Select<ledger_entries::Entity> {
+    let mut query = ledger_entries::Entity::find()
+        .join(
+            JoinType::InnerJoin,
+            ledger_entries::Relation::Transactions.def(),
+        )
+        .filter(ledger_entries::Column::SourceCurrency.eq(&rate.from_currency))
+        .filter(ledger_entries::Column::FunctionalCurrency.eq(&rate.to_currency))
+        .filter(ledger_entries::Column::ExchangeRate.eq(rate.rate))
+        .filter(transactions::Column::OrganizationId.eq(rate.organization_id))
+        .filter(transactions::Column::Status.eq(TransactionStatus::Posted))
+        .filter(transactions::Column::TransactionDate.gte(rate.effective_date));
+    if let Some(end) = window_end {
+        query = query.filter(transactions::Column::TransactionDate.lt(end));
+    }
+    query
 }
 
 // ============================================================================
@@ -4,16 +4,19 @@
 
 use chrono::{Datelike, NaiveDate};
 use sea_orm::{
-    ActiveModelTrait, ColumnTrait, DatabaseConnection, DbErr, EntityTrait, QueryFilter, QueryOrder,
-    Set, TransactionTrait,
+    ActiveModelTrait, ColumnTrait, ConnectionTrait, DatabaseConnection, DbErr, EntityTrait,
+    QueryFilter, QueryOrder, Set, TransactionTrait,
 };
 use uuid::Uuid;
+use zeltra_core::fiscal::{DateRange, PeriodCoverageFinding, validate_period_coverage};
 
 use crate::entities::{
     fiscal_periods, fiscal_years,
     sea_orm_active_enums::{FiscalPeriodStatus, FiscalYearStatus},
 };
 
+use super::balance_snapshot::rebuild_snapshots;
+
 /// Error types for fiscal operations.
 #[derive(Debug, thiserror::Error)]
 pub enum FiscalError {
@@ -58,6 +61,21 @@ pub struct FiscalYearWithPeriods {
     pub fiscal_year: fiscal_years::Model,
     /// The fiscal periods within this year.
     pub periods: Vec<fiscal_periods::Model>,
+    /// Gaps, overlaps and out-of-year dates in the regular periods. Warnings
+    /// only; an empty list means every day of the year has one period.
+    pub coverage: Vec<PeriodCoverageFinding>,
+}
+
+impl FiscalYearWithPeriods {
+    /// Pairs a fiscal year with its periods and checks their coverage.
+    fn new(fiscal_year: fiscal_years::Model, periods: Vec<fiscal_periods::Model>) -> Self {
+        let coverage = period_coverage(&fiscal_year, &periods);
+        Self {
+            fiscal_year,
+            periods,
+            coverage,
+        }
+    }
 }
 
 /// Input for creating a fiscal year.
@@ -127,78 +145,10 @@ impl FiscalRepository {
         &self,
         input: CreateFiscalYearInput,
     ) -> Result<FiscalYearWithPeriods, FiscalError> {
-        // Validate date range (Requirement 1.2)
-        if input.start_date >= input.end_date {
-            return Err(FiscalError::InvalidDateRange);
-        }
-
-        // Check for overlapping fiscal years (Requirement 1.3)
-        let overlapping = fiscal_years::Entity::find()
-            .filter(fiscal_years::Column::OrganizationId.eq(input.organization_id))
-            .filter(fiscal_years::Column::StartDate.lte(input.end_date))
-            .filter(fiscal_years::Column::EndDate.gte(input.start_date))
-            .one(&self.db)
-            .await?;
-
-        if let Some(existing) = overlapping {
-            return Err(FiscalError::OverlappingYear(existing.name));
-        }
-
         let txn = self.db.begin().await?;
-        let now = chrono::Utc::now().into();
-        let fiscal_year_id = Uuid::new_v4();
-
-        // Create fiscal year
-        let fiscal_year = fiscal_years::ActiveModel {
-            id: Set(fiscal_year_id),
-            organization_id: Set(input.organization_id),
-            name: Set(input.name),
-            start_date: Set(input.start_date),
-            end_date: Set(input.end_date),
-            status: Set(FiscalYearStatus::Open),
-            closed_by: Set(None),
-            closed_at: Set(None),
-            created_at: Set(now),
-            updated_at: Set(now),
-        };
-
-        let fiscal_year = fiscal_year.insert(&txn).await?;
-
-        // Auto-generate monthly periods (Requirement 1.1)
-        let periods = generate_monthly_periods(
-            fiscal_year_id,
-            input.organization_id,
-            input.start_date,
-            input.end_date,
-        );
-
-        let mut inserted_periods = Vec::with_capacity(periods.len());
-        for period in periods {
-            let period_model = fiscal_periods::ActiveModel {
-                id: Set(period.id),
-                organization_id: Set(period.organization_id),
-                fiscal_year_id: Set(period.fiscal_year_id),
-                name: Set(period.name),
-                period_number: Set(period.period_number),
-                start_date: Set(period.start_date),
-                end_date: Set(period.end_date),
-                status: Set(FiscalPeriodStatus::Open),
-                is_adjustment_period: Set(period.is_adjustment_period),
-                closed_by: Set(None),
-                closed_at: Set(None),
-                created_at: Set(now),
-                updated_at: Set(now),
-            };
-            let inserted = period_model.insert(&txn).await?;
-            inserted_periods.push(inserted);
-        }
-
+        let created = insert_fiscal_year(&txn, input).await?;
         txn.commit().await?;
-
-        Ok(FiscalYearWithPeriods {
-            fiscal_year,
-            periods: inserted_periods,
-        })
+        Ok(created)
     }
 
     /// Lists fiscal years with nested periods for an organization.
@@ -227,10 +177,7 @@ impl FiscalRepository {
                 .all(&self.db)
                 .await?;
 
-            results.push(FiscalYearWithPeriods {
-                fiscal_year: fy,
-                periods,
-            });
+            results.push(FiscalYearWithPeriods::new(fy, periods));
         }
 
         Ok(results)
@@ -257,10 +204,25 @@ impl FiscalRepository {
             .all(&self.db)
             .await?;
 
-        Ok(Some(FiscalYearWithPeriods {
-            fiscal_year: fy,
-            periods,
-        }))
+        Ok(Some(FiscalYearWithPeriods::new(fy, periods)))
+    }
+
+    /// Checks an organization's fiscal year for dates covered by no period
+    /// or by more than one, and for periods reaching outside the year.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the fiscal year does not belong to the
+    /// organization or the database query fails.
+    pub async fn validate_fiscal_year(
+        &self,
+        organization_id: Uuid,
+        fiscal_year_id: Uuid,
+    ) -> Result<FiscalYearWithPeriods, FiscalError> {
+        match self.find_fiscal_year_by_id(fiscal_year_id).await? {
+            Some(year) if year.fiscal_year.organization_id == organization_id => Ok(year),
+            _ => Err(FiscalError::YearNotFound(fiscal_year_id)),
+        }
     }
 
     /// Finds the fiscal period containing a specific date.
@@ -287,6 +249,9 @@ impl FiscalRepository {
     ///
     /// Requirements: 1.5, 1.6, 1.7
     ///
+    /// Closing a period writes its account balance snapshot. Closing an
+    /// already closed period again recomputes it.
+    ///
     /// # Errors
     ///
     /// Returns an error if:
@@ -339,7 +304,16 @@ impl FiscalRepository {
             active.closed_at = Set(Some(now));
         }
 
-        let updated = active.update(&self.db).await?;
+        let txn = self.db.begin().await?;
+        let updated = active.update(&txn).await?;
+
+        // Entries of a closed period are final, so its balances (and those of
+        // any later closed period) can be snapshotted
+        if new_status == FiscalPeriodStatus::Closed {
+            rebuild_snapshots(&txn, updated.organization_id, Some(updated.start_date)).await?;
+        }
+
+        txn.commit().await?;
         Ok(updated)
     }
 
@@ -357,6 +331,23 @@ impl FiscalRepository {
     }
 }
 
+/// Checks how a year's regular periods cover it. Adjustment periods share
+/// dates with the last regular period by design and are left out.
+fn period_coverage(
+    fiscal_year: &fiscal_years::Model,
+    periods: &[fiscal_periods::Model],
+) -> Vec<PeriodCoverageFinding> {
+    let ranges: Vec<DateRange> = periods
+        .iter()
+        .filter(|p| !p.is_adjustment_period)
+        .map(|p| DateRange::new(p.start_date, p.end_date))
+        .collect();
+    validate_period_coverage(
+        DateRange::new(fiscal_year.start_date, fiscal_year.end_date),
+        &ranges,
+    )
+}
+
 /// Validates fiscal period status transitions.
 fn validate_status_transition(
     from: &FiscalPeriodStatus,
@@ -396,6 +387,87 @@ struct PeriodData {
     is_adjustment_period: bool,
 }
 
+/// Creates a fiscal year with monthly periods on the given connection.
+///
+/// Shared by [`FiscalRepository::create_fiscal_year`] and organization
+/// bootstrap, which runs it inside its own database transaction.
+///
+/// # Errors
+///
+/// Returns an error if the dates are out of order, the year overlaps an
+/// existing one, or a database operation fails.
+pub(crate) async fn insert_fiscal_year<C: ConnectionTrait>(
+    conn: &C,
+    input: CreateFiscalYearInput,
+) -> Result<FiscalYearWithPeriods, FiscalError> {
+    // Validate date range (Requirement 1.2)
+    if input.start_date >= input.end_date {
+        return Err(FiscalError::InvalidDateRange);
+    }
+
+    // Check for overlapping fiscal years (Requirement 1.3)
+    let overlapping = fiscal_years::Entity::find()
+        .filter(fiscal_years::Column::OrganizationId.eq(input.organization_id))
+        .filter(fiscal_years::Column::StartDate.lte(input.end_date))
+        .filter(fiscal_years::Column::EndDate.gte(input.start_date))
+        .one(conn)
+        .await?;
+
+    if let Some(existing) = overlapping {
+        return Err(FiscalError::OverlappingYear(existing.name));
+    }
+
+    let now = chrono::Utc::now().into();
+    let fiscal_year_id = Uuid::new_v4();
+
+    // Create fiscal year
+    let fiscal_year = fiscal_years::ActiveModel {
+        id: Set(fiscal_year_id),
+        organization_id: Set(input.organization_id),
+        name: Set(input.name),
+        start_date: Set(input.start_date),
+        end_date: Set(input.end_date),
+        status: Set(FiscalYearStatus::Open),
+        closed_by: Set(None),
+        closed_at: Set(None),
+        created_at: Set(now),
+        updated_at: Set(now),
+    };
+
+    let fiscal_year = fiscal_year.insert(conn).await?;
+
+    // Auto-generate monthly periods (Requirement 1.1)
+    let periods = generate_monthly_periods(
+        fiscal_year_id,
+        input.organization_id,
+        input.start_date,
+        input.end_date,
+    );
+
+    let mut inserted_periods = Vec::with_capacity(periods.len());
+    for period in periods {
+        let period_model = fiscal_periods::ActiveModel {
+            id: Set(period.id),
+            organization_id: Set(period.organization_id),
+            fiscal_year_id: Set(period.fiscal_year_id),
+            name: Set(period.name),
+            period_number: Set(period.period_number),
+            start_date: Set(period.start_date),
+            end_date: Set(period.end_date),
+            status: Set(FiscalPeriodStatus::Open),
+            is_adjustment_period: Set(period.is_adjustment_period),
+            closed_by: Set(None),
+            closed_at: Set(None),
+            created_at: Set(now),
+            updated_at: Set(now),
+        };
+        let inserted = period_model.insert(conn).await?;
+        inserted_periods.push(inserted);
+    }
+
+    Ok(FiscalYearWithPeriods::new(fiscal_year, inserted_periods))
+}
+
 /// Generates monthly periods for a fiscal year.
 fn generate_monthly_periods(
     fiscal_year_id: Uuid,
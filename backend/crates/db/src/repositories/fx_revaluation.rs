@@ -0,0 +1,458 @@
+//! Foreign currency revaluation, on demand and at period end.
+//!
+//! [`FxRevaluationRepository::exposure`] revalues an organization's foreign
+//! monetary balances at the rates in effect on a date. The period-end run
+//! books the result as an adjustment draft per fiscal period, referenced
+//! `FXREVAL-YYYY-MM`; transaction references are unique per organization, so
+//! running it again finds the existing draft instead of creating another.
+
+use std::collections::{BTreeMap, HashMap};
+
+use chrono::{Days, NaiveDate};
+use rust_decimal::Decimal;
+use sea_orm::{
+    ColumnTrait, DatabaseConnection, DbErr, EntityTrait, JoinType, QueryFilter, QueryOrder,
+    QuerySelect, RelationTrait,
+};
+use serde::Serialize;
+use serde_json::json;
+use uuid::Uuid;
+use zeltra_core::currency::revaluation::AdjustmentLine;
+use zeltra_core::currency::{RateLookupPolicy, RevaluationResult, revaluation_reference, revalue};
+use zeltra_core::notification::NotificationType;
+use zeltra_core::settings::OrganizationSettings;
+
+use crate::entities::{
+    fiscal_periods, organization_users, organizations,
+    sea_orm_active_enums::{FiscalPeriodStatus, TransactionType, UserRole},
+    transactions,
+};
+
+use super::exchange_rate::{ExchangeRateError, ExchangeRateRepository};
+use super::notification::NotificationRepository;
+use super::report::{ForeignCurrencyBalance, ReportError, ReportRepository};
+use super::subscription::{Feature, SubscriptionRepository};
+use super::transaction::{
+    CreateLedgerEntryInput, CreateTransactionInput, TransactionError, TransactionRepository,
+};
+
+/// Error types for FX revaluation.
+#[derive(Debug, thiserror::Error)]
+pub enum FxRevaluationError {
+    /// Organization not found.
+    #[error("Organization not found: {0}")]
+    OrganizationNotFound(Uuid),
+
+    /// The organization has no owner to create the draft as.
+    #[error("Organization {0} has no owner")]
+    NoOwner(Uuid),
+
+    /// Stored organization settings could not be read.
+    #[error("Invalid organization settings: {0}")]
+    InvalidSettings(String),
+
+    /// Foreign balances could not be loaded.
+    #[error(transparent)]
+    Report(#[from] ReportError),
+
+    /// Exchange rate lookup failed.
+    #[error(transparent)]
+    ExchangeRate(#[from] ExchangeRateError),
+
+    /// The draft could not be created.
+    #[error(transparent)]
+    Transaction(#[from] TransactionError),
+
+    /// Database error.
+    #[error("Database error: {0}")]
+    Database(#[from] DbErr),
+}
+
+/// Foreign balances revalued at the rates in effect on a date.
+#[derive(Debug, Clone)]
+pub struct FxExposure {
+    /// The organization's functional currency.
+    pub functional_currency: String,
+    /// Foreign balances of monetary accounts at historical rates.
+    pub balances: Vec<ForeignCurrencyBalance>,
+    /// Effective date of the rate used for each currency.
+    pub rate_dates: BTreeMap<String, NaiveDate>,
+    /// Revaluation of the balances.
+    pub result: RevaluationResult,
+}
+
+/// Why a period-end revaluation was not prepared.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "reason", rename_all = "snake_case")]
+pub enum RevaluationSkip {
+    /// `fx_revaluation.gain_account_id` or `loss_account_id` is not set.
+    AccountsNotConfigured,
+    /// No rate on or before the period end for these currencies.
+    MissingRates {
+        /// Currencies without a rate, by code.
+        currencies: Vec<String>,
+    },
+}
+
+/// What the period-end run did for one fiscal period.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PeriodEndOutcome {
+    /// A draft was created.
+    Created {
+        /// The new draft.
+        transaction_id: Uuid,
+        /// Net unrealized gain (positive) or loss (negative).
+        total_unrealized: Decimal,
+    },
+    /// A transaction with the period's reference already exists.
+    Exists {
+        /// The existing transaction.
+        transaction_id: Uuid,
+    },
+    /// No foreign balance has an unrealized difference.
+    NothingToRevalue,
+    /// The organization is missing configuration or rates; its admins were
+    /// notified.
+    Skipped(RevaluationSkip),
+    /// The organization's plan does not include multi-currency.
+    MultiCurrencyDisabled,
+}
+
+/// Result of the period-end run for one fiscal period.
+#[derive(Debug, Clone)]
+pub struct PeriodEndRevaluation {
+    /// The organization.
+    pub organization_id: Uuid,
+    /// The fiscal period.
+    pub fiscal_period_id: Uuid,
+    /// Name of the fiscal period.
+    pub period_name: String,
+    /// Last day of the period, used as the revaluation date.
+    pub period_end: NaiveDate,
+    /// Reference of the revaluation draft.
+    pub reference: String,
+    /// What happened.
+    pub outcome: PeriodEndOutcome,
+}
+
+/// Repository for FX revaluation.
+#[derive(Debug, Clone)]
+pub struct FxRevaluationRepository {
+    db: DatabaseConnection,
+}
+
+impl FxRevaluationRepository {
+    /// Creates a new FX revaluation repository.
+    #[must_use]
+    pub const fn new(db: DatabaseConnection) -> Self {
+        Self { db }
+    }
+
+    /// Revalues an organization's foreign balances at the rates in effect on
+    /// `as_of`.
+    ///
+    /// Revalued amounts use the functional currency's decimal places.
+    /// Currencies without a rate are reported in the result's
+    /// `missing_rates`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a database query fails.
+    pub async fn exposure(
+        &self,
+        org: &organizations::Model,
+        as_of: NaiveDate,
+    ) -> Result<FxExposure, FxRevaluationError> {
+        let balances = ReportRepository::new(self.db.clone())
+            .query_foreign_balances(org.id, as_of)
+            .await?;
+
+        let rate_repo = ExchangeRateRepository::new(self.db.clone());
+        let mut rates = HashMap::new();
+        let mut rate_dates = BTreeMap::new();
+        for balance in &balances {
+            let currency = &balance.balance.currency;
+            if rate_dates.contains_key(currency) {
+                continue;
+            }
+            match rate_repo
+                // Balances are revalued at the closing rate whatever the
+                // organization books transactions at
+                .find_rate(
+                    org.id,
+                    currency,
+                    &org.base_currency,
+                    as_of,
+                    RateLookupPolicy::DailyLatest,
+                )
+                .await
+            {
+                Ok(lookup) => {
+                    // Stored rates have 10 decimal places; inverted ones may not
+                    rates.insert(currency.clone(), lookup.rate.round_dp(10));
+                    rate_dates.insert(currency.clone(), lookup.effective_date);
+                }
+                Err(ExchangeRateError::RateNotFound(..)) => {}
+                Err(e) => return Err(e.into()),
+            }
+        }
+
+        let decimal_places = TransactionRepository::new(self.db.clone())
+            .currency_decimal_places(&[org.base_currency.as_str()])
+            .await?
+            .get(&org.base_currency)
+            .copied()
+            .unwrap_or(2);
+
+        let core_balances: Vec<_> = balances.iter().map(|b| b.balance.clone()).collect();
+        let result = revalue(&core_balances, &rates, decimal_places);
+
+        Ok(FxExposure {
+            functional_currency: org.base_currency.clone(),
+            balances,
+            rate_dates,
+            result,
+        })
+    }
+
+    /// Lists open fiscal periods of active organizations that end on `today`
+    /// or the day after, optionally for one organization only.
+    ///
+    /// Adjustment periods are left out; they share a month with a regular
+    /// period.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    pub async fn due_periods(
+        &self,
+        today: NaiveDate,
+        organization_id: Option<Uuid>,
+    ) -> Result<Vec<fiscal_periods::Model>, DbErr> {
+        let mut query = fiscal_periods::Entity::find()
+            .join(
+                JoinType::InnerJoin,
+                fiscal_periods::Relation::Organizations.def(),
+            )
+            .filter(organizations::Column::IsActive.eq(true))
+            .filter(fiscal_periods::Column::Status.eq(FiscalPeriodStatus::Open))
+            .filter(fiscal_periods::Column::IsAdjustmentPeriod.eq(false))
+            .filter(fiscal_periods::Column::EndDate.gte(today))
+            .filter(fiscal_periods::Column::EndDate.lte(today + Days::new(1)));
+        if let Some(organization_id) = organization_id {
+            query = query.filter(fiscal_periods::Column::OrganizationId.eq(organization_id));
+        }
+        query
+            .order_by_asc(fiscal_periods::Column::OrganizationId)
+            .order_by_asc(fiscal_periods::Column::EndDate)
+            .all(&self.db)
+            .await
+    }
+
+    /// Prepares the revaluation draft for the end of `period`.
+    ///
+    /// Creates an adjustment draft dated on the last day of the period,
+    /// restating each foreign balance at the latest rate against the
+    /// configured gain and loss accounts. The draft is created as the
+    /// organization's first owner and goes through the normal approval flow.
+    /// When the accounts are not configured or a rate is missing, owners and
+    /// admins are notified once per period and reason instead.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the organization cannot be read or the draft
+    /// cannot be created.
+    pub async fn revalue_period_end(
+        &self,
+        period: &fiscal_periods::Model,
+    ) -> Result<PeriodEndRevaluation, FxRevaluationError> {
+        let reference = revaluation_reference(period.end_date);
+        let outcome = self.period_end_outcome(period, &reference).await?;
+        Ok(PeriodEndRevaluation {
+            organization_id: period.organization_id,
+            fiscal_period_id: period.id,
+            period_name: period.name.clone(),
+            period_end: period.end_date,
+            reference,
+            outcome,
+        })
+    }
+
+    async fn period_end_outcome(
+        &self,
+        period: &fiscal_periods::Model,
+        reference: &str,
+    ) -> Result<PeriodEndOutcome, FxRevaluationError> {
+        let org_id = period.organization_id;
+        let org = organizations::Entity::find_by_id(org_id)
+            .one(&self.db)
+            .await?
+            .ok_or(FxRevaluationError::OrganizationNotFound(org_id))?;
+        if !SubscriptionRepository::has_feature(&self.db, org_id, Feature::MultiCurrency).await? {
+            return Ok(PeriodEndOutcome::MultiCurrencyDisabled);
+        }
+
+        if let Some(existing) = transactions::Entity::find()
+            .filter(transactions::Column::OrganizationId.eq(org_id))
+            .filter(transactions::Column::ReferenceNumber.eq(reference))
+            .one(&self.db)
+            .await?
+        {
+            return Ok(PeriodEndOutcome::Exists {
+                transaction_id: existing.id,
+            });
+        }
+
+        let settings = OrganizationSettings::from_value(&org.settings)
+            .map_err(|e| FxRevaluationError::InvalidSettings(e.to_string()))?;
+        let (Some(gain_account_id), Some(loss_account_id)) = (
+            settings.fx_revaluation.gain_account_id,
+            settings.fx_revaluation.loss_account_id,
+        ) else {
+            return self
+                .skip(period, reference, RevaluationSkip::AccountsNotConfigured)
+                .await;
+        };
+
+        let exposure = self.exposure(&org, period.end_date).await?;
+        if !exposure.result.missing_rates.is_empty() {
+            let reason = RevaluationSkip::MissingRates {
+                currencies: exposure.result.missing_rates.clone(),
+            };
+            return self.skip(period, reference, reason).await;
+        }
+        let lines = exposure
+            .result
+            .adjustment_lines(gain_account_id, loss_account_id);
+        if lines.is_empty() {
+            return Ok(PeriodEndOutcome::NothingToRevalue);
+        }
+
+        let owner = organization_users::Entity::find()
+            .filter(organization_users::Column::OrganizationId.eq(org_id))
+            .filter(organization_users::Column::Role.eq(UserRole::Owner))
+            .order_by_asc(organization_users::Column::CreatedAt)
+            .one(&self.db)
+            .await?
+            .ok_or(FxRevaluationError::NoOwner(org_id))?;
+
+        let created = TransactionRepository::new(self.db.clone())
+            .create_transaction(CreateTransactionInput {
+                organization_id: org_id,
+                transaction_type: TransactionType::Adjustment,
+                transaction_date: period.end_date,
+                description: format!("FX revaluation for {}", period.name),
+                reference_number: Some(reference.to_string()),
+                memo: None,
+                entries: revaluation_entries(&exposure, &lines),
+                created_by: owner.user_id,
+            })
+            .await?;
+        Ok(PeriodEndOutcome::Created {
+            transaction_id: created.transaction.id,
+            total_unrealized: exposure.result.total_unrealized,
+        })
+    }
+
+    /// Notifies owners and admins that the revaluation for `period` was
+    /// skipped, unless they already were for the same reason.
+    async fn skip(
+        &self,
+        period: &fiscal_periods::Model,
+        reference: &str,
+        reason: RevaluationSkip,
+    ) -> Result<PeriodEndOutcome, FxRevaluationError> {
+        let notifications = NotificationRepository::new(self.db.clone());
+        let mut payload = json!({
+            "fiscal_period_id": period.id,
+            "period_name": period.name,
+            "period_end": period.end_date,
+            "reference": reference,
+        });
+        if let (Some(payload), Ok(serde_json::Value::Object(reason))) =
+            (payload.as_object_mut(), serde_json::to_value(&reason))
+        {
+            payload.extend(reason);
+        }
+
+        if !notifications
+            .exists(
+                period.organization_id,
+                NotificationType::FxRevaluationSkipped,
+                payload.clone(),
+            )
+            .await?
+        {
+            let recipients: Vec<Uuid> = organization_users::Entity::find()
+                .filter(organization_users::Column::OrganizationId.eq(period.organization_id))
+                .filter(organization_users::Column::Role.is_in([UserRole::Owner, UserRole::Admin]))
+                .all(&self.db)
+                .await?
+                .into_iter()
+                .map(|member| member.user_id)
+                .collect();
+            notifications
+                .notify(
+                    period.organization_id,
+                    NotificationType::FxRevaluationSkipped,
+                    payload,
+                    &recipients,
+                )
+                .await?;
+        }
+        Ok(PeriodEndOutcome::Skipped(reason))
+    }
+}
+
+/// Ledger entries booking `lines` of a revaluation.
+///
+/// Restating lines keep the balance's currency and rate but carry no source
+/// amount, only the functional difference; gain and loss lines are in the
+/// functional currency.
+#[must_use]
+pub fn revaluation_entries(
+    exposure: &FxExposure,
+    lines: &[AdjustmentLine],
+) -> Vec<CreateLedgerEntryInput> {
+    let rates: HashMap<&str, Decimal> = exposure
+        .result
+        .currencies
+        .iter()
+        .map(|c| (c.currency.as_str(), c.rate))
+        .collect();
+    let functional_currency = &exposure.functional_currency;
+    lines
+        .iter()
+        .map(|line| {
+            let amount = line.debit + line.credit;
+            let (source_currency, source_amount, exchange_rate) = match &line.currency {
+                Some(currency) => (
+                    currency.clone(),
+                    Decimal::ZERO,
+                    rates
+                        .get(currency.as_str())
+                        .copied()
+                        .unwrap_or(Decimal::ONE),
+                ),
+                None => (functional_currency.clone(), amount, Decimal::ONE),
+            };
+            CreateLedgerEntryInput {
+                account_id: line.account_id,
+                source_currency,
+                source_amount,
+                exchange_rate,
+                functional_currency: functional_currency.clone(),
+                functional_amount: amount,
+                debit: line.debit,
+                credit: line.credit,
+                memo: line
+                    .currency
+                    .as_ref()
+                    .map(|c| format!("Unrealized FX revaluation of {c} balance")),
+                dimensions: vec![],
+                rate_override: None,
+                event_at: None,
+            }
+        })
+        .collect()
+}
@@ -0,0 +1,265 @@
+//! Flattened ledger entries for external analytics.
+//!
+//! A fiscal period's entries are streamed in `(streamed_at, id)` order, one
+//! join query per page, where `streamed_at` is when the entry's transaction
+//! was posted, or when the entry was recorded for transactions not yet
+//! posted. Pages are addressed by a [`Cursor`] on the last entry returned, so
+//! deep pages cost the same as the first, and entries posted after a page was
+//! read land on later pages even when they were drafted before it.
+
+use chrono::{DateTime, FixedOffset, NaiveDate, Utc};
+use rust_decimal::Decimal;
+use sea_orm::{
+    ColumnTrait, DatabaseConnection, DbErr, EntityTrait, FromQueryResult, JoinType, QueryFilter,
+    QueryOrder, QuerySelect, RelationTrait, Value, sea_query::Expr,
+};
+use serde::Deserialize;
+use uuid::Uuid;
+
+use zeltra_shared::types::{Cursor, OrganizationId};
+
+use crate::entities::{
+    chart_of_accounts, ledger_entries,
+    sea_orm_active_enums::{AccountType, TransactionStatus},
+    transactions,
+};
+
+/// Position of an entry in the stream.
+const STREAMED_AT_SQL: &str = "COALESCE(transactions.posted_at, ledger_entries.created_at)";
+
+/// Dimension values of the outer entry as a JSON array of `{id, code}`.
+const ENTRY_DIMENSIONS_SQL: &str = "COALESCE((\
+     SELECT json_agg(json_build_object('id', dv.id, 'code', dv.code) ORDER BY dv.code) \
+     FROM entry_dimensions ed \
+     JOIN dimension_values dv ON dv.id = ed.dimension_value_id \
+     WHERE ed.ledger_entry_id = ledger_entries.id), '[]'::json)";
+
+/// Which entries to stream.
+#[derive(Debug, Clone)]
+pub struct LedgerStreamFilter {
+    /// Fiscal period the entries' transactions belong to.
+    pub fiscal_period_id: Uuid,
+    /// Transaction statuses to include.
+    pub statuses: Vec<TransactionStatus>,
+    /// Only entries booked at an overridden exchange rate.
+    pub overridden_only: bool,
+    /// Only entries after this position.
+    pub after: Option<Cursor>,
+    /// Maximum number of entries to return.
+    pub limit: u64,
+}
+
+/// A dimension value tagged on an entry.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct EntryDimension {
+    /// Dimension value ID.
+    pub id: Uuid,
+    /// Dimension value code.
+    pub code: String,
+}
+
+/// A ledger entry with its transaction and account flattened in.
+#[derive(Debug, Clone)]
+pub struct StreamedLedgerEntry {
+    /// Entry ID.
+    pub id: Uuid,
+    /// Transaction ID.
+    pub transaction_id: Uuid,
+    /// Transaction date.
+    pub transaction_date: NaiveDate,
+    /// Transaction reference number.
+    pub reference_number: Option<String>,
+    /// Transaction status.
+    pub status: TransactionStatus,
+    /// Account code.
+    pub account_code: String,
+    /// Account type.
+    pub account_type: AccountType,
+    /// Source currency.
+    pub source_currency: String,
+    /// Amount in the source currency.
+    pub source_amount: Decimal,
+    /// Rate from source to functional currency.
+    pub exchange_rate: Decimal,
+    /// Functional currency.
+    pub functional_currency: String,
+    /// Amount in the functional currency.
+    pub functional_amount: Decimal,
+    /// Whether the exchange rate was entered rather than looked up.
+    pub is_override: bool,
+    /// Debit amount.
+    pub debit: Decimal,
+    /// Credit amount.
+    pub credit: Decimal,
+    /// Dimension values, by code.
+    pub dimensions: Vec<EntryDimension>,
+    /// When the entry was recorded.
+    pub created_at: DateTime<Utc>,
+    /// When the entry's transaction was posted.
+    pub posted_at: Option<DateTime<Utc>>,
+}
+
+impl StreamedLedgerEntry {
+    /// Position of the entry in the stream.
+    #[must_use]
+    pub fn cursor(&self) -> Cursor {
+        Cursor {
+            at: self.posted_at.unwrap_or(self.created_at),
+            id: self.id,
+        }
+    }
+}
+
+/// One page of streamed entries.
+#[derive(Debug, Clone)]
+pub struct LedgerEntryPage {
+    /// Entries in stream order.
+    pub entries: Vec<StreamedLedgerEntry>,
+    /// Position to continue from, `None` on the last page.
+    pub next_cursor: Option<Cursor>,
+}
+
+/// Repository for streaming ledger entries.
+#[derive(Debug, Clone)]
+pub struct LedgerEntryRepository {
+    db: DatabaseConnection,
+}
+
+impl LedgerEntryRepository {
+    /// Creates a new ledger entry repository.
+    #[must_use]
+    pub const fn new(db: DatabaseConnection) -> Self {
+        Self { db }
+    }
+
+    /// Returns the next page of a fiscal period's entries.
+    ///
+    /// Posting moves an entry to the end of the stream, so when unposted
+    /// statuses are included an entry read before its transaction was posted
+    /// comes again afterwards.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    #[allow(clippy::too_many_lines)]
+    pub async fn stream_entries(
+        &self,
+        organization_id: OrganizationId,
+        filter: LedgerStreamFilter,
+    ) -> Result<LedgerEntryPage, DbErr> {
+        #[derive(Debug, FromQueryResult)]
+        struct StreamRow {
+            id: Uuid,
+            transaction_id: Uuid,
+            transaction_date: NaiveDate,
+            reference_number: Option<String>,
+            status: TransactionStatus,
+            account_code: String,
+            account_type: AccountType,
+            source_currency: String,
+            source_amount: Decimal,
+            exchange_rate: Decimal,
+            functional_currency: String,
+            functional_amount: Decimal,
+            is_override: bool,
+            debit: Decimal,
+            credit: Decimal,
+            dimensions: serde_json::Value,
+            created_at: DateTime<FixedOffset>,
+            posted_at: Option<DateTime<FixedOffset>>,
+        }
+
+        let mut query = ledger_entries::Entity::find()
+            .select_only()
+            .join(
+                JoinType::InnerJoin,
+                ledger_entries::Relation::Transactions.def(),
+            )
+            .join(
+                JoinType::InnerJoin,
+                ledger_entries::Relation::ChartOfAccounts.def(),
+            )
+            .column(ledger_entries::Column::Id)
+            .column(ledger_entries::Column::TransactionId)
+            .column(transactions::Column::TransactionDate)
+            .column(transactions::Column::ReferenceNumber)
+            .column(transactions::Column::Status)
+            .column_as(chart_of_accounts::Column::Code, "account_code")
+            .column_as(chart_of_accounts::Column::AccountType, "account_type")
+            .column(ledger_entries::Column::SourceCurrency)
+            .column(ledger_entries::Column::SourceAmount)
+            .column(ledger_entries::Column::ExchangeRate)
+            .column(ledger_entries::Column::FunctionalCurrency)
+            .column(ledger_entries::Column::FunctionalAmount)
+            .column(ledger_entries::Column::IsOverride)
+            .column(ledger_entries::Column::Debit)
+            .column(ledger_entries::Column::Credit)
+            .column_as(Expr::cust(ENTRY_DIMENSIONS_SQL), "dimensions")
+            .column(ledger_entries::Column::CreatedAt)
+            .column(transactions::Column::PostedAt)
+            .filter(transactions::Column::OrganizationId.eq(organization_id))
+            .filter(transactions::Column::FiscalPeriodId.eq(filter.fiscal_period_id))
+            .filter(transactions::Column::Status.is_in(filter.statuses));
+
+        if filter.overridden_only {
+            query = query.filter(ledger_entries::Column::IsOverride.eq(true));
+        }
+        if let Some(after) = filter.after {
+            query = query.filter(Expr::cust_with_values(
+                format!("({STREAMED_AT_SQL}, ledger_entries.id) > ($1, $2)"),
+                [Value::from(after.at), Value::from(after.id)],
+            ));
+        }
+
+        let mut rows = query
+            .order_by_asc(Expr::cust(STREAMED_AT_SQL))
+            .order_by_asc(ledger_entries::Column::Id)
+            .limit(filter.limit + 1) // +1 to check for a next page
+            .into_model::<StreamRow>()
+            .all(&self.db)
+            .await?;
+
+        let limit = usize::try_from(filter.limit).unwrap_or(usize::MAX);
+        let has_more = rows.len() > limit;
+        rows.truncate(limit);
+
+        let entries = rows
+            .into_iter()
+            .map(|row| {
+                let dimensions = serde_json::from_value(row.dimensions)
+                    .map_err(|e| DbErr::Json(e.to_string()))?;
+                Ok(StreamedLedgerEntry {
+                    id: row.id,
+                    transaction_id: row.transaction_id,
+                    transaction_date: row.transaction_date,
+                    reference_number: row.reference_number,
+                    status: row.status,
+                    account_code: row.account_code,
+                    account_type: row.account_type,
+                    source_currency: row.source_currency,
+                    source_amount: row.source_amount,
+                    exchange_rate: row.exchange_rate,
+                    functional_currency: row.functional_currency,
+                    functional_amount: row.functional_amount,
+                    is_override: row.is_override,
+                    debit: row.debit,
+                    credit: row.credit,
+                    dimensions,
+                    created_at: row.created_at.with_timezone(&Utc),
+                    posted_at: row.posted_at.map(|at| at.with_timezone(&Utc)),
+                })
+            })
+            .collect::<Result<Vec<_>, DbErr>>()?;
+
+        let next_cursor = if has_more {
+            entries.last().map(StreamedLedgerEntry::cursor)
+        } else {
+            None
+        };
+
+        Ok(LedgerEntryPage {
+            entries,
+            next_cursor,
+        })
+    }
+}
@@ -4,21 +4,42 @@
 //! hiding the `SeaORM` implementation details from the rest of the application.
 
 pub mod account;
+pub mod api_key;
+pub mod approval_delegation;
 pub mod approval_rule;
 pub mod attachment;
+pub mod backup;
+pub mod balance_snapshot;
 pub mod budget;
+pub mod budget_comment;
+pub mod chart_template;
+pub mod closing;
+pub mod comment;
+pub mod currency;
 pub mod dashboard;
 pub mod dimension;
 pub mod email_verification;
 pub mod exchange_rate;
 pub mod fiscal;
+pub mod fx_revaluation;
+pub mod ledger_entry;
+pub mod notification;
+pub mod operator_audit;
 pub mod organization;
+pub mod payment;
+pub mod period_close;
+pub mod reconciliation;
 pub mod report;
+pub mod report_definition;
+pub mod report_mapping;
 pub mod session;
 pub mod simulation;
+pub mod store;
 pub mod subscription;
 pub mod transaction;
+pub mod transaction_template;
 pub mod user;
+pub mod voucher;
 pub mod workflow;
 
 #[cfg(test)]
@@ -37,46 +58,116 @@ mod simulation_integration_tests;
 mod dashboard_integration_tests;
 
 pub use account::{
-    AccountError, AccountFilter, AccountRepository, AccountWithBalance, CreateAccountInput,
-    UpdateAccountInput,
+    AccountActivity, AccountError, AccountFilter, AccountRepository, AccountSortField,
+    AccountWithBalance, BalanceStatusBreakdown, CreateAccountInput, ImportAccountInput,
+    LedgerIntegrityAccount, UpdateAccountInput,
+};
+pub use api_key::{ApiKeyRepoError, ApiKeyRepository, CreateApiKeyInput, CreatedApiKey};
+pub use approval_delegation::{
+    ApprovalDelegationError, ApprovalDelegationRepository, CreateApprovalDelegationInput,
+    UpdateApprovalDelegationInput,
 };
 pub use approval_rule::{
     ApprovalRuleError, ApprovalRuleRepository, CreateApprovalRuleInput, UpdateApprovalRuleInput,
 };
-pub use attachment::AttachmentRepository;
+pub use attachment::{
+    AttachmentExtraction, AttachmentListFilter, AttachmentListItem, AttachmentRepository,
+};
+pub use backup::{
+    BACKUP_SCHEMA_VERSION, BackupError, BackupManifest, BackupRepository, CloneOrganizationInput,
+    CloneSections, CloneSummary, ImportSummary, Section,
+};
+pub use balance_snapshot::{AccountTotals, BackfillSummary, BalanceSnapshotRepository};
 pub use budget::{
-    ActualAmountResult, BudgetError, BudgetLineWithActual, BudgetLineWithDimensions,
-    BudgetRepository, BudgetVsActualSummary, BudgetWithSummary, CreateBudgetInput,
-    CreateBudgetLineInput, DimensionValueInfo, UpdateBudgetInput, UpdateBudgetLineInput,
-    calculate_actual_by_account_type, is_debit_normal_account,
+    ActualAmountResult, AssignBudgetInput, BudgetAssignment, BudgetError, BudgetLineError,
+    BudgetLineWithActual, BudgetLineWithDimensions, BudgetLinesDiff, BudgetOverrun,
+    BudgetRepository, BudgetTrend, BudgetTrendPeriod, BudgetVsActualSummary, BudgetWithSummary,
+    CreateBudgetInput, CreateBudgetLineInput, DimensionValueInfo, MAX_REPLACE_LINES,
+    UpdateBudgetInput, UpdateBudgetLineInput, calculate_actual_by_account_type,
+    is_debit_normal_account,
+};
+pub use budget_comment::{
+    BudgetCommentError, BudgetCommentRepository, BudgetCommentWithAuthor, CreateBudgetCommentInput,
+    EditBudgetCommentInput,
 };
+pub use chart_template::{AccountTemplate, ChartTemplate};
+pub use closing::{ClosingChecklist, ClosingError, ClosingRepository};
+pub use comment::{
+    CommentPage, CommentRepoError, CommentRepository, CommentWithAuthor, CreateCommentInput,
+};
+pub use currency::CurrencyRepository;
 pub use dashboard::{
     ActivityEvent, ActivityPagination, BudgetStatus, BurnRate, CashPosition, CurrencyExposure,
     DashboardError, DashboardRepository, DepartmentExpense, PendingApprovals,
 };
 pub use dimension::{
-    CreateDimensionTypeInput, CreateDimensionValueInput, DimensionError, DimensionRepository,
-    DimensionTypeFilter, DimensionValueFilter, UpdateDimensionTypeInput, UpdateDimensionValueInput,
+    AccountDefaultDimension, CreateDimensionTypeInput, CreateDimensionValueInput, DimensionError,
+    DimensionRepository, DimensionTypeFilter, DimensionValueFilter, RetagEntriesInput, RetagResult,
+    RetagTransaction, UpdateDimensionTypeInput, UpdateDimensionValueInput,
 };
-pub use email_verification::EmailVerificationRepository;
+pub use email_verification::{EmailVerificationRepository, VerifiedEmail};
 pub use exchange_rate::{
-    CreateExchangeRateInput, ExchangeRateError, ExchangeRateLookup, ExchangeRateRepository,
-    RateLookupMethod,
+    CorrectExchangeRateInput, CorrectedRate, CreateExchangeRateInput, ExchangeRateError,
+    ExchangeRateLookup, ExchangeRateRepository, RateLookupMethod, RateUsage, RateUsageTransaction,
 };
 pub use fiscal::{CreateFiscalYearInput, FiscalError, FiscalRepository, FiscalYearWithPeriods};
-pub use organization::{OrganizationError, OrganizationRepository};
+pub use fx_revaluation::{
+    FxExposure, FxRevaluationError, FxRevaluationRepository, PeriodEndOutcome,
+    PeriodEndRevaluation, RevaluationSkip, revaluation_entries,
+};
+pub use ledger_entry::{
+    EntryDimension, LedgerEntryPage, LedgerEntryRepository, LedgerStreamFilter, StreamedLedgerEntry,
+};
+pub use notification::{
+    NotificationPage, NotificationRepoError, NotificationRepository, NotifyOutcome,
+};
+pub use operator_audit::{OperatorActionInput, OperatorAuditRepository};
+pub use organization::{
+    BootstrapSummary, OrganizationBootstrap, OrganizationError, OrganizationRepository,
+};
+pub use payment::{AppliedPayment, ApplyPaymentInput, PaymentError, PaymentRepository, Settlement};
+pub use period_close::{
+    AutoCloseOutcome, AutoCloseRun, DuePeriod, MAX_GRACE_EXTENSION_DAYS, PeriodCloseError,
+    PeriodCloseRepository, PeriodSchedule,
+};
+pub use reconciliation::{
+    ReconciliationEntry, ReconciliationError, ReconciliationRepository, ReconciliationWithCheck,
+    StartReconciliationInput,
+};
 pub use report::{
-    AccountBalance, AccountLedgerEntry, DimensionInfo, DimensionalReportRow, ReportError,
-    ReportRepository, calculate_balance, is_debit_normal,
+    AccountBalance, AccountLedgerEntry, AccountLookup, AccountMovement, AccountPeriodMovement,
+    DataVersion, DimensionIncomeStatementData, DimensionInfo, DimensionalReportRow,
+    ForeignCurrencyBalance, MovementTransaction, ReportError, ReportRepository, VoidReasonSummary,
+    calculate_balance, is_debit_normal,
+};
+pub use report_definition::{
+    ReportDefinitionError, ReportDefinitionRepository, SavedReportDefinition,
+};
+pub use report_mapping::{
+    ReportMappingError, ReportMappingRepository, ResolvedReportMapping, core_account_subtype,
 };
 pub use session::SessionRepository;
 pub use simulation::{HistoricalAccountData, SimulationRepoError, SimulationRepository};
+pub use store::{
+    EntryReferenceStore, ExchangeRateStore, OrganizationStore, PaymentStore, Stores,
+    TransactionStore, WorkflowStore,
+};
 pub use subscription::{Feature, LimitCheckResult, ResourceLimit, SubscriptionRepository};
 pub use transaction::{
-    CreateLedgerEntryInput, CreateTransactionInput, LedgerEntryWithDimensions, TransactionError,
-    TransactionFilter, TransactionRepository, TransactionWithEntries,
+    CreateLedgerEntryInput, CreateTransactionInput, DraftPrefill, EntryReferences,
+    LedgerEntryWithDimensions, PrefilledDraft, TransactionError, TransactionFilter,
+    TransactionRepository, TransactionSortField, TransactionSummary, TransactionWithEntries,
+    UpdateTransactionInput, transaction_fingerprint,
+};
+pub use transaction_template::{
+    CreateTransactionTemplateInput, TemplateLineInput, TemplateWithLines, TransactionTemplateError,
+    TransactionTemplateRepository, UpdateTransactionTemplateInput,
 };
-pub use user::UserRepository;
+pub use user::{AnonymizedUser, SYSTEM_USER_ID, UpdateProfileInput, UserError, UserRepository};
+pub use voucher::VoucherRepository;
 pub use workflow::{
-    BulkApproveItemResult, BulkApproveResult, PendingTransaction, VoidResult, WorkflowRepository,
+    ApprovalContext, ApprovalOutcome, ApprovalPreview, ApprovalRequirement, BulkApproveItemResult,
+    BulkApproveResult, BulkVoidItemResult, BulkVoidResult, DueEscalation, EscalationRecipient,
+    PendingSortField, PendingTransaction, PreviewApprover, StatusHistoryEntry, TransactionActions,
+    TransactionHistory, VoidResult, WorkflowRepository,
 };
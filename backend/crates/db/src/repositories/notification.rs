@@ -0,0 +1,311 @@
+//! Notification repository: the in-app inbox and delivery preferences.
+//!
+//! Code that tells users about something calls [`NotificationRepository::notify`],
+//! which checks each recipient's preference for the notification type,
+//! writes the in-app notifications and returns who should also be emailed.
+//! Sending the emails is left to the caller.
+
+use std::collections::{HashMap, HashSet};
+
+use chrono::Utc;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, DatabaseConnection, DbErr, EntityTrait, Order, PaginatorTrait,
+    QueryFilter, QueryOrder, QuerySelect, Set, TransactionTrait, prelude::DateTimeWithTimeZone,
+    sea_query::Expr,
+};
+use uuid::Uuid;
+use zeltra_core::notification::{Delivery, NotificationType};
+
+use crate::entities::{notification_preferences, notifications};
+
+/// Error types for notification operations.
+#[derive(Debug, thiserror::Error)]
+pub enum NotificationRepoError {
+    /// Notification not found for this user.
+    #[error("Notification not found: {0}")]
+    NotFound(Uuid),
+
+    /// Database error.
+    #[error("Database error: {0}")]
+    Database(#[from] DbErr),
+}
+
+/// Result of notifying a set of users.
+#[derive(Debug, Clone, Default)]
+pub struct NotifyOutcome {
+    /// In-app notifications written.
+    pub created: Vec<notifications::Model>,
+    /// Recipients whose preference includes email, in the order given.
+    pub email_recipients: Vec<Uuid>,
+}
+
+/// One page of a user's inbox.
+#[derive(Debug, Clone)]
+pub struct NotificationPage {
+    /// Notifications, unread first and newest first within each group.
+    pub notifications: Vec<notifications::Model>,
+    /// Total notifications in the inbox.
+    pub total: u64,
+    /// Unread notifications in the inbox.
+    pub unread: u64,
+}
+
+/// Repository for notifications and notification preferences.
+#[derive(Debug, Clone)]
+pub struct NotificationRepository {
+    db: DatabaseConnection,
+}
+
+impl NotificationRepository {
+    /// Creates a new notification repository.
+    #[must_use]
+    pub const fn new(db: DatabaseConnection) -> Self {
+        Self { db }
+    }
+
+    /// Gets a user's delivery preference for every notification type,
+    /// filling in the default where none is stored.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    pub async fn preferences(
+        &self,
+        user_id: Uuid,
+    ) -> Result<Vec<(NotificationType, Delivery)>, DbErr> {
+        let stored: HashMap<String, String> = notification_preferences::Entity::find()
+            .filter(notification_preferences::Column::UserId.eq(user_id))
+            .all(&self.db)
+            .await?
+            .into_iter()
+            .map(|p| (p.notification_type, p.delivery))
+            .collect();
+
+        Ok(NotificationType::ALL
+            .into_iter()
+            .map(|t| {
+                let delivery = stored
+                    .get(t.as_str())
+                    .and_then(|d| d.parse().ok())
+                    .unwrap_or_default();
+                (t, delivery)
+            })
+            .collect())
+    }
+
+    /// Sets a user's delivery preference for the given types, leaving the
+    /// others unchanged. Returns the full set afterwards.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a database operation fails.
+    pub async fn set_preferences(
+        &self,
+        user_id: Uuid,
+        changes: &[(NotificationType, Delivery)],
+    ) -> Result<Vec<(NotificationType, Delivery)>, DbErr> {
+        if !changes.is_empty() {
+            let now = Utc::now();
+            let txn = self.db.begin().await?;
+            notification_preferences::Entity::delete_many()
+                .filter(notification_preferences::Column::UserId.eq(user_id))
+                .filter(
+                    notification_preferences::Column::NotificationType
+                        .is_in(changes.iter().map(|(t, _)| t.as_str())),
+                )
+                .exec(&txn)
+                .await?;
+            // The last change for a type wins
+            let latest: HashMap<NotificationType, Delivery> = changes.iter().copied().collect();
+            for (notification_type, delivery) in latest {
+                notification_preferences::ActiveModel {
+                    user_id: Set(user_id),
+                    notification_type: Set(notification_type.as_str().to_string()),
+                    delivery: Set(delivery.as_str().to_string()),
+                    updated_at: Set(now.into()),
+                }
+                .insert(&txn)
+                .await?;
+            }
+            txn.commit().await?;
+        }
+
+        self.preferences(user_id).await
+    }
+
+    /// Notifies users in an organization, honouring each one's delivery
+    /// preference for `notification_type`.
+    ///
+    /// Duplicate recipients are notified once.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a database operation fails.
+    pub async fn notify(
+        &self,
+        organization_id: Uuid,
+        notification_type: NotificationType,
+        payload: serde_json::Value,
+        recipients: &[Uuid],
+    ) -> Result<NotifyOutcome, DbErr> {
+        let mut recipients = recipients.to_vec();
+        let mut seen = HashSet::new();
+        recipients.retain(|id| seen.insert(*id));
+        if recipients.is_empty() {
+            return Ok(NotifyOutcome::default());
+        }
+
+        let stored: HashMap<Uuid, Delivery> = notification_preferences::Entity::find()
+            .filter(notification_preferences::Column::UserId.is_in(recipients.iter().copied()))
+            .filter(
+                notification_preferences::Column::NotificationType.eq(notification_type.as_str()),
+            )
+            .all(&self.db)
+            .await?
+            .into_iter()
+            .filter_map(|p| p.delivery.parse().ok().map(|d| (p.user_id, d)))
+            .collect();
+
+        let now = Utc::now();
+        let mut outcome = NotifyOutcome::default();
+        for user_id in recipients {
+            let delivery = stored.get(&user_id).copied().unwrap_or_default();
+            if delivery.in_app() {
+                let notification = notifications::ActiveModel {
+                    id: Set(Uuid::new_v4()),
+                    organization_id: Set(organization_id),
+                    user_id: Set(user_id),
+                    notification_type: Set(notification_type.as_str().to_string()),
+                    payload: Set(payload.clone()),
+                    read_at: Set(None),
+                    created_at: Set(now.into()),
+                }
+                .insert(&self.db)
+                .await?;
+                outcome.created.push(notification);
+            }
+            if delivery.email() {
+                outcome.email_recipients.push(user_id);
+            }
+        }
+
+        Ok(outcome)
+    }
+
+    /// Whether anyone in the organization has a notification of
+    /// `notification_type` whose payload contains `payload`.
+    ///
+    /// Lets senders that may run more than once, such as scheduled jobs,
+    /// notify about the same thing only once.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    pub async fn exists(
+        &self,
+        organization_id: Uuid,
+        notification_type: NotificationType,
+        payload: serde_json::Value,
+    ) -> Result<bool, DbErr> {
+        let count = notifications::Entity::find()
+            .filter(notifications::Column::OrganizationId.eq(organization_id))
+            .filter(notifications::Column::NotificationType.eq(notification_type.as_str()))
+            .filter(Expr::cust_with_values(
+                "notifications.payload @> $1",
+                [payload],
+            ))
+            .count(&self.db)
+            .await?;
+        Ok(count > 0)
+    }
+
+    /// Lists a user's notifications in an organization, unread first and
+    /// newest first within each group.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    pub async fn list(
+        &self,
+        organization_id: Uuid,
+        user_id: Uuid,
+        page: u64,
+        limit: u64,
+    ) -> Result<NotificationPage, DbErr> {
+        let inbox = notifications::Entity::find()
+            .filter(notifications::Column::OrganizationId.eq(organization_id))
+            .filter(notifications::Column::UserId.eq(user_id));
+
+        let total = inbox.clone().count(&self.db).await?;
+        let unread = inbox
+            .clone()
+            .filter(notifications::Column::ReadAt.is_null())
+            .count(&self.db)
+            .await?;
+        let notifications = inbox
+            .order_by(
+                Expr::col(notifications::Column::ReadAt).is_null(),
+                Order::Desc,
+            )
+            .order_by_desc(notifications::Column::CreatedAt)
+            .order_by_desc(notifications::Column::Id)
+            .offset(page.saturating_sub(1) * limit)
+            .limit(limit)
+            .all(&self.db)
+            .await?;
+
+        Ok(NotificationPage {
+            notifications,
+            total,
+            unread,
+        })
+    }
+
+    /// Marks one of a user's notifications read. Marking it again keeps the
+    /// first read time.
+    ///
+    /// # Errors
+    ///
+    /// Returns `NotFound` if the notification is not the user's in this
+    /// organization, or an error if a database operation fails.
+    pub async fn mark_read(
+        &self,
+        organization_id: Uuid,
+        user_id: Uuid,
+        notification_id: Uuid,
+    ) -> Result<notifications::Model, NotificationRepoError> {
+        let notification = notifications::Entity::find_by_id(notification_id)
+            .filter(notifications::Column::OrganizationId.eq(organization_id))
+            .filter(notifications::Column::UserId.eq(user_id))
+            .one(&self.db)
+            .await?
+            .ok_or(NotificationRepoError::NotFound(notification_id))?;
+        if notification.read_at.is_some() {
+            return Ok(notification);
+        }
+
+        let mut active: notifications::ActiveModel = notification.into();
+        active.read_at = Set(Some(Utc::now().into()));
+        Ok(active.update(&self.db).await?)
+    }
+
+    /// Marks every unread notification of a user in an organization read,
+    /// returning how many changed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database update fails.
+    pub async fn mark_all_read(&self, organization_id: Uuid, user_id: Uuid) -> Result<u64, DbErr> {
+        let result = notifications::Entity::update_many()
+            .col_expr(
+                notifications::Column::ReadAt,
+                Expr::value(DateTimeWithTimeZone::from(Utc::now())),
+            )
+            .filter(notifications::Column::OrganizationId.eq(organization_id))
+            .filter(notifications::Column::UserId.eq(user_id))
+            .filter(notifications::Column::ReadAt.is_null())
+            .exec(&self.db)
+            .await?;
+        Ok(result.rows_affected)
+    }
+}
@@ -0,0 +1,88 @@
+//! Audit log of support operations run by Zeltra's operators.
+//!
+//! The `admin` binary records every command here, including those that only
+//! printed a dry-run plan, so support activity can be traced back to the
+//! operator who ran it.
+
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, DatabaseConnection, DbErr, EntityTrait, QueryFilter, QueryOrder,
+    QuerySelect, Set,
+};
+use uuid::Uuid;
+
+use crate::entities::operator_audit_log;
+
+/// One support operation to record.
+#[derive(Debug, Clone)]
+pub struct OperatorActionInput {
+    /// Name of the operator who ran the command.
+    pub operator: String,
+    /// Command, e.g. `transaction force-void`.
+    pub command: String,
+    /// What the command was run against: a slug, email address or ID.
+    pub target: String,
+    /// Organization the command touched, if any.
+    pub organization_id: Option<Uuid>,
+    /// Whether the command was carried out, as opposed to a dry run.
+    pub executed: bool,
+    /// Reason the operator gave.
+    pub reason: Option<String>,
+    /// Command-specific details.
+    pub details: serde_json::Value,
+}
+
+/// Repository for the operator audit log.
+#[derive(Debug, Clone)]
+pub struct OperatorAuditRepository {
+    db: DatabaseConnection,
+}
+
+impl OperatorAuditRepository {
+    /// Creates a new operator audit repository.
+    #[must_use]
+    pub const fn new(db: DatabaseConnection) -> Self {
+        Self { db }
+    }
+
+    /// Records a support operation.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the insert fails.
+    pub async fn record(
+        &self,
+        input: OperatorActionInput,
+    ) -> Result<operator_audit_log::Model, DbErr> {
+        operator_audit_log::ActiveModel {
+            id: Set(Uuid::new_v4()),
+            operator: Set(input.operator),
+            command: Set(input.command),
+            target: Set(input.target),
+            organization_id: Set(input.organization_id),
+            executed: Set(input.executed),
+            reason: Set(input.reason),
+            details: Set(input.details),
+            ..Default::default()
+        }
+        .insert(&self.db)
+        .await
+    }
+
+    /// Lists the most recent operations on an organization, newest first.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    pub async fn list_for_organization(
+        &self,
+        organization_id: Uuid,
+        limit: u64,
+    ) -> Result<Vec<operator_audit_log::Model>, DbErr> {
+        operator_audit_log::Entity::find()
+            .filter(operator_audit_log::Column::OrganizationId.eq(organization_id))
+            .order_by_desc(operator_audit_log::Column::CreatedAt)
+            .limit(limit)
+            .all(&self.db)
+            .await
+    }
+}
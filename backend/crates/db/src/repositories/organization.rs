@@ -1,18 +1,30 @@
 //! Organization repository for database operations.
 
+use chrono::{Datelike, NaiveDate};
 use rust_decimal::Decimal;
 use sea_orm::{
-    ActiveModelTrait, ColumnTrait, DatabaseConnection, DbErr, EntityTrait, PaginatorTrait,
-    QueryFilter, Set, TransactionTrait,
+    ActiveModelTrait, ColumnTrait, ConnectionTrait, DatabaseConnection, DbErr, EntityTrait,
+    PaginatorTrait, QueryFilter, QuerySelect, Set, TransactionTrait,
 };
-use serde_json::json;
+use serde_json::{Value, json};
 use uuid::Uuid;
+use zeltra_core::organization::{SlugError, validate_slug};
+use zeltra_core::settings::{FieldError, OrganizationSettings, SettingsError, apply_patch};
 
 use crate::entities::{
-    currencies, organization_users, organizations,
+    chart_of_accounts, currencies, dimension_types, organization_slug_history, organization_users,
+    organizations,
     sea_orm_active_enums::{SubscriptionStatus, SubscriptionTier, TransactionStatus, UserRole},
     transactions, users,
 };
+use crate::repositories::account::import_accounts;
+use crate::repositories::chart_template::ChartTemplate;
+use crate::repositories::dimension::{CreateDimensionTypeInput, insert_dimension_type};
+use crate::repositories::fiscal::{
+    CreateFiscalYearInput, FiscalYearWithPeriods, insert_fiscal_year,
+};
+use crate::repositories::subscription::{LimitCheckResult, ResourceLimit, SubscriptionRepository};
+use crate::repositories::user::bump_token_version;
 
 /// Error types for organization operations.
 #[derive(Debug, thiserror::Error)]
@@ -49,15 +61,68 @@ pub enum OrganizationError {
     #[error("Name must be between 1 and 255 characters")]
     InvalidName,
 
+    /// Slug breaks the format rules.
+    #[error(transparent)]
+    InvalidSlug(#[from] SlugError),
+
+    /// Slug belongs to another organization, currently or previously.
+    #[error("Slug is already in use")]
+    SlugTaken,
+
     /// No fields provided for update.
     #[error("No fields provided for update")]
     EmptyUpdate,
 
+    /// Settings are unknown or invalid.
+    #[error(transparent)]
+    InvalidSettings(#[from] SettingsError),
+
+    /// The organization's plan does not allow another member.
+    #[error("Tier limit exceeded for {}", .0.resource.as_str())]
+    TierLimitExceeded(LimitCheckResult),
+
+    /// A bootstrap step failed; nothing was created.
+    #[error("Failed to bootstrap {step}: {message}")]
+    BootstrapFailed {
+        /// The step that failed.
+        step: &'static str,
+        /// Why it failed.
+        message: String,
+    },
+
     /// Database error.
     #[error("Database error: {0}")]
     Database(#[from] DbErr),
 }
 
+/// Dimension types created when an organization is bootstrapped, as
+/// `(code, name)`.
+const BOOTSTRAP_DIMENSION_TYPES: &[(&str, &str)] =
+    &[("DEPARTMENT", "Department"), ("PROJECT", "Project")];
+
+/// What to set up in a new organization besides its owner.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct OrganizationBootstrap {
+    /// Create the calendar fiscal year containing today, in the
+    /// organization's timezone, with monthly periods.
+    pub fiscal_year: bool,
+    /// Chart of accounts to create, in the base currency.
+    pub chart_template: Option<ChartTemplate>,
+    /// Create the Department and Project dimension types.
+    pub dimension_types: bool,
+}
+
+/// What bootstrapping a new organization created.
+#[derive(Debug, Clone, Default)]
+pub struct BootstrapSummary {
+    /// The fiscal year and its periods.
+    pub fiscal_year: Option<FiscalYearWithPeriods>,
+    /// Accounts from the chart template.
+    pub accounts: Vec<chart_of_accounts::Model>,
+    /// Dimension types.
+    pub dimension_types: Vec<dimension_types::Model>,
+}
+
 /// Organization repository for CRUD operations.
 #[derive(Debug, Clone)]
 pub struct OrganizationRepository {
@@ -82,17 +147,33 @@ impl OrganizationRepository {
 
     /// Finds an organization by slug.
     ///
+    /// Falls back to previous slugs, so a renamed organization is still
+    /// found by its old links. The returned model carries the current slug;
+    /// callers should compare it with the one they looked up and point
+    /// clients at the canonical slug when they differ.
+    ///
     /// # Errors
     ///
     /// Returns an error if the database query fails.
     pub async fn find_by_slug(&self, slug: &str) -> Result<Option<organizations::Model>, DbErr> {
-        organizations::Entity::find()
+        let current = organizations::Entity::find()
             .filter(organizations::Column::Slug.eq(slug))
             .one(&self.db)
-            .await
+            .await?;
+        if current.is_some() {
+            return Ok(current);
+        }
+
+        match organization_slug_history::Entity::find_by_id(slug)
+            .one(&self.db)
+            .await?
+        {
+            Some(previous) => self.find_by_id(previous.organization_id).await,
+            None => Ok(None),
+        }
     }
 
-    /// Checks if a slug is already taken.
+    /// Checks if a slug is already taken, currently or as a previous slug.
     ///
     /// # Errors
     ///
@@ -102,8 +183,118 @@ impl OrganizationRepository {
             .filter(organizations::Column::Slug.eq(slug))
             .count(&self.db)
             .await?;
+        let previous = organization_slug_history::Entity::find_by_id(slug)
+            .count(&self.db)
+            .await?;
 
-        Ok(count > 0)
+        Ok(count + previous > 0)
+    }
+
+    /// Validates the fields of a new organization.
+    ///
+    /// Checks the name length, the slug rules, that the base currency exists
+    /// in the currencies table, and that the timezone is a valid IANA
+    /// identifier. Slug availability is checked separately with
+    /// [`Self::slug_exists`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the currency lookup fails.
+    pub async fn validate_new_organization(
+        &self,
+        name: &str,
+        slug: &str,
+        base_currency: &str,
+        timezone: &str,
+    ) -> Result<Vec<FieldError>, DbErr> {
+        let mut errors = Vec::new();
+
+        if name.is_empty() || name.len() > 255 {
+            errors.push(FieldError::new(
+                "name",
+                OrganizationError::InvalidName.to_string(),
+            ));
+        }
+        if let Err(e) = validate_slug(slug) {
+            errors.push(FieldError::new("slug", e.to_string()));
+        }
+        if currencies::Entity::find_by_id(base_currency)
+            .one(&self.db)
+            .await?
+            .is_none()
+        {
+            errors.push(FieldError::new(
+                "base_currency",
+                format!("Unknown currency code: {base_currency}"),
+            ));
+        }
+        if timezone.parse::<chrono_tz::Tz>().is_err() {
+            errors.push(FieldError::new(
+                "timezone",
+                format!("Unknown IANA timezone: {timezone}"),
+            ));
+        }
+
+        Ok(errors)
+    }
+
+    /// Renames an organization's slug.
+    ///
+    /// The old slug is kept in the slug history so lookups by it still find
+    /// the organization. An organization may take back one of its own
+    /// previous slugs; anyone else's, current or previous, is rejected.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the slug is invalid or taken, the organization is
+    /// not found, or a database operation fails.
+    pub async fn rename_slug(
+        &self,
+        org_id: Uuid,
+        slug: &str,
+    ) -> Result<organizations::Model, OrganizationError> {
+        validate_slug(slug)?;
+
+        let org = organizations::Entity::find_by_id(org_id)
+            .one(&self.db)
+            .await?
+            .ok_or(OrganizationError::NotFound)?;
+        if org.slug == slug {
+            return Ok(org);
+        }
+
+        let current_owner = organizations::Entity::find()
+            .filter(organizations::Column::Slug.eq(slug))
+            .one(&self.db)
+            .await?;
+        let previous_owner = organization_slug_history::Entity::find_by_id(slug)
+            .one(&self.db)
+            .await?;
+        if current_owner.is_some() || previous_owner.is_some_and(|p| p.organization_id != org_id) {
+            return Err(OrganizationError::SlugTaken);
+        }
+
+        let txn = self.db.begin().await?;
+        let now = chrono::Utc::now().into();
+
+        organization_slug_history::Entity::delete_by_id(slug)
+            .exec(&txn)
+            .await?;
+        organization_slug_history::ActiveModel {
+            slug: Set(org.slug.clone()),
+            organization_id: Set(org_id),
+            renamed_at: Set(now),
+        }
+        .insert(&txn)
+        .await?;
+
+        let mut active: organizations::ActiveModel = org.into();
+        active.slug = Set(slug.to_string());
+        active.updated_at = Set(now);
+        let updated = active.update(&txn).await?;
+
+        txn.commit().await?;
+        Ok(updated)
     }
 
     /// Creates a new organization with the creator as owner.
@@ -120,63 +311,102 @@ impl OrganizationRepository {
         owner_id: Uuid,
     ) -> Result<organizations::Model, DbErr> {
         let txn = self.db.begin().await?;
+        let org = insert_with_owner(&txn, name, slug, base_currency, timezone, owner_id).await?;
+        txn.commit().await?;
 
-        let now = chrono::Utc::now().into();
-        let org_id = Uuid::new_v4();
-
-        // Create organization
-        let org = organizations::ActiveModel {
-            id: Set(org_id),
-            name: Set(name.to_string()),
-            slug: Set(slug.to_string()),
-            base_currency: Set(base_currency.to_string()),
-            timezone: Set(timezone.to_string()),
-            settings: Set(json!({})),
-            is_active: Set(true),
-            subscription_tier: Set(SubscriptionTier::Starter),
-            subscription_status: Set(SubscriptionStatus::Trialing),
-            trial_ends_at: Set(Some(
-                (chrono::Utc::now() + chrono::Duration::days(14)).into(),
-            )),
-            subscription_ends_at: Set(None),
-            payment_provider: Set(None),
-            payment_customer_id: Set(None),
-            payment_subscription_id: Set(None),
-            created_at: Set(now),
-            updated_at: Set(now),
-        };
+        Ok(org)
+    }
 
-        let org = org.insert(&txn).await?;
+    /// Creates a new organization with the creator as owner and sets up
+    /// what `bootstrap` asks for, all in one database transaction.
+    ///
+    /// The fiscal year, chart of accounts and dimension types are created
+    /// the same way as through their own endpoints, so the same validation
+    /// and tier limits apply.
+    ///
+    /// # Errors
+    ///
+    /// Returns `BootstrapFailed` naming the step that failed, or an error if
+    /// a database operation fails. Nothing is created on error.
+    pub async fn create_bootstrapped(
+        &self,
+        name: &str,
+        slug: &str,
+        base_currency: &str,
+        timezone: &str,
+        owner_id: Uuid,
+        bootstrap: OrganizationBootstrap,
+    ) -> Result<(organizations::Model, BootstrapSummary), OrganizationError> {
+        let txn = self.db.begin().await?;
+        let org = insert_with_owner(&txn, name, slug, base_currency, timezone, owner_id).await?;
+        let mut summary = BootstrapSummary::default();
+
+        if bootstrap.fiscal_year {
+            let tz = timezone
+                .parse::<chrono_tz::Tz>()
+                .unwrap_or(chrono_tz::Tz::UTC);
+            let year = chrono::Utc::now().with_timezone(&tz).year();
+            let input = CreateFiscalYearInput {
+                organization_id: org.id,
+                name: format!("FY {year}"),
+                start_date: NaiveDate::from_ymd_opt(year, 1, 1).unwrap_or_default(),
+                end_date: NaiveDate::from_ymd_opt(year, 12, 31).unwrap_or_default(),
+            };
+            let fiscal_year = insert_fiscal_year(&txn, input)
+                .await
+                .map_err(|e| bootstrap_failed("fiscal_year", &e))?;
+            summary.fiscal_year = Some(fiscal_year);
+        }
 
-        // Add owner to organization
-        let org_user = organization_users::ActiveModel {
-            user_id: Set(owner_id),
-            organization_id: Set(org_id),
-            role: Set(UserRole::Owner),
-            approval_limit: Set(None),
-            created_at: Set(now),
-            updated_at: Set(now),
-        };
+        if let Some(template) = bootstrap.chart_template {
+            let inputs = template.import_inputs(org.id, base_currency);
+            summary.accounts = import_accounts(&txn, org.id, inputs)
+                .await
+                .map_err(|e| bootstrap_failed("chart_of_accounts", &e))?;
+        }
 
-        org_user.insert(&txn).await?;
+        if bootstrap.dimension_types {
+            for (sort_order, (code, name)) in (1..).zip(BOOTSTRAP_DIMENSION_TYPES) {
+                let input = CreateDimensionTypeInput {
+                    organization_id: org.id,
+                    code: (*code).to_string(),
+                    name: (*name).to_string(),
+                    description: None,
+                    is_required: false,
+                    is_active: true,
+                    sort_order,
+                };
+                let created = insert_dimension_type(&txn, input)
+                    .await
+                    .map_err(|e| bootstrap_failed("dimension_types", &e))?;
+                summary.dimension_types.push(created);
+            }
+        }
 
         txn.commit().await?;
 
-        Ok(org)
+        Ok((org, summary))
     }
 
     /// Adds a user to an organization.
     ///
     /// # Errors
     ///
-    /// Returns an error if the database insert fails.
+    /// Returns an error if another member would exceed the organization's
+    /// tier limit, or if the database insert fails.
     pub async fn add_user(
         &self,
         org_id: Uuid,
         user_id: Uuid,
         role: UserRole,
         approval_limit: Option<Decimal>,
-    ) -> Result<organization_users::Model, DbErr> {
+    ) -> Result<organization_users::Model, OrganizationError> {
+        let txn = self.db.begin().await?;
+        let limit = SubscriptionRepository::check_limit(&txn, org_id, ResourceLimit::Users).await?;
+        if !limit.allowed {
+            return Err(OrganizationError::TierLimitExceeded(limit));
+        }
+
         let now = chrono::Utc::now().into();
 
         let org_user = organization_users::ActiveModel {
@@ -188,7 +418,9 @@ impl OrganizationRepository {
             updated_at: Set(now),
         };
 
-        org_user.insert(&self.db).await
+        let membership = org_user.insert(&txn).await?;
+        txn.commit().await?;
+        Ok(membership)
     }
 
     /// Gets all users in an organization.
@@ -298,6 +530,102 @@ impl OrganizationRepository {
         Ok(Some(updated))
     }
 
+    /// Returns an organization's settings, with defaults for unset fields.
+    ///
+    /// # Errors
+    ///
+    /// Returns `NotFound` if the organization does not exist, or
+    /// `InvalidSettings` if the stored document does not match the schema.
+    pub async fn get_settings(
+        &self,
+        org_id: Uuid,
+    ) -> Result<OrganizationSettings, OrganizationError> {
+        let org = self
+            .find_by_id(org_id)
+            .await?
+            .ok_or(OrganizationError::NotFound)?;
+
+        Ok(OrganizationSettings::from_value(&org.settings)?)
+    }
+
+    /// Deep-merges a settings patch into an organization's settings.
+    ///
+    /// Unknown keys and invalid values are rejected with field-level errors,
+    /// and newly referenced accounts must be active accounts of the
+    /// organization. The stored document is rewritten in normalized form with
+    /// `updated_at` set to now.
+    ///
+    /// # Errors
+    ///
+    /// Returns `NotFound` if the organization does not exist, or
+    /// `InvalidSettings` if the patch is rejected.
+    pub async fn update_settings(
+        &self,
+        org_id: Uuid,
+        patch: &Value,
+    ) -> Result<OrganizationSettings, OrganizationError> {
+        let txn = self.db.begin().await?;
+
+        // Lock the row so concurrent patches merge instead of overwriting
+        let org = organizations::Entity::find_by_id(org_id)
+            .lock_exclusive()
+            .one(&txn)
+            .await?
+            .ok_or(OrganizationError::NotFound)?;
+
+        let now = chrono::Utc::now();
+        let settings = apply_patch(&org.settings, patch, now)?;
+
+        let referenced_accounts = [
+            (
+                "bank_import.suspense_account_id",
+                settings.bank_import.suspense_account_id,
+            ),
+            (
+                "fx_revaluation.gain_account_id",
+                settings.fx_revaluation.gain_account_id,
+            ),
+            (
+                "fx_revaluation.loss_account_id",
+                settings.fx_revaluation.loss_account_id,
+            ),
+            (
+                "currency_balance.fx_clearing_account_id",
+                settings.currency_balance.fx_clearing_account_id,
+            ),
+        ];
+        let mut errors = Vec::new();
+        for (field, account_id) in referenced_accounts {
+            let pointer = format!("/{}", field.replace('.', "/"));
+            let changed = patch.pointer(&pointer).is_some_and(|v| !v.is_null());
+            let Some(account_id) = account_id.filter(|_| changed) else {
+                continue;
+            };
+            let account = chart_of_accounts::Entity::find_by_id(account_id)
+                .filter(chart_of_accounts::Column::OrganizationId.eq(org_id))
+                .filter(chart_of_accounts::Column::IsActive.eq(true))
+                .one(&txn)
+                .await?;
+            if account.is_none() {
+                errors.push(FieldError::new(
+                    field,
+                    "must be an active account of this organization",
+                ));
+            }
+        }
+        if !errors.is_empty() {
+            return Err(SettingsError::Invalid(errors).into());
+        }
+
+        let mut active: organizations::ActiveModel = org.into();
+        active.settings = Set(settings.to_value());
+        active.updated_at = Set(now.into());
+        active.update(&txn).await?;
+
+        txn.commit().await?;
+        Ok(settings)
+    }
+
     /// Checks if an organization has any posted transactions.
     ///
     /// This is used to prevent changing base currency after posting.
@@ -417,6 +745,9 @@ impl OrganizationRepository {
     /// - Requester has sufficient permissions (admin cannot remove owner)
     /// - Cannot remove the last owner
     ///
+    /// The user's token version is bumped so outstanding access tokens for
+    /// this organization stop working immediately.
+    ///
     /// # Errors
     ///
     /// Returns an error if validation fails or database operation fails.
@@ -452,12 +783,17 @@ impl OrganizationRepository {
             }
         }
 
-        // Delete membership
+        // Delete membership and invalidate tokens carrying the old role
+        let txn = self.db.begin().await?;
+
         organization_users::Entity::delete_many()
             .filter(organization_users::Column::OrganizationId.eq(org_id))
             .filter(organization_users::Column::UserId.eq(user_id))
-            .exec(&self.db)
+            .exec(&txn)
             .await?;
+        bump_token_version(&txn, user_id).await?;
+
+        txn.commit().await?;
 
         Ok(())
     }
@@ -470,6 +806,9 @@ impl OrganizationRepository {
     /// - Cannot demote the last owner
     /// - At least one field must be provided
     ///
+    /// Access tokens embed the role and approval limit, so the user's token
+    /// version is bumped and they must refresh to pick up the change.
+    ///
     /// # Errors
     ///
     /// Returns an error if validation fails or database operation fails.
@@ -531,7 +870,12 @@ impl OrganizationRepository {
         }
         active.updated_at = Set(chrono::Utc::now().into());
 
-        let updated = active.update(&self.db).await?;
+        // Tokens embed role and approval limit, so force the user to refresh
+        let txn = self.db.begin().await?;
+        let updated = active.update(&txn).await?;
+        bump_token_version(&txn, user_id).await?;
+        txn.commit().await?;
+
         Ok(updated)
     }
 }
@@ -555,6 +899,65 @@ pub const fn get_role_level(role: &UserRole) -> u8 {
     role_level(role)
 }
 
+/// Inserts an organization and its owner membership.
+pub(crate) async fn insert_with_owner<C: ConnectionTrait>(
+    conn: &C,
+    name: &str,
+    slug: &str,
+    base_currency: &str,
+    timezone: &str,
+    owner_id: Uuid,
+) -> Result<organizations::Model, DbErr> {
+    let now = chrono::Utc::now().into();
+    let org_id = Uuid::new_v4();
+
+    // Create organization
+    let org = organizations::ActiveModel {
+        id: Set(org_id),
+        name: Set(name.to_string()),
+        slug: Set(slug.to_string()),
+        base_currency: Set(base_currency.to_string()),
+        timezone: Set(timezone.to_string()),
+        settings: Set(json!({})),
+        is_active: Set(true),
+        subscription_tier: Set(SubscriptionTier::Starter),
+        subscription_status: Set(SubscriptionStatus::Trialing),
+        trial_ends_at: Set(Some(
+            (chrono::Utc::now() + chrono::Duration::days(14)).into(),
+        )),
+        subscription_ends_at: Set(None),
+        payment_provider: Set(None),
+        payment_customer_id: Set(None),
+        payment_subscription_id: Set(None),
+        created_at: Set(now),
+        updated_at: Set(now),
+    };
+
+    let org = org.insert(conn).await?;
+
+    // Add owner to organization
+    let org_user = organization_users::ActiveModel {
+        user_id: Set(owner_id),
+        organization_id: Set(org_id),
+        role: Set(UserRole::Owner),
+        approval_limit: Set(None),
+        created_at: Set(now),
+        updated_at: Set(now),
+    };
+
+    org_user.insert(conn).await?;
+
+    Ok(org)
+}
+
+/// A failed bootstrap step as an organization error.
+fn bootstrap_failed(step: &'static str, err: &impl std::fmt::Display) -> OrganizationError {
+    OrganizationError::BootstrapFailed {
+        step,
+        message: err.to_string(),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,365 @@
+//! Payment repository for applying payments against invoices and bills.
+//!
+//! A posted payment is applied to posted invoices or bills in partial
+//! amounts. The payment and the settled document are locked while an
+//! application is checked, so concurrent applications are counted in turn.
+
+use chrono::Utc;
+use rust_decimal::Decimal;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, ConnectionTrait, DatabaseConnection, DbErr, EntityTrait,
+    QueryFilter, QuerySelect, Set, TransactionTrait,
+};
+use uuid::Uuid;
+use zeltra_core::ledger::{ApplicationError, ApplicationLimits, SettlementStatus};
+use zeltra_shared::types::{OrganizationId, TransactionId};
+
+use crate::entities::{
+    ledger_entries, payment_applications,
+    sea_orm_active_enums::{TransactionStatus, TransactionType},
+    transactions,
+};
+
+/// Error types for payment application operations.
+#[derive(Debug, thiserror::Error)]
+pub enum PaymentError {
+    /// Payment transaction not found.
+    #[error("Payment not found: {0}")]
+    PaymentNotFound(Uuid),
+
+    /// Invoice or bill not found.
+    #[error("Transaction not found: {0}")]
+    DocumentNotFound(Uuid),
+
+    /// The transaction applied from is not a payment.
+    #[error("Transaction {0} is not a payment")]
+    NotAPayment(Uuid),
+
+    /// The transaction applied to is not an invoice or bill.
+    #[error("Transaction {0} is not an invoice or bill")]
+    NotSettleable(Uuid),
+
+    /// The transaction is not posted.
+    #[error("Transaction {0} is not posted")]
+    NotPosted(Uuid),
+
+    /// The payment has been voided.
+    #[error("Payment {0} is voided")]
+    PaymentVoided(Uuid),
+
+    /// The payment is already applied to the document.
+    #[error("Payment is already applied to transaction {0}")]
+    AlreadyApplied(Uuid),
+
+    /// The payment is not applied to the document.
+    #[error("Payment is not applied to transaction {0}")]
+    ApplicationNotFound(Uuid),
+
+    /// The amount breaks the payment's or the document's limit.
+    #[error(transparent)]
+    Application(#[from] ApplicationError),
+
+    /// Database error.
+    #[error("Database error: {0}")]
+    Database(#[from] DbErr),
+}
+
+/// Input for applying a payment to an invoice or bill.
+#[derive(Debug, Clone)]
+pub struct ApplyPaymentInput {
+    /// Invoice or bill the payment settles.
+    pub settles_transaction_id: Uuid,
+    /// Amount applied, in functional currency.
+    pub applied_amount: Decimal,
+    /// User applying the payment.
+    pub created_by: Uuid,
+}
+
+/// How much of an invoice or bill has been settled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Settlement {
+    /// Functional total of the invoice or bill.
+    pub total: Decimal,
+    /// Amount applied by payments.
+    pub applied: Decimal,
+    /// Status following from the two amounts.
+    pub status: SettlementStatus,
+}
+
+impl Settlement {
+    /// Settlement of `total` of which `applied` was paid.
+    #[must_use]
+    pub fn new(total: Decimal, applied: Decimal) -> Self {
+        Self {
+            total,
+            applied,
+            status: SettlementStatus::from_amounts(total, applied),
+        }
+    }
+
+    /// Amount left to settle.
+    #[must_use]
+    pub fn outstanding(&self) -> Decimal {
+        (self.total - self.applied).max(Decimal::ZERO)
+    }
+}
+
+/// A saved application and the settled document's new state.
+#[derive(Debug, Clone)]
+pub struct AppliedPayment {
+    /// The application.
+    pub application: payment_applications::Model,
+    /// Settlement of the invoice or bill after the application.
+    pub settlement: Settlement,
+}
+
+/// Repository for payment applications.
+#[derive(Debug, Clone)]
+pub struct PaymentRepository {
+    db: DatabaseConnection,
+}
+
+impl PaymentRepository {
+    /// Creates a new payment repository.
+    #[must_use]
+    pub fn new(db: DatabaseConnection) -> Self {
+        Self { db }
+    }
+
+    /// Applies part of a posted payment to a posted invoice or bill.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if either transaction is missing, of the wrong type
+    /// or not posted, if the payment is already applied to the document, or
+    /// if the amount exceeds the payment's unapplied total or the document's
+    /// outstanding balance.
+    pub async fn apply_payment(
+        &self,
+        organization_id: OrganizationId,
+        payment_id: TransactionId,
+        input: ApplyPaymentInput,
+    ) -> Result<AppliedPayment, PaymentError> {
+        let txn = self.db.begin().await?;
+
+        // Lock the payment first, then the document; a payment is never the
+        // document, so the order is always the same
+        let payment = transactions::Entity::find_by_id(payment_id)
+            .filter(transactions::Column::OrganizationId.eq(organization_id))
+            .lock_exclusive()
+            .one(&txn)
+            .await?
+            .ok_or(PaymentError::PaymentNotFound(payment_id.into_inner()))?;
+        check_payment(&payment)?;
+
+        let document = transactions::Entity::find_by_id(input.settles_transaction_id)
+            .filter(transactions::Column::OrganizationId.eq(organization_id))
+            .lock_exclusive()
+            .one(&txn)
+            .await?
+            .ok_or(PaymentError::DocumentNotFound(input.settles_transaction_id))?;
+        if !is_settleable(&document.transaction_type) {
+            return Err(PaymentError::NotSettleable(document.id));
+        }
+        if document.status != TransactionStatus::Posted {
+            return Err(PaymentError::NotPosted(document.id));
+        }
+
+        let existing = payment_applications::Entity::find()
+            .filter(payment_applications::Column::PaymentTransactionId.eq(payment.id))
+            .filter(payment_applications::Column::SettlesTransactionId.eq(document.id))
+            .one(&txn)
+            .await?;
+        if existing.is_some() {
+            return Err(PaymentError::AlreadyApplied(document.id));
+        }
+
+        let limits = ApplicationLimits {
+            payment_total: functional_total(&txn, payment.id).await?,
+            payment_applied: applied_sum(
+                &txn,
+                payment_applications::Column::PaymentTransactionId,
+                payment.id,
+            )
+            .await?,
+            document_total: functional_total(&txn, document.id).await?,
+            document_applied: applied_sum(
+                &txn,
+                payment_applications::Column::SettlesTransactionId,
+                document.id,
+            )
+            .await?,
+        };
+        limits.check(input.applied_amount)?;
+
+        let application = payment_applications::ActiveModel {
+            id: Set(Uuid::new_v4()),
+            organization_id: Set(organization_id.into_inner()),
+            payment_transaction_id: Set(payment.id),
+            settles_transaction_id: Set(document.id),
+            applied_amount: Set(input.applied_amount),
+            created_by: Set(input.created_by),
+            created_at: Set(Utc::now().into()),
+        }
+        .insert(&txn)
+        .await?;
+
+        txn.commit().await?;
+
+        Ok(AppliedPayment {
+            application,
+            settlement: Settlement::new(
+                limits.document_total,
+                limits.document_applied + input.applied_amount,
+            ),
+        })
+    }
+
+    /// Removes a payment's application to an invoice or bill.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the payment is missing or voided, or if it is not
+    /// applied to the document.
+    pub async fn unapply_payment(
+        &self,
+        organization_id: OrganizationId,
+        payment_id: TransactionId,
+        settles_transaction_id: Uuid,
+    ) -> Result<Settlement, PaymentError> {
+        let txn = self.db.begin().await?;
+
+        let payment = transactions::Entity::find_by_id(payment_id)
+            .filter(transactions::Column::OrganizationId.eq(organization_id))
+            .lock_exclusive()
+            .one(&txn)
+            .await?
+            .ok_or(PaymentError::PaymentNotFound(payment_id.into_inner()))?;
+        if payment.transaction_type != TransactionType::Payment {
+            return Err(PaymentError::NotAPayment(payment.id));
+        }
+        if payment.status == TransactionStatus::Voided {
+            return Err(PaymentError::PaymentVoided(payment.id));
+        }
+
+        let deleted = payment_applications::Entity::delete_many()
+            .filter(payment_applications::Column::OrganizationId.eq(organization_id))
+            .filter(payment_applications::Column::PaymentTransactionId.eq(payment.id))
+            .filter(payment_applications::Column::SettlesTransactionId.eq(settles_transaction_id))
+            .exec(&txn)
+            .await?;
+        if deleted.rows_affected == 0 {
+            return Err(PaymentError::ApplicationNotFound(settles_transaction_id));
+        }
+
+        let settlement = Settlement::new(
+            functional_total(&txn, settles_transaction_id).await?,
+            applied_sum(
+                &txn,
+                payment_applications::Column::SettlesTransactionId,
+                settles_transaction_id,
+            )
+            .await?,
+        );
+
+        txn.commit().await?;
+
+        Ok(settlement)
+    }
+
+    /// Amount applied to an invoice or bill by payments.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    pub async fn settled_amount(
+        &self,
+        organization_id: OrganizationId,
+        transaction_id: TransactionId,
+    ) -> Result<Decimal, PaymentError> {
+        let applied: Option<Decimal> = payment_applications::Entity::find()
+            .select_only()
+            .column_as(payment_applications::Column::AppliedAmount.sum(), "total")
+            .filter(payment_applications::Column::OrganizationId.eq(organization_id))
+            .filter(payment_applications::Column::SettlesTransactionId.eq(transaction_id))
+            .into_tuple()
+            .one(&self.db)
+            .await?
+            .flatten();
+
+        Ok(applied.unwrap_or(Decimal::ZERO))
+    }
+
+    /// Lists the applications of a payment.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    pub async fn list_applications(
+        &self,
+        organization_id: OrganizationId,
+        payment_id: TransactionId,
+    ) -> Result<Vec<payment_applications::Model>, PaymentError> {
+        Ok(payment_applications::Entity::find()
+            .filter(payment_applications::Column::OrganizationId.eq(organization_id))
+            .filter(payment_applications::Column::PaymentTransactionId.eq(payment_id))
+            .all(&self.db)
+            .await?)
+    }
+}
+
+/// Whether payments can be applied to transactions of this type.
+#[must_use]
+pub fn is_settleable(transaction_type: &TransactionType) -> bool {
+    matches!(
+        transaction_type,
+        TransactionType::Invoice | TransactionType::Bill
+    )
+}
+
+/// Rejects anything but a posted payment.
+fn check_payment(payment: &transactions::Model) -> Result<(), PaymentError> {
+    if payment.transaction_type != TransactionType::Payment {
+        return Err(PaymentError::NotAPayment(payment.id));
+    }
+    match payment.status {
+        TransactionStatus::Posted => Ok(()),
+        TransactionStatus::Voided => Err(PaymentError::PaymentVoided(payment.id)),
+        _ => Err(PaymentError::NotPosted(payment.id)),
+    }
+}
+
+/// Sum of a transaction's debits in functional currency.
+async fn functional_total<C: ConnectionTrait>(
+    db: &C,
+    transaction_id: Uuid,
+) -> Result<Decimal, DbErr> {
+    let total: Option<Decimal> = ledger_entries::Entity::find()
+        .select_only()
+        .column_as(ledger_entries::Column::Debit.sum(), "total")
+        .filter(ledger_entries::Column::TransactionId.eq(transaction_id))
+        .into_tuple()
+        .one(db)
+        .await?
+        .flatten();
+
+    Ok(total.unwrap_or(Decimal::ZERO))
+}
+
+/// Sum of the applications whose `column` is `transaction_id`.
+async fn applied_sum<C: ConnectionTrait>(
+    db: &C,
+    column: payment_applications::Column,
+    transaction_id: Uuid,
+) -> Result<Decimal, DbErr> {
+    let total: Option<Decimal> = payment_applications::Entity::find()
+        .select_only()
+        .column_as(payment_applications::Column::AppliedAmount.sum(), "total")
+        .filter(column.eq(transaction_id))
+        .into_tuple()
+        .one(db)
+        .await?
+        .flatten();
+
+    Ok(total.unwrap_or(Decimal::ZERO))
+}
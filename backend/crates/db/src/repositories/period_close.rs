@@ -0,0 +1,493 @@
+//! Automatic closing of fiscal periods.
+//!
+//! Organizations that enable `period_auto_close` have each ended period
+//! soft-closed after a grace period and closed some days later, once its
+//! closing checklist has no failing check. Changes are made as
+//! [`SYSTEM_USER_ID`], logged in `fiscal_period_events` and announced to the
+//! organization's owners, admins and accountants. Admins can push a period's
+//! dates by granting it a grace extension.
+
+use std::collections::{HashMap, hash_map::Entry};
+
+use chrono::NaiveDate;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, DatabaseConnection, DbErr, EntityTrait, JoinType, QueryFilter,
+    QueryOrder, QuerySelect, RelationTrait, Set,
+};
+use serde_json::json;
+use uuid::Uuid;
+use zeltra_core::fiscal::{AutoCloseSchedule, AutoCloseStep, CheckStatus};
+use zeltra_core::notification::NotificationType;
+use zeltra_core::settings::{AutoCloseSettings, OrganizationSettings};
+
+use crate::entities::{
+    fiscal_period_events, fiscal_periods, organization_users, organizations,
+    sea_orm_active_enums::{FiscalPeriodStatus, UserRole},
+};
+
+use super::closing::{ClosingError, ClosingRepository};
+use super::fiscal::{FiscalError, FiscalRepository};
+use super::notification::NotificationRepository;
+use super::user::SYSTEM_USER_ID;
+
+/// Longest grace extension granted at once, in days.
+pub const MAX_GRACE_EXTENSION_DAYS: u32 = 90;
+
+/// Error types for period auto-close.
+#[derive(Debug, thiserror::Error)]
+pub enum PeriodCloseError {
+    /// Fiscal period not found in the organization.
+    #[error("Fiscal period not found: {0}")]
+    PeriodNotFound(Uuid),
+
+    /// The period is already closed.
+    #[error("Fiscal period {0} is already closed")]
+    PeriodClosed(Uuid),
+
+    /// Extension is zero or longer than [`MAX_GRACE_EXTENSION_DAYS`].
+    #[error("Grace extension must be between 1 and {MAX_GRACE_EXTENSION_DAYS} days")]
+    InvalidExtension,
+
+    /// Stored organization settings could not be read.
+    #[error("Invalid organization settings: {0}")]
+    InvalidSettings(String),
+
+    /// The status change failed.
+    #[error(transparent)]
+    Fiscal(#[from] FiscalError),
+
+    /// The closing checklist could not be run.
+    #[error(transparent)]
+    Closing(#[from] ClosingError),
+
+    /// Database error.
+    #[error("Database error: {0}")]
+    Database(#[from] DbErr),
+}
+
+/// A period's auto-close dates.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PeriodSchedule {
+    /// Whether the organization has auto-close enabled.
+    pub enabled: bool,
+    /// The dates, with the period's grace extensions applied.
+    pub schedule: AutoCloseSchedule,
+}
+
+/// A period with an automatic step due.
+#[derive(Debug, Clone)]
+pub struct DuePeriod {
+    /// The period.
+    pub period: fiscal_periods::Model,
+    /// Its dates.
+    pub schedule: AutoCloseSchedule,
+    /// The step due.
+    pub step: AutoCloseStep,
+}
+
+/// What the auto-close run did with one period.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AutoCloseOutcome {
+    /// The period was soft-closed.
+    SoftClosed {
+        /// Draft, pending and approved transactions left in the period.
+        unposted_transactions: u64,
+    },
+    /// The period was closed.
+    Closed,
+    /// Closing checks failed; the close is retried on the next run.
+    Blocked {
+        /// Identifiers of the failing checks.
+        failed_checks: Vec<String>,
+    },
+    /// An earlier period of the year is still open.
+    EarlierPeriodsOpen,
+}
+
+/// Result of the auto-close run for one period.
+#[derive(Debug, Clone)]
+pub struct AutoCloseRun {
+    /// The organization.
+    pub organization_id: Uuid,
+    /// The fiscal period.
+    pub fiscal_period_id: Uuid,
+    /// Name of the fiscal period.
+    pub period_name: String,
+    /// What happened.
+    pub outcome: AutoCloseOutcome,
+}
+
+/// Repository for automatic period closing.
+#[derive(Debug, Clone)]
+pub struct PeriodCloseRepository {
+    db: DatabaseConnection,
+}
+
+impl PeriodCloseRepository {
+    /// Creates a new period close repository.
+    #[must_use]
+    pub const fn new(db: DatabaseConnection) -> Self {
+        Self { db }
+    }
+
+    /// Regular periods that ended before `today` and have a step due, in
+    /// organizations with auto-close enabled, earliest first.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a database query fails or an organization's
+    /// settings cannot be read.
+    pub async fn due_periods(
+        &self,
+        today: NaiveDate,
+        organization_id: Option<Uuid>,
+    ) -> Result<Vec<DuePeriod>, PeriodCloseError> {
+        let mut query = fiscal_periods::Entity::find()
+            .join(
+                JoinType::InnerJoin,
+                fiscal_periods::Relation::Organizations.def(),
+            )
+            .filter(organizations::Column::IsActive.eq(true))
+            .filter(
+                fiscal_periods::Column::Status
+                    .is_in([FiscalPeriodStatus::Open, FiscalPeriodStatus::SoftClose]),
+            )
+            .filter(fiscal_periods::Column::IsAdjustmentPeriod.eq(false))
+            .filter(fiscal_periods::Column::EndDate.lt(today));
+        if let Some(organization_id) = organization_id {
+            query = query.filter(fiscal_periods::Column::OrganizationId.eq(organization_id));
+        }
+        let periods = query
+            .order_by_asc(fiscal_periods::Column::OrganizationId)
+            .order_by_asc(fiscal_periods::Column::EndDate)
+            .order_by_asc(fiscal_periods::Column::PeriodNumber)
+            .all(&self.db)
+            .await?;
+        if periods.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let extensions = self
+            .extension_days(periods.iter().map(|p| p.id).collect())
+            .await?;
+        let mut settings: HashMap<Uuid, AutoCloseSettings> = HashMap::new();
+        for period in &periods {
+            if let Entry::Vacant(entry) = settings.entry(period.organization_id) {
+                entry.insert(self.settings(period.organization_id).await?);
+            }
+        }
+
+        let mut due = Vec::new();
+        for period in periods {
+            let Some(org_settings) = settings.get(&period.organization_id) else {
+                continue;
+            };
+            if !org_settings.enabled {
+                continue;
+            }
+            let schedule = AutoCloseSchedule::new(
+                period.end_date,
+                org_settings,
+                extensions.get(&period.id).copied().unwrap_or(0),
+            );
+            let soft_closed = period.status == FiscalPeriodStatus::SoftClose;
+            if let Some(step) = schedule.due_step(soft_closed, today) {
+                due.push(DuePeriod {
+                    period,
+                    schedule,
+                    step,
+                });
+            }
+        }
+        Ok(due)
+    }
+
+    /// Takes the step due for a period.
+    ///
+    /// A soft close reports the period's unposted transactions. A close runs
+    /// the closing checklist first; when a check fails the period stays
+    /// soft-closed and members are told once per scheduled close date.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the checklist, the status change or a database
+    /// operation fails.
+    pub async fn run(&self, due: &DuePeriod) -> Result<AutoCloseRun, PeriodCloseError> {
+        let period = &due.period;
+        let outcome = match due.step {
+            AutoCloseStep::SoftClose => self.soft_close(due).await?,
+            AutoCloseStep::Close => self.close(due).await?,
+        };
+        Ok(AutoCloseRun {
+            organization_id: period.organization_id,
+            fiscal_period_id: period.id,
+            period_name: period.name.clone(),
+            outcome,
+        })
+    }
+
+    /// Pushes a period's soft close and close dates back by `days`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `days` is out of range, the period is not in the
+    /// organization or already closed, or a database operation fails.
+    pub async fn extend_grace(
+        &self,
+        organization_id: Uuid,
+        period_id: Uuid,
+        days: u32,
+        reason: Option<String>,
+        extended_by: Uuid,
+    ) -> Result<PeriodSchedule, PeriodCloseError> {
+        if days == 0 || days > MAX_GRACE_EXTENSION_DAYS {
+            return Err(PeriodCloseError::InvalidExtension);
+        }
+        let period = self.find_period(organization_id, period_id).await?;
+        if period.status == FiscalPeriodStatus::Closed {
+            return Err(PeriodCloseError::PeriodClosed(period_id));
+        }
+
+        self.log(
+            &period,
+            "grace_extended",
+            extended_by,
+            i32::try_from(days).ok(),
+            json!({ "reason": reason }),
+        )
+        .await?;
+        self.period_schedule(&period).await
+    }
+
+    async fn soft_close(&self, due: &DuePeriod) -> Result<AutoCloseOutcome, PeriodCloseError> {
+        let period = &due.period;
+        match FiscalRepository::new(self.db.clone())
+            .update_period_status(
+                period.id,
+                FiscalPeriodStatus::SoftClose,
+                Some(SYSTEM_USER_ID),
+            )
+            .await
+        {
+            Ok(_) => {}
+            Err(FiscalError::EarlierPeriodsOpen) => {
+                return Ok(AutoCloseOutcome::EarlierPeriodsOpen);
+            }
+            Err(e) => return Err(e.into()),
+        }
+
+        let counts = ClosingRepository::new(self.db.clone())
+            .transaction_counts(period.id)
+            .await?;
+        let unposted: serde_json::Map<String, serde_json::Value> = ["draft", "pending", "approved"]
+            .into_iter()
+            .map(|status| {
+                let count = counts.get(status).copied().unwrap_or(0);
+                (status.to_string(), json!(count))
+            })
+            .collect();
+        let unposted_transactions = ["draft", "pending", "approved"]
+            .iter()
+            .filter_map(|status| counts.get(status))
+            .sum();
+
+        let details = json!({
+            "close_on": due.schedule.close_on,
+            "unposted_transactions": unposted,
+        });
+        self.log(period, "soft_closed", SYSTEM_USER_ID, None, details.clone())
+            .await?;
+        self.notify(period, NotificationType::PeriodSoftClosed, details)
+            .await?;
+        Ok(AutoCloseOutcome::SoftClosed {
+            unposted_transactions,
+        })
+    }
+
+    async fn close(&self, due: &DuePeriod) -> Result<AutoCloseOutcome, PeriodCloseError> {
+        let period = &due.period;
+        let checklist = ClosingRepository::new(self.db.clone())
+            .checklist(period)
+            .await?;
+        let failed: Vec<_> = checklist
+            .checks
+            .into_iter()
+            .filter(|c| c.status == CheckStatus::Fail)
+            .collect();
+
+        if !failed.is_empty() {
+            let failed_checks: Vec<String> = failed.iter().map(|c| c.check.clone()).collect();
+            let notifications = NotificationRepository::new(self.db.clone());
+            let key = json!({
+                "fiscal_period_id": period.id,
+                "close_on": due.schedule.close_on,
+            });
+            if !notifications
+                .exists(
+                    period.organization_id,
+                    NotificationType::PeriodCloseBlocked,
+                    key,
+                )
+                .await?
+            {
+                self.log(
+                    period,
+                    "close_blocked",
+                    SYSTEM_USER_ID,
+                    None,
+                    json!({ "close_on": due.schedule.close_on, "checks": failed }),
+                )
+                .await?;
+                self.notify(
+                    period,
+                    NotificationType::PeriodCloseBlocked,
+                    json!({
+                        "close_on": due.schedule.close_on,
+                        "failed_checks": failed_checks,
+                    }),
+                )
+                .await?;
+            }
+            return Ok(AutoCloseOutcome::Blocked { failed_checks });
+        }
+
+        match FiscalRepository::new(self.db.clone())
+            .update_period_status(period.id, FiscalPeriodStatus::Closed, Some(SYSTEM_USER_ID))
+            .await
+        {
+            Ok(_) => {}
+            Err(FiscalError::EarlierPeriodsOpen) => {
+                return Ok(AutoCloseOutcome::EarlierPeriodsOpen);
+            }
+            Err(e) => return Err(e.into()),
+        }
+        self.log(period, "closed", SYSTEM_USER_ID, None, json!({}))
+            .await?;
+        self.notify(period, NotificationType::PeriodClosed, json!({}))
+            .await?;
+        Ok(AutoCloseOutcome::Closed)
+    }
+
+    /// Notifies owners, admins and accountants about `period`, adding its
+    /// ID, name and end date to `details`.
+    async fn notify(
+        &self,
+        period: &fiscal_periods::Model,
+        notification_type: NotificationType,
+        details: serde_json::Value,
+    ) -> Result<(), DbErr> {
+        let mut payload = json!({
+            "fiscal_period_id": period.id,
+            "period_name": period.name,
+            "period_end": period.end_date,
+        });
+        if let (Some(payload), serde_json::Value::Object(details)) =
+            (payload.as_object_mut(), details)
+        {
+            payload.extend(details);
+        }
+
+        let recipients: Vec<Uuid> = organization_users::Entity::find()
+            .filter(organization_users::Column::OrganizationId.eq(period.organization_id))
+            .filter(organization_users::Column::Role.is_in([
+                UserRole::Owner,
+                UserRole::Admin,
+                UserRole::Accountant,
+            ]))
+            .all(&self.db)
+            .await?
+            .into_iter()
+            .map(|member| member.user_id)
+            .collect();
+        NotificationRepository::new(self.db.clone())
+            .notify(
+                period.organization_id,
+                notification_type,
+                payload,
+                &recipients,
+            )
+            .await?;
+        Ok(())
+    }
+
+    async fn log(
+        &self,
+        period: &fiscal_periods::Model,
+        event: &str,
+        actor_id: Uuid,
+        extension_days: Option<i32>,
+        details: serde_json::Value,
+    ) -> Result<fiscal_period_events::Model, DbErr> {
+        fiscal_period_events::ActiveModel {
+            id: Set(Uuid::new_v4()),
+            organization_id: Set(period.organization_id),
+            fiscal_period_id: Set(period.id),
+            event: Set(event.to_string()),
+            actor_id: Set(actor_id),
+            extension_days: Set(extension_days),
+            details: Set(details),
+            created_at: Set(chrono::Utc::now().into()),
+        }
+        .insert(&self.db)
+        .await
+    }
+
+    async fn find_period(
+        &self,
+        organization_id: Uuid,
+        period_id: Uuid,
+    ) -> Result<fiscal_periods::Model, PeriodCloseError> {
+        fiscal_periods::Entity::find_by_id(period_id)
+            .filter(fiscal_periods::Column::OrganizationId.eq(organization_id))
+            .one(&self.db)
+            .await?
+            .ok_or(PeriodCloseError::PeriodNotFound(period_id))
+    }
+
+    async fn period_schedule(
+        &self,
+        period: &fiscal_periods::Model,
+    ) -> Result<PeriodSchedule, PeriodCloseError> {
+        let settings = self.settings(period.organization_id).await?;
+        let extension_days = self
+            .extension_days(vec![period.id])
+            .await?
+            .get(&period.id)
+            .copied()
+            .unwrap_or(0);
+        Ok(PeriodSchedule {
+            enabled: settings.enabled,
+            schedule: AutoCloseSchedule::new(period.end_date, &settings, extension_days),
+        })
+    }
+
+    async fn settings(&self, organization_id: Uuid) -> Result<AutoCloseSettings, PeriodCloseError> {
+        let Some(org) = organizations::Entity::find_by_id(organization_id)
+            .one(&self.db)
+            .await?
+        else {
+            return Ok(AutoCloseSettings::default());
+        };
+        OrganizationSettings::from_value(&org.settings)
+            .map(|settings| settings.period_auto_close)
+            .map_err(|e| PeriodCloseError::InvalidSettings(e.to_string()))
+    }
+
+    /// Total grace extension of each period that has one.
+    async fn extension_days(&self, period_ids: Vec<Uuid>) -> Result<HashMap<Uuid, u32>, DbErr> {
+        let rows: Vec<(Uuid, Option<i64>)> = fiscal_period_events::Entity::find()
+            .filter(fiscal_period_events::Column::FiscalPeriodId.is_in(period_ids))
+            .filter(fiscal_period_events::Column::Event.eq("grace_extended"))
+            .select_only()
+            .column(fiscal_period_events::Column::FiscalPeriodId)
+            .column_as(fiscal_period_events::Column::ExtensionDays.sum(), "days")
+            .group_by(fiscal_period_events::Column::FiscalPeriodId)
+            .into_tuple()
+            .all(&self.db)
+            .await?;
+        Ok(rows
+            .into_iter()
+            .map(|(id, days)| (id, u32::try_from(days.unwrap_or(0)).unwrap_or(u32::MAX)))
+            .collect())
+    }
+}
@@ -0,0 +1,559 @@
+//! Reconciliation repository for bank account reconciliation.
+//!
+//! A reconciliation matches ledger entries on a bank account against a bank
+//! statement. Entries are cleared by adding them as reconciliation items; a
+//! reconciliation can only be completed once the cleared entries explain the
+//! statement movement, after which its items are locked.
+
+use std::collections::{HashMap, HashSet};
+
+use chrono::{NaiveDate, Utc};
+use rust_decimal::Decimal;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, DatabaseConnection, DbErr, EntityTrait, ModelTrait, QueryFilter,
+    QueryOrder, Set,
+};
+use uuid::Uuid;
+use zeltra_core::reconciliation::{
+    ReconciliationCheck, ReconciliationError as RuleError, ReconciliationService,
+    ReconciliationStatus as CoreStatus,
+};
+
+use crate::entities::{
+    chart_of_accounts, ledger_entries, organizations, reconciliation_items, reconciliations,
+    sea_orm_active_enums::{ReconciliationStatus, TransactionStatus},
+    transactions,
+};
+use crate::repositories::transaction::calculate_balance_change;
+
+/// Error types for reconciliation operations.
+#[derive(Debug, thiserror::Error)]
+pub enum ReconciliationError {
+    /// Reconciliation not found.
+    #[error("Reconciliation not found: {0}")]
+    NotFound(Uuid),
+
+    /// Account not found.
+    #[error("Account not found: {0}")]
+    AccountNotFound(Uuid),
+
+    /// Account is not a bank account.
+    #[error("Account {0} is not a bank account")]
+    NotBankAccount(Uuid),
+
+    /// Account is not in the organization's base currency.
+    #[error("Account {0} is not in the organization's base currency")]
+    NotBaseCurrency(Uuid),
+
+    /// Another reconciliation is already in progress for the account.
+    #[error("Reconciliation {0} is already in progress for this account")]
+    AlreadyInProgress(Uuid),
+
+    /// Statement date must be after the last completed reconciliation.
+    #[error("Statement date must be after the last reconciled statement date {0}")]
+    StatementDateNotAfterPrevious(NaiveDate),
+
+    /// Ledger entry not found on the reconciled account.
+    #[error("Ledger entry not found: {0}")]
+    EntryNotFound(Uuid),
+
+    /// Ledger entry's transaction has not hit the ledger.
+    #[error("Ledger entry {0} does not belong to a posted transaction")]
+    EntryNotPosted(Uuid),
+
+    /// Ledger entry is already part of a completed reconciliation.
+    #[error("Ledger entry {0} is already reconciled")]
+    EntryAlreadyReconciled(Uuid),
+
+    /// Only the most recent completed reconciliation can be reopened.
+    #[error("Only the most recent completed reconciliation can be reopened")]
+    NotLatest,
+
+    /// Reconciliation rule violation.
+    #[error(transparent)]
+    Rule(#[from] RuleError),
+
+    /// Database error.
+    #[error("Database error: {0}")]
+    Database(#[from] DbErr),
+}
+
+/// Input for starting a reconciliation.
+#[derive(Debug, Clone)]
+pub struct StartReconciliationInput {
+    /// Organization ID.
+    pub organization_id: Uuid,
+    /// Bank account ID.
+    pub account_id: Uuid,
+    /// Statement date.
+    pub statement_date: NaiveDate,
+    /// Statement ending balance.
+    pub statement_ending_balance: Decimal,
+    /// User starting the reconciliation.
+    pub created_by: Uuid,
+}
+
+/// Reconciliation with its current balance check.
+#[derive(Debug, Clone)]
+pub struct ReconciliationWithCheck {
+    /// The reconciliation record.
+    pub reconciliation: reconciliations::Model,
+    /// Balance check of the currently cleared entries.
+    pub check: ReconciliationCheck,
+    /// Number of cleared entries.
+    pub cleared_count: usize,
+}
+
+/// A ledger entry available for clearing in a reconciliation.
+#[derive(Debug, Clone)]
+pub struct ReconciliationEntry {
+    /// The ledger entry.
+    pub entry: ledger_entries::Model,
+    /// Transaction date.
+    pub transaction_date: NaiveDate,
+    /// Transaction description.
+    pub description: String,
+    /// Transaction reference number.
+    pub reference_number: Option<String>,
+    /// Whether the entry is cleared in this reconciliation.
+    pub cleared: bool,
+}
+
+/// Reconciliation repository for bank account reconciliation.
+#[derive(Debug, Clone)]
+pub struct ReconciliationRepository {
+    db: DatabaseConnection,
+}
+
+impl ReconciliationRepository {
+    /// Creates a new reconciliation repository.
+    #[must_use]
+    pub const fn new(db: DatabaseConnection) -> Self {
+        Self { db }
+    }
+
+    /// Starts a reconciliation for a bank account.
+    ///
+    /// The opening balance is the ending balance of the last completed
+    /// reconciliation for the account, or zero for the first one.
+    ///
+    /// Cleared entries are summed in the functional currency, so only bank
+    /// accounts in the organization's base currency can be reconciled.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - The account does not exist or is not a bank account
+    /// - The account is not in the organization's base currency
+    /// - Another reconciliation is in progress for the account
+    /// - The statement date is not after the last completed statement date
+    /// - Database operation fails
+    pub async fn start_reconciliation(
+        &self,
+        input: StartReconciliationInput,
+    ) -> Result<reconciliations::Model, ReconciliationError> {
+        let account = chart_of_accounts::Entity::find_by_id(input.account_id)
+            .filter(chart_of_accounts::Column::OrganizationId.eq(input.organization_id))
+            .one(&self.db)
+            .await?
+            .ok_or(ReconciliationError::AccountNotFound(input.account_id))?;
+
+        if !account.is_bank_account {
+            return Err(ReconciliationError::NotBankAccount(account.id));
+        }
+
+        let base_currency = organizations::Entity::find_by_id(input.organization_id)
+            .one(&self.db)
+            .await?
+            .map(|org| org.base_currency);
+        if base_currency.as_deref() != Some(account.currency.as_str()) {
+            return Err(ReconciliationError::NotBaseCurrency(account.id));
+        }
+
+        if let Some(open) = reconciliations::Entity::find()
+            .filter(reconciliations::Column::AccountId.eq(account.id))
+            .filter(reconciliations::Column::Status.eq(ReconciliationStatus::InProgress))
+            .one(&self.db)
+            .await?
+        {
+            return Err(ReconciliationError::AlreadyInProgress(open.id));
+        }
+
+        let previous = self.latest_completed(account.id).await?;
+        if let Some(prev) = &previous
+            && input.statement_date <= prev.statement_date
+        {
+            return Err(ReconciliationError::StatementDateNotAfterPrevious(
+                prev.statement_date,
+            ));
+        }
+
+        let now = Utc::now().into();
+        let reconciliation = reconciliations::ActiveModel {
+            id: Set(Uuid::new_v4()),
+            organization_id: Set(input.organization_id),
+            account_id: Set(account.id),
+            statement_date: Set(input.statement_date),
+            statement_ending_balance: Set(input.statement_ending_balance),
+            opening_balance: Set(previous.map_or(Decimal::ZERO, |p| p.statement_ending_balance)),
+            status: Set(ReconciliationStatus::InProgress),
+            created_by: Set(input.created_by),
+            completed_by: Set(None),
+            completed_at: Set(None),
+            created_at: Set(now),
+            updated_at: Set(now),
+        };
+
+        Ok(reconciliation.insert(&self.db).await?)
+    }
+
+    /// Lists reconciliations for an organization, newest statement first.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    pub async fn list_reconciliations(
+        &self,
+        organization_id: Uuid,
+        account_id: Option<Uuid>,
+    ) -> Result<Vec<reconciliations::Model>, ReconciliationError> {
+        let mut query = reconciliations::Entity::find()
+            .filter(reconciliations::Column::OrganizationId.eq(organization_id));
+
+        if let Some(account_id) = account_id {
+            query = query.filter(reconciliations::Column::AccountId.eq(account_id));
+        }
+
+        Ok(query
+            .order_by_desc(reconciliations::Column::StatementDate)
+            .all(&self.db)
+            .await?)
+    }
+
+    /// Gets a reconciliation with its current balance check.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the reconciliation is not found or the query fails.
+    pub async fn get_reconciliation(
+        &self,
+        organization_id: Uuid,
+        reconciliation_id: Uuid,
+    ) -> Result<ReconciliationWithCheck, ReconciliationError> {
+        let reconciliation = self.find(organization_id, reconciliation_id).await?;
+        let (cleared_net, cleared_count) = self.cleared_net(&reconciliation).await?;
+
+        Ok(ReconciliationWithCheck {
+            check: ReconciliationService::check(
+                reconciliation.opening_balance,
+                reconciliation.statement_ending_balance,
+                cleared_net,
+            ),
+            cleared_count,
+            reconciliation,
+        })
+    }
+
+    /// Lists ledger entries on the account up to the statement date that are
+    /// not locked by a completed reconciliation.
+    ///
+    /// Entries of posted and voided transactions are included, since both hit
+    /// the ledger (a void adds a posted reversal alongside the original).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the reconciliation is not found or the query fails.
+    pub async fn list_unreconciled_entries(
+        &self,
+        organization_id: Uuid,
+        reconciliation_id: Uuid,
+    ) -> Result<Vec<ReconciliationEntry>, ReconciliationError> {
+        let reconciliation = self.find(organization_id, reconciliation_id).await?;
+
+        let rows = ledger_entries::Entity::find()
+            .filter(ledger_entries::Column::AccountId.eq(reconciliation.account_id))
+            .find_also_related(transactions::Entity)
+            .filter(transactions::Column::OrganizationId.eq(organization_id))
+            .filter(
+                transactions::Column::Status
+                    .is_in([TransactionStatus::Posted, TransactionStatus::Voided]),
+            )
+            .filter(transactions::Column::TransactionDate.lte(reconciliation.statement_date))
+            .order_by_asc(transactions::Column::TransactionDate)
+            .order_by_asc(ledger_entries::Column::AccountVersion)
+            .all(&self.db)
+            .await?;
+
+        let entry_ids: Vec<Uuid> = rows.iter().map(|(e, _)| e.id).collect();
+        let items = reconciliation_items::Entity::find()
+            .filter(reconciliation_items::Column::LedgerEntryId.is_in(entry_ids))
+            .find_also_related(reconciliations::Entity)
+            .all(&self.db)
+            .await?;
+
+        let mut cleared = HashSet::new();
+        let mut locked = HashSet::new();
+        for (item, parent) in items {
+            if item.reconciliation_id == reconciliation.id {
+                cleared.insert(item.ledger_entry_id);
+            } else if parent.is_some_and(|p| p.status == ReconciliationStatus::Completed) {
+                locked.insert(item.ledger_entry_id);
+            }
+        }
+
+        Ok(rows
+            .into_iter()
+            .filter(|(entry, _)| !locked.contains(&entry.id))
+            .filter_map(|(entry, tx)| {
+                tx.map(|tx| ReconciliationEntry {
+                    cleared: cleared.contains(&entry.id),
+                    transaction_date: tx.transaction_date,
+                    description: tx.description,
+                    reference_number: tx.reference_number,
+                    entry,
+                })
+            })
+            .collect())
+    }
+
+    /// Marks a ledger entry as cleared or uncleared in a reconciliation.
+    ///
+    /// Idempotent: clearing a cleared entry (or unclearing an uncleared one) is a no-op.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - The reconciliation is not found or already completed
+    /// - The entry is not on the reconciled account, not posted, or dated after the statement
+    /// - The entry is locked by another completed reconciliation
+    /// - Database operation fails
+    pub async fn set_cleared(
+        &self,
+        organization_id: Uuid,
+        reconciliation_id: Uuid,
+        ledger_entry_id: Uuid,
+        cleared: bool,
+    ) -> Result<ReconciliationWithCheck, ReconciliationError> {
+        let reconciliation = self.find(organization_id, reconciliation_id).await?;
+        ReconciliationService::validate_can_modify(db_status_to_core(&reconciliation.status))?;
+
+        let existing = reconciliation_items::Entity::find()
+            .filter(reconciliation_items::Column::ReconciliationId.eq(reconciliation.id))
+            .filter(reconciliation_items::Column::LedgerEntryId.eq(ledger_entry_id))
+            .one(&self.db)
+            .await?;
+
+        match (cleared, existing) {
+            (true, None) => {
+                self.validate_clearable(&reconciliation, ledger_entry_id)
+                    .await?;
+                let item = reconciliation_items::ActiveModel {
+                    id: Set(Uuid::new_v4()),
+                    organization_id: Set(organization_id),
+                    reconciliation_id: Set(reconciliation.id),
+                    ledger_entry_id: Set(ledger_entry_id),
+                    created_at: Set(Utc::now().into()),
+                };
+                item.insert(&self.db).await?;
+            }
+            (false, Some(item)) => {
+                item.delete(&self.db).await?;
+            }
+            _ => {}
+        }
+
+        self.get_reconciliation(organization_id, reconciliation_id)
+            .await
+    }
+
+    /// Completes a reconciliation, locking its cleared entries.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the reconciliation is not found or already completed,
+    /// `ReconciliationError::Rule(OutOfBalance)` with the difference if the
+    /// cleared entries do not match the statement, or if the update fails.
+    pub async fn complete_reconciliation(
+        &self,
+        organization_id: Uuid,
+        reconciliation_id: Uuid,
+        completed_by: Uuid,
+    ) -> Result<ReconciliationWithCheck, ReconciliationError> {
+        let current = self
+            .get_reconciliation(organization_id, reconciliation_id)
+            .await?;
+
+        ReconciliationService::validate_complete(
+            db_status_to_core(&current.reconciliation.status),
+            &current.check,
+        )?;
+
+        let now = Utc::now().into();
+        let mut active: reconciliations::ActiveModel = current.reconciliation.into();
+        active.status = Set(ReconciliationStatus::Completed);
+        active.completed_by = Set(Some(completed_by));
+        active.completed_at = Set(Some(now));
+        active.updated_at = Set(now);
+        let reconciliation = active.update(&self.db).await?;
+
+        Ok(ReconciliationWithCheck {
+            reconciliation,
+            check: current.check,
+            cleared_count: current.cleared_count,
+        })
+    }
+
+    /// Reopens the most recent completed reconciliation of an account.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the reconciliation is not found, not completed, not
+    /// the latest for its account, another reconciliation is in progress, or
+    /// the update fails.
+    pub async fn reopen_reconciliation(
+        &self,
+        organization_id: Uuid,
+        reconciliation_id: Uuid,
+    ) -> Result<reconciliations::Model, ReconciliationError> {
+        let reconciliation = self.find(organization_id, reconciliation_id).await?;
+        ReconciliationService::validate_reopen(db_status_to_core(&reconciliation.status))?;
+
+        let latest = self.latest_completed(reconciliation.account_id).await?;
+        if latest.is_none_or(|l| l.id != reconciliation.id) {
+            return Err(ReconciliationError::NotLatest);
+        }
+
+        if let Some(open) = reconciliations::Entity::find()
+            .filter(reconciliations::Column::AccountId.eq(reconciliation.account_id))
+            .filter(reconciliations::Column::Status.eq(ReconciliationStatus::InProgress))
+            .one(&self.db)
+            .await?
+        {
+            return Err(ReconciliationError::AlreadyInProgress(open.id));
+        }
+
+        let mut active: reconciliations::ActiveModel = reconciliation.into();
+        active.status = Set(ReconciliationStatus::InProgress);
+        active.completed_by = Set(None);
+        active.completed_at = Set(None);
+        active.updated_at = Set(Utc::now().into());
+
+        Ok(active.update(&self.db).await?)
+    }
+
+    // ========================================================================
+    // Helper methods
+    // ========================================================================
+
+    /// Finds a reconciliation within an organization.
+    async fn find(
+        &self,
+        organization_id: Uuid,
+        reconciliation_id: Uuid,
+    ) -> Result<reconciliations::Model, ReconciliationError> {
+        reconciliations::Entity::find_by_id(reconciliation_id)
+            .filter(reconciliations::Column::OrganizationId.eq(organization_id))
+            .one(&self.db)
+            .await?
+            .ok_or(ReconciliationError::NotFound(reconciliation_id))
+    }
+
+    /// Gets the most recent completed reconciliation of an account.
+    async fn latest_completed(
+        &self,
+        account_id: Uuid,
+    ) -> Result<Option<reconciliations::Model>, ReconciliationError> {
+        Ok(reconciliations::Entity::find()
+            .filter(reconciliations::Column::AccountId.eq(account_id))
+            .filter(reconciliations::Column::Status.eq(ReconciliationStatus::Completed))
+            .order_by_desc(reconciliations::Column::StatementDate)
+            .one(&self.db)
+            .await?)
+    }
+
+    /// Checks that an entry may be cleared in the reconciliation.
+    async fn validate_clearable(
+        &self,
+        reconciliation: &reconciliations::Model,
+        ledger_entry_id: Uuid,
+    ) -> Result<(), ReconciliationError> {
+        let (entry, tx) = ledger_entries::Entity::find_by_id(ledger_entry_id)
+            .find_also_related(transactions::Entity)
+            .one(&self.db)
+            .await?
+            .ok_or(ReconciliationError::EntryNotFound(ledger_entry_id))?;
+
+        let tx = tx
+            .filter(|t| {
+                entry.account_id == reconciliation.account_id
+                    && t.organization_id == reconciliation.organization_id
+            })
+            .ok_or(ReconciliationError::EntryNotFound(ledger_entry_id))?;
+
+        if !matches!(
+            tx.status,
+            TransactionStatus::Posted | TransactionStatus::Voided
+        ) {
+            return Err(ReconciliationError::EntryNotPosted(ledger_entry_id));
+        }
+
+        ReconciliationService::validate_entry_date(
+            tx.transaction_date,
+            reconciliation.statement_date,
+        )?;
+
+        let locked = reconciliation_items::Entity::find()
+            .filter(reconciliation_items::Column::LedgerEntryId.eq(ledger_entry_id))
+            .find_also_related(reconciliations::Entity)
+            .all(&self.db)
+            .await?
+            .into_iter()
+            .any(|(_, parent)| parent.is_some_and(|p| p.status == ReconciliationStatus::Completed));
+
+        if locked {
+            return Err(ReconciliationError::EntryAlreadyReconciled(ledger_entry_id));
+        }
+
+        Ok(())
+    }
+
+    /// Sums the balance effect of the cleared entries.
+    ///
+    /// Uses the functional debit and credit, which are in the account's
+    /// currency since only base-currency accounts are reconciled.
+    async fn cleared_net(
+        &self,
+        reconciliation: &reconciliations::Model,
+    ) -> Result<(Decimal, usize), ReconciliationError> {
+        let account = chart_of_accounts::Entity::find_by_id(reconciliation.account_id)
+            .one(&self.db)
+            .await?
+            .ok_or(ReconciliationError::AccountNotFound(
+                reconciliation.account_id,
+            ))?;
+
+        let entries: HashMap<Uuid, ledger_entries::Model> = reconciliation_items::Entity::find()
+            .filter(reconciliation_items::Column::ReconciliationId.eq(reconciliation.id))
+            .find_also_related(ledger_entries::Entity)
+            .all(&self.db)
+            .await?
+            .into_iter()
+            .filter_map(|(_, entry)| entry.map(|e| (e.id, e)))
+            .collect();
+
+        let net = entries
+            .values()
+            .map(|e| calculate_balance_change(&account.account_type, e.debit, e.credit))
+            .sum();
+
+        Ok((net, entries.len()))
+    }
+}
+
+/// Converts database ReconciliationStatus to core ReconciliationStatus.
+fn db_status_to_core(status: &ReconciliationStatus) -> CoreStatus {
+    match status {
+        ReconciliationStatus::InProgress => CoreStatus::InProgress,
+        ReconciliationStatus::Completed => CoreStatus::Completed,
+    }
+}
@@ -2,20 +2,44 @@
 //!
 //! Implements Requirements 5.1-5.7, 6.1-6.7, 7.1-7.8, 8.1-8.6, 9.1-9.7 for report generation.
 
-use chrono::NaiveDate;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+
+use chrono::{Days, NaiveDate};
 use rust_decimal::Decimal;
 use sea_orm::{
-    ColumnTrait, DatabaseConnection, DbErr, EntityTrait, PaginatorTrait, QueryFilter, QueryOrder,
-    QuerySelect,
+    ActiveEnum, ColumnTrait, Condition, ConnectionTrait, DatabaseConnection, DbErr, EntityTrait,
+    FromQueryResult, JoinType, Order, PaginatorTrait, QueryFilter, QueryOrder, QueryResult,
+    QuerySelect, QueryTrait, RelationTrait, Select,
+    prelude::DateTimeWithTimeZone,
+    sea_query::{Alias, Expr, Func, Query, SelectStatement, SimpleExpr},
 };
 use uuid::Uuid;
+use zeltra_core::currency::ForeignBalance;
+use zeltra_core::reports::data_quality::{
+    self, DataQualityFinding, EntryConversion, FUNCTIONAL_AMOUNT_TOLERANCE, RequiredDimension,
+    RestrictedAccountUsage, TransactionDimensions, TransactionTotals,
+};
+use zeltra_core::reports::{
+    AggregateFunction, CustomReportResult, DimensionTaggedTotals, FieldKind,
+    MAX_CUSTOM_REPORT_ROWS, MovementChange, PeriodMovement, ReportDataset, ReportDimensionValue,
+    ReportField, ReportPlan, ReportValue,
+};
+use zeltra_core::workflow::VoidReasonCode;
 
 use crate::entities::{
-    chart_of_accounts, dimension_types, dimension_values, entry_dimensions, ledger_entries,
-    sea_orm_active_enums::{AccountSubtype, AccountType, TransactionStatus},
+    budget_line_dimensions, budget_lines, budgets, chart_of_accounts, dimension_retags,
+    dimension_types, dimension_values, entry_dimensions, fiscal_periods, ledger_entries,
+    organizations,
+    sea_orm_active_enums::{
+        AccountSubtype, AccountType, TransactionStatus, VoidReasonCode as DbVoidReasonCode,
+    },
     transactions,
 };
 
+use super::balance_snapshot::{AccountTotals, BalanceSnapshotRepository};
+use super::workflow::db_void_code_to_core;
+
 /// Error types for report operations.
 #[derive(Debug, thiserror::Error)]
 pub enum ReportError {
@@ -40,6 +64,22 @@ pub enum ReportError {
     #[error("Invalid dimension type: {0}")]
     InvalidDimensionType(String),
 
+    /// Dimension value not found in the organization.
+    #[error("Dimension value not found: {0}")]
+    DimensionValueNotFound(Uuid),
+
+    /// No account with this code in the organization.
+    #[error("Account not found: {0}")]
+    AccountCodeNotFound(String),
+
+    /// Account does not take postings, so it has no movement to report.
+    #[error("Account {0} does not allow direct posting")]
+    AccountNotPostable(Uuid),
+
+    /// Fiscal period not found in the organization.
+    #[error("Fiscal period not found: {0}")]
+    PeriodNotFound(Uuid),
+
     /// Database error.
     #[error("Database error: {0}")]
     Database(#[from] DbErr),
@@ -119,6 +159,139 @@ pub struct DimensionalReportRow {
     pub balance: Decimal,
 }
 
+/// Inputs for an income statement with one column per dimension value.
+#[derive(Debug, Clone)]
+pub struct DimensionIncomeStatementData {
+    /// Income statement accounts with their totals across all columns.
+    pub accounts: Vec<AccountBalance>,
+    /// Active values of the dimension type, by code.
+    pub values: Vec<ReportDimensionValue>,
+    /// Posted totals per account and set of values of the dimension type.
+    pub totals: Vec<DimensionTaggedTotals>,
+}
+
+/// Which entries a dimensional report covers, by their dimension values.
+///
+/// Include values of the same dimension type are alternatives, so listing
+/// two projects reports on either. Different types must all match.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DimensionValueFilter {
+    /// Values an entry must all carry.
+    pub all_of: Vec<Uuid>,
+    /// Values to report on; per dimension type, an entry must carry one.
+    pub include: Vec<Uuid>,
+    /// Values whose entries are left out.
+    pub exclude: Vec<Uuid>,
+    /// Whether an entry with no value of an included dimension type is kept.
+    /// Exclusion never drops untagged entries.
+    pub include_untagged: bool,
+}
+
+/// Voided transactions sharing a reason code.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VoidReasonSummary {
+    /// Reason code; `None` for transactions voided before codes existed.
+    pub reason_code: Option<VoidReasonCode>,
+    /// Number of voided transactions.
+    pub count: u64,
+    /// Sum of the voided transactions' debits, in functional currency.
+    pub total: Decimal,
+}
+
+/// Foreign currency balance of a monetary account, with account details.
+#[derive(Debug, Clone)]
+pub struct ForeignCurrencyBalance {
+    /// Account code.
+    pub code: String,
+    /// Account name.
+    pub name: String,
+    /// Account type.
+    pub account_type: AccountType,
+    /// Balance at historical rates.
+    pub balance: ForeignBalance,
+}
+
+/// Change markers for the data behind an organization's reports.
+///
+/// Any write that can change a report moves at least one marker, so two equal
+/// versions mean the same report output for the same parameters.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DataVersion {
+    /// Latest transaction change and transaction count.
+    pub transactions: (Option<DateTimeWithTimeZone>, i64),
+    /// Latest account change and account count.
+    pub accounts: (Option<DateTimeWithTimeZone>, i64),
+    /// Latest dimension type or value change, or re-tag of entries.
+    pub dimensions: Option<DateTimeWithTimeZone>,
+    /// Last change to the organization itself, e.g. its base currency.
+    pub organization: Option<DateTimeWithTimeZone>,
+}
+
+impl fmt::Display for DataVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let stamp =
+            |t: Option<DateTimeWithTimeZone>| t.map_or(0, |t| t.timestamp_micros()).to_string();
+        write!(
+            f,
+            "{}.{}.{}.{}.{}.{}",
+            stamp(self.transactions.0),
+            self.transactions.1,
+            stamp(self.accounts.0),
+            self.accounts.1,
+            stamp(self.dimensions),
+            stamp(self.organization),
+        )
+    }
+}
+
+/// An account picked by ID or by code.
+#[derive(Debug, Clone)]
+pub enum AccountLookup {
+    /// Account ID.
+    Id(Uuid),
+    /// Account code.
+    Code(String),
+}
+
+/// An account's movement over one fiscal period.
+#[derive(Debug, Clone)]
+pub struct AccountPeriodMovement {
+    /// Fiscal period.
+    pub period: fiscal_periods::Model,
+    /// Opening balance, activity and closing balance.
+    pub movement: PeriodMovement,
+}
+
+/// A transaction's contribution to an account's movement.
+#[derive(Debug, Clone)]
+pub struct MovementTransaction {
+    /// Transaction ID.
+    pub transaction_id: Uuid,
+    /// Transaction date.
+    pub transaction_date: NaiveDate,
+    /// Reference number.
+    pub reference_number: Option<String>,
+    /// Description.
+    pub description: String,
+    /// Net functional amount on the account's normal side.
+    pub amount: Decimal,
+}
+
+/// Period-over-period movement of one account.
+#[derive(Debug, Clone)]
+pub struct AccountMovement {
+    /// The account.
+    pub account: chart_of_accounts::Model,
+    /// Movement in the period being explained.
+    pub current: AccountPeriodMovement,
+    /// Movement in the period it is compared with.
+    pub compare: AccountPeriodMovement,
+    /// Change in net movement between the two periods.
+    pub change: MovementChange,
+    /// Largest contributors to the current period, by absolute amount.
+    pub top_transactions: Vec<MovementTransaction>,
+}
+
 /// Report repository for financial report queries.
 #[derive(Debug, Clone)]
 pub struct ReportRepository {
@@ -132,6 +305,78 @@ impl ReportRepository {
         Self { db }
     }
 
+    /// Reads the change markers for an organization's report data.
+    ///
+    /// Much cheaper than running a report, so callers can use it to decide
+    /// whether a previously served report is still current.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    pub async fn data_version(&self, organization_id: Uuid) -> Result<DataVersion, ReportError> {
+        let transactions: Option<(Option<DateTimeWithTimeZone>, i64)> =
+            transactions::Entity::find()
+                .select_only()
+                .column_as(transactions::Column::UpdatedAt.max(), "latest")
+                .column_as(transactions::Column::Id.count(), "count")
+                .filter(transactions::Column::OrganizationId.eq(organization_id))
+                .into_tuple()
+                .one(&self.db)
+                .await?;
+
+        let accounts: Option<(Option<DateTimeWithTimeZone>, i64)> =
+            chart_of_accounts::Entity::find()
+                .select_only()
+                .column_as(chart_of_accounts::Column::UpdatedAt.max(), "latest")
+                .column_as(chart_of_accounts::Column::Id.count(), "count")
+                .filter(chart_of_accounts::Column::OrganizationId.eq(organization_id))
+                .into_tuple()
+                .one(&self.db)
+                .await?;
+
+        let dimension_types: Option<DateTimeWithTimeZone> = dimension_types::Entity::find()
+            .select_only()
+            .column_as(dimension_types::Column::UpdatedAt.max(), "latest")
+            .filter(dimension_types::Column::OrganizationId.eq(organization_id))
+            .into_tuple()
+            .one(&self.db)
+            .await?
+            .flatten();
+
+        let dimension_values: Option<DateTimeWithTimeZone> = dimension_values::Entity::find()
+            .select_only()
+            .column_as(dimension_values::Column::UpdatedAt.max(), "latest")
+            .filter(dimension_values::Column::OrganizationId.eq(organization_id))
+            .into_tuple()
+            .one(&self.db)
+            .await?
+            .flatten();
+
+        let retags: Option<DateTimeWithTimeZone> = dimension_retags::Entity::find()
+            .select_only()
+            .column_as(dimension_retags::Column::CreatedAt.max(), "latest")
+            .filter(dimension_retags::Column::OrganizationId.eq(organization_id))
+            .into_tuple()
+            .one(&self.db)
+            .await?
+            .flatten();
+
+        let organization: Option<DateTimeWithTimeZone> =
+            organizations::Entity::find_by_id(organization_id)
+                .select_only()
+                .column(organizations::Column::UpdatedAt)
+                .into_tuple()
+                .one(&self.db)
+                .await?;
+
+        Ok(DataVersion {
+            transactions: transactions.unwrap_or_default(),
+            accounts: accounts.unwrap_or_default(),
+            dimensions: dimension_types.max(dimension_values).max(retags),
+            organization,
+        })
+    }
+
     // ========================================================================
     // Trial Balance Query (Requirements 5.1-5.7)
     // ========================================================================
@@ -140,6 +385,9 @@ impl ReportRepository {
     ///
     /// Requirements: 5.1, 5.2, 5.6, 5.7
     ///
+    /// Without dimension filters, totals start from the latest usable period
+    /// snapshot and only entries dated after it are scanned.
+    ///
     /// # Errors
     ///
     /// Returns an error if the database query fails.
@@ -157,6 +405,17 @@ impl ReportRepository {
             .all(&self.db)
             .await?;
 
+        // Without dimension filters, start from the latest period snapshot
+        if dimension_filters.is_empty() {
+            let totals = BalanceSnapshotRepository::new(self.db.clone())
+                .totals_as_of(organization_id, as_of)
+                .await?;
+            return Ok(accounts
+                .into_iter()
+                .map(|a| account_balance(a, &totals))
+                .collect());
+        }
+
         // Get posted transaction IDs up to as_of date
         let posted_tx_ids = self
             .get_posted_transaction_ids(organization_id, None, Some(as_of))
@@ -233,49 +492,14 @@ impl ReportRepository {
             .all(&self.db)
             .await?;
 
-        // Get posted transaction IDs up to as_of date
-        let posted_tx_ids = self
-            .get_posted_transaction_ids(organization_id, None, Some(as_of))
+        let totals = BalanceSnapshotRepository::new(self.db.clone())
+            .totals_as_of(organization_id, as_of)
             .await?;
 
-        if posted_tx_ids.is_empty() {
-            return Ok(accounts
-                .into_iter()
-                .map(|a| AccountBalance {
-                    account_id: a.id,
-                    code: a.code,
-                    name: a.name,
-                    account_type: a.account_type,
-                    account_subtype: a.account_subtype,
-                    total_debit: Decimal::ZERO,
-                    total_credit: Decimal::ZERO,
-                    balance: Decimal::ZERO,
-                })
-                .collect());
-        }
-
-        let mut result = Vec::with_capacity(accounts.len());
-
-        for account in accounts {
-            let (total_debit, total_credit) = self
-                .calculate_account_totals(account.id, &posted_tx_ids, &[])
-                .await?;
-
-            let balance = calculate_balance(&account.account_type, total_debit, total_credit);
-
-            result.push(AccountBalance {
-                account_id: account.id,
-                code: account.code,
-                name: account.name,
-                account_type: account.account_type,
-                account_subtype: account.account_subtype,
-                total_debit,
-                total_credit,
-                balance,
-            });
-        }
-
-        Ok(result)
+        Ok(accounts
+            .into_iter()
+            .map(|a| account_balance(a, &totals))
+            .collect())
     }
 
     // ========================================================================
@@ -361,6 +585,148 @@ impl ReportRepository {
         Ok(result)
     }
 
+    /// Queries posted income statement totals grouped by account and by the
+    /// values of one dimension type an entry carries.
+    ///
+    /// Entries with the same set of values are grouped together, so an entry
+    /// tagged with two values of the type stays one group for the caller to
+    /// split.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the date range is inverted, the dimension type is
+    /// not in the organization, or the database query fails.
+    #[allow(clippy::too_many_lines)]
+    pub async fn query_income_statement_by_dimension(
+        &self,
+        organization_id: Uuid,
+        from: NaiveDate,
+        to: NaiveDate,
+        dimension_type_id: Uuid,
+    ) -> Result<DimensionIncomeStatementData, ReportError> {
+        if from > to {
+            return Err(ReportError::InvalidDateRange {
+                start: from,
+                end: to,
+            });
+        }
+
+        dimension_types::Entity::find_by_id(dimension_type_id)
+            .filter(dimension_types::Column::OrganizationId.eq(organization_id))
+            .one(&self.db)
+            .await?
+            .ok_or_else(|| ReportError::InvalidDimensionType(dimension_type_id.to_string()))?;
+
+        let values = dimension_values::Entity::find()
+            .filter(dimension_values::Column::DimensionTypeId.eq(dimension_type_id))
+            .filter(dimension_values::Column::IsActive.eq(true))
+            .order_by_asc(dimension_values::Column::Code)
+            .all(&self.db)
+            .await?
+            .into_iter()
+            .map(|v| ReportDimensionValue {
+                id: v.id,
+                code: v.code,
+                name: v.name,
+            })
+            .collect();
+
+        let accounts = chart_of_accounts::Entity::find()
+            .filter(chart_of_accounts::Column::OrganizationId.eq(organization_id))
+            .filter(chart_of_accounts::Column::IsActive.eq(true))
+            .filter(
+                chart_of_accounts::Column::AccountType
+                    .is_in([AccountType::Revenue, AccountType::Expense]),
+            )
+            .order_by_asc(chart_of_accounts::Column::Code)
+            .all(&self.db)
+            .await?;
+        let account_ids: Vec<Uuid> = accounts.iter().map(|a| a.id).collect();
+
+        let posted_tx_ids = self
+            .get_posted_transaction_ids(organization_id, Some(from), Some(to))
+            .await?;
+
+        let entries: Vec<(Uuid, Uuid, Decimal, Decimal)> =
+            if posted_tx_ids.is_empty() || account_ids.is_empty() {
+                vec![]
+            } else {
+                ledger_entries::Entity::find()
+                    .select_only()
+                    .column(ledger_entries::Column::Id)
+                    .column(ledger_entries::Column::AccountId)
+                    .column(ledger_entries::Column::Debit)
+                    .column(ledger_entries::Column::Credit)
+                    .filter(ledger_entries::Column::AccountId.is_in(account_ids))
+                    .filter(ledger_entries::Column::TransactionId.is_in(posted_tx_ids))
+                    .into_tuple()
+                    .all(&self.db)
+                    .await?
+            };
+
+        let mut tags: std::collections::HashMap<Uuid, Vec<Uuid>> = std::collections::HashMap::new();
+        if !entries.is_empty() {
+            let tagged: Vec<(Uuid, Uuid)> = entry_dimensions::Entity::find()
+                .select_only()
+                .column(entry_dimensions::Column::LedgerEntryId)
+                .column(entry_dimensions::Column::DimensionValueId)
+                .join(
+                    JoinType::InnerJoin,
+                    entry_dimensions::Relation::DimensionValues.def(),
+                )
+                .filter(dimension_values::Column::DimensionTypeId.eq(dimension_type_id))
+                .filter(
+                    entry_dimensions::Column::LedgerEntryId
+                        .is_in(entries.iter().map(|(id, ..)| *id)),
+                )
+                .into_tuple()
+                .all(&self.db)
+                .await?;
+            for (entry_id, value_id) in tagged {
+                tags.entry(entry_id).or_default().push(value_id);
+            }
+        }
+
+        let mut groups: std::collections::BTreeMap<(Uuid, Vec<Uuid>), (Decimal, Decimal)> =
+            std::collections::BTreeMap::new();
+        let mut account_totals: AccountTotals = AccountTotals::new();
+        for (entry_id, account_id, debit, credit) in entries {
+            let mut value_ids = tags.remove(&entry_id).unwrap_or_default();
+            value_ids.sort_unstable();
+            let group = groups
+                .entry((account_id, value_ids))
+                .or_insert((Decimal::ZERO, Decimal::ZERO));
+            group.0 += debit;
+            group.1 += credit;
+            let account = account_totals
+                .entry(account_id)
+                .or_insert((Decimal::ZERO, Decimal::ZERO));
+            account.0 += debit;
+            account.1 += credit;
+        }
+
+        Ok(DimensionIncomeStatementData {
+            accounts: accounts
+                .into_iter()
+                .map(|a| account_balance(a, &account_totals))
+                .collect(),
+            values,
+            totals: groups
+                .into_iter()
+                .map(
+                    |((account_id, dimension_value_ids), (total_debit, total_credit))| {
+                        DimensionTaggedTotals {
+                            account_id,
+                            dimension_value_ids,
+                            total_debit,
+                            total_credit,
+                        }
+                    },
+                )
+                .collect(),
+        })
+    }
+
     // ========================================================================
     // Account Ledger Query (Requirements 8.1-8.6)
     // ========================================================================
@@ -413,11 +779,18 @@ impl ReportRepository {
             .await?;
 
         // Query entries with pagination (Requirement 8.5)
-        // Order by transaction date and entry creation (Requirement 8.6)
+        // Order by transaction date, then when each entry happened within the
+        // day (Requirement 8.6)
         let entries = ledger_entries::Entity::find()
             .filter(ledger_entries::Column::AccountId.eq(account_id))
             .filter(ledger_entries::Column::TransactionId.is_in(posted_tx_ids))
-            .order_by_asc(ledger_entries::Column::CreatedAt)
+            .join(
+                JoinType::InnerJoin,
+                ledger_entries::Relation::Transactions.def(),
+            )
+            .order_by_asc(transactions::Column::TransactionDate)
+            .order_by_asc(ledger_entries::Column::EventAt)
+            .order_by_asc(ledger_entries::Column::AccountVersion)
             .offset(page * limit)
             .limit(limit)
             .all(&self.db)
@@ -459,6 +832,198 @@ impl ReportRepository {
         Ok((result, total_count))
     }
 
+    // ========================================================================
+    // Account Movement Query
+    // ========================================================================
+
+    /// Compares an account's movement in two fiscal periods and lists the
+    /// transactions that contributed most to the current one.
+    ///
+    /// Opening balances count posted entries dated before each period;
+    /// period activity counts posted transactions recorded in the period.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the account or either period is not found in the
+    /// organization, the account does not allow direct posting, or a
+    /// database query fails.
+    pub async fn query_account_movement(
+        &self,
+        organization_id: Uuid,
+        account: &AccountLookup,
+        current_period_id: Uuid,
+        compare_period_id: Uuid,
+        top: u64,
+    ) -> Result<AccountMovement, ReportError> {
+        let account = self.find_postable_account(organization_id, account).await?;
+        let current = self.find_period(organization_id, current_period_id).await?;
+        let compare = self.find_period(organization_id, compare_period_id).await?;
+        let debit_normal = is_debit_normal(&account.account_type);
+
+        // Entries before the earlier period, then up to the later one
+        let (earlier, later) = if compare.start_date <= current.start_date {
+            (compare.start_date, current.start_date)
+        } else {
+            (current.start_date, compare.start_date)
+        };
+        let before_earlier: SimpleExpr = Expr::case(
+            Expr::col((transactions::Entity, transactions::Column::TransactionDate)).lt(earlier),
+            true,
+        )
+        .finally(false)
+        .into();
+        let openings: Vec<(bool, Decimal, Decimal)> =
+            posted_account_entries(organization_id, account.id)
+                .select_only()
+                .column_as(before_earlier, "before_earlier")
+                .column_as(ledger_entries::Column::Debit.sum(), "total_debit")
+                .column_as(ledger_entries::Column::Credit.sum(), "total_credit")
+                .filter(transactions::Column::TransactionDate.lt(later))
+                // By output name, as a repeated expression binds its date again
+                .group_by(Expr::col(Alias::new("before_earlier")))
+                .into_tuple()
+                .all(&self.db)
+                .await?;
+        let opening = |start: NaiveDate| {
+            openings
+                .iter()
+                .filter(|(before_earlier, _, _)| *before_earlier || start > earlier)
+                .fold((Decimal::ZERO, Decimal::ZERO), |(debit, credit), row| {
+                    (debit + row.1, credit + row.2)
+                })
+        };
+
+        let activity: HashMap<Uuid, (Decimal, Decimal)> =
+            posted_account_entries(organization_id, account.id)
+                .select_only()
+                .column(transactions::Column::FiscalPeriodId)
+                .column_as(ledger_entries::Column::Debit.sum(), "total_debit")
+                .column_as(ledger_entries::Column::Credit.sum(), "total_credit")
+                .filter(transactions::Column::FiscalPeriodId.is_in([current.id, compare.id]))
+                .group_by(transactions::Column::FiscalPeriodId)
+                .into_tuple::<(Uuid, Decimal, Decimal)>()
+                .all(&self.db)
+                .await?
+                .into_iter()
+                .map(|(period_id, debit, credit)| (period_id, (debit, credit)))
+                .collect();
+        let period_movement = |period: fiscal_periods::Model| {
+            let within = activity
+                .get(&period.id)
+                .copied()
+                .unwrap_or((Decimal::ZERO, Decimal::ZERO));
+            AccountPeriodMovement {
+                movement: PeriodMovement::new(debit_normal, opening(period.start_date), within),
+                period,
+            }
+        };
+        let current = period_movement(current);
+        let compare = period_movement(compare);
+
+        let top_transactions = self
+            .top_contributors(
+                organization_id,
+                account.id,
+                current.period.id,
+                debit_normal,
+                top,
+            )
+            .await?;
+
+        Ok(AccountMovement {
+            change: MovementChange::between(&compare.movement, &current.movement),
+            account,
+            current,
+            compare,
+            top_transactions,
+        })
+    }
+
+    /// Transactions with the largest net amount on an account in a period.
+    async fn top_contributors(
+        &self,
+        organization_id: Uuid,
+        account_id: Uuid,
+        period_id: Uuid,
+        debit_normal: bool,
+        top: u64,
+    ) -> Result<Vec<MovementTransaction>, ReportError> {
+        let net = Expr::col((ledger_entries::Entity, ledger_entries::Column::Debit))
+            .sum()
+            .sub(Expr::col((ledger_entries::Entity, ledger_entries::Column::Credit)).sum());
+        let rows: Vec<(Uuid, NaiveDate, Option<String>, String, Decimal)> =
+            posted_account_entries(organization_id, account_id)
+                .select_only()
+                .column(transactions::Column::Id)
+                .column(transactions::Column::TransactionDate)
+                .column(transactions::Column::ReferenceNumber)
+                .column(transactions::Column::Description)
+                .column_as(net.clone(), "net")
+                .filter(transactions::Column::FiscalPeriodId.eq(period_id))
+                .group_by(transactions::Column::Id)
+                .order_by(SimpleExpr::from(Func::abs(net)), Order::Desc)
+                .order_by_asc(transactions::Column::TransactionDate)
+                .order_by_asc(transactions::Column::Id)
+                .limit(top)
+                .into_tuple()
+                .all(&self.db)
+                .await?;
+        Ok(rows
+            .into_iter()
+            .map(
+                |(transaction_id, transaction_date, reference_number, description, net)| {
+                    MovementTransaction {
+                        transaction_id,
+                        transaction_date,
+                        reference_number,
+                        description,
+                        amount: if debit_normal { net } else { -net },
+                    }
+                },
+            )
+            .collect())
+    }
+
+    /// Finds an account that takes postings by ID or code.
+    async fn find_postable_account(
+        &self,
+        organization_id: Uuid,
+        lookup: &AccountLookup,
+    ) -> Result<chart_of_accounts::Model, ReportError> {
+        let query = chart_of_accounts::Entity::find()
+            .filter(chart_of_accounts::Column::OrganizationId.eq(organization_id));
+        let account = match lookup {
+            AccountLookup::Id(id) => query
+                .filter(chart_of_accounts::Column::Id.eq(*id))
+                .one(&self.db)
+                .await?
+                .ok_or(ReportError::AccountNotFound(*id))?,
+            AccountLookup::Code(code) => query
+                .filter(chart_of_accounts::Column::Code.eq(code.as_str()))
+                .one(&self.db)
+                .await?
+                .ok_or_else(|| ReportError::AccountCodeNotFound(code.clone()))?,
+        };
+
+        if !account.allow_direct_posting {
+            return Err(ReportError::AccountNotPostable(account.id));
+        }
+        Ok(account)
+    }
+
+    /// Finds a fiscal period in the organization.
+    async fn find_period(
+        &self,
+        organization_id: Uuid,
+        period_id: Uuid,
+    ) -> Result<fiscal_periods::Model, ReportError> {
+        fiscal_periods::Entity::find_by_id(period_id)
+            .filter(fiscal_periods::Column::OrganizationId.eq(organization_id))
+            .one(&self.db)
+            .await?
+            .ok_or(ReportError::PeriodNotFound(period_id))
+    }
+
     // ========================================================================
     // Dimensional Report Query (Requirements 9.1-9.7)
     // ========================================================================
@@ -469,7 +1034,8 @@ impl ReportRepository {
     ///
     /// # Errors
     ///
-    /// Returns an error if the database query fails.
+    /// Returns an error if an included or excluded dimension value does not
+    /// belong to the organization, or if the database query fails.
     pub async fn query_dimensional_report(
         &self,
         organization_id: Uuid,
@@ -477,7 +1043,7 @@ impl ReportRepository {
         to: NaiveDate,
         group_by: &[String],
         account_type_filter: Option<AccountType>,
-        dimension_filters: &[Uuid],
+        dimension_filter: &DimensionValueFilter,
     ) -> Result<(Vec<DimensionalReportRow>, Decimal), ReportError> {
         // Validate date range
         if from > to {
@@ -532,10 +1098,15 @@ impl ReportRepository {
             return Ok((vec![], Decimal::ZERO));
         }
 
+        let value_condition = self
+            .dimension_value_condition(organization_id, dimension_filter)
+            .await?;
+
         // Query all relevant ledger entries
         let entries = ledger_entries::Entity::find()
             .filter(ledger_entries::Column::AccountId.is_in(account_ids))
             .filter(ledger_entries::Column::TransactionId.is_in(posted_tx_ids))
+            .filter(value_condition)
             .all(&self.db)
             .await?;
 
@@ -551,10 +1122,13 @@ impl ReportRepository {
                 .await?;
 
             // Filter by dimension filters if specified (Requirement 9.5)
-            if !dimension_filters.is_empty() {
+            if !dimension_filter.all_of.is_empty() {
                 let entry_dim_ids: Vec<Uuid> =
                     entry_dims.iter().map(|d| d.dimension_value_id).collect();
-                let has_all = dimension_filters.iter().all(|f| entry_dim_ids.contains(f));
+                let has_all = dimension_filter
+                    .all_of
+                    .iter()
+                    .all(|f| entry_dim_ids.contains(f));
                 if !has_all {
                     continue;
                 }
@@ -595,6 +1169,614 @@ impl ReportRepository {
         self.build_dimensional_rows(dimension_groups).await
     }
 
+    /// Builds the entry condition for included and excluded dimension values.
+    ///
+    /// Included values are grouped by dimension type: an entry must carry one
+    /// value of each type, or none of that type when untagged entries are
+    /// included. Excluded values drop only entries that carry them.
+    async fn dimension_value_condition(
+        &self,
+        organization_id: Uuid,
+        filter: &DimensionValueFilter,
+    ) -> Result<Condition, ReportError> {
+        let mut condition = Condition::all();
+        if filter.include.is_empty() && filter.exclude.is_empty() {
+            return Ok(condition);
+        }
+
+        let requested: Vec<Uuid> = filter
+            .include
+            .iter()
+            .chain(&filter.exclude)
+            .copied()
+            .collect();
+        let value_types = self
+            .dimension_value_types(organization_id, &requested)
+            .await?;
+
+        let mut include_by_type: std::collections::BTreeMap<Uuid, Vec<Uuid>> =
+            std::collections::BTreeMap::new();
+        for value_id in &filter.include {
+            include_by_type
+                .entry(value_types[value_id])
+                .or_default()
+                .push(*value_id);
+        }
+
+        for (type_id, values) in include_by_type {
+            let tagged = Expr::exists(entry_dimension_query(values));
+            if filter.include_untagged {
+                let untagged = Expr::exists(
+                    entry_dimension_query_base()
+                        .inner_join(
+                            dimension_values::Entity,
+                            Expr::col((dimension_values::Entity, dimension_values::Column::Id))
+                                .equals((
+                                    entry_dimensions::Entity,
+                                    entry_dimensions::Column::DimensionValueId,
+                                )),
+                        )
+                        .and_where(
+                            Expr::col((
+                                dimension_values::Entity,
+                                dimension_values::Column::DimensionTypeId,
+                            ))
+                            .eq(type_id),
+                        )
+                        .to_owned(),
+                )
+                .not();
+                condition = condition.add(Condition::any().add(tagged).add(untagged));
+            } else {
+                condition = condition.add(tagged);
+            }
+        }
+
+        if !filter.exclude.is_empty() {
+            condition =
+                condition.add(Expr::exists(entry_dimension_query(filter.exclude.clone())).not());
+        }
+
+        Ok(condition)
+    }
+
+    /// Looks up the dimension type of each value.
+    ///
+    /// # Errors
+    ///
+    /// Returns `DimensionValueNotFound` for a value the organization does not
+    /// have.
+    async fn dimension_value_types(
+        &self,
+        organization_id: Uuid,
+        values: &[Uuid],
+    ) -> Result<HashMap<Uuid, Uuid>, ReportError> {
+        let value_types: HashMap<Uuid, Uuid> = dimension_values::Entity::find()
+            .select_only()
+            .column(dimension_values::Column::Id)
+            .column(dimension_values::Column::DimensionTypeId)
+            .filter(dimension_values::Column::OrganizationId.eq(organization_id))
+            .filter(dimension_values::Column::Id.is_in(values.to_vec()))
+            .into_tuple::<(Uuid, Uuid)>()
+            .all(&self.db)
+            .await?
+            .into_iter()
+            .collect();
+        if let Some(missing) = values.iter().find(|id| !value_types.contains_key(id)) {
+            return Err(ReportError::DimensionValueNotFound(*missing));
+        }
+        Ok(value_types)
+    }
+
+    // ========================================================================
+    // Void Analysis Query
+    // ========================================================================
+
+    /// Groups transactions voided between `from` and `to` (inclusive, UTC) by
+    /// reason code.
+    ///
+    /// Every code is returned, with zeros when unused, in
+    /// [`VoidReasonCode::ALL`] order. Voids without a code follow last, and
+    /// only if there are any.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the date range is inverted or the query fails.
+    pub async fn query_void_summary(
+        &self,
+        organization_id: Uuid,
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> Result<Vec<VoidReasonSummary>, ReportError> {
+        if from > to {
+            return Err(ReportError::InvalidDateRange {
+                start: from,
+                end: to,
+            });
+        }
+        let start = from.and_time(chrono::NaiveTime::MIN).and_utc();
+        let end = to
+            .checked_add_days(Days::new(1))
+            .unwrap_or(to)
+            .and_time(chrono::NaiveTime::MIN)
+            .and_utc();
+
+        let rows: Vec<(Option<DbVoidReasonCode>, i64, Option<Decimal>)> =
+            ledger_entries::Entity::find()
+                .select_only()
+                .join(
+                    JoinType::InnerJoin,
+                    ledger_entries::Relation::Transactions.def(),
+                )
+                .column(transactions::Column::VoidReasonCode)
+                .column_as(
+                    Expr::col((transactions::Entity, transactions::Column::Id)).count_distinct(),
+                    "count",
+                )
+                .column_as(ledger_entries::Column::Debit.sum(), "total")
+                .filter(transactions::Column::OrganizationId.eq(organization_id))
+                .filter(transactions::Column::Status.eq(TransactionStatus::Voided))
+                .filter(transactions::Column::VoidedAt.gte(start))
+                .filter(transactions::Column::VoidedAt.lt(end))
+                .group_by(transactions::Column::VoidReasonCode)
+                .into_tuple()
+                .all(&self.db)
+                .await?;
+
+        let mut summaries: Vec<VoidReasonSummary> = VoidReasonCode::ALL
+            .into_iter()
+            .map(|code| VoidReasonSummary {
+                reason_code: Some(code),
+                count: 0,
+                total: Decimal::ZERO,
+            })
+            .collect();
+
+        for (code, count, total) in rows {
+            let reason_code = code.as_ref().map(db_void_code_to_core);
+            let summary = VoidReasonSummary {
+                reason_code,
+                count: u64::try_from(count).unwrap_or_default(),
+                total: total.unwrap_or_default(),
+            };
+            match summaries.iter_mut().find(|s| s.reason_code == reason_code) {
+                Some(existing) => *existing = summary,
+                None => summaries.push(summary),
+            }
+        }
+
+        Ok(summaries)
+    }
+
+    /// Aggregates posted foreign currency entries by account and currency.
+    ///
+    /// Only monetary balance sheet accounts are included: assets and
+    /// liabilities, less inventory, prepaid and fixed asset subtypes, which
+    /// stay at their historical rates. Entries in the functional currency
+    /// are left out. Balances that net to zero in both currencies are
+    /// dropped.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    pub async fn query_foreign_balances(
+        &self,
+        organization_id: Uuid,
+        as_of: NaiveDate,
+    ) -> Result<Vec<ForeignCurrencyBalance>, ReportError> {
+        #[derive(Debug, FromQueryResult)]
+        struct ForeignBalanceRow {
+            account_id: Uuid,
+            code: String,
+            name: String,
+            account_type: AccountType,
+            source_currency: String,
+            source_balance: Option<Decimal>,
+            debit: Option<Decimal>,
+            credit: Option<Decimal>,
+        }
+
+        let signed_source = Expr::cust(
+            "SUM(CASE WHEN ledger_entries.debit > 0 \
+             THEN ledger_entries.source_amount ELSE -ledger_entries.source_amount END)",
+        );
+        let rows = ledger_entries::Entity::find()
+            .select_only()
+            .join(
+                JoinType::InnerJoin,
+                ledger_entries::Relation::Transactions.def(),
+            )
+            .join(
+                JoinType::InnerJoin,
+                ledger_entries::Relation::ChartOfAccounts.def(),
+            )
+            .column(ledger_entries::Column::AccountId)
+            .column(chart_of_accounts::Column::Code)
+            .column(chart_of_accounts::Column::Name)
+            .column(chart_of_accounts::Column::AccountType)
+            .column(ledger_entries::Column::SourceCurrency)
+            .column_as(signed_source, "source_balance")
+            .column_as(ledger_entries::Column::Debit.sum(), "debit")
+            .column_as(ledger_entries::Column::Credit.sum(), "credit")
+            .filter(transactions::Column::OrganizationId.eq(organization_id))
+            .filter(transactions::Column::Status.eq(TransactionStatus::Posted))
+            .filter(transactions::Column::TransactionDate.lte(as_of))
+            .filter(
+                chart_of_accounts::Column::AccountType
+                    .is_in([AccountType::Asset, AccountType::Liability]),
+            )
+            .filter(
+                Condition::any()
+                    .add(chart_of_accounts::Column::AccountSubtype.is_null())
+                    .add(chart_of_accounts::Column::AccountSubtype.is_not_in([
+                        AccountSubtype::Inventory,
+                        AccountSubtype::Prepaid,
+                        AccountSubtype::FixedAsset,
+                        AccountSubtype::AccumulatedDepreciation,
+                    ])),
+            )
+            .filter(
+                Expr::col((
+                    ledger_entries::Entity,
+                    ledger_entries::Column::SourceCurrency,
+                ))
+                .ne(Expr::col((
+                    ledger_entries::Entity,
+                    ledger_entries::Column::FunctionalCurrency,
+                ))),
+            )
+            .group_by(ledger_entries::Column::AccountId)
+            .group_by(chart_of_accounts::Column::Code)
+            .group_by(chart_of_accounts::Column::Name)
+            .group_by(chart_of_accounts::Column::AccountType)
+            .group_by(ledger_entries::Column::SourceCurrency)
+            .order_by_asc(chart_of_accounts::Column::Code)
+            .order_by_asc(ledger_entries::Column::SourceCurrency)
+            .into_model::<ForeignBalanceRow>()
+            .all(&self.db)
+            .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| ForeignCurrencyBalance {
+                code: row.code,
+                name: row.name,
+                account_type: row.account_type,
+                balance: ForeignBalance {
+                    account_id: row.account_id,
+                    currency: row.source_currency,
+                    source_balance: row.source_balance.unwrap_or_default(),
+                    booked_functional: row.debit.unwrap_or_default()
+                        - row.credit.unwrap_or_default(),
+                },
+            })
+            .filter(|b| {
+                !(b.balance.source_balance.is_zero() && b.balance.booked_functional.is_zero())
+            })
+            .collect())
+    }
+
+    /// Runs every data-quality check for an organization.
+    ///
+    /// Findings are grouped by check, in [`DataQualityCheck::ALL`] order.
+    ///
+    /// [`DataQualityCheck::ALL`]: zeltra_core::reports::DataQualityCheck::ALL
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any query fails.
+    pub async fn query_data_quality(
+        &self,
+        organization_id: Uuid,
+    ) -> Result<Vec<DataQualityFinding>, ReportError> {
+        let mut findings = self.find_unbalanced_transactions(organization_id).await?;
+        findings.extend(
+            self.find_functional_amount_mismatches(organization_id)
+                .await?,
+        );
+        findings.extend(
+            self.find_missing_required_dimensions(organization_id)
+                .await?,
+        );
+        findings.extend(
+            self.find_restricted_account_entries(organization_id)
+                .await?,
+        );
+        Ok(findings)
+    }
+
+    /// Checks draft, pending and approved transactions for balance.
+    ///
+    /// Posted transactions are balanced by the database trigger.
+    async fn find_unbalanced_transactions(
+        &self,
+        organization_id: Uuid,
+    ) -> Result<Vec<DataQualityFinding>, ReportError> {
+        let rows: Vec<(Uuid, Option<Decimal>, Option<Decimal>)> = ledger_entries::Entity::find()
+            .select_only()
+            .join(
+                JoinType::InnerJoin,
+                ledger_entries::Relation::Transactions.def(),
+            )
+            .column(ledger_entries::Column::TransactionId)
+            .column_as(ledger_entries::Column::Debit.sum(), "total_debit")
+            .column_as(ledger_entries::Column::Credit.sum(), "total_credit")
+            .filter(transactions::Column::OrganizationId.eq(organization_id))
+            .filter(transactions::Column::Status.is_in([
+                TransactionStatus::Draft,
+                TransactionStatus::Pending,
+                TransactionStatus::Approved,
+            ]))
+            .group_by(ledger_entries::Column::TransactionId)
+            .order_by_asc(ledger_entries::Column::TransactionId)
+            .into_tuple()
+            .all(&self.db)
+            .await?;
+
+        let totals: Vec<TransactionTotals> = rows
+            .into_iter()
+            .map(|(transaction_id, debit, credit)| TransactionTotals {
+                transaction_id,
+                total_debit: debit.unwrap_or_default(),
+                total_credit: credit.unwrap_or_default(),
+            })
+            .collect();
+
+        Ok(data_quality::check_unbalanced(&totals))
+    }
+
+    /// Checks posted entries' functional amounts against their conversion.
+    async fn find_functional_amount_mismatches(
+        &self,
+        organization_id: Uuid,
+    ) -> Result<Vec<DataQualityFinding>, ReportError> {
+        let rows: Vec<(Uuid, Uuid, Uuid, Decimal, Decimal, Decimal)> =
+            ledger_entries::Entity::find()
+                .select_only()
+                .join(
+                    JoinType::InnerJoin,
+                    ledger_entries::Relation::Transactions.def(),
+                )
+                .column(ledger_entries::Column::Id)
+                .column(ledger_entries::Column::TransactionId)
+                .column(ledger_entries::Column::AccountId)
+                .column(ledger_entries::Column::SourceAmount)
+                .column(ledger_entries::Column::ExchangeRate)
+                .column(ledger_entries::Column::FunctionalAmount)
+                .filter(transactions::Column::OrganizationId.eq(organization_id))
+                .filter(transactions::Column::Status.eq(TransactionStatus::Posted))
+                .order_by_asc(ledger_entries::Column::CreatedAt)
+                .into_tuple()
+                .all(&self.db)
+                .await?;
+
+        let entries: Vec<EntryConversion> = rows
+            .into_iter()
+            .map(
+                |(entry_id, transaction_id, account_id, source, rate, functional)| {
+                    EntryConversion {
+                        entry_id,
+                        transaction_id,
+                        account_id,
+                        source_amount: source,
+                        exchange_rate: rate,
+                        functional_amount: functional,
+                    }
+                },
+            )
+            .collect();
+
+        Ok(data_quality::check_functional_amounts(
+            &entries,
+            FUNCTIONAL_AMOUNT_TOLERANCE,
+        ))
+    }
+
+    /// Checks non-voided transactions for the active required dimension types.
+    async fn find_missing_required_dimensions(
+        &self,
+        organization_id: Uuid,
+    ) -> Result<Vec<DataQualityFinding>, ReportError> {
+        let required: Vec<RequiredDimension> = dimension_types::Entity::find()
+            .filter(dimension_types::Column::OrganizationId.eq(organization_id))
+            .filter(dimension_types::Column::IsRequired.eq(true))
+            .filter(dimension_types::Column::IsActive.eq(true))
+            .order_by_asc(dimension_types::Column::SortOrder)
+            .all(&self.db)
+            .await?
+            .into_iter()
+            .map(|t| RequiredDimension {
+                dimension_type_id: t.id,
+                code: t.code,
+            })
+            .collect();
+        if required.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let transaction_ids: Vec<Uuid> = transactions::Entity::find()
+            .select_only()
+            .column(transactions::Column::Id)
+            .filter(transactions::Column::OrganizationId.eq(organization_id))
+            .filter(transactions::Column::Status.ne(TransactionStatus::Voided))
+            .order_by_asc(transactions::Column::TransactionDate)
+            .order_by_asc(transactions::Column::Id)
+            .into_tuple()
+            .all(&self.db)
+            .await?;
+
+        let tagged: Vec<(Uuid, Uuid)> = entry_dimensions::Entity::find()
+            .select_only()
+            .join(
+                JoinType::InnerJoin,
+                entry_dimensions::Relation::LedgerEntries.def(),
+            )
+            .join(
+                JoinType::InnerJoin,
+                entry_dimensions::Relation::DimensionValues.def(),
+            )
+            .join(
+                JoinType::InnerJoin,
+                ledger_entries::Relation::Transactions.def(),
+            )
+            .column(ledger_entries::Column::TransactionId)
+            .column(dimension_values::Column::DimensionTypeId)
+            .filter(transactions::Column::OrganizationId.eq(organization_id))
+            .filter(transactions::Column::Status.ne(TransactionStatus::Voided))
+            .distinct()
+            .into_tuple()
+            .all(&self.db)
+            .await?;
+
+        let mut transactions: Vec<TransactionDimensions> = transaction_ids
+            .into_iter()
+            .map(|transaction_id| TransactionDimensions {
+                transaction_id,
+                dimension_type_ids: std::collections::HashSet::new(),
+            })
+            .collect();
+        let positions: std::collections::HashMap<Uuid, usize> = transactions
+            .iter()
+            .enumerate()
+            .map(|(i, t)| (t.transaction_id, i))
+            .collect();
+        for (transaction_id, dimension_type_id) in tagged {
+            if let Some(&i) = positions.get(&transaction_id) {
+                transactions[i].dimension_type_ids.insert(dimension_type_id);
+            }
+        }
+
+        Ok(data_quality::check_required_dimensions(
+            &transactions,
+            &required,
+        ))
+    }
+
+    /// Checks accounts closed to direct posting for non-voided entries.
+    async fn find_restricted_account_entries(
+        &self,
+        organization_id: Uuid,
+    ) -> Result<Vec<DataQualityFinding>, ReportError> {
+        let rows: Vec<(Uuid, String, i64)> = ledger_entries::Entity::find()
+            .select_only()
+            .join(
+                JoinType::InnerJoin,
+                ledger_entries::Relation::ChartOfAccounts.def(),
+            )
+            .join(
+                JoinType::InnerJoin,
+                ledger_entries::Relation::Transactions.def(),
+            )
+            .column(chart_of_accounts::Column::Id)
+            .column(chart_of_accounts::Column::Code)
+            .column_as(ledger_entries::Column::Id.count(), "entry_count")
+            .filter(chart_of_accounts::Column::OrganizationId.eq(organization_id))
+            .filter(chart_of_accounts::Column::AllowDirectPosting.eq(false))
+            .filter(transactions::Column::Status.ne(TransactionStatus::Voided))
+            .group_by(chart_of_accounts::Column::Id)
+            .group_by(chart_of_accounts::Column::Code)
+            .order_by_asc(chart_of_accounts::Column::Code)
+            .into_tuple()
+            .all(&self.db)
+            .await?;
+
+        let accounts: Vec<RestrictedAccountUsage> = rows
+            .into_iter()
+            .map(|(account_id, code, count)| RestrictedAccountUsage {
+                account_id,
+                code,
+                entry_count: u64::try_from(count).unwrap_or_default(),
+            })
+            .collect();
+
+        Ok(data_quality::check_direct_posting(&accounts))
+    }
+
+    // ========================================================================
+    // Custom Report Query
+    // ========================================================================
+
+    /// Runs a validated custom report definition.
+    ///
+    /// Every field of the plan maps to a fixed column expression and every
+    /// filter is bound as a query parameter, so nothing the user typed
+    /// becomes SQL. At most [`MAX_CUSTOM_REPORT_ROWS`] rows are returned.
+    ///
+    /// # Errors
+    ///
+    /// Returns `DimensionValueNotFound` for a filter value the organization
+    /// does not have, or an error if the query fails.
+    pub async fn run_custom_report(
+        &self,
+        organization_id: Uuid,
+        plan: &ReportPlan,
+    ) -> Result<CustomReportResult, ReportError> {
+        let value_types = self
+            .dimension_value_types(organization_id, &plan.dimension_value_ids)
+            .await?;
+        let mut dimensions: BTreeMap<Uuid, Vec<Uuid>> = BTreeMap::new();
+        for value_id in &plan.dimension_value_ids {
+            dimensions
+                .entry(value_types[value_id])
+                .or_default()
+                .push(*value_id);
+        }
+
+        let dataset = Alias::new("dataset");
+        let column = |field: ReportField| -> SimpleExpr {
+            let col = |f: ReportField| Expr::col((dataset.clone(), Alias::new(f.name())));
+            if field == ReportField::Variance {
+                col(ReportField::Budgeted).sub(col(ReportField::Actual))
+            } else {
+                col(field).into()
+            }
+        };
+
+        let mut query = Query::select();
+        query.from_subquery(
+            custom_report_base(organization_id, plan, &dimensions),
+            dataset.clone(),
+        );
+        for (i, field) in plan.fields.iter().enumerate() {
+            query.expr_as(column(*field), Alias::new(format!("c{i}")));
+            if plan.grouped {
+                query.add_group_by([column(*field)]);
+            }
+            query.order_by_expr(column(*field), Order::Asc);
+        }
+        if let Some((function, field)) = plan.aggregate {
+            let aggregate: SimpleExpr = match field.map(column) {
+                None => Func::count(Expr::val(1)).into(),
+                Some(value) => match function {
+                    AggregateFunction::Sum => Func::sum(value).into(),
+                    AggregateFunction::Avg => {
+                        Func::round_with_precision(Func::avg(value), 4).into()
+                    }
+                    AggregateFunction::Min => Func::min(value).into(),
+                    AggregateFunction::Max => Func::max(value).into(),
+                    AggregateFunction::Count => Func::count(value).into(),
+                },
+            };
+            query.expr_as(aggregate, Alias::new(format!("c{}", plan.fields.len())));
+        }
+        query.limit(MAX_CUSTOM_REPORT_ROWS as u64 + 1);
+
+        let backend = self.db.get_database_backend();
+        let rows = self.db.query_all(backend.build(&query)).await?;
+        let truncated = rows.len() > MAX_CUSTOM_REPORT_ROWS;
+        let rows = rows
+            .iter()
+            .take(MAX_CUSTOM_REPORT_ROWS)
+            .map(|row| read_custom_report_row(row, plan))
+            .collect::<Result<_, DbErr>>()?;
+
+        Ok(CustomReportResult {
+            columns: plan.column_names(),
+            rows,
+            truncated,
+        })
+    }
+
     // ========================================================================
     // Helper Methods
     // ========================================================================
@@ -754,6 +1936,282 @@ impl ReportRepository {
 // Balance Calculation Helper
 // ============================================================================
 
+/// Ledger entries of an account from the organization's posted transactions.
+fn posted_account_entries(
+    organization_id: Uuid,
+    account_id: Uuid,
+) -> Select<ledger_entries::Entity> {
+    ledger_entries::Entity::find()
+        .join(
+            JoinType::InnerJoin,
+            ledger_entries::Relation::Transactions.def(),
+        )
+        .filter(transactions::Column::OrganizationId.eq(organization_id))
+        .filter(transactions::Column::Status.eq(TransactionStatus::Posted))
+        .filter(ledger_entries::Column::AccountId.eq(account_id))
+}
+
+/// The rows of a custom report's dataset, one column per field named after
+/// it, with the plan's filters applied.
+///
+/// `dimensions` holds the filter's dimension values by type.
+fn custom_report_base(
+    organization_id: Uuid,
+    plan: &ReportPlan,
+    dimensions: &BTreeMap<Uuid, Vec<Uuid>>,
+) -> SelectStatement {
+    let fields = ReportField::ALL
+        .into_iter()
+        .filter(|f| f.in_dataset(plan.dataset) && *f != ReportField::Variance);
+    let account_types: Vec<AccountType> = plan
+        .account_types
+        .iter()
+        .filter_map(|t| AccountType::try_from_value(t).ok())
+        .collect();
+    let mut condition = Condition::all();
+    if !account_types.is_empty() {
+        condition = condition.add(chart_of_accounts::Column::AccountType.is_in(account_types));
+    }
+
+    let mut query = match plan.dataset {
+        ReportDataset::LedgerEntries => {
+            let dates = transactions::Column::TransactionDate;
+            if let Some(from) = plan.date_from {
+                condition = condition.add(dates.gte(from));
+            }
+            if let Some(to) = plan.date_to {
+                condition = condition.add(dates.lte(to));
+            }
+            for values in dimensions.values() {
+                condition = condition.add(Expr::exists(entry_dimension_query(values.clone())));
+            }
+            ledger_entries::Entity::find()
+                .select_only()
+                .join(
+                    JoinType::InnerJoin,
+                    ledger_entries::Relation::Transactions.def(),
+                )
+                .join(
+                    JoinType::InnerJoin,
+                    ledger_entries::Relation::ChartOfAccounts.def(),
+                )
+                .join(
+                    JoinType::InnerJoin,
+                    transactions::Relation::FiscalPeriods.def(),
+                )
+                .filter(transactions::Column::OrganizationId.eq(organization_id))
+                .filter(transactions::Column::Status.eq(TransactionStatus::Posted))
+                .filter(condition)
+                .into_query()
+        }
+        ReportDataset::BudgetVsActual => {
+            let starts = fiscal_periods::Column::StartDate;
+            if let Some(from) = plan.date_from {
+                condition = condition.add(starts.gte(from));
+            }
+            if let Some(to) = plan.date_to {
+                condition = condition.add(starts.lte(to));
+            }
+            for values in dimensions.values() {
+                condition = condition.add(Expr::exists(
+                    Query::select()
+                        .expr(Expr::val(1))
+                        .from(budget_line_dimensions::Entity)
+                        .and_where(
+                            Expr::col((
+                                budget_line_dimensions::Entity,
+                                budget_line_dimensions::Column::BudgetLineId,
+                            ))
+                            .equals((budget_lines::Entity, budget_lines::Column::Id)),
+                        )
+                        .and_where(
+                            budget_line_dimensions::Column::DimensionValueId.is_in(values.clone()),
+                        )
+                        .to_owned(),
+                ));
+            }
+            budget_lines::Entity::find()
+                .select_only()
+                .join(JoinType::InnerJoin, budget_lines::Relation::Budgets.def())
+                .join(
+                    JoinType::InnerJoin,
+                    budget_lines::Relation::ChartOfAccounts.def(),
+                )
+                .join(
+                    JoinType::InnerJoin,
+                    budget_lines::Relation::FiscalPeriods.def(),
+                )
+                .filter(budgets::Column::OrganizationId.eq(organization_id))
+                .filter(condition)
+                .into_query()
+        }
+    };
+    for field in fields {
+        query.expr_as(
+            custom_field_expr(field, dimensions),
+            Alias::new(field.name()),
+        );
+    }
+    query
+}
+
+/// The column expression of a dataset field.
+fn custom_field_expr(field: ReportField, dimensions: &BTreeMap<Uuid, Vec<Uuid>>) -> SimpleExpr {
+    let text = || Alias::new("text");
+    let debit = || Expr::col((ledger_entries::Entity, ledger_entries::Column::Debit));
+    let credit = || Expr::col((ledger_entries::Entity, ledger_entries::Column::Credit));
+    match field {
+        ReportField::AccountCode => {
+            Expr::col((chart_of_accounts::Entity, chart_of_accounts::Column::Code)).into()
+        }
+        ReportField::AccountName => {
+            Expr::col((chart_of_accounts::Entity, chart_of_accounts::Column::Name)).into()
+        }
+        ReportField::AccountType => Expr::col((
+            chart_of_accounts::Entity,
+            chart_of_accounts::Column::AccountType,
+        ))
+        .cast_as(text()),
+        ReportField::PeriodName => {
+            Expr::col((fiscal_periods::Entity, fiscal_periods::Column::Name)).into()
+        }
+        ReportField::TransactionDate => {
+            Expr::col((transactions::Entity, transactions::Column::TransactionDate)).into()
+        }
+        ReportField::TransactionType => {
+            Expr::col((transactions::Entity, transactions::Column::TransactionType)).cast_as(text())
+        }
+        ReportField::ReferenceNumber => {
+            Expr::col((transactions::Entity, transactions::Column::ReferenceNumber)).into()
+        }
+        ReportField::Description => {
+            Expr::col((transactions::Entity, transactions::Column::Description)).into()
+        }
+        ReportField::Currency => Expr::col((
+            ledger_entries::Entity,
+            ledger_entries::Column::SourceCurrency,
+        ))
+        .into(),
+        ReportField::Debit => debit().into(),
+        ReportField::Credit => credit().into(),
+        ReportField::Amount => debit().sub(credit()),
+        ReportField::BudgetName => Expr::col((budgets::Entity, budgets::Column::Name)).into(),
+        ReportField::Budgeted => {
+            Expr::col((budget_lines::Entity, budget_lines::Column::Amount)).into()
+        }
+        ReportField::Actual | ReportField::Variance => budget_line_actual(dimensions),
+    }
+}
+
+/// A budget line's posted actual for its account and period, in the
+/// account's normal direction, counting only entries with the filter's
+/// dimension values.
+fn budget_line_actual(dimensions: &BTreeMap<Uuid, Vec<Uuid>>) -> SimpleExpr {
+    let debit = || Expr::col((ledger_entries::Entity, ledger_entries::Column::Debit));
+    let credit = || Expr::col((ledger_entries::Entity, ledger_entries::Column::Credit));
+    let transaction_date =
+        || Expr::col((transactions::Entity, transactions::Column::TransactionDate));
+    let signed: SimpleExpr = Expr::case(
+        chart_of_accounts::Column::AccountType.is_in([AccountType::Asset, AccountType::Expense]),
+        debit().sub(credit()),
+    )
+    .finally(credit().sub(debit()))
+    .into();
+
+    let mut entries = Query::select();
+    entries
+        .expr(Func::coalesce([
+            SimpleExpr::from(Func::sum(signed)),
+            Expr::val(Decimal::ZERO).into(),
+        ]))
+        .from(ledger_entries::Entity)
+        .inner_join(
+            transactions::Entity,
+            Expr::col((transactions::Entity, transactions::Column::Id)).equals((
+                ledger_entries::Entity,
+                ledger_entries::Column::TransactionId,
+            )),
+        )
+        .and_where(
+            Expr::col((ledger_entries::Entity, ledger_entries::Column::AccountId))
+                .equals((budget_lines::Entity, budget_lines::Column::AccountId)),
+        )
+        .and_where(
+            Expr::col((transactions::Entity, transactions::Column::OrganizationId))
+                .equals((budgets::Entity, budgets::Column::OrganizationId)),
+        )
+        .and_where(transactions::Column::Status.eq(TransactionStatus::Posted))
+        .and_where(transaction_date().gte(Expr::col((
+            fiscal_periods::Entity,
+            fiscal_periods::Column::StartDate,
+        ))))
+        .and_where(transaction_date().lte(Expr::col((
+            fiscal_periods::Entity,
+            fiscal_periods::Column::EndDate,
+        ))));
+    for values in dimensions.values() {
+        entries.and_where(Expr::exists(entry_dimension_query(values.clone())));
+    }
+    SimpleExpr::SubQuery(None, Box::new(entries.into_sub_query_statement()))
+}
+
+/// Reads one custom report row from its positional `c<i>` columns.
+fn read_custom_report_row(row: &QueryResult, plan: &ReportPlan) -> Result<Vec<ReportValue>, DbErr> {
+    let mut values = Vec::with_capacity(plan.fields.len() + 1);
+    for (i, field) in plan.fields.iter().enumerate() {
+        let column = format!("c{i}");
+        values.push(match field.kind() {
+            FieldKind::Text => row
+                .try_get::<Option<String>>("", &column)?
+                .map_or(ReportValue::Null, ReportValue::Text),
+            FieldKind::Date => row
+                .try_get::<Option<NaiveDate>>("", &column)?
+                .map_or(ReportValue::Null, ReportValue::Date),
+            FieldKind::Number => row
+                .try_get::<Option<Decimal>>("", &column)?
+                .map_or(ReportValue::Null, ReportValue::Number),
+        });
+    }
+    if let Some((_, field)) = plan.aggregate {
+        let column = format!("c{}", plan.fields.len());
+        values.push(if field.is_some() {
+            row.try_get::<Option<Decimal>>("", &column)?
+                .map_or(ReportValue::Null, ReportValue::Number)
+        } else {
+            ReportValue::Number(Decimal::from(row.try_get::<i64>("", &column)?))
+        });
+    }
+    Ok(values)
+}
+
+/// Selects the dimension tags of the outer query's ledger entry.
+fn entry_dimension_query_base() -> SelectStatement {
+    Query::select()
+        .expr(Expr::val(1))
+        .from(entry_dimensions::Entity)
+        .and_where(
+            Expr::col((
+                entry_dimensions::Entity,
+                entry_dimensions::Column::LedgerEntryId,
+            ))
+            .equals((ledger_entries::Entity, ledger_entries::Column::Id)),
+        )
+        .to_owned()
+}
+
+/// Selects the outer query's ledger entry tags with any of `values`.
+fn entry_dimension_query(values: Vec<Uuid>) -> SelectStatement {
+    entry_dimension_query_base()
+        .and_where(
+            Expr::col((
+                entry_dimensions::Entity,
+                entry_dimensions::Column::DimensionValueId,
+            ))
+            .is_in(values),
+        )
+        .to_owned()
+}
+
 /// Calculates balance based on account type.
 ///
 /// - Asset/Expense (debit-normal): balance = debit - credit
@@ -772,6 +2230,25 @@ pub fn calculate_balance(
     }
 }
 
+/// Builds an account's report balance from posted totals.
+fn account_balance(account: chart_of_accounts::Model, totals: &AccountTotals) -> AccountBalance {
+    let (total_debit, total_credit) = totals
+        .get(&account.id)
+        .copied()
+        .unwrap_or((Decimal::ZERO, Decimal::ZERO));
+
+    AccountBalance {
+        balance: calculate_balance(&account.account_type, total_debit, total_credit),
+        account_id: account.id,
+        code: account.code,
+        name: account.name,
+        account_type: account.account_type,
+        account_subtype: account.account_subtype,
+        total_debit,
+        total_credit,
+    }
+}
+
 /// Determines if an account type is debit-normal.
 #[must_use]
 pub fn is_debit_normal(account_type: &AccountType) -> bool {
@@ -0,0 +1,237 @@
+//! Saved custom report definition repository.
+//!
+//! Definitions are validated with `zeltra_core::reports` before they are
+//! written; running one is [`ReportRepository::run_custom_report`].
+//!
+//! [`ReportRepository::run_custom_report`]: super::report::ReportRepository::run_custom_report
+
+use chrono::Utc;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, DatabaseConnection, DbErr, EntityTrait, QueryFilter, QueryOrder,
+    Set, prelude::DateTimeWithTimeZone,
+};
+use uuid::Uuid;
+use zeltra_core::reports::ReportDefinition;
+use zeltra_core::settings::FieldError;
+
+use crate::entities::report_definitions;
+
+/// Error types for custom report definition operations.
+#[derive(Debug, thiserror::Error)]
+pub enum ReportDefinitionError {
+    /// Definition not found in the organization.
+    #[error("Report definition not found: {0}")]
+    NotFound(Uuid),
+
+    /// The definition failed validation.
+    #[error("Invalid report definition: {} problem(s)", .0.len())]
+    Invalid(Vec<FieldError>),
+
+    /// Another definition of the organization has this name.
+    #[error("A report definition named '{0}' already exists")]
+    DuplicateName(String),
+
+    /// Database error.
+    #[error("Database error: {0}")]
+    Database(#[from] DbErr),
+}
+
+/// A stored custom report definition.
+#[derive(Debug, Clone)]
+pub struct SavedReportDefinition {
+    /// Definition ID.
+    pub id: Uuid,
+    /// The definition.
+    pub definition: ReportDefinition,
+    /// Who created it.
+    pub created_by: Option<Uuid>,
+    /// When it was created.
+    pub created_at: DateTimeWithTimeZone,
+    /// When it was last changed.
+    pub updated_at: DateTimeWithTimeZone,
+}
+
+impl TryFrom<report_definitions::Model> for SavedReportDefinition {
+    type Error = DbErr;
+
+    fn try_from(model: report_definitions::Model) -> Result<Self, Self::Error> {
+        let definition = serde_json::from_value(model.definition).map_err(|e| {
+            DbErr::Custom(format!("Unreadable report definition {}: {e}", model.id))
+        })?;
+        Ok(Self {
+            id: model.id,
+            definition,
+            created_by: model.created_by,
+            created_at: model.created_at,
+            updated_at: model.updated_at,
+        })
+    }
+}
+
+/// Repository for saved custom report definitions.
+#[derive(Debug, Clone)]
+pub struct ReportDefinitionRepository {
+    db: DatabaseConnection,
+}
+
+impl ReportDefinitionRepository {
+    /// Creates a new report definition repository.
+    #[must_use]
+    pub const fn new(db: DatabaseConnection) -> Self {
+        Self { db }
+    }
+
+    /// Lists an organization's definitions by name.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the query fails or a stored definition cannot be
+    /// read.
+    pub async fn list(
+        &self,
+        organization_id: Uuid,
+    ) -> Result<Vec<SavedReportDefinition>, ReportDefinitionError> {
+        let models = report_definitions::Entity::find()
+            .filter(report_definitions::Column::OrganizationId.eq(organization_id))
+            .order_by_asc(report_definitions::Column::Name)
+            .all(&self.db)
+            .await?;
+        Ok(models
+            .into_iter()
+            .map(SavedReportDefinition::try_from)
+            .collect::<Result<_, _>>()?)
+    }
+
+    /// Gets one definition.
+    ///
+    /// # Errors
+    ///
+    /// Returns `NotFound` if the organization has no such definition, or an
+    /// error if the query fails.
+    pub async fn get(
+        &self,
+        organization_id: Uuid,
+        definition_id: Uuid,
+    ) -> Result<SavedReportDefinition, ReportDefinitionError> {
+        Ok(self
+            .find(organization_id, definition_id)
+            .await?
+            .try_into()?)
+    }
+
+    /// Validates and saves a new definition.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Invalid` with every problem found, `DuplicateName` if the
+    /// name is taken, or an error if the insert fails.
+    pub async fn create(
+        &self,
+        organization_id: Uuid,
+        definition: &ReportDefinition,
+        created_by: Uuid,
+    ) -> Result<SavedReportDefinition, ReportDefinitionError> {
+        let (name, value) = self.prepare(organization_id, None, definition).await?;
+        let now: DateTimeWithTimeZone = Utc::now().into();
+        let model = report_definitions::ActiveModel {
+            id: Set(Uuid::new_v4()),
+            organization_id: Set(organization_id),
+            name: Set(name),
+            definition: Set(value),
+            created_by: Set(Some(created_by)),
+            created_at: Set(now),
+            updated_at: Set(now),
+        }
+        .insert(&self.db)
+        .await?;
+        Ok(model.try_into()?)
+    }
+
+    /// Validates a definition and replaces a saved one with it.
+    ///
+    /// # Errors
+    ///
+    /// Returns `NotFound`, `Invalid`, `DuplicateName`, or an error if the
+    /// update fails.
+    pub async fn update(
+        &self,
+        organization_id: Uuid,
+        definition_id: Uuid,
+        definition: &ReportDefinition,
+    ) -> Result<SavedReportDefinition, ReportDefinitionError> {
+        let existing = self.find(organization_id, definition_id).await?;
+        let (name, value) = self
+            .prepare(organization_id, Some(definition_id), definition)
+            .await?;
+        let mut model: report_definitions::ActiveModel = existing.into();
+        model.name = Set(name);
+        model.definition = Set(value);
+        model.updated_at = Set(Utc::now().into());
+        Ok(model.update(&self.db).await?.try_into()?)
+    }
+
+    /// Deletes a definition.
+    ///
+    /// # Errors
+    ///
+    /// Returns `NotFound` if the organization has no such definition, or an
+    /// error if the delete fails.
+    pub async fn delete(
+        &self,
+        organization_id: Uuid,
+        definition_id: Uuid,
+    ) -> Result<(), ReportDefinitionError> {
+        let result = report_definitions::Entity::delete_many()
+            .filter(report_definitions::Column::OrganizationId.eq(organization_id))
+            .filter(report_definitions::Column::Id.eq(definition_id))
+            .exec(&self.db)
+            .await?;
+        if result.rows_affected == 0 {
+            return Err(ReportDefinitionError::NotFound(definition_id));
+        }
+        Ok(())
+    }
+
+    async fn find(
+        &self,
+        organization_id: Uuid,
+        definition_id: Uuid,
+    ) -> Result<report_definitions::Model, ReportDefinitionError> {
+        report_definitions::Entity::find_by_id(definition_id)
+            .filter(report_definitions::Column::OrganizationId.eq(organization_id))
+            .one(&self.db)
+            .await?
+            .ok_or(ReportDefinitionError::NotFound(definition_id))
+    }
+
+    /// Validates a definition and checks its name is free, returning the
+    /// trimmed name and the JSON to store.
+    async fn prepare(
+        &self,
+        organization_id: Uuid,
+        definition_id: Option<Uuid>,
+        definition: &ReportDefinition,
+    ) -> Result<(String, serde_json::Value), ReportDefinitionError> {
+        definition
+            .validate()
+            .map_err(ReportDefinitionError::Invalid)?;
+        let name = definition.name.trim().to_string();
+
+        let mut taken = report_definitions::Entity::find()
+            .filter(report_definitions::Column::OrganizationId.eq(organization_id))
+            .filter(report_definitions::Column::Name.eq(name.as_str()));
+        if let Some(id) = definition_id {
+            taken = taken.filter(report_definitions::Column::Id.ne(id));
+        }
+        if taken.one(&self.db).await?.is_some() {
+            return Err(ReportDefinitionError::DuplicateName(name));
+        }
+
+        let value = serde_json::to_value(ReportDefinition {
+            name: name.clone(),
+            ..definition.clone()
+        })
+        .map_err(|e| DbErr::Custom(format!("Failed to serialize report definition: {e}")))?;
+        Ok((name, value))
+    }
+}
@@ -0,0 +1,194 @@
+//! Financial statement mapping repository.
+//!
+//! Organizations use the default mapping of account subtypes to statement
+//! sections from `zeltra_core::reports` until they save their own.
+
+use chrono::Utc;
+use sea_orm::{
+    ActiveModelTrait, DatabaseConnection, DbErr, EntityTrait, Set, TransactionTrait,
+    prelude::DateTimeWithTimeZone,
+};
+use tracing::warn;
+use uuid::Uuid;
+use zeltra_core::reports::{AccountSubtype as CoreAccountSubtype, StatementMapping};
+use zeltra_core::settings::FieldError;
+
+use crate::entities::{organizations, report_mappings, sea_orm_active_enums::AccountSubtype};
+
+/// Error types for statement mapping operations.
+#[derive(Debug, thiserror::Error)]
+pub enum ReportMappingError {
+    /// Organization not found.
+    #[error("Organization not found: {0}")]
+    OrganizationNotFound(Uuid),
+
+    /// The mapping failed validation.
+    #[error("Invalid report mapping: {} problem(s)", .0.len())]
+    Invalid(Vec<FieldError>),
+
+    /// Database error.
+    #[error("Database error: {0}")]
+    Database(#[from] DbErr),
+}
+
+/// An organization's statement mapping.
+#[derive(Debug, Clone)]
+pub struct ResolvedReportMapping {
+    /// The mapping reports use.
+    pub mapping: StatementMapping,
+    /// Whether this is the default mapping rather than a saved one.
+    pub is_default: bool,
+    /// When the saved mapping was last changed.
+    pub updated_at: Option<DateTimeWithTimeZone>,
+    /// Who last changed the saved mapping.
+    pub updated_by: Option<Uuid>,
+}
+
+/// Repository for per-organization statement mappings.
+#[derive(Debug, Clone)]
+pub struct ReportMappingRepository {
+    db: DatabaseConnection,
+}
+
+impl ReportMappingRepository {
+    /// Creates a new report mapping repository.
+    #[must_use]
+    pub const fn new(db: DatabaseConnection) -> Self {
+        Self { db }
+    }
+
+    /// Gets the mapping an organization's reports use: its saved mapping,
+    /// or the default when none is saved.
+    ///
+    /// A saved mapping that no longer parses is logged and replaced by the
+    /// default.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    pub async fn resolve(&self, organization_id: Uuid) -> Result<ResolvedReportMapping, DbErr> {
+        let saved = report_mappings::Entity::find_by_id(organization_id)
+            .one(&self.db)
+            .await?;
+        let Some(saved) = saved else {
+            return Ok(ResolvedReportMapping {
+                mapping: StatementMapping::default(),
+                is_default: true,
+                updated_at: None,
+                updated_by: None,
+            });
+        };
+
+        match serde_json::from_value(saved.mapping) {
+            Ok(mapping) => Ok(ResolvedReportMapping {
+                mapping,
+                is_default: false,
+                updated_at: Some(saved.updated_at),
+                updated_by: saved.updated_by,
+            }),
+            Err(e) => {
+                warn!(%organization_id, error = %e, "Saved report mapping is unreadable, using the default");
+                Ok(ResolvedReportMapping {
+                    mapping: StatementMapping::default(),
+                    is_default: true,
+                    updated_at: None,
+                    updated_by: None,
+                })
+            }
+        }
+    }
+
+    /// Validates and saves an organization's mapping, replacing any saved
+    /// one.
+    ///
+    /// Also touches the organization, so cached reports are not served with
+    /// the old grouping.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Invalid` with every problem found if the mapping fails
+    /// validation, `OrganizationNotFound` if the organization does not
+    /// exist, or an error if a database operation fails.
+    pub async fn save(
+        &self,
+        organization_id: Uuid,
+        mapping: &StatementMapping,
+        updated_by: Uuid,
+    ) -> Result<ResolvedReportMapping, ReportMappingError> {
+        mapping.validate().map_err(ReportMappingError::Invalid)?;
+        let value = serde_json::to_value(mapping)
+            .map_err(|e| DbErr::Custom(format!("Failed to serialize report mapping: {e}")))?;
+
+        let txn = self.db.begin().await?;
+        let org = organizations::Entity::find_by_id(organization_id)
+            .one(&txn)
+            .await?
+            .ok_or(ReportMappingError::OrganizationNotFound(organization_id))?;
+
+        let now: DateTimeWithTimeZone = Utc::now().into();
+        let saved = report_mappings::ActiveModel {
+            organization_id: Set(organization_id),
+            mapping: Set(value),
+            updated_by: Set(Some(updated_by)),
+            updated_at: Set(now),
+        };
+        if report_mappings::Entity::find_by_id(organization_id)
+            .one(&txn)
+            .await?
+            .is_some()
+        {
+            saved.update(&txn).await?;
+        } else {
+            saved.insert(&txn).await?;
+        }
+
+        let mut org: organizations::ActiveModel = org.into();
+        org.updated_at = Set(now);
+        org.update(&txn).await?;
+        txn.commit().await?;
+
+        Ok(ResolvedReportMapping {
+            mapping: mapping.clone(),
+            is_default: false,
+            updated_at: Some(now),
+            updated_by: Some(updated_by),
+        })
+    }
+}
+
+/// The core subtype for a stored account subtype.
+///
+/// Exhaustive on purpose: a subtype added to the database enum does not
+/// compile until the statement mapping knows about it.
+#[must_use]
+pub const fn core_account_subtype(subtype: &AccountSubtype) -> CoreAccountSubtype {
+    match subtype {
+        AccountSubtype::Cash => CoreAccountSubtype::Cash,
+        AccountSubtype::Bank => CoreAccountSubtype::Bank,
+        AccountSubtype::AccountsReceivable => CoreAccountSubtype::AccountsReceivable,
+        AccountSubtype::Inventory => CoreAccountSubtype::Inventory,
+        AccountSubtype::Prepaid => CoreAccountSubtype::Prepaid,
+        AccountSubtype::FixedAsset => CoreAccountSubtype::FixedAsset,
+        AccountSubtype::AccumulatedDepreciation => CoreAccountSubtype::AccumulatedDepreciation,
+        AccountSubtype::OtherAsset => CoreAccountSubtype::OtherAsset,
+        AccountSubtype::AccountsPayable => CoreAccountSubtype::AccountsPayable,
+        AccountSubtype::CreditCard => CoreAccountSubtype::CreditCard,
+        AccountSubtype::AccruedLiability => CoreAccountSubtype::AccruedLiability,
+        AccountSubtype::ShortTermDebt => CoreAccountSubtype::ShortTermDebt,
+        AccountSubtype::LongTermDebt => CoreAccountSubtype::LongTermDebt,
+        AccountSubtype::OtherLiability => CoreAccountSubtype::OtherLiability,
+        AccountSubtype::OwnerEquity => CoreAccountSubtype::OwnerEquity,
+        AccountSubtype::RetainedEarnings => CoreAccountSubtype::RetainedEarnings,
+        AccountSubtype::CommonStock => CoreAccountSubtype::CommonStock,
+        AccountSubtype::OtherEquity => CoreAccountSubtype::OtherEquity,
+        AccountSubtype::OperatingRevenue => CoreAccountSubtype::OperatingRevenue,
+        AccountSubtype::OtherRevenue => CoreAccountSubtype::OtherRevenue,
+        AccountSubtype::CostOfGoodsSold => CoreAccountSubtype::CostOfGoodsSold,
+        AccountSubtype::OperatingExpense => CoreAccountSubtype::OperatingExpense,
+        AccountSubtype::PayrollExpense => CoreAccountSubtype::PayrollExpense,
+        AccountSubtype::DepreciationExpense => CoreAccountSubtype::DepreciationExpense,
+        AccountSubtype::InterestExpense => CoreAccountSubtype::InterestExpense,
+        AccountSubtype::TaxExpense => CoreAccountSubtype::TaxExpense,
+        AccountSubtype::OtherExpense => CoreAccountSubtype::OtherExpense,
+    }
+}
@@ -48,6 +48,9 @@ pub struct HistoricalAccountData {
     pub account_type: String,
     /// Monthly amounts in the base period.
     pub monthly_amounts: Vec<Decimal>,
+    /// Dimension value of this slice; `None` for the whole account or the
+    /// untagged remainder of a split account.
+    pub dimension_value_id: Option<Uuid>,
 }
 
 /// Simulation repository for historical data queries.
@@ -65,6 +68,12 @@ impl SimulationRepository {
 
     /// Queries historical data for simulation.
     ///
+    /// Accounts named in `dimension_scopes` (account ID, dimension value ID)
+    /// are split into one slice per scoped dimension value plus an untagged
+    /// remainder, sharing the account's months so the slices add up to the
+    /// account total. An entry tagged with several scoped values counts
+    /// toward the first one listed. Other accounts stay whole.
+    ///
     /// Requirements: 10.1, 10.2, 10.4
     ///
     /// # Errors
@@ -72,12 +81,14 @@ impl SimulationRepository {
     /// Returns an error if:
     /// - Base period is invalid (start > end)
     /// - Database query fails
+    #[allow(clippy::too_many_lines)]
     pub async fn query_historical_data(
         &self,
         organization_id: Uuid,
         base_period_start: NaiveDate,
         base_period_end: NaiveDate,
         dimension_filters: &[Uuid],
+        dimension_scopes: &[(Uuid, Uuid)],
     ) -> Result<Vec<HistoricalAccountData>, SimulationRepoError> {
         // Validate base period
         if base_period_start > base_period_end {
@@ -124,6 +135,7 @@ impl SimulationRepository {
                     account_name: a.name,
                     account_type: account_type_to_string(&a.account_type),
                     monthly_amounts: vec![],
+                    dimension_value_id: None,
                 })
                 .collect());
         }
@@ -155,13 +167,23 @@ impl SimulationRepository {
                 .filter_entries_by_dimensions(entries, dimension_filters)
                 .await?;
 
-            // Group by month and calculate totals (Requirement 10.2)
-            let mut monthly_totals: HashMap<(i32, u32), Decimal> = HashMap::new();
+            let mut scoped_values: Vec<Uuid> = Vec::new();
+            for (account_id, dimension_value_id) in dimension_scopes {
+                if *account_id == account.id && !scoped_values.contains(dimension_value_id) {
+                    scoped_values.push(*dimension_value_id);
+                }
+            }
+            let entry_slices = self
+                .scoped_entry_slices(&filtered_entries, &scoped_values)
+                .await?;
+
+            // Group by slice and month and calculate totals (Requirement 10.2)
+            let mut monthly_totals: HashMap<(Option<Uuid>, (i32, u32)), Decimal> = HashMap::new();
+            let mut months: Vec<(i32, u32)> = Vec::new();
 
             for entry in filtered_entries {
                 if let Some(tx_date) = tx_date_map.get(&entry.transaction_id) {
-                    let year = tx_date.year();
-                    let month = tx_date.month();
+                    let month = (tx_date.year(), tx_date.month());
 
                     // Calculate amount based on account type
                     let amount = match account.account_type {
@@ -169,31 +191,77 @@ impl SimulationRepository {
                         _ => entry.debit - entry.credit,
                     };
 
-                    *monthly_totals.entry((year, month)).or_insert(Decimal::ZERO) += amount;
+                    let slice = entry_slices.get(&entry.id).copied();
+                    *monthly_totals
+                        .entry((slice, month))
+                        .or_insert(Decimal::ZERO) += amount;
+                    months.push(month);
                 }
             }
 
             // Convert to sorted monthly amounts
-            let mut months: Vec<(i32, u32)> = monthly_totals.keys().copied().collect();
             months.sort_unstable();
+            months.dedup();
 
-            let monthly_amounts: Vec<Decimal> = months
-                .iter()
-                .map(|key| monthly_totals.get(key).copied().unwrap_or(Decimal::ZERO))
-                .collect();
+            // Scoped slices first, then the remainder (the whole account when unscoped)
+            for slice in scoped_values.iter().copied().map(Some).chain([None]) {
+                let monthly_amounts: Vec<Decimal> = months
+                    .iter()
+                    .map(|month| {
+                        monthly_totals
+                            .get(&(slice, *month))
+                            .copied()
+                            .unwrap_or(Decimal::ZERO)
+                    })
+                    .collect();
 
-            result.push(HistoricalAccountData {
-                account_id: account.id,
-                account_code: account.code,
-                account_name: account.name,
-                account_type: account_type_to_string(&account.account_type),
-                monthly_amounts,
-            });
+                result.push(HistoricalAccountData {
+                    account_id: account.id,
+                    account_code: account.code.clone(),
+                    account_name: account.name.clone(),
+                    account_type: account_type_to_string(&account.account_type),
+                    monthly_amounts,
+                    dimension_value_id: slice,
+                });
+            }
         }
 
         Ok(result)
     }
 
+    /// Maps each entry tagged with a scoped dimension value to that value.
+    ///
+    /// Untagged entries are left out. Earlier values in `scoped_values` win
+    /// when an entry carries more than one.
+    async fn scoped_entry_slices(
+        &self,
+        entries: &[crate::entities::ledger_entries::Model],
+        scoped_values: &[Uuid],
+    ) -> Result<HashMap<Uuid, Uuid>, SimulationRepoError> {
+        if scoped_values.is_empty() || entries.is_empty() {
+            return Ok(HashMap::new());
+        }
+
+        let tags = entry_dimensions::Entity::find()
+            .filter(
+                entry_dimensions::Column::LedgerEntryId
+                    .is_in(entries.iter().map(|e| e.id).collect::<Vec<_>>()),
+            )
+            .filter(entry_dimensions::Column::DimensionValueId.is_in(scoped_values.to_vec()))
+            .all(&self.db)
+            .await?;
+
+        let rank = |value: &Uuid| scoped_values.iter().position(|v| v == value);
+        let mut slices: HashMap<Uuid, Uuid> = HashMap::new();
+        for tag in tags {
+            let current = slices.get(&tag.ledger_entry_id);
+            if current.is_none_or(|value| rank(&tag.dimension_value_id) < rank(value)) {
+                slices.insert(tag.ledger_entry_id, tag.dimension_value_id);
+            }
+        }
+        Ok(slices)
+    }
+
     /// Filters ledger entries by dimension values.
     async fn filter_entries_by_dimensions(
         &self,
@@ -32,6 +32,7 @@ mod tests {
             expense_growth_rate: expense_rate,
             account_adjustments: HashMap::new(),
             dimension_filters: vec![],
+            dimension_adjustments: vec![],
         }
     }
 
@@ -43,9 +44,10 @@ mod tests {
         HistoricalAccountData {
             account_id: Uuid::new_v4(),
             account_code: format!("{}-001", account_type.to_uppercase()),
-            account_name: format!("Test {} Account", account_type),
+            account_name: format!("Test {account_type} Account"),
             account_type: account_type.to_string(),
             monthly_amounts,
+            dimension_value_id: None,
         }
     }
 
@@ -181,7 +183,7 @@ mod tests {
             let result = SimulationEngine::run(&data, &params);
 
             prop_assert_eq!(
-                result.projections.len() as u32,
+                u32::try_from(result.projections.len()).unwrap(),
                 months,
                 "Should have one projection per month"
             );
@@ -404,6 +406,7 @@ mod tests {
             account_name: "Revenue".to_string(),
             account_type: "revenue".to_string(),
             monthly_amounts: vec![dec!(1000)],
+            dimension_value_id: None,
         }];
 
         let result = SimulationEngine::run(&data, &params);
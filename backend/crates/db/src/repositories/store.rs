@@ -0,0 +1,625 @@
+//! Object-safe traits over the repositories used by route handlers.
+//!
+//! Handlers resolve repositories through [`Stores`] instead of constructing
+//! them from a connection, so tests can swap in fakes and exercise handler
+//! logic without a database. Each trait mirrors the inherent methods of its
+//! repository; the Postgres implementations simply delegate to them.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use sea_orm::{DatabaseConnection, DbErr};
+use uuid::Uuid;
+use zeltra_core::currency::RateLookupPolicy;
+use zeltra_core::workflow::{ReviewClaim, VoidReasonCode, WorkflowError};
+use zeltra_shared::types::{OrganizationId, Sort, TransactionId};
+
+use crate::entities::{organization_users, organizations, transactions};
+
+use super::exchange_rate::{ExchangeRateError, ExchangeRateLookup, ExchangeRateRepository};
+use super::organization::OrganizationRepository;
+use super::payment::{
+    AppliedPayment, ApplyPaymentInput, PaymentError, PaymentRepository, Settlement,
+};
+use super::transaction::{
+    CreateLedgerEntryInput, CreateTransactionInput, EntryReferences, TransactionError,
+    TransactionFilter, TransactionRepository, TransactionSummary, TransactionWithEntries,
+    UpdateTransactionInput,
+};
+use super::workflow::{
+    ApprovalOutcome, BulkApproveResult, BulkVoidResult, PendingSortField, PendingTransaction,
+    TransactionActions, TransactionHistory, VoidResult, WorkflowRepository,
+};
+
+/// Transaction storage, implemented by [`TransactionRepository`].
+#[async_trait]
+pub trait TransactionStore: Send + Sync {
+    /// See [`TransactionRepository::list_transactions`].
+    async fn list_transactions(
+        &self,
+        organization_id: OrganizationId,
+        filter: TransactionFilter,
+    ) -> Result<Vec<transactions::Model>, TransactionError>;
+
+    /// See [`TransactionRepository::summarize`].
+    async fn summarize(
+        &self,
+        organization_id: OrganizationId,
+        filter: TransactionFilter,
+    ) -> Result<TransactionSummary, TransactionError>;
+
+    /// See [`TransactionRepository::create_transaction`].
+    async fn create_transaction(
+        &self,
+        input: CreateTransactionInput,
+    ) -> Result<TransactionWithEntries, TransactionError>;
+
+    /// See [`TransactionRepository::find_possible_duplicates`].
+    async fn find_possible_duplicates(
+        &self,
+        organization_id: OrganizationId,
+        transaction_date: NaiveDate,
+        entries: &[CreateLedgerEntryInput],
+        window_days: u32,
+    ) -> Result<Vec<Uuid>, TransactionError>;
+
+    /// See [`TransactionRepository::get_transaction`].
+    async fn get_transaction(
+        &self,
+        organization_id: OrganizationId,
+        transaction_id: TransactionId,
+    ) -> Result<TransactionWithEntries, TransactionError>;
+
+    /// See [`TransactionRepository::update_transaction`].
+    async fn update_transaction(
+        &self,
+        organization_id: OrganizationId,
+        transaction_id: TransactionId,
+        input: UpdateTransactionInput,
+    ) -> Result<transactions::Model, TransactionError>;
+
+    /// See [`TransactionRepository::delete_transaction`].
+    async fn delete_transaction(
+        &self,
+        organization_id: OrganizationId,
+        transaction_id: TransactionId,
+    ) -> Result<(), TransactionError>;
+
+    /// See [`TransactionRepository::currency_decimal_places`].
+    async fn currency_decimal_places(
+        &self,
+        codes: &[&str],
+    ) -> Result<HashMap<String, u32>, TransactionError>;
+}
+
+#[async_trait]
+impl TransactionStore for TransactionRepository {
+    async fn list_transactions(
+        &self,
+        organization_id: OrganizationId,
+        filter: TransactionFilter,
+    ) -> Result<Vec<transactions::Model>, TransactionError> {
+        Self::list_transactions(self, organization_id, filter).await
+    }
+
+    async fn summarize(
+        &self,
+        organization_id: OrganizationId,
+        filter: TransactionFilter,
+    ) -> Result<TransactionSummary, TransactionError> {
+        Self::summarize(self, organization_id, filter).await
+    }
+
+    async fn create_transaction(
+        &self,
+        input: CreateTransactionInput,
+    ) -> Result<TransactionWithEntries, TransactionError> {
+        Self::create_transaction(self, input).await
+    }
+
+    async fn find_possible_duplicates(
+        &self,
+        organization_id: OrganizationId,
+        transaction_date: NaiveDate,
+        entries: &[CreateLedgerEntryInput],
+        window_days: u32,
+    ) -> Result<Vec<Uuid>, TransactionError> {
+        Self::find_possible_duplicates(
+            self,
+            organization_id,
+            transaction_date,
+            entries,
+            window_days,
+        )
+        .await
+    }
+
+    async fn get_transaction(
+        &self,
+        organization_id: OrganizationId,
+        transaction_id: TransactionId,
+    ) -> Result<TransactionWithEntries, TransactionError> {
+        Self::get_transaction(self, organization_id, transaction_id).await
+    }
+
+    async fn update_transaction(
+        &self,
+        organization_id: OrganizationId,
+        transaction_id: TransactionId,
+        input: UpdateTransactionInput,
+    ) -> Result<transactions::Model, TransactionError> {
+        Self::update_transaction(self, organization_id, transaction_id, input).await
+    }
+
+    async fn delete_transaction(
+        &self,
+        organization_id: OrganizationId,
+        transaction_id: TransactionId,
+    ) -> Result<(), TransactionError> {
+        Self::delete_transaction(self, organization_id, transaction_id).await
+    }
+
+    async fn currency_decimal_places(
+        &self,
+        codes: &[&str],
+    ) -> Result<HashMap<String, u32>, TransactionError> {
+        Self::currency_decimal_places(self, codes).await
+    }
+}
+
+/// Accounts and dimension values referenced by new entries, implemented by
+/// [`TransactionRepository`].
+#[async_trait]
+pub trait EntryReferenceStore: Send + Sync {
+    /// See [`TransactionRepository::find_entry_references`].
+    async fn find_entry_references(
+        &self,
+        organization_id: OrganizationId,
+        account_ids: &[Uuid],
+        dimension_value_ids: &[Uuid],
+    ) -> Result<EntryReferences, TransactionError>;
+}
+
+#[async_trait]
+impl EntryReferenceStore for TransactionRepository {
+    async fn find_entry_references(
+        &self,
+        organization_id: OrganizationId,
+        account_ids: &[Uuid],
+        dimension_value_ids: &[Uuid],
+    ) -> Result<EntryReferences, TransactionError> {
+        Self::find_entry_references(self, organization_id, account_ids, dimension_value_ids).await
+    }
+}
+
+/// Approval workflow storage, implemented by [`WorkflowRepository`].
+#[async_trait]
+pub trait WorkflowStore: Send + Sync {
+    /// See [`WorkflowRepository::submit_transaction`].
+    async fn submit_transaction(
+        &self,
+        organization_id: OrganizationId,
+        transaction_id: TransactionId,
+        submitted_by: Uuid,
+    ) -> Result<transactions::Model, WorkflowError>;
+
+    /// See [`WorkflowRepository::approve_transaction`].
+    async fn approve_transaction(
+        &self,
+        organization_id: OrganizationId,
+        transaction_id: TransactionId,
+        approved_by: Uuid,
+        approval_notes: Option<String>,
+    ) -> Result<ApprovalOutcome, WorkflowError>;
+
+    /// See [`WorkflowRepository::reject_transaction`].
+    async fn reject_transaction(
+        &self,
+        organization_id: OrganizationId,
+        transaction_id: TransactionId,
+        rejected_by: Uuid,
+        rejection_reason: String,
+    ) -> Result<transactions::Model, WorkflowError>;
+
+    /// See [`WorkflowRepository::claim_transaction`].
+    async fn claim_transaction(
+        &self,
+        organization_id: OrganizationId,
+        transaction_id: TransactionId,
+        user_id: Uuid,
+    ) -> Result<ReviewClaim, WorkflowError>;
+
+    /// See [`WorkflowRepository::release_claim`].
+    async fn release_claim(
+        &self,
+        organization_id: OrganizationId,
+        transaction_id: TransactionId,
+        user_id: Uuid,
+    ) -> Result<(), WorkflowError>;
+
+    /// See [`WorkflowRepository::post_transaction`].
+    async fn post_transaction(
+        &self,
+        organization_id: OrganizationId,
+        transaction_id: TransactionId,
+        posted_by: Uuid,
+    ) -> Result<transactions::Model, WorkflowError>;
+
+    /// See [`WorkflowRepository::void_transaction`].
+    async fn void_transaction(
+        &self,
+        organization_id: OrganizationId,
+        transaction_id: TransactionId,
+        voided_by: Uuid,
+        void_reason_code: VoidReasonCode,
+        void_reason: String,
+    ) -> Result<VoidResult, WorkflowError>;
+
+    /// See [`WorkflowRepository::get_available_actions`].
+    async fn get_available_actions(
+        &self,
+        organization_id: OrganizationId,
+        transaction_id: TransactionId,
+        user_id: Uuid,
+    ) -> Result<TransactionActions, WorkflowError>;
+
+    /// See [`WorkflowRepository::get_status_history`].
+    async fn get_status_history(
+        &self,
+        organization_id: OrganizationId,
+        transaction_id: TransactionId,
+    ) -> Result<TransactionHistory, WorkflowError>;
+
+    /// See [`WorkflowRepository::get_pending_transactions`].
+    async fn get_pending_transactions(
+        &self,
+        organization_id: OrganizationId,
+        user_id: Uuid,
+        min_days: Option<u32>,
+        sort: Option<Sort<PendingSortField>>,
+    ) -> Result<Vec<PendingTransaction>, WorkflowError>;
+
+    /// See [`WorkflowRepository::bulk_approve`].
+    async fn bulk_approve(
+        &self,
+        organization_id: OrganizationId,
+        transaction_ids: Vec<TransactionId>,
+        approved_by: Uuid,
+        approval_notes: Option<String>,
+    ) -> Result<BulkApproveResult, WorkflowError>;
+
+    /// See [`WorkflowRepository::bulk_void`].
+    async fn bulk_void(
+        &self,
+        organization_id: OrganizationId,
+        transaction_ids: Vec<TransactionId>,
+        voided_by: Uuid,
+        void_reason_code: VoidReasonCode,
+        void_reason: String,
+    ) -> Result<BulkVoidResult, WorkflowError>;
+}
+
+#[async_trait]
+impl WorkflowStore for WorkflowRepository {
+    async fn submit_transaction(
+        &self,
+        organization_id: OrganizationId,
+        transaction_id: TransactionId,
+        submitted_by: Uuid,
+    ) -> Result<transactions::Model, WorkflowError> {
+        Self::submit_transaction(self, organization_id, transaction_id, submitted_by).await
+    }
+
+    async fn approve_transaction(
+        &self,
+        organization_id: OrganizationId,
+        transaction_id: TransactionId,
+        approved_by: Uuid,
+        approval_notes: Option<String>,
+    ) -> Result<ApprovalOutcome, WorkflowError> {
+        Self::approve_transaction(
+            self,
+            organization_id,
+            transaction_id,
+            approved_by,
+            approval_notes,
+        )
+        .await
+    }
+
+    async fn reject_transaction(
+        &self,
+        organization_id: OrganizationId,
+        transaction_id: TransactionId,
+        rejected_by: Uuid,
+        rejection_reason: String,
+    ) -> Result<transactions::Model, WorkflowError> {
+        Self::reject_transaction(
+            self,
+            organization_id,
+            transaction_id,
+            rejected_by,
+            rejection_reason,
+        )
+        .await
+    }
+
+    async fn claim_transaction(
+        &self,
+        organization_id: OrganizationId,
+        transaction_id: TransactionId,
+        user_id: Uuid,
+    ) -> Result<ReviewClaim, WorkflowError> {
+        Self::claim_transaction(self, organization_id, transaction_id, user_id).await
+    }
+
+    async fn release_claim(
+        &self,
+        organization_id: OrganizationId,
+        transaction_id: TransactionId,
+        user_id: Uuid,
+    ) -> Result<(), WorkflowError> {
+        Self::release_claim(self, organization_id, transaction_id, user_id).await
+    }
+
+    async fn post_transaction(
+        &self,
+        organization_id: OrganizationId,
+        transaction_id: TransactionId,
+        posted_by: Uuid,
+    ) -> Result<transactions::Model, WorkflowError> {
+        Self::post_transaction(self, organization_id, transaction_id, posted_by).await
+    }
+
+    async fn void_transaction(
+        &self,
+        organization_id: OrganizationId,
+        transaction_id: TransactionId,
+        voided_by: Uuid,
+        void_reason_code: VoidReasonCode,
+        void_reason: String,
+    ) -> Result<VoidResult, WorkflowError> {
+        Self::void_transaction(
+            self,
+            organization_id,
+            transaction_id,
+            voided_by,
+            void_reason_code,
+            void_reason,
+        )
+        .await
+    }
+
+    async fn get_available_actions(
+        &self,
+        organization_id: OrganizationId,
+        transaction_id: TransactionId,
+        user_id: Uuid,
+    ) -> Result<TransactionActions, WorkflowError> {
+        Self::get_available_actions(self, organization_id, transaction_id, user_id).await
+    }
+
+    async fn get_status_history(
+        &self,
+        organization_id: OrganizationId,
+        transaction_id: TransactionId,
+    ) -> Result<TransactionHistory, WorkflowError> {
+        Self::get_status_history(self, organization_id, transaction_id).await
+    }
+
+    async fn get_pending_transactions(
+        &self,
+        organization_id: OrganizationId,
+        user_id: Uuid,
+        min_days: Option<u32>,
+        sort: Option<Sort<PendingSortField>>,
+    ) -> Result<Vec<PendingTransaction>, WorkflowError> {
+        Self::get_pending_transactions(self, organization_id, user_id, min_days, sort).await
+    }
+
+    async fn bulk_approve(
+        &self,
+        organization_id: OrganizationId,
+        transaction_ids: Vec<TransactionId>,
+        approved_by: Uuid,
+        approval_notes: Option<String>,
+    ) -> Result<BulkApproveResult, WorkflowError> {
+        Self::bulk_approve(
+            self,
+            organization_id,
+            transaction_ids,
+            approved_by,
+            approval_notes,
+        )
+        .await
+    }
+
+    async fn bulk_void(
+        &self,
+        organization_id: OrganizationId,
+        transaction_ids: Vec<TransactionId>,
+        voided_by: Uuid,
+        void_reason_code: VoidReasonCode,
+        void_reason: String,
+    ) -> Result<BulkVoidResult, WorkflowError> {
+        Self::bulk_void(
+            self,
+            organization_id,
+            transaction_ids,
+            voided_by,
+            void_reason_code,
+            void_reason,
+        )
+        .await
+    }
+}
+
+/// Organization and membership lookups, implemented by
+/// [`OrganizationRepository`].
+#[async_trait]
+pub trait OrganizationStore: Send + Sync {
+    /// See [`OrganizationRepository::find_by_id`].
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<organizations::Model>, DbErr>;
+
+    /// See [`OrganizationRepository::is_member`].
+    async fn is_member(&self, org_id: Uuid, user_id: Uuid) -> Result<bool, DbErr>;
+
+    /// See [`OrganizationRepository::get_user_membership`].
+    async fn get_user_membership(
+        &self,
+        org_id: Uuid,
+        user_id: Uuid,
+    ) -> Result<Option<organization_users::Model>, DbErr>;
+}
+
+#[async_trait]
+impl OrganizationStore for OrganizationRepository {
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<organizations::Model>, DbErr> {
+        Self::find_by_id(self, id).await
+    }
+
+    async fn is_member(&self, org_id: Uuid, user_id: Uuid) -> Result<bool, DbErr> {
+        Self::is_member(self, org_id, user_id).await
+    }
+
+    async fn get_user_membership(
+        &self,
+        org_id: Uuid,
+        user_id: Uuid,
+    ) -> Result<Option<organization_users::Model>, DbErr> {
+        Self::get_user_membership(self, org_id, user_id).await
+    }
+}
+
+/// Exchange rate lookups, implemented by [`ExchangeRateRepository`].
+#[async_trait]
+pub trait ExchangeRateStore: Send + Sync {
+    /// See [`ExchangeRateRepository::find_rate`].
+    async fn find_rate(
+        &self,
+        organization_id: Uuid,
+        from_currency: &str,
+        to_currency: &str,
+        date: NaiveDate,
+        policy: RateLookupPolicy,
+    ) -> Result<ExchangeRateLookup, ExchangeRateError>;
+}
+
+#[async_trait]
+impl ExchangeRateStore for ExchangeRateRepository {
+    async fn find_rate(
+        &self,
+        organization_id: Uuid,
+        from_currency: &str,
+        to_currency: &str,
+        date: NaiveDate,
+        policy: RateLookupPolicy,
+    ) -> Result<ExchangeRateLookup, ExchangeRateError> {
+        Self::find_rate(
+            self,
+            organization_id,
+            from_currency,
+            to_currency,
+            date,
+            policy,
+        )
+        .await
+    }
+}
+
+/// Payment applications, implemented by [`PaymentRepository`].
+#[async_trait]
+pub trait PaymentStore: Send + Sync {
+    /// See [`PaymentRepository::apply_payment`].
+    async fn apply_payment(
+        &self,
+        organization_id: OrganizationId,
+        payment_id: TransactionId,
+        input: ApplyPaymentInput,
+    ) -> Result<AppliedPayment, PaymentError>;
+
+    /// See [`PaymentRepository::unapply_payment`].
+    async fn unapply_payment(
+        &self,
+        organization_id: OrganizationId,
+        payment_id: TransactionId,
+        settles_transaction_id: Uuid,
+    ) -> Result<Settlement, PaymentError>;
+
+    /// See [`PaymentRepository::settled_amount`].
+    async fn settled_amount(
+        &self,
+        organization_id: OrganizationId,
+        transaction_id: TransactionId,
+    ) -> Result<Decimal, PaymentError>;
+}
+
+#[async_trait]
+impl PaymentStore for PaymentRepository {
+    async fn apply_payment(
+        &self,
+        organization_id: OrganizationId,
+        payment_id: TransactionId,
+        input: ApplyPaymentInput,
+    ) -> Result<AppliedPayment, PaymentError> {
+        Self::apply_payment(self, organization_id, payment_id, input).await
+    }
+
+    async fn unapply_payment(
+        &self,
+        organization_id: OrganizationId,
+        payment_id: TransactionId,
+        settles_transaction_id: Uuid,
+    ) -> Result<Settlement, PaymentError> {
+        Self::unapply_payment(self, organization_id, payment_id, settles_transaction_id).await
+    }
+
+    async fn settled_amount(
+        &self,
+        organization_id: OrganizationId,
+        transaction_id: TransactionId,
+    ) -> Result<Decimal, PaymentError> {
+        Self::settled_amount(self, organization_id, transaction_id).await
+    }
+}
+
+/// The repositories handed out to route handlers.
+#[derive(Clone)]
+pub struct Stores {
+    /// Transactions.
+    pub transactions: Arc<dyn TransactionStore>,
+    /// Accounts and dimension values referenced by new entries.
+    pub entry_references: Arc<dyn EntryReferenceStore>,
+    /// Approval workflow.
+    pub workflow: Arc<dyn WorkflowStore>,
+    /// Organizations and memberships.
+    pub organizations: Arc<dyn OrganizationStore>,
+    /// Exchange rates.
+    pub exchange_rates: Arc<dyn ExchangeRateStore>,
+    /// Payment applications.
+    pub payments: Arc<dyn PaymentStore>,
+}
+
+impl Stores {
+    /// Postgres-backed repositories sharing `db`.
+    #[must_use]
+    pub fn postgres(db: &DatabaseConnection) -> Self {
+        Self {
+            transactions: Arc::new(TransactionRepository::new(db.clone())),
+            entry_references: Arc::new(TransactionRepository::new(db.clone())),
+            workflow: Arc::new(WorkflowRepository::new(db.clone())),
+            organizations: Arc::new(OrganizationRepository::new(db.clone())),
+            exchange_rates: Arc::new(ExchangeRateRepository::new(db.clone())),
+            payments: Arc::new(PaymentRepository::new(db.clone())),
+        }
+    }
+}
+
+impl std::fmt::Debug for Stores {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Stores").finish_non_exhaustive()
+    }
+}
@@ -1,16 +1,22 @@
 //! Subscription and tier management repository.
 //!
 //! Handles tier limits, feature checks, and usage tracking for multi-tenant `SaaS`.
+//!
+//! Resource limits are checked by counting rows, with the organization row
+//! locked. Repositories call [`SubscriptionRepository::check_limit`] in the
+//! same transaction as the insert it guards, so concurrent creates for one
+//! organization are serialized and cannot overshoot the limit.
 
 use chrono::{Datelike, Utc};
 use sea_orm::{
-    ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, PaginatorTrait, QueryFilter,
-    Set,
+    ActiveModelTrait, ColumnTrait, ConnectionTrait, DatabaseConnection, EntityTrait,
+    PaginatorTrait, QueryFilter, QuerySelect, Set,
 };
 use uuid::Uuid;
 
 use crate::entities::{
-    organization_usage, organization_users, organizations,
+    approval_rules, budgets, dimension_types, organization_usage, organization_users,
+    organizations,
     sea_orm_active_enums::{SubscriptionStatus, SubscriptionTier},
     tier_limits,
 };
@@ -55,19 +61,78 @@ pub enum ResourceLimit {
     ApprovalRules,
 }
 
+impl ResourceLimit {
+    /// API name of the resource.
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Users => "users",
+            Self::TransactionsPerMonth => "transactions_per_month",
+            Self::Dimensions => "dimensions",
+            Self::Currencies => "currencies",
+            Self::FiscalPeriods => "fiscal_periods",
+            Self::Budgets => "budgets",
+            Self::ApprovalRules => "approval_rules",
+        }
+    }
+
+    /// This resource's limit in `limits`, `None` when unlimited.
+    fn limit_in(self, limits: &tier_limits::Model) -> Option<i64> {
+        match self {
+            Self::Users => limits.max_users,
+            Self::TransactionsPerMonth => limits.max_transactions_per_month,
+            Self::Dimensions => Some(limits.max_dimensions),
+            Self::Currencies => Some(limits.max_currencies),
+            Self::FiscalPeriods => limits.max_fiscal_periods,
+            Self::Budgets => limits.max_budgets,
+            Self::ApprovalRules => limits.max_approval_rules,
+        }
+        .map(i64::from)
+    }
+}
+
 /// Result of a limit check.
 #[derive(Debug, Clone)]
 pub struct LimitCheckResult {
+    /// The resource checked.
+    pub resource: ResourceLimit,
     /// Whether the operation is allowed.
     pub allowed: bool,
     /// Current usage count.
     pub current: i64,
     /// Maximum limit (None = unlimited).
     pub limit: Option<i64>,
+    /// How many more may be created (None = unlimited).
+    pub remaining: Option<i64>,
     /// Human-readable message if limit exceeded.
     pub message: Option<String>,
+    /// Higher tiers that would allow one more, cheapest first. Only filled
+    /// in when the limit is exceeded.
+    pub upgrade_tiers: Vec<SubscriptionTier>,
+}
+
+impl LimitCheckResult {
+    /// A refusal for a reason other than usage.
+    fn refused(resource: ResourceLimit, message: &str) -> Self {
+        Self {
+            resource,
+            allowed: false,
+            current: 0,
+            limit: None,
+            remaining: None,
+            message: Some(message.to_string()),
+            upgrade_tiers: Vec::new(),
+        }
+    }
 }
 
+/// Order of the paid tiers, for upgrade hints.
+const UPGRADE_PATH: [SubscriptionTier; 3] = [
+    SubscriptionTier::Starter,
+    SubscriptionTier::Growth,
+    SubscriptionTier::Enterprise,
+];
+
 /// Repository for subscription and tier operations.
 pub struct SubscriptionRepository;
 
@@ -128,24 +193,29 @@ impl SubscriptionRepository {
     }
 
     /// Check if an organization is within a specific resource limit.
-    #[allow(clippy::cast_possible_wrap)]
-    pub async fn check_limit(
-        db: &DatabaseConnection,
+    ///
+    /// Locks the organization row until the end of the caller's transaction,
+    /// so run it in the transaction that creates the resource.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a database query fails.
+    pub async fn check_limit<C: ConnectionTrait>(
+        db: &C,
         organization_id: Uuid,
         resource: ResourceLimit,
     ) -> Result<LimitCheckResult, sea_orm::DbErr> {
         // Get organization's tier
         let org = organizations::Entity::find_by_id(organization_id)
+            .lock_exclusive()
             .one(db)
             .await?;
 
         let Some(org) = org else {
-            return Ok(LimitCheckResult {
-                allowed: false,
-                current: 0,
-                limit: None,
-                message: Some("Organization not found".to_string()),
-            });
+            return Ok(LimitCheckResult::refused(
+                resource,
+                "Organization not found",
+            ));
         };
 
         // Get tier limits
@@ -154,82 +224,116 @@ impl SubscriptionRepository {
             .await?;
 
         let Some(limits) = limits else {
-            return Ok(LimitCheckResult {
-                allowed: false,
-                current: 0,
-                limit: None,
-                message: Some("Tier limits not configured".to_string()),
-            });
+            return Ok(LimitCheckResult::refused(
+                resource,
+                "Tier limits not configured",
+            ));
         };
 
         // Get current usage
-        let (current, max_limit) = match resource {
+        let current = match resource {
             ResourceLimit::Users => {
-                let count = organization_users::Entity::find()
+                organization_users::Entity::find()
                     .filter(organization_users::Column::OrganizationId.eq(organization_id))
                     .count(db)
-                    .await? as i64;
-                (count, limits.max_users.map(i64::from))
+                    .await?
             }
             ResourceLimit::TransactionsPerMonth => {
                 let usage = Self::get_or_create_current_usage(db, organization_id).await?;
-                (
-                    i64::from(usage.transaction_count),
-                    limits.max_transactions_per_month.map(i64::from),
-                )
+                u64::try_from(usage.transaction_count).unwrap_or(0)
             }
             ResourceLimit::Dimensions => {
-                let usage = Self::get_or_create_current_usage(db, organization_id).await?;
-                (
-                    i64::from(usage.active_dimension_count),
-                    Some(i64::from(limits.max_dimensions)),
-                )
+                dimension_types::Entity::find()
+                    .filter(dimension_types::Column::OrganizationId.eq(organization_id))
+                    .filter(dimension_types::Column::IsActive.eq(true))
+                    .count(db)
+                    .await?
             }
             ResourceLimit::Currencies => {
                 let usage = Self::get_or_create_current_usage(db, organization_id).await?;
-                (
-                    i64::from(usage.active_currency_count),
-                    Some(i64::from(limits.max_currencies)),
-                )
+                u64::try_from(usage.active_currency_count).unwrap_or(0)
             }
             ResourceLimit::FiscalPeriods => {
                 // For fiscal periods, we'd need to count from fiscal_periods table
                 // For now, use a placeholder - will be implemented when needed
-                (0, limits.max_fiscal_periods.map(i64::from))
+                0
             }
             ResourceLimit::Budgets => {
-                // Would count from budgets table - will be implemented when needed
-                (0, limits.max_budgets.map(i64::from))
+                budgets::Entity::find()
+                    .filter(budgets::Column::OrganizationId.eq(organization_id))
+                    .filter(budgets::Column::IsActive.eq(true))
+                    .count(db)
+                    .await?
             }
             ResourceLimit::ApprovalRules => {
-                // Would count from approval_rules table - will be implemented when needed
-                (0, limits.max_approval_rules.map(i64::from))
+                approval_rules::Entity::find()
+                    .filter(approval_rules::Column::OrganizationId.eq(organization_id))
+                    .filter(approval_rules::Column::IsActive.eq(true))
+                    .count(db)
+                    .await?
             }
         };
+        let current = i64::try_from(current).unwrap_or(i64::MAX);
+        let max_limit = resource.limit_in(&limits);
 
         // None means unlimited
         let allowed = max_limit.is_none_or(|max| current < max);
 
-        let message = if allowed {
-            None
+        let (message, upgrade_tiers) = if allowed {
+            (None, Vec::new())
         } else {
-            Some(format!(
-                "Limit reached: {current}/{} for {resource:?}",
+            let message = format!(
+                "Limit reached: {current}/{} {} on the {} plan",
                 max_limit.unwrap_or(0),
-            ))
+                resource.as_str(),
+                limits.display_name,
+            );
+            (
+                Some(message),
+                Self::upgrade_tiers(db, &org.subscription_tier, resource, current).await?,
+            )
         };
 
         Ok(LimitCheckResult {
+            resource,
             allowed,
             current,
             limit: max_limit,
+            remaining: max_limit.map(|max| (max - current).max(0)),
             message,
+            upgrade_tiers,
         })
     }
 
+    /// Tiers above `tier` whose limit for `resource` exceeds `current`.
+    async fn upgrade_tiers<C: ConnectionTrait>(
+        db: &C,
+        tier: &SubscriptionTier,
+        resource: ResourceLimit,
+        current: i64,
+    ) -> Result<Vec<SubscriptionTier>, sea_orm::DbErr> {
+        let Some(position) = UPGRADE_PATH.iter().position(|t| t == tier) else {
+            return Ok(Vec::new());
+        };
+        let higher = &UPGRADE_PATH[position + 1..];
+        let limits = tier_limits::Entity::find()
+            .filter(tier_limits::Column::Tier.is_in(higher.iter().cloned()))
+            .all(db)
+            .await?;
+        Ok(higher
+            .iter()
+            .filter(|t| {
+                limits
+                    .iter()
+                    .any(|l| &l.tier == *t && resource.limit_in(l).is_none_or(|max| current < max))
+            })
+            .cloned()
+            .collect())
+    }
+
     /// Get or create usage record for current month.
-    pub async fn get_or_create_current_usage(
-        db: &DatabaseConnection,
+    pub async fn get_or_create_current_usage<C: ConnectionTrait>(
+        db: &C,
         organization_id: Uuid,
     ) -> Result<organization_usage::Model, sea_orm::DbErr> {
         let now = Utc::now();
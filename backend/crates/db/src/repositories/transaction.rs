@@ -2,20 +2,78 @@
 //!
 //! Implements Requirements 5.8, 5.9, 7.4, 8.1-8.5, 10.2-10.7 for transaction management.
 
-use chrono::{NaiveDate, Utc};
+use chrono::{DateTime, Days, NaiveDate, NaiveTime, TimeZone, Utc};
+use chrono_tz::Tz;
 use rust_decimal::Decimal;
 use sea_orm::{
-    ActiveModelTrait, ColumnTrait, DatabaseConnection, DatabaseTransaction, DbErr, EntityTrait,
-    QueryFilter, QueryOrder, QuerySelect, Set, TransactionTrait,
+    ActiveModelTrait, ColumnTrait, Condition, ConnectionTrait, DatabaseConnection,
+    DatabaseTransaction, DbErr, EntityTrait, QueryFilter, QueryOrder, QuerySelect, QueryTrait,
+    Select, Set, TransactionTrait, prelude::DateTimeWithTimeZone, sea_query::Expr,
 };
+use sha2::{Digest, Sha256};
 use uuid::Uuid;
+use zeltra_core::auth::UserRole as CoreUserRole;
+use zeltra_core::bank_import::HistoricalPosting;
+use zeltra_core::dimension::{DimensionDefault, MergedDimensions, apply_defaults};
+use zeltra_core::ledger::{
+    AccountInfo, FiscalPeriodStatus as CorePeriodStatus, LedgerError, validate_posting_permission,
+};
+use zeltra_core::workflow::{
+    TransactionStatus as CoreTransactionStatus, WorkflowError, WorkflowService,
+};
+use zeltra_shared::types::{OrganizationId, Sort, SortField, TransactionId};
 
 use crate::entities::{
-    chart_of_accounts, entry_dimensions, fiscal_periods, ledger_entries,
-    sea_orm_active_enums::{AccountType, TransactionStatus, TransactionType},
+    account_default_dimensions, attachments, chart_of_accounts, currencies, dimension_values,
+    entry_dimensions, exchange_rate_overrides, fiscal_periods, ledger_entries, organization_users,
+    organizations,
+    sea_orm_active_enums::{
+        AccountType, FiscalPeriodStatus, TransactionStatus, TransactionType, UserRole,
+    },
     transactions,
 };
 
+/// A transaction's total functional debit, correlated on `transactions.id`.
+const TOTAL_DEBIT_SQL: &str = "(SELECT COALESCE(SUM(le.debit), 0) FROM ledger_entries le \
+                               WHERE le.transaction_id = transactions.id)";
+
+/// Fingerprint of a transaction for duplicate detection.
+///
+/// SHA-256 over the date, the entries as sorted (account, debit, credit)
+/// tuples and the total debit. Amounts are normalized, so `100` and
+/// `100.0000` give the same fingerprint.
+#[must_use]
+pub fn transaction_fingerprint(
+    transaction_date: NaiveDate,
+    entries: impl IntoIterator<Item = (Uuid, Decimal, Decimal)>,
+) -> String {
+    let mut entries: Vec<(Uuid, Decimal, Decimal)> = entries
+        .into_iter()
+        .map(|(account_id, debit, credit)| (account_id, debit.normalize(), credit.normalize()))
+        .collect();
+    entries.sort_unstable();
+    let total: Decimal = entries.iter().map(|(_, debit, _)| *debit).sum();
+
+    let mut hasher = Sha256::new();
+    hasher.update(transaction_date.to_string());
+    for (account_id, debit, credit) in &entries {
+        hasher.update(format!("|{account_id}:{debit}:{credit}"));
+    }
+    hasher.update(format!("|{}", total.normalize()));
+    format!("{:x}", hasher.finalize())
+}
+
+/// Fingerprint of a transaction about to be created.
+fn input_fingerprint(transaction_date: NaiveDate, entries: &[CreateLedgerEntryInput]) -> String {
+    transaction_fingerprint(
+        transaction_date,
+        entries.iter().map(|e| (e.account_id, e.debit, e.credit)),
+    )
+}
+
+/// Counter of transactions created (in draft status).
+pub const TRANSACTIONS_CREATED_TOTAL: &str = "transactions_created_total";
+
 /// Error types for transaction operations.
 #[derive(Debug, thiserror::Error)]
 pub enum TransactionError {
@@ -27,10 +85,38 @@ pub enum TransactionError {
     #[error("Account not found: {0}")]
     AccountNotFound(Uuid),
 
+    /// Account is deactivated.
+    #[error("Account {0} is inactive")]
+    AccountInactive(String),
+
+    /// Account is a header or summary account that only rolls up children.
+    #[error("Account {0} does not allow direct posting")]
+    DirectPostingNotAllowed(String),
+
+    /// Entry currency does not match a foreign-currency account.
+    #[error("Account {account} only accepts {account_currency} entries, got {entry_currency}")]
+    CurrencyMismatch {
+        /// Account code.
+        account: String,
+        /// Currency the account is denominated in.
+        account_currency: String,
+        /// Source currency of the rejected entry.
+        entry_currency: String,
+    },
+
     /// No fiscal period found for the transaction date.
     #[error("No fiscal period found for date {0}")]
     NoFiscalPeriod(NaiveDate),
 
+    /// An entry's event time is on a different day than its transaction.
+    #[error("Entry event time {event_at} is not on transaction date {transaction_date}")]
+    EventOutsideTransactionDate {
+        /// Requested event time.
+        event_at: DateTimeWithTimeZone,
+        /// Transaction date, in the organization's timezone.
+        transaction_date: NaiveDate,
+    },
+
     /// Fiscal period is closed.
     #[error("Fiscal period is closed, no posting allowed")]
     PeriodClosed,
@@ -51,6 +137,10 @@ pub enum TransactionError {
     #[error("Can only delete draft transactions")]
     CanOnlyDeleteDraft,
 
+    /// The operation only applies to draft transactions.
+    #[error("Transaction is not a draft")]
+    NotDraft,
+
     /// Concurrent modification detected.
     #[error("Concurrent modification detected for account {0}, please retry")]
     ConcurrentModification(Uuid),
@@ -81,6 +171,47 @@ pub struct CreateTransactionInput {
     pub created_by: Uuid,
 }
 
+/// Input for updating a draft transaction; `None` leaves a field unchanged.
+#[derive(Debug, Clone)]
+pub struct UpdateTransactionInput {
+    /// New description.
+    pub description: Option<String>,
+    /// New memo.
+    pub memo: Option<String>,
+    /// New reference number.
+    pub reference_number: Option<String>,
+    /// New transaction date; moves the transaction to that date's period.
+    pub transaction_date: Option<NaiveDate>,
+    /// User making the change, whose role decides which periods they can
+    /// move the transaction into.
+    pub updated_by: Uuid,
+}
+
+/// Values read from a document to fill into a draft; `None` leaves a field
+/// unchanged.
+#[derive(Debug, Clone)]
+pub struct DraftPrefill {
+    /// New description.
+    pub description: Option<String>,
+    /// New transaction date.
+    pub transaction_date: Option<NaiveDate>,
+    /// New total, in `currency`.
+    pub amount: Option<Decimal>,
+    /// Currency of `amount`; `None` accepts the draft's currency.
+    pub currency: Option<String>,
+    /// User applying the prefill.
+    pub updated_by: Uuid,
+}
+
+/// Result of [`TransactionRepository::prefill_draft`].
+#[derive(Debug, Clone)]
+pub struct PrefilledDraft {
+    /// The updated transaction header.
+    pub transaction: transactions::Model,
+    /// Whether the amount was written to the entries.
+    pub amount_applied: bool,
+}
+
 /// Input for a single ledger entry.
 #[derive(Debug, Clone)]
 pub struct CreateLedgerEntryInput {
@@ -104,6 +235,22 @@ pub struct CreateLedgerEntryInput {
     pub memo: Option<String>,
     /// Dimension value IDs.
     pub dimensions: Vec<Uuid>,
+    /// When the entry happened, ordering it within its day. Must fall on the
+    /// transaction date in the organization's timezone. Defaults to now for
+    /// today's transactions and to the start of the day otherwise.
+    pub event_at: Option<DateTimeWithTimeZone>,
+    /// Set when `exchange_rate` was entered by the user rather than looked
+    /// up; the entry is flagged and the override logged.
+    pub rate_override: Option<RateOverrideInput>,
+}
+
+/// The lookup an entry's entered exchange rate replaced.
+#[derive(Debug, Clone)]
+pub struct RateOverrideInput {
+    /// Date whose rate the organization's lookup policy used.
+    pub rate_date: NaiveDate,
+    /// Rate the lookup found, if any.
+    pub looked_up_rate: Option<Decimal>,
 }
 
 /// Filter options for listing transactions.
@@ -119,6 +266,49 @@ pub struct TransactionFilter {
     pub date_to: Option<NaiveDate>,
     /// Filter by dimension value ID.
     pub dimension_value_id: Option<Uuid>,
+    /// Only transactions created by this user.
+    pub created_by: Option<Uuid>,
+    /// Only transactions with at least one entry on this account.
+    pub account_id: Option<Uuid>,
+    /// Minimum total functional debit, inclusive.
+    pub min_amount: Option<Decimal>,
+    /// Maximum total functional debit, inclusive.
+    pub max_amount: Option<Decimal>,
+    /// Sort order for listing. Newest transaction date first when `None`.
+    pub sort: Option<Sort<TransactionSortField>>,
+}
+
+/// Fields transaction lists can be sorted by.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransactionSortField {
+    /// Transaction date.
+    TransactionDate,
+    /// Creation timestamp.
+    CreatedAt,
+    /// Reference number.
+    ReferenceNumber,
+    /// Workflow status.
+    Status,
+}
+
+impl SortField for TransactionSortField {
+    const ALLOWED: &'static [(&'static str, Self)] = &[
+        ("transaction_date", Self::TransactionDate),
+        ("created_at", Self::CreatedAt),
+        ("reference_number", Self::ReferenceNumber),
+        ("status", Self::Status),
+    ];
+}
+
+impl TransactionSortField {
+    fn column(self) -> transactions::Column {
+        match self {
+            Self::TransactionDate => transactions::Column::TransactionDate,
+            Self::CreatedAt => transactions::Column::CreatedAt,
+            Self::ReferenceNumber => transactions::Column::ReferenceNumber,
+            Self::Status => transactions::Column::Status,
+        }
+    }
 }
 
 /// Transaction with its entries.
@@ -130,6 +320,17 @@ pub struct TransactionWithEntries {
     pub entries: Vec<LedgerEntryWithDimensions>,
 }
 
+/// Aggregated counts and totals for a filtered set of transactions.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TransactionSummary {
+    /// Number of transactions per status.
+    pub by_status: Vec<(TransactionStatus, u64)>,
+    /// Number of transactions per type.
+    pub by_type: Vec<(TransactionType, u64)>,
+    /// Sum of functional debits across posted transactions.
+    pub posted_debit_total: Decimal,
+}
+
 /// Ledger entry with its dimensions.
 #[derive(Debug, Clone)]
 pub struct LedgerEntryWithDimensions {
@@ -137,6 +338,63 @@ pub struct LedgerEntryWithDimensions {
     pub entry: ledger_entries::Model,
     /// Dimension value IDs.
     pub dimensions: Vec<Uuid>,
+    /// Dimension value IDs added from the account's defaults when the entry
+    /// was created. Empty when the entry is read back later.
+    pub defaulted_dimensions: Vec<Uuid>,
+    /// Number of attachments on this entry.
+    pub attachment_count: i64,
+}
+
+/// The accounts and dimension values a new transaction's entries refer to,
+/// loaded up front so the ledger service can validate the entries.
+#[derive(Debug, Clone, Default)]
+pub struct EntryReferences {
+    /// The organization's accounts among those requested, by ID.
+    pub accounts: std::collections::HashMap<Uuid, chart_of_accounts::Model>,
+    /// The organization's dimension values among those requested, by ID.
+    pub dimension_values: std::collections::HashMap<Uuid, dimension_values::Model>,
+}
+
+impl EntryReferences {
+    /// Account validator for [`LedgerService`](zeltra_core::ledger::LedgerService).
+    ///
+    /// # Errors
+    ///
+    /// Returns `AccountNotFound` for an account outside the organization.
+    pub fn account_info(&self, id: Uuid) -> Result<AccountInfo, LedgerError> {
+        self.accounts
+            .get(&id)
+            .map(|account| AccountInfo {
+                id,
+                is_active: account.is_active,
+                allow_direct_posting: account.allow_direct_posting,
+                currency: account.currency.clone(),
+            })
+            .ok_or(LedgerError::AccountNotFound(id))
+    }
+
+    /// Dimension validator for [`LedgerService`](zeltra_core::ledger::LedgerService).
+    ///
+    /// # Errors
+    ///
+    /// Returns `InvalidDimension` for a value outside the organization and
+    /// `DimensionInactive` for a deactivated one.
+    pub fn check_dimensions(&self, ids: &[Uuid]) -> Result<(), LedgerError> {
+        ids.iter()
+            .try_for_each(|id| match self.dimension_values.get(id) {
+                None => Err(LedgerError::InvalidDimension(*id)),
+                Some(value) if !value.is_active => Err(LedgerError::DimensionInactive(*id)),
+                Some(_) => Ok(()),
+            })
+    }
+
+    /// Code of a loaded account, or its ID if it was not found.
+    #[must_use]
+    pub fn account_code(&self, id: Uuid) -> String {
+        self.accounts
+            .get(&id)
+            .map_or_else(|| id.to_string(), |account| account.code.clone())
+    }
 }
 
 /// Transaction repository for CRUD operations.
@@ -161,6 +419,8 @@ impl TransactionRepository {
     /// Returns an error if:
     /// - No fiscal period exists for the transaction date
     /// - The fiscal period is closed
+    /// - An entry's account is inactive, does not allow direct posting or is
+    ///   in a different foreign currency
     /// - Database operation fails
     pub async fn create_transaction(
         &self,
@@ -171,6 +431,8 @@ impl TransactionRepository {
             .find_fiscal_period(input.organization_id, input.transaction_date)
             .await?;
 
+        let timezone = organization_timezone(&self.db, input.organization_id).await?;
+
         // Start database transaction
         let txn = self.db.begin().await?;
 
@@ -181,11 +443,12 @@ impl TransactionRepository {
 
         // Create ledger entries and dimensions
         let entries = self
-            .insert_entries(&txn, transaction.id, &input.entries)
+            .insert_entries(&txn, &transaction, timezone, &input.entries)
             .await?;
 
         // Commit database transaction
         txn.commit().await?;
+        metrics::counter!(TRANSACTIONS_CREATED_TOTAL).increment(1);
 
         Ok(TransactionWithEntries {
             transaction,
@@ -193,6 +456,248 @@ impl TransactionRepository {
         })
     }
 
+    /// Creates several transactions atomically.
+    ///
+    /// Either every transaction is created or none are.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - No fiscal period exists for any transaction date
+    /// - An entry's account is inactive, does not allow direct posting or is
+    ///   in a different foreign currency
+    /// - Database operation fails
+    pub async fn create_transactions(
+        &self,
+        inputs: Vec<CreateTransactionInput>,
+    ) -> Result<Vec<TransactionWithEntries>, TransactionError> {
+        // Resolve every fiscal period before opening the database transaction
+        let mut resolved = Vec::with_capacity(inputs.len());
+        for input in &inputs {
+            let period = self
+                .find_fiscal_period(input.organization_id, input.transaction_date)
+                .await?;
+            let timezone = organization_timezone(&self.db, input.organization_id).await?;
+            resolved.push((period.id, timezone));
+        }
+
+        let txn = self.db.begin().await?;
+
+        let mut created = Vec::with_capacity(inputs.len());
+        for (input, (fiscal_period_id, timezone)) in inputs.iter().zip(resolved) {
+            let transaction = self
+                .insert_transaction(&txn, input, fiscal_period_id)
+                .await?;
+            let entries = self
+                .insert_entries(&txn, &transaction, timezone, &input.entries)
+                .await?;
+            created.push(TransactionWithEntries {
+                transaction,
+                entries,
+            });
+        }
+
+        txn.commit().await?;
+        metrics::counter!(TRANSACTIONS_CREATED_TOTAL).increment(created.len() as u64);
+
+        Ok(created)
+    }
+
+    /// Loads the organization's accounts and dimension values among the
+    /// given IDs, one query each.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a database query fails.
+    pub async fn find_entry_references(
+        &self,
+        organization_id: OrganizationId,
+        account_ids: &[Uuid],
+        dimension_value_ids: &[Uuid],
+    ) -> Result<EntryReferences, TransactionError> {
+        let accounts = chart_of_accounts::Entity::find()
+            .filter(chart_of_accounts::Column::OrganizationId.eq(organization_id.into_inner()))
+            .filter(chart_of_accounts::Column::Id.is_in(account_ids.iter().copied()))
+            .all(&self.db)
+            .await?
+            .into_iter()
+            .map(|account| (account.id, account))
+            .collect();
+        let dimension_values = if dimension_value_ids.is_empty() {
+            std::collections::HashMap::new()
+        } else {
+            dimension_values::Entity::find()
+                .filter(dimension_values::Column::OrganizationId.eq(organization_id.into_inner()))
+                .filter(dimension_values::Column::Id.is_in(dimension_value_ids.iter().copied()))
+                .all(&self.db)
+                .await?
+                .into_iter()
+                .map(|value| (value.id, value))
+                .collect()
+        };
+        Ok(EntryReferences {
+            accounts,
+            dimension_values,
+        })
+    }
+
+    /// Finds non-voided transactions that look the same as a new one.
+    ///
+    /// A match has the same fingerprint (see [`transaction_fingerprint`])
+    /// and a date within `window_days` of `transaction_date`. Transactions
+    /// saved without a fingerprint get theirs computed and stored on the way.
+    /// Oldest first.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a database operation fails.
+    pub async fn find_possible_duplicates(
+        &self,
+        organization_id: OrganizationId,
+        transaction_date: NaiveDate,
+        entries: &[CreateLedgerEntryInput],
+        window_days: u32,
+    ) -> Result<Vec<Uuid>, TransactionError> {
+        let window = Days::new(u64::from(window_days));
+        let from = transaction_date
+            .checked_sub_days(window)
+            .unwrap_or(NaiveDate::MIN);
+        let to = transaction_date
+            .checked_add_days(window)
+            .unwrap_or(NaiveDate::MAX);
+        // The date is part of the fingerprint, so each day in the window has its own
+        let fingerprints: std::collections::HashMap<NaiveDate, String> = from
+            .iter_days()
+            .take_while(|day| *day <= to)
+            .map(|day| (day, input_fingerprint(day, entries)))
+            .collect();
+
+        let candidates = transactions::Entity::find()
+            .filter(transactions::Column::OrganizationId.eq(organization_id))
+            .filter(transactions::Column::Status.ne(TransactionStatus::Voided))
+            .filter(transactions::Column::TransactionDate.between(from, to))
+            .filter(
+                Condition::any()
+                    .add(transactions::Column::Fingerprint.is_in(fingerprints.values().cloned()))
+                    .add(transactions::Column::Fingerprint.is_null()),
+            )
+            .order_by_asc(transactions::Column::CreatedAt)
+            .all(&self.db)
+            .await?;
+
+        let missing: Vec<&transactions::Model> = candidates
+            .iter()
+            .filter(|t| t.fingerprint.is_none())
+            .collect();
+        let backfilled = self.backfill_fingerprints(&missing).await?;
+
+        Ok(candidates
+            .iter()
+            .filter(|t| {
+                t.fingerprint.as_ref().or_else(|| backfilled.get(&t.id))
+                    == fingerprints.get(&t.transaction_date)
+            })
+            .map(|t| t.id)
+            .collect())
+    }
+
+    /// Computes and stores fingerprints for transactions that have none.
+    async fn backfill_fingerprints(
+        &self,
+        transactions: &[&transactions::Model],
+    ) -> Result<std::collections::HashMap<Uuid, String>, TransactionError> {
+        if transactions.is_empty() {
+            return Ok(std::collections::HashMap::new());
+        }
+
+        let entries = ledger_entries::Entity::find()
+            .filter(
+                ledger_entries::Column::TransactionId
+                    .is_in(transactions.iter().map(|t| t.id).collect::<Vec<_>>()),
+            )
+            .all(&self.db)
+            .await?;
+        let mut by_transaction: std::collections::HashMap<Uuid, Vec<(Uuid, Decimal, Decimal)>> =
+            std::collections::HashMap::new();
+        for entry in entries {
+            by_transaction
+                .entry(entry.transaction_id)
+                .or_default()
+                .push((entry.account_id, entry.debit, entry.credit));
+        }
+
+        let mut fingerprints = std::collections::HashMap::with_capacity(transactions.len());
+        for transaction in transactions {
+            let fingerprint = transaction_fingerprint(
+                transaction.transaction_date,
+                by_transaction.remove(&transaction.id).unwrap_or_default(),
+            );
+            transactions::Entity::update_many()
+                .col_expr(
+                    transactions::Column::Fingerprint,
+                    Expr::value(fingerprint.clone()),
+                )
+                .filter(transactions::Column::Id.eq(transaction.id))
+                .exec(&self.db)
+                .await?;
+            fingerprints.insert(transaction.id, fingerprint);
+        }
+        Ok(fingerprints)
+    }
+
+    /// Lists descriptions of recent posted transactions with the non-bank
+    /// accounts they touched.
+    ///
+    /// Used as matching history for bank statement imports.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    pub async fn list_posting_history(
+        &self,
+        organization_id: OrganizationId,
+        limit: u64,
+    ) -> Result<Vec<HistoricalPosting>, TransactionError> {
+        let posted = transactions::Entity::find()
+            .filter(transactions::Column::OrganizationId.eq(organization_id))
+            .filter(transactions::Column::Status.eq(TransactionStatus::Posted))
+            .order_by_desc(transactions::Column::TransactionDate)
+            .limit(limit)
+            .all(&self.db)
+            .await?;
+
+        if posted.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let descriptions: std::collections::HashMap<Uuid, String> =
+            posted.into_iter().map(|t| (t.id, t.description)).collect();
+
+        let entries = ledger_entries::Entity::find()
+            .filter(
+                ledger_entries::Column::TransactionId
+                    .is_in(descriptions.keys().copied().collect::<Vec<_>>()),
+            )
+            .find_also_related(chart_of_accounts::Entity)
+            .all(&self.db)
+            .await?;
+
+        let history = entries
+            .into_iter()
+            .filter(|(_, account)| account.as_ref().is_some_and(|a| !a.is_bank_account))
+            .filter_map(|(entry, _)| {
+                descriptions
+                    .get(&entry.transaction_id)
+                    .map(|description| HistoricalPosting {
+                        description: description.clone(),
+                        account_id: entry.account_id,
+                    })
+            })
+            .collect();
+
+        Ok(history)
+    }
+
     /// Finds the fiscal period containing the given date.
     async fn find_fiscal_period(
         &self,
@@ -231,6 +736,10 @@ impl TransactionRepository {
             memo: Set(input.memo.clone()),
             status: Set(TransactionStatus::Draft), // Requirement 5.8
             created_by: Set(input.created_by),
+            fingerprint: Set(Some(input_fingerprint(
+                input.transaction_date,
+                &input.entries,
+            ))),
             created_at: Set(now),
             updated_at: Set(now),
             ..Default::default()
@@ -246,28 +755,51 @@ impl TransactionRepository {
     /// - Increment account_version for each entry
     /// - Calculate and store previous_balance and current_balance
     /// - Apply account type balance rules (debit-normal vs credit-normal)
+    #[allow(clippy::too_many_lines)]
     async fn insert_entries(
         &self,
         txn: &DatabaseTransaction,
-        transaction_id: Uuid,
+        transaction: &transactions::Model,
+        timezone: Tz,
         entries: &[CreateLedgerEntryInput],
     ) -> Result<Vec<LedgerEntryWithDimensions>, TransactionError> {
-        let now = Utc::now().into();
+        let transaction_id = transaction.id;
+        let now_utc = Utc::now();
+        let now = now_utc.into();
+        let default_event_at = default_event_at(transaction.transaction_date, timezone, now_utc);
         let mut result = Vec::with_capacity(entries.len());
 
         // Track balance changes per account within this transaction
         // Key: account_id, Value: (latest_version, latest_balance)
         let mut account_balances: std::collections::HashMap<Uuid, (i64, Decimal)> =
             std::collections::HashMap::new();
+        let mut account_defaults: std::collections::HashMap<Uuid, Vec<DimensionDefault>> =
+            std::collections::HashMap::new();
 
         for entry_input in entries {
             let entry_id = Uuid::new_v4();
 
+            let event_at = match entry_input.event_at {
+                Some(event_at) => {
+                    if event_at.with_timezone(&timezone).date_naive()
+                        != transaction.transaction_date
+                    {
+                        return Err(TransactionError::EventOutsideTransactionDate {
+                            event_at,
+                            transaction_date: transaction.transaction_date,
+                        });
+                    }
+                    event_at
+                }
+                None => default_event_at,
+            };
+
             // Get account info for balance calculation (Requirement 8.4, 8.5)
             let account = chart_of_accounts::Entity::find_by_id(entry_input.account_id)
                 .one(txn)
                 .await?
                 .ok_or(TransactionError::AccountNotFound(entry_input.account_id))?;
+            validate_entry_account(&account, entry_input)?;
 
             // Calculate balance change based on account type
             let balance_change = calculate_balance_change(
@@ -308,8 +840,9 @@ impl TransactionRepository {
                 debit: Set(entry_input.debit),
                 credit: Set(entry_input.credit),
                 memo: Set(entry_input.memo.clone()),
-                event_at: Set(now),
+                event_at: Set(event_at),
                 created_at: Set(now),
+                is_override: Set(entry_input.rate_override.is_some()),
                 // Balance tracking fields (Requirements 8.1-8.3)
                 account_version: Set(account_version),
                 account_previous_balance: Set(previous_balance),
@@ -318,8 +851,39 @@ impl TransactionRepository {
 
             let inserted_entry = entry.insert(txn).await?;
 
+            if let Some(rate_override) = &entry_input.rate_override {
+                exchange_rate_overrides::ActiveModel {
+                    id: Set(Uuid::new_v4()),
+                    organization_id: Set(transaction.organization_id),
+                    transaction_id: Set(transaction_id),
+                    ledger_entry_id: Set(entry_id),
+                    from_currency: Set(entry_input.source_currency.clone()),
+                    to_currency: Set(entry_input.functional_currency.clone()),
+                    rate_date: Set(rate_override.rate_date),
+                    looked_up_rate: Set(rate_override.looked_up_rate),
+                    override_rate: Set(entry_input.exchange_rate),
+                    overridden_by: Set(Some(transaction.created_by)),
+                    created_at: Set(now),
+                }
+                .insert(txn)
+                .await?;
+            }
+
+            // Fill dimension types the entry leaves out from the account's defaults
+            if let std::collections::hash_map::Entry::Vacant(slot) =
+                account_defaults.entry(entry_input.account_id)
+            {
+                slot.insert(Self::load_account_defaults(txn, entry_input.account_id).await?);
+            }
+            let merged = Self::merge_default_dimensions(
+                txn,
+                &entry_input.dimensions,
+                &account_defaults[&entry_input.account_id],
+            )
+            .await?;
+
             // Insert entry dimensions (Requirement 7.4)
-            for dimension_value_id in &entry_input.dimensions {
+            for dimension_value_id in &merged.dimensions {
                 let dimension = entry_dimensions::ActiveModel {
                     id: Set(Uuid::new_v4()),
                     ledger_entry_id: Set(entry_id),
@@ -331,13 +895,65 @@ impl TransactionRepository {
 
             result.push(LedgerEntryWithDimensions {
                 entry: inserted_entry,
-                dimensions: entry_input.dimensions.clone(),
+                dimensions: merged.dimensions,
+                defaulted_dimensions: merged.defaulted,
+                attachment_count: 0,
             });
         }
 
         Ok(result)
     }
 
+    /// Loads an account's default dimension values, skipping inactive ones.
+    async fn load_account_defaults(
+        txn: &DatabaseTransaction,
+        account_id: Uuid,
+    ) -> Result<Vec<DimensionDefault>, TransactionError> {
+        let defaults = account_default_dimensions::Entity::find()
+            .filter(account_default_dimensions::Column::AccountId.eq(account_id))
+            .order_by_asc(account_default_dimensions::Column::CreatedAt)
+            .find_also_related(dimension_values::Entity)
+            .all(txn)
+            .await?
+            .into_iter()
+            .filter_map(|(_, value)| value)
+            .filter(|value| value.is_active)
+            .map(|value| DimensionDefault {
+                dimension_type_id: value.dimension_type_id,
+                dimension_value_id: value.id,
+            })
+            .collect();
+        Ok(defaults)
+    }
+
+    /// Applies `defaults` to an entry's explicit dimension values.
+    async fn merge_default_dimensions(
+        txn: &DatabaseTransaction,
+        explicit: &[Uuid],
+        defaults: &[DimensionDefault],
+    ) -> Result<MergedDimensions, TransactionError> {
+        if defaults.is_empty() {
+            return Ok(MergedDimensions {
+                dimensions: explicit.to_vec(),
+                defaulted: vec![],
+            });
+        }
+
+        let value_types = if explicit.is_empty() {
+            std::collections::HashMap::new()
+        } else {
+            dimension_values::Entity::find()
+                .filter(dimension_values::Column::Id.is_in(explicit.to_vec()))
+                .all(txn)
+                .await?
+                .into_iter()
+                .map(|value| (value.id, value.dimension_type_id))
+                .collect()
+        };
+
+        Ok(apply_defaults(explicit, &value_types, defaults))
+    }
+
     /// Gets the latest account balance (version and balance).
     ///
     /// Returns (0, 0) if no entries exist for the account.
@@ -368,18 +984,102 @@ impl TransactionRepository {
     /// Returns an error if the database query fails.
     pub async fn list_transactions(
         &self,
-        organization_id: Uuid,
+        organization_id: OrganizationId,
         filter: TransactionFilter,
     ) -> Result<Vec<transactions::Model>, TransactionError> {
+        let mut query = Self::filtered_query(organization_id, &filter);
+        if let Some(sort) = filter.sort {
+            query = query.order_by(sort.field.column(), sort.direction.into());
+        }
+
+        // Break ties newest first so paging through equal keys is stable
+        let transactions = query
+            .order_by_desc(transactions::Column::TransactionDate)
+            .order_by_desc(transactions::Column::CreatedAt)
+            .order_by_desc(transactions::Column::Id)
+            .all(&self.db)
+            .await?;
+
+        Ok(transactions)
+    }
+
+    /// Summarizes transactions matching a filter.
+    ///
+    /// Counts are grouped by status and by type; the debit total only covers
+    /// posted transactions within the filter.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    pub async fn summarize(
+        &self,
+        organization_id: OrganizationId,
+        filter: TransactionFilter,
+    ) -> Result<TransactionSummary, TransactionError> {
+        let by_status: Vec<(TransactionStatus, i64)> =
+            Self::filtered_query(organization_id, &filter)
+                .select_only()
+                .column(transactions::Column::Status)
+                .column_as(transactions::Column::Id.count(), "count")
+                .group_by(transactions::Column::Status)
+                .into_tuple()
+                .all(&self.db)
+                .await?;
+
+        let by_type: Vec<(TransactionType, i64)> = Self::filtered_query(organization_id, &filter)
+            .select_only()
+            .column(transactions::Column::TransactionType)
+            .column_as(transactions::Column::Id.count(), "count")
+            .group_by(transactions::Column::TransactionType)
+            .into_tuple()
+            .all(&self.db)
+            .await?;
+
+        let posted_ids = Self::filtered_query(organization_id, &filter)
+            .filter(transactions::Column::Status.eq(TransactionStatus::Posted))
+            .select_only()
+            .column(transactions::Column::Id)
+            .into_query();
+
+        let posted_debit_total: Option<Decimal> = ledger_entries::Entity::find()
+            .select_only()
+            .column_as(ledger_entries::Column::Debit.sum(), "total")
+            .filter(ledger_entries::Column::TransactionId.in_subquery(posted_ids))
+            .into_tuple()
+            .one(&self.db)
+            .await?
+            .flatten();
+
+        Ok(TransactionSummary {
+            by_status: by_status
+                .into_iter()
+                .map(|(status, count)| (status, count.unsigned_abs()))
+                .collect(),
+            by_type: by_type
+                .into_iter()
+                .map(|(tx_type, count)| (tx_type, count.unsigned_abs()))
+                .collect(),
+            posted_debit_total: posted_debit_total.unwrap_or(Decimal::ZERO),
+        })
+    }
+
+    /// Builds the base transaction query for an organization and filter.
+    ///
+    /// Shared by listing and summaries so both always agree on which
+    /// transactions a filter selects.
+    fn filtered_query(
+        organization_id: OrganizationId,
+        filter: &TransactionFilter,
+    ) -> Select<transactions::Entity> {
         let mut query = transactions::Entity::find()
             .filter(transactions::Column::OrganizationId.eq(organization_id));
 
-        if let Some(status) = filter.status {
-            query = query.filter(transactions::Column::Status.eq(status));
+        if let Some(status) = &filter.status {
+            query = query.filter(transactions::Column::Status.eq(status.clone()));
         }
 
-        if let Some(tx_type) = filter.transaction_type {
-            query = query.filter(transactions::Column::TransactionType.eq(tx_type));
+        if let Some(tx_type) = &filter.transaction_type {
+            query = query.filter(transactions::Column::TransactionType.eq(tx_type.clone()));
         }
 
         if let Some(date_from) = filter.date_from {
@@ -390,15 +1090,41 @@ impl TransactionRepository {
             query = query.filter(transactions::Column::TransactionDate.lte(date_to));
         }
 
-        // TODO: Filter by dimension_value_id requires a join with entry_dimensions
+        if let Some(created_by) = filter.created_by {
+            query = query.filter(transactions::Column::CreatedBy.eq(created_by));
+        }
 
-        let transactions = query
-            .order_by_desc(transactions::Column::TransactionDate)
-            .order_by_desc(transactions::Column::CreatedAt)
-            .all(&self.db)
-            .await?;
+        // A subquery rather than a join, so a transaction with several entries
+        // on the account is still listed once
+        if let Some(account_id) = filter.account_id {
+            query = query.filter(
+                transactions::Column::Id.in_subquery(
+                    ledger_entries::Entity::find()
+                        .select_only()
+                        .column(ledger_entries::Column::TransactionId)
+                        .filter(ledger_entries::Column::AccountId.eq(account_id))
+                        .into_query(),
+                ),
+            );
+        }
 
-        Ok(transactions)
+        if let Some(min_amount) = filter.min_amount {
+            query = query.filter(Expr::cust_with_values(
+                format!("{TOTAL_DEBIT_SQL} >= $1"),
+                [min_amount],
+            ));
+        }
+
+        if let Some(max_amount) = filter.max_amount {
+            query = query.filter(Expr::cust_with_values(
+                format!("{TOTAL_DEBIT_SQL} <= $1"),
+                [max_amount],
+            ));
+        }
+
+        // TODO: Filter by dimension_value_id requires a join with entry_dimensions
+
+        query
     }
 
     /// Gets a transaction by ID with all entries and dimensions.
@@ -410,15 +1136,15 @@ impl TransactionRepository {
     /// Returns an error if the transaction is not found or database query fails.
     pub async fn get_transaction(
         &self,
-        organization_id: Uuid,
-        transaction_id: Uuid,
+        organization_id: OrganizationId,
+        transaction_id: TransactionId,
     ) -> Result<TransactionWithEntries, TransactionError> {
         // Get transaction
         let transaction = transactions::Entity::find_by_id(transaction_id)
             .filter(transactions::Column::OrganizationId.eq(organization_id))
             .one(&self.db)
             .await?
-            .ok_or(TransactionError::NotFound(transaction_id))?;
+            .ok_or(TransactionError::NotFound(transaction_id.into_inner()))?;
 
         // Get entries
         let entries = ledger_entries::Entity::find()
@@ -426,6 +1152,19 @@ impl TransactionRepository {
             .all(&self.db)
             .await?;
 
+        let attachment_counts: std::collections::HashMap<Uuid, i64> = attachments::Entity::find()
+            .select_only()
+            .column(attachments::Column::LedgerEntryId)
+            .column_as(attachments::Column::Id.count(), "count")
+            .filter(attachments::Column::TransactionId.eq(transaction_id))
+            .filter(attachments::Column::LedgerEntryId.is_not_null())
+            .group_by(attachments::Column::LedgerEntryId)
+            .into_tuple::<(Uuid, i64)>()
+            .all(&self.db)
+            .await?
+            .into_iter()
+            .collect();
+
         // Get dimensions for each entry
         let mut entries_with_dims = Vec::with_capacity(entries.len());
         for entry in entries {
@@ -437,7 +1176,13 @@ impl TransactionRepository {
                 .map(|d| d.dimension_value_id)
                 .collect();
 
-            entries_with_dims.push(LedgerEntryWithDimensions { entry, dimensions });
+            let attachment_count = attachment_counts.get(&entry.id).copied().unwrap_or(0);
+            entries_with_dims.push(LedgerEntryWithDimensions {
+                entry,
+                dimensions,
+                defaulted_dimensions: vec![],
+                attachment_count,
+            });
         }
 
         Ok(TransactionWithEntries {
@@ -448,54 +1193,208 @@ impl TransactionRepository {
 
     /// Updates a draft transaction.
     ///
+    /// A new date re-resolves the fiscal period. The period must accept
+    /// postings from the updating user, as posting would later require:
+    /// closed periods are rejected, and soft-closed ones need a role that can
+    /// post to them.
+    ///
     /// Requirements: 10.4, 10.5
     ///
     /// # Errors
     ///
     /// Returns an error if:
     /// - Transaction is not found
-    /// - Transaction is not in draft status
+    /// - Transaction is posted or voided
+    /// - No fiscal period exists for the new date, or it is closed to the user
     /// - Database operation fails
     pub async fn update_transaction(
         &self,
-        organization_id: Uuid,
-        transaction_id: Uuid,
-        description: Option<String>,
-        memo: Option<String>,
-        reference_number: Option<String>,
+        organization_id: OrganizationId,
+        transaction_id: TransactionId,
+        input: UpdateTransactionInput,
     ) -> Result<transactions::Model, TransactionError> {
         // Get existing transaction
         let transaction = transactions::Entity::find_by_id(transaction_id)
             .filter(transactions::Column::OrganizationId.eq(organization_id))
             .one(&self.db)
             .await?
-            .ok_or(TransactionError::NotFound(transaction_id))?;
+            .ok_or(TransactionError::NotFound(transaction_id.into_inner()))?;
 
         // Check status (Requirement 10.5)
-        match transaction.status {
-            TransactionStatus::Posted => return Err(TransactionError::CannotModifyPosted),
-            TransactionStatus::Voided => return Err(TransactionError::CannotModifyVoided),
-            _ => {}
-        }
+        check_modify(&transaction.status)?;
+
+        let new_date = input
+            .transaction_date
+            .filter(|date| *date != transaction.transaction_date);
+        let fiscal_period_id = match new_date {
+            Some(date) => Some(
+                self.resolve_period_for_user(organization_id, date, input.updated_by)
+                    .await?,
+            ),
+            None => None,
+        };
 
         // Update transaction
         let mut active: transactions::ActiveModel = transaction.into();
 
-        if let Some(desc) = description {
+        if let Some(desc) = input.description {
             active.description = Set(desc);
         }
-        if let Some(m) = memo {
+        if let Some(m) = input.memo {
             active.memo = Set(Some(m));
         }
-        if let Some(ref_num) = reference_number {
+        if let Some(ref_num) = input.reference_number {
             active.reference_number = Set(Some(ref_num));
         }
+        if let (Some(date), Some(period_id)) = (new_date, fiscal_period_id) {
+            active.transaction_date = Set(date);
+            active.fiscal_period_id = Set(period_id);
+        }
         active.updated_at = Set(Utc::now().into());
 
-        let updated = active.update(&self.db).await?;
+        let txn = self.db.begin().await?;
+        let updated = active.update(&txn).await?;
+        if let Some(date) = new_date {
+            move_entry_events(&txn, updated.organization_id, updated.id, date).await?;
+        }
+        txn.commit().await?;
         Ok(updated)
     }
 
+    /// Fills a draft transaction from document data.
+    ///
+    /// Description and date are always applied, with the same period checks
+    /// as [`Self::update_transaction`]. The amount is only applied to simple
+    /// drafts: exactly one debit and one credit entry in the same currency
+    /// and rate, matching `currency` when given. Anything else is left for
+    /// the user to adjust, and `amount_applied` is `false`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - Transaction is not found or is not a draft
+    /// - No fiscal period exists for the new date, or it is closed to the user
+    /// - Database operation fails
+    pub async fn prefill_draft(
+        &self,
+        organization_id: OrganizationId,
+        transaction_id: TransactionId,
+        prefill: DraftPrefill,
+    ) -> Result<PrefilledDraft, TransactionError> {
+        let transaction = transactions::Entity::find_by_id(transaction_id)
+            .filter(transactions::Column::OrganizationId.eq(organization_id))
+            .one(&self.db)
+            .await?
+            .ok_or(TransactionError::NotFound(transaction_id.into_inner()))?;
+
+        check_modify(&transaction.status)?;
+        if transaction.status != TransactionStatus::Draft {
+            return Err(TransactionError::NotDraft);
+        }
+
+        let new_date = prefill
+            .transaction_date
+            .filter(|date| *date != transaction.transaction_date);
+        let fiscal_period_id = match new_date {
+            Some(date) => Some(
+                self.resolve_period_for_user(organization_id, date, prefill.updated_by)
+                    .await?,
+            ),
+            None => None,
+        };
+
+        let txn = self.db.begin().await?;
+
+        let mut amount_applied = false;
+        if let Some(amount) = prefill.amount.filter(|a| *a > Decimal::ZERO) {
+            let entries = ledger_entries::Entity::find()
+                .filter(ledger_entries::Column::TransactionId.eq(transaction.id))
+                .all(&txn)
+                .await?;
+            if let [first, second] = entries.as_slice() {
+                let (debit, credit) = if first.debit > Decimal::ZERO {
+                    (first, second)
+                } else {
+                    (second, first)
+                };
+                let simple = debit.credit.is_zero()
+                    && credit.debit.is_zero()
+                    && credit.credit > Decimal::ZERO
+                    && debit.source_currency == credit.source_currency
+                    && debit.exchange_rate == credit.exchange_rate
+                    && prefill
+                        .currency
+                        .as_deref()
+                        .is_none_or(|c| c.eq_ignore_ascii_case(&debit.source_currency));
+                if simple {
+                    let functional = (amount * debit.exchange_rate).round_dp(4);
+                    for (entry, is_debit) in [(debit.clone(), true), (credit.clone(), false)] {
+                        let mut active: ledger_entries::ActiveModel = entry.into();
+                        active.source_amount = Set(amount);
+                        active.functional_amount = Set(functional);
+                        if is_debit {
+                            active.debit = Set(functional);
+                        } else {
+                            active.credit = Set(functional);
+                        }
+                        active.update(&txn).await?;
+                    }
+                    amount_applied = true;
+                }
+            }
+        }
+
+        let mut active: transactions::ActiveModel = transaction.into();
+        if let Some(desc) = prefill.description {
+            active.description = Set(desc);
+        }
+        if let (Some(date), Some(period_id)) = (new_date, fiscal_period_id) {
+            active.transaction_date = Set(date);
+            active.fiscal_period_id = Set(period_id);
+        }
+        active.updated_at = Set(Utc::now().into());
+        let transaction = active.update(&txn).await?;
+        if let Some(date) = new_date {
+            move_entry_events(&txn, transaction.organization_id, transaction.id, date).await?;
+        }
+
+        txn.commit().await?;
+
+        Ok(PrefilledDraft {
+            transaction,
+            amount_applied,
+        })
+    }
+
+    /// Finds the fiscal period for `date` and checks the user may post to it.
+    async fn resolve_period_for_user(
+        &self,
+        organization_id: OrganizationId,
+        date: NaiveDate,
+        user_id: Uuid,
+    ) -> Result<Uuid, TransactionError> {
+        let period = self
+            .find_fiscal_period(organization_id.into_inner(), date)
+            .await?;
+
+        // Someone without a membership gets no more than a viewer
+        let role = organization_users::Entity::find()
+            .filter(organization_users::Column::OrganizationId.eq(organization_id))
+            .filter(organization_users::Column::UserId.eq(user_id))
+            .one(&self.db)
+            .await?
+            .map_or(CoreUserRole::Viewer, |m| db_role_to_core(&m.role));
+
+        validate_posting_permission(&db_period_status_to_core(&period.status), &role).map_err(
+            |e| match e {
+                LedgerError::PeriodSoftClosed => TransactionError::PeriodSoftClosed,
+                _ => TransactionError::PeriodClosed,
+            },
+        )?;
+
+        Ok(period.id)
+    }
+
     /// Deletes a draft transaction.
     ///
     /// Requirements: 10.6, 10.7, 4.2, 4.4
@@ -508,23 +1407,18 @@ impl TransactionRepository {
     /// - Database operation fails
     pub async fn delete_transaction(
         &self,
-        organization_id: Uuid,
-        transaction_id: Uuid,
+        organization_id: OrganizationId,
+        transaction_id: TransactionId,
     ) -> Result<(), TransactionError> {
         // Get existing transaction
         let transaction = transactions::Entity::find_by_id(transaction_id)
             .filter(transactions::Column::OrganizationId.eq(organization_id))
             .one(&self.db)
             .await?
-            .ok_or(TransactionError::NotFound(transaction_id))?;
+            .ok_or(TransactionError::NotFound(transaction_id.into_inner()))?;
 
         // Check status (Requirements 4.2, 4.4, 10.7)
-        match transaction.status {
-            TransactionStatus::Draft => {} // OK to delete
-            TransactionStatus::Posted => return Err(TransactionError::CannotModifyPosted),
-            TransactionStatus::Voided => return Err(TransactionError::CannotModifyVoided),
-            _ => return Err(TransactionError::CanOnlyDeleteDraft),
-        }
+        check_delete(&transaction.status)?;
 
         // Delete transaction (cascade will delete entries and dimensions)
         transactions::Entity::delete_by_id(transaction_id)
@@ -533,12 +1427,187 @@ impl TransactionRepository {
 
         Ok(())
     }
+
+    /// Gets the number of decimal places of each of the given currencies.
+    ///
+    /// Unknown codes are left out of the map.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    pub async fn currency_decimal_places(
+        &self,
+        codes: &[&str],
+    ) -> Result<std::collections::HashMap<String, u32>, TransactionError> {
+        let rows = currencies::Entity::find()
+            .filter(currencies::Column::Code.is_in(codes.iter().copied()))
+            .all(&self.db)
+            .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|c| (c.code, u32::try_from(c.decimal_places).unwrap_or(2)))
+            .collect())
+    }
+
+    // ========================================================================
+    // Deprecated Uuid shims
+    // ========================================================================
+
+    /// Lists transactions for an organization given a raw `Uuid`.
+    ///
+    /// # Errors
+    ///
+    /// See [`Self::list_transactions`].
+    #[deprecated(note = "use `list_transactions` with an `OrganizationId`")]
+    pub async fn list_transactions_by_uuid(
+        &self,
+        organization_id: Uuid,
+        filter: TransactionFilter,
+    ) -> Result<Vec<transactions::Model>, TransactionError> {
+        self.list_transactions(organization_id.into(), filter).await
+    }
+
+    /// Gets a transaction given raw `Uuid`s.
+    ///
+    /// # Errors
+    ///
+    /// See [`Self::get_transaction`].
+    #[deprecated(note = "use `get_transaction` with typed ids")]
+    pub async fn get_transaction_by_uuid(
+        &self,
+        organization_id: Uuid,
+        transaction_id: Uuid,
+    ) -> Result<TransactionWithEntries, TransactionError> {
+        self.get_transaction(organization_id.into(), transaction_id.into())
+            .await
+    }
+
+    /// Updates a draft transaction given raw `Uuid`s.
+    ///
+    /// # Errors
+    ///
+    /// See [`Self::update_transaction`].
+    #[deprecated(note = "use `update_transaction` with typed ids")]
+    pub async fn update_transaction_by_uuid(
+        &self,
+        organization_id: Uuid,
+        transaction_id: Uuid,
+        input: UpdateTransactionInput,
+    ) -> Result<transactions::Model, TransactionError> {
+        self.update_transaction(organization_id.into(), transaction_id.into(), input)
+            .await
+    }
+
+    /// Deletes a draft transaction given raw `Uuid`s.
+    ///
+    /// # Errors
+    ///
+    /// See [`Self::delete_transaction`].
+    #[deprecated(note = "use `delete_transaction` with typed ids")]
+    pub async fn delete_transaction_by_uuid(
+        &self,
+        organization_id: Uuid,
+        transaction_id: Uuid,
+    ) -> Result<(), TransactionError> {
+        self.delete_transaction(organization_id.into(), transaction_id.into())
+            .await
+    }
 }
 
 // ============================================================================
 // Balance Calculation Helpers
 // ============================================================================
 
+/// Checks that an entry may be posted to `account`.
+///
+/// The account must be active and allow direct posting. Accounts in the
+/// entry's functional currency accept entries in any source currency, which
+/// are converted at the entry's exchange rate; accounts in any other currency
+/// hold a foreign balance and only accept entries in that currency.
+fn validate_entry_account(
+    account: &chart_of_accounts::Model,
+    entry: &CreateLedgerEntryInput,
+) -> Result<(), TransactionError> {
+    if !account.is_active {
+        return Err(TransactionError::AccountInactive(account.code.clone()));
+    }
+    if !account.allow_direct_posting {
+        return Err(TransactionError::DirectPostingNotAllowed(
+            account.code.clone(),
+        ));
+    }
+    if account.currency != entry.functional_currency && account.currency != entry.source_currency {
+        return Err(TransactionError::CurrencyMismatch {
+            account: account.code.clone(),
+            account_currency: account.currency.clone(),
+            entry_currency: entry.source_currency.clone(),
+        });
+    }
+    Ok(())
+}
+
+/// Reads an organization's timezone, falling back to UTC for an
+/// unrecognised name.
+async fn organization_timezone<C: ConnectionTrait>(
+    db: &C,
+    organization_id: Uuid,
+) -> Result<Tz, TransactionError> {
+    let timezone: Option<String> = organizations::Entity::find_by_id(organization_id)
+        .select_only()
+        .column(organizations::Column::Timezone)
+        .into_tuple()
+        .one(db)
+        .await?;
+    Ok(timezone
+        .and_then(|name| name.parse().ok())
+        .unwrap_or(Tz::UTC))
+}
+
+/// Event time of an entry that does not give one: `now` for a transaction
+/// dated today in `timezone`, and the start of the day otherwise, so
+/// backdated entries sort by their date rather than when they were entered.
+fn default_event_at(date: NaiveDate, timezone: Tz, now: DateTime<Utc>) -> DateTimeWithTimeZone {
+    if now.with_timezone(&timezone).date_naive() == date {
+        return now.into();
+    }
+    // A DST change can skip midnight; the day then starts an hour later
+    [
+        NaiveTime::MIN,
+        NaiveTime::from_hms_opt(1, 0, 0).unwrap_or(NaiveTime::MIN),
+    ]
+    .into_iter()
+    .find_map(|time| {
+        timezone
+            .from_local_datetime(&date.and_time(time))
+            .earliest()
+    })
+    .map_or_else(
+        || date.and_time(NaiveTime::MIN).and_utc().fixed_offset(),
+        |start| start.fixed_offset(),
+    )
+}
+
+/// Moves the event times of a transaction's entries onto `date`, after the
+/// transaction date changes.
+async fn move_entry_events<C: ConnectionTrait>(
+    db: &C,
+    organization_id: Uuid,
+    transaction_id: Uuid,
+    date: NaiveDate,
+) -> Result<(), TransactionError> {
+    let timezone = organization_timezone(db, organization_id).await?;
+    ledger_entries::Entity::update_many()
+        .col_expr(
+            ledger_entries::Column::EventAt,
+            Expr::value(default_event_at(date, timezone, Utc::now())),
+        )
+        .filter(ledger_entries::Column::TransactionId.eq(transaction_id))
+        .exec(db)
+        .await?;
+    Ok(())
+}
+
 /// Calculates the balance change for an entry based on account type.
 ///
 /// Requirements 8.4, 8.5:
@@ -564,36 +1633,101 @@ pub fn is_debit_normal(account_type: &AccountType) -> bool {
     matches!(account_type, AccountType::Asset | AccountType::Expense)
 }
 
+/// Converts database UserRole to core UserRole.
+pub(crate) fn db_role_to_core(role: &UserRole) -> CoreUserRole {
+    match role {
+        UserRole::Owner => CoreUserRole::Owner,
+        UserRole::Admin => CoreUserRole::Admin,
+        UserRole::Accountant => CoreUserRole::Accountant,
+        UserRole::Approver => CoreUserRole::Approver,
+        UserRole::Viewer => CoreUserRole::Viewer,
+        UserRole::Submitter => CoreUserRole::Submitter,
+    }
+}
+
+/// Converts database FiscalPeriodStatus to core FiscalPeriodStatus.
+fn db_period_status_to_core(status: &FiscalPeriodStatus) -> CorePeriodStatus {
+    match status {
+        FiscalPeriodStatus::Open => CorePeriodStatus::Open,
+        FiscalPeriodStatus::SoftClose => CorePeriodStatus::SoftClose,
+        FiscalPeriodStatus::Closed => CorePeriodStatus::Closed,
+    }
+}
+
 // ============================================================================
-// Transaction Status Validation Helpers
+// Transaction Status Conversions and Guards
 // ============================================================================
 
+impl From<&TransactionStatus> for CoreTransactionStatus {
+    fn from(status: &TransactionStatus) -> Self {
+        match status {
+            TransactionStatus::Draft => Self::Draft,
+            TransactionStatus::Pending => Self::Pending,
+            TransactionStatus::Approved => Self::Approved,
+            TransactionStatus::Posted => Self::Posted,
+            TransactionStatus::Voided => Self::Voided,
+        }
+    }
+}
+
+impl From<TransactionStatus> for CoreTransactionStatus {
+    fn from(status: TransactionStatus) -> Self {
+        Self::from(&status)
+    }
+}
+
+impl From<CoreTransactionStatus> for TransactionStatus {
+    fn from(status: CoreTransactionStatus) -> Self {
+        match status {
+            CoreTransactionStatus::Draft => Self::Draft,
+            CoreTransactionStatus::Pending => Self::Pending,
+            CoreTransactionStatus::Approved => Self::Approved,
+            CoreTransactionStatus::Posted => Self::Posted,
+            CoreTransactionStatus::Voided => Self::Voided,
+        }
+    }
+}
+
+/// Maps a rejection from the core status guards onto the repository error.
+///
+/// The guards only reject with the variants matched here.
+fn guard_error(error: &WorkflowError) -> TransactionError {
+    match error {
+        WorkflowError::CannotModifyVoided => TransactionError::CannotModifyVoided,
+        WorkflowError::CanOnlyDeleteDraft => TransactionError::CanOnlyDeleteDraft,
+        _ => TransactionError::CannotModifyPosted,
+    }
+}
+
+/// Applies [`WorkflowService::validate_modify`] to a stored status.
+fn check_modify(status: &TransactionStatus) -> Result<(), TransactionError> {
+    WorkflowService::validate_modify(status.into()).map_err(|e| guard_error(&e))
+}
+
+/// Applies [`WorkflowService::validate_delete`] to a stored status.
+fn check_delete(status: &TransactionStatus) -> Result<(), TransactionError> {
+    WorkflowService::validate_delete(status.into()).map_err(|e| guard_error(&e))
+}
+
 /// Checks if a transaction status allows modification.
 ///
-/// Property 15: Transaction Immutability
-/// - Posted transactions reject updates (except void)
-/// - Voided transactions reject all modifications
+/// # Errors
 ///
-/// Requirements 13.4, 13.5
+/// Returns `CannotModifyPosted` or `CannotModifyVoided`.
+#[deprecated(note = "use `zeltra_core::workflow::WorkflowService::validate_modify`")]
 pub fn can_modify_transaction(status: &TransactionStatus) -> Result<(), TransactionError> {
-    match status {
-        TransactionStatus::Posted => Err(TransactionError::CannotModifyPosted),
-        TransactionStatus::Voided => Err(TransactionError::CannotModifyVoided),
-        _ => Ok(()),
-    }
+    check_modify(status)
 }
 
 /// Checks if a transaction status allows deletion.
 ///
-/// Property 15: Transaction Immutability
-/// - Only draft transactions can be deleted
+/// # Errors
 ///
-/// Requirements 10.7
+/// Returns `CannotModifyPosted` or `CannotModifyVoided` for immutable
+/// transactions and `CanOnlyDeleteDraft` for other non-drafts.
+#[deprecated(note = "use `zeltra_core::workflow::WorkflowService::validate_delete`")]
 pub fn can_delete_transaction(status: &TransactionStatus) -> Result<(), TransactionError> {
-    match status {
-        TransactionStatus::Draft => Ok(()),
-        _ => Err(TransactionError::CanOnlyDeleteDraft),
-    }
+    check_delete(status)
 }
 
 #[cfg(test)]
@@ -601,6 +1735,28 @@ mod tests {
     use super::*;
     use proptest::prelude::*;
     use rust_decimal_macros::dec;
+    use sea_orm::{ActiveEnum, Iterable};
+
+    #[test]
+    fn test_default_event_at() {
+        let jakarta: Tz = "Asia/Jakarta".parse().unwrap();
+        let date = NaiveDate::from_ymd_opt(2025, 3, 10).unwrap();
+
+        // 2025-03-10 20:00 UTC is already 2025-03-11 in Jakarta
+        let now = Utc.with_ymd_and_hms(2025, 3, 10, 20, 0, 0).unwrap();
+        let backdated = default_event_at(date, jakarta, now);
+        assert_eq!(backdated.to_rfc3339(), "2025-03-10T00:00:00+07:00");
+        assert_eq!(backdated.with_timezone(&jakarta).date_naive(), date);
+
+        let today = NaiveDate::from_ymd_opt(2025, 3, 11).unwrap();
+        assert_eq!(default_event_at(today, jakarta, now), now);
+
+        // Midnight does not exist in Santiago on the day DST starts
+        let santiago: Tz = "America/Santiago".parse().unwrap();
+        let dst = NaiveDate::from_ymd_opt(2024, 9, 8).unwrap();
+        let start = default_event_at(dst, santiago, now);
+        assert_eq!(start.with_timezone(&santiago).date_naive(), dst);
+    }
 
     // ========================================================================
     // Property 2: Account Type Balance Rules
@@ -901,7 +2057,7 @@ mod tests {
         /// **Validates: Requirements 13.4**
         #[test]
         fn prop_posted_rejects_modification(_dummy in 0..100i32) {
-            let result = can_modify_transaction(&TransactionStatus::Posted);
+            let result = check_modify(&TransactionStatus::Posted);
             prop_assert!(result.is_err(), "Posted transactions should reject modifications");
 
             match result {
@@ -917,7 +2073,7 @@ mod tests {
         /// **Validates: Requirements 13.5**
         #[test]
         fn prop_voided_rejects_modification(_dummy in 0..100i32) {
-            let result = can_modify_transaction(&TransactionStatus::Voided);
+            let result = check_modify(&TransactionStatus::Voided);
             prop_assert!(result.is_err(), "Voided transactions should reject modifications");
 
             match result {
@@ -935,7 +2091,7 @@ mod tests {
         fn prop_immutable_statuses_reject_modification(
             status in immutable_status_strategy(),
         ) {
-            let result = can_modify_transaction(&status);
+            let result = check_modify(&status);
             prop_assert!(result.is_err(), "Immutable statuses should reject modifications");
         }
 
@@ -948,7 +2104,7 @@ mod tests {
         fn prop_modifiable_statuses_allow_modification(
             status in modifiable_status_strategy(),
         ) {
-            let result = can_modify_transaction(&status);
+            let result = check_modify(&status);
             prop_assert!(result.is_ok(), "Modifiable statuses should allow modifications");
         }
 
@@ -959,7 +2115,7 @@ mod tests {
         /// **Validates: Requirements 10.6**
         #[test]
         fn prop_draft_allows_deletion(_dummy in 0..100i32) {
-            let result = can_delete_transaction(&TransactionStatus::Draft);
+            let result = check_delete(&TransactionStatus::Draft);
             prop_assert!(result.is_ok(), "Draft transactions should allow deletion");
         }
 
@@ -972,12 +2128,17 @@ mod tests {
         fn prop_non_draft_rejects_deletion(
             status in non_draft_status_strategy(),
         ) {
-            let result = can_delete_transaction(&status);
+            let result = check_delete(&status);
             prop_assert!(result.is_err(), "Non-draft transactions should reject deletion");
 
-            match result {
-                Err(TransactionError::CanOnlyDeleteDraft) => {},
-                _ => prop_assert!(false, "Should return CanOnlyDeleteDraft error"),
+            match (&status, result) {
+                (TransactionStatus::Posted, Err(TransactionError::CannotModifyPosted))
+                | (TransactionStatus::Voided, Err(TransactionError::CannotModifyVoided))
+                | (
+                    TransactionStatus::Pending | TransactionStatus::Approved,
+                    Err(TransactionError::CanOnlyDeleteDraft),
+                ) => {},
+                (_, other) => prop_assert!(false, "Unexpected result for {:?}: {:?}", status, other),
             }
         }
 
@@ -993,8 +2154,8 @@ mod tests {
         fn prop_status_rules_consistent(
             status in transaction_status_strategy(),
         ) {
-            let can_mod = can_modify_transaction(&status).is_ok();
-            let can_del = can_delete_transaction(&status).is_ok();
+            let can_mod = check_modify(&status).is_ok();
+            let can_del = check_delete(&status).is_ok();
 
             match status {
                 TransactionStatus::Draft => {
@@ -1019,41 +2180,56 @@ mod tests {
 
     #[test]
     fn test_can_modify_draft() {
-        assert!(can_modify_transaction(&TransactionStatus::Draft).is_ok());
+        assert!(check_modify(&TransactionStatus::Draft).is_ok());
     }
 
     #[test]
     fn test_can_modify_pending() {
-        assert!(can_modify_transaction(&TransactionStatus::Pending).is_ok());
+        assert!(check_modify(&TransactionStatus::Pending).is_ok());
     }
 
     #[test]
     fn test_can_modify_approved() {
-        assert!(can_modify_transaction(&TransactionStatus::Approved).is_ok());
+        assert!(check_modify(&TransactionStatus::Approved).is_ok());
     }
 
     #[test]
     fn test_cannot_modify_posted() {
-        let result = can_modify_transaction(&TransactionStatus::Posted);
+        let result = check_modify(&TransactionStatus::Posted);
         assert!(matches!(result, Err(TransactionError::CannotModifyPosted)));
     }
 
     #[test]
     fn test_cannot_modify_voided() {
-        let result = can_modify_transaction(&TransactionStatus::Voided);
+        let result = check_modify(&TransactionStatus::Voided);
         assert!(matches!(result, Err(TransactionError::CannotModifyVoided)));
     }
 
+    #[test]
+    fn test_status_round_trips_through_core() {
+        for status in TransactionStatus::iter() {
+            let core = CoreTransactionStatus::from(&status);
+            assert_eq!(core.as_str(), status.to_value());
+            assert_eq!(TransactionStatus::from(core), status);
+        }
+        for core in CoreTransactionStatus::ALL {
+            assert_eq!(
+                CoreTransactionStatus::from(TransactionStatus::from(core)),
+                core
+            );
+        }
+    }
+
     #[test]
     fn test_can_delete_draft() {
-        assert!(can_delete_transaction(&TransactionStatus::Draft).is_ok());
+        assert!(check_delete(&TransactionStatus::Draft).is_ok());
     }
 
     #[test]
     fn test_cannot_delete_non_draft() {
-        assert!(can_delete_transaction(&TransactionStatus::Pending).is_err());
-        assert!(can_delete_transaction(&TransactionStatus::Approved).is_err());
-        assert!(can_delete_transaction(&TransactionStatus::Posted).is_err());
-        assert!(can_delete_transaction(&TransactionStatus::Voided).is_err());
+        assert!(check_delete(&TransactionStatus::Pending).is_err());
+        assert!(check_delete(&TransactionStatus::Approved).is_err());
+        assert!(check_delete(&TransactionStatus::Posted).is_err());
+        assert!(check_delete(&TransactionStatus::Voided).is_err());
     }
 }
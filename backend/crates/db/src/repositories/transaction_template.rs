@@ -0,0 +1,536 @@
+//! Transaction template repository.
+//!
+//! Templates are private to the user who saved them: every lookup is scoped
+//! to the organization and the owning user, so other members see a template
+//! as not found.
+
+use std::collections::HashMap;
+
+use chrono::Utc;
+use rust_decimal::Decimal;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, ModelTrait, PaginatorTrait,
+    QueryFilter, QueryOrder, Set, TransactionTrait,
+};
+use thiserror::Error;
+use uuid::Uuid;
+
+use zeltra_core::ledger::types::EntryType as CoreEntryType;
+use zeltra_core::template::{LineAmount, TemplateError, TemplateLine, TemplateService};
+
+use crate::entities::{
+    chart_of_accounts, currencies,
+    sea_orm_active_enums::{EntryType, TransactionType},
+    transaction_template_lines, transaction_templates,
+};
+
+/// Errors that can occur during transaction template operations.
+#[derive(Debug, Error)]
+pub enum TransactionTemplateError {
+    /// Template not found.
+    #[error("Transaction template {0} not found")]
+    NotFound(Uuid),
+
+    /// The user already has a template with this name.
+    #[error("A template named '{0}' already exists")]
+    DuplicateName(String),
+
+    /// A line has both a fixed amount and a percentage.
+    #[error("Line {0}: set either an amount or a percentage, not both")]
+    AmountAndPercentage(usize),
+
+    /// The entry skeleton is invalid.
+    #[error(transparent)]
+    Template(#[from] TemplateError),
+
+    /// An account does not exist in the organization.
+    #[error("Account not found: {0}")]
+    AccountNotFound(Uuid),
+
+    /// An account has been deactivated since the template was saved.
+    #[error("Account {code} is inactive; update the template before using it")]
+    InactiveAccount {
+        /// Code of the inactive account.
+        code: String,
+    },
+
+    /// The currency does not exist.
+    #[error("Currency not found: {0}")]
+    CurrencyNotFound(String),
+
+    /// Database error.
+    #[error("Database error: {0}")]
+    Database(#[from] sea_orm::DbErr),
+}
+
+/// Input for a template line.
+#[derive(Debug, Clone)]
+pub struct TemplateLineInput {
+    /// Account ID.
+    pub account_id: Uuid,
+    /// Debit or credit.
+    pub entry_type: EntryType,
+    /// Fixed amount used every time.
+    pub amount: Option<Decimal>,
+    /// Share of the total given at instantiation (0-100).
+    pub percentage: Option<Decimal>,
+    /// Optional memo.
+    pub memo: Option<String>,
+    /// Dimension value IDs.
+    pub dimensions: Vec<Uuid>,
+}
+
+/// Input for creating a transaction template.
+#[derive(Debug, Clone)]
+pub struct CreateTransactionTemplateInput {
+    /// User who owns the template.
+    pub user_id: Uuid,
+    /// Template name, unique per user.
+    pub name: String,
+    /// Transaction type.
+    pub transaction_type: TransactionType,
+    /// Transaction description.
+    pub description: String,
+    /// Optional transaction memo.
+    pub memo: Option<String>,
+    /// Currency of every line.
+    pub currency: String,
+    /// Entry skeleton.
+    pub lines: Vec<TemplateLineInput>,
+}
+
+/// Input for updating a transaction template.
+#[derive(Debug, Clone, Default)]
+pub struct UpdateTransactionTemplateInput {
+    /// New name.
+    pub name: Option<String>,
+    /// New transaction type.
+    pub transaction_type: Option<TransactionType>,
+    /// New description.
+    pub description: Option<String>,
+    /// New memo.
+    pub memo: Option<Option<String>>,
+    /// New currency.
+    pub currency: Option<String>,
+    /// Replacement entry skeleton.
+    pub lines: Option<Vec<TemplateLineInput>>,
+}
+
+/// Transaction template with its lines in order.
+#[derive(Debug, Clone)]
+pub struct TemplateWithLines {
+    /// Template.
+    pub template: transaction_templates::Model,
+    /// Lines ordered by line number.
+    pub lines: Vec<transaction_template_lines::Model>,
+}
+
+impl TemplateWithLines {
+    /// Returns the lines as core template lines.
+    #[must_use]
+    pub fn core_lines(&self) -> Vec<TemplateLine> {
+        self.lines
+            .iter()
+            .map(|line| {
+                core_line(
+                    line.account_id,
+                    &line.entry_type,
+                    line.amount,
+                    line.percentage,
+                )
+            })
+            .collect()
+    }
+}
+
+/// Repository for transaction template operations.
+#[derive(Debug, Clone)]
+pub struct TransactionTemplateRepository {
+    db: DatabaseConnection,
+}
+
+impl TransactionTemplateRepository {
+    /// Creates a new transaction template repository.
+    #[must_use]
+    pub const fn new(db: DatabaseConnection) -> Self {
+        Self { db }
+    }
+
+    /// Creates a transaction template.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the name is taken, the skeleton is invalid, an
+    /// account or the currency does not exist, or the database operation fails.
+    pub async fn create_template(
+        &self,
+        organization_id: Uuid,
+        input: CreateTransactionTemplateInput,
+    ) -> Result<TemplateWithLines, TransactionTemplateError> {
+        self.check_name_available(organization_id, input.user_id, &input.name, None)
+            .await?;
+        self.check_currency(&input.currency).await?;
+        self.validate_lines(organization_id, &input.lines).await?;
+
+        let now = Utc::now().into();
+        let txn = self.db.begin().await?;
+
+        let template = transaction_templates::ActiveModel {
+            id: Set(Uuid::new_v4()),
+            organization_id: Set(organization_id),
+            user_id: Set(input.user_id),
+            name: Set(input.name),
+            transaction_type: Set(input.transaction_type),
+            description: Set(input.description),
+            memo: Set(input.memo),
+            currency: Set(input.currency),
+            created_at: Set(now),
+            updated_at: Set(now),
+        }
+        .insert(&txn)
+        .await?;
+
+        let lines = insert_lines(&txn, &template, input.lines).await?;
+        txn.commit().await?;
+
+        Ok(TemplateWithLines { template, lines })
+    }
+
+    /// Lists a user's templates, ordered by name.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    pub async fn list_templates(
+        &self,
+        organization_id: Uuid,
+        user_id: Uuid,
+    ) -> Result<Vec<TemplateWithLines>, TransactionTemplateError> {
+        let templates = transaction_templates::Entity::find()
+            .filter(transaction_templates::Column::OrganizationId.eq(organization_id))
+            .filter(transaction_templates::Column::UserId.eq(user_id))
+            .order_by_asc(transaction_templates::Column::Name)
+            .all(&self.db)
+            .await?;
+
+        let mut lines_by_template: HashMap<Uuid, Vec<transaction_template_lines::Model>> =
+            HashMap::new();
+        for line in transaction_template_lines::Entity::find()
+            .filter(
+                transaction_template_lines::Column::TemplateId
+                    .is_in(templates.iter().map(|t| t.id)),
+            )
+            .order_by_asc(transaction_template_lines::Column::LineNumber)
+            .all(&self.db)
+            .await?
+        {
+            lines_by_template
+                .entry(line.template_id)
+                .or_default()
+                .push(line);
+        }
+
+        Ok(templates
+            .into_iter()
+            .map(|template| TemplateWithLines {
+                lines: lines_by_template.remove(&template.id).unwrap_or_default(),
+                template,
+            })
+            .collect())
+    }
+
+    /// Gets one of a user's templates.
+    ///
+    /// # Errors
+    ///
+    /// Returns `NotFound` if the template does not exist or belongs to
+    /// another user.
+    pub async fn get_template(
+        &self,
+        organization_id: Uuid,
+        user_id: Uuid,
+        template_id: Uuid,
+    ) -> Result<TemplateWithLines, TransactionTemplateError> {
+        let template = self
+            .find_template(organization_id, user_id, template_id)
+            .await?;
+        let lines = template
+            .find_related(transaction_template_lines::Entity)
+            .order_by_asc(transaction_template_lines::Column::LineNumber)
+            .all(&self.db)
+            .await?;
+
+        Ok(TemplateWithLines { template, lines })
+    }
+
+    /// Updates a template. New lines replace all existing lines.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the template does not exist, the new name is
+    /// taken, the new skeleton is invalid, or the database operation fails.
+    pub async fn update_template(
+        &self,
+        organization_id: Uuid,
+        user_id: Uuid,
+        template_id: Uuid,
+        input: UpdateTransactionTemplateInput,
+    ) -> Result<TemplateWithLines, TransactionTemplateError> {
+        let existing = self
+            .find_template(organization_id, user_id, template_id)
+            .await?;
+
+        if let Some(name) = &input.name {
+            self.check_name_available(organization_id, user_id, name, Some(template_id))
+                .await?;
+        }
+        if let Some(currency) = &input.currency {
+            self.check_currency(currency).await?;
+        }
+        if let Some(lines) = &input.lines {
+            self.validate_lines(organization_id, lines).await?;
+        }
+
+        let txn = self.db.begin().await?;
+
+        let mut active: transaction_templates::ActiveModel = existing.into();
+        if let Some(name) = input.name {
+            active.name = Set(name);
+        }
+        if let Some(transaction_type) = input.transaction_type {
+            active.transaction_type = Set(transaction_type);
+        }
+        if let Some(description) = input.description {
+            active.description = Set(description);
+        }
+        if let Some(memo) = input.memo {
+            active.memo = Set(memo);
+        }
+        if let Some(currency) = input.currency {
+            active.currency = Set(currency);
+        }
+        active.updated_at = Set(Utc::now().into());
+        let template = active.update(&txn).await?;
+
+        let lines = if let Some(lines) = input.lines {
+            transaction_template_lines::Entity::delete_many()
+                .filter(transaction_template_lines::Column::TemplateId.eq(template.id))
+                .exec(&txn)
+                .await?;
+            insert_lines(&txn, &template, lines).await?
+        } else {
+            template
+                .find_related(transaction_template_lines::Entity)
+                .order_by_asc(transaction_template_lines::Column::LineNumber)
+                .all(&txn)
+                .await?
+        };
+
+        txn.commit().await?;
+
+        Ok(TemplateWithLines { template, lines })
+    }
+
+    /// Deletes a template.
+    ///
+    /// # Errors
+    ///
+    /// Returns `NotFound` if the template does not exist or belongs to
+    /// another user.
+    pub async fn delete_template(
+        &self,
+        organization_id: Uuid,
+        user_id: Uuid,
+        template_id: Uuid,
+    ) -> Result<(), TransactionTemplateError> {
+        let existing = self
+            .find_template(organization_id, user_id, template_id)
+            .await?;
+        existing.delete(&self.db).await?;
+        Ok(())
+    }
+
+    /// Checks that every account a template uses is still active.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InactiveAccount` naming the first deactivated account, or
+    /// `AccountNotFound` if an account no longer exists.
+    pub async fn check_accounts_active(
+        &self,
+        organization_id: Uuid,
+        template: &TemplateWithLines,
+    ) -> Result<(), TransactionTemplateError> {
+        let accounts = self
+            .find_accounts(organization_id, template.lines.iter().map(|l| l.account_id))
+            .await?;
+
+        for line in &template.lines {
+            let account = accounts
+                .get(&line.account_id)
+                .ok_or(TransactionTemplateError::AccountNotFound(line.account_id))?;
+            if !account.is_active {
+                return Err(TransactionTemplateError::InactiveAccount {
+                    code: account.code.clone(),
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// Gets the number of decimal places of a currency.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CurrencyNotFound` if the currency does not exist.
+    pub async fn currency_decimal_places(
+        &self,
+        currency: &str,
+    ) -> Result<u32, TransactionTemplateError> {
+        let currency = self.check_currency(currency).await?;
+        Ok(u32::try_from(currency.decimal_places).unwrap_or(2))
+    }
+
+    async fn find_template(
+        &self,
+        organization_id: Uuid,
+        user_id: Uuid,
+        template_id: Uuid,
+    ) -> Result<transaction_templates::Model, TransactionTemplateError> {
+        transaction_templates::Entity::find_by_id(template_id)
+            .filter(transaction_templates::Column::OrganizationId.eq(organization_id))
+            .filter(transaction_templates::Column::UserId.eq(user_id))
+            .one(&self.db)
+            .await?
+            .ok_or(TransactionTemplateError::NotFound(template_id))
+    }
+
+    async fn check_name_available(
+        &self,
+        organization_id: Uuid,
+        user_id: Uuid,
+        name: &str,
+        exclude_id: Option<Uuid>,
+    ) -> Result<(), TransactionTemplateError> {
+        let mut query = transaction_templates::Entity::find()
+            .filter(transaction_templates::Column::OrganizationId.eq(organization_id))
+            .filter(transaction_templates::Column::UserId.eq(user_id))
+            .filter(transaction_templates::Column::Name.eq(name));
+        if let Some(id) = exclude_id {
+            query = query.filter(transaction_templates::Column::Id.ne(id));
+        }
+
+        if query.count(&self.db).await? > 0 {
+            return Err(TransactionTemplateError::DuplicateName(name.to_string()));
+        }
+        Ok(())
+    }
+
+    async fn check_currency(
+        &self,
+        code: &str,
+    ) -> Result<currencies::Model, TransactionTemplateError> {
+        currencies::Entity::find_by_id(code)
+            .one(&self.db)
+            .await?
+            .ok_or_else(|| TransactionTemplateError::CurrencyNotFound(code.to_string()))
+    }
+
+    /// Checks the skeleton's amounts and that its accounts exist.
+    async fn validate_lines(
+        &self,
+        organization_id: Uuid,
+        lines: &[TemplateLineInput],
+    ) -> Result<(), TransactionTemplateError> {
+        if let Some(i) = lines
+            .iter()
+            .position(|l| l.amount.is_some() && l.percentage.is_some())
+        {
+            return Err(TransactionTemplateError::AmountAndPercentage(i + 1));
+        }
+
+        let core_lines: Vec<TemplateLine> = lines
+            .iter()
+            .map(|line| {
+                core_line(
+                    line.account_id,
+                    &line.entry_type,
+                    line.amount,
+                    line.percentage,
+                )
+            })
+            .collect();
+        TemplateService::validate_lines(&core_lines)?;
+
+        let accounts = self
+            .find_accounts(organization_id, lines.iter().map(|l| l.account_id))
+            .await?;
+        if let Some(line) = lines.iter().find(|l| !accounts.contains_key(&l.account_id)) {
+            return Err(TransactionTemplateError::AccountNotFound(line.account_id));
+        }
+        Ok(())
+    }
+
+    async fn find_accounts(
+        &self,
+        organization_id: Uuid,
+        account_ids: impl Iterator<Item = Uuid>,
+    ) -> Result<HashMap<Uuid, chart_of_accounts::Model>, TransactionTemplateError> {
+        Ok(chart_of_accounts::Entity::find()
+            .filter(chart_of_accounts::Column::OrganizationId.eq(organization_id))
+            .filter(chart_of_accounts::Column::Id.is_in(account_ids))
+            .all(&self.db)
+            .await?
+            .into_iter()
+            .map(|a| (a.id, a))
+            .collect())
+    }
+}
+
+/// Converts a stored or requested line to a core template line.
+fn core_line(
+    account_id: Uuid,
+    entry_type: &EntryType,
+    amount: Option<Decimal>,
+    percentage: Option<Decimal>,
+) -> TemplateLine {
+    TemplateLine {
+        account_id,
+        entry_type: match entry_type {
+            EntryType::Debit => CoreEntryType::Debit,
+            EntryType::Credit => CoreEntryType::Credit,
+        },
+        amount: match (amount, percentage) {
+            (Some(amount), _) => LineAmount::Fixed(amount),
+            (None, Some(pct)) => LineAmount::Percentage(pct),
+            (None, None) => LineAmount::Open,
+        },
+    }
+}
+
+/// Inserts a template's lines, numbered from 1.
+async fn insert_lines<C: sea_orm::ConnectionTrait>(
+    db: &C,
+    template: &transaction_templates::Model,
+    lines: Vec<TemplateLineInput>,
+) -> Result<Vec<transaction_template_lines::Model>, TransactionTemplateError> {
+    let mut inserted = Vec::with_capacity(lines.len());
+    for (line_number, line) in (1_i16..).zip(lines) {
+        let model = transaction_template_lines::ActiveModel {
+            id: Set(Uuid::new_v4()),
+            organization_id: Set(template.organization_id),
+            template_id: Set(template.id),
+            line_number: Set(line_number),
+            account_id: Set(line.account_id),
+            entry_type: Set(line.entry_type),
+            amount: Set(line.amount),
+            percentage: Set(line.percentage),
+            memo: Set(line.memo),
+            dimension_value_ids: Set(line.dimensions),
+        }
+        .insert(db)
+        .await?;
+        inserted.push(model);
+    }
+    Ok(inserted)
+}
@@ -1,12 +1,73 @@
 //! User repository for database operations.
 
 use sea_orm::{
-    ActiveModelTrait, ColumnTrait, DatabaseConnection, DbErr, EntityTrait, PaginatorTrait,
-    QueryFilter, Set,
+    ActiveModelTrait, ColumnTrait, ConnectionTrait, DatabaseConnection, DbErr, EntityTrait,
+    PaginatorTrait, QueryFilter, QuerySelect, Set, TransactionTrait, sea_query::Expr,
 };
+use thiserror::Error;
 use uuid::Uuid;
 
-use crate::entities::{organization_users, organizations, users};
+use zeltra_core::auth::is_supported_locale;
+
+use crate::entities::{
+    api_keys, budget_editors, email_verification_tokens, notification_preferences, notifications,
+    organization_users, organizations, sea_orm_active_enums::UserRole, sessions, users,
+};
+
+/// The user that automatic changes, such as periods closed by the auto-close
+/// job, are attributed to. It is inactive and belongs to no organization.
+pub const SYSTEM_USER_ID: Uuid = Uuid::nil();
+
+/// Errors from user profile operations.
+#[derive(Debug, Error)]
+pub enum UserError {
+    /// User not found.
+    #[error("User {0} not found")]
+    NotFound(Uuid),
+
+    /// Full name is empty.
+    #[error("Full name cannot be empty")]
+    EmptyFullName,
+
+    /// Locale is not one of the supported locales.
+    #[error("Unsupported locale: {0}")]
+    UnsupportedLocale(String),
+
+    /// The user is not a member of the organization.
+    #[error("User is not a member of organization {0}")]
+    NotMember(Uuid),
+
+    /// The user is the only owner of these organizations, by slug, so
+    /// removing their memberships would leave them without one.
+    #[error("User is the only owner of: {}", .0.join(", "))]
+    SoleOwner(Vec<String>),
+
+    /// Database error.
+    #[error("Database error: {0}")]
+    Database(#[from] DbErr),
+}
+
+/// Result of [`UserRepository::anonymize`].
+#[derive(Debug, Clone)]
+pub struct AnonymizedUser {
+    /// The user as left behind, with placeholder email and name.
+    pub user: users::Model,
+    /// Organization memberships removed.
+    pub memberships_removed: u64,
+    /// Sessions deleted, with the addresses and user agents they recorded.
+    pub sessions_deleted: u64,
+}
+
+/// Profile fields to change; `None` leaves a field as it is.
+#[derive(Debug, Clone, Default)]
+pub struct UpdateProfileInput {
+    /// New display name.
+    pub full_name: Option<String>,
+    /// New locale, one of `zeltra_core::auth::SUPPORTED_LOCALES`.
+    pub locale: Option<String>,
+    /// Organization to load after login; the user must belong to it.
+    pub default_organization_id: Option<Uuid>,
+}
 
 /// User repository for CRUD operations.
 #[derive(Debug, Clone)]
@@ -61,13 +122,72 @@ impl UserRepository {
             full_name: Set(full_name.to_string()),
             is_active: Set(true),
             email_verified_at: Set(None),
+            token_version: Set(0),
             created_at: Set(now),
             updated_at: Set(now),
+            locale: Set(None),
+            default_organization_id: Set(None),
         };
 
         user.insert(&self.db).await
     }
 
+    /// Updates a user's display name, locale and default organization.
+    ///
+    /// Email is not changed here; an address change is confirmed through
+    /// [`EmailVerificationRepository::create_email_change_token`].
+    ///
+    /// [`EmailVerificationRepository::create_email_change_token`]:
+    ///     super::EmailVerificationRepository::create_email_change_token
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - The user is not found
+    /// - The full name is blank or the locale is unsupported
+    /// - The user is not a member of the default organization
+    /// - Database operation fails
+    pub async fn update_profile(
+        &self,
+        user_id: Uuid,
+        input: UpdateProfileInput,
+    ) -> Result<users::Model, UserError> {
+        let user = users::Entity::find_by_id(user_id)
+            .one(&self.db)
+            .await?
+            .ok_or(UserError::NotFound(user_id))?;
+
+        let mut active: users::ActiveModel = user.into();
+        if let Some(full_name) = input.full_name {
+            let full_name = full_name.trim();
+            if full_name.is_empty() {
+                return Err(UserError::EmptyFullName);
+            }
+            active.full_name = Set(full_name.to_string());
+        }
+        if let Some(locale) = input.locale {
+            if !is_supported_locale(&locale) {
+                return Err(UserError::UnsupportedLocale(locale));
+            }
+            active.locale = Set(Some(locale));
+        }
+        if let Some(org_id) = input.default_organization_id {
+            let is_member = organization_users::Entity::find()
+                .filter(organization_users::Column::UserId.eq(user_id))
+                .filter(organization_users::Column::OrganizationId.eq(org_id))
+                .count(&self.db)
+                .await?
+                > 0;
+            if !is_member {
+                return Err(UserError::NotMember(org_id));
+            }
+            active.default_organization_id = Set(Some(org_id));
+        }
+        active.updated_at = Set(chrono::Utc::now().into());
+
+        Ok(active.update(&self.db).await?)
+    }
+
     /// Gets all organizations for a user with their roles.
     ///
     /// # Errors
@@ -121,4 +241,243 @@ impl UserRepository {
 
         Ok(count > 0)
     }
+
+    /// Returns the current token version of a user, or `None` if the user
+    /// does not exist.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    pub async fn get_token_version(&self, user_id: Uuid) -> Result<Option<i32>, DbErr> {
+        users::Entity::find_by_id(user_id)
+            .select_only()
+            .column(users::Column::TokenVersion)
+            .into_tuple()
+            .one(&self.db)
+            .await
+    }
+
+    /// Increments a user's token version, invalidating every access token
+    /// issued to them so far.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database update fails.
+    pub async fn bump_token_version(&self, user_id: Uuid) -> Result<(), DbErr> {
+        bump_token_version(&self.db, user_id).await
+    }
+
+    /// Re-enables a disabled account so the user can log in again.
+    ///
+    /// Returns false when the account was not disabled.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database update fails.
+    pub async fn reactivate(&self, user_id: Uuid) -> Result<bool, DbErr> {
+        let result = users::Entity::update_many()
+            .col_expr(users::Column::IsActive, Expr::value(true))
+            .filter(users::Column::Id.eq(user_id))
+            .filter(users::Column::IsActive.eq(false))
+            .exec(&self.db)
+            .await?;
+
+        Ok(result.rows_affected > 0)
+    }
+
+    /// Disables an account: login is refused, every session and API key the
+    /// user created is revoked and access tokens issued so far stop working.
+    ///
+    /// Returns false when the account was already disabled; its sessions and
+    /// keys are revoked either way.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the user is not found or the database update
+    /// fails.
+    pub async fn deactivate(&self, user_id: Uuid) -> Result<bool, UserError> {
+        let txn = self.db.begin().await?;
+        let user = users::Entity::find_by_id(user_id)
+            .one(&txn)
+            .await?
+            .ok_or(UserError::NotFound(user_id))?;
+
+        if user.is_active {
+            users::Entity::update_many()
+                .col_expr(users::Column::IsActive, Expr::value(false))
+                .filter(users::Column::Id.eq(user_id))
+                .exec(&txn)
+                .await?;
+        }
+        let now = chrono::Utc::now();
+        sessions::Entity::update_many()
+            .col_expr(sessions::Column::RevokedAt, Expr::value(now))
+            .col_expr(sessions::Column::UpdatedAt, Expr::value(now))
+            .filter(sessions::Column::UserId.eq(user_id))
+            .filter(sessions::Column::RevokedAt.is_null())
+            .exec(&txn)
+            .await?;
+        revoke_api_keys(&txn, user_id).await?;
+        bump_token_version(&txn, user_id).await?;
+        txn.commit().await?;
+
+        Ok(user.is_active)
+    }
+
+    /// Slugs of the organizations the user is the only owner of, sorted.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    pub async fn sole_owner_of(&self, user_id: Uuid) -> Result<Vec<String>, DbErr> {
+        sole_owner_of(&self.db, user_id).await
+    }
+
+    /// Erases a user's personal data while keeping the user row, so the
+    /// transactions, approvals and comments that reference them stay valid.
+    ///
+    /// The email and name are replaced by placeholders derived from the ID
+    /// (`deleted-user-1a2b3c4d@invalid`, `Deleted user 1a2b3c4d`), the
+    /// password hash and profile are cleared and the account is disabled.
+    /// Sessions, email verification tokens, notifications and organization
+    /// memberships are deleted, and API keys the user created are revoked.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - The user is not found
+    /// - The user is the only owner of an organization
+    /// - Database operation fails
+    pub async fn anonymize(&self, user_id: Uuid) -> Result<AnonymizedUser, UserError> {
+        let txn = self.db.begin().await?;
+        users::Entity::find_by_id(user_id)
+            .one(&txn)
+            .await?
+            .ok_or(UserError::NotFound(user_id))?;
+
+        let sole_owner = sole_owner_of(&txn, user_id).await?;
+        if !sole_owner.is_empty() {
+            return Err(UserError::SoleOwner(sole_owner));
+        }
+
+        bump_token_version(&txn, user_id).await?;
+        revoke_api_keys(&txn, user_id).await?;
+        let short_id = &user_id.simple().to_string()[..8];
+        let user = users::ActiveModel {
+            id: Set(user_id),
+            email: Set(format!("deleted-user-{short_id}@invalid")),
+            password_hash: Set(String::new()),
+            full_name: Set(format!("Deleted user {short_id}")),
+            is_active: Set(false),
+            email_verified_at: Set(None),
+            locale: Set(None),
+            default_organization_id: Set(None),
+            ..Default::default()
+        }
+        .update(&txn)
+        .await?;
+
+        let sessions_deleted = sessions::Entity::delete_many()
+            .filter(sessions::Column::UserId.eq(user_id))
+            .exec(&txn)
+            .await?
+            .rows_affected;
+        email_verification_tokens::Entity::delete_many()
+            .filter(email_verification_tokens::Column::UserId.eq(user_id))
+            .exec(&txn)
+            .await?;
+        notifications::Entity::delete_many()
+            .filter(notifications::Column::UserId.eq(user_id))
+            .exec(&txn)
+            .await?;
+        notification_preferences::Entity::delete_many()
+            .filter(notification_preferences::Column::UserId.eq(user_id))
+            .exec(&txn)
+            .await?;
+        budget_editors::Entity::delete_many()
+            .filter(budget_editors::Column::UserId.eq(user_id))
+            .exec(&txn)
+            .await?;
+        let memberships_removed = organization_users::Entity::delete_many()
+            .filter(organization_users::Column::UserId.eq(user_id))
+            .exec(&txn)
+            .await?
+            .rows_affected;
+        txn.commit().await?;
+
+        Ok(AnonymizedUser {
+            user,
+            memberships_removed,
+            sessions_deleted,
+        })
+    }
+}
+
+/// Slugs of the organizations `user_id` is the only owner of, sorted.
+async fn sole_owner_of<C: ConnectionTrait>(db: &C, user_id: Uuid) -> Result<Vec<String>, DbErr> {
+    let owned: Vec<Uuid> = organization_users::Entity::find()
+        .select_only()
+        .column(organization_users::Column::OrganizationId)
+        .filter(organization_users::Column::UserId.eq(user_id))
+        .filter(organization_users::Column::Role.eq(UserRole::Owner))
+        .into_tuple()
+        .all(db)
+        .await?;
+
+    let mut sole = Vec::new();
+    for organization_id in owned {
+        let owners = organization_users::Entity::find()
+            .filter(organization_users::Column::OrganizationId.eq(organization_id))
+            .filter(organization_users::Column::Role.eq(UserRole::Owner))
+            .count(db)
+            .await?;
+        if owners == 1 {
+            sole.push(organization_id);
+        }
+    }
+    if sole.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let mut slugs: Vec<String> = organizations::Entity::find()
+        .select_only()
+        .column(organizations::Column::Slug)
+        .filter(organizations::Column::Id.is_in(sole))
+        .into_tuple()
+        .all(db)
+        .await?;
+    slugs.sort();
+    Ok(slugs)
+}
+
+/// Revokes the API keys `user_id` created that are still active.
+///
+/// Keys authenticate as their creator, so they must not outlive the
+/// creator's access.
+async fn revoke_api_keys<C: ConnectionTrait>(db: &C, user_id: Uuid) -> Result<(), DbErr> {
+    api_keys::Entity::update_many()
+        .col_expr(api_keys::Column::RevokedAt, Expr::value(chrono::Utc::now()))
+        .filter(api_keys::Column::CreatedBy.eq(user_id))
+        .filter(api_keys::Column::RevokedAt.is_null())
+        .exec(db)
+        .await?;
+
+    Ok(())
+}
+
+/// Increments a user's token version on the given connection.
+pub(crate) async fn bump_token_version<C: ConnectionTrait>(
+    db: &C,
+    user_id: Uuid,
+) -> Result<(), DbErr> {
+    users::Entity::update_many()
+        .col_expr(
+            users::Column::TokenVersion,
+            Expr::col(users::Column::TokenVersion).add(1),
+        )
+        .filter(users::Column::Id.eq(user_id))
+        .exec(db)
+        .await?;
+
+    Ok(())
 }
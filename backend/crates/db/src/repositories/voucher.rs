@@ -0,0 +1,231 @@
+//! Printable journal vouchers.
+//!
+//! Gathers what a voucher prints about a transaction: the organization, the
+//! accounts and dimension tags of its entries, and who submitted, approved,
+//! posted and voided it. Layout and rendering live in
+//! [`zeltra_core::reports::voucher`].
+
+use std::collections::{HashMap, HashSet};
+
+use chrono::Utc;
+use sea_orm::{
+    ActiveEnum, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, QueryOrder,
+    prelude::DateTimeWithTimeZone,
+};
+use uuid::Uuid;
+use zeltra_core::reports::voucher::{
+    Voucher, VoucherLine, VoucherSignature, VoucherStep, VoucherTag,
+};
+use zeltra_core::settings::OrganizationSettings;
+use zeltra_core::workflow::TransactionStatus as CoreTransactionStatus;
+use zeltra_shared::types::{OrganizationId, TransactionId};
+
+use crate::entities::{
+    chart_of_accounts, currencies, dimension_types, dimension_values, entry_dimensions,
+    ledger_entries, organizations, transaction_approvals, transactions, users,
+};
+
+use super::transaction::TransactionError;
+
+/// Voucher repository.
+#[derive(Debug, Clone)]
+pub struct VoucherRepository {
+    db: DatabaseConnection,
+}
+
+impl VoucherRepository {
+    /// Creates a new voucher repository.
+    #[must_use]
+    pub const fn new(db: DatabaseConnection) -> Self {
+        Self { db }
+    }
+
+    /// Builds the voucher of a transaction.
+    ///
+    /// Entries are listed debits first, each side in the order entered.
+    /// The approval trail has one line per recorded approval; transactions
+    /// approved before approvals were recorded individually show the final
+    /// approver. Amounts and dates follow the organization's locale; a
+    /// settings document that cannot be read falls back to the default.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the transaction is not found in the organization
+    /// or a database query fails.
+    pub async fn voucher(
+        &self,
+        organization_id: OrganizationId,
+        transaction_id: TransactionId,
+    ) -> Result<Voucher, TransactionError> {
+        let not_found = || TransactionError::NotFound(transaction_id.into_inner());
+        let transaction = transactions::Entity::find_by_id(transaction_id)
+            .filter(transactions::Column::OrganizationId.eq(organization_id))
+            .one(&self.db)
+            .await?
+            .ok_or_else(not_found)?;
+        let organization = organizations::Entity::find_by_id(organization_id)
+            .one(&self.db)
+            .await?
+            .ok_or_else(not_found)?;
+        let locale = OrganizationSettings::from_value(&organization.settings)
+            .map(|settings| settings.formatting.locale)
+            .unwrap_or_default();
+        let decimal_places = currencies::Entity::find_by_id(&organization.base_currency)
+            .one(&self.db)
+            .await?
+            .and_then(|c| u32::try_from(c.decimal_places).ok())
+            .unwrap_or(2);
+
+        let mut entries = ledger_entries::Entity::find()
+            .filter(ledger_entries::Column::TransactionId.eq(transaction.id))
+            .order_by_asc(ledger_entries::Column::CreatedAt)
+            .order_by_asc(ledger_entries::Column::Id)
+            .all(&self.db)
+            .await?;
+        entries.sort_by_key(|e| e.debit.is_zero());
+
+        let account_ids: HashSet<Uuid> = entries.iter().map(|e| e.account_id).collect();
+        let accounts: HashMap<Uuid, chart_of_accounts::Model> = chart_of_accounts::Entity::find()
+            .filter(chart_of_accounts::Column::Id.is_in(account_ids))
+            .all(&self.db)
+            .await?
+            .into_iter()
+            .map(|a| (a.id, a))
+            .collect();
+        let mut tags = self.tags(entries.iter().map(|e| e.id).collect()).await?;
+
+        let lines = entries
+            .into_iter()
+            .map(|entry| {
+                let account = accounts.get(&entry.account_id);
+                VoucherLine {
+                    account_code: account.map(|a| a.code.clone()).unwrap_or_default(),
+                    account_name: account.map(|a| a.name.clone()).unwrap_or_default(),
+                    memo: entry.memo,
+                    tags: tags.remove(&entry.id).unwrap_or_default(),
+                    debit: entry.debit,
+                    credit: entry.credit,
+                }
+            })
+            .collect();
+
+        let trail = self.trail(&transaction).await?;
+
+        Ok(Voucher {
+            organization_name: organization.name,
+            transaction_id: transaction.id,
+            reference_number: transaction.reference_number,
+            transaction_date: transaction.transaction_date,
+            transaction_type: transaction.transaction_type.to_value(),
+            status: CoreTransactionStatus::from(&transaction.status),
+            description: transaction.description,
+            memo: transaction.memo,
+            void_reason: transaction.void_reason,
+            currency: organization.base_currency,
+            decimal_places,
+            locale,
+            lines,
+            trail,
+        })
+    }
+
+    /// Dimension tags of each entry, ordered by dimension type.
+    async fn tags(
+        &self,
+        entry_ids: Vec<Uuid>,
+    ) -> Result<HashMap<Uuid, Vec<VoucherTag>>, TransactionError> {
+        let tagged = entry_dimensions::Entity::find()
+            .filter(entry_dimensions::Column::LedgerEntryId.is_in(entry_ids))
+            .find_also_related(dimension_values::Entity)
+            .all(&self.db)
+            .await?;
+        let type_ids: HashSet<Uuid> = tagged
+            .iter()
+            .filter_map(|(_, value)| value.as_ref().map(|v| v.dimension_type_id))
+            .collect();
+        let types: HashMap<Uuid, dimension_types::Model> = dimension_types::Entity::find()
+            .filter(dimension_types::Column::Id.is_in(type_ids))
+            .all(&self.db)
+            .await?
+            .into_iter()
+            .map(|t| (t.id, t))
+            .collect();
+
+        let mut tags: HashMap<Uuid, Vec<(i16, VoucherTag)>> = HashMap::new();
+        for (entry, value) in tagged {
+            let Some(value) = value else { continue };
+            let Some(dimension) = types.get(&value.dimension_type_id) else {
+                continue;
+            };
+            tags.entry(entry.ledger_entry_id).or_default().push((
+                dimension.sort_order,
+                VoucherTag {
+                    dimension: dimension.name.clone(),
+                    value: value.name,
+                },
+            ));
+        }
+        Ok(tags
+            .into_iter()
+            .map(|(entry_id, mut tags)| {
+                tags.sort_by(|a, b| (a.0, &a.1.dimension).cmp(&(b.0, &b.1.dimension)));
+                (entry_id, tags.into_iter().map(|(_, tag)| tag).collect())
+            })
+            .collect())
+    }
+
+    /// Approval trail of a transaction, with the users' names.
+    async fn trail(
+        &self,
+        transaction: &transactions::Model,
+    ) -> Result<Vec<VoucherSignature>, TransactionError> {
+        let approvals: Vec<(Uuid, DateTimeWithTimeZone)> = transaction_approvals::Entity::find()
+            .filter(transaction_approvals::Column::TransactionId.eq(transaction.id))
+            .order_by_asc(transaction_approvals::Column::ApprovedAt)
+            .all(&self.db)
+            .await?
+            .into_iter()
+            .map(|a| (a.approved_by, a.approved_at))
+            .collect();
+
+        let mut steps = Vec::new();
+        if let (Some(by), Some(at)) = (transaction.submitted_by, transaction.submitted_at) {
+            steps.push((VoucherStep::Submitted, by, at));
+        }
+        if approvals.is_empty() {
+            if let (Some(by), Some(at)) = (transaction.approved_by, transaction.approved_at) {
+                steps.push((VoucherStep::Approved, by, at));
+            }
+        } else {
+            steps.extend(
+                approvals
+                    .into_iter()
+                    .map(|(by, at)| (VoucherStep::Approved, by, at)),
+            );
+        }
+        if let (Some(by), Some(at)) = (transaction.posted_by, transaction.posted_at) {
+            steps.push((VoucherStep::Posted, by, at));
+        }
+        if let (Some(by), Some(at)) = (transaction.voided_by, transaction.voided_at) {
+            steps.push((VoucherStep::Voided, by, at));
+        }
+
+        let user_ids: HashSet<Uuid> = steps.iter().map(|(_, by, _)| *by).collect();
+        let names: HashMap<Uuid, String> = users::Entity::find()
+            .filter(users::Column::Id.is_in(user_ids))
+            .all(&self.db)
+            .await?
+            .into_iter()
+            .map(|u| (u.id, u.full_name))
+            .collect();
+
+        Ok(steps
+            .into_iter()
+            .map(|(step, by, at)| VoucherSignature {
+                step,
+                name: names.get(&by).cloned().unwrap_or_default(),
+                at: at.with_timezone(&Utc),
+            })
+            .collect())
+    }
+}
@@ -2,27 +2,91 @@
 //!
 //! Implements Requirements 1.1-1.4, 2.1-2.7, 5.1-5.4 for transaction workflow management.
 
-use chrono::Utc;
+use std::collections::HashMap;
+
+use chrono::{DateTime, NaiveTime, Utc};
 use rust_decimal::Decimal;
 use sea_orm::{
-    ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, QueryOrder, Set,
-    TransactionTrait,
+    ActiveModelTrait, ColumnTrait, Condition, ConnectionTrait, DatabaseConnection, EntityTrait,
+    Iterable, Order, QueryFilter, QueryOrder, QuerySelect, Set, TransactionTrait,
+    prelude::DateTimeWithTimeZone, sea_query::Expr,
 };
 use uuid::Uuid;
+use zeltra_shared::types::{OrganizationId, Sort, SortDirection, SortField, TransactionId};
 
+use zeltra_core::comment::CommentKind;
+use zeltra_core::ledger::FiscalPeriodStatus as CoreFiscalPeriodStatus;
+use zeltra_core::settings::OrganizationSettings;
 use zeltra_core::workflow::{
-    ApprovalEngine, ApprovalRule, OriginalEntry, ReversalInput, ReversalService, WorkflowError,
-    WorkflowService,
+    ActionAvailability, ActionContext, ApprovalEngine, ApprovalProgress, ApprovalRule,
+    BulkApprovalDecision, BulkApprovalItem, BulkApprover, DelegatedAuthority, OriginalEntry,
+    ReversalInput, ReversalService, ReviewClaim, TransactionStatus as CoreStatus, UserRole,
+    VoidReasonCode, WorkflowAction, WorkflowError, WorkflowService, days_pending, escalation_due,
 };
 
 use crate::entities::{
-    approval_rules, chart_of_accounts, entry_dimensions, ledger_entries, organization_users,
-    sea_orm_active_enums::{TransactionStatus, TransactionType},
-    transactions,
+    approval_delegations, approval_rules, chart_of_accounts, entry_dimensions, fiscal_periods,
+    ledger_entries, organization_users, organizations, payment_applications, reconciliation_items,
+    reconciliations,
+    sea_orm_active_enums::{
+        FiscalPeriodStatus, ReconciliationStatus, TransactionStatus, TransactionType,
+        UserRole as DbUserRole, VoidReasonCode as DbVoidReasonCode,
+    },
+    transaction_approvals, transaction_claims, transaction_status_history, transactions, users,
 };
 
+use super::comment::{record_workflow_comment, record_workflow_comments};
 use super::transaction::calculate_balance_change;
 
+/// Counter of transactions moved to posted status.
+pub const TRANSACTIONS_POSTED_TOTAL: &str = "transactions_posted_total";
+/// Counter of failed workflow operations, labeled by operation and error variant.
+pub const WORKFLOW_ERRORS_TOTAL: &str = "workflow_errors_total";
+
+/// Counts a failed workflow operation before handing the result back.
+fn record_workflow_result<T>(
+    operation: &'static str,
+    result: Result<T, WorkflowError>,
+) -> Result<T, WorkflowError> {
+    if let Err(e) = &result {
+        metrics::counter!(
+            WORKFLOW_ERRORS_TOTAL,
+            "operation" => operation,
+            "variant" => e.error_code(),
+        )
+        .increment(1);
+    }
+    result
+}
+
+/// Appends a transition to a transaction's status history.
+///
+/// `transaction` is the row after the transition; its status is the entry's
+/// `to_status`. `from_status` is `None` when the transition created it.
+async fn record_transition<C: ConnectionTrait>(
+    db: &C,
+    transaction: &transactions::Model,
+    from_status: Option<TransactionStatus>,
+    actor_id: Uuid,
+    notes: Option<String>,
+    at: DateTimeWithTimeZone,
+) -> Result<(), WorkflowError> {
+    transaction_status_history::ActiveModel {
+        id: Set(Uuid::new_v4()),
+        organization_id: Set(transaction.organization_id),
+        transaction_id: Set(transaction.id),
+        from_status: Set(from_status),
+        to_status: Set(transaction.status.clone()),
+        actor_id: Set(actor_id),
+        notes: Set(notes),
+        created_at: Set(at),
+    }
+    .insert(db)
+    .await
+    .map_err(|e| WorkflowError::Database(e.to_string()))?;
+    Ok(())
+}
+
 /// Result of a bulk approval operation.
 #[derive(Debug, Clone)]
 pub struct BulkApproveResult {
@@ -45,6 +109,63 @@ pub struct BulkApproveItemResult {
     pub error: Option<String>,
 }
 
+/// What a bulk approval needs to know, loaded up front.
+#[derive(Debug, Clone)]
+pub struct ApprovalContext {
+    /// Requested transactions that exist in the organization, keyed by ID.
+    pub transactions: HashMap<Uuid, transactions::Model>,
+    /// Debit total of each found transaction.
+    pub totals: HashMap<Uuid, Decimal>,
+    /// Users who already approved each found transaction.
+    pub prior_approvers: HashMap<Uuid, Vec<Uuid>>,
+    /// Users behind the approvals of each found transaction: the approvers
+    /// and the delegators they approved for.
+    pub counted_approvers: HashMap<Uuid, Vec<Uuid>>,
+    /// The approver's membership, `None` when not a member.
+    pub approver: Option<organization_users::Model>,
+    /// Authority the approver holds through active delegations.
+    pub delegations: Vec<(Uuid, DelegatedAuthority)>,
+    /// Active approval rules of the organization.
+    pub rules: Vec<ApprovalRule>,
+    /// Review claims on the found transactions, lapsed or not.
+    pub claims: HashMap<Uuid, ReviewClaim>,
+}
+
+/// Result of a bulk void operation.
+#[derive(Debug, Clone)]
+pub struct BulkVoidResult {
+    /// Results for each transaction, in request order.
+    pub results: Vec<BulkVoidItemResult>,
+    /// Number of successful voids.
+    pub success_count: usize,
+    /// Number of failed voids.
+    pub failure_count: usize,
+}
+
+/// Result for a single transaction in a bulk void.
+#[derive(Debug, Clone)]
+pub struct BulkVoidItemResult {
+    /// Transaction ID.
+    pub transaction_id: Uuid,
+    /// Whether the void succeeded.
+    pub success: bool,
+    /// Reversing transaction created by the void.
+    pub reversing_transaction_id: Option<Uuid>,
+    /// Error code if failed, e.g. `not_posted`, `period_closed`, `already_voided`.
+    pub error_code: Option<&'static str>,
+    /// Error message if failed.
+    pub error: Option<String>,
+}
+
+/// Outcome of recording an approval.
+#[derive(Debug, Clone)]
+pub struct ApprovalOutcome {
+    /// Transaction after the approval; still pending if more are required.
+    pub transaction: transactions::Model,
+    /// Approvals recorded against the required count.
+    pub progress: ApprovalProgress,
+}
+
 /// Pending transaction with approval info.
 #[derive(Debug, Clone)]
 pub struct PendingTransaction {
@@ -52,6 +173,147 @@ pub struct PendingTransaction {
     pub transaction: transactions::Model,
     /// Whether the current user can approve this transaction.
     pub can_approve: bool,
+    /// Delegator the user would approve for, when only a delegation allows it.
+    pub on_behalf_of: Option<Uuid>,
+    /// Approvals recorded against the required count.
+    pub progress: ApprovalProgress,
+    /// What approving the transaction requires.
+    pub requirement: ApprovalRequirement,
+    /// Sum of the transaction's debits.
+    pub total_amount: Decimal,
+    /// Whole days since the transaction was submitted.
+    pub days_pending: u32,
+    /// Active review claim, `None` when nobody is reviewing it.
+    pub claim: Option<ReviewClaim>,
+}
+
+/// Person notified when a pending transaction is escalated.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EscalationRecipient {
+    /// User ID.
+    pub user_id: Uuid,
+    /// Email address.
+    pub email: String,
+    /// Display name.
+    pub full_name: String,
+}
+
+/// Pending transaction due for an approval reminder.
+#[derive(Debug, Clone)]
+pub struct DueEscalation {
+    /// Transaction data.
+    pub transaction: transactions::Model,
+    /// Name of the owning organization.
+    pub organization_name: String,
+    /// Sum of the transaction's debits.
+    pub total_amount: Decimal,
+    /// Whole days since the transaction was submitted.
+    pub days_pending: u32,
+    /// Active members who could approve it and have not yet.
+    pub approvers: Vec<EscalationRecipient>,
+    /// The submitter, sent a courtesy note.
+    pub submitter: Option<EscalationRecipient>,
+}
+
+/// Member who could approve a previewed transaction.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PreviewApprover {
+    /// User ID.
+    pub user_id: Uuid,
+    /// Email address.
+    pub email: String,
+    /// Display name.
+    pub full_name: String,
+    /// Role in the organization.
+    pub role: String,
+    /// Approval limit, `None` when unlimited.
+    pub approval_limit: Option<Decimal>,
+}
+
+/// Which approval rule a transaction would hit and who could approve it.
+#[derive(Debug, Clone)]
+pub struct ApprovalPreview {
+    /// Matched rule, `None` when the default policy applies.
+    pub rule: Option<ApprovalRule>,
+    /// Minimum role required to approve.
+    pub required_role: String,
+    /// Number of approvals required.
+    pub required_approvals: u32,
+    /// Active members whose role and limit satisfy the policy, by email.
+    pub approvers: Vec<PreviewApprover>,
+}
+
+/// What approving a transaction requires.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ApprovalRequirement {
+    /// Minimum role required to approve.
+    pub required_role: String,
+    /// Number of different users who must approve.
+    pub required_approvals: u32,
+    /// Matched rule, `None` when the default policy applies or the rule has
+    /// since been deleted.
+    pub matched_rule_id: Option<Uuid>,
+    /// Whether this was recorded at submission rather than evaluated
+    /// against the current rules.
+    pub from_snapshot: bool,
+}
+
+impl ApprovalRequirement {
+    /// The requirement recorded when `transaction` was submitted, `None`
+    /// for drafts and transactions submitted before snapshots were kept.
+    #[must_use]
+    pub fn snapshot(transaction: &transactions::Model) -> Option<Self> {
+        let role = transaction.required_role_at_submit.as_ref()?;
+        let approvals = transaction.required_approvals?;
+        Some(Self {
+            required_role: db_role_to_string(role),
+            required_approvals: u32::try_from(approvals).unwrap_or(1),
+            matched_rule_id: transaction.matched_rule_id,
+            from_snapshot: true,
+        })
+    }
+
+    /// Evaluates the requirement for a transaction against `rules`.
+    fn evaluate(
+        rules: &[ApprovalRule],
+        transaction_type: &TransactionType,
+        amount: Decimal,
+    ) -> Self {
+        let tx_type = db_tx_type_to_string(transaction_type);
+        let required = ApprovalEngine::required_approval(rules, &tx_type, amount);
+        Self {
+            required_role: required.role.to_string(),
+            required_approvals: required.approvals,
+            matched_rule_id: required.rule.map(|rule| rule.id),
+            from_snapshot: false,
+        }
+    }
+
+    /// The snapshot of a submitted transaction, falling back to `rules`
+    /// for one submitted before snapshots were kept.
+    fn of(transaction: &transactions::Model, rules: &[ApprovalRule], amount: Decimal) -> Self {
+        Self::snapshot(transaction)
+            .unwrap_or_else(|| Self::evaluate(rules, &transaction.transaction_type, amount))
+    }
+}
+
+/// Fields the approval queue can be sorted by.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PendingSortField {
+    /// Submission timestamp.
+    SubmittedAt,
+    /// Transaction total. Sorted in memory once totals are computed.
+    Amount,
+    /// Time spent waiting for approval; ascending puts the newest first.
+    DaysPending,
+}
+
+impl SortField for PendingSortField {
+    const ALLOWED: &'static [(&'static str, Self)] = &[
+        ("submitted_at", Self::SubmittedAt),
+        ("amount", Self::Amount),
+        ("days_pending", Self::DaysPending),
+    ];
 }
 
 /// Void operation result.
@@ -63,6 +325,33 @@ pub struct VoidResult {
     pub reversing_transaction: transactions::Model,
 }
 
+/// One workflow transition of a transaction.
+#[derive(Debug, Clone)]
+pub struct StatusHistoryEntry {
+    /// History row.
+    pub history: transaction_status_history::Model,
+    /// Full name of the user who made the transition.
+    pub actor_name: Option<String>,
+}
+
+/// A transaction's workflow transitions, oldest first.
+#[derive(Debug, Clone)]
+pub struct TransactionHistory {
+    /// Transaction data.
+    pub transaction: transactions::Model,
+    /// Every recorded transition.
+    pub entries: Vec<StatusHistoryEntry>,
+}
+
+/// Workflow actions a user may take on a transaction.
+#[derive(Debug, Clone)]
+pub struct TransactionActions {
+    /// Transaction data.
+    pub transaction: transactions::Model,
+    /// Every workflow action, allowed or not.
+    pub actions: Vec<ActionAvailability>,
+}
+
 /// Workflow repository for transaction state transitions.
 #[derive(Debug, Clone)]
 pub struct WorkflowRepository {
@@ -78,6 +367,9 @@ impl WorkflowRepository {
 
     /// Submits a draft transaction for approval.
     ///
+    /// The approval rules are evaluated now and the outcome is stored on the
+    /// transaction, so later rule edits do not change what it requires.
+    ///
     /// Requirements: 1.1, 7.2
     ///
     /// # Errors
@@ -88,8 +380,21 @@ impl WorkflowRepository {
     /// - Database operation fails
     pub async fn submit_transaction(
         &self,
-        organization_id: Uuid,
-        transaction_id: Uuid,
+        organization_id: OrganizationId,
+        transaction_id: TransactionId,
+        submitted_by: Uuid,
+    ) -> Result<transactions::Model, WorkflowError> {
+        record_workflow_result(
+            "submit",
+            self.submit(organization_id, transaction_id, submitted_by)
+                .await,
+        )
+    }
+
+    async fn submit(
+        &self,
+        organization_id: OrganizationId,
+        transaction_id: TransactionId,
         submitted_by: Uuid,
     ) -> Result<transactions::Model, WorkflowError> {
         // Fetch transaction
@@ -98,31 +403,59 @@ impl WorkflowRepository {
             .one(&self.db)
             .await
             .map_err(|e| WorkflowError::Database(e.to_string()))?
-            .ok_or(WorkflowError::TransactionNotFound(transaction_id))?;
+            .ok_or(WorkflowError::TransactionNotFound(
+                transaction_id.into_inner(),
+            ))?;
 
         // Convert DB status to core status
-        let current_status = db_status_to_core(&transaction.status);
+        let current_status = CoreStatus::from(&transaction.status);
 
         // Validate transition using WorkflowService
         let _action = WorkflowService::submit(current_status, submitted_by)?;
 
+        let total = self.calculate_transaction_total(transaction.id).await?;
+        let rules = self.get_approval_rules(organization_id).await?;
+        let requirement =
+            ApprovalRequirement::evaluate(&rules, &transaction.transaction_type, total);
+
+        let txn = self
+            .db
+            .begin()
+            .await
+            .map_err(|e| WorkflowError::Database(e.to_string()))?;
+
         // Update transaction
         let now = Utc::now().into();
+        let from_status = transaction.status.clone();
         let mut active: transactions::ActiveModel = transaction.into();
         active.status = Set(TransactionStatus::Pending);
         active.submitted_at = Set(Some(now));
         active.submitted_by = Set(Some(submitted_by));
+        active.required_role_at_submit = Set(db_role_from_str(&requirement.required_role));
+        active.required_approvals = Set(Some(
+            i16::try_from(requirement.required_approvals).unwrap_or(i16::MAX),
+        ));
+        active.matched_rule_id = Set(requirement.matched_rule_id);
         active.updated_at = Set(now);
 
         let updated = active
-            .update(&self.db)
+            .update(&txn)
+            .await
+            .map_err(|e| WorkflowError::Database(e.to_string()))?;
+        record_transition(&txn, &updated, Some(from_status), submitted_by, None, now).await?;
+
+        txn.commit()
             .await
             .map_err(|e| WorkflowError::Database(e.to_string()))?;
 
         Ok(updated)
     }
 
-    /// Approves a pending transaction.
+    /// Records an approval of a pending transaction.
+    ///
+    /// The transaction moves to approved once the matched rule's number of
+    /// different users have approved it; until then it stays pending and the
+    /// outcome reports the progress.
     ///
     /// Requirements: 1.2, 3.4, 3.5, 7.3
     ///
@@ -131,54 +464,155 @@ impl WorkflowRepository {
     /// Returns an error if:
     /// - Transaction is not found
     /// - Transaction is not in pending status
+    /// - User has already approved it
     /// - User is not authorized to approve
     /// - Database operation fails
     pub async fn approve_transaction(
         &self,
-        organization_id: Uuid,
-        transaction_id: Uuid,
+        organization_id: OrganizationId,
+        transaction_id: TransactionId,
         approved_by: Uuid,
         approval_notes: Option<String>,
-    ) -> Result<transactions::Model, WorkflowError> {
-        // Fetch transaction
+    ) -> Result<ApprovalOutcome, WorkflowError> {
+        record_workflow_result(
+            "approve",
+            self.approve(organization_id, transaction_id, approved_by, approval_notes)
+                .await,
+        )
+    }
+
+    #[allow(clippy::too_many_lines)]
+    async fn approve(
+        &self,
+        organization_id: OrganizationId,
+        transaction_id: TransactionId,
+        approved_by: Uuid,
+        approval_notes: Option<String>,
+    ) -> Result<ApprovalOutcome, WorkflowError> {
+        let txn = self
+            .db
+            .begin()
+            .await
+            .map_err(|e| WorkflowError::Database(e.to_string()))?;
+
+        // Lock the transaction so concurrent approvals are counted in turn
         let transaction = transactions::Entity::find_by_id(transaction_id)
             .filter(transactions::Column::OrganizationId.eq(organization_id))
-            .one(&self.db)
+            .lock_exclusive()
+            .one(&txn)
             .await
             .map_err(|e| WorkflowError::Database(e.to_string()))?
-            .ok_or(WorkflowError::TransactionNotFound(transaction_id))?;
+            .ok_or(WorkflowError::TransactionNotFound(
+                transaction_id.into_inner(),
+            ))?;
 
-        // Convert DB status to core status
-        let current_status = db_status_to_core(&transaction.status);
+        let prior_approvals = transaction_approvals::Entity::find()
+            .filter(transaction_approvals::Column::TransactionId.eq(transaction.id))
+            .all(&txn)
+            .await
+            .map_err(|e| WorkflowError::Database(e.to_string()))?;
+        let prior_approvers: Vec<Uuid> = prior_approvals.iter().map(|a| a.approved_by).collect();
+
+        let total = self.calculate_transaction_total(transaction.id).await?;
+        let requirement = self
+            .requirement(organization_id, &transaction, total)
+            .await?;
 
         // Validate transition using WorkflowService
-        let _action =
-            WorkflowService::approve(current_status, approved_by, approval_notes.clone())?;
+        let action = WorkflowService::approve(
+            CoreStatus::from(&transaction.status),
+            approved_by,
+            approval_notes.clone(),
+            &prior_approvers,
+            requirement.required_approvals,
+        )?;
+        let WorkflowAction::Approve {
+            new_status,
+            progress,
+            ..
+        } = action
+        else {
+            unreachable!("WorkflowService::approve returns an Approve action");
+        };
+        check_claim(&txn, organization_id, transaction.id, approved_by).await?;
+
+        // Check user authorization, directly or through a delegation
+        let on_behalf_of = self
+            .check_approval_authorization(
+                organization_id,
+                approved_by,
+                &requirement.required_role,
+                total,
+            )
+            .await?;
+        WorkflowService::validate_distinct_approver(
+            &counted_approvers(&prior_approvals),
+            approved_by,
+            on_behalf_of,
+        )?;
 
-        // Check user authorization
-        self.check_approval_authorization(
-            organization_id,
+        let now = Utc::now().into();
+        transaction_approvals::ActiveModel {
+            id: Set(Uuid::new_v4()),
+            organization_id: Set(organization_id.into_inner()),
+            transaction_id: Set(transaction.id),
+            approved_by: Set(approved_by),
+            approved_on_behalf_of: Set(on_behalf_of),
+            approval_notes: Set(approval_notes.clone()),
+            approved_at: Set(now),
+        }
+        .insert(&txn)
+        .await
+        .map_err(|e| WorkflowError::Database(e.to_string()))?;
+
+        // Every approval is recorded; one short of the required count stays pending
+        let from_status = transaction.status.clone();
+        let transaction = if new_status == CoreStatus::Approved {
+            let mut active: transactions::ActiveModel = transaction.into();
+            active.status = Set(TransactionStatus::Approved);
+            active.approved_at = Set(Some(now));
+            active.approved_by = Set(Some(approved_by));
+            active.approved_on_behalf_of = Set(on_behalf_of);
+            active.approval_notes = Set(approval_notes.clone());
+            active.updated_at = Set(now);
+            active
+                .update(&txn)
+                .await
+                .map_err(|e| WorkflowError::Database(e.to_string()))?
+        } else {
+            transaction
+        };
+        if let Some(notes) = &approval_notes {
+            record_workflow_comment(
+                &txn,
+                &transaction,
+                approved_by,
+                CommentKind::Approval,
+                notes,
+                now,
+            )
+            .await
+            .map_err(|e| WorkflowError::Database(e.to_string()))?;
+        }
+        record_transition(
+            &txn,
+            &transaction,
+            Some(from_status),
             approved_by,
-            &transaction.transaction_type,
-            self.calculate_transaction_total(transaction_id).await?,
+            approval_notes,
+            now,
         )
         .await?;
+        clear_claims(&txn, vec![transaction.id]).await?;
 
-        // Update transaction
-        let now = Utc::now().into();
-        let mut active: transactions::ActiveModel = transaction.into();
-        active.status = Set(TransactionStatus::Approved);
-        active.approved_at = Set(Some(now));
-        active.approved_by = Set(Some(approved_by));
-        active.approval_notes = Set(approval_notes);
-        active.updated_at = Set(now);
-
-        let updated = active
-            .update(&self.db)
+        txn.commit()
             .await
             .map_err(|e| WorkflowError::Database(e.to_string()))?;
 
-        Ok(updated)
+        Ok(ApprovalOutcome {
+            transaction,
+            progress,
+        })
     }
 
     /// Rejects a pending transaction back to draft.
@@ -194,8 +628,28 @@ impl WorkflowRepository {
     /// - Database operation fails
     pub async fn reject_transaction(
         &self,
-        organization_id: Uuid,
-        transaction_id: Uuid,
+        organization_id: OrganizationId,
+        transaction_id: TransactionId,
+        rejected_by: Uuid,
+        rejection_reason: String,
+    ) -> Result<transactions::Model, WorkflowError> {
+        record_workflow_result(
+            "reject",
+            self.reject(
+                organization_id,
+                transaction_id,
+                rejected_by,
+                rejection_reason,
+            )
+            .await,
+        )
+    }
+
+    async fn reject(
+        &self,
+        organization_id: OrganizationId,
+        transaction_id: TransactionId,
+        rejected_by: Uuid,
         rejection_reason: String,
     ) -> Result<transactions::Model, WorkflowError> {
         // Fetch transaction
@@ -204,31 +658,201 @@ impl WorkflowRepository {
             .one(&self.db)
             .await
             .map_err(|e| WorkflowError::Database(e.to_string()))?
-            .ok_or(WorkflowError::TransactionNotFound(transaction_id))?;
+            .ok_or(WorkflowError::TransactionNotFound(
+                transaction_id.into_inner(),
+            ))?;
 
         // Convert DB status to core status
-        let current_status = db_status_to_core(&transaction.status);
+        let current_status = CoreStatus::from(&transaction.status);
 
         // Validate transition using WorkflowService
         let _action = WorkflowService::reject(current_status, rejection_reason.clone())?;
 
+        let txn = self
+            .db
+            .begin()
+            .await
+            .map_err(|e| WorkflowError::Database(e.to_string()))?;
+        check_claim(&txn, organization_id, transaction.id, rejected_by).await?;
+        clear_claims(&txn, vec![transaction.id]).await?;
+
+        // A resubmitted transaction collects its approvals from scratch
+        transaction_approvals::Entity::delete_many()
+            .filter(transaction_approvals::Column::TransactionId.eq(transaction.id))
+            .exec(&txn)
+            .await
+            .map_err(|e| WorkflowError::Database(e.to_string()))?;
+
         // Update transaction
         let now = Utc::now().into();
+        let from_status = transaction.status.clone();
         let mut active: transactions::ActiveModel = transaction.into();
         active.status = Set(TransactionStatus::Draft);
-        active.approval_notes = Set(Some(rejection_reason));
+        active.approval_notes = Set(Some(rejection_reason.clone()));
         active.submitted_at = Set(None);
         active.submitted_by = Set(None);
+        // Resubmission evaluates the rules afresh
+        active.required_role_at_submit = Set(None);
+        active.required_approvals = Set(None);
+        active.matched_rule_id = Set(None);
         active.updated_at = Set(now);
 
         let updated = active
-            .update(&self.db)
+            .update(&txn)
+            .await
+            .map_err(|e| WorkflowError::Database(e.to_string()))?;
+        record_workflow_comment(
+            &txn,
+            &updated,
+            rejected_by,
+            CommentKind::Rejection,
+            &rejection_reason,
+            now,
+        )
+        .await
+        .map_err(|e| WorkflowError::Database(e.to_string()))?;
+        record_transition(
+            &txn,
+            &updated,
+            Some(from_status),
+            rejected_by,
+            Some(rejection_reason),
+            now,
+        )
+        .await?;
+
+        txn.commit()
             .await
             .map_err(|e| WorkflowError::Database(e.to_string()))?;
 
         Ok(updated)
     }
 
+    /// Claims a pending transaction for review.
+    ///
+    /// The claim lasts [`CLAIM_TTL_MINUTES`](zeltra_core::workflow::CLAIM_TTL_MINUTES).
+    /// Claiming again renews it, and once it lapses anyone who may approve
+    /// can take it over.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - Transaction is not found
+    /// - Transaction is not pending
+    /// - User is neither an approver nor a delegate
+    /// - Another reviewer holds an active claim
+    /// - Database operation fails
+    pub async fn claim_transaction(
+        &self,
+        organization_id: OrganizationId,
+        transaction_id: TransactionId,
+        user_id: Uuid,
+    ) -> Result<ReviewClaim, WorkflowError> {
+        let txn = self
+            .db
+            .begin()
+            .await
+            .map_err(|e| WorkflowError::Database(e.to_string()))?;
+
+        // Lock the transaction so two reviewers claim it in turn
+        let transaction = transactions::Entity::find_by_id(transaction_id)
+            .filter(transactions::Column::OrganizationId.eq(organization_id))
+            .lock_exclusive()
+            .one(&txn)
+            .await
+            .map_err(|e| WorkflowError::Database(e.to_string()))?
+            .ok_or(WorkflowError::TransactionNotFound(
+                transaction_id.into_inner(),
+            ))?;
+        if transaction.status != TransactionStatus::Pending {
+            return Err(WorkflowError::NotPending {
+                status: CoreStatus::from(&transaction.status),
+            });
+        }
+
+        let member = organization_users::Entity::find()
+            .filter(organization_users::Column::OrganizationId.eq(organization_id))
+            .filter(organization_users::Column::UserId.eq(user_id))
+            .one(&txn)
+            .await
+            .map_err(|e| WorkflowError::Database(e.to_string()))?
+            .ok_or(WorkflowError::NotAuthorizedToApprove)?;
+        let is_approver = UserRole::parse(&db_role_to_string(&member.role))
+            .is_some_and(|role| role >= UserRole::Approver);
+        if !is_approver
+            && self
+                .delegated_authorities(organization_id, user_id, member.approval_limit)
+                .await?
+                .is_empty()
+        {
+            return Err(WorkflowError::NotAuthorizedToApprove);
+        }
+
+        let now = Utc::now();
+        if let Some(current) = load_claims(&txn, vec![transaction.id])
+            .await?
+            .remove(&transaction.id)
+        {
+            current.check_claim(user_id, now)?;
+        }
+
+        let reviewer_name = users::Entity::find_by_id(user_id)
+            .one(&txn)
+            .await
+            .map_err(|e| WorkflowError::Database(e.to_string()))?
+            .map(|u| u.full_name)
+            .unwrap_or_default();
+        let claim = ReviewClaim::new(user_id, reviewer_name, now);
+        clear_claims(&txn, vec![transaction.id]).await?;
+        transaction_claims::ActiveModel {
+            transaction_id: Set(transaction.id),
+            organization_id: Set(transaction.organization_id),
+            reviewing_by: Set(user_id),
+            reviewing_until: Set(claim.expires_at.into()),
+            claimed_at: Set(now.into()),
+        }
+        .insert(&txn)
+        .await
+        .map_err(|e| WorkflowError::Database(e.to_string()))?;
+
+        txn.commit()
+            .await
+            .map_err(|e| WorkflowError::Database(e.to_string()))?;
+
+        Ok(claim)
+    }
+
+    /// Releases a review claim before it lapses.
+    ///
+    /// The reviewer, an owner or an admin may release an active claim.
+    /// Releasing a lapsed or missing claim does nothing.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - Transaction is not found
+    /// - Another reviewer holds the active claim and the user is neither an
+    ///   owner nor an admin
+    /// - Database operation fails
+    pub async fn release_claim(
+        &self,
+        organization_id: OrganizationId,
+        transaction_id: TransactionId,
+        user_id: Uuid,
+    ) -> Result<(), WorkflowError> {
+        let transaction = transactions::Entity::find_by_id(transaction_id)
+            .filter(transactions::Column::OrganizationId.eq(organization_id))
+            .one(&self.db)
+            .await
+            .map_err(|e| WorkflowError::Database(e.to_string()))?
+            .ok_or(WorkflowError::TransactionNotFound(
+                transaction_id.into_inner(),
+            ))?;
+
+        check_claim(&self.db, organization_id, transaction.id, user_id).await?;
+        clear_claims(&self.db, vec![transaction.id]).await
+    }
+
     /// Posts an approved transaction.
     ///
     /// Requirements: 1.4, 7.5
@@ -241,8 +865,21 @@ impl WorkflowRepository {
     /// - Database operation fails
     pub async fn post_transaction(
         &self,
-        organization_id: Uuid,
-        transaction_id: Uuid,
+        organization_id: OrganizationId,
+        transaction_id: TransactionId,
+        posted_by: Uuid,
+    ) -> Result<transactions::Model, WorkflowError> {
+        let result = self.post(organization_id, transaction_id, posted_by).await;
+        if result.is_ok() {
+            metrics::counter!(TRANSACTIONS_POSTED_TOTAL).increment(1);
+        }
+        record_workflow_result("post", result)
+    }
+
+    async fn post(
+        &self,
+        organization_id: OrganizationId,
+        transaction_id: TransactionId,
         posted_by: Uuid,
     ) -> Result<transactions::Model, WorkflowError> {
         // Fetch transaction
@@ -251,16 +888,25 @@ impl WorkflowRepository {
             .one(&self.db)
             .await
             .map_err(|e| WorkflowError::Database(e.to_string()))?
-            .ok_or(WorkflowError::TransactionNotFound(transaction_id))?;
+            .ok_or(WorkflowError::TransactionNotFound(
+                transaction_id.into_inner(),
+            ))?;
 
         // Convert DB status to core status
-        let current_status = db_status_to_core(&transaction.status);
+        let current_status = CoreStatus::from(&transaction.status);
 
         // Validate transition using WorkflowService
         let _action = WorkflowService::post(current_status, posted_by)?;
 
+        let txn = self
+            .db
+            .begin()
+            .await
+            .map_err(|e| WorkflowError::Database(e.to_string()))?;
+
         // Update transaction
         let now = Utc::now().into();
+        let from_status = transaction.status.clone();
         let mut active: transactions::ActiveModel = transaction.into();
         active.status = Set(TransactionStatus::Posted);
         active.posted_at = Set(Some(now));
@@ -268,7 +914,12 @@ impl WorkflowRepository {
         active.updated_at = Set(now);
 
         let updated = active
-            .update(&self.db)
+            .update(&txn)
+            .await
+            .map_err(|e| WorkflowError::Database(e.to_string()))?;
+        record_transition(&txn, &updated, Some(from_status), posted_by, None, now).await?;
+
+        txn.commit()
             .await
             .map_err(|e| WorkflowError::Database(e.to_string()))?;
 
@@ -277,6 +928,9 @@ impl WorkflowRepository {
 
     /// Voids a posted transaction by creating a reversing entry.
     ///
+    /// The reason code is stored on the voided transaction; the reversing
+    /// transaction's description names the code and original reference.
+    ///
     /// Requirements: 2.1-2.7, 7.6
     ///
     /// # Errors
@@ -284,14 +938,36 @@ impl WorkflowRepository {
     /// Returns an error if:
     /// - Transaction is not found
     /// - Transaction is not in posted status
-    /// - Void reason is empty
+    /// - Void reason is empty and the code is `Other`
     /// - Database operation fails
-    #[allow(clippy::too_many_lines)]
     pub async fn void_transaction(
         &self,
-        organization_id: Uuid,
-        transaction_id: Uuid,
+        organization_id: OrganizationId,
+        transaction_id: TransactionId,
+        voided_by: Uuid,
+        void_reason_code: VoidReasonCode,
+        void_reason: String,
+    ) -> Result<VoidResult, WorkflowError> {
+        record_workflow_result(
+            "void",
+            self.void(
+                organization_id,
+                transaction_id,
+                voided_by,
+                void_reason_code,
+                void_reason,
+            )
+            .await,
+        )
+    }
+
+    #[allow(clippy::too_many_lines)]
+    async fn void(
+        &self,
+        organization_id: OrganizationId,
+        transaction_id: TransactionId,
         voided_by: Uuid,
+        void_reason_code: VoidReasonCode,
         void_reason: String,
     ) -> Result<VoidResult, WorkflowError> {
         // Fetch transaction with entries
@@ -300,21 +976,49 @@ impl WorkflowRepository {
             .one(&self.db)
             .await
             .map_err(|e| WorkflowError::Database(e.to_string()))?
-            .ok_or(WorkflowError::TransactionNotFound(transaction_id))?;
+            .ok_or(WorkflowError::TransactionNotFound(
+                transaction_id.into_inner(),
+            ))?;
 
         // Convert DB status to core status
-        let current_status = db_status_to_core(&transaction.status);
+        let current_status = CoreStatus::from(&transaction.status);
 
         // Validate transition using WorkflowService
-        let _action = WorkflowService::void(current_status, voided_by, void_reason.clone())?;
+        let _action = WorkflowService::void(
+            current_status,
+            voided_by,
+            void_reason_code,
+            void_reason.clone(),
+        )?;
 
-        // Fetch ledger entries
-        let entries = ledger_entries::Entity::find()
+        // The reversal posts into the original's period, which must not be closed
+        let period = fiscal_periods::Entity::find_by_id(transaction.fiscal_period_id)
+            .one(&self.db)
+            .await
+            .map_err(|e| WorkflowError::Database(e.to_string()))?;
+        if period.is_some_and(|p| p.status == FiscalPeriodStatus::Closed) {
+            return Err(WorkflowError::PeriodClosed);
+        }
+
+        // Fetch ledger entries
+        let entries = ledger_entries::Entity::find()
             .filter(ledger_entries::Column::TransactionId.eq(transaction_id))
             .all(&self.db)
             .await
             .map_err(|e| WorkflowError::Database(e.to_string()))?;
 
+        // Entries locked by a completed reconciliation cannot be reversed
+        let entry_ids: Vec<Uuid> = entries.iter().map(|e| e.id).collect();
+        if let Some(reconciliation_id) = self.find_completed_reconciliation(entry_ids).await? {
+            return Err(WorkflowError::EntryReconciled { reconciliation_id });
+        }
+
+        // Zero adjustment lines post nothing, so there is nothing to reverse
+        let entries: Vec<_> = entries
+            .into_iter()
+            .filter(|e| !(e.debit.is_zero() && e.credit.is_zero()))
+            .collect();
+
         // Convert to OriginalEntry for ReversalService
         let original_entries: Vec<OriginalEntry> = entries
             .iter()
@@ -333,10 +1037,12 @@ impl WorkflowRepository {
 
         // Create reversal input
         let reversal_input = ReversalInput {
-            original_transaction_id: transaction_id,
+            original_transaction_id: transaction_id.into_inner(),
+            original_reference: transaction.reference_number.clone(),
             original_entries,
             fiscal_period_id: transaction.fiscal_period_id,
             voided_by,
+            void_reason_code,
             void_reason: void_reason.clone(),
         };
 
@@ -356,13 +1062,21 @@ impl WorkflowRepository {
         let reversing_tx_id = reversal_output.reversing_transaction_id;
         let reversing_transaction = transactions::ActiveModel {
             id: Set(reversing_tx_id),
-            organization_id: Set(organization_id),
+            organization_id: Set(organization_id.into_inner()),
             fiscal_period_id: Set(transaction.fiscal_period_id),
-            reference_number: Set(transaction.reference_number.clone()),
+            // Reference numbers are unique; the description carries the original's
+            reference_number: Set(None),
             transaction_type: Set(TransactionType::Reversal),
             transaction_date: Set(transaction.transaction_date),
             description: Set(reversal_output.description),
-            memo: Set(Some(format!("Void reason: {void_reason}"))),
+            memo: Set(Some(format!(
+                "Void reason: {}",
+                if void_reason.is_empty() {
+                    reversal_output.void_reason_code.as_str()
+                } else {
+                    &void_reason
+                }
+            ))),
             status: Set(TransactionStatus::Posted),
             created_by: Set(voided_by),
             submitted_at: Set(Some(now)),
@@ -371,7 +1085,7 @@ impl WorkflowRepository {
             approved_by: Set(Some(voided_by)),
             posted_at: Set(Some(now)),
             posted_by: Set(Some(voided_by)),
-            reverses_transaction_id: Set(Some(transaction_id)),
+            reverses_transaction_id: Set(Some(transaction_id.into_inner())),
             created_at: Set(now),
             updated_at: Set(now),
             ..Default::default()
@@ -381,6 +1095,7 @@ impl WorkflowRepository {
             .insert(&txn)
             .await
             .map_err(|e| WorkflowError::Database(e.to_string()))?;
+        record_transition(&txn, &reversing_tx, None, voided_by, None, now).await?;
 
         // Insert reversing ledger entries with balance tracking
         for (idx, rev_entry) in reversal_output.reversing_entries.iter().enumerate() {
@@ -408,14 +1123,12 @@ impl WorkflowRepository {
                 None => (0, Decimal::ZERO),
             };
 
-            // Calculate balance change (reversed)
+            // Calculate balance change (reversed) at the original's functional
+            // amount, so foreign currency entries are undone at the same rate
+            let functional_amount = original_entry.functional_amount;
             let (debit, credit) = match rev_entry.entry_type {
-                zeltra_core::ledger::types::EntryType::Debit => {
-                    (rev_entry.source_amount, Decimal::ZERO)
-                }
-                zeltra_core::ledger::types::EntryType::Credit => {
-                    (Decimal::ZERO, rev_entry.source_amount)
-                }
+                zeltra_core::ledger::types::EntryType::Debit => (functional_amount, Decimal::ZERO),
+                zeltra_core::ledger::types::EntryType::Credit => (Decimal::ZERO, functional_amount),
             };
 
             let balance_change = calculate_balance_change(&account.account_type, debit, credit);
@@ -430,12 +1143,14 @@ impl WorkflowRepository {
                 source_amount: Set(rev_entry.source_amount),
                 exchange_rate: Set(original_entry.exchange_rate),
                 functional_currency: Set(original_entry.functional_currency.clone()),
-                functional_amount: Set(rev_entry.source_amount),
+                functional_amount: Set(functional_amount),
                 debit: Set(debit),
                 credit: Set(credit),
                 memo: Set(rev_entry.memo.clone()),
                 event_at: Set(now),
                 created_at: Set(now),
+                // Reverses at the original rate, overridden or not
+                is_override: Set(original_entry.is_override),
                 account_version: Set(prev_version + 1),
                 account_previous_balance: Set(prev_balance),
                 account_current_balance: Set(current_balance),
@@ -468,11 +1183,19 @@ impl WorkflowRepository {
         }
 
         // Update original transaction to voided
+        let from_status = transaction.status.clone();
+        let void_notes = if void_reason.is_empty() {
+            reversal_output.void_reason_code.as_str().to_string()
+        } else {
+            void_reason.clone()
+        };
         let mut original_active: transactions::ActiveModel = transaction.into();
         original_active.status = Set(TransactionStatus::Voided);
         original_active.voided_at = Set(Some(now));
         original_active.voided_by = Set(Some(voided_by));
         original_active.void_reason = Set(Some(void_reason));
+        original_active.void_reason_code =
+            Set(Some(core_void_code_to_db(reversal_output.void_reason_code)));
         original_active.reversed_by_transaction_id = Set(Some(reversing_tx_id));
         original_active.updated_at = Set(now);
 
@@ -480,6 +1203,26 @@ impl WorkflowRepository {
             .update(&txn)
             .await
             .map_err(|e| WorkflowError::Database(e.to_string()))?;
+        record_transition(
+            &txn,
+            &voided_tx,
+            Some(from_status),
+            voided_by,
+            Some(void_notes),
+            now,
+        )
+        .await?;
+
+        // Release payment applications from or to the voided transaction
+        payment_applications::Entity::delete_many()
+            .filter(
+                Condition::any()
+                    .add(payment_applications::Column::PaymentTransactionId.eq(transaction_id))
+                    .add(payment_applications::Column::SettlesTransactionId.eq(transaction_id)),
+            )
+            .exec(&txn)
+            .await
+            .map_err(|e| WorkflowError::Database(e.to_string()))?;
 
         // Commit transaction
         txn.commit()
@@ -494,15 +1237,22 @@ impl WorkflowRepository {
 
     /// Gets pending transactions that the user can approve.
     ///
+    /// With `min_days`, only transactions submitted at least that many whole
+    /// days ago are returned. Roles that cannot see every transaction only get
+    /// the ones they created or may approve on a delegator's behalf.
+    ///
     /// Requirements: 5.1
     ///
     /// # Errors
     ///
     /// Returns an error if the database query fails.
+    #[allow(clippy::too_many_lines)]
     pub async fn get_pending_transactions(
         &self,
-        organization_id: Uuid,
+        organization_id: OrganizationId,
         user_id: Uuid,
+        min_days: Option<u32>,
+        sort: Option<Sort<PendingSortField>>,
     ) -> Result<Vec<PendingTransaction>, WorkflowError> {
         // Get user's role and approval limit
         let org_user = organization_users::Entity::find()
@@ -516,109 +1266,869 @@ impl WorkflowRepository {
             Some(ou) => (db_role_to_string(&ou.role), ou.approval_limit),
             None => return Ok(vec![]), // User not in organization
         };
+        let delegations = self
+            .delegated_authorities(organization_id, user_id, approval_limit)
+            .await?;
+
+        let sees_all = UserRole::parse(&user_role).is_some_and(|role| {
+            zeltra_core::auth::UserRole::from(role).can_view_all_transactions()
+        });
 
         // Fetch all pending transactions
-        let pending = transactions::Entity::find()
+        let mut query = transactions::Entity::find()
             .filter(transactions::Column::OrganizationId.eq(organization_id))
-            .filter(transactions::Column::Status.eq(TransactionStatus::Pending))
+            .filter(transactions::Column::Status.eq(TransactionStatus::Pending));
+        let now = Utc::now();
+        if let Some(min_days) = min_days {
+            let cutoff = now - chrono::Duration::days(i64::from(min_days));
+            query = query.filter(transactions::Column::SubmittedAt.lte(cutoff));
+        }
+        match sort {
+            Some(Sort {
+                field: PendingSortField::SubmittedAt,
+                direction,
+            }) => {
+                query = query.order_by(transactions::Column::SubmittedAt, direction.into());
+            }
+            // The longest waiting were submitted first
+            Some(Sort {
+                field: PendingSortField::DaysPending,
+                direction,
+            }) => {
+                let order = match direction {
+                    SortDirection::Asc => Order::Desc,
+                    SortDirection::Desc => Order::Asc,
+                };
+                query = query.order_by(transactions::Column::SubmittedAt, order);
+            }
+            _ => {}
+        }
+        let pending = query
             .order_by_desc(transactions::Column::CreatedAt)
             .all(&self.db)
             .await
             .map_err(|e| WorkflowError::Database(e.to_string()))?;
 
-        // Fetch approval rules
+        // Fetch approval rules and the approvals recorded so far
         let rules = self.get_approval_rules(organization_id).await?;
+        let approvals = transaction_approvals::Entity::find()
+            .filter(
+                transaction_approvals::Column::TransactionId.is_in(pending.iter().map(|t| t.id)),
+            )
+            .all(&self.db)
+            .await
+            .map_err(|e| WorkflowError::Database(e.to_string()))?;
+
+        let mut claims = load_claims(&self.db, pending.iter().map(|t| t.id).collect()).await?;
 
         // Check each transaction
         let mut result = Vec::with_capacity(pending.len());
         for tx in pending {
             let total = self.calculate_transaction_total(tx.id).await?;
-            let tx_type = db_tx_type_to_string(&tx.transaction_type);
-
-            // Get required role for this transaction
-            let required_role = ApprovalEngine::get_required_approval(&rules, &tx_type, total);
+            let requirement = ApprovalRequirement::of(&tx, &rules, total);
+            let required_role = requirement.required_role.as_str();
+            let tx_approvals: Vec<_> = approvals
+                .iter()
+                .filter(|a| a.transaction_id == tx.id)
+                .cloned()
+                .collect();
+            let counted = counted_approvers(&tx_approvals);
+            let progress = ApprovalProgress {
+                approvals: u32::try_from(tx_approvals.len()).unwrap_or(u32::MAX),
+                required: requirement.required_approvals,
+            };
 
-            let can_approve = match required_role {
-                Some(role) => {
-                    ApprovalEngine::can_approve(&user_role, approval_limit, &role, total).is_ok()
-                }
-                None => {
-                    // No rule matches, default to Approver role
-                    ApprovalEngine::can_approve(&user_role, approval_limit, "approver", total)
-                        .is_ok()
-                }
+            let (can_approve, on_behalf_of) = if counted.contains(&user_id) {
+                (false, None)
+            } else if ApprovalEngine::can_approve(&user_role, approval_limit, required_role, total)
+                .is_ok()
+            {
+                (true, None)
+            } else {
+                let delegator = delegations.iter().find_map(|(delegator, authority)| {
+                    (!counted.contains(delegator)
+                        && ApprovalEngine::can_approve_delegated(authority, required_role, total)
+                            .is_ok())
+                    .then_some(*delegator)
+                });
+                (delegator.is_some(), delegator)
             };
+            if !sees_all && tx.created_by != user_id && !can_approve {
+                continue;
+            }
 
+            let days_pending = tx
+                .submitted_at
+                .map_or(0, |at| days_pending(at.with_timezone(&Utc), now));
+            let claim = claims.remove(&tx.id).filter(|c| c.is_active(now));
             result.push(PendingTransaction {
                 transaction: tx,
                 can_approve,
+                on_behalf_of,
+                progress,
+                requirement,
+                total_amount: total,
+                days_pending,
+                claim,
+            });
+        }
+
+        if let Some(Sort {
+            field: PendingSortField::Amount,
+            direction,
+        }) = sort
+        {
+            result.sort_by(|a, b| match direction {
+                SortDirection::Asc => a.total_amount.cmp(&b.total_amount),
+                SortDirection::Desc => b.total_amount.cmp(&a.total_amount),
             });
         }
 
         Ok(result)
     }
 
+    /// Previews which approval rule a transaction of `transaction_type` for
+    /// `amount` would hit, and which members could approve it.
+    ///
+    /// Matches rules exactly as approval does. Submitter and prior-approver
+    /// exclusions are not applied since there is no transaction yet.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    pub async fn preview_approval(
+        &self,
+        organization_id: OrganizationId,
+        transaction_type: &TransactionType,
+        amount: Decimal,
+    ) -> Result<ApprovalPreview, WorkflowError> {
+        let rules = self.get_approval_rules(organization_id).await?;
+        let members = self.get_members(organization_id).await?;
+        let tx_type = db_tx_type_to_string(transaction_type);
+        let required = ApprovalEngine::required_approval(&rules, &tx_type, amount);
+
+        let mut approvers: Vec<PreviewApprover> = members
+            .values()
+            .filter(|(ou, user)| qualifies(ou, user, required.role, amount))
+            .map(|(ou, user)| PreviewApprover {
+                user_id: user.id,
+                email: user.email.clone(),
+                full_name: user.full_name.clone(),
+                role: db_role_to_string(&ou.role),
+                approval_limit: ou.approval_limit,
+            })
+            .collect();
+        approvers.sort_by(|a, b| a.email.cmp(&b.email));
+
+        Ok(ApprovalPreview {
+            rule: required.rule.cloned(),
+            required_role: required.role.to_string(),
+            required_approvals: required.approvals,
+            approvers,
+        })
+    }
+
+    /// Finds pending transactions whose approvers should be reminded at `now`.
+    ///
+    /// Uses each organization's `approval_escalation` settings and skips
+    /// transactions already escalated on the same UTC day. Approvers are the
+    /// active members whose role and limit satisfy the transaction's rule and
+    /// who have not approved it yet; the submitter is listed separately.
+    /// Delegates are not reminded.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    pub async fn find_due_escalations(
+        &self,
+        now: DateTime<Utc>,
+    ) -> Result<Vec<DueEscalation>, WorkflowError> {
+        let start_of_day = now.date_naive().and_time(NaiveTime::MIN).and_utc();
+        let candidates = transactions::Entity::find()
+            .filter(transactions::Column::Status.eq(TransactionStatus::Pending))
+            .filter(transactions::Column::SubmittedAt.is_not_null())
+            .filter(
+                Condition::any()
+                    .add(transactions::Column::LastEscalatedAt.is_null())
+                    .add(transactions::Column::LastEscalatedAt.lt(start_of_day)),
+            )
+            .order_by_asc(transactions::Column::OrganizationId)
+            .order_by_asc(transactions::Column::SubmittedAt)
+            .all(&self.db)
+            .await
+            .map_err(|e| WorkflowError::Database(e.to_string()))?;
+        if candidates.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let orgs = organizations::Entity::find()
+            .filter(organizations::Column::Id.is_in(candidates.iter().map(|t| t.organization_id)))
+            .filter(organizations::Column::IsActive.eq(true))
+            .all(&self.db)
+            .await
+            .map_err(|e| WorkflowError::Database(e.to_string()))?;
+
+        let mut due = Vec::new();
+        for org in orgs {
+            // Unreadable settings fall back to the defaults rather than
+            // silencing reminders
+            let settings = OrganizationSettings::from_value(&org.settings)
+                .unwrap_or_default()
+                .approval_escalation;
+            if !settings.enabled {
+                continue;
+            }
+
+            let org_due: Vec<&transactions::Model> = candidates
+                .iter()
+                .filter(|t| t.organization_id == org.id)
+                .filter(|t| {
+                    t.submitted_at.is_some_and(|submitted_at| {
+                        escalation_due(
+                            submitted_at.with_timezone(&Utc),
+                            t.last_escalated_at.map(|at| at.with_timezone(&Utc)),
+                            now,
+                            settings.after_business_days,
+                        )
+                    })
+                })
+                .collect();
+            if org_due.is_empty() {
+                continue;
+            }
+
+            let organization_id = OrganizationId::from_uuid(org.id);
+            let rules = self.get_approval_rules(organization_id).await?;
+            let members = self.get_members(organization_id).await?;
+            let approvals = transaction_approvals::Entity::find()
+                .filter(
+                    transaction_approvals::Column::TransactionId
+                        .is_in(org_due.iter().map(|t| t.id)),
+                )
+                .all(&self.db)
+                .await
+                .map_err(|e| WorkflowError::Database(e.to_string()))?;
+
+            for tx in org_due {
+                let total = self.calculate_transaction_total(tx.id).await?;
+                let submitter_id = tx.submitted_by.unwrap_or(tx.created_by);
+                let approvers = eligible_approvers(tx, &rules, &members, &approvals, total);
+
+                let submitter = members
+                    .get(&submitter_id)
+                    .filter(|(_, user)| user.is_active)
+                    .map(|(_, user)| recipient(user));
+                let days_pending = tx
+                    .submitted_at
+                    .map_or(0, |at| days_pending(at.with_timezone(&Utc), now));
+
+                due.push(DueEscalation {
+                    transaction: tx.clone(),
+                    organization_name: org.name.clone(),
+                    total_amount: total,
+                    days_pending,
+                    approvers,
+                    submitter,
+                });
+            }
+        }
+
+        Ok(due)
+    }
+
+    /// Finds the members who could approve a pending transaction: active,
+    /// not its submitter, not already approved, and with a role and limit
+    /// that satisfy the matched rule. Delegates are not included.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the transaction is not found or a database query
+    /// fails.
+    pub async fn find_eligible_approvers(
+        &self,
+        organization_id: OrganizationId,
+        transaction_id: TransactionId,
+    ) -> Result<Vec<EscalationRecipient>, WorkflowError> {
+        let transaction = transactions::Entity::find_by_id(transaction_id)
+            .filter(transactions::Column::OrganizationId.eq(organization_id))
+            .one(&self.db)
+            .await
+            .map_err(|e| WorkflowError::Database(e.to_string()))?
+            .ok_or(WorkflowError::TransactionNotFound(
+                transaction_id.into_inner(),
+            ))?;
+        let rules = self.get_approval_rules(organization_id).await?;
+        let members = self.get_members(organization_id).await?;
+        let approvals = transaction_approvals::Entity::find()
+            .filter(transaction_approvals::Column::TransactionId.eq(transaction.id))
+            .all(&self.db)
+            .await
+            .map_err(|e| WorkflowError::Database(e.to_string()))?;
+        let total = self.calculate_transaction_total(transaction.id).await?;
+
+        Ok(eligible_approvers(
+            &transaction,
+            &rules,
+            &members,
+            &approvals,
+            total,
+        ))
+    }
+
+    /// Records that a transaction was escalated at `now`.
+    ///
+    /// Returns `false` without changing anything when the transaction is no
+    /// longer pending or was already escalated that UTC day, so concurrent
+    /// runs cannot both send reminders.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database update fails.
+    pub async fn mark_escalated(
+        &self,
+        transaction_id: TransactionId,
+        now: DateTime<Utc>,
+    ) -> Result<bool, WorkflowError> {
+        let start_of_day = now.date_naive().and_time(NaiveTime::MIN).and_utc();
+        let result = transactions::Entity::update_many()
+            .col_expr(transactions::Column::LastEscalatedAt, Expr::value(now))
+            .filter(transactions::Column::Id.eq(transaction_id))
+            .filter(transactions::Column::Status.eq(TransactionStatus::Pending))
+            .filter(
+                Condition::any()
+                    .add(transactions::Column::LastEscalatedAt.is_null())
+                    .add(transactions::Column::LastEscalatedAt.lt(start_of_day)),
+            )
+            .exec(&self.db)
+            .await
+            .map_err(|e| WorkflowError::Database(e.to_string()))?;
+
+        Ok(result.rows_affected > 0)
+    }
+
+    /// Gets a transaction's workflow transitions, oldest first.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - Transaction is not found
+    /// - Database operation fails
+    pub async fn get_status_history(
+        &self,
+        organization_id: OrganizationId,
+        transaction_id: TransactionId,
+    ) -> Result<TransactionHistory, WorkflowError> {
+        let transaction = transactions::Entity::find_by_id(transaction_id)
+            .filter(transactions::Column::OrganizationId.eq(organization_id))
+            .one(&self.db)
+            .await
+            .map_err(|e| WorkflowError::Database(e.to_string()))?
+            .ok_or(WorkflowError::TransactionNotFound(
+                transaction_id.into_inner(),
+            ))?;
+
+        let entries = transaction_status_history::Entity::find()
+            .filter(transaction_status_history::Column::TransactionId.eq(transaction.id))
+            .find_also_related(users::Entity)
+            .order_by_asc(transaction_status_history::Column::CreatedAt)
+            .all(&self.db)
+            .await
+            .map_err(|e| WorkflowError::Database(e.to_string()))?
+            .into_iter()
+            .map(|(history, actor)| StatusHistoryEntry {
+                history,
+                actor_name: actor.map(|u| u.full_name),
+            })
+            .collect();
+
+        Ok(TransactionHistory {
+            transaction,
+            entries,
+        })
+    }
+
+    /// Gets the workflow actions a user may take on a transaction.
+    ///
+    /// Gathers the approval rule, prior approvals, delegations and fiscal
+    /// period status the transitions would check, so the answer matches what
+    /// performing the action would do.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - Transaction is not found
+    /// - User is not a member of the organization
+    /// - Database operation fails
+    pub async fn get_available_actions(
+        &self,
+        organization_id: OrganizationId,
+        transaction_id: TransactionId,
+        user_id: Uuid,
+    ) -> Result<TransactionActions, WorkflowError> {
+        let transaction = transactions::Entity::find_by_id(transaction_id)
+            .filter(transactions::Column::OrganizationId.eq(organization_id))
+            .one(&self.db)
+            .await
+            .map_err(|e| WorkflowError::Database(e.to_string()))?
+            .ok_or(WorkflowError::TransactionNotFound(
+                transaction_id.into_inner(),
+            ))?;
+
+        let org_user = organization_users::Entity::find()
+            .filter(organization_users::Column::OrganizationId.eq(organization_id))
+            .filter(organization_users::Column::UserId.eq(user_id))
+            .one(&self.db)
+            .await
+            .map_err(|e| WorkflowError::Database(e.to_string()))?
+            .ok_or(WorkflowError::NotAuthorizedToApprove)?;
+        let user_role = UserRole::parse(&db_role_to_string(&org_user.role))
+            .ok_or(WorkflowError::NotAuthorizedToApprove)?;
+
+        let period = fiscal_periods::Entity::find_by_id(transaction.fiscal_period_id)
+            .one(&self.db)
+            .await
+            .map_err(|e| WorkflowError::Database(e.to_string()))?
+            .ok_or_else(|| {
+                WorkflowError::Database(format!(
+                    "Fiscal period not found: {}",
+                    transaction.fiscal_period_id
+                ))
+            })?;
+
+        let prior_approvers: Vec<Uuid> = transaction_approvals::Entity::find()
+            .filter(transaction_approvals::Column::TransactionId.eq(transaction.id))
+            .all(&self.db)
+            .await
+            .map_err(|e| WorkflowError::Database(e.to_string()))?
+            .into_iter()
+            .map(|a| a.approved_by)
+            .collect();
+        let total = self.calculate_transaction_total(transaction.id).await?;
+        let requirement = self
+            .requirement(organization_id, &transaction, total)
+            .await?;
+        let delegations: Vec<DelegatedAuthority> = self
+            .delegated_authorities(organization_id, user_id, org_user.approval_limit)
+            .await?
+            .into_iter()
+            .map(|(_, authority)| authority)
+            .collect();
+
+        // Only a posted transaction can be voided, so only then look for a lock
+        let reconciliation_id = if transaction.status == TransactionStatus::Posted {
+            let entry_ids: Vec<Uuid> = ledger_entries::Entity::find()
+                .select_only()
+                .column(ledger_entries::Column::Id)
+                .filter(ledger_entries::Column::TransactionId.eq(transaction.id))
+                .into_tuple()
+                .all(&self.db)
+                .await
+                .map_err(|e| WorkflowError::Database(e.to_string()))?;
+            self.find_completed_reconciliation(entry_ids).await?
+        } else {
+            None
+        };
+
+        let actions = WorkflowService::available_actions(&ActionContext {
+            status: CoreStatus::from(&transaction.status),
+            user_id,
+            user_role,
+            approval_limit: org_user.approval_limit,
+            amount: total,
+            required_role: &requirement.required_role,
+            required_approvals: requirement.required_approvals,
+            prior_approvers: &prior_approvers,
+            delegations: &delegations,
+            period_status: db_period_status_to_core(&period.status),
+            reconciliation_id,
+        });
+
+        Ok(TransactionActions {
+            transaction,
+            actions,
+        })
+    }
+
     /// Bulk approves multiple transactions.
     ///
+    /// Everything the approvals depend on is loaded in one go, every item is
+    /// checked in memory, and the allowed ones are applied in a single
+    /// database transaction. Denied items fail with the same error a single
+    /// approval would give.
+    ///
     /// Requirements: 5.2, 5.3, 5.4
     ///
     /// # Errors
     ///
-    /// Returns an error if the database query fails.
+    /// Returns an error if a database query fails, in which case nothing is
+    /// approved.
     pub async fn bulk_approve(
         &self,
-        organization_id: Uuid,
-        transaction_ids: Vec<Uuid>,
+        organization_id: OrganizationId,
+        transaction_ids: Vec<TransactionId>,
         approved_by: Uuid,
         approval_notes: Option<String>,
     ) -> Result<BulkApproveResult, WorkflowError> {
+        if transaction_ids.is_empty() {
+            return Ok(BulkApproveResult {
+                results: vec![],
+                success_count: 0,
+                failure_count: 0,
+            });
+        }
+
+        let txn = self
+            .db
+            .begin()
+            .await
+            .map_err(|e| WorkflowError::Database(e.to_string()))?;
+
+        let context = self
+            .load_approval_context_on(&txn, organization_id, &transaction_ids, approved_by)
+            .await?;
+        let plan = bulk_approval_plan(&context, &transaction_ids, approved_by);
+        apply_bulk_approval(
+            &txn,
+            &context,
+            &plan,
+            approved_by,
+            approval_notes.as_deref(),
+        )
+        .await?;
+
+        txn.commit()
+            .await
+            .map_err(|e| WorkflowError::Database(e.to_string()))?;
+
+        let mut success_count = 0;
+        let mut failure_count = 0;
+        let results = plan
+            .into_iter()
+            .map(
+                |item| match record_workflow_result("approve", item.decision) {
+                    Ok(_) => {
+                        success_count += 1;
+                        BulkApproveItemResult {
+                            transaction_id: item.transaction_id,
+                            success: true,
+                            error: None,
+                        }
+                    }
+                    Err(e) => {
+                        failure_count += 1;
+                        BulkApproveItemResult {
+                            transaction_id: item.transaction_id,
+                            success: false,
+                            error: Some(e.to_string()),
+                        }
+                    }
+                },
+            )
+            .collect();
+
+        Ok(BulkApproveResult {
+            results,
+            success_count,
+            failure_count,
+        })
+    }
+
+    /// Loads the transactions, totals and prior approvals of a bulk
+    /// approval together with the approver's authority and the
+    /// organization's approval rules.
+    ///
+    /// Requested IDs outside the organization are left out of
+    /// `transactions`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a database query fails.
+    pub async fn load_approval_context(
+        &self,
+        organization_id: OrganizationId,
+        transaction_ids: &[TransactionId],
+        approver_id: Uuid,
+    ) -> Result<ApprovalContext, WorkflowError> {
+        self.load_approval_context_on(&self.db, organization_id, transaction_ids, approver_id)
+            .await
+    }
+
+    /// Loads an approval context on `db`, locking the transactions so
+    /// concurrent approvals are counted in turn.
+    async fn load_approval_context_on<C: ConnectionTrait>(
+        &self,
+        db: &C,
+        organization_id: OrganizationId,
+        transaction_ids: &[TransactionId],
+        approver_id: Uuid,
+    ) -> Result<ApprovalContext, WorkflowError> {
+        let ids: Vec<Uuid> = transaction_ids.iter().map(|id| id.into_inner()).collect();
+
+        let transactions: HashMap<Uuid, transactions::Model> = transactions::Entity::find()
+            .filter(transactions::Column::OrganizationId.eq(organization_id))
+            .filter(transactions::Column::Id.is_in(ids.iter().copied()))
+            .lock_exclusive()
+            .all(db)
+            .await
+            .map_err(|e| WorkflowError::Database(e.to_string()))?
+            .into_iter()
+            .map(|t| (t.id, t))
+            .collect();
+        if transactions.is_empty() {
+            return Ok(ApprovalContext {
+                transactions,
+                totals: HashMap::new(),
+                prior_approvers: HashMap::new(),
+                counted_approvers: HashMap::new(),
+                approver: None,
+                delegations: vec![],
+                rules: vec![],
+                claims: HashMap::new(),
+            });
+        }
+
+        let found: Vec<Uuid> = transactions.keys().copied().collect();
+        let totals: HashMap<Uuid, Decimal> = ledger_entries::Entity::find()
+            .filter(ledger_entries::Column::TransactionId.is_in(found.iter().copied()))
+            .select_only()
+            .column(ledger_entries::Column::TransactionId)
+            .column_as(ledger_entries::Column::Debit.sum(), "total")
+            .group_by(ledger_entries::Column::TransactionId)
+            .into_tuple::<(Uuid, Decimal)>()
+            .all(db)
+            .await
+            .map_err(|e| WorkflowError::Database(e.to_string()))?
+            .into_iter()
+            .collect();
+
+        let mut approvals: HashMap<Uuid, Vec<transaction_approvals::Model>> = HashMap::new();
+        for approval in transaction_approvals::Entity::find()
+            .filter(transaction_approvals::Column::TransactionId.is_in(found))
+            .all(db)
+            .await
+            .map_err(|e| WorkflowError::Database(e.to_string()))?
+        {
+            approvals
+                .entry(approval.transaction_id)
+                .or_default()
+                .push(approval);
+        }
+        let prior_approvers = approvals
+            .iter()
+            .map(|(id, approvals)| (*id, approvals.iter().map(|a| a.approved_by).collect()))
+            .collect();
+        let counted_approvers = approvals
+            .iter()
+            .map(|(id, approvals)| (*id, counted_approvers(approvals)))
+            .collect();
+
+        let approver = organization_users::Entity::find()
+            .filter(organization_users::Column::OrganizationId.eq(organization_id))
+            .filter(organization_users::Column::UserId.eq(approver_id))
+            .one(db)
+            .await
+            .map_err(|e| WorkflowError::Database(e.to_string()))?;
+        let delegations = match &approver {
+            Some(member) => {
+                self.delegated_authorities(organization_id, approver_id, member.approval_limit)
+                    .await?
+            }
+            None => vec![],
+        };
+
+        Ok(ApprovalContext {
+            totals,
+            prior_approvers,
+            counted_approvers,
+            approver,
+            delegations,
+            rules: self.get_approval_rules(organization_id).await?,
+            claims: load_claims(db, transactions.keys().copied().collect()).await?,
+            transactions,
+        })
+    }
+
+    /// Voids multiple posted transactions with a shared reason.
+    ///
+    /// Each void runs the full reversal in its own database transaction, so
+    /// one failing item does not undo the others.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    pub async fn bulk_void(
+        &self,
+        organization_id: OrganizationId,
+        transaction_ids: Vec<TransactionId>,
+        voided_by: Uuid,
+        void_reason_code: VoidReasonCode,
+        void_reason: String,
+    ) -> Result<BulkVoidResult, WorkflowError> {
         let mut results = Vec::with_capacity(transaction_ids.len());
         let mut success_count = 0;
         let mut failure_count = 0;
 
         for tx_id in transaction_ids {
             match self
-                .approve_transaction(organization_id, tx_id, approved_by, approval_notes.clone())
+                .void_transaction(
+                    organization_id,
+                    tx_id,
+                    voided_by,
+                    void_reason_code,
+                    void_reason.clone(),
+                )
                 .await
             {
-                Ok(_) => {
+                Ok(result) => {
                     success_count += 1;
-                    results.push(BulkApproveItemResult {
-                        transaction_id: tx_id,
+                    results.push(BulkVoidItemResult {
+                        transaction_id: tx_id.into_inner(),
                         success: true,
+                        reversing_transaction_id: Some(result.reversing_transaction.id),
+                        error_code: None,
                         error: None,
                     });
                 }
                 Err(e) => {
                     failure_count += 1;
-                    results.push(BulkApproveItemResult {
-                        transaction_id: tx_id,
+                    results.push(BulkVoidItemResult {
+                        transaction_id: tx_id.into_inner(),
                         success: false,
+                        reversing_transaction_id: None,
+                        error_code: Some(bulk_void_error_code(&e)),
                         error: Some(e.to_string()),
                     });
                 }
             }
         }
 
-        Ok(BulkApproveResult {
+        Ok(BulkVoidResult {
             results,
             success_count,
             failure_count,
         })
     }
 
+    // ========================================================================
+    // Deprecated Uuid shims
+    // ========================================================================
+
+    /// Submits a draft transaction given raw `Uuid`s.
+    ///
+    /// # Errors
+    ///
+    /// See [`Self::submit_transaction`].
+    #[deprecated(note = "use `submit_transaction` with typed ids")]
+    pub async fn submit_transaction_by_uuid(
+        &self,
+        organization_id: Uuid,
+        transaction_id: Uuid,
+        submitted_by: Uuid,
+    ) -> Result<transactions::Model, WorkflowError> {
+        self.submit_transaction(organization_id.into(), transaction_id.into(), submitted_by)
+            .await
+    }
+
+    /// Approves a pending transaction given raw `Uuid`s.
+    ///
+    /// # Errors
+    ///
+    /// See [`Self::approve_transaction`].
+    #[deprecated(note = "use `approve_transaction` with typed ids")]
+    pub async fn approve_transaction_by_uuid(
+        &self,
+        organization_id: Uuid,
+        transaction_id: Uuid,
+        approved_by: Uuid,
+        approval_notes: Option<String>,
+    ) -> Result<ApprovalOutcome, WorkflowError> {
+        self.approve_transaction(
+            organization_id.into(),
+            transaction_id.into(),
+            approved_by,
+            approval_notes,
+        )
+        .await
+    }
+
+    /// Rejects a pending transaction given raw `Uuid`s.
+    ///
+    /// # Errors
+    ///
+    /// See [`Self::reject_transaction`].
+    #[deprecated(note = "use `reject_transaction` with typed ids")]
+    pub async fn reject_transaction_by_uuid(
+        &self,
+        organization_id: Uuid,
+        transaction_id: Uuid,
+        rejected_by: Uuid,
+        rejection_reason: String,
+    ) -> Result<transactions::Model, WorkflowError> {
+        self.reject_transaction(
+            organization_id.into(),
+            transaction_id.into(),
+            rejected_by,
+            rejection_reason,
+        )
+        .await
+    }
+
+    /// Posts an approved transaction given raw `Uuid`s.
+    ///
+    /// # Errors
+    ///
+    /// See [`Self::post_transaction`].
+    #[deprecated(note = "use `post_transaction` with typed ids")]
+    pub async fn post_transaction_by_uuid(
+        &self,
+        organization_id: Uuid,
+        transaction_id: Uuid,
+        posted_by: Uuid,
+    ) -> Result<transactions::Model, WorkflowError> {
+        self.post_transaction(organization_id.into(), transaction_id.into(), posted_by)
+            .await
+    }
+
+    /// Voids a posted transaction given raw `Uuid`s.
+    ///
+    /// # Errors
+    ///
+    /// See [`Self::void_transaction`].
+    #[deprecated(note = "use `void_transaction` with typed ids")]
+    pub async fn void_transaction_by_uuid(
+        &self,
+        organization_id: Uuid,
+        transaction_id: Uuid,
+        voided_by: Uuid,
+        void_reason_code: VoidReasonCode,
+        void_reason: String,
+    ) -> Result<VoidResult, WorkflowError> {
+        self.void_transaction(
+            organization_id.into(),
+            transaction_id.into(),
+            voided_by,
+            void_reason_code,
+            void_reason,
+        )
+        .await
+    }
+
     // ========================================================================
     // Helper methods
     // ========================================================================
 
     /// Checks if a user is authorized to approve a transaction.
+    ///
+    /// When the user's own role or limit falls short, their active
+    /// delegations are tried in turn. Returns the delegator whose authority
+    /// was used, if any.
     async fn check_approval_authorization(
         &self,
-        organization_id: Uuid,
+        organization_id: OrganizationId,
         user_id: Uuid,
-        transaction_type: &TransactionType,
+        required_role: &str,
         amount: Decimal,
-    ) -> Result<(), WorkflowError> {
+    ) -> Result<Option<Uuid>, WorkflowError> {
         // Get user's role and approval limit
         let org_user = organization_users::Entity::find()
             .filter(organization_users::Column::OrganizationId.eq(organization_id))
@@ -631,22 +2141,118 @@ impl WorkflowRepository {
         let user_role = db_role_to_string(&org_user.role);
         let approval_limit = org_user.approval_limit;
 
-        // Get approval rules
-        let rules = self.get_approval_rules(organization_id).await?;
-        let tx_type = db_tx_type_to_string(transaction_type);
+        // Check authorization
+        let Err(mut err) =
+            ApprovalEngine::can_approve(&user_role, approval_limit, required_role, amount)
+        else {
+            return Ok(None);
+        };
 
-        // Get required role
-        let required_role = ApprovalEngine::get_required_approval(&rules, &tx_type, amount)
-            .unwrap_or_else(|| "approver".to_string());
+        // Report why the last delegation fell short, e.g. its cap, over the
+        // user's own role
+        for (delegator, authority) in self
+            .delegated_authorities(organization_id, user_id, approval_limit)
+            .await?
+        {
+            match ApprovalEngine::can_approve_delegated(&authority, required_role, amount) {
+                Ok(()) => return Ok(Some(delegator)),
+                Err(e) => err = e,
+            }
+        }
+        Err(err)
+    }
 
-        // Check authorization
-        ApprovalEngine::can_approve(&user_role, approval_limit, &required_role, amount)
+    /// Gets the authority a user holds through delegations active right now.
+    ///
+    /// Expired delegations are filtered out here rather than cleaned up, so
+    /// they stop counting the moment they end.
+    async fn delegated_authorities(
+        &self,
+        organization_id: OrganizationId,
+        delegate_id: Uuid,
+        delegate_limit: Option<Decimal>,
+    ) -> Result<Vec<(Uuid, DelegatedAuthority)>, WorkflowError> {
+        let now = Utc::now();
+        let delegations = approval_delegations::Entity::find()
+            .filter(approval_delegations::Column::OrganizationId.eq(organization_id))
+            .filter(approval_delegations::Column::DelegateId.eq(delegate_id))
+            .filter(approval_delegations::Column::StartsAt.lte(now))
+            .filter(approval_delegations::Column::EndsAt.gt(now))
+            .order_by_asc(approval_delegations::Column::StartsAt)
+            .all(&self.db)
+            .await
+            .map_err(|e| WorkflowError::Database(e.to_string()))?;
+        if delegations.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let delegators = organization_users::Entity::find()
+            .filter(organization_users::Column::OrganizationId.eq(organization_id))
+            .filter(
+                organization_users::Column::UserId
+                    .is_in(delegations.iter().map(|d| d.delegator_id)),
+            )
+            .all(&self.db)
+            .await
+            .map_err(|e| WorkflowError::Database(e.to_string()))?;
+
+        // A delegator who has left the organization has nothing to delegate
+        Ok(delegations
+            .into_iter()
+            .filter_map(|d| {
+                let delegator = delegators.iter().find(|u| u.user_id == d.delegator_id)?;
+                Some((
+                    d.delegator_id,
+                    DelegatedAuthority {
+                        delegator_role: db_role_to_string(&delegator.role),
+                        delegator_limit: delegator.approval_limit,
+                        delegate_limit,
+                        amount_cap: d.amount_cap,
+                    },
+                ))
+            })
+            .collect())
+    }
+
+    /// Gets what approving a submitted transaction requires, loading the
+    /// rules only when it has no snapshot.
+    async fn requirement(
+        &self,
+        organization_id: OrganizationId,
+        transaction: &transactions::Model,
+        amount: Decimal,
+    ) -> Result<ApprovalRequirement, WorkflowError> {
+        if let Some(snapshot) = ApprovalRequirement::snapshot(transaction) {
+            return Ok(snapshot);
+        }
+        let rules = self.get_approval_rules(organization_id).await?;
+        Ok(ApprovalRequirement::evaluate(
+            &rules,
+            &transaction.transaction_type,
+            amount,
+        ))
+    }
+
+    /// Gets an organization's members with their user rows, keyed by user ID.
+    async fn get_members(
+        &self,
+        organization_id: OrganizationId,
+    ) -> Result<HashMap<Uuid, (organization_users::Model, users::Model)>, WorkflowError> {
+        Ok(organization_users::Entity::find()
+            .filter(organization_users::Column::OrganizationId.eq(organization_id))
+            .find_also_related(users::Entity)
+            .all(&self.db)
+            .await
+            .map_err(|e| WorkflowError::Database(e.to_string()))?
+            .into_iter()
+            .filter_map(|(ou, user)| user.map(|u| (ou.user_id, (ou, u))))
+            .collect())
     }
 
     /// Gets approval rules for an organization.
     async fn get_approval_rules(
         &self,
-        organization_id: Uuid,
+        organization_id: OrganizationId,
     ) -> Result<Vec<ApprovalRule>, WorkflowError> {
         let db_rules = approval_rules::Entity::find()
             .filter(approval_rules::Column::OrganizationId.eq(organization_id))
@@ -670,12 +2276,32 @@ impl WorkflowRepository {
                     .collect(),
                 required_role: db_role_to_string(&r.required_role),
                 priority: r.priority,
+                required_approvals: u32::try_from(r.required_approvals).unwrap_or(1),
             })
             .collect();
 
         Ok(rules)
     }
 
+    /// Finds a completed reconciliation that holds any of the given entries.
+    async fn find_completed_reconciliation(
+        &self,
+        ledger_entry_ids: Vec<Uuid>,
+    ) -> Result<Option<Uuid>, WorkflowError> {
+        let items = reconciliation_items::Entity::find()
+            .filter(reconciliation_items::Column::LedgerEntryId.is_in(ledger_entry_ids))
+            .find_also_related(reconciliations::Entity)
+            .all(&self.db)
+            .await
+            .map_err(|e| WorkflowError::Database(e.to_string()))?;
+
+        Ok(items.into_iter().find_map(|(item, parent)| {
+            parent
+                .filter(|p| p.status == ReconciliationStatus::Completed)
+                .map(|_| item.reconciliation_id)
+        }))
+    }
+
     /// Calculates the total amount of a transaction.
     async fn calculate_transaction_total(
         &self,
@@ -698,16 +2324,361 @@ impl WorkflowRepository {
 // Conversion helpers
 // ============================================================================
 
-/// Converts database TransactionStatus to core TransactionStatus.
-fn db_status_to_core(
-    status: &TransactionStatus,
-) -> zeltra_core::workflow::types::TransactionStatus {
+/// Whether an active member's role and limit cover `amount` under
+/// `required_role`.
+fn qualifies(
+    member: &organization_users::Model,
+    user: &users::Model,
+    required_role: &str,
+    amount: Decimal,
+) -> bool {
+    user.is_active
+        && ApprovalEngine::can_approve(
+            &db_role_to_string(&member.role),
+            member.approval_limit,
+            required_role,
+            amount,
+        )
+        .is_ok()
+}
+
+/// Loads the review claims on `transaction_ids` with their reviewers'
+/// names, keyed by transaction. Lapsed claims are included.
+async fn load_claims<C: ConnectionTrait>(
+    db: &C,
+    transaction_ids: Vec<Uuid>,
+) -> Result<HashMap<Uuid, ReviewClaim>, WorkflowError> {
+    if transaction_ids.is_empty() {
+        return Ok(HashMap::new());
+    }
+    Ok(transaction_claims::Entity::find()
+        .filter(transaction_claims::Column::TransactionId.is_in(transaction_ids))
+        .find_also_related(users::Entity)
+        .all(db)
+        .await
+        .map_err(|e| WorkflowError::Database(e.to_string()))?
+        .into_iter()
+        .map(|(claim, reviewer)| {
+            (
+                claim.transaction_id,
+                ReviewClaim {
+                    reviewer_id: claim.reviewing_by,
+                    reviewer_name: reviewer.map(|u| u.full_name).unwrap_or_default(),
+                    expires_at: claim.reviewing_until.with_timezone(&Utc),
+                },
+            )
+        })
+        .collect())
+}
+
+/// Checks that `user_id` may act on a transaction despite its review claim.
+async fn check_claim<C: ConnectionTrait>(
+    db: &C,
+    organization_id: OrganizationId,
+    transaction_id: Uuid,
+    user_id: Uuid,
+) -> Result<(), WorkflowError> {
+    let Some(claim) = load_claims(db, vec![transaction_id])
+        .await?
+        .remove(&transaction_id)
+    else {
+        return Ok(());
+    };
+    let role = organization_users::Entity::find()
+        .filter(organization_users::Column::OrganizationId.eq(organization_id))
+        .filter(organization_users::Column::UserId.eq(user_id))
+        .one(db)
+        .await
+        .map_err(|e| WorkflowError::Database(e.to_string()))?
+        .and_then(|member| UserRole::parse(&db_role_to_string(&member.role)));
+    claim.check_action(user_id, role, Utc::now())
+}
+
+/// Drops the review claims on `transaction_ids`.
+async fn clear_claims<C: ConnectionTrait>(
+    db: &C,
+    transaction_ids: Vec<Uuid>,
+) -> Result<(), WorkflowError> {
+    transaction_claims::Entity::delete_many()
+        .filter(transaction_claims::Column::TransactionId.is_in(transaction_ids))
+        .exec(db)
+        .await
+        .map_err(|e| WorkflowError::Database(e.to_string()))?;
+    Ok(())
+}
+
+/// Users behind `approvals`: each approver and the delegator they approved
+/// for, if any.
+fn counted_approvers(approvals: &[transaction_approvals::Model]) -> Vec<Uuid> {
+    approvals
+        .iter()
+        .flat_map(|a| [Some(a.approved_by), a.approved_on_behalf_of])
+        .flatten()
+        .collect()
+}
+
+/// Plans a bulk approval from its loaded context, in request order.
+fn bulk_approval_plan(
+    context: &ApprovalContext,
+    transaction_ids: &[TransactionId],
+    approved_by: Uuid,
+) -> Vec<BulkApprovalDecision> {
+    let required: Vec<Option<(String, u32, Decimal)>> = transaction_ids
+        .iter()
+        .map(|id| {
+            let transaction = context.transactions.get(&id.into_inner())?;
+            let total = context
+                .totals
+                .get(&transaction.id)
+                .copied()
+                .unwrap_or(Decimal::ZERO);
+            let requirement = ApprovalRequirement::of(transaction, &context.rules, total);
+            Some((
+                requirement.required_role,
+                requirement.required_approvals,
+                total,
+            ))
+        })
+        .collect();
+    let items: Vec<BulkApprovalItem<'_>> = transaction_ids
+        .iter()
+        .zip(&required)
+        .map(|(id, required)| {
+            let transaction = context.transactions.get(&id.into_inner());
+            let (required_role, required_approvals, amount) = required.as_ref().map_or(
+                ("approver", 1, Decimal::ZERO),
+                |(role, approvals, total)| (role.as_str(), *approvals, *total),
+            );
+            BulkApprovalItem {
+                transaction_id: id.into_inner(),
+                status: transaction.map(|t| CoreStatus::from(&t.status)),
+                amount,
+                required_role,
+                required_approvals,
+                prior_approvers: context
+                    .prior_approvers
+                    .get(&id.into_inner())
+                    .map_or(&[][..], Vec::as_slice),
+                counted_approvers: context
+                    .counted_approvers
+                    .get(&id.into_inner())
+                    .map_or(&[][..], Vec::as_slice),
+            }
+        })
+        .collect();
+
+    let role = context
+        .approver
+        .as_ref()
+        .map(|a| db_role_to_string(&a.role));
+    let mut plan = WorkflowService::plan_bulk_approval(
+        &items,
+        &BulkApprover {
+            user_id: approved_by,
+            role: role.as_deref(),
+            approval_limit: context.approver.as_ref().and_then(|a| a.approval_limit),
+            delegations: &context.delegations,
+        },
+    );
+
+    // Another reviewer's claim turns away an otherwise allowed approval
+    let user_role = role.as_deref().and_then(UserRole::parse);
+    let now = Utc::now();
+    for item in &mut plan {
+        if let (Ok(_), Some(claim)) = (&item.decision, context.claims.get(&item.transaction_id))
+            && let Err(e) = claim.check_action(approved_by, user_role, now)
+        {
+            item.decision = Err(e);
+        }
+    }
+    plan
+}
+
+/// Records the approvals a bulk plan allows, with one status update per
+/// delegator and batched approval, comment and history rows.
+async fn apply_bulk_approval<C: ConnectionTrait>(
+    db: &C,
+    context: &ApprovalContext,
+    plan: &[BulkApprovalDecision],
+    approved_by: Uuid,
+    approval_notes: Option<&str>,
+) -> Result<(), WorkflowError> {
+    let allowed: Vec<_> = plan
+        .iter()
+        .filter_map(|item| {
+            let approval = item.decision.as_ref().ok()?;
+            Some((context.transactions.get(&item.transaction_id)?, approval))
+        })
+        .collect();
+    if allowed.is_empty() {
+        return Ok(());
+    }
+
+    clear_claims(db, allowed.iter().map(|(t, _)| t.id).collect()).await?;
+
+    let now: DateTimeWithTimeZone = Utc::now().into();
+    transaction_approvals::Entity::insert_many(allowed.iter().map(|(transaction, approval)| {
+        transaction_approvals::ActiveModel {
+            id: Set(Uuid::new_v4()),
+            organization_id: Set(transaction.organization_id),
+            transaction_id: Set(transaction.id),
+            approved_by: Set(approved_by),
+            approved_on_behalf_of: Set(approval.on_behalf_of),
+            approval_notes: Set(approval_notes.map(str::to_string)),
+            approved_at: Set(now),
+        }
+    }))
+    .exec(db)
+    .await
+    .map_err(|e| WorkflowError::Database(e.to_string()))?;
+
+    // Approvals short of the required count leave the status alone
+    let mut completed: HashMap<Option<Uuid>, Vec<Uuid>> = HashMap::new();
+    for (transaction, approval) in &allowed {
+        if approval.new_status == CoreStatus::Approved {
+            completed
+                .entry(approval.on_behalf_of)
+                .or_default()
+                .push(transaction.id);
+        }
+    }
+    for (on_behalf_of, ids) in completed {
+        transactions::Entity::update_many()
+            .col_expr(
+                transactions::Column::Status,
+                transactions::Column::Status.save_as(Expr::val(TransactionStatus::Approved)),
+            )
+            .col_expr(transactions::Column::ApprovedAt, Expr::value(now))
+            .col_expr(transactions::Column::ApprovedBy, Expr::value(approved_by))
+            .col_expr(
+                transactions::Column::ApprovedOnBehalfOf,
+                Expr::value(on_behalf_of),
+            )
+            .col_expr(
+                transactions::Column::ApprovalNotes,
+                Expr::value(approval_notes.map(str::to_string)),
+            )
+            .col_expr(transactions::Column::UpdatedAt, Expr::value(now))
+            .filter(transactions::Column::Id.is_in(ids))
+            .exec(db)
+            .await
+            .map_err(|e| WorkflowError::Database(e.to_string()))?;
+    }
+
+    if let Some(notes) = approval_notes {
+        let approved: Vec<&transactions::Model> = allowed.iter().map(|(t, _)| *t).collect();
+        record_workflow_comments(
+            db,
+            &approved,
+            approved_by,
+            CommentKind::Approval,
+            notes,
+            now,
+        )
+        .await
+        .map_err(|e| WorkflowError::Database(e.to_string()))?;
+    }
+
+    transaction_status_history::Entity::insert_many(allowed.iter().map(
+        |(transaction, approval)| transaction_status_history::ActiveModel {
+            id: Set(Uuid::new_v4()),
+            organization_id: Set(transaction.organization_id),
+            transaction_id: Set(transaction.id),
+            from_status: Set(Some(transaction.status.clone())),
+            to_status: Set(approval.new_status.into()),
+            actor_id: Set(approved_by),
+            notes: Set(approval_notes.map(str::to_string)),
+            created_at: Set(now),
+        },
+    ))
+    .exec(db)
+    .await
+    .map_err(|e| WorkflowError::Database(e.to_string()))?;
+
+    Ok(())
+}
+
+/// Members who could approve `tx`, sorted by email.
+fn eligible_approvers(
+    tx: &transactions::Model,
+    rules: &[ApprovalRule],
+    members: &HashMap<Uuid, (organization_users::Model, users::Model)>,
+    approvals: &[transaction_approvals::Model],
+    total: Decimal,
+) -> Vec<EscalationRecipient> {
+    let required_role = ApprovalRequirement::of(tx, rules, total).required_role;
+    let submitter_id = tx.submitted_by.unwrap_or(tx.created_by);
+
+    let mut approvers: Vec<EscalationRecipient> = members
+        .values()
+        .filter(|(ou, user)| {
+            user.id != submitter_id
+                && !approvals
+                    .iter()
+                    .any(|a| a.transaction_id == tx.id && a.approved_by == user.id)
+                && qualifies(ou, user, &required_role, total)
+        })
+        .map(|(_, user)| recipient(user))
+        .collect();
+    approvers.sort_by(|a, b| a.email.cmp(&b.email));
+    approvers
+}
+
+/// Builds an escalation recipient from a user row.
+fn recipient(user: &users::Model) -> EscalationRecipient {
+    EscalationRecipient {
+        user_id: user.id,
+        email: user.email.clone(),
+        full_name: user.full_name.clone(),
+    }
+}
+
+/// Error code reported for a failed item in a bulk void.
+fn bulk_void_error_code(error: &WorkflowError) -> &'static str {
+    match error {
+        WorkflowError::InvalidTransition {
+            from: CoreStatus::Voided,
+            ..
+        } => "already_voided",
+        WorkflowError::InvalidTransition { .. } => "not_posted",
+        WorkflowError::TransactionNotFound(_) => "not_found",
+        WorkflowError::PeriodClosed => "period_closed",
+        WorkflowError::EntryReconciled { .. } => "entry_reconciled",
+        WorkflowError::VoidReasonRequired => "void_reason_required",
+        _ => "void_failed",
+    }
+}
+
+/// Converts database FiscalPeriodStatus to core FiscalPeriodStatus.
+fn db_period_status_to_core(status: &FiscalPeriodStatus) -> CoreFiscalPeriodStatus {
     match status {
-        TransactionStatus::Draft => zeltra_core::workflow::types::TransactionStatus::Draft,
-        TransactionStatus::Pending => zeltra_core::workflow::types::TransactionStatus::Pending,
-        TransactionStatus::Approved => zeltra_core::workflow::types::TransactionStatus::Approved,
-        TransactionStatus::Posted => zeltra_core::workflow::types::TransactionStatus::Posted,
-        TransactionStatus::Voided => zeltra_core::workflow::types::TransactionStatus::Voided,
+        FiscalPeriodStatus::Open => CoreFiscalPeriodStatus::Open,
+        FiscalPeriodStatus::SoftClose => CoreFiscalPeriodStatus::SoftClose,
+        FiscalPeriodStatus::Closed => CoreFiscalPeriodStatus::Closed,
+    }
+}
+
+/// Converts a core void reason code to the database enum.
+fn core_void_code_to_db(code: VoidReasonCode) -> DbVoidReasonCode {
+    match code {
+        VoidReasonCode::DuplicateEntry => DbVoidReasonCode::DuplicateEntry,
+        VoidReasonCode::WrongAmount => DbVoidReasonCode::WrongAmount,
+        VoidReasonCode::WrongAccount => DbVoidReasonCode::WrongAccount,
+        VoidReasonCode::WrongPeriod => DbVoidReasonCode::WrongPeriod,
+        VoidReasonCode::FraudCorrection => DbVoidReasonCode::FraudCorrection,
+        VoidReasonCode::Other => DbVoidReasonCode::Other,
+    }
+}
+
+/// Converts a database void reason code to the core enum.
+pub(crate) fn db_void_code_to_core(code: &DbVoidReasonCode) -> VoidReasonCode {
+    match code {
+        DbVoidReasonCode::DuplicateEntry => VoidReasonCode::DuplicateEntry,
+        DbVoidReasonCode::WrongAmount => VoidReasonCode::WrongAmount,
+        DbVoidReasonCode::WrongAccount => VoidReasonCode::WrongAccount,
+        DbVoidReasonCode::WrongPeriod => VoidReasonCode::WrongPeriod,
+        DbVoidReasonCode::FraudCorrection => VoidReasonCode::FraudCorrection,
+        DbVoidReasonCode::Other => VoidReasonCode::Other,
     }
 }
 
@@ -723,6 +2694,11 @@ fn db_role_to_string(role: &crate::entities::sea_orm_active_enums::UserRole) ->
     }
 }
 
+/// Finds the database role named `role`.
+fn db_role_from_str(role: &str) -> Option<DbUserRole> {
+    DbUserRole::iter().find(|r| db_role_to_string(r) == role)
+}
+
 /// Converts database TransactionType to string.
 fn db_tx_type_to_string(tx_type: &TransactionType) -> String {
     match tx_type {
@@ -12,7 +12,7 @@ mod tests {
 
     use zeltra_core::workflow::{
         ApprovalEngine, ApprovalRule, OriginalEntry, ReversalInput, ReversalService,
-        TransactionStatus, WorkflowError, WorkflowService,
+        TransactionStatus, VoidReasonCode, WorkflowError, WorkflowService,
     };
 
     // ========================================================================
@@ -64,6 +64,8 @@ mod tests {
                 TransactionStatus::Pending,
                 user_id,
                 approval_notes.clone(),
+                &[],
+                1,
             );
             prop_assert!(approve_result.is_ok(), "Approve should succeed from pending");
             let action = approve_result.unwrap();
@@ -112,6 +114,8 @@ mod tests {
                 TransactionStatus::Pending,
                 user_id,
                 None,
+                &[],
+                1,
             );
             prop_assert!(approve_result.is_ok());
 
@@ -137,7 +141,7 @@ mod tests {
             let status = TransactionStatus::Posted;
 
             // Void (posted → voided)
-            let void_result = WorkflowService::void(status, user_id, void_reason.clone());
+            let void_result = WorkflowService::void(status, user_id, VoidReasonCode::Other, void_reason.clone());
             prop_assert!(void_result.is_ok(), "Void should succeed from posted");
             let action = void_result.unwrap();
             prop_assert_eq!(action.new_status(), TransactionStatus::Voided);
@@ -208,9 +212,11 @@ mod tests {
             // Create reversal input
             let input = ReversalInput {
                 original_transaction_id: transaction_id,
+                original_reference: None,
                 original_entries: entries.clone(),
                 fiscal_period_id,
                 voided_by: user_id,
+                void_reason_code: VoidReasonCode::Other,
                 void_reason,
             };
 
@@ -247,9 +253,11 @@ mod tests {
         ) {
             let input = ReversalInput {
                 original_transaction_id: Uuid::new_v4(),
+                original_reference: None,
                 original_entries: entries.clone(),
                 fiscal_period_id: Uuid::new_v4(),
                 voided_by: user_id,
+                void_reason_code: VoidReasonCode::Other,
                 void_reason,
             };
 
@@ -304,6 +312,7 @@ mod tests {
                     transaction_types: vec!["journal".to_string()],
                     required_role: "admin".to_string(),
                     priority: 10,
+                    required_approvals: 1,
                 },
                 ApprovalRule {
                     id: Uuid::new_v4(),
@@ -313,6 +322,7 @@ mod tests {
                     transaction_types: vec!["journal".to_string()],
                     required_role: "approver".to_string(),
                     priority: 1,
+                    required_approvals: 1,
                 },
                 ApprovalRule {
                     id: Uuid::new_v4(),
@@ -322,6 +332,7 @@ mod tests {
                     transaction_types: vec!["journal".to_string()],
                     required_role: "accountant".to_string(),
                     priority: 5,
+                    required_approvals: 1,
                 },
             ];
 
@@ -411,7 +422,7 @@ mod tests {
             prop_assert!(submit_result.is_err(), "Submit should fail for posted");
 
             // Approve should fail
-            let approve_result = WorkflowService::approve(status, user_id, None);
+            let approve_result = WorkflowService::approve(status, user_id, None, &[], 1);
             prop_assert!(approve_result.is_err(), "Approve should fail for posted");
 
             // Reject should fail
@@ -423,7 +434,7 @@ mod tests {
             prop_assert!(post_result.is_err(), "Post should fail for posted");
 
             // Void should succeed
-            let void_result = WorkflowService::void(status, user_id, "reason".to_string());
+            let void_result = WorkflowService::void(status, user_id, VoidReasonCode::Other, "reason".to_string());
             prop_assert!(void_result.is_ok(), "Void should succeed for posted");
         }
 
@@ -442,7 +453,7 @@ mod tests {
             let submit_result = WorkflowService::submit(status, user_id);
             prop_assert!(submit_result.is_err(), "Submit should fail for voided");
 
-            let approve_result = WorkflowService::approve(status, user_id, None);
+            let approve_result = WorkflowService::approve(status, user_id, None, &[], 1);
             prop_assert!(approve_result.is_err(), "Approve should fail for voided");
 
             let reject_result = WorkflowService::reject(status, "reason".to_string());
@@ -451,7 +462,7 @@ mod tests {
             let post_result = WorkflowService::post(status, user_id);
             prop_assert!(post_result.is_err(), "Post should fail for voided");
 
-            let void_result = WorkflowService::void(status, user_id, "reason".to_string());
+            let void_result = WorkflowService::void(status, user_id, VoidReasonCode::Other, "reason".to_string());
             prop_assert!(void_result.is_err(), "Void should fail for voided");
         }
 
@@ -469,7 +480,7 @@ mod tests {
             prop_assert!(submit_result.is_ok(), "Submit should succeed for draft");
 
             // Pending can be approved
-            let approve_result = WorkflowService::approve(TransactionStatus::Pending, user_id, None);
+            let approve_result = WorkflowService::approve(TransactionStatus::Pending, user_id, None, &[], 1);
             prop_assert!(approve_result.is_ok(), "Approve should succeed for pending");
 
             // Pending can be rejected
@@ -503,14 +514,24 @@ mod tests {
     #[test]
     fn test_empty_void_reason_fails() {
         let user_id = Uuid::new_v4();
-        let result = WorkflowService::void(TransactionStatus::Posted, user_id, String::new());
+        let result = WorkflowService::void(
+            TransactionStatus::Posted,
+            user_id,
+            VoidReasonCode::Other,
+            String::new(),
+        );
         assert!(matches!(result, Err(WorkflowError::VoidReasonRequired)));
     }
 
     #[test]
     fn test_whitespace_void_reason_fails() {
         let user_id = Uuid::new_v4();
-        let result = WorkflowService::void(TransactionStatus::Posted, user_id, "   ".to_string());
+        let result = WorkflowService::void(
+            TransactionStatus::Posted,
+            user_id,
+            VoidReasonCode::Other,
+            "   ".to_string(),
+        );
         assert!(matches!(result, Err(WorkflowError::VoidReasonRequired)));
     }
 
@@ -523,7 +544,8 @@ mod tests {
         assert_eq!(submit.new_status(), TransactionStatus::Pending);
 
         // Pending → Approved
-        let approve = WorkflowService::approve(TransactionStatus::Pending, user_id, None).unwrap();
+        let approve =
+            WorkflowService::approve(TransactionStatus::Pending, user_id, None, &[], 1).unwrap();
         assert_eq!(approve.new_status(), TransactionStatus::Approved);
 
         // Approved → Posted
@@ -531,9 +553,13 @@ mod tests {
         assert_eq!(post.new_status(), TransactionStatus::Posted);
 
         // Posted → Voided
-        let void =
-            WorkflowService::void(TransactionStatus::Posted, user_id, "Test void".to_string())
-                .unwrap();
+        let void = WorkflowService::void(
+            TransactionStatus::Posted,
+            user_id,
+            VoidReasonCode::Other,
+            "Test void".to_string(),
+        )
+        .unwrap();
         assert_eq!(void.new_status(), TransactionStatus::Voided);
     }
 
@@ -572,9 +598,11 @@ mod tests {
 
         let input = ReversalInput {
             original_transaction_id: Uuid::new_v4(),
+            original_reference: None,
             original_entries: entries,
             fiscal_period_id: Uuid::new_v4(),
             voided_by: Uuid::new_v4(),
+            void_reason_code: VoidReasonCode::Other,
             void_reason: "Test".to_string(),
         };
 
@@ -596,6 +624,7 @@ mod tests {
             transaction_types: vec!["journal".to_string()],
             required_role: "approver".to_string(),
             priority: 1,
+            required_approvals: 1,
         }];
 
         // Expense type should not match
@@ -613,6 +642,7 @@ mod tests {
             transaction_types: vec!["journal".to_string()],
             required_role: "approver".to_string(),
             priority: 1,
+            required_approvals: 1,
         }];
 
         // At max boundary should match
@@ -0,0 +1,175 @@
+//! Integration tests for account activity buckets.
+
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use sea_orm::DatabaseConnection;
+use uuid::Uuid;
+
+use zeltra_core::ledger::ActivityGranularity;
+use zeltra_db::entities::sea_orm_active_enums::{AccountType, TransactionType};
+use zeltra_db::repositories::account::{AccountError, AccountRepository};
+use zeltra_db::repositories::transaction::{
+    CreateLedgerEntryInput, CreateTransactionInput, TransactionRepository,
+};
+use zeltra_shared::types::TransactionId;
+use zeltra_test_support::{Line, Org, OrgFixture, TestDb, post_transaction};
+
+const ACCOUNTS: &[(&str, AccountType)] =
+    &[("1000", AccountType::Asset), ("4000", AccountType::Revenue)];
+
+fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(y, m, d).unwrap()
+}
+
+async fn create_sale(
+    db: &DatabaseConnection,
+    org: &Org,
+    transaction_date: NaiveDate,
+    amount: Decimal,
+) -> TransactionId {
+    let entry = |account: Uuid, debit: Decimal, credit: Decimal| CreateLedgerEntryInput {
+        account_id: account,
+        source_currency: "USD".to_string(),
+        source_amount: debit + credit,
+        exchange_rate: Decimal::ONE,
+        functional_currency: "USD".to_string(),
+        functional_amount: debit + credit,
+        debit,
+        credit,
+        memo: None,
+        dimensions: vec![],
+        rate_override: None,
+        event_at: None,
+    };
+    let created = TransactionRepository::new(db.clone())
+        .create_transaction(CreateTransactionInput {
+            organization_id: org.id.into_inner(),
+            transaction_type: TransactionType::Invoice,
+            transaction_date,
+            description: format!("Sale on {transaction_date}"),
+            reference_number: None,
+            memo: None,
+            entries: vec![
+                entry(org.account("1000").into_inner(), amount, Decimal::ZERO),
+                entry(org.account("4000").into_inner(), Decimal::ZERO, amount),
+            ],
+            created_by: org.owner.user_id.into_inner(),
+        })
+        .await
+        .expect("Failed to create transaction");
+    TransactionId::from(created.transaction.id)
+}
+
+async fn post_sale(
+    db: &DatabaseConnection,
+    org: &Org,
+    transaction_date: NaiveDate,
+    amount: Decimal,
+) {
+    post_transaction(
+        db,
+        org,
+        transaction_date,
+        vec![Line::debit("1000", amount), Line::credit("4000", amount)],
+    )
+    .await;
+}
+
+async fn org_with_accounts(db: &DatabaseConnection) -> Org {
+    OrgFixture::new()
+        .with_fiscal_year_2025()
+        .with_accounts(ACCOUNTS)
+        .create(db)
+        .await
+}
+
+#[tokio::test]
+async fn test_monthly_buckets_straddle_month_boundary() {
+    let test_db = TestDb::new().await;
+    let db = test_db.conn();
+    let org = org_with_accounts(db).await;
+
+    post_sale(db, &org, date(2025, 2, 10), Decimal::new(40, 0)).await;
+    post_sale(db, &org, date(2025, 3, 31), Decimal::new(100, 0)).await;
+    post_sale(db, &org, date(2025, 4, 1), Decimal::new(25, 0)).await;
+    // Drafts never count towards activity
+    create_sale(db, &org, date(2025, 3, 15), Decimal::new(999, 0)).await;
+
+    let activity = AccountRepository::new(db.clone())
+        .get_activity(
+            org.id,
+            org.account("1000"),
+            date(2025, 3, 1),
+            date(2025, 5, 31),
+            ActivityGranularity::Month,
+        )
+        .await
+        .expect("Failed to get activity");
+
+    assert_eq!(activity.opening_balance, Decimal::new(40, 0));
+    assert_eq!(activity.buckets.len(), 3);
+
+    let march = &activity.buckets[0];
+    assert_eq!(
+        (march.start, march.end),
+        (date(2025, 3, 1), date(2025, 3, 31))
+    );
+    assert_eq!(march.debit, Decimal::new(100, 0));
+    assert_eq!(march.ending_balance, Decimal::new(140, 0));
+
+    let april = &activity.buckets[1];
+    assert_eq!(april.start, date(2025, 4, 1));
+    assert_eq!(april.net_movement, Decimal::new(25, 0));
+    assert_eq!(april.ending_balance, Decimal::new(165, 0));
+
+    let may = &activity.buckets[2];
+    assert_eq!(may.net_movement, Decimal::ZERO);
+    assert_eq!(may.ending_balance, Decimal::new(165, 0));
+}
+
+#[tokio::test]
+async fn test_credit_normal_account_weekly_buckets() {
+    let test_db = TestDb::new().await;
+    let db = test_db.conn();
+    let org = org_with_accounts(db).await;
+
+    // 2025-03-09 is a Sunday, 2025-03-10 the following Monday
+    post_sale(db, &org, date(2025, 3, 9), Decimal::new(60, 0)).await;
+    post_sale(db, &org, date(2025, 3, 10), Decimal::new(15, 0)).await;
+
+    let activity = AccountRepository::new(db.clone())
+        .get_activity(
+            org.id,
+            org.account("4000"),
+            date(2025, 3, 3),
+            date(2025, 3, 16),
+            ActivityGranularity::Week,
+        )
+        .await
+        .expect("Failed to get activity");
+
+    assert_eq!(activity.opening_balance, Decimal::ZERO);
+    let movements: Vec<_> = activity.buckets.iter().map(|b| b.net_movement).collect();
+    assert_eq!(movements, vec![Decimal::new(60, 0), Decimal::new(15, 0)]);
+    assert_eq!(activity.buckets[1].ending_balance, Decimal::new(75, 0));
+}
+
+#[tokio::test]
+async fn test_account_from_other_org_is_not_found() {
+    let test_db = TestDb::new().await;
+    let db = test_db.conn();
+    let org = org_with_accounts(db).await;
+    let other = org_with_accounts(db).await;
+
+    let result = AccountRepository::new(db.clone())
+        .get_activity(
+            other.id,
+            org.account("1000"),
+            date(2025, 1, 1),
+            date(2025, 12, 31),
+            ActivityGranularity::Month,
+        )
+        .await;
+
+    assert!(matches!(result, Err(AccountError::AccountNotFound(_))));
+}
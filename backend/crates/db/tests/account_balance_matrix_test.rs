@@ -0,0 +1,172 @@
+//! Integration tests for account balances at several dates.
+
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use sea_orm::DatabaseConnection;
+
+use zeltra_db::entities::sea_orm_active_enums::{AccountType, TransactionType};
+use zeltra_db::repositories::account::{AccountError, AccountRepository, BalanceMatrixFilter};
+use zeltra_db::repositories::transaction::{
+    CreateLedgerEntryInput, CreateTransactionInput, TransactionRepository,
+};
+use zeltra_db::repositories::workflow::WorkflowRepository;
+use zeltra_shared::types::TransactionId;
+use zeltra_test_support::{Org, OrgFixture, TestDb};
+
+const ACCOUNTS: &[(&str, AccountType)] = &[
+    ("1000", AccountType::Asset),
+    ("4000", AccountType::Revenue),
+    ("6100", AccountType::Expense),
+];
+
+fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(y, m, d).unwrap()
+}
+
+/// Creates a travel expense paid from cash, posting it when `post` is set.
+async fn travel(
+    db: &DatabaseConnection,
+    org: &Org,
+    transaction_date: NaiveDate,
+    amount: i64,
+    post: bool,
+) {
+    let amount = Decimal::new(amount, 0);
+    let entry = |code: &str, debit: Decimal, credit: Decimal| CreateLedgerEntryInput {
+        account_id: org.account(code).into_inner(),
+        source_currency: "USD".to_string(),
+        source_amount: amount,
+        exchange_rate: Decimal::ONE,
+        functional_currency: "USD".to_string(),
+        functional_amount: amount,
+        debit,
+        credit,
+        memo: None,
+        dimensions: vec![],
+        rate_override: None,
+        event_at: None,
+    };
+    let user_id = org.owner.user_id.into_inner();
+    let created = TransactionRepository::new(db.clone())
+        .create_transaction(CreateTransactionInput {
+            organization_id: org.id.into_inner(),
+            transaction_type: TransactionType::Expense,
+            transaction_date,
+            description: "Travel".to_string(),
+            reference_number: None,
+            memo: None,
+            entries: vec![
+                entry("6100", amount, Decimal::ZERO),
+                entry("1000", Decimal::ZERO, amount),
+            ],
+            created_by: user_id,
+        })
+        .await
+        .expect("Failed to create transaction");
+    if !post {
+        return;
+    }
+    let id = TransactionId::from(created.transaction.id);
+    let workflow = WorkflowRepository::new(db.clone());
+    workflow
+        .submit_transaction(org.id, id, user_id)
+        .await
+        .expect("Failed to submit transaction");
+    workflow
+        .approve_transaction(org.id, id, user_id, None)
+        .await
+        .expect("Failed to approve transaction");
+    workflow
+        .post_transaction(org.id, id, user_id)
+        .await
+        .expect("Failed to post transaction");
+}
+
+#[tokio::test]
+async fn test_balance_matrix_counts_posted_entries_at_each_date() {
+    let test_db = TestDb::new().await;
+    let db = test_db.conn();
+    let org = OrgFixture::new()
+        .with_fiscal_year_2025()
+        .with_accounts(ACCOUNTS)
+        .create(db)
+        .await;
+    let org_id = org.id.into_inner();
+
+    travel(db, &org, date(2025, 1, 10), 1000, true).await;
+    travel(db, &org, date(2025, 2, 12), 500, true).await;
+    // A draft does not count
+    travel(db, &org, date(2025, 2, 15), 300, false).await;
+
+    let repo = AccountRepository::new(db.clone());
+    let dates = [date(2025, 2, 28), date(2025, 1, 31), date(2025, 1, 5)];
+    let matrix = repo
+        .get_balance_matrix(org_id, &dates, &BalanceMatrixFilter::default())
+        .await
+        .expect("Failed to build matrix");
+    let rows: Vec<_> = matrix
+        .iter()
+        .map(|r| (r.account.code.as_str(), r.balances.clone()))
+        .collect();
+    assert_eq!(
+        rows,
+        vec![
+            (
+                "1000",
+                vec![
+                    Decimal::new(-1500, 0),
+                    Decimal::new(-1000, 0),
+                    Decimal::ZERO
+                ]
+            ),
+            ("4000", vec![Decimal::ZERO; 3]),
+            (
+                "6100",
+                vec![Decimal::new(1500, 0), Decimal::new(1000, 0), Decimal::ZERO]
+            ),
+        ]
+    );
+
+    // Type and account filters
+    let expenses = repo
+        .get_balance_matrix(
+            org_id,
+            &dates[..1],
+            &BalanceMatrixFilter {
+                account_types: vec![AccountType::Expense, AccountType::Revenue],
+                ..BalanceMatrixFilter::default()
+            },
+        )
+        .await
+        .unwrap();
+    let codes: Vec<_> = expenses.iter().map(|r| r.account.code.as_str()).collect();
+    assert_eq!(codes, vec!["4000", "6100"]);
+    let cash = repo
+        .get_balance_matrix(
+            org_id,
+            &dates[..1],
+            &BalanceMatrixFilter {
+                account_ids: vec![org.account("1000").into_inner()],
+                ..BalanceMatrixFilter::default()
+            },
+        )
+        .await
+        .unwrap();
+    assert_eq!(cash.len(), 1);
+    assert_eq!(cash[0].balances, vec![Decimal::new(-1500, 0)]);
+
+    // Dates outside the 2025 fiscal year are listed back
+    let err = repo
+        .get_balance_matrix(
+            org_id,
+            &[date(2024, 12, 31), date(2025, 6, 30), date(2026, 1, 1)],
+            &BalanceMatrixFilter::default(),
+        )
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        err,
+        AccountError::DatesOutsideFiscalYears(ref dates)
+            if *dates == vec![date(2024, 12, 31), date(2026, 1, 1)]
+    ));
+}
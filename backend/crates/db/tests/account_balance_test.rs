@@ -0,0 +1,161 @@
+//! Integration tests for account balances counting posted transactions only.
+
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use sea_orm::DatabaseConnection;
+
+use zeltra_db::entities::sea_orm_active_enums::{AccountType, TransactionType};
+use zeltra_db::repositories::account::{AccountFilter, AccountRepository, BalanceStatusBreakdown};
+use zeltra_db::repositories::transaction::{
+    CreateLedgerEntryInput, CreateTransactionInput, TransactionRepository,
+};
+use zeltra_db::repositories::workflow::WorkflowRepository;
+use zeltra_shared::types::TransactionId;
+use zeltra_test_support::{Org, OrgFixture, TestDb};
+
+const ACCOUNTS: &[(&str, AccountType)] =
+    &[("1000", AccountType::Asset), ("5000", AccountType::Expense)];
+
+fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(y, m, d).unwrap()
+}
+
+/// Creates a draft that debits cash and credits expenses.
+async fn create_cash_receipt(
+    db: &DatabaseConnection,
+    org: &Org,
+    transaction_date: NaiveDate,
+    amount: Decimal,
+) -> TransactionId {
+    let entry = |code: &str, debit: Decimal, credit: Decimal| CreateLedgerEntryInput {
+        account_id: org.account(code).into_inner(),
+        source_currency: "USD".to_string(),
+        source_amount: amount,
+        exchange_rate: Decimal::ONE,
+        functional_currency: "USD".to_string(),
+        functional_amount: amount,
+        debit,
+        credit,
+        memo: None,
+        dimensions: vec![],
+        rate_override: None,
+        event_at: None,
+    };
+    let created = TransactionRepository::new(db.clone())
+        .create_transaction(CreateTransactionInput {
+            organization_id: org.id.into_inner(),
+            transaction_type: TransactionType::Journal,
+            transaction_date,
+            description: "Refund received".to_string(),
+            reference_number: None,
+            memo: None,
+            entries: vec![
+                entry("1000", amount, Decimal::ZERO),
+                entry("5000", Decimal::ZERO, amount),
+            ],
+            created_by: org.owner.user_id.into_inner(),
+        })
+        .await
+        .expect("Failed to create transaction");
+    TransactionId::from(created.transaction.id)
+}
+
+async fn listed_cash_balance(repo: &AccountRepository, org: &Org) -> Decimal {
+    repo.list_accounts(org.id, AccountFilter::default())
+        .await
+        .expect("Failed to list accounts")
+        .into_iter()
+        .find(|a| a.account.code == "1000")
+        .expect("Cash account is listed")
+        .balance
+}
+
+#[tokio::test]
+async fn test_draft_and_pending_entries_do_not_change_balance() {
+    let test_db = TestDb::new().await;
+    let db = test_db.conn();
+    let org = OrgFixture::new()
+        .with_fiscal_year_2025()
+        .with_accounts(ACCOUNTS)
+        .create(db)
+        .await;
+    let user_id = org.owner.user_id.into_inner();
+    let repo = AccountRepository::new(db.clone());
+    let workflow = WorkflowRepository::new(db.clone());
+
+    let posted = create_cash_receipt(db, &org, date(2025, 3, 1), Decimal::new(100, 0)).await;
+    workflow
+        .submit_transaction(org.id, posted, user_id)
+        .await
+        .expect("Failed to submit transaction");
+    workflow
+        .approve_transaction(org.id, posted, user_id, None)
+        .await
+        .expect("Failed to approve transaction");
+    workflow
+        .post_transaction(org.id, posted, user_id)
+        .await
+        .expect("Failed to post transaction");
+    assert_eq!(listed_cash_balance(&repo, &org).await, Decimal::new(100, 0));
+
+    // A large draft and a pending transaction are written to the ledger but
+    // stay out of the balance until posted
+    let draft = create_cash_receipt(db, &org, date(2025, 3, 2), Decimal::new(50_000, 0)).await;
+    let pending = create_cash_receipt(db, &org, date(2025, 3, 3), Decimal::new(30, 0)).await;
+    workflow
+        .submit_transaction(org.id, pending, user_id)
+        .await
+        .expect("Failed to submit transaction");
+
+    assert_eq!(listed_cash_balance(&repo, &org).await, Decimal::new(100, 0));
+    let cash = repo
+        .find_account_by_id(org.account("1000"))
+        .await
+        .expect("Failed to find account")
+        .expect("Account exists");
+    assert_eq!(cash.balance, Decimal::new(100, 0));
+    let balance = repo
+        .get_balance_at_date(org.account("1000"), date(2025, 12, 31))
+        .await
+        .expect("Failed to get balance");
+    assert_eq!(balance, Decimal::new(100, 0));
+
+    let breakdown = repo
+        .get_status_breakdown(&cash.account)
+        .await
+        .expect("Failed to get breakdown");
+    assert_eq!(
+        breakdown,
+        BalanceStatusBreakdown {
+            posted: Decimal::new(100, 0),
+            approved: Decimal::ZERO,
+            pending: Decimal::new(30, 0),
+            draft: Decimal::new(50_000, 0),
+        }
+    );
+
+    // Posting the draft moves it into the balance
+    workflow
+        .submit_transaction(org.id, draft, user_id)
+        .await
+        .expect("Failed to submit transaction");
+    workflow
+        .approve_transaction(org.id, draft, user_id, None)
+        .await
+        .expect("Failed to approve transaction");
+    workflow
+        .post_transaction(org.id, draft, user_id)
+        .await
+        .expect("Failed to post transaction");
+    assert_eq!(
+        listed_cash_balance(&repo, &org).await,
+        Decimal::new(50_100, 0)
+    );
+    // Expenses were credited, so their balance is negative
+    let expenses = repo
+        .find_account_by_id(org.account("5000"))
+        .await
+        .expect("Failed to find account")
+        .expect("Account exists");
+    assert_eq!(expenses.balance, Decimal::new(-50_100, 0));
+}
@@ -0,0 +1,229 @@
+//! Integration tests for default dimension values per account.
+
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use uuid::Uuid;
+
+use zeltra_db::entities::sea_orm_active_enums::{AccountType, TransactionType};
+use zeltra_db::repositories::dimension::{
+    CreateDimensionTypeInput, CreateDimensionValueInput, DimensionError, DimensionRepository,
+};
+use zeltra_db::repositories::transaction::{
+    CreateLedgerEntryInput, CreateTransactionInput, TransactionRepository,
+};
+use zeltra_test_support::{Org, OrgFixture, TestDb};
+
+async fn create_type(repo: &DimensionRepository, org_id: Uuid, code: &str) -> Uuid {
+    repo.create_dimension_type(CreateDimensionTypeInput {
+        organization_id: org_id,
+        code: code.to_string(),
+        name: code.to_string(),
+        description: None,
+        is_required: false,
+        is_active: true,
+        sort_order: 0,
+    })
+    .await
+    .expect("Failed to create dimension type")
+    .id
+}
+
+async fn create_value(
+    repo: &DimensionRepository,
+    org_id: Uuid,
+    type_id: Uuid,
+    code: &str,
+    is_active: bool,
+) -> Uuid {
+    repo.create_dimension_value(CreateDimensionValueInput {
+        organization_id: org_id,
+        dimension_type_id: type_id,
+        code: code.to_string(),
+        name: code.to_string(),
+        description: None,
+        parent_id: None,
+        is_active,
+        effective_from: None,
+        effective_to: None,
+    })
+    .await
+    .expect("Failed to create dimension value")
+    .id
+}
+
+fn entry(
+    account_id: Uuid,
+    debit: i64,
+    credit: i64,
+    dimensions: Vec<Uuid>,
+) -> CreateLedgerEntryInput {
+    let amount = Decimal::new(debit.max(credit), 0);
+    CreateLedgerEntryInput {
+        account_id,
+        source_currency: "USD".to_string(),
+        source_amount: amount,
+        exchange_rate: Decimal::ONE,
+        functional_currency: "USD".to_string(),
+        functional_amount: amount,
+        debit: Decimal::new(debit, 0),
+        credit: Decimal::new(credit, 0),
+        memo: None,
+        dimensions,
+        rate_override: None,
+        event_at: None,
+    }
+}
+
+fn transaction(org: &Org, entries: Vec<CreateLedgerEntryInput>) -> CreateTransactionInput {
+    CreateTransactionInput {
+        organization_id: org.id.into_inner(),
+        transaction_type: TransactionType::Expense,
+        transaction_date: NaiveDate::from_ymd_opt(2025, 4, 10).unwrap(),
+        description: "Cloud hosting".to_string(),
+        reference_number: None,
+        memo: None,
+        created_by: org.owner.user_id.into_inner(),
+        entries,
+    }
+}
+
+#[tokio::test]
+async fn test_defaults_fill_missing_types_and_explicit_values_win() {
+    let test_db = TestDb::new().await;
+    let db = test_db.conn();
+    let org = OrgFixture::new()
+        .with_fiscal_year_2025()
+        .with_accounts(&[
+            ("1000", AccountType::Asset),
+            ("6100", AccountType::Expense),
+            ("6200", AccountType::Expense),
+        ])
+        .create(db)
+        .await;
+    let org_id = org.id.into_inner();
+    let cash = org.account("1000").into_inner();
+    let engineering_costs = org.account("6100").into_inner();
+    let other_costs = org.account("6200").into_inner();
+
+    let dimensions = DimensionRepository::new(db.clone());
+    let department = create_type(&dimensions, org_id, "DEPT").await;
+    let project = create_type(&dimensions, org_id, "PROJECT").await;
+    let dept_eng = create_value(&dimensions, org_id, department, "DEPT-ENG", true).await;
+    let dept_ops = create_value(&dimensions, org_id, department, "DEPT-OPS", true).await;
+    let project_apollo = create_value(&dimensions, org_id, project, "APOLLO", true).await;
+
+    dimensions
+        .add_account_default(org_id, engineering_costs, dept_eng)
+        .await
+        .expect("Failed to add default");
+
+    let created = TransactionRepository::new(db.clone())
+        .create_transaction(transaction(
+            &org,
+            vec![
+                // No department: defaulted
+                entry(engineering_costs, 60, 0, vec![project_apollo]),
+                // Explicit department wins over the default
+                entry(engineering_costs, 30, 0, vec![dept_ops]),
+                // No defaults on this account
+                entry(other_costs, 10, 0, vec![]),
+                entry(cash, 0, 100, vec![]),
+            ],
+        ))
+        .await
+        .expect("Failed to create transaction");
+
+    let entries = &created.entries;
+    assert_eq!(entries[0].dimensions, [project_apollo, dept_eng]);
+    assert_eq!(entries[0].defaulted_dimensions, [dept_eng]);
+    assert_eq!(entries[1].dimensions, [dept_ops]);
+    assert!(entries[1].defaulted_dimensions.is_empty());
+    assert!(entries[2].dimensions.is_empty());
+    assert!(entries[3].defaulted_dimensions.is_empty());
+
+    // The defaulted value is stored like any other
+    let stored = TransactionRepository::new(db.clone())
+        .get_transaction(org.id, created.transaction.id.into())
+        .await
+        .expect("Failed to load transaction");
+    let first = stored
+        .entries
+        .iter()
+        .find(|e| e.entry.id == entries[0].entry.id)
+        .unwrap();
+    assert_eq!(first.dimensions.len(), 2);
+    assert!(first.dimensions.contains(&dept_eng));
+}
+
+#[tokio::test]
+async fn test_add_default_validation() {
+    let test_db = TestDb::new().await;
+    let db = test_db.conn();
+    let org = OrgFixture::new()
+        .with_accounts(&[("6100", AccountType::Expense)])
+        .create(db)
+        .await;
+    let other = OrgFixture::new().create(db).await;
+    let org_id = org.id.into_inner();
+    let account = org.account("6100").into_inner();
+
+    let dimensions = DimensionRepository::new(db.clone());
+    let department = create_type(&dimensions, org_id, "DEPT").await;
+    let dept_eng = create_value(&dimensions, org_id, department, "DEPT-ENG", true).await;
+    let dept_ops = create_value(&dimensions, org_id, department, "DEPT-OPS", true).await;
+    let retired = create_value(&dimensions, org_id, department, "DEPT-OLD", false).await;
+    let other_type = create_type(&dimensions, other.id.into_inner(), "DEPT").await;
+    let foreign = create_value(
+        &dimensions,
+        other.id.into_inner(),
+        other_type,
+        "DEPT-ENG",
+        true,
+    )
+    .await;
+
+    let err = dimensions
+        .add_account_default(org_id, account, foreign)
+        .await
+        .unwrap_err();
+    assert!(matches!(err, DimensionError::ValueNotFound(id) if id == foreign));
+
+    let err = dimensions
+        .add_account_default(org_id, account, retired)
+        .await
+        .unwrap_err();
+    assert!(matches!(err, DimensionError::ValueInactive(id) if id == retired));
+
+    let err = dimensions
+        .add_account_default(other.id.into_inner(), account, foreign)
+        .await
+        .unwrap_err();
+    assert!(matches!(err, DimensionError::AccountNotFound(id) if id == account));
+
+    dimensions
+        .add_account_default(org_id, account, dept_eng)
+        .await
+        .expect("Failed to add default");
+    let err = dimensions
+        .add_account_default(org_id, account, dept_ops)
+        .await
+        .unwrap_err();
+    assert!(matches!(err, DimensionError::DefaultAlreadySet(ref code) if code == "DEPT"));
+
+    let defaults = dimensions
+        .list_account_defaults(org_id, account)
+        .await
+        .expect("Failed to list defaults");
+    assert_eq!(defaults.len(), 1);
+    assert_eq!(defaults[0].value.id, dept_eng);
+
+    dimensions
+        .remove_account_default(org_id, account, dept_eng)
+        .await
+        .expect("Failed to remove default");
+    let err = dimensions
+        .remove_account_default(org_id, account, dept_eng)
+        .await
+        .unwrap_err();
+    assert!(matches!(err, DimensionError::DefaultNotFound(_)));
+}
@@ -0,0 +1,117 @@
+//! Integration tests for importing a chart of accounts in one batch.
+
+use std::collections::HashMap;
+
+use uuid::Uuid;
+
+use zeltra_core::account_import::ImportIssue;
+use zeltra_db::entities::sea_orm_active_enums::AccountType;
+use zeltra_db::repositories::account::{
+    AccountError, AccountFilter, AccountRepository, CreateAccountInput, ImportAccountInput,
+};
+use zeltra_test_support::{OrgFixture, TestDb};
+
+fn input(
+    org_id: Uuid,
+    row: usize,
+    code: &str,
+    parent_code: Option<&str>,
+    currency: &str,
+) -> ImportAccountInput {
+    ImportAccountInput {
+        row,
+        account: CreateAccountInput {
+            organization_id: org_id,
+            code: code.to_string(),
+            name: format!("Account {code}"),
+            description: None,
+            account_type: AccountType::Asset,
+            account_subtype: None,
+            parent_id: None,
+            currency: currency.to_string(),
+            is_active: true,
+            allow_direct_posting: true,
+            is_bank_account: false,
+            bank_account_number: None,
+        },
+        parent_code: parent_code.map(ToString::to_string),
+    }
+}
+
+#[tokio::test]
+async fn test_import_resolves_parent_defined_after_child() {
+    let test_db = TestDb::new().await;
+    let db = test_db.conn();
+    let org = OrgFixture::new()
+        .with_accounts(&[("1000", AccountType::Asset)])
+        .create(db)
+        .await;
+    let org_id = org.id.into_inner();
+    let repo = AccountRepository::new(db.clone());
+
+    // 1110 comes before its parent 1100; 1100 hangs off an existing account
+    let batch = vec![
+        input(org_id, 2, "1110", Some("1100"), "USD"),
+        input(org_id, 3, "1100", Some("1000"), "USD"),
+        input(org_id, 4, "1200", None, "USD"),
+    ];
+    assert!(
+        repo.validate_accounts_batch(org_id, &batch)
+            .await
+            .unwrap()
+            .is_empty()
+    );
+
+    let created = repo
+        .create_accounts_batch(org_id, batch)
+        .await
+        .expect("Failed to import accounts");
+    assert_eq!(created.len(), 3);
+    let by_code: HashMap<&str, _> = created.iter().map(|a| (a.code.as_str(), a)).collect();
+    assert_eq!(
+        by_code["1100"].parent_id,
+        Some(org.account("1000").into_inner())
+    );
+    assert_eq!(by_code["1110"].parent_id, Some(by_code["1100"].id));
+    assert_eq!(by_code["1200"].parent_id, None);
+}
+
+#[tokio::test]
+async fn test_import_rejects_cycles_and_creates_nothing() {
+    let test_db = TestDb::new().await;
+    let db = test_db.conn();
+    let org = OrgFixture::new()
+        .with_accounts(&[("1000", AccountType::Asset)])
+        .create(db)
+        .await;
+    let org_id = org.id.into_inner();
+    let repo = AccountRepository::new(db.clone());
+
+    let batch = vec![
+        input(org_id, 2, "2000", None, "USD"),
+        input(org_id, 3, "1100", Some("1200"), "USD"),
+        input(org_id, 4, "1200", Some("1100"), "USD"),
+        input(org_id, 5, "1000", None, "ZZZ"),
+    ];
+    let result = repo.create_accounts_batch(org_id, batch).await;
+    let Err(AccountError::InvalidImport(issues)) = result else {
+        panic!("Expected the import to be rejected, got {result:?}");
+    };
+    assert_eq!(
+        issues,
+        vec![
+            ImportIssue::new(3, "parent_code", "Account '1100' is part of a parent cycle"),
+            ImportIssue::new(4, "parent_code", "Account '1200' is part of a parent cycle"),
+            ImportIssue::new(5, "code", "Account code '1000' already exists"),
+            ImportIssue::new(5, "currency", "Currency 'ZZZ' not found"),
+        ]
+    );
+
+    // The valid row was not created either
+    let accounts = repo
+        .list_accounts(org.id, AccountFilter::default())
+        .await
+        .expect("Failed to list accounts");
+    let codes: Vec<&str> = accounts.iter().map(|a| a.account.code.as_str()).collect();
+    assert_eq!(codes, vec!["1000"]);
+}
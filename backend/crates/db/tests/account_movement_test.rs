@@ -0,0 +1,207 @@
+//! Integration tests for the period-over-period account movement report.
+
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use sea_orm::DatabaseConnection;
+use uuid::Uuid;
+
+use zeltra_db::entities::sea_orm_active_enums::{AccountType, TransactionType};
+use zeltra_db::repositories::report::{AccountLookup, ReportError, ReportRepository};
+use zeltra_db::repositories::transaction::{
+    CreateLedgerEntryInput, CreateTransactionInput, TransactionRepository,
+};
+use zeltra_db::repositories::workflow::WorkflowRepository;
+use zeltra_shared::types::TransactionId;
+use zeltra_test_support::{Org, OrgFixture, TestDb};
+
+const ACCOUNTS: &[(&str, AccountType)] =
+    &[("1000", AccountType::Asset), ("6100", AccountType::Expense)];
+
+/// Posts a travel expense paid from cash.
+async fn post_travel(
+    db: &DatabaseConnection,
+    org: &Org,
+    date: NaiveDate,
+    reference: &str,
+    amount: i64,
+) -> Uuid {
+    let amount = Decimal::new(amount, 0);
+    let entry = |account: &str, debit: Decimal, credit: Decimal| CreateLedgerEntryInput {
+        account_id: org.account(account).into_inner(),
+        source_currency: "USD".to_string(),
+        source_amount: amount,
+        exchange_rate: Decimal::ONE,
+        functional_currency: "USD".to_string(),
+        functional_amount: amount,
+        debit,
+        credit,
+        memo: None,
+        dimensions: vec![],
+        rate_override: None,
+        event_at: None,
+    };
+    let user_id = org.owner.user_id.into_inner();
+    let created = TransactionRepository::new(db.clone())
+        .create_transaction(CreateTransactionInput {
+            organization_id: org.id.into_inner(),
+            transaction_type: TransactionType::Expense,
+            transaction_date: date,
+            description: format!("Travel {reference}"),
+            reference_number: Some(reference.to_string()),
+            memo: None,
+            entries: vec![
+                entry("6100", amount, Decimal::ZERO),
+                entry("1000", Decimal::ZERO, amount),
+            ],
+            created_by: user_id,
+        })
+        .await
+        .expect("Failed to create transaction");
+    let id = TransactionId::from(created.transaction.id);
+    let workflow = WorkflowRepository::new(db.clone());
+    workflow
+        .submit_transaction(org.id, id, user_id)
+        .await
+        .expect("Failed to submit transaction");
+    workflow
+        .approve_transaction(org.id, id, user_id, None)
+        .await
+        .expect("Failed to approve transaction");
+    workflow
+        .post_transaction(org.id, id, user_id)
+        .await
+        .expect("Failed to post transaction");
+    created.transaction.id
+}
+
+fn date(month: u32, day: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(2025, month, day).unwrap()
+}
+
+#[tokio::test]
+async fn test_movement_against_previous_month_lists_top_transactions() {
+    let test_db = TestDb::new().await;
+    let db = test_db.conn();
+    let org = OrgFixture::new()
+        .with_fiscal_year_2025()
+        .with_accounts(ACCOUNTS)
+        .create(db)
+        .await;
+    let periods = &org.fiscal_year.as_ref().unwrap().periods;
+    let (january, february) = (periods[0].into_inner(), periods[1].into_inner());
+
+    post_travel(db, &org, date(1, 10), "T-1", 1000).await;
+    post_travel(db, &org, date(2, 3), "T-2", 300).await;
+    let largest = post_travel(db, &org, date(2, 12), "T-3", 900).await;
+    post_travel(db, &org, date(2, 20), "T-4", 200).await;
+
+    let movement = ReportRepository::new(db.clone())
+        .query_account_movement(
+            org.id.into_inner(),
+            &AccountLookup::Code("6100".to_string()),
+            february,
+            january,
+            2,
+        )
+        .await
+        .expect("Failed to query movement");
+
+    assert_eq!(movement.account.id, org.account("6100").into_inner());
+    assert_eq!(movement.compare.movement.opening_balance, Decimal::ZERO);
+    assert_eq!(
+        movement.compare.movement.closing_balance,
+        Decimal::new(1000, 0)
+    );
+    assert_eq!(
+        movement.current.movement.opening_balance,
+        Decimal::new(1000, 0)
+    );
+    assert_eq!(movement.current.movement.total_debit, Decimal::new(1400, 0));
+    assert_eq!(movement.current.movement.total_credit, Decimal::ZERO);
+    assert_eq!(
+        movement.current.movement.closing_balance,
+        Decimal::new(2400, 0)
+    );
+    assert_eq!(movement.change.absolute, Decimal::new(400, 0));
+    assert_eq!(movement.change.percent, Some(Decimal::new(40, 0)));
+
+    let top: Vec<_> = movement
+        .top_transactions
+        .iter()
+        .map(|t| (t.reference_number.as_deref(), t.amount))
+        .collect();
+    assert_eq!(
+        top,
+        vec![
+            (Some("T-3"), Decimal::new(900, 0)),
+            (Some("T-2"), Decimal::new(300, 0))
+        ]
+    );
+    assert_eq!(movement.top_transactions[0].transaction_id, largest);
+}
+
+#[tokio::test]
+async fn test_movement_against_quiet_period_has_no_percent_change() {
+    let test_db = TestDb::new().await;
+    let db = test_db.conn();
+    let org = OrgFixture::new()
+        .with_fiscal_year_2025()
+        .with_accounts(ACCOUNTS)
+        .create(db)
+        .await;
+    let org_id = org.id.into_inner();
+    let periods = &org.fiscal_year.as_ref().unwrap().periods;
+    let (january, march) = (periods[0].into_inner(), periods[2].into_inner());
+    let travel = org.account("6100").into_inner();
+
+    post_travel(db, &org, date(1, 10), "T-1", 500).await;
+
+    // January against a quiet March, then the other way round
+    let repo = ReportRepository::new(db.clone());
+    let movement = repo
+        .query_account_movement(org_id, &AccountLookup::Id(travel), january, march, 10)
+        .await
+        .expect("Failed to query movement");
+    assert_eq!(
+        movement.compare.movement.opening_balance,
+        Decimal::new(500, 0)
+    );
+    assert_eq!(
+        movement.compare.movement.closing_balance,
+        Decimal::new(500, 0)
+    );
+    assert_eq!(movement.change.absolute, Decimal::new(500, 0));
+    assert_eq!(movement.change.percent, None);
+
+    let movement = repo
+        .query_account_movement(org_id, &AccountLookup::Id(travel), march, january, 10)
+        .await
+        .expect("Failed to query movement");
+    assert_eq!(movement.change.absolute, Decimal::new(-500, 0));
+    assert_eq!(movement.change.percent, Some(Decimal::new(-100, 0)));
+    assert!(movement.top_transactions.is_empty());
+
+    // Unknown periods and account codes are rejected
+    let err = repo
+        .query_account_movement(
+            org_id,
+            &AccountLookup::Id(travel),
+            january,
+            Uuid::new_v4(),
+            10,
+        )
+        .await
+        .unwrap_err();
+    assert!(matches!(err, ReportError::PeriodNotFound(_)));
+    let err = repo
+        .query_account_movement(
+            org_id,
+            &AccountLookup::Code("9999".to_string()),
+            january,
+            march,
+            10,
+        )
+        .await
+        .unwrap_err();
+    assert!(matches!(err, ReportError::AccountCodeNotFound(_)));
+}
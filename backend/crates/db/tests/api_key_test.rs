@@ -0,0 +1,142 @@
+//! Integration tests for API key storage and lookup.
+
+use chrono::{Duration, Utc};
+use sea_orm::{ActiveModelTrait, Set};
+
+use zeltra_core::auth::{ApiKeyError, ApiKeyRole, ApiKeyScope};
+use zeltra_db::entities::api_keys;
+use zeltra_db::repositories::api_key::{ApiKeyRepoError, ApiKeyRepository, CreateApiKeyInput};
+use zeltra_test_support::{Org, OrgFixture, TestDb};
+
+fn input(org: &Org, role: ApiKeyRole, scopes: &[ApiKeyScope]) -> CreateApiKeyInput {
+    CreateApiKeyInput {
+        name: "Warehouse sync".to_string(),
+        role,
+        scopes: scopes.to_vec(),
+        expires_at: None,
+        created_by: org.owner.user_id.into_inner(),
+    }
+}
+
+#[tokio::test]
+async fn test_key_is_stored_hashed_and_authenticates() {
+    let test_db = TestDb::new().await;
+    let db = test_db.conn();
+    let org = OrgFixture::new().create(db).await;
+    let repo = ApiKeyRepository::new(db.clone());
+
+    let created = repo
+        .create(
+            org.id.into_inner(),
+            input(
+                &org,
+                ApiKeyRole::Integration,
+                &[
+                    ApiKeyScope::WriteTransactions,
+                    ApiKeyScope::ReadTransactions,
+                ],
+            ),
+        )
+        .await
+        .expect("Failed to create API key");
+    assert!(created.key.starts_with("zk_live_"));
+    assert_ne!(created.api_key.key_hash, created.key);
+    assert!(created.key.starts_with(&created.api_key.key_prefix));
+    assert_eq!(
+        created.api_key.scopes,
+        vec!["read:transactions", "write:transactions"]
+    );
+
+    let found = repo
+        .authenticate(&created.key)
+        .await
+        .unwrap()
+        .expect("Key should authenticate");
+    assert_eq!(found.id, created.api_key.id);
+    assert!(found.last_used_at.is_some());
+
+    assert!(
+        repo.authenticate("zk_live_unknown")
+            .await
+            .unwrap()
+            .is_none()
+    );
+
+    repo.revoke(org.id.into_inner(), created.api_key.id)
+        .await
+        .expect("Failed to revoke API key");
+    assert!(repo.authenticate(&created.key).await.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn test_expired_key_is_rejected() {
+    let test_db = TestDb::new().await;
+    let db = test_db.conn();
+    let org = OrgFixture::new().create(db).await;
+    let repo = ApiKeyRepository::new(db.clone());
+
+    let created = repo
+        .create(
+            org.id.into_inner(),
+            CreateApiKeyInput {
+                expires_at: Some(Utc::now() + Duration::hours(1)),
+                ..input(&org, ApiKeyRole::Viewer, &[ApiKeyScope::ReadReports])
+            },
+        )
+        .await
+        .expect("Failed to create API key");
+    assert!(repo.authenticate(&created.key).await.unwrap().is_some());
+
+    // Move the expiry into the past
+    api_keys::ActiveModel {
+        id: Set(created.api_key.id),
+        expires_at: Set(Some((Utc::now() - Duration::minutes(1)).into())),
+        ..Default::default()
+    }
+    .update(db)
+    .await
+    .unwrap();
+    assert!(repo.authenticate(&created.key).await.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn test_invalid_key_settings_are_rejected() {
+    let test_db = TestDb::new().await;
+    let db = test_db.conn();
+    let org = OrgFixture::new().create(db).await;
+    let repo = ApiKeyRepository::new(db.clone());
+    let org_id = org.id.into_inner();
+
+    let err = repo
+        .create(
+            org_id,
+            input(&org, ApiKeyRole::Viewer, &[ApiKeyScope::WriteTransactions]),
+        )
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        err,
+        ApiKeyRepoError::Invalid(ApiKeyError::ScopeNotAllowed(ApiKeyScope::WriteTransactions))
+    ));
+
+    let err = repo
+        .create(org_id, input(&org, ApiKeyRole::Integration, &[]))
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        err,
+        ApiKeyRepoError::Invalid(ApiKeyError::NoScopes)
+    ));
+
+    let err = repo
+        .create(
+            org_id,
+            CreateApiKeyInput {
+                expires_at: Some(Utc::now() - Duration::days(1)),
+                ..input(&org, ApiKeyRole::Viewer, &[ApiKeyScope::ReadReports])
+            },
+        )
+        .await
+        .unwrap_err();
+    assert!(matches!(err, ApiKeyRepoError::ExpiryInPast));
+}
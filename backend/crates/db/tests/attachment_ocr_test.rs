@@ -0,0 +1,250 @@
+//! Integration tests for storing OCR extractions and applying them to drafts.
+
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use sea_orm::DatabaseConnection;
+use uuid::Uuid;
+
+use zeltra_core::attachment::{
+    AttachmentRepository as _, AttachmentType, CreateAttachmentInput, ExtractedDocument,
+    ExtractionRecord,
+};
+use zeltra_db::entities::sea_orm_active_enums::{AccountType, TransactionType};
+use zeltra_db::repositories::AttachmentRepository;
+use zeltra_db::repositories::transaction::{
+    CreateLedgerEntryInput, CreateTransactionInput, DraftPrefill, TransactionRepository,
+};
+use zeltra_shared::types::TransactionId;
+use zeltra_test_support::{Org, OrgFixture, TestDb};
+
+const ACCOUNTS: &[(&str, AccountType)] =
+    &[("6100", AccountType::Expense), ("1000", AccountType::Asset)];
+
+fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(y, m, d).unwrap()
+}
+
+async fn org_with_accounts(db: &DatabaseConnection) -> Org {
+    OrgFixture::new()
+        .with_fiscal_year_2025()
+        .with_accounts(ACCOUNTS)
+        .create(db)
+        .await
+}
+
+/// Draft paying the given expense lines from cash.
+async fn create_draft(db: &DatabaseConnection, org: &Org, expenses: &[i64]) -> Uuid {
+    let entry = |account: &str, debit: i64, credit: i64| CreateLedgerEntryInput {
+        account_id: org.account(account).into_inner(),
+        source_currency: "USD".to_string(),
+        source_amount: Decimal::from(debit + credit),
+        exchange_rate: Decimal::ONE,
+        functional_currency: "USD".to_string(),
+        functional_amount: Decimal::from(debit + credit),
+        debit: Decimal::from(debit),
+        credit: Decimal::from(credit),
+        memo: None,
+        dimensions: vec![],
+        rate_override: None,
+        event_at: None,
+    };
+    let mut entries: Vec<_> = expenses.iter().map(|e| entry("6100", *e, 0)).collect();
+    entries.push(entry("1000", 0, expenses.iter().sum()));
+
+    TransactionRepository::new(db.clone())
+        .create_transaction(CreateTransactionInput {
+            organization_id: org.id.into_inner(),
+            transaction_type: TransactionType::Expense,
+            transaction_date: date(2025, 6, 10),
+            description: "Receipt".to_string(),
+            reference_number: None,
+            memo: None,
+            entries,
+            created_by: org.owner.user_id.into_inner(),
+        })
+        .await
+        .expect("Failed to create transaction")
+        .transaction
+        .id
+}
+
+async fn attach(
+    repo: &AttachmentRepository,
+    org: &Org,
+    transaction_id: Uuid,
+    attachment_type: AttachmentType,
+    mime_type: &str,
+) -> Uuid {
+    let id = Uuid::new_v4();
+    repo.create(CreateAttachmentInput {
+        id,
+        organization_id: org.id.into_inner(),
+        transaction_id: Some(transaction_id),
+        ledger_entry_id: None,
+        attachment_type,
+        filename: "scan".to_string(),
+        file_size: 1024,
+        mime_type: mime_type.to_string(),
+        checksum_sha256: None,
+        storage_provider: "local".to_string(),
+        storage_bucket: "local".to_string(),
+        storage_key: format!("{}/{id}/scan", org.id),
+        storage_region: None,
+        uploaded_by: org.owner.user_id.into_inner(),
+    })
+    .await
+    .expect("Failed to create attachment");
+    id
+}
+
+fn receipt(total: i64) -> ExtractedDocument {
+    ExtractedDocument {
+        vendor: Some("Office Depot".to_string()),
+        date: Some(date(2025, 7, 2)),
+        total: Some(Decimal::from(total)),
+        currency: Some("USD".to_string()),
+        line_items: vec![],
+        confidence: 0.92,
+    }
+}
+
+#[tokio::test]
+async fn test_pending_extraction_lists_only_unprocessed_documents() {
+    let test_db = TestDb::new().await;
+    let db = test_db.conn();
+    let org = org_with_accounts(db).await;
+    let repo = AttachmentRepository::new(db.clone());
+    let tx = create_draft(db, &org, &[100]).await;
+
+    let pdf = attach(&repo, &org, tx, AttachmentType::Receipt, "application/pdf").await;
+    let image = attach(&repo, &org, tx, AttachmentType::Invoice, "image/png").await;
+    attach(&repo, &org, tx, AttachmentType::Contract, "application/pdf").await;
+    attach(&repo, &org, tx, AttachmentType::Receipt, "text/csv").await;
+
+    let pending: Vec<Uuid> = repo
+        .list_pending_extraction(10)
+        .await
+        .expect("Failed to list pending")
+        .into_iter()
+        .map(|a| a.id)
+        .collect();
+    assert_eq!(pending, vec![pdf, image]);
+
+    let record = ExtractionRecord::Completed(receipt(100));
+    repo.save_extraction(pdf, &record)
+        .await
+        .expect("Failed to save extraction");
+
+    let pending = repo
+        .list_pending_extraction(10)
+        .await
+        .expect("Failed to list pending");
+    assert_eq!(pending.len(), 1);
+    assert_eq!(pending[0].id, image);
+
+    let stored = repo
+        .find_extraction(pdf, org.id.into_inner())
+        .await
+        .expect("Failed to load extraction")
+        .expect("Attachment exists");
+    assert_eq!(stored.record, Some(record));
+    assert!(stored.processed_at.is_some());
+}
+
+#[tokio::test]
+async fn test_prefill_simple_draft_sets_description_date_and_amount() {
+    let test_db = TestDb::new().await;
+    let db = test_db.conn();
+    let org = org_with_accounts(db).await;
+    let tx = create_draft(db, &org, &[100]).await;
+    let doc = receipt(42);
+
+    let tx_repo = TransactionRepository::new(db.clone());
+    let prefilled = tx_repo
+        .prefill_draft(
+            org.id,
+            TransactionId::from(tx),
+            DraftPrefill {
+                description: doc.vendor_name().map(String::from),
+                transaction_date: doc.date,
+                amount: doc.total,
+                currency: doc.currency.clone(),
+                updated_by: org.owner.user_id.into_inner(),
+            },
+        )
+        .await
+        .expect("Failed to prefill draft");
+
+    assert!(prefilled.amount_applied);
+    assert_eq!(prefilled.transaction.description, "Office Depot");
+    assert_eq!(prefilled.transaction.transaction_date, date(2025, 7, 2));
+
+    let loaded = tx_repo
+        .get_transaction(org.id, TransactionId::from(tx))
+        .await
+        .expect("Failed to load transaction");
+    let total_debit: Decimal = loaded.entries.iter().map(|e| e.entry.debit).sum();
+    let total_credit: Decimal = loaded.entries.iter().map(|e| e.entry.credit).sum();
+    assert_eq!(total_debit, Decimal::from(42));
+    assert_eq!(total_credit, Decimal::from(42));
+}
+
+#[tokio::test]
+async fn test_prefill_split_draft_keeps_amounts() {
+    let test_db = TestDb::new().await;
+    let db = test_db.conn();
+    let org = org_with_accounts(db).await;
+    let tx = create_draft(db, &org, &[40, 60]).await;
+
+    let tx_repo = TransactionRepository::new(db.clone());
+    let prefilled = tx_repo
+        .prefill_draft(
+            org.id,
+            TransactionId::from(tx),
+            DraftPrefill {
+                description: Some("Office Depot".to_string()),
+                transaction_date: None,
+                amount: Some(Decimal::from(42)),
+                currency: Some("USD".to_string()),
+                updated_by: org.owner.user_id.into_inner(),
+            },
+        )
+        .await
+        .expect("Failed to prefill draft");
+
+    assert!(!prefilled.amount_applied);
+    assert_eq!(prefilled.transaction.description, "Office Depot");
+
+    let loaded = tx_repo
+        .get_transaction(org.id, TransactionId::from(tx))
+        .await
+        .expect("Failed to load transaction");
+    let total_debit: Decimal = loaded.entries.iter().map(|e| e.entry.debit).sum();
+    assert_eq!(total_debit, Decimal::from(100));
+}
+
+#[tokio::test]
+async fn test_prefill_in_other_currency_keeps_amounts() {
+    let test_db = TestDb::new().await;
+    let db = test_db.conn();
+    let org = org_with_accounts(db).await;
+    let tx = create_draft(db, &org, &[100]).await;
+
+    let prefilled = TransactionRepository::new(db.clone())
+        .prefill_draft(
+            org.id,
+            TransactionId::from(tx),
+            DraftPrefill {
+                description: None,
+                transaction_date: None,
+                amount: Some(Decimal::from(42)),
+                currency: Some("EUR".to_string()),
+                updated_by: org.owner.user_id.into_inner(),
+            },
+        )
+        .await
+        .expect("Failed to prefill draft");
+
+    assert!(!prefilled.amount_applied);
+    assert_eq!(prefilled.transaction.description, "Receipt");
+}
@@ -0,0 +1,471 @@
+//! Integration tests for line-level attachments.
+
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use sea_orm::{ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, sea_query::Expr};
+use uuid::Uuid;
+
+use zeltra_core::attachment::{
+    AttachmentError, AttachmentRepository as _, AttachmentType, CreateAttachmentInput,
+};
+use zeltra_core::workflow::VoidReasonCode;
+use zeltra_db::entities::sea_orm_active_enums::{
+    AccountType, AttachmentType as DbAttachmentType, TransactionType,
+};
+use zeltra_db::entities::{attachments, ledger_entries, users};
+use zeltra_db::repositories::transaction::{
+    CreateLedgerEntryInput, CreateTransactionInput, TransactionRepository, TransactionWithEntries,
+};
+use zeltra_db::repositories::workflow::WorkflowRepository;
+use zeltra_db::repositories::{AttachmentListFilter, AttachmentRepository};
+use zeltra_shared::types::PageRequest;
+use zeltra_test_support::{Org, OrgFixture, TestDb};
+
+const ACCOUNTS: &[(&str, AccountType)] =
+    &[("6100", AccountType::Expense), ("1000", AccountType::Asset)];
+
+async fn org_with_accounts(db: &DatabaseConnection) -> Org {
+    OrgFixture::new()
+        .with_fiscal_year_2025()
+        .with_accounts(ACCOUNTS)
+        .create(db)
+        .await
+}
+
+/// Draft expense report with two expense lines paid from cash.
+async fn create_expense_report(db: &DatabaseConnection, org: &Org) -> TransactionWithEntries {
+    let entry = |account: &str, debit: i64, credit: i64| CreateLedgerEntryInput {
+        account_id: org.account(account).into_inner(),
+        source_currency: "USD".to_string(),
+        source_amount: Decimal::from(debit + credit),
+        exchange_rate: Decimal::ONE,
+        functional_currency: "USD".to_string(),
+        functional_amount: Decimal::from(debit + credit),
+        debit: Decimal::from(debit),
+        credit: Decimal::from(credit),
+        memo: None,
+        dimensions: vec![],
+        rate_override: None,
+        event_at: None,
+    };
+    TransactionRepository::new(db.clone())
+        .create_transaction(CreateTransactionInput {
+            organization_id: org.id.into_inner(),
+            transaction_type: TransactionType::Expense,
+            transaction_date: NaiveDate::from_ymd_opt(2025, 6, 10).unwrap(),
+            description: "Expense report".to_string(),
+            reference_number: None,
+            memo: None,
+            entries: vec![
+                entry("6100", 40, 0),
+                entry("6100", 60, 0),
+                entry("1000", 0, 100),
+            ],
+            created_by: org.owner.user_id.into_inner(),
+        })
+        .await
+        .expect("Failed to create transaction")
+}
+
+async fn attach(
+    repo: &AttachmentRepository,
+    org: &Org,
+    transaction_id: Uuid,
+    ledger_entry_id: Option<Uuid>,
+) -> Uuid {
+    let id = Uuid::new_v4();
+    repo.create(CreateAttachmentInput {
+        id,
+        organization_id: org.id.into_inner(),
+        transaction_id: Some(transaction_id),
+        ledger_entry_id,
+        attachment_type: AttachmentType::Receipt,
+        filename: "receipt.pdf".to_string(),
+        file_size: 1024,
+        mime_type: "application/pdf".to_string(),
+        checksum_sha256: None,
+        storage_provider: "local".to_string(),
+        storage_bucket: "local".to_string(),
+        storage_key: format!("{}/{id}/receipt.pdf", org.id),
+        storage_region: None,
+        uploaded_by: org.owner.user_id.into_inner(),
+    })
+    .await
+    .expect("Failed to create attachment");
+    id
+}
+
+#[tokio::test]
+async fn test_entry_must_belong_to_transaction_and_org() {
+    let test_db = TestDb::new().await;
+    let db = test_db.conn();
+    let org = org_with_accounts(db).await;
+    let other = org_with_accounts(db).await;
+    let report = create_expense_report(db, &org).await;
+    let other_report = create_expense_report(db, &other).await;
+    let repo = AttachmentRepository::new(db.clone());
+    let (org_id, tx_id) = (org.id.into_inner(), report.transaction.id);
+    let entry_id = report.entries[0].entry.id;
+
+    assert!(repo.entry_exists(entry_id, tx_id, org_id).await.unwrap());
+    assert!(
+        !repo
+            .entry_exists(other_report.entries[0].entry.id, tx_id, org_id)
+            .await
+            .unwrap()
+    );
+    assert!(
+        !repo
+            .entry_exists(entry_id, tx_id, other.id.into_inner())
+            .await
+            .unwrap()
+    );
+}
+
+#[tokio::test]
+async fn test_list_filters_by_entry_and_counts_per_entry() {
+    let test_db = TestDb::new().await;
+    let db = test_db.conn();
+    let org = org_with_accounts(db).await;
+    let report = create_expense_report(db, &org).await;
+    let repo = AttachmentRepository::new(db.clone());
+    let tx_id = report.transaction.id;
+    let (first, second) = (report.entries[0].entry.id, report.entries[1].entry.id);
+
+    attach(&repo, &org, tx_id, None).await;
+    attach(&repo, &org, tx_id, Some(first)).await;
+    attach(&repo, &org, tx_id, Some(first)).await;
+    attach(&repo, &org, tx_id, Some(second)).await;
+
+    let org_id = org.id.into_inner();
+    let all = repo.list_by_transaction(tx_id, None, org_id).await.unwrap();
+    let on_first = repo
+        .list_by_transaction(tx_id, Some(first), org_id)
+        .await
+        .unwrap();
+    assert_eq!(all.len(), 4);
+    assert_eq!(on_first.len(), 2);
+    assert!(on_first.iter().all(|a| a.ledger_entry_id == Some(first)));
+
+    let fetched = TransactionRepository::new(db.clone())
+        .get_transaction(org.id, tx_id.into())
+        .await
+        .unwrap();
+    let count = |entry_id: Uuid| {
+        fetched
+            .entries
+            .iter()
+            .find(|e| e.entry.id == entry_id)
+            .map(|e| e.attachment_count)
+    };
+    assert_eq!(count(first), Some(2));
+    assert_eq!(count(second), Some(1));
+    assert_eq!(count(report.entries[2].entry.id), Some(0));
+}
+
+#[tokio::test]
+async fn test_deleting_entry_deletes_its_attachments() {
+    let test_db = TestDb::new().await;
+    let db = test_db.conn();
+    let org = org_with_accounts(db).await;
+    let report = create_expense_report(db, &org).await;
+    let repo = AttachmentRepository::new(db.clone());
+    let tx_id = report.transaction.id;
+    let (removed, kept) = (report.entries[0].entry.id, report.entries[1].entry.id);
+    let on_removed = attach(&repo, &org, tx_id, Some(removed)).await;
+    let on_kept = attach(&repo, &org, tx_id, Some(kept)).await;
+
+    ledger_entries::Entity::delete_by_id(removed)
+        .exec(db)
+        .await
+        .unwrap();
+
+    let org_id = org.id.into_inner();
+    assert!(repo.find_by_id(on_removed, org_id).await.unwrap().is_none());
+    assert!(repo.find_by_id(on_kept, org_id).await.unwrap().is_some());
+}
+
+#[tokio::test]
+async fn test_deleting_draft_deletes_line_attachments_only() {
+    let test_db = TestDb::new().await;
+    let db = test_db.conn();
+    let org = org_with_accounts(db).await;
+    let report = create_expense_report(db, &org).await;
+    let repo = AttachmentRepository::new(db.clone());
+    let tx_id = report.transaction.id;
+    let on_transaction = attach(&repo, &org, tx_id, None).await;
+    let on_line = attach(&repo, &org, tx_id, Some(report.entries[0].entry.id)).await;
+
+    TransactionRepository::new(db.clone())
+        .delete_transaction(org.id, tx_id.into())
+        .await
+        .expect("Failed to delete draft");
+
+    assert!(
+        attachments::Entity::find_by_id(on_line)
+            .one(db)
+            .await
+            .unwrap()
+            .is_none()
+    );
+    // Transaction-level attachments are detached, as before
+    let detached = attachments::Entity::find_by_id(on_transaction)
+        .one(db)
+        .await
+        .unwrap()
+        .expect("transaction-level attachment should remain");
+    assert_eq!(detached.transaction_id, None);
+}
+
+#[tokio::test]
+async fn test_listing_pages_filters_and_names_uploader() {
+    let test_db = TestDb::new().await;
+    let db = test_db.conn();
+    let org = org_with_accounts(db).await;
+    let other = org_with_accounts(db).await;
+    let repo = AttachmentRepository::new(db.clone());
+    let report = create_expense_report(db, &org).await.transaction.id;
+    let second_report = create_expense_report(db, &org).await.transaction.id;
+    let mut ids = Vec::new();
+    for _ in 0..5 {
+        ids.push(attach(&repo, &org, report, None).await);
+    }
+    let contract = attach(&repo, &org, second_report, None).await;
+    let other_report = create_expense_report(db, &other).await.transaction.id;
+    attach(&repo, &other, other_report, None).await;
+
+    attachments::Entity::update_many()
+        .col_expr(
+            attachments::Column::AttachmentType,
+            attachments::Column::AttachmentType.save_as(Expr::val(DbAttachmentType::Contract)),
+        )
+        .col_expr(
+            attachments::Column::CreatedAt,
+            Expr::value(
+                NaiveDate::from_ymd_opt(2025, 1, 31)
+                    .unwrap()
+                    .and_hms_opt(23, 0, 0)
+                    .unwrap()
+                    .and_utc(),
+            ),
+        )
+        .filter(attachments::Column::Id.eq(contract))
+        .exec(db)
+        .await
+        .unwrap();
+
+    let org_id = org.id.into_inner();
+    let page = |page, per_page| PageRequest { page, per_page };
+    let of_report = AttachmentListFilter {
+        transaction_id: Some(report),
+        ..AttachmentListFilter::default()
+    };
+    let first = repo
+        .list_attachments(org_id, &of_report, &page(1, 2))
+        .await
+        .unwrap();
+    let last = repo
+        .list_attachments(org_id, &of_report, &page(3, 2))
+        .await
+        .unwrap();
+    assert_eq!(first.meta.total, 5);
+    assert_eq!(first.meta.total_pages, 3);
+    assert_eq!(first.data.len(), 2);
+    assert_eq!(last.data.len(), 1);
+    let owner = users::Entity::find_by_id(org.owner.user_id.into_inner())
+        .one(db)
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(
+        first
+            .data
+            .iter()
+            .all(|a| a.uploader_name.as_deref() == Some(owner.full_name.as_str()))
+    );
+
+    // Org-wide, filtered by type and upload date
+    let all = repo
+        .list_attachments(org_id, &AttachmentListFilter::default(), &page(1, 50))
+        .await
+        .unwrap();
+    assert_eq!(all.meta.total, 6);
+    let contracts = AttachmentListFilter {
+        attachment_type: Some(AttachmentType::Contract),
+        ..AttachmentListFilter::default()
+    };
+    let found = repo
+        .list_attachments(org_id, &contracts, &page(1, 50))
+        .await
+        .unwrap();
+    assert_eq!(found.data.len(), 1);
+    assert_eq!(found.data[0].attachment.id, contract);
+
+    let january = AttachmentListFilter {
+        uploaded_from: NaiveDate::from_ymd_opt(2025, 1, 1),
+        uploaded_to: NaiveDate::from_ymd_opt(2025, 1, 31),
+        ..AttachmentListFilter::default()
+    };
+    let found = repo
+        .list_attachments(org_id, &january, &page(1, 50))
+        .await
+        .unwrap();
+    assert_eq!(found.meta.total, 1);
+    assert_eq!(found.data[0].attachment.id, contract);
+}
+
+/// Posts and voids a transaction.
+async fn void(db: &DatabaseConnection, org: &Org, transaction_id: Uuid) {
+    let user_id = org.owner.user_id.into_inner();
+    let id = transaction_id.into();
+    let workflow = WorkflowRepository::new(db.clone());
+    workflow
+        .submit_transaction(org.id, id, user_id)
+        .await
+        .expect("Failed to submit transaction");
+    workflow
+        .approve_transaction(org.id, id, user_id, None)
+        .await
+        .expect("Failed to approve transaction");
+    workflow
+        .post_transaction(org.id, id, user_id)
+        .await
+        .expect("Failed to post transaction");
+    workflow
+        .void_transaction(
+            org.id,
+            id,
+            user_id,
+            VoidReasonCode::WrongAmount,
+            String::new(),
+        )
+        .await
+        .expect("Failed to void transaction");
+}
+
+#[tokio::test]
+async fn test_move_and_move_back_keep_storage_key() {
+    let test_db = TestDb::new().await;
+    let db = test_db.conn();
+    let org = org_with_accounts(db).await;
+    let voided = create_expense_report(db, &org).await;
+    let reentered = create_expense_report(db, &org).await.transaction.id;
+    let repo = AttachmentRepository::new(db.clone());
+    let (org_id, user_id) = (org.id.into_inner(), org.owner.user_id.into_inner());
+    let on_line = attach(
+        &repo,
+        &org,
+        voided.transaction.id,
+        Some(voided.entries[0].entry.id),
+    )
+    .await;
+    let before = repo.find_by_id(on_line, org_id).await.unwrap().unwrap();
+
+    let moved = repo
+        .move_to_transaction(on_line, org_id, reentered, user_id)
+        .await
+        .unwrap();
+    assert_eq!(moved.transaction_id, Some(reentered));
+    assert_eq!(moved.ledger_entry_id, None);
+    assert_eq!(moved.moved_from_transaction_id, Some(voided.transaction.id));
+    assert_eq!(moved.storage_key, before.storage_key);
+    let row = attachments::Entity::find_by_id(on_line)
+        .one(db)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(row.moved_by, Some(user_id));
+    assert!(row.moved_at.is_some());
+
+    let back = repo
+        .move_to_transaction(on_line, org_id, voided.transaction.id, user_id)
+        .await
+        .unwrap();
+    assert_eq!(back.transaction_id, Some(voided.transaction.id));
+    assert_eq!(back.moved_from_transaction_id, Some(reentered));
+    assert_eq!(back.storage_key, before.storage_key);
+}
+
+#[tokio::test]
+async fn test_move_to_other_org_or_voided_transaction_is_rejected() {
+    let test_db = TestDb::new().await;
+    let db = test_db.conn();
+    let org = org_with_accounts(db).await;
+    let other = org_with_accounts(db).await;
+    let report = create_expense_report(db, &org).await.transaction.id;
+    let other_report = create_expense_report(db, &other).await.transaction.id;
+    let voided = create_expense_report(db, &org).await.transaction.id;
+    void(db, &org, voided).await;
+    let repo = AttachmentRepository::new(db.clone());
+    let (org_id, user_id) = (org.id.into_inner(), org.owner.user_id.into_inner());
+    let id = attach(&repo, &org, report, None).await;
+
+    let err = repo
+        .move_to_transaction(id, org_id, other_report, user_id)
+        .await
+        .unwrap_err();
+    assert!(matches!(err, AttachmentError::TransactionNotFound(t) if t == other_report));
+    // Nor can the other org move it, even to its own transaction
+    let err = repo
+        .move_to_transaction(id, other.id.into_inner(), other_report, user_id)
+        .await
+        .unwrap_err();
+    assert!(matches!(err, AttachmentError::NotFound(_)));
+
+    let err = repo
+        .move_to_transaction(id, org_id, voided, user_id)
+        .await
+        .unwrap_err();
+    assert!(matches!(err, AttachmentError::TransactionVoided(t) if t == voided));
+
+    let unchanged = repo.find_by_id(id, org_id).await.unwrap().unwrap();
+    assert_eq!(unchanged.transaction_id, Some(report));
+    assert_eq!(unchanged.moved_from_transaction_id, None);
+}
+
+#[tokio::test]
+async fn test_move_all_relinks_every_attachment() {
+    let test_db = TestDb::new().await;
+    let db = test_db.conn();
+    let org = org_with_accounts(db).await;
+    let voided = create_expense_report(db, &org).await;
+    let reentered = create_expense_report(db, &org).await.transaction.id;
+    let untouched = create_expense_report(db, &org).await.transaction.id;
+    let repo = AttachmentRepository::new(db.clone());
+    let (org_id, user_id) = (org.id.into_inner(), org.owner.user_id.into_inner());
+    let from = voided.transaction.id;
+    attach(&repo, &org, from, None).await;
+    attach(&repo, &org, from, Some(voided.entries[1].entry.id)).await;
+    attach(&repo, &org, untouched, None).await;
+
+    void(db, &org, from).await;
+    let moved = repo
+        .move_all(org_id, from, reentered, user_id)
+        .await
+        .unwrap();
+
+    assert_eq!(moved, 2);
+    assert!(
+        repo.list_by_transaction(from, None, org_id)
+            .await
+            .unwrap()
+            .is_empty()
+    );
+    let relinked = repo
+        .list_by_transaction(reentered, None, org_id)
+        .await
+        .unwrap();
+    assert_eq!(relinked.len(), 2);
+    assert!(
+        relinked
+            .iter()
+            .all(|a| a.moved_from_transaction_id == Some(from) && a.ledger_entry_id.is_none())
+    );
+    assert_eq!(
+        repo.list_by_transaction(untouched, None, org_id)
+            .await
+            .unwrap()
+            .len(),
+        1
+    );
+}
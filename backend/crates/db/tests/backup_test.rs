@@ -0,0 +1,293 @@
+//! Integration tests for organization export and import.
+
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use sea_orm::{ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter};
+use uuid::Uuid;
+
+use zeltra_core::workflow::VoidReasonCode;
+use zeltra_db::entities::sea_orm_active_enums::{AccountType, TransactionStatus, TransactionType};
+use zeltra_db::entities::{ledger_entries, transactions};
+use zeltra_db::repositories::account::{AccountFilter, AccountRepository};
+use zeltra_db::repositories::backup::{BackupError, BackupManifest, BackupRepository, Section};
+use zeltra_db::repositories::transaction::{
+    CreateLedgerEntryInput, CreateTransactionInput, TransactionRepository,
+};
+use zeltra_db::repositories::workflow::WorkflowRepository;
+use zeltra_shared::types::{OrganizationId, TransactionId};
+use zeltra_test_support::{Org, OrgFixture, TestDb};
+
+const ACCOUNTS: &[(&str, AccountType)] =
+    &[("1000", AccountType::Asset), ("4000", AccountType::Revenue)];
+
+async fn create_invoice(
+    db: &DatabaseConnection,
+    org: &Org,
+    reference: Option<&str>,
+    amount: Decimal,
+) -> TransactionId {
+    let entry = |account: Uuid, debit: Decimal, credit: Decimal| CreateLedgerEntryInput {
+        account_id: account,
+        source_currency: "USD".to_string(),
+        source_amount: debit + credit,
+        exchange_rate: Decimal::ONE,
+        functional_currency: "USD".to_string(),
+        functional_amount: debit + credit,
+        debit,
+        credit,
+        memo: None,
+        dimensions: vec![],
+        rate_override: None,
+        event_at: None,
+    };
+    let created = TransactionRepository::new(db.clone())
+        .create_transaction(CreateTransactionInput {
+            organization_id: org.id.into_inner(),
+            transaction_type: TransactionType::Invoice,
+            transaction_date: NaiveDate::from_ymd_opt(2025, 3, 15).unwrap(),
+            description: format!("Invoice {}", reference.unwrap_or("without reference")),
+            reference_number: reference.map(str::to_string),
+            memo: None,
+            entries: vec![
+                entry(org.account("1000").into_inner(), amount, Decimal::ZERO),
+                entry(org.account("4000").into_inner(), Decimal::ZERO, amount),
+            ],
+            created_by: org.owner.user_id.into_inner(),
+        })
+        .await
+        .expect("Failed to create transaction");
+    TransactionId::from(created.transaction.id)
+}
+
+async fn post_invoice(
+    db: &DatabaseConnection,
+    org: &Org,
+    reference: Option<&str>,
+    amount: Decimal,
+) -> TransactionId {
+    let id = create_invoice(db, org, reference, amount).await;
+    let user_id = org.owner.user_id.into_inner();
+    let workflow = WorkflowRepository::new(db.clone());
+    workflow
+        .submit_transaction(org.id, id, user_id)
+        .await
+        .expect("Failed to submit transaction");
+    workflow
+        .approve_transaction(org.id, id, user_id, None)
+        .await
+        .expect("Failed to approve transaction");
+    workflow
+        .post_transaction(org.id, id, user_id)
+        .await
+        .expect("Failed to post transaction");
+    id
+}
+
+/// Source organization with posted, voided and draft transactions.
+async fn populated_org(db: &DatabaseConnection) -> Org {
+    let org = OrgFixture::new()
+        .with_fiscal_year_2025()
+        .with_accounts(ACCOUNTS)
+        .create(db)
+        .await;
+
+    post_invoice(db, &org, Some("INV-001"), Decimal::new(1200, 0)).await;
+    let voided = post_invoice(db, &org, None, Decimal::new(300, 0)).await;
+    WorkflowRepository::new(db.clone())
+        .void_transaction(
+            org.id,
+            voided,
+            org.owner.user_id.into_inner(),
+            VoidReasonCode::DuplicateEntry,
+            String::new(),
+        )
+        .await
+        .expect("Failed to void transaction");
+    create_invoice(db, &org, Some("INV-003"), Decimal::new(50, 0)).await;
+
+    org
+}
+
+async fn export(db: &DatabaseConnection, org_id: OrganizationId) -> (BackupManifest, Vec<u8>) {
+    let mut bundle = Vec::new();
+    let manifest = BackupRepository::new(db.clone())
+        .export(org_id, &mut bundle)
+        .await
+        .expect("Failed to export");
+    (manifest, bundle)
+}
+
+async fn balances(db: &DatabaseConnection, org_id: OrganizationId) -> Vec<(String, Decimal)> {
+    AccountRepository::new(db.clone())
+        .list_accounts(org_id, AccountFilter::default())
+        .await
+        .expect("Failed to list accounts")
+        .into_iter()
+        .map(|a| (a.account.code, a.balance))
+        .collect()
+}
+
+async fn transaction_count(db: &DatabaseConnection, org_id: OrganizationId) -> usize {
+    transactions::Entity::find()
+        .filter(transactions::Column::OrganizationId.eq(org_id.into_inner()))
+        .all(db)
+        .await
+        .unwrap()
+        .len()
+}
+
+#[tokio::test]
+async fn test_export_import_round_trip() {
+    let test_db = TestDb::new().await;
+    let db = test_db.conn();
+    let source = populated_org(db).await;
+    let target = OrgFixture::new().create(db).await;
+
+    let (manifest, bundle) = export(db, source.id).await;
+    let summary = BackupRepository::new(db.clone())
+        .import(target.id, target.owner.user_id.into_inner(), &bundle[..])
+        .await
+        .expect("Failed to import");
+
+    assert_eq!(summary.counts, manifest.counts);
+    assert_eq!(summary.counts[&Section::Accounts], 2);
+    // Three invoices plus the reversal created by the void
+    assert_eq!(summary.counts[&Section::Transactions], 4);
+    assert_eq!(summary.counts[&Section::LedgerEntries], 8);
+
+    assert_eq!(balances(db, source.id).await, balances(db, target.id).await);
+
+    let imported = transactions::Entity::find()
+        .filter(transactions::Column::OrganizationId.eq(target.id.into_inner()))
+        .all(db)
+        .await
+        .unwrap();
+    let voided = imported
+        .iter()
+        .find(|t| t.status == TransactionStatus::Voided)
+        .expect("voided transaction should be imported");
+    assert_eq!(voided.description, "Invoice without reference");
+    let reversal = imported
+        .iter()
+        .find(|t| Some(t.id) == voided.reversed_by_transaction_id)
+        .expect("reversal should be linked");
+    assert_eq!(reversal.reverses_transaction_id, Some(voided.id));
+
+    // Nothing in the target points back at the source
+    let entries = ledger_entries::Entity::find()
+        .filter(ledger_entries::Column::TransactionId.is_in(imported.iter().map(|t| t.id)))
+        .all(db)
+        .await
+        .unwrap();
+    assert_eq!(entries.len(), 8);
+    assert!(entries.iter().all(|e| {
+        !source
+            .accounts
+            .values()
+            .any(|a| a.into_inner() == e.account_id)
+    }));
+
+    // The source owner is not a member of the target, so the importer takes their place
+    let user_ids: Vec<Uuid> = imported.iter().map(|t| t.created_by).collect();
+    assert!(
+        user_ids
+            .iter()
+            .all(|u| *u == target.owner.user_id.into_inner())
+    );
+}
+
+#[tokio::test]
+async fn test_import_requires_empty_organization() {
+    let test_db = TestDb::new().await;
+    let db = test_db.conn();
+    let source = populated_org(db).await;
+    let target = OrgFixture::new()
+        .with_accounts(&[("9999", AccountType::Asset)])
+        .create(db)
+        .await;
+
+    let (_, bundle) = export(db, source.id).await;
+    let result = BackupRepository::new(db.clone())
+        .import(target.id, target.owner.user_id.into_inner(), &bundle[..])
+        .await;
+
+    assert!(matches!(
+        result,
+        Err(BackupError::NotEmpty(Section::Accounts))
+    ));
+}
+
+#[tokio::test]
+async fn test_import_rejects_unbalanced_transaction_and_rolls_back() {
+    let test_db = TestDb::new().await;
+    let db = test_db.conn();
+    let source = populated_org(db).await;
+    let target = OrgFixture::new().create(db).await;
+
+    let exported = String::from_utf8(export(db, source.id).await.1).unwrap();
+    let mut tampered = false;
+    let mut bundle = String::new();
+    for line in exported.lines() {
+        let mut value: serde_json::Value = serde_json::from_str(line).unwrap();
+        let is_debit = value["data"]["debit"]
+            .as_str()
+            .and_then(|d| d.parse::<Decimal>().ok())
+            .is_some_and(|d| d > Decimal::ZERO);
+        if !tampered && value["section"] == "ledger_entries" && is_debit {
+            for field in ["debit", "source_amount", "functional_amount"] {
+                value["data"][field] = serde_json::json!("1");
+            }
+            tampered = true;
+        }
+        bundle.push_str(&value.to_string());
+        bundle.push('\n');
+    }
+    assert!(tampered);
+
+    let result = BackupRepository::new(db.clone())
+        .import(
+            target.id,
+            target.owner.user_id.into_inner(),
+            bundle.as_bytes(),
+        )
+        .await;
+
+    assert!(
+        matches!(result, Err(BackupError::Unbalanced { .. })),
+        "{result:?}"
+    );
+    assert_eq!(transaction_count(db, target.id).await, 0);
+}
+
+#[tokio::test]
+async fn test_import_rejects_count_mismatch() {
+    let test_db = TestDb::new().await;
+    let db = test_db.conn();
+    let source = populated_org(db).await;
+    let target = OrgFixture::new().create(db).await;
+
+    let bundle = String::from_utf8(export(db, source.id).await.1)
+        .unwrap()
+        .replacen("\"accounts\":2", "\"accounts\":3", 1);
+
+    let result = BackupRepository::new(db.clone())
+        .import(
+            target.id,
+            target.owner.user_id.into_inner(),
+            bundle.as_bytes(),
+        )
+        .await;
+
+    assert!(
+        matches!(
+            result,
+            Err(BackupError::CountMismatch {
+                section: Section::Accounts,
+                expected: 3,
+                actual: 2
+            })
+        ),
+        "{result:?}"
+    );
+    assert_eq!(transaction_count(db, target.id).await, 0);
+}
@@ -0,0 +1,215 @@
+//! Integration tests for period-end balance snapshots.
+//!
+//! Reports that start from a snapshot must match a full recomputation from
+//! the ledger.
+
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use sea_orm::{ColumnTrait, DatabaseConnection, EntityTrait, PaginatorTrait, QueryFilter};
+
+use zeltra_db::entities::{
+    account_period_balances,
+    sea_orm_active_enums::{AccountType, FiscalPeriodStatus},
+};
+use zeltra_db::repositories::account::AccountRepository;
+use zeltra_db::repositories::balance_snapshot::BalanceSnapshotRepository;
+use zeltra_db::repositories::fiscal::FiscalRepository;
+use zeltra_db::repositories::report::{AccountBalance, ReportRepository};
+use zeltra_test_support::{Line, Org, OrgFixture, TestDb, post_transaction};
+
+const ACCOUNTS: &[(&str, AccountType)] = &[
+    ("1000", AccountType::Asset),
+    ("2000", AccountType::Liability),
+    ("4000", AccountType::Revenue),
+    ("5000", AccountType::Expense),
+];
+
+fn date(month: u32, day: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(2025, month, day).unwrap()
+}
+
+/// Posts a two-line transaction debiting one account and crediting another.
+async fn post(
+    db: &DatabaseConnection,
+    org: &Org,
+    on: NaiveDate,
+    debit_account: &str,
+    credit_account: &str,
+    amount: i64,
+) {
+    let amount = Decimal::new(amount, 2);
+    post_transaction(
+        db,
+        org,
+        on,
+        vec![
+            Line::debit(debit_account, amount),
+            Line::credit(credit_account, amount),
+        ],
+    )
+    .await;
+}
+
+/// Seeds a few months of activity with varied amounts.
+async fn seed(db: &DatabaseConnection, org: &Org) {
+    for month in 1..=3 {
+        for day in [3, 14, 28] {
+            let amount = i64::from(month * 10_000 + day * 137);
+            post(db, org, date(month, day), "1000", "4000", amount).await;
+            post(db, org, date(month, day), "5000", "1000", amount / 3).await;
+            post(db, org, date(month, day), "1000", "2000", amount / 7).await;
+        }
+    }
+}
+
+async fn set_status(db: &DatabaseConnection, org: &Org, period: usize, status: FiscalPeriodStatus) {
+    let period_id = org.fiscal_year.as_ref().unwrap().periods[period].into_inner();
+    FiscalRepository::new(db.clone())
+        .update_period_status(period_id, status, None)
+        .await
+        .expect("Failed to update period status");
+}
+
+async fn snapshot_rows(db: &DatabaseConnection, org: &Org) -> u64 {
+    account_period_balances::Entity::find()
+        .filter(account_period_balances::Column::OrganizationId.eq(org.id.into_inner()))
+        .count(db)
+        .await
+        .unwrap()
+}
+
+/// Drops the snapshots so reports recompute from the full ledger.
+async fn clear_snapshots(db: &DatabaseConnection, org: &Org) {
+    account_period_balances::Entity::delete_many()
+        .filter(account_period_balances::Column::OrganizationId.eq(org.id.into_inner()))
+        .exec(db)
+        .await
+        .unwrap();
+}
+
+/// Code, debit, credit and balance of each report row.
+type Totals = Vec<(String, Decimal, Decimal, Decimal)>;
+
+fn totals(rows: &[AccountBalance]) -> Totals {
+    rows.iter()
+        .map(|r| (r.code.clone(), r.total_debit, r.total_credit, r.balance))
+        .collect()
+}
+
+/// Trial balance, balance sheet and as-of balances at `as_of`.
+async fn balances(
+    db: &DatabaseConnection,
+    org: &Org,
+    as_of: NaiveDate,
+) -> (Totals, Totals, Vec<Decimal>) {
+    let reports = ReportRepository::new(db.clone());
+    let trial_balance = reports
+        .query_trial_balance(org.id.into_inner(), as_of, &[])
+        .await
+        .unwrap();
+    let balance_sheet = reports
+        .query_balance_sheet(org.id.into_inner(), as_of)
+        .await
+        .unwrap();
+
+    let accounts = AccountRepository::new(db.clone());
+    let mut as_of_balances = Vec::new();
+    for (code, _) in ACCOUNTS {
+        as_of_balances.push(
+            accounts
+                .get_balance_at_date(org.account(code), as_of)
+                .await
+                .unwrap(),
+        );
+    }
+
+    (
+        totals(&trial_balance),
+        totals(&balance_sheet),
+        as_of_balances,
+    )
+}
+
+#[tokio::test]
+async fn test_snapshot_plus_delta_matches_full_recomputation() {
+    let test_db = TestDb::new().await;
+    let db = test_db.conn();
+    let org = OrgFixture::new()
+        .with_fiscal_year_2025()
+        .with_accounts(ACCOUNTS)
+        .create(db)
+        .await;
+    seed(db, &org).await;
+
+    set_status(db, &org, 0, FiscalPeriodStatus::Closed).await;
+    set_status(db, &org, 1, FiscalPeriodStatus::Closed).await;
+    assert_eq!(snapshot_rows(db, &org).await, 2 * ACCOUNTS.len() as u64);
+
+    // Activity after the snapshots only lands in March
+    post(db, &org, date(3, 30), "1000", "4000", 99_999).await;
+
+    let as_of_dates = [
+        date(1, 31),
+        date(2, 10),
+        date(2, 28),
+        date(3, 29),
+        date(3, 31),
+    ];
+    let mut with_snapshots = Vec::new();
+    for as_of in as_of_dates {
+        with_snapshots.push(balances(db, &org, as_of).await);
+    }
+
+    clear_snapshots(db, &org).await;
+
+    for (as_of, expected) in as_of_dates.into_iter().zip(with_snapshots) {
+        let recomputed = balances(db, &org, as_of).await;
+        assert_eq!(recomputed, expected, "Balances differ as of {as_of}");
+
+        // As-of balances agree with the trial balance
+        let trial_balance_balances: Vec<Decimal> = recomputed.0.iter().map(|r| r.3).collect();
+        assert_eq!(recomputed.2, trial_balance_balances);
+    }
+
+    // The backfill rebuilds the same snapshots
+    let written = BalanceSnapshotRepository::new(db.clone())
+        .backfill(org.id.into_inner())
+        .await
+        .unwrap();
+    assert_eq!(written, 2 * ACCOUNTS.len() as u64);
+    let rebuilt = balances(db, &org, date(3, 31)).await;
+    clear_snapshots(db, &org).await;
+    assert_eq!(balances(db, &org, date(3, 31)).await, rebuilt);
+}
+
+#[tokio::test]
+async fn test_no_snapshot_while_an_earlier_period_can_change() {
+    let test_db = TestDb::new().await;
+    let db = test_db.conn();
+    let org = OrgFixture::new()
+        .with_fiscal_year_2025()
+        .with_accounts(ACCOUNTS)
+        .create(db)
+        .await;
+    seed(db, &org).await;
+
+    // January only soft-closes, so February's totals are not final yet
+    set_status(db, &org, 0, FiscalPeriodStatus::SoftClose).await;
+    set_status(db, &org, 1, FiscalPeriodStatus::Closed).await;
+    assert_eq!(snapshot_rows(db, &org).await, 0);
+
+    post(db, &org, date(1, 20), "1000", "4000", 5_000).await;
+
+    // Closing January snapshots both periods, including the late entry
+    set_status(db, &org, 0, FiscalPeriodStatus::Closed).await;
+    assert_eq!(snapshot_rows(db, &org).await, 2 * ACCOUNTS.len() as u64);
+
+    // Entries posted out of date order break the running balance chain, so
+    // only the report totals are compared here
+    let (trial_balance, balance_sheet, _) = balances(db, &org, date(2, 28)).await;
+    clear_snapshots(db, &org).await;
+    let (recomputed_trial_balance, recomputed_balance_sheet, _) =
+        balances(db, &org, date(2, 28)).await;
+    assert_eq!(recomputed_trial_balance, trial_balance);
+    assert_eq!(recomputed_balance_sheet, balance_sheet);
+}
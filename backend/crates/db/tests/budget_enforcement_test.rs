@@ -0,0 +1,328 @@
+//! Integration tests for checking transactions against budgets on submission.
+
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use sea_orm::{DatabaseConnection, EntityTrait};
+use uuid::Uuid;
+
+use zeltra_db::entities::sea_orm_active_enums::{AccountType, BudgetType, TransactionType};
+use zeltra_db::entities::transactions;
+use zeltra_db::repositories::budget::{
+    BudgetRepository, CreateBudgetInput, CreateBudgetLineInput, UpdateBudgetInput,
+};
+use zeltra_db::repositories::dimension::{
+    CreateDimensionTypeInput, CreateDimensionValueInput, DimensionRepository,
+};
+use zeltra_db::repositories::transaction::{
+    CreateLedgerEntryInput, CreateTransactionInput, TransactionRepository,
+};
+use zeltra_shared::types::TransactionId;
+use zeltra_test_support::{Line, Org, OrgFixture, TestDb, post_transaction};
+
+const ACCOUNTS: &[(&str, AccountType)] = &[
+    ("1000", AccountType::Asset),
+    ("4000", AccountType::Revenue),
+    ("6000", AccountType::Expense),
+];
+
+fn date(month: u32, day: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(2025, month, day).unwrap()
+}
+
+async fn org_with_accounts(db: &DatabaseConnection) -> Org {
+    OrgFixture::new()
+        .with_fiscal_year_2025()
+        .with_accounts(ACCOUNTS)
+        .create(db)
+        .await
+}
+
+async fn department(db: &DatabaseConnection, org: &Org, codes: &[&str]) -> Vec<Uuid> {
+    let repo = DimensionRepository::new(db.clone());
+    let type_id = repo
+        .create_dimension_type(CreateDimensionTypeInput {
+            organization_id: org.id.into_inner(),
+            code: "DEPT".to_string(),
+            name: "Department".to_string(),
+            description: None,
+            is_required: false,
+            is_active: true,
+            sort_order: 0,
+        })
+        .await
+        .expect("Failed to create dimension type")
+        .id;
+    let mut ids = Vec::new();
+    for code in codes {
+        let value = repo
+            .create_dimension_value(CreateDimensionValueInput {
+                organization_id: org.id.into_inner(),
+                dimension_type_id: type_id,
+                code: (*code).to_string(),
+                name: (*code).to_string(),
+                description: None,
+                parent_id: None,
+                is_active: true,
+                effective_from: None,
+                effective_to: None,
+            })
+            .await
+            .expect("Failed to create dimension value");
+        ids.push(value.id);
+    }
+    ids
+}
+
+/// Creates a draft paying `lines` (account, amount, dimensions) from cash.
+async fn create_draft(
+    db: &DatabaseConnection,
+    org: &Org,
+    on: NaiveDate,
+    lines: &[(&str, Decimal, Vec<Uuid>)],
+) -> TransactionId {
+    let entry = |account: &str, debit: Decimal, credit: Decimal, dimensions: Vec<Uuid>| {
+        CreateLedgerEntryInput {
+            account_id: org.account(account).into_inner(),
+            source_currency: "USD".to_string(),
+            source_amount: debit + credit,
+            exchange_rate: Decimal::ONE,
+            functional_currency: "USD".to_string(),
+            functional_amount: debit + credit,
+            debit,
+            credit,
+            memo: None,
+            dimensions,
+            rate_override: None,
+            event_at: None,
+        }
+    };
+    let total: Decimal = lines.iter().map(|(_, amount, _)| *amount).sum();
+    let mut entries: Vec<CreateLedgerEntryInput> = lines
+        .iter()
+        .map(|(account, amount, dims)| entry(account, *amount, Decimal::ZERO, dims.clone()))
+        .collect();
+    entries.push(entry("1000", Decimal::ZERO, total, vec![]));
+    let created = TransactionRepository::new(db.clone())
+        .create_transaction(CreateTransactionInput {
+            organization_id: org.id.into_inner(),
+            transaction_type: TransactionType::Expense,
+            transaction_date: on,
+            description: "Spend".to_string(),
+            reference_number: None,
+            memo: None,
+            entries,
+            created_by: org.owner.user_id.into_inner(),
+        })
+        .await
+        .expect("Failed to create transaction");
+    TransactionId::from(created.transaction.id)
+}
+
+/// Creates and posts a payment of `amount` against `account`.
+async fn post_spend(
+    db: &DatabaseConnection,
+    org: &Org,
+    on: NaiveDate,
+    account: &str,
+    amount: Decimal,
+    dimensions: Vec<Uuid>,
+) {
+    post_transaction(
+        db,
+        org,
+        on,
+        vec![
+            Line::debit(account, amount).with_dimensions(dimensions),
+            Line::credit("1000", amount),
+        ],
+    )
+    .await;
+}
+
+/// Creates a budget with `lines` (account, period index, amount, dimensions).
+async fn create_budget(
+    db: &DatabaseConnection,
+    org: &Org,
+    name: &str,
+    lines: &[(&str, usize, Decimal, Vec<Uuid>)],
+) -> Uuid {
+    let repo = BudgetRepository::new(db.clone());
+    let fiscal_year = org.fiscal_year.as_ref().unwrap();
+    let budget = repo
+        .create_budget(CreateBudgetInput {
+            organization_id: org.id.into_inner(),
+            fiscal_year_id: fiscal_year.id.into_inner(),
+            name: name.to_string(),
+            description: None,
+            budget_type: BudgetType::Monthly,
+            currency: "USD".to_string(),
+            created_by: org.owner.user_id.into_inner(),
+        })
+        .await
+        .expect("Failed to create budget");
+    let lines = lines
+        .iter()
+        .map(|(account, period, amount, dims)| CreateBudgetLineInput {
+            account_id: org.account(account).into_inner(),
+            fiscal_period_id: fiscal_year.periods[*period].into_inner(),
+            amount: *amount,
+            notes: None,
+            dimensions: dims.clone(),
+        })
+        .collect();
+    repo.replace_lines(
+        org.id.into_inner(),
+        budget.id,
+        org.owner.user_id.into_inner(),
+        lines,
+    )
+    .await
+    .expect("Failed to add budget lines");
+    budget.id
+}
+
+#[tokio::test]
+async fn test_lines_crossed_by_transaction_are_reported() {
+    let test_db = TestDb::new().await;
+    let db = test_db.conn();
+    let org = org_with_accounts(db).await;
+    let org_id = org.id.into_inner();
+    let depts = department(db, &org, &["ENG", "SALES"]).await;
+    let (eng, sales) = (depts[0], depts[1]);
+    let repo = BudgetRepository::new(db.clone());
+
+    // March is period 2
+    create_budget(
+        db,
+        &org,
+        "Operating",
+        &[
+            ("6000", 2, dec!(1000), vec![]),
+            ("4000", 2, dec!(100), vec![]),
+        ],
+    )
+    .await;
+    create_budget(
+        db,
+        &org,
+        "Engineering",
+        &[("6000", 2, dec!(500), vec![eng])],
+    )
+    .await;
+    let inactive = create_budget(db, &org, "Old plan", &[("6000", 2, dec!(10), vec![])]).await;
+    repo.update_budget(
+        org_id,
+        inactive,
+        org.owner.user_id.into_inner(),
+        UpdateBudgetInput {
+            is_active: Some(false),
+            ..UpdateBudgetInput::default()
+        },
+    )
+    .await
+    .unwrap();
+    post_spend(db, &org, date(3, 3), "6000", dec!(400), vec![eng]).await;
+
+    // ENG: 400 posted + 150 > 500; whole account: 400 + 150 + 200 = 750
+    let draft = create_draft(
+        db,
+        &org,
+        date(3, 20),
+        &[
+            ("6000", dec!(150), vec![eng]),
+            ("6000", dec!(200), vec![sales]),
+        ],
+    )
+    .await;
+    let overruns = repo
+        .check_transaction_budgets(org_id, draft.into_inner(), 100)
+        .await
+        .unwrap();
+    assert_eq!(overruns.len(), 1);
+    let eng_line = &overruns[0];
+    assert_eq!(eng_line.budget_name, "Engineering");
+    assert_eq!(eng_line.account_code, "6000");
+    assert_eq!(eng_line.period_name, "March 2025");
+    assert_eq!(eng_line.utilization.budgeted, dec!(500));
+    assert_eq!(eng_line.utilization.actual, dec!(400));
+    assert_eq!(eng_line.utilization.impact, dec!(150));
+    assert_eq!(eng_line.utilization.remaining(), dec!(100));
+    assert_eq!(eng_line.utilization.percent(), Some(dec!(110.00)));
+
+    // A lower threshold catches the whole-account line too
+    let overruns = repo
+        .check_transaction_budgets(org_id, draft.into_inner(), 70)
+        .await
+        .unwrap();
+    let mut budgeted: Vec<Decimal> = overruns.iter().map(|o| o.utilization.budgeted).collect();
+    budgeted.sort();
+    assert_eq!(budgeted, [dec!(500), dec!(1000)]);
+
+    // An unknown transaction has nothing to check
+    assert!(
+        repo.check_transaction_budgets(org_id, Uuid::new_v4(), 100)
+            .await
+            .unwrap()
+            .is_empty()
+    );
+
+    repo.record_budget_warning(org_id, draft.into_inner(), true)
+        .await
+        .unwrap();
+    let flagged = transactions::Entity::find_by_id(draft.into_inner())
+        .one(db)
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(flagged.budget_warning);
+}
+
+#[tokio::test]
+async fn test_only_the_transaction_period_is_checked() {
+    let test_db = TestDb::new().await;
+    let db = test_db.conn();
+    let org = org_with_accounts(db).await;
+    let org_id = org.id.into_inner();
+    let repo = BudgetRepository::new(db.clone());
+
+    create_budget(
+        db,
+        &org,
+        "Operating",
+        &[
+            ("6000", 2, dec!(1000), vec![]),
+            ("6000", 3, dec!(1000), vec![]),
+        ],
+    )
+    .await;
+    post_spend(db, &org, date(3, 10), "6000", dec!(900), vec![]).await;
+
+    // March spend does not count against April
+    let april = create_draft(db, &org, date(4, 2), &[("6000", dec!(300), vec![])]).await;
+    assert!(
+        repo.check_transaction_budgets(org_id, april.into_inner(), 100)
+            .await
+            .unwrap()
+            .is_empty()
+    );
+
+    let march = create_draft(db, &org, date(3, 28), &[("6000", dec!(300), vec![])]).await;
+    let overruns = repo
+        .check_transaction_budgets(org_id, march.into_inner(), 100)
+        .await
+        .unwrap();
+    assert_eq!(overruns.len(), 1);
+    assert_eq!(overruns[0].period_name, "March 2025");
+    assert_eq!(overruns[0].utilization.projected(), dec!(1200));
+
+    // April's own posted spend does count, while the March draft does not
+    post_spend(db, &org, date(4, 5), "6000", dec!(800), vec![]).await;
+    let overruns = repo
+        .check_transaction_budgets(org_id, april.into_inner(), 100)
+        .await
+        .unwrap();
+    assert_eq!(overruns.len(), 1);
+    assert_eq!(overruns[0].period_name, "April 2025");
+    assert_eq!(overruns[0].utilization.actual, dec!(800));
+}
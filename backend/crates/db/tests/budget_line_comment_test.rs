@@ -0,0 +1,169 @@
+//! Integration tests for budget line variance comments.
+
+use rust_decimal::Decimal;
+use uuid::Uuid;
+
+use zeltra_core::auth::UserRole as CoreUserRole;
+use zeltra_core::comment::CommentError;
+use zeltra_db::entities::sea_orm_active_enums::{AccountType, BudgetType, UserRole};
+use zeltra_db::repositories::budget::{BudgetRepository, CreateBudgetInput, CreateBudgetLineInput};
+use zeltra_db::repositories::budget_comment::{
+    BudgetCommentError, BudgetCommentRepository, CreateBudgetCommentInput, EditBudgetCommentInput,
+};
+use zeltra_test_support::{Org, OrgFixture, TestDb};
+
+const ACCOUNTS: &[(&str, AccountType)] = &[("5000", AccountType::Expense)];
+
+fn line(org: &Org, period: usize, amount: i64) -> CreateBudgetLineInput {
+    CreateBudgetLineInput {
+        account_id: org.account("5000").into_inner(),
+        fiscal_period_id: org.fiscal_year.as_ref().unwrap().periods[period].into_inner(),
+        amount: Decimal::new(amount, 0),
+        notes: None,
+        dimensions: vec![],
+    }
+}
+
+fn edit(org: &Org, budget_id: Uuid, line_id: Uuid, comment_id: Uuid) -> EditBudgetCommentInput {
+    EditBudgetCommentInput {
+        organization_id: org.id.into_inner(),
+        budget_id,
+        budget_line_id: line_id,
+        comment_id,
+        editor_id: Uuid::nil(),
+        editor_role: CoreUserRole::Viewer,
+        body: "Campaign moved to March".to_string(),
+    }
+}
+
+#[tokio::test]
+#[allow(clippy::too_many_lines)]
+async fn test_comments_are_versioned_and_edits_are_restricted() {
+    let test_db = TestDb::new().await;
+    let db = test_db.conn();
+    let org = OrgFixture::new()
+        .with_fiscal_year_2025()
+        .with_accounts(ACCOUNTS)
+        .with_member(UserRole::Viewer)
+        .with_member(UserRole::Viewer)
+        .with_member(UserRole::Accountant)
+        .create(db)
+        .await;
+    let org_id = org.id.into_inner();
+    let author = org.members[0].user_id.into_inner();
+    let other_viewer = org.members[1].user_id.into_inner();
+    let accountant = org.member(&UserRole::Accountant).user_id.into_inner();
+
+    let budgets = BudgetRepository::new(db.clone());
+    let budget_id = budgets
+        .create_budget(CreateBudgetInput {
+            organization_id: org_id,
+            fiscal_year_id: org.fiscal_year.as_ref().unwrap().id.into_inner(),
+            name: "Marketing 2025".to_string(),
+            description: None,
+            budget_type: BudgetType::Monthly,
+            currency: "USD".to_string(),
+            created_by: org.owner.user_id.into_inner(),
+        })
+        .await
+        .expect("Failed to create budget")
+        .id;
+    let lines = budgets
+        .create_budget_lines(
+            org_id,
+            budget_id,
+            accountant,
+            vec![line(&org, 0, 500), line(&org, 1, 500)],
+        )
+        .await
+        .expect("Failed to create lines");
+    let (january, february) = (lines[0].line.id, lines[1].line.id);
+
+    let repo = BudgetCommentRepository::new(db.clone());
+    let comment = repo
+        .create(CreateBudgetCommentInput {
+            organization_id: org_id,
+            budget_id,
+            budget_line_id: january,
+            author_id: author,
+            body: "  Campaign launched early  ".to_string(),
+        })
+        .await
+        .expect("Viewer comments on a line");
+    assert_eq!(comment.comment.body, "Campaign launched early");
+    assert_eq!(
+        comment.comment.fiscal_period_id,
+        lines[0].line.fiscal_period_id
+    );
+    let comment_id = comment.comment.comment_id;
+
+    // Blank comments and lines of another budget are rejected
+    let err = repo
+        .create(CreateBudgetCommentInput {
+            organization_id: org_id,
+            budget_id,
+            budget_line_id: january,
+            author_id: author,
+            body: "   ".to_string(),
+        })
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        err,
+        BudgetCommentError::Comment(CommentError::EmptyBody)
+    ));
+    let err = repo
+        .find_line(org_id, Uuid::new_v4(), january)
+        .await
+        .unwrap_err();
+    assert!(matches!(err, BudgetCommentError::LineNotFound(_)));
+
+    // Another viewer cannot edit it
+    let err = repo
+        .edit(EditBudgetCommentInput {
+            editor_id: other_viewer,
+            ..edit(&org, budget_id, january, comment_id)
+        })
+        .await
+        .unwrap_err();
+    assert!(matches!(err, BudgetCommentError::NotAuthor));
+
+    // The author and an accountant can; each edit adds a version
+    repo.edit(EditBudgetCommentInput {
+        editor_id: author,
+        ..edit(&org, budget_id, january, comment_id)
+    })
+    .await
+    .expect("Author edits their comment");
+    let edited = repo
+        .edit(EditBudgetCommentInput {
+            editor_id: accountant,
+            editor_role: CoreUserRole::Accountant,
+            body: "Campaign moved to March; spend reallocated".to_string(),
+            ..edit(&org, budget_id, january, comment_id)
+        })
+        .await
+        .expect("Accountant edits any comment");
+    assert_eq!(edited.comment.comment_id, comment_id);
+    assert_eq!(edited.comment.author_id, author);
+    assert_eq!(edited.comment.edited_by, Some(accountant));
+
+    let current = repo.list(org_id, january, false).await.unwrap();
+    assert_eq!(current.len(), 1);
+    assert_eq!(
+        current[0].comment.body,
+        "Campaign moved to March; spend reallocated"
+    );
+    let history = repo.list(org_id, january, true).await.unwrap();
+    assert_eq!(history.len(), 3);
+    assert_eq!(history.iter().filter(|c| c.comment.is_current).count(), 1);
+
+    // Only commented lines appear in the latest-comment map
+    let latest = repo
+        .latest_for_lines(org_id, &[january, february])
+        .await
+        .unwrap();
+    assert_eq!(latest.len(), 1);
+    assert_eq!(latest[&january].comment.id, edited.comment.id);
+    assert!(!latest.contains_key(&february));
+}
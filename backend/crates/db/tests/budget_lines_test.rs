@@ -0,0 +1,265 @@
+//! Integration tests for replacing a budget's lines in one request.
+
+use rust_decimal::Decimal;
+use sea_orm::DatabaseConnection;
+use uuid::Uuid;
+
+use zeltra_db::entities::sea_orm_active_enums::{AccountType, BudgetType};
+use zeltra_db::repositories::account::{AccountRepository, UpdateAccountInput};
+use zeltra_db::repositories::budget::{
+    BudgetError, BudgetLinesDiff, BudgetRepository, CreateBudgetInput, CreateBudgetLineInput,
+    MAX_REPLACE_LINES,
+};
+use zeltra_test_support::{Org, OrgFixture, TestDb};
+
+const ACCOUNTS: &[(&str, AccountType)] = &[
+    ("5000", AccountType::Expense),
+    ("5100", AccountType::Expense),
+    ("5200", AccountType::Expense),
+];
+
+fn period(org: &Org, month: u32) -> Uuid {
+    let year = org.fiscal_year.as_ref().expect("fixture has a fiscal year");
+    year.periods[month as usize - 1].into_inner()
+}
+
+fn line(org: &Org, code: &str, month: u32, amount: i64) -> CreateBudgetLineInput {
+    CreateBudgetLineInput {
+        account_id: org.account(code).into_inner(),
+        fiscal_period_id: period(org, month),
+        amount: Decimal::new(amount, 0),
+        notes: None,
+        dimensions: vec![],
+    }
+}
+
+async fn org_with_budget(db: &DatabaseConnection) -> (Org, Uuid) {
+    let org = OrgFixture::new()
+        .with_fiscal_year_2025()
+        .with_accounts(ACCOUNTS)
+        .create(db)
+        .await;
+    let budget = BudgetRepository::new(db.clone())
+        .create_budget(CreateBudgetInput {
+            organization_id: org.id.into_inner(),
+            fiscal_year_id: org
+                .fiscal_year
+                .as_ref()
+                .expect("fixture has a fiscal year")
+                .id
+                .into_inner(),
+            name: "Operating 2025".to_string(),
+            description: None,
+            budget_type: BudgetType::Monthly,
+            currency: "USD".to_string(),
+            created_by: org.owner.user_id.into_inner(),
+        })
+        .await
+        .expect("Failed to create budget");
+    (org, budget.id)
+}
+
+async fn amounts(db: &DatabaseConnection, budget_id: Uuid) -> Vec<(Uuid, Uuid, Decimal)> {
+    let mut lines: Vec<_> = BudgetRepository::new(db.clone())
+        .get_budget_lines(budget_id)
+        .await
+        .expect("Failed to load lines")
+        .into_iter()
+        .map(|l| (l.line.account_id, l.line.fiscal_period_id, l.line.amount))
+        .collect();
+    lines.sort();
+    lines
+}
+
+#[tokio::test]
+async fn test_replace_reconciles_existing_lines() {
+    let test_db = TestDb::new().await;
+    let db = test_db.conn();
+    let (org, budget_id) = org_with_budget(db).await;
+    let repo = BudgetRepository::new(db.clone());
+    let org_id = org.id.into_inner();
+    let user_id = org.owner.user_id.into_inner();
+
+    let first = repo
+        .replace_lines(
+            org_id,
+            budget_id,
+            user_id,
+            vec![
+                line(&org, "5000", 1, 100),
+                line(&org, "5100", 1, 200),
+                line(&org, "5200", 1, 300),
+            ],
+        )
+        .await
+        .expect("Failed to replace lines");
+    assert_eq!(
+        first,
+        BudgetLinesDiff {
+            created: 3,
+            ..Default::default()
+        }
+    );
+
+    // Keep 5000, change 5100, drop 5200, add 5000 in February
+    let second = repo
+        .replace_lines(
+            org_id,
+            budget_id,
+            user_id,
+            vec![
+                line(&org, "5000", 1, 100),
+                line(&org, "5100", 1, 250),
+                line(&org, "5000", 2, 120),
+            ],
+        )
+        .await
+        .expect("Failed to replace lines");
+    assert_eq!(
+        second,
+        BudgetLinesDiff {
+            created: 1,
+            updated: 1,
+            deleted: 1,
+            unchanged: 1,
+        }
+    );
+
+    let mut expected = vec![
+        (
+            org.account("5000").into_inner(),
+            period(&org, 1),
+            Decimal::new(100, 0),
+        ),
+        (
+            org.account("5100").into_inner(),
+            period(&org, 1),
+            Decimal::new(250, 0),
+        ),
+        (
+            org.account("5000").into_inner(),
+            period(&org, 2),
+            Decimal::new(120, 0),
+        ),
+    ];
+    expected.sort();
+    assert_eq!(amounts(db, budget_id).await, expected);
+}
+
+#[tokio::test]
+async fn test_replace_with_empty_set_deletes_everything() {
+    let test_db = TestDb::new().await;
+    let db = test_db.conn();
+    let (org, budget_id) = org_with_budget(db).await;
+    let repo = BudgetRepository::new(db.clone());
+    let org_id = org.id.into_inner();
+    let user_id = org.owner.user_id.into_inner();
+
+    repo.replace_lines(org_id, budget_id, user_id, vec![line(&org, "5000", 1, 100)])
+        .await
+        .expect("Failed to replace lines");
+    let diff = repo
+        .replace_lines(org_id, budget_id, user_id, vec![])
+        .await
+        .expect("Failed to replace lines");
+
+    assert_eq!(diff.deleted, 1);
+    assert!(amounts(db, budget_id).await.is_empty());
+}
+
+#[tokio::test]
+async fn test_invalid_lines_reported_by_index_and_nothing_applied() {
+    let test_db = TestDb::new().await;
+    let db = test_db.conn();
+    let (org, budget_id) = org_with_budget(db).await;
+    let repo = BudgetRepository::new(db.clone());
+    let org_id = org.id.into_inner();
+    let user_id = org.owner.user_id.into_inner();
+
+    repo.replace_lines(org_id, budget_id, user_id, vec![line(&org, "5000", 1, 100)])
+        .await
+        .expect("Failed to replace lines");
+
+    AccountRepository::new(db.clone())
+        .update_account(
+            org.account("5200"),
+            UpdateAccountInput {
+                is_active: Some(false),
+                ..Default::default()
+            },
+        )
+        .await
+        .expect("Failed to deactivate account");
+
+    let mut outside_year = line(&org, "5100", 1, 50);
+    outside_year.fiscal_period_id = Uuid::new_v4();
+
+    let result = repo
+        .replace_lines(
+            org_id,
+            budget_id,
+            user_id,
+            vec![
+                line(&org, "5000", 1, 999),
+                outside_year,
+                line(&org, "5200", 1, 10),
+                line(&org, "5000", 1, 5),
+            ],
+        )
+        .await;
+
+    let Err(BudgetError::InvalidLines(errors)) = result else {
+        panic!("expected InvalidLines, got {result:?}");
+    };
+    let indices: Vec<usize> = errors.iter().map(|e| e.index).collect();
+    assert_eq!(indices, vec![1, 2, 3]);
+    assert!(errors[1].message.contains("inactive"));
+    assert!(errors[2].message.contains("index 0"));
+
+    // The valid first line was not applied either
+    assert_eq!(
+        amounts(db, budget_id).await,
+        vec![(
+            org.account("5000").into_inner(),
+            period(&org, 1),
+            Decimal::new(100, 0)
+        )]
+    );
+}
+
+#[tokio::test]
+async fn test_locked_budget_rejected() {
+    let test_db = TestDb::new().await;
+    let db = test_db.conn();
+    let (org, budget_id) = org_with_budget(db).await;
+    let repo = BudgetRepository::new(db.clone());
+    let org_id = org.id.into_inner();
+    let user_id = org.owner.user_id.into_inner();
+
+    repo.lock_budget(org_id, budget_id)
+        .await
+        .expect("Failed to lock budget");
+
+    let result = repo
+        .replace_lines(org_id, budget_id, user_id, vec![line(&org, "5000", 1, 100)])
+        .await;
+    assert!(matches!(result, Err(BudgetError::BudgetLocked)));
+}
+
+#[tokio::test]
+async fn test_payload_size_guarded() {
+    let test_db = TestDb::new().await;
+    let db = test_db.conn();
+    let (org, budget_id) = org_with_budget(db).await;
+
+    let lines = vec![line(&org, "5000", 1, 1); MAX_REPLACE_LINES + 1];
+    let result = BudgetRepository::new(db.clone())
+        .replace_lines(
+            org.id.into_inner(),
+            budget_id,
+            org.owner.user_id.into_inner(),
+            lines,
+        )
+        .await;
+    assert!(matches!(result, Err(BudgetError::TooManyLines(n)) if n == MAX_REPLACE_LINES + 1));
+}
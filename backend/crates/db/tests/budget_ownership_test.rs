@@ -0,0 +1,213 @@
+//! Integration tests for budget owners and editors.
+
+use rust_decimal::Decimal;
+use sea_orm::DatabaseConnection;
+use uuid::Uuid;
+
+use zeltra_core::auth::UserRole as CoreUserRole;
+use zeltra_db::entities::sea_orm_active_enums::{AccountType, BudgetType, UserRole};
+use zeltra_db::repositories::budget::{
+    AssignBudgetInput, BudgetError, BudgetRepository, CreateBudgetInput, CreateBudgetLineInput,
+    UpdateBudgetInput,
+};
+use zeltra_test_support::{Org, OrgFixture, TestDb};
+
+const ACCOUNTS: &[(&str, AccountType)] = &[("5000", AccountType::Expense)];
+
+/// Org with two approvers (department heads) and an accountant.
+async fn org_with_departments(db: &DatabaseConnection) -> Org {
+    OrgFixture::new()
+        .with_fiscal_year_2025()
+        .with_accounts(ACCOUNTS)
+        .with_member(UserRole::Approver)
+        .with_member(UserRole::Approver)
+        .with_member(UserRole::Accountant)
+        .create(db)
+        .await
+}
+
+async fn create_budget(db: &DatabaseConnection, org: &Org, name: &str) -> Uuid {
+    BudgetRepository::new(db.clone())
+        .create_budget(CreateBudgetInput {
+            organization_id: org.id.into_inner(),
+            fiscal_year_id: org
+                .fiscal_year
+                .as_ref()
+                .expect("fixture has a fiscal year")
+                .id
+                .into_inner(),
+            name: name.to_string(),
+            description: None,
+            budget_type: BudgetType::Monthly,
+            currency: "USD".to_string(),
+            created_by: org.owner.user_id.into_inner(),
+        })
+        .await
+        .expect("Failed to create budget")
+        .id
+}
+
+fn line(org: &Org, amount: i64) -> CreateBudgetLineInput {
+    CreateBudgetLineInput {
+        account_id: org.account("5000").into_inner(),
+        fiscal_period_id: org.fiscal_year.as_ref().unwrap().periods[0].into_inner(),
+        amount: Decimal::new(amount, 0),
+        notes: None,
+        dimensions: vec![],
+    }
+}
+
+#[tokio::test]
+async fn test_owner_edits_their_budget_and_others_are_rejected() {
+    let test_db = TestDb::new().await;
+    let db = test_db.conn();
+    let org = org_with_departments(db).await;
+    let org_id = org.id.into_inner();
+    let head = org.members[0].user_id.into_inner();
+    let other_head = org.members[1].user_id.into_inner();
+    let accountant = org.member(&UserRole::Accountant).user_id.into_inner();
+    let budget_id = create_budget(db, &org, "Marketing 2025").await;
+    let repo = BudgetRepository::new(db.clone());
+
+    let assignment = repo
+        .assign_budget(
+            org_id,
+            budget_id,
+            AssignBudgetInput {
+                owner_user_id: Some(Some(head)),
+                ..Default::default()
+            },
+        )
+        .await
+        .expect("Failed to assign budget");
+    assert_eq!(assignment.budget.owner_user_id, Some(head));
+
+    // The owner edits the budget and its lines
+    let lines = repo
+        .create_budget_lines(org_id, budget_id, head, vec![line(&org, 500)])
+        .await
+        .expect("Owner adds lines");
+    repo.update_budget(
+        org_id,
+        budget_id,
+        head,
+        UpdateBudgetInput {
+            description: Some(Some("Campaigns".to_string())),
+            ..Default::default()
+        },
+    )
+    .await
+    .expect("Owner updates the budget");
+
+    // Another department head cannot touch it
+    let err = repo
+        .replace_lines(org_id, budget_id, other_head, vec![line(&org, 900)])
+        .await
+        .unwrap_err();
+    assert!(matches!(err, BudgetError::NotBudgetEditor));
+    let err = repo
+        .delete_budget_line(org_id, budget_id, lines[0].line.id, other_head)
+        .await
+        .unwrap_err();
+    assert!(matches!(err, BudgetError::NotBudgetEditor));
+    let err = repo
+        .update_budget(org_id, budget_id, other_head, UpdateBudgetInput::default())
+        .await
+        .unwrap_err();
+    assert!(matches!(err, BudgetError::NotBudgetEditor));
+
+    // Finance edits every budget
+    repo.replace_lines(org_id, budget_id, accountant, vec![line(&org, 700)])
+        .await
+        .expect("Accountant edits any budget");
+
+    // Listing an editor lets them in
+    repo.assign_budget(
+        org_id,
+        budget_id,
+        AssignBudgetInput {
+            editors: Some(vec![other_head, other_head]),
+            ..Default::default()
+        },
+    )
+    .await
+    .expect("Failed to add editor");
+    repo.replace_lines(org_id, budget_id, other_head, vec![line(&org, 800)])
+        .await
+        .expect("Editor edits the budget");
+}
+
+#[tokio::test]
+async fn test_listing_shows_ownership_and_assignees_must_be_members() {
+    let test_db = TestDb::new().await;
+    let db = test_db.conn();
+    let org = org_with_departments(db).await;
+    let org_id = org.id.into_inner();
+    let head = org.members[0].user_id.into_inner();
+    let other_head = org.members[1].user_id.into_inner();
+    let budget_id = create_budget(db, &org, "Sales 2025").await;
+    let repo = BudgetRepository::new(db.clone());
+
+    // The creator owns a new budget
+    let listed = repo.list_budgets(org_id).await.unwrap();
+    assert_eq!(
+        listed[0].budget.owner_user_id,
+        Some(org.owner.user_id.into_inner())
+    );
+    assert!(listed[0].editors.is_empty());
+
+    let err = repo
+        .assign_budget(
+            org_id,
+            budget_id,
+            AssignBudgetInput {
+                owner_user_id: Some(Some(head)),
+                editors: Some(vec![Uuid::new_v4()]),
+            },
+        )
+        .await
+        .unwrap_err();
+    assert!(matches!(err, BudgetError::NotAMember(_)));
+
+    let assignment = repo
+        .assign_budget(
+            org_id,
+            budget_id,
+            AssignBudgetInput {
+                owner_user_id: Some(Some(head)),
+                editors: Some(vec![other_head]),
+            },
+        )
+        .await
+        .expect("Failed to assign budget");
+    assert_eq!(assignment.editors, vec![other_head]);
+
+    let listed = repo.list_budgets(org_id).await.unwrap();
+    let budget = &listed[0];
+    assert_eq!(budget.budget.owner_user_id, Some(head));
+    assert_eq!(budget.editors, vec![other_head]);
+    assert!(budget.can_edit(head, CoreUserRole::Approver));
+    assert!(budget.can_edit(other_head, CoreUserRole::Approver));
+    assert!(budget.can_edit(Uuid::new_v4(), CoreUserRole::Accountant));
+    assert!(!budget.can_edit(Uuid::new_v4(), CoreUserRole::Viewer));
+
+    // Clearing the owner and editors leaves the budget to Finance
+    let cleared = repo
+        .assign_budget(
+            org_id,
+            budget_id,
+            AssignBudgetInput {
+                owner_user_id: Some(None),
+                editors: Some(vec![]),
+            },
+        )
+        .await
+        .unwrap();
+    assert_eq!(cleared.budget.owner_user_id, None);
+    assert!(cleared.editors.is_empty());
+    let err = repo
+        .create_budget_lines(org_id, budget_id, head, vec![line(&org, 100)])
+        .await
+        .unwrap_err();
+    assert!(matches!(err, BudgetError::NotBudgetEditor));
+}
@@ -0,0 +1,170 @@
+//! Integration tests for the budget vs actual trend across a fiscal year.
+
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use sea_orm::DatabaseConnection;
+use uuid::Uuid;
+
+use zeltra_core::budget::ConvertSide;
+use zeltra_db::entities::sea_orm_active_enums::{AccountType, BudgetType};
+use zeltra_db::repositories::budget::{BudgetRepository, CreateBudgetInput, CreateBudgetLineInput};
+use zeltra_test_support::{Line, Org, OrgFixture, TestDb, post_transaction};
+
+const ACCOUNTS: &[(&str, AccountType)] = &[
+    ("1000", AccountType::Asset),
+    ("5000", AccountType::Expense),
+    ("5100", AccountType::Expense),
+    ("5200", AccountType::Expense),
+];
+
+fn line(org: &Org, code: &str, month: u32, amount: i64) -> CreateBudgetLineInput {
+    let year = org.fiscal_year.as_ref().expect("fixture has a fiscal year");
+    CreateBudgetLineInput {
+        account_id: org.account(code).into_inner(),
+        fiscal_period_id: year.periods[month as usize - 1].into_inner(),
+        amount: Decimal::new(amount, 0),
+        notes: None,
+        dimensions: vec![],
+    }
+}
+
+/// Posts an expense paid from cash.
+async fn post_expense(
+    db: &DatabaseConnection,
+    org: &Org,
+    month: u32,
+    expense_account: &str,
+    amount: i64,
+) {
+    let amount = Decimal::new(amount, 0);
+    post_transaction(
+        db,
+        org,
+        NaiveDate::from_ymd_opt(2025, month, 15).unwrap(),
+        vec![
+            Line::debit(expense_account, amount),
+            Line::credit("1000", amount),
+        ],
+    )
+    .await;
+}
+
+/// Budgets 5000 and 5100 for the first quarter and posts actuals in
+/// January and February, plus an unbudgeted 5200 expense.
+async fn seeded(db: &DatabaseConnection) -> (Org, Uuid) {
+    let org = OrgFixture::new()
+        .with_fiscal_year_2025()
+        .with_accounts(ACCOUNTS)
+        .create(db)
+        .await;
+    let repo = BudgetRepository::new(db.clone());
+    let budget = repo
+        .create_budget(CreateBudgetInput {
+            organization_id: org.id.into_inner(),
+            fiscal_year_id: org
+                .fiscal_year
+                .as_ref()
+                .expect("fixture has a fiscal year")
+                .id
+                .into_inner(),
+            name: "Operating 2025".to_string(),
+            description: None,
+            budget_type: BudgetType::Monthly,
+            currency: "USD".to_string(),
+            created_by: org.owner.user_id.into_inner(),
+        })
+        .await
+        .expect("Failed to create budget");
+    repo.create_budget_lines(
+        org.id.into_inner(),
+        budget.id,
+        budget.created_by,
+        vec![
+            line(&org, "5000", 1, 1000),
+            line(&org, "5000", 2, 1000),
+            line(&org, "5000", 3, 500),
+            line(&org, "5100", 1, 200),
+        ],
+    )
+    .await
+    .expect("Failed to create budget lines");
+
+    post_expense(db, &org, 1, "5000", 800).await;
+    post_expense(db, &org, 1, "5100", 150).await;
+    post_expense(db, &org, 2, "5000", 1100).await;
+    post_expense(db, &org, 2, "5200", 999).await;
+
+    (org, budget.id)
+}
+
+#[tokio::test]
+async fn test_trend_lists_every_period_with_running_totals() {
+    let test_db = TestDb::new().await;
+    let db = test_db.conn();
+    let (org, budget_id) = seeded(db).await;
+
+    let trend = BudgetRepository::new(db.clone())
+        .get_budget_trend(
+            org.id.into_inner(),
+            budget_id,
+            None,
+            &[],
+            ConvertSide::default(),
+        )
+        .await
+        .expect("Failed to get trend");
+
+    assert_eq!(trend.currency, "USD");
+    assert_eq!(trend.periods.len(), 12);
+    let year = org.fiscal_year.as_ref().unwrap();
+    for (period, id) in trend.periods.iter().zip(&year.periods) {
+        assert_eq!(period.fiscal_period_id, id.into_inner());
+        assert!(period.warning.is_none());
+    }
+
+    // 5200 is not budgeted, so its spend is not an actual
+    let jan = &trend.periods[0].point;
+    assert_eq!(jan.budgeted, Decimal::from(1200));
+    assert_eq!(jan.actual, Decimal::from(950));
+
+    let feb = &trend.periods[1].point;
+    assert_eq!(feb.actual, Decimal::from(1100));
+    assert_eq!(feb.cumulative_budgeted, Decimal::from(2200));
+    assert_eq!(feb.cumulative_actual, Decimal::from(2050));
+    assert_eq!(feb.cumulative_variance, Decimal::from(150));
+
+    // Periods without lines or postings still appear, as zeros
+    let dec = &trend.periods[11].point;
+    assert_eq!(dec.budgeted, Decimal::ZERO);
+    assert_eq!(dec.actual, Decimal::ZERO);
+    assert_eq!(dec.cumulative_budgeted, Decimal::from(2700));
+    assert_eq!(dec.cumulative_actual, Decimal::from(2050));
+    assert_eq!(dec.cumulative_variance, Decimal::from(650));
+}
+
+#[tokio::test]
+async fn test_trend_filtered_by_account() {
+    let test_db = TestDb::new().await;
+    let db = test_db.conn();
+    let (org, budget_id) = seeded(db).await;
+
+    let trend = BudgetRepository::new(db.clone())
+        .get_budget_trend(
+            org.id.into_inner(),
+            budget_id,
+            Some(org.account("5100").into_inner()),
+            &[],
+            ConvertSide::default(),
+        )
+        .await
+        .expect("Failed to get trend");
+
+    assert_eq!(trend.periods.len(), 12);
+    assert_eq!(trend.periods[0].point.budgeted, Decimal::from(200));
+    assert_eq!(trend.periods[0].point.actual, Decimal::from(150));
+    assert_eq!(trend.periods[1].point.actual, Decimal::ZERO);
+    assert_eq!(
+        trend.periods[11].point.cumulative_variance,
+        Decimal::from(50)
+    );
+}
@@ -0,0 +1,247 @@
+//! Integration tests for the fiscal period closing checklist.
+
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use sea_orm::{ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, sea_query::Expr};
+use uuid::Uuid;
+
+use zeltra_core::fiscal::{CheckStatus, ClosingCheck};
+use zeltra_db::entities::{
+    chart_of_accounts, fiscal_periods, ledger_entries,
+    sea_orm_active_enums::{AccountType, RateSource, TransactionStatus, TransactionType},
+    transactions,
+};
+use zeltra_db::repositories::{
+    ClosingRepository, CreateExchangeRateInput, ExchangeRateRepository, FiscalRepository,
+    ReconciliationRepository, StartReconciliationInput,
+    transaction::{CreateLedgerEntryInput, CreateTransactionInput, TransactionRepository},
+};
+use zeltra_test_support::{Org, OrgFixture, TestDb};
+
+const ACCOUNTS: &[(&str, AccountType)] = &[
+    ("1000", AccountType::Asset),
+    ("1010", AccountType::Asset),
+    ("4000", AccountType::Revenue),
+];
+
+fn march(day: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(2025, 3, day).unwrap()
+}
+
+async fn setup() -> (TestDb, Org, fiscal_periods::Model) {
+    let test_db = TestDb::new().await;
+    let org = OrgFixture::new()
+        .with_fiscal_year_2025()
+        .with_accounts(ACCOUNTS)
+        .create(test_db.conn())
+        .await;
+    let period = FiscalRepository::new(test_db.conn().clone())
+        .find_period_for_date(org.id.into_inner(), march(1))
+        .await
+        .unwrap()
+        .unwrap();
+    (test_db, org, period)
+}
+
+/// Creates a draft sale of `amount` in `currency`, converted at 1:1.
+async fn create_sale(db: &DatabaseConnection, org: &Org, currency: &str, amount: i64) -> Uuid {
+    let amount = Decimal::from(amount);
+    let entry = |account: &str, debit: Decimal, credit: Decimal| CreateLedgerEntryInput {
+        account_id: org.account(account).into_inner(),
+        source_currency: currency.to_string(),
+        source_amount: amount,
+        exchange_rate: Decimal::ONE,
+        functional_currency: "USD".to_string(),
+        functional_amount: amount,
+        debit,
+        credit,
+        memo: None,
+        dimensions: vec![],
+        rate_override: None,
+        event_at: None,
+    };
+    TransactionRepository::new(db.clone())
+        .create_transaction(CreateTransactionInput {
+            organization_id: org.id.into_inner(),
+            transaction_type: TransactionType::Journal,
+            transaction_date: march(10),
+            description: "Sale".to_string(),
+            reference_number: None,
+            memo: None,
+            entries: vec![
+                entry("1000", amount, Decimal::ZERO),
+                entry("4000", Decimal::ZERO, amount),
+            ],
+            created_by: org.owner.user_id.into_inner(),
+        })
+        .await
+        .expect("Failed to create transaction")
+        .transaction
+        .id
+}
+
+fn check<'a>(checks: &'a [ClosingCheck], name: &str) -> &'a ClosingCheck {
+    checks.iter().find(|c| c.check == name).unwrap()
+}
+
+#[tokio::test]
+async fn test_clean_period_passes() {
+    let (test_db, _org, period) = setup().await;
+
+    let checklist = ClosingRepository::new(test_db.conn().clone())
+        .checklist(&period)
+        .await
+        .unwrap();
+
+    assert!(
+        checklist
+            .checks
+            .iter()
+            .all(|c| c.status == CheckStatus::Pass)
+    );
+    assert_eq!(checklist.transaction_counts["draft"], 0);
+    assert_eq!(checklist.transaction_counts.len(), 5);
+}
+
+#[tokio::test]
+async fn test_drafts_pending_and_unbalanced_are_reported() {
+    let (test_db, org, period) = setup().await;
+    let db = test_db.conn();
+    let draft = create_sale(db, &org, "USD", 100).await;
+    let unbalanced = create_sale(db, &org, "USD", 200).await;
+    let pending = create_sale(db, &org, "USD", 300).await;
+
+    ledger_entries::Entity::update_many()
+        .col_expr(
+            ledger_entries::Column::Debit,
+            Expr::value(Decimal::from(250)),
+        )
+        .col_expr(
+            ledger_entries::Column::FunctionalAmount,
+            Expr::value(Decimal::from(250)),
+        )
+        .filter(ledger_entries::Column::TransactionId.eq(unbalanced))
+        .filter(ledger_entries::Column::AccountId.eq(org.account("1000").into_inner()))
+        .exec(db)
+        .await
+        .unwrap();
+    transactions::Entity::update_many()
+        .col_expr(
+            transactions::Column::Status,
+            transactions::Column::Status.save_as(Expr::val(TransactionStatus::Pending)),
+        )
+        .filter(transactions::Column::Id.eq(pending))
+        .exec(db)
+        .await
+        .unwrap();
+
+    let repo = ClosingRepository::new(db.clone());
+    let checklist = repo.checklist(&period).await.unwrap();
+
+    assert_eq!(checklist.transaction_counts["draft"], 2);
+    assert_eq!(checklist.transaction_counts["pending"], 1);
+
+    let drafts = check(&checklist.checks, "unposted_drafts");
+    assert_eq!(drafts.status, CheckStatus::Warn);
+    assert_eq!(drafts.total, 2);
+    assert!(drafts.ids.contains(&draft) && drafts.ids.contains(&unbalanced));
+
+    let pending_check = check(&checklist.checks, "pending_transactions");
+    assert_eq!(pending_check.status, CheckStatus::Fail);
+    assert_eq!(pending_check.ids, vec![pending]);
+
+    let unbalanced_check = check(&checklist.checks, "unbalanced_drafts");
+    assert_eq!(unbalanced_check.status, CheckStatus::Fail);
+    assert_eq!(unbalanced_check.ids, vec![unbalanced]);
+
+    // Each check runs on its own as well
+    assert_eq!(
+        repo.check_unbalanced_drafts(period.id).await.unwrap(),
+        *unbalanced_check
+    );
+}
+
+#[tokio::test]
+async fn test_missing_exchange_rate_is_reported_until_rate_exists() {
+    let (test_db, org, period) = setup().await;
+    let db = test_db.conn();
+    let sale = create_sale(db, &org, "EUR", 100).await;
+    let repo = ClosingRepository::new(db.clone());
+
+    let missing = repo.check_missing_exchange_rates(&period).await.unwrap();
+    assert_eq!(missing.status, CheckStatus::Fail);
+    assert_eq!(missing.total, 2);
+    let entry_ids: Vec<Uuid> = ledger_entries::Entity::find()
+        .filter(ledger_entries::Column::TransactionId.eq(sale))
+        .all(db)
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|e| e.id)
+        .collect();
+    assert!(missing.ids.iter().all(|id| entry_ids.contains(id)));
+
+    ExchangeRateRepository::new(db.clone())
+        .create_or_update_rate(CreateExchangeRateInput {
+            organization_id: org.id.into_inner(),
+            from_currency: "EUR".to_string(),
+            to_currency: "USD".to_string(),
+            rate: Decimal::new(11, 1),
+            effective_date: march(1),
+            source: RateSource::Manual,
+            source_reference: None,
+            created_by: None,
+        })
+        .await
+        .unwrap();
+
+    let missing = repo.check_missing_exchange_rates(&period).await.unwrap();
+    assert_eq!(missing.status, CheckStatus::Pass);
+}
+
+#[tokio::test]
+async fn test_bank_account_needs_reconciliation_through_period_end() {
+    let (test_db, org, period) = setup().await;
+    let db = test_db.conn();
+    let bank = org.account("1010").into_inner();
+    chart_of_accounts::Entity::update_many()
+        .col_expr(chart_of_accounts::Column::IsBankAccount, Expr::value(true))
+        .filter(chart_of_accounts::Column::Id.eq(bank))
+        .exec(db)
+        .await
+        .unwrap();
+    let repo = ClosingRepository::new(db.clone());
+
+    let unreconciled = repo
+        .check_unreconciled_bank_accounts(&period)
+        .await
+        .unwrap();
+    assert_eq!(unreconciled.status, CheckStatus::Warn);
+    assert_eq!(unreconciled.ids, vec![bank]);
+
+    let reconciliations = ReconciliationRepository::new(db.clone());
+    let reconciliation = reconciliations
+        .start_reconciliation(StartReconciliationInput {
+            organization_id: org.id.into_inner(),
+            account_id: bank,
+            statement_date: period.end_date,
+            statement_ending_balance: Decimal::ZERO,
+            created_by: org.owner.user_id.into_inner(),
+        })
+        .await
+        .unwrap();
+    reconciliations
+        .complete_reconciliation(
+            org.id.into_inner(),
+            reconciliation.id,
+            org.owner.user_id.into_inner(),
+        )
+        .await
+        .unwrap();
+
+    let unreconciled = repo
+        .check_unreconciled_bank_accounts(&period)
+        .await
+        .unwrap();
+    assert_eq!(unreconciled.status, CheckStatus::Pass);
+}
@@ -0,0 +1,339 @@
+//! Integration tests for saved custom report definitions.
+
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use sea_orm::DatabaseConnection;
+use uuid::Uuid;
+
+use zeltra_core::reports::{
+    AggregateFunction, ReportAggregate, ReportDataset, ReportDefinition, ReportFilters, ReportValue,
+};
+use zeltra_db::entities::sea_orm_active_enums::{AccountType, BudgetType};
+use zeltra_db::repositories::budget::{BudgetRepository, CreateBudgetInput, CreateBudgetLineInput};
+use zeltra_db::repositories::dimension::{
+    CreateDimensionTypeInput, CreateDimensionValueInput, DimensionRepository,
+};
+use zeltra_db::repositories::report::ReportRepository;
+use zeltra_db::repositories::{ReportDefinitionError, ReportDefinitionRepository};
+use zeltra_test_support::{Line, Org, OrgFixture, TestDb, post_transaction};
+
+const ACCOUNTS: &[(&str, AccountType)] = &[
+    ("1000", AccountType::Asset),
+    ("6000", AccountType::Expense),
+    ("6100", AccountType::Expense),
+];
+
+fn date(month: u32, day: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(2025, month, day).unwrap()
+}
+
+fn text(value: &str) -> ReportValue {
+    ReportValue::Text(value.to_string())
+}
+
+fn strings(values: &[&str]) -> Vec<String> {
+    values.iter().map(ToString::to_string).collect()
+}
+
+async fn dimension_values(
+    db: &DatabaseConnection,
+    org: &Org,
+    code: &str,
+    values: &[&str],
+) -> Vec<Uuid> {
+    let repo = DimensionRepository::new(db.clone());
+    let type_id = repo
+        .create_dimension_type(CreateDimensionTypeInput {
+            organization_id: org.id.into_inner(),
+            code: code.to_string(),
+            name: code.to_string(),
+            description: None,
+            is_required: false,
+            is_active: true,
+            sort_order: 0,
+        })
+        .await
+        .expect("Failed to create dimension type")
+        .id;
+    let mut ids = Vec::new();
+    for value in values {
+        let created = repo
+            .create_dimension_value(CreateDimensionValueInput {
+                organization_id: org.id.into_inner(),
+                dimension_type_id: type_id,
+                code: (*value).to_string(),
+                name: (*value).to_string(),
+                description: None,
+                parent_id: None,
+                is_active: true,
+                effective_from: None,
+                effective_to: None,
+            })
+            .await
+            .expect("Failed to create dimension value");
+        ids.push(created.id);
+    }
+    ids
+}
+
+/// Posts an expense paid from cash, tagging the expense entry.
+async fn post_expense(
+    db: &DatabaseConnection,
+    org: &Org,
+    account: &str,
+    on: NaiveDate,
+    amount: Decimal,
+    dimensions: Vec<Uuid>,
+) {
+    post_transaction(
+        db,
+        org,
+        on,
+        vec![
+            Line::debit(account, amount).with_dimensions(dimensions),
+            Line::credit("1000", amount),
+        ],
+    )
+    .await;
+}
+
+#[tokio::test]
+#[allow(clippy::too_many_lines)]
+async fn test_definition_with_every_filter_runs() {
+    let test_db = TestDb::new().await;
+    let db = test_db.conn();
+    let org = OrgFixture::new()
+        .with_fiscal_year_2025()
+        .with_accounts(ACCOUNTS)
+        .create(db)
+        .await;
+    let org_id = org.id.into_inner();
+    let depts = dimension_values(db, &org, "DEPT", &["ENG", "SALES"]).await;
+    let (eng, sales) = (depts[0], depts[1]);
+    let alpha = dimension_values(db, &org, "PROJECT", &["ALPHA"]).await[0];
+
+    post_expense(db, &org, "6000", date(3, 10), dec!(100), vec![eng, alpha]).await;
+    post_expense(db, &org, "6000", date(3, 12), dec!(200), vec![sales, alpha]).await;
+    // No project tag
+    post_expense(db, &org, "6100", date(3, 10), dec!(300), vec![eng]).await;
+    // Outside the date range
+    post_expense(db, &org, "6000", date(5, 10), dec!(400), vec![eng, alpha]).await;
+
+    let filters = ReportFilters {
+        date_from: Some(date(3, 1)),
+        date_to: Some(date(3, 31)),
+        account_types: strings(&["expense"]),
+        dimension_value_ids: vec![eng, sales, alpha],
+    };
+    let definitions = ReportDefinitionRepository::new(db.clone());
+    let reports = ReportRepository::new(db.clone());
+
+    let detail = definitions
+        .create(
+            org_id,
+            &ReportDefinition {
+                name: "  March project spend  ".to_string(),
+                dataset: ReportDataset::LedgerEntries,
+                columns: strings(&["transaction_date", "account_code", "debit"]),
+                filters: filters.clone(),
+                group_by: vec![],
+                aggregate: None,
+            },
+            org.owner.user_id.into_inner(),
+        )
+        .await
+        .expect("Failed to create definition");
+    assert_eq!(detail.definition.name, "March project spend");
+    let plan = detail.definition.validate().unwrap();
+    let result = reports.run_custom_report(org_id, &plan).await.unwrap();
+    assert_eq!(
+        result.columns,
+        ["transaction_date", "account_code", "debit"]
+    );
+    assert_eq!(
+        result.rows,
+        [
+            vec![
+                ReportValue::Date(date(3, 10)),
+                text("6000"),
+                ReportValue::Number(dec!(100))
+            ],
+            vec![
+                ReportValue::Date(date(3, 12)),
+                text("6000"),
+                ReportValue::Number(dec!(200))
+            ],
+        ]
+    );
+    assert!(!result.truncated);
+
+    let grouped = ReportDefinition {
+        name: "March project spend by account".to_string(),
+        dataset: ReportDataset::LedgerEntries,
+        columns: strings(&["account_code"]),
+        filters: filters.clone(),
+        group_by: strings(&["account_code"]),
+        aggregate: Some(ReportAggregate {
+            function: AggregateFunction::Sum,
+            field: Some("amount".to_string()),
+        }),
+    };
+    let plan = grouped.validate().unwrap();
+    let result = reports.run_custom_report(org_id, &plan).await.unwrap();
+    assert_eq!(result.columns, ["account_code", "sum_amount"]);
+    assert_eq!(
+        result.rows,
+        [vec![text("6000"), ReportValue::Number(dec!(300))]]
+    );
+
+    // Budget lines are filtered on their own tags; actuals on the entries'
+    let budgets = BudgetRepository::new(db.clone());
+    let budget = budgets
+        .create_budget(CreateBudgetInput {
+            organization_id: org_id,
+            fiscal_year_id: org.fiscal_year.as_ref().unwrap().id.into_inner(),
+            name: "Operating 2025".to_string(),
+            description: None,
+            budget_type: BudgetType::Monthly,
+            currency: "USD".to_string(),
+            created_by: org.owner.user_id.into_inner(),
+        })
+        .await
+        .expect("Failed to create budget");
+    let march = org.fiscal_year.as_ref().unwrap().periods[2].into_inner();
+    let line = |code: &str, amount: Decimal, dimensions: Vec<Uuid>| CreateBudgetLineInput {
+        account_id: org.account(code).into_inner(),
+        fiscal_period_id: march,
+        amount,
+        notes: None,
+        dimensions,
+    };
+    budgets
+        .replace_lines(
+            org_id,
+            budget.id,
+            org.owner.user_id.into_inner(),
+            vec![
+                line("6000", dec!(500), vec![eng]),
+                line("6100", dec!(250), vec![]),
+            ],
+        )
+        .await
+        .expect("Failed to add budget lines");
+
+    let plan = ReportDefinition {
+        name: "ENG budget".to_string(),
+        dataset: ReportDataset::BudgetVsActual,
+        columns: strings(&[
+            "account_code",
+            "period_name",
+            "budgeted",
+            "actual",
+            "variance",
+        ]),
+        filters: ReportFilters {
+            dimension_value_ids: vec![eng],
+            ..filters
+        },
+        group_by: vec![],
+        aggregate: None,
+    }
+    .validate()
+    .unwrap();
+    let result = reports.run_custom_report(org_id, &plan).await.unwrap();
+    assert_eq!(
+        result.rows,
+        [vec![
+            text("6000"),
+            text("March 2025"),
+            ReportValue::Number(dec!(500)),
+            ReportValue::Number(dec!(100)),
+            ReportValue::Number(dec!(400)),
+        ]]
+    );
+}
+
+#[tokio::test]
+async fn test_unknown_field_is_rejected_before_saving() {
+    let test_db = TestDb::new().await;
+    let db = test_db.conn();
+    let org = OrgFixture::new().create(db).await;
+    let repo = ReportDefinitionRepository::new(db.clone());
+
+    let smuggled = ReportDefinition {
+        name: "Everything".to_string(),
+        dataset: ReportDataset::LedgerEntries,
+        columns: strings(&["account_code", "debit; DROP TABLE ledger_entries; --"]),
+        filters: ReportFilters::default(),
+        group_by: vec![],
+        aggregate: None,
+    };
+    let err = repo
+        .create(
+            org.id.into_inner(),
+            &smuggled,
+            org.owner.user_id.into_inner(),
+        )
+        .await
+        .unwrap_err();
+    let ReportDefinitionError::Invalid(fields) = err else {
+        panic!("expected a validation error, got {err:?}");
+    };
+    assert_eq!(
+        fields.iter().map(|f| f.field.as_str()).collect::<Vec<_>>(),
+        ["columns[1]"]
+    );
+    assert!(repo.list(org.id.into_inner()).await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_definition_names_are_unique_per_organization() {
+    let test_db = TestDb::new().await;
+    let db = test_db.conn();
+    let org = OrgFixture::new().create(db).await;
+    let other = OrgFixture::new().create(db).await;
+    let repo = ReportDefinitionRepository::new(db.clone());
+    let definition = ReportDefinition {
+        name: "Entries".to_string(),
+        dataset: ReportDataset::LedgerEntries,
+        columns: strings(&["account_code"]),
+        filters: ReportFilters::default(),
+        group_by: vec![],
+        aggregate: None,
+    };
+
+    let saved = repo
+        .create(
+            org.id.into_inner(),
+            &definition,
+            org.owner.user_id.into_inner(),
+        )
+        .await
+        .unwrap();
+    let err = repo
+        .create(
+            org.id.into_inner(),
+            &definition,
+            org.owner.user_id.into_inner(),
+        )
+        .await
+        .unwrap_err();
+    assert!(matches!(err, ReportDefinitionError::DuplicateName(_)));
+    repo.create(
+        other.id.into_inner(),
+        &definition,
+        other.owner.user_id.into_inner(),
+    )
+    .await
+    .expect("Names are only unique within an organization");
+
+    // Renaming to its own name is not a conflict
+    repo.update(org.id.into_inner(), saved.id, &definition)
+        .await
+        .unwrap();
+    let err = repo.get(other.id.into_inner(), saved.id).await.unwrap_err();
+    assert!(matches!(err, ReportDefinitionError::NotFound(_)));
+    repo.delete(org.id.into_inner(), saved.id).await.unwrap();
+    assert!(repo.list(org.id.into_inner()).await.unwrap().is_empty());
+}
@@ -0,0 +1,165 @@
+//! Integration tests for the data-quality report checks.
+
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use sea_orm::DatabaseConnection;
+use uuid::Uuid;
+
+use zeltra_core::reports::DataQualityCheck;
+use zeltra_db::entities::sea_orm_active_enums::{AccountType, TransactionType};
+use zeltra_db::repositories::account::{AccountRepository, UpdateAccountInput};
+use zeltra_db::repositories::dimension::{CreateDimensionTypeInput, DimensionRepository};
+use zeltra_db::repositories::report::ReportRepository;
+use zeltra_db::repositories::transaction::{
+    CreateLedgerEntryInput, CreateTransactionInput, TransactionRepository,
+};
+use zeltra_test_support::{Org, OrgFixture, TestDb};
+
+const ACCOUNTS: &[(&str, AccountType)] =
+    &[("1000", AccountType::Asset), ("4000", AccountType::Revenue)];
+
+fn entry(account: Uuid, debit: Decimal, credit: Decimal) -> CreateLedgerEntryInput {
+    CreateLedgerEntryInput {
+        account_id: account,
+        source_currency: "USD".to_string(),
+        source_amount: debit + credit,
+        exchange_rate: Decimal::ONE,
+        functional_currency: "USD".to_string(),
+        functional_amount: debit + credit,
+        debit,
+        credit,
+        memo: None,
+        dimensions: vec![],
+        rate_override: None,
+        event_at: None,
+    }
+}
+
+async fn org_with_accounts(db: &DatabaseConnection) -> Org {
+    OrgFixture::new()
+        .with_fiscal_year_2025()
+        .with_accounts(ACCOUNTS)
+        .create(db)
+        .await
+}
+
+async fn create_draft(db: &DatabaseConnection, org: &Org, debit: Decimal, credit: Decimal) -> Uuid {
+    TransactionRepository::new(db.clone())
+        .create_transaction(CreateTransactionInput {
+            organization_id: org.id.into_inner(),
+            transaction_type: TransactionType::Journal,
+            transaction_date: NaiveDate::from_ymd_opt(2025, 3, 10).unwrap(),
+            description: "Draft".to_string(),
+            reference_number: None,
+            memo: None,
+            entries: vec![
+                entry(org.account("1000").into_inner(), debit, Decimal::ZERO),
+                entry(org.account("4000").into_inner(), Decimal::ZERO, credit),
+            ],
+            created_by: org.owner.user_id.into_inner(),
+        })
+        .await
+        .expect("Failed to create transaction")
+        .transaction
+        .id
+}
+
+#[tokio::test]
+async fn test_clean_ledger_has_no_findings() {
+    let test_db = TestDb::new().await;
+    let db = test_db.conn();
+    let org = org_with_accounts(db).await;
+    let amount = Decimal::new(100, 0);
+    create_draft(db, &org, amount, amount).await;
+
+    let findings = ReportRepository::new(db.clone())
+        .query_data_quality(org.id.into_inner())
+        .await
+        .expect("Failed to run checks");
+
+    assert!(findings.is_empty(), "unexpected findings: {findings:?}");
+}
+
+#[tokio::test]
+async fn test_unbalanced_draft_reported() {
+    let test_db = TestDb::new().await;
+    let db = test_db.conn();
+    let org = org_with_accounts(db).await;
+    let id = create_draft(db, &org, Decimal::new(100, 0), Decimal::new(90, 0)).await;
+
+    let findings = ReportRepository::new(db.clone())
+        .query_data_quality(org.id.into_inner())
+        .await
+        .expect("Failed to run checks");
+
+    assert_eq!(findings.len(), 1);
+    assert_eq!(findings[0].check, DataQualityCheck::UnbalancedTransaction);
+    assert_eq!(findings[0].transaction_id, Some(id));
+}
+
+#[tokio::test]
+async fn test_missing_required_dimension_reported() {
+    let test_db = TestDb::new().await;
+    let db = test_db.conn();
+    let org = org_with_accounts(db).await;
+    let amount = Decimal::new(100, 0);
+    let id = create_draft(db, &org, amount, amount).await;
+
+    DimensionRepository::new(db.clone())
+        .create_dimension_type(CreateDimensionTypeInput {
+            organization_id: org.id.into_inner(),
+            code: "DEPT".to_string(),
+            name: "Department".to_string(),
+            description: None,
+            is_required: true,
+            is_active: true,
+            sort_order: 0,
+        })
+        .await
+        .expect("Failed to create dimension type");
+
+    let findings = ReportRepository::new(db.clone())
+        .query_data_quality(org.id.into_inner())
+        .await
+        .expect("Failed to run checks");
+
+    assert_eq!(findings.len(), 1);
+    assert_eq!(
+        findings[0].check,
+        DataQualityCheck::MissingRequiredDimension
+    );
+    assert_eq!(findings[0].transaction_id, Some(id));
+    assert!(findings[0].details.contains("DEPT"));
+}
+
+#[tokio::test]
+async fn test_entries_on_restricted_account_reported() {
+    let test_db = TestDb::new().await;
+    let db = test_db.conn();
+    let org = org_with_accounts(db).await;
+    let amount = Decimal::new(100, 0);
+    create_draft(db, &org, amount, amount).await;
+
+    AccountRepository::new(db.clone())
+        .update_account(
+            org.account("1000"),
+            UpdateAccountInput {
+                allow_direct_posting: Some(false),
+                ..Default::default()
+            },
+        )
+        .await
+        .expect("Failed to update account");
+
+    let findings = ReportRepository::new(db.clone())
+        .query_data_quality(org.id.into_inner())
+        .await
+        .expect("Failed to run checks");
+
+    assert_eq!(findings.len(), 1);
+    assert_eq!(findings[0].check, DataQualityCheck::DirectPostingNotAllowed);
+    assert_eq!(
+        findings[0].account_id,
+        Some(org.account("1000").into_inner())
+    );
+}
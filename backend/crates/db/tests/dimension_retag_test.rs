@@ -0,0 +1,350 @@
+//! Integration tests for moving ledger entries between dimension values.
+//!
+//! Re-tagging only changes which value an entry is tagged with; amounts,
+//! balances and snapshots must come out the same.
+
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use sea_orm::{ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, QueryOrder};
+use uuid::Uuid;
+
+use zeltra_db::entities::{
+    account_period_balances, dimension_retags, entry_dimensions, ledger_entries,
+    sea_orm_active_enums::{AccountType, FiscalPeriodStatus},
+};
+use zeltra_db::repositories::dimension::{
+    CreateDimensionTypeInput, CreateDimensionValueInput, DimensionError, DimensionRepository,
+    RetagEntriesInput,
+};
+use zeltra_db::repositories::fiscal::FiscalRepository;
+use zeltra_db::repositories::report::ReportRepository;
+use zeltra_shared::types::TransactionId;
+use zeltra_test_support::{Line, Org, OrgFixture, TestDb, post_transaction};
+
+fn date(month: u32, day: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(2025, month, day).unwrap()
+}
+
+async fn create_org(db: &DatabaseConnection) -> Org {
+    OrgFixture::new()
+        .with_fiscal_year_2025()
+        .with_accounts(&[
+            ("1000", AccountType::Asset),
+            ("6000", AccountType::Expense),
+            ("6100", AccountType::Expense),
+        ])
+        .create(db)
+        .await
+}
+
+async fn create_type(repo: &DimensionRepository, org: &Org, code: &str) -> Uuid {
+    repo.create_dimension_type(CreateDimensionTypeInput {
+        organization_id: org.id.into_inner(),
+        code: code.to_string(),
+        name: code.to_string(),
+        description: None,
+        is_required: false,
+        is_active: true,
+        sort_order: 0,
+    })
+    .await
+    .expect("Failed to create dimension type")
+    .id
+}
+
+async fn create_value(repo: &DimensionRepository, org: &Org, type_id: Uuid, code: &str) -> Uuid {
+    repo.create_dimension_value(CreateDimensionValueInput {
+        organization_id: org.id.into_inner(),
+        dimension_type_id: type_id,
+        code: code.to_string(),
+        name: code.to_string(),
+        description: None,
+        parent_id: None,
+        is_active: true,
+        effective_from: None,
+        effective_to: None,
+    })
+    .await
+    .expect("Failed to create dimension value")
+    .id
+}
+
+/// Posts an expense on `account` paid from cash, with the expense line
+/// tagged with `dimensions`.
+async fn post_expense(
+    db: &DatabaseConnection,
+    org: &Org,
+    on: NaiveDate,
+    account: &str,
+    amount: i64,
+    dimensions: Vec<Uuid>,
+) -> TransactionId {
+    let amount = Decimal::new(amount, 2);
+    post_transaction(
+        db,
+        org,
+        on,
+        vec![
+            Line::debit(account, amount).with_dimensions(dimensions),
+            Line::credit("1000", amount),
+        ],
+    )
+    .await
+}
+
+fn retag(org: &Org, from: Uuid, to: Uuid) -> RetagEntriesInput {
+    RetagEntriesInput {
+        from_value_id: from,
+        to_value_id: to,
+        date_from: None,
+        date_to: None,
+        account_ids: vec![],
+        reason: Some("Merged departments".to_string()),
+        retagged_by: org.owner.user_id.into_inner(),
+        dry_run: false,
+    }
+}
+
+/// Every ledger entry of the organization as stored.
+async fn entries(db: &DatabaseConnection, org: &Org) -> Vec<ledger_entries::Model> {
+    ledger_entries::Entity::find()
+        .filter(ledger_entries::Column::AccountId.is_in([
+            org.account("1000").into_inner(),
+            org.account("6000").into_inner(),
+            org.account("6100").into_inner(),
+        ]))
+        .order_by_asc(ledger_entries::Column::Id)
+        .all(db)
+        .await
+        .unwrap()
+}
+
+async fn snapshots(db: &DatabaseConnection, org: &Org) -> Vec<account_period_balances::Model> {
+    account_period_balances::Entity::find()
+        .filter(account_period_balances::Column::OrganizationId.eq(org.id.into_inner()))
+        .order_by_asc(account_period_balances::Column::Id)
+        .all(db)
+        .await
+        .unwrap()
+}
+
+/// Code and balance of each trial balance row, optionally for one value.
+async fn trial_balance(
+    db: &DatabaseConnection,
+    org: &Org,
+    dimensions: &[Uuid],
+) -> Vec<(String, Decimal)> {
+    ReportRepository::new(db.clone())
+        .query_trial_balance(org.id.into_inner(), date(12, 31), dimensions)
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|row| (row.code, row.balance))
+        .collect()
+}
+
+async fn tagged_with(db: &DatabaseConnection, value: Uuid) -> usize {
+    entry_dimensions::Entity::find()
+        .filter(entry_dimensions::Column::DimensionValueId.eq(value))
+        .all(db)
+        .await
+        .unwrap()
+        .len()
+}
+
+#[tokio::test]
+async fn test_retag_moves_tags_without_touching_amounts() {
+    let test_db = TestDb::new().await;
+    let db = test_db.conn();
+    let org = create_org(db).await;
+    let dimensions = DimensionRepository::new(db.clone());
+    let dept = create_type(&dimensions, &org, "DEPT").await;
+    let ops = create_value(&dimensions, &org, dept, "OPS").await;
+    let fin = create_value(&dimensions, &org, dept, "FIN").await;
+
+    post_expense(db, &org, date(1, 15), "6000", 10_000, vec![ops]).await;
+    let march = post_expense(db, &org, date(3, 10), "6000", 20_000, vec![ops]).await;
+    post_expense(db, &org, date(3, 20), "6100", 30_000, vec![ops]).await;
+    post_expense(db, &org, date(5, 5), "6000", 40_000, vec![fin]).await;
+    FiscalRepository::new(db.clone())
+        .update_period_status(
+            org.fiscal_year.as_ref().unwrap().periods[0].into_inner(),
+            FiscalPeriodStatus::Closed,
+            None,
+        )
+        .await
+        .expect("Failed to close January");
+
+    let entries_before = entries(db, &org).await;
+    let snapshots_before = snapshots(db, &org).await;
+    let totals_before = trial_balance(db, &org, &[]).await;
+    assert!(!snapshots_before.is_empty());
+    let reports = ReportRepository::new(db.clone());
+    let version_before = reports.data_version(org.id.into_inner()).await.unwrap();
+
+    // A dry run counts and samples but changes nothing
+    let mut input = retag(&org, ops, fin);
+    input.date_from = Some(date(2, 1));
+    input.account_ids = vec![org.account("6000").into_inner()];
+    input.dry_run = true;
+    let preview = dimensions
+        .retag_entries(org.id.into_inner(), input.clone())
+        .await
+        .unwrap();
+    assert_eq!(preview.affected_entries, 1);
+    assert!(preview.retag.is_none());
+    assert_eq!(preview.sample.len(), 1);
+    assert_eq!(preview.sample[0].transaction_id, march.into_inner());
+    assert_eq!(preview.sample[0].entry_count, 1);
+    assert_eq!(tagged_with(db, ops).await, 3);
+    assert_eq!(
+        reports.data_version(org.id.into_inner()).await.unwrap(),
+        version_before
+    );
+
+    // Applying the same filters moves only that entry
+    input.dry_run = false;
+    let applied = dimensions
+        .retag_entries(org.id.into_inner(), input)
+        .await
+        .unwrap();
+    assert_eq!(applied.affected_entries, 1);
+    let record = applied.retag.expect("re-tag is recorded");
+    assert_eq!(record.from_value_id, ops);
+    assert_eq!(record.to_value_id, fin);
+    assert_eq!(record.date_from, Some(date(2, 1)));
+    assert_eq!(record.account_ids, [org.account("6000").into_inner()]);
+    assert_eq!(record.affected_entries, 1);
+    assert_eq!(tagged_with(db, ops).await, 2);
+    assert_eq!(tagged_with(db, fin).await, 2);
+    // Dimensional reports change, so cached ones must not be served
+    assert_ne!(
+        reports.data_version(org.id.into_inner()).await.unwrap(),
+        version_before
+    );
+
+    // The rest, including the closed period, moves with no filters
+    let applied = dimensions
+        .retag_entries(org.id.into_inner(), retag(&org, ops, fin))
+        .await
+        .unwrap();
+    assert_eq!(applied.affected_entries, 2);
+    assert_eq!(tagged_with(db, ops).await, 0);
+    assert_eq!(tagged_with(db, fin).await, 4);
+    assert!(
+        trial_balance(db, &org, &[ops])
+            .await
+            .iter()
+            .all(|(_, b)| b.is_zero())
+    );
+    assert_eq!(
+        trial_balance(db, &org, &[fin]).await,
+        trial_balance(db, &org, &[])
+            .await
+            .into_iter()
+            .map(|(code, balance)| {
+                // Cash lines were never tagged
+                let balance = if code == "1000" {
+                    Decimal::ZERO
+                } else {
+                    balance
+                };
+                (code, balance)
+            })
+            .collect::<Vec<_>>()
+    );
+
+    // Amounts, running balances and snapshots are untouched
+    assert_eq!(entries(db, &org).await, entries_before);
+    assert_eq!(snapshots(db, &org).await, snapshots_before);
+    assert_eq!(trial_balance(db, &org, &[]).await, totals_before);
+
+    let history = dimension_retags::Entity::find()
+        .filter(dimension_retags::Column::OrganizationId.eq(org.id.into_inner()))
+        .all(db)
+        .await
+        .unwrap();
+    assert_eq!(history.len(), 2);
+}
+
+#[tokio::test]
+async fn test_retag_merges_entries_already_tagged_with_the_target() {
+    let test_db = TestDb::new().await;
+    let db = test_db.conn();
+    let org = create_org(db).await;
+    let dimensions = DimensionRepository::new(db.clone());
+    let dept = create_type(&dimensions, &org, "DEPT").await;
+    let ops = create_value(&dimensions, &org, dept, "OPS").await;
+    let fin = create_value(&dimensions, &org, dept, "FIN").await;
+
+    // An entry can carry both values of a type; it keeps a single tag
+    post_expense(db, &org, date(2, 1), "6000", 5_000, vec![ops, fin]).await;
+
+    let applied = dimensions
+        .retag_entries(org.id.into_inner(), retag(&org, ops, fin))
+        .await
+        .unwrap();
+    assert_eq!(applied.affected_entries, 1);
+    assert_eq!(tagged_with(db, ops).await, 0);
+    assert_eq!(tagged_with(db, fin).await, 1);
+}
+
+#[tokio::test]
+async fn test_retag_rejects_invalid_requests() {
+    let test_db = TestDb::new().await;
+    let db = test_db.conn();
+    let org = create_org(db).await;
+    let other = create_org(db).await;
+    let dimensions = DimensionRepository::new(db.clone());
+    let dept = create_type(&dimensions, &org, "DEPT").await;
+    let project = create_type(&dimensions, &org, "PROJECT").await;
+    let ops = create_value(&dimensions, &org, dept, "OPS").await;
+    let apollo = create_value(&dimensions, &org, project, "APOLLO").await;
+    let other_dept = create_type(&dimensions, &other, "DEPT").await;
+    let other_ops = create_value(&dimensions, &other, other_dept, "OPS").await;
+    post_expense(db, &org, date(2, 1), "6000", 5_000, vec![ops]).await;
+    let org_id = org.id.into_inner();
+
+    let err = dimensions
+        .retag_entries(org_id, retag(&org, ops, apollo))
+        .await
+        .unwrap_err();
+    assert!(matches!(err, DimensionError::RetagTypeMismatch), "{err:?}");
+    let err = dimensions
+        .retag_entries(org_id, retag(&org, ops, ops))
+        .await
+        .unwrap_err();
+    assert!(matches!(err, DimensionError::RetagSameValue), "{err:?}");
+    let err = dimensions
+        .retag_entries(org_id, retag(&org, ops, other_ops))
+        .await
+        .unwrap_err();
+    assert!(matches!(err, DimensionError::ValueNotFound(id) if id == other_ops));
+
+    let mut input = retag(
+        &org,
+        ops,
+        create_value(&dimensions, &org, dept, "FIN").await,
+    );
+    input.date_from = Some(date(3, 1));
+    input.date_to = Some(date(2, 1));
+    let err = dimensions
+        .retag_entries(org_id, input.clone())
+        .await
+        .unwrap_err();
+    assert!(matches!(err, DimensionError::InvalidDateRange), "{err:?}");
+    input.date_to = None;
+    input.account_ids = vec![other.account("6000").into_inner()];
+    let err = dimensions.retag_entries(org_id, input).await.unwrap_err();
+    assert!(matches!(err, DimensionError::AccountNotFound(_)), "{err:?}");
+
+    // Nothing moved and nothing was recorded
+    assert_eq!(tagged_with(db, ops).await, 1);
+    assert!(
+        dimension_retags::Entity::find()
+            .all(db)
+            .await
+            .unwrap()
+            .is_empty()
+    );
+}
@@ -0,0 +1,246 @@
+//! Integration tests for including and excluding dimension values in the
+//! dimensional report.
+
+use std::collections::HashMap;
+
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use sea_orm::DatabaseConnection;
+use uuid::Uuid;
+
+use zeltra_db::entities::sea_orm_active_enums::AccountType;
+use zeltra_db::repositories::dimension::{
+    CreateDimensionTypeInput, CreateDimensionValueInput, DimensionRepository,
+};
+use zeltra_db::repositories::report::{DimensionValueFilter, ReportError, ReportRepository};
+use zeltra_test_support::{Line, Org, OrgFixture, TestDb, post_transaction};
+
+const ACCOUNTS: &[(&str, AccountType)] =
+    &[("1000", AccountType::Asset), ("5000", AccountType::Expense)];
+
+struct Seeded {
+    org: Org,
+    alpha: Uuid,
+    beta: Uuid,
+    contractors: Uuid,
+}
+
+async fn dimension_type(db: &DatabaseConnection, org: &Org, code: &str, name: &str) -> Uuid {
+    DimensionRepository::new(db.clone())
+        .create_dimension_type(CreateDimensionTypeInput {
+            organization_id: org.id.into_inner(),
+            code: code.to_string(),
+            name: name.to_string(),
+            description: None,
+            is_required: false,
+            is_active: true,
+            sort_order: 0,
+        })
+        .await
+        .expect("Failed to create dimension type")
+        .id
+}
+
+async fn dimension_value(db: &DatabaseConnection, org: &Org, type_id: Uuid, code: &str) -> Uuid {
+    DimensionRepository::new(db.clone())
+        .create_dimension_value(CreateDimensionValueInput {
+            organization_id: org.id.into_inner(),
+            dimension_type_id: type_id,
+            code: code.to_string(),
+            name: code.to_string(),
+            description: None,
+            parent_id: None,
+            is_active: true,
+            effective_from: None,
+            effective_to: None,
+        })
+        .await
+        .expect("Failed to create dimension value")
+        .id
+}
+
+/// Posts an expense paid from cash, tagging the expense entry.
+async fn post_expense(db: &DatabaseConnection, org: &Org, amount: i64, dimensions: Vec<Uuid>) {
+    let amount = Decimal::new(amount, 0);
+    post_transaction(
+        db,
+        org,
+        NaiveDate::from_ymd_opt(2025, 3, 10).unwrap(),
+        vec![
+            Line::debit("5000", amount).with_dimensions(dimensions),
+            Line::credit("1000", amount),
+        ],
+    )
+    .await;
+}
+
+/// Posts expenses for projects Alpha, Beta and Gamma, one without a
+/// project, and one contractor cost on Alpha.
+async fn seeded(db: &DatabaseConnection) -> Seeded {
+    let org = OrgFixture::new()
+        .with_fiscal_year_2025()
+        .with_accounts(ACCOUNTS)
+        .create(db)
+        .await;
+    let project = dimension_type(db, &org, "PROJECT", "Project").await;
+    let cost_type = dimension_type(db, &org, "COST_TYPE", "Cost Type").await;
+    let alpha = dimension_value(db, &org, project, "ALPHA").await;
+    let beta = dimension_value(db, &org, project, "BETA").await;
+    let gamma = dimension_value(db, &org, project, "GAMMA").await;
+    let contractors = dimension_value(db, &org, cost_type, "CONTRACTORS").await;
+
+    post_expense(db, &org, 100, vec![alpha]).await;
+    post_expense(db, &org, 200, vec![beta]).await;
+    post_expense(db, &org, 400, vec![gamma]).await;
+    post_expense(db, &org, 800, vec![]).await;
+    post_expense(db, &org, 1600, vec![alpha, contractors]).await;
+
+    Seeded {
+        org,
+        alpha,
+        beta,
+        contractors,
+    }
+}
+
+/// Runs the expense report by project, returning balances keyed by project
+/// code (empty for entries without a project) and the grand total.
+async fn by_project(
+    db: &DatabaseConnection,
+    org: &Org,
+    filter: &DimensionValueFilter,
+) -> Result<(HashMap<String, Decimal>, Decimal), ReportError> {
+    let (rows, grand_total) = ReportRepository::new(db.clone())
+        .query_dimensional_report(
+            org.id.into_inner(),
+            NaiveDate::from_ymd_opt(2025, 1, 1).unwrap(),
+            NaiveDate::from_ymd_opt(2025, 12, 31).unwrap(),
+            &["Project".to_string()],
+            Some(AccountType::Expense),
+            filter,
+        )
+        .await?;
+    let balances = rows
+        .into_iter()
+        .map(|row| {
+            let code = row
+                .dimensions
+                .first()
+                .map(|d| d.code.clone())
+                .unwrap_or_default();
+            (code, row.balance)
+        })
+        .collect();
+    Ok((balances, grand_total))
+}
+
+#[tokio::test]
+async fn test_include_values_of_one_type_are_alternatives() {
+    let test_db = TestDb::new().await;
+    let db = test_db.conn();
+    let seeded = seeded(db).await;
+
+    let (balances, total) = by_project(
+        db,
+        &seeded.org,
+        &DimensionValueFilter {
+            include: vec![seeded.alpha, seeded.beta],
+            ..Default::default()
+        },
+    )
+    .await
+    .expect("Failed to run report");
+
+    assert_eq!(balances.len(), 2);
+    assert_eq!(balances["ALPHA"], Decimal::from(1700));
+    assert_eq!(balances["BETA"], Decimal::from(200));
+    assert_eq!(total, Decimal::from(1900));
+
+    // Untagged entries join only when asked for
+    let (balances, total) = by_project(
+        db,
+        &seeded.org,
+        &DimensionValueFilter {
+            include: vec![seeded.alpha, seeded.beta],
+            include_untagged: true,
+            ..Default::default()
+        },
+    )
+    .await
+    .expect("Failed to run report");
+
+    assert_eq!(balances[""], Decimal::from(800));
+    assert!(!balances.contains_key("GAMMA"));
+    assert_eq!(total, Decimal::from(2700));
+}
+
+#[tokio::test]
+async fn test_exclude_keeps_untagged_entries() {
+    let test_db = TestDb::new().await;
+    let db = test_db.conn();
+    let seeded = seeded(db).await;
+
+    let (balances, total) = by_project(
+        db,
+        &seeded.org,
+        &DimensionValueFilter {
+            exclude: vec![seeded.contractors],
+            ..Default::default()
+        },
+    )
+    .await
+    .expect("Failed to run report");
+
+    assert_eq!(balances["ALPHA"], Decimal::from(100));
+    assert_eq!(balances["BETA"], Decimal::from(200));
+    assert_eq!(balances["GAMMA"], Decimal::from(400));
+    assert_eq!(balances[""], Decimal::from(800));
+    assert_eq!(total, Decimal::from(1500));
+}
+
+#[tokio::test]
+async fn test_include_and_exclude_combined() {
+    let test_db = TestDb::new().await;
+    let db = test_db.conn();
+    let seeded = seeded(db).await;
+
+    let (balances, total) = by_project(
+        db,
+        &seeded.org,
+        &DimensionValueFilter {
+            include: vec![seeded.alpha, seeded.beta],
+            exclude: vec![seeded.contractors],
+            ..Default::default()
+        },
+    )
+    .await
+    .expect("Failed to run report");
+
+    assert_eq!(balances.len(), 2);
+    assert_eq!(balances["ALPHA"], Decimal::from(100));
+    assert_eq!(balances["BETA"], Decimal::from(200));
+    assert_eq!(total, Decimal::from(300));
+}
+
+#[tokio::test]
+async fn test_unknown_value_rejected() {
+    let test_db = TestDb::new().await;
+    let db = test_db.conn();
+    let seeded = seeded(db).await;
+    let unknown = Uuid::new_v4();
+
+    let result = by_project(
+        db,
+        &seeded.org,
+        &DimensionValueFilter {
+            exclude: vec![unknown],
+            ..Default::default()
+        },
+    )
+    .await;
+
+    assert!(matches!(
+        result,
+        Err(ReportError::DimensionValueNotFound(id)) if id == unknown
+    ));
+}
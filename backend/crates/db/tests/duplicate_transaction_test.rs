@@ -0,0 +1,170 @@
+//! Integration tests for duplicate transaction detection.
+
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use sea_orm::{ColumnTrait, EntityTrait, QueryFilter, sea_query::Expr};
+
+use zeltra_db::entities::{
+    sea_orm_active_enums::{AccountType, TransactionType},
+    transactions,
+};
+use zeltra_db::repositories::transaction::{
+    CreateLedgerEntryInput, CreateTransactionInput, TransactionRepository, transaction_fingerprint,
+};
+use zeltra_test_support::{Org, OrgFixture, TestDb};
+
+const ACCOUNTS: &[(&str, AccountType)] =
+    &[("1000", AccountType::Asset), ("4000", AccountType::Revenue)];
+
+fn date(day: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(2025, 3, day).unwrap()
+}
+
+/// Entries for a cash sale of `cents`.
+fn sale(org: &Org, cents: i64) -> Vec<CreateLedgerEntryInput> {
+    let amount = Decimal::new(cents, 2);
+    let entry = |account: &str, debit: Decimal, credit: Decimal| CreateLedgerEntryInput {
+        account_id: org.account(account).into_inner(),
+        source_currency: "USD".to_string(),
+        source_amount: amount,
+        exchange_rate: Decimal::ONE,
+        functional_currency: "USD".to_string(),
+        functional_amount: amount,
+        debit,
+        credit,
+        memo: None,
+        dimensions: vec![],
+        rate_override: None,
+        event_at: None,
+    };
+    vec![
+        entry("1000", amount, Decimal::ZERO),
+        entry("4000", Decimal::ZERO, amount),
+    ]
+}
+
+async fn create_sale(repo: &TransactionRepository, org: &Org, day: u32, cents: i64) -> uuid::Uuid {
+    repo.create_transaction(CreateTransactionInput {
+        organization_id: org.id.into_inner(),
+        transaction_type: TransactionType::Journal,
+        transaction_date: date(day),
+        description: "Cash sale".to_string(),
+        reference_number: None,
+        memo: None,
+        entries: sale(org, cents),
+        created_by: org.owner.user_id.into_inner(),
+    })
+    .await
+    .expect("Failed to create transaction")
+    .transaction
+    .id
+}
+
+#[tokio::test]
+async fn test_exact_duplicate_is_found() {
+    let test_db = TestDb::new().await;
+    let db = test_db.conn();
+    let org = OrgFixture::new()
+        .with_fiscal_year_2025()
+        .with_accounts(ACCOUNTS)
+        .create(db)
+        .await;
+    let repo = TransactionRepository::new(db.clone());
+    let existing = create_sale(&repo, &org, 10, 25_000).await;
+
+    let duplicates = repo
+        .find_possible_duplicates(org.id, date(10), &sale(&org, 25_000), 0)
+        .await
+        .unwrap();
+    assert_eq!(duplicates, vec![existing]);
+
+    // Entry order and amount scale do not matter
+    let mut reordered = sale(&org, 25_000);
+    reordered.reverse();
+    for entry in &mut reordered {
+        entry.debit = entry.debit.round_dp(4);
+        entry.credit = entry.credit.round_dp(4);
+    }
+    let duplicates = repo
+        .find_possible_duplicates(org.id, date(10), &reordered, 0)
+        .await
+        .unwrap();
+    assert_eq!(duplicates, vec![existing]);
+}
+
+#[tokio::test]
+async fn test_near_misses_are_not_flagged() {
+    let test_db = TestDb::new().await;
+    let db = test_db.conn();
+    let org = OrgFixture::new()
+        .with_fiscal_year_2025()
+        .with_accounts(ACCOUNTS)
+        .create(db)
+        .await;
+    let repo = TransactionRepository::new(db.clone());
+    let existing = create_sale(&repo, &org, 10, 25_000).await;
+
+    // One cent more
+    let duplicates = repo
+        .find_possible_duplicates(org.id, date(10), &sale(&org, 25_001), 0)
+        .await
+        .unwrap();
+    assert!(duplicates.is_empty());
+
+    // The next day, outside the default same-date window
+    let duplicates = repo
+        .find_possible_duplicates(org.id, date(11), &sale(&org, 25_000), 0)
+        .await
+        .unwrap();
+    assert!(duplicates.is_empty());
+
+    // ...but inside a one-day window
+    let duplicates = repo
+        .find_possible_duplicates(org.id, date(11), &sale(&org, 25_000), 1)
+        .await
+        .unwrap();
+    assert_eq!(duplicates, vec![existing]);
+}
+
+#[tokio::test]
+async fn test_missing_fingerprint_is_computed_on_read() {
+    let test_db = TestDb::new().await;
+    let db = test_db.conn();
+    let org = OrgFixture::new()
+        .with_fiscal_year_2025()
+        .with_accounts(ACCOUNTS)
+        .create(db)
+        .await;
+    let repo = TransactionRepository::new(db.clone());
+    let existing = create_sale(&repo, &org, 10, 25_000).await;
+
+    // As if created before fingerprints existed
+    transactions::Entity::update_many()
+        .col_expr(
+            transactions::Column::Fingerprint,
+            Expr::value(Option::<String>::None),
+        )
+        .filter(transactions::Column::Id.eq(existing))
+        .exec(db)
+        .await
+        .unwrap();
+
+    let duplicates = repo
+        .find_possible_duplicates(org.id, date(10), &sale(&org, 25_000), 0)
+        .await
+        .unwrap();
+    assert_eq!(duplicates, vec![existing]);
+
+    let stored = transactions::Entity::find_by_id(existing)
+        .one(db)
+        .await
+        .unwrap()
+        .unwrap();
+    let expected = transaction_fingerprint(
+        date(10),
+        sale(&org, 25_000)
+            .iter()
+            .map(|e| (e.account_id, e.debit, e.credit)),
+    );
+    assert_eq!(stored.fingerprint, Some(expected));
+}
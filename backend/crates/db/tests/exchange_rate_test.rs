@@ -7,6 +7,7 @@ use chrono::NaiveDate;
 use rust_decimal_macros::dec;
 use sea_orm::{ActiveModelTrait, Database, DatabaseConnection, Set};
 use uuid::Uuid;
+use zeltra_core::currency::RateLookupPolicy;
 use zeltra_db::{
     entities::{organizations, sea_orm_active_enums::RateSource},
     repositories::{
@@ -212,6 +213,7 @@ async fn test_find_direct_rate() {
             "EUR",
             "USD",
             NaiveDate::from_ymd_opt(2025, 1, 10).unwrap(),
+            RateLookupPolicy::DailyLatest,
         )
         .await
         .unwrap();
@@ -226,10 +228,141 @@ async fn test_find_direct_rate() {
             "EUR",
             "USD",
             NaiveDate::from_ymd_opt(2025, 1, 20).unwrap(),
+            RateLookupPolicy::DailyLatest,
         )
         .await
         .unwrap();
 
     assert_eq!(lookup2.rate, dec!(1.12));
     assert_eq!(lookup2.lookup_method, RateLookupMethod::Direct);
+    assert_eq!(
+        lookup2.effective_date,
+        NaiveDate::from_ymd_opt(2025, 1, 10).unwrap()
+    );
+    assert_eq!(lookup2.staleness_days, 10);
+    assert!(!lookup2.is_stale(10));
+    assert!(lookup2.is_stale(9));
+}
+
+// ============================================================================
+// Test: Lookup policies pick the daily or the monthly booking rate
+// ============================================================================
+#[tokio::test]
+async fn test_find_rate_by_lookup_policy() {
+    let db = Database::connect(&get_database_url())
+        .await
+        .expect("Failed to connect to database");
+
+    let org_id = create_test_org(&db).await;
+    let repo = ExchangeRateRepository::new(db.clone());
+    let date = |month, day| NaiveDate::from_ymd_opt(2025, month, day).unwrap();
+
+    for (effective_date, rate) in [(date(3, 1), dec!(1.08)), (date(3, 12), dec!(1.11))] {
+        repo.create_or_update_rate(CreateExchangeRateInput {
+            organization_id: org_id,
+            from_currency: "EUR".to_string(),
+            to_currency: "USD".to_string(),
+            rate,
+            effective_date,
+            source: RateSource::Manual,
+            source_reference: None,
+            created_by: None,
+        })
+        .await
+        .unwrap();
+    }
+
+    // Daily: the latest rate on or before the date
+    let daily = repo
+        .find_rate(
+            org_id,
+            "EUR",
+            "USD",
+            date(3, 20),
+            RateLookupPolicy::DailyLatest,
+        )
+        .await
+        .unwrap();
+    assert_eq!(daily.rate, dec!(1.11));
+    assert_eq!(daily.staleness_days, 8);
+
+    // Monthly: the rate dated the first of the month, never stale within it
+    let monthly = repo
+        .find_rate(
+            org_id,
+            "EUR",
+            "USD",
+            date(3, 20),
+            RateLookupPolicy::MonthlyFixed,
+        )
+        .await
+        .unwrap();
+    assert_eq!(monthly.rate, dec!(1.08));
+    assert_eq!(monthly.effective_date, date(3, 1));
+    assert_eq!(monthly.staleness_days, 0);
+
+    // Inverse lookups honour the policy too
+    let inverse = repo
+        .find_rate(
+            org_id,
+            "USD",
+            "EUR",
+            date(3, 20),
+            RateLookupPolicy::MonthlyFixed,
+        )
+        .await
+        .unwrap();
+    assert_eq!(inverse.lookup_method, RateLookupMethod::Inverse);
+    assert_eq!(inverse.effective_date, date(3, 1));
+
+    // April has no rate on its first day, so an earlier one does not count
+    let result = repo
+        .find_rate(
+            org_id,
+            "EUR",
+            "USD",
+            date(4, 15),
+            RateLookupPolicy::MonthlyFixed,
+        )
+        .await;
+    assert!(matches!(
+        result,
+        Err(ExchangeRateError::RateNotFound(_, _, rate_date)) if rate_date == date(4, 1)
+    ));
+}
+
+// ============================================================================
+// Test: Future-dated rates are rejected
+// ============================================================================
+#[tokio::test]
+async fn test_future_dated_rate_fails() {
+    let db = Database::connect(&get_database_url())
+        .await
+        .expect("Failed to connect to database");
+
+    let org_id = create_test_org(&db).await;
+    let repo = ExchangeRateRepository::new(db.clone());
+    let today = chrono::Utc::now().date_naive();
+
+    let input = |effective_date| CreateExchangeRateInput {
+        organization_id: org_id,
+        from_currency: "EUR".to_string(),
+        to_currency: "USD".to_string(),
+        rate: dec!(1.10),
+        effective_date,
+        source: RateSource::Manual,
+        source_reference: None,
+        created_by: None,
+    };
+
+    let tomorrow = today.succ_opt().unwrap();
+    let result = repo.create_or_update_rate(input(tomorrow)).await;
+    assert!(matches!(
+        result,
+        Err(ExchangeRateError::FutureEffectiveDate(date)) if date == tomorrow
+    ));
+
+    repo.create_or_update_rate(input(today))
+        .await
+        .expect("Today's rate should be accepted");
 }
@@ -0,0 +1,64 @@
+//! Integration tests for fiscal period coverage checks.
+
+use chrono::NaiveDate;
+use sea_orm::{ActiveModelTrait, Set};
+use uuid::Uuid;
+
+use zeltra_core::fiscal::PeriodCoverageFinding;
+use zeltra_db::entities::fiscal_periods;
+use zeltra_db::repositories::fiscal::{CreateFiscalYearInput, FiscalError, FiscalRepository};
+use zeltra_test_support::{OrgFixture, TestDb};
+
+fn date(month: u32, day: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(2025, month, day).unwrap()
+}
+
+#[tokio::test]
+async fn test_generated_year_is_clean_and_moved_period_leaves_gap() {
+    let test_db = TestDb::new().await;
+    let db = test_db.conn();
+    let org = OrgFixture::new().create(db).await;
+    let repo = FiscalRepository::new(db.clone());
+
+    let year = repo
+        .create_fiscal_year(CreateFiscalYearInput {
+            organization_id: org.id.into_inner(),
+            name: "FY 2025".to_string(),
+            start_date: date(1, 1),
+            end_date: date(12, 31),
+        })
+        .await
+        .expect("Failed to create fiscal year");
+    assert!(year.coverage.is_empty());
+
+    // July now starts on the 4th, leaving three days without a period
+    let july = year.periods[6].clone();
+    let mut active: fiscal_periods::ActiveModel = july.into();
+    active.start_date = Set(date(7, 4));
+    active.update(db).await.expect("Failed to move period");
+
+    let validated = repo
+        .validate_fiscal_year(org.id.into_inner(), year.fiscal_year.id)
+        .await
+        .expect("Failed to validate fiscal year");
+    assert_eq!(
+        validated.coverage,
+        vec![PeriodCoverageFinding::Gap {
+            start_date: date(7, 1),
+            end_date: date(7, 3),
+        }]
+    );
+
+    // Another organization's year is not found
+    let other = OrgFixture::new().create(db).await;
+    let err = repo
+        .validate_fiscal_year(other.id.into_inner(), year.fiscal_year.id)
+        .await
+        .unwrap_err();
+    assert!(matches!(err, FiscalError::YearNotFound(_)));
+    let err = repo
+        .validate_fiscal_year(org.id.into_inner(), Uuid::new_v4())
+        .await
+        .unwrap_err();
+    assert!(matches!(err, FiscalError::YearNotFound(_)));
+}
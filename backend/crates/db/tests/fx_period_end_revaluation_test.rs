@@ -0,0 +1,297 @@
+//! Integration tests for the month-end FX revaluation drafts.
+
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use sea_orm::{DatabaseConnection, EntityTrait};
+use serde_json::json;
+use uuid::Uuid;
+
+use zeltra_db::entities::sea_orm_active_enums::{
+    AccountType, RateSource, SubscriptionTier, TransactionStatus, TransactionType, UserRole,
+};
+use zeltra_db::entities::{fiscal_periods, transactions};
+use zeltra_db::repositories::transaction::{
+    CreateLedgerEntryInput, CreateTransactionInput, TransactionRepository,
+};
+use zeltra_db::repositories::{
+    CreateExchangeRateInput, ExchangeRateRepository, FxRevaluationRepository,
+    NotificationRepository, OrganizationRepository, PeriodEndOutcome, RevaluationSkip,
+    WorkflowRepository,
+};
+use zeltra_shared::types::TransactionId;
+use zeltra_test_support::{Org, OrgFixture, TestDb};
+
+const ACCOUNTS: &[(&str, AccountType)] = &[
+    ("1200", AccountType::Asset),
+    ("4000", AccountType::Revenue),
+    ("7900", AccountType::Revenue),
+    ("8900", AccountType::Expense),
+];
+
+fn date(m: u32, d: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(2025, m, d).unwrap()
+}
+
+fn entry(
+    account_id: Uuid,
+    source: (&str, Decimal, Decimal),
+    debit: Decimal,
+    credit: Decimal,
+) -> CreateLedgerEntryInput {
+    CreateLedgerEntryInput {
+        account_id,
+        source_currency: source.0.to_string(),
+        source_amount: source.1,
+        exchange_rate: source.2,
+        functional_currency: "USD".to_string(),
+        functional_amount: debit + credit,
+        debit,
+        credit,
+        memo: None,
+        dimensions: vec![],
+        rate_override: None,
+        event_at: None,
+    }
+}
+
+/// USD/EUR organization with a posted EUR 1,000 receivable booked at 1.08.
+async fn two_currency_org(fixture: OrgFixture, db: &DatabaseConnection) -> Org {
+    let org = fixture
+        .with_fiscal_year_2025()
+        .with_accounts(ACCOUNTS)
+        .create(db)
+        .await;
+    let created = TransactionRepository::new(db.clone())
+        .create_transaction(CreateTransactionInput {
+            organization_id: org.id.into_inner(),
+            transaction_type: TransactionType::Journal,
+            transaction_date: date(3, 5),
+            description: "EUR sale".to_string(),
+            reference_number: None,
+            memo: None,
+            entries: vec![
+                entry(
+                    org.account("1200").into_inner(),
+                    ("EUR", dec!(1000), dec!(1.08)),
+                    dec!(1080),
+                    Decimal::ZERO,
+                ),
+                entry(
+                    org.account("4000").into_inner(),
+                    ("USD", dec!(1080), Decimal::ONE),
+                    Decimal::ZERO,
+                    dec!(1080),
+                ),
+            ],
+            created_by: org.owner.user_id.into_inner(),
+        })
+        .await
+        .expect("Failed to create transaction");
+
+    let workflow = WorkflowRepository::new(db.clone());
+    let tx_id = TransactionId::from_uuid(created.transaction.id);
+    let user = org.owner.user_id.into_inner();
+    workflow
+        .submit_transaction(org.id, tx_id, user)
+        .await
+        .expect("Failed to submit");
+    workflow
+        .approve_transaction(org.id, tx_id, user, None)
+        .await
+        .expect("Failed to approve");
+    workflow
+        .post_transaction(org.id, tx_id, user)
+        .await
+        .expect("Failed to post");
+    org
+}
+
+async fn store_rate(db: &DatabaseConnection, org: &Org, on: NaiveDate, rate: Decimal) {
+    ExchangeRateRepository::new(db.clone())
+        .create_or_update_rate(CreateExchangeRateInput {
+            organization_id: org.id.into_inner(),
+            from_currency: "EUR".to_string(),
+            to_currency: "USD".to_string(),
+            rate,
+            effective_date: on,
+            source: RateSource::Manual,
+            source_reference: None,
+            created_by: None,
+        })
+        .await
+        .expect("Failed to store rate");
+}
+
+async fn configure_accounts(db: &DatabaseConnection, org: &Org) {
+    OrganizationRepository::new(db.clone())
+        .update_settings(
+            org.id.into_inner(),
+            &json!({
+                "fx_revaluation": {
+                    "gain_account_id": org.account("7900").into_inner(),
+                    "loss_account_id": org.account("8900").into_inner(),
+                }
+            }),
+        )
+        .await
+        .expect("Failed to update settings");
+}
+
+async fn march(db: &DatabaseConnection, org: &Org) -> fiscal_periods::Model {
+    let repo = FxRevaluationRepository::new(db.clone());
+    let mut due = repo
+        .due_periods(date(3, 31), Some(org.id.into_inner()))
+        .await
+        .expect("Failed to list due periods");
+    assert_eq!(due.len(), 1, "only March ends on 31 March");
+    due.remove(0)
+}
+
+#[tokio::test]
+async fn test_due_periods_end_today_or_tomorrow() {
+    let test_db = TestDb::new().await;
+    let db = test_db.conn();
+    let org = OrgFixture::new().with_fiscal_year_2025().create(db).await;
+    let repo = FxRevaluationRepository::new(db.clone());
+    let ends = |periods: Vec<fiscal_periods::Model>| {
+        periods
+            .into_iter()
+            .filter(|p| p.organization_id == org.id.into_inner())
+            .map(|p| p.end_date)
+            .collect::<Vec<_>>()
+    };
+
+    let on_30th = repo.due_periods(date(3, 30), None).await.unwrap();
+    assert_eq!(ends(on_30th), [date(3, 31)]);
+    let on_31st = repo.due_periods(date(3, 31), None).await.unwrap();
+    assert_eq!(ends(on_31st), [date(3, 31)]);
+    let mid_month = repo
+        .due_periods(date(3, 15), Some(org.id.into_inner()))
+        .await
+        .unwrap();
+    assert!(mid_month.is_empty());
+}
+
+#[tokio::test]
+async fn test_period_end_creates_adjustment_draft_once() {
+    let test_db = TestDb::new().await;
+    let db = test_db.conn();
+    let org = two_currency_org(OrgFixture::new(), db).await;
+    configure_accounts(db, &org).await;
+    store_rate(db, &org, date(3, 31), dec!(1.12)).await;
+    let repo = FxRevaluationRepository::new(db.clone());
+    let period = march(db, &org).await;
+
+    let run = repo.revalue_period_end(&period).await.unwrap();
+    assert_eq!(run.reference, "FXREVAL-2025-03");
+    let PeriodEndOutcome::Created {
+        transaction_id,
+        total_unrealized,
+    } = run.outcome
+    else {
+        panic!("expected a draft, got {:?}", run.outcome);
+    };
+    assert_eq!(total_unrealized, dec!(40));
+
+    let draft = transactions::Entity::find_by_id(transaction_id)
+        .one(db)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(draft.transaction_type, TransactionType::Adjustment);
+    assert_eq!(draft.status, TransactionStatus::Draft);
+    assert_eq!(draft.transaction_date, date(3, 31));
+    assert_eq!(draft.reference_number.as_deref(), Some("FXREVAL-2025-03"));
+    assert_eq!(draft.created_by, org.owner.user_id.into_inner());
+
+    let again = repo.revalue_period_end(&period).await.unwrap();
+    assert_eq!(again.outcome, PeriodEndOutcome::Exists { transaction_id });
+}
+
+#[tokio::test]
+async fn test_missing_configuration_notifies_admins_once() {
+    let test_db = TestDb::new().await;
+    let db = test_db.conn();
+    let org = two_currency_org(OrgFixture::new().with_member(UserRole::Admin), db).await;
+    store_rate(db, &org, date(3, 31), dec!(1.12)).await;
+    let repo = FxRevaluationRepository::new(db.clone());
+    let period = march(db, &org).await;
+
+    for _ in 0..2 {
+        let run = repo.revalue_period_end(&period).await.unwrap();
+        assert_eq!(
+            run.outcome,
+            PeriodEndOutcome::Skipped(RevaluationSkip::AccountsNotConfigured)
+        );
+    }
+
+    let notifications = NotificationRepository::new(db.clone());
+    for user_id in [org.owner.user_id, org.members[0].user_id] {
+        let inbox = notifications
+            .list(org.id.into_inner(), user_id.into_inner(), 1, 20)
+            .await
+            .unwrap();
+        assert_eq!(inbox.total, 1);
+        let notification = &inbox.notifications[0];
+        assert_eq!(notification.notification_type, "fx_revaluation_skipped");
+        assert_eq!(notification.payload["reason"], "accounts_not_configured");
+        assert_eq!(notification.payload["reference"], "FXREVAL-2025-03");
+    }
+}
+
+#[tokio::test]
+async fn test_missing_rate_skips_with_currencies() {
+    let test_db = TestDb::new().await;
+    let db = test_db.conn();
+    let org = two_currency_org(OrgFixture::new(), db).await;
+    configure_accounts(db, &org).await;
+    // Only a rate after the period end
+    store_rate(db, &org, date(4, 1), dec!(1.12)).await;
+
+    let run = FxRevaluationRepository::new(db.clone())
+        .revalue_period_end(&march(db, &org).await)
+        .await
+        .unwrap();
+
+    assert_eq!(
+        run.outcome,
+        PeriodEndOutcome::Skipped(RevaluationSkip::MissingRates {
+            currencies: vec!["EUR".to_string()],
+        })
+    );
+}
+
+#[tokio::test]
+async fn test_unchanged_rate_has_nothing_to_revalue() {
+    let test_db = TestDb::new().await;
+    let db = test_db.conn();
+    let org = two_currency_org(OrgFixture::new(), db).await;
+    configure_accounts(db, &org).await;
+    store_rate(db, &org, date(3, 31), dec!(1.08)).await;
+
+    let run = FxRevaluationRepository::new(db.clone())
+        .revalue_period_end(&march(db, &org).await)
+        .await
+        .unwrap();
+
+    assert_eq!(run.outcome, PeriodEndOutcome::NothingToRevalue);
+}
+
+#[tokio::test]
+async fn test_plan_without_multi_currency_is_left_alone() {
+    let test_db = TestDb::new().await;
+    let db = test_db.conn();
+    let org = OrgFixture::new()
+        .with_tier(SubscriptionTier::Starter)
+        .with_fiscal_year_2025()
+        .create(db)
+        .await;
+
+    let run = FxRevaluationRepository::new(db.clone())
+        .revalue_period_end(&march(db, &org).await)
+        .await
+        .unwrap();
+
+    assert_eq!(run.outcome, PeriodEndOutcome::MultiCurrencyDisabled);
+}
@@ -0,0 +1,253 @@
+//! Integration tests for foreign currency balances and FX revaluation.
+
+use std::collections::HashMap;
+
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use sea_orm::DatabaseConnection;
+use uuid::Uuid;
+
+use zeltra_core::currency::{ForeignBalance, revalue};
+use zeltra_db::entities::sea_orm_active_enums::{AccountType, TransactionType};
+use zeltra_db::repositories::WorkflowRepository;
+use zeltra_db::repositories::report::ReportRepository;
+use zeltra_db::repositories::transaction::{
+    CreateLedgerEntryInput, CreateTransactionInput, TransactionRepository,
+};
+use zeltra_shared::types::TransactionId;
+use zeltra_test_support::{Org, OrgFixture, TestDb};
+
+const ACCOUNTS: &[(&str, AccountType)] = &[
+    ("1200", AccountType::Asset),
+    ("2000", AccountType::Liability),
+    ("4000", AccountType::Revenue),
+    ("6000", AccountType::Expense),
+    ("7900", AccountType::Revenue),
+    ("8900", AccountType::Expense),
+];
+
+fn date(m: u32, d: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(2025, m, d).unwrap()
+}
+
+/// Ledger line; `source` is `(currency, amount, rate)`.
+fn entry(
+    account_id: Uuid,
+    source: (&str, Decimal, Decimal),
+    debit: Decimal,
+    credit: Decimal,
+) -> CreateLedgerEntryInput {
+    CreateLedgerEntryInput {
+        account_id,
+        source_currency: source.0.to_string(),
+        source_amount: source.1,
+        exchange_rate: source.2,
+        functional_currency: "USD".to_string(),
+        functional_amount: debit + credit,
+        debit,
+        credit,
+        memo: None,
+        dimensions: vec![],
+        rate_override: None,
+        event_at: None,
+    }
+}
+
+async fn book(
+    db: &DatabaseConnection,
+    org: &Org,
+    on: NaiveDate,
+    entries: Vec<CreateLedgerEntryInput>,
+    post: bool,
+) {
+    let created = TransactionRepository::new(db.clone())
+        .create_transaction(CreateTransactionInput {
+            organization_id: org.id.into_inner(),
+            transaction_type: TransactionType::Journal,
+            transaction_date: on,
+            description: "FX fixture".to_string(),
+            reference_number: None,
+            memo: None,
+            entries,
+            created_by: org.owner.user_id.into_inner(),
+        })
+        .await
+        .expect("Failed to create transaction");
+    if !post {
+        return;
+    }
+
+    let workflow = WorkflowRepository::new(db.clone());
+    let tx_id = TransactionId::from_uuid(created.transaction.id);
+    let user = org.owner.user_id.into_inner();
+    workflow
+        .submit_transaction(org.id, tx_id, user)
+        .await
+        .expect("Failed to submit");
+    workflow
+        .approve_transaction(org.id, tx_id, user, None)
+        .await
+        .expect("Failed to approve");
+    workflow
+        .post_transaction(org.id, tx_id, user)
+        .await
+        .expect("Failed to post");
+}
+
+/// EUR 1,000 receivable at 1.08 and EUR 400 payable at 1.10, both posted.
+async fn org_with_eur_balances(db: &DatabaseConnection) -> Org {
+    let org = OrgFixture::new()
+        .with_fiscal_year_2025()
+        .with_accounts(ACCOUNTS)
+        .create(db)
+        .await;
+    let usd = |amount| ("USD", amount, Decimal::ONE);
+
+    book(
+        db,
+        &org,
+        date(3, 5),
+        vec![
+            entry(
+                org.account("1200").into_inner(),
+                ("EUR", dec!(1000), dec!(1.08)),
+                dec!(1080),
+                Decimal::ZERO,
+            ),
+            entry(
+                org.account("4000").into_inner(),
+                usd(dec!(1080)),
+                Decimal::ZERO,
+                dec!(1080),
+            ),
+        ],
+        true,
+    )
+    .await;
+    book(
+        db,
+        &org,
+        date(3, 12),
+        vec![
+            entry(
+                org.account("6000").into_inner(),
+                usd(dec!(440)),
+                dec!(440),
+                Decimal::ZERO,
+            ),
+            entry(
+                org.account("2000").into_inner(),
+                ("EUR", dec!(400), dec!(1.10)),
+                Decimal::ZERO,
+                dec!(440),
+            ),
+        ],
+        true,
+    )
+    .await;
+    org
+}
+
+async fn foreign_balances(
+    db: &DatabaseConnection,
+    org: &Org,
+    as_of: NaiveDate,
+) -> Vec<ForeignBalance> {
+    ReportRepository::new(db.clone())
+        .query_foreign_balances(org.id.into_inner(), as_of)
+        .await
+        .expect("Failed to query foreign balances")
+        .into_iter()
+        .map(|b| b.balance)
+        .collect()
+}
+
+#[tokio::test]
+async fn test_foreign_balances_are_signed_and_exclude_drafts_and_later_entries() {
+    let test_db = TestDb::new().await;
+    let db = test_db.conn();
+    let org = org_with_eur_balances(db).await;
+
+    // Draft and post-cutoff EUR activity must not count
+    let extra = || {
+        vec![
+            entry(
+                org.account("1200").into_inner(),
+                ("EUR", dec!(50), dec!(1.2)),
+                dec!(60),
+                Decimal::ZERO,
+            ),
+            entry(
+                org.account("4000").into_inner(),
+                ("USD", dec!(60), Decimal::ONE),
+                Decimal::ZERO,
+                dec!(60),
+            ),
+        ]
+    };
+    book(db, &org, date(3, 20), extra(), false).await;
+    book(db, &org, date(4, 2), extra(), true).await;
+
+    let balances = foreign_balances(db, &org, date(3, 31)).await;
+
+    assert_eq!(
+        balances,
+        vec![
+            ForeignBalance {
+                account_id: org.account("1200").into_inner(),
+                currency: "EUR".to_string(),
+                source_balance: dec!(1000),
+                booked_functional: dec!(1080),
+            },
+            ForeignBalance {
+                account_id: org.account("2000").into_inner(),
+                currency: "EUR".to_string(),
+                source_balance: dec!(-400),
+                booked_functional: dec!(-440),
+            },
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_posted_revaluation_brings_unrealized_to_zero() {
+    let test_db = TestDb::new().await;
+    let db = test_db.conn();
+    let org = org_with_eur_balances(db).await;
+    let as_of = date(3, 31);
+    let rates = HashMap::from([("EUR".to_string(), dec!(1.12))]);
+
+    let result = revalue(&foreign_balances(db, &org, as_of).await, &rates, 2);
+    assert_eq!(result.total_unrealized, dec!(32));
+
+    let entries = result
+        .adjustment_lines(
+            org.account("7900").into_inner(),
+            org.account("8900").into_inner(),
+        )
+        .into_iter()
+        .map(|line| {
+            let amount = line.debit + line.credit;
+            let source = match &line.currency {
+                Some(_) => ("EUR", Decimal::ZERO, dec!(1.12)),
+                None => ("USD", amount, Decimal::ONE),
+            };
+            entry(line.account_id, source, line.debit, line.credit)
+        })
+        .collect();
+    book(db, &org, as_of, entries, true).await;
+
+    let again = revalue(&foreign_balances(db, &org, as_of).await, &rates, 2);
+    assert_eq!(again.total_unrealized, Decimal::ZERO);
+    assert!(again.accounts.iter().all(|a| a.unrealized.is_zero()));
+    assert_eq!(again.accounts[0].source_balance, dec!(1000));
+    assert_eq!(again.accounts[0].booked_functional, dec!(1120));
+
+    // Restating entries carry no source amount and are not data-quality findings
+    let findings = ReportRepository::new(db.clone())
+        .query_data_quality(org.id.into_inner())
+        .await
+        .expect("Failed to run checks");
+    assert!(findings.is_empty(), "unexpected findings: {findings:?}");
+}
@@ -0,0 +1,190 @@
+//! Integration tests for the income statement by dimension.
+
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use sea_orm::{ActiveEnum, DatabaseConnection};
+use uuid::Uuid;
+
+use zeltra_core::reports::{
+    AccountBalance as CoreAccountBalance, DimensionAllocation, DimensionIncomeStatementReport,
+    ReportService, StatementMapping,
+};
+use zeltra_db::entities::sea_orm_active_enums::AccountType;
+use zeltra_db::repositories::dimension::{
+    CreateDimensionTypeInput, CreateDimensionValueInput, DimensionRepository,
+};
+use zeltra_db::repositories::report::{ReportError, ReportRepository};
+use zeltra_test_support::{Line, Org, OrgFixture, TestDb, post_transaction};
+
+const ACCOUNTS: &[(&str, AccountType)] =
+    &[("1000", AccountType::Asset), ("6000", AccountType::Expense)];
+
+async fn dimension_type(db: &DatabaseConnection, org: &Org, code: &str) -> Uuid {
+    DimensionRepository::new(db.clone())
+        .create_dimension_type(CreateDimensionTypeInput {
+            organization_id: org.id.into_inner(),
+            code: code.to_string(),
+            name: code.to_string(),
+            description: None,
+            is_required: false,
+            is_active: true,
+            sort_order: 0,
+        })
+        .await
+        .expect("Failed to create dimension type")
+        .id
+}
+
+async fn dimension_value(
+    db: &DatabaseConnection,
+    org: &Org,
+    type_id: Uuid,
+    code: &str,
+    is_active: bool,
+) -> Uuid {
+    DimensionRepository::new(db.clone())
+        .create_dimension_value(CreateDimensionValueInput {
+            organization_id: org.id.into_inner(),
+            dimension_type_id: type_id,
+            code: code.to_string(),
+            name: code.to_string(),
+            description: None,
+            parent_id: None,
+            is_active,
+            effective_from: None,
+            effective_to: None,
+        })
+        .await
+        .expect("Failed to create dimension value")
+        .id
+}
+
+/// Posts an expense paid from cash, tagging the expense entry.
+async fn post_expense(db: &DatabaseConnection, org: &Org, amount: Decimal, dimensions: Vec<Uuid>) {
+    post_transaction(
+        db,
+        org,
+        NaiveDate::from_ymd_opt(2025, 3, 10).unwrap(),
+        vec![
+            Line::debit("6000", amount).with_dimensions(dimensions),
+            Line::credit("1000", amount),
+        ],
+    )
+    .await;
+}
+
+async fn by_department(
+    db: &DatabaseConnection,
+    org: &Org,
+    dept: Uuid,
+    allocation: Option<&DimensionAllocation>,
+) -> DimensionIncomeStatementReport {
+    let data = ReportRepository::new(db.clone())
+        .query_income_statement_by_dimension(
+            org.id.into_inner(),
+            NaiveDate::from_ymd_opt(2025, 1, 1).unwrap(),
+            NaiveDate::from_ymd_opt(2025, 12, 31).unwrap(),
+            dept,
+        )
+        .await
+        .expect("Failed to query income statement");
+    let accounts: Vec<CoreAccountBalance> = data
+        .accounts
+        .iter()
+        .map(|a| CoreAccountBalance {
+            account_id: a.account_id,
+            code: a.code.clone(),
+            name: a.name.clone(),
+            account_type: a.account_type.to_value(),
+            account_subtype: None,
+            total_debit: a.total_debit,
+            total_credit: a.total_credit,
+            balance: a.balance,
+        })
+        .collect();
+    ReportService::income_statement_by_dimension(
+        &accounts,
+        &data.values,
+        &data.totals,
+        allocation,
+        &StatementMapping::default(),
+    )
+    .expect("Failed to build income statement")
+}
+
+fn net_income_by_column(report: &DimensionIncomeStatementReport) -> Vec<(String, Decimal)> {
+    report
+        .columns
+        .iter()
+        .map(|c| (c.code.clone(), c.statement.net_income))
+        .collect()
+}
+
+#[tokio::test]
+async fn test_entry_with_two_departments_is_split_evenly() {
+    let test_db = TestDb::new().await;
+    let db = test_db.conn();
+    let org = OrgFixture::new()
+        .with_fiscal_year_2025()
+        .with_accounts(ACCOUNTS)
+        .create(db)
+        .await;
+    let dept = dimension_type(db, &org, "DEPT").await;
+    let project = dimension_type(db, &org, "PROJECT").await;
+    let eng = dimension_value(db, &org, dept, "ENG", true).await;
+    let sales = dimension_value(db, &org, dept, "SALES", true).await;
+    dimension_value(db, &org, dept, "LEGACY", false).await;
+    let alpha = dimension_value(db, &org, project, "ALPHA", true).await;
+
+    post_expense(db, &org, dec!(100), vec![eng]).await;
+    post_expense(db, &org, dec!(200), vec![sales, alpha]).await;
+    post_expense(db, &org, dec!(300), vec![alpha]).await;
+    // Shared cost tagged with both departments
+    post_expense(db, &org, dec!(1001), vec![eng, sales]).await;
+
+    let report = by_department(db, &org, dept, None).await;
+    assert_eq!(
+        net_income_by_column(&report),
+        [
+            ("ENG".to_string(), dec!(-600.5)),
+            ("SALES".to_string(), dec!(-700.5)),
+            ("unallocated".to_string(), dec!(-300)),
+        ]
+    );
+    // Splitting rather than counting in both keeps the columns additive
+    assert_eq!(report.total.net_income, dec!(-1601));
+
+    let allocation = DimensionAllocation {
+        shares: vec![(eng, dec!(60)), (sales, dec!(40))],
+    };
+    let report = by_department(db, &org, dept, Some(&allocation)).await;
+    assert_eq!(
+        net_income_by_column(&report),
+        [
+            ("ENG".to_string(), dec!(-780.5)),
+            ("SALES".to_string(), dec!(-820.5)),
+            ("unallocated".to_string(), dec!(0)),
+        ]
+    );
+    assert_eq!(report.total.net_income, dec!(-1601));
+}
+
+#[tokio::test]
+async fn test_dimension_type_must_belong_to_organization() {
+    let test_db = TestDb::new().await;
+    let db = test_db.conn();
+    let org = OrgFixture::new().create(db).await;
+    let other = OrgFixture::new().create(db).await;
+    let foreign = dimension_type(db, &other, "DEPT").await;
+
+    let result = ReportRepository::new(db.clone())
+        .query_income_statement_by_dimension(
+            org.id.into_inner(),
+            NaiveDate::from_ymd_opt(2025, 1, 1).unwrap(),
+            NaiveDate::from_ymd_opt(2025, 12, 31).unwrap(),
+            foreign,
+        )
+        .await;
+    assert!(matches!(result, Err(ReportError::InvalidDimensionType(_))));
+}
@@ -0,0 +1,282 @@
+//! Integration tests for streaming a fiscal period's ledger entries.
+
+use std::collections::HashSet;
+
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use sea_orm::DatabaseConnection;
+use uuid::Uuid;
+
+use zeltra_db::entities::sea_orm_active_enums::{AccountType, TransactionStatus, TransactionType};
+use zeltra_db::repositories::dimension::{
+    CreateDimensionTypeInput, CreateDimensionValueInput, DimensionRepository,
+};
+use zeltra_db::repositories::ledger_entry::{
+    LedgerEntryPage, LedgerEntryRepository, LedgerStreamFilter, StreamedLedgerEntry,
+};
+use zeltra_db::repositories::transaction::{
+    CreateLedgerEntryInput, CreateTransactionInput, TransactionRepository,
+};
+use zeltra_shared::types::{Cursor, TransactionId};
+use zeltra_test_support::{Line, Org, OrgFixture, TestDb, post_draft, post_transaction};
+
+const ACCOUNTS: &[(&str, AccountType)] =
+    &[("1000", AccountType::Asset), ("5000", AccountType::Expense)];
+
+async fn org_with_year(db: &DatabaseConnection) -> Org {
+    OrgFixture::new()
+        .with_fiscal_year_2025()
+        .with_accounts(ACCOUNTS)
+        .create(db)
+        .await
+}
+
+/// Creates an expense paid from cash on `date`, tagging the expense entry.
+async fn create_expense(
+    db: &DatabaseConnection,
+    org: &Org,
+    date: NaiveDate,
+    dimensions: Vec<Uuid>,
+) -> TransactionId {
+    let amount = Decimal::new(75, 0);
+    let entry = |account: &str, debit: Decimal, credit: Decimal, dimensions: Vec<Uuid>| {
+        CreateLedgerEntryInput {
+            account_id: org.account(account).into_inner(),
+            source_currency: "USD".to_string(),
+            source_amount: amount,
+            exchange_rate: Decimal::ONE,
+            functional_currency: "USD".to_string(),
+            functional_amount: amount,
+            debit,
+            credit,
+            memo: None,
+            dimensions,
+            rate_override: None,
+            event_at: None,
+        }
+    };
+    let created = TransactionRepository::new(db.clone())
+        .create_transaction(CreateTransactionInput {
+            organization_id: org.id.into_inner(),
+            transaction_type: TransactionType::Expense,
+            transaction_date: date,
+            description: "Courier".to_string(),
+            reference_number: None,
+            memo: None,
+            entries: vec![
+                entry("5000", amount, Decimal::ZERO, dimensions),
+                entry("1000", Decimal::ZERO, amount, vec![]),
+            ],
+            created_by: org.owner.user_id.into_inner(),
+        })
+        .await
+        .expect("Failed to create transaction");
+    TransactionId::from(created.transaction.id)
+}
+
+/// Creates and posts an expense on `date`.
+async fn post_expense(
+    db: &DatabaseConnection,
+    org: &Org,
+    date: NaiveDate,
+    dimensions: Vec<Uuid>,
+) -> TransactionId {
+    let amount = Decimal::new(75, 0);
+    post_transaction(
+        db,
+        org,
+        date,
+        vec![
+            Line::debit("5000", amount).with_dimensions(dimensions),
+            Line::credit("1000", amount),
+        ],
+    )
+    .await
+}
+
+fn march() -> NaiveDate {
+    NaiveDate::from_ymd_opt(2025, 3, 10).unwrap()
+}
+
+async fn page(
+    db: &DatabaseConnection,
+    org: &Org,
+    statuses: Vec<TransactionStatus>,
+    after: Option<Cursor>,
+    limit: u64,
+) -> LedgerEntryPage {
+    LedgerEntryRepository::new(db.clone())
+        .stream_entries(
+            org.id,
+            LedgerStreamFilter {
+                fiscal_period_id: org.fiscal_year.as_ref().unwrap().periods[2].into_inner(),
+                statuses,
+                overridden_only: false,
+                after,
+                limit,
+            },
+        )
+        .await
+        .expect("Failed to stream entries")
+}
+
+#[tokio::test]
+async fn test_cursor_is_stable_under_concurrent_inserts() {
+    let test_db = TestDb::new().await;
+    let db = test_db.conn();
+    let org = org_with_year(db).await;
+    // Drafted before the first page, posted after it
+    let draft = create_expense(db, &org, march(), vec![]).await;
+    for _ in 0..3 {
+        post_expense(db, &org, march(), vec![]).await;
+    }
+    let posted = vec![TransactionStatus::Posted];
+
+    let first = page(db, &org, posted.clone(), None, 4).await;
+    assert_eq!(first.entries.len(), 4);
+    let frontier = first.next_cursor.expect("more entries follow");
+    assert_eq!(frontier.id, first.entries[3].id);
+
+    // Entries are posted while the client is reading and between pages
+    let (_, second) = tokio::join!(
+        post_expense(db, &org, march(), vec![]),
+        page(db, &org, posted.clone(), Some(frontier), 4),
+    );
+    post_expense(db, &org, march(), vec![]).await;
+    post_draft(db, &org, draft).await;
+    let mut seen: Vec<_> = first
+        .entries
+        .iter()
+        .chain(&second.entries)
+        .cloned()
+        .collect();
+    // Resume after the last entry seen, as an incremental pull would
+    let mut after = seen.last().map(StreamedLedgerEntry::cursor);
+    loop {
+        let next = page(db, &org, posted.clone(), after, 4).await;
+        seen.extend(next.entries);
+        after = next.next_cursor;
+        if after.is_none() {
+            break;
+        }
+    }
+
+    // Every entry comes exactly once, in stream order
+    assert_eq!(seen.len(), 12);
+    let ids: HashSet<Uuid> = seen.iter().map(|e| e.id).collect();
+    assert_eq!(ids.len(), seen.len());
+    let key = |c: Cursor| (c.at, c.id);
+    assert!(
+        seen.windows(2)
+            .all(|w| key(w[0].cursor()) < key(w[1].cursor()))
+    );
+
+    // Entries posted after the first page sort after its frontier, even the
+    // ones recorded before it
+    let original: HashSet<Uuid> = first.entries.iter().map(|e| e.id).collect();
+    assert!(
+        seen[4..]
+            .iter()
+            .all(|e| !original.contains(&e.id) && key(e.cursor()) > key(frontier))
+    );
+    let late: Vec<_> = seen
+        .iter()
+        .filter(|e| e.transaction_id == draft.into_inner())
+        .collect();
+    assert_eq!(late.len(), 2);
+    assert!(
+        late.iter()
+            .all(|e| e.created_at < first.entries[0].created_at)
+    );
+
+    // Re-reading from the same cursor gives the same entries
+    let again = page(db, &org, posted, Some(frontier), 8).await;
+    let again_ids: Vec<Uuid> = again.entries.iter().map(|e| e.id).collect();
+    let seen_ids: Vec<Uuid> = seen[4..].iter().map(|e| e.id).collect();
+    assert_eq!(again_ids, seen_ids);
+    assert!(again.next_cursor.is_none());
+}
+
+#[tokio::test]
+async fn test_stream_keeps_to_period_status_and_organization() {
+    let test_db = TestDb::new().await;
+    let db = test_db.conn();
+    let org = org_with_year(db).await;
+    let other = org_with_year(db).await;
+
+    let dimensions = DimensionRepository::new(db.clone());
+    let project = dimensions
+        .create_dimension_type(CreateDimensionTypeInput {
+            organization_id: org.id.into_inner(),
+            code: "PROJECT".to_string(),
+            name: "Project".to_string(),
+            description: None,
+            is_required: false,
+            is_active: true,
+            sort_order: 0,
+        })
+        .await
+        .unwrap();
+    let alpha = dimensions
+        .create_dimension_value(CreateDimensionValueInput {
+            organization_id: org.id.into_inner(),
+            dimension_type_id: project.id,
+            code: "ALPHA".to_string(),
+            name: "Alpha".to_string(),
+            description: None,
+            parent_id: None,
+            is_active: true,
+            effective_from: None,
+            effective_to: None,
+        })
+        .await
+        .unwrap();
+
+    let tagged = post_expense(db, &org, march(), vec![alpha.id]).await;
+    let draft = create_expense(db, &org, march(), vec![]).await;
+    post_expense(
+        db,
+        &org,
+        NaiveDate::from_ymd_opt(2025, 4, 2).unwrap(),
+        vec![],
+    )
+    .await;
+    post_expense(db, &other, march(), vec![]).await;
+
+    let posted = page(db, &org, vec![TransactionStatus::Posted], None, 500).await;
+    assert_eq!(posted.entries.len(), 2);
+    assert!(posted.next_cursor.is_none());
+    assert!(
+        posted
+            .entries
+            .iter()
+            .all(|e| e.transaction_id == tagged.into_inner()
+                && e.status == TransactionStatus::Posted)
+    );
+    let expense = posted
+        .entries
+        .iter()
+        .find(|e| e.account_code == "5000")
+        .unwrap();
+    assert_eq!(expense.account_type, AccountType::Expense);
+    assert_eq!(expense.debit, Decimal::new(75, 0));
+    assert_eq!(expense.dimensions.len(), 1);
+    assert_eq!(expense.dimensions[0].id, alpha.id);
+    assert_eq!(expense.dimensions[0].code, "ALPHA");
+
+    let broader = page(
+        db,
+        &org,
+        vec![TransactionStatus::Posted, TransactionStatus::Draft],
+        None,
+        500,
+    )
+    .await;
+    assert_eq!(broader.entries.len(), 4);
+    assert!(
+        broader
+            .entries
+            .iter()
+            .any(|e| e.transaction_id == draft.into_inner())
+    );
+}
@@ -0,0 +1,173 @@
+//! Integration tests for ordering an account's ledger by entry event time.
+
+use chrono::{DateTime, FixedOffset, NaiveDate};
+use rust_decimal::Decimal;
+use sea_orm::{ActiveModelTrait, DatabaseConnection, Set};
+use uuid::Uuid;
+
+use zeltra_db::entities::organizations;
+use zeltra_db::entities::sea_orm_active_enums::{AccountType, TransactionType};
+use zeltra_db::repositories::account::AccountRepository;
+use zeltra_db::repositories::report::ReportRepository;
+use zeltra_db::repositories::transaction::{
+    CreateLedgerEntryInput, CreateTransactionInput, TransactionError, TransactionRepository,
+};
+use zeltra_db::repositories::workflow::WorkflowRepository;
+use zeltra_shared::types::TransactionId;
+use zeltra_test_support::{Org, OrgFixture, TestDb};
+
+const ACCOUNTS: &[(&str, AccountType)] =
+    &[("1000", AccountType::Asset), ("5000", AccountType::Expense)];
+
+fn date() -> NaiveDate {
+    NaiveDate::from_ymd_opt(2025, 3, 10).unwrap()
+}
+
+fn at(rfc3339: &str) -> DateTime<FixedOffset> {
+    DateTime::parse_from_rfc3339(rfc3339).unwrap()
+}
+
+/// Creates an expense paid from cash on 2025-03-10, with both entries at
+/// `event_at`.
+async fn create_expense(
+    db: &DatabaseConnection,
+    org: &Org,
+    amount: i64,
+    event_at: DateTime<FixedOffset>,
+) -> Result<Uuid, TransactionError> {
+    let amount = Decimal::new(amount, 0);
+    let entry = |account: &str, debit: Decimal, credit: Decimal| CreateLedgerEntryInput {
+        account_id: org.account(account).into_inner(),
+        source_currency: "USD".to_string(),
+        source_amount: amount,
+        exchange_rate: Decimal::ONE,
+        functional_currency: "USD".to_string(),
+        functional_amount: amount,
+        debit,
+        credit,
+        memo: None,
+        dimensions: vec![],
+        rate_override: None,
+        event_at: Some(event_at),
+    };
+    let created = TransactionRepository::new(db.clone())
+        .create_transaction(CreateTransactionInput {
+            organization_id: org.id.into_inner(),
+            transaction_type: TransactionType::Expense,
+            transaction_date: date(),
+            description: "Seeded expense".to_string(),
+            reference_number: None,
+            memo: None,
+            entries: vec![
+                entry("5000", amount, Decimal::ZERO),
+                entry("1000", Decimal::ZERO, amount),
+            ],
+            created_by: org.owner.user_id.into_inner(),
+        })
+        .await?;
+    Ok(created.transaction.id)
+}
+
+async fn post(db: &DatabaseConnection, org: &Org, transaction_id: Uuid) {
+    let id = TransactionId::from(transaction_id);
+    let user_id = org.owner.user_id.into_inner();
+    let workflow = WorkflowRepository::new(db.clone());
+    workflow
+        .submit_transaction(org.id, id, user_id)
+        .await
+        .expect("Failed to submit transaction");
+    workflow
+        .approve_transaction(org.id, id, user_id, None)
+        .await
+        .expect("Failed to approve transaction");
+    workflow
+        .post_transaction(org.id, id, user_id)
+        .await
+        .expect("Failed to post transaction");
+}
+
+#[tokio::test]
+async fn test_same_day_entries_listed_by_event_time() {
+    let test_db = TestDb::new().await;
+    let db = test_db.conn();
+    let org = OrgFixture::new()
+        .with_fiscal_year_2025()
+        .with_accounts(ACCOUNTS)
+        .create(db)
+        .await;
+
+    // Entered in reverse: the afternoon payment is recorded first
+    let afternoon = create_expense(db, &org, 200, at("2025-03-10T15:00:00Z"))
+        .await
+        .expect("Failed to create transaction");
+    let morning = create_expense(db, &org, 100, at("2025-03-10T09:00:00Z"))
+        .await
+        .expect("Failed to create transaction");
+    post(db, &org, afternoon).await;
+    post(db, &org, morning).await;
+
+    let cash = org.account("1000");
+    let ledger = AccountRepository::new(db.clone())
+        .get_ledger_entries(cash, None, None, false, 1, 50)
+        .await
+        .expect("Failed to get ledger");
+    let order: Vec<Uuid> = ledger
+        .entries
+        .iter()
+        .map(|e| e.entry.transaction_id)
+        .collect();
+    assert_eq!(order, vec![afternoon, morning]);
+    assert_eq!(ledger.entries[1].entry.event_at, at("2025-03-10T09:00:00Z"));
+
+    let (entries, total) = ReportRepository::new(db.clone())
+        .query_account_ledger(
+            org.id.into_inner(),
+            cash.into_inner(),
+            date(),
+            date(),
+            0,
+            50,
+        )
+        .await
+        .expect("Failed to get account ledger");
+    assert_eq!(total, 2);
+    let order: Vec<Uuid> = entries.iter().map(|e| e.transaction_id).collect();
+    assert_eq!(order, vec![morning, afternoon]);
+}
+
+#[tokio::test]
+async fn test_event_time_must_fall_on_transaction_date() {
+    let test_db = TestDb::new().await;
+    let db = test_db.conn();
+    let org = OrgFixture::new()
+        .with_fiscal_year_2025()
+        .with_accounts(ACCOUNTS)
+        .create(db)
+        .await;
+
+    let result = create_expense(db, &org, 100, at("2025-03-11T00:00:00Z")).await;
+    assert!(matches!(
+        result,
+        Err(TransactionError::EventOutsideTransactionDate { transaction_date, .. })
+            if transaction_date == date()
+    ));
+
+    // The day is taken in the organization's timezone (UTC+7)
+    organizations::ActiveModel {
+        id: Set(org.id.into_inner()),
+        timezone: Set("Asia/Jakarta".to_string()),
+        ..Default::default()
+    }
+    .update(db)
+    .await
+    .expect("Failed to update timezone");
+
+    create_expense(db, &org, 100, at("2025-03-09T18:00:00Z"))
+        .await
+        .expect("01:00 in Jakarta is on the transaction date");
+    let result = create_expense(db, &org, 100, at("2025-03-10T18:00:00Z")).await;
+    assert!(matches!(
+        result,
+        Err(TransactionError::EventOutsideTransactionDate { .. })
+    ));
+}
@@ -0,0 +1,204 @@
+//! Integration tests for ledger integrity verification.
+
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use sea_orm::{ColumnTrait, EntityTrait, QueryFilter, sea_query::Expr};
+use uuid::Uuid;
+
+use zeltra_core::ledger::IntegrityStatus;
+use zeltra_db::entities::{
+    ledger_entries,
+    sea_orm_active_enums::{AccountType, TransactionType},
+};
+use zeltra_db::repositories::{
+    AccountRepository, LedgerIntegrityAccount,
+    transaction::{CreateLedgerEntryInput, CreateTransactionInput, TransactionRepository},
+};
+use zeltra_test_support::{Org, OrgFixture, TestDb};
+
+const ACCOUNTS: &[(&str, AccountType)] = &[
+    ("1000", AccountType::Asset),
+    ("4000", AccountType::Revenue),
+    ("5000", AccountType::Expense),
+];
+
+async fn create_sale(repo: &TransactionRepository, org: &Org, cents: i64) -> Uuid {
+    let amount = Decimal::new(cents, 2);
+    let entry = |account: &str, debit: Decimal, credit: Decimal| CreateLedgerEntryInput {
+        account_id: org.account(account).into_inner(),
+        source_currency: "USD".to_string(),
+        source_amount: amount,
+        exchange_rate: Decimal::ONE,
+        functional_currency: "USD".to_string(),
+        functional_amount: amount,
+        debit,
+        credit,
+        memo: None,
+        dimensions: vec![],
+        rate_override: None,
+        event_at: None,
+    };
+    repo.create_transaction(CreateTransactionInput {
+        organization_id: org.id.into_inner(),
+        transaction_type: TransactionType::Journal,
+        transaction_date: NaiveDate::from_ymd_opt(2025, 3, 10).unwrap(),
+        description: "Cash sale".to_string(),
+        reference_number: None,
+        memo: None,
+        entries: vec![
+            entry("1000", amount, Decimal::ZERO),
+            entry("4000", Decimal::ZERO, amount),
+        ],
+        created_by: org.owner.user_id.into_inner(),
+    })
+    .await
+    .expect("Failed to create transaction")
+    .transaction
+    .id
+}
+
+/// The cash entry of a transaction.
+async fn cash_entry(db: &sea_orm::DatabaseConnection, org: &Org, transaction_id: Uuid) -> Uuid {
+    ledger_entries::Entity::find()
+        .filter(ledger_entries::Column::TransactionId.eq(transaction_id))
+        .filter(ledger_entries::Column::AccountId.eq(org.account("1000").into_inner()))
+        .one(db)
+        .await
+        .unwrap()
+        .unwrap()
+        .id
+}
+
+fn account<'a>(report: &'a [LedgerIntegrityAccount], code: &str) -> &'a LedgerIntegrityAccount {
+    report.iter().find(|a| a.code == code).unwrap()
+}
+
+#[tokio::test]
+async fn test_untouched_ledger_is_ok() {
+    let test_db = TestDb::new().await;
+    let db = test_db.conn();
+    let org = OrgFixture::new()
+        .with_fiscal_year_2025()
+        .with_accounts(ACCOUNTS)
+        .create(db)
+        .await;
+    let repo = TransactionRepository::new(db.clone());
+    for cents in [10_000, 2_550, 99] {
+        create_sale(&repo, &org, cents).await;
+    }
+
+    let report = AccountRepository::new(db.clone())
+        .verify_ledger_integrity(org.id)
+        .await
+        .unwrap();
+
+    assert_eq!(report.len(), ACCOUNTS.len());
+    assert!(
+        report
+            .iter()
+            .all(|a| a.integrity.status == IntegrityStatus::Ok)
+    );
+    let cash = account(&report, "1000");
+    assert_eq!(cash.integrity.entries_checked, 3);
+    assert_eq!(cash.integrity.expected_balance, Decimal::new(12_649, 2));
+    // Accounts without entries are reported too
+    assert_eq!(account(&report, "5000").integrity.entries_checked, 0);
+}
+
+#[tokio::test]
+async fn test_corrupted_row_is_pinpointed() {
+    let test_db = TestDb::new().await;
+    let db = test_db.conn();
+    let org = OrgFixture::new()
+        .with_fiscal_year_2025()
+        .with_accounts(ACCOUNTS)
+        .create(db)
+        .await;
+    let repo = TransactionRepository::new(db.clone());
+    create_sale(&repo, &org, 10_000).await;
+    let second = create_sale(&repo, &org, 2_500).await;
+    create_sale(&repo, &org, 500).await;
+
+    let corrupted = cash_entry(db, &org, second).await;
+    ledger_entries::Entity::update_many()
+        .col_expr(
+            ledger_entries::Column::AccountCurrentBalance,
+            Expr::value(Decimal::new(12_600, 2)),
+        )
+        .filter(ledger_entries::Column::Id.eq(corrupted))
+        .exec(db)
+        .await
+        .unwrap();
+
+    let report = AccountRepository::new(db.clone())
+        .verify_ledger_integrity(org.id)
+        .await
+        .unwrap();
+
+    let cash = account(&report, "1000");
+    assert_eq!(cash.integrity.status, IntegrityStatus::Diverged);
+    assert_eq!(cash.integrity.divergent_entries, 1);
+    let divergence = cash.integrity.first_divergence.unwrap();
+    assert_eq!(divergence.entry_id, corrupted);
+    assert_eq!(divergence.account_version, 2);
+    assert_eq!(divergence.expected_balance, Decimal::new(12_500, 2));
+    assert_eq!(divergence.delta, Decimal::ONE);
+
+    assert_eq!(
+        account(&report, "4000").integrity.status,
+        IntegrityStatus::Ok
+    );
+}
+
+#[tokio::test]
+async fn test_deleted_draft_reports_version_gap() {
+    let test_db = TestDb::new().await;
+    let db = test_db.conn();
+    let org = OrgFixture::new()
+        .with_fiscal_year_2025()
+        .with_accounts(ACCOUNTS)
+        .create(db)
+        .await;
+    let repo = TransactionRepository::new(db.clone());
+    create_sale(&repo, &org, 10_000).await;
+    let draft = create_sale(&repo, &org, 2_500).await;
+    let last = create_sale(&repo, &org, 500).await;
+    repo.delete_transaction(org.id, draft.into()).await.unwrap();
+
+    let report = AccountRepository::new(db.clone())
+        .verify_ledger_integrity(org.id)
+        .await
+        .unwrap();
+
+    let cash = account(&report, "1000");
+    assert_eq!(cash.integrity.status, IntegrityStatus::VersionGap);
+    assert!(cash.integrity.first_divergence.is_none());
+    let gap = cash.integrity.first_gap.unwrap();
+    assert_eq!(gap.entry_id, cash_entry(db, &org, last).await);
+    assert_eq!(gap.expected_version, 2);
+    assert_eq!(gap.actual_version, 3);
+}
+
+#[tokio::test]
+async fn test_other_organizations_are_not_checked() {
+    let test_db = TestDb::new().await;
+    let db = test_db.conn();
+    let org = OrgFixture::new()
+        .with_fiscal_year_2025()
+        .with_accounts(ACCOUNTS)
+        .create(db)
+        .await;
+    let other = OrgFixture::new()
+        .with_fiscal_year_2025()
+        .with_accounts(ACCOUNTS)
+        .create(db)
+        .await;
+    create_sale(&TransactionRepository::new(db.clone()), &other, 100).await;
+
+    let report = AccountRepository::new(db.clone())
+        .verify_ledger_integrity(org.id)
+        .await
+        .unwrap();
+
+    assert!(report.iter().all(|a| a.integrity.entries_checked == 0));
+}
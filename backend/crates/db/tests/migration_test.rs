@@ -0,0 +1,109 @@
+//! Integration tests for running the migrations up and down.
+
+use sea_orm::{ConnectionTrait, DatabaseConnection, DbBackend, Statement};
+
+use zeltra_db::migration::replaceable::{self, FUNCTIONS, ReplaceableObject, VIEWS};
+use zeltra_db::migration::{Migrator, MigratorTrait};
+use zeltra_test_support::TestDb;
+
+async fn missing(db: &DatabaseConnection, objects: &[ReplaceableObject]) -> Vec<String> {
+    let mut missing = Vec::new();
+    for object in objects {
+        if !object.exists(db).await.unwrap() {
+            missing.push(object.signature());
+        }
+    }
+    missing
+}
+
+async fn present(db: &DatabaseConnection, objects: &[ReplaceableObject]) -> Vec<String> {
+    let mut present = Vec::new();
+    for object in objects {
+        if object.exists(db).await.unwrap() {
+            present.push(object.signature());
+        }
+    }
+    present
+}
+
+async fn function_source(db: &DatabaseConnection, name: &str) -> String {
+    let row = db
+        .query_one(Statement::from_sql_and_values(
+            DbBackend::Postgres,
+            "SELECT prosrc FROM pg_proc WHERE proname = $1",
+            [name.into()],
+        ))
+        .await
+        .unwrap()
+        .unwrap();
+    row.try_get("", "prosrc").unwrap()
+}
+
+#[tokio::test]
+async fn test_migrations_round_trip() {
+    let test_db = TestDb::empty().await;
+    let db = test_db.conn();
+
+    Migrator::up(db, None).await.expect("Failed to migrate up");
+    assert!(missing(db, FUNCTIONS).await.is_empty());
+    assert!(missing(db, VIEWS).await.is_empty());
+    assert!(
+        function_source(db, "get_account_balance_at")
+            .await
+            .contains("event_at")
+    );
+
+    Migrator::down(db, None)
+        .await
+        .expect("Failed to migrate down");
+    assert!(
+        Migrator::get_applied_migrations(db)
+            .await
+            .unwrap()
+            .is_empty()
+    );
+    assert!(present(db, FUNCTIONS).await.is_empty());
+    assert!(present(db, VIEWS).await.is_empty());
+
+    Migrator::up(db, None)
+        .await
+        .expect("Failed to migrate up again");
+    assert!(missing(db, FUNCTIONS).await.is_empty());
+    assert!(missing(db, VIEWS).await.is_empty());
+    assert!(
+        Migrator::get_pending_migrations(db)
+            .await
+            .unwrap()
+            .is_empty()
+    );
+}
+
+#[tokio::test]
+async fn test_replaceable_objects_can_be_reapplied() {
+    let test_db = TestDb::new().await;
+    let db = test_db.conn();
+
+    // Re-applying the current versions over themselves changes nothing
+    replaceable::replace_all(db, FUNCTIONS).await.unwrap();
+    replaceable::replace_all(db, VIEWS).await.unwrap();
+    replaceable::replace_all(db, FUNCTIONS).await.unwrap();
+    assert!(missing(db, FUNCTIONS).await.is_empty());
+    assert!(missing(db, VIEWS).await.is_empty());
+
+    // Rolling back the extracting migrations keeps what the earlier ones made
+    Migrator::down(db, Some(2)).await.unwrap();
+    assert!(missing(db, FUNCTIONS).await.is_empty());
+    assert!(missing(db, VIEWS).await.is_empty());
+    assert!(
+        function_source(db, "get_account_balance_at")
+            .await
+            .contains("event_at")
+    );
+    Migrator::up(db, None).await.unwrap();
+    assert!(
+        Migrator::get_pending_migrations(db)
+            .await
+            .unwrap()
+            .is_empty()
+    );
+}
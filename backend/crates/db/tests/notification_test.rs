@@ -0,0 +1,206 @@
+//! Integration tests for notifications and delivery preferences.
+
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use serde_json::json;
+use uuid::Uuid;
+
+use zeltra_core::notification::{Delivery, NotificationType};
+use zeltra_db::entities::sea_orm_active_enums::{AccountType, TransactionType, UserRole};
+use zeltra_db::repositories::notification::{NotificationRepoError, NotificationRepository};
+use zeltra_db::repositories::transaction::{
+    CreateLedgerEntryInput, CreateTransactionInput, TransactionRepository,
+};
+use zeltra_db::repositories::workflow::WorkflowRepository;
+use zeltra_shared::types::TransactionId;
+use zeltra_test_support::{OrgFixture, TestDb};
+
+const ACCOUNTS: &[(&str, AccountType)] =
+    &[("1000", AccountType::Asset), ("5000", AccountType::Expense)];
+
+#[tokio::test]
+async fn test_notify_honours_delivery_preferences() {
+    let test_db = TestDb::new().await;
+    let db = test_db.conn();
+    let org = OrgFixture::new()
+        .with_member(UserRole::Approver)
+        .with_member(UserRole::Accountant)
+        .create(db)
+        .await;
+    let owner = org.owner.user_id.into_inner();
+    let approver = org.member(&UserRole::Approver).user_id.into_inner();
+    let accountant = org.member(&UserRole::Accountant).user_id.into_inner();
+    let repo = NotificationRepository::new(db.clone());
+
+    repo.set_preferences(
+        approver,
+        &[(NotificationType::ApprovalRequested, Delivery::Email)],
+    )
+    .await
+    .expect("Failed to set preferences");
+    let preferences = repo
+        .set_preferences(
+            accountant,
+            &[(NotificationType::ApprovalRequested, Delivery::InApp)],
+        )
+        .await
+        .expect("Failed to set preferences");
+    assert_eq!(preferences.len(), NotificationType::ALL.len());
+    assert!(preferences.contains(&(NotificationType::ApprovalRequested, Delivery::InApp)));
+    assert!(preferences.contains(&(NotificationType::TransactionRejected, Delivery::Both)));
+
+    let outcome = repo
+        .notify(
+            org.id.into_inner(),
+            NotificationType::ApprovalRequested,
+            json!({ "reference_number": "EXP-1" }),
+            &[owner, approver, accountant, owner],
+        )
+        .await
+        .expect("Failed to notify");
+
+    let notified: Vec<Uuid> = outcome.created.iter().map(|n| n.user_id).collect();
+    assert_eq!(notified, vec![owner, accountant]);
+    assert_eq!(outcome.email_recipients, vec![owner, approver]);
+    assert_eq!(outcome.created[0].payload["reference_number"], "EXP-1");
+
+    // Preferences are per type
+    let outcome = repo
+        .notify(
+            org.id.into_inner(),
+            NotificationType::ApprovalReminder,
+            json!({}),
+            &[approver],
+        )
+        .await
+        .expect("Failed to notify");
+    assert_eq!(outcome.created.len(), 1);
+    assert_eq!(outcome.email_recipients, vec![approver]);
+}
+
+#[tokio::test]
+async fn test_read_state_transitions() {
+    let test_db = TestDb::new().await;
+    let db = test_db.conn();
+    let org = OrgFixture::new()
+        .with_member(UserRole::Viewer)
+        .create(db)
+        .await;
+    let org_id = org.id.into_inner();
+    let owner = org.owner.user_id.into_inner();
+    let viewer = org.member(&UserRole::Viewer).user_id.into_inner();
+    let repo = NotificationRepository::new(db.clone());
+
+    let mut ids = Vec::new();
+    for n in 0..3 {
+        let outcome = repo
+            .notify(
+                org_id,
+                NotificationType::TransactionRejected,
+                json!({ "n": n }),
+                &[owner],
+            )
+            .await
+            .expect("Failed to notify");
+        ids.push(outcome.created[0].id);
+    }
+
+    let first = repo
+        .mark_read(org_id, owner, ids[0])
+        .await
+        .expect("Failed to mark read");
+    let read_at = first.read_at.expect("read_at is set");
+    let again = repo
+        .mark_read(org_id, owner, ids[0])
+        .await
+        .expect("Failed to mark read");
+    assert_eq!(again.read_at, Some(read_at));
+
+    // Unread first, the read one last
+    let page = repo
+        .list(org_id, owner, 1, 20)
+        .await
+        .expect("Failed to list");
+    assert_eq!(page.total, 3);
+    assert_eq!(page.unread, 2);
+    assert_eq!(page.notifications.last().map(|n| n.id), Some(ids[0]));
+    assert!(page.notifications[..2].iter().all(|n| n.read_at.is_none()));
+
+    // Other users cannot see or change the owner's notifications
+    let result = repo.mark_read(org_id, viewer, ids[1]).await;
+    assert!(matches!(result, Err(NotificationRepoError::NotFound(id)) if id == ids[1]));
+    let page = repo
+        .list(org_id, viewer, 1, 20)
+        .await
+        .expect("Failed to list");
+    assert_eq!(page.total, 0);
+
+    assert_eq!(repo.mark_all_read(org_id, owner).await.unwrap(), 2);
+    assert_eq!(repo.mark_all_read(org_id, owner).await.unwrap(), 0);
+    let page = repo
+        .list(org_id, owner, 1, 20)
+        .await
+        .expect("Failed to list");
+    assert_eq!(page.unread, 0);
+}
+
+#[tokio::test]
+async fn test_eligible_approvers_exclude_submitter_and_lower_roles() {
+    let test_db = TestDb::new().await;
+    let db = test_db.conn();
+    let org = OrgFixture::new()
+        .with_fiscal_year_2025()
+        .with_accounts(ACCOUNTS)
+        .with_member(UserRole::Approver)
+        .with_member(UserRole::Submitter)
+        .create(db)
+        .await;
+    let owner = org.owner.user_id.into_inner();
+    let amount = Decimal::new(100, 0);
+    let entry = |account: &str, debit: Decimal, credit: Decimal| CreateLedgerEntryInput {
+        account_id: org.account(account).into_inner(),
+        source_currency: "USD".to_string(),
+        source_amount: amount,
+        exchange_rate: Decimal::ONE,
+        functional_currency: "USD".to_string(),
+        functional_amount: amount,
+        debit,
+        credit,
+        memo: None,
+        dimensions: vec![],
+        rate_override: None,
+        event_at: None,
+    };
+    let created = TransactionRepository::new(db.clone())
+        .create_transaction(CreateTransactionInput {
+            organization_id: org.id.into_inner(),
+            transaction_type: TransactionType::Expense,
+            transaction_date: NaiveDate::from_ymd_opt(2025, 3, 10).unwrap(),
+            description: "Office supplies".to_string(),
+            reference_number: None,
+            memo: None,
+            entries: vec![
+                entry("5000", amount, Decimal::ZERO),
+                entry("1000", Decimal::ZERO, amount),
+            ],
+            created_by: owner,
+        })
+        .await
+        .expect("Failed to create transaction");
+    let transaction_id = TransactionId::from(created.transaction.id);
+    let workflow = WorkflowRepository::new(db.clone());
+    workflow
+        .submit_transaction(org.id, transaction_id, owner)
+        .await
+        .expect("Failed to submit transaction");
+
+    let approvers = workflow
+        .find_eligible_approvers(org.id, transaction_id)
+        .await
+        .expect("Failed to find approvers");
+    let ids: Vec<Uuid> = approvers.iter().map(|a| a.user_id).collect();
+    assert_eq!(
+        ids,
+        vec![org.member(&UserRole::Approver).user_id.into_inner()]
+    );
+}
@@ -0,0 +1,85 @@
+//! Integration tests for the operator audit log and account reactivation.
+
+use sea_orm::{ActiveModelTrait, Set};
+use serde_json::json;
+use uuid::Uuid;
+
+use zeltra_db::UserRepository;
+use zeltra_db::entities::users;
+use zeltra_db::repositories::{OperatorActionInput, OperatorAuditRepository};
+use zeltra_test_support::{OrgFixture, TestDb};
+
+fn action(command: &str, organization_id: Option<Uuid>) -> OperatorActionInput {
+    OperatorActionInput {
+        operator: "dana".to_string(),
+        command: command.to_string(),
+        target: "acme".to_string(),
+        organization_id,
+        executed: false,
+        reason: Some("Ticket 812".to_string()),
+        details: json!({ "step": command }),
+    }
+}
+
+#[tokio::test]
+async fn test_operations_are_listed_per_organization_newest_first() {
+    let test_db = TestDb::new().await;
+    let db = test_db.conn();
+    let org = OrgFixture::new().create(db).await;
+    let other = OrgFixture::new().create(db).await;
+    let org_id = Some(org.id.into_inner());
+    let repo = OperatorAuditRepository::new(db.clone());
+
+    let first = repo
+        .record(action("org info", org_id))
+        .await
+        .expect("Failed to record operation");
+    assert_eq!(first.operator, "dana");
+    assert_eq!(first.reason.as_deref(), Some("Ticket 812"));
+    assert_eq!(first.details, json!({ "step": "org info" }));
+    repo.record(action("transaction force-void", org_id))
+        .await
+        .unwrap();
+    repo.record(action("org info", Some(other.id.into_inner())))
+        .await
+        .unwrap();
+    repo.record(action("user unlock", None)).await.unwrap();
+
+    let listed = repo
+        .list_for_organization(org.id.into_inner(), 10)
+        .await
+        .unwrap();
+    let commands: Vec<&str> = listed.iter().map(|op| op.command.as_str()).collect();
+    assert_eq!(commands, ["transaction force-void", "org info"]);
+
+    let limited = repo
+        .list_for_organization(org.id.into_inner(), 1)
+        .await
+        .unwrap();
+    assert_eq!(limited.len(), 1);
+}
+
+#[tokio::test]
+async fn test_reactivate_only_changes_disabled_accounts() {
+    let test_db = TestDb::new().await;
+    let db = test_db.conn();
+    let org = OrgFixture::new().create(db).await;
+    let user_id = org.owner.user_id.into_inner();
+    let repo = UserRepository::new(db.clone());
+
+    assert!(!repo.reactivate(user_id).await.unwrap());
+
+    users::ActiveModel {
+        id: Set(user_id),
+        is_active: Set(false),
+        ..Default::default()
+    }
+    .update(db)
+    .await
+    .expect("Failed to disable user");
+
+    assert!(repo.reactivate(user_id).await.unwrap());
+    let user = repo.find_by_id(user_id).await.unwrap().unwrap();
+    assert!(user.is_active);
+    assert!(!repo.reactivate(user_id).await.unwrap());
+}
@@ -0,0 +1,160 @@
+//! Integration tests for bootstrapping new organizations.
+
+use chrono::Utc;
+use rust_decimal::Decimal;
+use sea_orm::{EntityTrait, PaginatorTrait};
+use uuid::Uuid;
+
+use zeltra_db::OrganizationRepository;
+use zeltra_db::entities::{
+    chart_of_accounts, fiscal_years, organizations,
+    sea_orm_active_enums::{TransactionStatus, TransactionType},
+};
+use zeltra_db::repositories::transaction::{
+    CreateLedgerEntryInput, CreateTransactionInput, TransactionRepository,
+};
+use zeltra_db::repositories::workflow::WorkflowRepository;
+use zeltra_db::repositories::{ChartTemplate, OrganizationBootstrap, OrganizationError};
+use zeltra_test_support::{OrgFixture, TestDb};
+
+fn unique_slug() -> String {
+    format!("boot-{}", &Uuid::new_v4().simple().to_string()[..12])
+}
+
+#[tokio::test]
+async fn test_bootstrapped_organization_posts_a_transaction() {
+    let test_db = TestDb::new().await;
+    let db = test_db.conn();
+    let creator = OrgFixture::new()
+        .create(db)
+        .await
+        .owner
+        .user_id
+        .into_inner();
+
+    let (org, summary) = OrganizationRepository::new(db.clone())
+        .create_bootstrapped(
+            "Bootstrapped",
+            &unique_slug(),
+            "USD",
+            "UTC",
+            creator,
+            OrganizationBootstrap {
+                fiscal_year: true,
+                chart_template: Some(ChartTemplate::Service),
+                dimension_types: true,
+            },
+        )
+        .await
+        .expect("Failed to create organization");
+
+    let fiscal_year = summary.fiscal_year.expect("fiscal year should be created");
+    assert_eq!(fiscal_year.fiscal_year.organization_id, org.id);
+    assert!(fiscal_year.coverage.is_empty());
+    assert_eq!(
+        summary.accounts.len(),
+        ChartTemplate::Service.accounts().len()
+    );
+    let codes: Vec<_> = summary
+        .dimension_types
+        .iter()
+        .map(|d| d.code.as_str())
+        .collect();
+    assert_eq!(codes, ["DEPARTMENT", "PROJECT"]);
+
+    // The new organization takes a posted transaction straight away
+    let account = |code: &str| summary.accounts.iter().find(|a| a.code == code).unwrap().id;
+    let entry = |account_id: Uuid, debit: i64, credit: i64| CreateLedgerEntryInput {
+        account_id,
+        source_currency: "USD".to_string(),
+        source_amount: Decimal::from(debit + credit),
+        exchange_rate: Decimal::ONE,
+        functional_currency: "USD".to_string(),
+        functional_amount: Decimal::from(debit + credit),
+        debit: Decimal::from(debit),
+        credit: Decimal::from(credit),
+        memo: None,
+        dimensions: vec![],
+        rate_override: None,
+        event_at: None,
+    };
+    let created = TransactionRepository::new(db.clone())
+        .create_transaction(CreateTransactionInput {
+            organization_id: org.id,
+            transaction_type: TransactionType::Invoice,
+            transaction_date: Utc::now().date_naive(),
+            description: "First invoice".to_string(),
+            reference_number: None,
+            memo: None,
+            entries: vec![
+                entry(account("1100"), 500, 0),
+                entry(account("4000"), 0, 500),
+            ],
+            created_by: creator,
+        })
+        .await
+        .expect("Failed to create transaction");
+    let id = created.transaction.id.into();
+    let workflow = WorkflowRepository::new(db.clone());
+    workflow
+        .submit_transaction(org.id.into(), id, creator)
+        .await
+        .expect("Failed to submit transaction");
+    workflow
+        .approve_transaction(org.id.into(), id, creator, None)
+        .await
+        .expect("Failed to approve transaction");
+    let posted = workflow
+        .post_transaction(org.id.into(), id, creator)
+        .await
+        .expect("Failed to post transaction");
+    assert_eq!(posted.status, TransactionStatus::Posted);
+}
+
+#[tokio::test]
+async fn test_failed_bootstrap_creates_nothing() {
+    let test_db = TestDb::new().await;
+    let db = test_db.conn();
+    let creator = OrgFixture::new()
+        .create(db)
+        .await
+        .owner
+        .user_id
+        .into_inner();
+    let organizations_before = organizations::Entity::find().count(db).await.unwrap();
+
+    // The fiscal year is created, then the chart fails: accounts need a
+    // known currency
+    let err = OrganizationRepository::new(db.clone())
+        .create_bootstrapped(
+            "Broken",
+            &unique_slug(),
+            "XTS",
+            "UTC",
+            creator,
+            OrganizationBootstrap {
+                fiscal_year: true,
+                chart_template: Some(ChartTemplate::Service),
+                dimension_types: true,
+            },
+        )
+        .await
+        .unwrap_err();
+
+    assert!(matches!(
+        err,
+        OrganizationError::BootstrapFailed {
+            step: "chart_of_accounts",
+            ..
+        }
+    ));
+    assert_eq!(
+        organizations::Entity::find().count(db).await.unwrap(),
+        organizations_before
+    );
+    assert_eq!(fiscal_years::Entity::find().count(db).await.unwrap(), 0);
+    assert_eq!(
+        chart_of_accounts::Entity::find().count(db).await.unwrap(),
+        0
+    );
+}
@@ -0,0 +1,437 @@
+//! Integration tests for cloning an organization's configuration.
+
+use std::collections::HashMap;
+
+use chrono::NaiveDate;
+use rust_decimal_macros::dec;
+use sea_orm::{ColumnTrait, DatabaseConnection, EntityTrait, PaginatorTrait, QueryFilter};
+use serde_json::json;
+use uuid::Uuid;
+
+use zeltra_db::OrganizationRepository;
+use zeltra_db::entities::sea_orm_active_enums::{AccountType, BudgetType, UserRole};
+use zeltra_db::entities::{
+    budget_line_dimensions, budget_lines, chart_of_accounts, dimension_values, fiscal_periods,
+    organization_users, transactions,
+};
+use zeltra_db::repositories::account::{AccountRepository, CreateAccountInput};
+use zeltra_db::repositories::approval_rule::{ApprovalRuleRepository, CreateApprovalRuleInput};
+use zeltra_db::repositories::backup::{
+    BackupError, BackupRepository, CloneOrganizationInput, CloneSections, Section,
+};
+use zeltra_db::repositories::budget::{BudgetRepository, CreateBudgetInput, CreateBudgetLineInput};
+use zeltra_db::repositories::dimension::{
+    CreateDimensionTypeInput, CreateDimensionValueInput, DimensionRepository,
+};
+use zeltra_shared::types::OrganizationId;
+use zeltra_test_support::{Line, Org, OrgFixture, TestDb, post_transaction};
+
+fn slug(prefix: &str) -> String {
+    format!("{prefix}-{}", &Uuid::new_v4().simple().to_string()[..12])
+}
+
+/// Creates an account under `parent`.
+async fn create_account(
+    db: &DatabaseConnection,
+    org: &Org,
+    code: &str,
+    account_type: AccountType,
+    parent_id: Option<Uuid>,
+) -> Uuid {
+    AccountRepository::new(db.clone())
+        .create_account(CreateAccountInput {
+            organization_id: org.id.into_inner(),
+            code: code.to_string(),
+            name: format!("Account {code}"),
+            description: None,
+            account_type,
+            account_subtype: None,
+            parent_id,
+            currency: "USD".to_string(),
+            is_active: true,
+            allow_direct_posting: true,
+            is_bank_account: false,
+            bank_account_number: None,
+        })
+        .await
+        .expect("Failed to create account")
+        .id
+}
+
+/// Creates a dimension value under `parent`.
+async fn create_value(
+    repo: &DimensionRepository,
+    org: &Org,
+    type_id: Uuid,
+    code: &str,
+    parent_id: Option<Uuid>,
+) -> Uuid {
+    repo.create_dimension_value(CreateDimensionValueInput {
+        organization_id: org.id.into_inner(),
+        dimension_type_id: type_id,
+        code: code.to_string(),
+        name: code.to_string(),
+        description: None,
+        parent_id,
+        is_active: true,
+        effective_from: None,
+        effective_to: None,
+    })
+    .await
+    .expect("Failed to create dimension value")
+    .id
+}
+
+/// Accounts of an organization by code, as `(id, parent code)`.
+async fn accounts_by_code(
+    db: &DatabaseConnection,
+    org_id: Uuid,
+) -> HashMap<String, (Uuid, Option<String>)> {
+    let accounts = chart_of_accounts::Entity::find()
+        .filter(chart_of_accounts::Column::OrganizationId.eq(org_id))
+        .all(db)
+        .await
+        .unwrap();
+    let codes: HashMap<Uuid, String> = accounts.iter().map(|a| (a.id, a.code.clone())).collect();
+    accounts
+        .iter()
+        .map(|a| {
+            let parent = a.parent_id.map(|p| codes[&p].clone());
+            (a.code.clone(), (a.id, parent))
+        })
+        .collect()
+}
+
+/// Dimension values of an organization by code, as `(id, parent code)`.
+async fn values_by_code(
+    db: &DatabaseConnection,
+    org_id: Uuid,
+) -> HashMap<String, (Uuid, Option<String>)> {
+    let values = dimension_values::Entity::find()
+        .filter(dimension_values::Column::OrganizationId.eq(org_id))
+        .all(db)
+        .await
+        .unwrap();
+    let codes: HashMap<Uuid, String> = values.iter().map(|v| (v.id, v.code.clone())).collect();
+    values
+        .iter()
+        .map(|v| {
+            let parent = v.parent_id.map(|p| codes[&p].clone());
+            (v.code.clone(), (v.id, parent))
+        })
+        .collect()
+}
+
+#[tokio::test]
+#[allow(clippy::too_many_lines)]
+async fn test_clone_preserves_hierarchies_under_new_ids() {
+    let test_db = TestDb::new().await;
+    let db = test_db.conn();
+    let mut org = OrgFixture::new()
+        .with_fiscal_year_2025()
+        .with_member(UserRole::Accountant)
+        .create(db)
+        .await;
+    let org_id = org.id.into_inner();
+    let owner_id = org.owner.user_id.into_inner();
+
+    let assets = create_account(db, &org, "1000", AccountType::Asset, None).await;
+    let current = create_account(db, &org, "1100", AccountType::Asset, Some(assets)).await;
+    let cash = create_account(db, &org, "1110", AccountType::Asset, Some(current)).await;
+    let expense = create_account(db, &org, "6000", AccountType::Expense, None).await;
+    org.accounts.insert("1110".to_string(), cash.into());
+    org.accounts.insert("6000".to_string(), expense.into());
+
+    let dimensions = DimensionRepository::new(db.clone());
+    let dept = dimensions
+        .create_dimension_type(CreateDimensionTypeInput {
+            organization_id: org_id,
+            code: "DEPT".to_string(),
+            name: "Department".to_string(),
+            description: None,
+            is_required: false,
+            is_active: true,
+            sort_order: 0,
+        })
+        .await
+        .expect("Failed to create dimension type")
+        .id;
+    let eng = create_value(&dimensions, &org, dept, "ENG", None).await;
+    let platform = create_value(&dimensions, &org, dept, "ENG-PLATFORM", Some(eng)).await;
+    create_value(&dimensions, &org, dept, "ENG-PLATFORM-API", Some(platform)).await;
+    dimensions
+        .add_account_default(org_id, expense, eng)
+        .await
+        .expect("Failed to add default dimension");
+
+    let budgets = BudgetRepository::new(db.clone());
+    let fiscal_year = org.fiscal_year.as_ref().unwrap();
+    let budget = budgets
+        .create_budget(CreateBudgetInput {
+            organization_id: org_id,
+            fiscal_year_id: fiscal_year.id.into_inner(),
+            name: "Operating".to_string(),
+            description: None,
+            budget_type: BudgetType::Monthly,
+            currency: "USD".to_string(),
+            created_by: owner_id,
+        })
+        .await
+        .expect("Failed to create budget");
+    budgets
+        .replace_lines(
+            org_id,
+            budget.id,
+            owner_id,
+            vec![CreateBudgetLineInput {
+                account_id: expense,
+                fiscal_period_id: fiscal_year.periods[2].into_inner(),
+                amount: dec!(500),
+                notes: None,
+                dimensions: vec![platform],
+            }],
+        )
+        .await
+        .expect("Failed to add budget lines");
+
+    ApprovalRuleRepository::new(db.clone())
+        .create_rule(
+            org_id,
+            CreateApprovalRuleInput {
+                name: "Large expenses".to_string(),
+                description: None,
+                min_amount: Some(dec!(1000)),
+                max_amount: None,
+                transaction_types: vec!["expense".to_string()],
+                required_role: "approver".to_string(),
+                priority: 1,
+                required_approvals: 1,
+            },
+        )
+        .await
+        .expect("Failed to create approval rule");
+
+    OrganizationRepository::new(db.clone())
+        .update_settings(
+            org_id,
+            &json!({ "bank_import": { "suspense_account_id": cash } }),
+        )
+        .await
+        .expect("Failed to update settings");
+    post_transaction(
+        db,
+        &org,
+        NaiveDate::from_ymd_opt(2025, 3, 10).unwrap(),
+        vec![
+            Line::debit("6000", dec!(100)),
+            Line::credit("1110", dec!(100)),
+        ],
+    )
+    .await;
+
+    let summary = BackupRepository::new(db.clone())
+        .clone_organization(
+            org.id,
+            owner_id,
+            CloneOrganizationInput {
+                name: "Training sandbox".to_string(),
+                slug: slug("sandbox"),
+                sections: CloneSections::default(),
+            },
+        )
+        .await
+        .expect("Failed to clone organization");
+    let clone = &summary.organization;
+    assert_ne!(clone.id, org_id);
+    assert_eq!(clone.base_currency, "USD");
+
+    let counts: Vec<(&str, u64)> = summary.counts.iter().map(|(k, v)| (*k, *v)).collect();
+    assert_eq!(
+        counts,
+        [
+            ("account_default_dimensions", 1),
+            ("accounts", 4),
+            ("approval_rules", 1),
+            ("budget_line_dimensions", 1),
+            ("budget_lines", 1),
+            ("budgets", 1),
+            ("dimension_types", 1),
+            ("dimension_values", 3),
+            ("fiscal_periods", 12),
+            ("fiscal_years", 1),
+        ]
+    );
+
+    // Parent chains are the same, but every id is new
+    let source_accounts = accounts_by_code(db, org_id).await;
+    let cloned_accounts = accounts_by_code(db, clone.id).await;
+    assert_eq!(cloned_accounts.len(), source_accounts.len());
+    for (code, (id, parent)) in &cloned_accounts {
+        assert_ne!(*id, source_accounts[code].0);
+        assert_eq!(*parent, source_accounts[code].1);
+    }
+    assert_eq!(cloned_accounts["1110"].1.as_deref(), Some("1100"));
+    assert_eq!(cloned_accounts["1100"].1.as_deref(), Some("1000"));
+
+    let source_values = values_by_code(db, org_id).await;
+    let cloned_values = values_by_code(db, clone.id).await;
+    assert_eq!(cloned_values.len(), 3);
+    for (code, (id, parent)) in &cloned_values {
+        assert_ne!(*id, source_values[code].0);
+        assert_eq!(*parent, source_values[code].1);
+    }
+    assert_eq!(
+        cloned_values["ENG-PLATFORM-API"].1.as_deref(),
+        Some("ENG-PLATFORM")
+    );
+
+    // Budget lines point at the clone's account, period and value
+    let line = budget_lines::Entity::find()
+        .filter(budget_lines::Column::AccountId.eq(cloned_accounts["6000"].0))
+        .one(db)
+        .await
+        .unwrap()
+        .expect("Budget line was not cloned");
+    let period = fiscal_periods::Entity::find_by_id(line.fiscal_period_id)
+        .one(db)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(period.organization_id, clone.id);
+    assert_eq!(period.name, "March 2025");
+    let tag = budget_line_dimensions::Entity::find()
+        .filter(budget_line_dimensions::Column::BudgetLineId.eq(line.id))
+        .one(db)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(tag.dimension_value_id, cloned_values["ENG-PLATFORM"].0);
+
+    let defaults = dimensions
+        .list_account_defaults(clone.id, cloned_accounts["6000"].0)
+        .await
+        .unwrap();
+    assert_eq!(defaults.len(), 1);
+    assert_eq!(
+        defaults[0].default.dimension_value_id,
+        cloned_values["ENG"].0
+    );
+
+    let settings = OrganizationRepository::new(db.clone())
+        .get_settings(clone.id)
+        .await
+        .unwrap();
+    assert_eq!(
+        settings.bank_import.suspense_account_id,
+        Some(cloned_accounts["1110"].0)
+    );
+
+    // Only the caller comes along, and no transactions do
+    let members = organization_users::Entity::find()
+        .filter(organization_users::Column::OrganizationId.eq(clone.id))
+        .all(db)
+        .await
+        .unwrap();
+    assert_eq!(members.len(), 1);
+    assert_eq!(members[0].user_id, owner_id);
+    let copied = transactions::Entity::find()
+        .filter(transactions::Column::OrganizationId.eq(clone.id))
+        .count(db)
+        .await
+        .unwrap();
+    assert_eq!(copied, 0);
+}
+
+#[tokio::test]
+async fn test_clone_copies_only_selected_sections() {
+    let test_db = TestDb::new().await;
+    let db = test_db.conn();
+    let org = OrgFixture::new()
+        .with_fiscal_year_2025()
+        .with_accounts(&[("1000", AccountType::Asset)])
+        .create(db)
+        .await;
+    let owner_id = org.owner.user_id.into_inner();
+    let repo = BackupRepository::new(db.clone());
+    OrganizationRepository::new(db.clone())
+        .update_settings(
+            org.id.into_inner(),
+            &json!({ "bank_import": { "suspense_account_id": org.account("1000") } }),
+        )
+        .await
+        .expect("Failed to update settings");
+
+    // Budgets cannot be copied without the periods they are planned in
+    let taken = slug("partial");
+    let err = repo
+        .clone_organization(
+            org.id,
+            owner_id,
+            CloneOrganizationInput {
+                name: "Partial".to_string(),
+                slug: taken.clone(),
+                sections: CloneSections {
+                    fiscal_calendar: false,
+                    ..CloneSections::default()
+                },
+            },
+        )
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        err,
+        BackupError::MissingDependency {
+            section: Section::Budgets,
+            requires: Section::FiscalPeriods,
+        }
+    ));
+    assert!(
+        !OrganizationRepository::new(db.clone())
+            .slug_exists(&taken)
+            .await
+            .unwrap()
+    );
+
+    let summary = repo
+        .clone_organization(
+            org.id,
+            owner_id,
+            CloneOrganizationInput {
+                name: "Calendar only".to_string(),
+                slug: taken,
+                sections: CloneSections {
+                    accounts: false,
+                    dimensions: false,
+                    approval_rules: false,
+                    budgets: false,
+                    ..CloneSections::default()
+                },
+            },
+        )
+        .await
+        .unwrap();
+    assert_eq!(
+        summary.counts.keys().copied().collect::<Vec<_>>(),
+        ["fiscal_periods", "fiscal_years"]
+    );
+    // The suspense account was not copied, so the setting is cleared
+    let settings = OrganizationRepository::new(db.clone())
+        .get_settings(summary.organization.id)
+        .await
+        .unwrap();
+    assert_eq!(settings.bank_import.suspense_account_id, None);
+
+    let err = repo
+        .clone_organization(
+            OrganizationId::from(Uuid::new_v4()),
+            owner_id,
+            CloneOrganizationInput {
+                name: "Nothing".to_string(),
+                slug: slug("missing"),
+                sections: CloneSections::default(),
+            },
+        )
+        .await
+        .unwrap_err();
+    assert!(matches!(err, BackupError::OrganizationNotFound(_)));
+}
@@ -878,6 +878,8 @@ async fn test_data_preservation_on_user_removal() {
             description: None,
             is_active: true,
             allow_direct_posting: true,
+            is_bank_account: false,
+            bank_account_number: None,
         })
         .await
         .expect("Failed to create cash account");
@@ -897,6 +899,8 @@ async fn test_data_preservation_on_user_removal() {
             description: None,
             is_active: true,
             allow_direct_posting: true,
+            is_bank_account: false,
+            bank_account_number: None,
         })
         .await
         .expect("Failed to create expense account");
@@ -923,6 +927,8 @@ async fn test_data_preservation_on_user_removal() {
                     credit: dec!(0),
                     memo: None,
                     dimensions: vec![],
+                    rate_override: None,
+                    event_at: None,
                 },
                 CreateLedgerEntryInput {
                     account_id: cash_account.id,
@@ -935,6 +941,8 @@ async fn test_data_preservation_on_user_removal() {
                     credit: dec!(100.00),
                     memo: None,
                     dimensions: vec![],
+                    rate_override: None,
+                    event_at: None,
                 },
             ],
         })
@@ -952,7 +960,7 @@ async fn test_data_preservation_on_user_removal() {
     // Property 6: Data Preservation on Removal
     // All transactions and ledger entries created by that user SHALL remain unchanged
     let found_tx = tx_repo
-        .get_transaction(org.id, tx_id)
+        .get_transaction(org.id.into(), tx_id.into())
         .await
         .expect("Failed to get transaction");
 
@@ -1035,3 +1043,223 @@ async fn test_cross_tenant_isolation() {
     cleanup_org(&db, org1.id).await;
     cleanup_org(&db, org2.id).await;
 }
+
+// ============================================================================
+// Integration Tests for token version invalidation
+// ============================================================================
+
+use rust_decimal::Decimal;
+use zeltra_db::UserRepository;
+
+#[tokio::test]
+async fn test_member_changes_bump_token_version() {
+    let db = Database::connect(&get_database_url())
+        .await
+        .expect("Failed to connect to database");
+
+    let owner_id = create_test_user(&db).await;
+    let member_id = create_test_user(&db).await;
+    let org_repo = OrganizationRepository::new(db.clone());
+    let user_repo = UserRepository::new(db.clone());
+
+    let org = org_repo
+        .create_with_owner(
+            "Token Version Org",
+            &format!("token-version-{}", Uuid::new_v4()),
+            "USD",
+            "UTC",
+            owner_id,
+        )
+        .await
+        .expect("Failed to create organization");
+
+    org_repo
+        .add_user(org.id, member_id, UserRole::Viewer, None)
+        .await
+        .expect("Failed to add member");
+
+    assert_eq!(
+        user_repo.get_token_version(member_id).await.unwrap(),
+        Some(0)
+    );
+
+    // Role change invalidates outstanding tokens
+    org_repo
+        .update_member(
+            org.id,
+            member_id,
+            &UserRole::Owner,
+            Some(UserRole::Approver),
+            Some(Some(Decimal::from(1000))),
+        )
+        .await
+        .expect("Failed to update member");
+    assert_eq!(
+        user_repo.get_token_version(member_id).await.unwrap(),
+        Some(1)
+    );
+
+    // Failed updates leave the version untouched
+    let result = org_repo
+        .update_member(org.id, member_id, &UserRole::Owner, None, None)
+        .await;
+    assert!(matches!(result, Err(OrganizationError::EmptyUpdate)));
+    assert_eq!(
+        user_repo.get_token_version(member_id).await.unwrap(),
+        Some(1)
+    );
+
+    // Removal invalidates as well
+    org_repo
+        .remove_member(org.id, member_id, &UserRole::Owner)
+        .await
+        .expect("Failed to remove member");
+    assert_eq!(
+        user_repo.get_token_version(member_id).await.unwrap(),
+        Some(2)
+    );
+
+    // The owner was never touched
+    assert_eq!(
+        user_repo.get_token_version(owner_id).await.unwrap(),
+        Some(0)
+    );
+    assert_eq!(
+        user_repo.get_token_version(Uuid::new_v4()).await.unwrap(),
+        None
+    );
+
+    cleanup_org(&db, org.id).await;
+}
+
+// ============================================================================
+// Integration Tests for organization settings
+// ============================================================================
+
+use serde_json::json;
+use zeltra_core::settings::SettingsError;
+use zeltra_test_support::{OrgFixture, TestDb};
+
+#[tokio::test]
+async fn test_get_settings_applies_defaults() {
+    let test_db = TestDb::new().await;
+    let org = OrgFixture::new().create(test_db.conn()).await;
+    let repo = OrganizationRepository::new(test_db.conn().clone());
+
+    let settings = repo.get_settings(org.id.into_inner()).await.unwrap();
+
+    assert_eq!(settings.bank_import.suspense_account_id, None);
+    assert_eq!(settings.updated_at, None);
+}
+
+#[tokio::test]
+async fn test_update_settings_merges_and_bumps_marker() {
+    let test_db = TestDb::new().await;
+    let org = OrgFixture::new()
+        .with_accounts(&[("9999", AccountType::Asset)])
+        .create(test_db.conn())
+        .await;
+    let repo = OrganizationRepository::new(test_db.conn().clone());
+    let suspense = org.account("9999").into_inner();
+
+    let updated = repo
+        .update_settings(
+            org.id.into_inner(),
+            &json!({ "bank_import": { "suspense_account_id": suspense } }),
+        )
+        .await
+        .unwrap();
+    assert_eq!(updated.bank_import.suspense_account_id, Some(suspense));
+    assert!(updated.updated_at.is_some());
+
+    // An empty section patch leaves the stored value alone
+    let again = repo
+        .update_settings(org.id.into_inner(), &json!({ "bank_import": {} }))
+        .await
+        .unwrap();
+    assert_eq!(again.bank_import.suspense_account_id, Some(suspense));
+    assert!(again.updated_at >= updated.updated_at);
+
+    let stored = repo.get_settings(org.id.into_inner()).await.unwrap();
+    assert_eq!(stored, again);
+}
+
+#[tokio::test]
+async fn test_update_settings_rejects_unknown_keys() {
+    let test_db = TestDb::new().await;
+    let org = OrgFixture::new().create(test_db.conn()).await;
+    let repo = OrganizationRepository::new(test_db.conn().clone());
+
+    let result = repo
+        .update_settings(org.id.into_inner(), &json!({ "theme": "dark" }))
+        .await;
+
+    match result {
+        Err(OrganizationError::InvalidSettings(SettingsError::Invalid(fields))) => {
+            assert_eq!(fields.len(), 1);
+            assert_eq!(fields[0].field, "theme");
+        }
+        other => panic!("Expected InvalidSettings, got {other:?}"),
+    }
+}
+
+#[tokio::test]
+async fn test_update_settings_rejects_foreign_suspense_account() {
+    let test_db = TestDb::new().await;
+    let org = OrgFixture::new().create(test_db.conn()).await;
+    let other = OrgFixture::new()
+        .with_accounts(&[("9999", AccountType::Asset)])
+        .create(test_db.conn())
+        .await;
+    let repo = OrganizationRepository::new(test_db.conn().clone());
+
+    let result = repo
+        .update_settings(
+            org.id.into_inner(),
+            &json!({ "bank_import": { "suspense_account_id": other.account("9999") } }),
+        )
+        .await;
+
+    match result {
+        Err(OrganizationError::InvalidSettings(SettingsError::Invalid(fields))) => {
+            assert_eq!(fields[0].field, "bank_import.suspense_account_id");
+        }
+        other => panic!("Expected InvalidSettings, got {other:?}"),
+    }
+    let stored = repo.get_settings(org.id.into_inner()).await.unwrap();
+    assert_eq!(stored.bank_import.suspense_account_id, None);
+}
+
+#[tokio::test]
+async fn test_rename_slug_keeps_history_and_allows_reclaiming_own_slug() {
+    let test_db = TestDb::new().await;
+    let org = OrgFixture::new().create(test_db.conn()).await;
+    let other = OrgFixture::new().create(test_db.conn()).await;
+    let repo = OrganizationRepository::new(test_db.conn().clone());
+    let org_id = org.id.into_inner();
+    let original = repo.find_by_id(org_id).await.unwrap().unwrap().slug;
+    let renamed = format!("acme-{}", &original[5..17]);
+
+    let updated = repo.rename_slug(org_id, &renamed).await.unwrap();
+    assert_eq!(updated.slug, renamed);
+    assert!(repo.slug_exists(&original).await.unwrap());
+    assert_eq!(
+        repo.find_by_slug(&original).await.unwrap().map(|o| o.slug),
+        Some(renamed.clone())
+    );
+
+    // Another organization cannot take the retired slug
+    let result = repo.rename_slug(other.id.into_inner(), &original).await;
+    assert!(matches!(result, Err(OrganizationError::SlugTaken)));
+
+    // The original owner can
+    let restored = repo.rename_slug(org_id, &original).await.unwrap();
+    assert_eq!(restored.slug, original);
+    assert_eq!(
+        repo.find_by_slug(&renamed).await.unwrap().map(|o| o.id),
+        Some(org_id)
+    );
+
+    let result = repo.rename_slug(org_id, "Not A Slug").await;
+    assert!(matches!(result, Err(OrganizationError::InvalidSlug(_))));
+}
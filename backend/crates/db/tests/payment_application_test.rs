@@ -0,0 +1,291 @@
+//! Integration tests for applying payments to invoices.
+
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use sea_orm::DatabaseConnection;
+use uuid::Uuid;
+
+use zeltra_core::ledger::{ApplicationError, SettlementStatus};
+use zeltra_core::workflow::VoidReasonCode;
+use zeltra_db::entities::sea_orm_active_enums::{AccountType, TransactionType};
+use zeltra_db::repositories::payment::{ApplyPaymentInput, PaymentError, PaymentRepository};
+use zeltra_db::repositories::transaction::{
+    CreateLedgerEntryInput, CreateTransactionInput, TransactionRepository,
+};
+use zeltra_db::repositories::workflow::WorkflowRepository;
+use zeltra_shared::types::TransactionId;
+use zeltra_test_support::{Org, OrgFixture, TestDb};
+
+const ACCOUNTS: &[(&str, AccountType)] = &[
+    ("1000", AccountType::Asset),
+    ("1200", AccountType::Asset),
+    ("4000", AccountType::Revenue),
+];
+
+async fn create(
+    db: &DatabaseConnection,
+    org: &Org,
+    transaction_type: TransactionType,
+    debit_account: &str,
+    credit_account: &str,
+    amount: i64,
+) -> TransactionId {
+    let amount = Decimal::new(amount, 0);
+    let entry = |account: Uuid, debit: Decimal, credit: Decimal| CreateLedgerEntryInput {
+        account_id: account,
+        source_currency: "USD".to_string(),
+        source_amount: debit + credit,
+        exchange_rate: Decimal::ONE,
+        functional_currency: "USD".to_string(),
+        functional_amount: debit + credit,
+        debit,
+        credit,
+        memo: None,
+        dimensions: vec![],
+        rate_override: None,
+        event_at: None,
+    };
+    let created = TransactionRepository::new(db.clone())
+        .create_transaction(CreateTransactionInput {
+            organization_id: org.id.into_inner(),
+            transaction_type,
+            transaction_date: NaiveDate::from_ymd_opt(2025, 5, 12).unwrap(),
+            description: "Consulting".to_string(),
+            reference_number: None,
+            memo: None,
+            entries: vec![
+                entry(
+                    org.account(debit_account).into_inner(),
+                    amount,
+                    Decimal::ZERO,
+                ),
+                entry(
+                    org.account(credit_account).into_inner(),
+                    Decimal::ZERO,
+                    amount,
+                ),
+            ],
+            created_by: org.owner.user_id.into_inner(),
+        })
+        .await
+        .expect("Failed to create transaction");
+    TransactionId::from(created.transaction.id)
+}
+
+async fn post(db: &DatabaseConnection, org: &Org, id: TransactionId) {
+    let user_id = org.owner.user_id.into_inner();
+    let workflow = WorkflowRepository::new(db.clone());
+    workflow
+        .submit_transaction(org.id, id, user_id)
+        .await
+        .expect("Failed to submit transaction");
+    workflow
+        .approve_transaction(org.id, id, user_id, None)
+        .await
+        .expect("Failed to approve transaction");
+    workflow
+        .post_transaction(org.id, id, user_id)
+        .await
+        .expect("Failed to post transaction");
+}
+
+/// Posts a receivable invoice for `amount`.
+async fn post_invoice(db: &DatabaseConnection, org: &Org, amount: i64) -> TransactionId {
+    let id = create(db, org, TransactionType::Invoice, "1200", "4000", amount).await;
+    post(db, org, id).await;
+    id
+}
+
+/// Posts a customer payment for `amount`.
+async fn post_payment(db: &DatabaseConnection, org: &Org, amount: i64) -> TransactionId {
+    let id = create(db, org, TransactionType::Payment, "1000", "1200", amount).await;
+    post(db, org, id).await;
+    id
+}
+
+fn apply(org: &Org, invoice: TransactionId, amount: i64) -> ApplyPaymentInput {
+    ApplyPaymentInput {
+        settles_transaction_id: invoice.into_inner(),
+        applied_amount: Decimal::new(amount, 0),
+        created_by: org.owner.user_id.into_inner(),
+    }
+}
+
+#[tokio::test]
+async fn test_invoice_settled_by_two_partial_payments() {
+    let test_db = TestDb::new().await;
+    let db = test_db.conn();
+    let org = OrgFixture::new()
+        .with_fiscal_year_2025()
+        .with_accounts(ACCOUNTS)
+        .create(db)
+        .await;
+    let payments = PaymentRepository::new(db.clone());
+
+    let invoice = post_invoice(db, &org, 1000).await;
+    let first = post_payment(db, &org, 400).await;
+    let second = post_payment(db, &org, 700).await;
+
+    let applied = payments
+        .apply_payment(org.id, first, apply(&org, invoice, 400))
+        .await
+        .expect("Failed to apply first payment");
+    assert_eq!(applied.settlement.status, SettlementStatus::PartiallyPaid);
+    assert_eq!(applied.settlement.outstanding(), Decimal::new(600, 0));
+
+    // The second payment is larger than what is left on the invoice
+    let err = payments
+        .apply_payment(org.id, second, apply(&org, invoice, 700))
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        err,
+        PaymentError::Application(ApplicationError::ExceedsOutstanding { outstanding, .. })
+            if outstanding == Decimal::new(600, 0)
+    ));
+
+    let applied = payments
+        .apply_payment(org.id, second, apply(&org, invoice, 600))
+        .await
+        .expect("Failed to apply second payment");
+    assert_eq!(applied.settlement.status, SettlementStatus::Paid);
+    assert_eq!(
+        payments.settled_amount(org.id, invoice).await.unwrap(),
+        Decimal::new(1000, 0)
+    );
+
+    // Unapplying the first payment reopens part of the invoice
+    let settlement = payments
+        .unapply_payment(org.id, first, invoice.into_inner())
+        .await
+        .expect("Failed to unapply payment");
+    assert_eq!(settlement.status, SettlementStatus::PartiallyPaid);
+    assert_eq!(settlement.applied, Decimal::new(600, 0));
+}
+
+#[tokio::test]
+async fn test_payment_cannot_apply_more_than_its_total() {
+    let test_db = TestDb::new().await;
+    let db = test_db.conn();
+    let org = OrgFixture::new()
+        .with_fiscal_year_2025()
+        .with_accounts(ACCOUNTS)
+        .create(db)
+        .await;
+    let payments = PaymentRepository::new(db.clone());
+
+    let first_invoice = post_invoice(db, &org, 300).await;
+    let second_invoice = post_invoice(db, &org, 300).await;
+    let payment = post_payment(db, &org, 500).await;
+
+    payments
+        .apply_payment(org.id, payment, apply(&org, first_invoice, 300))
+        .await
+        .expect("Failed to apply payment");
+    let err = payments
+        .apply_payment(org.id, payment, apply(&org, second_invoice, 250))
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        err,
+        PaymentError::Application(ApplicationError::ExceedsPayment { available, .. })
+            if available == Decimal::new(200, 0)
+    ));
+
+    // Applying to the same invoice twice is rejected
+    let err = payments
+        .apply_payment(org.id, payment, apply(&org, first_invoice, 1))
+        .await
+        .unwrap_err();
+    assert!(matches!(err, PaymentError::AlreadyApplied(_)));
+}
+
+#[tokio::test]
+async fn test_apply_requires_posted_payment_and_invoice() {
+    let test_db = TestDb::new().await;
+    let db = test_db.conn();
+    let org = OrgFixture::new()
+        .with_fiscal_year_2025()
+        .with_accounts(ACCOUNTS)
+        .create(db)
+        .await;
+    let payments = PaymentRepository::new(db.clone());
+
+    let invoice = post_invoice(db, &org, 100).await;
+    let payment = post_payment(db, &org, 100).await;
+    let draft_invoice = create(db, &org, TransactionType::Invoice, "1200", "4000", 100).await;
+    let draft_payment = create(db, &org, TransactionType::Payment, "1000", "1200", 100).await;
+
+    let err = payments
+        .apply_payment(org.id, draft_payment, apply(&org, invoice, 50))
+        .await
+        .unwrap_err();
+    assert!(matches!(err, PaymentError::NotPosted(_)));
+
+    let err = payments
+        .apply_payment(org.id, payment, apply(&org, draft_invoice, 50))
+        .await
+        .unwrap_err();
+    assert!(matches!(err, PaymentError::NotPosted(_)));
+
+    // A payment is not something a payment settles
+    let err = payments
+        .apply_payment(org.id, payment, apply(&org, draft_payment, 50))
+        .await
+        .unwrap_err();
+    assert!(matches!(err, PaymentError::NotSettleable(_)));
+
+    let err = payments
+        .apply_payment(org.id, invoice, apply(&org, invoice, 50))
+        .await
+        .unwrap_err();
+    assert!(matches!(err, PaymentError::NotAPayment(_)));
+}
+
+#[tokio::test]
+async fn test_voiding_payment_releases_applications() {
+    let test_db = TestDb::new().await;
+    let db = test_db.conn();
+    let org = OrgFixture::new()
+        .with_fiscal_year_2025()
+        .with_accounts(ACCOUNTS)
+        .create(db)
+        .await;
+    let payments = PaymentRepository::new(db.clone());
+
+    let invoice = post_invoice(db, &org, 1000).await;
+    let payment = post_payment(db, &org, 1000).await;
+    payments
+        .apply_payment(org.id, payment, apply(&org, invoice, 1000))
+        .await
+        .expect("Failed to apply payment");
+
+    WorkflowRepository::new(db.clone())
+        .void_transaction(
+            org.id,
+            payment,
+            org.owner.user_id.into_inner(),
+            VoidReasonCode::WrongAmount,
+            String::new(),
+        )
+        .await
+        .expect("Failed to void payment");
+
+    assert_eq!(
+        payments.settled_amount(org.id, invoice).await.unwrap(),
+        Decimal::ZERO
+    );
+    assert!(
+        payments
+            .list_applications(org.id, payment)
+            .await
+            .unwrap()
+            .is_empty()
+    );
+
+    let err = payments
+        .unapply_payment(org.id, payment, invoice.into_inner())
+        .await
+        .unwrap_err();
+    assert!(matches!(err, PaymentError::PaymentVoided(_)));
+}
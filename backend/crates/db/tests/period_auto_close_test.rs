@@ -0,0 +1,326 @@
+//! Integration tests for the fiscal period auto-close job.
+
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use sea_orm::{ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, QueryOrder};
+use serde_json::json;
+use uuid::Uuid;
+
+use zeltra_db::entities::sea_orm_active_enums::{
+    AccountType, FiscalPeriodStatus, TransactionType, UserRole,
+};
+use zeltra_db::entities::{fiscal_period_events, fiscal_periods};
+use zeltra_db::repositories::transaction::{
+    CreateLedgerEntryInput, CreateTransactionInput, TransactionRepository,
+};
+use zeltra_db::repositories::{
+    AutoCloseOutcome, AutoCloseRun, NotificationRepository, OrganizationRepository,
+    PeriodCloseError, PeriodCloseRepository, SYSTEM_USER_ID, WorkflowRepository,
+};
+use zeltra_shared::types::TransactionId;
+use zeltra_test_support::{Org, OrgFixture, TestDb};
+
+const ACCOUNTS: &[(&str, AccountType)] =
+    &[("1100", AccountType::Asset), ("4000", AccountType::Revenue)];
+
+fn date(m: u32, d: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(2025, m, d).unwrap()
+}
+
+fn entry(account_id: Uuid, debit: Decimal, credit: Decimal) -> CreateLedgerEntryInput {
+    CreateLedgerEntryInput {
+        account_id,
+        source_currency: "USD".to_string(),
+        source_amount: debit + credit,
+        exchange_rate: Decimal::ONE,
+        functional_currency: "USD".to_string(),
+        functional_amount: debit + credit,
+        debit,
+        credit,
+        memo: None,
+        dimensions: vec![],
+        rate_override: None,
+        event_at: None,
+    }
+}
+
+/// Creates a sale on `on` and submits it; posts it too when `post` is set.
+async fn sale(db: &DatabaseConnection, org: &Org, on: NaiveDate, post: bool) {
+    let created = TransactionRepository::new(db.clone())
+        .create_transaction(CreateTransactionInput {
+            organization_id: org.id.into_inner(),
+            transaction_type: TransactionType::Journal,
+            transaction_date: on,
+            description: "Sale".to_string(),
+            reference_number: None,
+            memo: None,
+            entries: vec![
+                entry(org.account("1100").into_inner(), dec!(250), Decimal::ZERO),
+                entry(org.account("4000").into_inner(), Decimal::ZERO, dec!(250)),
+            ],
+            created_by: org.owner.user_id.into_inner(),
+        })
+        .await
+        .expect("Failed to create transaction");
+
+    let workflow = WorkflowRepository::new(db.clone());
+    let tx_id = TransactionId::from_uuid(created.transaction.id);
+    let user = org.owner.user_id.into_inner();
+    workflow
+        .submit_transaction(org.id, tx_id, user)
+        .await
+        .expect("Failed to submit");
+    if post {
+        workflow
+            .approve_transaction(org.id, tx_id, user, None)
+            .await
+            .expect("Failed to approve");
+        workflow
+            .post_transaction(org.id, tx_id, user)
+            .await
+            .expect("Failed to post");
+    }
+}
+
+/// Organization with auto-close after 5 + 10 days, a posted sale in
+/// January and a sale still pending approval in February.
+async fn org_with_periods(db: &DatabaseConnection) -> Org {
+    let org = OrgFixture::new()
+        .with_member(UserRole::Accountant)
+        .with_member(UserRole::Viewer)
+        .with_fiscal_year_2025()
+        .with_accounts(ACCOUNTS)
+        .create(db)
+        .await;
+    OrganizationRepository::new(db.clone())
+        .update_settings(
+            org.id.into_inner(),
+            &json!({
+                "period_auto_close": {
+                    "enabled": true,
+                    "soft_close_after_days": 5,
+                    "close_after_days": 10,
+                }
+            }),
+        )
+        .await
+        .expect("Failed to update settings");
+    sale(db, &org, date(1, 20), true).await;
+    sale(db, &org, date(2, 10), false).await;
+    org
+}
+
+/// Runs the job for one organization as of `today`.
+async fn run_job(db: &DatabaseConnection, org: &Org, today: NaiveDate) -> Vec<AutoCloseRun> {
+    let repo = PeriodCloseRepository::new(db.clone());
+    let due = repo
+        .due_periods(today, Some(org.id.into_inner()))
+        .await
+        .expect("Failed to list due periods");
+    let mut runs = Vec::new();
+    for period in &due {
+        runs.push(repo.run(period).await.expect("Failed to run auto-close"));
+    }
+    runs
+}
+
+async fn period_ending(
+    db: &DatabaseConnection,
+    org: &Org,
+    end: NaiveDate,
+) -> fiscal_periods::Model {
+    fiscal_periods::Entity::find()
+        .filter(fiscal_periods::Column::OrganizationId.eq(org.id.into_inner()))
+        .filter(fiscal_periods::Column::EndDate.eq(end))
+        .one(db)
+        .await
+        .unwrap()
+        .expect("period exists")
+}
+
+async fn events(db: &DatabaseConnection, period_id: Uuid) -> Vec<String> {
+    fiscal_period_events::Entity::find()
+        .filter(fiscal_period_events::Column::FiscalPeriodId.eq(period_id))
+        .order_by_asc(fiscal_period_events::Column::CreatedAt)
+        .all(db)
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|e| e.event)
+        .collect()
+}
+
+async fn inbox_types(db: &DatabaseConnection, org: &Org, user_id: Uuid) -> Vec<String> {
+    let mut types: Vec<String> = NotificationRepository::new(db.clone())
+        .list(org.id.into_inner(), user_id, 1, 50)
+        .await
+        .unwrap()
+        .notifications
+        .into_iter()
+        .map(|n| n.notification_type)
+        .collect();
+    types.sort();
+    types
+}
+
+#[tokio::test]
+async fn test_job_closes_clean_period_and_holds_blocked_one() {
+    let test_db = TestDb::new().await;
+    let db = test_db.conn();
+    let org = org_with_periods(db).await;
+    let january = period_ending(db, &org, date(1, 31)).await;
+    let february = period_ending(db, &org, date(2, 28)).await;
+
+    // Within January's grace period
+    assert!(run_job(db, &org, date(2, 5)).await.is_empty());
+
+    let runs = run_job(db, &org, date(2, 6)).await;
+    assert_eq!(runs.len(), 1);
+    assert_eq!(runs[0].fiscal_period_id, january.id);
+    assert_eq!(
+        runs[0].outcome,
+        AutoCloseOutcome::SoftClosed {
+            unposted_transactions: 0
+        }
+    );
+    let soft_closed = period_ending(db, &org, date(1, 31)).await;
+    assert_eq!(soft_closed.status, FiscalPeriodStatus::SoftClose);
+    assert_eq!(soft_closed.closed_by, Some(SYSTEM_USER_ID));
+
+    let runs = run_job(db, &org, date(2, 16)).await;
+    assert_eq!(runs.len(), 1);
+    assert_eq!(runs[0].outcome, AutoCloseOutcome::Closed);
+    let closed = period_ending(db, &org, date(1, 31)).await;
+    assert_eq!(closed.status, FiscalPeriodStatus::Closed);
+    assert_eq!(closed.closed_by, Some(SYSTEM_USER_ID));
+    assert_eq!(events(db, january.id).await, ["soft_closed", "closed"]);
+
+    // February has a transaction pending approval
+    let runs = run_job(db, &org, date(3, 6)).await;
+    assert_eq!(runs.len(), 1);
+    assert_eq!(runs[0].fiscal_period_id, february.id);
+    assert_eq!(
+        runs[0].outcome,
+        AutoCloseOutcome::SoftClosed {
+            unposted_transactions: 1
+        }
+    );
+
+    for _ in 0..2 {
+        let runs = run_job(db, &org, date(3, 16)).await;
+        assert_eq!(runs.len(), 1);
+        assert_eq!(
+            runs[0].outcome,
+            AutoCloseOutcome::Blocked {
+                failed_checks: vec!["pending_transactions".to_string()]
+            }
+        );
+    }
+    let blocked = period_ending(db, &org, date(2, 28)).await;
+    assert_eq!(blocked.status, FiscalPeriodStatus::SoftClose);
+    assert_eq!(
+        events(db, february.id).await,
+        ["soft_closed", "close_blocked"]
+    );
+
+    // Owners and accountants hear about every step, blocked closes once
+    let expected = [
+        "period_close_blocked",
+        "period_closed",
+        "period_soft_closed",
+        "period_soft_closed",
+    ];
+    assert_eq!(
+        inbox_types(db, &org, org.owner.user_id.into_inner()).await,
+        expected
+    );
+    let accountant = org.member(&UserRole::Accountant).user_id.into_inner();
+    assert_eq!(inbox_types(db, &org, accountant).await, expected);
+    let viewer = org.member(&UserRole::Viewer).user_id.into_inner();
+    assert!(inbox_types(db, &org, viewer).await.is_empty());
+
+    let soft_closed = NotificationRepository::new(db.clone())
+        .list(org.id.into_inner(), org.owner.user_id.into_inner(), 1, 50)
+        .await
+        .unwrap()
+        .notifications
+        .into_iter()
+        .find(|n| {
+            n.notification_type == "period_soft_closed"
+                && n.payload["fiscal_period_id"] == json!(february.id)
+        })
+        .expect("February soft close notification");
+    assert_eq!(soft_closed.payload["unposted_transactions"]["pending"], 1);
+    assert_eq!(soft_closed.payload["close_on"], "2025-03-16");
+}
+
+#[tokio::test]
+async fn test_grace_extension_pushes_schedule() {
+    let test_db = TestDb::new().await;
+    let db = test_db.conn();
+    let org = org_with_periods(db).await;
+    let january = period_ending(db, &org, date(1, 31)).await;
+    let repo = PeriodCloseRepository::new(db.clone());
+    let org_id = org.id.into_inner();
+    let owner = org.owner.user_id.into_inner();
+
+    let extended = repo
+        .extend_grace(
+            org_id,
+            january.id,
+            3,
+            Some("Audit fieldwork".to_string()),
+            owner,
+        )
+        .await
+        .unwrap();
+    assert!(extended.enabled);
+    assert_eq!(extended.schedule.soft_close_on, date(2, 9));
+    let extended = repo
+        .extend_grace(org_id, january.id, 4, None, owner)
+        .await
+        .unwrap();
+    assert_eq!(extended.schedule.extension_days, 7);
+    assert_eq!(extended.schedule.soft_close_on, date(2, 13));
+    assert_eq!(extended.schedule.close_on, date(2, 23));
+
+    assert!(run_job(db, &org, date(2, 12)).await.is_empty());
+    let runs = run_job(db, &org, date(2, 13)).await;
+    assert_eq!(runs.len(), 1);
+    assert_eq!(runs[0].fiscal_period_id, january.id);
+
+    assert!(matches!(
+        repo.extend_grace(org_id, january.id, 0, None, owner).await,
+        Err(PeriodCloseError::InvalidExtension)
+    ));
+    assert!(matches!(
+        repo.extend_grace(org_id, january.id, 91, None, owner).await,
+        Err(PeriodCloseError::InvalidExtension)
+    ));
+    let other = OrgFixture::new().with_fiscal_year_2025().create(db).await;
+    assert!(matches!(
+        repo.extend_grace(other.id.into_inner(), january.id, 1, None, owner)
+            .await,
+        Err(PeriodCloseError::PeriodNotFound(_))
+    ));
+
+    run_job(db, &org, date(2, 23)).await;
+    assert!(matches!(
+        repo.extend_grace(org_id, january.id, 1, None, owner).await,
+        Err(PeriodCloseError::PeriodClosed(_))
+    ));
+    assert_eq!(
+        events(db, january.id).await,
+        ["grace_extended", "grace_extended", "soft_closed", "closed"]
+    );
+}
+
+#[tokio::test]
+async fn test_disabled_organizations_are_left_alone() {
+    let test_db = TestDb::new().await;
+    let db = test_db.conn();
+    let org = OrgFixture::new().with_fiscal_year_2025().create(db).await;
+
+    assert!(run_job(db, &org, date(12, 31)).await.is_empty());
+}
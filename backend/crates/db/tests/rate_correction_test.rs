@@ -0,0 +1,241 @@
+//! Integration tests for correcting and deleting stored exchange rates.
+
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use sea_orm::{ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter};
+use uuid::Uuid;
+
+use zeltra_db::entities::{
+    rate_corrections,
+    sea_orm_active_enums::{AccountType, RateSource, TransactionType},
+};
+use zeltra_db::repositories::transaction::{
+    CreateLedgerEntryInput, CreateTransactionInput, TransactionRepository,
+};
+use zeltra_db::repositories::{
+    CorrectExchangeRateInput, CreateExchangeRateInput, ExchangeRateError, ExchangeRateRepository,
+    WorkflowRepository,
+};
+use zeltra_shared::types::TransactionId;
+use zeltra_test_support::{Org, OrgFixture, TestDb};
+
+const ACCOUNTS: &[(&str, AccountType)] =
+    &[("1200", AccountType::Asset), ("4000", AccountType::Revenue)];
+
+fn date(m: u32, d: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(2025, m, d).unwrap()
+}
+
+async fn store_rate(db: &DatabaseConnection, org: &Org, on: NaiveDate, rate: Decimal) -> Uuid {
+    ExchangeRateRepository::new(db.clone())
+        .create_or_update_rate(CreateExchangeRateInput {
+            organization_id: org.id.into_inner(),
+            from_currency: "EUR".to_string(),
+            to_currency: "USD".to_string(),
+            rate,
+            effective_date: on,
+            source: RateSource::Manual,
+            source_reference: None,
+            created_by: None,
+        })
+        .await
+        .expect("Failed to store rate")
+        .id
+}
+
+/// Books an EUR 1,000 receivable against USD revenue at `rate`.
+async fn book_eur_sale(
+    db: &DatabaseConnection,
+    org: &Org,
+    on: NaiveDate,
+    rate: Decimal,
+    post: bool,
+) {
+    let functional = dec!(1000) * rate;
+    let entry =
+        |account: &str, source: (&str, Decimal, Decimal), debit, credit| CreateLedgerEntryInput {
+            account_id: org.account(account).into_inner(),
+            source_currency: source.0.to_string(),
+            source_amount: source.1,
+            exchange_rate: source.2,
+            functional_currency: "USD".to_string(),
+            functional_amount: functional,
+            debit,
+            credit,
+            memo: None,
+            dimensions: vec![],
+            rate_override: None,
+            event_at: None,
+        };
+    let user = org.owner.user_id.into_inner();
+    let created = TransactionRepository::new(db.clone())
+        .create_transaction(CreateTransactionInput {
+            organization_id: org.id.into_inner(),
+            transaction_type: TransactionType::Journal,
+            transaction_date: on,
+            description: "EUR sale".to_string(),
+            reference_number: None,
+            memo: None,
+            entries: vec![
+                entry("1200", ("EUR", dec!(1000), rate), functional, Decimal::ZERO),
+                entry(
+                    "4000",
+                    ("USD", functional, Decimal::ONE),
+                    Decimal::ZERO,
+                    functional,
+                ),
+            ],
+            created_by: user,
+        })
+        .await
+        .expect("Failed to create transaction");
+    if !post {
+        return;
+    }
+
+    let workflow = WorkflowRepository::new(db.clone());
+    let tx_id = TransactionId::from_uuid(created.transaction.id);
+    workflow
+        .submit_transaction(org.id, tx_id, user)
+        .await
+        .expect("Failed to submit");
+    workflow
+        .approve_transaction(org.id, tx_id, user, None)
+        .await
+        .expect("Failed to approve");
+    workflow
+        .post_transaction(org.id, tx_id, user)
+        .await
+        .expect("Failed to post");
+}
+
+#[tokio::test]
+async fn test_usage_counts_posted_entries_within_the_rate_window() {
+    let test_db = TestDb::new().await;
+    let db = test_db.conn();
+    let org = OrgFixture::new()
+        .with_fiscal_year_2025()
+        .with_accounts(ACCOUNTS)
+        .create(db)
+        .await;
+    let march = store_rate(db, &org, date(3, 1), dec!(1.08)).await;
+    store_rate(db, &org, date(3, 15), dec!(1.10)).await;
+
+    book_eur_sale(db, &org, date(3, 5), dec!(1.08), true).await;
+    book_eur_sale(db, &org, date(3, 8), dec!(1.08), true).await;
+    // Not counted: a draft, a different rate, and a date after the next rate
+    book_eur_sale(db, &org, date(3, 6), dec!(1.08), false).await;
+    book_eur_sale(db, &org, date(3, 7), dec!(1.09), true).await;
+    book_eur_sale(db, &org, date(3, 20), dec!(1.08), true).await;
+
+    let repo = ExchangeRateRepository::new(db.clone());
+    let (usage, transactions) = repo
+        .list_rate_usage(org.id.into_inner(), march)
+        .await
+        .unwrap();
+    assert_eq!(usage.window_start, date(3, 1));
+    assert_eq!(usage.window_end, Some(date(3, 15)));
+    assert_eq!(usage.entry_count, 2);
+    let dates: Vec<NaiveDate> = transactions.iter().map(|t| t.transaction_date).collect();
+    assert_eq!(dates, [date(3, 5), date(3, 8)]);
+    assert!(transactions.iter().all(|t| t.entry_count == 1));
+    assert_eq!(transactions[0].source_amount, dec!(1000));
+    assert_eq!(transactions[0].functional_amount, dec!(1080));
+
+    // Another organization cannot see the rate
+    let other = OrgFixture::new().create(db).await;
+    assert!(matches!(
+        repo.rate_usage(other.id.into_inner(), march).await,
+        Err(ExchangeRateError::NotFound(_))
+    ));
+}
+
+#[tokio::test]
+async fn test_delete_refuses_used_rate_unless_forced() {
+    let test_db = TestDb::new().await;
+    let db = test_db.conn();
+    let org = OrgFixture::new()
+        .with_fiscal_year_2025()
+        .with_accounts(ACCOUNTS)
+        .create(db)
+        .await;
+    let used = store_rate(db, &org, date(3, 1), dec!(1.08)).await;
+    let unused = store_rate(db, &org, date(4, 1), dec!(1.12)).await;
+    book_eur_sale(db, &org, date(3, 5), dec!(1.08), true).await;
+
+    let repo = ExchangeRateRepository::new(db.clone());
+    let org_id = org.id.into_inner();
+    assert!(matches!(
+        repo.delete_rate(org_id, used, false).await,
+        Err(ExchangeRateError::InUse(1))
+    ));
+    assert!(repo.find_by_id(org_id, used).await.unwrap().is_some());
+
+    let usage = repo.delete_rate(org_id, unused, false).await.unwrap();
+    assert_eq!(usage.entry_count, 0);
+
+    let usage = repo.delete_rate(org_id, used, true).await.unwrap();
+    assert_eq!(usage.entry_count, 1);
+    assert!(repo.find_by_id(org_id, used).await.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn test_correction_records_old_rate_and_usage() {
+    let test_db = TestDb::new().await;
+    let db = test_db.conn();
+    let org = OrgFixture::new()
+        .with_fiscal_year_2025()
+        .with_accounts(ACCOUNTS)
+        .create(db)
+        .await;
+    let rate = store_rate(db, &org, date(3, 1), dec!(10.8)).await;
+    book_eur_sale(db, &org, date(3, 5), dec!(10.8), true).await;
+
+    let repo = ExchangeRateRepository::new(db.clone());
+    let org_id = org.id.into_inner();
+    let corrected = repo
+        .correct_rate(
+            org_id,
+            rate,
+            CorrectExchangeRateInput {
+                rate: dec!(1.08),
+                reason: Some("Misplaced decimal".to_string()),
+                corrected_by: org.owner.user_id.into_inner(),
+            },
+        )
+        .await
+        .unwrap();
+    assert_eq!(corrected.rate.rate, dec!(1.08));
+    assert_eq!(corrected.correction.old_rate, dec!(10.8));
+    assert_eq!(corrected.correction.new_rate, dec!(1.08));
+    assert_eq!(corrected.correction.affected_entries, 1);
+    assert_eq!(corrected.usage.entry_count, 1);
+
+    // Entries booked at the old value no longer match the rate
+    assert_eq!(repo.rate_usage(org_id, rate).await.unwrap().entry_count, 0);
+    assert!(matches!(
+        repo.correct_rate(
+            org_id,
+            rate,
+            CorrectExchangeRateInput {
+                rate: Decimal::ZERO,
+                reason: None,
+                corrected_by: org.owner.user_id.into_inner(),
+            },
+        )
+        .await,
+        Err(ExchangeRateError::NonPositiveRate)
+    ));
+
+    // History outlives the rate
+    repo.delete_rate(org_id, rate, false).await.unwrap();
+    let history = rate_corrections::Entity::find()
+        .filter(rate_corrections::Column::OrganizationId.eq(org_id))
+        .all(db)
+        .await
+        .unwrap();
+    assert_eq!(history.len(), 1);
+    assert_eq!(history[0].exchange_rate_id, None);
+    assert_eq!(history[0].reason.as_deref(), Some("Misplaced decimal"));
+}
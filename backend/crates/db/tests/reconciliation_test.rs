@@ -0,0 +1,338 @@
+//! Integration tests for bank account reconciliation.
+
+use chrono::{NaiveDate, Utc};
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, Set,
+    sea_query::Expr,
+};
+use uuid::Uuid;
+
+use zeltra_core::reconciliation::ReconciliationError as RuleError;
+use zeltra_core::workflow::{VoidReasonCode, WorkflowError};
+use zeltra_db::entities::sea_orm_active_enums::{AccountType, ReconciliationStatus};
+use zeltra_db::entities::{chart_of_accounts, ledger_entries, reconciliations};
+use zeltra_db::repositories::WorkflowRepository;
+use zeltra_db::repositories::reconciliation::{
+    ReconciliationError, ReconciliationRepository, StartReconciliationInput,
+};
+use zeltra_shared::types::TransactionId;
+use zeltra_test_support::{Line, Org, OrgFixture, TestDb, post_transaction};
+
+/// Creates an organization whose account 1000 is a bank account.
+async fn org_with_bank(db: &DatabaseConnection) -> Org {
+    let org = OrgFixture::new()
+        .with_fiscal_year_2025()
+        .with_accounts(&[
+            ("1000", AccountType::Asset),
+            ("1100", AccountType::Asset),
+            ("4000", AccountType::Revenue),
+        ])
+        .create(db)
+        .await;
+    chart_of_accounts::Entity::update_many()
+        .col_expr(chart_of_accounts::Column::IsBankAccount, Expr::value(true))
+        .filter(chart_of_accounts::Column::Id.eq(org.account("1000").into_inner()))
+        .exec(db)
+        .await
+        .unwrap();
+    org
+}
+
+/// Posts a deposit of `amount` into the bank on `date` and returns the
+/// transaction and its bank entry.
+async fn deposit(
+    db: &DatabaseConnection,
+    org: &Org,
+    date: NaiveDate,
+    amount: Decimal,
+) -> (TransactionId, Uuid) {
+    let id = post_transaction(
+        db,
+        org,
+        date,
+        vec![Line::debit("1000", amount), Line::credit("4000", amount)],
+    )
+    .await;
+    let entry = ledger_entries::Entity::find()
+        .filter(ledger_entries::Column::TransactionId.eq(id.into_inner()))
+        .filter(ledger_entries::Column::AccountId.eq(org.account("1000").into_inner()))
+        .one(db)
+        .await
+        .unwrap()
+        .unwrap();
+    (id, entry.id)
+}
+
+fn input(
+    org: &Org,
+    statement_date: NaiveDate,
+    ending_balance: Decimal,
+) -> StartReconciliationInput {
+    StartReconciliationInput {
+        organization_id: org.id.into_inner(),
+        account_id: org.account("1000").into_inner(),
+        statement_date,
+        statement_ending_balance: ending_balance,
+        created_by: org.owner.user_id.into_inner(),
+    }
+}
+
+fn date(month: u32, day: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(2025, month, day).unwrap()
+}
+
+#[tokio::test]
+async fn test_start_requires_base_currency_bank_account() {
+    let test_db = TestDb::new().await;
+    let db = test_db.conn();
+    let org = org_with_bank(db).await;
+    let repo = ReconciliationRepository::new(db.clone());
+
+    let mut not_bank = input(&org, date(1, 31), dec!(0));
+    not_bank.account_id = org.account("1100").into_inner();
+    assert!(matches!(
+        repo.start_reconciliation(not_bank).await,
+        Err(ReconciliationError::NotBankAccount(_))
+    ));
+
+    chart_of_accounts::Entity::update_many()
+        .col_expr(chart_of_accounts::Column::Currency, Expr::value("EUR"))
+        .filter(chart_of_accounts::Column::Id.eq(org.account("1000").into_inner()))
+        .exec(db)
+        .await
+        .unwrap();
+    assert!(matches!(
+        repo.start_reconciliation(input(&org, date(1, 31), dec!(0)))
+            .await,
+        Err(ReconciliationError::NotBaseCurrency(_))
+    ));
+}
+
+#[tokio::test]
+async fn test_start_clear_and_complete() {
+    let test_db = TestDb::new().await;
+    let db = test_db.conn();
+    let org = org_with_bank(db).await;
+    let org_id = org.id.into_inner();
+    let user_id = org.owner.user_id.into_inner();
+    let (_, first) = deposit(db, &org, date(3, 10), dec!(300)).await;
+    let (_, second) = deposit(db, &org, date(3, 20), dec!(120)).await;
+    let (_, later) = deposit(db, &org, date(4, 5), dec!(50)).await;
+    let repo = ReconciliationRepository::new(db.clone());
+
+    let reconciliation = repo
+        .start_reconciliation(input(&org, date(3, 31), dec!(300)))
+        .await
+        .unwrap();
+    assert_eq!(reconciliation.status, ReconciliationStatus::InProgress);
+    assert_eq!(reconciliation.opening_balance, Decimal::ZERO);
+
+    let entries = repo
+        .list_unreconciled_entries(org_id, reconciliation.id)
+        .await
+        .unwrap();
+    let ids: Vec<Uuid> = entries.iter().map(|e| e.entry.id).collect();
+    assert_eq!(ids, [first, second]);
+
+    let after = repo
+        .set_cleared(org_id, reconciliation.id, later, true)
+        .await;
+    assert!(matches!(
+        after,
+        Err(ReconciliationError::Rule(
+            RuleError::EntryAfterStatementDate
+        ))
+    ));
+
+    for entry in [first, second] {
+        repo.set_cleared(org_id, reconciliation.id, entry, true)
+            .await
+            .unwrap();
+    }
+    let current = repo
+        .set_cleared(org_id, reconciliation.id, first, true)
+        .await
+        .unwrap();
+    assert_eq!(current.cleared_count, 2);
+    assert_eq!(current.check.cleared_net, dec!(420));
+    assert_eq!(current.check.difference, dec!(-120));
+
+    let result = repo
+        .complete_reconciliation(org_id, reconciliation.id, user_id)
+        .await;
+    assert!(matches!(
+        result,
+        Err(ReconciliationError::Rule(RuleError::OutOfBalance { difference })) if difference == dec!(-120)
+    ));
+
+    let current = repo
+        .set_cleared(org_id, reconciliation.id, second, false)
+        .await
+        .unwrap();
+    assert_eq!(current.cleared_count, 1);
+    assert!(current.check.is_balanced());
+    let entries = repo
+        .list_unreconciled_entries(org_id, reconciliation.id)
+        .await
+        .unwrap();
+    assert!(entries.iter().any(|e| e.entry.id == first && e.cleared));
+    assert!(entries.iter().any(|e| e.entry.id == second && !e.cleared));
+
+    let completed = repo
+        .complete_reconciliation(org_id, reconciliation.id, user_id)
+        .await
+        .unwrap();
+    assert_eq!(
+        completed.reconciliation.status,
+        ReconciliationStatus::Completed
+    );
+    assert_eq!(completed.reconciliation.completed_by, Some(user_id));
+
+    // The next statement starts from this one and no longer offers its entries
+    let next = repo
+        .start_reconciliation(input(&org, date(4, 30), dec!(470)))
+        .await
+        .unwrap();
+    assert_eq!(next.opening_balance, dec!(300));
+    let ids: Vec<Uuid> = repo
+        .list_unreconciled_entries(org_id, next.id)
+        .await
+        .unwrap()
+        .iter()
+        .map(|e| e.entry.id)
+        .collect();
+    assert_eq!(ids, [second, later]);
+    assert!(matches!(
+        repo.set_cleared(org_id, next.id, first, true).await,
+        Err(ReconciliationError::EntryAlreadyReconciled(id)) if id == first
+    ));
+}
+
+#[tokio::test]
+async fn test_reopen_only_latest_without_open_reconciliation() {
+    let test_db = TestDb::new().await;
+    let db = test_db.conn();
+    let org = org_with_bank(db).await;
+    let org_id = org.id.into_inner();
+    let user_id = org.owner.user_id.into_inner();
+    let repo = ReconciliationRepository::new(db.clone());
+
+    let march = repo
+        .start_reconciliation(input(&org, date(3, 31), dec!(0)))
+        .await
+        .unwrap();
+    assert!(matches!(
+        repo.reopen_reconciliation(org_id, march.id).await,
+        Err(ReconciliationError::Rule(RuleError::NotCompleted))
+    ));
+    repo.complete_reconciliation(org_id, march.id, user_id)
+        .await
+        .unwrap();
+
+    let reopened = repo.reopen_reconciliation(org_id, march.id).await.unwrap();
+    assert_eq!(reopened.status, ReconciliationStatus::InProgress);
+    assert_eq!(reopened.completed_by, None);
+    repo.complete_reconciliation(org_id, march.id, user_id)
+        .await
+        .unwrap();
+
+    let april = repo
+        .start_reconciliation(input(&org, date(4, 30), dec!(0)))
+        .await
+        .unwrap();
+    assert!(matches!(
+        repo.reopen_reconciliation(org_id, march.id).await,
+        Err(ReconciliationError::AlreadyInProgress(id)) if id == april.id
+    ));
+
+    repo.complete_reconciliation(org_id, april.id, user_id)
+        .await
+        .unwrap();
+    assert!(matches!(
+        repo.reopen_reconciliation(org_id, march.id).await,
+        Err(ReconciliationError::NotLatest)
+    ));
+}
+
+#[tokio::test]
+async fn test_one_open_reconciliation_per_account() {
+    let test_db = TestDb::new().await;
+    let db = test_db.conn();
+    let org = org_with_bank(db).await;
+    let repo = ReconciliationRepository::new(db.clone());
+
+    let open = repo
+        .start_reconciliation(input(&org, date(3, 31), dec!(0)))
+        .await
+        .unwrap();
+    assert!(matches!(
+        repo.start_reconciliation(input(&org, date(4, 30), dec!(0)))
+            .await,
+        Err(ReconciliationError::AlreadyInProgress(id)) if id == open.id
+    ));
+
+    // The partial unique index holds even when the check is bypassed
+    let now = Utc::now().into();
+    let duplicate = reconciliations::ActiveModel {
+        id: Set(Uuid::new_v4()),
+        organization_id: Set(org.id.into_inner()),
+        account_id: Set(open.account_id),
+        statement_date: Set(date(4, 30)),
+        statement_ending_balance: Set(Decimal::ZERO),
+        opening_balance: Set(Decimal::ZERO),
+        status: Set(ReconciliationStatus::InProgress),
+        created_by: Set(org.owner.user_id.into_inner()),
+        completed_by: Set(None),
+        completed_at: Set(None),
+        created_at: Set(now),
+        updated_at: Set(now),
+    }
+    .insert(db)
+    .await;
+    let err = duplicate.unwrap_err().to_string();
+    assert!(err.contains("idx_reconciliations_one_open"), "{err}");
+}
+
+#[tokio::test]
+async fn test_void_rejected_while_entry_is_reconciled() {
+    let test_db = TestDb::new().await;
+    let db = test_db.conn();
+    let org = org_with_bank(db).await;
+    let org_id = org.id.into_inner();
+    let user_id = org.owner.user_id.into_inner();
+    let (id, entry) = deposit(db, &org, date(3, 10), dec!(300)).await;
+    let repo = ReconciliationRepository::new(db.clone());
+
+    let reconciliation = repo
+        .start_reconciliation(input(&org, date(3, 31), dec!(300)))
+        .await
+        .unwrap();
+    repo.set_cleared(org_id, reconciliation.id, entry, true)
+        .await
+        .unwrap();
+    repo.complete_reconciliation(org_id, reconciliation.id, user_id)
+        .await
+        .unwrap();
+
+    let workflow = WorkflowRepository::new(db.clone());
+    let void = || {
+        workflow.void_transaction(
+            org.id,
+            id,
+            user_id,
+            VoidReasonCode::DuplicateEntry,
+            String::new(),
+        )
+    };
+    assert!(matches!(
+        void().await,
+        Err(WorkflowError::EntryReconciled { reconciliation_id }) if reconciliation_id == reconciliation.id
+    ));
+
+    repo.reopen_reconciliation(org_id, reconciliation.id)
+        .await
+        .unwrap();
+    void().await.expect("Failed to void after reopening");
+}
@@ -0,0 +1,109 @@
+//! Integration tests for per-organization financial statement mappings.
+
+use sea_orm::{
+    ActiveEnum, DatabaseConnection, EntityTrait, Iterable, prelude::DateTimeWithTimeZone,
+};
+use uuid::Uuid;
+
+use zeltra_core::reports::StatementMapping;
+use zeltra_db::entities::{organizations, sea_orm_active_enums::AccountSubtype};
+use zeltra_db::repositories::{ReportMappingError, ReportMappingRepository, core_account_subtype};
+use zeltra_test_support::{OrgFixture, TestDb};
+
+async fn org_updated_at(db: &DatabaseConnection, org_id: Uuid) -> DateTimeWithTimeZone {
+    organizations::Entity::find_by_id(org_id)
+        .one(db)
+        .await
+        .unwrap()
+        .unwrap()
+        .updated_at
+}
+
+#[test]
+fn test_default_mapping_covers_every_stored_subtype() {
+    let mapping = StatementMapping::default();
+
+    for subtype in AccountSubtype::iter() {
+        let core = core_account_subtype(&subtype);
+        assert_eq!(core.as_str(), subtype.to_value());
+        let (kind, section) = mapping
+            .place(core.account_type(), Some(core.as_str()))
+            .unwrap();
+        assert!(section.is_some(), "{} has no section", core.as_str());
+        assert!(kind.accepts(core.account_type()));
+    }
+}
+
+#[tokio::test]
+async fn test_organization_without_mapping_uses_default() {
+    let test_db = TestDb::new().await;
+    let db = test_db.conn();
+    let org = OrgFixture::new().create(db).await;
+
+    let resolved = ReportMappingRepository::new(db.clone())
+        .resolve(org.id.into_inner())
+        .await
+        .unwrap();
+
+    assert!(resolved.is_default);
+    assert_eq!(resolved.mapping, StatementMapping::default());
+    assert_eq!(resolved.updated_at, None);
+}
+
+#[tokio::test]
+async fn test_saved_mapping_replaces_default_and_touches_organization() {
+    let test_db = TestDb::new().await;
+    let db = test_db.conn();
+    let org = OrgFixture::new().create(db).await;
+    let repo = ReportMappingRepository::new(db.clone());
+    let before = org_updated_at(db, org.id.into_inner()).await;
+
+    let mut mapping = StatementMapping::default();
+    for section in &mut mapping.sections {
+        if section.key == "cost_of_goods_sold" {
+            section.name = "Cost of Revenue".to_string();
+        }
+    }
+    for _ in 0..2 {
+        repo.save(
+            org.id.into_inner(),
+            &mapping,
+            org.owner.user_id.into_inner(),
+        )
+        .await
+        .unwrap();
+    }
+
+    let resolved = repo.resolve(org.id.into_inner()).await.unwrap();
+    assert!(!resolved.is_default);
+    assert_eq!(resolved.mapping, mapping);
+    assert_eq!(resolved.updated_by, Some(org.owner.user_id.into_inner()));
+    assert!(org_updated_at(db, org.id.into_inner()).await > before);
+}
+
+#[tokio::test]
+async fn test_invalid_mapping_is_not_saved() {
+    let test_db = TestDb::new().await;
+    let db = test_db.conn();
+    let org = OrgFixture::new().create(db).await;
+    let repo = ReportMappingRepository::new(db.clone());
+
+    let mut mapping = StatementMapping::default();
+    mapping
+        .subtypes
+        .retain(|m| m.subtype != "accrued_liability");
+    let Err(ReportMappingError::Invalid(fields)) = repo
+        .save(
+            org.id.into_inner(),
+            &mapping,
+            org.owner.user_id.into_inner(),
+        )
+        .await
+    else {
+        panic!("an unmapped subtype should be rejected");
+    };
+
+    assert_eq!(fields.len(), 1);
+    assert_eq!(fields[0].field, "subtypes.accrued_liability");
+    assert!(repo.resolve(org.id.into_inner()).await.unwrap().is_default);
+}
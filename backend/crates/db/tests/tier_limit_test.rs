@@ -0,0 +1,189 @@
+//! Integration tests for tier limit enforcement on resource creation.
+
+use sea_orm::DatabaseConnection;
+use uuid::Uuid;
+
+use zeltra_db::entities::sea_orm_active_enums::{SubscriptionTier, UserRole};
+use zeltra_db::repositories::{
+    ApprovalRuleError, ApprovalRuleRepository, CreateApprovalRuleInput, CreateDimensionTypeInput,
+    DimensionError, DimensionRepository, OrganizationRepository, ResourceLimit,
+    SubscriptionRepository, UpdateDimensionTypeInput, UserRepository,
+};
+use zeltra_test_support::{Org, OrgFixture, TestDb};
+
+async fn create_dimension_type(
+    db: &DatabaseConnection,
+    org: &Org,
+    code: &str,
+) -> Result<Uuid, DimensionError> {
+    DimensionRepository::new(db.clone())
+        .create_dimension_type(CreateDimensionTypeInput {
+            organization_id: org.id.into_inner(),
+            code: code.to_string(),
+            name: code.to_string(),
+            description: None,
+            is_required: false,
+            is_active: true,
+            sort_order: 0,
+        })
+        .await
+        .map(|t| t.id)
+}
+
+#[tokio::test]
+async fn test_starter_dimension_limit_blocks_next_create() {
+    let test_db = TestDb::new().await;
+    let db = test_db.conn();
+    let starter = OrgFixture::new()
+        .with_tier(SubscriptionTier::Starter)
+        .create(db)
+        .await;
+    let enterprise = OrgFixture::new().create(db).await;
+
+    for code in ["DEPT", "PROJ"] {
+        create_dimension_type(db, &starter, code).await.unwrap();
+        create_dimension_type(db, &enterprise, code).await.unwrap();
+    }
+
+    let Err(DimensionError::TierLimitExceeded(limit)) =
+        create_dimension_type(db, &starter, "REGION").await
+    else {
+        panic!("third dimension type should exceed the starter limit");
+    };
+    assert_eq!(limit.current, 2);
+    assert_eq!(limit.limit, Some(2));
+    assert_eq!(limit.remaining, Some(0));
+    assert_eq!(
+        limit.upgrade_tiers,
+        [SubscriptionTier::Growth, SubscriptionTier::Enterprise]
+    );
+
+    create_dimension_type(db, &enterprise, "REGION")
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn test_inactive_dimension_types_free_a_slot_until_reactivated() {
+    let test_db = TestDb::new().await;
+    let db = test_db.conn();
+    let org = OrgFixture::new()
+        .with_tier(SubscriptionTier::Starter)
+        .create(db)
+        .await;
+    let repo = DimensionRepository::new(db.clone());
+    let dept = create_dimension_type(db, &org, "DEPT").await.unwrap();
+    create_dimension_type(db, &org, "PROJ").await.unwrap();
+
+    let set_active = |is_active| UpdateDimensionTypeInput {
+        is_active: Some(is_active),
+        ..Default::default()
+    };
+    repo.update_dimension_type(dept, set_active(false))
+        .await
+        .unwrap();
+    create_dimension_type(db, &org, "REGION").await.unwrap();
+
+    assert!(matches!(
+        repo.update_dimension_type(dept, set_active(true)).await,
+        Err(DimensionError::TierLimitExceeded(_))
+    ));
+}
+
+#[tokio::test]
+async fn test_concurrent_creates_stop_at_the_limit() {
+    let test_db = TestDb::new().await;
+    let db = test_db.conn();
+    let org = OrgFixture::new()
+        .with_tier(SubscriptionTier::Starter)
+        .create(db)
+        .await;
+
+    let codes: Vec<String> = (0..6).map(|i| format!("DIM{i}")).collect();
+    let results = futures::future::join_all(
+        codes
+            .iter()
+            .map(|code| create_dimension_type(db, &org, code)),
+    )
+    .await;
+
+    assert_eq!(results.iter().filter(|r| r.is_ok()).count(), 2);
+    assert!(
+        results
+            .iter()
+            .all(|r| matches!(r, Ok(_) | Err(DimensionError::TierLimitExceeded(_))))
+    );
+}
+
+#[tokio::test]
+async fn test_starter_approval_rule_limit() {
+    let test_db = TestDb::new().await;
+    let db = test_db.conn();
+    let org = OrgFixture::new()
+        .with_tier(SubscriptionTier::Starter)
+        .create(db)
+        .await;
+    let repo = ApprovalRuleRepository::new(db.clone());
+    let rule = |priority| CreateApprovalRuleInput {
+        name: format!("Rule {priority}"),
+        description: None,
+        min_amount: None,
+        max_amount: None,
+        transaction_types: vec!["expense".to_string()],
+        required_role: "approver".to_string(),
+        priority,
+        required_approvals: 1,
+    };
+
+    for priority in 1..=3 {
+        repo.create_rule(org.id.into_inner(), rule(priority))
+            .await
+            .unwrap();
+    }
+    let Err(ApprovalRuleError::TierLimitExceeded(limit)) =
+        repo.create_rule(org.id.into_inner(), rule(4)).await
+    else {
+        panic!("fourth rule should exceed the starter limit");
+    };
+    assert_eq!((limit.current, limit.limit), (3, Some(3)));
+    assert_eq!(
+        limit.upgrade_tiers,
+        [SubscriptionTier::Growth, SubscriptionTier::Enterprise]
+    );
+}
+
+#[tokio::test]
+async fn test_member_limit_counts_existing_members() {
+    let test_db = TestDb::new().await;
+    let db = test_db.conn();
+    let org = OrgFixture::new()
+        .with_tier(SubscriptionTier::Starter)
+        .with_member(UserRole::Viewer)
+        .create(db)
+        .await;
+    let user = UserRepository::new(db.clone())
+        .create(
+            &format!("{}@example.com", Uuid::new_v4()),
+            "hash",
+            "New Member",
+        )
+        .await
+        .unwrap();
+
+    let limit = SubscriptionRepository::check_limit(db, org.id.into_inner(), ResourceLimit::Users)
+        .await
+        .unwrap();
+    assert!(limit.allowed);
+    assert_eq!(limit.current, 2);
+    assert_eq!(limit.remaining, Some(48));
+    assert!(limit.upgrade_tiers.is_empty());
+
+    OrganizationRepository::new(db.clone())
+        .add_user(org.id.into_inner(), user.id, UserRole::Viewer, None)
+        .await
+        .unwrap();
+    let limit = SubscriptionRepository::check_limit(db, org.id.into_inner(), ResourceLimit::Users)
+        .await
+        .unwrap();
+    assert_eq!(limit.current, 3);
+}
@@ -0,0 +1,298 @@
+//! Integration tests for review claims on pending transactions.
+
+use chrono::{Duration, NaiveDate, Utc};
+use rust_decimal::Decimal;
+use sea_orm::{ActiveModelTrait, DatabaseConnection, EntityTrait, Set};
+use uuid::Uuid;
+
+use zeltra_core::workflow::WorkflowError;
+use zeltra_db::entities::{
+    sea_orm_active_enums::{AccountType, TransactionStatus, TransactionType, UserRole},
+    transaction_claims,
+};
+use zeltra_db::repositories::transaction::{
+    CreateLedgerEntryInput, CreateTransactionInput, TransactionRepository,
+};
+use zeltra_db::repositories::workflow::WorkflowRepository;
+use zeltra_shared::types::TransactionId;
+use zeltra_test_support::{Org, OrgFixture, TestDb};
+
+const ACCOUNTS: &[(&str, AccountType)] =
+    &[("1000", AccountType::Asset), ("5000", AccountType::Expense)];
+
+async fn org_with_approvers(db: &DatabaseConnection) -> Org {
+    OrgFixture::new()
+        .with_fiscal_year_2025()
+        .with_accounts(ACCOUNTS)
+        .with_member(UserRole::Approver)
+        .with_member(UserRole::Approver)
+        .with_member(UserRole::Admin)
+        .create(db)
+        .await
+}
+
+/// Creates and submits an expense, returning its ID.
+async fn pending_transaction(db: &DatabaseConnection, org: &Org) -> TransactionId {
+    let owner = org.owner.user_id.into_inner();
+    let amount = Decimal::new(100, 0);
+    let entry = |account: &str, debit: Decimal, credit: Decimal| CreateLedgerEntryInput {
+        account_id: org.account(account).into_inner(),
+        source_currency: "USD".to_string(),
+        source_amount: amount,
+        exchange_rate: Decimal::ONE,
+        functional_currency: "USD".to_string(),
+        functional_amount: amount,
+        debit,
+        credit,
+        memo: None,
+        dimensions: vec![],
+        rate_override: None,
+        event_at: None,
+    };
+    let transaction = TransactionRepository::new(db.clone())
+        .create_transaction(CreateTransactionInput {
+            organization_id: org.id.into_inner(),
+            transaction_type: TransactionType::Expense,
+            transaction_date: NaiveDate::from_ymd_opt(2025, 3, 10).unwrap(),
+            description: "Office supplies".to_string(),
+            reference_number: None,
+            memo: None,
+            entries: vec![
+                entry("5000", amount, Decimal::ZERO),
+                entry("1000", Decimal::ZERO, amount),
+            ],
+            created_by: owner,
+        })
+        .await
+        .expect("Failed to create transaction")
+        .transaction;
+    let transaction_id = TransactionId::from(transaction.id);
+    WorkflowRepository::new(db.clone())
+        .submit_transaction(org.id, transaction_id, owner)
+        .await
+        .expect("Failed to submit transaction");
+    transaction_id
+}
+
+/// Moves a claim's expiry into the past.
+async fn expire_claim(db: &DatabaseConnection, transaction_id: TransactionId) {
+    let claim = transaction_claims::Entity::find_by_id(transaction_id.into_inner())
+        .one(db)
+        .await
+        .unwrap()
+        .expect("claim exists");
+    let mut active: transaction_claims::ActiveModel = claim.into();
+    active.reviewing_until = Set((Utc::now() - Duration::minutes(1)).into());
+    active.update(db).await.unwrap();
+}
+
+#[tokio::test]
+async fn test_claim_turns_away_other_approvers_until_released() {
+    let test_db = TestDb::new().await;
+    let db = test_db.conn();
+    let org = org_with_approvers(db).await;
+    let first = org.members[0].user_id.into_inner();
+    let second = org.members[1].user_id.into_inner();
+    let transaction_id = pending_transaction(db, &org).await;
+    let workflow = WorkflowRepository::new(db.clone());
+
+    let claim = workflow
+        .claim_transaction(org.id, transaction_id, first)
+        .await
+        .expect("Failed to claim");
+    assert_eq!(claim.reviewer_id, first);
+    assert!(claim.expires_at > Utc::now() + Duration::minutes(9));
+
+    // Renewing keeps the claim with the same reviewer
+    let renewed = workflow
+        .claim_transaction(org.id, transaction_id, first)
+        .await
+        .expect("Failed to renew claim");
+    assert!(renewed.expires_at >= claim.expires_at);
+
+    let err = workflow
+        .claim_transaction(org.id, transaction_id, second)
+        .await
+        .unwrap_err();
+    let WorkflowError::ClaimedByOther {
+        reviewer_id,
+        reviewer_name,
+        ..
+    } = err
+    else {
+        panic!("expected ClaimedByOther, got {err:?}");
+    };
+    assert_eq!(reviewer_id, first);
+    assert!(!reviewer_name.is_empty());
+
+    let err = workflow
+        .approve_transaction(org.id, transaction_id, second, None)
+        .await
+        .unwrap_err();
+    assert!(matches!(err, WorkflowError::ClaimedByOther { .. }));
+    let err = workflow
+        .reject_transaction(org.id, transaction_id, second, "Wrong account".to_string())
+        .await
+        .unwrap_err();
+    assert!(matches!(err, WorkflowError::ClaimedByOther { .. }));
+    let err = workflow
+        .release_claim(org.id, transaction_id, second)
+        .await
+        .unwrap_err();
+    assert!(matches!(err, WorkflowError::ClaimedByOther { .. }));
+
+    let pending = workflow
+        .get_pending_transactions(org.id, second, None, None)
+        .await
+        .unwrap();
+    let shown = pending[0].claim.as_ref().expect("claim is listed");
+    assert_eq!(shown.reviewer_id, first);
+
+    workflow
+        .release_claim(org.id, transaction_id, first)
+        .await
+        .expect("Failed to release claim");
+    let pending = workflow
+        .get_pending_transactions(org.id, second, None, None)
+        .await
+        .unwrap();
+    assert!(pending[0].claim.is_none());
+    workflow
+        .claim_transaction(org.id, transaction_id, second)
+        .await
+        .expect("Released claim can be taken");
+}
+
+#[tokio::test]
+async fn test_lapsed_claim_is_taken_over_and_hidden() {
+    let test_db = TestDb::new().await;
+    let db = test_db.conn();
+    let org = org_with_approvers(db).await;
+    let first = org.members[0].user_id.into_inner();
+    let second = org.members[1].user_id.into_inner();
+    let transaction_id = pending_transaction(db, &org).await;
+    let workflow = WorkflowRepository::new(db.clone());
+
+    workflow
+        .claim_transaction(org.id, transaction_id, first)
+        .await
+        .expect("Failed to claim");
+    expire_claim(db, transaction_id).await;
+
+    // A lapsed claim is not shown and does not block anyone
+    let pending = workflow
+        .get_pending_transactions(org.id, second, None, None)
+        .await
+        .unwrap();
+    assert!(pending[0].claim.is_none());
+
+    let claim = workflow
+        .claim_transaction(org.id, transaction_id, second)
+        .await
+        .expect("Lapsed claim can be taken over");
+    assert_eq!(claim.reviewer_id, second);
+
+    let err = workflow
+        .approve_transaction(org.id, transaction_id, first, None)
+        .await
+        .unwrap_err();
+    assert!(matches!(err, WorkflowError::ClaimedByOther { .. }));
+}
+
+#[tokio::test]
+async fn test_approval_clears_claim_and_admin_acts_through_it() {
+    let test_db = TestDb::new().await;
+    let db = test_db.conn();
+    let org = org_with_approvers(db).await;
+    let first = org.members[0].user_id.into_inner();
+    let admin = org.member(&UserRole::Admin).user_id.into_inner();
+    let workflow = WorkflowRepository::new(db.clone());
+
+    let claimed = pending_transaction(db, &org).await;
+    workflow
+        .claim_transaction(org.id, claimed, first)
+        .await
+        .expect("Failed to claim");
+    let outcome = workflow
+        .approve_transaction(org.id, claimed, first, None)
+        .await
+        .expect("Claimant approves");
+    assert_eq!(outcome.transaction.status, TransactionStatus::Approved);
+    assert!(
+        transaction_claims::Entity::find_by_id(claimed.into_inner())
+            .one(db)
+            .await
+            .unwrap()
+            .is_none()
+    );
+
+    // Approved transactions cannot be claimed
+    let err = workflow
+        .claim_transaction(org.id, claimed, first)
+        .await
+        .unwrap_err();
+    assert!(matches!(err, WorkflowError::NotPending { .. }));
+
+    let overridden = pending_transaction(db, &org).await;
+    workflow
+        .claim_transaction(org.id, overridden, first)
+        .await
+        .expect("Failed to claim");
+    let result = workflow
+        .bulk_approve(org.id, vec![overridden], admin, None)
+        .await
+        .unwrap();
+    assert_eq!(result.success_count, 1);
+    assert!(
+        transaction_claims::Entity::find_by_id(overridden.into_inner())
+            .one(db)
+            .await
+            .unwrap()
+            .is_none()
+    );
+}
+
+#[tokio::test]
+async fn test_only_approvers_claim_and_bulk_approval_honors_claims() {
+    let test_db = TestDb::new().await;
+    let db = test_db.conn();
+    let org = OrgFixture::new()
+        .with_fiscal_year_2025()
+        .with_accounts(ACCOUNTS)
+        .with_member(UserRole::Approver)
+        .with_member(UserRole::Approver)
+        .with_member(UserRole::Viewer)
+        .create(db)
+        .await;
+    let first = org.members[0].user_id.into_inner();
+    let second = org.members[1].user_id.into_inner();
+    let viewer = org.member(&UserRole::Viewer).user_id.into_inner();
+    let claimed = pending_transaction(db, &org).await;
+    let open = pending_transaction(db, &org).await;
+    let workflow = WorkflowRepository::new(db.clone());
+
+    let err = workflow
+        .claim_transaction(org.id, claimed, viewer)
+        .await
+        .unwrap_err();
+    assert!(matches!(err, WorkflowError::NotAuthorizedToApprove));
+    let err = workflow
+        .claim_transaction(org.id, TransactionId::from(Uuid::new_v4()), first)
+        .await
+        .unwrap_err();
+    assert!(matches!(err, WorkflowError::TransactionNotFound(_)));
+
+    workflow
+        .claim_transaction(org.id, claimed, first)
+        .await
+        .expect("Failed to claim");
+    let result = workflow
+        .bulk_approve(org.id, vec![claimed, open], second, None)
+        .await
+        .unwrap();
+    assert_eq!(result.success_count, 1);
+    assert_eq!(result.failure_count, 1);
+    assert_eq!(result.results[0].transaction_id, claimed.into_inner());
+    assert!(!result.results[0].success);
+    assert!(result.results[1].success);
+}
@@ -0,0 +1,280 @@
+//! Integration tests for transaction comment threads and mentions.
+
+use chrono::{Duration, NaiveDate, Utc};
+use rust_decimal::Decimal;
+use uuid::Uuid;
+
+use zeltra_core::auth::UserRole as CoreUserRole;
+use zeltra_core::comment::{CommentError, parse_mentions};
+use zeltra_db::entities::{
+    sea_orm_active_enums::{AccountType, TransactionType, UserRole},
+    transactions,
+};
+use zeltra_db::repositories::comment::{CommentRepoError, CommentRepository, CreateCommentInput};
+use zeltra_db::repositories::transaction::{
+    CreateLedgerEntryInput, CreateTransactionInput, TransactionRepository,
+};
+use zeltra_db::repositories::workflow::WorkflowRepository;
+use zeltra_shared::types::TransactionId;
+use zeltra_test_support::{Org, OrgFixture, TestDb};
+
+const ACCOUNTS: &[(&str, AccountType)] =
+    &[("1000", AccountType::Asset), ("5000", AccountType::Expense)];
+
+fn email(user_id: Uuid) -> String {
+    format!("user-{}@test.zeltra.dev", user_id.simple())
+}
+
+async fn create_transaction(
+    db: &sea_orm::DatabaseConnection,
+    org: &Org,
+    created_by: Uuid,
+) -> transactions::Model {
+    let amount = Decimal::new(100, 0);
+    let entry = |account: &str, debit: Decimal, credit: Decimal| CreateLedgerEntryInput {
+        account_id: org.account(account).into_inner(),
+        source_currency: "USD".to_string(),
+        source_amount: amount,
+        exchange_rate: Decimal::ONE,
+        functional_currency: "USD".to_string(),
+        functional_amount: amount,
+        debit,
+        credit,
+        memo: None,
+        dimensions: vec![],
+        rate_override: None,
+        event_at: None,
+    };
+    TransactionRepository::new(db.clone())
+        .create_transaction(CreateTransactionInput {
+            organization_id: org.id.into_inner(),
+            transaction_type: TransactionType::Expense,
+            transaction_date: NaiveDate::from_ymd_opt(2025, 3, 10).unwrap(),
+            description: "Office supplies".to_string(),
+            reference_number: None,
+            memo: None,
+            entries: vec![
+                entry("5000", amount, Decimal::ZERO),
+                entry("1000", Decimal::ZERO, amount),
+            ],
+            created_by,
+        })
+        .await
+        .expect("Failed to create transaction")
+        .transaction
+}
+
+fn comment(transaction: &transactions::Model, author_id: Uuid, body: &str) -> CreateCommentInput {
+    CreateCommentInput {
+        organization_id: transaction.organization_id,
+        transaction_id: transaction.id,
+        author_id,
+        body: body.to_string(),
+        parent_comment_id: None,
+    }
+}
+
+#[tokio::test]
+async fn test_rejection_reason_is_mirrored_into_thread() {
+    let test_db = TestDb::new().await;
+    let db = test_db.conn();
+    let org = OrgFixture::new()
+        .with_fiscal_year_2025()
+        .with_accounts(ACCOUNTS)
+        .with_member(UserRole::Approver)
+        .create(db)
+        .await;
+    let owner = org.owner.user_id.into_inner();
+    let approver = org.member(&UserRole::Approver).user_id.into_inner();
+    let transaction = create_transaction(db, &org, owner).await;
+    let transaction_id = TransactionId::from(transaction.id);
+    let workflow = WorkflowRepository::new(db.clone());
+    let repo = CommentRepository::new(db.clone());
+
+    workflow
+        .submit_transaction(org.id, transaction_id, owner)
+        .await
+        .expect("Failed to submit transaction");
+    repo.create(comment(&transaction, owner, "Receipt attached"))
+        .await
+        .expect("Failed to comment");
+    workflow
+        .reject_transaction(
+            org.id,
+            transaction_id,
+            approver,
+            "Please fix the dimension, then resubmit".to_string(),
+        )
+        .await
+        .expect("Failed to reject transaction");
+
+    let page = repo
+        .list(org.id.into_inner(), transaction.id, 1, 50)
+        .await
+        .expect("Failed to list comments");
+    assert_eq!(page.total, 2);
+    let thread: Vec<(&str, &str)> = page
+        .comments
+        .iter()
+        .map(|c| (c.comment.kind.as_str(), c.comment.body.as_str()))
+        .collect();
+    assert_eq!(
+        thread,
+        vec![
+            ("comment", "Receipt attached"),
+            ("rejection", "Please fix the dimension, then resubmit"),
+        ]
+    );
+    assert_eq!(page.comments[1].comment.author_id, approver);
+
+    // Replies must stay on the same transaction
+    let other = create_transaction(db, &org, owner).await;
+    let result = repo
+        .create(CreateCommentInput {
+            parent_comment_id: Some(page.comments[1].comment.id),
+            ..comment(&other, owner, "Wrong thread")
+        })
+        .await;
+    assert!(matches!(result, Err(CommentRepoError::ParentNotFound(_))));
+}
+
+#[tokio::test]
+async fn test_mentions_resolve_to_members_who_can_read() {
+    let test_db = TestDb::new().await;
+    let db = test_db.conn();
+    let org = OrgFixture::new()
+        .with_fiscal_year_2025()
+        .with_accounts(ACCOUNTS)
+        .with_member(UserRole::Approver)
+        .with_member(UserRole::Submitter)
+        .create(db)
+        .await;
+    let owner = org.owner.user_id.into_inner();
+    let approver = org.member(&UserRole::Approver).user_id.into_inner();
+    let submitter = org.member(&UserRole::Submitter).user_id.into_inner();
+    let transaction = create_transaction(db, &org, owner).await;
+    let repo = CommentRepository::new(db.clone());
+
+    // The author, an unknown address and a Submitter on someone else's
+    // transaction are left out; case does not matter
+    let body = format!(
+        "@{} please check, cc @{} and @{} and @nobody@example.com.",
+        email(approver).to_uppercase(),
+        email(owner),
+        email(submitter),
+    );
+    let emails = parse_mentions(&body);
+    assert_eq!(emails.len(), 4);
+    let mentioned = repo
+        .find_mentioned_members(&transaction, &emails, owner)
+        .await
+        .expect("Failed to resolve mentions");
+    let ids: Vec<Uuid> = mentioned.iter().map(|m| m.user_id).collect();
+    assert_eq!(ids, vec![approver]);
+
+    // A Submitter can be mentioned on their own transaction
+    let own = create_transaction(db, &org, submitter).await;
+    let mentioned = repo
+        .find_mentioned_members(&own, &emails, owner)
+        .await
+        .expect("Failed to resolve mentions");
+    assert_eq!(mentioned.len(), 2);
+}
+
+#[tokio::test]
+async fn test_delete_rules_and_pagination() {
+    let test_db = TestDb::new().await;
+    let db = test_db.conn();
+    let org = OrgFixture::new()
+        .with_fiscal_year_2025()
+        .with_accounts(ACCOUNTS)
+        .with_member(UserRole::Accountant)
+        .create(db)
+        .await;
+    let org_id = org.id.into_inner();
+    let owner = org.owner.user_id.into_inner();
+    let accountant = org.member(&UserRole::Accountant).user_id.into_inner();
+    let transaction = create_transaction(db, &org, owner).await;
+    let repo = CommentRepository::new(db.clone());
+
+    let mut ids = Vec::new();
+    for n in 0..3 {
+        let posted = repo
+            .create(comment(&transaction, accountant, &format!("Note {n}")))
+            .await
+            .expect("Failed to comment");
+        ids.push(posted.comment.id);
+    }
+    let now = Utc::now();
+
+    // Other members may not delete; the author may not after the window
+    let result = repo
+        .delete(
+            org_id,
+            transaction.id,
+            ids[0],
+            Uuid::new_v4(),
+            CoreUserRole::Approver,
+            now,
+        )
+        .await;
+    assert!(matches!(
+        result,
+        Err(CommentRepoError::Comment(CommentError::NotAuthor))
+    ));
+    let result = repo
+        .delete(
+            org_id,
+            transaction.id,
+            ids[0],
+            accountant,
+            CoreUserRole::Accountant,
+            now + Duration::minutes(16),
+        )
+        .await;
+    assert!(matches!(
+        result,
+        Err(CommentRepoError::Comment(CommentError::DeleteWindowPassed))
+    ));
+
+    let deleted = repo
+        .delete(
+            org_id,
+            transaction.id,
+            ids[0],
+            accountant,
+            CoreUserRole::Accountant,
+            now,
+        )
+        .await
+        .expect("Failed to delete comment");
+    assert_eq!(deleted.deleted_by, Some(accountant));
+    let deleted = repo
+        .delete(
+            org_id,
+            transaction.id,
+            ids[1],
+            owner,
+            CoreUserRole::Owner,
+            now + Duration::days(1),
+        )
+        .await
+        .expect("Failed to delete comment");
+    assert_eq!(deleted.deleted_by, Some(owner));
+
+    // Deleted comments keep their place in the thread
+    let page = repo
+        .list(org_id, transaction.id, 1, 2)
+        .await
+        .expect("Failed to list comments");
+    assert_eq!(page.total, 3);
+    let listed: Vec<Uuid> = page.comments.iter().map(|c| c.comment.id).collect();
+    assert_eq!(listed, ids[..2]);
+    assert!(page.comments.iter().all(|c| c.comment.deleted_at.is_some()));
+    let page = repo
+        .list(org_id, transaction.id, 2, 2)
+        .await
+        .expect("Failed to list comments");
+    assert_eq!(page.comments.len(), 1);
+    assert_eq!(page.comments[0].comment.id, ids[2]);
+}
@@ -0,0 +1,176 @@
+//! Integration tests for moving a draft transaction to another date.
+
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use sea_orm::DatabaseConnection;
+use uuid::Uuid;
+
+use zeltra_db::entities::sea_orm_active_enums::{
+    AccountType, FiscalPeriodStatus, TransactionType, UserRole,
+};
+use zeltra_db::repositories::fiscal::FiscalRepository;
+use zeltra_db::repositories::transaction::{
+    CreateLedgerEntryInput, CreateTransactionInput, TransactionError, TransactionRepository,
+    UpdateTransactionInput,
+};
+use zeltra_shared::types::TransactionId;
+use zeltra_test_support::{Org, OrgFixture, TestDb};
+
+const ACCOUNTS: &[(&str, AccountType)] =
+    &[("1000", AccountType::Asset), ("4000", AccountType::Revenue)];
+
+fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(y, m, d).unwrap()
+}
+
+fn period(org: &Org, month: u32) -> Uuid {
+    let year = org.fiscal_year.as_ref().expect("fixture has a fiscal year");
+    year.periods[month as usize - 1].into_inner()
+}
+
+async fn org_with_accounts(db: &DatabaseConnection) -> Org {
+    OrgFixture::new()
+        .with_fiscal_year_2025()
+        .with_member(UserRole::Accountant)
+        .with_member(UserRole::Approver)
+        .with_accounts(ACCOUNTS)
+        .create(db)
+        .await
+}
+
+async fn create_draft(db: &DatabaseConnection, org: &Org, transaction_date: NaiveDate) -> Uuid {
+    let amount = Decimal::new(100, 0);
+    let entry = |account: Uuid, debit: Decimal, credit: Decimal| CreateLedgerEntryInput {
+        account_id: account,
+        source_currency: "USD".to_string(),
+        source_amount: debit + credit,
+        exchange_rate: Decimal::ONE,
+        functional_currency: "USD".to_string(),
+        functional_amount: debit + credit,
+        debit,
+        credit,
+        memo: None,
+        dimensions: vec![],
+        rate_override: None,
+        event_at: None,
+    };
+    TransactionRepository::new(db.clone())
+        .create_transaction(CreateTransactionInput {
+            organization_id: org.id.into_inner(),
+            transaction_type: TransactionType::Invoice,
+            transaction_date,
+            description: "Consulting".to_string(),
+            reference_number: None,
+            memo: None,
+            entries: vec![
+                entry(org.account("1000").into_inner(), amount, Decimal::ZERO),
+                entry(org.account("4000").into_inner(), Decimal::ZERO, amount),
+            ],
+            created_by: org.owner.user_id.into_inner(),
+        })
+        .await
+        .expect("Failed to create transaction")
+        .transaction
+        .id
+}
+
+async fn move_to(
+    db: &DatabaseConnection,
+    org: &Org,
+    transaction_id: Uuid,
+    new_date: NaiveDate,
+    user_id: Uuid,
+) -> Result<zeltra_db::entities::transactions::Model, TransactionError> {
+    TransactionRepository::new(db.clone())
+        .update_transaction(
+            org.id,
+            TransactionId::from(transaction_id),
+            UpdateTransactionInput {
+                description: None,
+                memo: None,
+                reference_number: None,
+                transaction_date: Some(new_date),
+                updated_by: user_id,
+            },
+        )
+        .await
+}
+
+async fn set_period_status(db: &DatabaseConnection, period_id: Uuid, status: FiscalPeriodStatus) {
+    FiscalRepository::new(db.clone())
+        .update_period_status(period_id, status, None)
+        .await
+        .expect("Failed to update period status");
+}
+
+#[tokio::test]
+async fn test_date_move_across_period_boundary() {
+    let test_db = TestDb::new().await;
+    let db = test_db.conn();
+    let org = org_with_accounts(db).await;
+    let owner = org.owner.user_id.into_inner();
+
+    let id = create_draft(db, &org, date(2025, 3, 31)).await;
+
+    let moved = move_to(db, &org, id, date(2025, 4, 1), owner)
+        .await
+        .expect("Failed to move transaction");
+
+    assert_eq!(moved.transaction_date, date(2025, 4, 1));
+    assert_eq!(moved.fiscal_period_id, period(&org, 4));
+    assert_eq!(moved.description, "Consulting");
+}
+
+#[tokio::test]
+async fn test_date_move_into_closed_period_rejected() {
+    let test_db = TestDb::new().await;
+    let db = test_db.conn();
+    let org = org_with_accounts(db).await;
+    let owner = org.owner.user_id.into_inner();
+
+    set_period_status(db, period(&org, 1), FiscalPeriodStatus::Closed).await;
+    let id = create_draft(db, &org, date(2025, 2, 10)).await;
+
+    let result = move_to(db, &org, id, date(2025, 1, 20), owner).await;
+    assert!(matches!(result, Err(TransactionError::PeriodClosed)));
+
+    let unchanged = TransactionRepository::new(db.clone())
+        .get_transaction(org.id, TransactionId::from(id))
+        .await
+        .expect("Failed to load transaction");
+    assert_eq!(unchanged.transaction.transaction_date, date(2025, 2, 10));
+    assert_eq!(unchanged.transaction.fiscal_period_id, period(&org, 2));
+}
+
+#[tokio::test]
+async fn test_date_move_into_soft_closed_period_depends_on_role() {
+    let test_db = TestDb::new().await;
+    let db = test_db.conn();
+    let org = org_with_accounts(db).await;
+
+    set_period_status(db, period(&org, 1), FiscalPeriodStatus::SoftClose).await;
+    let id = create_draft(db, &org, date(2025, 2, 10)).await;
+
+    let approver = org.member(&UserRole::Approver).user_id.into_inner();
+    let result = move_to(db, &org, id, date(2025, 1, 20), approver).await;
+    assert!(matches!(result, Err(TransactionError::PeriodSoftClosed)));
+
+    let accountant = org.member(&UserRole::Accountant).user_id.into_inner();
+    let moved = move_to(db, &org, id, date(2025, 1, 20), accountant)
+        .await
+        .expect("Accountant may post to a soft-closed period");
+    assert_eq!(moved.fiscal_period_id, period(&org, 1));
+}
+
+#[tokio::test]
+async fn test_date_without_fiscal_period_rejected() {
+    let test_db = TestDb::new().await;
+    let db = test_db.conn();
+    let org = org_with_accounts(db).await;
+    let owner = org.owner.user_id.into_inner();
+
+    let id = create_draft(db, &org, date(2025, 6, 1)).await;
+
+    let result = move_to(db, &org, id, date(2026, 6, 1), owner).await;
+    assert!(matches!(result, Err(TransactionError::NoFiscalPeriod(_))));
+}
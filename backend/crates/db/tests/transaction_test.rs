@@ -10,8 +10,11 @@ use uuid::Uuid;
 
 use zeltra_db::{
     entities::sea_orm_active_enums::{TransactionStatus, TransactionType},
-    repositories::transaction::{CreateLedgerEntryInput, TransactionFilter, TransactionRepository},
+    repositories::transaction::{
+        CreateLedgerEntryInput, TransactionFilter, TransactionRepository, UpdateTransactionInput,
+    },
 };
+use zeltra_shared::types::{OrganizationId, TransactionId};
 
 fn get_database_url() -> String {
     env::var("DATABASE_URL").unwrap_or_else(|_| {
@@ -21,8 +24,7 @@ fn get_database_url() -> String {
     })
 }
 
-// Helper to create test entries (will be used in full integration tests)
-#[allow(dead_code)]
+// Helper to create balanced test entries
 fn create_balanced_entries(
     debit_account_id: Uuid,
     credit_account_id: Uuid,
@@ -41,6 +43,8 @@ fn create_balanced_entries(
             credit: Decimal::ZERO,
             memo: Some("Debit entry".to_string()),
             dimensions: vec![],
+            rate_override: None,
+            event_at: None,
         },
         CreateLedgerEntryInput {
             account_id: credit_account_id,
@@ -53,6 +57,8 @@ fn create_balanced_entries(
             credit: amount,
             memo: Some("Credit entry".to_string()),
             dimensions: vec![],
+            rate_override: None,
+            event_at: None,
         },
     ]
 }
@@ -98,7 +104,7 @@ async fn test_list_transactions_with_filters() {
     let filter = TransactionFilter::default();
 
     // Use a random org_id that likely doesn't exist
-    let org_id = Uuid::new_v4();
+    let org_id = OrganizationId::new();
 
     let result = repo.list_transactions(org_id, filter).await;
 
@@ -121,8 +127,8 @@ async fn test_get_transaction_not_found() {
 
     let repo = TransactionRepository::new(db);
 
-    let org_id = Uuid::new_v4();
-    let transaction_id = Uuid::new_v4();
+    let org_id = OrganizationId::new();
+    let transaction_id = TransactionId::new();
 
     let result = repo.get_transaction(org_id, transaction_id).await;
 
@@ -133,7 +139,7 @@ async fn test_get_transaction_not_found() {
 
     match result {
         Err(zeltra_db::repositories::transaction::TransactionError::NotFound(id)) => {
-            assert_eq!(id, transaction_id);
+            assert_eq!(id, transaction_id.into_inner());
         }
         _ => panic!("Expected NotFound error"),
     }
@@ -150,8 +156,8 @@ async fn test_delete_transaction_not_found() {
 
     let repo = TransactionRepository::new(db);
 
-    let org_id = Uuid::new_v4();
-    let transaction_id = Uuid::new_v4();
+    let org_id = OrganizationId::new();
+    let transaction_id = TransactionId::new();
 
     let result = repo.delete_transaction(org_id, transaction_id).await;
 
@@ -162,7 +168,7 @@ async fn test_delete_transaction_not_found() {
 
     match result {
         Err(zeltra_db::repositories::transaction::TransactionError::NotFound(id)) => {
-            assert_eq!(id, transaction_id);
+            assert_eq!(id, transaction_id.into_inner());
         }
         _ => panic!("Expected NotFound error"),
     }
@@ -179,16 +185,20 @@ async fn test_update_transaction_not_found() {
 
     let repo = TransactionRepository::new(db);
 
-    let org_id = Uuid::new_v4();
-    let transaction_id = Uuid::new_v4();
+    let org_id = OrganizationId::new();
+    let transaction_id = TransactionId::new();
 
     let result = repo
         .update_transaction(
             org_id,
             transaction_id,
-            Some("Updated description".to_string()),
-            None,
-            None,
+            UpdateTransactionInput {
+                description: Some("Updated description".to_string()),
+                memo: None,
+                reference_number: None,
+                transaction_date: None,
+                updated_by: Uuid::new_v4(),
+            },
         )
         .await;
 
@@ -199,7 +209,7 @@ async fn test_update_transaction_not_found() {
 
     match result {
         Err(zeltra_db::repositories::transaction::TransactionError::NotFound(id)) => {
-            assert_eq!(id, transaction_id);
+            assert_eq!(id, transaction_id.into_inner());
         }
         _ => panic!("Expected NotFound error"),
     }
@@ -221,7 +231,7 @@ async fn test_list_transactions_filter_by_status() {
         ..Default::default()
     };
 
-    let org_id = Uuid::new_v4();
+    let org_id = OrganizationId::new();
     let result = repo.list_transactions(org_id, filter).await;
 
     assert!(result.is_ok(), "Filter by status should work");
@@ -243,7 +253,7 @@ async fn test_list_transactions_filter_by_type() {
         ..Default::default()
     };
 
-    let org_id = Uuid::new_v4();
+    let org_id = OrganizationId::new();
     let result = repo.list_transactions(org_id, filter).await;
 
     assert!(result.is_ok(), "Filter by type should work");
@@ -266,8 +276,576 @@ async fn test_list_transactions_filter_by_date_range() {
         ..Default::default()
     };
 
-    let org_id = Uuid::new_v4();
+    let org_id = OrganizationId::new();
     let result = repo.list_transactions(org_id, filter).await;
 
     assert!(result.is_ok(), "Filter by date range should work");
 }
+
+// ============================================================================
+// Test: Transaction summary counts (list view filter chips)
+// ============================================================================
+
+use rust_decimal_macros::dec;
+use sea_orm::{ActiveModelTrait, DatabaseConnection, EntityTrait, Set};
+use zeltra_db::{
+    OrganizationRepository,
+    entities::{
+        organizations,
+        sea_orm_active_enums::{AccountSubtype, AccountType},
+        users,
+    },
+    repositories::{
+        WorkflowRepository,
+        account::{AccountRepository, CreateAccountInput},
+        fiscal::{CreateFiscalYearInput, FiscalRepository},
+        transaction::CreateTransactionInput,
+    },
+};
+use zeltra_shared::types::AccountId;
+
+async fn create_summary_user(db: &DatabaseConnection) -> Uuid {
+    let user_id = Uuid::new_v4();
+    users::ActiveModel {
+        id: Set(user_id),
+        email: Set(format!("summary-{user_id}@example.com")),
+        password_hash: Set("$argon2id$test".to_string()),
+        full_name: Set("Summary User".to_string()),
+        is_active: Set(true),
+        ..Default::default()
+    }
+    .insert(db)
+    .await
+    .expect("Failed to create test user");
+    user_id
+}
+
+async fn create_summary_account(
+    repo: &AccountRepository,
+    org_id: Uuid,
+    code: &str,
+    account_type: AccountType,
+    account_subtype: AccountSubtype,
+) -> AccountId {
+    let account = repo
+        .create_account(CreateAccountInput {
+            organization_id: org_id,
+            code: code.to_string(),
+            name: format!("Account {code}"),
+            account_type,
+            account_subtype: Some(account_subtype),
+            currency: "USD".to_string(),
+            parent_id: None,
+            description: None,
+            is_active: true,
+            allow_direct_posting: true,
+            is_bank_account: false,
+            bank_account_number: None,
+        })
+        .await
+        .expect("Failed to create account");
+    AccountId::from_uuid(account.id)
+}
+
+#[tokio::test]
+#[allow(clippy::too_many_lines)]
+async fn test_summarize_counts_by_status_and_type() {
+    let db = Database::connect(&get_database_url())
+        .await
+        .expect("Failed to connect to database");
+
+    let owner_id = create_summary_user(&db).await;
+    let org = OrganizationRepository::new(db.clone())
+        .create_with_owner(
+            "Summary Org",
+            &format!("summary-org-{}", Uuid::new_v4()),
+            "USD",
+            "UTC",
+            owner_id,
+        )
+        .await
+        .expect("Failed to create organization");
+    let org_id = OrganizationId::from_uuid(org.id);
+
+    FiscalRepository::new(db.clone())
+        .create_fiscal_year(CreateFiscalYearInput {
+            organization_id: org.id,
+            name: "FY 2026".to_string(),
+            start_date: NaiveDate::from_ymd_opt(2026, 1, 1).unwrap(),
+            end_date: NaiveDate::from_ymd_opt(2026, 12, 31).unwrap(),
+        })
+        .await
+        .expect("Failed to create fiscal year");
+
+    let account_repo = AccountRepository::new(db.clone());
+    let cash = create_summary_account(
+        &account_repo,
+        org.id,
+        "1000",
+        AccountType::Asset,
+        AccountSubtype::Cash,
+    )
+    .await;
+    let supplies = create_summary_account(
+        &account_repo,
+        org.id,
+        "5000",
+        AccountType::Expense,
+        AccountSubtype::OperatingExpense,
+    )
+    .await;
+
+    let repo = TransactionRepository::new(db.clone());
+    let workflow = WorkflowRepository::new(db.clone());
+
+    // (type, amount, target status)
+    let seeds = [
+        (
+            TransactionType::Expense,
+            dec!(100),
+            TransactionStatus::Draft,
+        ),
+        (TransactionType::Expense, dec!(40), TransactionStatus::Draft),
+        (
+            TransactionType::Journal,
+            dec!(75),
+            TransactionStatus::Pending,
+        ),
+        (
+            TransactionType::Expense,
+            dec!(100),
+            TransactionStatus::Posted,
+        ),
+        (
+            TransactionType::Journal,
+            dec!(250.50),
+            TransactionStatus::Posted,
+        ),
+    ];
+
+    for (idx, (tx_type, amount, status)) in seeds.iter().enumerate() {
+        let created = repo
+            .create_transaction(CreateTransactionInput {
+                organization_id: org.id,
+                transaction_type: tx_type.clone(),
+                transaction_date: NaiveDate::from_ymd_opt(2026, 3, 1).unwrap(),
+                description: format!("Summary seed {idx}"),
+                reference_number: None,
+                memo: None,
+                created_by: owner_id,
+                entries: create_balanced_entries(
+                    supplies.into_inner(),
+                    cash.into_inner(),
+                    *amount,
+                    "USD",
+                ),
+            })
+            .await
+            .expect("Failed to create transaction");
+        let tx_id = TransactionId::from_uuid(created.transaction.id);
+
+        if *status == TransactionStatus::Draft {
+            continue;
+        }
+        workflow
+            .submit_transaction(org_id, tx_id, owner_id)
+            .await
+            .expect("Failed to submit");
+        if *status == TransactionStatus::Posted {
+            workflow
+                .approve_transaction(org_id, tx_id, owner_id, None)
+                .await
+                .expect("Failed to approve");
+            workflow
+                .post_transaction(org_id, tx_id, owner_id)
+                .await
+                .expect("Failed to post");
+        }
+    }
+
+    let summary = repo
+        .summarize(org_id, TransactionFilter::default())
+        .await
+        .expect("Failed to summarize");
+
+    let status_count = |status: TransactionStatus| {
+        summary
+            .by_status
+            .iter()
+            .find(|(s, _)| *s == status)
+            .map_or(0, |(_, c)| *c)
+    };
+    let type_count = |tx_type: TransactionType| {
+        summary
+            .by_type
+            .iter()
+            .find(|(t, _)| *t == tx_type)
+            .map_or(0, |(_, c)| *c)
+    };
+
+    assert_eq!(status_count(TransactionStatus::Draft), 2);
+    assert_eq!(status_count(TransactionStatus::Pending), 1);
+    assert_eq!(status_count(TransactionStatus::Posted), 2);
+    assert_eq!(status_count(TransactionStatus::Approved), 0);
+    assert_eq!(type_count(TransactionType::Expense), 3);
+    assert_eq!(type_count(TransactionType::Journal), 2);
+    assert_eq!(summary.posted_debit_total, dec!(350.50));
+
+    // Filters narrow every aggregate consistently
+    let expenses = repo
+        .summarize(
+            org_id,
+            TransactionFilter {
+                transaction_type: Some(TransactionType::Expense),
+                ..Default::default()
+            },
+        )
+        .await
+        .expect("Failed to summarize expenses");
+    assert_eq!(expenses.by_type, vec![(TransactionType::Expense, 3)]);
+    assert_eq!(expenses.posted_debit_total, dec!(100));
+
+    organizations::Entity::delete_by_id(org.id)
+        .exec(&db)
+        .await
+        .ok();
+}
+
+// ============================================================================
+// Test: Entries are rejected on accounts that cannot take them
+// ============================================================================
+
+use zeltra_db::repositories::transaction::TransactionError;
+use zeltra_test_support::{OrgFixture, TestDb};
+
+async fn create_posting_account(
+    repo: &AccountRepository,
+    org_id: Uuid,
+    code: &str,
+    currency: &str,
+    is_active: bool,
+    allow_direct_posting: bool,
+) -> Uuid {
+    repo.create_account(CreateAccountInput {
+        organization_id: org_id,
+        code: code.to_string(),
+        name: format!("Account {code}"),
+        account_type: AccountType::Asset,
+        account_subtype: None,
+        currency: currency.to_string(),
+        parent_id: None,
+        description: None,
+        is_active,
+        allow_direct_posting,
+        is_bank_account: false,
+        bank_account_number: None,
+    })
+    .await
+    .expect("Failed to create account")
+    .id
+}
+
+#[tokio::test]
+async fn test_create_transaction_rejects_unpostable_accounts() {
+    let test_db = TestDb::new().await;
+    let db = test_db.conn();
+    let org = OrgFixture::new()
+        .with_fiscal_year_2025()
+        .with_accounts(&[("4000", AccountType::Revenue)])
+        .create(db)
+        .await;
+    let org_id = org.id.into_inner();
+
+    let account_repo = AccountRepository::new(db.clone());
+    let inactive = create_posting_account(&account_repo, org_id, "1900", "USD", false, true).await;
+    let header = create_posting_account(&account_repo, org_id, "1800", "USD", true, false).await;
+    let eur_bank = create_posting_account(&account_repo, org_id, "1200", "EUR", true, true).await;
+    let revenue = org.account("4000").into_inner();
+
+    let repo = TransactionRepository::new(db.clone());
+    let create = |entries: Vec<CreateLedgerEntryInput>| {
+        repo.create_transaction(CreateTransactionInput {
+            organization_id: org_id,
+            transaction_type: TransactionType::Journal,
+            transaction_date: NaiveDate::from_ymd_opt(2025, 3, 15).unwrap(),
+            description: "Account checks".to_string(),
+            reference_number: None,
+            memo: None,
+            entries,
+            created_by: org.owner.user_id.into_inner(),
+        })
+    };
+
+    let err = create(create_balanced_entries(inactive, revenue, dec!(100), "USD"))
+        .await
+        .unwrap_err();
+    assert!(matches!(err, TransactionError::AccountInactive(ref code) if code == "1900"));
+
+    let err = create(create_balanced_entries(header, revenue, dec!(100), "USD"))
+        .await
+        .unwrap_err();
+    assert!(matches!(err, TransactionError::DirectPostingNotAllowed(ref code) if code == "1800"));
+
+    // A EUR account only takes EUR entries
+    let mut entries = create_balanced_entries(eur_bank, revenue, dec!(110), "USD");
+    let err = create(entries.clone()).await.unwrap_err();
+    assert!(matches!(
+        err,
+        TransactionError::CurrencyMismatch { ref account, ref account_currency, ref entry_currency }
+            if account == "1200" && account_currency == "EUR" && entry_currency == "USD"
+    ));
+
+    // EUR on the EUR account, and EUR translated into the USD revenue account
+    for entry in &mut entries {
+        entry.source_currency = "EUR".to_string();
+        entry.source_amount = dec!(100);
+        entry.exchange_rate = dec!(1.1);
+    }
+    create(entries)
+        .await
+        .expect("Entries in the account currency should be accepted");
+}
+
+// ============================================================================
+// Test: Listing only the transactions a user created
+// ============================================================================
+
+use zeltra_db::entities::sea_orm_active_enums::UserRole;
+
+#[tokio::test]
+async fn test_list_transactions_filter_by_creator() {
+    let test_db = TestDb::new().await;
+    let db = test_db.conn();
+    let org = OrgFixture::new()
+        .with_fiscal_year_2025()
+        .with_member(UserRole::Submitter)
+        .with_accounts(&[("1000", AccountType::Asset), ("4000", AccountType::Revenue)])
+        .create(db)
+        .await;
+    let owner_id = org.owner.user_id.into_inner();
+    let submitter_id = org.member(&UserRole::Submitter).user_id.into_inner();
+
+    let repo = TransactionRepository::new(db.clone());
+    for (description, created_by) in [
+        ("Owner entry", owner_id),
+        ("Submitter entry", submitter_id),
+        ("Another owner entry", owner_id),
+    ] {
+        repo.create_transaction(CreateTransactionInput {
+            organization_id: org.id.into_inner(),
+            transaction_type: TransactionType::Journal,
+            transaction_date: NaiveDate::from_ymd_opt(2025, 3, 15).unwrap(),
+            description: description.to_string(),
+            reference_number: None,
+            memo: None,
+            created_by,
+            entries: create_balanced_entries(
+                org.account("1000").into_inner(),
+                org.account("4000").into_inner(),
+                dec!(50),
+                "USD",
+            ),
+        })
+        .await
+        .expect("Failed to create transaction");
+    }
+
+    let own = repo
+        .list_transactions(
+            org.id,
+            TransactionFilter {
+                created_by: Some(submitter_id),
+                ..Default::default()
+            },
+        )
+        .await
+        .expect("Failed to list transactions");
+    assert_eq!(own.len(), 1);
+    assert_eq!(own[0].description, "Submitter entry");
+    assert_eq!(own[0].created_by, submitter_id);
+
+    let all = repo
+        .list_transactions(org.id, TransactionFilter::default())
+        .await
+        .expect("Failed to list transactions");
+    assert_eq!(all.len(), 3);
+}
+
+// ============================================================================
+// Test: Filtering by account and amount range
+// ============================================================================
+
+/// Descriptions of the listed transactions, sorted.
+async fn listed_descriptions(
+    repo: &TransactionRepository,
+    org_id: OrganizationId,
+    filter: TransactionFilter,
+) -> Vec<String> {
+    let mut descriptions: Vec<String> = repo
+        .list_transactions(org_id, filter)
+        .await
+        .expect("Failed to list transactions")
+        .into_iter()
+        .map(|t| t.description)
+        .collect();
+    descriptions.sort();
+    descriptions
+}
+
+#[tokio::test]
+#[allow(clippy::too_many_lines)]
+async fn test_list_transactions_filter_by_account_and_amount() {
+    let test_db = TestDb::new().await;
+    let db = test_db.conn();
+    let org = OrgFixture::new()
+        .with_fiscal_year_2025()
+        .with_member(UserRole::Submitter)
+        .with_accounts(&[
+            ("1000", AccountType::Asset),
+            ("1010", AccountType::Asset),
+            ("4000", AccountType::Revenue),
+            ("5000", AccountType::Expense),
+        ])
+        .create(db)
+        .await;
+    let owner_id = org.owner.user_id.into_inner();
+    let submitter_id = org.member(&UserRole::Submitter).user_id.into_inner();
+    let account = |code: &str| org.account(code).into_inner();
+
+    // (description, type, date, creator, entries)
+    let seeds = [
+        (
+            "Bank expense",
+            TransactionType::Expense,
+            NaiveDate::from_ymd_opt(2025, 3, 10).unwrap(),
+            owner_id,
+            create_balanced_entries(account("5000"), account("1010"), dec!(6000), "USD"),
+        ),
+        (
+            "Cash expense",
+            TransactionType::Expense,
+            NaiveDate::from_ymd_opt(2025, 3, 12).unwrap(),
+            owner_id,
+            create_balanced_entries(account("5000"), account("1000"), dec!(7000), "USD"),
+        ),
+        (
+            "Bank deposit",
+            TransactionType::Journal,
+            NaiveDate::from_ymd_opt(2025, 2, 1).unwrap(),
+            submitter_id,
+            create_balanced_entries(account("1010"), account("4000"), dec!(2000), "USD"),
+        ),
+        (
+            "Split bank payment",
+            TransactionType::Journal,
+            NaiveDate::from_ymd_opt(2025, 5, 5).unwrap(),
+            owner_id,
+            [
+                create_balanced_entries(account("5000"), account("1010"), dec!(3000), "USD"),
+                create_balanced_entries(account("5000"), account("1010"), dec!(2500), "USD"),
+            ]
+            .concat(),
+        ),
+    ];
+
+    let repo = TransactionRepository::new(db.clone());
+    for (description, transaction_type, transaction_date, created_by, entries) in seeds {
+        repo.create_transaction(CreateTransactionInput {
+            organization_id: org.id.into_inner(),
+            transaction_type,
+            transaction_date,
+            description: description.to_string(),
+            reference_number: None,
+            memo: None,
+            created_by,
+            entries,
+        })
+        .await
+        .expect("Failed to create transaction");
+    }
+
+    let list = |filter| listed_descriptions(&repo, org.id, filter);
+
+    // Two entries on 1010 still list the transaction once
+    assert_eq!(
+        list(TransactionFilter {
+            account_id: Some(account("1010")),
+            ..Default::default()
+        })
+        .await,
+        ["Bank deposit", "Bank expense", "Split bank payment"]
+    );
+
+    // Amounts compare against the total debit, inclusive at both ends
+    assert_eq!(
+        list(TransactionFilter {
+            min_amount: Some(dec!(5500)),
+            ..Default::default()
+        })
+        .await,
+        ["Bank expense", "Cash expense", "Split bank payment"]
+    );
+    assert_eq!(
+        list(TransactionFilter {
+            max_amount: Some(dec!(5500)),
+            ..Default::default()
+        })
+        .await,
+        ["Bank deposit", "Split bank payment"]
+    );
+    assert_eq!(
+        list(TransactionFilter {
+            min_amount: Some(dec!(5000)),
+            max_amount: Some(dec!(6500)),
+            ..Default::default()
+        })
+        .await,
+        ["Bank expense", "Split bank payment"]
+    );
+
+    // Combined with the existing filters
+    assert_eq!(
+        list(TransactionFilter {
+            account_id: Some(account("1010")),
+            min_amount: Some(dec!(5000)),
+            date_from: NaiveDate::from_ymd_opt(2025, 3, 1),
+            date_to: NaiveDate::from_ymd_opt(2025, 3, 31),
+            ..Default::default()
+        })
+        .await,
+        ["Bank expense"]
+    );
+    assert_eq!(
+        list(TransactionFilter {
+            account_id: Some(account("1010")),
+            transaction_type: Some(TransactionType::Journal),
+            ..Default::default()
+        })
+        .await,
+        ["Bank deposit", "Split bank payment"]
+    );
+    assert_eq!(
+        list(TransactionFilter {
+            account_id: Some(account("1010")),
+            created_by: Some(submitter_id),
+            status: Some(TransactionStatus::Draft),
+            ..Default::default()
+        })
+        .await,
+        ["Bank deposit"]
+    );
+
+    // Summaries select the same transactions
+    let summary = repo
+        .summarize(
+            org.id,
+            TransactionFilter {
+                account_id: Some(account("1010")),
+                min_amount: Some(dec!(5000)),
+                ..Default::default()
+            },
+        )
+        .await
+        .expect("Failed to summarize");
+    assert_eq!(summary.by_status, vec![(TransactionStatus::Draft, 2)]);
+}
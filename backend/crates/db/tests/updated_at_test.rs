@@ -0,0 +1,434 @@
+//! Integration tests for `updated_at` tracking on mutable tables.
+
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use sea_orm::{
+    ConnectionTrait, DatabaseConnection, DbBackend, EntityTrait, Statement,
+    prelude::DateTimeWithTimeZone,
+};
+use serde_json::json;
+use uuid::Uuid;
+
+use zeltra_db::OrganizationRepository;
+use zeltra_db::entities::{
+    chart_of_accounts, organizations,
+    sea_orm_active_enums::{AccountType, BudgetType, FiscalPeriodStatus},
+};
+use zeltra_db::repositories::account::{AccountFilter, AccountRepository, UpdateAccountInput};
+use zeltra_db::repositories::approval_rule::{
+    ApprovalRuleRepository, CreateApprovalRuleInput, UpdateApprovalRuleInput,
+};
+use zeltra_db::repositories::budget::{
+    BudgetRepository, CreateBudgetInput, CreateBudgetLineInput, UpdateBudgetInput,
+    UpdateBudgetLineInput,
+};
+use zeltra_db::repositories::dimension::{
+    CreateDimensionTypeInput, CreateDimensionValueInput, DimensionRepository, DimensionTypeFilter,
+    DimensionValueFilter, UpdateDimensionTypeInput, UpdateDimensionValueInput,
+};
+use zeltra_db::repositories::fiscal::FiscalRepository;
+use zeltra_test_support::{Org, OrgFixture, TestDb};
+
+async fn org_with_year(db: &DatabaseConnection) -> Org {
+    OrgFixture::new()
+        .with_fiscal_year_2025()
+        .with_accounts(&[("1000", AccountType::Asset), ("5000", AccountType::Expense)])
+        .create(db)
+        .await
+}
+
+async fn account_updated_at(db: &DatabaseConnection, id: Uuid) -> DateTimeWithTimeZone {
+    chart_of_accounts::Entity::find_by_id(id)
+        .one(db)
+        .await
+        .unwrap()
+        .unwrap()
+        .updated_at
+}
+
+#[tokio::test]
+async fn test_every_updated_at_table_has_touch_trigger() {
+    let test_db = TestDb::new().await;
+    let db = test_db.conn();
+
+    let rows = db
+        .query_all(Statement::from_string(
+            DbBackend::Postgres,
+            r"
+SELECT c.table_name::text AS table_name
+FROM information_schema.columns c
+JOIN information_schema.tables t
+  ON t.table_schema = c.table_schema AND t.table_name = c.table_name
+WHERE c.table_schema = 'public'
+  AND c.column_name = 'updated_at'
+  AND t.table_type = 'BASE TABLE'
+  AND NOT EXISTS (
+      SELECT 1 FROM pg_trigger tg
+      WHERE tg.tgrelid = format('public.%I', c.table_name)::regclass
+        AND tg.tgname = 'trg_touch_updated_at'
+  )
+",
+        ))
+        .await
+        .unwrap();
+    let missing: Vec<String> = rows
+        .iter()
+        .map(|row| row.try_get("", "table_name").unwrap())
+        .collect();
+
+    assert!(missing.is_empty(), "no touch trigger on {missing:?}");
+}
+
+#[tokio::test]
+async fn test_trigger_stamps_updates_that_leave_updated_at_alone() {
+    let test_db = TestDb::new().await;
+    let db = test_db.conn();
+    let org = org_with_year(db).await;
+    let account_id = org.account("1000").into_inner();
+    let before = account_updated_at(db, account_id).await;
+
+    db.execute_unprepared(&format!(
+        "UPDATE chart_of_accounts SET name = 'Renamed' WHERE id = '{account_id}'"
+    ))
+    .await
+    .unwrap();
+    let touched = account_updated_at(db, account_id).await;
+    assert!(touched > before);
+
+    // A no-op update is not a change
+    db.execute_unprepared(&format!(
+        "UPDATE chart_of_accounts SET name = name WHERE id = '{account_id}'"
+    ))
+    .await
+    .unwrap();
+    assert_eq!(account_updated_at(db, account_id).await, touched);
+
+    // An explicit value is kept
+    db.execute_unprepared(&format!(
+        "UPDATE chart_of_accounts SET updated_at = '2020-01-01T00:00:00Z' WHERE id = '{account_id}'"
+    ))
+    .await
+    .unwrap();
+    let explicit = account_updated_at(db, account_id).await;
+    assert_eq!(
+        explicit.with_timezone(&Utc).to_rfc3339(),
+        "2020-01-01T00:00:00+00:00"
+    );
+}
+
+#[tokio::test]
+async fn test_account_and_dimension_updates_advance_updated_at() {
+    let test_db = TestDb::new().await;
+    let db = test_db.conn();
+    let org = org_with_year(db).await;
+    let org_id = org.id.into_inner();
+
+    let accounts = AccountRepository::new(db.clone());
+    let account_id = org.account("5000");
+    let before = account_updated_at(db, account_id.into_inner()).await;
+    let account = accounts
+        .update_account(
+            account_id,
+            UpdateAccountInput {
+                name: Some("Office Supplies".to_string()),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+    assert!(account.updated_at > before);
+
+    let dimensions = DimensionRepository::new(db.clone());
+    let dim_type = dimensions
+        .create_dimension_type(CreateDimensionTypeInput {
+            organization_id: org_id,
+            code: "DEPT".to_string(),
+            name: "Department".to_string(),
+            description: None,
+            is_required: false,
+            is_active: true,
+            sort_order: 1,
+        })
+        .await
+        .unwrap();
+    let updated_type = dimensions
+        .update_dimension_type(
+            dim_type.id,
+            UpdateDimensionTypeInput {
+                name: Some("Departments".to_string()),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+    assert!(updated_type.updated_at > dim_type.updated_at);
+
+    let value = dimensions
+        .create_dimension_value(CreateDimensionValueInput {
+            organization_id: org_id,
+            dimension_type_id: dim_type.id,
+            code: "ENG".to_string(),
+            name: "Engineering".to_string(),
+            description: None,
+            parent_id: None,
+            is_active: true,
+            effective_from: None,
+            effective_to: None,
+        })
+        .await
+        .unwrap();
+    let updated_value = dimensions
+        .update_dimension_value(
+            value.id,
+            UpdateDimensionValueInput {
+                is_active: Some(false),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+    assert!(updated_value.updated_at > value.updated_at);
+}
+
+#[tokio::test]
+#[allow(clippy::too_many_lines)]
+async fn test_budget_fiscal_and_rule_updates_advance_updated_at() {
+    let test_db = TestDb::new().await;
+    let db = test_db.conn();
+    let org = org_with_year(db).await;
+    let org_id = org.id.into_inner();
+    let year = org.fiscal_year.as_ref().expect("fixture has a fiscal year");
+
+    let budgets = BudgetRepository::new(db.clone());
+    let budget = budgets
+        .create_budget(CreateBudgetInput {
+            organization_id: org_id,
+            fiscal_year_id: year.id.into_inner(),
+            name: "Operating 2025".to_string(),
+            description: None,
+            budget_type: BudgetType::Monthly,
+            currency: "USD".to_string(),
+            created_by: org.owner.user_id.into_inner(),
+        })
+        .await
+        .unwrap();
+    let updated_budget = budgets
+        .update_budget(
+            org_id,
+            budget.id,
+            budget.created_by,
+            UpdateBudgetInput {
+                name: Some("Operating 2025 (revised)".to_string()),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+    assert!(updated_budget.updated_at > budget.updated_at);
+
+    let lines = budgets
+        .create_budget_lines(
+            org_id,
+            budget.id,
+            budget.created_by,
+            vec![CreateBudgetLineInput {
+                account_id: org.account("5000").into_inner(),
+                fiscal_period_id: year.periods[0].into_inner(),
+                amount: Decimal::new(1000, 0),
+                notes: None,
+                dimensions: vec![],
+            }],
+        )
+        .await
+        .unwrap();
+    let line = &lines[0].line;
+    let updated_line = budgets
+        .update_budget_line(
+            org_id,
+            budget.id,
+            line.id,
+            budget.created_by,
+            UpdateBudgetLineInput {
+                amount: Some(Decimal::new(1200, 0)),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+    assert!(updated_line.updated_at > line.updated_at);
+
+    let fiscal = FiscalRepository::new(db.clone());
+    let period_before = fiscal
+        .list_fiscal_years(org_id)
+        .await
+        .unwrap()
+        .into_iter()
+        .flat_map(|y| y.periods)
+        .find(|p| p.id == year.periods[0].into_inner())
+        .unwrap();
+    let period = fiscal
+        .update_period_status(
+            period_before.id,
+            FiscalPeriodStatus::SoftClose,
+            Some(org.owner.user_id.into_inner()),
+        )
+        .await
+        .unwrap();
+    assert!(period.updated_at > period_before.updated_at);
+
+    let rules = ApprovalRuleRepository::new(db.clone());
+    let rule = rules
+        .create_rule(
+            org_id,
+            CreateApprovalRuleInput {
+                name: "Large expenses".to_string(),
+                description: None,
+                min_amount: Some(Decimal::new(5000, 0)),
+                max_amount: None,
+                transaction_types: vec!["expense".to_string()],
+                required_role: "approver".to_string(),
+                priority: 1,
+                required_approvals: 1,
+            },
+        )
+        .await
+        .unwrap();
+    let updated_rule = rules
+        .update_rule(
+            org_id,
+            rule.id,
+            UpdateApprovalRuleInput {
+                priority: Some(2),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+    assert!(updated_rule.updated_at > rule.updated_at);
+
+    let org_before = organizations::Entity::find_by_id(org_id)
+        .one(db)
+        .await
+        .unwrap()
+        .unwrap()
+        .updated_at;
+    OrganizationRepository::new(db.clone())
+        .update_settings(org_id, &json!({ "bank_import": {} }))
+        .await
+        .unwrap();
+    let org_after = organizations::Entity::find_by_id(org_id)
+        .one(db)
+        .await
+        .unwrap()
+        .unwrap()
+        .updated_at;
+    assert!(org_after > org_before);
+}
+
+#[tokio::test]
+async fn test_modified_since_lists_only_changed_accounts_and_dimensions() {
+    let test_db = TestDb::new().await;
+    let db = test_db.conn();
+    let org = org_with_year(db).await;
+    let org_id = org.id.into_inner();
+
+    let dimensions = DimensionRepository::new(db.clone());
+    let dim_type = dimensions
+        .create_dimension_type(CreateDimensionTypeInput {
+            organization_id: org_id,
+            code: "PROJECT".to_string(),
+            name: "Project".to_string(),
+            description: None,
+            is_required: false,
+            is_active: true,
+            sort_order: 1,
+        })
+        .await
+        .unwrap();
+    let mut values = Vec::new();
+    for code in ["ALPHA", "BETA"] {
+        let value = dimensions
+            .create_dimension_value(CreateDimensionValueInput {
+                organization_id: org_id,
+                dimension_type_id: dim_type.id,
+                code: code.to_string(),
+                name: code.to_string(),
+                description: None,
+                parent_id: None,
+                is_active: true,
+                effective_from: None,
+                effective_to: None,
+            })
+            .await
+            .unwrap();
+        values.push(value);
+    }
+
+    let watermark: DateTime<Utc> = account_updated_at(db, org.account("5000").into_inner())
+        .await
+        .max(values[1].updated_at)
+        .into();
+
+    let accounts = AccountRepository::new(db.clone());
+    accounts
+        .update_account(
+            org.account("5000"),
+            UpdateAccountInput {
+                name: Some("Travel".to_string()),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+    dimensions
+        .update_dimension_value(
+            values[0].id,
+            UpdateDimensionValueInput {
+                name: Some("Alpha".to_string()),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+
+    let changed_accounts = accounts
+        .list_accounts(
+            org.id,
+            AccountFilter {
+                modified_since: Some(watermark),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+    let codes: Vec<&str> = changed_accounts
+        .iter()
+        .map(|a| a.account.code.as_str())
+        .collect();
+    assert_eq!(codes, ["5000"]);
+
+    let changed_values = dimensions
+        .list_dimension_values(
+            org_id,
+            DimensionValueFilter {
+                modified_since: Some(watermark),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+    assert_eq!(changed_values.len(), 1);
+    assert_eq!(changed_values[0].id, values[0].id);
+
+    let changed_types = dimensions
+        .list_dimension_types(
+            org_id,
+            DimensionTypeFilter {
+                modified_since: Some(watermark),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+    assert!(changed_types.is_empty());
+}
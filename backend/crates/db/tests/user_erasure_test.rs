@@ -0,0 +1,198 @@
+//! Integration tests for deactivating users and erasing their personal data.
+
+use chrono::{Duration, NaiveDate, Utc};
+use rust_decimal_macros::dec;
+use sea_orm::{ColumnTrait, DatabaseConnection, EntityTrait, PaginatorTrait, QueryFilter};
+use uuid::Uuid;
+
+use zeltra_db::entities::sea_orm_active_enums::{AccountType, UserRole};
+use zeltra_db::entities::{organization_users, sessions, transactions, users};
+use zeltra_db::repositories::workflow::WorkflowRepository;
+use zeltra_db::repositories::{SessionRepository, UserError, UserRepository};
+use zeltra_db::{OrganizationRepository, entities::sea_orm_active_enums::TransactionStatus};
+use zeltra_test_support::{Line, Org, OrgFixture, TestDb, post_transaction_as};
+
+async fn open_session(db: &DatabaseConnection, org: &Org, user_id: Uuid) {
+    SessionRepository::new(db.clone())
+        .create(
+            user_id,
+            org.id.into_inner(),
+            &Uuid::new_v4().to_string(),
+            Utc::now() + Duration::days(7),
+            Some("Mozilla/5.0"),
+            Some("203.0.113.7"),
+        )
+        .await
+        .expect("Failed to create session");
+}
+
+#[tokio::test]
+async fn test_erased_user_keeps_their_transactions() {
+    let test_db = TestDb::new().await;
+    let db = test_db.conn();
+    let org = OrgFixture::new()
+        .with_fiscal_year_2025()
+        .with_accounts(&[("1000", AccountType::Asset), ("6000", AccountType::Expense)])
+        .with_member(UserRole::Accountant)
+        .create(db)
+        .await;
+    let accountant = org.member(&UserRole::Accountant).user_id.into_inner();
+    let repo = UserRepository::new(db.clone());
+    let original = repo.find_by_id(accountant).await.unwrap().unwrap();
+
+    let posted = post_transaction_as(
+        db,
+        &org,
+        accountant.into(),
+        NaiveDate::from_ymd_opt(2025, 3, 10).unwrap(),
+        vec![
+            Line::debit("6000", dec!(250)),
+            Line::credit("1000", dec!(250)),
+        ],
+    )
+    .await;
+    open_session(db, &org, accountant).await;
+
+    let erased = repo.anonymize(accountant).await.unwrap();
+    let short_id = &accountant.simple().to_string()[..8];
+    assert_eq!(erased.user.id, accountant);
+    assert_eq!(
+        erased.user.email,
+        format!("deleted-user-{short_id}@invalid")
+    );
+    assert_eq!(erased.user.full_name, format!("Deleted user {short_id}"));
+    assert!(erased.user.password_hash.is_empty());
+    assert!(!erased.user.is_active);
+    assert!(erased.user.email_verified_at.is_none());
+    assert_eq!(erased.user.token_version, original.token_version + 1);
+    assert_eq!(erased.memberships_removed, 1);
+    assert_eq!(erased.sessions_deleted, 1);
+
+    // The old address no longer finds anyone
+    assert!(repo.find_by_email(&original.email).await.unwrap().is_none());
+    assert!(
+        !OrganizationRepository::new(db.clone())
+            .is_member(org.id.into_inner(), accountant)
+            .await
+            .unwrap()
+    );
+    assert_eq!(
+        sessions::Entity::find()
+            .filter(sessions::Column::UserId.eq(accountant))
+            .count(db)
+            .await
+            .unwrap(),
+        0
+    );
+
+    // The posted transaction still points at the user, now shown anonymized
+    let transaction = transactions::Entity::find_by_id(posted.into_inner())
+        .one(db)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(transaction.created_by, accountant);
+    assert_eq!(transaction.status, TransactionStatus::Posted);
+    let history = WorkflowRepository::new(db.clone())
+        .get_status_history(org.id, posted)
+        .await
+        .unwrap();
+    let submitted = history
+        .entries
+        .iter()
+        .find(|entry| entry.history.actor_id == accountant)
+        .expect("submission is still in the history");
+    assert_eq!(
+        submitted.actor_name.as_deref(),
+        Some(erased.user.full_name.as_str())
+    );
+}
+
+#[tokio::test]
+async fn test_sole_owner_cannot_be_erased() {
+    let test_db = TestDb::new().await;
+    let db = test_db.conn();
+    let org = OrgFixture::new().create(db).await;
+    let other = OrgFixture::new().create(db).await;
+    let owner = org.owner.user_id.into_inner();
+    let repo = UserRepository::new(db.clone());
+
+    let err = repo.anonymize(owner).await.unwrap_err();
+    let UserError::SoleOwner(slugs) = err else {
+        panic!("expected the sole owner error, got {err:?}");
+    };
+    let slug = OrganizationRepository::new(db.clone())
+        .find_by_id(org.id.into_inner())
+        .await
+        .unwrap()
+        .unwrap()
+        .slug;
+    assert_eq!(slugs, [slug]);
+    let user = repo.find_by_id(owner).await.unwrap().unwrap();
+    assert!(user.is_active);
+    assert!(!user.email.ends_with("@invalid"));
+
+    // Owning alongside someone else does not block
+    let co_owner = other.owner.user_id.into_inner();
+    OrganizationRepository::new(db.clone())
+        .add_user(org.id.into_inner(), co_owner, UserRole::Owner, None)
+        .await
+        .unwrap();
+    assert!(repo.sole_owner_of(owner).await.unwrap().is_empty());
+    repo.anonymize(owner).await.unwrap();
+    assert_eq!(
+        organization_users::Entity::find()
+            .filter(organization_users::Column::OrganizationId.eq(org.id.into_inner()))
+            .count(db)
+            .await
+            .unwrap(),
+        1
+    );
+
+    assert!(matches!(
+        repo.anonymize(Uuid::new_v4()).await,
+        Err(UserError::NotFound(_))
+    ));
+}
+
+#[tokio::test]
+async fn test_deactivate_revokes_sessions() {
+    let test_db = TestDb::new().await;
+    let db = test_db.conn();
+    let org = OrgFixture::new()
+        .with_member(UserRole::Viewer)
+        .create(db)
+        .await;
+    let viewer = org.member(&UserRole::Viewer).user_id.into_inner();
+    let repo = UserRepository::new(db.clone());
+    open_session(db, &org, viewer).await;
+
+    assert!(repo.deactivate(viewer).await.unwrap());
+    let user = users::Entity::find_by_id(viewer)
+        .one(db)
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(!user.is_active);
+    assert_eq!(user.token_version, 1);
+    assert_eq!(
+        SessionRepository::new(db.clone())
+            .count_active_sessions(viewer)
+            .await
+            .unwrap(),
+        0
+    );
+    // Membership and identity are untouched
+    assert!(
+        OrganizationRepository::new(db.clone())
+            .is_member(org.id.into_inner(), viewer)
+            .await
+            .unwrap()
+    );
+
+    assert!(!repo.deactivate(viewer).await.unwrap());
+    assert!(matches!(
+        repo.deactivate(Uuid::new_v4()).await,
+        Err(UserError::NotFound(_))
+    ));
+}
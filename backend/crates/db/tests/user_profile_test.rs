@@ -0,0 +1,114 @@
+//! Integration tests for user profile preferences and email changes.
+
+use zeltra_db::repositories::{UpdateProfileInput, UserError};
+use zeltra_db::{EmailVerificationRepository, UserRepository};
+use zeltra_test_support::{OrgFixture, TestDb};
+
+#[tokio::test]
+async fn test_update_profile_sets_preferences() {
+    let test_db = TestDb::new().await;
+    let db = test_db.conn();
+    let org = OrgFixture::new().create(db).await;
+    let user_id = org.owner.user_id.into_inner();
+
+    let user = UserRepository::new(db.clone())
+        .update_profile(
+            user_id,
+            UpdateProfileInput {
+                full_name: Some("  Dewi Lestari ".to_string()),
+                locale: Some("id-ID".to_string()),
+                default_organization_id: Some(org.id.into_inner()),
+            },
+        )
+        .await
+        .expect("Failed to update profile");
+
+    assert_eq!(user.full_name, "Dewi Lestari");
+    assert_eq!(user.locale.as_deref(), Some("id-ID"));
+    assert_eq!(user.default_organization_id, Some(org.id.into_inner()));
+}
+
+#[tokio::test]
+async fn test_default_org_must_be_a_membership() {
+    let test_db = TestDb::new().await;
+    let db = test_db.conn();
+    let org = OrgFixture::new().create(db).await;
+    let other = OrgFixture::new().create(db).await;
+    let repo = UserRepository::new(db.clone());
+    let user_id = org.owner.user_id.into_inner();
+
+    let result = repo
+        .update_profile(
+            user_id,
+            UpdateProfileInput {
+                default_organization_id: Some(other.id.into_inner()),
+                ..Default::default()
+            },
+        )
+        .await;
+
+    assert!(matches!(result, Err(UserError::NotMember(id)) if id == other.id.into_inner()));
+    let user = repo.find_by_id(user_id).await.unwrap().unwrap();
+    assert_eq!(user.default_organization_id, None);
+}
+
+#[tokio::test]
+async fn test_unsupported_locale_is_rejected() {
+    let test_db = TestDb::new().await;
+    let db = test_db.conn();
+    let org = OrgFixture::new().create(db).await;
+
+    let result = UserRepository::new(db.clone())
+        .update_profile(
+            org.owner.user_id.into_inner(),
+            UpdateProfileInput {
+                locale: Some("xx-XX".to_string()),
+                ..Default::default()
+            },
+        )
+        .await;
+
+    assert!(matches!(result, Err(UserError::UnsupportedLocale(_))));
+}
+
+#[tokio::test]
+async fn test_email_changes_only_when_token_is_confirmed() {
+    let test_db = TestDb::new().await;
+    let db = test_db.conn();
+    let org = OrgFixture::new().create(db).await;
+    let user_id = org.owner.user_id.into_inner();
+    let users = UserRepository::new(db.clone());
+    let verification = EmailVerificationRepository::new(db.clone());
+    let old_email = users.find_by_id(user_id).await.unwrap().unwrap().email;
+    let new_email = format!("new-{user_id}@test.zeltra.dev");
+
+    let token = verification
+        .create_email_change_token(user_id, &new_email)
+        .await
+        .expect("Failed to create token");
+    let pending = users.find_by_id(user_id).await.unwrap().unwrap();
+    assert_eq!(pending.email, old_email);
+
+    let verified = verification
+        .verify_token(&token)
+        .await
+        .expect("Failed to verify token");
+    assert_eq!(verified.user.email, new_email);
+    assert_eq!(verified.previous_email, Some(old_email));
+    assert!(verification.verify_token(&token).await.is_err());
+}
+
+#[tokio::test]
+async fn test_signup_token_keeps_email() {
+    let test_db = TestDb::new().await;
+    let db = test_db.conn();
+    let org = OrgFixture::new().create(db).await;
+    let user_id = org.owner.user_id.into_inner();
+    let verification = EmailVerificationRepository::new(db.clone());
+
+    let token = verification.create_token(user_id).await.unwrap();
+    let verified = verification.verify_token(&token).await.unwrap();
+
+    assert_eq!(verified.previous_email, None);
+    assert!(verified.user.email_verified_at.is_some());
+}
@@ -0,0 +1,259 @@
+//! Integration tests for printable journal vouchers.
+
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use sea_orm::{ActiveModelTrait, DatabaseConnection, Set};
+use serde_json::json;
+use uuid::Uuid;
+
+use zeltra_core::reports::voucher::{VoucherStep, VoucherTag};
+use zeltra_core::workflow::{TransactionStatus, VoidReasonCode};
+use zeltra_db::entities::sea_orm_active_enums::{AccountType, TransactionType, UserRole};
+use zeltra_db::entities::users;
+use zeltra_db::repositories::dimension::{
+    CreateDimensionTypeInput, CreateDimensionValueInput, DimensionRepository,
+};
+use zeltra_db::repositories::transaction::{
+    CreateLedgerEntryInput, CreateTransactionInput, TransactionError, TransactionRepository,
+};
+use zeltra_db::repositories::{OrganizationRepository, VoucherRepository, WorkflowRepository};
+use zeltra_shared::format::Locale;
+use zeltra_shared::types::TransactionId;
+use zeltra_test_support::{Org, OrgFixture, TestDb};
+
+async fn rename_user(db: &DatabaseConnection, id: Uuid, name: &str) {
+    users::ActiveModel {
+        id: Set(id),
+        full_name: Set(name.to_string()),
+        ..Default::default()
+    }
+    .update(db)
+    .await
+    .expect("Failed to rename user");
+}
+
+/// Creates a dimension value `value` of a new dimension type `dimension`.
+async fn dimension_value(
+    db: &DatabaseConnection,
+    org: &Org,
+    dimension: &str,
+    sort_order: i16,
+    value: &str,
+) -> Uuid {
+    let repo = DimensionRepository::new(db.clone());
+    let type_id = repo
+        .create_dimension_type(CreateDimensionTypeInput {
+            organization_id: org.id.into_inner(),
+            code: dimension.to_uppercase(),
+            name: dimension.to_string(),
+            description: None,
+            is_required: false,
+            is_active: true,
+            sort_order,
+        })
+        .await
+        .expect("Failed to create dimension type")
+        .id;
+    repo.create_dimension_value(CreateDimensionValueInput {
+        organization_id: org.id.into_inner(),
+        dimension_type_id: type_id,
+        code: value.to_uppercase(),
+        name: value.to_string(),
+        description: None,
+        parent_id: None,
+        is_active: true,
+        effective_from: None,
+        effective_to: None,
+    })
+    .await
+    .expect("Failed to create dimension value")
+    .id
+}
+
+/// Creates an expense paid from cash, with the cash line entered first.
+async fn create_expense(
+    db: &DatabaseConnection,
+    org: &Org,
+    amount: Decimal,
+    dimensions: Vec<Uuid>,
+) -> TransactionId {
+    let entry = |code: &str, debit: Decimal, credit: Decimal, dimensions: Vec<Uuid>| {
+        CreateLedgerEntryInput {
+            account_id: org.account(code).into_inner(),
+            source_currency: "USD".to_string(),
+            source_amount: debit + credit,
+            exchange_rate: Decimal::ONE,
+            functional_currency: "USD".to_string(),
+            functional_amount: debit + credit,
+            debit,
+            credit,
+            memo: Some(format!("Line {code}")),
+            dimensions,
+            rate_override: None,
+            event_at: None,
+        }
+    };
+    let created = TransactionRepository::new(db.clone())
+        .create_transaction(CreateTransactionInput {
+            organization_id: org.id.into_inner(),
+            transaction_type: TransactionType::Expense,
+            transaction_date: NaiveDate::from_ymd_opt(2025, 3, 15).unwrap(),
+            description: "Team offsite".to_string(),
+            reference_number: Some("JV-0042".to_string()),
+            memo: None,
+            entries: vec![
+                entry("1000", Decimal::ZERO, amount, vec![]),
+                entry("6000", amount, Decimal::ZERO, dimensions),
+            ],
+            created_by: org.owner.user_id.into_inner(),
+        })
+        .await
+        .expect("Failed to create transaction");
+    TransactionId::from(created.transaction.id)
+}
+
+#[tokio::test]
+#[allow(clippy::too_many_lines)]
+async fn test_voucher_lists_entries_tags_and_trail() {
+    let test_db = TestDb::new().await;
+    let db = test_db.conn();
+    let org = OrgFixture::new()
+        .with_member(UserRole::Accountant)
+        .with_fiscal_year_2025()
+        .with_accounts(&[("1000", AccountType::Asset), ("6000", AccountType::Expense)])
+        .create(db)
+        .await;
+    let owner = org.owner.user_id.into_inner();
+    let accountant = org.member(&UserRole::Accountant).user_id.into_inner();
+    rename_user(db, owner, "Ana Putri").await;
+    rename_user(db, accountant, "Budi Santoso").await;
+    OrganizationRepository::new(db.clone())
+        .update_settings(
+            org.id.into_inner(),
+            &json!({"formatting": {"locale": "id-ID"}}),
+        )
+        .await
+        .expect("Failed to update settings");
+
+    // Sorted by dimension type, not by creation
+    let project = dimension_value(db, &org, "Project", 2, "Offsite").await;
+    let department = dimension_value(db, &org, "Department", 1, "Sales").await;
+    let id = create_expense(db, &org, dec!(1234.56), vec![project, department]).await;
+
+    let workflow = WorkflowRepository::new(db.clone());
+    workflow
+        .submit_transaction(org.id, id, owner)
+        .await
+        .expect("Failed to submit");
+    workflow
+        .approve_transaction(org.id, id, accountant, None)
+        .await
+        .expect("Failed to approve");
+    workflow
+        .post_transaction(org.id, id, owner)
+        .await
+        .expect("Failed to post");
+
+    let repo = VoucherRepository::new(db.clone());
+    let voucher = repo.voucher(org.id, id).await.unwrap();
+    assert!(voucher.organization_name.starts_with("Test Organization"));
+    assert_eq!(voucher.reference(), "JV-0042");
+    assert_eq!(voucher.transaction_type, "expense");
+    assert_eq!(voucher.status, TransactionStatus::Posted);
+    assert_eq!(voucher.currency, "USD");
+    assert_eq!(voucher.decimal_places, 2);
+    assert_eq!(voucher.locale, Locale::IdId);
+
+    // Debits first, whatever order the lines were entered in
+    let codes: Vec<&str> = voucher
+        .lines
+        .iter()
+        .map(|l| l.account_code.as_str())
+        .collect();
+    assert_eq!(codes, ["6000", "1000"]);
+    assert_eq!(voucher.lines[0].account_name, "Account 6000");
+    assert_eq!(voucher.lines[0].memo.as_deref(), Some("Line 6000"));
+    assert_eq!(
+        voucher.lines[0].tags,
+        [
+            VoucherTag {
+                dimension: "Department".to_string(),
+                value: "Sales".to_string(),
+            },
+            VoucherTag {
+                dimension: "Project".to_string(),
+                value: "Offsite".to_string(),
+            },
+        ]
+    );
+    assert!(voucher.lines[1].tags.is_empty());
+    assert_eq!(voucher.total_debit(), dec!(1234.56));
+    assert_eq!(
+        voucher.total_in_words(),
+        "Seribu dua ratus tiga puluh empat dan 56/100 dolar Amerika Serikat"
+    );
+    OrganizationRepository::new(db.clone())
+        .update_settings(
+            org.id.into_inner(),
+            &json!({"formatting": {"locale": "en-US"}}),
+        )
+        .await
+        .expect("Failed to update settings");
+    assert_eq!(
+        repo.voucher(org.id, id).await.unwrap().total_in_words(),
+        "One thousand two hundred thirty-four and 56/100 US dollars"
+    );
+
+    let trail: Vec<(VoucherStep, &str)> = voucher
+        .trail
+        .iter()
+        .map(|s| (s.step, s.name.as_str()))
+        .collect();
+    assert_eq!(
+        trail,
+        [
+            (VoucherStep::Submitted, "Ana Putri"),
+            (VoucherStep::Approved, "Budi Santoso"),
+            (VoucherStep::Posted, "Ana Putri"),
+        ]
+    );
+
+    workflow
+        .void_transaction(
+            org.id,
+            id,
+            owner,
+            VoidReasonCode::DuplicateEntry,
+            "Entered twice".to_string(),
+        )
+        .await
+        .expect("Failed to void");
+    let voided = repo.voucher(org.id, id).await.unwrap();
+    assert!(voided.is_void());
+    assert_eq!(voided.trail.last().unwrap().step, VoucherStep::Voided);
+    assert!(voided.void_reason.is_some());
+    assert!(voided.to_html().contains("<div class=\"void\">VOID</div>"));
+}
+
+#[tokio::test]
+async fn test_voucher_of_another_organization_is_not_found() {
+    let test_db = TestDb::new().await;
+    let db = test_db.conn();
+    let org = OrgFixture::new()
+        .with_fiscal_year_2025()
+        .with_accounts(&[("1000", AccountType::Asset), ("6000", AccountType::Expense)])
+        .create(db)
+        .await;
+    let other = OrgFixture::new().create(db).await;
+    let id = create_expense(db, &org, dec!(10), vec![]).await;
+
+    let repo = VoucherRepository::new(db.clone());
+    let draft = repo.voucher(org.id, id).await.unwrap();
+    assert_eq!(draft.status, TransactionStatus::Draft);
+    assert!(draft.trail.is_empty());
+    assert!(matches!(
+        repo.voucher(other.id, id).await,
+        Err(TransactionError::NotFound(_))
+    ));
+}
@@ -0,0 +1,159 @@
+//! Integration tests for zero-amount lines on adjustment transactions.
+
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use sea_orm::{ConnectionTrait, DatabaseConnection, DbBackend, Statement};
+use uuid::Uuid;
+
+use zeltra_db::entities::sea_orm_active_enums::{AccountType, TransactionType};
+use zeltra_db::migration::{Migrator, MigratorTrait};
+use zeltra_db::repositories::transaction::{
+    CreateLedgerEntryInput, CreateTransactionInput, TransactionError, TransactionRepository,
+    TransactionWithEntries,
+};
+use zeltra_db::repositories::workflow::WorkflowRepository;
+use zeltra_shared::types::TransactionId;
+use zeltra_test_support::{Org, OrgFixture, TestDb};
+
+const ACCOUNTS: &[(&str, AccountType)] = &[
+    ("1000", AccountType::Asset),
+    ("1200", AccountType::Asset),
+    ("5000", AccountType::Expense),
+];
+
+fn entry(account: Uuid, debit: Decimal, credit: Decimal) -> CreateLedgerEntryInput {
+    CreateLedgerEntryInput {
+        account_id: account,
+        source_currency: "USD".to_string(),
+        source_amount: debit + credit,
+        exchange_rate: Decimal::ONE,
+        functional_currency: "USD".to_string(),
+        functional_amount: debit + credit,
+        debit,
+        credit,
+        memo: None,
+        dimensions: vec![],
+        rate_override: None,
+        event_at: None,
+    }
+}
+
+/// Creates a 250.00 entry from 1000 to 5000 with a zero line on 1200.
+async fn create_with_zero_line(
+    db: &DatabaseConnection,
+    org: &Org,
+    transaction_type: TransactionType,
+) -> Result<TransactionWithEntries, TransactionError> {
+    let amount = Decimal::new(25000, 2);
+    TransactionRepository::new(db.clone())
+        .create_transaction(CreateTransactionInput {
+            organization_id: org.id.into_inner(),
+            transaction_type,
+            transaction_date: NaiveDate::from_ymd_opt(2025, 6, 30).unwrap(),
+            description: "Reclassify accrual".to_string(),
+            reference_number: None,
+            memo: None,
+            entries: vec![
+                entry(org.account("5000").into_inner(), amount, Decimal::ZERO),
+                entry(org.account("1000").into_inner(), Decimal::ZERO, amount),
+                entry(
+                    org.account("1200").into_inner(),
+                    Decimal::ZERO,
+                    Decimal::ZERO,
+                ),
+            ],
+            created_by: org.owner.user_id.into_inner(),
+        })
+        .await
+}
+
+#[tokio::test]
+async fn test_adjustment_with_zero_line_posts() {
+    let test_db = TestDb::new().await;
+    let db = test_db.conn();
+    let org = OrgFixture::new()
+        .with_fiscal_year_2025()
+        .with_accounts(ACCOUNTS)
+        .create(db)
+        .await;
+
+    let created = create_with_zero_line(db, &org, TransactionType::Adjustment)
+        .await
+        .expect("Failed to create adjustment");
+    assert_eq!(created.entries.len(), 3);
+
+    let id = TransactionId::from(created.transaction.id);
+    let user_id = org.owner.user_id.into_inner();
+    let workflow = WorkflowRepository::new(db.clone());
+    workflow
+        .submit_transaction(org.id, id, user_id)
+        .await
+        .expect("Failed to submit adjustment");
+    workflow
+        .approve_transaction(org.id, id, user_id, None)
+        .await
+        .expect("Failed to approve adjustment");
+    workflow
+        .post_transaction(org.id, id, user_id)
+        .await
+        .expect("Zero line should not unbalance the adjustment");
+
+    let zero_line = created
+        .entries
+        .iter()
+        .find(|e| e.entry.account_id == org.account("1200").into_inner())
+        .unwrap();
+    assert_eq!(zero_line.entry.account_current_balance, Decimal::ZERO);
+}
+
+#[tokio::test]
+async fn test_zero_line_rejected_outside_adjustments() {
+    let test_db = TestDb::new().await;
+    let db = test_db.conn();
+    let org = OrgFixture::new()
+        .with_fiscal_year_2025()
+        .with_accounts(ACCOUNTS)
+        .create(db)
+        .await;
+
+    let result = create_with_zero_line(db, &org, TransactionType::Journal).await;
+    assert!(result.is_err());
+}
+
+#[tokio::test]
+async fn test_rollback_keeps_zero_lines() {
+    let test_db = TestDb::new().await;
+    let db = test_db.conn();
+    let org = OrgFixture::new()
+        .with_fiscal_year_2025()
+        .with_accounts(ACCOUNTS)
+        .create(db)
+        .await;
+    create_with_zero_line(db, &org, TransactionType::Adjustment)
+        .await
+        .expect("Failed to create adjustment");
+
+    let zero_lines = || async {
+        db.query_one(Statement::from_string(
+            DbBackend::Postgres,
+            "SELECT COUNT(*) AS n FROM ledger_entries WHERE debit = 0 AND credit = 0",
+        ))
+        .await
+        .unwrap()
+        .unwrap()
+        .try_get::<i64>("", "n")
+        .unwrap()
+    };
+
+    let migrations = Migrator::migrations();
+    let position = migrations
+        .iter()
+        .position(|m| m.name() == "m20260108_000019_zero_adjustment_entries")
+        .unwrap();
+    let steps = u32::try_from(migrations.len() - position).unwrap();
+    Migrator::down(db, Some(steps)).await.unwrap();
+    assert_eq!(zero_lines().await, 1);
+
+    Migrator::up(db, None).await.unwrap();
+    assert_eq!(zero_lines().await, 1);
+}
@@ -0,0 +1,187 @@
+//! Reminders for transactions left waiting on approval.
+
+use std::{sync::Arc, time::Duration};
+
+use async_trait::async_trait;
+use chrono::Utc;
+use sea_orm::{DbErr, prelude::Uuid};
+use serde_json::json;
+use tracing::{info, warn};
+use zeltra_core::notification::NotificationType;
+use zeltra_db::repositories::{
+    DueEscalation, EscalationRecipient, NotificationRepository, WorkflowRepository,
+};
+use zeltra_shared::{EmailService, types::TransactionId};
+
+use crate::job::{Job, JobContext, JobError, Schedule};
+
+/// Reminds eligible approvers about transactions pending longer than their
+/// organization's `approval_escalation` threshold, with a courtesy note to
+/// the submitter, by email and in the app as each recipient prefers.
+///
+/// Each transaction is claimed before its emails go out, so it is escalated
+/// at most once per UTC day even if sending fails.
+pub struct ApprovalEscalationJob {
+    email: Arc<EmailService>,
+}
+
+impl ApprovalEscalationJob {
+    /// Creates the job.
+    #[must_use]
+    pub const fn new(email: Arc<EmailService>) -> Self {
+        Self { email }
+    }
+
+    /// Sends the reminders for one transaction, returning how many failed.
+    ///
+    /// Each recipient's preferences decide whether the reminder goes to
+    /// their inbox, their email, or both.
+    async fn notify(&self, repo: &NotificationRepository, escalation: &DueEscalation) -> usize {
+        let tx = &escalation.transaction;
+        let label = tx
+            .reference_number
+            .clone()
+            .unwrap_or_else(|| tx.description.clone());
+        let payload = json!({
+            "transaction_id": tx.id,
+            "reference_number": tx.reference_number,
+            "description": tx.description,
+            "days_pending": escalation.days_pending,
+        });
+
+        let mut failed = 0;
+        let approvers = match deliver(
+            repo,
+            tx.organization_id,
+            NotificationType::ApprovalReminder,
+            &payload,
+            &escalation.approvers,
+        )
+        .await
+        {
+            Ok(approvers) => approvers,
+            Err(e) => {
+                warn!(transaction_id = %tx.id, error = %e, "Failed to write approval reminders");
+                failed += 1;
+                escalation.approvers.iter().collect()
+            }
+        };
+        for approver in approvers {
+            if let Err(e) = self
+                .email
+                .send_approval_reminder_email(
+                    &approver.email,
+                    &approver.full_name,
+                    &escalation.organization_name,
+                    &label,
+                    escalation.days_pending,
+                )
+                .await
+            {
+                warn!(transaction_id = %tx.id, user_id = %approver.user_id, error = %e, "Failed to send approval reminder");
+                failed += 1;
+            }
+        }
+
+        // The submitter only hears about it when someone was reminded
+        if !escalation.approvers.is_empty()
+            && let Some(submitter) = &escalation.submitter
+        {
+            let email_submitter = match deliver(
+                repo,
+                tx.organization_id,
+                NotificationType::ApprovalPending,
+                &payload,
+                std::slice::from_ref(submitter),
+            )
+            .await
+            {
+                Ok(recipients) => !recipients.is_empty(),
+                Err(e) => {
+                    warn!(transaction_id = %tx.id, error = %e, "Failed to write pending approval notice");
+                    failed += 1;
+                    true
+                }
+            };
+            if email_submitter
+                && let Err(e) = self
+                    .email
+                    .send_approval_pending_notice(
+                        &submitter.email,
+                        &submitter.full_name,
+                        &escalation.organization_name,
+                        &label,
+                        escalation.days_pending,
+                    )
+                    .await
+            {
+                warn!(transaction_id = %tx.id, user_id = %submitter.user_id, error = %e, "Failed to send pending approval notice");
+                failed += 1;
+            }
+        }
+        failed
+    }
+}
+
+/// Writes in-app notifications for `recipients` and returns those who also
+/// want an email.
+async fn deliver<'a>(
+    repo: &NotificationRepository,
+    organization_id: Uuid,
+    notification_type: NotificationType,
+    payload: &serde_json::Value,
+    recipients: &'a [EscalationRecipient],
+) -> Result<Vec<&'a EscalationRecipient>, DbErr> {
+    let ids: Vec<Uuid> = recipients.iter().map(|r| r.user_id).collect();
+    let outcome = repo
+        .notify(organization_id, notification_type, payload.clone(), &ids)
+        .await?;
+    Ok(recipients
+        .iter()
+        .filter(|r| outcome.email_recipients.contains(&r.user_id))
+        .collect())
+}
+
+#[async_trait]
+impl Job for ApprovalEscalationJob {
+    fn name(&self) -> &'static str {
+        "approval_escalation"
+    }
+
+    fn schedule(&self) -> Schedule {
+        Schedule::every(Duration::from_secs(3600))
+    }
+
+    async fn run(&self, ctx: &JobContext) -> Result<(), JobError> {
+        let repo = WorkflowRepository::new(ctx.db.clone());
+        let notifications = NotificationRepository::new(ctx.db.clone());
+        let now = Utc::now();
+        let due = repo
+            .find_due_escalations(now)
+            .await
+            .map_err(|e| JobError::Failed(e.to_string()))?;
+
+        let (mut escalated, mut unassigned, mut failed) = (0_usize, 0_usize, 0_usize);
+        for escalation in due {
+            let claimed = repo
+                .mark_escalated(TransactionId::from(escalation.transaction.id), now)
+                .await
+                .map_err(|e| JobError::Failed(e.to_string()))?;
+            if !claimed {
+                continue;
+            }
+            if escalation.approvers.is_empty() {
+                warn!(transaction_id = %escalation.transaction.id, "No eligible approver to remind");
+                unassigned += 1;
+            }
+            failed += self.notify(&notifications, &escalation).await;
+            escalated += 1;
+        }
+
+        info!(
+            escalated,
+            unassigned, failed, "Approval escalation run finished"
+        );
+        Ok(())
+    }
+}
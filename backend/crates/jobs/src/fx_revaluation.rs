@@ -0,0 +1,63 @@
+//! Month-end FX revaluation drafts.
+
+use std::time::Duration;
+
+use async_trait::async_trait;
+use chrono::Utc;
+use tracing::{info, warn};
+use zeltra_db::repositories::{FxRevaluationRepository, PeriodEndOutcome};
+
+use crate::job::{Job, JobContext, JobError, Schedule};
+
+/// Prepares the unrealized FX gain/loss adjustment for fiscal periods ending
+/// today or tomorrow, in organizations whose plan includes multi-currency.
+///
+/// Drafts are left for review and posting. A period already revalued is
+/// recognised by its `FXREVAL-YYYY-MM` reference, so the job can run several
+/// times a day. Organizations missing the gain/loss accounts or a rate get a
+/// notification instead of a draft.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FxRevaluationJob;
+
+#[async_trait]
+impl Job for FxRevaluationJob {
+    fn name(&self) -> &'static str {
+        "fx_revaluation"
+    }
+
+    fn schedule(&self) -> Schedule {
+        Schedule::every(Duration::from_secs(6 * 3600))
+    }
+
+    async fn run(&self, ctx: &JobContext) -> Result<(), JobError> {
+        let repo = FxRevaluationRepository::new(ctx.db.clone());
+        let periods = repo.due_periods(Utc::now().date_naive(), None).await?;
+
+        let (mut created, mut skipped, mut failed) = (0_usize, 0_usize, 0_usize);
+        for period in &periods {
+            match repo.revalue_period_end(period).await {
+                Ok(run) => match run.outcome {
+                    PeriodEndOutcome::Created { transaction_id, .. } => {
+                        info!(organization_id = %run.organization_id, %transaction_id, reference = %run.reference, "FX revaluation draft created");
+                        created += 1;
+                    }
+                    PeriodEndOutcome::Skipped(reason) => {
+                        warn!(organization_id = %run.organization_id, reference = %run.reference, ?reason, "FX revaluation skipped");
+                        skipped += 1;
+                    }
+                    _ => {}
+                },
+                Err(e) => {
+                    warn!(organization_id = %period.organization_id, fiscal_period_id = %period.id, error = %e, "Failed to prepare FX revaluation");
+                    failed += 1;
+                }
+            }
+        }
+
+        info!(
+            periods = periods.len(),
+            created, skipped, failed, "FX revaluation run finished"
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,176 @@
+//! Job trait and schedules.
+
+use std::{fmt, str::FromStr, time::Duration};
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sea_orm::{DatabaseConnection, DbErr};
+
+/// Default limit on a single run of a job.
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(300);
+
+/// Error types for job runs.
+#[derive(Debug, thiserror::Error)]
+pub enum JobError {
+    /// Database error.
+    #[error("Database error: {0}")]
+    Database(#[from] DbErr),
+
+    /// Cron expression could not be parsed.
+    #[error("Invalid cron expression '{expression}': {reason}")]
+    InvalidSchedule {
+        /// The rejected expression.
+        expression: String,
+        /// Parser error.
+        reason: String,
+    },
+
+    /// Job-specific failure.
+    #[error("{0}")]
+    Failed(String),
+}
+
+/// Resources available to every job run.
+#[derive(Debug, Clone)]
+pub struct JobContext {
+    /// Database connection pool.
+    pub db: DatabaseConnection,
+}
+
+impl JobContext {
+    /// Creates a job context.
+    #[must_use]
+    pub const fn new(db: DatabaseConnection) -> Self {
+        Self { db }
+    }
+}
+
+/// When a job runs.
+#[derive(Debug, Clone)]
+pub enum Schedule {
+    /// Runs repeatedly, waiting the given duration after each run finishes.
+    Every(Duration),
+    /// Runs at the times matched by a cron expression, in UTC.
+    Cron(Box<cron::Schedule>),
+}
+
+impl Schedule {
+    /// Runs repeatedly with `period` between the end of one run and the next.
+    #[must_use]
+    pub const fn every(period: Duration) -> Self {
+        Self::Every(period)
+    }
+
+    /// Parses a cron expression with a leading seconds field,
+    /// e.g. `0 30 2 * * *` for 02:30:00 UTC daily.
+    ///
+    /// # Errors
+    ///
+    /// Returns `JobError::InvalidSchedule` if the expression is invalid.
+    pub fn cron(expression: &str) -> Result<Self, JobError> {
+        cron::Schedule::from_str(expression)
+            .map(|schedule| Self::Cron(Box::new(schedule)))
+            .map_err(|e| JobError::InvalidSchedule {
+                expression: expression.to_string(),
+                reason: e.to_string(),
+            })
+    }
+
+    /// Returns the next run time after a run that finished at `now`.
+    ///
+    /// `None` means the schedule has no further runs.
+    #[must_use]
+    pub fn next_after(&self, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        match self {
+            Self::Every(period) => chrono::Duration::from_std(*period)
+                .ok()
+                .and_then(|period| now.checked_add_signed(period)),
+            Self::Cron(schedule) => schedule.after(&now).next(),
+        }
+    }
+}
+
+impl fmt::Display for Schedule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Every(period) => write!(f, "every {}s", period.as_secs()),
+            Self::Cron(schedule) => write!(f, "cron {schedule}"),
+        }
+    }
+}
+
+/// A periodic background task.
+///
+/// Jobs are registered with a [`Scheduler`](crate::Scheduler), which owns
+/// timing, timeouts and logging; `run` only does the work.
+#[async_trait]
+pub trait Job: Send + Sync + 'static {
+    /// Unique name used in logs and on the status board.
+    fn name(&self) -> &'static str;
+
+    /// When the job runs.
+    fn schedule(&self) -> Schedule;
+
+    /// Longest a single run may take before it is cancelled.
+    fn timeout(&self) -> Duration {
+        DEFAULT_TIMEOUT
+    }
+
+    /// Whether at most one replica may run the job at a time.
+    ///
+    /// Exclusive runs hold an advisory lock derived from the job name; a run
+    /// that finds the lock taken is skipped. Return `false` for jobs that
+    /// maintain per-process state.
+    fn exclusive(&self) -> bool {
+        true
+    }
+
+    /// Performs one run of the job.
+    async fn run(&self, ctx: &JobContext) -> Result<(), JobError>;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_every_schedule_adds_period() {
+        let now = Utc.with_ymd_and_hms(2026, 1, 1, 0, 0, 0).unwrap();
+        let schedule = Schedule::every(Duration::from_secs(90));
+
+        assert_eq!(
+            schedule.next_after(now),
+            Some(Utc.with_ymd_and_hms(2026, 1, 1, 0, 1, 30).unwrap())
+        );
+    }
+
+    #[test]
+    fn test_cron_schedule_next_fire_time() {
+        let now = Utc.with_ymd_and_hms(2026, 1, 1, 3, 0, 0).unwrap();
+        let schedule = Schedule::cron("0 30 2 * * *").unwrap();
+
+        assert_eq!(
+            schedule.next_after(now),
+            Some(Utc.with_ymd_and_hms(2026, 1, 2, 2, 30, 0).unwrap())
+        );
+    }
+
+    #[test]
+    fn test_invalid_cron_expression() {
+        let err = Schedule::cron("every tuesday").unwrap_err();
+        assert!(matches!(err, JobError::InvalidSchedule { .. }));
+    }
+
+    #[test]
+    fn test_schedule_display() {
+        assert_eq!(
+            Schedule::every(Duration::from_secs(3600)).to_string(),
+            "every 3600s"
+        );
+        assert_eq!(
+            Schedule::cron("0 0 * * * *").unwrap().to_string(),
+            "cron 0 0 * * * *"
+        );
+    }
+}
@@ -0,0 +1,28 @@
+//! Background job scheduler for Zeltra.
+//!
+//! This crate provides:
+//! - The [`Job`] trait for periodic tasks
+//! - A [`Scheduler`] that runs registered jobs with jittered start, per-run
+//!   timeouts and panic isolation
+//! - A [`JobBoard`] exposing each job's last run and next run
+//! - Maintenance jobs for expired sessions and verification tokens
+//! - OCR extraction of receipt and invoice attachments
+//! - Reminders for transactions left waiting on approval
+//! - Month-end FX revaluation drafts
+//! - Automatic soft close and close of ended fiscal periods
+
+pub mod approval;
+pub mod fx_revaluation;
+pub mod job;
+pub mod maintenance;
+pub mod ocr;
+pub mod period_close;
+pub mod scheduler;
+
+pub use approval::ApprovalEscalationJob;
+pub use fx_revaluation::FxRevaluationJob;
+pub use job::{Job, JobContext, JobError, Schedule};
+pub use maintenance::{ExpiredSessionCleanupJob, ExpiredVerificationTokenCleanupJob};
+pub use ocr::OcrExtractionJob;
+pub use period_close::PeriodAutoCloseJob;
+pub use scheduler::{JobBoard, JobStatus, RunOutcome, Scheduler};
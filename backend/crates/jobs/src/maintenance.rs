@@ -0,0 +1,55 @@
+//! Housekeeping jobs for expired authentication data.
+
+use std::time::Duration;
+
+use async_trait::async_trait;
+use tracing::info;
+use zeltra_db::{EmailVerificationRepository, SessionRepository};
+
+use crate::job::{Job, JobContext, JobError, Schedule};
+
+/// Deletes sessions past their expiry.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ExpiredSessionCleanupJob;
+
+#[async_trait]
+impl Job for ExpiredSessionCleanupJob {
+    fn name(&self) -> &'static str {
+        "expired_session_cleanup"
+    }
+
+    fn schedule(&self) -> Schedule {
+        Schedule::every(Duration::from_secs(3600))
+    }
+
+    async fn run(&self, ctx: &JobContext) -> Result<(), JobError> {
+        let deleted = SessionRepository::new(ctx.db.clone())
+            .cleanup_expired()
+            .await?;
+        info!(deleted, "Expired sessions removed");
+        Ok(())
+    }
+}
+
+/// Deletes email verification tokens past their expiry.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ExpiredVerificationTokenCleanupJob;
+
+#[async_trait]
+impl Job for ExpiredVerificationTokenCleanupJob {
+    fn name(&self) -> &'static str {
+        "expired_verification_token_cleanup"
+    }
+
+    fn schedule(&self) -> Schedule {
+        Schedule::every(Duration::from_secs(6 * 3600))
+    }
+
+    async fn run(&self, ctx: &JobContext) -> Result<(), JobError> {
+        let deleted = EmailVerificationRepository::new(ctx.db.clone())
+            .cleanup_expired()
+            .await?;
+        info!(deleted, "Expired verification tokens removed");
+        Ok(())
+    }
+}
@@ -0,0 +1,108 @@
+//! OCR extraction of uploaded receipts and invoices.
+
+use std::{sync::Arc, time::Duration};
+
+use async_trait::async_trait;
+use tracing::{info, warn};
+use zeltra_core::attachment::{ExtractionRecord, OcrProvider};
+use zeltra_core::storage::{StorageError, StorageService};
+use zeltra_db::repositories::AttachmentRepository;
+
+use crate::job::{Job, JobContext, JobError, Schedule};
+
+/// Sends unprocessed receipt and invoice attachments to an OCR provider and
+/// stores the extracted fields.
+///
+/// Permanent failures are recorded on the attachment so it is not retried;
+/// transient ones (provider or storage unavailable) are left for the next run.
+pub struct OcrExtractionJob<P> {
+    provider: Arc<P>,
+    storage: Arc<StorageService>,
+    interval: Duration,
+    batch_size: u64,
+}
+
+impl<P: OcrProvider> OcrExtractionJob<P> {
+    /// Creates the job, processing up to `batch_size` attachments every
+    /// `interval`.
+    #[must_use]
+    pub fn new(
+        provider: Arc<P>,
+        storage: Arc<StorageService>,
+        interval: Duration,
+        batch_size: u64,
+    ) -> Self {
+        Self {
+            provider,
+            storage,
+            interval,
+            batch_size,
+        }
+    }
+}
+
+#[async_trait]
+impl<P: OcrProvider + 'static> Job for OcrExtractionJob<P> {
+    fn name(&self) -> &'static str {
+        "attachment_ocr_extraction"
+    }
+
+    fn schedule(&self) -> Schedule {
+        Schedule::every(self.interval)
+    }
+
+    async fn run(&self, ctx: &JobContext) -> Result<(), JobError> {
+        let repo = AttachmentRepository::new(ctx.db.clone());
+        let pending = repo
+            .list_pending_extraction(self.batch_size)
+            .await
+            .map_err(|e| JobError::Failed(e.to_string()))?;
+
+        let (mut completed, mut failed, mut deferred) = (0_usize, 0_usize, 0_usize);
+        for attachment in pending {
+            let bytes = match self.storage.read(&attachment.storage_key).await {
+                Ok(bytes) => Ok(bytes),
+                Err(StorageError::NotFound { .. }) => Err("file missing from storage".to_string()),
+                Err(e) => {
+                    warn!(attachment_id = %attachment.id, error = %e, "Failed to read attachment");
+                    deferred += 1;
+                    continue;
+                }
+            };
+
+            let record = match bytes {
+                Ok(bytes) => match self.provider.extract(&bytes, &attachment.mime_type).await {
+                    Ok(document) => ExtractionRecord::Completed(document),
+                    Err(e) if e.is_transient() => {
+                        warn!(
+                            attachment_id = %attachment.id,
+                            provider = self.provider.name(),
+                            error = %e,
+                            "OCR extraction deferred"
+                        );
+                        deferred += 1;
+                        continue;
+                    }
+                    Err(e) => ExtractionRecord::Failed {
+                        error: e.to_string(),
+                    },
+                },
+                Err(error) => ExtractionRecord::Failed { error },
+            };
+
+            match &record {
+                ExtractionRecord::Completed(_) => completed += 1,
+                ExtractionRecord::Failed { .. } => failed += 1,
+            }
+            repo.save_extraction(attachment.id, &record)
+                .await
+                .map_err(|e| JobError::Failed(e.to_string()))?;
+        }
+
+        info!(
+            provider = self.provider.name(),
+            completed, failed, deferred, "Attachment OCR run finished"
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,75 @@
+//! Automatic soft close and close of ended fiscal periods.
+
+use std::time::Duration;
+
+use async_trait::async_trait;
+use chrono::Utc;
+use tracing::{info, warn};
+use zeltra_db::repositories::{AutoCloseOutcome, PeriodCloseRepository};
+
+use crate::job::{Job, JobContext, JobError, Schedule};
+
+/// Soft-closes and closes fiscal periods on the schedule set by each
+/// organization's `period_auto_close` settings.
+///
+/// A period takes at most one step per run. A close whose checklist has
+/// failing checks is left for the next run; owners, admins and accountants
+/// are told once per scheduled close date.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PeriodAutoCloseJob;
+
+#[async_trait]
+impl Job for PeriodAutoCloseJob {
+    fn name(&self) -> &'static str {
+        "period_auto_close"
+    }
+
+    fn schedule(&self) -> Schedule {
+        Schedule::every(Duration::from_secs(6 * 3600))
+    }
+
+    async fn run(&self, ctx: &JobContext) -> Result<(), JobError> {
+        let repo = PeriodCloseRepository::new(ctx.db.clone());
+        let due = repo
+            .due_periods(Utc::now().date_naive(), None)
+            .await
+            .map_err(|e| JobError::Failed(e.to_string()))?;
+
+        let (mut soft_closed, mut closed, mut blocked, mut failed) =
+            (0_usize, 0_usize, 0_usize, 0_usize);
+        for period in &due {
+            match repo.run(period).await {
+                Ok(run) => match run.outcome {
+                    AutoCloseOutcome::SoftClosed {
+                        unposted_transactions,
+                    } => {
+                        info!(organization_id = %run.organization_id, fiscal_period_id = %run.fiscal_period_id, period = %run.period_name, unposted_transactions, "Fiscal period soft-closed");
+                        soft_closed += 1;
+                    }
+                    AutoCloseOutcome::Closed => {
+                        info!(organization_id = %run.organization_id, fiscal_period_id = %run.fiscal_period_id, period = %run.period_name, "Fiscal period closed");
+                        closed += 1;
+                    }
+                    AutoCloseOutcome::Blocked { failed_checks } => {
+                        warn!(organization_id = %run.organization_id, fiscal_period_id = %run.fiscal_period_id, ?failed_checks, "Fiscal period close blocked by closing checks");
+                        blocked += 1;
+                    }
+                    AutoCloseOutcome::EarlierPeriodsOpen => {
+                        warn!(organization_id = %run.organization_id, fiscal_period_id = %run.fiscal_period_id, "Fiscal period not closed, earlier periods are open");
+                        blocked += 1;
+                    }
+                },
+                Err(e) => {
+                    warn!(organization_id = %period.period.organization_id, fiscal_period_id = %period.period.id, error = %e, "Failed to auto-close fiscal period");
+                    failed += 1;
+                }
+            }
+        }
+
+        info!(
+            periods = due.len(),
+            soft_closed, closed, blocked, failed, "Period auto-close run finished"
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,557 @@
+//! Job registry and run loop.
+//!
+//! Each registered job gets its own task. A run is spawned separately so a
+//! panic or a timeout ends only that run; the job is scheduled again as usual.
+//! Exclusive jobs run under a database advisory lock so that only one replica
+//! executes a given run.
+
+use std::{
+    any::Any,
+    sync::{Arc, PoisonError, RwLock},
+    time::{Duration, Instant},
+};
+
+use chrono::{DateTime, Utc};
+use rand::Rng;
+use serde::Serialize;
+use tokio::task::JoinError;
+use tracing::{debug, error, info, warn};
+use zeltra_db::{lock_key, try_with_lock};
+
+use crate::job::{Job, JobContext, JobError, Schedule};
+
+/// Default upper bound of the random delay before a job's first run.
+pub const DEFAULT_MAX_JITTER: Duration = Duration::from_secs(30);
+
+/// How a run ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RunOutcome {
+    /// The job returned `Ok`.
+    Succeeded,
+    /// The job returned an error.
+    Failed,
+    /// The run exceeded the job's timeout and was cancelled.
+    TimedOut,
+    /// The job panicked.
+    Panicked,
+    /// Another replica held the job's lock, so this replica did not run it.
+    Skipped,
+}
+
+impl RunOutcome {
+    /// Returns the outcome as used in logs.
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Succeeded => "succeeded",
+            Self::Failed => "failed",
+            Self::TimedOut => "timed_out",
+            Self::Panicked => "panicked",
+            Self::Skipped => "skipped",
+        }
+    }
+}
+
+/// Current state of a registered job.
+#[derive(Debug, Clone, Serialize)]
+pub struct JobStatus {
+    /// Job name.
+    pub name: &'static str,
+    /// Human-readable schedule.
+    pub schedule: String,
+    /// Per-run timeout in seconds.
+    pub timeout_secs: u64,
+    /// Whether a run is in progress.
+    pub running: bool,
+    /// Start of the most recent run.
+    pub last_started_at: Option<DateTime<Utc>>,
+    /// End of the most recent completed run.
+    pub last_finished_at: Option<DateTime<Utc>>,
+    /// Duration of the most recent completed run in milliseconds.
+    pub last_duration_ms: Option<u64>,
+    /// Outcome of the most recent completed run.
+    pub last_outcome: Option<RunOutcome>,
+    /// Error of the most recent unsuccessful run, kept until the next success.
+    pub last_error: Option<String>,
+    /// When the job runs next.
+    pub next_run_at: Option<DateTime<Utc>>,
+    /// Number of completed runs.
+    pub runs: u64,
+    /// Number of runs that did not succeed.
+    pub failures: u64,
+    /// Number of runs skipped because another replica held the lock.
+    pub skipped: u64,
+}
+
+impl JobStatus {
+    fn new(job: &dyn Job) -> Self {
+        Self {
+            name: job.name(),
+            schedule: job.schedule().to_string(),
+            timeout_secs: job.timeout().as_secs(),
+            running: false,
+            last_started_at: None,
+            last_finished_at: None,
+            last_duration_ms: None,
+            last_outcome: None,
+            last_error: None,
+            next_run_at: None,
+            runs: 0,
+            failures: 0,
+            skipped: 0,
+        }
+    }
+}
+
+/// Shared view of every registered job's status.
+#[derive(Debug, Clone, Default)]
+pub struct JobBoard {
+    statuses: Arc<RwLock<Vec<JobStatus>>>,
+}
+
+impl JobBoard {
+    /// Returns the status of every job, in registration order.
+    #[must_use]
+    pub fn snapshot(&self) -> Vec<JobStatus> {
+        self.statuses
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+
+    /// Returns the status of the named job.
+    #[must_use]
+    pub fn get(&self, name: &str) -> Option<JobStatus> {
+        self.statuses
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .iter()
+            .find(|status| status.name == name)
+            .cloned()
+    }
+
+    fn insert(&self, status: JobStatus) {
+        self.statuses
+            .write()
+            .unwrap_or_else(PoisonError::into_inner)
+            .push(status);
+    }
+
+    fn update(&self, name: &str, f: impl FnOnce(&mut JobStatus)) {
+        let mut statuses = self
+            .statuses
+            .write()
+            .unwrap_or_else(PoisonError::into_inner);
+        if let Some(status) = statuses.iter_mut().find(|status| status.name == name) {
+            f(status);
+        }
+    }
+}
+
+/// Registry of background jobs.
+///
+/// ```ignore
+/// let board = Scheduler::new(JobContext::new(db))
+///     .register(ExpiredSessionCleanupJob)
+///     .start();
+/// ```
+pub struct Scheduler {
+    ctx: JobContext,
+    jobs: Vec<Arc<dyn Job>>,
+    max_jitter: Duration,
+}
+
+impl Scheduler {
+    /// Creates an empty scheduler whose jobs share `ctx`.
+    #[must_use]
+    pub fn new(ctx: JobContext) -> Self {
+        Self {
+            ctx,
+            jobs: Vec::new(),
+            max_jitter: DEFAULT_MAX_JITTER,
+        }
+    }
+
+    /// Sets the upper bound of the random delay before each job's first run.
+    #[must_use]
+    pub const fn with_max_jitter(mut self, max_jitter: Duration) -> Self {
+        self.max_jitter = max_jitter;
+        self
+    }
+
+    /// Adds a job.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a job with the same name is already registered.
+    #[must_use]
+    pub fn register(mut self, job: impl Job) -> Self {
+        assert!(
+            self.jobs
+                .iter()
+                .all(|existing| existing.name() != job.name()),
+            "job '{}' is registered twice",
+            job.name()
+        );
+        self.jobs.push(Arc::new(job));
+        self
+    }
+
+    /// Spawns every registered job and returns their status board.
+    ///
+    /// Must be called from within a Tokio runtime.
+    pub fn start(self) -> JobBoard {
+        let board = JobBoard::default();
+
+        for job in self.jobs {
+            board.insert(JobStatus::new(job.as_ref()));
+            let first_run = first_run_at(&job, self.max_jitter, Utc::now());
+            info!(job = job.name(), schedule = %job.schedule(), "Job registered");
+            tokio::spawn(run_loop(job, self.ctx.clone(), board.clone(), first_run));
+        }
+
+        board
+    }
+}
+
+impl std::fmt::Debug for Scheduler {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Scheduler")
+            .field(
+                "jobs",
+                &self.jobs.iter().map(|job| job.name()).collect::<Vec<_>>(),
+            )
+            .field("max_jitter", &self.max_jitter)
+            .finish_non_exhaustive()
+    }
+}
+
+/// First run time: interval jobs start after the jitter alone, cron jobs at
+/// their next fire time plus the jitter.
+fn first_run_at(
+    job: &Arc<dyn Job>,
+    max_jitter: Duration,
+    now: DateTime<Utc>,
+) -> Option<DateTime<Utc>> {
+    let schedule = job.schedule();
+    let (base, bound) = match schedule {
+        Schedule::Every(period) => (Some(now), max_jitter.min(period)),
+        Schedule::Cron(_) => (schedule.next_after(now), max_jitter),
+    };
+    let jitter = random_jitter(bound);
+    base.and_then(|base| base.checked_add_signed(chrono::Duration::from_std(jitter).ok()?))
+}
+
+fn random_jitter(bound: Duration) -> Duration {
+    let bound_ms = u64::try_from(bound.as_millis()).unwrap_or(u64::MAX);
+    if bound_ms == 0 {
+        return Duration::ZERO;
+    }
+    Duration::from_millis(rand::rng().random_range(0..bound_ms))
+}
+
+async fn run_loop(
+    job: Arc<dyn Job>,
+    ctx: JobContext,
+    board: JobBoard,
+    mut next_run: Option<DateTime<Utc>>,
+) {
+    let schedule = job.schedule();
+
+    while let Some(run_at) = next_run {
+        board.update(job.name(), |status| status.next_run_at = Some(run_at));
+        let wait = (run_at - Utc::now()).to_std().unwrap_or_default();
+        tokio::time::sleep(wait).await;
+
+        run_once(&job, &ctx, &board).await;
+
+        next_run = schedule.next_after(Utc::now());
+    }
+
+    warn!(job = job.name(), "Job has no further runs scheduled");
+    board.update(job.name(), |status| status.next_run_at = None);
+}
+
+/// Runs a job once, recording the outcome on the board.
+pub(crate) async fn run_once(job: &Arc<dyn Job>, ctx: &JobContext, board: &JobBoard) -> RunOutcome {
+    let name = job.name();
+    let timeout = job.timeout();
+    let started_at = Utc::now();
+    let start = Instant::now();
+    board.update(name, |status| {
+        status.running = true;
+        status.last_started_at = Some(started_at);
+    });
+
+    let task = {
+        let job = Arc::clone(job);
+        let ctx = ctx.clone();
+        tokio::spawn(async move { run_exclusive(job.as_ref(), &ctx).await })
+    };
+    let abort = task.abort_handle();
+
+    let (outcome, error) = match tokio::time::timeout(timeout, task).await {
+        Ok(Ok(Ok(true))) => (RunOutcome::Succeeded, None),
+        Ok(Ok(Ok(false))) => {
+            debug!(job = name, "Job run skipped, lock held by another replica");
+            board.update(name, |status| {
+                status.running = false;
+                status.skipped += 1;
+            });
+            return RunOutcome::Skipped;
+        }
+        Ok(Ok(Err(e))) => (RunOutcome::Failed, Some(e.to_string())),
+        Ok(Err(e)) => (RunOutcome::Panicked, Some(join_error_message(e))),
+        Err(_) => {
+            abort.abort();
+            (
+                RunOutcome::TimedOut,
+                Some(format!("Run exceeded timeout of {}s", timeout.as_secs())),
+            )
+        }
+    };
+
+    let duration_ms = u64::try_from(start.elapsed().as_millis()).unwrap_or(u64::MAX);
+    match &error {
+        None => debug!(
+            job = name,
+            duration_ms,
+            outcome = outcome.as_str(),
+            "Job run finished"
+        ),
+        Some(error) => error!(
+            job = name,
+            duration_ms,
+            outcome = outcome.as_str(),
+            error = %error,
+            "Job run failed"
+        ),
+    }
+
+    board.update(name, |status| {
+        status.running = false;
+        status.last_finished_at = Some(Utc::now());
+        status.last_duration_ms = Some(duration_ms);
+        status.last_outcome = Some(outcome);
+        status.runs += 1;
+        if error.is_some() {
+            status.failures += 1;
+        }
+        status.last_error = error;
+    });
+
+    outcome
+}
+
+/// Runs the job, under its advisory lock if it is exclusive.
+///
+/// Returns `false` if the lock was held elsewhere and the job did not run.
+async fn run_exclusive(job: &dyn Job, ctx: &JobContext) -> Result<bool, JobError> {
+    if !job.exclusive() {
+        job.run(ctx).await?;
+        return Ok(true);
+    }
+
+    let key = lock_key(&format!("job:{}", job.name()));
+    match try_with_lock(&ctx.db, key, || job.run(ctx)).await? {
+        Some(result) => result.map(|()| true),
+        None => Ok(false),
+    }
+}
+
+fn join_error_message(error: JoinError) -> String {
+    if !error.is_panic() {
+        return error.to_string();
+    }
+    let payload: Box<dyn Any + Send> = error.into_panic();
+    let message = payload
+        .downcast_ref::<&str>()
+        .map(ToString::to_string)
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "non-string panic payload".to_string());
+    format!("Job panicked: {message}")
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    use async_trait::async_trait;
+    use sea_orm::DatabaseConnection;
+
+    use super::*;
+    use crate::JobError;
+
+    /// Test job whose behaviour is chosen per run.
+    struct TestJob {
+        name: &'static str,
+        period: Duration,
+        timeout: Duration,
+        behaviour: fn(u32) -> Result<(), JobError>,
+        sleep: Duration,
+        exclusive: bool,
+        runs: Arc<AtomicU32>,
+    }
+
+    impl TestJob {
+        fn new(name: &'static str, behaviour: fn(u32) -> Result<(), JobError>) -> Self {
+            Self {
+                name,
+                period: Duration::from_millis(10),
+                timeout: Duration::from_secs(5),
+                behaviour,
+                sleep: Duration::ZERO,
+                exclusive: false,
+                runs: Arc::new(AtomicU32::new(0)),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl Job for TestJob {
+        fn name(&self) -> &'static str {
+            self.name
+        }
+
+        fn schedule(&self) -> Schedule {
+            Schedule::every(self.period)
+        }
+
+        fn timeout(&self) -> Duration {
+            self.timeout
+        }
+
+        fn exclusive(&self) -> bool {
+            self.exclusive
+        }
+
+        async fn run(&self, _ctx: &JobContext) -> Result<(), JobError> {
+            let run = self.runs.fetch_add(1, Ordering::SeqCst);
+            tokio::time::sleep(self.sleep).await;
+            (self.behaviour)(run)
+        }
+    }
+
+    fn ctx() -> JobContext {
+        JobContext::new(DatabaseConnection::Disconnected)
+    }
+
+    async fn run_registered(job: TestJob) -> (RunOutcome, JobStatus) {
+        let name = job.name;
+        let board = JobBoard::default();
+        board.insert(JobStatus::new(&job));
+        let job: Arc<dyn Job> = Arc::new(job);
+
+        let outcome = run_once(&job, &ctx(), &board).await;
+        (outcome, board.get(name).unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_successful_run_is_recorded() {
+        let (outcome, status) = run_registered(TestJob::new("ok", |_| Ok(()))).await;
+
+        assert_eq!(outcome, RunOutcome::Succeeded);
+        assert_eq!(status.last_outcome, Some(RunOutcome::Succeeded));
+        assert_eq!(status.runs, 1);
+        assert_eq!(status.failures, 0);
+        assert!(status.last_error.is_none());
+        assert!(!status.running);
+    }
+
+    #[tokio::test]
+    async fn test_failed_run_records_error() {
+        let (outcome, status) = run_registered(TestJob::new("failing", |_| {
+            Err(JobError::Failed("upstream unavailable".to_string()))
+        }))
+        .await;
+
+        assert_eq!(outcome, RunOutcome::Failed);
+        assert_eq!(status.failures, 1);
+        assert_eq!(status.last_error.as_deref(), Some("upstream unavailable"));
+    }
+
+    #[tokio::test]
+    async fn test_panicking_run_is_isolated() {
+        let (outcome, status) = run_registered(TestJob::new("panicking", |_| panic!("boom"))).await;
+
+        assert_eq!(outcome, RunOutcome::Panicked);
+        assert_eq!(status.last_error.as_deref(), Some("Job panicked: boom"));
+    }
+
+    #[tokio::test]
+    async fn test_slow_run_times_out() {
+        let job = TestJob {
+            timeout: Duration::from_millis(20),
+            sleep: Duration::from_secs(5),
+            ..TestJob::new("slow", |_| Ok(()))
+        };
+
+        let (outcome, status) = run_registered(job).await;
+
+        assert_eq!(outcome, RunOutcome::TimedOut);
+        assert_eq!(status.last_outcome, Some(RunOutcome::TimedOut));
+        assert!(!status.running);
+    }
+
+    #[tokio::test]
+    async fn test_scheduler_keeps_running_after_panic() {
+        let job = TestJob::new("flaky", |run| {
+            assert!(run != 0, "first run panics");
+            Ok(())
+        });
+        let runs = Arc::clone(&job.runs);
+
+        let board = Scheduler::new(ctx())
+            .with_max_jitter(Duration::ZERO)
+            .register(job)
+            .start();
+
+        tokio::time::timeout(Duration::from_secs(5), async {
+            while runs.load(Ordering::SeqCst) < 3 {
+                tokio::time::sleep(Duration::from_millis(5)).await;
+            }
+        })
+        .await
+        .unwrap();
+
+        let status = board.get("flaky").unwrap();
+        assert!(status.runs >= 2);
+        assert!(status.failures >= 1);
+        assert!(status.next_run_at.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_exclusive_job_fails_without_database() {
+        let job = TestJob {
+            exclusive: true,
+            ..TestJob::new("locked", |_| Ok(()))
+        };
+        let runs = Arc::clone(&job.runs);
+
+        let (outcome, status) = run_registered(job).await;
+
+        assert_eq!(outcome, RunOutcome::Failed);
+        assert!(status.last_error.unwrap().starts_with("Database error"));
+        assert_eq!(runs.load(Ordering::SeqCst), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "registered twice")]
+    fn test_duplicate_job_names_are_rejected() {
+        let _ = Scheduler::new(ctx())
+            .register(TestJob::new("dup", |_| Ok(())))
+            .register(TestJob::new("dup", |_| Ok(())));
+    }
+
+    #[test]
+    fn test_interval_jitter_bounded_by_period() {
+        let job: Arc<dyn Job> = Arc::new(TestJob::new("jittered", |_| Ok(())));
+        let now = Utc::now();
+
+        let first = first_run_at(&job, Duration::from_secs(30), now).unwrap();
+
+        assert!(first >= now);
+        assert!(first <= now + chrono::Duration::milliseconds(10));
+    }
+}
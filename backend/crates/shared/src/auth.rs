@@ -1,10 +1,15 @@
 //! Authentication types for JWT and tokens.
 
 use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
 /// JWT claims for access tokens.
+///
+/// Role and approval limit are snapshots taken when the token was issued.
+/// Membership changes take effect at the next refresh; the user's token
+/// version is bumped on such changes so older tokens are rejected.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Claims {
     /// Subject (user ID).
@@ -13,6 +18,15 @@ pub struct Claims {
     pub org: Uuid,
     /// User's role in the organization.
     pub role: String,
+    /// User's approval limit in the organization, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub approval_limit: Option<Decimal>,
+    /// User's token version at issue time.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ver: Option<i32>,
+    /// Scopes of the API key the request was made with; `None` for users.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scopes: Option<Vec<String>>,
     /// Issued at timestamp.
     pub iat: i64,
     /// Expiration timestamp.
@@ -28,11 +42,42 @@ impl Claims {
             sub: user_id,
             org: org_id,
             role: role.to_string(),
+            approval_limit: None,
+            ver: None,
+            scopes: None,
             iat: now.timestamp(),
             exp: expires_at.timestamp(),
         }
     }
 
+    /// Creates claims carrying a user's organization membership.
+    #[must_use]
+    pub fn for_member(member: &TokenMember, expires_at: DateTime<Utc>) -> Self {
+        Self {
+            approval_limit: member.approval_limit,
+            ver: Some(member.token_version),
+            ..Self::new(member.user_id, member.org_id, &member.role, expires_at)
+        }
+    }
+
+    /// Creates claims for a request authenticated with an API key.
+    ///
+    /// The subject is the user who created the key and `role` is the role
+    /// the key acts with; the claims live for the request only.
+    #[must_use]
+    pub fn for_api_key(
+        created_by: Uuid,
+        org_id: Uuid,
+        role: &str,
+        scopes: Vec<String>,
+        expires_at: DateTime<Utc>,
+    ) -> Self {
+        Self {
+            scopes: Some(scopes),
+            ..Self::new(created_by, org_id, role, expires_at)
+        }
+    }
+
     /// Returns the user ID from claims.
     #[must_use]
     pub const fn user_id(&self) -> Uuid {
@@ -44,6 +89,33 @@ impl Claims {
     pub const fn organization_id(&self) -> Uuid {
         self.org
     }
+
+    /// Returns the token version the claims were issued with.
+    #[must_use]
+    pub const fn token_version(&self) -> Option<i32> {
+        self.ver
+    }
+
+    /// Returns true if the claims came from an API key.
+    #[must_use]
+    pub const fn is_api_key(&self) -> bool {
+        self.scopes.is_some()
+    }
+}
+
+/// Organization membership details embedded in issued tokens.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TokenMember {
+    /// User ID.
+    pub user_id: Uuid,
+    /// Organization the token is issued for.
+    pub org_id: Uuid,
+    /// User's role in the organization.
+    pub role: String,
+    /// User's approval limit in the organization, if any.
+    pub approval_limit: Option<Decimal>,
+    /// User's current token version.
+    pub token_version: i32,
 }
 
 /// Token pair returned after successful authentication.
@@ -128,6 +200,38 @@ pub struct UserOrganization {
     pub role: String,
 }
 
+/// Profile of the authenticated user.
+#[derive(Debug, Clone, Serialize)]
+pub struct UserProfile {
+    /// User ID.
+    pub id: Uuid,
+    /// User email.
+    pub email: String,
+    /// User full name.
+    pub full_name: String,
+    /// Whether the email address has been verified.
+    pub email_verified: bool,
+    /// Preferred locale (BCP 47), if set.
+    pub locale: Option<String>,
+    /// Organization loaded after login, if set.
+    pub default_organization_id: Option<Uuid>,
+    /// Organizations the user belongs to.
+    pub organizations: Vec<UserOrganization>,
+}
+
+/// Update profile request.
+#[derive(Debug, Clone, Deserialize)]
+pub struct UpdateProfileRequest {
+    /// New full name (optional).
+    pub full_name: Option<String>,
+    /// New locale (optional, BCP 47).
+    pub locale: Option<String>,
+    /// New default organization (optional).
+    pub default_organization_id: Option<Uuid>,
+    /// New email (optional); takes effect once confirmed from the new address.
+    pub email: Option<String>,
+}
+
 /// Refresh token request.
 #[derive(Debug, Clone, Deserialize)]
 pub struct RefreshRequest {
@@ -154,12 +258,71 @@ pub struct CreateOrganizationRequest {
     /// Timezone (IANA format).
     #[serde(default = "default_timezone")]
     pub timezone: String,
+    /// What to set up besides the owner. Nothing by default.
+    #[serde(default)]
+    pub bootstrap: BootstrapRequest,
+}
+
+/// What to set up in a new organization.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct BootstrapRequest {
+    /// Create the current calendar fiscal year with monthly periods.
+    pub fiscal_year: bool,
+    /// Chart-of-accounts template to apply, e.g. `service`.
+    pub chart_of_accounts: Option<String>,
+    /// Create the Department and Project dimension types.
+    pub dimension_types: bool,
 }
 
 fn default_timezone() -> String {
     "UTC".to_string()
 }
 
+/// Clone organization request.
+#[derive(Debug, Clone, Deserialize)]
+pub struct CloneOrganizationRequest {
+    /// Name of the new organization.
+    pub name: String,
+    /// Slug of the new organization.
+    pub slug: String,
+    /// What to copy. Everything by default.
+    #[serde(default)]
+    pub copy: CloneSectionsRequest,
+}
+
+/// Parts of an organization's configuration to copy into a clone.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+#[allow(clippy::struct_excessive_bools)]
+pub struct CloneSectionsRequest {
+    /// Chart of accounts.
+    pub accounts: bool,
+    /// Dimension types and values.
+    pub dimensions: bool,
+    /// Fiscal years and periods.
+    pub fiscal_calendar: bool,
+    /// Approval rules.
+    pub approval_rules: bool,
+    /// Budgets and their lines.
+    pub budgets: bool,
+    /// Organization settings.
+    pub settings: bool,
+}
+
+impl Default for CloneSectionsRequest {
+    fn default() -> Self {
+        Self {
+            accounts: true,
+            dimensions: true,
+            fiscal_calendar: true,
+            approval_rules: true,
+            budgets: true,
+            settings: true,
+        }
+    }
+}
+
 /// Add user to organization request.
 #[derive(Debug, Clone, Deserialize)]
 pub struct AddUserRequest {
@@ -176,6 +339,8 @@ pub struct AddUserRequest {
 pub struct UpdateOrganizationRequest {
     /// Organization name (optional).
     pub name: Option<String>,
+    /// New slug (optional); the old one keeps resolving to this organization.
+    pub slug: Option<String>,
     /// Base currency (optional, ISO 4217 code).
     pub base_currency: Option<String>,
     /// Timezone (optional, IANA format).
@@ -19,6 +19,7 @@ mod tests {
             secret: "phase1-secret".to_string(),
             access_token_expires_minutes: 15,
             refresh_token_expires_days: 3,
+            ..JwtConfig::default()
         })
     }
 
@@ -129,6 +130,7 @@ mod tests {
             secret: "different-secret".into(),
             access_token_expires_minutes: 15,
             refresh_token_expires_days: 3,
+            ..JwtConfig::default()
         });
 
         let token = service
@@ -166,6 +168,32 @@ mod tests {
         assert_eq!(req.timezone, "Asia/Jakarta");
     }
 
+    #[test]
+    fn create_org_request_bootstraps_nothing_unless_asked() {
+        let json = json!({
+            "name": "Org",
+            "slug": "org-slug",
+            "base_currency": "USD"
+        });
+        let req: CreateOrganizationRequest =
+            serde_json::from_value(json).expect("deserialize request");
+        assert!(!req.bootstrap.fiscal_year);
+        assert_eq!(req.bootstrap.chart_of_accounts, None);
+        assert!(!req.bootstrap.dimension_types);
+
+        let json = json!({
+            "name": "Org",
+            "slug": "org-slug",
+            "base_currency": "USD",
+            "bootstrap": { "fiscal_year": true, "chart_of_accounts": "service" }
+        });
+        let req: CreateOrganizationRequest =
+            serde_json::from_value(json).expect("deserialize request");
+        assert!(req.bootstrap.fiscal_year);
+        assert_eq!(req.bootstrap.chart_of_accounts.as_deref(), Some("service"));
+        assert!(!req.bootstrap.dimension_types);
+    }
+
     #[test]
     fn add_user_request_preserves_optional_approval_limit() {
         let req = AddUserRequest {
@@ -180,6 +208,7 @@ mod tests {
     fn update_org_request_supports_partial_updates() {
         let req = UpdateOrganizationRequest {
             name: None,
+            slug: None,
             base_currency: Some("IDR".to_string()),
             timezone: None,
         };
@@ -1,6 +1,12 @@
 //! Application configuration management.
 
+use std::fmt;
+
 use serde::Deserialize;
+use thiserror::Error;
+use uuid::Uuid;
+
+use crate::jwt::{JwtAlgorithm, JwtVerificationKey};
 
 /// Application configuration.
 #[derive(Debug, Clone, Deserialize)]
@@ -14,6 +20,21 @@ pub struct AppConfig {
     /// Email configuration.
     #[serde(default)]
     pub email: EmailConfig,
+    /// Metrics configuration.
+    #[serde(default)]
+    pub metrics: MetricsConfig,
+    /// Operator access configuration.
+    #[serde(default)]
+    pub admin: AdminConfig,
+    /// Dashboard configuration.
+    #[serde(default)]
+    pub dashboard: DashboardConfig,
+    /// Attachment OCR configuration.
+    #[serde(default)]
+    pub ocr: OcrConfig,
+    /// File storage settings, read from the `STORAGE_*` variables.
+    #[serde(skip)]
+    pub storage: StorageSettings,
 }
 
 /// Server configuration.
@@ -25,6 +46,15 @@ pub struct ServerConfig {
     /// Port to listen on.
     #[serde(default = "default_port")]
     pub port: u16,
+    /// Maximum request body size in bytes.
+    #[serde(default = "default_body_limit_bytes")]
+    pub body_limit_bytes: usize,
+    /// Maximum request body size in bytes for import endpoints.
+    #[serde(default = "default_import_body_limit_bytes")]
+    pub import_body_limit_bytes: usize,
+    /// Maximum request body size in bytes for organization backup import.
+    #[serde(default = "default_backup_body_limit_bytes")]
+    pub backup_body_limit_bytes: usize,
 }
 
 fn default_host() -> String {
@@ -35,6 +65,18 @@ fn default_port() -> u16 {
     8080
 }
 
+fn default_body_limit_bytes() -> usize {
+    2 * 1024 * 1024
+}
+
+fn default_import_body_limit_bytes() -> usize {
+    10 * 1024 * 1024
+}
+
+fn default_backup_body_limit_bytes() -> usize {
+    1024 * 1024 * 1024
+}
+
 /// Database configuration.
 #[derive(Debug, Clone, Deserialize)]
 pub struct DatabaseConfig {
@@ -67,6 +109,21 @@ pub struct JwtConfig {
     /// Refresh token expiration in seconds.
     #[serde(default = "default_refresh_token_expiry")]
     pub refresh_token_expiry_secs: u64,
+    /// Signing algorithm (`HS256`, `RS256` or `EdDSA`).
+    #[serde(default)]
+    pub algorithm: JwtAlgorithm,
+    /// Key identifier of the primary signing key.
+    #[serde(default = "default_jwt_key_id")]
+    pub key_id: String,
+    /// PEM-encoded private key for asymmetric algorithms.
+    #[serde(default)]
+    pub private_key_pem: Option<String>,
+    /// PEM-encoded public key for asymmetric algorithms.
+    #[serde(default)]
+    pub public_key_pem: Option<String>,
+    /// Additional keys accepted when verifying tokens.
+    #[serde(default)]
+    pub verification_keys: Vec<JwtVerificationKey>,
 }
 
 fn default_access_token_expiry() -> u64 {
@@ -77,6 +134,10 @@ fn default_refresh_token_expiry() -> u64 {
     604_800 // 7 days
 }
 
+fn default_jwt_key_id() -> String {
+    "default".to_string()
+}
+
 /// Email configuration.
 #[derive(Debug, Clone, Deserialize)]
 pub struct EmailConfig {
@@ -137,6 +198,410 @@ impl Default for EmailConfig {
     }
 }
 
+/// Prometheus metrics configuration.
+#[derive(Debug, Clone, Deserialize)]
+pub struct MetricsConfig {
+    /// Whether metrics are recorded and exposed.
+    #[serde(default = "default_metrics_enabled")]
+    pub enabled: bool,
+    /// Address of a dedicated listener for `/metrics`.
+    ///
+    /// When set, `/metrics` is served only on this address (for example a
+    /// loopback or cluster-internal interface) instead of the public listener.
+    #[serde(default)]
+    pub bind_address: Option<String>,
+    /// Bearer token required to scrape `/metrics`.
+    #[serde(default)]
+    pub token: Option<String>,
+}
+
+fn default_metrics_enabled() -> bool {
+    true
+}
+
+impl Default for MetricsConfig {
+    fn default() -> Self {
+        Self {
+            enabled: default_metrics_enabled(),
+            bind_address: None,
+            token: None,
+        }
+    }
+}
+
+/// Operator access to `/admin` endpoints.
+///
+/// With neither field set, admin endpoints reject every request.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct AdminConfig {
+    /// Organization whose owners may use admin endpoints.
+    #[serde(default)]
+    pub organization_id: Option<Uuid>,
+    /// Static bearer token accepted on admin endpoints.
+    #[serde(default)]
+    pub token: Option<String>,
+}
+
+/// Dashboard configuration.
+#[derive(Debug, Clone, Deserialize)]
+pub struct DashboardConfig {
+    /// Seconds a cached dashboard section is served before it is recomputed.
+    #[serde(default = "default_dashboard_cache_ttl_secs")]
+    pub cache_ttl_secs: u64,
+}
+
+fn default_dashboard_cache_ttl_secs() -> u64 {
+    60
+}
+
+impl Default for DashboardConfig {
+    fn default() -> Self {
+        Self {
+            cache_ttl_secs: default_dashboard_cache_ttl_secs(),
+        }
+    }
+}
+
+/// Attachment OCR configuration.
+///
+/// Requires file storage. Without an endpoint (or without the `ocr-http`
+/// build feature) a stub provider records empty extractions.
+#[derive(Debug, Clone, Deserialize)]
+pub struct OcrConfig {
+    /// Whether the extraction job runs.
+    #[serde(default)]
+    pub enabled: bool,
+    /// URL of the external OCR service.
+    #[serde(default)]
+    pub endpoint: Option<String>,
+    /// Bearer token sent to the OCR service.
+    #[serde(default)]
+    pub api_key: Option<String>,
+    /// Seconds between extraction runs.
+    #[serde(default = "default_ocr_poll_interval_secs")]
+    pub poll_interval_secs: u64,
+    /// Attachments processed per run.
+    #[serde(default = "default_ocr_batch_size")]
+    pub batch_size: u64,
+    /// Seconds to wait for the OCR service per document.
+    #[serde(default = "default_ocr_timeout_secs")]
+    pub timeout_secs: u64,
+}
+
+fn default_ocr_poll_interval_secs() -> u64 {
+    60
+}
+
+fn default_ocr_batch_size() -> u64 {
+    20
+}
+
+fn default_ocr_timeout_secs() -> u64 {
+    30
+}
+
+impl Default for OcrConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            endpoint: None,
+            api_key: None,
+            poll_interval_secs: default_ocr_poll_interval_secs(),
+            batch_size: default_ocr_batch_size(),
+            timeout_secs: default_ocr_timeout_secs(),
+        }
+    }
+}
+
+/// File storage settings as given in the environment.
+///
+/// Unset and blank variables are `None`. [`StorageSettings::backend`] checks
+/// that the chosen provider has everything it needs.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct StorageSettings {
+    /// `STORAGE_TYPE`: `local`, `s3` or `azure`. Unset disables storage.
+    pub storage_type: Option<String>,
+    /// `STORAGE_LOCAL_PATH`.
+    pub local_path: Option<String>,
+    /// `STORAGE_S3_ENDPOINT`.
+    pub s3_endpoint: Option<String>,
+    /// `STORAGE_S3_BUCKET`.
+    pub s3_bucket: Option<String>,
+    /// `STORAGE_S3_ACCESS_KEY`.
+    pub s3_access_key: Option<String>,
+    /// `STORAGE_S3_SECRET_KEY`.
+    pub s3_secret_key: Option<String>,
+    /// `STORAGE_S3_REGION`.
+    pub s3_region: Option<String>,
+    /// `STORAGE_AZURE_ACCOUNT`.
+    pub azure_account: Option<String>,
+    /// `STORAGE_AZURE_ACCESS_KEY`.
+    pub azure_access_key: Option<String>,
+    /// `STORAGE_AZURE_CONTAINER`.
+    pub azure_container: Option<String>,
+}
+
+/// A complete file storage configuration.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StorageBackend {
+    /// Local filesystem.
+    Local {
+        /// Root directory.
+        path: String,
+    },
+    /// S3-compatible object storage (AWS, R2, Supabase).
+    S3 {
+        /// Endpoint URL.
+        endpoint: String,
+        /// Bucket name.
+        bucket: String,
+        /// Access key ID.
+        access_key: String,
+        /// Secret access key.
+        secret_key: String,
+        /// Region.
+        region: String,
+    },
+    /// Azure Blob storage.
+    Azure {
+        /// Storage account.
+        account: String,
+        /// Account access key.
+        access_key: String,
+        /// Container name.
+        container: String,
+    },
+}
+
+fn env_setting(name: &str) -> Option<String> {
+    std::env::var(name)
+        .ok()
+        .filter(|value| !value.trim().is_empty())
+}
+
+impl StorageSettings {
+    /// Reads the `STORAGE_*` environment variables.
+    #[must_use]
+    pub fn from_env() -> Self {
+        Self {
+            storage_type: env_setting("STORAGE_TYPE"),
+            local_path: env_setting("STORAGE_LOCAL_PATH"),
+            s3_endpoint: env_setting("STORAGE_S3_ENDPOINT"),
+            s3_bucket: env_setting("STORAGE_S3_BUCKET"),
+            s3_access_key: env_setting("STORAGE_S3_ACCESS_KEY"),
+            s3_secret_key: env_setting("STORAGE_S3_SECRET_KEY"),
+            s3_region: env_setting("STORAGE_S3_REGION"),
+            azure_account: env_setting("STORAGE_AZURE_ACCOUNT"),
+            azure_access_key: env_setting("STORAGE_AZURE_ACCESS_KEY"),
+            azure_container: env_setting("STORAGE_AZURE_CONTAINER"),
+        }
+    }
+
+    /// Resolves the configured backend; `None` when storage is disabled.
+    ///
+    /// # Errors
+    ///
+    /// Returns every problem found: an unknown `STORAGE_TYPE`, or each
+    /// variable the chosen provider requires but is missing.
+    pub fn backend(&self) -> Result<Option<StorageBackend>, Vec<ConfigProblem>> {
+        let mut problems = Vec::new();
+        let mut required = |value: Option<&str>, env_var: &'static str| {
+            if let Some(value) = value {
+                value.to_string()
+            } else {
+                problems.push(ConfigProblem::new(
+                    env_var,
+                    "required by the configured STORAGE_TYPE but not set",
+                ));
+                String::new()
+            }
+        };
+
+        let backend = match self.storage_type.as_deref() {
+            None => return Ok(None),
+            Some("local") => StorageBackend::Local {
+                path: self
+                    .local_path
+                    .clone()
+                    .unwrap_or_else(|| "./uploads".to_string()),
+            },
+            Some("s3") => StorageBackend::S3 {
+                endpoint: required(self.s3_endpoint.as_deref(), "STORAGE_S3_ENDPOINT"),
+                bucket: required(self.s3_bucket.as_deref(), "STORAGE_S3_BUCKET"),
+                access_key: required(self.s3_access_key.as_deref(), "STORAGE_S3_ACCESS_KEY"),
+                secret_key: required(self.s3_secret_key.as_deref(), "STORAGE_S3_SECRET_KEY"),
+                region: self.s3_region.clone().unwrap_or_else(|| "auto".to_string()),
+            },
+            Some("azure") => StorageBackend::Azure {
+                account: required(self.azure_account.as_deref(), "STORAGE_AZURE_ACCOUNT"),
+                access_key: required(self.azure_access_key.as_deref(), "STORAGE_AZURE_ACCESS_KEY"),
+                container: required(self.azure_container.as_deref(), "STORAGE_AZURE_CONTAINER"),
+            },
+            Some(other) => {
+                return Err(vec![ConfigProblem::new(
+                    "STORAGE_TYPE",
+                    format!("unknown storage type `{other}`; expected local, s3 or azure"),
+                )]);
+            }
+        };
+
+        if problems.is_empty() {
+            Ok(Some(backend))
+        } else {
+            Err(problems)
+        }
+    }
+}
+
+/// One problem found by [`AppConfig::validate`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigProblem {
+    /// Environment variable that controls the setting.
+    pub env_var: &'static str,
+    /// What is wrong with it.
+    pub message: String,
+}
+
+impl ConfigProblem {
+    fn new(env_var: &'static str, message: impl Into<String>) -> Self {
+        Self {
+            env_var,
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for ConfigProblem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.env_var, self.message)
+    }
+}
+
+/// Every problem found in a configuration, in section order.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub struct ConfigErrors(pub Vec<ConfigProblem>);
+
+impl fmt::Display for ConfigErrors {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} configuration problem(s)", self.0.len())?;
+        for problem in &self.0 {
+            write!(f, "\n  {problem}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Shortest accepted HS256 secret, in bytes.
+pub const MIN_JWT_SECRET_LEN: usize = 32;
+
+/// Fewest distinct characters accepted in an HS256 secret.
+const MIN_JWT_SECRET_DISTINCT_CHARS: usize = 10;
+
+impl JwtConfig {
+    fn validate(&self, problems: &mut Vec<ConfigProblem>) {
+        if self.algorithm.is_asymmetric() {
+            if self.private_key_pem.is_none() {
+                problems.push(ConfigProblem::new(
+                    "ZELTRA__JWT__PRIVATE_KEY_PEM",
+                    "required for asymmetric signing algorithms",
+                ));
+            }
+            if self.public_key_pem.is_none() {
+                problems.push(ConfigProblem::new(
+                    "ZELTRA__JWT__PUBLIC_KEY_PEM",
+                    "required for asymmetric signing algorithms",
+                ));
+            }
+        } else if self.secret.len() < MIN_JWT_SECRET_LEN {
+            problems.push(ConfigProblem::new(
+                "ZELTRA__JWT__SECRET",
+                format!("must be at least {MIN_JWT_SECRET_LEN} bytes long"),
+            ));
+        } else {
+            let distinct = self
+                .secret
+                .chars()
+                .collect::<std::collections::HashSet<_>>()
+                .len();
+            if distinct < MIN_JWT_SECRET_DISTINCT_CHARS {
+                problems.push(ConfigProblem::new(
+                    "ZELTRA__JWT__SECRET",
+                    format!(
+                        "too predictable; use at least {MIN_JWT_SECRET_DISTINCT_CHARS} distinct characters"
+                    ),
+                ));
+            }
+        }
+
+        // Token lifetimes are applied in whole minutes and whole days
+        if self.access_token_expiry_secs < 60 {
+            problems.push(ConfigProblem::new(
+                "ZELTRA__JWT__ACCESS_TOKEN_EXPIRY_SECS",
+                "must be at least 60",
+            ));
+        }
+        if self.refresh_token_expiry_secs < 86_400 {
+            problems.push(ConfigProblem::new(
+                "ZELTRA__JWT__REFRESH_TOKEN_EXPIRY_SECS",
+                "must be at least 86400",
+            ));
+        }
+        if self.refresh_token_expiry_secs <= self.access_token_expiry_secs {
+            problems.push(ConfigProblem::new(
+                "ZELTRA__JWT__REFRESH_TOKEN_EXPIRY_SECS",
+                "must be longer than ZELTRA__JWT__ACCESS_TOKEN_EXPIRY_SECS",
+            ));
+        }
+    }
+}
+
+impl DatabaseConfig {
+    fn validate(&self, problems: &mut Vec<ConfigProblem>) {
+        if !(self.url.starts_with("postgres://") || self.url.starts_with("postgresql://")) {
+            problems.push(ConfigProblem::new(
+                "ZELTRA__DATABASE__URL",
+                "must be a postgres:// or postgresql:// URL",
+            ));
+        }
+        if self.max_connections == 0 {
+            problems.push(ConfigProblem::new(
+                "ZELTRA__DATABASE__MAX_CONNECTIONS",
+                "must be at least 1",
+            ));
+        }
+        if self.min_connections > self.max_connections {
+            problems.push(ConfigProblem::new(
+                "ZELTRA__DATABASE__MIN_CONNECTIONS",
+                "must not exceed ZELTRA__DATABASE__MAX_CONNECTIONS",
+            ));
+        }
+    }
+}
+
+impl EmailConfig {
+    fn validate(&self, problems: &mut Vec<ConfigProblem>) {
+        let host = self.smtp_host.trim();
+        if host.is_empty() || host.contains(char::is_whitespace) || host.contains("://") {
+            problems.push(ConfigProblem::new(
+                "ZELTRA__EMAIL__SMTP_HOST",
+                "must be a host name or address, without a scheme",
+            ));
+        }
+        if self.smtp_port == 0 {
+            problems.push(ConfigProblem::new(
+                "ZELTRA__EMAIL__SMTP_PORT",
+                "must be between 1 and 65535",
+            ));
+        }
+        if !self.from_email.contains('@') {
+            problems.push(ConfigProblem::new(
+                "ZELTRA__EMAIL__FROM_EMAIL",
+                "must be an email address",
+            ));
+        }
+    }
+}
+
 impl AppConfig {
     /// Loads configuration from environment and config files.
     ///
@@ -152,7 +617,31 @@ impl AppConfig {
             .add_source(config::Environment::with_prefix("ZELTRA").separator("__"))
             .build()?;
 
-        config.try_deserialize()
+        let mut config: Self = config.try_deserialize()?;
+        config.storage = StorageSettings::from_env();
+        Ok(config)
+    }
+
+    /// Checks every section and reports all problems at once.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ConfigErrors`] listing each problem with the environment
+    /// variable that controls it.
+    pub fn validate(&self) -> Result<(), ConfigErrors> {
+        let mut problems = Vec::new();
+        self.database.validate(&mut problems);
+        self.jwt.validate(&mut problems);
+        self.email.validate(&mut problems);
+        if let Err(storage) = self.storage.backend() {
+            problems.extend(storage);
+        }
+
+        if problems.is_empty() {
+            Ok(())
+        } else {
+            Err(ConfigErrors(problems))
+        }
     }
 }
 
@@ -160,12 +649,14 @@ impl AppConfig {
 mod tests {
     use super::*;
 
-    #[test]
-    fn test_server_config_defaults() {
-        let config = AppConfig {
+    fn config_with_defaults() -> AppConfig {
+        AppConfig {
             server: ServerConfig {
                 host: default_host(),
                 port: default_port(),
+                body_limit_bytes: default_body_limit_bytes(),
+                import_body_limit_bytes: default_import_body_limit_bytes(),
+                backup_body_limit_bytes: default_backup_body_limit_bytes(),
             },
             database: DatabaseConfig {
                 url: "postgres://".into(),
@@ -176,12 +667,46 @@ mod tests {
                 secret: "secret".into(),
                 access_token_expiry_secs: default_access_token_expiry(),
                 refresh_token_expiry_secs: default_refresh_token_expiry(),
+                algorithm: JwtAlgorithm::default(),
+                key_id: default_jwt_key_id(),
+                private_key_pem: None,
+                public_key_pem: None,
+                verification_keys: Vec::new(),
             },
             email: EmailConfig::default(),
-        };
+            metrics: MetricsConfig::default(),
+            admin: AdminConfig::default(),
+            dashboard: DashboardConfig::default(),
+            ocr: OcrConfig::default(),
+            storage: StorageSettings::default(),
+        }
+    }
+
+    /// Defaults with a secret that passes validation.
+    fn valid_config() -> AppConfig {
+        let mut config = config_with_defaults();
+        config.database.url = "postgres://zeltra@localhost/zeltra".into();
+        config.jwt.secret = "kR7v-Qm2xZ9pLw4sTn8bYc1dHf6gJe3a".into();
+        config
+    }
+
+    fn problem_vars(config: &AppConfig) -> Vec<&'static str> {
+        config
+            .validate()
+            .err()
+            .map(|errors| errors.0.iter().map(|p| p.env_var).collect())
+            .unwrap_or_default()
+    }
+
+    #[test]
+    fn test_server_config_defaults() {
+        let config = config_with_defaults();
 
         assert_eq!(config.server.host, "0.0.0.0");
         assert_eq!(config.server.port, 8080);
+        assert_eq!(config.server.body_limit_bytes, 2 * 1024 * 1024);
+        assert_eq!(config.server.import_body_limit_bytes, 10 * 1024 * 1024);
+        assert_eq!(config.server.backup_body_limit_bytes, 1024 * 1024 * 1024);
     }
 
     #[test]
@@ -194,6 +719,8 @@ mod tests {
     fn test_jwt_config_defaults() {
         assert_eq!(default_access_token_expiry(), 900);
         assert_eq!(default_refresh_token_expiry(), 604_800);
+        assert_eq!(default_jwt_key_id(), "default");
+        assert_eq!(JwtAlgorithm::default(), JwtAlgorithm::Hs256);
     }
 
     #[test]
@@ -208,6 +735,162 @@ mod tests {
         assert_eq!(config.frontend_url, "http://localhost:3000");
     }
 
+    #[test]
+    fn test_metrics_config_defaults() {
+        let config = MetricsConfig::default();
+        assert!(config.enabled);
+        assert!(config.bind_address.is_none());
+        assert!(config.token.is_none());
+    }
+
+    #[test]
+    fn test_dashboard_config_defaults() {
+        assert_eq!(DashboardConfig::default().cache_ttl_secs, 60);
+    }
+
+    #[test]
+    fn test_valid_config_passes() {
+        assert_eq!(valid_config().validate(), Ok(()));
+    }
+
+    #[test]
+    fn test_all_problems_reported_together() {
+        let mut config = valid_config();
+        config.database.url = "mysql://localhost/zeltra".into();
+        config.jwt.secret = "short".into();
+        config.email.smtp_port = 0;
+        config.storage.storage_type = Some("s3".into());
+        config.storage.s3_endpoint = Some("https://r2.example.com".into());
+
+        let errors = config.validate().unwrap_err();
+        let vars: Vec<_> = errors.0.iter().map(|p| p.env_var).collect();
+        assert_eq!(
+            vars,
+            [
+                "ZELTRA__DATABASE__URL",
+                "ZELTRA__JWT__SECRET",
+                "ZELTRA__EMAIL__SMTP_PORT",
+                "STORAGE_S3_BUCKET",
+                "STORAGE_S3_ACCESS_KEY",
+                "STORAGE_S3_SECRET_KEY",
+            ]
+        );
+        assert!(errors.to_string().starts_with("6 configuration problem(s)"));
+        assert!(
+            errors
+                .to_string()
+                .contains("ZELTRA__JWT__SECRET: must be at least 32 bytes long")
+        );
+    }
+
+    #[test]
+    fn test_low_entropy_secret_rejected() {
+        let mut config = valid_config();
+        config.jwt.secret = "ab".repeat(20);
+        assert_eq!(problem_vars(&config), ["ZELTRA__JWT__SECRET"]);
+    }
+
+    #[test]
+    fn test_asymmetric_keys_required_instead_of_secret() {
+        let mut config = valid_config();
+        config.jwt.algorithm = JwtAlgorithm::Rs256;
+        config.jwt.secret = String::new();
+        config.jwt.public_key_pem = Some("-----BEGIN PUBLIC KEY-----".into());
+        assert_eq!(problem_vars(&config), ["ZELTRA__JWT__PRIVATE_KEY_PEM"]);
+    }
+
+    #[test]
+    fn test_token_expiry_relationship() {
+        let mut config = valid_config();
+        config.jwt.access_token_expiry_secs = 30 * 86_400;
+        config.jwt.refresh_token_expiry_secs = 7 * 86_400;
+        assert_eq!(
+            problem_vars(&config),
+            ["ZELTRA__JWT__REFRESH_TOKEN_EXPIRY_SECS"]
+        );
+
+        // Lifetimes shorter than the units they are applied in
+        config.jwt.access_token_expiry_secs = 59;
+        config.jwt.refresh_token_expiry_secs = 3600;
+        assert_eq!(
+            problem_vars(&config),
+            [
+                "ZELTRA__JWT__ACCESS_TOKEN_EXPIRY_SECS",
+                "ZELTRA__JWT__REFRESH_TOKEN_EXPIRY_SECS",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_database_pool_and_smtp_host() {
+        let mut config = valid_config();
+        config.database.min_connections = 20;
+        config.email.smtp_host = "smtp://mail.example.com".into();
+        assert_eq!(
+            problem_vars(&config),
+            [
+                "ZELTRA__DATABASE__MIN_CONNECTIONS",
+                "ZELTRA__EMAIL__SMTP_HOST"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_storage_backend_resolution() {
+        assert_eq!(StorageSettings::default().backend(), Ok(None));
+
+        let local = StorageSettings {
+            storage_type: Some("local".into()),
+            ..Default::default()
+        };
+        assert_eq!(
+            local.backend(),
+            Ok(Some(StorageBackend::Local {
+                path: "./uploads".into()
+            }))
+        );
+
+        let azure = StorageSettings {
+            storage_type: Some("azure".into()),
+            azure_account: Some("zeltradev".into()),
+            ..Default::default()
+        };
+        let vars: Vec<_> = azure
+            .backend()
+            .unwrap_err()
+            .iter()
+            .map(|p| p.env_var)
+            .collect();
+        assert_eq!(
+            vars,
+            ["STORAGE_AZURE_ACCESS_KEY", "STORAGE_AZURE_CONTAINER"]
+        );
+    }
+
+    #[test]
+    fn test_unknown_storage_type_is_an_error() {
+        let mut config = valid_config();
+        config.storage.storage_type = Some("azblob".into());
+        assert_eq!(problem_vars(&config), ["STORAGE_TYPE"]);
+    }
+
+    #[test]
+    fn test_storage_settings_from_env_ignore_blank_values() {
+        temp_env::with_vars(
+            [
+                ("STORAGE_TYPE", Some("s3")),
+                ("STORAGE_S3_BUCKET", Some("  ")),
+                ("STORAGE_S3_REGION", None::<&str>),
+            ],
+            || {
+                let settings = StorageSettings::from_env();
+                assert_eq!(settings.storage_type.as_deref(), Some("s3"));
+                assert!(settings.s3_bucket.is_none());
+                assert!(settings.s3_region.is_none());
+            },
+        );
+    }
+
     #[test]
     fn test_app_config_load() {
         // Set environment variables
@@ -85,6 +85,214 @@ The Zeltra Team"
         self.send_email(to_email, subject, &body).await
     }
 
+    /// Sends the link confirming a change of address to the new address.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the email cannot be sent.
+    pub async fn send_email_change_email(
+        &self,
+        to_email: &str,
+        to_name: &str,
+        token: &str,
+    ) -> Result<(), EmailError> {
+        let verification_url = format!("{}/verify-email?token={}", self.config.frontend_url, token);
+
+        let subject = "Confirm your new email address - Zeltra";
+        let body = format!(
+            r"Hi {to_name},
+
+Please confirm that you want to use this address for your Zeltra account by clicking the link below:
+
+{verification_url}
+
+This link will expire in 24 hours. Until then, your account keeps using your current address.
+
+If you didn't request this change, you can safely ignore this email.
+
+Best regards,
+The Zeltra Team"
+        );
+
+        self.send_email(to_email, subject, &body).await
+    }
+
+    /// Tells the previous address that the account's email has changed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the email cannot be sent.
+    pub async fn send_email_changed_notice(
+        &self,
+        to_email: &str,
+        to_name: &str,
+        new_email: &str,
+    ) -> Result<(), EmailError> {
+        let subject = "Your email address was changed - Zeltra";
+        let body = format!(
+            r"Hi {to_name},
+
+The email address for your Zeltra account was changed to {new_email}. You will no longer receive account emails at this address.
+
+If you didn't make this change, please contact your organization's administrator immediately.
+
+Best regards,
+The Zeltra Team"
+        );
+
+        self.send_email(to_email, subject, &body).await
+    }
+
+    /// Tells an approver that a transaction was submitted for their approval.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the email cannot be sent.
+    pub async fn send_approval_requested_email(
+        &self,
+        to_email: &str,
+        to_name: &str,
+        organization_name: &str,
+        transaction_label: &str,
+    ) -> Result<(), EmailError> {
+        let approvals_url = format!("{}/dashboard/approvals", self.config.frontend_url);
+
+        let subject = format!("Approval requested: {transaction_label} - Zeltra");
+        let body = format!(
+            r"Hi {to_name},
+
+{transaction_label} in {organization_name} has been submitted and is waiting for your approval.
+
+You can review it in the approval queue:
+
+{approvals_url}
+
+Best regards,
+The Zeltra Team"
+        );
+
+        self.send_email(to_email, &subject, &body).await
+    }
+
+    /// Tells a submitter that their transaction was rejected.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the email cannot be sent.
+    pub async fn send_transaction_rejected_email(
+        &self,
+        to_email: &str,
+        to_name: &str,
+        organization_name: &str,
+        transaction_label: &str,
+        reason: &str,
+    ) -> Result<(), EmailError> {
+        let subject = format!("Transaction rejected: {transaction_label} - Zeltra");
+        let body = format!(
+            r"Hi {to_name},
+
+{transaction_label}, which you submitted in {organization_name}, was rejected.
+
+Reason: {reason}
+
+It is back in draft, so you can correct it and submit it again.
+
+Best regards,
+The Zeltra Team"
+        );
+
+        self.send_email(to_email, &subject, &body).await
+    }
+
+    /// Tells a member they were mentioned in a transaction comment.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the email cannot be sent.
+    pub async fn send_comment_mention_email(
+        &self,
+        to_email: &str,
+        to_name: &str,
+        organization_name: &str,
+        transaction_label: &str,
+        author_name: &str,
+        comment: &str,
+    ) -> Result<(), EmailError> {
+        let subject = format!("{author_name} mentioned you on {transaction_label} - Zeltra");
+        let body = format!(
+            r"Hi {to_name},
+
+{author_name} mentioned you in a comment on {transaction_label} in {organization_name}:
+
+{comment}
+
+Best regards,
+The Zeltra Team"
+        );
+
+        self.send_email(to_email, &subject, &body).await
+    }
+
+    /// Reminds an approver that a transaction has been waiting for approval.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the email cannot be sent.
+    pub async fn send_approval_reminder_email(
+        &self,
+        to_email: &str,
+        to_name: &str,
+        organization_name: &str,
+        transaction_label: &str,
+        days_pending: u32,
+    ) -> Result<(), EmailError> {
+        let approvals_url = format!("{}/dashboard/approvals", self.config.frontend_url);
+
+        let subject = format!("Approval waiting: {transaction_label} - Zeltra");
+        let body = format!(
+            r"Hi {to_name},
+
+{transaction_label} in {organization_name} has been waiting for approval for {days_pending} days.
+
+You can review it in the approval queue:
+
+{approvals_url}
+
+Best regards,
+The Zeltra Team"
+        );
+
+        self.send_email(to_email, &subject, &body).await
+    }
+
+    /// Tells a submitter that approvers were reminded about their transaction.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the email cannot be sent.
+    pub async fn send_approval_pending_notice(
+        &self,
+        to_email: &str,
+        to_name: &str,
+        organization_name: &str,
+        transaction_label: &str,
+        days_pending: u32,
+    ) -> Result<(), EmailError> {
+        let subject = format!("Still awaiting approval: {transaction_label} - Zeltra");
+        let body = format!(
+            r"Hi {to_name},
+
+{transaction_label}, which you submitted in {organization_name}, has been waiting for approval for {days_pending} days. We have sent a reminder to the people who can approve it.
+
+No action is needed from you.
+
+Best regards,
+The Zeltra Team"
+        );
+
+        self.send_email(to_email, &subject, &body).await
+    }
+
     /// Sends a generic email.
     ///
     /// # Errors
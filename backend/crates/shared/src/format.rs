@@ -0,0 +1,184 @@
+//! Locale-aware display formatting for amounts and dates.
+//!
+//! Amounts in API responses are plain decimal strings. These helpers render
+//! them the way a reader in a given locale expects, for report exports and
+//! any client that wants server-side formatting.
+
+use std::fmt;
+use std::str::FromStr;
+
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+/// Locales with known number and date conventions.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Locale {
+    /// English (United States): `1,234.56`, `01/31/2025`.
+    #[default]
+    #[serde(rename = "en-US")]
+    EnUs,
+    /// German (Germany): `1.234,56`, `31.01.2025`.
+    #[serde(rename = "de-DE")]
+    DeDe,
+    /// Indonesian (Indonesia): `1.234,56`, `31/01/2025`.
+    #[serde(rename = "id-ID")]
+    IdId,
+}
+
+/// A locale tag that is not one of the supported [`Locale`]s.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[error("Unsupported locale: {0}")]
+pub struct UnknownLocale(pub String);
+
+impl Locale {
+    /// All supported locales.
+    pub const ALL: [Self; 3] = [Self::EnUs, Self::DeDe, Self::IdId];
+
+    /// BCP 47 tag for the locale.
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::EnUs => "en-US",
+            Self::DeDe => "de-DE",
+            Self::IdId => "id-ID",
+        }
+    }
+
+    /// Separator between the integer and fractional parts.
+    #[must_use]
+    pub const fn decimal_separator(self) -> char {
+        match self {
+            Self::EnUs => '.',
+            Self::DeDe | Self::IdId => ',',
+        }
+    }
+
+    /// Separator between groups of three integer digits.
+    #[must_use]
+    pub const fn group_separator(self) -> char {
+        match self {
+            Self::EnUs => ',',
+            Self::DeDe | Self::IdId => '.',
+        }
+    }
+}
+
+impl fmt::Display for Locale {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for Locale {
+    type Err = UnknownLocale;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|locale| locale.as_str().eq_ignore_ascii_case(s))
+            .ok_or_else(|| UnknownLocale(s.to_string()))
+    }
+}
+
+/// Formats an amount with the locale's separators.
+///
+/// The amount is rounded to `decimal_places` using banker's rounding, so a
+/// zero-decimal currency such as IDR or JPY has no fractional part. Negative
+/// amounts get a leading minus sign.
+#[must_use]
+pub fn format_amount(amount: Decimal, decimal_places: u32, locale: Locale) -> String {
+    let rounded = amount.round_dp(decimal_places);
+    let digits = format!("{:.*}", decimal_places as usize, rounded.abs());
+    let (integer, fraction) = digits.split_once('.').unwrap_or((digits.as_str(), ""));
+
+    let mut out = String::with_capacity(digits.len() + integer.len() / 3 + 1);
+    if rounded.is_sign_negative() && !rounded.is_zero() {
+        out.push('-');
+    }
+    for (i, digit) in integer.chars().enumerate() {
+        if i > 0 && (integer.len() - i) % 3 == 0 {
+            out.push(locale.group_separator());
+        }
+        out.push(digit);
+    }
+    if !fraction.is_empty() {
+        out.push(locale.decimal_separator());
+        out.push_str(fraction);
+    }
+    out
+}
+
+/// Formats an amount with its currency symbol placed for the locale.
+///
+/// `en-US` puts the symbol first (`-$1,234.56`), `de-DE` after the number
+/// (`-1.234,56 €`) and `id-ID` first with a space (`-Rp 1.234`).
+#[must_use]
+pub fn format_currency(
+    amount: Decimal,
+    symbol: &str,
+    decimal_places: u32,
+    locale: Locale,
+) -> String {
+    let number = format_amount(amount, decimal_places, locale);
+    let (sign, number) = match number.strip_prefix('-') {
+        Some(rest) => ("-", rest),
+        None => ("", number.as_str()),
+    };
+    match locale {
+        Locale::EnUs => format!("{sign}{symbol}{number}"),
+        Locale::DeDe => format!("{sign}{number} {symbol}"),
+        Locale::IdId => format!("{sign}{symbol} {number}"),
+    }
+}
+
+/// Formats a date in the locale's short numeric form.
+#[must_use]
+pub fn format_date(date: NaiveDate, locale: Locale) -> String {
+    let pattern = match locale {
+        Locale::EnUs => "%m/%d/%Y",
+        Locale::DeDe => "%d.%m.%Y",
+        Locale::IdId => "%d/%m/%Y",
+    };
+    date.format(pattern).to_string()
+}
+
+/// How an export writes its amounts and dates.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ExportStyle {
+    /// Plain decimal strings and ISO 8601 dates, as in JSON responses.
+    #[default]
+    Plain,
+    /// Amounts rounded to `decimal_places` with the locale's separators, and
+    /// dates in the locale's short form.
+    Localized {
+        /// Locale to format for.
+        locale: Locale,
+        /// Decimal places amounts are rounded to.
+        decimal_places: u32,
+    },
+}
+
+impl ExportStyle {
+    /// Writes an amount in this style.
+    #[must_use]
+    pub fn amount(self, amount: Decimal) -> String {
+        match self {
+            Self::Plain => amount.to_string(),
+            Self::Localized {
+                locale,
+                decimal_places,
+            } => format_amount(amount, decimal_places, locale),
+        }
+    }
+
+    /// Writes a date in this style.
+    #[must_use]
+    pub fn date(self, date: NaiveDate) -> String {
+        match self {
+            Self::Plain => date.to_string(),
+            Self::Localized { locale, .. } => format_date(date, locale),
+        }
+    }
+}
@@ -0,0 +1,98 @@
+use chrono::NaiveDate;
+use rstest::rstest;
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+
+use crate::format::{
+    ExportStyle, Locale, UnknownLocale, format_amount, format_currency, format_date,
+};
+
+#[rstest]
+#[case(Locale::EnUs, dec!(1234567.891), 2, "1,234,567.89")]
+#[case(Locale::DeDe, dec!(1234567.891), 2, "1.234.567,89")]
+#[case(Locale::IdId, dec!(1234567.891), 2, "1.234.567,89")]
+#[case(Locale::EnUs, dec!(-1234.5), 2, "-1,234.50")]
+#[case(Locale::DeDe, dec!(-1234.5), 2, "-1.234,50")]
+#[case(Locale::IdId, dec!(-1234.5), 2, "-1.234,50")]
+#[case(Locale::EnUs, dec!(999), 2, "999.00")]
+#[case(Locale::EnUs, dec!(0), 2, "0.00")]
+#[case(Locale::DeDe, dec!(100000), 2, "100.000,00")]
+fn test_format_amount(
+    #[case] locale: Locale,
+    #[case] amount: Decimal,
+    #[case] decimal_places: u32,
+    #[case] expected: &str,
+) {
+    assert_eq!(format_amount(amount, decimal_places, locale), expected);
+}
+
+#[rstest]
+#[case(Locale::IdId, dec!(15000000), "15.000.000")]
+#[case(Locale::IdId, dec!(-2500.4), "-2.500")]
+#[case(Locale::EnUs, dec!(1234.5), "1,234")]
+#[case(Locale::EnUs, dec!(1235.5), "1,236")]
+#[case(Locale::DeDe, dec!(-0.4), "0")]
+fn test_format_amount_zero_decimal_currency(
+    #[case] locale: Locale,
+    #[case] amount: Decimal,
+    #[case] expected: &str,
+) {
+    assert_eq!(format_amount(amount, 0, locale), expected);
+}
+
+#[rstest]
+#[case(Locale::EnUs, dec!(-1234.56), "$", 2, "-$1,234.56")]
+#[case(Locale::DeDe, dec!(-1234.56), "€", 2, "-1.234,56 €")]
+#[case(Locale::IdId, dec!(1500000), "Rp", 0, "Rp 1.500.000")]
+#[case(Locale::EnUs, dec!(98765), "¥", 0, "¥98,765")]
+fn test_format_currency(
+    #[case] locale: Locale,
+    #[case] amount: Decimal,
+    #[case] symbol: &str,
+    #[case] decimal_places: u32,
+    #[case] expected: &str,
+) {
+    assert_eq!(
+        format_currency(amount, symbol, decimal_places, locale),
+        expected
+    );
+}
+
+#[rstest]
+#[case(Locale::EnUs, "01/31/2025")]
+#[case(Locale::DeDe, "31.01.2025")]
+#[case(Locale::IdId, "31/01/2025")]
+fn test_format_date(#[case] locale: Locale, #[case] expected: &str) {
+    let date = NaiveDate::from_ymd_opt(2025, 1, 31).unwrap();
+    assert_eq!(format_date(date, locale), expected);
+}
+
+#[test]
+fn test_export_style() {
+    let date = NaiveDate::from_ymd_opt(2025, 1, 31).unwrap();
+    assert_eq!(ExportStyle::Plain.amount(dec!(-1234.5000)), "-1234.5000");
+    assert_eq!(ExportStyle::Plain.date(date), "2025-01-31");
+
+    let localized = ExportStyle::Localized {
+        locale: Locale::IdId,
+        decimal_places: 0,
+    };
+    assert_eq!(localized.amount(dec!(-1234.5000)), "-1.234");
+    assert_eq!(localized.date(date), "31/01/2025");
+}
+
+#[test]
+fn test_locale_tags_round_trip() {
+    for locale in Locale::ALL {
+        assert_eq!(locale.as_str().parse::<Locale>(), Ok(locale));
+        let json = serde_json::to_value(locale).unwrap();
+        assert_eq!(json, serde_json::json!(locale.as_str()));
+        assert_eq!(serde_json::from_value::<Locale>(json).unwrap(), locale);
+    }
+    assert_eq!("de-de".parse::<Locale>(), Ok(Locale::DeDe));
+    assert_eq!(
+        "fr-FR".parse::<Locale>(),
+        Err(UnknownLocale("fr-FR".to_string()))
+    );
+    assert_eq!(Locale::default(), Locale::EnUs);
+}
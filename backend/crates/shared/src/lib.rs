@@ -8,13 +8,17 @@
 //! - Configuration management
 //! - JWT claims and auth types
 //! - Email service for transactional emails
+//! - Locale-aware amount and date formatting
+//! - Amounts written out in words
 
 pub mod auth;
 pub mod config;
 pub mod email;
 pub mod error;
+pub mod format;
 pub mod jwt;
 pub mod types;
+pub mod words;
 
 #[cfg(test)]
 mod auth_phase1_tests;
@@ -23,10 +27,18 @@ mod email_tests;
 #[cfg(test)]
 mod error_tests;
 #[cfg(test)]
+mod format_tests;
+#[cfg(test)]
 mod jwt_tests;
+#[cfg(test)]
+mod words_tests;
 
-pub use auth::{Claims, TokenPair};
-pub use config::{AppConfig, EmailConfig};
+pub use auth::{Claims, TokenMember, TokenPair};
+pub use config::{
+    AdminConfig, AppConfig, ConfigErrors, ConfigProblem, DashboardConfig, EmailConfig,
+    MetricsConfig, OcrConfig, StorageBackend, StorageSettings,
+};
 pub use email::{EmailError, EmailService};
 pub use error::{AppError, AppResult};
-pub use jwt::{JwtConfig, JwtError, JwtService};
+pub use format::Locale;
+pub use jwt::{JwtAlgorithm, JwtConfig, JwtError, JwtService, JwtSigningKey, JwtVerificationKey};
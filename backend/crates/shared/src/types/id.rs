@@ -1,6 +1,10 @@
 //! Typed IDs for type-safe entity references.
 //!
 //! Using typed IDs prevents accidentally passing a `UserId` where an `OrgId` is expected.
+//!
+//! Entities keep plain `Uuid` columns; the wrappers convert to and from `Uuid`
+//! via `From`, and (with the `sea-orm` feature) into `sea_orm::Value` so they
+//! can be used directly in query filters.
 
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
@@ -52,6 +56,31 @@ macro_rules! typed_id {
                 Ok(Self(Uuid::parse_str(s)?))
             }
         }
+
+        impl From<Uuid> for $name {
+            fn from(uuid: Uuid) -> Self {
+                Self(uuid)
+            }
+        }
+
+        impl From<$name> for Uuid {
+            fn from(id: $name) -> Self {
+                id.0
+            }
+        }
+
+        impl AsRef<Uuid> for $name {
+            fn as_ref(&self) -> &Uuid {
+                &self.0
+            }
+        }
+
+        #[cfg(feature = "sea-orm")]
+        impl From<$name> for sea_orm::Value {
+            fn from(id: $name) -> Self {
+                Self::from(id.0)
+            }
+        }
     };
 }
 
@@ -39,3 +39,38 @@ fn test_typed_id_from_str() {
 fn test_typed_id_from_str_error() {
     assert!(UserId::from_str("invalid").is_err());
 }
+
+#[test]
+fn test_typed_id_uuid_conversions() {
+    let uuid = Uuid::new_v4();
+    let id: TransactionId = uuid.into();
+    assert_eq!(id, TransactionId::from_uuid(uuid));
+
+    let back: Uuid = id.into();
+    assert_eq!(back, uuid);
+    assert_eq!(id.as_ref(), &uuid);
+}
+
+#[test]
+fn test_typed_id_serde_is_transparent() {
+    let uuid = Uuid::new_v4();
+    let id = OrganizationId::from_uuid(uuid);
+
+    let json = serde_json::to_string(&id).unwrap();
+    assert_eq!(json, format!("\"{uuid}\""));
+
+    let parsed: OrganizationId = serde_json::from_str(&json).unwrap();
+    assert_eq!(parsed, id);
+}
+
+#[test]
+fn test_typed_ids_are_distinct_types() {
+    fn takes_account(id: AccountId) -> Uuid {
+        id.into_inner()
+    }
+
+    let uuid = Uuid::new_v4();
+    // Converting through `Uuid` is explicit; `takes_account(OrganizationId)` does not compile.
+    let org = OrganizationId::from_uuid(uuid);
+    assert_eq!(takes_account(AccountId::from(Uuid::from(org))), uuid);
+}
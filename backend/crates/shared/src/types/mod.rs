@@ -13,4 +13,7 @@ mod pagination_tests;
 
 pub use id::*;
 pub use money::Money;
-pub use pagination::{PageRequest, PageResponse};
+pub use pagination::{
+    Cursor, InvalidCursor, PageRequest, PageResponse, Sort, SortDirection, SortField, SortParams,
+    UnknownSortField,
+};
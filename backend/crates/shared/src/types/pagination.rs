@@ -1,6 +1,10 @@
 //! Pagination types for list endpoints.
 
+use base64::Engine;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use chrono::{DateTime, SecondsFormat, Utc};
 use serde::{Deserialize, Serialize};
+use uuid::Uuid;
 
 /// Request parameters for paginated queries.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -92,3 +96,136 @@ impl<T> PageResponse<T> {
         }
     }
 }
+
+/// Direction of a sorted list.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SortDirection {
+    /// Smallest first.
+    #[default]
+    Asc,
+    /// Largest first.
+    Desc,
+}
+
+#[cfg(feature = "sea-orm")]
+impl From<SortDirection> for sea_orm::Order {
+    fn from(direction: SortDirection) -> Self {
+        match direction {
+            SortDirection::Asc => Self::Asc,
+            SortDirection::Desc => Self::Desc,
+        }
+    }
+}
+
+/// A field a list endpoint can be sorted by.
+///
+/// Each endpoint defines an enum of its sortable fields and lists their
+/// query-string names in [`SortField::ALLOWED`]; anything else is rejected.
+pub trait SortField: Copy + Sized + 'static {
+    /// Query-string names of the sortable fields.
+    const ALLOWED: &'static [(&'static str, Self)];
+}
+
+/// Query parameters for sorted lists: `?sort=<field>&order=asc|desc`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SortParams {
+    /// Field to sort by.
+    pub sort: Option<String>,
+    /// Sort direction, ascending when omitted.
+    pub order: Option<SortDirection>,
+}
+
+/// A validated sort for one endpoint.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Sort<F> {
+    /// Field to sort by.
+    pub field: F,
+    /// Sort direction.
+    pub direction: SortDirection,
+}
+
+/// A sort field that is not in the endpoint's whitelist.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("Unknown sort field '{field}'. Allowed fields: {}", allowed.join(", "))]
+pub struct UnknownSortField {
+    /// The requested field.
+    pub field: String,
+    /// Fields the endpoint accepts.
+    pub allowed: Vec<&'static str>,
+}
+
+impl SortParams {
+    /// Resolves the requested sort against an endpoint's whitelist.
+    ///
+    /// Returns `None` when no sort field was given, so the endpoint keeps its
+    /// default ordering.
+    ///
+    /// # Errors
+    ///
+    /// Returns `UnknownSortField` if the field is not sortable.
+    pub fn resolve<F: SortField>(&self) -> Result<Option<Sort<F>>, UnknownSortField> {
+        let Some(name) = self.sort.as_deref() else {
+            return Ok(None);
+        };
+
+        let field = F::ALLOWED
+            .iter()
+            .find(|(allowed, _)| *allowed == name)
+            .map(|(_, field)| *field)
+            .ok_or_else(|| UnknownSortField {
+                field: name.to_string(),
+                allowed: F::ALLOWED.iter().map(|(allowed, _)| *allowed).collect(),
+            })?;
+
+        Ok(Some(Sort {
+            field,
+            direction: self.order.unwrap_or_default(),
+        }))
+    }
+}
+
+/// Position in a list ordered by a timestamp, then ID.
+///
+/// Handed to clients as an opaque string; the next page holds the rows after
+/// it. Unlike an offset it does not shift when rows are inserted behind it,
+/// and the database seeks to it instead of skipping rows.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cursor {
+    /// Timestamp the last row returned was ordered by.
+    pub at: DateTime<Utc>,
+    /// ID of the last row returned.
+    pub id: Uuid,
+}
+
+/// A cursor string that was not issued by [`Cursor::encode`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+#[error("Invalid cursor")]
+pub struct InvalidCursor;
+
+impl Cursor {
+    /// Encodes the cursor as an opaque URL-safe string.
+    #[must_use]
+    pub fn encode(&self) -> String {
+        let at = self.at.to_rfc3339_opts(SecondsFormat::Micros, true);
+        URL_SAFE_NO_PAD.encode(format!("{at}|{}", self.id))
+    }
+
+    /// Decodes a string produced by [`Cursor::encode`].
+    ///
+    /// # Errors
+    ///
+    /// Returns `InvalidCursor` if the string is not a valid cursor.
+    pub fn decode(cursor: &str) -> Result<Self, InvalidCursor> {
+        let bytes = URL_SAFE_NO_PAD.decode(cursor).map_err(|_| InvalidCursor)?;
+        let raw = String::from_utf8(bytes).map_err(|_| InvalidCursor)?;
+        let (at, id) = raw.split_once('|').ok_or(InvalidCursor)?;
+
+        Ok(Self {
+            at: DateTime::parse_from_rfc3339(at)
+                .map_err(|_| InvalidCursor)?
+                .with_timezone(&Utc),
+            id: id.parse().map_err(|_| InvalidCursor)?,
+        })
+    }
+}
@@ -55,3 +55,88 @@ fn test_page_response_empty() {
     let response: PageResponse<i32> = PageResponse::new(vec![], 1, 10, 0);
     assert_eq!(response.meta.total_pages, 1);
 }
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TestSort {
+    Name,
+    CreatedAt,
+}
+
+impl SortField for TestSort {
+    const ALLOWED: &'static [(&'static str, Self)] =
+        &[("name", Self::Name), ("created_at", Self::CreatedAt)];
+}
+
+fn sort_params(sort: Option<&str>, order: Option<SortDirection>) -> SortParams {
+    SortParams {
+        sort: sort.map(ToString::to_string),
+        order,
+    }
+}
+
+#[test]
+fn test_sort_params_without_field_keeps_default_order() {
+    let sort = sort_params(None, Some(SortDirection::Desc)).resolve::<TestSort>();
+    assert_eq!(sort, Ok(None));
+}
+
+#[test]
+fn test_sort_params_resolves_known_field() {
+    let sort = sort_params(Some("created_at"), Some(SortDirection::Desc))
+        .resolve::<TestSort>()
+        .unwrap();
+    assert_eq!(
+        sort,
+        Some(Sort {
+            field: TestSort::CreatedAt,
+            direction: SortDirection::Desc,
+        })
+    );
+}
+
+#[test]
+fn test_sort_params_defaults_to_ascending() {
+    let sort = sort_params(Some("name"), None)
+        .resolve::<TestSort>()
+        .unwrap()
+        .unwrap();
+    assert_eq!(sort.direction, SortDirection::Asc);
+}
+
+#[test]
+fn test_sort_params_rejects_unknown_field() {
+    let err = sort_params(Some("password"), None)
+        .resolve::<TestSort>()
+        .unwrap_err();
+    assert_eq!(err.field, "password");
+    assert_eq!(err.allowed, ["name", "created_at"]);
+    assert_eq!(
+        err.to_string(),
+        "Unknown sort field 'password'. Allowed fields: name, created_at"
+    );
+}
+
+#[test]
+fn test_sort_direction_deserializes_lowercase() {
+    let params: SortParams = serde_json::from_str(r#"{"sort":"name","order":"desc"}"#).unwrap();
+    assert_eq!(params.order, Some(SortDirection::Desc));
+}
+
+#[test]
+fn test_cursor_round_trips() {
+    let cursor = Cursor {
+        at: "2025-03-10T09:15:30.123456Z".parse().unwrap(),
+        id: uuid::Uuid::new_v4(),
+    };
+    let encoded = cursor.encode();
+
+    assert!(!encoded.contains('|'));
+    assert_eq!(Cursor::decode(&encoded), Ok(cursor));
+}
+
+#[test]
+fn test_cursor_rejects_foreign_strings() {
+    assert_eq!(Cursor::decode("not a cursor"), Err(InvalidCursor));
+    assert_eq!(Cursor::decode("MjAyNQ"), Err(InvalidCursor));
+    assert_eq!(Cursor::decode(""), Err(InvalidCursor));
+}
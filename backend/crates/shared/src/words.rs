@@ -0,0 +1,333 @@
+//! Numbers and amounts spelled out in words.
+//!
+//! Printed vouchers and cheques state their total in words as well as in
+//! figures, so a changed digit is caught. English follows US usage ("one
+//! thousand two hundred thirty-four", no "and" inside the number);
+//! Indonesian follows the usual written form ("seribu dua ratus tiga puluh
+//! empat").
+
+use std::fmt;
+
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+use crate::format::Locale;
+
+/// Languages amounts can be written out in.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Language {
+    /// English (US usage).
+    #[default]
+    #[serde(rename = "en")]
+    English,
+    /// Indonesian.
+    #[serde(rename = "id")]
+    Indonesian,
+}
+
+impl Language {
+    /// ISO 639-1 code of the language.
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::English => "en",
+            Self::Indonesian => "id",
+        }
+    }
+}
+
+impl fmt::Display for Language {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl From<Locale> for Language {
+    /// The language of a locale; locales without their own number words
+    /// fall back to English.
+    fn from(locale: Locale) -> Self {
+        match locale {
+            Locale::IdId => Self::Indonesian,
+            Locale::EnUs | Locale::DeDe => Self::English,
+        }
+    }
+}
+
+/// How the fractional part of an amount is written.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum FractionStyle {
+    /// In words with the currency's minor unit: "... and fifty-six cents".
+    #[default]
+    Units,
+    /// In figures over the minor unit count, cheque style: "... and 56/100
+    /// US dollars".
+    Ratio,
+}
+
+const EN_ONES: [&str; 20] = [
+    "zero",
+    "one",
+    "two",
+    "three",
+    "four",
+    "five",
+    "six",
+    "seven",
+    "eight",
+    "nine",
+    "ten",
+    "eleven",
+    "twelve",
+    "thirteen",
+    "fourteen",
+    "fifteen",
+    "sixteen",
+    "seventeen",
+    "eighteen",
+    "nineteen",
+];
+
+const EN_TENS: [&str; 10] = [
+    "", "", "twenty", "thirty", "forty", "fifty", "sixty", "seventy", "eighty", "ninety",
+];
+
+/// Short-scale names of successive powers of a thousand.
+const EN_SCALES: [&str; 13] = [
+    "",
+    "thousand",
+    "million",
+    "billion",
+    "trillion",
+    "quadrillion",
+    "quintillion",
+    "sextillion",
+    "septillion",
+    "octillion",
+    "nonillion",
+    "decillion",
+    "undecillion",
+];
+
+const ID_ONES: [&str; 10] = [
+    "nol", "satu", "dua", "tiga", "empat", "lima", "enam", "tujuh", "delapan", "sembilan",
+];
+
+const ID_SCALES: [&str; 13] = [
+    "",
+    "ribu",
+    "juta",
+    "miliar",
+    "triliun",
+    "kuadriliun",
+    "kuintiliun",
+    "sekstiliun",
+    "septiliun",
+    "oktiliun",
+    "noniliun",
+    "desiliun",
+    "undesiliun",
+];
+
+/// Writes out a whole number.
+#[must_use]
+pub fn number_in_words(n: u128, language: Language) -> String {
+    if n == 0 {
+        return match language {
+            Language::English => EN_ONES[0],
+            Language::Indonesian => ID_ONES[0],
+        }
+        .to_string();
+    }
+
+    let mut groups = Vec::new();
+    let mut rest = n;
+    while rest > 0 {
+        // Always below 1000, so the cast is lossless
+        #[allow(clippy::cast_possible_truncation)]
+        groups.push((rest % 1000) as u16);
+        rest /= 1000;
+    }
+
+    let mut words: Vec<String> = Vec::new();
+    for (scale, &group) in groups.iter().enumerate().rev() {
+        if group == 0 {
+            continue;
+        }
+        match language {
+            Language::English => {
+                words.push(en_below_thousand(group));
+                if scale > 0 {
+                    words.push(EN_SCALES[scale].to_string());
+                }
+            }
+            // "seribu" rather than "satu ribu", but "satu juta"
+            Language::Indonesian if scale == 1 && group == 1 => words.push("seribu".to_string()),
+            Language::Indonesian => {
+                words.push(id_below_thousand(group));
+                if scale > 0 {
+                    words.push(ID_SCALES[scale].to_string());
+                }
+            }
+        }
+    }
+    words.join(" ")
+}
+
+fn en_below_thousand(n: u16) -> String {
+    let (hundreds, rest) = (usize::from(n / 100), usize::from(n % 100));
+    let mut parts = Vec::new();
+    if hundreds > 0 {
+        parts.push(format!("{} hundred", EN_ONES[hundreds]));
+    }
+    match rest {
+        0 => {}
+        1..20 => parts.push(EN_ONES[rest].to_string()),
+        _ if rest % 10 == 0 => parts.push(EN_TENS[rest / 10].to_string()),
+        _ => parts.push(format!("{}-{}", EN_TENS[rest / 10], EN_ONES[rest % 10])),
+    }
+    parts.join(" ")
+}
+
+fn id_below_thousand(n: u16) -> String {
+    let (hundreds, rest) = (usize::from(n / 100), usize::from(n % 100));
+    let mut parts = Vec::new();
+    match hundreds {
+        0 => {}
+        1 => parts.push("seratus".to_string()),
+        _ => parts.push(format!("{} ratus", ID_ONES[hundreds])),
+    }
+    match rest {
+        0 => {}
+        1..10 => parts.push(ID_ONES[rest].to_string()),
+        10 => parts.push("sepuluh".to_string()),
+        11 => parts.push("sebelas".to_string()),
+        12..20 => parts.push(format!("{} belas", ID_ONES[rest % 10])),
+        _ if rest % 10 == 0 => parts.push(format!("{} puluh", ID_ONES[rest / 10])),
+        _ => parts.push(format!(
+            "{} puluh {}",
+            ID_ONES[rest / 10],
+            ID_ONES[rest % 10]
+        )),
+    }
+    parts.join(" ")
+}
+
+/// Names of a currency's major and minor units in one language.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CurrencyUnits<'a> {
+    /// Major unit, singular.
+    pub one: &'a str,
+    /// Major unit, plural.
+    pub many: &'a str,
+    /// Minor unit, singular.
+    pub minor_one: &'a str,
+    /// Minor unit, plural.
+    pub minor_many: &'a str,
+}
+
+impl<'a> CurrencyUnits<'a> {
+    /// Unit names for an ISO 4217 code.
+    ///
+    /// Currencies without names of their own are written with their code
+    /// as the major unit, and cents (sen in Indonesian) as the minor unit.
+    #[must_use]
+    pub fn for_currency(code: &'a str, language: Language) -> Self {
+        let (one, many, minor_one, minor_many) = match (language, code) {
+            (Language::English, "USD") => ("US dollar", "US dollars", "cent", "cents"),
+            (Language::English, "EUR") => ("euro", "euros", "cent", "cents"),
+            (Language::English, "GBP") => ("pound sterling", "pounds sterling", "penny", "pence"),
+            (Language::English, "JPY") => ("yen", "yen", "sen", "sen"),
+            (Language::English, "IDR") => ("rupiah", "rupiah", "sen", "sen"),
+            (Language::English, "SGD") => {
+                ("Singapore dollar", "Singapore dollars", "cent", "cents")
+            }
+            (Language::English, "AUD") => {
+                ("Australian dollar", "Australian dollars", "cent", "cents")
+            }
+            (Language::English, "MYR") => ("ringgit", "ringgit", "sen", "sen"),
+            (Language::English, _) => (code, code, "cent", "cents"),
+            (Language::Indonesian, "USD") => ("dolar Amerika Serikat", "", "sen", ""),
+            (Language::Indonesian, "EUR") => ("euro", "", "sen", ""),
+            (Language::Indonesian, "GBP") => ("pound sterling", "", "penny", ""),
+            (Language::Indonesian, "JPY") => ("yen", "", "sen", ""),
+            (Language::Indonesian, "IDR") => ("rupiah", "", "sen", ""),
+            (Language::Indonesian, "SGD") => ("dolar Singapura", "", "sen", ""),
+            (Language::Indonesian, "AUD") => ("dolar Australia", "", "sen", ""),
+            (Language::Indonesian, "MYR") => ("ringgit", "", "sen", ""),
+            (Language::Indonesian, _) => (code, "", "sen", ""),
+        };
+        // Indonesian nouns do not inflect for number
+        if language == Language::Indonesian {
+            return Self {
+                one,
+                many: one,
+                minor_one,
+                minor_many: minor_one,
+            };
+        }
+        Self {
+            one,
+            many,
+            minor_one,
+            minor_many,
+        }
+    }
+}
+
+/// Writes out an amount of a currency.
+///
+/// The amount is rounded to `decimal_places` the same way
+/// [`format_amount`](crate::format::format_amount) rounds it, so the words
+/// always match the figures printed next to them. A zero-decimal currency
+/// such as IDR or JPY never has a fractional part; a zero fraction is left
+/// out in [`FractionStyle::Units`]. Negative amounts are prefixed with
+/// "minus".
+#[must_use]
+pub fn amount_in_words(
+    amount: Decimal,
+    currency: &str,
+    decimal_places: u32,
+    language: Language,
+    style: FractionStyle,
+) -> String {
+    let rounded = amount.round_dp(decimal_places);
+    let mut units = rounded.abs();
+    units.rescale(decimal_places);
+    let minor_per_major = 10u128.pow(decimal_places);
+    let total = units.mantissa().unsigned_abs();
+    let (major, minor) = (total / minor_per_major, total % minor_per_major);
+
+    let names = CurrencyUnits::for_currency(currency, language);
+    let and = match language {
+        Language::English => "and",
+        Language::Indonesian => "dan",
+    };
+    let plural = |n: u128, one: &str, many: &str| {
+        format!(
+            "{} {}",
+            number_in_words(n, language),
+            if n == 1 { one } else { many }
+        )
+    };
+
+    let words = match style {
+        FractionStyle::Ratio if decimal_places > 0 => format!(
+            "{} {and} {minor:0width$}/{minor_per_major} {}",
+            number_in_words(major, language),
+            names.many,
+            width = decimal_places as usize,
+        ),
+        _ if minor == 0 => plural(major, names.one, names.many),
+        _ if major == 0 => plural(minor, names.minor_one, names.minor_many),
+        _ => format!(
+            "{} {and} {}",
+            plural(major, names.one, names.many),
+            plural(minor, names.minor_one, names.minor_many)
+        ),
+    };
+    if rounded.is_sign_negative() && !rounded.is_zero() {
+        format!("minus {words}")
+    } else {
+        words
+    }
+}
@@ -0,0 +1,204 @@
+use rstest::rstest;
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+
+use crate::format::Locale;
+use crate::words::{CurrencyUnits, FractionStyle, Language, amount_in_words, number_in_words};
+
+#[rstest]
+#[case(0, "zero")]
+#[case(1, "one")]
+#[case(9, "nine")]
+#[case(10, "ten")]
+#[case(11, "eleven")]
+#[case(15, "fifteen")]
+#[case(19, "nineteen")]
+#[case(20, "twenty")]
+#[case(21, "twenty-one")]
+#[case(40, "forty")]
+#[case(99, "ninety-nine")]
+#[case(100, "one hundred")]
+#[case(101, "one hundred one")]
+#[case(110, "one hundred ten")]
+#[case(999, "nine hundred ninety-nine")]
+#[case(1000, "one thousand")]
+#[case(1001, "one thousand one")]
+#[case(1010, "one thousand ten")]
+#[case(1234, "one thousand two hundred thirty-four")]
+#[case(10_000, "ten thousand")]
+#[case(100_000, "one hundred thousand")]
+#[case(101_000, "one hundred one thousand")]
+#[case(1_000_000, "one million")]
+#[case(1_000_001, "one million one")]
+#[case(1_001_000, "one million one thousand")]
+#[case(2_000_000_000, "two billion")]
+#[case(1_000_000_000_000, "one trillion")]
+#[case(
+    123_456_789,
+    "one hundred twenty-three million four hundred fifty-six thousand seven hundred eighty-nine"
+)]
+fn test_number_in_words_english(#[case] n: u128, #[case] expected: &str) {
+    assert_eq!(number_in_words(n, Language::English), expected);
+}
+
+#[rstest]
+#[case(0, "nol")]
+#[case(1, "satu")]
+#[case(10, "sepuluh")]
+#[case(11, "sebelas")]
+#[case(12, "dua belas")]
+#[case(19, "sembilan belas")]
+#[case(20, "dua puluh")]
+#[case(21, "dua puluh satu")]
+#[case(100, "seratus")]
+#[case(101, "seratus satu")]
+#[case(111, "seratus sebelas")]
+#[case(200, "dua ratus")]
+#[case(999, "sembilan ratus sembilan puluh sembilan")]
+#[case(1000, "seribu")]
+#[case(1001, "seribu satu")]
+#[case(1234, "seribu dua ratus tiga puluh empat")]
+#[case(2000, "dua ribu")]
+#[case(11_000, "sebelas ribu")]
+#[case(100_000, "seratus ribu")]
+#[case(101_000, "seratus satu ribu")]
+#[case(1_000_000, "satu juta")]
+#[case(1_001_000, "satu juta seribu")]
+#[case(2_500_000, "dua juta lima ratus ribu")]
+#[case(1_000_000_000, "satu miliar")]
+#[case(1_000_000_000_000, "satu triliun")]
+fn test_number_in_words_indonesian(#[case] n: u128, #[case] expected: &str) {
+    assert_eq!(number_in_words(n, Language::Indonesian), expected);
+}
+
+#[test]
+fn test_number_in_words_largest() {
+    let words = number_in_words(u128::MAX, Language::English);
+    assert!(words.starts_with("three hundred forty undecillion"));
+    assert!(words.ends_with("four hundred fifty-five"));
+    let words = number_in_words(u128::MAX, Language::Indonesian);
+    assert!(words.starts_with("tiga ratus empat puluh undesiliun"));
+}
+
+#[rstest]
+#[case(dec!(1234.56), "USD", 2, "one thousand two hundred thirty-four US dollars and fifty-six cents")]
+#[case(dec!(0), "USD", 2, "zero US dollars")]
+#[case(dec!(0.00), "USD", 2, "zero US dollars")]
+#[case(dec!(1), "USD", 2, "one US dollar")]
+#[case(dec!(1.01), "USD", 2, "one US dollar and one cent")]
+#[case(dec!(0.56), "USD", 2, "fifty-six cents")]
+#[case(dec!(1000000), "USD", 2, "one million US dollars")]
+#[case(dec!(1000000.00), "EUR", 2, "one million euros")]
+#[case(dec!(0.995), "USD", 2, "one US dollar")]
+#[case(dec!(0.005), "USD", 2, "zero US dollars")]
+#[case(dec!(2.5), "GBP", 2, "two pounds sterling and fifty pence")]
+#[case(dec!(-1234.5), "USD", 2, "minus one thousand two hundred thirty-four US dollars and fifty cents")]
+#[case(dec!(1500000), "IDR", 0, "one million five hundred thousand rupiah")]
+#[case(dec!(1234.5), "JPY", 0, "one thousand two hundred thirty-four yen")]
+#[case(dec!(1235.5), "JPY", 0, "one thousand two hundred thirty-six yen")]
+#[case(dec!(1), "JPY", 0, "one yen")]
+#[case(dec!(0), "IDR", 0, "zero rupiah")]
+#[case(dec!(12.345), "CHF", 2, "twelve CHF and thirty-four cents")]
+#[case(dec!(1.005), "KWD", 3, "one KWD and five cents")]
+fn test_amount_in_words_english(
+    #[case] amount: Decimal,
+    #[case] currency: &str,
+    #[case] decimal_places: u32,
+    #[case] expected: &str,
+) {
+    assert_eq!(
+        amount_in_words(
+            amount,
+            currency,
+            decimal_places,
+            Language::English,
+            FractionStyle::Units
+        ),
+        expected
+    );
+}
+
+#[rstest]
+#[case(dec!(1234.56), "USD", 2, "seribu dua ratus tiga puluh empat dolar Amerika Serikat dan lima puluh enam sen")]
+#[case(dec!(0), "IDR", 0, "nol rupiah")]
+#[case(dec!(1000000), "IDR", 0, "satu juta rupiah")]
+#[case(dec!(1500000), "IDR", 0, "satu juta lima ratus ribu rupiah")]
+#[case(dec!(1000), "IDR", 0, "seribu rupiah")]
+#[case(dec!(15750000.4), "IDR", 0, "lima belas juta tujuh ratus lima puluh ribu rupiah")]
+#[case(dec!(0.10), "MYR", 2, "sepuluh sen")]
+#[case(dec!(-25), "EUR", 2, "minus dua puluh lima euro")]
+#[case(dec!(1.01), "SGD", 2, "satu dolar Singapura dan satu sen")]
+fn test_amount_in_words_indonesian(
+    #[case] amount: Decimal,
+    #[case] currency: &str,
+    #[case] decimal_places: u32,
+    #[case] expected: &str,
+) {
+    assert_eq!(
+        amount_in_words(
+            amount,
+            currency,
+            decimal_places,
+            Language::Indonesian,
+            FractionStyle::Units
+        ),
+        expected
+    );
+}
+
+#[rstest]
+#[case(Language::English, dec!(1234.56), "USD", 2, "one thousand two hundred thirty-four and 56/100 US dollars")]
+#[case(Language::English, dec!(1000000), "USD", 2, "one million and 00/100 US dollars")]
+#[case(Language::English, dec!(0.07), "USD", 2, "zero and 07/100 US dollars")]
+#[case(Language::English, dec!(1.005), "KWD", 3, "one and 005/1000 KWD")]
+#[case(Language::English, dec!(1000000), "JPY", 0, "one million yen")]
+#[case(Language::Indonesian, dec!(1234.56), "USD", 2, "seribu dua ratus tiga puluh empat dan 56/100 dolar Amerika Serikat")]
+#[case(Language::Indonesian, dec!(2500), "IDR", 0, "dua ribu lima ratus rupiah")]
+fn test_amount_in_words_ratio(
+    #[case] language: Language,
+    #[case] amount: Decimal,
+    #[case] currency: &str,
+    #[case] decimal_places: u32,
+    #[case] expected: &str,
+) {
+    assert_eq!(
+        amount_in_words(
+            amount,
+            currency,
+            decimal_places,
+            language,
+            FractionStyle::Ratio
+        ),
+        expected
+    );
+}
+
+#[test]
+fn test_largest_decimal_amount() {
+    let words = amount_in_words(
+        Decimal::MAX,
+        "USD",
+        0,
+        Language::English,
+        FractionStyle::Units,
+    );
+    assert!(words.starts_with("seventy-nine octillion"));
+    assert!(words.ends_with("three hundred thirty-five US dollars"));
+}
+
+#[test]
+fn test_indonesian_units_do_not_inflect() {
+    let units = CurrencyUnits::for_currency("USD", Language::Indonesian);
+    assert_eq!(units.one, units.many);
+    assert_eq!(units.minor_one, units.minor_many);
+    let units = CurrencyUnits::for_currency("XYZ", Language::Indonesian);
+    assert_eq!((units.one, units.minor_one), ("XYZ", "sen"));
+}
+
+#[rstest]
+#[case(Locale::EnUs, Language::English)]
+#[case(Locale::DeDe, Language::English)]
+#[case(Locale::IdId, Language::Indonesian)]
+fn test_language_from_locale(#[case] locale: Locale, #[case] expected: Language) {
+    assert_eq!(Language::from(locale), expected);
+}
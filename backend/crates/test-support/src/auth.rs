@@ -0,0 +1,33 @@
+//! Access tokens for fixture members.
+
+use sea_orm::ActiveEnum;
+use zeltra_shared::{JwtConfig, JwtService, TokenMember, types::OrganizationId};
+
+use crate::Member;
+
+/// Returns a JWT service with the default test configuration.
+///
+/// Tokens it mints are accepted by any service built from
+/// `JwtConfig::default()`.
+pub fn jwt_service() -> JwtService {
+    JwtService::new(JwtConfig::default())
+}
+
+/// Mints an access token for `member` in `org_id`.
+///
+/// The token carries the member's role and the token version fixtures start
+/// users at, so it passes revocation checks.
+///
+/// # Panics
+///
+/// Panics if the token cannot be signed.
+pub fn access_token(jwt: &JwtService, org_id: OrganizationId, member: &Member) -> String {
+    jwt.generate_member_access_token(&TokenMember {
+        user_id: member.user_id.into(),
+        org_id: org_id.into(),
+        role: member.role.to_value(),
+        approval_limit: None,
+        token_version: 0,
+    })
+    .expect("failed to sign test access token")
+}
@@ -0,0 +1,429 @@
+//! Fixture builders for organization data.
+//!
+//! Fixtures write through the same entities and repositories as the
+//! application, and give every row a fresh ID so tests sharing a database do
+//! not see each other's data.
+
+use std::collections::HashMap;
+
+use chrono::{NaiveDate, Utc};
+use rust_decimal::Decimal;
+use sea_orm::{ActiveModelTrait, DatabaseConnection, Set};
+use uuid::Uuid;
+use zeltra_db::{
+    entities::{
+        organization_users, organizations,
+        sea_orm_active_enums::{
+            AccountType, SubscriptionStatus, SubscriptionTier, TransactionType, UserRole,
+        },
+        users,
+    },
+    repositories::{
+        AccountRepository, CreateAccountInput, CreateFiscalYearInput, FiscalRepository,
+        WorkflowRepository,
+        transaction::{CreateLedgerEntryInput, CreateTransactionInput, TransactionRepository},
+    },
+};
+use zeltra_shared::types::{
+    AccountId, FiscalPeriodId, FiscalYearId, OrganizationId, TransactionId, UserId,
+};
+
+/// Functional currency of fixture organizations.
+const CURRENCY: &str = "USD";
+
+/// Builder for an organization with an owner and optional ledger setup.
+///
+/// Every organization gets an owner; further members, a fiscal year and
+/// accounts are opt-in.
+#[derive(Debug, Clone, Default)]
+pub struct OrgFixture {
+    tier: Option<SubscriptionTier>,
+    members: Vec<UserRole>,
+    fiscal_year: Option<i32>,
+    accounts: Vec<(String, AccountType)>,
+}
+
+impl OrgFixture {
+    /// Starts an organization with only an owner.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Puts the organization on `tier` instead of enterprise.
+    #[must_use]
+    pub fn with_tier(mut self, tier: SubscriptionTier) -> Self {
+        self.tier = Some(tier);
+        self
+    }
+
+    /// Adds a member with `role`.
+    #[must_use]
+    pub fn with_member(mut self, role: UserRole) -> Self {
+        self.members.push(role);
+        self
+    }
+
+    /// Adds calendar fiscal year 2025 with 12 open monthly periods.
+    #[must_use]
+    pub const fn with_fiscal_year_2025(self) -> Self {
+        self.with_fiscal_year(2025)
+    }
+
+    /// Adds a calendar fiscal year with 12 open monthly periods.
+    #[must_use]
+    pub const fn with_fiscal_year(mut self, year: i32) -> Self {
+        self.fiscal_year = Some(year);
+        self
+    }
+
+    /// Adds postable accounts, given as `(code, type)` pairs.
+    #[must_use]
+    pub fn with_accounts(mut self, accounts: &[(&str, AccountType)]) -> Self {
+        self.accounts.extend(
+            accounts
+                .iter()
+                .map(|(code, account_type)| ((*code).to_string(), account_type.clone())),
+        );
+        self
+    }
+
+    /// Inserts the organization and everything configured on the builder.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any insert fails.
+    pub async fn create(self, db: &DatabaseConnection) -> Org {
+        let id = Uuid::new_v4();
+        let now = Utc::now();
+
+        organizations::ActiveModel {
+            id: Set(id),
+            name: Set(format!("Test Organization {}", id.simple())),
+            slug: Set(format!("test-{}", id.simple())),
+            base_currency: Set(CURRENCY.to_string()),
+            timezone: Set("UTC".to_string()),
+            settings: Set(serde_json::json!({})),
+            subscription_tier: Set(self.tier.unwrap_or(SubscriptionTier::Enterprise)),
+            subscription_status: Set(SubscriptionStatus::Active),
+            trial_ends_at: Set(None),
+            subscription_ends_at: Set(None),
+            payment_provider: Set(None),
+            payment_customer_id: Set(None),
+            payment_subscription_id: Set(None),
+            is_active: Set(true),
+            created_at: Set(now.into()),
+            updated_at: Set(now.into()),
+        }
+        .insert(db)
+        .await
+        .expect("failed to insert fixture organization");
+
+        let owner = insert_member(db, id, UserRole::Owner).await;
+        let mut members = Vec::with_capacity(self.members.len());
+        for role in self.members {
+            members.push(insert_member(db, id, role).await);
+        }
+
+        let fiscal_year = match self.fiscal_year {
+            Some(year) => Some(insert_fiscal_year(db, id, year).await),
+            None => None,
+        };
+
+        let repo = AccountRepository::new(db.clone());
+        let mut accounts = HashMap::with_capacity(self.accounts.len());
+        for (code, account_type) in self.accounts {
+            let account = repo
+                .create_account(CreateAccountInput {
+                    organization_id: id,
+                    code: code.clone(),
+                    name: format!("Account {code}"),
+                    description: None,
+                    account_type,
+                    account_subtype: None,
+                    parent_id: None,
+                    currency: CURRENCY.to_string(),
+                    is_active: true,
+                    allow_direct_posting: true,
+                    is_bank_account: false,
+                    bank_account_number: None,
+                })
+                .await
+                .unwrap_or_else(|e| panic!("failed to insert fixture account {code}: {e}"));
+            accounts.insert(code, account.id.into());
+        }
+
+        Org {
+            id: id.into(),
+            owner,
+            members,
+            fiscal_year,
+            accounts,
+        }
+    }
+}
+
+/// An organization created by [`OrgFixture`].
+#[derive(Debug, Clone)]
+pub struct Org {
+    /// Organization ID.
+    pub id: OrganizationId,
+    /// The organization's owner.
+    pub owner: Member,
+    /// Members added with [`OrgFixture::with_member`], in order.
+    pub members: Vec<Member>,
+    /// Fiscal year, if one was requested.
+    pub fiscal_year: Option<FiscalYearFixture>,
+    /// Account IDs by code.
+    pub accounts: HashMap<String, AccountId>,
+}
+
+impl Org {
+    /// Returns the ID of the account with `code`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the fixture has no such account.
+    pub fn account(&self, code: &str) -> AccountId {
+        *self
+            .accounts
+            .get(code)
+            .unwrap_or_else(|| panic!("fixture has no account {code}"))
+    }
+
+    /// Returns the first member with `role`, including the owner.
+    ///
+    /// # Panics
+    ///
+    /// Panics if no member has the role.
+    pub fn member(&self, role: &UserRole) -> &Member {
+        std::iter::once(&self.owner)
+            .chain(&self.members)
+            .find(|member| &member.role == role)
+            .unwrap_or_else(|| panic!("fixture has no {role:?} member"))
+    }
+}
+
+/// A user and their role in a fixture organization.
+#[derive(Debug, Clone)]
+pub struct Member {
+    /// User ID.
+    pub user_id: UserId,
+    /// Role in the organization.
+    pub role: UserRole,
+}
+
+/// A fiscal year created by [`OrgFixture`].
+#[derive(Debug, Clone)]
+pub struct FiscalYearFixture {
+    /// Fiscal year ID.
+    pub id: FiscalYearId,
+    /// Period IDs, January first.
+    pub periods: Vec<FiscalPeriodId>,
+}
+
+/// One line of a fixture transaction, in the fixture currency.
+#[derive(Debug, Clone)]
+pub struct Line<'a> {
+    /// Code of a fixture account.
+    pub account: &'a str,
+    /// Debit amount.
+    pub debit: Decimal,
+    /// Credit amount.
+    pub credit: Decimal,
+    /// Dimension value IDs to tag the entry with.
+    pub dimensions: Vec<Uuid>,
+}
+
+impl<'a> Line<'a> {
+    /// Debits `amount` to `account`.
+    #[must_use]
+    pub const fn debit(account: &'a str, amount: Decimal) -> Self {
+        Self {
+            account,
+            debit: amount,
+            credit: Decimal::ZERO,
+            dimensions: Vec::new(),
+        }
+    }
+
+    /// Credits `amount` to `account`.
+    #[must_use]
+    pub const fn credit(account: &'a str, amount: Decimal) -> Self {
+        Self {
+            account,
+            debit: Decimal::ZERO,
+            credit: amount,
+            dimensions: Vec::new(),
+        }
+    }
+
+    /// Tags the entry with dimension values.
+    #[must_use]
+    pub fn with_dimensions(mut self, dimensions: Vec<Uuid>) -> Self {
+        self.dimensions = dimensions;
+        self
+    }
+}
+
+/// Creates a journal transaction with `lines` on `date` and takes it through
+/// submission, approval and posting as the owner.
+///
+/// # Panics
+///
+/// Panics if any step fails, including for unbalanced lines.
+pub async fn post_transaction(
+    db: &DatabaseConnection,
+    org: &Org,
+    date: NaiveDate,
+    lines: Vec<Line<'_>>,
+) -> TransactionId {
+    post_transaction_as(db, org, org.owner.user_id, date, lines).await
+}
+
+/// Like [`post_transaction`], but created and submitted by `submitter`; the
+/// owner still approves and posts.
+///
+/// # Panics
+///
+/// Panics if any step fails, including for unbalanced lines.
+pub async fn post_transaction_as(
+    db: &DatabaseConnection,
+    org: &Org,
+    submitter: UserId,
+    date: NaiveDate,
+    lines: Vec<Line<'_>>,
+) -> TransactionId {
+    let entries = lines
+        .into_iter()
+        .map(|line| {
+            let amount = line.debit + line.credit;
+            CreateLedgerEntryInput {
+                account_id: org.account(line.account).into_inner(),
+                source_currency: CURRENCY.to_string(),
+                source_amount: amount,
+                exchange_rate: Decimal::ONE,
+                functional_currency: CURRENCY.to_string(),
+                functional_amount: amount,
+                debit: line.debit,
+                credit: line.credit,
+                memo: None,
+                dimensions: line.dimensions,
+                rate_override: None,
+                event_at: None,
+            }
+        })
+        .collect();
+    let created = TransactionRepository::new(db.clone())
+        .create_transaction(CreateTransactionInput {
+            organization_id: org.id.into_inner(),
+            transaction_type: TransactionType::Journal,
+            transaction_date: date,
+            description: format!("Fixture transaction on {date}"),
+            reference_number: None,
+            memo: None,
+            entries,
+            created_by: submitter.into_inner(),
+        })
+        .await
+        .unwrap_or_else(|e| panic!("failed to create fixture transaction: {e}"));
+
+    let id = TransactionId::from(created.transaction.id);
+    submit_and_post(db, org, submitter, id).await;
+    id
+}
+
+/// Takes the draft `id` through submission, approval and posting as the
+/// owner.
+///
+/// For drafts created some other way than [`post_transaction`], such as
+/// through the API.
+///
+/// # Panics
+///
+/// Panics if any step fails.
+pub async fn post_draft(db: &DatabaseConnection, org: &Org, id: TransactionId) {
+    submit_and_post(db, org, org.owner.user_id, id).await;
+}
+
+/// Submits `id` as `submitter`, then approves and posts it as the owner.
+async fn submit_and_post(db: &DatabaseConnection, org: &Org, submitter: UserId, id: TransactionId) {
+    let owner = org.owner.user_id.into_inner();
+    let workflow = WorkflowRepository::new(db.clone());
+    workflow
+        .submit_transaction(org.id, id, submitter.into_inner())
+        .await
+        .unwrap_or_else(|e| panic!("failed to submit fixture transaction: {e}"));
+    workflow
+        .approve_transaction(org.id, id, owner, None)
+        .await
+        .unwrap_or_else(|e| panic!("failed to approve fixture transaction: {e}"));
+    workflow
+        .post_transaction(org.id, id, owner)
+        .await
+        .unwrap_or_else(|e| panic!("failed to post fixture transaction: {e}"));
+}
+
+/// Inserts a verified, active user and adds them to the organization.
+async fn insert_member(db: &DatabaseConnection, org_id: Uuid, role: UserRole) -> Member {
+    let user_id = Uuid::new_v4();
+    let now = Utc::now();
+
+    users::ActiveModel {
+        id: Set(user_id),
+        email: Set(format!("user-{}@test.zeltra.dev", user_id.simple())),
+        password_hash: Set("$argon2id$v=19$m=65536,t=3,p=4$test_hash".to_string()),
+        full_name: Set("Test User".to_string()),
+        is_active: Set(true),
+        email_verified_at: Set(Some(now.into())),
+        token_version: Set(0),
+        created_at: Set(now.into()),
+        updated_at: Set(now.into()),
+        locale: Set(None),
+        default_organization_id: Set(None),
+    }
+    .insert(db)
+    .await
+    .expect("failed to insert fixture user");
+
+    organization_users::ActiveModel {
+        user_id: Set(user_id),
+        organization_id: Set(org_id),
+        role: Set(role.clone()),
+        approval_limit: Set(None),
+        created_at: Set(now.into()),
+        updated_at: Set(now.into()),
+    }
+    .insert(db)
+    .await
+    .expect("failed to insert fixture membership");
+
+    Member {
+        user_id: user_id.into(),
+        role,
+    }
+}
+
+async fn insert_fiscal_year(db: &DatabaseConnection, org_id: Uuid, year: i32) -> FiscalYearFixture {
+    let fiscal_year = FiscalRepository::new(db.clone())
+        .create_fiscal_year(CreateFiscalYearInput {
+            organization_id: org_id,
+            name: format!("FY {year}"),
+            start_date: NaiveDate::from_ymd_opt(year, 1, 1).expect("valid fiscal year start"),
+            end_date: NaiveDate::from_ymd_opt(year, 12, 31).expect("valid fiscal year end"),
+        })
+        .await
+        .unwrap_or_else(|e| panic!("failed to insert fixture fiscal year {year}: {e}"));
+
+    let mut periods = fiscal_year.periods;
+    periods.sort_by_key(|period| period.period_number);
+
+    FiscalYearFixture {
+        id: fiscal_year.fiscal_year.id.into(),
+        periods: periods
+            .into_iter()
+            .filter(|period| !period.is_adjustment_period)
+            .map(|period| period.id.into())
+            .collect(),
+    }
+}
@@ -0,0 +1,132 @@
+//! Application state and requests for route tests.
+//!
+//! Route tests build their state with [`test_app_state`] and drive a router
+//! with [`send`] or [`send_request`]. The state is the API crate's
+//! `AppState`, which implements [`TestAppState`]; the trait lives here so
+//! this crate does not depend on the API crate.
+
+use axum::{
+    Router,
+    body::{Body, Bytes},
+    http::{HeaderMap, Request, StatusCode, header::AUTHORIZATION, header::CONTENT_TYPE},
+};
+use http_body_util::BodyExt;
+use tower::ServiceExt;
+
+use crate::TestDb;
+
+/// Application state that can be built around a [`TestDb`].
+pub trait TestAppState {
+    /// Builds state backed by `test_db`, with no storage, metrics or jobs.
+    ///
+    /// The JWT service accepts tokens minted by
+    /// [`access_token`](crate::access_token) with
+    /// [`jwt_service`](crate::jwt_service).
+    fn from_test_db(test_db: &TestDb) -> Self;
+}
+
+/// Builds application state backed by `test_db`.
+pub fn test_app_state<S: TestAppState>(test_db: &TestDb) -> S {
+    S::from_test_db(test_db)
+}
+
+/// Status, headers and body of a response to a test request.
+pub struct TestResponse {
+    /// Response status.
+    pub status: StatusCode,
+    /// Response headers.
+    pub headers: HeaderMap,
+    /// Collected response body.
+    pub body: Bytes,
+}
+
+impl TestResponse {
+    /// Parses the body as JSON, or returns `Null` when it is not JSON.
+    #[must_use]
+    pub fn json(&self) -> serde_json::Value {
+        serde_json::from_slice(&self.body).unwrap_or_default()
+    }
+
+    /// Returns the body as text.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the body is not UTF-8.
+    #[must_use]
+    pub fn text(&self) -> String {
+        String::from_utf8(self.body.to_vec()).expect("response body is not UTF-8")
+    }
+
+    /// Returns the value of the `name` header, if present.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the header value is not visible ASCII.
+    #[must_use]
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .get(name)
+            .map(|value| value.to_str().expect("header value is not ASCII"))
+    }
+}
+
+/// Builds a request with a bearer `token` and `body` as its JSON payload.
+///
+/// # Panics
+///
+/// Panics if `method` or `uri` is invalid.
+pub fn json_request(
+    method: &str,
+    uri: &str,
+    token: &str,
+    body: &serde_json::Value,
+) -> Request<Body> {
+    Request::builder()
+        .method(method)
+        .uri(uri)
+        .header(AUTHORIZATION, format!("Bearer {token}"))
+        .header(CONTENT_TYPE, "application/json")
+        .body(Body::from(body.to_string()))
+        .expect("invalid test request")
+}
+
+/// Sends `request` to `app` and collects the response.
+///
+/// # Panics
+///
+/// Panics if the router fails or the body cannot be read.
+pub async fn send_request(app: Router, request: Request<Body>) -> TestResponse {
+    let response = app.oneshot(request).await.expect("router failed");
+    let status = response.status();
+    let headers = response.headers().clone();
+    let body = response
+        .into_body()
+        .collect()
+        .await
+        .expect("failed to read response body")
+        .to_bytes();
+    TestResponse {
+        status,
+        headers,
+        body,
+    }
+}
+
+/// Sends a JSON request with a bearer `token` to `app`.
+///
+/// Returns the status and the body parsed as JSON, or `Null` when the body
+/// is not JSON.
+///
+/// # Panics
+///
+/// Panics if the request cannot be built or the router fails.
+pub async fn send(
+    app: Router,
+    method: &str,
+    uri: &str,
+    token: &str,
+    body: serde_json::Value,
+) -> (StatusCode, serde_json::Value) {
+    let response = send_request(app, json_request(method, uri, token, &body)).await;
+    (response.status, response.json())
+}
@@ -0,0 +1,38 @@
+//! Test support for Zeltra integration tests.
+//!
+//! This crate provides:
+//! - [`TestDb`], a migrated database private to one test and dropped with it
+//! - [`OrgFixture`], a builder for organizations with members, a fiscal year
+//!   and accounts
+//! - [`post_transaction`], which posts a balanced transaction to a fixture
+//!   organization, and [`post_draft`] for drafts created elsewhere
+//! - Helpers to mint access tokens for fixture members
+//! - [`test_app_state`] and [`send`] for route tests against a router
+//!
+//! # Usage
+//!
+//! ```ignore
+//! use zeltra_db::entities::sea_orm_active_enums::{AccountType, UserRole};
+//! use zeltra_test_support::{OrgFixture, TestDb};
+//!
+//! let test_db = TestDb::new().await;
+//! let org = OrgFixture::new()
+//!     .with_member(UserRole::Approver)
+//!     .with_fiscal_year_2025()
+//!     .with_accounts(&[("1000", AccountType::Asset), ("4000", AccountType::Revenue)])
+//!     .create(test_db.conn())
+//!     .await;
+//! ```
+
+pub mod auth;
+pub mod db;
+pub mod fixtures;
+pub mod http;
+
+pub use auth::{access_token, jwt_service};
+pub use db::TestDb;
+pub use fixtures::{
+    FiscalYearFixture, Line, Member, Org, OrgFixture, post_draft, post_transaction,
+    post_transaction_as,
+};
+pub use http::{TestAppState, TestResponse, json_request, send, send_request, test_app_state};